@@ -0,0 +1,12 @@
+-- Extend category rules beyond plain substring matching
+-- match_type: how `pattern` is compared against the merchant/description
+-- min_amount/max_amount: optional inclusive range on the absolute transaction amount
+-- account_id: optional scope limiting the rule to a single account
+
+ALTER TABLE category_rules ADD COLUMN match_type TEXT NOT NULL DEFAULT 'contains'
+    CHECK(match_type IN ('contains', 'starts_with', 'regex', 'exact'));
+ALTER TABLE category_rules ADD COLUMN min_amount REAL;
+ALTER TABLE category_rules ADD COLUMN max_amount REAL;
+ALTER TABLE category_rules ADD COLUMN account_id INTEGER REFERENCES accounts(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_category_rules_account ON category_rules(account_id);
@@ -0,0 +1,6 @@
+-- Track completed first-run setup steps so the guided checklist survives restarts
+
+CREATE TABLE IF NOT EXISTS onboarding_steps (
+    step TEXT PRIMARY KEY CHECK(step IN ('account_created', 'first_import', 'first_budget', 'first_debt')),
+    completed_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
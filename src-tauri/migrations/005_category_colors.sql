@@ -0,0 +1,3 @@
+-- Optional display color for categories (hex, e.g. '#4CAF50')
+
+ALTER TABLE categories ADD COLUMN color TEXT;
@@ -0,0 +1,19 @@
+-- Persisted notifications for the in-app notification center
+-- entity_type/entity_id optionally link a notification to the record it is about
+
+CREATE TABLE IF NOT EXISTS notifications (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    notification_type TEXT NOT NULL,
+    severity TEXT NOT NULL DEFAULT 'info' CHECK(severity IN ('info', 'warning', 'critical')),
+    title TEXT NOT NULL,
+    message TEXT NOT NULL,
+    entity_type TEXT CHECK(entity_type IN ('account', 'transaction', 'category', 'debt', 'spending_target')),
+    entity_id INTEGER,
+    read_at TEXT,
+    dismissed_at TEXT,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK((entity_type IS NULL) = (entity_id IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_notifications_created_at ON notifications(created_at);
+CREATE INDEX IF NOT EXISTS idx_notifications_read_at ON notifications(read_at);
@@ -0,0 +1,15 @@
+-- Spending freezes ("no-spend" periods) for one category, or for all spending when category_id is NULL
+-- Violations are derived from transactions rather than stored, so recategorizing a transaction updates them
+
+CREATE TABLE IF NOT EXISTS spending_freezes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    category_id INTEGER,
+    start_date TEXT NOT NULL,
+    end_date TEXT, -- NULL until the freeze is lifted
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE CASCADE,
+    CHECK(end_date IS NULL OR end_date >= start_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_spending_freezes_dates ON spending_freezes(start_date, end_date);
@@ -0,0 +1,17 @@
+-- Foreign-currency transactions and historical exchange rates for FX reporting
+
+-- The amount in the currency it was charged in; `amount` stays in the home currency as posted
+ALTER TABLE transactions ADD COLUMN original_currency TEXT;
+ALTER TABLE transactions ADD COLUMN original_amount REAL;
+
+-- Home-currency units per one unit of `currency`, effective from `date` until the next rate
+CREATE TABLE IF NOT EXISTS exchange_rates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    currency TEXT NOT NULL,
+    date TEXT NOT NULL,
+    rate REAL NOT NULL CHECK(rate > 0),
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(currency, date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_original_currency ON transactions(original_currency, date);
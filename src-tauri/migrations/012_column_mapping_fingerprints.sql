@@ -0,0 +1,6 @@
+-- Saved mappings remember the headers of the file they were made for, so a file with the
+-- same headers can be mapped automatically. NULL for mappings saved without headers
+
+ALTER TABLE column_mappings ADD COLUMN header_fingerprint TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_column_mappings_fingerprint ON column_mappings(header_fingerprint);
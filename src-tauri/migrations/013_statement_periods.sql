@@ -0,0 +1,4 @@
+-- Day of the month an account's statement closes (e.g. a credit card billing cycle)
+-- Closing days past the end of a short month fall on its last day. NULL means calendar months
+
+ALTER TABLE accounts ADD COLUMN statement_closing_day INTEGER CHECK(statement_closing_day BETWEEN 1 AND 31);
@@ -0,0 +1,12 @@
+-- Debts paid automatically from an account on each due date, for cash-flow projections
+
+CREATE TABLE IF NOT EXISTS debt_autopay (
+    debt_id INTEGER PRIMARY KEY,
+    account_id INTEGER NOT NULL,
+    amount REAL CHECK(amount IS NULL OR amount > 0), -- NULL pays the debt's minimum payment
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (debt_id) REFERENCES debts(id) ON DELETE CASCADE,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_debt_autopay_account ON debt_autopay(account_id);
@@ -0,0 +1,6 @@
+-- Saved mappings for exports with separate debit/credit columns instead of one signed amount
+-- amount_col is left empty when debit_col/credit_col are used
+
+ALTER TABLE column_mappings ADD COLUMN debit_col TEXT;
+ALTER TABLE column_mappings ADD COLUMN credit_col TEXT;
+ALTER TABLE column_mappings ADD COLUMN invert_amounts INTEGER NOT NULL DEFAULT 0;
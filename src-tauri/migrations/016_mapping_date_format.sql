@@ -0,0 +1,4 @@
+-- Date format for saved mappings, e.g. 'DD/MM/YYYY' or a chrono format like '%d/%m/%Y'
+-- NULL detects the format from the file's dates at import time
+
+ALTER TABLE column_mappings ADD COLUMN date_format TEXT;
@@ -0,0 +1,3 @@
+-- Free-form notes on a transaction, set when adding or editing it by hand
+
+ALTER TABLE transactions ADD COLUMN notes TEXT;
@@ -0,0 +1,4 @@
+-- Closed accounts are archived rather than deleted, so their history stays in analytics
+-- Archived accounts are hidden from list_accounts unless asked for
+
+ALTER TABLE accounts ADD COLUMN archived INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+-- Day of the month each debt's payment is due (previously always the 15th)
+-- Due days past the end of a short month fall on its last day
+
+ALTER TABLE debts ADD COLUMN due_day INTEGER NOT NULL DEFAULT 15 CHECK(due_day BETWEEN 1 AND 31);
@@ -0,0 +1,7 @@
+-- Each payment's split between accrued interest and principal
+-- Only the principal part reduces the debt's balance. Earlier payments went entirely to principal
+
+ALTER TABLE debt_payments ADD COLUMN interest REAL NOT NULL DEFAULT 0;
+ALTER TABLE debt_payments ADD COLUMN principal REAL NOT NULL DEFAULT 0;
+
+UPDATE debt_payments SET principal = amount;
@@ -0,0 +1,19 @@
+-- Alerts raised by the alert evaluator (targets nearly used up, unusual transactions, payments due)
+-- alert_key identifies the event so re-evaluating never raises the same alert twice,
+-- and a dismissed alert stays dismissed
+
+CREATE TABLE IF NOT EXISTS alerts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    alert_type TEXT NOT NULL CHECK(alert_type IN ('target_threshold', 'large_transaction', 'payment_due')),
+    alert_key TEXT NOT NULL UNIQUE,
+    severity TEXT NOT NULL DEFAULT 'warning' CHECK(severity IN ('info', 'warning', 'critical')),
+    title TEXT NOT NULL,
+    message TEXT NOT NULL,
+    entity_type TEXT CHECK(entity_type IN ('transaction', 'category', 'debt')),
+    entity_id INTEGER,
+    dismissed_at TEXT,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK((entity_type IS NULL) = (entity_id IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_alerts_dismissed_at ON alerts(dismissed_at);
@@ -0,0 +1,86 @@
+-- Full-text index behind global search: transaction descriptions, merchants, and notes,
+-- category names, and debt names, kept in sync by triggers
+--
+-- Each entity gets rowid = id * 4 + type code (1 transaction, 2 category, 3 debt) so the
+-- triggers can find an entity's row without scanning the index
+
+CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+    entity_type UNINDEXED,
+    entity_id UNINDEXED,
+    title,
+    body,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+INSERT INTO search_index (rowid, entity_type, entity_id, title, body)
+SELECT id * 4 + 1, 'transaction', id, description, TRIM(COALESCE(merchant, '') || ' ' || COALESCE(notes, ''))
+FROM transactions;
+
+INSERT INTO search_index (rowid, entity_type, entity_id, title, body)
+SELECT id * 4 + 2, 'category', id, name, '' FROM categories;
+
+INSERT INTO search_index (rowid, entity_type, entity_id, title, body)
+SELECT id * 4 + 3, 'debt', id, name, '' FROM debts;
+
+-- Transactions
+
+CREATE TRIGGER IF NOT EXISTS search_index_transaction_insert AFTER INSERT ON transactions
+BEGIN
+    INSERT INTO search_index (rowid, entity_type, entity_id, title, body)
+    VALUES (new.id * 4 + 1, 'transaction', new.id, new.description,
+            TRIM(COALESCE(new.merchant, '') || ' ' || COALESCE(new.notes, '')));
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_index_transaction_update
+AFTER UPDATE OF description, merchant, notes ON transactions
+BEGIN
+    DELETE FROM search_index WHERE rowid = old.id * 4 + 1;
+    INSERT INTO search_index (rowid, entity_type, entity_id, title, body)
+    VALUES (new.id * 4 + 1, 'transaction', new.id, new.description,
+            TRIM(COALESCE(new.merchant, '') || ' ' || COALESCE(new.notes, '')));
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_index_transaction_delete AFTER DELETE ON transactions
+BEGIN
+    DELETE FROM search_index WHERE rowid = old.id * 4 + 1;
+END;
+
+-- Categories
+
+CREATE TRIGGER IF NOT EXISTS search_index_category_insert AFTER INSERT ON categories
+BEGIN
+    INSERT INTO search_index (rowid, entity_type, entity_id, title, body)
+    VALUES (new.id * 4 + 2, 'category', new.id, new.name, '');
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_index_category_update AFTER UPDATE OF name ON categories
+BEGIN
+    DELETE FROM search_index WHERE rowid = old.id * 4 + 2;
+    INSERT INTO search_index (rowid, entity_type, entity_id, title, body)
+    VALUES (new.id * 4 + 2, 'category', new.id, new.name, '');
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_index_category_delete AFTER DELETE ON categories
+BEGIN
+    DELETE FROM search_index WHERE rowid = old.id * 4 + 2;
+END;
+
+-- Debts
+
+CREATE TRIGGER IF NOT EXISTS search_index_debt_insert AFTER INSERT ON debts
+BEGIN
+    INSERT INTO search_index (rowid, entity_type, entity_id, title, body)
+    VALUES (new.id * 4 + 3, 'debt', new.id, new.name, '');
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_index_debt_update AFTER UPDATE OF name ON debts
+BEGIN
+    DELETE FROM search_index WHERE rowid = old.id * 4 + 3;
+    INSERT INTO search_index (rowid, entity_type, entity_id, title, body)
+    VALUES (new.id * 4 + 3, 'debt', new.id, new.name, '');
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_index_debt_delete AFTER DELETE ON debts
+BEGIN
+    DELETE FROM search_index WHERE rowid = old.id * 4 + 3;
+END;
@@ -0,0 +1,15 @@
+-- Record of changes made through the app's mutating commands: what changed, its
+-- values before and after (as JSON), and when. Old entries are pruned at startup
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    action TEXT NOT NULL CHECK(action IN ('create', 'update', 'delete')),
+    entity_type TEXT NOT NULL,
+    entity_id INTEGER,
+    old_values TEXT CHECK(old_values IS NULL OR json_valid(old_values)),
+    new_values TEXT CHECK(new_values IS NULL OR json_valid(new_values)),
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON audit_log(created_at);
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity ON audit_log(entity_type, entity_id);
@@ -0,0 +1,25 @@
+-- Recurring bills and income (rent, utilities, paychecks) that are posted as real
+-- transactions when due and included in cash-flow projections
+--
+-- Occurrences are counted from start_date so monthly items due on the 31st stay on the
+-- last day of short months; next_due_date is NULL once the schedule has run out
+
+CREATE TABLE IF NOT EXISTS scheduled_transactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id INTEGER NOT NULL,
+    category_id INTEGER NOT NULL,
+    amount REAL NOT NULL CHECK(amount != 0),
+    description TEXT NOT NULL,
+    merchant TEXT,
+    recurrence TEXT NOT NULL CHECK(recurrence IN ('once', 'weekly', 'biweekly', 'monthly', 'quarterly', 'yearly')),
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    next_due_date TEXT,
+    auto_post INTEGER NOT NULL DEFAULT 1, -- 0 waits for the user to confirm each occurrence
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE,
+    FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE RESTRICT,
+    CHECK(end_date IS NULL OR end_date >= start_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_transactions_next_due_date ON scheduled_transactions(next_due_date);
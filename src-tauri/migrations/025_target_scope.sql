@@ -0,0 +1,33 @@
+-- Spending targets can cover one category (as before), one account, or all spending
+-- scope: which of category_id / account_id is set; an 'overall' target sets neither
+--
+-- category_id becomes nullable, which SQLite can only do by rebuilding the table
+
+CREATE TABLE spending_targets_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    scope TEXT NOT NULL DEFAULT 'category' CHECK(scope IN ('category', 'account', 'overall')),
+    category_id INTEGER,
+    account_id INTEGER,
+    amount REAL NOT NULL CHECK(amount > 0),
+    period TEXT NOT NULL CHECK(period IN ('monthly', 'quarterly', 'yearly')),
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE CASCADE,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE,
+    CHECK(
+        (scope = 'category' AND category_id IS NOT NULL AND account_id IS NULL) OR
+        (scope = 'account' AND account_id IS NOT NULL AND category_id IS NULL) OR
+        (scope = 'overall' AND category_id IS NULL AND account_id IS NULL)
+    )
+);
+
+INSERT INTO spending_targets_new (id, scope, category_id, amount, period, start_date, end_date, created_at)
+SELECT id, 'category', category_id, amount, period, start_date, end_date, created_at FROM spending_targets;
+
+DROP TABLE spending_targets;
+ALTER TABLE spending_targets_new RENAME TO spending_targets;
+
+CREATE INDEX idx_spending_targets_category ON spending_targets(category_id);
+CREATE INDEX idx_spending_targets_account ON spending_targets(account_id);
+CREATE INDEX idx_spending_targets_dates ON spending_targets(start_date, end_date);
@@ -0,0 +1,4 @@
+-- Categories no longer in use are archived rather than deleted, so their transactions keep them
+-- Archived categories are hidden from list_categories and suggestions unless asked for
+
+ALTER TABLE categories ADD COLUMN archived INTEGER NOT NULL DEFAULT 0;
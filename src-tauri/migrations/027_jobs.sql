@@ -0,0 +1,20 @@
+-- Long-running operations (imports, report exports) run in the background as jobs.
+-- Progress is kept here so the UI can look a job up after missing its events; jobs still
+-- queued or running when the app closed are marked failed at the next startup
+
+CREATE TABLE IF NOT EXISTS jobs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'queued' CHECK(status IN ('queued', 'running', 'completed', 'failed', 'cancelled')),
+    processed INTEGER NOT NULL DEFAULT 0,
+    total INTEGER, -- NULL while the amount of work isn't known
+    message TEXT,
+    result TEXT CHECK(result IS NULL OR json_valid(result)),
+    error TEXT,
+    cancel_requested INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    started_at TEXT,
+    finished_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status, created_at);
@@ -0,0 +1,8 @@
+-- Statement due day for credit card accounts, and the card a debt tracks
+-- The due date is the first due day after a statement closes. NULL means no due day is set
+
+ALTER TABLE accounts ADD COLUMN statement_due_day INTEGER CHECK(statement_due_day BETWEEN 1 AND 31);
+
+-- A debt linked to a card takes the card's due day and can be synced to its statement balance
+ALTER TABLE debts ADD COLUMN account_id INTEGER REFERENCES accounts(id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_debts_account ON debts(account_id);
@@ -0,0 +1,27 @@
+-- Optional bank sync: a connection is one linked aggregator login (e.g. a SimpleFIN Bridge
+-- access grant) and each of its remote accounts can be mapped onto a local account.
+-- Access credentials are never stored here; they live in a separate file readable only by the user
+
+CREATE TABLE IF NOT EXISTS bank_connections (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    provider TEXT NOT NULL,
+    name TEXT NOT NULL,
+    last_synced_at TEXT,
+    last_error TEXT, -- Cleared by the next successful sync
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS bank_account_links (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    connection_id INTEGER NOT NULL REFERENCES bank_connections(id) ON DELETE CASCADE,
+    remote_account_id TEXT NOT NULL,
+    remote_name TEXT NOT NULL,
+    institution TEXT,
+    remote_balance REAL, -- As last reported by the provider
+    account_id INTEGER REFERENCES accounts(id) ON DELETE SET NULL, -- NULL until mapped; unmapped accounts aren't imported
+    last_synced_at TEXT,
+    UNIQUE(connection_id, remote_account_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_bank_account_links_account ON bank_account_links(account_id);
@@ -0,0 +1,10 @@
+-- Per-account duplicate detection for imports. Accounts without a row keep the default:
+-- a row is a duplicate when any account already has its date, amount and description
+
+CREATE TABLE IF NOT EXISTS duplicate_settings (
+    account_id INTEGER PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    match_fields TEXT NOT NULL DEFAULT 'date,amount,description', -- Comma-separated fields compared
+    lookback_days INTEGER CHECK (lookback_days IS NULL OR lookback_days >= 0), -- NULL compares all history
+    strictness TEXT NOT NULL DEFAULT 'strict' CHECK (strictness IN ('strict', 'counted', 'off')),
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,7 @@
+-- Version of the duplicate-detection hash each transaction was stored under, so the algorithm can
+-- change without breaking existing rows. Rows from before versioning hold version 1 hashes
+-- (date, amount and description, shared by every account) until they're rehashed
+
+ALTER TABLE transactions ADD COLUMN hash_version INTEGER NOT NULL DEFAULT 1;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_hash_version ON transactions(hash_version);
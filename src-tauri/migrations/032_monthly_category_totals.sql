@@ -0,0 +1,61 @@
+-- Spending per calendar month and category, kept in step with transactions by triggers so
+-- monthly trends read one small table instead of scanning transactions once per month
+-- Only spending (negative amounts) is totalled, as a positive sum
+
+CREATE TABLE IF NOT EXISTS monthly_category_totals (
+    month TEXT NOT NULL, -- YYYY-MM
+    category_id INTEGER NOT NULL,
+    spending REAL NOT NULL DEFAULT 0,
+    transaction_count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (month, category_id)
+);
+
+INSERT INTO monthly_category_totals (month, category_id, spending, transaction_count)
+SELECT strftime('%Y-%m', date), category_id, SUM(ABS(amount)), COUNT(*)
+FROM transactions
+WHERE amount < 0 AND strftime('%Y-%m', date) IS NOT NULL
+GROUP BY strftime('%Y-%m', date), category_id;
+
+CREATE TRIGGER IF NOT EXISTS monthly_totals_transaction_insert AFTER INSERT ON transactions
+WHEN new.amount < 0 AND strftime('%Y-%m', new.date) IS NOT NULL
+BEGIN
+    INSERT INTO monthly_category_totals (month, category_id, spending, transaction_count)
+    VALUES (strftime('%Y-%m', new.date), new.category_id, -new.amount, 1)
+    ON CONFLICT (month, category_id) DO UPDATE SET
+        spending = spending + excluded.spending,
+        transaction_count = transaction_count + 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS monthly_totals_transaction_delete AFTER DELETE ON transactions
+WHEN old.amount < 0 AND strftime('%Y-%m', old.date) IS NOT NULL
+BEGIN
+    UPDATE monthly_category_totals
+    SET spending = spending + old.amount, transaction_count = transaction_count - 1
+    WHERE month = strftime('%Y-%m', old.date) AND category_id = old.category_id;
+    DELETE FROM monthly_category_totals
+    WHERE month = strftime('%Y-%m', old.date) AND category_id = old.category_id AND transaction_count <= 0;
+END;
+
+-- An update moves the old values out and the new ones in, as a delete then an insert would
+
+CREATE TRIGGER IF NOT EXISTS monthly_totals_transaction_update_old
+AFTER UPDATE OF date, amount, category_id ON transactions
+WHEN old.amount < 0 AND strftime('%Y-%m', old.date) IS NOT NULL
+BEGIN
+    UPDATE monthly_category_totals
+    SET spending = spending + old.amount, transaction_count = transaction_count - 1
+    WHERE month = strftime('%Y-%m', old.date) AND category_id = old.category_id;
+    DELETE FROM monthly_category_totals
+    WHERE month = strftime('%Y-%m', old.date) AND category_id = old.category_id AND transaction_count <= 0;
+END;
+
+CREATE TRIGGER IF NOT EXISTS monthly_totals_transaction_update_new
+AFTER UPDATE OF date, amount, category_id ON transactions
+WHEN new.amount < 0 AND strftime('%Y-%m', new.date) IS NOT NULL
+BEGIN
+    INSERT INTO monthly_category_totals (month, category_id, spending, transaction_count)
+    VALUES (strftime('%Y-%m', new.date), new.category_id, -new.amount, 1)
+    ON CONFLICT (month, category_id) DO UPDATE SET
+        spending = spending + excluded.spending,
+        transaction_count = transaction_count + 1;
+END;
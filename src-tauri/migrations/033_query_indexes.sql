@@ -0,0 +1,22 @@
+-- Indexes matched to how the app actually queries, checked with get_query_plan
+--
+-- The single-column account_id and category_id indexes from 002 are prefixes of the
+-- (account_id, date) and (category_id, date) indexes, which serve those lookups just as well,
+-- and idx_transactions_hash duplicates the index behind the UNIQUE constraint on hash.
+-- Each one only slowed down writes
+
+DROP INDEX IF EXISTS idx_transactions_account_id;
+DROP INDEX IF EXISTS idx_transactions_category_id;
+DROP INDEX IF EXISTS idx_transactions_hash;
+
+-- Listings and running balances order an account's rows by (date, id)
+DROP INDEX IF EXISTS idx_transactions_account_date;
+CREATE INDEX IF NOT EXISTS idx_transactions_account_date ON transactions(account_id, date, id);
+
+-- Created in 001 already; listed so this file holds the full set the queries rely on
+CREATE INDEX IF NOT EXISTS idx_transactions_category_date ON transactions(category_id, date);
+CREATE INDEX IF NOT EXISTS idx_debt_payments_debt_date ON debt_payments(debt_id, date);
+
+-- Rules are applied in (priority DESC, created_at, id) order
+DROP INDEX IF EXISTS idx_category_rules_priority;
+CREATE INDEX IF NOT EXISTS idx_category_rules_priority ON category_rules(priority DESC, created_at, id);
@@ -0,0 +1,102 @@
+-- Money is kept to whole cents (see utils/money.rs for the rounding policy)
+--
+-- Amounts written before that policy may carry fractions of a cent, from imports of
+-- computed values or from float arithmetic in earlier versions. Round them once here
+-- so sums read back exactly, and keep monthly spending totals in integer cents so the
+-- triggers below never accumulate float error
+
+-- Rebuilt below, so the old triggers must not fire while amounts are rounded
+DROP TRIGGER IF EXISTS monthly_totals_transaction_insert;
+DROP TRIGGER IF EXISTS monthly_totals_transaction_delete;
+DROP TRIGGER IF EXISTS monthly_totals_transaction_update_old;
+DROP TRIGGER IF EXISTS monthly_totals_transaction_update_new;
+DROP TABLE IF EXISTS monthly_category_totals;
+
+UPDATE transactions SET amount = ROUND(amount * 100) / 100.0
+WHERE amount != ROUND(amount * 100) / 100.0;
+UPDATE transactions SET original_amount = ROUND(original_amount * 100) / 100.0
+WHERE original_amount != ROUND(original_amount * 100) / 100.0;
+UPDATE accounts SET balance = ROUND(balance * 100) / 100.0
+WHERE balance != ROUND(balance * 100) / 100.0;
+
+-- Columns checked to be positive keep at least a cent
+UPDATE debts SET
+    balance = ROUND(balance * 100) / 100.0,
+    original_balance = ROUND(original_balance * 100) / 100.0,
+    min_payment = ROUND(min_payment * 100) / 100.0
+WHERE balance != ROUND(balance * 100) / 100.0
+    OR original_balance != ROUND(original_balance * 100) / 100.0
+    OR min_payment != ROUND(min_payment * 100) / 100.0;
+UPDATE debt_plans SET monthly_amount = MAX(ROUND(monthly_amount * 100) / 100.0, 0.01)
+WHERE monthly_amount != ROUND(monthly_amount * 100) / 100.0;
+UPDATE debt_payments SET
+    amount = MAX(ROUND(amount * 100) / 100.0, 0.01),
+    interest = ROUND(interest * 100) / 100.0,
+    principal = ROUND(principal * 100) / 100.0
+WHERE amount != ROUND(amount * 100) / 100.0
+    OR interest != ROUND(interest * 100) / 100.0
+    OR principal != ROUND(principal * 100) / 100.0;
+UPDATE debt_autopay SET amount = MAX(ROUND(amount * 100) / 100.0, 0.01)
+WHERE amount != ROUND(amount * 100) / 100.0;
+UPDATE spending_targets SET amount = MAX(ROUND(amount * 100) / 100.0, 0.01)
+WHERE amount != ROUND(amount * 100) / 100.0;
+UPDATE scheduled_transactions SET amount = ROUND(amount * 100) / 100.0
+WHERE amount != ROUND(amount * 100) / 100.0 AND ROUND(amount * 100) != 0;
+
+CREATE TABLE IF NOT EXISTS monthly_category_totals (
+    month TEXT NOT NULL, -- YYYY-MM
+    category_id INTEGER NOT NULL,
+    spending_cents INTEGER NOT NULL DEFAULT 0,
+    transaction_count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (month, category_id)
+);
+
+INSERT INTO monthly_category_totals (month, category_id, spending_cents, transaction_count)
+SELECT strftime('%Y-%m', date), category_id, CAST(SUM(ROUND(ABS(amount) * 100)) AS INTEGER), COUNT(*)
+FROM transactions
+WHERE amount < 0 AND strftime('%Y-%m', date) IS NOT NULL
+GROUP BY strftime('%Y-%m', date), category_id;
+
+CREATE TRIGGER IF NOT EXISTS monthly_totals_transaction_insert AFTER INSERT ON transactions
+WHEN new.amount < 0 AND strftime('%Y-%m', new.date) IS NOT NULL
+BEGIN
+    INSERT INTO monthly_category_totals (month, category_id, spending_cents, transaction_count)
+    VALUES (strftime('%Y-%m', new.date), new.category_id, CAST(ROUND(-new.amount * 100) AS INTEGER), 1)
+    ON CONFLICT (month, category_id) DO UPDATE SET
+        spending_cents = spending_cents + excluded.spending_cents,
+        transaction_count = transaction_count + 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS monthly_totals_transaction_delete AFTER DELETE ON transactions
+WHEN old.amount < 0 AND strftime('%Y-%m', old.date) IS NOT NULL
+BEGIN
+    UPDATE monthly_category_totals
+    SET spending_cents = spending_cents - CAST(ROUND(-old.amount * 100) AS INTEGER),
+        transaction_count = transaction_count - 1
+    WHERE month = strftime('%Y-%m', old.date) AND category_id = old.category_id;
+    DELETE FROM monthly_category_totals
+    WHERE month = strftime('%Y-%m', old.date) AND category_id = old.category_id AND transaction_count <= 0;
+END;
+
+CREATE TRIGGER IF NOT EXISTS monthly_totals_transaction_update_old
+AFTER UPDATE OF date, amount, category_id ON transactions
+WHEN old.amount < 0 AND strftime('%Y-%m', old.date) IS NOT NULL
+BEGIN
+    UPDATE monthly_category_totals
+    SET spending_cents = spending_cents - CAST(ROUND(-old.amount * 100) AS INTEGER),
+        transaction_count = transaction_count - 1
+    WHERE month = strftime('%Y-%m', old.date) AND category_id = old.category_id;
+    DELETE FROM monthly_category_totals
+    WHERE month = strftime('%Y-%m', old.date) AND category_id = old.category_id AND transaction_count <= 0;
+END;
+
+CREATE TRIGGER IF NOT EXISTS monthly_totals_transaction_update_new
+AFTER UPDATE OF date, amount, category_id ON transactions
+WHEN new.amount < 0 AND strftime('%Y-%m', new.date) IS NOT NULL
+BEGIN
+    INSERT INTO monthly_category_totals (month, category_id, spending_cents, transaction_count)
+    VALUES (strftime('%Y-%m', new.date), new.category_id, CAST(ROUND(-new.amount * 100) AS INTEGER), 1)
+    ON CONFLICT (month, category_id) DO UPDATE SET
+        spending_cents = spending_cents + excluded.spending_cents,
+        transaction_count = transaction_count + 1;
+END;
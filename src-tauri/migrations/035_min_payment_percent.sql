@@ -0,0 +1,4 @@
+-- Credit-card-style minimum payments: a percentage of the balance, with min_payment as the floor
+-- NULL keeps the minimum fixed at min_payment
+
+ALTER TABLE debts ADD COLUMN min_payment_percent REAL CHECK(min_payment_percent IS NULL OR (min_payment_percent > 0 AND min_payment_percent <= 100));
@@ -0,0 +1,7 @@
+-- Fixed-term loans alongside revolving debts
+-- A loan's original_balance is its principal and interest_rate its APR; min_payment holds
+-- the amortized monthly payment, so payoff plans treat it like any other minimum
+
+ALTER TABLE debts ADD COLUMN kind TEXT NOT NULL DEFAULT 'revolving' CHECK(kind IN ('revolving', 'loan'));
+ALTER TABLE debts ADD COLUMN term_months INTEGER CHECK(term_months IS NULL OR term_months > 0);
+ALTER TABLE debts ADD COLUMN loan_start_date TEXT;
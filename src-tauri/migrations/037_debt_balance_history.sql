@@ -0,0 +1,35 @@
+-- Each debt's balance after every change, so progress charts show what actually
+-- happened (manual corrections and increases included) rather than a history
+-- rebuilt from payments alone
+--
+-- `date` is when the balance took effect: a payment's date, a statement's closing
+-- date, otherwise the day the change was made
+
+CREATE TABLE IF NOT EXISTS debt_balance_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    debt_id INTEGER NOT NULL REFERENCES debts(id) ON DELETE CASCADE,
+    balance REAL NOT NULL,
+    date TEXT NOT NULL, -- YYYY-MM-DD
+    change TEXT NOT NULL CHECK(change IN ('opened', 'payment', 'correction', 'adjustment', 'statement', 'import')),
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_debt_balance_history_debt_date ON debt_balance_history(debt_id, date);
+
+-- Backfill what can be known: the opening balance, the balance after each payment,
+-- and the current balance where it no longer matches the payments
+INSERT INTO debt_balance_history (debt_id, balance, date, change)
+SELECT id, original_balance, date(created_at), 'opened' FROM debts;
+
+INSERT INTO debt_balance_history (debt_id, balance, date, change)
+SELECT p.debt_id,
+    MAX(ROUND((d.original_balance - SUM(p.principal) OVER (PARTITION BY p.debt_id ORDER BY p.date, p.id)) * 100) / 100.0, 0),
+    p.date,
+    'payment'
+FROM debt_payments p JOIN debts d ON d.id = p.debt_id
+ORDER BY p.debt_id, p.date, p.id;
+
+INSERT INTO debt_balance_history (debt_id, balance, date, change)
+SELECT d.id, d.balance, date(d.updated_at), 'adjustment'
+FROM debts d
+WHERE ROUND(d.balance * 100) != ROUND(MAX(d.original_balance - COALESCE((SELECT SUM(p.principal) FROM debt_payments p WHERE p.debt_id = d.id), 0), 0) * 100);
@@ -0,0 +1,11 @@
+-- Pending transactions: authorized by the bank but not yet posted
+-- Card exports list them alongside posted rows, and when one posts it comes back with a new
+-- date and description, so imports match it to the pending row instead of adding it twice.
+-- Everything already imported is taken as posted
+
+ALTER TABLE transactions ADD COLUMN status TEXT NOT NULL DEFAULT 'posted' CHECK(status IN ('pending', 'posted'));
+
+CREATE INDEX IF NOT EXISTS idx_transactions_pending ON transactions(account_id, date) WHERE status = 'pending';
+
+-- Saved mappings remember which column, if any, carries the pending/posted status
+ALTER TABLE column_mappings ADD COLUMN status_col TEXT;
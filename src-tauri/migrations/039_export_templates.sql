@@ -0,0 +1,13 @@
+-- Saved layouts for transaction CSV exports, for accountants and tools that expect
+-- particular columns, headers and formats
+
+CREATE TABLE IF NOT EXISTS export_templates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    columns TEXT NOT NULL, -- JSON array of {field, header}, in file order
+    date_format TEXT, -- e.g. DD/MM/YYYY; NULL writes YYYY-MM-DD
+    decimal_separator TEXT NOT NULL DEFAULT '.' CHECK (decimal_separator IN ('.', ',')),
+    delimiter TEXT NOT NULL DEFAULT ',' CHECK (delimiter IN (',', ';', '|', char(9))),
+    include_header INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,15 @@
+-- Automatic backups: one row per backup attempt, scheduled or run by hand
+-- A backup is a full data archive written to the configured backup folder
+
+CREATE TABLE IF NOT EXISTS backup_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    started_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, -- UTC
+    kind TEXT NOT NULL CHECK (kind IN ('scheduled', 'manual')),
+    status TEXT NOT NULL CHECK (status IN ('succeeded', 'failed')),
+    file_path TEXT, -- NULL when the backup failed before writing
+    size_bytes INTEGER,
+    pruned_count INTEGER NOT NULL DEFAULT 0, -- Older backups deleted to keep the retention count
+    error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_backup_history_started ON backup_history(status, started_at);
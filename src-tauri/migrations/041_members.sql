@@ -0,0 +1,16 @@
+-- Household members: who made each transaction on a shared account
+-- Attribution is optional; transactions without a member count toward the household as a whole
+
+CREATE TABLE IF NOT EXISTS members (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    color TEXT, -- Hex color for charts, e.g. #3b82f6
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE transactions ADD COLUMN member_id INTEGER REFERENCES members(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_member ON transactions(member_id, date) WHERE member_id IS NOT NULL;
+
+-- Saved mappings remember which column, if any, names the member (e.g. a cardholder column)
+ALTER TABLE column_mappings ADD COLUMN member_col TEXT;
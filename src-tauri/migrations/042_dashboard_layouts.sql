@@ -0,0 +1,9 @@
+-- Which widgets the dashboard shows, in what order, and their settings
+-- The app uses the layout named 'default'; without one the dashboard shows spending, debts and targets
+
+CREATE TABLE IF NOT EXISTS dashboard_layouts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    widgets TEXT NOT NULL, -- JSON array of {kind, limit, days_ahead}, in display order
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
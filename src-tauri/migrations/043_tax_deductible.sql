@@ -0,0 +1,7 @@
+-- Tax-relevant spending: categories flagged as deductible (charitable giving, business
+-- expenses), with a per-transaction override for the odd exception either way
+
+ALTER TABLE categories ADD COLUMN tax_deductible INTEGER NOT NULL DEFAULT 0;
+
+-- NULL follows the category; 0 or 1 overrides it
+ALTER TABLE transactions ADD COLUMN tax_deductible INTEGER;
@@ -0,0 +1,8 @@
+-- Period locks: months closed after they've been reconciled
+-- Transactions dated in a locked month can't be created, changed, deleted, or imported
+-- unless the change explicitly overrides the lock
+
+CREATE TABLE IF NOT EXISTS period_locks (
+    month TEXT PRIMARY KEY, -- YYYY-MM
+    locked_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,5 @@
+-- Category rule statistics: how often each rule categorizes a transaction, to find rules that never fire
+-- Counting starts with this migration; matches from before it aren't known
+
+ALTER TABLE category_rules ADD COLUMN match_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE category_rules ADD COLUMN last_matched_at TEXT;
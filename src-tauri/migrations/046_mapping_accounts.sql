@@ -0,0 +1,4 @@
+-- Saved column mappings can remember the account their files are imported into,
+-- so a recognized file suggests both its mapping and its account
+
+ALTER TABLE column_mappings ADD COLUMN account_id INTEGER REFERENCES accounts(id) ON DELETE SET NULL;
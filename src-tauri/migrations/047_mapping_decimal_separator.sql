@@ -0,0 +1,4 @@
+-- Saved mappings can say which character is the decimal point in their amounts ('.' or ','),
+-- for files where it can't be told from the values alone (e.g. "1.234" meaning 1234)
+
+ALTER TABLE column_mappings ADD COLUMN decimal_separator TEXT;
@@ -0,0 +1,29 @@
+-- Investments: the securities each account holds, and prices to value them
+-- Holdings are counted in net worth on top of the account's cash balance
+
+-- `quantity` shares of `symbol` held from `date` until the account's next record for the symbol
+-- A quantity of 0 records that the position was sold
+CREATE TABLE IF NOT EXISTS holdings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id INTEGER NOT NULL,
+    symbol TEXT NOT NULL,
+    quantity REAL NOT NULL CHECK(quantity >= 0),
+    cost_basis REAL CHECK(cost_basis IS NULL OR cost_basis >= 0), -- Total paid for the position
+    date TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE,
+    UNIQUE(account_id, symbol, date)
+);
+
+-- Price of one share of `symbol`, effective from `date` until the next price
+CREATE TABLE IF NOT EXISTS security_prices (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    symbol TEXT NOT NULL,
+    date TEXT NOT NULL,
+    price REAL NOT NULL CHECK(price >= 0),
+    source TEXT NOT NULL DEFAULT 'manual' CHECK(source IN ('manual', 'csv')),
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(symbol, date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_holdings_symbol ON holdings(symbol, date);
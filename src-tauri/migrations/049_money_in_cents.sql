@@ -0,0 +1,224 @@
+-- Money is stored as INTEGER cents (see utils/money.rs)
+--
+-- Each REAL dollar column becomes a *_cents column holding the same amount in whole cents.
+-- Migration 034 already rounded stored amounts, so the conversion only changes the type.
+-- Columns checked to be positive (or non-zero) keep at least a cent, as in 034.
+--
+-- SQLite can't add a column checked to be positive to a table that already has rows, so
+-- those tables are rebuilt. debt_payments is rebuilt against debt_plans_new and the old
+-- tables dropped together, so dropping debt_plans doesn't clear any payment's plan_id
+
+-- accounts
+ALTER TABLE accounts ADD COLUMN balance_cents INTEGER NOT NULL DEFAULT 0;
+UPDATE accounts SET balance_cents = CAST(ROUND(balance * 100) AS INTEGER);
+ALTER TABLE accounts DROP COLUMN balance;
+
+-- transactions; the monthly totals triggers read the amount, so they're rebuilt on the new column
+DROP TRIGGER IF EXISTS monthly_totals_transaction_insert;
+DROP TRIGGER IF EXISTS monthly_totals_transaction_delete;
+DROP TRIGGER IF EXISTS monthly_totals_transaction_update_old;
+DROP TRIGGER IF EXISTS monthly_totals_transaction_update_new;
+
+ALTER TABLE transactions ADD COLUMN amount_cents INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE transactions ADD COLUMN original_amount_cents INTEGER;
+UPDATE transactions SET
+    amount_cents = CAST(ROUND(amount * 100) AS INTEGER),
+    original_amount_cents = CAST(ROUND(original_amount * 100) AS INTEGER);
+ALTER TABLE transactions DROP COLUMN amount;
+ALTER TABLE transactions DROP COLUMN original_amount;
+
+CREATE TRIGGER monthly_totals_transaction_insert AFTER INSERT ON transactions
+WHEN new.amount_cents < 0 AND strftime('%Y-%m', new.date) IS NOT NULL
+BEGIN
+    INSERT INTO monthly_category_totals (month, category_id, spending_cents, transaction_count)
+    VALUES (strftime('%Y-%m', new.date), new.category_id, -new.amount_cents, 1)
+    ON CONFLICT (month, category_id) DO UPDATE SET
+        spending_cents = spending_cents + excluded.spending_cents,
+        transaction_count = transaction_count + 1;
+END;
+
+CREATE TRIGGER monthly_totals_transaction_delete AFTER DELETE ON transactions
+WHEN old.amount_cents < 0 AND strftime('%Y-%m', old.date) IS NOT NULL
+BEGIN
+    UPDATE monthly_category_totals
+    SET spending_cents = spending_cents + old.amount_cents,
+        transaction_count = transaction_count - 1
+    WHERE month = strftime('%Y-%m', old.date) AND category_id = old.category_id;
+    DELETE FROM monthly_category_totals
+    WHERE month = strftime('%Y-%m', old.date) AND category_id = old.category_id AND transaction_count <= 0;
+END;
+
+CREATE TRIGGER monthly_totals_transaction_update_old
+AFTER UPDATE OF date, amount_cents, category_id ON transactions
+WHEN old.amount_cents < 0 AND strftime('%Y-%m', old.date) IS NOT NULL
+BEGIN
+    UPDATE monthly_category_totals
+    SET spending_cents = spending_cents + old.amount_cents,
+        transaction_count = transaction_count - 1
+    WHERE month = strftime('%Y-%m', old.date) AND category_id = old.category_id;
+    DELETE FROM monthly_category_totals
+    WHERE month = strftime('%Y-%m', old.date) AND category_id = old.category_id AND transaction_count <= 0;
+END;
+
+CREATE TRIGGER monthly_totals_transaction_update_new
+AFTER UPDATE OF date, amount_cents, category_id ON transactions
+WHEN new.amount_cents < 0 AND strftime('%Y-%m', new.date) IS NOT NULL
+BEGIN
+    INSERT INTO monthly_category_totals (month, category_id, spending_cents, transaction_count)
+    VALUES (strftime('%Y-%m', new.date), new.category_id, -new.amount_cents, 1)
+    ON CONFLICT (month, category_id) DO UPDATE SET
+        spending_cents = spending_cents + excluded.spending_cents,
+        transaction_count = transaction_count + 1;
+END;
+
+-- category_rules
+ALTER TABLE category_rules ADD COLUMN min_amount_cents INTEGER;
+ALTER TABLE category_rules ADD COLUMN max_amount_cents INTEGER;
+UPDATE category_rules SET
+    min_amount_cents = CAST(ROUND(min_amount * 100) AS INTEGER),
+    max_amount_cents = CAST(ROUND(max_amount * 100) AS INTEGER);
+ALTER TABLE category_rules DROP COLUMN min_amount;
+ALTER TABLE category_rules DROP COLUMN max_amount;
+
+-- debts
+DROP INDEX IF EXISTS idx_debts_balance;
+ALTER TABLE debts ADD COLUMN balance_cents INTEGER NOT NULL DEFAULT 0 CHECK(balance_cents >= 0);
+ALTER TABLE debts ADD COLUMN original_balance_cents INTEGER NOT NULL DEFAULT 0 CHECK(original_balance_cents >= 0);
+ALTER TABLE debts ADD COLUMN min_payment_cents INTEGER NOT NULL DEFAULT 0 CHECK(min_payment_cents >= 0);
+UPDATE debts SET
+    balance_cents = CAST(ROUND(balance * 100) AS INTEGER),
+    original_balance_cents = CAST(ROUND(original_balance * 100) AS INTEGER),
+    min_payment_cents = CAST(ROUND(min_payment * 100) AS INTEGER);
+ALTER TABLE debts DROP COLUMN balance;
+ALTER TABLE debts DROP COLUMN original_balance;
+ALTER TABLE debts DROP COLUMN min_payment;
+CREATE INDEX idx_debts_balance ON debts(balance_cents);
+
+-- debt_balance_history
+ALTER TABLE debt_balance_history ADD COLUMN balance_cents INTEGER NOT NULL DEFAULT 0;
+UPDATE debt_balance_history SET balance_cents = CAST(ROUND(balance * 100) AS INTEGER);
+ALTER TABLE debt_balance_history DROP COLUMN balance;
+
+-- debt_autopay
+ALTER TABLE debt_autopay ADD COLUMN amount_cents INTEGER CHECK(amount_cents IS NULL OR amount_cents > 0); -- NULL pays the debt's minimum payment
+UPDATE debt_autopay SET amount_cents = MAX(CAST(ROUND(amount * 100) AS INTEGER), 1) WHERE amount IS NOT NULL;
+ALTER TABLE debt_autopay DROP COLUMN amount;
+
+-- debt_plans
+CREATE TABLE debt_plans_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    strategy TEXT NOT NULL CHECK(strategy IN ('avalanche', 'snowball')),
+    monthly_amount_cents INTEGER NOT NULL CHECK(monthly_amount_cents > 0),
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO debt_plans_new (id, strategy, monthly_amount_cents, created_at, updated_at)
+SELECT id, strategy, MAX(CAST(ROUND(monthly_amount * 100) AS INTEGER), 1), created_at, updated_at FROM debt_plans;
+
+-- debt_payments
+CREATE TABLE debt_payments_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    debt_id INTEGER NOT NULL,
+    amount_cents INTEGER NOT NULL CHECK(amount_cents > 0),
+    interest_cents INTEGER NOT NULL DEFAULT 0,
+    principal_cents INTEGER NOT NULL DEFAULT 0,
+    date TEXT NOT NULL,
+    plan_id INTEGER,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (debt_id) REFERENCES debts(id) ON DELETE CASCADE,
+    FOREIGN KEY (plan_id) REFERENCES debt_plans_new(id) ON DELETE SET NULL
+);
+
+INSERT INTO debt_payments_new (id, debt_id, amount_cents, interest_cents, principal_cents, date, plan_id, created_at)
+SELECT id, debt_id, MAX(CAST(ROUND(amount * 100) AS INTEGER), 1), CAST(ROUND(interest * 100) AS INTEGER),
+       CAST(ROUND(principal * 100) AS INTEGER), date, plan_id, created_at
+FROM debt_payments;
+
+DROP TABLE debt_payments;
+DROP TABLE debt_plans;
+ALTER TABLE debt_plans_new RENAME TO debt_plans;
+ALTER TABLE debt_payments_new RENAME TO debt_payments;
+
+CREATE INDEX idx_debt_payments_debt_date ON debt_payments(debt_id, date);
+CREATE INDEX idx_debt_payments_plan ON debt_payments(plan_id);
+
+-- spending_targets
+CREATE TABLE spending_targets_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    scope TEXT NOT NULL DEFAULT 'category' CHECK(scope IN ('category', 'account', 'overall')),
+    category_id INTEGER,
+    account_id INTEGER,
+    amount_cents INTEGER NOT NULL CHECK(amount_cents > 0),
+    period TEXT NOT NULL CHECK(period IN ('monthly', 'quarterly', 'yearly')),
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE CASCADE,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE,
+    CHECK(
+        (scope = 'category' AND category_id IS NOT NULL AND account_id IS NULL) OR
+        (scope = 'account' AND account_id IS NOT NULL AND category_id IS NULL) OR
+        (scope = 'overall' AND category_id IS NULL AND account_id IS NULL)
+    )
+);
+
+INSERT INTO spending_targets_new (id, scope, category_id, account_id, amount_cents, period, start_date, end_date, created_at)
+SELECT id, scope, category_id, account_id, MAX(CAST(ROUND(amount * 100) AS INTEGER), 1), period, start_date, end_date, created_at
+FROM spending_targets;
+
+DROP TABLE spending_targets;
+ALTER TABLE spending_targets_new RENAME TO spending_targets;
+
+CREATE INDEX idx_spending_targets_category ON spending_targets(category_id);
+CREATE INDEX idx_spending_targets_account ON spending_targets(account_id);
+CREATE INDEX idx_spending_targets_dates ON spending_targets(start_date, end_date);
+
+-- scheduled_transactions
+CREATE TABLE scheduled_transactions_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id INTEGER NOT NULL,
+    category_id INTEGER NOT NULL,
+    amount_cents INTEGER NOT NULL CHECK(amount_cents != 0),
+    description TEXT NOT NULL,
+    merchant TEXT,
+    recurrence TEXT NOT NULL CHECK(recurrence IN ('once', 'weekly', 'biweekly', 'monthly', 'quarterly', 'yearly')),
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    next_due_date TEXT,
+    auto_post INTEGER NOT NULL DEFAULT 1, -- 0 waits for the user to confirm each occurrence
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE,
+    FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE RESTRICT,
+    CHECK(end_date IS NULL OR end_date >= start_date)
+);
+
+-- 034 left amounts under half a cent alone; they keep a cent in their direction
+INSERT INTO scheduled_transactions_new (
+    id, account_id, category_id, amount_cents, description, merchant, recurrence,
+    start_date, end_date, next_due_date, auto_post, created_at
+)
+SELECT id, account_id, category_id,
+       CASE
+           WHEN CAST(ROUND(amount * 100) AS INTEGER) != 0 THEN CAST(ROUND(amount * 100) AS INTEGER)
+           WHEN amount < 0 THEN -1
+           ELSE 1
+       END,
+       description, merchant, recurrence, start_date, end_date, next_due_date, auto_post, created_at
+FROM scheduled_transactions;
+
+DROP TABLE scheduled_transactions;
+ALTER TABLE scheduled_transactions_new RENAME TO scheduled_transactions;
+
+CREATE INDEX idx_scheduled_transactions_next_due_date ON scheduled_transactions(next_due_date);
+
+-- bank_account_links
+ALTER TABLE bank_account_links ADD COLUMN remote_balance_cents INTEGER; -- As last reported by the provider
+UPDATE bank_account_links SET remote_balance_cents = CAST(ROUND(remote_balance * 100) AS INTEGER);
+ALTER TABLE bank_account_links DROP COLUMN remote_balance;
+
+-- holdings
+ALTER TABLE holdings ADD COLUMN cost_basis_cents INTEGER
+    CHECK(cost_basis_cents IS NULL OR cost_basis_cents >= 0); -- Total paid for the position
+UPDATE holdings SET cost_basis_cents = CAST(ROUND(cost_basis * 100) AS INTEGER);
+ALTER TABLE holdings DROP COLUMN cost_basis;
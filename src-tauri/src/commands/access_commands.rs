@@ -0,0 +1,283 @@
+use crate::constants::{MAX_APP_PASSWORD_LENGTH, MIN_APP_PASSWORD_LENGTH};
+use crate::errors::AccessError;
+use crate::models::setting::{AccessStatus, AppRole};
+use crate::utils::access_guard::AccessGuard;
+use crate::DbPool;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use sqlx::SqlitePool;
+
+// Stored in app_settings but not exposed through list_settings/update_setting
+const APP_ROLE_KEY: &str = "app_role";
+const APP_PASSWORD_KEY: &str = "app_password_hash";
+
+// Helper functions
+
+async fn load_value(db: &SqlitePool, key: &str) -> Result<Option<String>, AccessError> {
+    let value: Option<(String,)> = sqlx::query_as("SELECT value FROM app_settings WHERE key = ?")
+        .bind(key)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| AccessError::Database(e.to_string()))?;
+
+    Ok(value.map(|(value,)| value))
+}
+
+async fn store_value(db: &SqlitePool, key: &str, value: Option<&str>) -> Result<(), AccessError> {
+    let result = match value {
+        Some(value) => {
+            sqlx::query(
+                "INSERT INTO app_settings (key, value) VALUES (?, ?)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = CURRENT_TIMESTAMP"
+            )
+            .bind(key)
+            .bind(value)
+            .execute(db)
+            .await
+        }
+        None => {
+            sqlx::query("DELETE FROM app_settings WHERE key = ?")
+                .bind(key)
+                .execute(db)
+                .await
+        }
+    };
+
+    result.map(|_| ()).map_err(|e| AccessError::Database(e.to_string()))
+}
+
+async fn load_role(db: &SqlitePool) -> Result<AppRole, AccessError> {
+    Ok(load_value(db, APP_ROLE_KEY)
+        .await?
+        .and_then(|role| role.parse().ok())
+        .unwrap_or(AppRole::Owner))
+}
+
+/// Argon2id hash of the password with a random salt, as a PHC string (`$argon2id$...`)
+fn hash_password(password: &str) -> Result<String, AccessError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| AccessError::Hashing(e.to_string()))
+}
+
+/// Salted SHA-256 stored as `salt$hash` by earlier versions, checked until it's replaced
+fn legacy_hash(salt: &str, password: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{}:{}", salt, password));
+    format!("{}${:x}", salt, hasher.finalize())
+}
+
+fn password_matches(stored: &str, password: &str) -> bool {
+    match PasswordHash::new(stored) {
+        Ok(hash) => Argon2::default().verify_password(password.as_bytes(), &hash).is_ok(),
+        Err(_) => stored
+            .split_once('$')
+            .is_some_and(|(salt, _)| legacy_hash(salt, password) == stored),
+    }
+}
+
+/// Check the app password, making the next attempt wait after a wrong one
+///
+/// A password still stored with the old SHA-256 hash is rehashed with Argon2 once it's
+/// entered correctly.
+pub(crate) async fn verify_password(
+    db: &SqlitePool,
+    guard: &AccessGuard,
+    password: Option<&str>,
+) -> Result<(), AccessError> {
+    let stored = load_value(db, APP_PASSWORD_KEY).await?.ok_or(AccessError::NoPassword)?;
+    guard.check_password_attempt()?;
+
+    let Some(password) = password.filter(|password| password_matches(&stored, password)) else {
+        tracing::warn!("Incorrect app password entered");
+        guard.record_wrong_password();
+        return Err(AccessError::WrongPassword);
+    };
+
+    if PasswordHash::new(&stored).is_err() {
+        // Read-only mode refuses the write; the old hash is replaced on a later unlock
+        if let Err(e) = store_value(db, APP_PASSWORD_KEY, Some(&hash_password(password)?)).await {
+            tracing::warn!(error = %e, "Failed to upgrade the app password hash");
+        }
+    }
+    Ok(())
+}
+
+fn validate_password(password: &str) -> Result<(), AccessError> {
+    let length = password.chars().count();
+    if length < MIN_APP_PASSWORD_LENGTH {
+        return Err(AccessError::ValidationError(format!(
+            "App password must be at least {} characters",
+            MIN_APP_PASSWORD_LENGTH
+        )));
+    }
+    if length > MAX_APP_PASSWORD_LENGTH {
+        return Err(AccessError::ValidationError(format!(
+            "App password cannot exceed {} characters",
+            MAX_APP_PASSWORD_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+// Business logic functions (used by both commands and tests)
+
+pub async fn get_access_status_impl(
+    db: &SqlitePool,
+    guard: &AccessGuard,
+) -> Result<AccessStatus, AccessError> {
+    Ok(AccessStatus {
+        role: load_role(db).await?,
+        locked: guard.is_locked(),
+        password_set: load_value(db, APP_PASSWORD_KEY).await?.is_some(),
+    })
+}
+
+/// Set, change, or clear (`new_password` None) the app password
+///
+/// Changing or clearing an existing password needs the current one. Clearing
+/// it also switches back to the owner role, since a viewer could never unlock.
+pub async fn set_app_password_impl(
+    db: &SqlitePool,
+    guard: &AccessGuard,
+    current_password: Option<&str>,
+    new_password: Option<&str>,
+) -> Result<AccessStatus, AccessError> {
+    guard.ensure_writable()?;
+    if load_value(db, APP_PASSWORD_KEY).await?.is_some() {
+        verify_password(db, guard, current_password).await?;
+    }
+
+    match new_password {
+        Some(password) => {
+            validate_password(password)?;
+            store_value(db, APP_PASSWORD_KEY, Some(&hash_password(password)?)).await?;
+        }
+        None => {
+            store_value(db, APP_PASSWORD_KEY, None).await?;
+            store_value(db, APP_ROLE_KEY, None).await?;
+            guard.set_locked(false);
+        }
+    }
+
+    tracing::info!(cleared = new_password.is_none(), "App password changed");
+    get_access_status_impl(db, guard).await
+}
+
+/// Switch between the owner and viewer roles
+///
+/// Switching to viewer needs an app password to be set and locks editing right
+/// away; switching back to owner needs the password.
+pub async fn set_app_role_impl(
+    db: &SqlitePool,
+    guard: &AccessGuard,
+    role: &str,
+    password: Option<&str>,
+) -> Result<AccessStatus, AccessError> {
+    guard.ensure_writable()?;
+    let role: AppRole = role.parse().map_err(AccessError::ValidationError)?;
+
+    match role {
+        AppRole::Viewer => {
+            if load_value(db, APP_PASSWORD_KEY).await?.is_none() {
+                return Err(AccessError::NoPassword);
+            }
+        }
+        AppRole::Owner => verify_password(db, guard, password).await?,
+    }
+
+    store_value(db, APP_ROLE_KEY, Some(&role.to_string())).await?;
+    guard.set_locked(role == AppRole::Viewer);
+
+    tracing::info!(role = %role, "App role changed");
+    get_access_status_impl(db, guard).await
+}
+
+/// Allow edits for the rest of the session while keeping the viewer role
+pub async fn unlock_editing_impl(
+    db: &SqlitePool,
+    guard: &AccessGuard,
+    password: &str,
+) -> Result<AccessStatus, AccessError> {
+    verify_password(db, guard, Some(password)).await?;
+    guard.set_locked(false);
+    get_access_status_impl(db, guard).await
+}
+
+/// Lock editing again, e.g. before handing the laptop back to a viewer
+pub async fn lock_editing_impl(
+    db: &SqlitePool,
+    guard: &AccessGuard,
+) -> Result<AccessStatus, AccessError> {
+    guard.set_locked(load_role(db).await? == AppRole::Viewer);
+    get_access_status_impl(db, guard).await
+}
+
+/// Apply the saved role at startup; a viewer starts locked
+pub async fn load_app_role(db: &SqlitePool, guard: &AccessGuard) -> Result<(), AccessError> {
+    guard.set_locked(load_role(db).await? == AppRole::Viewer);
+    Ok(())
+}
+
+// Tauri command handlers (extract pool from managed state)
+// None of these are in MUTATING_COMMANDS: they check the password themselves, and the
+// ones that write check read-only mode
+
+#[tauri::command]
+pub async fn get_access_status(
+    db_pool: tauri::State<'_, DbPool>,
+    guard: tauri::State<'_, AccessGuard>,
+) -> Result<AccessStatus, String> {
+    get_access_status_impl(&db_pool.pool(), &guard)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn set_app_password(
+    db_pool: tauri::State<'_, DbPool>,
+    guard: tauri::State<'_, AccessGuard>,
+    current_password: Option<String>,
+    new_password: Option<String>,
+) -> Result<AccessStatus, String> {
+    set_app_password_impl(&db_pool.pool(), &guard, current_password.as_deref(), new_password.as_deref())
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn set_app_role(
+    db_pool: tauri::State<'_, DbPool>,
+    guard: tauri::State<'_, AccessGuard>,
+    role: String,
+    password: Option<String>,
+) -> Result<AccessStatus, String> {
+    set_app_role_impl(&db_pool.pool(), &guard, &role, password.as_deref())
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn unlock_editing(
+    db_pool: tauri::State<'_, DbPool>,
+    guard: tauri::State<'_, AccessGuard>,
+    password: String,
+) -> Result<AccessStatus, String> {
+    unlock_editing_impl(&db_pool.pool(), &guard, &password)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn lock_editing(
+    db_pool: tauri::State<'_, DbPool>,
+    guard: tauri::State<'_, AccessGuard>,
+) -> Result<AccessStatus, String> {
+    lock_editing_impl(&db_pool.pool(), &guard)
+        .await
+        .map_err(|e| e.to_user_message())
+}
@@ -1,14 +1,23 @@
+use crate::commands::transaction_commands::ensure_unlocked;
+use crate::constants::{MAX_DEBT_DUE_DAY, MAX_STATEMENT_CLOSING_DAY};
 use crate::errors::sanitize_db_error;
-use crate::models::account::{Account, NewAccount, UpdateAccount};
+use crate::models::account::{Account, AccountType, NewAccount, StatementSummary, UpdateAccount};
+use crate::services::statement_period::StatementPeriod;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates;
+use crate::utils::query_builder::UpdateBuilder;
 use crate::DbPool;
 use sqlx::{Row, SqlitePool};
 
 // Business logic functions (used by both commands and tests)
 
-pub async fn list_accounts_impl(db: &SqlitePool) -> Result<Vec<Account>, String> {
+/// List accounts by name; archived accounts are left out unless `include_archived`
+pub async fn list_accounts_impl(db: &SqlitePool, include_archived: bool) -> Result<Vec<Account>, String> {
     sqlx::query_as::<_, Account>(
-        "SELECT id, name, type, balance, created_at, updated_at FROM accounts ORDER BY name"
+        "SELECT id, name, type, balance_cents, statement_closing_day, statement_due_day, archived, created_at, updated_at FROM accounts
+         WHERE archived = 0 OR ? ORDER BY name"
     )
+    .bind(include_archived)
     .fetch_all(db)
     .await
     .map_err(|e| sanitize_db_error(e, "load accounts"))
@@ -19,11 +28,11 @@ pub async fn create_account_impl(
     account: NewAccount,
 ) -> Result<i64, String> {
     let result = sqlx::query(
-        "INSERT INTO accounts (name, type, balance) VALUES (?, ?, ?)"
+        "INSERT INTO accounts (name, type, balance_cents) VALUES (?, ?, ?)"
     )
     .bind(&account.name)
     .bind(account.account_type.to_string())
-    .bind(account.initial_balance)
+    .bind(account.initial_balance_cents)
     .execute(db)
     .await
     .map_err(|e| sanitize_db_error(e, "create account"))?;
@@ -35,108 +44,203 @@ pub async fn update_account_impl(
     db: &SqlitePool,
     update: UpdateAccount,
 ) -> Result<Account, String> {
-    // Use match statement with static SQL for all 7 combinations
-    // This prevents any possibility of SQL injection from future modifications
-    let result = match (&update.name, &update.account_type, update.balance) {
-        // All three fields
-        (Some(name), Some(account_type), Some(balance)) => {
-            sqlx::query(
-                "UPDATE accounts SET name = ?, type = ?, balance = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
-            )
-            .bind(name)
-            .bind(account_type.to_string())
-            .bind(balance)
-            .bind(update.id)
-            .execute(db)
-            .await
-        }
-        // Two fields: name + type
-        (Some(name), Some(account_type), None) => {
-            sqlx::query(
-                "UPDATE accounts SET name = ?, type = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
-            )
-            .bind(name)
-            .bind(account_type.to_string())
-            .bind(update.id)
-            .execute(db)
-            .await
-        }
-        // Two fields: name + balance
-        (Some(name), None, Some(balance)) => {
-            sqlx::query(
-                "UPDATE accounts SET name = ?, balance = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
-            )
-            .bind(name)
-            .bind(balance)
-            .bind(update.id)
-            .execute(db)
-            .await
+    // Column names are fixed in the builder; only values come from the caller
+    let builder = UpdateBuilder::new("accounts")
+        .set_if("name", update.name.as_deref())
+        .set_if("type", update.account_type.as_ref().map(|t| t.to_string()))
+        .set_if("balance_cents", update.balance_cents)
+        .touch_updated_at();
+
+    if builder.is_empty() {
+        return Err("At least one field must be provided for update".to_string());
+    }
+
+    let result = builder
+        .execute(db, update.id)
+        .await
+    .map_err(|e| sanitize_db_error(e, "update account"))?;
+
+    if result.rows_affected() == 0 {
+        return Err(format!("Account with id {} not found", update.id));
+    }
+
+    // Fetch and return the updated account
+    sqlx::query_as::<_, Account>(
+        "SELECT id, name, type, balance_cents, statement_closing_day, statement_due_day, archived, created_at, updated_at FROM accounts WHERE id = ?"
+    )
+    .bind(update.id)
+    .fetch_one(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "fetch updated account"))
+}
+
+/// Set the day an account's statement closes, or None to follow calendar months
+pub async fn set_statement_closing_day_impl(
+    db: &SqlitePool,
+    account_id: i64,
+    closing_day: Option<u32>,
+) -> Result<Account, String> {
+    if let Some(day) = closing_day {
+        if !(1..=MAX_STATEMENT_CLOSING_DAY).contains(&day) {
+            return Err(format!("Statement closing day must be between 1 and {}", MAX_STATEMENT_CLOSING_DAY));
         }
-        // Two fields: type + balance
-        (None, Some(account_type), Some(balance)) => {
-            sqlx::query(
-                "UPDATE accounts SET type = ?, balance = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
-            )
-            .bind(account_type.to_string())
-            .bind(balance)
-            .bind(update.id)
-            .execute(db)
-            .await
+    }
+
+    let result = sqlx::query(
+        "UPDATE accounts SET statement_closing_day = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+    )
+    .bind(closing_day)
+    .bind(account_id)
+    .execute(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "update statement closing day"))?;
+
+    if result.rows_affected() == 0 {
+        return Err(format!("Account with id {} not found", account_id));
+    }
+
+    sqlx::query_as::<_, Account>(
+        "SELECT id, name, type, balance_cents, statement_closing_day, statement_due_day, archived, created_at, updated_at FROM accounts WHERE id = ?"
+    )
+    .bind(account_id)
+    .fetch_one(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "fetch updated account"))
+}
+
+/// Set a credit card's statement closing day and payment due day
+/// Debts linked to the card take its due day, so payment schedules match the real bill
+pub async fn set_statement_cycle_impl(
+    db: &SqlitePool,
+    account_id: i64,
+    closing_day: Option<u32>,
+    due_day: Option<u32>,
+) -> Result<Account, String> {
+    if let Some(day) = closing_day {
+        if !(1..=MAX_STATEMENT_CLOSING_DAY).contains(&day) {
+            return Err(format!("Statement closing day must be between 1 and {}", MAX_STATEMENT_CLOSING_DAY));
         }
-        // Single field: name only
-        (Some(name), None, None) => {
-            sqlx::query(
-                "UPDATE accounts SET name = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
-            )
-            .bind(name)
-            .bind(update.id)
-            .execute(db)
-            .await
+    }
+    if let Some(day) = due_day {
+        if !(1..=MAX_DEBT_DUE_DAY).contains(&day) {
+            return Err(format!("Payment due day must be between 1 and {}", MAX_DEBT_DUE_DAY));
         }
-        // Single field: type only
-        (None, Some(account_type), None) => {
-            sqlx::query(
-                "UPDATE accounts SET type = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
-            )
-            .bind(account_type.to_string())
-            .bind(update.id)
-            .execute(db)
-            .await
+    }
+
+    let account_type: Option<(String,)> = sqlx::query_as("SELECT type FROM accounts WHERE id = ?")
+        .bind(account_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load account"))?;
+    match account_type {
+        None => return Err(format!("Account with id {} not found", account_id)),
+        Some((t,)) if t != AccountType::CreditCard.to_string() => {
+            return Err("Statement cycles can only be set on credit card accounts".to_string());
         }
-        // Single field: balance only
-        (None, None, Some(balance)) => {
-            sqlx::query(
-                "UPDATE accounts SET balance = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
-            )
-            .bind(balance)
-            .bind(update.id)
-            .execute(db)
+        Some(_) => {}
+    }
+
+    let mut tx = db.begin()
+        .await
+        .map_err(|e| sanitize_db_error(e, "begin transaction"))?;
+
+    sqlx::query(
+        "UPDATE accounts SET statement_closing_day = ?, statement_due_day = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+    )
+    .bind(closing_day)
+    .bind(due_day)
+    .bind(account_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| sanitize_db_error(e, "update statement cycle"))?;
+
+    if let Some(day) = due_day {
+        sqlx::query("UPDATE debts SET due_day = ?, updated_at = CURRENT_TIMESTAMP WHERE account_id = ?")
+            .bind(day)
+            .bind(account_id)
+            .execute(&mut *tx)
             .await
-        }
-        // No fields provided
-        (None, None, None) => {
-            return Err("At least one field must be provided for update".to_string());
-        }
+            .map_err(|e| sanitize_db_error(e, "update linked debts"))?;
     }
-    .map_err(|e| sanitize_db_error(e, "update account"))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| sanitize_db_error(e, "commit transaction"))?;
+
+    sqlx::query_as::<_, Account>(
+        "SELECT id, name, type, balance_cents, statement_closing_day, statement_due_day, archived, created_at, updated_at FROM accounts WHERE id = ?"
+    )
+    .bind(account_id)
+    .fetch_one(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "fetch updated account"))
+}
+
+/// Charges, payments and balance for the statement period containing `date`
+/// Without a date, the most recently closed statement as of `today`
+pub async fn get_statement_summary_impl(
+    db: &SqlitePool,
+    account_id: i64,
+    date: Option<&str>,
+    today: chrono::NaiveDate,
+) -> Result<StatementSummary, String> {
+    let date = match date {
+        Some(d) => chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date '{}': expected YYYY-MM-DD", d))?,
+        None => {
+            let closing_day = StatementPeriod::closing_day(db, account_id).await?;
+            let (current_start, _) = StatementPeriod::containing(today, closing_day).ok_or("Date calculation error")?;
+            current_start.pred_opt().ok_or("Date calculation error")?
+        }
+    };
+
+    StatementPeriod::summarize(db, account_id, date, today).await
+}
+
+/// Archive (retire) or unarchive an account; its transactions are kept either way
+async fn set_account_archived(
+    db: &SqlitePool,
+    account_id: i64,
+    archived: bool,
+) -> Result<Account, String> {
+    let result = sqlx::query(
+        "UPDATE accounts SET archived = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+    )
+    .bind(archived)
+    .bind(account_id)
+    .execute(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "archive account"))?;
 
     if result.rows_affected() == 0 {
-        return Err(format!("Account with id {} not found", update.id));
+        return Err(format!("Account with id {} not found", account_id));
     }
 
-    // Fetch and return the updated account
+    tracing::info!(account_id = account_id, archived = archived, "Account archive state changed");
+
     sqlx::query_as::<_, Account>(
-        "SELECT id, name, type, balance, created_at, updated_at FROM accounts WHERE id = ?"
+        "SELECT id, name, type, balance_cents, statement_closing_day, statement_due_day, archived, created_at, updated_at FROM accounts WHERE id = ?"
     )
-    .bind(update.id)
+    .bind(account_id)
     .fetch_one(db)
     .await
     .map_err(|e| sanitize_db_error(e, "fetch updated account"))
 }
 
+pub async fn archive_account_impl(db: &SqlitePool, account_id: i64) -> Result<Account, String> {
+    set_account_archived(db, account_id, true).await
+}
+
+pub async fn unarchive_account_impl(db: &SqlitePool, account_id: i64) -> Result<Account, String> {
+    set_account_archived(db, account_id, false).await
+}
+
+/// Delete an account and, through the foreign key cascade, its transactions
+/// Refused when any of those transactions is in a locked month, unless `override_lock` is set
 pub async fn delete_account_impl(
     db: &SqlitePool,
     account_id: i64,
+    override_lock: bool,
 ) -> Result<i64, String> {
     // Wrap all operations in a transaction to ensure atomicity
     let mut tx = db.begin()
@@ -163,6 +267,16 @@ pub async fn delete_account_impl(
 
     let transaction_count: i64 = count_result.get("count");
 
+    let dates: Vec<String> = sqlx::query_scalar("SELECT DISTINCT date FROM transactions WHERE account_id = ?")
+        .bind(account_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load transaction dates"))?;
+    let dates: Vec<&str> = dates.iter().map(String::as_str).collect();
+    ensure_unlocked(db, &dates, override_lock)
+        .await
+        .map_err(|e| e.to_user_message())?;
+
     // Delete the account (CASCADE will delete associated transactions)
     sqlx::query("DELETE FROM accounts WHERE id = ?")
         .bind(account_id)
@@ -181,30 +295,103 @@ pub async fn delete_account_impl(
 // Tauri command handlers (extract pool from managed state)
 
 #[tauri::command]
-pub async fn list_accounts(db_pool: tauri::State<'_, DbPool>) -> Result<Vec<Account>, String> {
-    list_accounts_impl(&db_pool.0).await
+pub async fn list_accounts(
+    db_pool: tauri::State<'_, DbPool>,
+    include_archived: Option<bool>,
+) -> Result<Vec<Account>, String> {
+    list_accounts_impl(&db_pool.pool(), include_archived.unwrap_or(false)).await
 }
 
 #[tauri::command]
 pub async fn create_account(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     account: NewAccount,
 ) -> Result<i64, String> {
-    create_account_impl(&db_pool.0, account).await
+    let result = create_account_impl(&db_pool.pool(), account).await?;
+    notify_changed(&app, DataKind::Accounts, ChangeAction::Created, [result]);
+    Ok(result)
 }
 
 #[tauri::command]
 pub async fn update_account(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     update: UpdateAccount,
 ) -> Result<Account, String> {
-    update_account_impl(&db_pool.0, update).await
+    let result = update_account_impl(&db_pool.pool(), update).await?;
+    notify_changed(&app, DataKind::Accounts, ChangeAction::Updated, [result.id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn set_statement_closing_day(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    account_id: i64,
+    closing_day: Option<u32>,
+) -> Result<Account, String> {
+    let result = set_statement_closing_day_impl(&db_pool.pool(), account_id, closing_day).await?;
+    notify_changed(&app, DataKind::Accounts, ChangeAction::Updated, [result.id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn set_statement_cycle(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    account_id: i64,
+    closing_day: Option<u32>,
+    due_day: Option<u32>,
+) -> Result<Account, String> {
+    let result = set_statement_cycle_impl(&db_pool.pool(), account_id, closing_day, due_day).await?;
+    notify_changed(&app, DataKind::Accounts, ChangeAction::Updated, [result.id]);
+    if due_day.is_some() {
+        notify_changed(&app, DataKind::Debts, ChangeAction::Updated, vec![]);
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_statement_summary(
+    db_pool: tauri::State<'_, DbPool>,
+    account_id: i64,
+    date: Option<String>,
+) -> Result<StatementSummary, String> {
+    get_statement_summary_impl(&db_pool.pool(), account_id, date.as_deref(), dates::today(&db_pool.pool()).await).await
 }
 
 #[tauri::command]
 pub async fn delete_account(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     account_id: i64,
+    override_lock: Option<bool>,
 ) -> Result<i64, String> {
-    delete_account_impl(&db_pool.0, account_id).await
+    let result = delete_account_impl(&db_pool.pool(), account_id, override_lock.unwrap_or(false)).await?;
+    notify_changed(&app, DataKind::Accounts, ChangeAction::Deleted, [account_id]);
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Deleted, vec![]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn archive_account(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    account_id: i64,
+) -> Result<Account, String> {
+    let result = archive_account_impl(&db_pool.pool(), account_id).await?;
+    notify_changed(&app, DataKind::Accounts, ChangeAction::Updated, [result.id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn unarchive_account(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    account_id: i64,
+) -> Result<Account, String> {
+    let result = unarchive_account_impl(&db_pool.pool(), account_id).await?;
+    notify_changed(&app, DataKind::Accounts, ChangeAction::Updated, [result.id]);
+    Ok(result)
 }
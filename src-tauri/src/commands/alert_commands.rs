@@ -0,0 +1,115 @@
+use crate::errors::AlertError;
+use crate::models::alert::Alert;
+use crate::services::alert_evaluator::AlertEvaluator;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates;
+use crate::DbPool;
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+use tauri::Emitter;
+
+/// Event emitted with the newly raised alerts after each evaluation that raised any
+pub const ALERTS_EVENT: &str = "alerts-raised";
+
+// Business logic functions (used by both commands and tests)
+
+/// Check targets, recent transactions, and upcoming debt payments as of `today`
+/// Returns only the alerts raised by this evaluation; events already alerted on are skipped
+pub async fn evaluate_alerts_impl(db: &SqlitePool, today: NaiveDate) -> Result<Vec<Alert>, AlertError> {
+    let candidates = AlertEvaluator::evaluate(db, today).await.map_err(AlertError::Database)?;
+
+    let mut raised = Vec::new();
+    for alert in candidates {
+        let inserted = sqlx::query_as::<_, Alert>(
+            "INSERT INTO alerts (alert_type, alert_key, severity, title, message, entity_type, entity_id)
+             VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(alert_key) DO NOTHING
+             RETURNING id, alert_type, alert_key, severity, title, message, entity_type, entity_id, dismissed_at, created_at"
+        )
+        .bind(alert.alert_type.to_string())
+        .bind(&alert.alert_key)
+        .bind(alert.severity.to_string())
+        .bind(&alert.title)
+        .bind(&alert.message)
+        .bind(alert.entity_type)
+        .bind(alert.entity_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| AlertError::Database(e.to_string()))?;
+        raised.extend(inserted);
+    }
+
+    if !raised.is_empty() {
+        tracing::info!(count = raised.len(), "Raised alerts");
+    }
+    Ok(raised)
+}
+
+/// Alerts newest first; dismissed alerts are left out unless `include_dismissed`
+pub async fn list_alerts_impl(db: &SqlitePool, include_dismissed: bool) -> Result<Vec<Alert>, AlertError> {
+    sqlx::query_as::<_, Alert>(
+        "SELECT id, alert_type, alert_key, severity, title, message, entity_type, entity_id, dismissed_at, created_at
+         FROM alerts
+         WHERE ? = 1 OR dismissed_at IS NULL
+         ORDER BY created_at DESC, id DESC"
+    )
+    .bind(include_dismissed)
+    .fetch_all(db)
+    .await
+    .map_err(|e| AlertError::Database(e.to_string()))
+}
+
+/// Dismiss an alert; dismissing it again keeps the original time
+pub async fn dismiss_alert_impl(db: &SqlitePool, id: i64) -> Result<(), AlertError> {
+    let result = sqlx::query("UPDATE alerts SET dismissed_at = COALESCE(dismissed_at, CURRENT_TIMESTAMP) WHERE id = ?")
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| AlertError::Database(e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err(AlertError::NotFound(id));
+    }
+    Ok(())
+}
+
+/// Evaluate alerts as of today and tell the frontend about any new ones
+pub async fn evaluate_and_emit(app: &tauri::AppHandle, db: &SqlitePool) -> Result<Vec<Alert>, AlertError> {
+    let raised = evaluate_alerts_impl(db, dates::today(db).await).await?;
+    if !raised.is_empty() {
+        if let Err(e) = app.emit(ALERTS_EVENT, &raised) {
+            tracing::warn!(error = %e, "Failed to emit alerts");
+        }
+        let ids: Vec<i64> = raised.iter().map(|alert| alert.id).collect();
+        notify_changed(app, DataKind::Alerts, ChangeAction::Created, ids);
+    }
+    Ok(raised)
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn evaluate_alerts(app: tauri::AppHandle, db_pool: tauri::State<'_, DbPool>) -> Result<Vec<Alert>, String> {
+    evaluate_and_emit(&app, &db_pool.pool())
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn list_alerts(
+    db_pool: tauri::State<'_, DbPool>,
+    include_dismissed: Option<bool>,
+) -> Result<Vec<Alert>, String> {
+    list_alerts_impl(&db_pool.pool(), include_dismissed.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn dismiss_alert(app: tauri::AppHandle, db_pool: tauri::State<'_, DbPool>, id: i64) -> Result<(), String> {
+    dismiss_alert_impl(&db_pool.pool(), id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Alerts, ChangeAction::Updated, [id]);
+    Ok(())
+}
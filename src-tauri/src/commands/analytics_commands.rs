@@ -1,87 +1,216 @@
+use crate::commands::dashboard_commands::get_dashboard_config_impl;
+use crate::commands::debt_commands::get_upcoming_payments_impl;
+use crate::commands::scheduled_transaction_commands::list_scheduled_transactions_impl;
+use crate::commands::transaction_commands::{matching_amounts, TransactionFilter};
+use crate::constants::{
+    AMOUNT_DISTRIBUTION_BUCKETS_CENTS, DEFAULT_CATEGORY_STATS_MONTHS, DEFAULT_DASHBOARD_TOP_CATEGORIES,
+    DEFAULT_DASHBOARD_UPCOMING_BILLS, DEFAULT_FINANCIAL_HEALTH_MONTHS, DEFAULT_NET_WORTH_HISTORY_MONTHS, DEFAULT_TOP_ITEMS_LIMIT,
+    DEFAULT_UPCOMING_BILL_DAYS, MAX_CATEGORY_STATS_MONTHS, MAX_CSV_FILE_SIZE, MAX_CSV_ROWS, MAX_FINANCIAL_HEALTH_MONTHS, MAX_NET_WORTH_HISTORY_MONTHS, MAX_PAGE_SIZE, MAX_UPCOMING_PAYMENT_MONTHS, PERCENT_TO_DECIMAL_DIVISOR,
+};
 use crate::errors::sanitize_db_error;
-use crate::services::spending_aggregator::{CategorySpending, SpendingAggregator, SpendingByCategory};
+use crate::models::dashboard::{DashboardWidget, WidgetKind};
+use crate::models::report::{ReportSpec, ReportTextFormat};
+use crate::models::spending_target::NewSpendingTarget;
+use crate::services::amount_distribution::{self, AmountDistribution};
+use crate::services::analytics_cache::AnalyticsCache;
+use crate::services::budget_csv::{BudgetCsv, BudgetCsvLine, BudgetCsvRow};
+use crate::services::category_statistics::{CategoryStatistics, CategoryStatisticsCalculator};
+use crate::services::csv_parser::CsvParser;
+use crate::services::financial_health::{FinancialHealth, FinancialHealthCalculator};
+use crate::services::insight_generator::{InsightGenerator, SpendingInsights};
+use crate::services::pdf_report::{AnalyticsReportData, PdfReport};
+use crate::services::portfolio_valuer::PortfolioValuer;
+use crate::services::report_renderer::ReportRenderer;
+use crate::services::spending_aggregator::{
+    CategorySpending, LargestTransaction, SpendingAggregator, SpendingByCategory,
+    SpendingByMember, SpendingByMerchant,
+};
+use crate::services::statement_period::StatementPeriod;
+use crate::services::tax_report::TaxReportBuilder;
 use crate::services::target_tracker::{TargetTracker, TargetsProgress};
-use crate::services::trends_calculator::{TrendsCalculator, SpendingTrends};
+use crate::services::transaction_scheduler::Schedule;
+use crate::services::trends_calculator::{SpendingHeatmap, SpendingTrends, TrendsCalculator};
+use crate::services::variance_reporter::{BudgetVarianceReport, VarianceReporter};
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates;
+use crate::utils::money::{self, Cents};
 use crate::DbPool;
-use chrono::Datelike;
+use chrono::{Months, NaiveDate};
 use serde::Serialize;
 use sqlx::SqlitePool;
+use std::collections::HashSet;
+use tauri_plugin_clipboard_manager::ClipboardExt;
 
 // Business logic functions (used by both commands and tests)
 
 // T071: get_spending_by_category
+/// `member_id` limits the spending to one household member's
 pub async fn get_spending_by_category_impl(
     db: &SqlitePool,
     start_date: &str,
     end_date: &str,
     account_id: Option<i64>,
+    member_id: Option<i64>,
 ) -> Result<SpendingByCategory, String> {
-    SpendingAggregator::get_spending_by_category(db, start_date, end_date, account_id).await
+    SpendingAggregator::get_spending_by_category(db, start_date, end_date, account_id, member_id).await
 }
 
 #[tauri::command]
 pub async fn get_spending_by_category(
     db_pool: tauri::State<'_, DbPool>,
+    cache: tauri::State<'_, AnalyticsCache>,
     start_date: String,
     end_date: String,
     account_id: Option<i64>,
+    member_id: Option<i64>,
 ) -> Result<SpendingByCategory, String> {
-    get_spending_by_category_impl(&db_pool.0, &start_date, &end_date, account_id).await
+    let db = db_pool.pool();
+    let key = AnalyticsCache::key("spending_by_category", &(&start_date, &end_date, account_id, member_id));
+    cache
+        .get_or_compute(key, || {
+            get_spending_by_category_impl(&db, &start_date, &end_date, account_id, member_id)
+        })
+        .await
+}
+
+// get_spending_by_member
+pub async fn get_spending_by_member_impl(
+    db: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+    account_id: Option<i64>,
+) -> Result<SpendingByMember, String> {
+    SpendingAggregator::get_spending_by_member(db, start_date, end_date, account_id).await
+}
+
+#[tauri::command]
+pub async fn get_spending_by_member(
+    db_pool: tauri::State<'_, DbPool>,
+    cache: tauri::State<'_, AnalyticsCache>,
+    start_date: String,
+    end_date: String,
+    account_id: Option<i64>,
+) -> Result<SpendingByMember, String> {
+    let db = db_pool.pool();
+    let key = AnalyticsCache::key("spending_by_member", &(&start_date, &end_date, account_id));
+    cache
+        .get_or_compute(key, || get_spending_by_member_impl(&db, &start_date, &end_date, account_id))
+        .await
 }
 
 // T072: get_spending_trends
+/// `account_id` limits the trends to one account's spending
+/// The "statement" interval buckets by `statement_account_id`'s statement periods, or by
+/// `account_id`'s when no statement account is given
 pub async fn get_spending_trends_impl(
     db: &SqlitePool,
     start_date: &str,
     end_date: &str,
     interval: &str,
     category_id: Option<i64>,
+    account_id: Option<i64>,
+    statement_account_id: Option<i64>,
 ) -> Result<SpendingTrends, String> {
-    TrendsCalculator::get_spending_trends(db, start_date, end_date, interval, category_id).await
+    if interval == "statement" {
+        let statement_account_id = statement_account_id
+            .or(account_id)
+            .ok_or("An account is required for the statement interval")?;
+        let closing_day = StatementPeriod::closing_day(db, statement_account_id).await?;
+        return TrendsCalculator::get_statement_trends(db, start_date, end_date, closing_day, category_id, account_id)
+            .await;
+    }
+
+    TrendsCalculator::get_spending_trends(db, start_date, end_date, interval, category_id, account_id).await
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn get_spending_trends(
     db_pool: tauri::State<'_, DbPool>,
+    cache: tauri::State<'_, AnalyticsCache>,
     start_date: String,
     end_date: String,
     interval: String,
     category_id: Option<i64>,
+    account_id: Option<i64>,
+    statement_account_id: Option<i64>,
 ) -> Result<SpendingTrends, String> {
-    get_spending_trends_impl(&db_pool.0, &start_date, &end_date, &interval, category_id).await
+    let db = db_pool.pool();
+    let key = AnalyticsCache::key(
+        "spending_trends",
+        &(&start_date, &end_date, &interval, category_id, account_id, statement_account_id),
+    );
+    cache
+        .get_or_compute(key, || {
+            get_spending_trends_impl(
+                &db,
+                &start_date,
+                &end_date,
+                &interval,
+                category_id,
+                account_id,
+                statement_account_id,
+            )
+        })
+        .await
+}
+
+// get_spending_heatmap
+pub async fn get_spending_heatmap_impl(
+    db: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+    category_id: Option<i64>,
+) -> Result<SpendingHeatmap, String> {
+    let (start, end) = (dates::parse_date(start_date)?, dates::parse_date(end_date)?);
+    if start > end {
+        return Err("Start date must be on or before end date".to_string());
+    }
+
+    TrendsCalculator::get_spending_heatmap(db, &dates::format_date(start), &dates::format_date(end), category_id)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load spending heatmap"))
+}
+
+#[tauri::command]
+pub async fn get_spending_heatmap(
+    db_pool: tauri::State<'_, DbPool>,
+    cache: tauri::State<'_, AnalyticsCache>,
+    start_date: String,
+    end_date: String,
+    category_id: Option<i64>,
+) -> Result<SpendingHeatmap, String> {
+    let db = db_pool.pool();
+    let key = AnalyticsCache::key("spending_heatmap", &(&start_date, &end_date, category_id));
+    cache
+        .get_or_compute(key, || get_spending_heatmap_impl(&db, &start_date, &end_date, category_id))
+        .await
 }
 
 // T073: get_spending_targets_progress
+/// The "statement" period runs from the start of `statement_account_id`'s current statement to today
 pub async fn get_spending_targets_progress_impl(
     db: &SqlitePool,
     period: Option<String>,
     custom_start: Option<String>,
     custom_end: Option<String>,
+    statement_account_id: Option<i64>,
 ) -> Result<TargetsProgress, String> {
     // Calculate date range based on period or custom dates
     let (start_date, end_date) = if let (Some(start), Some(end)) = (custom_start, custom_end) {
         (start, end)
     } else {
         let period_str = period.unwrap_or_else(|| "monthly".to_string());
+        let today = dates::today(db).await;
         match period_str.as_str() {
-            "monthly" => {
-                let now = chrono::Local::now().naive_local();
-                let start = now.format("%Y-%m-01").to_string();
-                let end = now.format("%Y-%m-%d").to_string();
-                (start, end)
-            }
-            "quarterly" => {
-                let now = chrono::Local::now().naive_local();
-                let quarter_start_month = ((now.month() - 1) / 3) * 3 + 1;
-                let start = format!("{}-{:02}-01", now.year(), quarter_start_month);
-                let end = now.format("%Y-%m-%d").to_string();
-                (start, end)
-            }
-            "yearly" => {
-                let now = chrono::Local::now().naive_local();
-                let start = format!("{}-01-01", now.year());
-                let end = now.format("%Y-%m-%d").to_string();
-                (start, end)
+            "monthly" => (dates::format_date(dates::month_start(today)), dates::format_date(today)),
+            "quarterly" => (dates::format_date(dates::quarter_start(today)), dates::format_date(today)),
+            "yearly" => (dates::format_date(dates::year_start(today)), dates::format_date(today)),
+            "statement" => {
+                let account_id = statement_account_id.ok_or("An account is required for the statement period")?;
+                let closing_day = StatementPeriod::closing_day(db, account_id).await?;
+                let (start, _) = StatementPeriod::containing(today, closing_day).ok_or("Date calculation error")?;
+                (start.format("%Y-%m-%d").to_string(), today.format("%Y-%m-%d").to_string())
             }
             _ => return Err(format!("Invalid period: {}", period_str)),
         }
@@ -96,48 +225,26 @@ pub async fn get_spending_targets_progress(
     period: Option<String>,
     custom_start: Option<String>,
     custom_end: Option<String>,
+    statement_account_id: Option<i64>,
 ) -> Result<TargetsProgress, String> {
-    get_spending_targets_progress_impl(&db_pool.0, period, custom_start, custom_end).await
+    get_spending_targets_progress_impl(&db_pool.pool(), period, custom_start, custom_end, statement_account_id).await
 }
 
 // T074: create_spending_target
-pub async fn create_spending_target_impl(
-    db: &SqlitePool,
-    category_id: i64,
-    amount: f64,
-    period: &str,
-    start_date: &str,
-    end_date: Option<&str>,
-) -> Result<i64, String> {
-    TargetTracker::create_target(
-        db,
-        category_id,
-        amount,
-        period,
-        start_date,
-        end_date,
-    )
-    .await
+/// A target with neither a category nor an account caps all spending
+pub async fn create_spending_target_impl(db: &SqlitePool, target: NewSpendingTarget) -> Result<i64, String> {
+    TargetTracker::create_target(db, &target).await
 }
 
 #[tauri::command]
 pub async fn create_spending_target(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
-    category_id: i64,
-    amount: f64,
-    period: String,
-    start_date: String,
-    end_date: Option<String>,
+    target: NewSpendingTarget,
 ) -> Result<i64, String> {
-    create_spending_target_impl(
-        &db_pool.0,
-        category_id,
-        amount,
-        &period,
-        &start_date,
-        end_date.as_deref(),
-    )
-    .await
+    let target_id = create_spending_target_impl(&db_pool.pool(), target).await?;
+    notify_changed(&app, DataKind::SpendingTargets, ChangeAction::Created, [target_id]);
+    Ok(target_id)
 }
 
 // T075: update_spending_target
@@ -149,138 +256,1087 @@ pub struct UpdateTargetResponse {
 pub async fn update_spending_target_impl(
     db: &SqlitePool,
     target_id: i64,
-    amount: Option<f64>,
+    amount_cents: Option<Cents>,
     end_date: Option<&str>,
 ) -> Result<UpdateTargetResponse, String> {
-    let success = TargetTracker::update_target(db, target_id, amount, end_date).await?;
+    let success = TargetTracker::update_target(db, target_id, amount_cents, end_date).await?;
     Ok(UpdateTargetResponse { success })
 }
 
 #[tauri::command]
 pub async fn update_spending_target(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     target_id: i64,
     amount: Option<f64>,
     end_date: Option<String>,
 ) -> Result<UpdateTargetResponse, String> {
-    update_spending_target_impl(&db_pool.0, target_id, amount, end_date.as_deref()).await
+    let amount_cents = amount.map(money::parse_dollars).transpose()?;
+    let result = update_spending_target_impl(&db_pool.pool(), target_id, amount_cents, end_date.as_deref()).await?;
+    notify_changed(&app, DataKind::SpendingTargets, ChangeAction::Updated, [target_id]);
+    Ok(result)
+}
+
+// export_budgets_csv
+#[derive(Debug, Serialize)]
+pub struct BudgetExportResponse {
+    pub success: bool,
+    pub file_path: String,
+    pub record_count: usize,
+}
+
+/// Write the category targets in effect today as Category, Period, Amount rows
+pub async fn export_budgets_csv_impl(db: &SqlitePool, output_path: &str) -> Result<BudgetExportResponse, String> {
+    let today = dates::format_date(dates::today(db).await);
+    let rows = sqlx::query_as::<_, (String, String, Cents)>(
+        "SELECT c.name, st.period, st.amount_cents
+         FROM spending_targets st
+         JOIN categories c ON c.id = st.category_id
+         WHERE st.scope = 'category' AND st.start_date <= ? AND (st.end_date IS NULL OR st.end_date >= ?)
+         ORDER BY c.name COLLATE NOCASE, st.period"
+    )
+    .bind(&today)
+    .bind(&today)
+    .fetch_all(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "load budgets"))?;
+
+    let rows: Vec<BudgetCsvRow> = rows
+        .into_iter()
+        .map(|(category, period, amount_cents)| BudgetCsvRow { category, period, amount_cents })
+        .collect();
+    let csv = BudgetCsv::to_csv(&rows)?;
+    std::fs::write(output_path, csv).map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(BudgetExportResponse {
+        success: true,
+        file_path: output_path.to_string(),
+        record_count: rows.len(),
+    })
+}
+
+#[tauri::command]
+pub async fn export_budgets_csv(
+    db_pool: tauri::State<'_, DbPool>,
+    output_path: String,
+) -> Result<BudgetExportResponse, String> {
+    export_budgets_csv_impl(&db_pool.pool(), &output_path).await
+}
+
+// import_budgets_csv
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetImportAction {
+    Create,
+    Update, // Changes the amount of the target in effect on the start date
+    Unchanged,
+    Error,
+}
+
+/// What importing one line of a budget file does, or why it can't
+#[derive(Debug, Serialize)]
+pub struct BudgetImportRow {
+    pub line: usize,
+    pub category: String,
+    pub period: String,
+    #[serde(rename = "amount", with = "money::dollars::option")]
+    pub amount_cents: Option<Cents>, // None when the amount couldn't be read
+    pub action: BudgetImportAction,
+    pub category_id: Option<i64>,
+    pub target_id: Option<i64>, // The existing target the row updates or leaves unchanged
+    pub message: Option<String>,
+}
+
+impl BudgetImportRow {
+    fn fail(mut self, message: String) -> Self {
+        self.action = BudgetImportAction::Error;
+        self.message = Some(message);
+        self
+    }
 }
 
-// T076: get_dashboard_summary
 #[derive(Debug, Serialize)]
+pub struct BudgetImportReport {
+    pub start_date: String, // When the imported budgets take effect
+    pub rows: Vec<BudgetImportRow>,
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub errors: usize,
+    pub applied: bool, // False for a dry run, or when any row has an error
+}
+
+/// The target a row updates, as it was before the import
+struct ExistingTarget {
+    id: i64,
+    start_date: String,
+    end_date: Option<String>,
+}
+
+/// Check one line of a budget file against the categories and the targets already set
+async fn plan_budget_row(
+    db: &SqlitePool,
+    line: BudgetCsvLine,
+    start_date: &str,
+    seen: &mut HashSet<(i64, String)>,
+) -> Result<(BudgetImportRow, Option<ExistingTarget>), String> {
+    let period = if line.period.is_empty() { "monthly".to_string() } else { line.period.to_lowercase() };
+    let amount_cents = CsvParser::parse_cents(&line.amount, None).ok().filter(|amount| *amount > 0);
+    let row = BudgetImportRow {
+        line: line.line,
+        category: line.category,
+        period,
+        amount_cents,
+        action: BudgetImportAction::Create,
+        category_id: None,
+        target_id: None,
+        message: None,
+    };
+
+    if row.category.is_empty() {
+        return Ok((row.fail("The category is blank".to_string()), None));
+    }
+    let category = sqlx::query_as::<_, (i64, bool)>(
+        "SELECT id, archived FROM categories WHERE name = ? COLLATE NOCASE LIMIT 1"
+    )
+    .bind(&row.category)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "find category"))?;
+    let Some((category_id, archived)) = category else {
+        let message = format!("There's no category named \"{}\"", row.category);
+        return Ok((row.fail(message), None));
+    };
+    let mut row = BudgetImportRow { category_id: Some(category_id), ..row };
+    if archived {
+        let message = format!("The category \"{}\" is archived", row.category);
+        return Ok((row.fail(message), None));
+    }
+    if !matches!(row.period.as_str(), "monthly" | "quarterly" | "yearly") {
+        let message = format!("Unknown period \"{}\"; use monthly, quarterly or yearly", row.period);
+        return Ok((row.fail(message), None));
+    }
+    let Some(amount_cents) = row.amount_cents else {
+        return Ok((row.fail(format!("\"{}\" isn't an amount above zero", line.amount)), None));
+    };
+    if !seen.insert((category_id, row.period.clone())) {
+        let message = format!("This category already has a {} budget earlier in the file", row.period);
+        return Ok((row.fail(message), None));
+    }
+
+    // The target in effect on the start date, and any set to start later
+    let targets = sqlx::query_as::<_, (i64, Cents, String, Option<String>)>(
+        "SELECT id, amount_cents, start_date, end_date FROM spending_targets
+         WHERE scope = 'category' AND category_id = ? AND period = ?
+           AND (end_date IS NULL OR end_date >= ?)
+         ORDER BY start_date"
+    )
+    .bind(category_id)
+    .bind(&row.period)
+    .bind(start_date)
+    .fetch_all(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "load spending targets"))?;
+
+    if let Some((_, _, later_start, _)) = targets.iter().find(|(_, _, start, _)| start.as_str() > start_date) {
+        let message = format!("A {} target starting {} is already set; change it instead", row.period, later_start);
+        return Ok((row.fail(message), None));
+    }
+    let Some((id, current, existing_start, existing_end)) = targets.into_iter().next() else {
+        return Ok((row, None));
+    };
+
+    row.target_id = Some(id);
+    if current == amount_cents {
+        row.action = BudgetImportAction::Unchanged;
+        return Ok((row, None));
+    }
+    row.action = BudgetImportAction::Update;
+    row.message = Some(format!("Was {:.2}", money::from_cents(current)));
+    let existing = ExistingTarget { id, start_date: existing_start, end_date: existing_end };
+    Ok((row, Some(existing)))
+}
+
+/// Create or update category targets from a Category, Period, Amount file, taking effect on
+/// `start_date` (default the start of this month). A blank period means monthly
+/// A target that started earlier is ended the day before and continued at the new amount, so
+/// past periods keep the budget they had. Nothing is written for a dry run or when any row
+/// has an error; the report says what each row would do
+pub async fn import_budgets_csv_impl(
+    db: &SqlitePool,
+    csv_content: &str,
+    start_date: Option<&str>,
+    dry_run: bool,
+) -> Result<BudgetImportReport, String> {
+    if csv_content.len() > MAX_CSV_FILE_SIZE {
+        return Err(format!("File is too large ({} bytes, max {})", csv_content.len(), MAX_CSV_FILE_SIZE));
+    }
+    let start = match start_date {
+        Some(date) => dates::parse_date(date)?,
+        None => dates::month_start(dates::today(db).await),
+    };
+    let start_date = dates::format_date(start);
+    let lines = BudgetCsv::parse(csv_content)?;
+    if lines.len() > MAX_CSV_ROWS {
+        return Err(format!("Too many rows ({}, max {})", lines.len(), MAX_CSV_ROWS));
+    }
+
+    let mut seen = HashSet::new();
+    let mut planned = Vec::with_capacity(lines.len());
+    for line in lines {
+        planned.push(plan_budget_row(db, line, &start_date, &mut seen).await?);
+    }
+
+    let count = |action| planned.iter().filter(|(row, _)| row.action == action).count();
+    let mut report = BudgetImportReport {
+        start_date: start_date.clone(),
+        created: count(BudgetImportAction::Create),
+        updated: count(BudgetImportAction::Update),
+        unchanged: count(BudgetImportAction::Unchanged),
+        errors: count(BudgetImportAction::Error),
+        rows: Vec::new(),
+        applied: false,
+    };
+
+    if !dry_run && report.errors == 0 {
+        let db_error = |e| sanitize_db_error(e, "import budgets");
+        let day_before = dates::format_date(start.pred_opt().ok_or("Invalid start date")?);
+        let mut tx = db.begin().await.map_err(db_error)?;
+        for (row, existing) in &planned {
+            let (Some(category_id), Some(amount_cents)) = (row.category_id, row.amount_cents) else {
+                continue;
+            };
+            let end_date = match (row.action, existing) {
+                (BudgetImportAction::Create, _) => None,
+                (BudgetImportAction::Update, Some(existing)) if existing.start_date == start_date => {
+                    sqlx::query("UPDATE spending_targets SET amount_cents = ? WHERE id = ?")
+                        .bind(amount_cents)
+                        .bind(existing.id)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(db_error)?;
+                    continue;
+                }
+                (BudgetImportAction::Update, Some(existing)) => {
+                    sqlx::query("UPDATE spending_targets SET end_date = ? WHERE id = ?")
+                        .bind(&day_before)
+                        .bind(existing.id)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(db_error)?;
+                    existing.end_date.clone()
+                }
+                _ => continue,
+            };
+            sqlx::query(
+                "INSERT INTO spending_targets (scope, category_id, account_id, amount_cents, period, start_date, end_date)
+                 VALUES ('category', ?, NULL, ?, ?, ?, ?)"
+            )
+            .bind(category_id)
+            .bind(amount_cents)
+            .bind(&row.period)
+            .bind(&start_date)
+            .bind(end_date)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        }
+        tx.commit().await.map_err(db_error)?;
+        report.applied = true;
+    }
+
+    report.rows = planned.into_iter().map(|(row, _)| row).collect();
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn import_budgets_csv(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    csv_content: String,
+    start_date: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<BudgetImportReport, String> {
+    let report =
+        import_budgets_csv_impl(&db_pool.pool(), &csv_content, start_date.as_deref(), dry_run.unwrap_or(false)).await?;
+    if report.applied && report.created + report.updated > 0 {
+        notify_changed(&app, DataKind::SpendingTargets, ChangeAction::Updated, vec![]);
+    }
+    Ok(report)
+}
+
+// T076: get_dashboard_summary
+#[derive(Debug, Clone, Serialize)]
+/// The period totals, plus a section for each widget in the layout; sections for widgets
+/// that aren't shown are None and never computed
 pub struct DashboardSummary {
     pub period: DatePeriod,
-    pub total_spending: f64,
-    pub total_income: f64,
-    pub net: f64,
-    pub top_categories: Vec<CategorySpending>,
-    pub debt_summary: DebtSummary,
-    pub target_summary: TargetSummary,
+    #[serde(rename = "total_spending", with = "money::dollars")]
+    pub total_spending_cents: Cents,
+    #[serde(rename = "total_income", with = "money::dollars")]
+    pub total_income_cents: Cents,
+    #[serde(rename = "net", with = "money::dollars")]
+    pub net_cents: Cents,
+    pub account_ids: Vec<i64>, // Empty when the summary covers every account
+    pub widgets: Vec<DashboardWidget>, // The layout the summary was built for
+    pub top_categories: Option<Vec<CategorySpending>>, // Spending widget
+    pub comparison: Option<PeriodComparison>,          // Spending widget
+    pub debt_summary: Option<DebtSummary>,
+    pub target_summary: Option<TargetSummary>,
+    pub net_worth: Option<NetWorthSummary>,
+    pub upcoming_bills: Option<Vec<UpcomingBill>>, // Soonest first
 }
 
-#[derive(Debug, Serialize)]
+/// The same totals for the equally long period just before the dashboard's
+#[derive(Debug, Clone, Serialize)]
+pub struct PeriodComparison {
+    pub previous_period: DatePeriod,
+    #[serde(rename = "previous_spending", with = "money::dollars")]
+    pub previous_spending_cents: Cents,
+    #[serde(rename = "previous_income", with = "money::dollars")]
+    pub previous_income_cents: Cents,
+    #[serde(rename = "previous_net", with = "money::dollars")]
+    pub previous_net_cents: Cents,
+    #[serde(rename = "spending_change", with = "money::dollars")]
+    pub spending_change_cents: Cents,
+    #[serde(rename = "income_change", with = "money::dollars")]
+    pub income_change_cents: Cents,
+    #[serde(rename = "net_change", with = "money::dollars")]
+    pub net_change_cents: Cents,
+    pub spending_change_percent: Option<f64>, // None when nothing was spent in the previous period
+    pub income_change_percent: Option<f64>,   // None when nothing was earned in the previous period
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct DatePeriod {
     pub start_date: String,
     pub end_date: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DebtSummary {
-    pub total_debt: f64,
-    pub total_monthly_payment: f64,
+    #[serde(rename = "total_debt", with = "money::dollars")]
+    pub total_debt_cents: Cents,
+    #[serde(rename = "total_monthly_payment", with = "money::dollars")]
+    pub total_monthly_payment_cents: Cents,
     pub next_payoff_date: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TargetSummary {
     pub on_track_count: i64,
     pub over_count: i64,
-    pub total_variance: f64,
+    #[serde(rename = "total_variance", with = "money::dollars")]
+    pub total_variance_cents: Cents,
+}
+
+/// Account balances and investments less debt balances, as of now rather than the dashboard period
+#[derive(Debug, Clone, Serialize)]
+pub struct NetWorthSummary {
+    #[serde(rename = "accounts_total", with = "money::dollars")]
+    pub accounts_total_cents: Cents,
+    #[serde(rename = "investments_total", with = "money::dollars")]
+    pub investments_total_cents: Cents, // Holdings at their latest prices
+    #[serde(rename = "debts_total", with = "money::dollars")]
+    pub debts_total_cents: Cents,
+    #[serde(rename = "net_worth", with = "money::dollars")]
+    pub net_worth_cents: Cents,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpcomingBill {
+    pub name: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents, // Positive
+    pub due_date: String,
+    pub source: String, // "debt" for a minimum payment, "scheduled" for a scheduled transaction
+}
+
+/// Resolve a named reporting period into an inclusive (start_date, end_date) range ending `today`
+/// Accepts "current_month", "last_30_days", "current_year", or a four-digit year (e.g. "2024")
+fn resolve_period(period: &str, today: NaiveDate) -> Result<(String, String), String> {
+    match period {
+        "current_month" => Ok((dates::format_date(dates::month_start(today)), dates::format_date(today))),
+        "last_30_days" => Ok((dates::format_date(today - chrono::Duration::days(30)), dates::format_date(today))),
+        "current_year" => Ok((dates::format_date(dates::year_start(today)), dates::format_date(today))),
+        year if year.len() == 4 && year.chars().all(|c| c.is_ascii_digit()) => {
+            Ok((format!("{}-01-01", year), format!("{}-12-31", year)))
+        }
+        _ => Err(format!("Invalid period: {}", period)),
+    }
+}
+
+/// Resolve either a custom range (both ends required) or a named period, defaulting to "current_month"
+fn resolve_range(
+    period: Option<&str>,
+    custom_start: Option<&str>,
+    custom_end: Option<&str>,
+    today: NaiveDate,
+) -> Result<(NaiveDate, NaiveDate), String> {
+    let (start_date, end_date) = match (custom_start, custom_end) {
+        (Some(start), Some(end)) => (start.to_string(), end.to_string()),
+        (None, None) => resolve_period(period.unwrap_or("current_month"), today)?,
+        _ => return Err("A custom range needs both a start and an end date".to_string()),
+    };
+    let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid start date: {}", start_date))?;
+    let end = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid end date: {}", end_date))?;
+    if start > end {
+        return Err("Start date must be on or before end date".to_string());
+    }
+    Ok((start, end))
+}
+
+fn percent_change(current: Cents, previous: Cents) -> Option<f64> {
+    if previous > 0 {
+        Some((current - previous) as f64 / previous as f64 * PERCENT_TO_DECIMAL_DIVISOR)
+    } else {
+        None
+    }
+}
+
+/// Debt payments and scheduled bills due from `today` through `days_ahead` days out, soonest
+/// first; overdue scheduled bills still waiting to be posted are included
+async fn upcoming_bills(
+    db: &SqlitePool,
+    today: NaiveDate,
+    days_ahead: i64,
+    limit: i64,
+) -> Result<Vec<UpcomingBill>, String> {
+    let end = today + chrono::Duration::days(days_ahead);
+    let mut bills = Vec::new();
+
+    let months_ahead = ((days_ahead / 28) as u32 + 1).min(MAX_UPCOMING_PAYMENT_MONTHS);
+    for payment in get_upcoming_payments_impl(db, months_ahead, today)
+        .await
+        .map_err(|e| e.to_user_message())?
+        .into_iter()
+        .flat_map(|schedule| schedule.payments)
+    {
+        if dates::parse_date(&payment.due_date).is_ok_and(|date| date <= end) {
+            bills.push(UpcomingBill {
+                name: payment.debt_name,
+                amount_cents: payment.amount_cents,
+                due_date: payment.due_date,
+                source: "debt".to_string(),
+            });
+        }
+    }
+
+    for item in list_scheduled_transactions_impl(db).await.map_err(|e| e.to_user_message())? {
+        if item.amount_cents >= 0 {
+            continue;
+        }
+        let (Some(schedule), Some(next_due)) =
+            (Schedule::of(&item), item.next_due_date.as_deref().and_then(|d| dates::parse_date(d).ok()))
+        else {
+            continue;
+        };
+        for date in schedule.occurrences_between(next_due, end) {
+            bills.push(UpcomingBill {
+                name: item.description.clone(),
+                amount_cents: item.amount_cents.abs(),
+                due_date: dates::format_date(date),
+                source: "scheduled".to_string(),
+            });
+        }
+    }
+
+    bills.sort_by(|a, b| a.due_date.cmp(&b.due_date).then_with(|| a.name.cmp(&b.name)));
+    bills.truncate(limit as usize);
+    Ok(bills)
+}
+
+async fn net_worth(db: &SqlitePool) -> Result<NetWorthSummary, String> {
+    let (accounts_total_cents, debts_total_cents) = sqlx::query_as::<_, (Cents, Cents)>(
+        "SELECT
+            (SELECT COALESCE(SUM(balance_cents), 0) FROM accounts),
+            (SELECT COALESCE(SUM(balance_cents), 0) FROM debts)"
+    )
+    .fetch_one(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "calculate net worth for dashboard"))?;
+    let today = dates::format_date(dates::today(db).await);
+    let investments_total_cents = PortfolioValuer::total_value(db, &today)
+        .await
+        .map_err(|e| sanitize_db_error(e, "value investments for dashboard"))?;
+
+    Ok(NetWorthSummary {
+        accounts_total_cents,
+        investments_total_cents,
+        debts_total_cents,
+        net_worth_cents: accounts_total_cents + investments_total_cents - debts_total_cents,
+    })
 }
 
+/// `custom_start`/`custom_end` (both required) take precedence over `period`, which defaults to "current_month"
+/// Spending, income, top categories and the comparison are limited to `account_ids` when given;
+/// debts, targets, net worth and bills always cover everything
+/// `widgets` is the layout to build the summary for; None uses the saved one
 pub async fn get_dashboard_summary_impl(
     db: &SqlitePool,
-    period: &str,
+    period: Option<&str>,
+    custom_start: Option<&str>,
+    custom_end: Option<&str>,
+    account_ids: Option<&[i64]>,
+    widgets: Option<&[DashboardWidget]>,
 ) -> Result<DashboardSummary, String> {
+    let widgets = match widgets {
+        Some(widgets) => widgets.to_vec(),
+        None => get_dashboard_config_impl(db).await?.widgets,
+    };
+    let widget = |kind: WidgetKind| widgets.iter().find(|w| w.kind == kind);
+
     // Calculate date range
-    let (start_date, end_date) = match period {
-        "current_month" => {
-            let now = chrono::Local::now().naive_local();
-            let start = now.format("%Y-%m-01").to_string();
-            let end = now.format("%Y-%m-%d").to_string();
-            (start, end)
+    let today = dates::today(db).await;
+    let (start, end) = resolve_range(period, custom_start, custom_end, today)?;
+    let start_date = start.format("%Y-%m-%d").to_string();
+    let end_date = end.format("%Y-%m-%d").to_string();
+
+    // An empty selection means no filter rather than no accounts
+    let account_ids = account_ids.filter(|ids| !ids.is_empty());
+    if let Some(ids) = account_ids {
+        for &id in ids {
+            let exists = sqlx::query_as::<_, (i64,)>("SELECT id FROM accounts WHERE id = ?")
+                .bind(id)
+                .fetch_optional(db)
+                .await
+                .map_err(|e| sanitize_db_error(e, "look up dashboard account"))?;
+            if exists.is_none() {
+                return Err(format!("Account not found: {}", id));
+            }
         }
-        "last_30_days" => {
-            let now = chrono::Local::now().naive_local();
-            let start = (now - chrono::Duration::days(30)).format("%Y-%m-%d").to_string();
-            let end = now.format("%Y-%m-%d").to_string();
-            (start, end)
+    }
+
+    // Get spending and income
+    let (total_spending_cents, total_income_cents) =
+        SpendingAggregator::get_totals(db, &start_date, &end_date, account_ids).await?;
+    let net_cents = total_income_cents - total_spending_cents;
+
+    let (mut top_categories, mut comparison) = (None, None);
+    if let Some(spending) = widget(WidgetKind::Spending) {
+        // Compare against the period of the same length ending the day before
+        let previous_end = start - chrono::Duration::days(1);
+        let previous_start = previous_end - (end - start);
+        let previous_period = DatePeriod {
+            start_date: previous_start.format("%Y-%m-%d").to_string(),
+            end_date: previous_end.format("%Y-%m-%d").to_string(),
+        };
+        let (previous_spending, previous_income) = SpendingAggregator::get_totals(
+            db,
+            &previous_period.start_date,
+            &previous_period.end_date,
+            account_ids,
+        )
+        .await?;
+        let previous_net = previous_income - previous_spending;
+        comparison = Some(PeriodComparison {
+            previous_period,
+            previous_spending_cents: previous_spending,
+            previous_income_cents: previous_income,
+            previous_net_cents: previous_net,
+            spending_change_cents: total_spending_cents - previous_spending,
+            income_change_cents: total_income_cents - previous_income,
+            net_change_cents: net_cents - previous_net,
+            spending_change_percent: percent_change(total_spending_cents, previous_spending),
+            income_change_percent: percent_change(total_income_cents, previous_income),
+        });
+
+        let limit = spending.limit.unwrap_or(DEFAULT_DASHBOARD_TOP_CATEGORIES);
+        top_categories =
+            Some(SpendingAggregator::get_top_categories(db, &start_date, &end_date, limit, account_ids).await?);
+    }
+
+    let debt_summary = match widget(WidgetKind::Debts) {
+        Some(_) => {
+            let (total_debt, total_monthly_payment) = sqlx::query_as::<_, (Cents, Cents)>(
+                "SELECT COALESCE(SUM(balance_cents), 0), COALESCE(SUM(min_payment_cents), 0) FROM debts"
+            )
+            .fetch_one(db)
+            .await
+            .map_err(|e| sanitize_db_error(e, "calculate total debt for dashboard"))?;
+            Some(DebtSummary {
+                total_debt_cents: total_debt,
+                total_monthly_payment_cents: total_monthly_payment,
+                next_payoff_date: None, // TODO: Calculate from active plan
+            })
         }
-        "current_year" => {
-            let now = chrono::Local::now().naive_local();
-            let start = format!("{}-01-01", now.year());
-            let end = now.format("%Y-%m-%d").to_string();
-            (start, end)
+        None => None,
+    };
+
+    let target_summary = match widget(WidgetKind::Targets) {
+        Some(_) => {
+            let targets = TargetTracker::get_targets_progress(db, &start_date, &end_date).await?;
+            Some(TargetSummary {
+                on_track_count: targets.targets.iter().filter(|t| t.status == "on_track").count() as i64,
+                over_count: targets.targets.iter().filter(|t| t.status == "over").count() as i64,
+                total_variance_cents: targets.targets.iter().map(|t| t.variance_cents).sum(),
+            })
         }
-        _ => return Err(format!("Invalid period: {}", period)),
+        None => None,
     };
 
-    // Get spending and income
-    let total_spending = SpendingAggregator::get_total_spending(db, &start_date, &end_date).await?;
-    let total_income = SpendingAggregator::get_total_income(db, &start_date, &end_date).await?;
-    let net = total_income - total_spending;
+    let net_worth = match widget(WidgetKind::NetWorth) {
+        Some(_) => Some(net_worth(db).await?),
+        None => None,
+    };
+
+    let upcoming_bills = match widget(WidgetKind::UpcomingBills) {
+        Some(bills) => Some(
+            upcoming_bills(
+                db,
+                today,
+                bills.days_ahead.unwrap_or(DEFAULT_UPCOMING_BILL_DAYS),
+                bills.limit.unwrap_or(DEFAULT_DASHBOARD_UPCOMING_BILLS),
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    Ok(DashboardSummary {
+        period: DatePeriod { start_date, end_date },
+        total_spending_cents,
+        total_income_cents,
+        net_cents,
+        account_ids: account_ids.map(<[i64]>::to_vec).unwrap_or_default(),
+        widgets,
+        top_categories,
+        comparison,
+        debt_summary,
+        target_summary,
+        net_worth,
+        upcoming_bills,
+    })
+}
+
+#[tauri::command]
+pub async fn get_dashboard_summary(
+    db_pool: tauri::State<'_, DbPool>,
+    cache: tauri::State<'_, AnalyticsCache>,
+    period: Option<String>,
+    custom_start: Option<String>,
+    custom_end: Option<String>,
+    account_ids: Option<Vec<i64>>,
+) -> Result<DashboardSummary, String> {
+    let db = db_pool.pool();
+    // Periods like "current_month" move with the date, so today is part of the key
+    let today = dates::today(&db).await;
+    let key = AnalyticsCache::key(
+        "dashboard_summary",
+        &(today, &period, &custom_start, &custom_end, &account_ids),
+    );
+    cache
+        .get_or_compute(key, || {
+            get_dashboard_summary_impl(
+                &db,
+                period.as_deref(),
+                custom_start.as_deref(),
+                custom_end.as_deref(),
+                account_ids.as_deref(),
+                None,
+            )
+        })
+        .await
+}
+
+/// Drop every cached analytics result, e.g. after changing the database outside the app;
+/// returns the new data version
+#[tauri::command]
+pub fn invalidate_analytics_cache(cache: tauri::State<'_, AnalyticsCache>) -> u64 {
+    cache.invalidate()
+}
+
+// get_spending_insights
+/// Same period arguments as the dashboard summary
+pub async fn get_spending_insights_impl(
+    db: &SqlitePool,
+    period: Option<&str>,
+    custom_start: Option<&str>,
+    custom_end: Option<&str>,
+) -> Result<SpendingInsights, String> {
+    let (start, end) = resolve_range(period, custom_start, custom_end, dates::today(db).await)?;
+    InsightGenerator::build(db, start, end).await
+}
+
+#[tauri::command]
+pub async fn get_spending_insights(
+    db_pool: tauri::State<'_, DbPool>,
+    period: Option<String>,
+    custom_start: Option<String>,
+    custom_end: Option<String>,
+) -> Result<SpendingInsights, String> {
+    get_spending_insights_impl(&db_pool.pool(), period.as_deref(), custom_start.as_deref(), custom_end.as_deref()).await
+}
+
+// get_category_statistics
+/// Monthly spending statistics and seasonal profile for a category over `months` completed months
+pub async fn get_category_statistics_impl(
+    db: &SqlitePool,
+    category_id: i64,
+    months: Option<u32>,
+    today: NaiveDate,
+) -> Result<CategoryStatistics, String> {
+    let months = months.unwrap_or(DEFAULT_CATEGORY_STATS_MONTHS);
+    if months == 0 || months > MAX_CATEGORY_STATS_MONTHS {
+        return Err(format!("Months must be between 1 and {}", MAX_CATEGORY_STATS_MONTHS));
+    }
+    CategoryStatisticsCalculator::calculate(db, category_id, months, today).await
+}
+
+#[tauri::command]
+pub async fn get_category_statistics(
+    db_pool: tauri::State<'_, DbPool>,
+    category_id: i64,
+    months: Option<u32>,
+) -> Result<CategoryStatistics, String> {
+    get_category_statistics_impl(&db_pool.pool(), category_id, months, dates::today(&db_pool.pool()).await).await
+}
+
+// get_financial_health
+/// Savings rate, debt-to-income ratio, and months of expenses covered, with monthly trends,
+/// over `months` completed months (12 unless asked otherwise)
+pub async fn get_financial_health_impl(
+    db: &SqlitePool,
+    months: Option<u32>,
+    today: NaiveDate,
+) -> Result<FinancialHealth, String> {
+    let months = months.unwrap_or(DEFAULT_FINANCIAL_HEALTH_MONTHS);
+    if months == 0 || months > MAX_FINANCIAL_HEALTH_MONTHS {
+        return Err(format!("Months must be between 1 and {}", MAX_FINANCIAL_HEALTH_MONTHS));
+    }
+    FinancialHealthCalculator::calculate(db, months, today).await
+}
+
+#[tauri::command]
+pub async fn get_financial_health(
+    db_pool: tauri::State<'_, DbPool>,
+    cache: tauri::State<'_, AnalyticsCache>,
+    months: Option<u32>,
+) -> Result<FinancialHealth, String> {
+    let db = db_pool.pool();
+    let today = dates::today(&db).await;
+    let key = AnalyticsCache::key("financial_health", &(months, today));
+    cache
+        .get_or_compute(key, || get_financial_health_impl(&db, months, today))
+        .await
+}
 
-    // Get top 5 categories
-    let top_categories = SpendingAggregator::get_top_categories(db, &start_date, &end_date, 5).await?;
+// get_net_worth_history
+/// Net worth on one date of the history
+#[derive(Debug, Clone, Serialize)]
+pub struct NetWorthPoint {
+    pub date: String,
+    #[serde(rename = "accounts_total", with = "money::dollars")]
+    pub accounts_total_cents: Cents,
+    #[serde(rename = "investments_total", with = "money::dollars")]
+    pub investments_total_cents: Cents,
+    #[serde(rename = "debts_total", with = "money::dollars")]
+    pub debts_total_cents: Cents,
+    #[serde(rename = "net_worth", with = "money::dollars")]
+    pub net_worth_cents: Cents,
+}
 
-    // Get debt summary
-    let total_debt = sqlx::query_as::<_, (f64,)>(
-        "SELECT COALESCE(SUM(balance), 0) FROM debts"
+/// Net worth at the end of `date`. Account balances are walked back from their current
+/// balances by the transactions dated after it; debts use the balance history, and
+/// investments the holdings and prices in effect then
+async fn net_worth_on(db: &SqlitePool, date: NaiveDate) -> Result<NetWorthPoint, String> {
+    let date = dates::format_date(date);
+    let (accounts_total_cents, debts_total_cents) = sqlx::query_as::<_, (Cents, Cents)>(
+        "SELECT
+            (SELECT COALESCE(SUM(balance_cents), 0) FROM accounts)
+              - (SELECT COALESCE(SUM(amount_cents), 0) FROM transactions WHERE date > ?1),
+            (SELECT COALESCE(SUM(COALESCE(
+                (SELECT h.balance_cents FROM debt_balance_history h
+                 WHERE h.debt_id = d.id AND h.date <= ?1
+                 ORDER BY h.date DESC, h.id DESC LIMIT 1),
+                -- Debts from before balances were recorded only have their current balance
+                CASE WHEN NOT EXISTS (SELECT 1 FROM debt_balance_history h WHERE h.debt_id = d.id) THEN d.balance_cents END,
+                0
+             )), 0) FROM debts d)"
     )
+    .bind(&date)
     .fetch_one(db)
     .await
-    .map_err(|e| sanitize_db_error(e, "calculate total debt for dashboard"))?
-    .0;
+    .map_err(|e| sanitize_db_error(e, "calculate net worth history"))?;
+    let investments_total_cents = PortfolioValuer::total_value(db, &date)
+        .await
+        .map_err(|e| sanitize_db_error(e, "value investments"))?;
+
+    Ok(NetWorthPoint {
+        date,
+        accounts_total_cents,
+        investments_total_cents,
+        debts_total_cents,
+        net_worth_cents: accounts_total_cents + investments_total_cents - debts_total_cents,
+    })
+}
+
+/// Net worth at the end of each of the last `months` months (12 unless asked otherwise),
+/// oldest first; the last point is `today` rather than the end of this month
+pub async fn get_net_worth_history_impl(
+    db: &SqlitePool,
+    months: Option<u32>,
+    today: NaiveDate,
+) -> Result<Vec<NetWorthPoint>, String> {
+    let months = months.unwrap_or(DEFAULT_NET_WORTH_HISTORY_MONTHS);
+    if months == 0 || months > MAX_NET_WORTH_HISTORY_MONTHS {
+        return Err(format!("Months must be between 1 and {}", MAX_NET_WORTH_HISTORY_MONTHS));
+    }
+
+    let this_month = dates::month_start(today);
+    let mut points = Vec::with_capacity(months as usize);
+    for back in (0..months - 1).rev() {
+        let month_end = this_month
+            .checked_sub_months(Months::new(back))
+            .and_then(|start| start.pred_opt())
+            .ok_or("Net worth history reaches past the earliest supported date")?;
+        points.push(net_worth_on(db, month_end).await?);
+    }
+    points.push(net_worth_on(db, today).await?);
+    Ok(points)
+}
+
+#[tauri::command]
+pub async fn get_net_worth_history(
+    db_pool: tauri::State<'_, DbPool>,
+    cache: tauri::State<'_, AnalyticsCache>,
+    months: Option<u32>,
+) -> Result<Vec<NetWorthPoint>, String> {
+    let db = db_pool.pool();
+    let today = dates::today(&db).await;
+    let key = AnalyticsCache::key("net_worth_history", &(months, today));
+    cache
+        .get_or_compute(key, || get_net_worth_history_impl(&db, months, today))
+        .await
+}
+
+// get_largest_transactions
+#[derive(Debug, Serialize)]
+pub struct LargestTransactionsResponse {
+    pub period: DatePeriod,
+    pub expenses: Vec<LargestTransaction>,
+    pub income: Vec<LargestTransaction>,
+}
+
+pub async fn get_largest_transactions_impl(
+    db: &SqlitePool,
+    period: &str,
+    limit: Option<i64>,
+    direction: Option<&str>,
+) -> Result<LargestTransactionsResponse, String> {
+    let (start_date, end_date) = resolve_period(period, dates::today(db).await)?;
+    let limit = limit.unwrap_or(DEFAULT_TOP_ITEMS_LIMIT).clamp(1, MAX_PAGE_SIZE);
+
+    let (include_expenses, include_income) = match direction.unwrap_or("both") {
+        "expense" => (true, false),
+        "income" => (false, true),
+        "both" => (true, true),
+        other => return Err(format!("Invalid direction: {}", other)),
+    };
+
+    let expenses = if include_expenses {
+        SpendingAggregator::get_largest_transactions(db, &start_date, &end_date, limit, true, None)
+            .await
+            .map_err(|e| sanitize_db_error(e, "load largest expenses"))?
+    } else {
+        Vec::new()
+    };
 
-    let total_monthly_payment = sqlx::query_as::<_, (f64,)>(
-        "SELECT COALESCE(SUM(min_payment), 0) FROM debts"
+    let income = if include_income {
+        SpendingAggregator::get_largest_transactions(db, &start_date, &end_date, limit, false, None)
+            .await
+            .map_err(|e| sanitize_db_error(e, "load largest income"))?
+    } else {
+        Vec::new()
+    };
+
+    Ok(LargestTransactionsResponse {
+        period: DatePeriod { start_date, end_date },
+        expenses,
+        income,
+    })
+}
+
+#[tauri::command]
+pub async fn get_largest_transactions(
+    db_pool: tauri::State<'_, DbPool>,
+    period: String,
+    limit: Option<i64>,
+    direction: Option<String>,
+) -> Result<LargestTransactionsResponse, String> {
+    get_largest_transactions_impl(&db_pool.pool(), &period, limit, direction.as_deref()).await
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountSummary {
+    pub account_id: i64,
+    pub account_name: String,
+    pub period: DatePeriod,
+    #[serde(rename = "inflows", with = "money::dollars")]
+    pub inflows_cents: Cents,
+    #[serde(rename = "outflows", with = "money::dollars")]
+    pub outflows_cents: Cents,
+    #[serde(rename = "net_change", with = "money::dollars")]
+    pub net_change_cents: Cents, // inflows - outflows
+    pub top_categories: Vec<CategorySpending>,
+    pub largest_transactions: Vec<LargestTransaction>, // Biggest outflows first
+}
+
+/// Money in and out of one account over `start_date`..=`end_date`, with where it went
+pub async fn get_account_summary_impl(
+    db: &SqlitePool,
+    account_id: i64,
+    start_date: &str,
+    end_date: &str,
+) -> Result<AccountSummary, String> {
+    let (start, end) = (dates::parse_date(start_date)?, dates::parse_date(end_date)?);
+    if start > end {
+        return Err("Start date must be on or before end date".to_string());
+    }
+    let (start_date, end_date) = (dates::format_date(start), dates::format_date(end));
+
+    let (account_name,) = sqlx::query_as::<_, (String,)>("SELECT name FROM accounts WHERE id = ?")
+        .bind(account_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load account"))?
+        .ok_or_else(|| format!("Account with id {} not found", account_id))?;
+
+    let account_ids = [account_id];
+    let (outflows_cents, inflows_cents) = SpendingAggregator::get_totals(db, &start_date, &end_date, Some(&account_ids))
+        .await
+        .map_err(|e| sanitize_db_error(e, "load account totals"))?;
+    let top_categories =
+        SpendingAggregator::get_top_categories(db, &start_date, &end_date, DEFAULT_TOP_ITEMS_LIMIT, Some(&account_ids))
+            .await
+            .map_err(|e| sanitize_db_error(e, "load account categories"))?;
+    let largest_transactions = SpendingAggregator::get_largest_transactions(
+        db,
+        &start_date,
+        &end_date,
+        DEFAULT_TOP_ITEMS_LIMIT,
+        true,
+        Some(account_id),
     )
-    .fetch_one(db)
     .await
-    .map_err(|e| sanitize_db_error(e, "calculate total debt payments for dashboard"))?
-    .0;
+    .map_err(|e| sanitize_db_error(e, "load account transactions"))?;
 
-    // Get target summary
-    let targets = TargetTracker::get_targets_progress(db, &start_date, &end_date).await?;
-    let on_track_count = targets.targets.iter().filter(|t| t.status == "on_track").count() as i64;
-    let over_count = targets.targets.iter().filter(|t| t.status == "over").count() as i64;
-    let total_variance: f64 = targets.targets.iter().map(|t| t.variance).sum();
-
-    Ok(DashboardSummary {
+    Ok(AccountSummary {
+        account_id,
+        account_name,
         period: DatePeriod { start_date, end_date },
-        total_spending,
-        total_income,
-        net,
+        inflows_cents,
+        outflows_cents,
+        net_change_cents: inflows_cents - outflows_cents,
         top_categories,
-        debt_summary: DebtSummary {
-            total_debt,
-            total_monthly_payment,
-            next_payoff_date: None, // TODO: Calculate from active plan
-        },
-        target_summary: TargetSummary {
-            on_track_count,
-            over_count,
-            total_variance,
-        },
+        largest_transactions,
     })
 }
 
 #[tauri::command]
-pub async fn get_dashboard_summary(
+pub async fn get_account_summary(
     db_pool: tauri::State<'_, DbPool>,
-    period: String,
-) -> Result<DashboardSummary, String> {
-    get_dashboard_summary_impl(&db_pool.0, &period).await
+    cache: tauri::State<'_, AnalyticsCache>,
+    account_id: i64,
+    start_date: String,
+    end_date: String,
+) -> Result<AccountSummary, String> {
+    let db = db_pool.pool();
+    let key = AnalyticsCache::key("account_summary", &(account_id, &start_date, &end_date));
+    cache
+        .get_or_compute(key, || get_account_summary_impl(&db, account_id, &start_date, &end_date))
+        .await
+}
+
+// get_amount_distribution
+/// Histogram and percentiles of the absolute amounts of transactions matching `filter`
+/// `direction` is "expense" (the default), "income" or "both"; the filter's paging is ignored
+pub async fn get_amount_distribution_impl(
+    db: &SqlitePool,
+    filter: Option<TransactionFilter>,
+    direction: Option<&str>,
+) -> Result<AmountDistribution, String> {
+    let expenses = match direction.unwrap_or("expense") {
+        "expense" => Some(true),
+        "income" => Some(false),
+        "both" => None,
+        other => return Err(format!("Invalid direction: {}", other)),
+    };
+
+    let amounts = matching_amounts(db, filter, expenses).await.map_err(|e| e.to_user_message())?;
+    Ok(amount_distribution::distribution(&amounts, &AMOUNT_DISTRIBUTION_BUCKETS_CENTS))
+}
+
+#[tauri::command]
+pub async fn get_amount_distribution(
+    db_pool: tauri::State<'_, DbPool>,
+    filter: Option<TransactionFilter>,
+    direction: Option<String>,
+) -> Result<AmountDistribution, String> {
+    get_amount_distribution_impl(&db_pool.pool(), filter, direction.as_deref()).await
+}
+
+// get_spending_by_merchant
+pub async fn get_spending_by_merchant_impl(
+    db: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+    account_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<SpendingByMerchant, String> {
+    let limit = limit.unwrap_or(DEFAULT_TOP_ITEMS_LIMIT).clamp(1, MAX_PAGE_SIZE);
+    SpendingAggregator::get_spending_by_merchant(db, start_date, end_date, account_id, limit as usize).await
+}
+
+#[tauri::command]
+pub async fn get_spending_by_merchant(
+    db_pool: tauri::State<'_, DbPool>,
+    start_date: String,
+    end_date: String,
+    account_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<SpendingByMerchant, String> {
+    get_spending_by_merchant_impl(&db_pool.pool(), &start_date, &end_date, account_id, limit).await
+}
+
+// copy_report_to_clipboard
+#[derive(Debug, Serialize)]
+pub struct ClipboardReportResponse {
+    pub text: String,
+    pub row_count: usize,
+}
+
+/// Render a report as a small markdown or TSV table for pasting elsewhere
+pub async fn render_report_text_impl(
+    db: &SqlitePool,
+    report_spec: &ReportSpec,
+    format: Option<ReportTextFormat>,
+) -> Result<ClipboardReportResponse, String> {
+    let table = ReportRenderer::build(db, report_spec)
+        .await
+        .map_err(|e| sanitize_db_error(e, "build report"))?;
+
+    Ok(ClipboardReportResponse {
+        text: ReportRenderer::render(&table, format.unwrap_or_default()),
+        row_count: table.rows.len(),
+    })
+}
+
+#[tauri::command]
+pub async fn copy_report_to_clipboard(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    report_spec: ReportSpec,
+    format: Option<ReportTextFormat>,
+) -> Result<ClipboardReportResponse, String> {
+    let response = render_report_text_impl(&db_pool.pool(), &report_spec, format).await?;
+    app.clipboard()
+        .write_text(response.text.clone())
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+
+    Ok(response)
 }
 
 // T077: export_analytics_report
@@ -296,35 +1352,26 @@ pub async fn export_analytics_report_impl(
     format: &str,
     start_date: &str,
     end_date: &str,
-    _include_charts: bool,
+    include_charts: bool,
     output_path: &str,
 ) -> Result<ExportReportResponse, String> {
     // Get analytics data
-    let spending_data = SpendingAggregator::get_spending_by_category(db, start_date, end_date, None).await?;
+    let spending_data = SpendingAggregator::get_spending_by_category(db, start_date, end_date, None, None).await?;
 
     match format {
         "pdf" => {
-            // For now, create a text-based report
-            // TODO: Implement actual PDF generation
-            let content = format!(
-                "Budget Balancer Analytics Report\n\
-                 Period: {} to {}\n\
-                 \n\
-                 Total Spending: ${:.2}\n\
-                 \n\
-                 Categories:\n",
-                start_date, end_date, spending_data.total_spending
-            );
-
-            let mut full_content = content;
-            for cat in spending_data.categories {
-                full_content.push_str(&format!(
-                    "  - {}: ${:.2} ({:.1}%)\n",
-                    cat.category_name, cat.amount, cat.percentage
-                ));
-            }
+            let trends = TrendsCalculator::get_spending_trends(db, start_date, end_date, "monthly", None, None).await?;
+            let targets = TargetTracker::get_targets_progress(db, start_date, end_date).await?;
+            let report = AnalyticsReportData {
+                start_date: start_date.to_string(),
+                end_date: end_date.to_string(),
+                spending: spending_data,
+                trends,
+                targets,
+            };
 
-            std::fs::write(output_path, full_content)
+            let bytes = PdfReport::render(&report, include_charts)?;
+            std::fs::write(output_path, bytes)
                 .map_err(|e| format!("Failed to write file: {}", e))?;
         }
         "xlsx" => {
@@ -334,7 +1381,7 @@ pub async fn export_analytics_report_impl(
             for cat in spending_data.categories {
                 content.push_str(&format!(
                     "{},{:.2},{:.1}\n",
-                    cat.category_name, cat.amount, cat.percentage
+                    cat.category_name, money::from_cents(cat.amount_cents), cat.percentage
                 ));
             }
 
@@ -364,7 +1411,7 @@ pub async fn export_analytics_report(
     output_path: String,
 ) -> Result<ExportReportResponse, String> {
     export_analytics_report_impl(
-        &db_pool.0,
+        &db_pool.pool(),
         &format,
         &start_date,
         &end_date,
@@ -373,3 +1420,80 @@ pub async fn export_analytics_report(
     )
     .await
 }
+
+// generate_tax_report
+#[derive(Debug, Serialize)]
+pub struct TaxReportResponse {
+    pub success: bool,
+    pub file_path: String,
+    pub file_size: u64,
+    pub transaction_count: i64,
+    #[serde(rename = "total", with = "money::dollars")]
+    pub total_cents: Cents,
+}
+
+/// Write the year's tax-deductible transactions, grouped by category, as "csv" or "pdf"
+pub async fn generate_tax_report_impl(
+    db: &SqlitePool,
+    year: i32,
+    format: &str,
+    output_path: &str,
+) -> Result<TaxReportResponse, String> {
+    if !(1000..=9999).contains(&year) {
+        return Err(format!("Invalid year: {}", year));
+    }
+    let report = TaxReportBuilder::build(db, year)
+        .await
+        .map_err(|e| sanitize_db_error(e, "build tax report"))?;
+
+    let bytes = match format {
+        "csv" => TaxReportBuilder::to_csv(&report)?.into_bytes(),
+        "pdf" => PdfReport::render_tax_report(&report)?,
+        _ => return Err(format!("Unsupported format: {}", format)),
+    };
+    std::fs::write(output_path, &bytes).map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(TaxReportResponse {
+        success: true,
+        file_path: output_path.to_string(),
+        file_size: bytes.len() as u64,
+        transaction_count: report.transaction_count,
+        total_cents: report.total_cents,
+    })
+}
+
+#[tauri::command]
+pub async fn generate_tax_report(
+    db_pool: tauri::State<'_, DbPool>,
+    year: i32,
+    format: String,
+    output_path: String,
+) -> Result<TaxReportResponse, String> {
+    generate_tax_report_impl(&db_pool.pool(), year, &format, &output_path).await
+}
+
+// get_budget_variance_report
+/// Budget against actual for each budgeted category over the month or quarter
+/// containing `date` (default today), with an end-of-period projection at the current run rate
+pub async fn get_budget_variance_report_impl(
+    db: &SqlitePool,
+    period: &str,
+    date: Option<&str>,
+    today: NaiveDate,
+) -> Result<BudgetVarianceReport, String> {
+    let date = match date {
+        Some(date) => dates::parse_date(date)?,
+        None => today,
+    };
+
+    VarianceReporter::build(db, period, date, today).await
+}
+
+#[tauri::command]
+pub async fn get_budget_variance_report(
+    db_pool: tauri::State<'_, DbPool>,
+    period: String,
+    date: Option<String>,
+) -> Result<BudgetVarianceReport, String> {
+    get_budget_variance_report_impl(&db_pool.pool(), &period, date.as_deref(), dates::today(&db_pool.pool()).await).await
+}
@@ -0,0 +1,67 @@
+use crate::constants::{DEFAULT_OFFSET, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::errors::AuditError;
+use crate::models::audit_log::{AuditLogEntry, AuditLogFilter};
+use crate::DbPool;
+use sqlx::SqlitePool;
+
+// Business logic functions (used by both commands and tests)
+
+/// Audit log entries newest first, optionally narrowed to one entity or action
+pub async fn list_audit_log_impl(
+    db: &SqlitePool,
+    filter: Option<AuditLogFilter>,
+) -> Result<Vec<AuditLogEntry>, AuditError> {
+    let filter = filter.unwrap_or_default();
+    let limit = filter.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = filter.offset.unwrap_or(DEFAULT_OFFSET).max(0);
+    let entity_type = filter.entity_type.map(|t| t.to_string());
+    let action = filter.action.map(|a| a.to_string());
+
+    sqlx::query_as::<_, AuditLogEntry>(
+        "SELECT id, action, entity_type, entity_id, old_values, new_values, created_at
+         FROM audit_log
+         WHERE (? IS NULL OR entity_type = ?)
+           AND (? IS NULL OR entity_id = ?)
+           AND (? IS NULL OR action = ?)
+         ORDER BY created_at DESC, id DESC
+         LIMIT ? OFFSET ?"
+    )
+    .bind(&entity_type)
+    .bind(&entity_type)
+    .bind(filter.entity_id)
+    .bind(filter.entity_id)
+    .bind(&action)
+    .bind(&action)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(db)
+    .await
+    .map_err(|e| AuditError::Database(e.to_string()))
+}
+
+/// Delete audit log entries older than `retention_days`. Runs automatically at startup
+pub async fn prune_audit_log_impl(db: &SqlitePool, retention_days: i64) -> Result<u64, AuditError> {
+    if retention_days < 0 {
+        return Err(AuditError::ValidationError("Retention days must be non-negative".to_string()));
+    }
+
+    let result = sqlx::query("DELETE FROM audit_log WHERE created_at < datetime('now', ?)")
+        .bind(format!("-{} days", retention_days))
+        .execute(db)
+        .await
+        .map_err(|e| AuditError::Database(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn list_audit_log(
+    db_pool: tauri::State<'_, DbPool>,
+    filter: Option<AuditLogFilter>,
+) -> Result<Vec<AuditLogEntry>, String> {
+    list_audit_log_impl(&db_pool.pool(), filter)
+        .await
+        .map_err(|e| e.to_user_message())
+}
@@ -0,0 +1,127 @@
+// Automatic and on-demand backups of all data to the configured backup folder
+
+use crate::commands::data_commands::export_all_data_impl;
+use crate::commands::settings_commands::get_setting_impl;
+use crate::constants::{DEFAULT_BACKUP_RETENTION, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::errors::sanitize_db_error;
+use crate::models::backup::BackupRecord;
+use crate::models::setting::SettingKey;
+use crate::services::backup_scheduler::{BackupFrequency, BackupScheduler};
+use crate::DbPool;
+use chrono::{NaiveDateTime, Utc};
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+
+/// How `backup_history.started_at` is written, matching SQLite's CURRENT_TIMESTAMP
+const STARTED_AT_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// Business logic functions (used by both commands and tests)
+
+/// Write a backup to the backup folder, delete backups beyond the retention count, and
+/// record the attempt. `kind` is "manual" or "scheduled"
+/// A failed backup is recorded too, then returned as the error
+pub async fn run_backup_impl(db: &SqlitePool, kind: &str) -> Result<BackupRecord, String> {
+    let dir = get_setting_impl(db, SettingKey::BackupDir)
+        .await?
+        .ok_or_else(|| "No backup folder is set. Choose one in settings first".to_string())?;
+    let retention = get_setting_impl(db, SettingKey::BackupRetention)
+        .await?
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_BACKUP_RETENTION);
+
+    let now = Utc::now();
+    let path = PathBuf::from(&dir).join(BackupScheduler::file_name(now));
+    let outcome = match export_all_data_impl(db, &path.display().to_string()).await {
+        Ok(_) => {
+            let size = std::fs::metadata(&path).map(|m| m.len() as i64).ok();
+            // A backup that was written still counts when old ones can't be cleaned up
+            let pruned = BackupScheduler::prune(&PathBuf::from(&dir), retention).unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Failed to prune old backups");
+                Vec::new()
+            });
+            Ok((size, pruned.len() as i64))
+        }
+        Err(e) => Err(e.to_user_message()),
+    };
+
+    let (status, file_path, size_bytes, pruned_count, error) = match &outcome {
+        Ok((size, pruned)) => ("succeeded", Some(path.display().to_string()), *size, *pruned, None),
+        Err(e) => ("failed", None, None, 0, Some(e.clone())),
+    };
+    let record = sqlx::query_as::<_, BackupRecord>(
+        "INSERT INTO backup_history (started_at, kind, status, file_path, size_bytes, pruned_count, error)
+         VALUES (?, ?, ?, ?, ?, ?, ?)
+         RETURNING id, started_at, kind, status, file_path, size_bytes, pruned_count, error"
+    )
+    .bind(now.format(STARTED_AT_FORMAT).to_string())
+    .bind(kind)
+    .bind(status)
+    .bind(file_path)
+    .bind(size_bytes)
+    .bind(pruned_count)
+    .bind(&error)
+    .fetch_one(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "record backup"))?;
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(record),
+    }
+}
+
+/// Take a backup if automatic backups are on and the last successful one is older than
+/// the backup frequency. Returns None when no backup was due
+pub async fn run_scheduled_backup_impl(db: &SqlitePool) -> Result<Option<BackupRecord>, String> {
+    let Some(frequency) = get_setting_impl(db, SettingKey::BackupFrequency).await? else {
+        return Ok(None);
+    };
+    let frequency: BackupFrequency = frequency.parse()?;
+    if get_setting_impl(db, SettingKey::BackupDir).await?.is_none() {
+        return Ok(None);
+    }
+
+    let last: Option<String> = sqlx::query_scalar(
+        "SELECT MAX(started_at) FROM backup_history WHERE status = 'succeeded'"
+    )
+    .fetch_one(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "load backup history"))?;
+    let last = last
+        .and_then(|at| NaiveDateTime::parse_from_str(&at, STARTED_AT_FORMAT).ok())
+        .map(|at| at.and_utc());
+
+    if !frequency.is_due(last, Utc::now()) {
+        return Ok(None);
+    }
+    run_backup_impl(db, "scheduled").await.map(Some)
+}
+
+/// Backup attempts, newest first
+pub async fn list_backup_history_impl(db: &SqlitePool, limit: Option<i64>) -> Result<Vec<BackupRecord>, String> {
+    sqlx::query_as::<_, BackupRecord>(
+        "SELECT id, started_at, kind, status, file_path, size_bytes, pruned_count, error
+         FROM backup_history
+         ORDER BY started_at DESC, id DESC
+         LIMIT ?"
+    )
+    .bind(limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE))
+    .fetch_all(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "load backup history"))
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn run_backup_now(db_pool: tauri::State<'_, DbPool>) -> Result<BackupRecord, String> {
+    run_backup_impl(&db_pool.pool(), "manual").await
+}
+
+#[tauri::command]
+pub async fn list_backup_history(
+    db_pool: tauri::State<'_, DbPool>,
+    limit: Option<i64>,
+) -> Result<Vec<BackupRecord>, String> {
+    list_backup_history_impl(&db_pool.pool(), limit).await
+}
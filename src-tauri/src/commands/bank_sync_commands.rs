@@ -0,0 +1,358 @@
+use crate::commands::csv_commands::notify_import_changes;
+use crate::errors::BankSyncError;
+use crate::models::bank_sync::{AccountSyncResult, BankAccountLink, BankConnection, SyncSummary};
+use crate::services::bank_sync::{self, BankSync, BankSyncProvider, CredentialStore, SIMPLEFIN};
+use crate::services::transaction_importer::TransactionImporter;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates::{self, UserTimezone};
+use crate::DbPool;
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+/// Provider calls block on the network, so they run on the blocking thread pool
+async fn run_blocking<T, F>(call: F) -> Result<T, BankSyncError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, BankSyncError> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(call)
+        .await
+        .map_err(|e| BankSyncError::Network(e.to_string()))?
+}
+
+async fn links_for(db: &SqlitePool, connection_id: i64) -> Result<Vec<BankAccountLink>, BankSyncError> {
+    sqlx::query_as::<_, BankAccountLink>(
+        "SELECT id, connection_id, remote_account_id, remote_name, institution, remote_balance_cents, account_id, last_synced_at
+         FROM bank_account_links WHERE connection_id = ? ORDER BY remote_name, id"
+    )
+    .bind(connection_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| BankSyncError::Database(e.to_string()))
+}
+
+// Business logic functions (used by both commands and tests)
+
+pub async fn get_bank_connection_impl(db: &SqlitePool, connection_id: i64) -> Result<BankConnection, BankSyncError> {
+    let mut connection = sqlx::query_as::<_, BankConnection>(
+        "SELECT id, provider, name, last_synced_at, last_error, created_at, updated_at
+         FROM bank_connections WHERE id = ?"
+    )
+    .bind(connection_id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| BankSyncError::Database(e.to_string()))?
+    .ok_or(BankSyncError::ConnectionNotFound(connection_id))?;
+
+    connection.accounts = links_for(db, connection_id).await?;
+    Ok(connection)
+}
+
+pub async fn list_bank_connections_impl(db: &SqlitePool) -> Result<Vec<BankConnection>, BankSyncError> {
+    let mut connections = sqlx::query_as::<_, BankConnection>(
+        "SELECT id, provider, name, last_synced_at, last_error, created_at, updated_at
+         FROM bank_connections ORDER BY name, id"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| BankSyncError::Database(e.to_string()))?;
+
+    for connection in &mut connections {
+        connection.accounts = links_for(db, connection.id).await?;
+    }
+    Ok(connections)
+}
+
+/// Claim `setup_token` with the provider and save the resulting credential
+/// Setup tokens only work once, so a failure after claiming means asking the provider for a new one
+pub async fn link_bank_connection_impl(
+    db: &SqlitePool,
+    store: &CredentialStore,
+    provider: Arc<dyn BankSyncProvider>,
+    name: &str,
+    setup_token: String,
+) -> Result<BankConnection, BankSyncError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(BankSyncError::EmptyName);
+    }
+
+    let claimer = provider.clone();
+    let credential = run_blocking(move || claimer.claim(&setup_token)).await?;
+
+    let connection_id = sqlx::query("INSERT INTO bank_connections (provider, name) VALUES (?, ?)")
+        .bind(provider.name())
+        .bind(name)
+        .execute(db)
+        .await
+        .map_err(|e| BankSyncError::Database(e.to_string()))?
+        .last_insert_rowid();
+
+    if let Err(e) = store.save(connection_id, &credential) {
+        // A connection without its credential could never sync
+        if let Err(cleanup) = sqlx::query("DELETE FROM bank_connections WHERE id = ?")
+            .bind(connection_id)
+            .execute(db)
+            .await
+        {
+            tracing::warn!(error = %cleanup, connection_id, "Failed to remove bank connection after credential error");
+        }
+        return Err(e);
+    }
+
+    get_bank_connection_impl(db, connection_id).await
+}
+
+/// Send a remote account's transactions to a local account, or stop importing it with None
+pub async fn map_bank_account_impl(
+    db: &SqlitePool,
+    link_id: i64,
+    account_id: Option<i64>,
+) -> Result<BankAccountLink, BankSyncError> {
+    if let Some(account_id) = account_id {
+        let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM accounts WHERE id = ?")
+            .bind(account_id)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| BankSyncError::Database(e.to_string()))?;
+        if exists.is_none() {
+            return Err(BankSyncError::AccountNotFound(account_id));
+        }
+    }
+
+    sqlx::query_as::<_, BankAccountLink>(
+        "UPDATE bank_account_links SET account_id = ? WHERE id = ?
+         RETURNING id, connection_id, remote_account_id, remote_name, institution, remote_balance_cents, account_id, last_synced_at"
+    )
+    .bind(account_id)
+    .bind(link_id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| BankSyncError::Database(e.to_string()))?
+    .ok_or(BankSyncError::LinkNotFound(link_id))
+}
+
+/// Fetch new transactions for every mapped account on a connection and import them through the
+/// CSV import pipeline: amount checks, duplicate detection, categorization rules and an undoable
+/// import batch per account. Remote accounts seen for the first time are recorded unmapped
+pub async fn sync_accounts_impl(
+    db: &SqlitePool,
+    store: &CredentialStore,
+    provider: Arc<dyn BankSyncProvider>,
+    connection_id: i64,
+    today: NaiveDate,
+) -> Result<SyncSummary, BankSyncError> {
+    let connection = get_bank_connection_impl(db, connection_id).await?;
+    let credential = store.load(connection_id)?;
+
+    // Far enough back for the account synced longest ago; a newly mapped account gets full history
+    let start_date = connection
+        .accounts
+        .iter()
+        .filter(|link| link.account_id.is_some())
+        .map(|link| BankSync::start_date(link.last_synced_at.as_deref(), today))
+        .min()
+        .unwrap_or_else(|| BankSync::start_date(connection.last_synced_at.as_deref(), today));
+
+    let result = sync_connection(db, provider, credential, connection_id, start_date).await;
+    let last_error = result.as_ref().err().map(|e| e.to_user_message());
+    sqlx::query(
+        "UPDATE bank_connections
+         SET last_synced_at = CASE WHEN ? IS NULL THEN CURRENT_TIMESTAMP ELSE last_synced_at END,
+             last_error = ?, updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?"
+    )
+    .bind(&last_error)
+    .bind(&last_error)
+    .bind(connection_id)
+    .execute(db)
+    .await
+    .map_err(|e| BankSyncError::Database(e.to_string()))?;
+
+    result
+}
+
+async fn sync_connection(
+    db: &SqlitePool,
+    provider: Arc<dyn BankSyncProvider>,
+    credential: String,
+    connection_id: i64,
+    start_date: NaiveDate,
+) -> Result<SyncSummary, BankSyncError> {
+    let data = run_blocking(move || provider.fetch(&credential, start_date)).await?;
+    let timezone = UserTimezone::load(db).await;
+
+    let mut summary = SyncSummary {
+        connection_id,
+        start_date: start_date.format("%Y-%m-%d").to_string(),
+        accounts: Vec::with_capacity(data.accounts.len()),
+        imported: 0,
+        duplicates: 0,
+        provider_messages: data.messages,
+    };
+
+    for remote in data.accounts {
+        let link = sqlx::query_as::<_, BankAccountLink>(
+            "INSERT INTO bank_account_links (connection_id, remote_account_id, remote_name, institution, remote_balance_cents)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(connection_id, remote_account_id) DO UPDATE SET
+                remote_name = excluded.remote_name,
+                institution = excluded.institution,
+                remote_balance_cents = excluded.remote_balance_cents
+             RETURNING id, connection_id, remote_account_id, remote_name, institution, remote_balance_cents, account_id, last_synced_at"
+        )
+        .bind(connection_id)
+        .bind(&remote.id)
+        .bind(&remote.name)
+        .bind(&remote.institution)
+        .bind(remote.balance_cents)
+        .fetch_one(db)
+        .await
+        .map_err(|e| BankSyncError::Database(e.to_string()))?;
+
+        // Pending transactions change when they post, so they wait for a later sync
+        let transactions: Vec<_> = remote
+            .transactions
+            .iter()
+            .filter(|t| !t.pending)
+            .map(|t| BankSync::to_parsed(t, &timezone))
+            .collect();
+        let mut result = AccountSyncResult {
+            link_id: link.id,
+            remote_name: link.remote_name,
+            account_id: link.account_id,
+            fetched: transactions.len(),
+            imported: 0,
+            duplicates: 0,
+            locked: 0,
+            errors: 0,
+            import_batch_id: None,
+        };
+
+        if let Some(account_id) = link.account_id {
+            if !transactions.is_empty() {
+                let stats = TransactionImporter::import_transactions(db, account_id, transactions, false, |_| {
+                    ControlFlow::Continue(())
+                })
+                .await
+                .map_err(|e| BankSyncError::Import(e.to_string()))?;
+                result.imported = stats.imported;
+                result.duplicates = stats.duplicates;
+                result.locked = stats.locked;
+                result.errors = stats.errors;
+                result.import_batch_id = Some(stats.import_batch_id);
+            }
+
+            sqlx::query("UPDATE bank_account_links SET last_synced_at = CURRENT_TIMESTAMP WHERE id = ?")
+                .bind(link.id)
+                .execute(db)
+                .await
+                .map_err(|e| BankSyncError::Database(e.to_string()))?;
+        }
+
+        summary.imported += result.imported;
+        summary.duplicates += result.duplicates;
+        summary.accounts.push(result);
+    }
+
+    Ok(summary)
+}
+
+/// Forget a connection and its saved credential; transactions it imported stay
+pub async fn remove_bank_connection_impl(
+    db: &SqlitePool,
+    store: &CredentialStore,
+    connection_id: i64,
+) -> Result<(), BankSyncError> {
+    let result = sqlx::query("DELETE FROM bank_connections WHERE id = ?")
+        .bind(connection_id)
+        .execute(db)
+        .await
+        .map_err(|e| BankSyncError::Database(e.to_string()))?;
+    if result.rows_affected() == 0 {
+        return Err(BankSyncError::ConnectionNotFound(connection_id));
+    }
+
+    store.remove(connection_id)
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn list_bank_connections(db_pool: tauri::State<'_, DbPool>) -> Result<Vec<BankConnection>, String> {
+    list_bank_connections_impl(&db_pool.pool())
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+/// Link a SimpleFIN Bridge connection from the setup token the Bridge gives out
+#[tauri::command]
+pub async fn link_bank_connection(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    name: String,
+    setup_token: String,
+) -> Result<BankConnection, String> {
+    let result = async {
+        let provider = bank_sync::provider(SIMPLEFIN)?;
+        let store = CredentialStore::default_location()?;
+        link_bank_connection_impl(&db_pool.pool(), &store, provider, &name, setup_token).await
+    }
+    .await
+    .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::BankConnections, ChangeAction::Created, [result.id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn map_bank_account(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    link_id: i64,
+    account_id: Option<i64>,
+) -> Result<BankAccountLink, String> {
+    let result = map_bank_account_impl(&db_pool.pool(), link_id, account_id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::BankConnections, ChangeAction::Updated, [result.connection_id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn sync_accounts(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    connection_id: i64,
+) -> Result<SyncSummary, String> {
+    let result = async {
+        let connection = get_bank_connection_impl(&db_pool.pool(), connection_id).await?;
+        let provider = bank_sync::provider(&connection.provider)?;
+        let store = CredentialStore::default_location()?;
+        sync_accounts_impl(&db_pool.pool(), &store, provider, connection_id, dates::today(&db_pool.pool()).await).await
+    }
+    .await;
+
+    // The connection's last sync or last error changed either way
+    notify_changed(&app, DataKind::BankConnections, ChangeAction::Updated, [connection_id]);
+    let summary = result.map_err(|e| e.to_user_message())?;
+    for import_batch_id in summary.accounts.iter().filter_map(|account| account.import_batch_id) {
+        notify_import_changes(&app, import_batch_id);
+    }
+    Ok(summary)
+}
+
+#[tauri::command]
+pub async fn remove_bank_connection(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    connection_id: i64,
+) -> Result<(), String> {
+    let store = CredentialStore::default_location().map_err(|e| e.to_user_message())?;
+    remove_bank_connection_impl(&db_pool.pool(), &store, connection_id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::BankConnections, ChangeAction::Deleted, [connection_id]);
+    Ok(())
+}
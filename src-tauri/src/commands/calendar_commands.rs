@@ -0,0 +1,145 @@
+use crate::commands::debt_commands::get_upcoming_payments_impl;
+use crate::commands::scheduled_transaction_commands::list_scheduled_transactions_impl;
+use crate::constants::DEFAULT_CALENDAR_EXPORT_MONTHS;
+use crate::models::scheduled_transaction::Recurrence;
+use crate::services::ical_writer::{IcalEvent, IcalWriter};
+use crate::services::transaction_scheduler::Schedule;
+use crate::utils::dates;
+use crate::utils::money::{self, Cents};
+use crate::DbPool;
+use chrono::{Months, NaiveDate};
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+#[derive(Debug, Serialize)]
+pub struct CalendarExportResult {
+    pub success: bool,
+    pub file_path: String,
+    pub event_count: usize,
+}
+
+fn dollars(amount: Cents) -> String {
+    format!("${:.2}", money::from_cents(amount))
+}
+
+/// Upcoming debt due dates, scheduled transactions, and the start of each spending
+/// target period from `today` through `months_ahead` months out, in date order
+async fn build_calendar_events(
+    db: &SqlitePool,
+    months_ahead: u32,
+    today: NaiveDate,
+) -> Result<Vec<IcalEvent>, String> {
+    let end = today.checked_add_months(Months::new(months_ahead)).unwrap_or(today);
+    let mut events = Vec::new();
+
+    for payment in get_upcoming_payments_impl(db, months_ahead, today)
+        .await
+        .map_err(|e| e.to_user_message())?
+        .into_iter()
+        .flat_map(|schedule| schedule.payments)
+    {
+        let Some(date) = dates::parse_date(&payment.due_date).ok() else {
+            continue;
+        };
+        events.push(IcalEvent {
+            uid: format!("debt-{}-{}@budget-balancer", payment.debt_id, payment.due_date),
+            date,
+            summary: format!("{} payment due: {}", payment.debt_name, dollars(payment.amount_cents)),
+            description: Some(format!("Minimum payment of {} on {}", dollars(payment.amount_cents), payment.debt_name)),
+            category: "Debt payment".to_string(),
+        });
+    }
+
+    // Overdue items waiting for confirmation stay on the calendar until they're posted
+    for item in list_scheduled_transactions_impl(db).await.map_err(|e| e.to_user_message())? {
+        let (Some(schedule), Some(next_due)) = (Schedule::of(&item), item.next_due_date.as_deref().and_then(|date| dates::parse_date(date).ok()))
+        else {
+            continue;
+        };
+        let kind = if item.amount_cents < 0 { "Bill" } else { "Income" };
+        for date in schedule.occurrences_between(next_due, end.pred_opt().unwrap_or(end)) {
+            events.push(IcalEvent {
+                uid: format!("scheduled-{}-{}@budget-balancer", item.id, date),
+                date,
+                summary: format!("{}: {} {}", kind, item.description, dollars(item.amount_cents.abs())),
+                description: item.merchant.clone(),
+                category: kind.to_string(),
+            });
+        }
+    }
+
+    let targets: Vec<(i64, String, Cents, String, String, Option<String>)> = sqlx::query_as(
+        "SELECT st.id, COALESCE(c.name, a.name, 'overall'), st.amount_cents, st.period, st.start_date, st.end_date
+         FROM spending_targets st
+         LEFT JOIN categories c ON c.id = st.category_id
+         LEFT JOIN accounts a ON a.id = st.account_id
+         WHERE st.start_date < ? AND (st.end_date IS NULL OR st.end_date >= ?)
+         ORDER BY st.id"
+    )
+    .bind(end.format("%Y-%m-%d").to_string())
+    .bind(today.format("%Y-%m-%d").to_string())
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to load spending targets: {}", e))?;
+
+    for (target_id, target_name, amount, period, start_date, end_date) in targets {
+        let Ok(recurrence) = period.parse::<Recurrence>() else {
+            continue;
+        };
+        let Some(start) = dates::parse_date(&start_date).ok() else {
+            continue;
+        };
+        // A target's periods run back to back from its start date
+        let schedule = Schedule {
+            recurrence,
+            start,
+            end: end_date.as_deref().and_then(|date| dates::parse_date(date).ok()),
+        };
+        for date in schedule.occurrences_between(today, end.pred_opt().unwrap_or(end)) {
+            events.push(IcalEvent {
+                uid: format!("target-{}-{}@budget-balancer", target_id, date),
+                date,
+                summary: format!("New {} {} target period: {}", period, target_name, dollars(amount)),
+                description: None,
+                category: "Spending target".to_string(),
+            });
+        }
+    }
+
+    events.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.summary.cmp(&b.summary)));
+    Ok(events)
+}
+
+// Business logic functions (used by both commands and tests)
+
+/// Write upcoming payments, bills, and target periods to an .ics file at `output_path`
+/// for import into a calendar app. Covers `months_ahead` months (default 6) from `today`
+pub async fn export_payment_calendar_impl(
+    db: &SqlitePool,
+    output_path: &str,
+    months_ahead: Option<u32>,
+    today: NaiveDate,
+) -> Result<CalendarExportResult, String> {
+    let months_ahead = months_ahead.unwrap_or(DEFAULT_CALENDAR_EXPORT_MONTHS);
+    let events = build_calendar_events(db, months_ahead, today).await?;
+    let ics = IcalWriter::write("Budget Balancer", &events, chrono::Utc::now());
+
+    std::fs::write(output_path, ics).map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(CalendarExportResult {
+        success: true,
+        file_path: output_path.to_string(),
+        event_count: events.len(),
+    })
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn export_payment_calendar(
+    db_pool: tauri::State<'_, DbPool>,
+    output_path: String,
+    months_ahead: Option<u32>,
+) -> Result<CalendarExportResult, String> {
+    export_payment_calendar_impl(&db_pool.pool(), &output_path, months_ahead, dates::today(&db_pool.pool()).await).await
+}
@@ -0,0 +1,41 @@
+use crate::constants::{DEFAULT_PROJECTION_DAYS, MAX_PROJECTION_DAYS};
+use crate::models::cash_flow::CashFlowProjection;
+use crate::services::cash_flow_projector::CashFlowProjector;
+use crate::utils::dates;
+use crate::DbPool;
+use sqlx::SqlitePool;
+
+// Business logic functions (used by both commands and tests)
+
+/// Account balances over the next `days` (default 90) from `today`, with autopay debts
+/// debited on their due dates and a warning wherever the funding account comes up short
+pub async fn get_cash_flow_projection_impl(
+    db: &SqlitePool,
+    days: Option<i64>,
+    today: Option<&str>,
+) -> Result<CashFlowProjection, String> {
+    let days = days.unwrap_or(DEFAULT_PROJECTION_DAYS);
+    if !(1..=MAX_PROJECTION_DAYS).contains(&days) {
+        return Err(format!("Projection must cover 1 to {} days", MAX_PROJECTION_DAYS));
+    }
+    let today = match today {
+        Some(date) => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date: {}", date))?,
+        None => dates::today(db).await,
+    };
+    let end = today + chrono::Duration::days(days - 1);
+
+    CashFlowProjector::build(db, today, end)
+        .await
+        .map_err(|e| format!("Failed to project cash flow: {}", e))
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn get_cash_flow_projection(
+    db_pool: tauri::State<'_, DbPool>,
+    days: Option<i64>,
+) -> Result<CashFlowProjection, String> {
+    get_cash_flow_projection_impl(&db_pool.pool(), days, None).await
+}
@@ -1,46 +1,1424 @@
-use crate::errors::sanitize_db_error;
-use crate::models::category::{Category, NewCategory};
+use crate::constants::{
+    DEFAULT_CATEGORY_ID, DEFAULT_PAGE_SIZE, DEFAULT_RULE_CONFLICT_EXAMPLES, DEFAULT_STALE_RULE_DAYS,
+    MAX_CATEGORY_ICON_LENGTH, MAX_CATEGORY_NAME_LENGTH, MAX_CATEGORY_TEMPLATE_SIZE, MAX_PAGE_SIZE, MAX_RULE_PATTERN_LENGTH, MONTHS_PER_YEAR,
+};
+use crate::errors::{sanitize_db_error, CategoryError};
+use crate::models::audit_log::AuditEntity;
+use crate::models::category::{Category, CategoryType, NewCategory, UpdateCategory};
+use crate::models::category_rule::{CategoryRule, NewCategoryRule, RuleMatchType, RuleStatistics};
+use crate::models::category_template::{
+    BudgetTemplateInfo, BudgetTemplateSource, CategoryReseedResult, CategoryTemplate, CategoryTemplateImportResult,
+    ConflictResolution, TemplateBudget, TemplateCategory, TemplateRule, CATEGORY_TEMPLATE_VERSION,
+};
+use crate::services::audit_log::AuditRecord;
+use crate::services::budget_templates::BudgetTemplates;
+use crate::services::categorizer::{Categorizer, CompiledRule};
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates;
+use crate::utils::money;
+use crate::utils::query_builder::UpdateBuilder;
 use crate::DbPool;
-use sqlx::SqlitePool;
+use serde::Serialize;
+use serde_json::json;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::BTreeMap;
 
 // Business logic functions (used by both commands and tests)
 
-pub async fn list_categories_impl(db: &SqlitePool) -> Result<Vec<Category>, String> {
+/// List categories by name; archived categories are left out unless `include_archived`
+pub async fn list_categories_impl(db: &SqlitePool, include_archived: bool) -> Result<Vec<Category>, String> {
     sqlx::query_as::<_, Category>(
-        "SELECT id, name, type, parent_id, icon, created_at FROM categories ORDER BY name"
+        "SELECT id, name, type, parent_id, icon, color, archived, tax_deductible, created_at FROM categories
+         WHERE archived = 0 OR ? ORDER BY name"
     )
+    .bind(include_archived)
     .fetch_all(db)
     .await
     .map_err(|e| sanitize_db_error(e, "load categories"))
 }
 
+/// Accepts hex colors in `#RGB` or `#RRGGBB` form
+pub(crate) fn is_valid_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| (hex.len() == 3 || hex.len() == 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
 pub async fn create_category_impl(
     db: &SqlitePool,
     category: NewCategory,
 ) -> Result<i64, String> {
+    if let Some(color) = &category.color {
+        if !is_valid_color(color) {
+            return Err(format!("Invalid color '{}': expected a hex color like #4CAF50", color));
+        }
+    }
+
     let result = sqlx::query(
-        "INSERT INTO categories (name, type, icon) VALUES (?, 'custom', ?)"
+        "INSERT INTO categories (name, type, icon, color) VALUES (?, 'custom', ?, ?)"
     )
     .bind(&category.name)
     .bind(&category.icon)
+    .bind(&category.color)
     .execute(db)
     .await
     .map_err(|e| sanitize_db_error(e, "create category"))?;
+    let category_id = result.last_insert_rowid();
+
+    AuditRecord::created(AuditEntity::Category, category_id)
+        .new_values(&category)
+        .record(db)
+        .await;
+
+    Ok(category_id)
+}
+
+/// Rename or restyle a category, predefined ones included
+/// Names stay unique ignoring case; a category's type can't be changed, and categories
+/// are never deleted (archive them instead)
+pub async fn update_category_impl(db: &SqlitePool, update: UpdateCategory) -> Result<Category, CategoryError> {
+    let fetch = || {
+        sqlx::query_as::<_, Category>(
+            "SELECT id, name, type, parent_id, icon, color, archived, tax_deductible, created_at FROM categories WHERE id = ?"
+        )
+        .bind(update.id)
+    };
+    let current = fetch()
+        .fetch_optional(db)
+        .await
+        .map_err(|e| CategoryError::Database(e.to_string()))?
+        .ok_or(CategoryError::NotFound(update.id))?;
+
+    if let Some(category_type) = update.category_type {
+        if category_type.to_string() != current.category_type {
+            return Err(CategoryError::ValidationError(format!(
+                "A {} category can't be changed to {}",
+                current.category_type, category_type
+            )));
+        }
+    }
+
+    let name = update.name.as_deref().map(str::trim);
+    if let Some(name) = name {
+        if name.is_empty() {
+            return Err(CategoryError::ValidationError("Category name cannot be empty".to_string()));
+        }
+        if name.chars().count() > MAX_CATEGORY_NAME_LENGTH {
+            return Err(CategoryError::ValidationError(format!(
+                "Category name too long (max {} characters)",
+                MAX_CATEGORY_NAME_LENGTH
+            )));
+        }
+        let (taken,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM categories WHERE name = ? COLLATE NOCASE AND id != ?)"
+        )
+        .bind(name)
+        .bind(update.id)
+        .fetch_one(db)
+        .await
+        .map_err(|e| CategoryError::Database(e.to_string()))?;
+        if taken {
+            return Err(CategoryError::ValidationError(format!("A category named '{}' already exists", name)));
+        }
+    }
+
+    // An empty icon or color clears it
+    let icon = update.icon.as_deref().map(str::trim).map(|icon| (!icon.is_empty()).then_some(icon));
+    if let Some(Some(icon)) = icon {
+        if icon.chars().count() > MAX_CATEGORY_ICON_LENGTH {
+            return Err(CategoryError::ValidationError(format!(
+                "Category icon too long (max {} characters)",
+                MAX_CATEGORY_ICON_LENGTH
+            )));
+        }
+    }
+    let color = update.color.as_deref().map(str::trim).map(|color| (!color.is_empty()).then_some(color));
+    if let Some(Some(color)) = color {
+        if !is_valid_color(color) {
+            return Err(CategoryError::ValidationError(format!(
+                "Invalid color '{}': expected a hex color like #4CAF50",
+                color
+            )));
+        }
+    }
+
+    // Column names are fixed in the builder; only values come from the caller
+    let builder = UpdateBuilder::new("categories")
+        .set_if("name", name)
+        .set_if("icon", icon)
+        .set_if("color", color);
+    if builder.is_empty() {
+        return Err(CategoryError::ValidationError("At least one field must be provided for update".to_string()));
+    }
+    builder
+        .execute(db, update.id)
+        .await
+        .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+    let updated = fetch()
+        .fetch_one(db)
+        .await
+        .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+    AuditRecord::updated(AuditEntity::Category, update.id)
+        .old_values(&current)
+        .new_values(&updated)
+        .record(db)
+        .await;
+
+    Ok(updated)
+}
+
+/// Set one of a category's on/off flags: `archived` or `tax_deductible`
+async fn set_category_flag(
+    conn: &mut SqliteConnection,
+    category_id: i64,
+    flag: &'static str,
+    value: bool,
+) -> Result<Category, CategoryError> {
+    let fetch = || {
+        sqlx::query_as::<_, Category>(
+            "SELECT id, name, type, parent_id, icon, color, archived, tax_deductible, created_at FROM categories WHERE id = ?"
+        )
+        .bind(category_id)
+    };
+
+    let current = fetch()
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| CategoryError::Database(e.to_string()))?
+        .ok_or(CategoryError::NotFound(category_id))?;
+
+    sqlx::query(&format!("UPDATE categories SET {} = ? WHERE id = ?", flag))
+        .bind(value)
+        .bind(category_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+    let updated = fetch()
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+    AuditRecord::updated(AuditEntity::Category, category_id)
+        .old_values(&current)
+        .new_values(&updated)
+        .record(&mut *conn)
+        .await;
+
+    Ok(updated)
+}
+
+/// Set a category flag in a transaction of its own
+async fn set_category_flag_now(
+    db: &SqlitePool,
+    category_id: i64,
+    flag: &'static str,
+    value: bool,
+) -> Result<Category, CategoryError> {
+    let mut tx = db.begin().await.map_err(|e| CategoryError::Database(e.to_string()))?;
+    let updated = set_category_flag(&mut tx, category_id, flag, value).await?;
+    tx.commit().await.map_err(|e| CategoryError::Database(e.to_string()))?;
+    Ok(updated)
+}
+
+/// Archive (retire) a category; transactions and analytics keep it
+pub async fn archive_category_impl(db: &SqlitePool, category_id: i64) -> Result<Category, CategoryError> {
+    set_category_flag_now(db, category_id, "archived", true).await
+}
+
+pub async fn unarchive_category_impl(db: &SqlitePool, category_id: i64) -> Result<Category, CategoryError> {
+    set_category_flag_now(db, category_id, "archived", false).await
+}
+
+/// Flag a category as tax-deductible, so its transactions go in the tax report unless
+/// a transaction overrides it
+pub async fn set_category_tax_deductible_impl(
+    db: &SqlitePool,
+    category_id: i64,
+    tax_deductible: bool,
+) -> Result<Category, CategoryError> {
+    set_category_flag_now(db, category_id, "tax_deductible", tax_deductible).await
+}
+
+pub async fn list_category_rules_impl(
+    db: &SqlitePool,
+    category_id: Option<i64>,
+) -> Result<Vec<CategoryRule>, CategoryError> {
+    sqlx::query_as::<_, CategoryRule>(
+        "SELECT id, pattern, category_id, priority, match_type, min_amount_cents, max_amount_cents, account_id, created_at
+         FROM category_rules
+         WHERE (? IS NULL OR category_id = ?)
+         ORDER BY priority DESC, created_at ASC, id ASC"
+    )
+    .bind(category_id)
+    .bind(category_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| CategoryError::Database(e.to_string()))
+}
+
+/// Every rule with how often it has matched, most used first; rules that never matched come last
+/// A rule is stale when it hasn't matched in `stale_after_days` days (180 unless asked otherwise)
+pub async fn get_rule_statistics_impl(
+    db: &SqlitePool,
+    stale_after_days: Option<i64>,
+) -> Result<Vec<RuleStatistics>, CategoryError> {
+    let stale_after_days = stale_after_days.unwrap_or(DEFAULT_STALE_RULE_DAYS);
+    if stale_after_days < 1 {
+        return Err(CategoryError::ValidationError("Stale rule window must be at least 1 day".to_string()));
+    }
+    let cutoff = dates::format_date(dates::today(db).await - chrono::Duration::days(stale_after_days));
+
+    let mut statistics = sqlx::query_as::<_, RuleStatistics>(
+        "SELECT r.id AS rule_id, r.pattern, r.match_type, r.category_id, c.name AS category_name, r.priority,
+                r.account_id, r.match_count, r.last_matched_at, r.created_at
+         FROM category_rules r
+         JOIN categories c ON c.id = r.category_id
+         ORDER BY r.match_count DESC, r.last_matched_at DESC, r.priority DESC, r.id ASC"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+    for rule in &mut statistics {
+        rule.never_matched = rule.match_count == 0;
+        // Timestamps start with the date, so they compare against it as text
+        rule.stale = rule.last_matched_at.as_deref().is_none_or(|at| at < cutoff.as_str());
+    }
+    Ok(statistics)
+}
+
+/// Validate a rule's pattern and amount bounds, returning its normalized pattern
+pub(crate) fn validate_rule_definition(rule: &NewCategoryRule) -> Result<String, CategoryError> {
+    let pattern = rule.pattern.trim();
+    if pattern.is_empty() {
+        return Err(CategoryError::ValidationError("Pattern cannot be empty".to_string()));
+    }
+    if pattern.len() > MAX_RULE_PATTERN_LENGTH {
+        return Err(CategoryError::ValidationError(format!(
+            "Pattern too long (max {} characters)",
+            MAX_RULE_PATTERN_LENGTH
+        )));
+    }
+
+    // Regex rules must compile; other rule types are stored lowercased for matching
+    let pattern = match rule.match_type {
+        RuleMatchType::Regex => {
+            Categorizer::compile_regex(pattern)
+                .map_err(|e| CategoryError::InvalidPattern(e.to_string()))?;
+            pattern.to_string()
+        }
+        _ => pattern.to_lowercase(),
+    };
+
+    for amount in [rule.min_amount_cents, rule.max_amount_cents].into_iter().flatten() {
+        if amount < 0 {
+            return Err(CategoryError::ValidationError(format!(
+                "Amount bounds must be non-negative, got {}",
+                money::from_cents(amount)
+            )));
+        }
+    }
+    if let (Some(min), Some(max)) = (rule.min_amount_cents, rule.max_amount_cents) {
+        if min > max {
+            return Err(CategoryError::ValidationError(format!(
+                "Minimum amount {} exceeds maximum amount {}",
+                money::from_cents(min),
+                money::from_cents(max)
+            )));
+        }
+    }
+
+    Ok(pattern)
+}
+
+/// Validate a rule definition (including referenced category/account) and return its normalized pattern
+/// Shared by rule creation and categorization preview so both accept the same rules
+async fn validate_new_rule(db: &SqlitePool, rule: &NewCategoryRule) -> Result<String, CategoryError> {
+    let pattern = validate_rule_definition(rule)?;
+
+    let category_exists: Option<(i64,)> = sqlx::query_as("SELECT id FROM categories WHERE id = ?")
+        .bind(rule.category_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| CategoryError::Database(e.to_string()))?;
+    if category_exists.is_none() {
+        return Err(CategoryError::NotFound(rule.category_id));
+    }
+
+    if let Some(account_id) = rule.account_id {
+        let account_exists: Option<(i64,)> = sqlx::query_as("SELECT id FROM accounts WHERE id = ?")
+            .bind(account_id)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| CategoryError::Database(e.to_string()))?;
+        if account_exists.is_none() {
+            return Err(CategoryError::AccountNotFound(account_id));
+        }
+    }
+
+    Ok(pattern)
+}
+
+pub async fn create_category_rule_impl(
+    db: &SqlitePool,
+    rule: NewCategoryRule,
+) -> Result<i64, CategoryError> {
+    let pattern = validate_new_rule(db, &rule).await?;
+
+    let result = sqlx::query(
+        "INSERT INTO category_rules (pattern, category_id, priority, match_type, min_amount_cents, max_amount_cents, account_id)
+         VALUES (?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(&pattern)
+    .bind(rule.category_id)
+    .bind(rule.priority)
+    .bind(rule.match_type.to_string())
+    .bind(rule.min_amount_cents)
+    .bind(rule.max_amount_cents)
+    .bind(rule.account_id)
+    .execute(db)
+    .await
+    .map_err(|e| CategoryError::Database(e.to_string()))?;
 
     Ok(result.last_insert_rowid())
 }
 
+pub async fn delete_category_rule_impl(db: &SqlitePool, id: i64) -> Result<(), CategoryError> {
+    let result = sqlx::query("DELETE FROM category_rules WHERE id = ?")
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err(CategoryError::RuleNotFound(id));
+    }
+
+    Ok(())
+}
+
+/// A transaction matched by a previewed rule
+#[derive(Debug, Serialize)]
+pub struct CategorizationPreviewMatch {
+    pub transaction_id: i64,
+    pub account_id: i64,
+    pub date: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: money::Cents,
+    pub description: String,
+    pub merchant: Option<String>,
+    pub current_category_id: i64,
+    /// Category the transaction would get if re-categorized with the rule in place
+    pub new_category_id: i64,
+    pub would_change: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CategorizationPreview {
+    pub category_id: i64,
+    pub matched_count: usize,
+    pub changed_count: usize,
+    /// Matched transactions (newest first), truncated to the requested limit
+    pub matches: Vec<CategorizationPreviewMatch>,
+}
+
+#[derive(sqlx::FromRow)]
+struct PreviewTransactionRow {
+    id: i64,
+    account_id: i64,
+    category_id: i64,
+    date: String,
+    amount_cents: money::Cents,
+    description: String,
+    merchant: Option<String>,
+}
+
+/// Dry-run a rule (saved via `rule_id`, or an unsaved definition via `rule`) against
+/// existing transactions without writing anything.
+/// A matched transaction "would change" only when this rule wins over every
+/// higher-priority rule and assigns a different category than it has today.
+pub async fn preview_categorization_impl(
+    db: &SqlitePool,
+    rule_id: Option<i64>,
+    rule: Option<NewCategoryRule>,
+    limit: Option<i64>,
+) -> Result<CategorizationPreview, CategoryError> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE) as usize;
+
+    let mut rules = Categorizer::load_rules(db)
+        .await
+        .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+    // Resolve the candidate rule and its position in evaluation order
+    let candidate_index = match (rule_id, rule) {
+        (Some(id), None) => rules
+            .iter()
+            .position(|r| r.rule.id == id)
+            .ok_or(CategoryError::RuleNotFound(id))?,
+        (None, Some(new_rule)) => {
+            let pattern = validate_new_rule(db, &new_rule).await?;
+            let candidate = CompiledRule::new(CategoryRule {
+                id: 0, // Unsaved
+                pattern,
+                category_id: new_rule.category_id,
+                priority: new_rule.priority,
+                match_type: new_rule.match_type.to_string(),
+                min_amount_cents: new_rule.min_amount_cents,
+                max_amount_cents: new_rule.max_amount_cents,
+                account_id: new_rule.account_id,
+                created_at: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            })
+            .map_err(|e| CategoryError::InvalidPattern(e.to_string()))?;
+
+            // A new rule is evaluated after existing rules of equal or higher priority
+            let index = rules
+                .iter()
+                .position(|r| r.rule.priority < new_rule.priority)
+                .unwrap_or(rules.len());
+            rules.insert(index, candidate);
+            index
+        }
+        _ => {
+            return Err(CategoryError::ValidationError(
+                "Provide either a rule ID or a rule definition".to_string(),
+            ))
+        }
+    };
+
+    let candidate = &rules[candidate_index];
+    let transactions = sqlx::query_as::<_, PreviewTransactionRow>(
+        "SELECT id, account_id, category_id, date, amount_cents, description, merchant
+         FROM transactions
+         WHERE (? IS NULL OR account_id = ?)
+         ORDER BY date DESC, id DESC"
+    )
+    .bind(candidate.rule.account_id)
+    .bind(candidate.rule.account_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+    let mut matched_count = 0;
+    let mut changed_count = 0;
+    let mut matches = Vec::new();
+
+    for tx in transactions {
+        let text = tx.merchant.as_deref().unwrap_or(&tx.description);
+        if !candidate.matches(text, tx.amount_cents, Some(tx.account_id)) {
+            continue;
+        }
+        matched_count += 1;
+
+        // Any earlier rule that also matches takes precedence over the candidate
+        let new_category_id = rules[..candidate_index]
+            .iter()
+            .find(|r| r.matches(text, tx.amount_cents, Some(tx.account_id)))
+            .unwrap_or(candidate)
+            .rule
+            .category_id;
+        let would_change = new_category_id != tx.category_id;
+        if would_change {
+            changed_count += 1;
+        }
+
+        if matches.len() < limit {
+            matches.push(CategorizationPreviewMatch {
+                transaction_id: tx.id,
+                account_id: tx.account_id,
+                date: tx.date,
+                amount_cents: tx.amount_cents,
+                description: tx.description,
+                merchant: tx.merchant,
+                current_category_id: tx.category_id,
+                new_category_id,
+                would_change,
+            });
+        }
+    }
+
+    Ok(CategorizationPreview {
+        category_id: candidate.rule.category_id,
+        matched_count,
+        changed_count,
+        matches,
+    })
+}
+
+/// A transaction matched by both rules of a conflict
+#[derive(Debug, Serialize)]
+pub struct RuleConflictExample {
+    pub transaction_id: i64,
+    pub account_id: i64,
+    pub date: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: money::Cents,
+    pub description: String,
+    pub merchant: Option<String>,
+    pub category_id: i64,
+}
+
+/// Two rules of equal priority that can match the same transaction but assign different categories
+#[derive(Debug, Serialize)]
+pub struct RuleConflict {
+    /// Evaluated first (it was created first), so it's the one that wins today
+    pub first_rule: CategoryRule,
+    pub second_rule: CategoryRule,
+    /// Existing transactions both rules match; zero when only the patterns overlap
+    pub matched_count: usize,
+    /// Matched transactions (newest first), truncated to the requested limit
+    pub examples: Vec<RuleConflictExample>,
+}
+
+/// Find pairs of equal-priority rules that disagree on the category for the same transactions
+/// Pairs are found from transactions both rules match, and from patterns that overlap even
+/// when no transaction has hit both yet. Conflicts with the most matches come first.
+pub async fn detect_rule_conflicts_impl(
+    db: &SqlitePool,
+    example_limit: Option<i64>,
+) -> Result<Vec<RuleConflict>, CategoryError> {
+    let example_limit = example_limit.unwrap_or(DEFAULT_RULE_CONFLICT_EXAMPLES).clamp(1, MAX_PAGE_SIZE) as usize;
+
+    // Rules come in evaluation order, so the first of each pair is the one applied today
+    let rules = Categorizer::load_rules(db)
+        .await
+        .map_err(|e| CategoryError::Database(e.to_string()))?;
+    let conflicting = |a: &CompiledRule, b: &CompiledRule| {
+        a.rule.priority == b.rule.priority && a.rule.category_id != b.rule.category_id
+    };
+    let new_conflict = |i: usize, j: usize| RuleConflict {
+        first_rule: rules[i].rule.clone(),
+        second_rule: rules[j].rule.clone(),
+        matched_count: 0,
+        examples: Vec::new(),
+    };
+
+    let mut conflicts: BTreeMap<(usize, usize), RuleConflict> = BTreeMap::new();
+    for i in 0..rules.len() {
+        for j in (i + 1)..rules.len() {
+            if conflicting(&rules[i], &rules[j]) && rules[i].overlaps(&rules[j]) {
+                conflicts.insert((i, j), new_conflict(i, j));
+            }
+        }
+    }
+
+    let transactions = sqlx::query_as::<_, PreviewTransactionRow>(
+        "SELECT id, account_id, category_id, date, amount_cents, description, merchant
+         FROM transactions
+         ORDER BY date DESC, id DESC"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+    for tx in transactions {
+        let text = tx.merchant.as_deref().unwrap_or(&tx.description);
+        let matched: Vec<usize> = (0..rules.len())
+            .filter(|&i| rules[i].matches(text, tx.amount_cents, Some(tx.account_id)))
+            .collect();
+
+        for (k, &i) in matched.iter().enumerate() {
+            for &j in &matched[(k + 1)..] {
+                if !conflicting(&rules[i], &rules[j]) {
+                    continue;
+                }
+                let conflict = conflicts.entry((i, j)).or_insert_with(|| new_conflict(i, j));
+                conflict.matched_count += 1;
+                if conflict.examples.len() < example_limit {
+                    conflict.examples.push(RuleConflictExample {
+                        transaction_id: tx.id,
+                        account_id: tx.account_id,
+                        date: tx.date.clone(),
+                        amount_cents: tx.amount_cents,
+                        description: tx.description.clone(),
+                        merchant: tx.merchant.clone(),
+                        category_id: tx.category_id,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut conflicts: Vec<RuleConflict> = conflicts.into_values().collect();
+    conflicts.sort_by_key(|conflict| std::cmp::Reverse(conflict.matched_count));
+    Ok(conflicts)
+}
+
+/// Export the category tree with icons, colors, active budgets and rules as a shareable template
+/// Account-scoped rules are left out since accounts don't carry over between databases
+pub async fn export_category_template_impl(
+    db: &SqlitePool,
+    name: Option<String>,
+) -> Result<CategoryTemplate, CategoryError> {
+    let categories = sqlx::query_as::<_, Category>(
+        "SELECT id, name, type, parent_id, icon, color, archived, tax_deductible, created_at FROM categories ORDER BY name"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+    let budgets: Vec<(i64, money::Cents, String)> = sqlx::query_as(
+        "SELECT category_id, amount_cents, period FROM spending_targets
+         WHERE scope = 'category' AND (end_date IS NULL OR end_date >= ?)
+         ORDER BY start_date, id"
+    )
+    .bind(dates::format_date(dates::today(db).await))
+    .fetch_all(db)
+    .await
+    .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+    let rules = sqlx::query_as::<_, CategoryRule>(
+        "SELECT id, pattern, category_id, priority, match_type, min_amount_cents, max_amount_cents, account_id, created_at
+         FROM category_rules
+         WHERE account_id IS NULL
+         ORDER BY priority DESC, created_at ASC, id ASC"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+    fn build(
+        category: &Category,
+        categories: &[Category],
+        budgets: &[(i64, money::Cents, String)],
+        rules: &[CategoryRule],
+        visited: &mut std::collections::HashSet<i64>,
+    ) -> TemplateCategory {
+        visited.insert(category.id);
+        let children = categories
+            .iter()
+            .filter(|c| c.parent_id == Some(category.id) && !visited.contains(&c.id))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|child| build(child, categories, budgets, rules, visited))
+            .collect();
+
+        TemplateCategory {
+            name: category.name.clone(),
+            icon: category.icon.clone(),
+            color: category.color.clone(),
+            budgets: budgets
+                .iter()
+                .filter(|(category_id, _, _)| *category_id == category.id)
+                .map(|(_, amount, period)| TemplateBudget {
+                    amount_cents: *amount,
+                    percent_of_income: None,
+                    period: period.clone(),
+                })
+                .collect(),
+            rules: rules
+                .iter()
+                .filter(|r| r.category_id == category.id)
+                .map(|r| TemplateRule {
+                    pattern: r.pattern.clone(),
+                    priority: r.priority,
+                    match_type: r.match_type.parse().unwrap_or_default(),
+                    min_amount_cents: r.min_amount_cents,
+                    max_amount_cents: r.max_amount_cents,
+                })
+                .collect(),
+            children,
+        }
+    }
+
+    // Roots are top-level categories and any whose parent no longer exists
+    let mut visited = std::collections::HashSet::new();
+    let mut roots = Vec::new();
+    for category in &categories {
+        let is_root = category
+            .parent_id
+            .is_none_or(|parent_id| !categories.iter().any(|c| c.id == parent_id));
+        if is_root {
+            roots.push(build(category, &categories, &budgets, &rules, &mut visited));
+        }
+    }
+
+    Ok(CategoryTemplate {
+        version: CATEGORY_TEMPLATE_VERSION,
+        name,
+        description: None,
+        exported_at: Some(chrono::Utc::now().to_rfc3339()),
+        categories: roots,
+    })
+}
+
+/// Flatten a template tree depth-first so parents come before their children
+fn flatten_template<'a>(
+    categories: &'a [TemplateCategory],
+    parent: Option<&'a str>,
+    out: &mut Vec<(&'a TemplateCategory, Option<&'a str>)>,
+) {
+    for category in categories {
+        out.push((category, parent));
+        flatten_template(&category.children, Some(category.name.as_str()), out);
+    }
+}
+
+/// Parse and check a template document (size, JSON shape, version)
+fn parse_category_template(content: &str) -> Result<CategoryTemplate, CategoryError> {
+    if content.len() > MAX_CATEGORY_TEMPLATE_SIZE {
+        return Err(CategoryError::ValidationError(format!(
+            "Template too large (max {} MB)",
+            MAX_CATEGORY_TEMPLATE_SIZE / crate::constants::BYTES_PER_MB
+        )));
+    }
+
+    let template: CategoryTemplate = serde_json::from_str(content)
+        .map_err(|e| CategoryError::ValidationError(format!("Invalid template: {}", e)))?;
+    if template.version > CATEGORY_TEMPLATE_VERSION {
+        return Err(CategoryError::ValidationError(format!(
+            "Unsupported template version {} (max {})",
+            template.version, CATEGORY_TEMPLATE_VERSION
+        )));
+    }
+
+    Ok(template)
+}
+
+/// Resolve a template budget to a concrete amount for its period
+fn resolve_budget_amount(
+    budget: &TemplateBudget,
+    category_name: &str,
+    monthly_income: Option<money::Cents>,
+) -> Result<money::Cents, CategoryError> {
+    let months = match budget.period.as_str() {
+        "monthly" => 1.0,
+        "quarterly" => 3.0,
+        "yearly" => MONTHS_PER_YEAR,
+        _ => {
+            return Err(CategoryError::ValidationError(format!(
+                "Invalid budget period '{}' for '{}'",
+                budget.period, category_name
+            )))
+        }
+    };
+
+    let amount = match budget.percent_of_income {
+        Some(percent) => {
+            if !percent.is_finite() || percent <= 0.0 || percent > 100.0 {
+                return Err(CategoryError::ValidationError(format!(
+                    "Budget percentage for '{}' must be between 0 and 100, got {}",
+                    category_name, percent
+                )));
+            }
+            let income = monthly_income.ok_or_else(|| {
+                CategoryError::ValidationError(
+                    "Monthly income is required for percentage-based budgets".to_string(),
+                )
+            })?;
+            money::percent_of(income, percent * months)
+        }
+        None => budget.amount_cents,
+    };
+
+    if amount <= 0 {
+        return Err(CategoryError::ValidationError(format!(
+            "Budget amount for '{}' must be positive, got {}",
+            category_name,
+            money::from_cents(amount)
+        )));
+    }
+
+    Ok(amount)
+}
+
+/// First free "Name (n)" for a category whose name is taken
+async fn unique_category_name(
+    conn: &mut sqlx::SqliteConnection,
+    name: &str,
+) -> Result<String, CategoryError> {
+    for n in 2.. {
+        let candidate = format!("{} ({})", name, n);
+        let (exists,): (bool,) = sqlx::query_as("SELECT EXISTS(SELECT 1 FROM categories WHERE name = ?)")
+            .bind(&candidate)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| CategoryError::Database(e.to_string()))?;
+        if !exists {
+            return Ok(candidate);
+        }
+    }
+    unreachable!("category name suffixes are unbounded")
+}
+
+/// The date template budgets start on: `start_date`, or the first of the current month
+async fn template_start_date(db: &SqlitePool, start_date: Option<&str>) -> Result<String, CategoryError> {
+    match start_date {
+        Some(date) => {
+            chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+                CategoryError::ValidationError(format!("Invalid start date: {}", date))
+            })?;
+            Ok(date.to_string())
+        }
+        None => Ok(dates::format_date(dates::month_start(dates::today(db).await))),
+    }
+}
+
+/// Create categories, budgets and rules from a template. Name conflicts are handled per
+/// `conflict`; budgets start at `start_date`, and duplicate budgets or rules are skipped.
+/// New categories get `category_type`.
+/// The whole template is validated before anything is written; the caller owns the
+/// transaction, so nothing is kept unless it commits.
+async fn apply_category_template(
+    conn: &mut SqliteConnection,
+    template: &CategoryTemplate,
+    conflict: ConflictResolution,
+    start_date: &str,
+    monthly_income: Option<money::Cents>,
+    category_type: CategoryType,
+) -> Result<CategoryTemplateImportResult, CategoryError> {
+
+    if let Some(income) = monthly_income {
+        if income <= 0 {
+            return Err(CategoryError::ValidationError(format!(
+                "Monthly income must be positive, got {}",
+                money::from_cents(income)
+            )));
+        }
+    }
+
+    let mut entries = Vec::new();
+    flatten_template(&template.categories, None, &mut entries);
+
+    // Validate everything up front so a bad entry doesn't leave a partial import
+    let mut seen_names = std::collections::HashSet::new();
+    let mut budget_amounts = Vec::with_capacity(entries.len());
+    let mut rule_patterns = Vec::with_capacity(entries.len());
+    for (category, _) in &entries {
+        let name = category.name.trim();
+        if name.is_empty() {
+            return Err(CategoryError::ValidationError("Category name cannot be empty".to_string()));
+        }
+        if !seen_names.insert(name.to_string()) {
+            return Err(CategoryError::ValidationError(format!(
+                "Category '{}' appears more than once in the template",
+                name
+            )));
+        }
+        if let Some(color) = &category.color {
+            if !is_valid_color(color) {
+                return Err(CategoryError::ValidationError(format!(
+                    "Invalid color '{}' for category '{}'",
+                    color, name
+                )));
+            }
+        }
+
+        let mut amounts = Vec::with_capacity(category.budgets.len());
+        for budget in &category.budgets {
+            amounts.push(resolve_budget_amount(budget, name, monthly_income)?);
+        }
+        budget_amounts.push(amounts);
+
+        let mut patterns = Vec::with_capacity(category.rules.len());
+        for rule in &category.rules {
+            patterns.push(validate_rule_definition(&NewCategoryRule {
+                pattern: rule.pattern.clone(),
+                category_id: 0, // Resolved during import
+                priority: rule.priority,
+                match_type: rule.match_type,
+                min_amount_cents: rule.min_amount_cents,
+                max_amount_cents: rule.max_amount_cents,
+                account_id: None,
+            })?);
+        }
+        rule_patterns.push(patterns);
+    }
+
+    let mut result = CategoryTemplateImportResult::default();
+    let mut category_ids: std::collections::HashMap<&str, i64> = std::collections::HashMap::new();
+
+    for (((category, parent), amounts), patterns) in entries.iter().zip(budget_amounts).zip(rule_patterns) {
+        let name = category.name.trim();
+
+        let existing: Option<(i64,)> = sqlx::query_as("SELECT id FROM categories WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+        let new_name = match (existing, conflict) {
+            (Some((id,)), ConflictResolution::Skip) => {
+                result.categories_skipped += 1;
+                result.budgets_skipped += amounts.len();
+                result.rules_skipped += patterns.len();
+                category_ids.insert(name, id);
+                continue;
+            }
+            (Some((id,)), ConflictResolution::Merge) => {
+                result.categories_existing += 1;
+                category_ids.insert(name, id);
+                None
+            }
+            (Some(_), ConflictResolution::Rename) => {
+                result.categories_renamed += 1;
+                Some(unique_category_name(&mut *conn, name).await?)
+            }
+            (None, _) => {
+                result.categories_created += 1;
+                Some(name.to_string())
+            }
+        };
+
+        if let Some(new_name) = new_name {
+            let parent_id = parent.and_then(|p| category_ids.get(p.trim()).copied());
+            let inserted = sqlx::query(
+                "INSERT INTO categories (name, type, parent_id, icon, color) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(&new_name)
+            .bind(category_type.to_string())
+            .bind(parent_id)
+            .bind(&category.icon)
+            .bind(&category.color)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| CategoryError::Database(e.to_string()))?;
+            let inserted_id = inserted.last_insert_rowid();
+            category_ids.insert(name, inserted_id);
+
+            AuditRecord::created(AuditEntity::Category, inserted_id)
+                .new_values(&json!({
+                    "name": new_name,
+                    "parent_id": parent_id,
+                    "icon": category.icon,
+                    "color": category.color,
+                }))
+                .record(&mut *conn)
+                .await;
+        }
+        let category_id = category_ids[name];
+
+        for (budget, amount) in category.budgets.iter().zip(amounts) {
+            let (exists,): (bool,) = sqlx::query_as(
+                "SELECT EXISTS(SELECT 1 FROM spending_targets
+                 WHERE category_id = ? AND period = ? AND (end_date IS NULL OR end_date >= ?))"
+            )
+            .bind(category_id)
+            .bind(&budget.period)
+            .bind(start_date)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+            if exists {
+                result.budgets_skipped += 1;
+                continue;
+            }
+
+            sqlx::query(
+                "INSERT INTO spending_targets (category_id, amount_cents, period, start_date) VALUES (?, ?, ?, ?)"
+            )
+            .bind(category_id)
+            .bind(amount)
+            .bind(&budget.period)
+            .bind(start_date)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| CategoryError::Database(e.to_string()))?;
+            result.budgets_created += 1;
+        }
+
+        for (rule, pattern) in category.rules.iter().zip(patterns) {
+            let match_type = rule.match_type.to_string();
+            let (exists,): (bool,) = sqlx::query_as(
+                "SELECT EXISTS(SELECT 1 FROM category_rules
+                 WHERE pattern = ? AND category_id = ? AND match_type = ? AND account_id IS NULL)"
+            )
+            .bind(&pattern)
+            .bind(category_id)
+            .bind(&match_type)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| CategoryError::Database(e.to_string()))?;
+
+            if exists {
+                result.rules_skipped += 1;
+                continue;
+            }
+
+            sqlx::query(
+                "INSERT INTO category_rules (pattern, category_id, priority, match_type, min_amount_cents, max_amount_cents)
+                 VALUES (?, ?, ?, ?, ?, ?)"
+            )
+            .bind(&pattern)
+            .bind(category_id)
+            .bind(rule.priority)
+            .bind(&match_type)
+            .bind(rule.min_amount_cents)
+            .bind(rule.max_amount_cents)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| CategoryError::Database(e.to_string()))?;
+            result.rules_created += 1;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Import a shared category template, merging into existing categories with the same name
+pub async fn import_category_template_impl(
+    db: &SqlitePool,
+    content: &str,
+    start_date: Option<&str>,
+) -> Result<CategoryTemplateImportResult, CategoryError> {
+    let template = parse_category_template(content)?;
+    let start_date = template_start_date(db, start_date).await?;
+
+    let mut tx = db.begin().await.map_err(|e| CategoryError::Database(e.to_string()))?;
+    let result =
+        apply_category_template(&mut tx, &template, ConflictResolution::Merge, &start_date, None, CategoryType::Custom)
+            .await?;
+    tx.commit().await.map_err(|e| CategoryError::Database(e.to_string()))?;
+    Ok(result)
+}
+
+pub fn list_budget_templates_impl() -> Result<Vec<BudgetTemplateInfo>, CategoryError> {
+    BudgetTemplates::list().map_err(CategoryError::ValidationError)
+}
+
+/// Set up categories, budgets and rules from a bundled or imported template in one step
+/// `monthly_income` is required when the template has percentage-based budgets
+pub async fn apply_budget_template_impl(
+    db: &SqlitePool,
+    template: BudgetTemplateSource,
+    conflict: Option<ConflictResolution>,
+    monthly_income: Option<money::Cents>,
+    start_date: Option<&str>,
+) -> Result<CategoryTemplateImportResult, CategoryError> {
+    let template = match template {
+        BudgetTemplateSource::Bundled { id } => {
+            BudgetTemplates::get(&id).map_err(CategoryError::ValidationError)?
+        }
+        BudgetTemplateSource::Imported { content } => parse_category_template(&content)?,
+    };
+    let start_date = template_start_date(db, start_date).await?;
+
+    let mut tx = db.begin().await.map_err(|e| CategoryError::Database(e.to_string()))?;
+    let result = apply_category_template(
+        &mut tx,
+        &template,
+        conflict.unwrap_or_default(),
+        &start_date,
+        monthly_income,
+        CategoryType::Custom,
+    )
+    .await?;
+    tx.commit().await.map_err(|e| CategoryError::Database(e.to_string()))?;
+    Ok(result)
+}
+
+pub fn list_category_seed_sets_impl() -> Result<Vec<BudgetTemplateInfo>, CategoryError> {
+    BudgetTemplates::list_seed_sets().map_err(CategoryError::ValidationError)
+}
+
+/// Reseed the predefined categories from a category set (standard, minimal, detailed, business)
+///
+/// Categories already named like a set category are reused rather than duplicated, and archived
+/// ones are restored; predefined categories are moved under the set's parents, while custom
+/// categories keep their place. With `replace`, predefined categories outside the set are archived
+/// (Uncategorized never is), so transactions keep their categories either way.
+/// The reseed runs in one transaction, so a failure partway leaves the categories as they were.
+pub async fn reseed_categories_impl(
+    db: &SqlitePool,
+    set: &str,
+    replace: bool,
+) -> Result<CategoryReseedResult, CategoryError> {
+    let template = BudgetTemplates::seed_set(set).map_err(CategoryError::ValidationError)?;
+    let start_date = template_start_date(db, None).await?;
+
+    let mut tx = db.begin().await.map_err(|e| CategoryError::Database(e.to_string()))?;
+    let import = apply_category_template(
+        &mut tx,
+        &template,
+        ConflictResolution::Merge,
+        &start_date,
+        None,
+        CategoryType::Predefined,
+    )
+    .await?;
+    let mut result = CategoryReseedResult { set: set.to_string(), import, ..Default::default() };
+
+    let mut entries = Vec::new();
+    flatten_template(&template.categories, None, &mut entries);
+    let fetch_by_name = |name: &str| {
+        sqlx::query_as::<_, Category>(
+            "SELECT id, name, type, parent_id, icon, color, archived, tax_deductible, created_at FROM categories WHERE name = ?"
+        )
+        .bind(name.trim().to_string())
+    };
+
+    let mut seeded_ids = Vec::with_capacity(entries.len());
+    for (category, parent) in &entries {
+        let Some(current) = fetch_by_name(&category.name)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| CategoryError::Database(e.to_string()))?
+        else {
+            continue;
+        };
+        seeded_ids.push(current.id);
+
+        if current.archived {
+            set_category_flag(&mut tx, current.id, "archived", false).await?;
+            result.categories_restored += 1;
+        }
+
+        let parent_id = match parent {
+            Some(parent) => fetch_by_name(parent)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| CategoryError::Database(e.to_string()))?
+                .map(|parent| parent.id),
+            None => None,
+        };
+        if current.category_type == CategoryType::Predefined.to_string() && current.parent_id != parent_id {
+            sqlx::query("UPDATE categories SET parent_id = ? WHERE id = ?")
+                .bind(parent_id)
+                .bind(current.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| CategoryError::Database(e.to_string()))?;
+            AuditRecord::updated(AuditEntity::Category, current.id)
+                .old_values(&json!({ "parent_id": current.parent_id }))
+                .new_values(&json!({ "parent_id": parent_id }))
+                .record(&mut *tx)
+                .await;
+            result.categories_moved += 1;
+        }
+    }
+
+    if replace {
+        let retired: Vec<(i64,)> = sqlx::query_as(
+            "SELECT id FROM categories WHERE type = 'predefined' AND archived = 0 AND id != ?"
+        )
+        .bind(DEFAULT_CATEGORY_ID)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CategoryError::Database(e.to_string()))?;
+        for (id,) in retired.into_iter().filter(|(id,)| !seeded_ids.contains(id)) {
+            set_category_flag(&mut tx, id, "archived", true).await?;
+            result.categories_archived += 1;
+        }
+    }
+
+    tx.commit().await.map_err(|e| CategoryError::Database(e.to_string()))?;
+    Ok(result)
+}
+
 // Tauri command handlers (extract pool from managed state)
 
 #[tauri::command]
-pub async fn list_categories(db_pool: tauri::State<'_, DbPool>) -> Result<Vec<Category>, String> {
-    list_categories_impl(&db_pool.0).await
+pub async fn list_categories(
+    db_pool: tauri::State<'_, DbPool>,
+    include_archived: Option<bool>,
+) -> Result<Vec<Category>, String> {
+    list_categories_impl(&db_pool.pool(), include_archived.unwrap_or(false)).await
+}
+
+#[tauri::command]
+pub async fn archive_category(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    category_id: i64,
+) -> Result<Category, String> {
+    let result = archive_category_impl(&db_pool.pool(), category_id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Categories, ChangeAction::Updated, [result.id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn unarchive_category(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    category_id: i64,
+) -> Result<Category, String> {
+    let result = unarchive_category_impl(&db_pool.pool(), category_id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Categories, ChangeAction::Updated, [result.id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn set_category_tax_deductible(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    category_id: i64,
+    tax_deductible: bool,
+) -> Result<Category, String> {
+    let result = set_category_tax_deductible_impl(&db_pool.pool(), category_id, tax_deductible)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Categories, ChangeAction::Updated, [result.id]);
+    Ok(result)
 }
 
 #[tauri::command]
 pub async fn create_category(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     category: NewCategory,
 ) -> Result<i64, String> {
-    create_category_impl(&db_pool.0, category).await
+    let result = create_category_impl(&db_pool.pool(), category).await?;
+    notify_changed(&app, DataKind::Categories, ChangeAction::Created, [result]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn update_category(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    update: UpdateCategory,
+) -> Result<Category, String> {
+    let result = update_category_impl(&db_pool.pool(), update)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Categories, ChangeAction::Updated, [result.id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn list_category_rules(
+    db_pool: tauri::State<'_, DbPool>,
+    category_id: Option<i64>,
+) -> Result<Vec<CategoryRule>, String> {
+    list_category_rules_impl(&db_pool.pool(), category_id)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn get_rule_statistics(
+    db_pool: tauri::State<'_, DbPool>,
+    stale_after_days: Option<i64>,
+) -> Result<Vec<RuleStatistics>, String> {
+    get_rule_statistics_impl(&db_pool.pool(), stale_after_days)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn create_category_rule(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    rule: NewCategoryRule,
+) -> Result<i64, String> {
+    let result = create_category_rule_impl(&db_pool.pool(), rule)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::CategoryRules, ChangeAction::Created, [result]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn delete_category_rule(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    id: i64,
+) -> Result<(), String> {
+    delete_category_rule_impl(&db_pool.pool(), id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::CategoryRules, ChangeAction::Deleted, [id]);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn preview_categorization(
+    db_pool: tauri::State<'_, DbPool>,
+    rule_id: Option<i64>,
+    rule: Option<NewCategoryRule>,
+    limit: Option<i64>,
+) -> Result<CategorizationPreview, String> {
+    preview_categorization_impl(&db_pool.pool(), rule_id, rule, limit)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn detect_rule_conflicts(
+    db_pool: tauri::State<'_, DbPool>,
+    example_limit: Option<i64>,
+) -> Result<Vec<RuleConflict>, String> {
+    detect_rule_conflicts_impl(&db_pool.pool(), example_limit)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn export_category_template(
+    db_pool: tauri::State<'_, DbPool>,
+    name: Option<String>,
+) -> Result<String, String> {
+    let template = export_category_template_impl(&db_pool.pool(), name)
+        .await
+        .map_err(|e| e.to_user_message())?;
+
+    serde_json::to_string_pretty(&template)
+        .map_err(|e| crate::errors::sanitize_error(e, "Category template serialization", "Failed to export category template"))
+}
+
+#[tauri::command]
+pub async fn import_category_template(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    content: String,
+    start_date: Option<String>,
+) -> Result<CategoryTemplateImportResult, String> {
+    let result = import_category_template_impl(&db_pool.pool(), &content, start_date.as_deref())
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Categories, ChangeAction::Created, vec![]);
+    notify_changed(&app, DataKind::CategoryRules, ChangeAction::Created, vec![]);
+    notify_changed(&app, DataKind::SpendingTargets, ChangeAction::Created, vec![]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn list_budget_templates() -> Result<Vec<BudgetTemplateInfo>, String> {
+    list_budget_templates_impl().map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn list_category_seed_sets() -> Result<Vec<BudgetTemplateInfo>, String> {
+    list_category_seed_sets_impl().map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn reseed_categories(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    set: String,
+    replace: Option<bool>,
+) -> Result<CategoryReseedResult, String> {
+    let result = reseed_categories_impl(&db_pool.pool(), &set, replace.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Categories, ChangeAction::Updated, vec![]);
+    notify_changed(&app, DataKind::CategoryRules, ChangeAction::Created, vec![]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn apply_budget_template(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    template: BudgetTemplateSource,
+    conflict: Option<ConflictResolution>,
+    monthly_income: Option<f64>,
+    start_date: Option<String>,
+) -> Result<CategoryTemplateImportResult, String> {
+    let monthly_income = monthly_income.map(money::parse_dollars).transpose()?;
+    let result = apply_budget_template_impl(&db_pool.pool(), template, conflict, monthly_income, start_date.as_deref())
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Categories, ChangeAction::Created, vec![]);
+    notify_changed(&app, DataKind::CategoryRules, ChangeAction::Created, vec![]);
+    notify_changed(&app, DataKind::SpendingTargets, ChangeAction::Created, vec![]);
+    Ok(result)
 }
@@ -1,13 +1,38 @@
-use crate::constants::{MAX_CSV_FILE_SIZE, MAX_CSV_ROWS, MIN_CSV_IMPORT_INTERVAL_MS};
-use crate::errors::CsvImportError;
+use crate::commands::freeze_commands::flag_freeze_violations_impl;
+use crate::commands::transaction_commands::ensure_unlocked;
+use crate::constants::{
+    COLUMN_DETECTION_SAMPLE_ROWS, DEFAULT_IMPORT_PREVIEW_ROWS, DEFAULT_PAGE_SIZE, MAX_BATCH_IMPORT_FILES, MAX_CSV_FILE_SIZE,
+    MAX_CSV_ROWS, MAX_IMPORT_PREVIEW_ROWS,
+    MAX_DUPLICATE_LOOKBACK_DAYS, MAX_PAGE_SIZE, MIN_CSV_IMPORT_INTERVAL_MS, REHASH_BATCH_SIZE,
+};
+use crate::errors::{CsvImportError, TransactionError};
+use crate::models::audit_log::AuditEntity;
 use crate::models::column_mapping::NewColumnMapping;
-use crate::services::csv_parser::{ColumnMapping, CsvParser};
-use crate::services::transaction_importer::TransactionImporter;
+use crate::models::duplicate_settings::{
+    DuplicateField, DuplicateSettings, DuplicateSettingsUpdate, RehashProgress, RehashResult,
+};
+use crate::models::import_batch::{ImportBatch, UndoImportResult};
+use crate::models::transaction::NewTransaction;
+use crate::services::account_validator::{AccountValidator, ImportWarning};
+use crate::services::audit_log::AuditRecord;
+use crate::services::column_detector::{ColumnDetector, MappingSuggestion};
+use crate::services::csv_parser::{ColumnMapping, CsvEncoding, CsvError, CsvParser};
+use crate::services::duplicate_detector::{DuplicateDetector, DuplicateError};
+use crate::services::mapping_expression::MappingExpression;
+use crate::services::transaction_importer::{ImportError, ImportPreview, ImportProgress, TransactionImporter};
+use crate::utils::change_events::{notify_changed, ChangeAction, ChangeSink, DataKind};
 use crate::utils::rate_limiter::RateLimiter;
+use crate::utils::money::Cents;
 use crate::DbPool;
 use once_cell::sync::Lazy;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::SqlitePool;
+use std::ops::ControlFlow;
+use tauri::Emitter;
+
+/// Event emitted after each imported batch with an ImportProgress payload
+pub const IMPORT_PROGRESS_EVENT: &str = "csv-import-progress";
 
 // Get rate limiter interval from environment variable or use default
 // Set CSV_RATE_LIMIT_MS=50 for fast test execution
@@ -36,6 +61,43 @@ pub struct ImportResult {
     pub imported: usize,
     pub duplicates: usize,
     pub errors: usize,
+    pub pending_posted: usize, // Pending transactions already imported that this file shows posted
+    pub locked: usize, // Rows dated in a locked month, left out unless the import overrides the lock
+    pub freeze_violations: usize, // Imported expenses that break a spending freeze
+    pub import_batch_id: i64, // Pass to undo_import to remove everything this import added
+    pub message: String,
+    pub warnings: Vec<ImportWarning>, // The file looks wrong for the account's type
+}
+
+/// One file of a batch import: its content, or a path to read it from, and where it goes
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchImportFile {
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub csv_content: Option<String>,
+    #[serde(default)]
+    pub encoding: Option<CsvEncoding>, // Used when reading `path`; detected when None
+    pub account_id: i64,
+    pub mapping: ColumnMapping,
+}
+
+/// How one file of a batch import went
+#[derive(Debug, Serialize)]
+pub struct BatchFileResult {
+    pub file: String, // The path, or "File N" for content passed directly
+    pub account_id: i64,
+    pub result: Option<ImportResult>, // None when the file couldn't be imported
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchImportResult {
+    pub files: Vec<BatchFileResult>, // In the order given
+    pub imported: usize,
+    pub duplicates: usize,
+    pub errors: usize, // Rows that couldn't be imported, across every file
+    pub failed_files: usize,
     pub message: String,
 }
 
@@ -45,15 +107,44 @@ pub async fn save_column_mapping_impl(
     db: &SqlitePool,
     mapping: NewColumnMapping,
 ) -> Result<i64, CsvImportError> {
+    // Amounts come from a signed amount column or from debit/credit columns
+    if mapping.amount_col.trim().is_empty() && mapping.debit_col.is_none() && mapping.credit_col.is_none() {
+        return Err(CsvImportError::MissingColumn("amount".to_string()));
+    }
+    if let Some(format) = &mapping.date_format {
+        CsvParser::date_format_pattern(format).map_err(CsvImportError::from)?;
+    }
+    if let Some(separator) = mapping.decimal_separator {
+        CsvParser::validate_decimal_separator(separator).map_err(CsvImportError::from)?;
+    }
+    for field in std::iter::once(&mapping.description_col).chain(&mapping.merchant_col) {
+        if MappingExpression::is_expression(field) {
+            MappingExpression::validate(field).map_err(CsvImportError::from)?;
+        }
+    }
+    if let Some(account_id) = mapping.account_id {
+        ensure_account(db, account_id).await?;
+    }
+
     let result = sqlx::query(
-        "INSERT INTO column_mappings (source_name, date_col, amount_col, description_col, merchant_col)
-         VALUES (?, ?, ?, ?, ?)"
+        "INSERT INTO column_mappings
+            (source_name, date_col, amount_col, description_col, merchant_col, debit_col, credit_col, status_col, member_col, invert_amounts, date_format, decimal_separator, header_fingerprint, account_id)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(&mapping.source_name)
     .bind(&mapping.date_col)
     .bind(&mapping.amount_col)
     .bind(&mapping.description_col)
     .bind(&mapping.merchant_col)
+    .bind(&mapping.debit_col)
+    .bind(&mapping.credit_col)
+    .bind(&mapping.status_col)
+    .bind(&mapping.member_col)
+    .bind(mapping.invert_amounts)
+    .bind(&mapping.date_format)
+    .bind(mapping.decimal_separator.map(String::from))
+    .bind(mapping.headers.as_deref().map(ColumnDetector::header_fingerprint))
+    .bind(mapping.account_id)
     .execute(db)
     .await
     .map_err(|e| {
@@ -67,17 +158,21 @@ pub async fn save_column_mapping_impl(
     Ok(result.last_insert_rowid())
 }
 
-pub async fn import_csv_impl(
-    db: &SqlitePool,
-    account_id: i64,
-    csv_content: String,
-    mapping: ColumnMapping,
-) -> Result<ImportResult, CsvImportError> {
-    // Check rate limit FIRST (before expensive operations)
-    // This ensures rate limiting cannot be bypassed by calling _impl directly
-    CSV_RATE_LIMITER.check_and_update()
-        .map_err(|err| CsvImportError::RateLimitExceeded(err.seconds()))?;
+/// Read a CSV file as text, detecting its encoding unless one is given
+pub fn read_csv_file_impl(path: &str, encoding: Option<CsvEncoding>) -> Result<String, CsvImportError> {
+    let size = std::fs::metadata(path).map_err(|e| CsvImportError::FileRead(e.to_string()))?.len() as usize;
+    if size > MAX_CSV_FILE_SIZE {
+        return Err(CsvImportError::FileTooLarge { size, max: MAX_CSV_FILE_SIZE });
+    }
 
+    let bytes = std::fs::read(path).map_err(|e| CsvImportError::FileRead(e.to_string()))?;
+    CsvParser::decode(&bytes, encoding).map_err(|e| match e {
+        CsvError::ParseError(message) => CsvImportError::InvalidEncoding(message),
+        e => CsvImportError::from(e),
+    })
+}
+
+fn validate_csv_size(csv_content: &str) -> Result<(), CsvImportError> {
     // Validate file size
     if csv_content.len() > MAX_CSV_FILE_SIZE {
         return Err(CsvImportError::FileTooLarge {
@@ -95,20 +190,531 @@ pub async fn import_csv_impl(
         });
     }
 
-    match TransactionImporter::import(db, account_id, &csv_content, &mapping).await {
-        Ok(stats) => Ok(ImportResult {
-            success: true,
-            total: stats.total,
-            imported: stats.imported,
-            duplicates: stats.duplicates,
-            errors: stats.errors,
-            message: format!(
-                "Imported {} of {} transactions ({} duplicates skipped, {} errors)",
-                stats.imported, stats.total, stats.duplicates, stats.errors
-            ),
-        }),
-        Err(e) => Err(CsvImportError::Database(e.to_string())),
+    Ok(())
+}
+
+type SavedMappingRow = (
+    i64,
+    String,
+    String,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    bool,
+    Option<String>,
+    Option<String>,
+    Option<i64>,
+    Option<String>,
+);
+
+/// A saved mapping recognized from a file's headers, with the account it imports into
+#[derive(Debug, Serialize)]
+pub struct DetectedMapping {
+    pub mapping_id: i64,
+    pub mapping_name: String,
+    pub mapping: ColumnMapping,
+    /// None when the mapping isn't linked to an account or the account is archived
+    pub account_id: Option<i64>,
+    pub account_name: Option<String>,
+    pub header_fingerprint: String,
+}
+
+/// The newest saved mapping made for files with `header_fingerprint`
+async fn find_saved_mapping(
+    db: &SqlitePool,
+    header_fingerprint: &str,
+) -> Result<Option<DetectedMapping>, CsvImportError> {
+    let saved = sqlx::query_as::<_, SavedMappingRow>(
+        "SELECT m.id, m.source_name, m.date_col, m.amount_col, m.description_col, m.merchant_col, m.debit_col,
+                m.credit_col, m.status_col, m.member_col, m.invert_amounts, m.date_format, m.decimal_separator,
+                a.id, a.name
+         FROM column_mappings m
+         LEFT JOIN accounts a ON a.id = m.account_id AND a.archived = 0
+         WHERE m.header_fingerprint = ?
+         ORDER BY m.created_at DESC, m.id DESC
+         LIMIT 1"
+    )
+    .bind(header_fingerprint)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| CsvImportError::Database(e.to_string()))?;
+
+    Ok(saved.map(
+        |(id, source_name, date, amount, description, merchant, debit, credit, status, member, invert_amounts, date_format, decimal_separator, account_id, account_name)| {
+            DetectedMapping {
+                mapping_id: id,
+                mapping_name: source_name,
+                mapping: ColumnMapping {
+                    date,
+                    amount,
+                    description,
+                    merchant,
+                    debit,
+                    credit,
+                    status,
+                    member,
+                    invert_amounts,
+                    date_format,
+                    delimiter: None,
+                    decimal_separator: decimal_separator.and_then(|s| s.chars().next()),
+                },
+                account_id,
+                account_name,
+                header_fingerprint: header_fingerprint.to_string(),
+            }
+        },
+    ))
+}
+
+/// Guess a column mapping from a file's headers and sample values
+/// A saved mapping made for a file with the same headers takes precedence over detection
+pub async fn suggest_column_mapping_impl(
+    db: &SqlitePool,
+    csv_content: &str,
+) -> Result<MappingSuggestion, CsvImportError> {
+    validate_csv_size(csv_content)?;
+    let (headers, samples) = CsvParser::sample(csv_content, COLUMN_DETECTION_SAMPLE_ROWS)
+        .map_err(|e| CsvImportError::InvalidFormat(e.to_string()))?;
+
+    let mut suggestion = ColumnDetector::suggest(&headers, &samples);
+
+    if let Some(saved) = find_saved_mapping(db, &suggestion.header_fingerprint).await? {
+        suggestion.mapping = Some(saved.mapping);
+        suggestion.confidence = 1.0;
+        suggestion.saved_mapping_id = Some(saved.mapping_id);
+        suggestion.saved_mapping_name = Some(saved.mapping_name);
+    }
+
+    Ok(suggestion)
+}
+
+/// Recognize a file by its headers and return the saved mapping and account it was last imported with
+/// None when no saved mapping was made for files with these headers
+pub async fn detect_mapping_for_csv_impl(
+    db: &SqlitePool,
+    csv_content: &str,
+) -> Result<Option<DetectedMapping>, CsvImportError> {
+    validate_csv_size(csv_content)?;
+    let headers = CsvParser::get_headers(csv_content).map_err(|e| CsvImportError::InvalidFormat(e.to_string()))?;
+    find_saved_mapping(db, &ColumnDetector::header_fingerprint(&headers)).await
+}
+
+/// Parse a CSV with `mapping` and show the first `limit` rows as they would be imported into
+/// `account_id`, with duplicates, unparseable rows and inferred categories. Nothing is written,
+/// so previews are not rate limited
+pub async fn preview_csv_import_impl(
+    db: &SqlitePool,
+    account_id: i64,
+    csv_content: &str,
+    mapping: &ColumnMapping,
+    limit: Option<usize>,
+) -> Result<ImportPreview, CsvImportError> {
+    validate_csv_size(csv_content)?;
+    let limit = limit.unwrap_or(DEFAULT_IMPORT_PREVIEW_ROWS).clamp(1, MAX_IMPORT_PREVIEW_ROWS);
+
+    let rows = CsvParser::rows(csv_content, mapping).map_err(CsvImportError::from)?;
+
+    TransactionImporter::preview(db, account_id, rows, limit)
+        .await
+        .map_err(|e| CsvImportError::Database(e.to_string()))
+}
+
+pub async fn import_csv_impl(
+    db: &SqlitePool,
+    account_id: i64,
+    csv_content: String,
+    mapping: ColumnMapping,
+) -> Result<ImportResult, CsvImportError> {
+    import_csv_with_progress_impl(db, account_id, csv_content, mapping, false, |_| {}).await
+}
+
+/// Import a CSV in batches, reporting progress after each batch
+/// Rows dated in a locked month are left out unless `override_lock` is set
+pub async fn import_csv_with_progress_impl<F>(
+    db: &SqlitePool,
+    account_id: i64,
+    csv_content: String,
+    mapping: ColumnMapping,
+    override_lock: bool,
+    mut on_progress: F,
+) -> Result<ImportResult, CsvImportError>
+where
+    F: FnMut(&ImportProgress) + Send,
+{
+    import_csv_cancellable_impl(db, account_id, csv_content, mapping, override_lock, |progress| {
+        on_progress(progress);
+        ControlFlow::Continue(())
+    })
+    .await
+}
+
+/// Import a CSV in batches; `on_progress` can stop the import after any batch by returning `Break`
+/// Batches imported before stopping stay in the import batch, so they can be undone
+pub async fn import_csv_cancellable_impl<F>(
+    db: &SqlitePool,
+    account_id: i64,
+    csv_content: String,
+    mapping: ColumnMapping,
+    override_lock: bool,
+    on_progress: F,
+) -> Result<ImportResult, CsvImportError>
+where
+    F: FnMut(&ImportProgress) -> ControlFlow<()> + Send,
+{
+    // Check rate limit FIRST (before expensive operations)
+    // This ensures rate limiting cannot be bypassed by calling _impl directly
+    CSV_RATE_LIMITER.check_and_update()
+        .map_err(|err| CsvImportError::RateLimitExceeded(err.seconds()))?;
+
+    import_checked(db, account_id, csv_content, mapping, override_lock, on_progress).await
+}
+
+/// Import one file once the rate limit has been checked
+/// The watch folder calls this directly; it imports one file at a time on its own
+pub(crate) async fn import_checked<F>(
+    db: &SqlitePool,
+    account_id: i64,
+    csv_content: String,
+    mapping: ColumnMapping,
+    override_lock: bool,
+    on_progress: F,
+) -> Result<ImportResult, CsvImportError>
+where
+    F: FnMut(&ImportProgress) -> ControlFlow<()> + Send,
+{
+    validate_csv_size(&csv_content)?;
+
+    // Check the mapping against the file first, so a bad mapping is reported as such
+    CsvParser::rows(&csv_content, &mapping).map_err(CsvImportError::from)?;
+
+    let stats = TransactionImporter::import(db, account_id, &csv_content, &mapping, override_lock, on_progress)
+        .await
+        .map_err(|e| match e {
+            ImportError::Cancelled => CsvImportError::Cancelled,
+            e => CsvImportError::Database(e.to_string()),
+        })?;
+
+    // The import already succeeded, so a failure to flag freeze violations is only logged
+    let freeze_violations = flag_freeze_violations_impl(db, &stats.transaction_ids)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to flag spending freeze violations");
+            0
+        });
+
+    AuditRecord::created(AuditEntity::Import, stats.import_batch_id)
+        .new_values(&json!({
+            "account_id": account_id,
+            "total": stats.total,
+            "imported": stats.imported,
+            "duplicates": stats.duplicates,
+            "pending_posted": stats.pending_posted,
+            "locked": stats.locked,
+            "errors": stats.errors,
+        }))
+        .record(db)
+        .await;
+
+    // Warnings are advisory, so a failure to compute them doesn't fail the import either
+    let warnings = import_warnings(db, account_id, stats.import_batch_id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to check import against account type");
+            Vec::new()
+        });
+
+    Ok(ImportResult {
+        success: true,
+        total: stats.total,
+        imported: stats.imported,
+        duplicates: stats.duplicates,
+        errors: stats.errors,
+        pending_posted: stats.pending_posted,
+        locked: stats.locked,
+        freeze_violations,
+        import_batch_id: stats.import_batch_id,
+        message: format!(
+            "Imported {} of {} transactions ({} duplicates skipped, {} in locked months skipped, {} pending transactions posted, {} errors)",
+            stats.imported, stats.total, stats.duplicates, stats.locked, stats.pending_posted, stats.errors
+        ),
+        warnings,
+    })
+}
+
+/// Import several files one after another, each into its own account with its own mapping
+/// The batch counts as one import against the rate limit. A file that fails is reported and the
+/// rest still run; cancelling (`on_progress` breaking, called with each file's index) stops the
+/// whole batch, keeping the files already imported.
+pub async fn import_csv_batch_impl<F>(
+    db: &SqlitePool,
+    files: Vec<BatchImportFile>,
+    override_lock: bool,
+    mut on_progress: F,
+) -> Result<BatchImportResult, CsvImportError>
+where
+    F: FnMut(usize, &ImportProgress) -> ControlFlow<()> + Send,
+{
+    if files.is_empty() {
+        return Err(CsvImportError::InvalidBatch("Choose at least one file to import".to_string()));
+    }
+    if files.len() > MAX_BATCH_IMPORT_FILES {
+        return Err(CsvImportError::InvalidBatch(format!(
+            "At most {} files can be imported at once",
+            MAX_BATCH_IMPORT_FILES
+        )));
+    }
+    CSV_RATE_LIMITER.check_and_update()
+        .map_err(|err| CsvImportError::RateLimitExceeded(err.seconds()))?;
+
+    let mut results = Vec::with_capacity(files.len());
+    for (index, file) in files.into_iter().enumerate() {
+        let name = file.path.clone().unwrap_or_else(|| format!("File {}", index + 1));
+        let content = match (file.csv_content, &file.path) {
+            (Some(content), _) => Ok(content),
+            (None, Some(path)) => read_csv_file_impl(path, file.encoding),
+            (None, None) => Err(CsvImportError::InvalidBatch(format!("{} has no path or content", name))),
+        };
+        let outcome = match content {
+            Ok(content) => {
+                import_checked(db, file.account_id, content, file.mapping, override_lock, |progress| {
+                    on_progress(index, progress)
+                })
+                .await
+            }
+            Err(e) => Err(e),
+        };
+
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(CsvImportError::Cancelled) => return Err(CsvImportError::Cancelled),
+            Err(e) => (None, Some(e.to_user_message())),
+        };
+        results.push(BatchFileResult { file: name, account_id: file.account_id, result, error });
+    }
+
+    let imported = results.iter().filter_map(|f| f.result.as_ref()).map(|r| r.imported).sum();
+    let duplicates = results.iter().filter_map(|f| f.result.as_ref()).map(|r| r.duplicates).sum();
+    let errors = results.iter().filter_map(|f| f.result.as_ref()).map(|r| r.errors).sum();
+    let failed_files = results.iter().filter(|f| f.error.is_some()).count();
+    Ok(BatchImportResult {
+        message: format!(
+            "Imported {} transactions from {} of {} files ({} duplicates skipped, {} errors)",
+            imported,
+            results.len() - failed_files,
+            results.len(),
+            duplicates,
+            errors
+        ),
+        files: results,
+        imported,
+        duplicates,
+        errors,
+        failed_files,
+    })
+}
+
+async fn import_warnings(db: &SqlitePool, account_id: i64, import_batch_id: i64) -> Result<Vec<ImportWarning>, String> {
+    let rows = sqlx::query_as::<_, (String, Cents)>("SELECT date, amount_cents FROM transactions WHERE import_batch_id = ?")
+        .bind(import_batch_id)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+    AccountValidator::import_warnings(db, account_id, &rows).await
+}
+
+/// Recent imports, newest first, optionally for one account
+pub async fn list_import_batches_impl(
+    db: &SqlitePool,
+    account_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<ImportBatch>, CsvImportError> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    sqlx::query_as::<_, ImportBatch>(
+        "SELECT b.id, b.account_id, a.name AS account_name, b.status, b.total_rows, b.imported_count,
+                b.duplicate_count, b.error_count,
+                (SELECT COUNT(*) FROM transactions t WHERE t.import_batch_id = b.id) AS remaining_count,
+                b.created_at, b.undone_at
+         FROM import_batches b
+         JOIN accounts a ON a.id = b.account_id
+         WHERE (? IS NULL OR b.account_id = ?)
+         ORDER BY b.created_at DESC, b.id DESC
+         LIMIT ?"
+    )
+    .bind(account_id)
+    .bind(account_id)
+    .bind(limit)
+    .fetch_all(db)
+    .await
+    .map_err(|e| CsvImportError::Database(e.to_string()))
+}
+
+/// Delete every transaction an import added and mark the batch as undone
+/// Failed imports can be undone too, since rows from their earlier batches were kept
+/// Nothing is deleted when any of the rows is dated in a locked month, unless `override_lock` is set
+pub async fn undo_import_impl(
+    db: &SqlitePool,
+    import_batch_id: i64,
+    override_lock: bool,
+) -> Result<UndoImportResult, CsvImportError> {
+    let mut tx = db.begin().await.map_err(|e| CsvImportError::Database(e.to_string()))?;
+
+    let status: Option<(String,)> = sqlx::query_as("SELECT status FROM import_batches WHERE id = ?")
+        .bind(import_batch_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| CsvImportError::Database(e.to_string()))?;
+    let status_before = match status {
+        None => return Err(CsvImportError::ImportBatchNotFound(import_batch_id)),
+        Some((status,)) if status == "undone" || status == "in_progress" => {
+            return Err(CsvImportError::ImportNotUndoable { id: import_batch_id, status });
+        }
+        Some((status,)) => status,
+    };
+
+    let dates: Vec<String> = sqlx::query_scalar("SELECT DISTINCT date FROM transactions WHERE import_batch_id = ?")
+        .bind(import_batch_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CsvImportError::Database(e.to_string()))?;
+    let dates: Vec<&str> = dates.iter().map(String::as_str).collect();
+    ensure_unlocked(db, &dates, override_lock).await.map_err(|e| match e {
+        TransactionError::PeriodLocked(month) => CsvImportError::PeriodLocked(month),
+        e => CsvImportError::Database(e.to_string()),
+    })?;
+
+    let deleted = sqlx::query("DELETE FROM transactions WHERE import_batch_id = ?")
+        .bind(import_batch_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| CsvImportError::Database(e.to_string()))?
+        .rows_affected();
+
+    sqlx::query("UPDATE import_batches SET status = 'undone', undone_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(import_batch_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| CsvImportError::Database(e.to_string()))?;
+
+    AuditRecord::deleted(AuditEntity::Import, import_batch_id)
+        .old_values(&json!({ "status": status_before, "transactions_deleted": deleted }))
+        .record(&mut *tx)
+        .await;
+
+    tx.commit().await.map_err(|e| CsvImportError::Database(e.to_string()))?;
+
+    tracing::info!(import_batch_id, deleted, "Undid CSV import");
+    Ok(UndoImportResult { import_batch_id, deleted })
+}
+
+async fn ensure_account(db: &SqlitePool, account_id: i64) -> Result<(), CsvImportError> {
+    let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM accounts WHERE id = ?")
+        .bind(account_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| CsvImportError::Database(e.to_string()))?;
+    exists.map(|_| ()).ok_or(CsvImportError::AccountNotFound(account_id))
+}
+
+pub async fn get_duplicate_settings_impl(db: &SqlitePool, account_id: i64) -> Result<DuplicateSettings, CsvImportError> {
+    ensure_account(db, account_id).await?;
+    DuplicateDetector::settings(db, account_id)
+        .await
+        .map_err(|e| CsvImportError::Database(e.to_string()))
+}
+
+/// Set how imports into an account detect duplicates; None returns it to the default
+pub async fn update_duplicate_settings_impl(
+    db: &SqlitePool,
+    account_id: i64,
+    settings: Option<DuplicateSettingsUpdate>,
+) -> Result<DuplicateSettings, CsvImportError> {
+    ensure_account(db, account_id).await?;
+
+    let Some(settings) = settings else {
+        sqlx::query("DELETE FROM duplicate_settings WHERE account_id = ?")
+            .bind(account_id)
+            .execute(db)
+            .await
+            .map_err(|e| CsvImportError::Database(e.to_string()))?;
+        return get_duplicate_settings_impl(db, account_id).await;
+    };
+
+    let mut fields: Vec<DuplicateField> = Vec::new();
+    for field in settings.fields {
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    if !fields.iter().any(|f| matches!(f, DuplicateField::Amount | DuplicateField::Description | DuplicateField::Merchant)) {
+        return Err(CsvImportError::InvalidDuplicateSettings(
+            "Compare at least one of amount, description or merchant".to_string(),
+        ));
+    }
+    if let Some(days) = settings.lookback_days {
+        if !(0..=MAX_DUPLICATE_LOOKBACK_DAYS).contains(&days) {
+            return Err(CsvImportError::InvalidDuplicateSettings(format!(
+                "Lookback must be between 0 and {} days",
+                MAX_DUPLICATE_LOOKBACK_DAYS
+            )));
+        }
+    }
+    let match_fields: Vec<String> = fields.iter().map(ToString::to_string).collect();
+
+    sqlx::query(
+        "INSERT INTO duplicate_settings (account_id, match_fields, lookback_days, strictness)
+         VALUES (?, ?, ?, ?)
+         ON CONFLICT(account_id) DO UPDATE SET match_fields = excluded.match_fields,
+             lookback_days = excluded.lookback_days, strictness = excluded.strictness,
+             updated_at = CURRENT_TIMESTAMP"
+    )
+    .bind(account_id)
+    .bind(match_fields.join(","))
+    .bind(settings.lookback_days)
+    .bind(settings.strictness.to_string())
+    .execute(db)
+    .await
+    .map_err(|e| CsvImportError::Database(e.to_string()))?;
+
+    get_duplicate_settings_impl(db, account_id).await
+}
+
+/// Move transactions stored under an older hash version to the current one, in batches
+/// `on_progress` can stop after any batch by returning `Break`; finished batches are kept and a
+/// later run picks up the rest
+pub async fn rehash_transactions_impl<F>(db: &SqlitePool, mut on_progress: F) -> Result<RehashResult, CsvImportError>
+where
+    F: FnMut(&RehashProgress) -> ControlFlow<()> + Send,
+{
+    let db_error = |e: DuplicateError| CsvImportError::Database(e.to_string());
+    let total = DuplicateDetector::outdated_count(db).await.map_err(db_error)?.max(0) as usize;
+
+    let mut rehashed = 0;
+    loop {
+        let count = DuplicateDetector::rehash_batch(db, REHASH_BATCH_SIZE).await.map_err(db_error)?;
+        if count == 0 {
+            break;
+        }
+        rehashed += count;
+        // Rows imported under an old version mid-run can push past the first count
+        let progress = RehashProgress { processed: rehashed, total: total.max(rehashed) };
+        if on_progress(&progress).is_break() {
+            break;
+        }
+    }
+
+    if rehashed > 0 {
+        tracing::info!(rehashed = rehashed, "Migrated transaction hashes");
     }
+    Ok(RehashResult {
+        hash_version: NewTransaction::HASH_VERSION,
+        rehashed,
+        remaining: DuplicateDetector::outdated_count(db).await.map_err(db_error)?,
+    })
 }
 
 // Tauri command handlers (extract pool from managed state)
@@ -128,25 +734,131 @@ pub async fn get_csv_headers(csv_content: String) -> Result<Vec<String>, String>
     })
 }
 
+#[tauri::command]
+pub async fn read_csv_file(path: String, encoding: Option<CsvEncoding>) -> Result<String, String> {
+    read_csv_file_impl(&path, encoding).map_err(|e| e.to_user_message())
+}
+
 #[tauri::command]
 pub async fn save_column_mapping(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     mapping: NewColumnMapping,
 ) -> Result<i64, String> {
-    save_column_mapping_impl(&db_pool.0, mapping)
+    let result = save_column_mapping_impl(&db_pool.pool(), mapping)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::ColumnMappings, ChangeAction::Created, [result]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn suggest_column_mapping(
+    db_pool: tauri::State<'_, DbPool>,
+    csv_content: String,
+) -> Result<MappingSuggestion, String> {
+    suggest_column_mapping_impl(&db_pool.pool(), &csv_content)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn detect_mapping_for_csv(
+    db_pool: tauri::State<'_, DbPool>,
+    csv_content: String,
+) -> Result<Option<DetectedMapping>, String> {
+    detect_mapping_for_csv_impl(&db_pool.pool(), &csv_content)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn preview_csv_import(
+    db_pool: tauri::State<'_, DbPool>,
+    account_id: i64,
+    csv_content: String,
+    mapping: ColumnMapping,
+    limit: Option<usize>,
+) -> Result<ImportPreview, String> {
+    preview_csv_import_impl(&db_pool.pool(), account_id, &csv_content, &mapping, limit)
         .await
         .map_err(|e| e.to_user_message())
 }
 
 #[tauri::command]
 pub async fn import_csv(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     account_id: i64,
     csv_content: String,
     mapping: ColumnMapping,
+    override_lock: Option<bool>,
 ) -> Result<ImportResult, String> {
-    // Rate limiting is enforced in import_csv_impl to prevent bypass
-    import_csv_impl(&db_pool.0, account_id, csv_content, mapping)
+    let override_lock = override_lock.unwrap_or(false);
+    // Rate limiting is enforced in import_csv_with_progress_impl to prevent bypass
+    let result = import_csv_with_progress_impl(&db_pool.pool(), account_id, csv_content, mapping, override_lock, |progress| {
+        if let Err(e) = app.emit(IMPORT_PROGRESS_EVENT, progress.clone()) {
+            tracing::warn!(error = %e, "Failed to emit import progress");
+        }
+    })
+    .await
+    .map_err(|e| e.to_user_message())?;
+    notify_import_changes(&app, result.import_batch_id);
+    Ok(result)
+}
+
+/// Tell listeners about the transactions and import batch an import added
+pub fn notify_import_changes(sink: &impl ChangeSink, import_batch_id: i64) {
+    notify_changed(sink, DataKind::Transactions, ChangeAction::Created, vec![]);
+    notify_changed(sink, DataKind::ImportBatches, ChangeAction::Created, [import_batch_id]);
+}
+
+#[tauri::command]
+pub async fn list_import_batches(
+    db_pool: tauri::State<'_, DbPool>,
+    account_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<ImportBatch>, String> {
+    list_import_batches_impl(&db_pool.pool(), account_id, limit)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn undo_import(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    import_batch_id: i64,
+    override_lock: Option<bool>,
+) -> Result<UndoImportResult, String> {
+    let result = undo_import_impl(&db_pool.pool(), import_batch_id, override_lock.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Deleted, vec![]);
+    notify_changed(&app, DataKind::ImportBatches, ChangeAction::Updated, [result.import_batch_id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_duplicate_settings(
+    db_pool: tauri::State<'_, DbPool>,
+    account_id: i64,
+) -> Result<DuplicateSettings, String> {
+    get_duplicate_settings_impl(&db_pool.pool(), account_id)
         .await
         .map_err(|e| e.to_user_message())
 }
+
+#[tauri::command]
+pub async fn update_duplicate_settings(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    account_id: i64,
+    settings: Option<DuplicateSettingsUpdate>,
+) -> Result<DuplicateSettings, String> {
+    let result = update_duplicate_settings_impl(&db_pool.pool(), account_id, settings)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Settings, ChangeAction::Updated, [account_id]);
+    Ok(result)
+}
@@ -0,0 +1,94 @@
+// Dashboard layout: which widgets appear, in what order, and their settings
+
+use crate::constants::{DEFAULT_DASHBOARD_LAYOUT, MAX_DASHBOARD_WIDGET_LIMIT, MAX_UPCOMING_BILL_DAYS};
+use crate::errors::sanitize_db_error;
+use crate::models::dashboard::{DashboardConfig, DashboardWidget, WidgetKind};
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::DbPool;
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+fn validate_widgets(widgets: &[DashboardWidget]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for widget in widgets {
+        if !seen.insert(widget.kind) {
+            return Err(format!("The {:?} widget appears more than once", widget.kind));
+        }
+        if let Some(limit) = widget.limit {
+            if !matches!(widget.kind, WidgetKind::Spending | WidgetKind::UpcomingBills) {
+                return Err(format!("The {:?} widget doesn't take a limit", widget.kind));
+            }
+            if !(1..=MAX_DASHBOARD_WIDGET_LIMIT).contains(&limit) {
+                return Err(format!("Widget limit must be between 1 and {}", MAX_DASHBOARD_WIDGET_LIMIT));
+            }
+        }
+        if let Some(days) = widget.days_ahead {
+            if widget.kind != WidgetKind::UpcomingBills {
+                return Err(format!("The {:?} widget doesn't take days_ahead", widget.kind));
+            }
+            if !(1..=MAX_UPCOMING_BILL_DAYS).contains(&days) {
+                return Err(format!("days_ahead must be between 1 and {}", MAX_UPCOMING_BILL_DAYS));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Business logic functions (used by both commands and tests)
+
+/// The saved dashboard layout, or the default one when none has been saved
+pub async fn get_dashboard_config_impl(db: &SqlitePool) -> Result<DashboardConfig, String> {
+    let row = sqlx::query_as::<_, (String, String)>("SELECT widgets, updated_at FROM dashboard_layouts WHERE name = ?")
+        .bind(DEFAULT_DASHBOARD_LAYOUT)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load dashboard layout"))?;
+
+    match row {
+        Some((widgets, updated_at)) => Ok(DashboardConfig {
+            widgets: serde_json::from_str(&widgets).map_err(|e| format!("Invalid dashboard layout: {}", e))?,
+            updated_at: Some(updated_at),
+        }),
+        None => Ok(DashboardConfig::default()),
+    }
+}
+
+/// Replace the dashboard layout; an empty list is a dashboard with only the period totals
+pub async fn save_dashboard_config_impl(
+    db: &SqlitePool,
+    widgets: Vec<DashboardWidget>,
+) -> Result<DashboardConfig, String> {
+    validate_widgets(&widgets)?;
+    let json = serde_json::to_string(&widgets).map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "INSERT INTO dashboard_layouts (name, widgets) VALUES (?, ?)
+         ON CONFLICT(name) DO UPDATE SET widgets = excluded.widgets, updated_at = CURRENT_TIMESTAMP"
+    )
+    .bind(DEFAULT_DASHBOARD_LAYOUT)
+    .bind(json)
+    .execute(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "save dashboard layout"))?;
+
+    get_dashboard_config_impl(db).await
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn get_dashboard_config(db_pool: tauri::State<'_, DbPool>) -> Result<DashboardConfig, String> {
+    get_dashboard_config_impl(&db_pool.pool()).await
+}
+
+#[tauri::command]
+pub async fn save_dashboard_config(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    widgets: Vec<DashboardWidget>,
+) -> Result<DashboardConfig, String> {
+    let config = save_dashboard_config_impl(&db_pool.pool(), widgets).await?;
+    // Also drops the cached dashboard summary, which was built for the old layout
+    notify_changed(&app, DataKind::Settings, ChangeAction::Updated, Vec::new());
+    Ok(config)
+}
@@ -0,0 +1,1052 @@
+// Full data export/import as one portable, versioned JSON archive
+// Used for backups, for moving data between installs, and for importing Mint/YNAB exports
+
+use crate::commands::category_commands::{is_valid_color, validate_rule_definition};
+use crate::constants::{BYTES_PER_MB, DEFAULT_CATEGORY_ID, MAX_DATA_ARCHIVE_SIZE, MAX_TRANSACTION_CENTS};
+use crate::errors::DataArchiveError;
+use crate::models::category_rule::{NewCategoryRule, RuleMatchType};
+use crate::models::debt::BalanceChange;
+use crate::models::data_archive::{
+    AppExportFormat, ArchiveConflict, ArchivedAccount, ArchivedCategory, ArchivedColumnMapping, ArchivedDebt, ArchivedDebtPayment,
+    ArchivedRule, ArchivedTarget, ArchivedTransaction, DataArchive, DataExportResult, DataImportResult, ImportCounts,
+    DATA_ARCHIVE_VERSION,
+};
+use crate::models::transaction::NewTransaction;
+use crate::services::balance_history::record_balance;
+use crate::services::duplicate_detector::DuplicateDetector;
+use crate::services::merchant_normalizer::MerchantNormalizer;
+use crate::services::period_locks::PeriodLocks;
+use crate::services::mint_importer::MintImporter;
+use crate::services::ynab_importer::YnabImporter;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates;
+use crate::utils::money::{self, Cents};
+use crate::DbPool;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::{HashMap, HashSet};
+
+/// A stored rule with names in place of IDs; match_type is still the stored text
+type RuleRow = (String, String, i32, String, Option<Cents>, Option<Cents>, Option<String>);
+
+fn db_error(e: sqlx::Error) -> DataArchiveError {
+    DataArchiveError::Database(e.to_string())
+}
+
+fn invalid(message: impl Into<String>) -> DataArchiveError {
+    DataArchiveError::Invalid(message.into())
+}
+
+fn valid_date(date: &str) -> bool {
+    dates::parse_date(date).is_ok()
+}
+
+/// Count an archived record against what it matched
+fn tally(counts: &mut ImportCounts, existed: bool, conflict: ArchiveConflict) {
+    match (existed, conflict) {
+        (false, _) => counts.created += 1,
+        (true, ArchiveConflict::Skip) => counts.skipped += 1,
+        (true, _) => counts.updated += 1,
+    }
+}
+
+/// ID of the record named `name`, from this import or already in the database
+async fn resolve(
+    conn: &mut SqliteConnection,
+    ids: &mut HashMap<String, i64>,
+    kind: &'static str,
+    table: &str,
+    name: &str,
+) -> Result<i64, DataArchiveError> {
+    let name = name.trim();
+    if let Some(id) = ids.get(name) {
+        return Ok(*id);
+    }
+
+    let id: Option<i64> = sqlx::query_scalar(&format!("SELECT id FROM {} WHERE name = ? ORDER BY id LIMIT 1", table))
+        .bind(name)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(db_error)?;
+    let id = id.ok_or_else(|| DataArchiveError::MissingReference { kind, name: name.to_string() })?;
+    ids.insert(name.to_string(), id);
+    Ok(id)
+}
+
+async fn resolve_optional(
+    conn: &mut SqliteConnection,
+    ids: &mut HashMap<String, i64>,
+    kind: &'static str,
+    table: &str,
+    name: Option<&str>,
+) -> Result<Option<i64>, DataArchiveError> {
+    match name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => resolve(conn, ids, kind, table, name).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Reject names that are empty or repeated within one section of the archive
+fn check_names<'a>(section: &str, names: impl Iterator<Item = &'a str>) -> Result<(), DataArchiveError> {
+    let mut seen = HashSet::new();
+    for name in names {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(invalid(format!("A name in {} is empty", section)));
+        }
+        if !seen.insert(name) {
+            return Err(invalid(format!("'{}' appears more than once in {}", name, section)));
+        }
+    }
+    Ok(())
+}
+
+/// Validate everything up front so a bad record doesn't leave a partial import
+/// Returns the rules' normalized patterns, in order
+fn validate_archive(archive: &DataArchive) -> Result<Vec<String>, DataArchiveError> {
+    check_names("accounts", archive.accounts.iter().map(|a| a.name.as_str()))?;
+    for account in &archive.accounts {
+        if !matches!(account.account_type.as_str(), "checking" | "savings" | "credit_card") {
+            return Err(invalid(format!("Account '{}' has unknown type '{}'", account.name, account.account_type)));
+        }
+        for day in [account.statement_closing_day, account.statement_due_day].into_iter().flatten() {
+            if !(1..=31).contains(&day) {
+                return Err(invalid(format!("Account '{}' has statement day {} (must be 1-31)", account.name, day)));
+            }
+        }
+    }
+
+    check_names("categories", archive.categories.iter().map(|c| c.name.as_str()))?;
+    for category in &archive.categories {
+        if let Some(color) = category.color.as_deref().filter(|c| !is_valid_color(c)) {
+            return Err(invalid(format!("Invalid color '{}' for category '{}'", color, category.name)));
+        }
+    }
+
+    let mut patterns = Vec::with_capacity(archive.category_rules.len());
+    for rule in &archive.category_rules {
+        let pattern = validate_rule_definition(&NewCategoryRule {
+            pattern: rule.pattern.clone(),
+            category_id: 0, // Resolved during import
+            priority: rule.priority,
+            match_type: rule.match_type,
+            min_amount_cents: rule.min_amount_cents,
+            max_amount_cents: rule.max_amount_cents,
+            account_id: None,
+        })
+        .map_err(|e| invalid(format!("Rule '{}': {}", rule.pattern, e)))?;
+        patterns.push(pattern);
+    }
+
+    for transaction in &archive.transactions {
+        if !valid_date(&transaction.date) {
+            return Err(invalid(format!("Transaction date '{}' is not YYYY-MM-DD", transaction.date)));
+        }
+        if transaction.amount_cents.abs() > MAX_TRANSACTION_CENTS {
+            return Err(invalid(format!(
+                "Transaction amount {} on {} is out of range",
+                money::from_cents(transaction.amount_cents),
+                transaction.date
+            )));
+        }
+        if transaction.description.trim().is_empty() {
+            return Err(invalid(format!("A transaction on {} has no description", transaction.date)));
+        }
+        if !matches!(transaction.status.as_str(), "pending" | "posted") {
+            return Err(invalid(format!("Transaction on {} has unknown status '{}'", transaction.date, transaction.status)));
+        }
+    }
+
+    for debt in &archive.debts {
+        if debt.name.trim().is_empty() {
+            return Err(invalid("A name in debts is empty"));
+        }
+        let original_balance = debt.original_balance_cents.unwrap_or(debt.balance_cents);
+        if debt.balance_cents < 0 || original_balance < 0 || debt.min_payment_cents < 0 {
+            return Err(invalid(format!("Debt '{}' has a negative amount", debt.name)));
+        }
+        if !(0.0..=100.0).contains(&debt.interest_rate) {
+            return Err(invalid(format!("Debt '{}' has interest rate {} (must be 0-100)", debt.name, debt.interest_rate)));
+        }
+        if let Some(percent) = debt.min_payment_percent {
+            if !(percent > 0.0 && percent <= 100.0) {
+                return Err(invalid(format!("Debt '{}' has minimum payment percentage {} (must be above 0, at most 100)", debt.name, percent)));
+            }
+        }
+        match debt.kind.as_str() {
+            "revolving" => {}
+            "loan" => {
+                if debt.term_months.is_none_or(|t| t <= 0) || !debt.loan_start_date.as_deref().is_some_and(valid_date) {
+                    return Err(invalid(format!("Loan '{}' needs a term and a YYYY-MM-DD start date", debt.name)));
+                }
+            }
+            other => return Err(invalid(format!("Debt '{}' has unknown kind '{}'", debt.name, other))),
+        }
+        if !(1..=31).contains(&debt.due_day) {
+            return Err(invalid(format!("Debt '{}' has due day {} (must be 1-31)", debt.name, debt.due_day)));
+        }
+    }
+
+    for payment in &archive.debt_payments {
+        if !valid_date(&payment.date) {
+            return Err(invalid(format!("Payment date '{}' is not YYYY-MM-DD", payment.date)));
+        }
+        if !(payment.amount_cents > 0 && payment.interest_cents >= 0 && payment.interest_cents <= payment.amount_cents) {
+            return Err(invalid(format!(
+                "Payment of {} to '{}' on {} is invalid",
+                money::from_cents(payment.amount_cents),
+                payment.debt,
+                payment.date
+            )));
+        }
+    }
+
+    for target in &archive.spending_targets {
+        let scoped = match target.scope.as_str() {
+            "category" => target.category.is_some() && target.account.is_none(),
+            "account" => target.account.is_some() && target.category.is_none(),
+            "overall" => target.category.is_none() && target.account.is_none(),
+            other => return Err(invalid(format!("Spending target has unknown scope '{}'", other))),
+        };
+        if !scoped {
+            return Err(invalid(format!("A {} spending target names the wrong kind of record", target.scope)));
+        }
+        if !matches!(target.period.as_str(), "monthly" | "quarterly" | "yearly") {
+            return Err(invalid(format!("Spending target has unknown period '{}'", target.period)));
+        }
+        if target.amount_cents <= 0 {
+            return Err(invalid(format!("Spending target amount {} must be positive", money::from_cents(target.amount_cents))));
+        }
+        let dates_valid = valid_date(&target.start_date)
+            && target.end_date.as_deref().is_none_or(|end| valid_date(end) && end >= target.start_date.as_str());
+        if !dates_valid {
+            return Err(invalid(format!("Spending target starting '{}' has invalid dates", target.start_date)));
+        }
+    }
+
+    check_names("column mappings", archive.column_mappings.iter().map(|m| m.source_name.as_str()))?;
+
+    Ok(patterns)
+}
+
+// Business logic functions (used by both commands and tests)
+
+/// Everything in the database that the archive format covers
+pub async fn collect_data_archive(db: &SqlitePool) -> Result<DataArchive, DataArchiveError> {
+    let accounts = sqlx::query_as::<_, ArchivedAccount>(
+        "SELECT name, type, balance_cents, statement_closing_day, statement_due_day, archived FROM accounts ORDER BY id"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(db_error)?;
+
+    let categories = sqlx::query_as::<_, ArchivedCategory>(
+        "SELECT c.name, p.name AS parent, c.icon, c.color, c.archived, c.tax_deductible
+         FROM categories c LEFT JOIN categories p ON p.id = c.parent_id
+         ORDER BY c.id"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(db_error)?;
+
+    let rules: Vec<RuleRow> = sqlx::query_as(
+        "SELECT r.pattern, c.name, r.priority, r.match_type, r.min_amount_cents, r.max_amount_cents, a.name
+         FROM category_rules r
+         JOIN categories c ON c.id = r.category_id
+         LEFT JOIN accounts a ON a.id = r.account_id
+         ORDER BY r.id"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(db_error)?;
+    let category_rules = rules
+        .into_iter()
+        .map(|(pattern, category, priority, match_type, min_amount_cents, max_amount_cents, account)| ArchivedRule {
+            pattern,
+            category,
+            priority,
+            match_type: match_type.parse::<RuleMatchType>().unwrap_or_default(),
+            min_amount_cents,
+            max_amount_cents,
+            account,
+        })
+        .collect();
+
+    let transactions = sqlx::query_as::<_, ArchivedTransaction>(
+        "SELECT a.name AS account, c.name AS category, t.date, t.amount_cents, t.description, t.merchant, t.notes,
+                t.original_currency, t.original_amount_cents, t.status, m.name AS member, t.tax_deductible
+         FROM transactions t
+         JOIN accounts a ON a.id = t.account_id
+         LEFT JOIN categories c ON c.id = t.category_id
+         LEFT JOIN members m ON m.id = t.member_id
+         ORDER BY t.date, t.id"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(db_error)?;
+
+    let debts = sqlx::query_as::<_, ArchivedDebt>(
+        "SELECT d.name, d.balance_cents, d.original_balance_cents, d.interest_rate, d.min_payment_cents, d.min_payment_percent,
+                d.kind, d.term_months, d.loan_start_date, d.due_day, a.name AS account
+         FROM debts d LEFT JOIN accounts a ON a.id = d.account_id
+         ORDER BY d.id"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(db_error)?;
+
+    let debt_payments = sqlx::query_as::<_, ArchivedDebtPayment>(
+        "SELECT d.name AS debt, p.amount_cents, p.date, p.interest_cents, p.principal_cents
+         FROM debt_payments p JOIN debts d ON d.id = p.debt_id
+         ORDER BY p.date, p.id"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(db_error)?;
+
+    let spending_targets = sqlx::query_as::<_, ArchivedTarget>(
+        "SELECT t.scope, c.name AS category, a.name AS account, t.amount_cents, t.period, t.start_date, t.end_date
+         FROM spending_targets t
+         LEFT JOIN categories c ON c.id = t.category_id
+         LEFT JOIN accounts a ON a.id = t.account_id
+         ORDER BY t.start_date, t.id"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(db_error)?;
+
+    let column_mappings = sqlx::query_as::<_, ArchivedColumnMapping>(
+        "SELECT m.source_name, m.date_col, m.amount_col, m.description_col, m.merchant_col, m.debit_col, m.credit_col,
+                m.status_col, m.member_col, m.invert_amounts, m.date_format, m.decimal_separator, m.header_fingerprint,
+                a.name AS account
+         FROM column_mappings m
+         LEFT JOIN accounts a ON a.id = m.account_id
+         ORDER BY m.id"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(db_error)?;
+
+    Ok(DataArchive {
+        version: DATA_ARCHIVE_VERSION,
+        exported_at: Some(chrono::Utc::now().to_rfc3339()),
+        accounts,
+        categories,
+        category_rules,
+        transactions,
+        debts,
+        debt_payments,
+        spending_targets,
+        column_mappings,
+    })
+}
+
+pub async fn export_all_data_impl(db: &SqlitePool, output_path: &str) -> Result<DataExportResult, DataArchiveError> {
+    let archive = collect_data_archive(db).await?;
+    let json = serde_json::to_string_pretty(&archive).map_err(|e| DataArchiveError::Io(e.to_string()))?;
+    std::fs::write(output_path, json).map_err(|e| DataArchiveError::Io(e.to_string()))?;
+
+    Ok(DataExportResult {
+        path: output_path.to_string(),
+        accounts: archive.accounts.len(),
+        categories: archive.categories.len(),
+        category_rules: archive.category_rules.len(),
+        transactions: archive.transactions.len(),
+        debts: archive.debts.len(),
+        debt_payments: archive.debt_payments.len(),
+        spending_targets: archive.spending_targets.len(),
+        column_mappings: archive.column_mappings.len(),
+    })
+}
+
+pub fn parse_data_archive(content: &str) -> Result<DataArchive, DataArchiveError> {
+    if content.len() > MAX_DATA_ARCHIVE_SIZE {
+        return Err(DataArchiveError::TooLarge { max_mb: MAX_DATA_ARCHIVE_SIZE / BYTES_PER_MB });
+    }
+
+    let archive: DataArchive = serde_json::from_str(content).map_err(|e| invalid(e.to_string()))?;
+    if archive.version > DATA_ARCHIVE_VERSION {
+        return Err(DataArchiveError::UnsupportedVersion { found: archive.version, max: DATA_ARCHIVE_VERSION });
+    }
+    Ok(archive)
+}
+
+/// Write an archive into the database in a single transaction, matching existing records by
+/// name (transactions by account, date, amount and description). Adding or changing a
+/// transaction in a locked month fails the whole restore unless `override_lock` is set
+pub async fn restore_data_archive(
+    db: &SqlitePool,
+    archive: &DataArchive,
+    conflict: ArchiveConflict,
+    override_lock: bool,
+) -> Result<DataImportResult, DataArchiveError> {
+    let patterns = validate_archive(archive)?;
+    let locks = if override_lock { PeriodLocks::default() } else { PeriodLocks::load(db).await.map_err(db_error)? };
+
+    // Resolved before the transaction opens so it never waits on our own write lock
+    let mut merchant_ids: HashMap<&str, Option<i64>> = HashMap::new();
+    for merchant in archive.transactions.iter().filter_map(|t| t.merchant.as_deref()) {
+        if !merchant_ids.contains_key(merchant) {
+            let id = MerchantNormalizer::resolve(db, merchant).await.map_err(DataArchiveError::Database)?;
+            merchant_ids.insert(merchant, id);
+        }
+    }
+    let today = dates::today(db).await;
+
+    let mut result = DataImportResult::default();
+    let mut account_ids: HashMap<String, i64> = HashMap::new();
+    let mut category_ids: HashMap<String, i64> = HashMap::new();
+    let mut debt_ids: HashMap<String, i64> = HashMap::new();
+    let mut debt_occurrences: HashMap<String, i64> = HashMap::new();
+    let mut member_ids: HashMap<String, i64> = HashMap::new(); // Keyed on the lowercased name
+    let mut tx = db.begin().await.map_err(db_error)?;
+
+    for account in &archive.accounts {
+        let name = account.name.trim();
+        let existing: Option<i64> = sqlx::query_scalar("SELECT id FROM accounts WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        let id = match existing {
+            None => sqlx::query(
+                "INSERT INTO accounts (name, type, balance_cents, statement_closing_day, statement_due_day, archived)
+                 VALUES (?, ?, ?, ?, ?, ?)"
+            )
+            .bind(name)
+            .bind(&account.account_type)
+            .bind(account.balance_cents)
+            .bind(account.statement_closing_day)
+            .bind(account.statement_due_day)
+            .bind(account.archived)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?
+            .last_insert_rowid(),
+            Some(id) => {
+                let update = match conflict {
+                    ArchiveConflict::Skip => None,
+                    ArchiveConflict::Overwrite => Some(
+                        sqlx::query(
+                            "UPDATE accounts SET type = ?, balance_cents = ?, statement_closing_day = ?, statement_due_day = ?,
+                                archived = ?, updated_at = CURRENT_TIMESTAMP
+                             WHERE id = ?"
+                        )
+                        .bind(&account.account_type)
+                        .bind(account.balance_cents)
+                        .bind(account.statement_closing_day)
+                        .bind(account.statement_due_day)
+                        .bind(account.archived)
+                        .bind(id),
+                    ),
+                    ArchiveConflict::Merge => Some(
+                        sqlx::query(
+                            "UPDATE accounts SET statement_closing_day = COALESCE(statement_closing_day, ?),
+                                statement_due_day = COALESCE(statement_due_day, ?), updated_at = CURRENT_TIMESTAMP
+                             WHERE id = ?"
+                        )
+                        .bind(account.statement_closing_day)
+                        .bind(account.statement_due_day)
+                        .bind(id),
+                    ),
+                };
+                if let Some(update) = update {
+                    update.execute(&mut *tx).await.map_err(db_error)?;
+                }
+                id
+            }
+        };
+        tally(&mut result.accounts, existing.is_some(), conflict);
+        account_ids.insert(name.to_string(), id);
+    }
+
+    // Parents before children; a pass that places nothing means the parents form a cycle
+    let archive_categories: HashSet<&str> = archive.categories.iter().map(|c| c.name.trim()).collect();
+    let mut pending: Vec<&ArchivedCategory> = archive.categories.iter().collect();
+    while !pending.is_empty() {
+        let mut waiting = Vec::new();
+        for category in &pending {
+            let parent = category.parent.as_deref().map(str::trim).filter(|p| !p.is_empty());
+            if parent.is_some_and(|p| archive_categories.contains(p) && !category_ids.contains_key(p)) {
+                waiting.push(*category);
+                continue;
+            }
+
+            let name = category.name.trim();
+            let parent_id = resolve_optional(&mut tx, &mut category_ids, "Category", "categories", parent).await?;
+            let existing: Option<i64> = sqlx::query_scalar("SELECT id FROM categories WHERE name = ?")
+                .bind(name)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(db_error)?;
+
+            let id = match existing {
+                None => sqlx::query(
+                    "INSERT INTO categories (name, type, parent_id, icon, color, archived, tax_deductible)
+                     VALUES (?, 'custom', ?, ?, ?, ?, ?)"
+                )
+                .bind(name)
+                .bind(parent_id)
+                .bind(&category.icon)
+                .bind(&category.color)
+                .bind(category.archived)
+                .bind(category.tax_deductible)
+                .execute(&mut *tx)
+                .await
+                .map_err(db_error)?
+                .last_insert_rowid(),
+                Some(id) => {
+                    let update = match conflict {
+                        ArchiveConflict::Skip => None,
+                        ArchiveConflict::Overwrite => Some(
+                            sqlx::query(
+                                "UPDATE categories SET parent_id = ?, icon = ?, color = ?, archived = ?, tax_deductible = ?
+                                 WHERE id = ?"
+                            )
+                            .bind(parent_id)
+                            .bind(&category.icon)
+                            .bind(&category.color)
+                            .bind(category.archived)
+                            .bind(category.tax_deductible)
+                            .bind(id),
+                        ),
+                        ArchiveConflict::Merge => Some(
+                            sqlx::query(
+                                "UPDATE categories SET parent_id = COALESCE(parent_id, ?), icon = COALESCE(icon, ?),
+                                    color = COALESCE(color, ?)
+                                 WHERE id = ?"
+                            )
+                            .bind(parent_id)
+                            .bind(&category.icon)
+                            .bind(&category.color)
+                            .bind(id),
+                        ),
+                    };
+                    if let Some(update) = update {
+                        update.execute(&mut *tx).await.map_err(db_error)?;
+                    }
+                    id
+                }
+            };
+            tally(&mut result.categories, existing.is_some(), conflict);
+            category_ids.insert(name.to_string(), id);
+        }
+
+        if waiting.len() == pending.len() {
+            let names: Vec<&str> = waiting.iter().map(|c| c.name.trim()).collect();
+            return Err(invalid(format!("Categories {} are each other's parents", names.join(", "))));
+        }
+        pending = waiting;
+    }
+
+    for mapping in &archive.column_mappings {
+        let name = mapping.source_name.trim();
+        let account_id = resolve_optional(&mut tx, &mut account_ids, "Account", "accounts", mapping.account.as_deref()).await?;
+        let existing: Option<i64> = sqlx::query_scalar("SELECT id FROM column_mappings WHERE source_name = ?")
+            .bind(name)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        let statement = match (existing, conflict) {
+            (None, _) => Some(sqlx::query(
+                "INSERT INTO column_mappings (date_col, amount_col, description_col, merchant_col, debit_col, credit_col,
+                    status_col, member_col, invert_amounts, date_format, header_fingerprint, account_id, decimal_separator,
+                    source_name)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            )),
+            (Some(_), ArchiveConflict::Skip) => None,
+            (Some(_), ArchiveConflict::Overwrite) => Some(sqlx::query(
+                "UPDATE column_mappings SET date_col = ?, amount_col = ?, description_col = ?, merchant_col = ?,
+                    debit_col = ?, credit_col = ?, status_col = ?, member_col = ?, invert_amounts = ?, date_format = ?,
+                    header_fingerprint = ?, account_id = ?, decimal_separator = ?
+                 WHERE source_name = ?"
+            )),
+            // Columns the mapping needs are always present; only the optional ones can be filled in
+            (Some(_), ArchiveConflict::Merge) => Some(sqlx::query(
+                "UPDATE column_mappings SET date_col = ?1, amount_col = ?2, description_col = ?3,
+                    merchant_col = COALESCE(merchant_col, ?4), debit_col = COALESCE(debit_col, ?5),
+                    credit_col = COALESCE(credit_col, ?6), status_col = COALESCE(status_col, ?7),
+                    member_col = COALESCE(member_col, ?8), invert_amounts = ?9,
+                    date_format = COALESCE(date_format, ?10), header_fingerprint = COALESCE(header_fingerprint, ?11),
+                    account_id = COALESCE(account_id, ?12), decimal_separator = COALESCE(decimal_separator, ?13)
+                 WHERE source_name = ?14"
+            )),
+        };
+        if let Some(statement) = statement {
+            statement
+                .bind(&mapping.date_col)
+                .bind(&mapping.amount_col)
+                .bind(&mapping.description_col)
+                .bind(&mapping.merchant_col)
+                .bind(&mapping.debit_col)
+                .bind(&mapping.credit_col)
+                .bind(&mapping.status_col)
+                .bind(&mapping.member_col)
+                .bind(mapping.invert_amounts)
+                .bind(&mapping.date_format)
+                .bind(&mapping.header_fingerprint)
+                .bind(account_id)
+                .bind(&mapping.decimal_separator)
+                .bind(name)
+                .execute(&mut *tx)
+                .await
+                .map_err(db_error)?;
+        }
+        tally(&mut result.column_mappings, existing.is_some(), conflict);
+    }
+
+    for (rule, pattern) in archive.category_rules.iter().zip(&patterns) {
+        let category_id = resolve(&mut tx, &mut category_ids, "Category", "categories", &rule.category).await?;
+        let account_id = resolve_optional(&mut tx, &mut account_ids, "Account", "accounts", rule.account.as_deref()).await?;
+        let match_type = rule.match_type.to_string();
+        let existing: Option<i64> = sqlx::query_scalar(
+            "SELECT id FROM category_rules
+             WHERE pattern = ? AND category_id = ? AND match_type = ? AND account_id IS ? AND min_amount_cents IS ? AND max_amount_cents IS ?"
+        )
+        .bind(pattern)
+        .bind(category_id)
+        .bind(&match_type)
+        .bind(account_id)
+        .bind(rule.min_amount_cents)
+        .bind(rule.max_amount_cents)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        match (existing, conflict) {
+            (None, _) => {
+                sqlx::query(
+                    "INSERT INTO category_rules (pattern, category_id, priority, match_type, min_amount_cents, max_amount_cents, account_id)
+                     VALUES (?, ?, ?, ?, ?, ?, ?)"
+                )
+                .bind(pattern)
+                .bind(category_id)
+                .bind(rule.priority)
+                .bind(&match_type)
+                .bind(rule.min_amount_cents)
+                .bind(rule.max_amount_cents)
+                .bind(account_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(db_error)?;
+                result.category_rules.created += 1;
+            }
+            // Only the priority can differ, and there's nothing missing to merge
+            (Some(id), ArchiveConflict::Overwrite) => {
+                sqlx::query("UPDATE category_rules SET priority = ? WHERE id = ?")
+                    .bind(rule.priority)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(db_error)?;
+                result.category_rules.updated += 1;
+            }
+            (Some(_), _) => result.category_rules.skipped += 1,
+        }
+    }
+
+    for debt in &archive.debts {
+        let name = debt.name.trim();
+        let account_id = resolve_optional(&mut tx, &mut account_ids, "Account", "accounts", debt.account.as_deref()).await?;
+        let original_balance = debt.original_balance_cents.unwrap_or(debt.balance_cents);
+        // Debt names needn't be unique, so the nth debt with a name matches the nth one stored
+        let occurrence = debt_occurrences.entry(name.to_string()).or_insert(0);
+        let existing: Option<i64> = sqlx::query_scalar("SELECT id FROM debts WHERE name = ? ORDER BY id LIMIT 1 OFFSET ?")
+            .bind(name)
+            .bind(*occurrence)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        let id = match existing {
+            None => sqlx::query(
+                "INSERT INTO debts (name, balance_cents, original_balance_cents, interest_rate, min_payment_cents, min_payment_percent, kind,
+                    term_months, loan_start_date, due_day, account_id)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(name)
+            .bind(debt.balance_cents)
+            .bind(original_balance)
+            .bind(debt.interest_rate)
+            .bind(debt.min_payment_cents)
+            .bind(debt.min_payment_percent)
+            .bind(&debt.kind)
+            .bind(debt.term_months)
+            .bind(&debt.loan_start_date)
+            .bind(debt.due_day)
+            .bind(account_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?
+            .last_insert_rowid(),
+            Some(id) => {
+                let update = match conflict {
+                    ArchiveConflict::Skip => None,
+                    ArchiveConflict::Overwrite => Some(
+                        sqlx::query(
+                            "UPDATE debts SET balance_cents = ?, original_balance_cents = ?, interest_rate = ?, min_payment_cents = ?,
+                                min_payment_percent = ?, kind = ?, term_months = ?, loan_start_date = ?, due_day = ?,
+                                account_id = ?, updated_at = CURRENT_TIMESTAMP
+                             WHERE id = ?"
+                        )
+                        .bind(debt.balance_cents)
+                        .bind(original_balance)
+                        .bind(debt.interest_rate)
+                        .bind(debt.min_payment_cents)
+                        .bind(debt.min_payment_percent)
+                        .bind(&debt.kind)
+                        .bind(debt.term_months)
+                        .bind(&debt.loan_start_date)
+                        .bind(debt.due_day)
+                        .bind(account_id)
+                        .bind(id),
+                    ),
+                    ArchiveConflict::Merge => Some(
+                        sqlx::query(
+                            "UPDATE debts SET account_id = COALESCE(account_id, ?), updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+                        )
+                        .bind(account_id)
+                        .bind(id),
+                    ),
+                };
+                if let Some(update) = update {
+                    update.execute(&mut *tx).await.map_err(db_error)?;
+                }
+                id
+            }
+        };
+        if existing.is_none() || matches!(conflict, ArchiveConflict::Overwrite) {
+            record_balance(&mut *tx, id, debt.balance_cents, today, BalanceChange::Import).await.map_err(db_error)?;
+        }
+        tally(&mut result.debts, existing.is_some(), conflict);
+        // Payments go to the first debt with their debt's name
+        if *occurrence == 0 {
+            debt_ids.insert(name.to_string(), id);
+        }
+        *occurrence += 1;
+    }
+
+    for payment in &archive.debt_payments {
+        let debt_id = resolve(&mut tx, &mut debt_ids, "Debt", "debts", &payment.debt).await?;
+        let principal = payment.principal_cents.unwrap_or(payment.amount_cents - payment.interest_cents);
+        let existing: Option<i64> =
+            sqlx::query_scalar("SELECT id FROM debt_payments WHERE debt_id = ? AND date = ? AND amount_cents = ?")
+                .bind(debt_id)
+                .bind(&payment.date)
+                .bind(payment.amount_cents)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(db_error)?;
+
+        match (existing, conflict) {
+            (None, _) => {
+                sqlx::query("INSERT INTO debt_payments (debt_id, amount_cents, date, interest_cents, principal_cents) VALUES (?, ?, ?, ?, ?)")
+                    .bind(debt_id)
+                    .bind(payment.amount_cents)
+                    .bind(&payment.date)
+                    .bind(payment.interest_cents)
+                    .bind(principal)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(db_error)?;
+                result.debt_payments.created += 1;
+            }
+            (Some(id), ArchiveConflict::Overwrite) => {
+                sqlx::query("UPDATE debt_payments SET interest_cents = ?, principal_cents = ? WHERE id = ?")
+                    .bind(payment.interest_cents)
+                    .bind(principal)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(db_error)?;
+                result.debt_payments.updated += 1;
+            }
+            (Some(_), _) => result.debt_payments.skipped += 1,
+        }
+    }
+
+    for target in &archive.spending_targets {
+        let category_id =
+            resolve_optional(&mut tx, &mut category_ids, "Category", "categories", target.category.as_deref()).await?;
+        let account_id = resolve_optional(&mut tx, &mut account_ids, "Account", "accounts", target.account.as_deref()).await?;
+        let existing: Option<i64> = sqlx::query_scalar(
+            "SELECT id FROM spending_targets
+             WHERE scope = ? AND category_id IS ? AND account_id IS ? AND period = ? AND start_date = ?"
+        )
+        .bind(&target.scope)
+        .bind(category_id)
+        .bind(account_id)
+        .bind(&target.period)
+        .bind(&target.start_date)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        let statement = match (existing, conflict) {
+            (None, _) => Some(
+                sqlx::query(
+                    "INSERT INTO spending_targets (scope, category_id, account_id, amount_cents, period, start_date, end_date)
+                     VALUES (?, ?, ?, ?, ?, ?, ?)"
+                )
+                .bind(&target.scope)
+                .bind(category_id)
+                .bind(account_id)
+                .bind(target.amount_cents)
+                .bind(&target.period)
+                .bind(&target.start_date)
+                .bind(&target.end_date),
+            ),
+            (Some(_), ArchiveConflict::Skip) => None,
+            (Some(id), ArchiveConflict::Overwrite) => Some(
+                sqlx::query("UPDATE spending_targets SET amount_cents = ?, end_date = ? WHERE id = ?")
+                    .bind(target.amount_cents)
+                    .bind(&target.end_date)
+                    .bind(id),
+            ),
+            (Some(id), ArchiveConflict::Merge) => Some(
+                sqlx::query("UPDATE spending_targets SET end_date = COALESCE(end_date, ?) WHERE id = ?")
+                    .bind(&target.end_date)
+                    .bind(id),
+            ),
+        };
+        if let Some(statement) = statement {
+            statement.execute(&mut *tx).await.map_err(db_error)?;
+        }
+        tally(&mut result.spending_targets, existing.is_some(), conflict);
+    }
+
+    let mut transaction_occurrences: HashMap<(i64, String, Cents, String), i64> = HashMap::new();
+    for transaction in &archive.transactions {
+        let account_id = resolve(&mut tx, &mut account_ids, "Account", "accounts", &transaction.account).await?;
+        let category_id =
+            resolve_optional(&mut tx, &mut category_ids, "Category", "categories", transaction.category.as_deref())
+                .await?
+                .unwrap_or(DEFAULT_CATEGORY_ID);
+        let merchant_id = transaction.merchant.as_deref().and_then(|m| merchant_ids.get(m).copied().flatten());
+        let member_id = match transaction.member.as_deref().map(str::trim).filter(|m| !m.is_empty()) {
+            Some(name) => Some(match member_ids.get(&name.to_lowercase()) {
+                Some(&id) => id,
+                None => {
+                    // Member names are unique ignoring case, so "alex" finds "Alex"
+                    sqlx::query("INSERT OR IGNORE INTO members (name) VALUES (?)")
+                        .bind(name)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(db_error)?;
+                    let id: i64 = sqlx::query_scalar("SELECT id FROM members WHERE name = ?")
+                        .bind(name)
+                        .fetch_one(&mut *tx)
+                        .await
+                        .map_err(db_error)?;
+                    member_ids.insert(name.to_lowercase(), id);
+                    id
+                }
+            }),
+            None => None,
+        };
+        let description = transaction.description.trim();
+        // Same-day repeats (two coffees) are real, so the nth copy matches the nth one stored
+        let key = (account_id, transaction.date.clone(), transaction.amount_cents, description.to_string());
+        let occurrence = transaction_occurrences.entry(key).or_insert(0);
+        let existing: Option<i64> = sqlx::query_scalar(
+            "SELECT id FROM transactions WHERE account_id = ? AND date = ? AND amount_cents = ? AND description = ?
+             ORDER BY id LIMIT 1 OFFSET ?"
+        )
+        .bind(account_id)
+        .bind(&transaction.date)
+        .bind(transaction.amount_cents)
+        .bind(description)
+        .bind(*occurrence)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        *occurrence += 1;
+
+        // Repeats are kept, so a transaction may need a hash other than its own
+        let hash = match existing {
+            None => {
+                let hash = NewTransaction::calculate_hash(account_id, &transaction.date, transaction.amount_cents, description);
+                DuplicateDetector::free_hash(&mut *tx, &hash, &HashSet::new())
+                    .await
+                    .map_err(|e| DataArchiveError::Database(e.to_string()))?
+            }
+            Some(_) => String::new(),
+        };
+        let statement = match (existing, conflict) {
+            (None, _) => Some(
+                sqlx::query(
+                    "INSERT INTO transactions (account_id, category_id, date, amount_cents, description, merchant, merchant_id,
+                        notes, original_currency, original_amount_cents, status, member_id, tax_deductible, hash, hash_version)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+                )
+                .bind(account_id)
+                .bind(category_id)
+                .bind(&transaction.date)
+                .bind(transaction.amount_cents)
+                .bind(description)
+                .bind(&transaction.merchant)
+                .bind(merchant_id)
+                .bind(&transaction.notes)
+                .bind(&transaction.original_currency)
+                .bind(transaction.original_amount_cents)
+                .bind(&transaction.status)
+                .bind(member_id)
+                .bind(transaction.tax_deductible)
+                .bind(&hash)
+                .bind(NewTransaction::HASH_VERSION),
+            ),
+            (Some(_), ArchiveConflict::Skip) => None,
+            (Some(id), ArchiveConflict::Overwrite) => Some(
+                sqlx::query(
+                    "UPDATE transactions SET category_id = ?, merchant = ?, merchant_id = ?, notes = ?,
+                        original_currency = ?, original_amount_cents = ?, status = ?, member_id = ?, tax_deductible = ?,
+                        updated_at = CURRENT_TIMESTAMP
+                     WHERE id = ?"
+                )
+                .bind(category_id)
+                .bind(&transaction.merchant)
+                .bind(merchant_id)
+                .bind(&transaction.notes)
+                .bind(&transaction.original_currency)
+                .bind(transaction.original_amount_cents)
+                .bind(&transaction.status)
+                .bind(member_id)
+                .bind(transaction.tax_deductible)
+                .bind(id),
+            ),
+            // An uncategorized transaction takes the archive's category; anything else keeps its own
+            (Some(id), ArchiveConflict::Merge) => Some(
+                sqlx::query(
+                    "UPDATE transactions SET category_id = CASE WHEN category_id = ? THEN ? ELSE category_id END,
+                        merchant = COALESCE(merchant, ?), merchant_id = COALESCE(merchant_id, ?), notes = COALESCE(notes, ?),
+                        original_currency = COALESCE(original_currency, ?), original_amount_cents = COALESCE(original_amount_cents, ?),
+                        member_id = COALESCE(member_id, ?), tax_deductible = COALESCE(tax_deductible, ?),
+                        updated_at = CURRENT_TIMESTAMP
+                     WHERE id = ?"
+                )
+                .bind(DEFAULT_CATEGORY_ID)
+                .bind(category_id)
+                .bind(&transaction.merchant)
+                .bind(merchant_id)
+                .bind(&transaction.notes)
+                .bind(&transaction.original_currency)
+                .bind(transaction.original_amount_cents)
+                .bind(member_id)
+                .bind(transaction.tax_deductible)
+                .bind(id),
+            ),
+        };
+        if let Some(statement) = statement {
+            // Skipped duplicates leave the month untouched, so only real writes are blocked
+            if locks.is_locked(&transaction.date) {
+                return Err(DataArchiveError::PeriodLocked(PeriodLocks::month_of(&transaction.date).to_string()));
+            }
+            statement.execute(&mut *tx).await.map_err(db_error)?;
+        }
+        tally(&mut result.transactions, existing.is_some(), conflict);
+    }
+
+    tx.commit().await.map_err(db_error)?;
+    Ok(result)
+}
+
+pub async fn import_all_data_impl(
+    db: &SqlitePool,
+    input_path: &str,
+    conflict: ArchiveConflict,
+    override_lock: bool,
+) -> Result<DataImportResult, DataArchiveError> {
+    let size = std::fs::metadata(input_path).map_err(|e| DataArchiveError::Io(e.to_string()))?.len();
+    if size > MAX_DATA_ARCHIVE_SIZE as u64 {
+        return Err(DataArchiveError::TooLarge { max_mb: MAX_DATA_ARCHIVE_SIZE / BYTES_PER_MB });
+    }
+    let content = std::fs::read_to_string(input_path).map_err(|e| DataArchiveError::Io(e.to_string()))?;
+    let archive = parse_data_archive(&content)?;
+    restore_data_archive(db, &archive, conflict, override_lock).await
+}
+
+/// Import another budgeting app's transaction export, creating its accounts and categories as needed
+pub async fn import_from_app_impl(
+    db: &SqlitePool,
+    format: AppExportFormat,
+    csv_content: &str,
+    conflict: ArchiveConflict,
+    override_lock: bool,
+) -> Result<DataImportResult, DataArchiveError> {
+    // Whole histories are expected, so this uses the archive limit rather than the CSV one
+    if csv_content.len() > MAX_DATA_ARCHIVE_SIZE {
+        return Err(DataArchiveError::TooLarge { max_mb: MAX_DATA_ARCHIVE_SIZE / BYTES_PER_MB });
+    }
+
+    let archive = match format {
+        AppExportFormat::Mint => MintImporter::parse(csv_content),
+        AppExportFormat::Ynab => YnabImporter::parse(csv_content),
+    }
+    .map_err(|e| invalid(e.to_string()))?;
+    restore_data_archive(db, &archive, conflict, override_lock).await
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn export_all_data(db_pool: tauri::State<'_, DbPool>, output_path: String) -> Result<DataExportResult, String> {
+    export_all_data_impl(&db_pool.pool(), &output_path)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn import_all_data(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    input_path: String,
+    conflict: Option<ArchiveConflict>,
+    override_lock: Option<bool>,
+) -> Result<DataImportResult, String> {
+    let result = import_all_data_impl(
+        &db_pool.pool(),
+        &input_path,
+        conflict.unwrap_or_default(),
+        override_lock.unwrap_or(false),
+    )
+        .await
+        .map_err(|e| e.to_user_message())?;
+    for kind in [
+        DataKind::Accounts,
+        DataKind::Categories,
+        DataKind::CategoryRules,
+        DataKind::ColumnMappings,
+        DataKind::Debts,
+        DataKind::SpendingTargets,
+        DataKind::Transactions,
+        DataKind::Merchants,
+    ] {
+        notify_changed(&app, kind, ChangeAction::Created, vec![]);
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn import_from_app(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    format: AppExportFormat,
+    csv_content: String,
+    conflict: Option<ArchiveConflict>,
+    override_lock: Option<bool>,
+) -> Result<DataImportResult, String> {
+    let result = import_from_app_impl(
+        &db_pool.pool(),
+        format,
+        &csv_content,
+        conflict.unwrap_or_default(),
+        override_lock.unwrap_or(false),
+    )
+        .await
+        .map_err(|e| e.to_user_message())?;
+    for kind in [DataKind::Accounts, DataKind::Categories, DataKind::Transactions, DataKind::Merchants] {
+        notify_changed(&app, kind, ChangeAction::Created, vec![]);
+    }
+    Ok(result)
+}
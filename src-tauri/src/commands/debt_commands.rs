@@ -1,10 +1,27 @@
-use crate::constants::{MAX_INTEREST_RATE, MIN_INTEREST_RATE};
+use crate::constants::{
+    DEFAULT_PAYMENT_INCREMENTS, MAX_DEBT_DUE_DAY, MAX_INTEREST_RATE, MAX_LOAN_TERM_MONTHS, MAX_PAYOFF_SCENARIOS,
+    MAX_UPCOMING_PAYMENT_MONTHS, MIN_INTEREST_RATE,
+};
+use crate::commands::account_commands::get_statement_summary_impl;
 use crate::errors::DebtError;
-use crate::models::debt::{Debt, DebtPayment, NewDebt};
-use crate::services::avalanche_calculator::AvalancheCalculator;
+use crate::models::account::AccountType;
+use crate::models::audit_log::AuditEntity;
+use crate::models::debt::{BalanceChange, Debt, DebtAutopay, DebtKind, DebtPayment, NewDebt, NewLoan};
+use crate::services::amortization::{self, AmortizationSchedule};
+use crate::services::audit_log::AuditRecord;
+use crate::services::balance_history::record_balance;
+use crate::services::avalanche_calculator::{AvalancheCalculator, PayoffPlan};
+use crate::services::interest_calculator::split_payment;
+use crate::services::payment_scheduler::{PaymentSchedule, PaymentScheduler};
 use crate::services::snowball_calculator::SnowballCalculator;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates;
+use crate::utils::money::{self, Cents};
+use crate::utils::query_builder::UpdateBuilder;
 use crate::DbPool;
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::SqlitePool;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,7 +29,8 @@ pub struct PayoffPlanResponse {
     pub plan_id: i64,
     pub strategy: String,
     pub payoff_date: String,
-    pub total_interest: f64,
+    #[serde(rename = "total_interest", with = "money::dollars")]
+    pub total_interest_cents: Cents,
     pub monthly_breakdown: Vec<MonthlyPaymentResponse>,
     pub debt_summaries: Vec<DebtSummaryResponse>,
 }
@@ -22,15 +40,18 @@ pub struct MonthlyPaymentResponse {
     pub month: i32,
     pub date: String,
     pub payments: Vec<DebtPaymentDetailResponse>,
-    pub total_paid: f64,
-    pub remaining_balance: f64,
+    #[serde(rename = "total_paid", with = "money::dollars")]
+    pub total_paid_cents: Cents,
+    #[serde(rename = "remaining_balance", with = "money::dollars")]
+    pub remaining_balance_cents: Cents,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DebtPaymentDetailResponse {
     pub debt_id: i64,
     pub debt_name: String,
-    pub amount: f64,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,26 +59,40 @@ pub struct DebtSummaryResponse {
     pub debt_id: i64,
     pub debt_name: String,
     pub payoff_month: i32,
-    pub total_interest_paid: f64,
+    #[serde(rename = "total_interest_paid", with = "money::dollars")]
+    pub total_interest_paid_cents: Cents,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecordPaymentResponse {
     pub payment_id: i64,
-    pub updated_balance: f64,
+    #[serde(rename = "updated_balance", with = "money::dollars")]
+    pub updated_balance_cents: Cents,
+    #[serde(rename = "interest", with = "money::dollars")]
+    pub interest_cents: Cents,
+    #[serde(rename = "principal", with = "money::dollars")]
+    pub principal_cents: Cents,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The balance a debt had from `date` on, and what changed it
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct BalancePoint {
     pub date: String,
-    pub balance: f64,
+    #[serde(rename = "balance", with = "money::dollars")]
+    pub balance_cents: Cents,
+    pub change: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DebtProgressResponse {
     pub debt: Debt,
     pub payments: Vec<DebtPayment>,
-    pub total_paid: f64,
+    #[serde(rename = "total_paid", with = "money::dollars")]
+    pub total_paid_cents: Cents,
+    #[serde(rename = "total_interest_paid", with = "money::dollars")]
+    pub total_interest_paid_cents: Cents,
+    #[serde(rename = "total_principal_paid", with = "money::dollars")]
+    pub total_principal_paid_cents: Cents,
     pub balance_history: Vec<BalancePoint>,
 }
 
@@ -65,13 +100,15 @@ pub struct DebtProgressResponse {
 pub struct StrategyComparison {
     pub strategy: String,
     pub payoff_date: String,
-    pub total_interest: f64,
+    #[serde(rename = "total_interest", with = "money::dollars")]
+    pub total_interest_cents: Cents,
     pub payoff_months: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComparisonSavings {
-    pub interest_saved: f64,
+    #[serde(rename = "interest_saved", with = "money::dollars")]
+    pub interest_saved_cents: Cents,
     pub months_saved: i32,
 }
 
@@ -82,15 +119,47 @@ pub struct CompareStrategiesResponse {
     pub savings: ComparisonSavings,
 }
 
+/// Both strategies at one monthly amount; `error` is set instead when the amount can't pay off the debts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoffScenarioResult {
+    #[serde(rename = "monthly_amount", with = "money::dollars")]
+    pub monthly_amount_cents: Cents,
+    pub avalanche: Option<StrategyComparison>,
+    pub snowball: Option<StrategyComparison>,
+    pub error: Option<String>,
+}
+
+/// What paying `increment` more each month does, compared with the current monthly amount
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginalPaymentImpact {
+    #[serde(rename = "increment", with = "money::dollars")]
+    pub increment_cents: Cents,
+    #[serde(rename = "monthly_amount", with = "money::dollars")]
+    pub monthly_amount_cents: Cents,
+    pub payoff_date: String,
+    pub payoff_months: i32,
+    pub months_saved: i32,
+    #[serde(rename = "interest_saved", with = "money::dollars")]
+    pub interest_saved_cents: Cents,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginalPaymentImpactResponse {
+    pub current: StrategyComparison,
+    #[serde(rename = "monthly_amount", with = "money::dollars")]
+    pub monthly_amount_cents: Cents,
+    pub impacts: Vec<MarginalPaymentImpact>,
+}
+
 // Business logic functions (used by both commands and tests)
 
 pub async fn create_debt_impl(db: &SqlitePool, debt: NewDebt) -> Result<i64, DebtError> {
     // Validate inputs
-    if debt.balance < 0.0 {
-        return Err(DebtError::InvalidBalance(debt.balance));
+    if debt.balance_cents < 0 {
+        return Err(DebtError::InvalidBalance(money::from_cents(debt.balance_cents)));
     }
-    if debt.min_payment < 0.0 {
-        return Err(DebtError::InvalidMinPayment(debt.min_payment));
+    if debt.min_payment_cents < 0 {
+        return Err(DebtError::InvalidMinPayment(money::from_cents(debt.min_payment_cents)));
     }
     if debt.interest_rate < MIN_INTEREST_RATE || debt.interest_rate > MAX_INTEREST_RATE {
         return Err(DebtError::InvalidInterestRate {
@@ -100,33 +169,172 @@ pub async fn create_debt_impl(db: &SqlitePool, debt: NewDebt) -> Result<i64, Deb
         });
     }
 
+    let today = dates::today(db).await;
+    let balance = debt.balance_cents;
+    let mut tx = db.begin().await.map_err(|e| DebtError::Database(e.to_string()))?;
     let result = sqlx::query(
-        "INSERT INTO debts (name, balance, original_balance, interest_rate, min_payment) VALUES (?, ?, ?, ?, ?)"
+        "INSERT INTO debts (name, balance_cents, original_balance_cents, interest_rate, min_payment_cents) VALUES (?, ?, ?, ?, ?)"
     )
     .bind(&debt.name)
-    .bind(debt.balance)
-    .bind(debt.balance)  // original_balance = balance initially
+    .bind(balance)
+    .bind(balance)  // original_balance = balance initially
     .bind(debt.interest_rate)
-    .bind(debt.min_payment)
-    .execute(db)
+    .bind(debt.min_payment_cents)
+    .execute(&mut *tx)
     .await
     .map_err(|e| DebtError::Database(e.to_string()))?;
+    let debt_id = result.last_insert_rowid();
+    record_balance(&mut *tx, debt_id, balance, today, BalanceChange::Opened)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+    tx.commit().await.map_err(|e| DebtError::Database(e.to_string()))?;
 
-    Ok(result.last_insert_rowid())
+    if let Some(created) = get_debt(db, debt_id).await? {
+        AuditRecord::created(AuditEntity::Debt, debt_id)
+            .new_values(&created)
+            .record(db)
+            .await;
+    }
+
+    Ok(debt_id)
 }
 
 // T030: Create debt command
 #[tauri::command]
-pub async fn create_debt(db_pool: tauri::State<'_, DbPool>, debt: NewDebt) -> Result<i64, String> {
-    create_debt_impl(&db_pool.0, debt)
+pub async fn create_debt(app: tauri::AppHandle, db_pool: tauri::State<'_, DbPool>, debt: NewDebt) -> Result<i64, String> {
+    let result = create_debt_impl(&db_pool.pool(), debt)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Debts, ChangeAction::Created, [result]);
+    Ok(result)
+}
+
+/// Add a fixed-term loan. Its monthly payment is the amortized one, and its balance is
+/// what the schedule leaves owed on `today` unless given
+pub async fn create_loan_impl(db: &SqlitePool, loan: NewLoan, today: NaiveDate) -> Result<i64, DebtError> {
+    if loan.principal_cents < 0 {
+        return Err(DebtError::InvalidBalance(money::from_cents(loan.principal_cents)));
+    }
+    if let Some(balance) = loan.balance_cents.filter(|b| *b < 0) {
+        return Err(DebtError::InvalidBalance(money::from_cents(balance)));
+    }
+    if loan.interest_rate < MIN_INTEREST_RATE || loan.interest_rate > MAX_INTEREST_RATE {
+        return Err(DebtError::InvalidInterestRate {
+            min: MIN_INTEREST_RATE,
+            max: MAX_INTEREST_RATE,
+            actual: loan.interest_rate,
+        });
+    }
+    if !(1..=MAX_LOAN_TERM_MONTHS).contains(&loan.term_months) {
+        return Err(DebtError::InvalidLoanTerm {
+            max: MAX_LOAN_TERM_MONTHS,
+            actual: loan.term_months,
+        });
+    }
+    let start_date = dates::parse_date(&loan.start_date).map_err(|_| DebtError::InvalidLoanStartDate(loan.start_date.clone()))?;
+
+    let principal = loan.principal_cents;
+    let payment = amortization::monthly_payment(principal, loan.interest_rate, loan.term_months);
+    let balance = match loan.balance_cents {
+        Some(balance) => balance,
+        None => amortization::balance_on(principal, loan.interest_rate, loan.term_months, start_date, today),
+    };
+
+    let mut tx = db.begin().await.map_err(|e| DebtError::Database(e.to_string()))?;
+    let result = sqlx::query(
+        "INSERT INTO debts (name, balance_cents, original_balance_cents, interest_rate, min_payment_cents, kind, term_months, loan_start_date, due_day)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(&loan.name)
+    .bind(balance)
+    .bind(principal)
+    .bind(loan.interest_rate)
+    .bind(payment)
+    .bind(DebtKind::Loan.to_string())
+    .bind(loan.term_months)
+    .bind(dates::format_date(start_date))
+    .bind(start_date.day())
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| DebtError::Database(e.to_string()))?;
+    let debt_id = result.last_insert_rowid();
+    record_balance(&mut *tx, debt_id, balance, today, BalanceChange::Opened)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+    tx.commit().await.map_err(|e| DebtError::Database(e.to_string()))?;
+
+    if let Some(created) = get_debt(db, debt_id).await? {
+        AuditRecord::created(AuditEntity::Debt, debt_id)
+            .new_values(&created)
+            .record(db)
+            .await;
+    }
+
+    Ok(debt_id)
+}
+
+#[tauri::command]
+pub async fn create_loan(app: tauri::AppHandle, db_pool: tauri::State<'_, DbPool>, loan: NewLoan) -> Result<i64, String> {
+    let result = create_loan_impl(&db_pool.pool(), loan, dates::today(&db_pool.pool()).await)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Debts, ChangeAction::Created, [result]);
+    Ok(result)
+}
+
+/// Every scheduled payment of a loan, split into interest and principal
+pub async fn get_amortization_schedule_impl(db: &SqlitePool, debt_id: i64) -> Result<AmortizationSchedule, DebtError> {
+    let debt = get_debt(db, debt_id).await?.ok_or(DebtError::NotFound(debt_id))?;
+    let (Some(term_months), Some(start_date)) = (debt.term_months, debt.loan_start_date.as_deref()) else {
+        return Err(DebtError::NotLoan(debt_id));
+    };
+    if debt.kind != DebtKind::Loan.to_string() {
+        return Err(DebtError::NotLoan(debt_id));
+    }
+    let start = dates::parse_date(start_date).map_err(|_| DebtError::InvalidLoanStartDate(start_date.to_string()))?;
+
+    let term_months = term_months as u32;
+    let principal = debt.original_balance_cents;
+    let rows = amortization::schedule(principal, debt.interest_rate, term_months, start);
+
+    Ok(AmortizationSchedule {
+        debt_id,
+        principal_cents: principal,
+        interest_rate: debt.interest_rate,
+        term_months,
+        start_date: start_date.to_string(),
+        monthly_payment_cents: amortization::monthly_payment(principal, debt.interest_rate, term_months),
+        total_interest_cents: rows.iter().map(|r| r.interest_cents).sum(),
+        total_paid_cents: rows.iter().map(|r| r.payment_cents).sum(),
+        rows,
+    })
+}
+
+#[tauri::command]
+pub async fn get_amortization_schedule(
+    db_pool: tauri::State<'_, DbPool>,
+    debt_id: i64,
+) -> Result<AmortizationSchedule, String> {
+    get_amortization_schedule_impl(&db_pool.pool(), debt_id)
         .await
         .map_err(|e| e.to_user_message())
 }
 
+async fn get_debt(db: &SqlitePool, debt_id: i64) -> Result<Option<Debt>, DebtError> {
+    sqlx::query_as::<_, Debt>(
+        "SELECT id, name, balance_cents, original_balance_cents, interest_rate, min_payment_cents, min_payment_percent, kind, term_months, loan_start_date, due_day, account_id, created_at, updated_at
+         FROM debts WHERE id = ?"
+    )
+    .bind(debt_id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| DebtError::Database(e.to_string()))
+}
+
 pub async fn list_debts_impl(db: &SqlitePool) -> Result<Vec<Debt>, DebtError> {
     sqlx::query_as::<_, Debt>(
-        "SELECT id, name, balance, original_balance, interest_rate, min_payment, created_at, updated_at
-         FROM debts ORDER BY balance DESC"
+        "SELECT id, name, balance_cents, original_balance_cents, interest_rate, min_payment_cents, min_payment_percent, kind, term_months, loan_start_date, due_day, account_id, created_at, updated_at
+         FROM debts ORDER BY balance_cents DESC"
     )
     .fetch_all(db)
     .await
@@ -136,7 +344,7 @@ pub async fn list_debts_impl(db: &SqlitePool) -> Result<Vec<Debt>, DebtError> {
 // T031: List debts command
 #[tauri::command]
 pub async fn list_debts(db_pool: tauri::State<'_, DbPool>) -> Result<Vec<Debt>, String> {
-    list_debts_impl(&db_pool.0)
+    list_debts_impl(&db_pool.pool())
         .await
         .map_err(|e| e.to_user_message())
 }
@@ -144,14 +352,14 @@ pub async fn list_debts(db_pool: tauri::State<'_, DbPool>) -> Result<Vec<Debt>,
 pub async fn update_debt_impl(
     db: &SqlitePool,
     debt_id: i64,
-    balance: Option<f64>,
+    balance_cents: Option<Cents>,
     interest_rate: Option<f64>,
-    min_payment: Option<f64>,
+    min_payment_cents: Option<Cents>,
 ) -> Result<bool, DebtError> {
     // Validate inputs
-    if let Some(bal) = balance {
-        if bal < 0.0 {
-            return Err(DebtError::InvalidBalance(bal));
+    if let Some(bal) = balance_cents {
+        if bal < 0 {
+            return Err(DebtError::InvalidBalance(money::from_cents(bal)));
         }
     }
     if let Some(rate) = interest_rate {
@@ -163,54 +371,39 @@ pub async fn update_debt_impl(
             });
         }
     }
-    if let Some(payment) = min_payment {
-        if payment < 0.0 {
-            return Err(DebtError::InvalidMinPayment(payment));
+    if let Some(payment) = min_payment_cents {
+        if payment < 0 {
+            return Err(DebtError::InvalidMinPayment(money::from_cents(payment)));
         }
     }
 
     // Check if debt exists
-    let exists: Option<(i64,)> = sqlx::query_as("SELECT id FROM debts WHERE id = ?")
-        .bind(debt_id)
-        .fetch_optional(db)
+    let current = get_debt(db, debt_id).await?.ok_or(DebtError::NotFound(debt_id))?;
+
+    let today = dates::today(db).await;
+    let mut tx = db.begin().await.map_err(|e| DebtError::Database(e.to_string()))?;
+    UpdateBuilder::new("debts")
+        .set_if("balance_cents", balance_cents)
+        .set_if("interest_rate", interest_rate)
+        .set_if("min_payment_cents", min_payment_cents)
+        .touch_updated_at()
+        .execute(&mut *tx, debt_id)
         .await
         .map_err(|e| DebtError::Database(e.to_string()))?;
-
-    if exists.is_none() {
-        return Err(DebtError::NotFound(debt_id));
+    if let Some(balance) = balance_cents.filter(|b| *b != current.balance_cents) {
+        record_balance(&mut *tx, debt_id, balance, today, BalanceChange::Adjustment)
+            .await
+            .map_err(|e| DebtError::Database(e.to_string()))?;
     }
+    tx.commit().await.map_err(|e| DebtError::Database(e.to_string()))?;
 
-    // Build update query dynamically
-    let mut updates = Vec::new();
-    let mut query = String::from("UPDATE debts SET ");
-
-    if balance.is_some() {
-        updates.push("balance = ?");
-    }
-    if interest_rate.is_some() {
-        updates.push("interest_rate = ?");
-    }
-    if min_payment.is_some() {
-        updates.push("min_payment = ?");
+    if let Some(updated) = get_debt(db, debt_id).await? {
+        AuditRecord::updated(AuditEntity::Debt, debt_id)
+            .old_values(&current)
+            .new_values(&updated)
+            .record(db)
+            .await;
     }
-    updates.push("updated_at = CURRENT_TIMESTAMP");
-
-    query.push_str(&updates.join(", "));
-    query.push_str(" WHERE id = ?");
-
-    let mut q = sqlx::query(&query);
-    if let Some(bal) = balance {
-        q = q.bind(bal);
-    }
-    if let Some(rate) = interest_rate {
-        q = q.bind(rate);
-    }
-    if let Some(payment) = min_payment {
-        q = q.bind(payment);
-    }
-    q = q.bind(debt_id);
-
-    q.execute(db).await.map_err(|e| DebtError::Database(e.to_string()))?;
 
     Ok(true)
 }
@@ -218,25 +411,30 @@ pub async fn update_debt_impl(
 // T032: Update debt command
 #[tauri::command]
 pub async fn update_debt(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     debt_id: i64,
     balance: Option<f64>,
     interest_rate: Option<f64>,
     min_payment: Option<f64>,
 ) -> Result<bool, String> {
-    update_debt_impl(&db_pool.0, debt_id, balance, interest_rate, min_payment)
+    let balance = balance.map(money::parse_dollars).transpose()?;
+    let min_payment = min_payment.map(money::parse_dollars).transpose()?;
+    let result = update_debt_impl(&db_pool.pool(), debt_id, balance, interest_rate, min_payment)
         .await
-        .map_err(|e| e.to_user_message())
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Debts, ChangeAction::Updated, [debt_id]);
+    Ok(result)
 }
 
 pub async fn calculate_payoff_plan_impl(
     db: &SqlitePool,
     strategy: String,
-    monthly_amount: f64,
+    monthly_amount: Cents,
 ) -> Result<PayoffPlanResponse, DebtError> {
     let debts = sqlx::query_as::<_, Debt>(
-        "SELECT id, name, balance, original_balance, interest_rate, min_payment, created_at, updated_at
-         FROM debts WHERE balance > 0 ORDER BY balance DESC"
+        "SELECT id, name, balance_cents, original_balance_cents, interest_rate, min_payment_cents, min_payment_percent, kind, term_months, loan_start_date, due_day, account_id, created_at, updated_at
+         FROM debts WHERE balance_cents > 0 ORDER BY balance_cents DESC"
     )
     .fetch_all(db)
     .await
@@ -246,15 +444,11 @@ pub async fn calculate_payoff_plan_impl(
         return Err(DebtError::NoDebts);
     }
 
-    let plan = match strategy.as_str() {
-        "avalanche" => AvalancheCalculator::calculate_payoff_plan(debts, monthly_amount)?,
-        "snowball" => SnowballCalculator::calculate_payoff_plan(debts, monthly_amount)?,
-        _ => return Err(DebtError::InvalidStrategy(strategy)),
-    };
+    let plan = run_strategy(&strategy, debts, monthly_amount, dates::today(db).await)?;
 
     // Save the plan
     let result = sqlx::query(
-        "INSERT INTO debt_plans (strategy, monthly_amount) VALUES (?, ?)"
+        "INSERT INTO debt_plans (strategy, monthly_amount_cents) VALUES (?, ?)"
     )
     .bind(&plan.strategy)
     .bind(monthly_amount)
@@ -268,48 +462,65 @@ pub async fn calculate_payoff_plan_impl(
         plan_id,
         strategy: plan.strategy,
         payoff_date: plan.payoff_date,
-        total_interest: plan.total_interest,
+        total_interest_cents: plan.total_interest_cents,
         monthly_breakdown: plan.monthly_breakdown.into_iter().map(|m| MonthlyPaymentResponse {
             month: m.month,
             date: m.date,
             payments: m.payments.into_iter().map(|p| DebtPaymentDetailResponse {
                 debt_id: p.debt_id,
                 debt_name: p.debt_name,
-                amount: p.amount,
+                amount_cents: p.amount_cents,
             }).collect(),
-            total_paid: m.total_paid,
-            remaining_balance: m.remaining_balance,
+            total_paid_cents: m.total_paid_cents,
+            remaining_balance_cents: m.remaining_balance_cents,
         }).collect(),
         debt_summaries: plan.debt_summaries.into_iter().map(|s| DebtSummaryResponse {
             debt_id: s.debt_id,
             debt_name: s.debt_name,
             payoff_month: s.payoff_month,
-            total_interest_paid: s.total_interest_paid,
+            total_interest_paid_cents: s.total_interest_paid_cents,
         }).collect(),
     })
 }
 
+fn run_strategy(
+    strategy: &str,
+    debts: Vec<Debt>,
+    monthly_amount: Cents,
+    start_date: NaiveDate,
+) -> Result<PayoffPlan, DebtError> {
+    match strategy {
+        "avalanche" => AvalancheCalculator::calculate_payoff_plan(debts, monthly_amount, start_date),
+        "snowball" => SnowballCalculator::calculate_payoff_plan(debts, monthly_amount, start_date),
+        _ => Err(DebtError::InvalidStrategy(strategy.to_string())),
+    }
+}
+
 // T033: Calculate payoff plan command
 #[tauri::command]
 pub async fn calculate_payoff_plan(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     strategy: String,
     monthly_amount: f64,
 ) -> Result<PayoffPlanResponse, String> {
-    calculate_payoff_plan_impl(&db_pool.0, strategy, monthly_amount)
+    let monthly_amount = money::parse_dollars(monthly_amount)?;
+    let result = calculate_payoff_plan_impl(&db_pool.pool(), strategy, monthly_amount)
         .await
-        .map_err(|e| e.to_user_message())
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::PayoffPlans, ChangeAction::Created, [result.plan_id]);
+    Ok(result)
 }
 
 pub async fn get_payoff_plan_impl(db: &SqlitePool, plan_id: i64) -> Result<PayoffPlanResponse, DebtError> {
     #[derive(sqlx::FromRow)]
     struct DebtPlan {
         strategy: String,
-        monthly_amount: f64,
+        monthly_amount_cents: Cents,
     }
 
     let plan = sqlx::query_as::<_, DebtPlan>(
-        "SELECT strategy, monthly_amount FROM debt_plans WHERE id = ?"
+        "SELECT strategy, monthly_amount_cents FROM debt_plans WHERE id = ?"
     )
     .bind(plan_id)
     .fetch_optional(db)
@@ -319,16 +530,17 @@ pub async fn get_payoff_plan_impl(db: &SqlitePool, plan_id: i64) -> Result<Payof
 
     // Recalculate the plan (plans are not fully stored, just metadata)
     let debts = sqlx::query_as::<_, Debt>(
-        "SELECT id, name, balance, original_balance, interest_rate, min_payment, created_at, updated_at
-         FROM debts WHERE balance > 0"
+        "SELECT id, name, balance_cents, original_balance_cents, interest_rate, min_payment_cents, min_payment_percent, kind, term_months, loan_start_date, due_day, account_id, created_at, updated_at
+         FROM debts WHERE balance_cents > 0"
     )
     .fetch_all(db)
     .await
     .map_err(|e| DebtError::Database(e.to_string()))?;
 
+    let start_date = dates::today(db).await;
     let calc_plan = match plan.strategy.as_str() {
-        "avalanche" => AvalancheCalculator::calculate_payoff_plan(debts, plan.monthly_amount)?,
-        "snowball" => SnowballCalculator::calculate_payoff_plan(debts, plan.monthly_amount)?,
+        "avalanche" => AvalancheCalculator::calculate_payoff_plan(debts, plan.monthly_amount_cents, start_date)?,
+        "snowball" => SnowballCalculator::calculate_payoff_plan(debts, plan.monthly_amount_cents, start_date)?,
         _ => return Err(DebtError::InvalidStrategy(plan.strategy)),
     };
 
@@ -336,23 +548,23 @@ pub async fn get_payoff_plan_impl(db: &SqlitePool, plan_id: i64) -> Result<Payof
         plan_id,
         strategy: calc_plan.strategy,
         payoff_date: calc_plan.payoff_date,
-        total_interest: calc_plan.total_interest,
+        total_interest_cents: calc_plan.total_interest_cents,
         monthly_breakdown: calc_plan.monthly_breakdown.into_iter().map(|m| MonthlyPaymentResponse {
             month: m.month,
             date: m.date,
             payments: m.payments.into_iter().map(|p| DebtPaymentDetailResponse {
                 debt_id: p.debt_id,
                 debt_name: p.debt_name,
-                amount: p.amount,
+                amount_cents: p.amount_cents,
             }).collect(),
-            total_paid: m.total_paid,
-            remaining_balance: m.remaining_balance,
+            total_paid_cents: m.total_paid_cents,
+            remaining_balance_cents: m.remaining_balance_cents,
         }).collect(),
         debt_summaries: calc_plan.debt_summaries.into_iter().map(|s| DebtSummaryResponse {
             debt_id: s.debt_id,
             debt_name: s.debt_name,
             payoff_month: s.payoff_month,
-            total_interest_paid: s.total_interest_paid,
+            total_interest_paid_cents: s.total_interest_paid_cents,
         }).collect(),
     })
 }
@@ -360,7 +572,7 @@ pub async fn get_payoff_plan_impl(db: &SqlitePool, plan_id: i64) -> Result<Payof
 // T034: Get payoff plan command
 #[tauri::command]
 pub async fn get_payoff_plan(db_pool: tauri::State<'_, DbPool>, plan_id: i64) -> Result<PayoffPlanResponse, String> {
-    get_payoff_plan_impl(&db_pool.0, plan_id)
+    get_payoff_plan_impl(&db_pool.pool(), plan_id)
         .await
         .map_err(|e| e.to_user_message())
 }
@@ -368,12 +580,12 @@ pub async fn get_payoff_plan(db_pool: tauri::State<'_, DbPool>, plan_id: i64) ->
 pub async fn record_debt_payment_impl(
     db: &SqlitePool,
     debt_id: i64,
-    amount: f64,
+    amount: Cents,
     date: String,
     plan_id: Option<i64>,
 ) -> Result<RecordPaymentResponse, DebtError> {
-    if amount <= 0.0 {
-        return Err(DebtError::InvalidPaymentAmount(amount));
+    if amount <= 0 {
+        return Err(DebtError::InvalidPaymentAmount(money::from_cents(amount)));
     }
 
     // Use a transaction to ensure atomicity
@@ -381,7 +593,7 @@ pub async fn record_debt_payment_impl(
 
     // Get current debt
     let debt = sqlx::query_as::<_, Debt>(
-        "SELECT id, name, balance, original_balance, interest_rate, min_payment, created_at, updated_at
+        "SELECT id, name, balance_cents, original_balance_cents, interest_rate, min_payment_cents, min_payment_percent, kind, term_months, loan_start_date, due_day, account_id, created_at, updated_at
          FROM debts WHERE id = ?"
     )
     .bind(debt_id)
@@ -390,19 +602,37 @@ pub async fn record_debt_payment_impl(
     .map_err(|e| DebtError::Database(e.to_string()))?
     .ok_or(DebtError::NotFound(debt_id))?;
 
-    if amount > debt.balance {
+    // Interest accrues daily from the previous payment (or when the debt was added)
+    let payment_date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|_| DebtError::InvalidPaymentDate(date.clone()))?;
+    let (last_payment,): (Option<String>,) =
+        sqlx::query_as("SELECT MAX(date) FROM debt_payments WHERE debt_id = ? AND date <= ?")
+            .bind(debt_id)
+            .bind(&date)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| DebtError::Database(e.to_string()))?;
+    let accrued_from = last_payment.as_deref().unwrap_or(&debt.created_at);
+    let days = NaiveDate::parse_from_str(accrued_from.get(..10).unwrap_or(accrued_from), "%Y-%m-%d")
+        .map(|from| (payment_date - from).num_days())
+        .unwrap_or(0);
+
+    let (interest, principal) = split_payment(debt.balance_cents, debt.interest_rate, days, amount);
+    if principal > debt.balance_cents {
         return Err(DebtError::PaymentExceedsBalance {
-            payment: amount,
-            balance: debt.balance,
+            payment: money::from_cents(amount),
+            balance: money::from_cents(debt.balance_cents + interest),
         });
     }
 
     // Record payment
     let payment_result = sqlx::query(
-        "INSERT INTO debt_payments (debt_id, amount, date, plan_id) VALUES (?, ?, ?, ?)"
+        "INSERT INTO debt_payments (debt_id, amount_cents, interest_cents, principal_cents, date, plan_id) VALUES (?, ?, ?, ?, ?, ?)"
     )
     .bind(debt_id)
     .bind(amount)
+    .bind(interest)
+    .bind(principal)
     .bind(&date)
     .bind(plan_id)
     .execute(&mut *tx)
@@ -411,36 +641,218 @@ pub async fn record_debt_payment_impl(
 
     let payment_id = payment_result.last_insert_rowid();
 
-    // Update debt balance
-    let updated_balance = debt.balance - amount;
-    sqlx::query("UPDATE debts SET balance = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+    // Only the principal reduces the balance
+    let updated_balance = (debt.balance_cents - principal).max(0);
+    sqlx::query("UPDATE debts SET balance_cents = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
         .bind(updated_balance)
         .bind(debt_id)
         .execute(&mut *tx)
         .await
         .map_err(|e| DebtError::Database(e.to_string()))?;
+    record_balance(&mut *tx, debt_id, updated_balance, payment_date, BalanceChange::Payment)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+
+    AuditRecord::created(AuditEntity::DebtPayment, payment_id)
+        .new_values(&json!({
+            "debt_id": debt_id,
+            "amount": money::from_cents(amount),
+            "interest": money::from_cents(interest),
+            "principal": money::from_cents(principal),
+            "date": date,
+            "plan_id": plan_id,
+        }))
+        .record(&mut *tx)
+        .await;
+    AuditRecord::updated(AuditEntity::Debt, debt_id)
+        .old_values(&json!({ "balance": money::from_cents(debt.balance_cents) }))
+        .new_values(&json!({ "balance": money::from_cents(updated_balance) }))
+        .record(&mut *tx)
+        .await;
 
     // Commit transaction
     tx.commit().await.map_err(|e| DebtError::Database(e.to_string()))?;
 
     Ok(RecordPaymentResponse {
         payment_id,
-        updated_balance,
+        updated_balance_cents: updated_balance,
+        interest_cents: interest,
+        principal_cents: principal,
     })
 }
 
 // T035: Record debt payment command
 #[tauri::command]
 pub async fn record_debt_payment(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     debt_id: i64,
     amount: f64,
     date: String,
     plan_id: Option<i64>,
 ) -> Result<RecordPaymentResponse, String> {
-    record_debt_payment_impl(&db_pool.0, debt_id, amount, date, plan_id)
+    let amount = money::parse_dollars(amount)?;
+    let result = record_debt_payment_impl(&db_pool.pool(), debt_id, amount, date, plan_id)
         .await
-        .map_err(|e| e.to_user_message())
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Debts, ChangeAction::Updated, [debt_id]);
+    Ok(result)
+}
+
+/// Give back the principal a payment no longer covers (`principal_removed` is negative when
+/// a correction pays down more)
+///
+/// The current balance is shifted rather than rebuilt from the original one, so manual
+/// balance edits and interest accrued since the debt was added are kept.
+async fn adjust_debt_balance(
+    conn: &mut sqlx::SqliteConnection,
+    debt_id: i64,
+    principal_removed: Cents,
+) -> Result<Cents, DebtError> {
+    let current: Cents = sqlx::query_scalar("SELECT balance_cents FROM debts WHERE id = ?")
+        .bind(debt_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?
+        .ok_or(DebtError::NotFound(debt_id))?;
+
+    let balance = (current + principal_removed).max(0);
+    sqlx::query("UPDATE debts SET balance_cents = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(balance)
+        .bind(debt_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+    Ok(balance)
+}
+
+async fn get_debt_payment(conn: &mut sqlx::SqliteConnection, payment_id: i64) -> Result<DebtPayment, DebtError> {
+    sqlx::query_as::<_, DebtPayment>(
+        "SELECT id, debt_id, amount_cents, interest_cents, principal_cents, date, plan_id, created_at FROM debt_payments WHERE id = ?"
+    )
+    .bind(payment_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| DebtError::Database(e.to_string()))?
+    .ok_or(DebtError::PaymentNotFound(payment_id))
+}
+
+/// Correct a recorded payment's amount or date, then shift the debt's balance by the change in principal
+///
+/// The payment keeps the interest it was charged (up to the new amount); the rest of
+/// the amount is principal.
+pub async fn update_debt_payment_impl(
+    db: &SqlitePool,
+    payment_id: i64,
+    amount: Option<Cents>,
+    date: Option<String>,
+) -> Result<Debt, DebtError> {
+    if let Some(amount) = amount.filter(|a| *a <= 0) {
+        return Err(DebtError::InvalidPaymentAmount(money::from_cents(amount)));
+    }
+    if let Some(d) = &date {
+        dates::parse_date(d).map_err(|_| DebtError::InvalidPaymentDate(d.clone()))?;
+    }
+
+    let today = dates::today(db).await;
+    let mut tx = db.begin().await.map_err(|e| DebtError::Database(e.to_string()))?;
+    let current = get_debt_payment(&mut tx, payment_id).await?;
+
+    let amount = amount.unwrap_or(current.amount_cents);
+    let interest = current.interest_cents.min(amount);
+    let principal = amount - interest;
+    let date = date.unwrap_or_else(|| current.date.clone());
+
+    sqlx::query("UPDATE debt_payments SET amount_cents = ?, interest_cents = ?, principal_cents = ?, date = ? WHERE id = ?")
+        .bind(amount)
+        .bind(interest)
+        .bind(principal)
+        .bind(&date)
+        .bind(payment_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+    let principal_removed = current.principal_cents - principal;
+    let balance = adjust_debt_balance(&mut tx, current.debt_id, principal_removed).await?;
+    record_balance(&mut *tx, current.debt_id, balance, today, BalanceChange::Correction)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+
+    AuditRecord::updated(AuditEntity::DebtPayment, payment_id)
+        .old_values(&current)
+        .new_values(&json!({
+            "amount": money::from_cents(amount),
+            "interest": money::from_cents(interest),
+            "principal": money::from_cents(principal),
+            "date": date,
+        }))
+        .record(&mut *tx)
+        .await;
+    AuditRecord::updated(AuditEntity::Debt, current.debt_id)
+        .new_values(&json!({ "balance": money::from_cents(balance) }))
+        .record(&mut *tx)
+        .await;
+
+    tx.commit().await.map_err(|e| DebtError::Database(e.to_string()))?;
+    get_debt(db, current.debt_id).await?.ok_or(DebtError::NotFound(current.debt_id))
+}
+
+#[tauri::command]
+pub async fn update_debt_payment(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    payment_id: i64,
+    amount: Option<f64>,
+    date: Option<String>,
+) -> Result<Debt, String> {
+    let amount = amount.map(money::parse_dollars).transpose()?;
+    let result = update_debt_payment_impl(&db_pool.pool(), payment_id, amount, date)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Debts, ChangeAction::Updated, [result.id]);
+    Ok(result)
+}
+
+/// Remove a recorded payment and add its principal back to the debt's balance
+pub async fn delete_debt_payment_impl(db: &SqlitePool, payment_id: i64) -> Result<Debt, DebtError> {
+    let today = dates::today(db).await;
+    let mut tx = db.begin().await.map_err(|e| DebtError::Database(e.to_string()))?;
+    let current = get_debt_payment(&mut tx, payment_id).await?;
+
+    sqlx::query("DELETE FROM debt_payments WHERE id = ?")
+        .bind(payment_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+    let balance = adjust_debt_balance(&mut tx, current.debt_id, current.principal_cents).await?;
+    record_balance(&mut *tx, current.debt_id, balance, today, BalanceChange::Correction)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+
+    AuditRecord::deleted(AuditEntity::DebtPayment, payment_id)
+        .old_values(&current)
+        .record(&mut *tx)
+        .await;
+    AuditRecord::updated(AuditEntity::Debt, current.debt_id)
+        .new_values(&json!({ "balance": money::from_cents(balance) }))
+        .record(&mut *tx)
+        .await;
+
+    tx.commit().await.map_err(|e| DebtError::Database(e.to_string()))?;
+    get_debt(db, current.debt_id).await?.ok_or(DebtError::NotFound(current.debt_id))
+}
+
+#[tauri::command]
+pub async fn delete_debt_payment(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    payment_id: i64,
+) -> Result<Debt, String> {
+    let result = delete_debt_payment_impl(&db_pool.pool(), payment_id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Debts, ChangeAction::Updated, [result.id]);
+    Ok(result)
 }
 
 pub async fn get_debt_progress_impl(
@@ -450,7 +862,7 @@ pub async fn get_debt_progress_impl(
     end_date: Option<String>,
 ) -> Result<DebtProgressResponse, DebtError> {
     let debt = sqlx::query_as::<_, Debt>(
-        "SELECT id, name, balance, original_balance, interest_rate, min_payment, created_at, updated_at
+        "SELECT id, name, balance_cents, original_balance_cents, interest_rate, min_payment_cents, min_payment_percent, kind, term_months, loan_start_date, due_day, account_id, created_at, updated_at
          FROM debts WHERE id = ?"
     )
     .bind(debt_id)
@@ -459,22 +871,34 @@ pub async fn get_debt_progress_impl(
     .map_err(|e| DebtError::Database(e.to_string()))?
     .ok_or(DebtError::NotFound(debt_id))?;
 
-    let payments = if let (Some(start), Some(end)) = (start_date, end_date) {
-        sqlx::query_as::<_, DebtPayment>(
-            "SELECT id, debt_id, amount, date, plan_id, created_at
+    let (payments, balance_history) = if let (Some(start), Some(end)) = (start_date, end_date) {
+        let payments = sqlx::query_as::<_, DebtPayment>(
+            "SELECT id, debt_id, amount_cents, interest_cents, principal_cents, date, plan_id, created_at
              FROM debt_payments
              WHERE debt_id = ? AND date >= ? AND date <= ?
              ORDER BY date DESC"
         )
         .bind(debt_id)
-        .bind(start)
-        .bind(end)
+        .bind(&start)
+        .bind(&end)
         .fetch_all(db)
         .await
-        .map_err(|e| DebtError::Database(e.to_string()))?
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+        let history = sqlx::query_as::<_, BalancePoint>(
+            "SELECT date, balance_cents, change FROM debt_balance_history
+             WHERE debt_id = ? AND date >= ? AND date <= ?
+             ORDER BY date, id"
+        )
+        .bind(debt_id)
+        .bind(&start)
+        .bind(&end)
+        .fetch_all(db)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+        (payments, history)
     } else {
-        sqlx::query_as::<_, DebtPayment>(
-            "SELECT id, debt_id, amount, date, plan_id, created_at
+        let payments = sqlx::query_as::<_, DebtPayment>(
+            "SELECT id, debt_id, amount_cents, interest_cents, principal_cents, date, plan_id, created_at
              FROM debt_payments
              WHERE debt_id = ?
              ORDER BY date DESC"
@@ -482,27 +906,27 @@ pub async fn get_debt_progress_impl(
         .bind(debt_id)
         .fetch_all(db)
         .await
-        .map_err(|e| DebtError::Database(e.to_string()))?
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+        let history = sqlx::query_as::<_, BalancePoint>(
+            "SELECT date, balance_cents, change FROM debt_balance_history WHERE debt_id = ? ORDER BY date, id"
+        )
+        .bind(debt_id)
+        .fetch_all(db)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+        (payments, history)
     };
 
-    let total_paid: f64 = payments.iter().map(|p| p.amount).sum();
-
-    // Build balance history from payments
-    let mut balance_history = Vec::new();
-    let mut current_balance = debt.original_balance;
-
-    for payment in &payments {
-        current_balance -= payment.amount;
-        balance_history.push(BalancePoint {
-            date: payment.date.clone(),
-            balance: current_balance.max(0.0),
-        });
-    }
+    let total_paid_cents = payments.iter().map(|p| p.amount_cents).sum();
+    let total_interest_paid_cents = payments.iter().map(|p| p.interest_cents).sum();
+    let total_principal_paid_cents = payments.iter().map(|p| p.principal_cents).sum();
 
     Ok(DebtProgressResponse {
         debt,
         payments,
-        total_paid,
+        total_paid_cents,
+        total_interest_paid_cents,
+        total_principal_paid_cents,
         balance_history,
     })
 }
@@ -515,15 +939,15 @@ pub async fn get_debt_progress(
     start_date: Option<String>,
     end_date: Option<String>,
 ) -> Result<DebtProgressResponse, String> {
-    get_debt_progress_impl(&db_pool.0, debt_id, start_date, end_date)
+    get_debt_progress_impl(&db_pool.pool(), debt_id, start_date, end_date)
         .await
         .map_err(|e| e.to_user_message())
 }
 
-pub async fn compare_strategies_impl(db: &SqlitePool, monthly_amount: f64) -> Result<CompareStrategiesResponse, DebtError> {
+pub async fn compare_strategies_impl(db: &SqlitePool, monthly_amount: Cents) -> Result<CompareStrategiesResponse, DebtError> {
     let debts = sqlx::query_as::<_, Debt>(
-        "SELECT id, name, balance, original_balance, interest_rate, min_payment, created_at, updated_at
-         FROM debts WHERE balance > 0"
+        "SELECT id, name, balance_cents, original_balance_cents, interest_rate, min_payment_cents, min_payment_percent, kind, term_months, loan_start_date, due_day, account_id, created_at, updated_at
+         FROM debts WHERE balance_cents > 0"
     )
     .fetch_all(db)
     .await
@@ -533,36 +957,547 @@ pub async fn compare_strategies_impl(db: &SqlitePool, monthly_amount: f64) -> Re
         return Err(DebtError::NoDebts);
     }
 
-    let avalanche_plan = AvalancheCalculator::calculate_payoff_plan(debts.clone(), monthly_amount)?;
-    let snowball_plan = SnowballCalculator::calculate_payoff_plan(debts, monthly_amount)?;
+    let start_date = dates::today(db).await;
+    let avalanche = strategy_comparison(AvalancheCalculator::calculate_payoff_plan(debts.clone(), monthly_amount, start_date)?);
+    let snowball = strategy_comparison(SnowballCalculator::calculate_payoff_plan(debts, monthly_amount, start_date)?);
 
-    let interest_saved = snowball_plan.total_interest - avalanche_plan.total_interest;
-    let months_saved = (snowball_plan.monthly_breakdown.len() as i32) - (avalanche_plan.monthly_breakdown.len() as i32);
+    let interest_saved = snowball.total_interest_cents - avalanche.total_interest_cents;
+    let months_saved = snowball.payoff_months - avalanche.payoff_months;
 
     Ok(CompareStrategiesResponse {
-        avalanche: StrategyComparison {
-            strategy: "avalanche".to_string(),
-            payoff_date: avalanche_plan.payoff_date,
-            total_interest: avalanche_plan.total_interest,
-            payoff_months: avalanche_plan.monthly_breakdown.len() as i32,
-        },
-        snowball: StrategyComparison {
-            strategy: "snowball".to_string(),
-            payoff_date: snowball_plan.payoff_date,
-            total_interest: snowball_plan.total_interest,
-            payoff_months: snowball_plan.monthly_breakdown.len() as i32,
-        },
+        avalanche,
+        snowball,
         savings: ComparisonSavings {
-            interest_saved: interest_saved.max(0.0),
+            interest_saved_cents: interest_saved.max(0),
             months_saved: months_saved.max(0),
         },
     })
 }
 
+fn strategy_comparison(plan: PayoffPlan) -> StrategyComparison {
+    StrategyComparison {
+        payoff_months: plan.monthly_breakdown.len() as i32,
+        strategy: plan.strategy,
+        payoff_date: plan.payoff_date,
+        total_interest_cents: plan.total_interest_cents,
+    }
+}
+
+/// Run both strategies at each monthly amount without saving any plans
+///
+/// Scenarios come back in the order given. An amount too small to cover the
+/// minimum payments gets an error on its own result rather than failing the call.
+pub async fn simulate_payoff_scenarios_impl(
+    db: &SqlitePool,
+    monthly_amounts: Vec<Cents>,
+) -> Result<Vec<PayoffScenarioResult>, DebtError> {
+    if monthly_amounts.is_empty() || monthly_amounts.len() > MAX_PAYOFF_SCENARIOS {
+        return Err(DebtError::InvalidScenarioCount {
+            max: MAX_PAYOFF_SCENARIOS,
+            actual: monthly_amounts.len(),
+        });
+    }
+    if let Some(&amount) = monthly_amounts.iter().find(|a| **a <= 0) {
+        return Err(DebtError::InvalidPaymentAmount(money::from_cents(amount)));
+    }
+
+    let debts = sqlx::query_as::<_, Debt>(
+        "SELECT id, name, balance_cents, original_balance_cents, interest_rate, min_payment_cents, min_payment_percent, kind, term_months, loan_start_date, due_day, account_id, created_at, updated_at
+         FROM debts WHERE balance_cents > 0"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| DebtError::Database(e.to_string()))?;
+
+    if debts.is_empty() {
+        return Err(DebtError::NoDebts);
+    }
+
+    let start_date = dates::today(db).await;
+    Ok(monthly_amounts
+        .into_iter()
+        .map(|monthly_amount| {
+            let plans = AvalancheCalculator::calculate_payoff_plan(debts.clone(), monthly_amount, start_date).and_then(|avalanche| {
+                let snowball = SnowballCalculator::calculate_payoff_plan(debts.clone(), monthly_amount, start_date)?;
+                Ok((avalanche, snowball))
+            });
+            match plans {
+                Ok((avalanche, snowball)) => PayoffScenarioResult {
+                    monthly_amount_cents: monthly_amount,
+                    avalanche: Some(strategy_comparison(avalanche)),
+                    snowball: Some(strategy_comparison(snowball)),
+                    error: None,
+                },
+                Err(e) => PayoffScenarioResult {
+                    monthly_amount_cents: monthly_amount,
+                    avalanche: None,
+                    snowball: None,
+                    error: Some(e.to_user_message()),
+                },
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn simulate_payoff_scenarios(
+    db_pool: tauri::State<'_, DbPool>,
+    monthly_amounts: Vec<f64>,
+) -> Result<Vec<PayoffScenarioResult>, String> {
+    let monthly_amounts = monthly_amounts.into_iter().map(money::parse_dollars).collect::<Result<_, _>>()?;
+    simulate_payoff_scenarios_impl(&db_pool.pool(), monthly_amounts)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+/// Months and interest saved by paying a little more than `monthly_amount` each month
+///
+/// Each increment (by default +$25, +$50 and +$100) is simulated with the given strategy
+/// and compared with the plan at `monthly_amount` itself. Nothing is saved.
+pub async fn get_marginal_payment_impact_impl(
+    db: &SqlitePool,
+    strategy: String,
+    monthly_amount: Cents,
+    increments: Option<Vec<Cents>>,
+) -> Result<MarginalPaymentImpactResponse, DebtError> {
+    let increments = increments.unwrap_or_else(|| DEFAULT_PAYMENT_INCREMENTS.to_vec());
+    if increments.is_empty() || increments.len() > MAX_PAYOFF_SCENARIOS {
+        return Err(DebtError::InvalidScenarioCount {
+            max: MAX_PAYOFF_SCENARIOS,
+            actual: increments.len(),
+        });
+    }
+    if let Some(&amount) = increments.iter().find(|a| **a <= 0) {
+        return Err(DebtError::InvalidPaymentAmount(money::from_cents(amount)));
+    }
+
+    let debts = sqlx::query_as::<_, Debt>(
+        "SELECT id, name, balance_cents, original_balance_cents, interest_rate, min_payment_cents, min_payment_percent, kind, term_months, loan_start_date, due_day, account_id, created_at, updated_at
+         FROM debts WHERE balance_cents > 0"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| DebtError::Database(e.to_string()))?;
+
+    if debts.is_empty() {
+        return Err(DebtError::NoDebts);
+    }
+
+    let start_date = dates::today(db).await;
+    let current = strategy_comparison(run_strategy(&strategy, debts.clone(), monthly_amount, start_date)?);
+
+    let impacts = increments
+        .into_iter()
+        .map(|increment| {
+            let increased = monthly_amount + increment;
+            let plan = strategy_comparison(run_strategy(&strategy, debts.clone(), increased, start_date)?);
+            Ok(MarginalPaymentImpact {
+                increment_cents: increment,
+                monthly_amount_cents: increased,
+                months_saved: current.payoff_months - plan.payoff_months,
+                interest_saved_cents: current.total_interest_cents - plan.total_interest_cents,
+                payoff_date: plan.payoff_date,
+                payoff_months: plan.payoff_months,
+            })
+        })
+        .collect::<Result<Vec<_>, DebtError>>()?;
+
+    Ok(MarginalPaymentImpactResponse {
+        current,
+        monthly_amount_cents: monthly_amount,
+        impacts,
+    })
+}
+
+#[tauri::command]
+pub async fn get_marginal_payment_impact(
+    db_pool: tauri::State<'_, DbPool>,
+    strategy: String,
+    monthly_amount: f64,
+    increments: Option<Vec<f64>>,
+) -> Result<MarginalPaymentImpactResponse, String> {
+    let monthly_amount = money::parse_dollars(monthly_amount)?;
+    let increments = increments
+        .map(|increments| increments.into_iter().map(money::parse_dollars).collect::<Result<_, _>>())
+        .transpose()?;
+    get_marginal_payment_impact_impl(&db_pool.pool(), strategy, monthly_amount, increments)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
 // T037: Compare strategies command
 #[tauri::command]
 pub async fn compare_strategies(db_pool: tauri::State<'_, DbPool>, monthly_amount: f64) -> Result<CompareStrategiesResponse, String> {
-    compare_strategies_impl(&db_pool.0, monthly_amount)
+    compare_strategies_impl(&db_pool.pool(), money::parse_dollars(monthly_amount)?)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+async fn get_debt_autopay(db: &SqlitePool, debt_id: i64) -> Result<Option<DebtAutopay>, DebtError> {
+    sqlx::query_as::<_, DebtAutopay>(
+        "SELECT ap.debt_id, d.name AS debt_name, ap.account_id, a.name AS account_name, ap.amount_cents, ap.created_at
+         FROM debt_autopay ap
+         JOIN debts d ON d.id = ap.debt_id
+         JOIN accounts a ON a.id = ap.account_id
+         WHERE ap.debt_id = ?"
+    )
+    .bind(debt_id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| DebtError::Database(e.to_string()))
+}
+
+/// Pay a debt automatically from `account_id` on each due date, replacing any existing autopay
+/// `amount` defaults to the debt's minimum payment
+pub async fn set_debt_autopay_impl(
+    db: &SqlitePool,
+    debt_id: i64,
+    account_id: i64,
+    amount: Option<Cents>,
+) -> Result<DebtAutopay, DebtError> {
+    if let Some(amount) = amount {
+        if amount <= 0 {
+            return Err(DebtError::InvalidPaymentAmount(money::from_cents(amount)));
+        }
+    }
+
+    let debt_exists: Option<(i64,)> = sqlx::query_as("SELECT id FROM debts WHERE id = ?")
+        .bind(debt_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+    if debt_exists.is_none() {
+        return Err(DebtError::NotFound(debt_id));
+    }
+
+    let account_exists: Option<(i64,)> = sqlx::query_as("SELECT id FROM accounts WHERE id = ?")
+        .bind(account_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+    if account_exists.is_none() {
+        return Err(DebtError::AccountNotFound(account_id));
+    }
+
+    let previous = get_debt_autopay(db, debt_id).await?;
+
+    sqlx::query(
+        "INSERT INTO debt_autopay (debt_id, account_id, amount_cents) VALUES (?, ?, ?)
+         ON CONFLICT(debt_id) DO UPDATE SET account_id = excluded.account_id, amount_cents = excluded.amount_cents"
+    )
+    .bind(debt_id)
+    .bind(account_id)
+    .bind(amount)
+    .execute(db)
+    .await
+    .map_err(|e| DebtError::Database(e.to_string()))?;
+
+    let autopay = get_debt_autopay(db, debt_id).await?.ok_or(DebtError::NotFound(debt_id))?;
+    AuditRecord::updated(AuditEntity::Debt, debt_id)
+        .old_values(&json!({ "autopay": previous }))
+        .new_values(&json!({ "autopay": autopay }))
+        .record(db)
+        .await;
+
+    Ok(autopay)
+}
+
+#[tauri::command]
+pub async fn set_debt_autopay(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    debt_id: i64,
+    account_id: i64,
+    amount: Option<f64>,
+) -> Result<DebtAutopay, String> {
+    let amount = amount.map(money::parse_dollars).transpose()?;
+    let result = set_debt_autopay_impl(&db_pool.pool(), debt_id, account_id, amount)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Debts, ChangeAction::Updated, [debt_id]);
+    Ok(result)
+}
+
+/// Stop paying a debt automatically; returns whether it had autopay
+pub async fn remove_debt_autopay_impl(db: &SqlitePool, debt_id: i64) -> Result<bool, DebtError> {
+    let previous = get_debt_autopay(db, debt_id).await?;
+
+    let result = sqlx::query("DELETE FROM debt_autopay WHERE debt_id = ?")
+        .bind(debt_id)
+        .execute(db)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+
+    let removed = result.rows_affected() > 0;
+    if removed {
+        AuditRecord::updated(AuditEntity::Debt, debt_id)
+            .old_values(&json!({ "autopay": previous }))
+            .new_values(&json!({ "autopay": null }))
+            .record(db)
+            .await;
+    }
+
+    Ok(removed)
+}
+
+#[tauri::command]
+pub async fn remove_debt_autopay(app: tauri::AppHandle, db_pool: tauri::State<'_, DbPool>, debt_id: i64) -> Result<bool, String> {
+    let result = remove_debt_autopay_impl(&db_pool.pool(), debt_id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Debts, ChangeAction::Updated, [debt_id]);
+    Ok(result)
+}
+
+pub async fn list_debt_autopays_impl(db: &SqlitePool) -> Result<Vec<DebtAutopay>, DebtError> {
+    sqlx::query_as::<_, DebtAutopay>(
+        "SELECT ap.debt_id, d.name AS debt_name, ap.account_id, a.name AS account_name, ap.amount_cents, ap.created_at
+         FROM debt_autopay ap
+         JOIN debts d ON d.id = ap.debt_id
+         JOIN accounts a ON a.id = ap.account_id
+         ORDER BY d.name"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| DebtError::Database(e.to_string()))
+}
+
+#[tauri::command]
+pub async fn list_debt_autopays(db_pool: tauri::State<'_, DbPool>) -> Result<Vec<DebtAutopay>, String> {
+    list_debt_autopays_impl(&db_pool.pool())
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+/// Set the day of the month a debt's payment is due
+/// Days past the end of a short month fall on its last day
+pub async fn set_debt_due_day_impl(db: &SqlitePool, debt_id: i64, due_day: u32) -> Result<Debt, DebtError> {
+    if !(1..=MAX_DEBT_DUE_DAY).contains(&due_day) {
+        return Err(DebtError::InvalidDueDay {
+            max: MAX_DEBT_DUE_DAY,
+            actual: due_day,
+        });
+    }
+
+    let current = get_debt(db, debt_id).await?.ok_or(DebtError::NotFound(debt_id))?;
+
+    let result = sqlx::query("UPDATE debts SET due_day = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(due_day)
+        .bind(debt_id)
+        .execute(db)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+    if result.rows_affected() == 0 {
+        return Err(DebtError::NotFound(debt_id));
+    }
+
+    AuditRecord::updated(AuditEntity::Debt, debt_id)
+        .old_values(&json!({ "due_day": current.due_day }))
+        .new_values(&json!({ "due_day": due_day }))
+        .record(db)
+        .await;
+
+    get_debt(db, debt_id).await?.ok_or(DebtError::NotFound(debt_id))
+}
+
+#[tauri::command]
+pub async fn set_debt_due_day(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    debt_id: i64,
+    due_day: u32,
+) -> Result<Debt, String> {
+    let result = set_debt_due_day_impl(&db_pool.pool(), debt_id, due_day)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Debts, ChangeAction::Updated, [debt_id]);
+    Ok(result)
+}
+
+/// Set a credit-card-style minimum payment: `percent` of the balance, with the debt's
+/// min_payment as the floor. None makes the minimum fixed again
+pub async fn set_debt_min_payment_percent_impl(
+    db: &SqlitePool,
+    debt_id: i64,
+    percent: Option<f64>,
+) -> Result<Debt, DebtError> {
+    if let Some(p) = percent {
+        if !(p > 0.0 && p <= 100.0) {
+            return Err(DebtError::InvalidMinPaymentPercent(p));
+        }
+    }
+
+    let current = get_debt(db, debt_id).await?.ok_or(DebtError::NotFound(debt_id))?;
+
+    sqlx::query("UPDATE debts SET min_payment_percent = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(percent)
+        .bind(debt_id)
+        .execute(db)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+
+    AuditRecord::updated(AuditEntity::Debt, debt_id)
+        .old_values(&json!({ "min_payment_percent": current.min_payment_percent }))
+        .new_values(&json!({ "min_payment_percent": percent }))
+        .record(db)
+        .await;
+
+    get_debt(db, debt_id).await?.ok_or(DebtError::NotFound(debt_id))
+}
+
+#[tauri::command]
+pub async fn set_debt_min_payment_percent(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    debt_id: i64,
+    percent: Option<f64>,
+) -> Result<Debt, String> {
+    let result = set_debt_min_payment_percent_impl(&db_pool.pool(), debt_id, percent)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Debts, ChangeAction::Updated, [debt_id]);
+    Ok(result)
+}
+
+/// Link a debt to the credit card account it tracks, or unlink it with None
+/// The debt takes the card's payment due day when one is set
+pub async fn link_debt_account_impl(
+    db: &SqlitePool,
+    debt_id: i64,
+    account_id: Option<i64>,
+) -> Result<Debt, DebtError> {
+    let current = get_debt(db, debt_id).await?.ok_or(DebtError::NotFound(debt_id))?;
+
+    let mut due_day = None;
+    if let Some(account_id) = account_id {
+        let account: Option<(String, Option<i64>)> =
+            sqlx::query_as("SELECT type, statement_due_day FROM accounts WHERE id = ?")
+                .bind(account_id)
+                .fetch_optional(db)
+                .await
+                .map_err(|e| DebtError::Database(e.to_string()))?;
+        let (account_type, statement_due_day) = account.ok_or(DebtError::AccountNotFound(account_id))?;
+        if account_type != AccountType::CreditCard.to_string() {
+            return Err(DebtError::NotCreditCard(account_id));
+        }
+        due_day = statement_due_day;
+    }
+
+    sqlx::query(
+        "UPDATE debts SET account_id = ?, due_day = COALESCE(?, due_day), updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+    )
+    .bind(account_id)
+    .bind(due_day)
+    .bind(debt_id)
+    .execute(db)
+    .await
+    .map_err(|e| DebtError::Database(e.to_string()))?;
+
+    let updated = get_debt(db, debt_id).await?.ok_or(DebtError::NotFound(debt_id))?;
+    AuditRecord::updated(AuditEntity::Debt, debt_id)
+        .old_values(&json!({ "account_id": current.account_id, "due_day": current.due_day }))
+        .new_values(&json!({ "account_id": updated.account_id, "due_day": updated.due_day }))
+        .record(db)
+        .await;
+
+    Ok(updated)
+}
+
+/// Set a linked debt's balance to what its card's statement says is owed, so payoff
+/// plans start from the real bill. `date` picks the statement as in get_statement_summary
+pub async fn sync_debt_to_statement_impl(
+    db: &SqlitePool,
+    debt_id: i64,
+    date: Option<&str>,
+    today: NaiveDate,
+) -> Result<Debt, DebtError> {
+    let current = get_debt(db, debt_id).await?.ok_or(DebtError::NotFound(debt_id))?;
+    let account_id = current.account_id.ok_or(DebtError::NotLinked(debt_id))?;
+    if let Some(d) = date {
+        NaiveDate::parse_from_str(d, "%Y-%m-%d").map_err(|_| DebtError::InvalidPaymentDate(d.to_string()))?;
+    }
+
+    let statement = get_statement_summary_impl(db, account_id, date, today)
+        .await
+        .map_err(DebtError::Database)?;
+
+    let mut tx = db.begin().await.map_err(|e| DebtError::Database(e.to_string()))?;
+    sqlx::query("UPDATE debts SET balance_cents = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(statement.amount_due_cents)
+        .bind(debt_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+    let closing_date = dates::parse_date(&statement.period_end).unwrap_or(today);
+    record_balance(&mut *tx, debt_id, statement.amount_due_cents, closing_date, BalanceChange::Statement)
+        .await
+        .map_err(|e| DebtError::Database(e.to_string()))?;
+    tx.commit().await.map_err(|e| DebtError::Database(e.to_string()))?;
+
+    AuditRecord::updated(AuditEntity::Debt, debt_id)
+        .old_values(&json!({ "balance": money::from_cents(current.balance_cents) }))
+        .new_values(&json!({
+            "balance": money::from_cents(statement.amount_due_cents),
+            "statement_end": statement.period_end,
+        }))
+        .record(db)
+        .await;
+    tracing::info!(debt_id = debt_id, statement_end = %statement.period_end, "Synced debt balance to statement");
+
+    get_debt(db, debt_id).await?.ok_or(DebtError::NotFound(debt_id))
+}
+
+#[tauri::command]
+pub async fn link_debt_account(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    debt_id: i64,
+    account_id: Option<i64>,
+) -> Result<Debt, String> {
+    let result = link_debt_account_impl(&db_pool.pool(), debt_id, account_id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Debts, ChangeAction::Updated, [debt_id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn sync_debt_to_statement(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    debt_id: i64,
+    date: Option<String>,
+) -> Result<Debt, String> {
+    let result = sync_debt_to_statement_impl(&db_pool.pool(), debt_id, date.as_deref(), dates::today(&db_pool.pool()).await)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Debts, ChangeAction::Updated, [debt_id]);
+    Ok(result)
+}
+
+/// Minimum payments and due dates for open debts from `today` through the next `months_ahead` months
+pub async fn get_upcoming_payments_impl(
+    db: &SqlitePool,
+    months_ahead: u32,
+    today: NaiveDate,
+) -> Result<Vec<PaymentSchedule>, DebtError> {
+    if !(1..=MAX_UPCOMING_PAYMENT_MONTHS).contains(&months_ahead) {
+        return Err(DebtError::InvalidMonthsAhead {
+            max: MAX_UPCOMING_PAYMENT_MONTHS,
+            actual: months_ahead,
+        });
+    }
+
+    let debts = list_debts_impl(db).await?;
+    Ok(PaymentScheduler::upcoming_payments(&debts, today, months_ahead))
+}
+
+#[tauri::command]
+pub async fn get_upcoming_payments(
+    db_pool: tauri::State<'_, DbPool>,
+    months_ahead: u32,
+) -> Result<Vec<PaymentSchedule>, String> {
+    get_upcoming_payments_impl(&db_pool.pool(), months_ahead, dates::today(&db_pool.pool()).await)
         .await
         .map_err(|e| e.to_user_message())
 }
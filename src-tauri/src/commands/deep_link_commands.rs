@@ -0,0 +1,227 @@
+use crate::commands::freeze_commands::flag_freeze_violations_impl;
+use crate::commands::transaction_commands::ensure_unlocked;
+use crate::constants::{DEFAULT_CATEGORY_ID, MAX_DESCRIPTION_LENGTH, MAX_TRANSACTION_CENTS};
+use crate::errors::sanitize_db_error;
+use crate::models::transaction::NewTransaction;
+use crate::services::categorizer::Categorizer;
+use crate::services::deep_link::{DeepLink, DeepLinkAction};
+use crate::services::merchant_normalizer::MerchantNormalizer;
+use crate::utils::access_guard::AccessGuard;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates;
+use crate::utils::money::{self, Cents};
+use crate::DbPool;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tauri::{Emitter, Manager};
+
+/// Event emitted to the frontend with the outcome of a deep link opened by the OS
+pub const DEEP_LINK_EVENT: &str = "deep-link";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DeepLinkResult {
+    ExpenseAdded {
+        transaction_id: i64,
+        account_id: i64,
+        category_id: i64,
+        #[serde(rename = "amount", with = "money::dollars")]
+        amount_cents: Cents,
+    },
+    Navigate {
+        view: String,
+    },
+}
+
+// Business logic functions (used by both commands and tests)
+
+/// Resolve the account for a quick entry: by name, or the only open account when none is given
+async fn resolve_account(db: &SqlitePool, account: Option<&str>) -> Result<i64, String> {
+    let accounts: Vec<(i64, String)> = sqlx::query_as("SELECT id, name FROM accounts WHERE archived = 0 ORDER BY id")
+        .fetch_all(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load accounts"))?;
+
+    match account {
+        Some(name) => accounts
+            .iter()
+            .find(|(_, account_name)| account_name.eq_ignore_ascii_case(name))
+            .map(|(id, _)| *id)
+            .ok_or_else(|| format!("Unknown account: {}", name)),
+        None => match accounts.as_slice() {
+            [(id, _)] => Ok(*id),
+            [] => Err("Create an account before adding expenses".to_string()),
+            _ => Err("Specify an account for the expense".to_string()),
+        },
+    }
+}
+
+async fn add_expense(
+    db: &SqlitePool,
+    amount_cents: Cents,
+    category: Option<&str>,
+    description: Option<&str>,
+    merchant: Option<&str>,
+    account: Option<&str>,
+    date: Option<&str>,
+) -> Result<DeepLinkResult, String> {
+    if amount_cents > MAX_TRANSACTION_CENTS {
+        return Err(format!("Amount exceeds maximum of {}", money::from_cents(MAX_TRANSACTION_CENTS)));
+    }
+    let amount_cents = -amount_cents;
+
+    let date = match date {
+        Some(date) => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date: {}", date))?
+            .format("%Y-%m-%d")
+            .to_string(),
+        None => dates::format_date(dates::today(db).await),
+    };
+    // Links come from outside the app, so they never override a locked month
+    ensure_unlocked(db, &[&date], false).await.map_err(|e| e.to_user_message())?;
+
+    let description = description
+        .or(merchant)
+        .or(category)
+        .unwrap_or("Quick expense")
+        .to_string();
+    if description.len() > MAX_DESCRIPTION_LENGTH {
+        return Err(format!(
+            "Description too long (max {} characters)",
+            MAX_DESCRIPTION_LENGTH
+        ));
+    }
+
+    let account_id = resolve_account(db, account).await?;
+
+    // A named category must exist; otherwise fall back to the categorization rules
+    let category_id = match category {
+        Some(name) => {
+            let found: Option<(i64,)> = sqlx::query_as("SELECT id FROM categories WHERE name = ? COLLATE NOCASE")
+                .bind(name)
+                .fetch_optional(db)
+                .await
+                .map_err(|e| sanitize_db_error(e, "load category"))?;
+            found.map(|(id,)| id).ok_or_else(|| format!("Unknown category: {}", name))?
+        }
+        None => Categorizer::categorize(db, merchant, &description, amount_cents, Some(account_id))
+            .await
+            .map_err(|e| sanitize_db_error(e, "categorize expense"))?
+            .unwrap_or(DEFAULT_CATEGORY_ID),
+    };
+
+    let merchant_id = match merchant {
+        Some(raw) => MerchantNormalizer::resolve(db, raw)
+            .await
+            .map_err(|e| sanitize_db_error(e, "resolve merchant"))?,
+        None => None,
+    };
+
+    // Quick entries are never duplicates of each other (two coffees on the same day are both real),
+    // so the hash is salted rather than derived from the visible fields alone
+    let salt = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    let hash = NewTransaction::calculate_hash(account_id, &date, amount_cents, &format!("{}|deep-link|{}", description, salt));
+
+    let result = sqlx::query(
+        "INSERT INTO transactions (account_id, category_id, date, amount_cents, description, merchant, merchant_id, hash,
+            hash_version)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(account_id)
+    .bind(category_id)
+    .bind(&date)
+    .bind(amount_cents)
+    .bind(&description)
+    .bind(merchant)
+    .bind(merchant_id)
+    .bind(&hash)
+    .bind(NewTransaction::HASH_VERSION)
+    .execute(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "add expense"))?;
+    let transaction_id = result.last_insert_rowid();
+
+    if let Err(e) = flag_freeze_violations_impl(db, &[transaction_id]).await {
+        tracing::warn!(error = %e, "Failed to flag spending freeze violation");
+    }
+
+    Ok(DeepLinkResult::ExpenseAdded {
+        transaction_id,
+        account_id,
+        category_id,
+        amount_cents,
+    })
+}
+
+/// Dispatch a budgetbalancer:// deep link to the matching action
+pub async fn handle_deep_link_impl(db: &SqlitePool, url: &str) -> Result<DeepLinkResult, String> {
+    match DeepLink::parse(url)? {
+        DeepLinkAction::AddExpense {
+            amount_cents,
+            category,
+            description,
+            merchant,
+            account,
+            date,
+        } => {
+            add_expense(
+                db,
+                amount_cents,
+                category.as_deref(),
+                description.as_deref(),
+                merchant.as_deref(),
+                account.as_deref(),
+                date.as_deref(),
+            )
+            .await
+        }
+        DeepLinkAction::Open { view } => Ok(DeepLinkResult::Navigate { view }),
+    }
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn handle_deep_link(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    url: String,
+) -> Result<DeepLinkResult, String> {
+    let result = handle_deep_link_impl(&db_pool.pool(), &url).await?;
+    notify_deep_link_changes(&app, &result);
+    Ok(result)
+}
+
+fn notify_deep_link_changes(app: &tauri::AppHandle, result: &DeepLinkResult) {
+    if let DeepLinkResult::ExpenseAdded { transaction_id, .. } = result {
+        notify_changed(app, DataKind::Transactions, ChangeAction::Created, [*transaction_id]);
+    }
+}
+
+/// Handle deep links delivered by the OS and report each outcome to the frontend
+pub fn dispatch_deep_links(app: &tauri::AppHandle, urls: Vec<url::Url>) {
+    for url in urls {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let pool = app.state::<DbPool>();
+            // OS links skip the invoke handler, so read-only and viewer mode are checked here
+            let result = match app.state::<AccessGuard>().check("handle_deep_link") {
+                Ok(()) => handle_deep_link_impl(&pool.pool(), url.as_str()).await,
+                Err(e) => Err(e.to_user_message()),
+            };
+            let payload = match result {
+                Ok(result) => {
+                    notify_deep_link_changes(&app, &result);
+                    serde_json::json!({ "ok": true, "result": result })
+                }
+                Err(error) => {
+                    tracing::warn!(error = %error, "Deep link failed");
+                    serde_json::json!({ "ok": false, "error": error })
+                }
+            };
+            if let Err(e) = app.emit(DEEP_LINK_EVENT, payload) {
+                tracing::error!(error = %e, "Failed to emit deep link result");
+            }
+        });
+    }
+}
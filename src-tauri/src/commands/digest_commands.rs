@@ -0,0 +1,158 @@
+use crate::commands::notification_commands::create_notification_impl;
+use crate::commands::settings_commands::get_setting_impl;
+use crate::models::notification::{NewNotification, NotificationSeverity};
+use crate::models::setting::SettingKey;
+use crate::services::digest_generator::{Digest, DigestFrequency, DigestGenerator};
+use crate::utils::dates;
+use crate::DbPool;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize)]
+pub struct WeeklyDigestResult {
+    pub digest: Digest,
+    pub notification_id: i64,
+    pub file_path: Option<String>, // Only set when a digest folder is configured
+}
+
+#[derive(Debug, Serialize)]
+pub struct GeneratedDigest {
+    pub digest: Digest,
+    pub format: String,
+    pub content: String,
+    pub file_path: Option<String>, // Only set when an output path was given
+}
+
+// Business logic functions (used by both commands and tests)
+
+type Renderer = fn(&Digest) -> String;
+
+/// Format name, file extension, and renderer for "markdown" (default) or "html"
+fn renderer(format: Option<&str>) -> Result<(&'static str, &'static str, Renderer), String> {
+    match format.unwrap_or("markdown") {
+        "markdown" => Ok(("markdown", "md", DigestGenerator::render_markdown)),
+        "html" => Ok(("html", "html", DigestGenerator::render_html)),
+        other => Err(format!("Unsupported format: {}", other)),
+    }
+}
+
+async fn parse_date(db: &SqlitePool, date: Option<&str>) -> Result<chrono::NaiveDate, String> {
+    match date {
+        Some(date) => dates::parse_date(date),
+        None => Ok(dates::today(db).await),
+    }
+}
+
+/// Render the digest for the week ending on `date` or the month containing it (default today)
+/// Nothing is stored; the document is also written to `output_path` when one is given
+pub async fn generate_digest_impl(
+    db: &SqlitePool,
+    frequency: &str,
+    date: Option<&str>,
+    format: Option<&str>,
+    output_path: Option<&str>,
+) -> Result<GeneratedDigest, String> {
+    let frequency: DigestFrequency = frequency.parse()?;
+    let date = parse_date(db, date).await?;
+    let (format, _, render) = renderer(format)?;
+
+    let digest = DigestGenerator::build(db, frequency, date).await?;
+    let content = render(&digest);
+
+    let file_path = match output_path {
+        Some(path) => {
+            std::fs::write(path, &content).map_err(|e| format!("Failed to write digest: {}", e))?;
+            Some(path.to_string())
+        }
+        None => None,
+    };
+
+    Ok(GeneratedDigest {
+        digest,
+        format: format.to_string(),
+        content,
+        file_path,
+    })
+}
+
+/// Generate the weekly digest for the 7 days ending on `week_ending` (default today),
+/// store it as a notification, and write it to the configured digest folder if there is one
+/// `format` is "markdown" (default) or "html"
+pub async fn generate_weekly_digest_impl(
+    db: &SqlitePool,
+    week_ending: Option<&str>,
+    format: Option<&str>,
+) -> Result<WeeklyDigestResult, String> {
+    let week_ending = parse_date(db, week_ending).await?;
+    let (_, extension, render) = renderer(format)?;
+
+    let digest = DigestGenerator::build_weekly(db, week_ending).await?;
+
+    let file_path = match get_setting_impl(db, SettingKey::DigestOutputDir).await? {
+        Some(dir) => {
+            let mut path = PathBuf::from(dir);
+            path.push(format!("weekly-digest-{}.{}", digest.period.end_date, extension));
+            std::fs::write(&path, render(&digest))
+                .map_err(|e| format!("Failed to write digest: {}", e))?;
+            Some(path.display().to_string())
+        }
+        None => None,
+    };
+
+    let notification = create_notification_impl(
+        db,
+        NewNotification {
+            notification_type: "weekly_digest".to_string(),
+            severity: if digest.budgets_over > 0 {
+                NotificationSeverity::Warning
+            } else {
+                NotificationSeverity::Info
+            },
+            title: format!(
+                "Weekly summary: {} to {}",
+                digest.period.start_date, digest.period.end_date
+            ),
+            message: DigestGenerator::summary(&digest),
+            entity_type: None,
+            entity_id: None,
+        },
+    )
+    .await
+    .map_err(|e| e.to_user_message())?;
+
+    Ok(WeeklyDigestResult {
+        digest,
+        notification_id: notification.id,
+        file_path,
+    })
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn generate_weekly_digest(
+    db_pool: tauri::State<'_, DbPool>,
+    week_ending: Option<String>,
+    format: Option<String>,
+) -> Result<WeeklyDigestResult, String> {
+    generate_weekly_digest_impl(&db_pool.pool(), week_ending.as_deref(), format.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn generate_digest(
+    db_pool: tauri::State<'_, DbPool>,
+    frequency: String,
+    date: Option<String>,
+    format: Option<String>,
+    output_path: Option<String>,
+) -> Result<GeneratedDigest, String> {
+    generate_digest_impl(
+        &db_pool.pool(),
+        &frequency,
+        date.as_deref(),
+        format.as_deref(),
+        output_path.as_deref(),
+    )
+    .await
+}
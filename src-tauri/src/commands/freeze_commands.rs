@@ -0,0 +1,227 @@
+use crate::commands::notification_commands::create_notification_impl;
+use crate::constants::{MAX_DESCRIPTION_LENGTH, MAX_FREEZE_NAME_LENGTH};
+use crate::errors::sanitize_db_error;
+use crate::models::notification::{NewNotification, NotificationSeverity};
+use crate::models::spending_freeze::{FreezeStatus, NewSpendingFreeze, SpendingFreeze};
+use crate::services::freeze_tracker::FreezeTracker;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates;
+use crate::utils::money::{self, Cents};
+use crate::DbPool;
+use sqlx::SqlitePool;
+
+// Business logic functions (used by both commands and tests)
+
+async fn get_freeze(db: &SqlitePool, freeze_id: i64) -> Result<SpendingFreeze, String> {
+    sqlx::query_as::<_, SpendingFreeze>(
+        "SELECT id, name, category_id, start_date, end_date, created_at FROM spending_freezes WHERE id = ?"
+    )
+    .bind(freeze_id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "load spending freeze"))?
+    .ok_or_else(|| format!("Spending freeze {} not found", freeze_id))
+}
+
+/// Start a freeze on one category, or on all spending when no category is given
+pub async fn create_spending_freeze_impl(
+    db: &SqlitePool,
+    freeze: NewSpendingFreeze,
+) -> Result<SpendingFreeze, String> {
+    let name = freeze.name.trim();
+    if name.is_empty() {
+        return Err("Freeze name cannot be empty".to_string());
+    }
+    if name.len() > MAX_FREEZE_NAME_LENGTH {
+        return Err(format!("Freeze name too long (max {} characters)", MAX_FREEZE_NAME_LENGTH));
+    }
+
+    let start = dates::parse_date(&freeze.start_date)?;
+    let end = freeze.end_date.as_deref().map(dates::parse_date).transpose()?;
+    if end.is_some_and(|end| end < start) {
+        return Err("Freeze end date must be on or after the start date".to_string());
+    }
+
+    if let Some(category_id) = freeze.category_id {
+        let exists = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM categories WHERE id = ?")
+            .bind(category_id)
+            .fetch_one(db)
+            .await
+            .map_err(|e| sanitize_db_error(e, "check category"))?
+            .0 > 0;
+        if !exists {
+            return Err(format!("Category {} not found", category_id));
+        }
+    }
+
+    let result = sqlx::query(
+        "INSERT INTO spending_freezes (name, category_id, start_date, end_date) VALUES (?, ?, ?, ?)"
+    )
+    .bind(name)
+    .bind(freeze.category_id)
+    .bind(dates::format_date(start))
+    .bind(end.map(dates::format_date))
+    .execute(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "create spending freeze"))?;
+
+    get_freeze(db, result.last_insert_rowid()).await
+}
+
+/// End a freeze on `end_date` (default today); violations up to then are kept in its history
+pub async fn lift_spending_freeze_impl(
+    db: &SqlitePool,
+    freeze_id: i64,
+    end_date: Option<&str>,
+) -> Result<SpendingFreeze, String> {
+    let freeze = get_freeze(db, freeze_id).await?;
+    let end = match end_date {
+        Some(date) => dates::parse_date(date)?,
+        None => dates::today(db).await,
+    };
+    if end < dates::parse_date(&freeze.start_date)? {
+        return Err("Freeze end date must be on or after the start date".to_string());
+    }
+
+    sqlx::query("UPDATE spending_freezes SET end_date = ? WHERE id = ?")
+        .bind(dates::format_date(end))
+        .bind(freeze_id)
+        .execute(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "lift spending freeze"))?;
+
+    get_freeze(db, freeze_id).await
+}
+
+pub async fn delete_spending_freeze_impl(db: &SqlitePool, freeze_id: i64) -> Result<(), String> {
+    let result = sqlx::query("DELETE FROM spending_freezes WHERE id = ?")
+        .bind(freeze_id)
+        .execute(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "delete spending freeze"))?;
+
+    if result.rows_affected() == 0 {
+        return Err(format!("Spending freeze {} not found", freeze_id));
+    }
+    Ok(())
+}
+
+/// Violation tallies and no-spend streaks for every freeze as of `as_of` (default today)
+/// Active freezes come first, then the most recently started
+pub async fn get_freeze_status_impl(db: &SqlitePool, as_of: Option<&str>) -> Result<Vec<FreezeStatus>, String> {
+    let as_of = match as_of {
+        Some(date) => dates::parse_date(date)?,
+        None => dates::today(db).await,
+    };
+
+    let freezes = sqlx::query_as::<_, (i64, String, Option<i64>, String, Option<String>, String, Option<String>)>(
+        "SELECT f.id, f.name, f.category_id, f.start_date, f.end_date, f.created_at, c.name
+         FROM spending_freezes f
+         LEFT JOIN categories c ON c.id = f.category_id
+         ORDER BY f.start_date DESC, f.id DESC"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "load spending freezes"))?;
+
+    let mut statuses = Vec::with_capacity(freezes.len());
+    for (id, name, category_id, start_date, end_date, created_at, category_name) in freezes {
+        let freeze = SpendingFreeze {
+            id,
+            name,
+            category_id,
+            start_date,
+            end_date,
+            created_at,
+        };
+        statuses.push(FreezeTracker::status(db, freeze, category_name, as_of).await?);
+    }
+    // Stable sort keeps the start-date order within each group
+    statuses.sort_by_key(|status| !status.active);
+
+    Ok(statuses)
+}
+
+/// Flag newly added transactions that break a freeze with one notification per freeze
+/// Returns the number of violating transactions
+pub async fn flag_freeze_violations_impl(db: &SqlitePool, transaction_ids: &[i64]) -> Result<usize, String> {
+    let breaches = FreezeTracker::breaches(db, transaction_ids).await?;
+
+    let mut flagged = 0;
+    for breach in breaches {
+        let total: Cents = breach.violations.iter().map(|v| -v.amount_cents).sum();
+        let (mut message, entity) = match breach.violations.as_slice() {
+            [violation] => (
+                format!("{} (${:.2}) on {}", violation.description, money::from_cents(-violation.amount_cents), violation.date),
+                Some(violation.transaction_id),
+            ),
+            violations => (format!("{} transactions totaling ${:.2}", violations.len(), money::from_cents(total)), None),
+        };
+        if message.len() > MAX_DESCRIPTION_LENGTH {
+            let cut = (0..=MAX_DESCRIPTION_LENGTH).rev().find(|i| message.is_char_boundary(*i)).unwrap_or(0);
+            message.truncate(cut);
+        }
+
+        create_notification_impl(
+            db,
+            NewNotification {
+                notification_type: "freeze_violation".to_string(),
+                severity: NotificationSeverity::Warning,
+                title: format!("Spending freeze broken: {}", breach.freeze_name),
+                message,
+                entity_type: entity.map(|_| "transaction".to_string()),
+                entity_id: entity,
+            },
+        )
+        .await
+        .map_err(|e| e.to_user_message())?;
+
+        flagged += breach.violations.len();
+    }
+
+    Ok(flagged)
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn create_spending_freeze(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    freeze: NewSpendingFreeze,
+) -> Result<SpendingFreeze, String> {
+    let result = create_spending_freeze_impl(&db_pool.pool(), freeze).await?;
+    notify_changed(&app, DataKind::SpendingFreezes, ChangeAction::Created, [result.id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn lift_spending_freeze(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    freeze_id: i64,
+    end_date: Option<String>,
+) -> Result<SpendingFreeze, String> {
+    let result = lift_spending_freeze_impl(&db_pool.pool(), freeze_id, end_date.as_deref()).await?;
+    notify_changed(&app, DataKind::SpendingFreezes, ChangeAction::Updated, [freeze_id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn delete_spending_freeze(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    freeze_id: i64,
+) -> Result<(), String> {
+    delete_spending_freeze_impl(&db_pool.pool(), freeze_id).await?;
+    notify_changed(&app, DataKind::SpendingFreezes, ChangeAction::Deleted, [freeze_id]);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_freeze_status(
+    db_pool: tauri::State<'_, DbPool>,
+    as_of: Option<String>,
+) -> Result<Vec<FreezeStatus>, String> {
+    get_freeze_status_impl(&db_pool.pool(), as_of.as_deref()).await
+}
@@ -0,0 +1,189 @@
+use crate::commands::transaction_commands::ensure_unlocked;
+use crate::constants::CURRENCY_CODE_LENGTH;
+use crate::errors::sanitize_db_error;
+use crate::models::exchange_rate::{ExchangeRate, ForeignSpendingReport};
+use crate::services::fx_converter::FxConverter;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates;
+use crate::utils::money::{self, Cents};
+use crate::DbPool;
+use sqlx::SqlitePool;
+
+// Business logic functions (used by both commands and tests)
+
+/// Normalize a currency code to upper case, rejecting anything but three letters
+fn normalize_currency(currency: &str) -> Result<String, String> {
+    let code = currency.trim().to_ascii_uppercase();
+    if code.len() != CURRENCY_CODE_LENGTH || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(format!("Invalid currency code: {}", currency));
+    }
+    Ok(code)
+}
+
+/// Record the rate for a currency on a date, replacing any rate already on file for that date
+pub async fn set_exchange_rate_impl(
+    db: &SqlitePool,
+    currency: &str,
+    date: &str,
+    rate: f64,
+) -> Result<ExchangeRate, String> {
+    let currency = normalize_currency(currency)?;
+    let date = dates::format_date(dates::parse_date(date)?);
+    if !rate.is_finite() || rate <= 0.0 {
+        return Err("Exchange rate must be a positive number".to_string());
+    }
+
+    sqlx::query(
+        "INSERT INTO exchange_rates (currency, date, rate) VALUES (?, ?, ?)
+         ON CONFLICT(currency, date) DO UPDATE SET rate = excluded.rate"
+    )
+    .bind(&currency)
+    .bind(&date)
+    .bind(rate)
+    .execute(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "save exchange rate"))?;
+
+    sqlx::query_as::<_, ExchangeRate>(
+        "SELECT id, currency, date, rate, created_at FROM exchange_rates WHERE currency = ? AND date = ?"
+    )
+    .bind(&currency)
+    .bind(date)
+    .fetch_one(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "load exchange rate"))
+}
+
+/// Rates on file, newest first, optionally for one currency
+pub async fn list_exchange_rates_impl(db: &SqlitePool, currency: Option<&str>) -> Result<Vec<ExchangeRate>, String> {
+    let currency = currency.map(normalize_currency).transpose()?;
+
+    sqlx::query_as::<_, ExchangeRate>(
+        "SELECT id, currency, date, rate, created_at
+         FROM exchange_rates
+         WHERE (? IS NULL OR currency = ?)
+         ORDER BY currency, date DESC"
+    )
+    .bind(&currency)
+    .bind(&currency)
+    .fetch_all(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "list exchange rates"))
+}
+
+/// Mark a transaction as charged in a foreign currency, or clear it when `currency` is None
+/// `original_amount_cents` is in that currency and must have the same sign as the posted amount
+/// Transactions in a locked month are left alone unless `override_lock` is set
+pub async fn set_transaction_currency_impl(
+    db: &SqlitePool,
+    transaction_id: i64,
+    currency: Option<&str>,
+    original_amount_cents: Option<Cents>,
+    override_lock: bool,
+) -> Result<(), String> {
+    let (amount, date) = sqlx::query_as::<_, (Cents, String)>("SELECT amount_cents, date FROM transactions WHERE id = ?")
+        .bind(transaction_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load transaction"))?
+        .ok_or_else(|| format!("Transaction {} not found", transaction_id))?;
+    ensure_unlocked(db, &[date.as_str()], override_lock)
+        .await
+        .map_err(|e| e.to_user_message())?;
+
+    let (currency, original_amount) = match currency {
+        Some(currency) => {
+            let original_amount = original_amount_cents.ok_or("Original amount is required with a currency")?;
+            if original_amount == 0 || original_amount.signum() != amount.signum() {
+                return Err("Original amount must be non-zero with the same sign as the transaction amount".to_string());
+            }
+            (Some(normalize_currency(currency)?), Some(original_amount))
+        }
+        None => (None, None),
+    };
+
+    sqlx::query("UPDATE transactions SET original_currency = ?, original_amount_cents = ? WHERE id = ?")
+        .bind(currency)
+        .bind(original_amount)
+        .bind(transaction_id)
+        .execute(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "update transaction currency"))?;
+
+    Ok(())
+}
+
+/// Spending by original currency between two dates, converted at transaction-date rates
+/// and at the rates in effect on `as_of` (default today) to show the FX impact
+pub async fn get_foreign_spending_report_impl(
+    db: &SqlitePool,
+    start_date: &str,
+    end_date: &str,
+    as_of: Option<&str>,
+) -> Result<ForeignSpendingReport, String> {
+    if dates::parse_date(start_date)? > dates::parse_date(end_date)? {
+        return Err("Start date must be on or before the end date".to_string());
+    }
+    let as_of = match as_of {
+        Some(date) => dates::parse_date(date)?,
+        None => dates::today(db).await,
+    };
+
+    FxConverter::report(db, start_date, end_date, &as_of.format("%Y-%m-%d").to_string())
+        .await
+        .map_err(|e| format!("Failed to build foreign spending report: {}", e))
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn set_exchange_rate(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    currency: String,
+    date: String,
+    rate: f64,
+) -> Result<ExchangeRate, String> {
+    let result = set_exchange_rate_impl(&db_pool.pool(), &currency, &date, rate).await?;
+    notify_changed(&app, DataKind::ExchangeRates, ChangeAction::Updated, [result.id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn list_exchange_rates(
+    db_pool: tauri::State<'_, DbPool>,
+    currency: Option<String>,
+) -> Result<Vec<ExchangeRate>, String> {
+    list_exchange_rates_impl(&db_pool.pool(), currency.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn set_transaction_currency(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    transaction_id: i64,
+    currency: Option<String>,
+    original_amount: Option<f64>,
+    override_lock: Option<bool>,
+) -> Result<(), String> {
+    set_transaction_currency_impl(
+        &db_pool.pool(),
+        transaction_id,
+        currency.as_deref(),
+        original_amount.map(money::parse_dollars).transpose()?,
+        override_lock.unwrap_or(false),
+    )
+    .await?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Updated, [transaction_id]);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_foreign_spending_report(
+    db_pool: tauri::State<'_, DbPool>,
+    start_date: String,
+    end_date: String,
+    as_of: Option<String>,
+) -> Result<ForeignSpendingReport, String> {
+    get_foreign_spending_report_impl(&db_pool.pool(), &start_date, &end_date, as_of.as_deref()).await
+}
@@ -0,0 +1,314 @@
+// Investments: what each account holds and the prices it's valued at
+//
+// Holdings and prices are dated records; the latest of each on or before a date is what counts
+// then, so the value of an account can be worked out for any day in its history
+
+use crate::constants::{MAX_CSV_FILE_SIZE, MAX_CSV_ROWS, MAX_SECURITY_SYMBOL_LENGTH};
+use crate::errors::sanitize_db_error;
+use crate::models::investment::{
+    Holding, HoldingValue, NewHolding, PriceImportError, PriceImportResult, SecurityPrice,
+};
+use crate::services::csv_parser::CsvParser;
+use crate::services::portfolio_valuer::PortfolioValuer;
+use crate::services::price_csv::PriceCsv;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates;
+use crate::DbPool;
+use sqlx::SqlitePool;
+use std::collections::BTreeSet;
+
+/// Normalize a ticker symbol to upper case, rejecting blanks and anything that isn't a ticker
+fn normalize_symbol(symbol: &str) -> Result<String, String> {
+    let symbol = symbol.trim().to_uppercase();
+    let valid = !symbol.is_empty()
+        && symbol.len() <= MAX_SECURITY_SYMBOL_LENGTH
+        && symbol.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-'));
+    if !valid {
+        return Err(format!("Invalid symbol: {}", symbol));
+    }
+    Ok(symbol)
+}
+
+/// A YYYY-MM-DD date, defaulting to today
+async fn date_or_today(db: &SqlitePool, date: Option<&str>) -> Result<String, String> {
+    match date {
+        Some(date) => dates::parse_date(date).map(dates::format_date),
+        None => Ok(dates::format_date(dates::today(db).await)),
+    }
+}
+
+// Business logic functions (used by both commands and tests)
+
+/// Record how much of a security an account holds from a date on (default today)
+/// A record already on file for the same account, symbol and date is replaced
+pub async fn record_holding_impl(db: &SqlitePool, holding: NewHolding) -> Result<Holding, String> {
+    let symbol = normalize_symbol(&holding.symbol)?;
+    if !holding.quantity.is_finite() || holding.quantity < 0.0 {
+        return Err("Quantity must be zero or more".to_string());
+    }
+    if holding.cost_basis_cents.is_some_and(|cost| cost < 0) {
+        return Err("Cost basis must be zero or more".to_string());
+    }
+    let date = date_or_today(db, holding.date.as_deref()).await?;
+
+    let account_exists: Option<i64> = sqlx::query_scalar("SELECT id FROM accounts WHERE id = ?")
+        .bind(holding.account_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load account"))?;
+    if account_exists.is_none() {
+        return Err(format!("Account {} not found", holding.account_id));
+    }
+
+    sqlx::query(
+        "INSERT INTO holdings (account_id, symbol, quantity, cost_basis_cents, date) VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT(account_id, symbol, date) DO UPDATE SET quantity = excluded.quantity, cost_basis_cents = excluded.cost_basis_cents"
+    )
+    .bind(holding.account_id)
+    .bind(&symbol)
+    .bind(holding.quantity)
+    .bind(holding.cost_basis_cents)
+    .bind(&date)
+    .execute(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "save holding"))?;
+
+    sqlx::query_as::<_, Holding>(
+        "SELECT id, account_id, symbol, quantity, cost_basis_cents, date, created_at
+         FROM holdings WHERE account_id = ? AND symbol = ? AND date = ?"
+    )
+    .bind(holding.account_id)
+    .bind(&symbol)
+    .bind(&date)
+    .fetch_one(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "load holding"))
+}
+
+/// Positions held on `as_of` (default today), optionally in one account, with their current value
+pub async fn list_holdings_impl(
+    db: &SqlitePool,
+    account_id: Option<i64>,
+    as_of: Option<&str>,
+) -> Result<Vec<HoldingValue>, String> {
+    let date = date_or_today(db, as_of).await?;
+    PortfolioValuer::holdings_on(db, &date, account_id)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load holdings"))
+}
+
+/// Every holding record for an account, newest first, for reviewing and correcting its history
+pub async fn list_holding_history_impl(db: &SqlitePool, account_id: i64) -> Result<Vec<Holding>, String> {
+    sqlx::query_as::<_, Holding>(
+        "SELECT id, account_id, symbol, quantity, cost_basis_cents, date, created_at
+         FROM holdings WHERE account_id = ?
+         ORDER BY date DESC, symbol"
+    )
+    .bind(account_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "load holding history"))
+}
+
+/// Remove one holding record; the record before it, if any, is in effect again
+pub async fn delete_holding_impl(db: &SqlitePool, holding_id: i64) -> Result<(), String> {
+    let result = sqlx::query("DELETE FROM holdings WHERE id = ?")
+        .bind(holding_id)
+        .execute(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "delete holding"))?;
+    if result.rows_affected() == 0 {
+        return Err(format!("Holding {} not found", holding_id));
+    }
+    Ok(())
+}
+
+/// Record the price of a security on a date (default today), replacing any price already on file for that date
+pub async fn set_security_price_impl(
+    db: &SqlitePool,
+    symbol: &str,
+    price: f64,
+    date: Option<&str>,
+) -> Result<SecurityPrice, String> {
+    let symbol = normalize_symbol(symbol)?;
+    if !price.is_finite() || price < 0.0 {
+        return Err("Price must be zero or more".to_string());
+    }
+    let date = date_or_today(db, date).await?;
+
+    sqlx::query(
+        "INSERT INTO security_prices (symbol, date, price, source) VALUES (?, ?, ?, 'manual')
+         ON CONFLICT(symbol, date) DO UPDATE SET price = excluded.price, source = excluded.source"
+    )
+    .bind(&symbol)
+    .bind(&date)
+    .bind(price)
+    .execute(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "save security price"))?;
+
+    sqlx::query_as::<_, SecurityPrice>(
+        "SELECT id, symbol, date, price, source, created_at FROM security_prices WHERE symbol = ? AND date = ?"
+    )
+    .bind(&symbol)
+    .bind(&date)
+    .fetch_one(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "load security price"))
+}
+
+/// Prices on file, newest first, optionally for one symbol
+pub async fn list_security_prices_impl(db: &SqlitePool, symbol: Option<&str>) -> Result<Vec<SecurityPrice>, String> {
+    let symbol = symbol.map(normalize_symbol).transpose()?;
+
+    sqlx::query_as::<_, SecurityPrice>(
+        "SELECT id, symbol, date, price, source, created_at
+         FROM security_prices
+         WHERE (? IS NULL OR symbol = ?)
+         ORDER BY symbol, date DESC"
+    )
+    .bind(&symbol)
+    .bind(&symbol)
+    .fetch_all(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "list security prices"))
+}
+
+/// Import prices from a CSV with Symbol, Date and Price columns, such as a quote history download
+/// Lines that can't be read are reported and skipped; the rest are saved together, replacing
+/// prices already on file for the same symbol and date
+pub async fn import_security_prices_csv_impl(db: &SqlitePool, csv_content: &str) -> Result<PriceImportResult, String> {
+    if csv_content.len() > MAX_CSV_FILE_SIZE {
+        return Err(format!("File is too large ({} bytes, max {})", csv_content.len(), MAX_CSV_FILE_SIZE));
+    }
+    let lines = PriceCsv::parse(csv_content)?;
+    if lines.len() > MAX_CSV_ROWS {
+        return Err(format!("Too many rows ({}, max {})", lines.len(), MAX_CSV_ROWS));
+    }
+
+    let mut prices = Vec::with_capacity(lines.len());
+    let mut errors = Vec::new();
+    for line in lines {
+        let parsed = normalize_symbol(&line.symbol).and_then(|symbol| {
+            let date = CsvParser::normalize_date(&line.date).map_err(|_| format!("Invalid date: {}", line.date))?;
+            let price = CsvParser::parse_amount(&line.price, None)
+                .ok()
+                .filter(|price| price.is_finite() && *price >= 0.0)
+                .ok_or_else(|| format!("Invalid price: {}", line.price))?;
+            Ok((symbol, date, price))
+        });
+        match parsed {
+            Ok(price) => prices.push(price),
+            Err(message) => errors.push(PriceImportError { line: line.line, message }),
+        }
+    }
+
+    let db_error = |e| sanitize_db_error(e, "import security prices");
+    let mut tx = db.begin().await.map_err(db_error)?;
+    for (symbol, date, price) in &prices {
+        sqlx::query(
+            "INSERT INTO security_prices (symbol, date, price, source) VALUES (?, ?, ?, 'csv')
+             ON CONFLICT(symbol, date) DO UPDATE SET price = excluded.price, source = excluded.source"
+        )
+        .bind(symbol)
+        .bind(date)
+        .bind(price)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    }
+    tx.commit().await.map_err(db_error)?;
+
+    let symbols: BTreeSet<String> = prices.iter().map(|(symbol, _, _)| symbol.clone()).collect();
+    Ok(PriceImportResult {
+        imported: prices.len(),
+        symbols: symbols.into_iter().collect(),
+        errors,
+    })
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn record_holding(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    holding: NewHolding,
+) -> Result<Holding, String> {
+    let result = record_holding_impl(&db_pool.pool(), holding).await?;
+    notify_changed(&app, DataKind::Investments, ChangeAction::Updated, [result.id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn list_holdings(
+    db_pool: tauri::State<'_, DbPool>,
+    account_id: Option<i64>,
+    as_of: Option<String>,
+) -> Result<Vec<HoldingValue>, String> {
+    list_holdings_impl(&db_pool.pool(), account_id, as_of.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn list_holding_history(db_pool: tauri::State<'_, DbPool>, account_id: i64) -> Result<Vec<Holding>, String> {
+    list_holding_history_impl(&db_pool.pool(), account_id).await
+}
+
+#[tauri::command]
+pub async fn delete_holding(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    holding_id: i64,
+) -> Result<(), String> {
+    delete_holding_impl(&db_pool.pool(), holding_id).await?;
+    notify_changed(&app, DataKind::Investments, ChangeAction::Deleted, [holding_id]);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_security_price(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    symbol: String,
+    price: f64,
+    date: Option<String>,
+) -> Result<SecurityPrice, String> {
+    let result = set_security_price_impl(&db_pool.pool(), &symbol, price, date.as_deref()).await?;
+    notify_changed(&app, DataKind::Investments, ChangeAction::Updated, vec![]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn list_security_prices(
+    db_pool: tauri::State<'_, DbPool>,
+    symbol: Option<String>,
+) -> Result<Vec<SecurityPrice>, String> {
+    list_security_prices_impl(&db_pool.pool(), symbol.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn import_security_prices_csv(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    csv_content: String,
+) -> Result<PriceImportResult, String> {
+    let result = import_security_prices_csv_impl(&db_pool.pool(), &csv_content).await?;
+    if result.imported > 0 {
+        notify_changed(&app, DataKind::Investments, ChangeAction::Updated, vec![]);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_symbol() {
+        assert_eq!(normalize_symbol(" vti ").unwrap(), "VTI");
+        assert_eq!(normalize_symbol("brk.b").unwrap(), "BRK.B");
+        assert!(normalize_symbol("").is_err());
+        assert!(normalize_symbol("VTI FUND").is_err());
+        assert!(normalize_symbol("ABCDEFGHIJKLM").is_err(), "Longer than any ticker");
+    }
+}
@@ -0,0 +1,334 @@
+use crate::commands::analytics_commands::{export_analytics_report_impl, ExportReportResponse};
+use crate::commands::csv_commands::{
+    import_csv_batch_impl, import_csv_cancellable_impl, notify_import_changes, rehash_transactions_impl, BatchImportFile,
+};
+use crate::constants::{DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::errors::JobError;
+use crate::models::job::{Job, JobKind, JobStatus};
+use crate::services::csv_parser::ColumnMapping;
+use crate::services::job_queue::{JobNotifier, JobQueue};
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::DbPool;
+use sqlx::SqlitePool;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+use tauri::Emitter;
+
+/// Event emitted with the Job whenever a background job starts, reports progress, or finishes
+pub const JOB_PROGRESS_EVENT: &str = "job-progress";
+
+/// Options for a background report export, as taken by export_analytics_report
+#[derive(Debug, Clone)]
+pub struct ReportExportRequest {
+    pub format: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub include_charts: bool,
+    pub output_path: String,
+}
+
+fn event_notifier(app: tauri::AppHandle) -> JobNotifier {
+    Arc::new(move |job: &Job| {
+        if let Err(e) = app.emit(JOB_PROGRESS_EVENT, job.clone()) {
+            tracing::warn!(error = %e, job_id = job.id, "Failed to emit job progress");
+        }
+    })
+}
+
+// Business logic functions (used by both commands and tests)
+
+pub async fn get_job_status_impl(db: &SqlitePool, job_id: i64) -> Result<Job, JobError> {
+    JobQueue::get(db, job_id).await
+}
+
+pub async fn list_jobs_impl(db: &SqlitePool, status: Option<&str>, limit: Option<i64>) -> Result<Vec<Job>, JobError> {
+    let status = status
+        .map(|s| s.parse::<JobStatus>())
+        .transpose()
+        .map_err(JobError::Database)?;
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    JobQueue::list(db, status, limit).await
+}
+
+pub async fn cancel_job_impl(db: &SqlitePool, job_id: i64) -> Result<Job, JobError> {
+    JobQueue::request_cancel(db, job_id).await
+}
+
+/// Run a CSV import as job `job_id`
+/// Progress is recorded after each batch; a cancel request stops the import at the next batch
+pub async fn run_import_job(
+    db: SqlitePool,
+    job_id: i64,
+    notify: JobNotifier,
+    account_id: i64,
+    csv_content: String,
+    mapping: ColumnMapping,
+    override_lock: bool,
+) -> Result<Job, JobError> {
+    JobQueue::run(db.clone(), job_id, notify, |ctx| async move {
+        import_csv_cancellable_impl(&db, account_id, csv_content, mapping, override_lock, |progress| {
+            // The importer reports progress synchronously, so record it in the background
+            let recorder = ctx.clone();
+            let message = format!(
+                "{} imported, {} duplicates, {} errors",
+                progress.imported, progress.duplicates, progress.errors
+            );
+            let (processed, total) = (progress.processed as i64, progress.estimated_total as i64);
+            tauri::async_runtime::spawn(async move {
+                // An Err here means cancel was requested; the importer stops at its next batch
+                let _ = recorder.progress(processed, Some(total), Some(&message)).await;
+            });
+
+            if ctx.is_cancelled() {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .await
+        .map_err(|e| e.to_user_message())
+    })
+    .await
+}
+
+/// Run a batch of CSV imports as job `job_id`
+/// Progress counts files; a cancel request stops the file being imported at its next batch
+/// and skips the rest
+pub async fn run_batch_import_job(
+    db: SqlitePool,
+    job_id: i64,
+    notify: JobNotifier,
+    files: Vec<BatchImportFile>,
+    override_lock: bool,
+) -> Result<Job, JobError> {
+    let file_count = files.len() as i64;
+    JobQueue::run(db.clone(), job_id, notify, |ctx| async move {
+        import_csv_batch_impl(&db, files, override_lock, |index, progress| {
+            let recorder = ctx.clone();
+            let message = format!(
+                "File {} of {}: {} imported, {} duplicates, {} errors",
+                index + 1,
+                file_count,
+                progress.imported,
+                progress.duplicates,
+                progress.errors
+            );
+            let processed = index as i64 + i64::from(progress.done);
+            tauri::async_runtime::spawn(async move {
+                // An Err here means cancel was requested; the importer stops at its next batch
+                let _ = recorder.progress(processed, Some(file_count), Some(&message)).await;
+            });
+
+            if ctx.is_cancelled() {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .await
+        .map_err(|e| e.to_user_message())
+    })
+    .await
+}
+
+/// Run an analytics report export as job `job_id`
+pub async fn run_report_export_job(
+    db: SqlitePool,
+    job_id: i64,
+    notify: JobNotifier,
+    request: ReportExportRequest,
+) -> Result<Job, JobError> {
+    JobQueue::run(db.clone(), job_id, notify, |ctx| async move {
+        ctx.progress(0, Some(1), Some("Rendering report")).await?;
+        let response = export_analytics_report_impl(
+            &db,
+            &request.format,
+            &request.start_date,
+            &request.end_date,
+            request.include_charts,
+            &request.output_path,
+        )
+        .await?;
+        ctx.progress(1, Some(1), Some("Report saved")).await?;
+        Ok::<ExportReportResponse, String>(response)
+    })
+    .await
+}
+
+/// Run a migration of stored transaction hashes to the current version as job `job_id`
+/// Progress is recorded after each batch; a cancel request stops at the next batch and keeps those done
+pub async fn run_rehash_job(db: SqlitePool, job_id: i64, notify: JobNotifier) -> Result<Job, JobError> {
+    JobQueue::run(db.clone(), job_id, notify, |ctx| async move {
+        let result = rehash_transactions_impl(&db, |progress| {
+            let recorder = ctx.clone();
+            let message = format!("{} of {} transactions rehashed", progress.processed, progress.total);
+            let (processed, total) = (progress.processed as i64, progress.total as i64);
+            tauri::async_runtime::spawn(async move {
+                // An Err here means cancel was requested; the rehash stops at its next batch
+                let _ = recorder.progress(processed, Some(total), Some(&message)).await;
+            });
+
+            if ctx.is_cancelled() {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .await
+        .map_err(|e| e.to_user_message())?;
+
+        if ctx.is_cancelled() {
+            return Err(format!("Rehash cancelled after {} transactions", result.rehashed));
+        }
+        Ok(result)
+    })
+    .await
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn get_job_status(db_pool: tauri::State<'_, DbPool>, job_id: i64) -> Result<Job, String> {
+    get_job_status_impl(&db_pool.pool(), job_id)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn list_jobs(
+    db_pool: tauri::State<'_, DbPool>,
+    status: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<Job>, String> {
+    list_jobs_impl(&db_pool.pool(), status.as_deref(), limit)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn cancel_job(app: tauri::AppHandle, db_pool: tauri::State<'_, DbPool>, job_id: i64) -> Result<Job, String> {
+    let job = cancel_job_impl(&db_pool.pool(), job_id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    // A job cancelled before it started won't report anything itself
+    if let Err(e) = app.emit(JOB_PROGRESS_EVENT, job.clone()) {
+        tracing::warn!(error = %e, job_id = job.id, "Failed to emit job progress");
+    }
+    Ok(job)
+}
+
+/// Start a CSV import in the background and return its job ID right away
+/// The finished job's result is the ImportResult import_csv would have returned
+#[tauri::command]
+pub async fn start_import_job(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    account_id: i64,
+    csv_content: String,
+    mapping: ColumnMapping,
+    override_lock: Option<bool>,
+) -> Result<i64, String> {
+    let db = db_pool.pool();
+    let job_id = JobQueue::enqueue(&db, JobKind::CsvImport)
+        .await
+        .map_err(|e| e.to_user_message())?;
+
+    let notify = event_notifier(app.clone());
+    tauri::async_runtime::spawn(async move {
+        match run_import_job(db, job_id, notify, account_id, csv_content, mapping, override_lock.unwrap_or(false)).await {
+            Ok(job) => {
+                if let Some(import_batch_id) = job.result.and_then(|result| result.0["import_batch_id"].as_i64()) {
+                    notify_import_changes(&app, import_batch_id);
+                }
+            }
+            Err(e) => tracing::error!(error = %e, job_id = job_id, "Import job failed to run"),
+        }
+    });
+    Ok(job_id)
+}
+
+/// Start importing several CSV files in the background, one after another, and return the job ID right away
+/// The finished job's result is a BatchImportResult with an ImportResult or error per file
+#[tauri::command]
+pub async fn import_csv_batch(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    files: Vec<BatchImportFile>,
+    override_lock: Option<bool>,
+) -> Result<i64, String> {
+    let db = db_pool.pool();
+    let job_id = JobQueue::enqueue(&db, JobKind::CsvBatchImport)
+        .await
+        .map_err(|e| e.to_user_message())?;
+
+    let notify = event_notifier(app.clone());
+    tauri::async_runtime::spawn(async move {
+        match run_batch_import_job(db, job_id, notify, files, override_lock.unwrap_or(false)).await {
+            Ok(job) => {
+                let batch_ids = job
+                    .result
+                    .map(|result| {
+                        result.0["files"]
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|file| file["result"]["import_batch_id"].as_i64())
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                for import_batch_id in batch_ids {
+                    notify_import_changes(&app, import_batch_id);
+                }
+            }
+            Err(e) => tracing::error!(error = %e, job_id = job_id, "Batch import job failed to run"),
+        }
+    });
+    Ok(job_id)
+}
+
+/// Start an analytics report export in the background and return its job ID right away
+#[tauri::command]
+pub async fn start_report_export_job(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    format: String,
+    start_date: String,
+    end_date: String,
+    include_charts: bool,
+    output_path: String,
+) -> Result<i64, String> {
+    let db = db_pool.pool();
+    let job_id = JobQueue::enqueue(&db, JobKind::ReportExport)
+        .await
+        .map_err(|e| e.to_user_message())?;
+
+    let request = ReportExportRequest { format, start_date, end_date, include_charts, output_path };
+    let notify = event_notifier(app);
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = run_report_export_job(db, job_id, notify, request).await {
+            tracing::error!(error = %e, job_id = job_id, "Report export job failed to run");
+        }
+    });
+    Ok(job_id)
+}
+
+/// Start migrating stored transaction hashes to the current version in the background and
+/// return its job ID right away
+/// The finished job's result is a RehashResult
+#[tauri::command]
+pub async fn rehash_transactions(app: tauri::AppHandle, db_pool: tauri::State<'_, DbPool>) -> Result<i64, String> {
+    let db = db_pool.pool();
+    let job_id = JobQueue::enqueue(&db, JobKind::Rehash)
+        .await
+        .map_err(|e| e.to_user_message())?;
+
+    let notify = event_notifier(app.clone());
+    tauri::async_runtime::spawn(async move {
+        match run_rehash_job(db, job_id, notify).await {
+            // Batches finished before a cancel are kept, so either way hashes may have changed
+            Ok(_) => notify_changed(&app, DataKind::Transactions, ChangeAction::Updated, Vec::new()),
+            Err(e) => tracing::error!(error = %e, job_id = job_id, "Rehash job failed to run"),
+        }
+    });
+    Ok(job_id)
+}
@@ -0,0 +1,99 @@
+// Household members: who spent what from shared accounts
+
+use crate::commands::category_commands::is_valid_color;
+use crate::constants::MAX_MEMBER_NAME_LENGTH;
+use crate::errors::sanitize_db_error;
+use crate::models::member::{Member, NewMember};
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::DbPool;
+use sqlx::SqlitePool;
+
+// Business logic functions (used by both commands and tests)
+
+pub async fn list_members_impl(db: &SqlitePool) -> Result<Vec<Member>, String> {
+    sqlx::query_as::<_, Member>("SELECT id, name, color, created_at FROM members ORDER BY name")
+        .fetch_all(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load members"))
+}
+
+/// Add a member; names are unique ignoring case
+pub async fn create_member_impl(db: &SqlitePool, member: NewMember) -> Result<Member, String> {
+    let name = member.name.trim();
+    if name.is_empty() {
+        return Err("Member name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_MEMBER_NAME_LENGTH {
+        return Err(format!("Member name too long (max {} characters)", MAX_MEMBER_NAME_LENGTH));
+    }
+    if let Some(color) = &member.color {
+        if !is_valid_color(color) {
+            return Err(format!("Invalid color '{}': expected a hex color like #4CAF50", color));
+        }
+    }
+
+    sqlx::query_as::<_, Member>(
+        "INSERT INTO members (name, color) VALUES (?, ?) RETURNING id, name, color, created_at"
+    )
+    .bind(name)
+    .bind(&member.color)
+    .fetch_one(db)
+    .await
+    .map_err(|e| {
+        if e.to_string().contains("UNIQUE constraint failed") {
+            format!("A member named '{}' already exists", name)
+        } else {
+            sanitize_db_error(e, "create member")
+        }
+    })
+}
+
+/// Delete a member; their transactions stay, no longer attributed to anyone
+pub async fn delete_member_impl(db: &SqlitePool, member_id: i64) -> Result<(), String> {
+    let mut tx = db.begin().await.map_err(|e| sanitize_db_error(e, "delete member"))?;
+    sqlx::query("UPDATE transactions SET member_id = NULL WHERE member_id = ?")
+        .bind(member_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| sanitize_db_error(e, "delete member"))?;
+    let result = sqlx::query("DELETE FROM members WHERE id = ?")
+        .bind(member_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| sanitize_db_error(e, "delete member"))?;
+    if result.rows_affected() == 0 {
+        return Err(format!("Household member not found with ID {}", member_id));
+    }
+    tx.commit().await.map_err(|e| sanitize_db_error(e, "delete member"))
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn list_members(db_pool: tauri::State<'_, DbPool>) -> Result<Vec<Member>, String> {
+    list_members_impl(&db_pool.pool()).await
+}
+
+#[tauri::command]
+pub async fn create_member(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    member: NewMember,
+) -> Result<Member, String> {
+    let member = create_member_impl(&db_pool.pool(), member).await?;
+    notify_changed(&app, DataKind::Members, ChangeAction::Created, [member.id]);
+    Ok(member)
+}
+
+#[tauri::command]
+pub async fn delete_member(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    member_id: i64,
+) -> Result<(), String> {
+    delete_member_impl(&db_pool.pool(), member_id).await?;
+    notify_changed(&app, DataKind::Members, ChangeAction::Deleted, [member_id]);
+    // Their transactions are no longer attributed to anyone
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Updated, Vec::new());
+    Ok(())
+}
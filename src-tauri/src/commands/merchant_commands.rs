@@ -0,0 +1,311 @@
+use crate::constants::{MAX_BULK_OPERATION_IDS, MAX_MERCHANT_LENGTH};
+use crate::errors::MerchantError;
+use crate::models::merchant::{Merchant, MerchantLinkResult, MerchantMergeResult, MerchantSummary};
+use crate::services::merchant_normalizer::MerchantNormalizer;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::query_builder::{cache_in_list, placeholders};
+use crate::DbPool;
+use sqlx::SqlitePool;
+
+// Business logic functions (used by both commands and tests)
+
+pub async fn list_merchants_impl(db: &SqlitePool) -> Result<Vec<MerchantSummary>, MerchantError> {
+    let merchants: Vec<(i64, String, i64)> = sqlx::query_as(
+        "SELECT m.id, m.name, COUNT(t.id)
+         FROM merchants m
+         LEFT JOIN transactions t ON t.merchant_id = m.id
+         GROUP BY m.id, m.name
+         ORDER BY m.name"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| MerchantError::Database(e.to_string()))?;
+
+    let aliases: Vec<(i64, String)> = sqlx::query_as(
+        "SELECT merchant_id, alias FROM merchant_aliases ORDER BY alias"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| MerchantError::Database(e.to_string()))?;
+
+    Ok(merchants
+        .into_iter()
+        .map(|(id, name, transaction_count)| MerchantSummary {
+            id,
+            aliases: aliases
+                .iter()
+                .filter(|(merchant_id, _)| *merchant_id == id)
+                .map(|(_, alias)| alias.clone())
+                .collect(),
+            name,
+            transaction_count,
+        })
+        .collect())
+}
+
+pub async fn rename_merchant_impl(
+    db: &SqlitePool,
+    merchant_id: i64,
+    name: &str,
+) -> Result<Merchant, MerchantError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(MerchantError::ValidationError("Merchant name cannot be empty".to_string()));
+    }
+    if name.len() > MAX_MERCHANT_LENGTH {
+        return Err(MerchantError::ValidationError(format!(
+            "Merchant name too long (max {} characters)",
+            MAX_MERCHANT_LENGTH
+        )));
+    }
+
+    // Renaming onto another merchant's name should be a merge instead
+    let conflict: Option<(i64,)> = sqlx::query_as("SELECT id FROM merchants WHERE name = ? AND id != ?")
+        .bind(name)
+        .bind(merchant_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| MerchantError::Database(e.to_string()))?;
+    if conflict.is_some() {
+        return Err(MerchantError::DuplicateName(name.to_string()));
+    }
+
+    let result = sqlx::query("UPDATE merchants SET name = ? WHERE id = ?")
+        .bind(name)
+        .bind(merchant_id)
+        .execute(db)
+        .await
+        .map_err(|e| MerchantError::Database(e.to_string()))?;
+    if result.rows_affected() == 0 {
+        return Err(MerchantError::NotFound(merchant_id));
+    }
+
+    // Descriptors matching the new name should resolve to this merchant too
+    if let Some(key) = MerchantNormalizer::alias_key(name) {
+        sqlx::query("INSERT OR IGNORE INTO merchant_aliases (merchant_id, alias) VALUES (?, ?)")
+            .bind(merchant_id)
+            .bind(&key)
+            .execute(db)
+            .await
+            .map_err(|e| MerchantError::Database(e.to_string()))?;
+    }
+
+    sqlx::query_as::<_, Merchant>("SELECT id, name, created_at FROM merchants WHERE id = ?")
+        .bind(merchant_id)
+        .fetch_one(db)
+        .await
+        .map_err(|e| MerchantError::Database(e.to_string()))
+}
+
+/// Merge `source_ids` into `target_id`: aliases and transactions move to the target,
+/// source names become target aliases, and the source merchants are deleted
+pub async fn merge_merchants_impl(
+    db: &SqlitePool,
+    source_ids: Vec<i64>,
+    target_id: i64,
+) -> Result<MerchantMergeResult, MerchantError> {
+    let mut source_ids = source_ids;
+    source_ids.sort_unstable();
+    source_ids.dedup();
+
+    if source_ids.is_empty() {
+        return Err(MerchantError::ValidationError("Source merchant IDs cannot be empty".to_string()));
+    }
+    if source_ids.len() > MAX_BULK_OPERATION_IDS {
+        return Err(MerchantError::ValidationError(format!(
+            "Cannot merge more than {} merchants at once",
+            MAX_BULK_OPERATION_IDS
+        )));
+    }
+    if source_ids.contains(&target_id) {
+        return Err(MerchantError::ValidationError(
+            "Target merchant cannot also be a source".to_string(),
+        ));
+    }
+
+    let mut tx = db.begin().await.map_err(|e| MerchantError::Database(e.to_string()))?;
+
+    let target: Option<(i64,)> = sqlx::query_as("SELECT id FROM merchants WHERE id = ?")
+        .bind(target_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| MerchantError::Database(e.to_string()))?;
+    if target.is_none() {
+        return Err(MerchantError::NotFound(target_id));
+    }
+
+    let placeholders = placeholders(source_ids.len());
+
+    let select_str = format!("SELECT id, name FROM merchants WHERE id IN ({})", placeholders);
+    let mut select = sqlx::query_as::<_, (i64, String)>(&select_str).persistent(cache_in_list(source_ids.len()));
+    for id in &source_ids {
+        select = select.bind(id);
+    }
+    let sources = select
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| MerchantError::Database(e.to_string()))?;
+    if let Some(missing) = source_ids.iter().find(|id| !sources.iter().any(|(sid, _)| sid == *id)) {
+        return Err(MerchantError::NotFound(*missing));
+    }
+
+    let transactions_str = format!(
+        "UPDATE transactions SET merchant_id = ? WHERE merchant_id IN ({})",
+        placeholders
+    );
+    let mut update_transactions = sqlx::query(&transactions_str)
+        .persistent(cache_in_list(source_ids.len()))
+        .bind(target_id);
+    for id in &source_ids {
+        update_transactions = update_transactions.bind(id);
+    }
+    let transactions_updated = update_transactions
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| MerchantError::Database(e.to_string()))?
+        .rows_affected() as i64;
+
+    let aliases_str = format!(
+        "UPDATE merchant_aliases SET merchant_id = ? WHERE merchant_id IN ({})",
+        placeholders
+    );
+    let mut update_aliases = sqlx::query(&aliases_str)
+        .persistent(cache_in_list(source_ids.len()))
+        .bind(target_id);
+    for id in &source_ids {
+        update_aliases = update_aliases.bind(id);
+    }
+    update_aliases
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| MerchantError::Database(e.to_string()))?;
+
+    // Keep resolving future imports of the old names to the target
+    for (_, name) in &sources {
+        if let Some(key) = MerchantNormalizer::alias_key(name) {
+            sqlx::query("INSERT OR IGNORE INTO merchant_aliases (merchant_id, alias) VALUES (?, ?)")
+                .bind(target_id)
+                .bind(&key)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| MerchantError::Database(e.to_string()))?;
+        }
+    }
+
+    let delete_str = format!("DELETE FROM merchants WHERE id IN ({})", placeholders);
+    let mut delete = sqlx::query(&delete_str);
+    for id in &source_ids {
+        delete = delete.bind(id);
+    }
+    delete
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| MerchantError::Database(e.to_string()))?;
+
+    tx.commit().await.map_err(|e| MerchantError::Database(e.to_string()))?;
+
+    Ok(MerchantMergeResult {
+        merchant_id: target_id,
+        merged_count: sources.len(),
+        transactions_updated,
+    })
+}
+
+/// Link existing transactions that have merchant text but no merchant yet
+/// (e.g. imported before merchants existed)
+pub async fn normalize_merchants_impl(db: &SqlitePool) -> Result<MerchantLinkResult, MerchantError> {
+    let (merchants_before,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM merchants")
+        .fetch_one(db)
+        .await
+        .map_err(|e| MerchantError::Database(e.to_string()))?;
+
+    let raw_names: Vec<(String,)> = sqlx::query_as(
+        "SELECT DISTINCT merchant FROM transactions WHERE merchant_id IS NULL AND merchant IS NOT NULL"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| MerchantError::Database(e.to_string()))?;
+
+    let mut transactions_linked = 0;
+    for (raw,) in raw_names {
+        let Some(merchant_id) = MerchantNormalizer::resolve(db, &raw)
+            .await
+            .map_err(MerchantError::Database)?
+        else {
+            continue;
+        };
+
+        let result = sqlx::query("UPDATE transactions SET merchant_id = ? WHERE merchant_id IS NULL AND merchant = ?")
+            .bind(merchant_id)
+            .bind(&raw)
+            .execute(db)
+            .await
+            .map_err(|e| MerchantError::Database(e.to_string()))?;
+        transactions_linked += result.rows_affected() as usize;
+    }
+
+    let (merchants_after,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM merchants")
+        .fetch_one(db)
+        .await
+        .map_err(|e| MerchantError::Database(e.to_string()))?;
+
+    Ok(MerchantLinkResult {
+        transactions_linked,
+        merchants_created: (merchants_after - merchants_before).max(0) as usize,
+    })
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn list_merchants(
+    db_pool: tauri::State<'_, DbPool>,
+) -> Result<Vec<MerchantSummary>, String> {
+    list_merchants_impl(&db_pool.pool())
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn rename_merchant(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    merchant_id: i64,
+    name: String,
+) -> Result<Merchant, String> {
+    let result = rename_merchant_impl(&db_pool.pool(), merchant_id, &name)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Merchants, ChangeAction::Updated, [merchant_id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn merge_merchants(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    source_ids: Vec<i64>,
+    target_id: i64,
+) -> Result<MerchantMergeResult, String> {
+    let merged_ids = source_ids.clone();
+    let result = merge_merchants_impl(&db_pool.pool(), source_ids, target_id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Merchants, ChangeAction::Deleted, merged_ids);
+    notify_changed(&app, DataKind::Merchants, ChangeAction::Updated, [result.merchant_id]);
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Updated, vec![]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn normalize_merchants(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+) -> Result<MerchantLinkResult, String> {
+    let result = normalize_merchants_impl(&db_pool.pool())
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Merchants, ChangeAction::Created, vec![]);
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Updated, vec![]);
+    Ok(result)
+}
@@ -4,3 +4,32 @@ pub mod category_commands;
 pub mod account_commands;
 pub mod debt_commands;
 pub mod analytics_commands;
+pub mod onboarding_commands;
+pub mod schema_commands;
+pub mod merchant_commands;
+pub mod notification_commands;
+pub mod settings_commands;
+pub mod digest_commands;
+pub mod deep_link_commands;
+pub mod tray_commands;
+pub mod freeze_commands;
+pub mod streak_commands;
+pub mod fx_commands;
+pub mod cash_flow_commands;
+pub mod access_commands;
+pub mod alert_commands;
+pub mod search_commands;
+pub mod audit_commands;
+pub mod scheduled_transaction_commands;
+pub mod calendar_commands;
+pub mod job_commands;
+pub mod bank_sync_commands;
+pub mod data_commands;
+pub mod backup_commands;
+pub mod profile_commands;
+pub mod member_commands;
+pub mod dashboard_commands;
+pub mod receipt_commands;
+pub mod period_lock_commands;
+pub mod watch_folder_commands;
+pub mod investment_commands;
@@ -0,0 +1,275 @@
+use crate::constants::{
+    DEFAULT_OFFSET, DEFAULT_PAGE_SIZE, MAX_BULK_OPERATION_IDS, MAX_DESCRIPTION_LENGTH,
+    MAX_NOTIFICATION_TITLE_LENGTH, MAX_PAGE_SIZE,
+};
+use crate::errors::NotificationError;
+use crate::models::notification::{
+    NewNotification, Notification, NotificationFilter, NotificationList, NOTIFICATION_ENTITY_TYPES,
+};
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::query_builder::{cache_in_list, placeholders};
+use crate::DbPool;
+use sqlx::SqlitePool;
+
+// Business logic functions (used by both commands and tests)
+
+fn validate_ids(ids: &[i64]) -> Result<(), NotificationError> {
+    if ids.is_empty() {
+        return Err(NotificationError::ValidationError("Notification IDs cannot be empty".to_string()));
+    }
+    if ids.len() > MAX_BULK_OPERATION_IDS {
+        return Err(NotificationError::ValidationError(format!(
+            "Cannot update more than {} notifications at once",
+            MAX_BULK_OPERATION_IDS
+        )));
+    }
+    Ok(())
+}
+
+/// Record a notification for the notification center
+/// Used by backend features (budgets, imports, debts) rather than exposed as a command
+pub async fn create_notification_impl(
+    db: &SqlitePool,
+    notification: NewNotification,
+) -> Result<Notification, NotificationError> {
+    let notification_type = notification.notification_type.trim();
+    if notification_type.is_empty() {
+        return Err(NotificationError::ValidationError("Notification type cannot be empty".to_string()));
+    }
+
+    let title = notification.title.trim();
+    if title.is_empty() {
+        return Err(NotificationError::ValidationError("Notification title cannot be empty".to_string()));
+    }
+    if title.len() > MAX_NOTIFICATION_TITLE_LENGTH {
+        return Err(NotificationError::ValidationError(format!(
+            "Notification title too long (max {} characters)",
+            MAX_NOTIFICATION_TITLE_LENGTH
+        )));
+    }
+    if notification.message.len() > MAX_DESCRIPTION_LENGTH {
+        return Err(NotificationError::ValidationError(format!(
+            "Notification message too long (max {} characters)",
+            MAX_DESCRIPTION_LENGTH
+        )));
+    }
+
+    match (&notification.entity_type, notification.entity_id) {
+        (None, None) => {}
+        (Some(entity_type), Some(_)) if NOTIFICATION_ENTITY_TYPES.contains(&entity_type.as_str()) => {}
+        (Some(entity_type), Some(_)) => {
+            return Err(NotificationError::ValidationError(format!(
+                "Invalid entity type: {}",
+                entity_type
+            )));
+        }
+        _ => {
+            return Err(NotificationError::ValidationError(
+                "Entity type and entity ID must be provided together".to_string(),
+            ));
+        }
+    }
+
+    sqlx::query_as::<_, Notification>(
+        "INSERT INTO notifications (notification_type, severity, title, message, entity_type, entity_id)
+         VALUES (?, ?, ?, ?, ?, ?)
+         RETURNING id, notification_type, severity, title, message, entity_type, entity_id, read_at, dismissed_at, created_at"
+    )
+    .bind(notification_type)
+    .bind(notification.severity.to_string())
+    .bind(title)
+    .bind(&notification.message)
+    .bind(&notification.entity_type)
+    .bind(notification.entity_id)
+    .fetch_one(db)
+    .await
+    .map_err(|e| NotificationError::Database(e.to_string()))
+}
+
+pub async fn list_notifications_impl(
+    db: &SqlitePool,
+    filter: Option<NotificationFilter>,
+) -> Result<NotificationList, NotificationError> {
+    let filter = filter.unwrap_or_default();
+    let limit = filter.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = filter.offset.unwrap_or(DEFAULT_OFFSET).max(0);
+    let severity = filter.severity.map(|s| s.to_string());
+
+    let notifications = sqlx::query_as::<_, Notification>(
+        "SELECT id, notification_type, severity, title, message, entity_type, entity_id, read_at, dismissed_at, created_at
+         FROM notifications
+         WHERE (? = 0 OR read_at IS NULL)
+           AND (? = 1 OR dismissed_at IS NULL)
+           AND (? IS NULL OR severity = ?)
+           AND (? IS NULL OR notification_type = ?)
+           AND (? IS NULL OR entity_type = ?)
+           AND (? IS NULL OR entity_id = ?)
+         ORDER BY created_at DESC, id DESC
+         LIMIT ? OFFSET ?"
+    )
+    .bind(filter.unread_only)
+    .bind(filter.include_dismissed)
+    .bind(&severity)
+    .bind(&severity)
+    .bind(&filter.notification_type)
+    .bind(&filter.notification_type)
+    .bind(&filter.entity_type)
+    .bind(&filter.entity_type)
+    .bind(filter.entity_id)
+    .bind(filter.entity_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(db)
+    .await
+    .map_err(|e| NotificationError::Database(e.to_string()))?;
+
+    let (unread_count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM notifications WHERE read_at IS NULL AND dismissed_at IS NULL"
+    )
+    .fetch_one(db)
+    .await
+    .map_err(|e| NotificationError::Database(e.to_string()))?;
+
+    Ok(NotificationList {
+        notifications,
+        unread_count,
+    })
+}
+
+/// Mark notifications as read; already-read notifications keep their original read time
+/// Returns the number of notifications newly marked
+pub async fn mark_notifications_read_impl(
+    db: &SqlitePool,
+    ids: Vec<i64>,
+) -> Result<u64, NotificationError> {
+    validate_ids(&ids)?;
+
+    let placeholders = placeholders(ids.len());
+
+    let check_str = format!("SELECT id FROM notifications WHERE id IN ({})", placeholders);
+    let mut check = sqlx::query_as::<_, (i64,)>(&check_str).persistent(cache_in_list(ids.len()));
+    for id in &ids {
+        check = check.bind(id);
+    }
+    let existing = check
+        .fetch_all(db)
+        .await
+        .map_err(|e| NotificationError::Database(e.to_string()))?;
+    if let Some(missing) = ids.iter().find(|id| !existing.iter().any(|(eid,)| eid == *id)) {
+        return Err(NotificationError::NotFound(*missing));
+    }
+
+    let update_str = format!(
+        "UPDATE notifications SET read_at = CURRENT_TIMESTAMP WHERE read_at IS NULL AND id IN ({})",
+        placeholders
+    );
+    let mut update = sqlx::query(&update_str).persistent(cache_in_list(ids.len()));
+    for id in &ids {
+        update = update.bind(id);
+    }
+    let result = update
+        .execute(db)
+        .await
+        .map_err(|e| NotificationError::Database(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn mark_all_notifications_read_impl(db: &SqlitePool) -> Result<u64, NotificationError> {
+    let result = sqlx::query("UPDATE notifications SET read_at = CURRENT_TIMESTAMP WHERE read_at IS NULL")
+        .execute(db)
+        .await
+        .map_err(|e| NotificationError::Database(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
+
+/// Dismiss notifications (hidden from the default list); dismissing also marks them read
+/// so they become eligible for pruning
+pub async fn dismiss_notification_impl(db: &SqlitePool, id: i64) -> Result<(), NotificationError> {
+    let result = sqlx::query(
+        "UPDATE notifications
+         SET dismissed_at = COALESCE(dismissed_at, CURRENT_TIMESTAMP),
+             read_at = COALESCE(read_at, CURRENT_TIMESTAMP)
+         WHERE id = ?"
+    )
+    .bind(id)
+    .execute(db)
+    .await
+    .map_err(|e| NotificationError::Database(e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err(NotificationError::NotFound(id));
+    }
+
+    Ok(())
+}
+
+/// Delete notifications that were read more than `retention_days` ago
+/// Unread notifications are never pruned. Runs automatically at startup
+pub async fn prune_notifications_impl(db: &SqlitePool, retention_days: i64) -> Result<u64, NotificationError> {
+    if retention_days < 0 {
+        return Err(NotificationError::ValidationError("Retention days must be non-negative".to_string()));
+    }
+
+    let result = sqlx::query(
+        "DELETE FROM notifications WHERE read_at IS NOT NULL AND read_at < datetime('now', ?)"
+    )
+    .bind(format!("-{} days", retention_days))
+    .execute(db)
+    .await
+    .map_err(|e| NotificationError::Database(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn list_notifications(
+    db_pool: tauri::State<'_, DbPool>,
+    filter: Option<NotificationFilter>,
+) -> Result<NotificationList, String> {
+    list_notifications_impl(&db_pool.pool(), filter)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn mark_notifications_read(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    ids: Vec<i64>,
+) -> Result<u64, String> {
+    let notification_ids = ids.clone();
+    let result = mark_notifications_read_impl(&db_pool.pool(), ids)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Notifications, ChangeAction::Updated, notification_ids);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn mark_all_notifications_read(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+) -> Result<u64, String> {
+    let result = mark_all_notifications_read_impl(&db_pool.pool())
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Notifications, ChangeAction::Updated, vec![]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn dismiss_notification(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    id: i64,
+) -> Result<(), String> {
+    dismiss_notification_impl(&db_pool.pool(), id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Notifications, ChangeAction::Updated, [id]);
+    Ok(())
+}
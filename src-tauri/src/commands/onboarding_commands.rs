@@ -0,0 +1,95 @@
+use crate::errors::sanitize_db_error;
+use crate::models::onboarding::{OnboardingStatus, OnboardingStep, OnboardingStepStatus};
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::DbPool;
+use sqlx::SqlitePool;
+
+// Business logic functions (used by both commands and tests)
+
+/// Whether existing data already shows a step as done (e.g. accounts created before onboarding existed)
+async fn step_satisfied_by_data(db: &SqlitePool, step: OnboardingStep) -> Result<bool, String> {
+    let query = match step {
+        OnboardingStep::AccountCreated => "SELECT EXISTS(SELECT 1 FROM accounts)",
+        OnboardingStep::FirstImport => "SELECT EXISTS(SELECT 1 FROM transactions)",
+        OnboardingStep::FirstBudget => "SELECT EXISTS(SELECT 1 FROM spending_targets)",
+        OnboardingStep::FirstDebt => "SELECT EXISTS(SELECT 1 FROM debts)",
+    };
+
+    let exists: (bool,) = sqlx::query_as(query)
+        .fetch_one(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load onboarding status"))?;
+
+    Ok(exists.0)
+}
+
+pub async fn get_onboarding_status_impl(db: &SqlitePool) -> Result<OnboardingStatus, String> {
+    let recorded: Vec<(String, String)> = sqlx::query_as(
+        "SELECT step, completed_at FROM onboarding_steps"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "load onboarding status"))?;
+
+    let mut steps = Vec::with_capacity(OnboardingStep::ALL.len());
+    for step in OnboardingStep::ALL {
+        let completed_at = recorded
+            .iter()
+            .find(|(name, _)| *name == step.to_string())
+            .map(|(_, completed_at)| completed_at.clone());
+
+        let completed = completed_at.is_some() || step_satisfied_by_data(db, step).await?;
+
+        steps.push(OnboardingStepStatus {
+            step,
+            completed,
+            completed_at,
+        });
+    }
+
+    let completed_count = steps.iter().filter(|s| s.completed).count();
+    let total_steps = steps.len();
+
+    Ok(OnboardingStatus {
+        steps,
+        completed_count,
+        total_steps,
+        is_complete: completed_count == total_steps,
+    })
+}
+
+pub async fn complete_onboarding_step_impl(
+    db: &SqlitePool,
+    step: &str,
+) -> Result<OnboardingStatus, String> {
+    let step: OnboardingStep = step.parse()?;
+
+    // Completing a step twice keeps the original completion time
+    sqlx::query("INSERT OR IGNORE INTO onboarding_steps (step) VALUES (?)")
+        .bind(step.to_string())
+        .execute(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "complete onboarding step"))?;
+
+    get_onboarding_status_impl(db).await
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn get_onboarding_status(
+    db_pool: tauri::State<'_, DbPool>,
+) -> Result<OnboardingStatus, String> {
+    get_onboarding_status_impl(&db_pool.pool()).await
+}
+
+#[tauri::command]
+pub async fn complete_onboarding_step(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    step: String,
+) -> Result<OnboardingStatus, String> {
+    let result = complete_onboarding_step_impl(&db_pool.pool(), &step).await?;
+    notify_changed(&app, DataKind::Onboarding, ChangeAction::Updated, vec![]);
+    Ok(result)
+}
@@ -0,0 +1,98 @@
+// Period locks: months closed after reconciling, so later edits and imports can't rewrite them
+
+use crate::errors::sanitize_db_error;
+use crate::models::period_lock::PeriodLock;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::DbPool;
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+
+/// Normalize a YYYY-MM month, rejecting anything that isn't one
+fn parse_month(month: &str) -> Result<String, String> {
+    NaiveDate::parse_from_str(&format!("{}-01", month.trim()), "%Y-%m-%d")
+        .map(|date| date.format("%Y-%m").to_string())
+        .map_err(|_| format!("Invalid month: {}. Expected YYYY-MM", month))
+}
+
+// Business logic functions (used by both commands and tests)
+
+/// Locked months, most recent first
+pub async fn list_period_locks_impl(db: &SqlitePool) -> Result<Vec<PeriodLock>, String> {
+    sqlx::query_as::<_, PeriodLock>("SELECT month, locked_at FROM period_locks ORDER BY month DESC")
+        .fetch_all(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load period locks"))
+}
+
+/// Close `month` (YYYY-MM); locking a month that is already locked keeps its original lock time
+pub async fn lock_period_impl(db: &SqlitePool, month: &str) -> Result<PeriodLock, String> {
+    let month = parse_month(month)?;
+    sqlx::query("INSERT INTO period_locks (month) VALUES (?) ON CONFLICT(month) DO NOTHING")
+        .bind(&month)
+        .execute(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "lock period"))?;
+
+    sqlx::query_as::<_, PeriodLock>("SELECT month, locked_at FROM period_locks WHERE month = ?")
+        .bind(&month)
+        .fetch_one(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "lock period"))
+}
+
+/// Reopen `month` for changes
+pub async fn unlock_period_impl(db: &SqlitePool, month: &str) -> Result<(), String> {
+    let month = parse_month(month)?;
+    let result = sqlx::query("DELETE FROM period_locks WHERE month = ?")
+        .bind(&month)
+        .execute(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "unlock period"))?;
+    if result.rows_affected() == 0 {
+        return Err(format!("{} is not locked", month));
+    }
+    Ok(())
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn list_period_locks(db_pool: tauri::State<'_, DbPool>) -> Result<Vec<PeriodLock>, String> {
+    list_period_locks_impl(&db_pool.pool()).await
+}
+
+#[tauri::command]
+pub async fn lock_period(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    month: String,
+) -> Result<PeriodLock, String> {
+    let lock = lock_period_impl(&db_pool.pool(), &month).await?;
+    notify_changed(&app, DataKind::PeriodLocks, ChangeAction::Created, Vec::new());
+    Ok(lock)
+}
+
+#[tauri::command]
+pub async fn unlock_period(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    month: String,
+) -> Result<(), String> {
+    unlock_period_impl(&db_pool.pool(), &month).await?;
+    notify_changed(&app, DataKind::PeriodLocks, ChangeAction::Deleted, Vec::new());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_month() {
+        assert_eq!(parse_month("2024-03").unwrap(), "2024-03");
+        assert_eq!(parse_month(" 2024-3 ").unwrap(), "2024-03");
+        assert!(parse_month("2024-13").is_err());
+        assert!(parse_month("March 2024").is_err());
+        assert!(parse_month("2024-03-15").is_err());
+    }
+}
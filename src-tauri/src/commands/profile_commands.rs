@@ -0,0 +1,127 @@
+// Profiles: separate sets of budget data, each in its own database file
+
+use crate::commands::access_commands::verify_password;
+use crate::commands::watch_folder_commands::restart_folder_watcher;
+use crate::errors::{AccessError, ProfileError};
+use crate::models::profile::{Profile, ProfileList};
+use crate::services::job_queue::JobQueue;
+use crate::services::profile_registry::ProfileRegistry;
+use crate::utils::access_guard::AccessGuard;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::{open_database, start_profile, DbPool};
+
+// Business logic functions (used by both commands and tests)
+
+pub fn list_profiles_impl(registry: &ProfileRegistry) -> ProfileList {
+    registry.list()
+}
+
+/// Add a profile with a new, empty database; the open profile stays open
+pub async fn create_profile_impl(registry: &ProfileRegistry, name: &str) -> Result<Profile, ProfileError> {
+    let profile = registry.new_profile(name)?;
+    let path = registry.file_path(&profile);
+
+    // Set the database up before the profile is saved, so a saved profile always opens.
+    // Only creating it is mutating, so the guard of the open profile doesn't apply
+    let pool = open_database(&path, &AccessGuard::default()).await.map_err(ProfileError::Database)?;
+    pool.close().await;
+
+    registry.add(profile.clone())?;
+    tracing::info!(profile = %profile.name, "Created profile");
+    Ok(profile)
+}
+
+/// Open the named profile in place of the current one
+///
+/// The new database is opened and migrated before anything changes, so a profile that
+/// fails to open leaves the current one in use. Read-only mode and the app role are
+/// per-profile settings and are reloaded from the new database, so while editing is
+/// locked the switch needs the open profile's app password: otherwise a viewer could
+/// leave for a profile without one and edit freely.
+pub async fn switch_profile_impl(
+    db_pool: &DbPool,
+    registry: &ProfileRegistry,
+    guard: &AccessGuard,
+    name: &str,
+    password: Option<&str>,
+) -> Result<ProfileList, ProfileError> {
+    let path = registry.database_path(name)?;
+    if path == registry.active_database_path()? {
+        return Ok(registry.list());
+    }
+
+    if guard.is_locked() {
+        verify_password(&db_pool.pool(), guard, password).await.map_err(|e| match e {
+            AccessError::WrongPassword | AccessError::NoPassword => ProfileError::Locked,
+            e => ProfileError::Access(e),
+        })?;
+    }
+
+    // Jobs hold on to the pool they started with, which is closed below
+    let unfinished = JobQueue::count_unfinished(&db_pool.pool())
+        .await
+        .map_err(|e| ProfileError::Database(e.to_string()))?;
+    if unfinished > 0 {
+        return Err(ProfileError::JobsRunning);
+    }
+
+    let pool = open_database(&path, guard).await.map_err(ProfileError::Database)?;
+    if let Err(e) = registry.set_active(name) {
+        pool.close().await;
+        return Err(e);
+    }
+    // Waits for queries still running on the old database to finish
+    db_pool.replace(pool.clone()).close().await;
+
+    if !guard.is_forced() {
+        // Can't fail: only forced read-only mode refuses to turn off
+        let _ = guard.set_read_only(false);
+    }
+    start_profile(&pool, guard).await;
+
+    let list = registry.list();
+    tracing::info!(profile = %list.active, "Switched profile");
+    Ok(list)
+}
+
+// Tauri command handlers (extract state from managed state)
+// switch_profile isn't in MUTATING_COMMANDS: every profile has its own read-only and
+// viewer settings, which apply once it's open, and a locked viewer needs the password
+
+#[tauri::command]
+pub fn list_profiles(registry: tauri::State<'_, ProfileRegistry>) -> ProfileList {
+    list_profiles_impl(&registry)
+}
+
+#[tauri::command]
+pub async fn create_profile(
+    registry: tauri::State<'_, ProfileRegistry>,
+    name: String,
+) -> Result<Profile, String> {
+    create_profile_impl(&registry, &name)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn switch_profile(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    registry: tauri::State<'_, ProfileRegistry>,
+    guard: tauri::State<'_, AccessGuard>,
+    name: String,
+    password: Option<String>,
+) -> Result<ProfileList, String> {
+    let list = switch_profile_impl(&db_pool, &registry, &guard, &name, password.as_deref())
+        .await
+        .map_err(|e| e.to_user_message())?;
+    // Each profile has its own watch folder setting
+    if let Err(e) = restart_folder_watcher(&app).await {
+        tracing::warn!(error = %e, "Failed to restart folder watcher");
+    }
+    // Everything on screen came from the old database
+    for kind in DataKind::ALL {
+        notify_changed(&app, kind, ChangeAction::Updated, Vec::new());
+    }
+    Ok(list)
+}
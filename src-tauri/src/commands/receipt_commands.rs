@@ -0,0 +1,28 @@
+// Receipts: read the amount, date, and merchant off a receipt photo
+
+use crate::errors::ReceiptError;
+use crate::services::receipt_extractor::{ReceiptData, ReceiptExtractor};
+use std::path::PathBuf;
+
+// Business logic functions (used by both commands and tests)
+
+/// Suggest transaction details from a receipt image
+///
+/// Nothing is saved; the frontend fills a new manual transaction with the
+/// suggestions for the user to confirm or correct. OCR runs on the blocking
+/// thread pool since it can take a few seconds.
+pub async fn extract_receipt_data_impl(image_path: String) -> Result<ReceiptData, ReceiptError> {
+    let path = PathBuf::from(image_path);
+    tauri::async_runtime::spawn_blocking(move || ReceiptExtractor::extract(&path))
+        .await
+        .map_err(|e| ReceiptError::OcrFailed(e.to_string()))?
+}
+
+// Tauri command handlers
+
+#[tauri::command]
+pub async fn extract_receipt_data(image_path: String) -> Result<ReceiptData, String> {
+    extract_receipt_data_impl(image_path)
+        .await
+        .map_err(|e| e.to_user_message())
+}
@@ -0,0 +1,300 @@
+use crate::commands::transaction_commands::{
+    create_transaction_impl, ensure_account_exists, ensure_category_exists, non_empty,
+    validate_transaction_fields,
+};
+use crate::constants::DEFAULT_CATEGORY_ID;
+use crate::errors::TransactionError;
+use crate::models::audit_log::AuditEntity;
+use crate::models::scheduled_transaction::{NewScheduledTransaction, ScheduledPosting, ScheduledTransaction};
+use crate::models::transaction::CreateTransaction;
+use crate::services::audit_log::AuditRecord;
+use crate::services::transaction_scheduler::Schedule;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates;
+use crate::DbPool;
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+
+const SCHEDULED_TRANSACTION_COLUMNS: &str = "id, account_id, category_id, amount_cents, description, merchant, recurrence, \
+     start_date, end_date, next_due_date, auto_post, created_at";
+
+fn parse_date(date: &str) -> Result<NaiveDate, TransactionError> {
+    dates::parse_date(date).map_err(|_| TransactionError::InvalidDate(date.to_string()))
+}
+
+async fn get_scheduled_transaction(db: &SqlitePool, id: i64) -> Result<ScheduledTransaction, TransactionError> {
+    sqlx::query_as::<_, ScheduledTransaction>(&format!(
+        "SELECT {} FROM scheduled_transactions WHERE id = ?",
+        SCHEDULED_TRANSACTION_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| TransactionError::Database(e.to_string()))?
+    .ok_or(TransactionError::ScheduleNotFound(id))
+}
+
+/// Post the occurrence due on `due` and move the item on to its next occurrence
+///
+/// A transaction with the same date, amount, and description (say, the bill came in
+/// with a bank import first) counts as the posting rather than being created twice.
+async fn post_occurrence(
+    db: &SqlitePool,
+    item: &ScheduledTransaction,
+    schedule: &Schedule,
+    due: NaiveDate,
+) -> Result<ScheduledPosting, TransactionError> {
+    let created = create_transaction_impl(
+        db,
+        CreateTransaction {
+            account_id: item.account_id,
+            category_id: Some(item.category_id),
+            date: dates::format_date(due),
+            amount_cents: item.amount_cents,
+            description: item.description.clone(),
+            merchant: item.merchant.clone(),
+            notes: None,
+        },
+        false,
+    )
+    .await;
+    let (transaction_id, already_existed) = match created {
+        Ok(transaction) => (transaction.id, false),
+        Err(TransactionError::Duplicate(id)) => (id, true),
+        Err(e) => return Err(e),
+    };
+
+    let next_due_date = schedule.next_after(due).map(dates::format_date);
+    sqlx::query("UPDATE scheduled_transactions SET next_due_date = ? WHERE id = ?")
+        .bind(&next_due_date)
+        .bind(item.id)
+        .execute(db)
+        .await
+        .map_err(|e| TransactionError::Database(e.to_string()))?;
+
+    Ok(ScheduledPosting {
+        scheduled_transaction_id: item.id,
+        date: dates::format_date(due),
+        transaction_id,
+        already_existed,
+    })
+}
+
+// Business logic functions (used by both commands and tests)
+
+/// Schedule a recurring (or one-off) transaction; its first occurrence is `start_date`
+pub async fn create_scheduled_transaction_impl(
+    db: &SqlitePool,
+    scheduled: NewScheduledTransaction,
+) -> Result<ScheduledTransaction, TransactionError> {
+    let merchant = non_empty(scheduled.merchant.as_deref());
+    let description = scheduled.description.trim().to_string();
+    let start_date = validate_transaction_fields(
+        &scheduled.start_date,
+        scheduled.amount_cents,
+        &description,
+        merchant.as_deref(),
+        None,
+    )?;
+    let end_date = match scheduled.end_date.as_deref().and_then(|d| non_empty(Some(d))) {
+        Some(date) => {
+            let end = parse_date(&date)?;
+            if end < parse_date(&start_date)? {
+                return Err(TransactionError::ValidationError(
+                    "End date cannot be before the start date".to_string(),
+                ));
+            }
+            Some(dates::format_date(end))
+        }
+        None => None,
+    };
+
+    ensure_account_exists(db, scheduled.account_id).await?;
+    let category_id = scheduled.category_id.unwrap_or(DEFAULT_CATEGORY_ID);
+    ensure_category_exists(db, category_id).await?;
+
+    let result = sqlx::query(
+        "INSERT INTO scheduled_transactions
+             (account_id, category_id, amount_cents, description, merchant, recurrence, start_date, end_date, next_due_date, auto_post)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(scheduled.account_id)
+    .bind(category_id)
+    .bind(scheduled.amount_cents)
+    .bind(&description)
+    .bind(&merchant)
+    .bind(scheduled.recurrence.to_string())
+    .bind(&start_date)
+    .bind(&end_date)
+    .bind(&start_date)
+    .bind(scheduled.auto_post.unwrap_or(true))
+    .execute(db)
+    .await
+    .map_err(|e| TransactionError::Database(e.to_string()))?;
+
+    let created = get_scheduled_transaction(db, result.last_insert_rowid()).await?;
+    AuditRecord::created(AuditEntity::ScheduledTransaction, created.id)
+        .new_values(&created)
+        .record(db)
+        .await;
+
+    Ok(created)
+}
+
+/// Scheduled transactions, soonest due first; finished ones come last
+pub async fn list_scheduled_transactions_impl(db: &SqlitePool) -> Result<Vec<ScheduledTransaction>, TransactionError> {
+    sqlx::query_as::<_, ScheduledTransaction>(&format!(
+        "SELECT {} FROM scheduled_transactions ORDER BY next_due_date IS NULL, next_due_date, id",
+        SCHEDULED_TRANSACTION_COLUMNS
+    ))
+    .fetch_all(db)
+    .await
+    .map_err(|e| TransactionError::Database(e.to_string()))
+}
+
+/// Delete a scheduled transaction; transactions it already posted are kept
+pub async fn delete_scheduled_transaction_impl(db: &SqlitePool, id: i64) -> Result<(), TransactionError> {
+    let existing = get_scheduled_transaction(db, id).await?;
+
+    sqlx::query("DELETE FROM scheduled_transactions WHERE id = ?")
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| TransactionError::Database(e.to_string()))?;
+
+    AuditRecord::deleted(AuditEntity::ScheduledTransaction, id)
+        .old_values(&existing)
+        .record(db)
+        .await;
+
+    Ok(())
+}
+
+/// Post every auto-post occurrence due on or before `today` (default: the local date),
+/// catching up on any that came due while the app was closed. Runs at startup
+///
+/// An item that fails to post is logged and left due, so it's retried next time.
+pub async fn post_due_scheduled_transactions_impl(
+    db: &SqlitePool,
+    today: Option<&str>,
+) -> Result<Vec<ScheduledPosting>, TransactionError> {
+    let today = match today {
+        Some(date) => parse_date(date)?,
+        None => dates::today(db).await,
+    };
+
+    let due: Vec<ScheduledTransaction> = sqlx::query_as(&format!(
+        "SELECT {} FROM scheduled_transactions
+         WHERE auto_post = 1 AND next_due_date IS NOT NULL AND next_due_date <= ?
+         ORDER BY next_due_date, id",
+        SCHEDULED_TRANSACTION_COLUMNS
+    ))
+    .bind(dates::format_date(today))
+    .fetch_all(db)
+    .await
+    .map_err(|e| TransactionError::Database(e.to_string()))?;
+
+    let mut postings = Vec::new();
+    for item in due {
+        let Some(schedule) = Schedule::of(&item) else {
+            tracing::warn!(scheduled_transaction_id = item.id, "Skipping scheduled transaction with an invalid schedule");
+            continue;
+        };
+        let mut next_due = item.next_due_date.as_deref().and_then(|d| parse_date(d).ok());
+        while let Some(due) = next_due.filter(|due| *due <= today) {
+            match post_occurrence(db, &item, &schedule, due).await {
+                Ok(posting) => postings.push(posting),
+                Err(e) => {
+                    tracing::warn!(error = %e, scheduled_transaction_id = item.id, "Failed to post scheduled transaction");
+                    break;
+                }
+            }
+            next_due = schedule.next_after(due);
+        }
+    }
+
+    Ok(postings)
+}
+
+/// Post a scheduled transaction's next occurrence now, for items that wait for confirmation
+/// (or to record a bill paid early). The transaction keeps the occurrence's due date
+pub async fn confirm_scheduled_transaction_impl(db: &SqlitePool, id: i64) -> Result<ScheduledPosting, TransactionError> {
+    let item = get_scheduled_transaction(db, id).await?;
+    let schedule = Schedule::of(&item).ok_or_else(|| {
+        TransactionError::ValidationError(format!("Scheduled transaction {} has an invalid schedule", id))
+    })?;
+    let due = match item.next_due_date.as_deref() {
+        Some(date) => parse_date(date)?,
+        None => return Err(TransactionError::ScheduleFinished(id)),
+    };
+
+    post_occurrence(db, &item, &schedule, due).await
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn create_scheduled_transaction(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    scheduled: NewScheduledTransaction,
+) -> Result<ScheduledTransaction, String> {
+    let result = create_scheduled_transaction_impl(&db_pool.pool(), scheduled)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::ScheduledTransactions, ChangeAction::Created, [result.id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn list_scheduled_transactions(
+    db_pool: tauri::State<'_, DbPool>,
+) -> Result<Vec<ScheduledTransaction>, String> {
+    list_scheduled_transactions_impl(&db_pool.pool())
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn delete_scheduled_transaction(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    id: i64,
+) -> Result<(), String> {
+    delete_scheduled_transaction_impl(&db_pool.pool(), id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::ScheduledTransactions, ChangeAction::Deleted, [id]);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn post_due_scheduled_transactions(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+) -> Result<Vec<ScheduledPosting>, String> {
+    let result = post_due_scheduled_transactions_impl(&db_pool.pool(), None)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    if !result.is_empty() {
+        let transaction_ids: Vec<i64> = result.iter().map(|posting| posting.transaction_id).collect();
+        let scheduled_ids: Vec<i64> = result.iter().map(|posting| posting.scheduled_transaction_id).collect();
+        notify_changed(&app, DataKind::Transactions, ChangeAction::Created, transaction_ids);
+        notify_changed(&app, DataKind::ScheduledTransactions, ChangeAction::Updated, scheduled_ids);
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn confirm_scheduled_transaction(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    id: i64,
+) -> Result<ScheduledPosting, String> {
+    let result = confirm_scheduled_transaction_impl(&db_pool.pool(), id)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Created, [result.transaction_id]);
+    notify_changed(&app, DataKind::ScheduledTransactions, ChangeAction::Updated, [result.scheduled_transaction_id]);
+    Ok(result)
+}
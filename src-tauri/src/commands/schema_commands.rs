@@ -0,0 +1,349 @@
+use crate::errors::{sanitize_db_error, QueryError};
+use crate::services::query_console::{QueryConsole, QueryPlan, QueryResult};
+use crate::DbPool;
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+#[derive(Debug, Serialize)]
+pub struct FieldDescription {
+    pub name: String,
+    pub description: String,
+    pub data_type: String,
+    pub nullable: bool,
+    pub primary_key: bool,
+    pub default_value: Option<String>,
+}
+
+/// A foreign key from this entity to another (many-to-one)
+#[derive(Debug, Serialize)]
+pub struct RelationshipDescription {
+    pub field: String,
+    pub references_entity: String,
+    pub references_field: String,
+    pub on_delete: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EntityDescription {
+    pub name: String,
+    pub description: String,
+    pub fields: Vec<FieldDescription>,
+    pub relationships: Vec<RelationshipDescription>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DataModelDescription {
+    pub entities: Vec<EntityDescription>,
+}
+
+#[derive(sqlx::FromRow)]
+struct TableInfoRow {
+    name: String,
+    #[sqlx(rename = "type")]
+    data_type: String,
+    notnull: bool,
+    dflt_value: Option<String>,
+    pk: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct ForeignKeyRow {
+    table: String,
+    from: String,
+    to: Option<String>,
+    on_delete: String,
+}
+
+/// A user-facing table and what its fields mean
+struct EntityDoc {
+    name: &'static str,
+    description: &'static str,
+    fields: &'static [(&'static str, &'static str)],
+    /// Columns deliberately left out; every other column must be in `fields`
+    #[cfg_attr(not(test), allow(dead_code))] // Only the drift test reads it
+    internal: &'static [&'static str],
+}
+
+/// The tables reports and power users can build on, and the fields worth showing
+///
+/// Bookkeeping tables (audit log, jobs, caches, the search index) and internal columns
+/// such as duplicate hashes are left out on purpose. Amounts are whole cents (the `*_cents`
+/// columns, so 1234 is $12.34); dates are YYYY-MM-DD.
+const USER_FACING_ENTITIES: &[EntityDoc] = &[
+    EntityDoc {
+        name: "accounts",
+        description: "Bank, credit card and other accounts transactions belong to",
+        fields: &[
+            ("id", "Account ID"),
+            ("name", "Account name"),
+            ("type", "checking, savings or credit_card"),
+            ("balance_cents", "Current balance"),
+            ("statement_closing_day", "Day of the month a credit card statement closes"),
+            ("statement_due_day", "Day of the month a credit card statement is due"),
+            ("archived", "Hidden from lists but kept for history"),
+            ("created_at", "When the account was added"),
+        ],
+        internal: &["updated_at"],
+    },
+    EntityDoc {
+        name: "categories",
+        description: "Spending and income categories, optionally nested one level under a parent",
+        fields: &[
+            ("id", "Category ID"),
+            ("name", "Category name"),
+            ("type", "predefined or custom"),
+            ("parent_id", "Parent category, for subcategories"),
+            ("icon", "Icon shown next to the name"),
+            ("color", "Hex color used in charts"),
+            ("archived", "Hidden from pickers but kept for history"),
+            ("tax_deductible", "Transactions in this category are tax deductible by default"),
+        ],
+        internal: &["created_at"],
+    },
+    EntityDoc {
+        name: "category_rules",
+        description: "Rules that categorize transactions by description, amount and account",
+        fields: &[
+            ("id", "Rule ID"),
+            ("pattern", "Text matched against the transaction description"),
+            ("match_type", "contains, starts_with, exact or regex"),
+            ("category_id", "Category given to matching transactions"),
+            ("priority", "Higher priority rules are tried first"),
+            ("min_amount_cents", "Only match amounts at least this large"),
+            ("max_amount_cents", "Only match amounts at most this large"),
+            ("account_id", "Only match transactions in this account"),
+            ("match_count", "How many transactions the rule has categorized"),
+            ("last_matched_at", "When the rule last categorized a transaction"),
+        ],
+        internal: &["created_at"],
+    },
+    EntityDoc {
+        name: "debts",
+        description: "Credit cards and loans being paid down",
+        fields: &[
+            ("id", "Debt ID"),
+            ("name", "Debt name"),
+            ("kind", "revolving (credit card) or loan"),
+            ("balance_cents", "Amount still owed"),
+            ("original_balance_cents", "Amount owed when the debt was added"),
+            ("interest_rate", "Annual percentage rate"),
+            ("min_payment_cents", "Fixed minimum monthly payment"),
+            ("min_payment_percent", "Minimum payment as a percentage of the balance, if set"),
+            ("due_day", "Day of the month the payment is due"),
+            ("term_months", "Loan term, for installment loans"),
+            ("loan_start_date", "Date the loan started"),
+            ("account_id", "Account linked to the debt"),
+        ],
+        internal: &["created_at", "updated_at"],
+    },
+    EntityDoc {
+        name: "debt_payments",
+        description: "Payments recorded against debts",
+        fields: &[
+            ("id", "Payment ID"),
+            ("debt_id", "Debt the payment went to"),
+            ("date", "Payment date"),
+            ("amount_cents", "Total paid"),
+            ("interest_cents", "Part of the payment that covered interest"),
+            ("principal_cents", "Part of the payment that reduced the balance"),
+        ],
+        internal: &["plan_id", "created_at"],
+    },
+    EntityDoc {
+        name: "members",
+        description: "Household members transactions can be assigned to",
+        fields: &[("id", "Member ID"), ("name", "Member name"), ("color", "Hex color used in charts")],
+        internal: &["created_at"],
+    },
+    EntityDoc {
+        name: "merchants",
+        description: "Normalized merchant names that transactions are grouped under",
+        fields: &[("id", "Merchant ID"), ("name", "Merchant name")],
+        internal: &["created_at"],
+    },
+    EntityDoc {
+        name: "spending_targets",
+        description: "Budgets for a category, an account or overall spending",
+        fields: &[
+            ("id", "Target ID"),
+            ("scope", "category, account or overall"),
+            ("category_id", "Category the target applies to"),
+            ("account_id", "Account the target applies to"),
+            ("amount_cents", "Amount allowed per period"),
+            ("period", "monthly, quarterly or yearly"),
+            ("start_date", "First day the target applies"),
+            ("end_date", "Last day the target applies, if it ends"),
+        ],
+        internal: &["created_at"],
+    },
+    EntityDoc {
+        name: "transactions",
+        description: "Individual purchases, deposits and transfers",
+        fields: &[
+            ("id", "Transaction ID"),
+            ("account_id", "Account the transaction is in"),
+            ("category_id", "Category the transaction is counted under"),
+            ("date", "Transaction date"),
+            ("amount_cents", "Negative for spending, positive for income"),
+            ("description", "Description from the bank or entered by hand"),
+            ("merchant", "Merchant name as imported"),
+            ("merchant_id", "Normalized merchant"),
+            ("notes", "Notes added by the user"),
+            ("status", "pending or posted"),
+            ("member_id", "Household member the transaction belongs to"),
+            ("tax_deductible", "Overrides the category's tax deductible setting when set"),
+            ("original_currency", "Currency the transaction was made in, if not the default"),
+            ("original_amount_cents", "Amount in the original currency"),
+        ],
+        internal: &["hash", "hash_version", "import_batch_id", "created_at", "updated_at"],
+    },
+];
+
+// Business logic functions (used by both commands and tests)
+
+/// Describe the user-facing entities, their fields and relationships
+/// Types and nullability come from the live schema, so they always match the applied
+/// migrations; which tables and fields appear, and what they mean, comes from
+/// `USER_FACING_ENTITIES`
+pub async fn describe_data_model_impl(db: &SqlitePool) -> Result<DataModelDescription, String> {
+    let mut entities = Vec::with_capacity(USER_FACING_ENTITIES.len());
+    for doc in USER_FACING_ENTITIES {
+        let table = doc.name;
+        let columns = sqlx::query_as::<_, TableInfoRow>(
+            "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?) ORDER BY cid"
+        )
+        .bind(table)
+        .fetch_all(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "describe data model"))?;
+
+        let foreign_keys = sqlx::query_as::<_, ForeignKeyRow>(
+            "SELECT \"table\", \"from\", \"to\", on_delete FROM pragma_foreign_key_list(?) ORDER BY id, seq"
+        )
+        .bind(table)
+        .fetch_all(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "describe data model"))?;
+
+        let described = |field: &str| doc.fields.iter().find(|(name, _)| *name == field).map(|(_, meaning)| *meaning);
+
+        let fields = columns
+            .into_iter()
+            .filter_map(|c| {
+                let description = described(&c.name)?;
+                Some(FieldDescription {
+                    // INTEGER PRIMARY KEY columns are implicitly NOT NULL (rowid alias)
+                    nullable: !c.notnull && c.pk == 0,
+                    primary_key: c.pk > 0,
+                    description: description.to_string(),
+                    name: c.name,
+                    data_type: c.data_type,
+                    default_value: c.dflt_value,
+                })
+            })
+            .collect();
+
+        // Links to hidden tables (like import batches) would point nowhere
+        let relationships = foreign_keys
+            .into_iter()
+            .filter(|fk| described(&fk.from).is_some() && USER_FACING_ENTITIES.iter().any(|e| e.name == fk.table))
+            .map(|fk| RelationshipDescription {
+                field: fk.from,
+                // Omitted target column means the referenced table's primary key
+                references_field: fk.to.unwrap_or_else(|| "id".to_string()),
+                references_entity: fk.table,
+                on_delete: fk.on_delete,
+            })
+            .collect();
+
+        entities.push(EntityDescription {
+            name: table.to_string(),
+            description: doc.description.to_string(),
+            fields,
+            relationships,
+        });
+    }
+
+    Ok(DataModelDescription { entities })
+}
+
+/// Run a single SELECT for the query console and return its rows as JSON
+/// The statement runs on its own read-only connection, so even a query that slips
+/// past validation can't change anything
+pub async fn execute_readonly_query_impl(
+    db: &SqlitePool,
+    sql: &str,
+    limit: Option<usize>,
+) -> Result<QueryResult, QueryError> {
+    let result = QueryConsole::execute(db, sql, limit).await?;
+    tracing::info!(rows = result.row_count, truncated = result.truncated, "Ran console query");
+    Ok(result)
+}
+
+/// How SQLite would run a SELECT, for checking that a query uses the indexes meant for it
+/// The statement is validated like a console query and planned without being run
+pub async fn get_query_plan_impl(db: &SqlitePool, sql: &str) -> Result<QueryPlan, QueryError> {
+    QueryConsole::plan(db, sql).await
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn describe_data_model(
+    db_pool: tauri::State<'_, DbPool>,
+) -> Result<DataModelDescription, String> {
+    describe_data_model_impl(&db_pool.pool()).await
+}
+
+#[tauri::command]
+pub async fn execute_readonly_query(
+    db_pool: tauri::State<'_, DbPool>,
+    sql: String,
+    limit: Option<usize>,
+) -> Result<QueryResult, String> {
+    execute_readonly_query_impl(&db_pool.pool(), &sql, limit)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn get_query_plan(db_pool: tauri::State<'_, DbPool>, sql: String) -> Result<QueryPlan, String> {
+    get_query_plan_impl(&db_pool.pool(), &sql)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::collections::BTreeSet;
+
+    /// Fails when a migration adds, drops or renames a column on a described entity
+    /// without `USER_FACING_ENTITIES` being updated to match
+    #[tokio::test]
+    async fn test_documented_fields_match_schema() {
+        let db = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&db).await.unwrap();
+
+        for doc in USER_FACING_ENTITIES {
+            let columns: BTreeSet<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info(?)")
+                .bind(doc.name)
+                .fetch_all(&db)
+                .await
+                .unwrap()
+                .into_iter()
+                .collect();
+            let documented: BTreeSet<String> = doc.fields.iter().map(|(name, _)| name.to_string()).collect();
+            let internal: BTreeSet<String> = doc.internal.iter().map(|name| name.to_string()).collect();
+
+            assert!(documented.is_disjoint(&internal), "{} lists a field as both documented and internal", doc.name);
+            assert_eq!(
+                documented.union(&internal).cloned().collect::<BTreeSet<_>>(),
+                columns,
+                "{}'s documented and internal fields don't match its columns",
+                doc.name
+            );
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::constants::{DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE, MAX_SEARCH_QUERY_LENGTH};
+use crate::errors::SearchError;
+use crate::models::search::{SearchEntityType, SearchResult};
+use crate::DbPool;
+use sqlx::SqlitePool;
+
+/// Turn free text into an FTS5 query that matches every word as a prefix
+///
+/// Each word is quoted so FTS5 operators and punctuation in the input are
+/// searched for literally rather than parsed. Returns None when nothing
+/// searchable is left.
+fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|word| word.replace('"', ""))
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .map(|word| format!("\"{}\"*", word))
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+// Business logic functions (used by both commands and tests)
+
+/// Search transactions, categories, and debts at once, best matches first
+///
+/// Titles (descriptions and names) weigh more than a transaction's merchant
+/// and notes. `entity_type` narrows the search to one kind of result.
+pub async fn global_search_impl(
+    db: &SqlitePool,
+    query: String,
+    entity_type: Option<SearchEntityType>,
+    limit: Option<i64>,
+) -> Result<Vec<SearchResult>, SearchError> {
+    if query.len() > MAX_SEARCH_QUERY_LENGTH {
+        return Err(SearchError::QueryTooLong { max: MAX_SEARCH_QUERY_LENGTH });
+    }
+
+    let Some(match_query) = fts_query(&query) else {
+        return Ok(Vec::new());
+    };
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let entity_type = entity_type.map(|t| t.to_string());
+
+    // bm25 weights follow the column order: entity_type, entity_id, title, body
+    sqlx::query_as::<_, SearchResult>(
+        "SELECT search_index.entity_type, search_index.entity_id, search_index.title,
+                snippet(search_index, -1, '', '', '…', 16) AS snippet,
+                -bm25(search_index, 0.0, 0.0, 10.0, 1.0) AS score,
+                t.date, t.amount_cents
+         FROM search_index
+         LEFT JOIN transactions t ON search_index.entity_type = 'transaction' AND t.id = search_index.entity_id
+         WHERE search_index MATCH ? AND (? IS NULL OR search_index.entity_type = ?)
+         ORDER BY score DESC, search_index.entity_id DESC
+         LIMIT ?"
+    )
+    .bind(&match_query)
+    .bind(&entity_type)
+    .bind(&entity_type)
+    .bind(limit)
+    .fetch_all(db)
+    .await
+    .map_err(|e| SearchError::Database(e.to_string()))
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn global_search(
+    db_pool: tauri::State<'_, DbPool>,
+    query: String,
+    entity_type: Option<SearchEntityType>,
+    limit: Option<i64>,
+) -> Result<Vec<SearchResult>, String> {
+    global_search_impl(&db_pool.pool(), query, entity_type, limit)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fts_query_prefix_matches_each_word() {
+        assert_eq!(fts_query("whole foo").as_deref(), Some("\"whole\"* \"foo\"*"));
+    }
+
+    #[test]
+    fn test_fts_query_neutralizes_operators() {
+        assert_eq!(fts_query("\"NOT\" rent OR").as_deref(), Some("\"NOT\"* \"rent\"* \"OR\"*"));
+        assert_eq!(fts_query("a*b").as_deref(), Some("\"a*b\"*"));
+    }
+
+    #[test]
+    fn test_fts_query_without_words_is_none() {
+        assert_eq!(fts_query("   "), None);
+        assert_eq!(fts_query("\" - *"), None);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::commands::watch_folder_commands::restart_folder_watcher;
+use crate::constants::MAX_BACKUP_RETENTION;
+use crate::errors::{sanitize_db_error, AccessError};
+use crate::models::setting::{AppSetting, ReadOnlyStatus, SettingKey};
+use crate::services::backup_scheduler::BackupFrequency;
+use crate::utils::access_guard::AccessGuard;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates::UserTimezone;
+use crate::DbPool;
+use sqlx::SqlitePool;
+use std::path::Path;
+
+// Business logic functions (used by both commands and tests)
+
+fn validate_setting(key: SettingKey, value: &str) -> Result<(), String> {
+    match key {
+        SettingKey::DigestOutputDir => {
+            let path = Path::new(value);
+            if !path.is_absolute() {
+                return Err("Digest folder must be an absolute path".to_string());
+            }
+            if !path.is_dir() {
+                return Err("Digest folder does not exist".to_string());
+            }
+            Ok(())
+        }
+        SettingKey::ReadOnly => match value {
+            "true" | "false" => Ok(()),
+            _ => Err("Read-only setting must be true or false".to_string()),
+        },
+        SettingKey::Timezone => value.parse::<UserTimezone>().map(|_| ()),
+        SettingKey::ExcludePending => match value {
+            "true" | "false" => Ok(()),
+            _ => Err("Exclude pending setting must be true or false".to_string()),
+        },
+        SettingKey::BackupDir => {
+            let path = Path::new(value);
+            if !path.is_absolute() {
+                return Err("Backup folder must be an absolute path".to_string());
+            }
+            if !path.is_dir() {
+                return Err("Backup folder does not exist".to_string());
+            }
+            Ok(())
+        }
+        SettingKey::BackupFrequency => value.parse::<BackupFrequency>().map(|_| ()),
+        SettingKey::BackupRetention => match value.parse::<usize>() {
+            Ok(count) if (1..=MAX_BACKUP_RETENTION).contains(&count) => Ok(()),
+            _ => Err(format!("Backup retention must be a number of backups from 1 to {}", MAX_BACKUP_RETENTION)),
+        },
+        SettingKey::WatchDir => {
+            let path = Path::new(value);
+            if !path.is_absolute() {
+                return Err("Watch folder must be an absolute path".to_string());
+            }
+            if !path.is_dir() {
+                return Err("Watch folder does not exist".to_string());
+            }
+            Ok(())
+        }
+    }
+}
+
+pub async fn get_setting_impl(db: &SqlitePool, key: SettingKey) -> Result<Option<String>, String> {
+    let value: Option<(String,)> = sqlx::query_as("SELECT value FROM app_settings WHERE key = ?")
+        .bind(key.to_string())
+        .fetch_optional(db)
+        .await
+        .map_err(|e| sanitize_db_error(e, "load setting"))?;
+
+    Ok(value.map(|(value,)| value))
+}
+
+pub async fn list_settings_impl(db: &SqlitePool) -> Result<Vec<AppSetting>, String> {
+    let stored: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT key, value, updated_at FROM app_settings"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| sanitize_db_error(e, "load settings"))?;
+
+    Ok(SettingKey::ALL
+        .iter()
+        .map(|key| {
+            let stored = stored.iter().find(|(name, _, _)| *name == key.to_string());
+            AppSetting {
+                key: *key,
+                value: stored.map(|(_, value, _)| value.clone()),
+                updated_at: stored.map(|(_, _, updated_at)| updated_at.clone()),
+            }
+        })
+        .collect())
+}
+
+/// Set a setting, or clear it when `value` is None or blank
+pub async fn update_setting_impl(
+    db: &SqlitePool,
+    key: &str,
+    value: Option<&str>,
+) -> Result<AppSetting, String> {
+    let key: SettingKey = key.parse()?;
+
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(value) => {
+            validate_setting(key, value)?;
+            sqlx::query(
+                "INSERT INTO app_settings (key, value) VALUES (?, ?)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = CURRENT_TIMESTAMP"
+            )
+            .bind(key.to_string())
+            .bind(value)
+            .execute(db)
+            .await
+            .map_err(|e| sanitize_db_error(e, "update setting"))?;
+        }
+        None => {
+            sqlx::query("DELETE FROM app_settings WHERE key = ?")
+                .bind(key.to_string())
+                .execute(db)
+                .await
+                .map_err(|e| sanitize_db_error(e, "update setting"))?;
+        }
+    }
+
+    list_settings_impl(db)
+        .await?
+        .into_iter()
+        .find(|setting| setting.key == key)
+        .ok_or_else(|| "Failed to update setting".to_string())
+}
+
+pub fn get_read_only_mode_impl(guard: &AccessGuard) -> ReadOnlyStatus {
+    ReadOnlyStatus {
+        enabled: guard.is_read_only(),
+        forced: guard.is_forced(),
+    }
+}
+
+/// Turn read-only mode on or off and remember the choice for the next launch
+pub async fn set_read_only_mode_impl(
+    db: &SqlitePool,
+    guard: &AccessGuard,
+    enabled: bool,
+) -> Result<ReadOnlyStatus, String> {
+    if guard.is_forced() {
+        // Read-only for the whole session, and the database won't take the setting
+        return match enabled {
+            true => Ok(get_read_only_mode_impl(guard)),
+            false => Err(AccessError::ReadOnlyFlag.to_user_message()),
+        };
+    }
+
+    // The database refuses writes in read-only mode, this one included, so the mode is
+    // lifted while the choice is saved
+    let previous = guard.is_read_only();
+    let value = if enabled { "true" } else { "false" };
+    let _ = guard.set_read_only(false);
+    let saved = update_setting_impl(db, &SettingKey::ReadOnly.to_string(), Some(value)).await;
+    // Can't fail: only forced read-only mode refuses to turn off
+    let _ = guard.set_read_only(if saved.is_ok() { enabled } else { previous });
+    saved?;
+
+    tracing::info!(enabled = enabled, "Read-only mode changed");
+    Ok(get_read_only_mode_impl(guard))
+}
+
+/// Apply the saved read-only setting at startup
+pub async fn load_read_only_mode(db: &SqlitePool, guard: &AccessGuard) -> Result<(), String> {
+    if get_setting_impl(db, SettingKey::ReadOnly).await?.as_deref() == Some("true") {
+        guard.set_read_only(true).map_err(|e| e.to_user_message())?;
+    }
+    Ok(())
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn list_settings(db_pool: tauri::State<'_, DbPool>) -> Result<Vec<AppSetting>, String> {
+    list_settings_impl(&db_pool.pool()).await
+}
+
+#[tauri::command]
+pub async fn update_setting(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    guard: tauri::State<'_, AccessGuard>,
+    key: String,
+    value: Option<String>,
+) -> Result<AppSetting, String> {
+    let setting = update_setting_impl(&db_pool.pool(), &key, value.as_deref()).await?;
+    if setting.key == SettingKey::ReadOnly {
+        // Only reachable while writable, so this can only turn read-only mode on
+        guard
+            .set_read_only(setting.value.as_deref() == Some("true"))
+            .map_err(|e| e.to_user_message())?;
+    }
+    if setting.key == SettingKey::WatchDir {
+        // The folder is saved either way; a watcher that can't start is retried at the next launch
+        if let Err(e) = restart_folder_watcher(&app).await {
+            tracing::warn!(error = %e, "Failed to restart folder watcher");
+        }
+    }
+    notify_changed(&app, DataKind::Settings, ChangeAction::Updated, vec![]);
+    Ok(setting)
+}
+
+#[tauri::command]
+pub fn get_read_only_mode(guard: tauri::State<'_, AccessGuard>) -> ReadOnlyStatus {
+    get_read_only_mode_impl(&guard)
+}
+
+#[tauri::command]
+pub async fn set_read_only_mode(
+    db_pool: tauri::State<'_, DbPool>,
+    guard: tauri::State<'_, AccessGuard>,
+    enabled: bool,
+) -> Result<ReadOnlyStatus, String> {
+    set_read_only_mode_impl(&db_pool.pool(), &guard, enabled).await
+}
@@ -0,0 +1,27 @@
+use crate::services::streak_tracker::{StreakTracker, Streaks};
+use crate::utils::dates;
+use crate::DbPool;
+use sqlx::SqlitePool;
+
+// Business logic functions (used by both commands and tests)
+
+/// Under-budget, on-time payment and no-spend streaks for dashboard badges, as of `as_of` (default today)
+pub async fn get_streaks_impl(db: &SqlitePool, as_of: Option<&str>) -> Result<Streaks, String> {
+    let as_of = match as_of {
+        Some(date) => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date: {}", date))?,
+        None => dates::today(db).await,
+    };
+
+    StreakTracker::get_streaks(db, as_of).await
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn get_streaks(
+    db_pool: tauri::State<'_, DbPool>,
+    as_of: Option<String>,
+) -> Result<Streaks, String> {
+    get_streaks_impl(&db_pool.pool(), as_of.as_deref()).await
+}
@@ -1,45 +1,107 @@
+use crate::commands::freeze_commands::flag_freeze_violations_impl;
 use crate::constants::{
-    DEFAULT_CATEGORY_ID, DEFAULT_OFFSET, DEFAULT_PAGE_SIZE, MAX_BULK_OPERATION_IDS,
-    MAX_PAGE_SIZE, MAX_SEARCH_QUERY_LENGTH,
+    BULK_OPERATION_CHUNK_SIZE, DEFAULT_CATEGORY_ID, DEFAULT_CATEGORY_SUGGESTIONS, DEFAULT_OFFSET,
+    DEFAULT_PAGE_SIZE, DEFAULT_TOP_ITEMS_LIMIT, MAX_BULK_OPERATION_IDS, MAX_DESCRIPTION_LENGTH,
+    MAX_FILTER_IDS, MAX_MERCHANT_LENGTH, MAX_NOTES_LENGTH, MAX_PAGE_SIZE, MAX_SEARCH_QUERY_LENGTH,
+    MAX_TRANSACTION_CENTS,
 };
 use crate::errors::TransactionError;
-use crate::models::transaction::Transaction;
+use crate::models::audit_log::AuditEntity;
+use crate::models::export_template::{ExportTemplate, NewExportTemplate};
+use crate::models::transaction::{
+    CreateTransaction, NewTransaction, Transaction, TransactionStatus, TransactionWithBalance, UpdateTransaction,
+};
+use crate::services::audit_log::AuditRecord;
 use crate::services::categorizer::Categorizer;
+use crate::services::category_suggester::{CategorySuggester, CategorySuggestion};
+use crate::services::merchant_normalizer::MerchantNormalizer;
+use crate::services::period_locks::PeriodLocks;
+use crate::services::qif_writer::{QifAccount, QifTransaction, QifWriter};
+use crate::services::template_csv_writer::{ExportRow, TemplateCsvWriter};
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::utils::dates;
+use crate::utils::money;
+use crate::utils::query_builder::{cache_in_list, placeholders};
 use crate::DbPool;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::SqlitePool;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TransactionFilter {
-    pub account_id: Option<i64>,
-    pub category_id: Option<i64>,
+    // Each takes one id or a list; a transaction matches any id in the list,
+    // and an empty list doesn't filter
+    #[serde(default, deserialize_with = "one_or_many_ids")]
+    pub account_id: Option<Vec<i64>>,
+    #[serde(default, deserialize_with = "one_or_many_ids")]
+    pub category_id: Option<Vec<i64>>,
     pub start_date: Option<String>,
     pub end_date: Option<String>,
     pub search: Option<String>,
+    // Amount bounds compare against the absolute amount, so "over $200" catches
+    // both a $200 charge and a $200 refund
+    #[serde(rename = "min_amount", default, with = "money::dollars::option")]
+    pub min_amount_cents: Option<money::Cents>,
+    #[serde(rename = "max_amount", default, with = "money::dollars::option")]
+    pub max_amount_cents: Option<money::Cents>,
+    pub only_uncategorized: Option<bool>, // Only transactions still in the default category
+    pub exclude_category_ids: Option<Vec<i64>>,
+    #[serde(default)]
+    pub pending: Option<bool>, // Some(true) for only pending transactions, Some(false) for only posted ones
+    #[serde(default, deserialize_with = "one_or_many_ids")]
+    pub member_id: Option<Vec<i64>>, // Household members the transactions are attributed to
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
 
+/// Accept `3` as well as `[3, 4]` so callers filtering on one id keep working
+fn one_or_many_ids<'de, D>(deserializer: D) -> Result<Option<Vec<i64>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(i64),
+        Many(Vec<i64>),
+    }
+
+    Ok(Option::<OneOrMany>::deserialize(deserializer)?.map(|ids| match ids {
+        OneOrMany::One(id) => vec![id],
+        OneOrMany::Many(ids) => ids,
+    }))
+}
+
 // Helper struct to build SQL WHERE clauses for transaction filters
 // This eliminates duplication between list and count operations
 struct TransactionFilterBuilder {
     where_clauses: Vec<String>,
-    account_id: Option<i64>,
-    category_id: Option<i64>,
+    account_ids: Vec<i64>,
+    category_ids: Vec<i64>,
     start_date: Option<String>,
     end_date: Option<String>,
     search: Option<String>,
+    min_amount_cents: Option<money::Cents>,
+    max_amount_cents: Option<money::Cents>,
+    only_uncategorized: bool,
+    exclude_category_ids: Vec<i64>,
+    status: Option<TransactionStatus>,
+    member_ids: Vec<i64>,
 }
 
 impl TransactionFilterBuilder {
     fn new(filter: &TransactionFilter) -> Self {
         let mut where_clauses = Vec::new();
+        let account_ids = filter.account_id.clone().unwrap_or_default();
+        let category_ids = filter.category_id.clone().unwrap_or_default();
+        let only_uncategorized = filter.only_uncategorized.unwrap_or(false);
+        let exclude_category_ids = filter.exclude_category_ids.clone().unwrap_or_default();
 
-        if filter.account_id.is_some() {
-            where_clauses.push(" AND account_id = ?".to_string());
+        if !account_ids.is_empty() {
+            where_clauses.push(format!(" AND account_id IN ({})", placeholders(account_ids.len())));
         }
-        if filter.category_id.is_some() {
-            where_clauses.push(" AND category_id = ?".to_string());
+        if !category_ids.is_empty() {
+            where_clauses.push(format!(" AND category_id IN ({})", placeholders(category_ids.len())));
         }
         if filter.start_date.is_some() {
             where_clauses.push(" AND date >= ?".to_string());
@@ -50,6 +112,28 @@ impl TransactionFilterBuilder {
         if filter.search.is_some() {
             where_clauses.push(" AND (LOWER(description) LIKE LOWER(?) ESCAPE '!' OR LOWER(merchant) LIKE LOWER(?) ESCAPE '!')".to_string());
         }
+        if filter.min_amount_cents.is_some() {
+            where_clauses.push(" AND ABS(amount_cents) >= ?".to_string());
+        }
+        if filter.max_amount_cents.is_some() {
+            where_clauses.push(" AND ABS(amount_cents) <= ?".to_string());
+        }
+        if only_uncategorized {
+            where_clauses.push(" AND category_id = ?".to_string());
+        }
+        if !exclude_category_ids.is_empty() {
+            where_clauses.push(format!(" AND category_id NOT IN ({})", placeholders(exclude_category_ids.len())));
+        }
+        let status = filter
+            .pending
+            .map(|pending| if pending { TransactionStatus::Pending } else { TransactionStatus::Posted });
+        if status.is_some() {
+            where_clauses.push(" AND status = ?".to_string());
+        }
+        let member_ids = filter.member_id.clone().unwrap_or_default();
+        if !member_ids.is_empty() {
+            where_clauses.push(format!(" AND member_id IN ({})", placeholders(member_ids.len())));
+        }
 
         // Format search pattern here to own it
         // Escape LIKE wildcards (% and _) to prevent pattern injection
@@ -61,11 +145,17 @@ impl TransactionFilterBuilder {
 
         Self {
             where_clauses,
-            account_id: filter.account_id,
-            category_id: filter.category_id,
+            account_ids,
+            category_ids,
             start_date: filter.start_date.clone(),
             end_date: filter.end_date.clone(),
             search,
+            min_amount_cents: filter.min_amount_cents,
+            max_amount_cents: filter.max_amount_cents,
+            only_uncategorized,
+            exclude_category_ids,
+            status,
+            member_ids,
         }
     }
 
@@ -73,6 +163,16 @@ impl TransactionFilterBuilder {
         self.where_clauses.join("")
     }
 
+    /// Whether the statement is worth caching (long id lists rarely repeat)
+    fn persistent(&self) -> bool {
+        let longest = [&self.account_ids, &self.category_ids, &self.exclude_category_ids, &self.member_ids]
+            .iter()
+            .map(|ids| ids.len())
+            .max()
+            .unwrap_or(0);
+        cache_in_list(longest)
+    }
+
     fn bind_parameters<'q, O>(
         &'q self,
         mut query: sqlx::query::QueryAs<'q, sqlx::Sqlite, O, sqlx::sqlite::SqliteArguments<'q>>,
@@ -80,10 +180,10 @@ impl TransactionFilterBuilder {
     where
         O: for<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow>,
     {
-        if let Some(account_id) = self.account_id {
+        for account_id in &self.account_ids {
             query = query.bind(account_id);
         }
-        if let Some(category_id) = self.category_id {
+        for category_id in &self.category_ids {
             query = query.bind(category_id);
         }
         if let Some(ref start_date) = self.start_date {
@@ -95,10 +195,54 @@ impl TransactionFilterBuilder {
         if let Some(ref search_pattern) = self.search {
             query = query.bind(search_pattern).bind(search_pattern);
         }
+        if let Some(min_amount_cents) = self.min_amount_cents {
+            query = query.bind(min_amount_cents);
+        }
+        if let Some(max_amount_cents) = self.max_amount_cents {
+            query = query.bind(max_amount_cents);
+        }
+        if self.only_uncategorized {
+            query = query.bind(DEFAULT_CATEGORY_ID);
+        }
+        for category_id in &self.exclude_category_ids {
+            query = query.bind(category_id);
+        }
+        if let Some(status) = self.status {
+            query = query.bind(status.to_string());
+        }
+        for member_id in &self.member_ids {
+            query = query.bind(member_id);
+        }
         query
     }
 }
 
+/// Reject oversized id lists and amount bounds that are negative or out of order
+fn validate_filter(filter: &TransactionFilter) -> Result<(), TransactionError> {
+    for ids in [&filter.account_id, &filter.category_id, &filter.exclude_category_ids, &filter.member_id].into_iter().flatten() {
+        if ids.len() > MAX_FILTER_IDS {
+            return Err(TransactionError::ValidationError(
+                format!("Too many ids in filter (max {})", MAX_FILTER_IDS)
+            ));
+        }
+    }
+    for amount_cents in [filter.min_amount_cents, filter.max_amount_cents].into_iter().flatten() {
+        if amount_cents < 0 {
+            return Err(TransactionError::ValidationError(
+                "Amount filters must be zero or more".to_string()
+            ));
+        }
+    }
+    if let (Some(min), Some(max)) = (filter.min_amount_cents, filter.max_amount_cents) {
+        if min > max {
+            return Err(TransactionError::ValidationError(
+                "Minimum amount can't be more than the maximum amount".to_string()
+            ));
+        }
+    }
+    Ok(())
+}
+
 // Business logic functions (used by both commands and tests)
 
 pub async fn list_transactions_impl(
@@ -111,6 +255,12 @@ pub async fn list_transactions_impl(
         start_date: None,
         end_date: None,
         search: None,
+        min_amount_cents: None,
+        max_amount_cents: None,
+        only_uncategorized: None,
+        exclude_category_ids: None,
+        pending: None,
+        member_id: None,
         limit: Some(DEFAULT_PAGE_SIZE),
         offset: Some(DEFAULT_OFFSET),
     });
@@ -123,15 +273,17 @@ pub async fn list_transactions_impl(
         .min(MAX_PAGE_SIZE);
     let offset = filter.offset.unwrap_or(DEFAULT_OFFSET);
 
+    validate_filter(&filter)?;
+
     // Build WHERE clause using helper to avoid duplication
     let filter_builder = TransactionFilterBuilder::new(&filter);
 
     let query = format!(
-        "SELECT id, account_id, category_id, date, amount, description, merchant, hash, created_at FROM transactions WHERE 1=1{} ORDER BY date DESC LIMIT ? OFFSET ?",
+        "SELECT id, account_id, category_id, date, amount_cents, description, merchant, hash, created_at, notes, status, member_id, tax_deductible FROM transactions WHERE 1=1{} ORDER BY date DESC LIMIT ? OFFSET ?",
         filter_builder.build_where_clause()
     );
 
-    let query_builder = sqlx::query_as::<_, Transaction>(&query);
+    let query_builder = sqlx::query_as::<_, Transaction>(&query).persistent(filter_builder.persistent());
 
     // Bind filter parameters first, then pagination
     let query_builder = filter_builder.bind_parameters(query_builder);
@@ -143,6 +295,65 @@ pub async fn list_transactions_impl(
         .map_err(|e| TransactionError::Database(e.to_string()))
 }
 
+/// Like `list_transactions_impl`, with each row's running account balance
+///
+/// The balance starts from the account's initial balance and adds every earlier
+/// transaction in (date, id) order, so filters narrow the rows shown but never
+/// change the balances on them.
+pub async fn list_transactions_with_balance_impl(
+    db: &SqlitePool,
+    filter: Option<TransactionFilter>,
+) -> Result<Vec<TransactionWithBalance>, TransactionError> {
+    let filter = filter.unwrap_or(TransactionFilter {
+        account_id: None,
+        category_id: None,
+        start_date: None,
+        end_date: None,
+        search: None,
+        min_amount_cents: None,
+        max_amount_cents: None,
+        only_uncategorized: None,
+        exclude_category_ids: None,
+        pending: None,
+        member_id: None,
+        limit: Some(DEFAULT_PAGE_SIZE),
+        offset: Some(DEFAULT_OFFSET),
+    });
+
+    let limit = filter
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .min(MAX_PAGE_SIZE);
+    let offset = filter.offset.unwrap_or(DEFAULT_OFFSET);
+
+    validate_filter(&filter)?;
+    let filter_builder = TransactionFilterBuilder::new(&filter);
+
+    // Balances are computed over all of an account's transactions before filtering
+    let query = format!(
+        "SELECT id, account_id, category_id, date, amount_cents, description, merchant, hash, created_at, notes, status, member_id, tax_deductible, running_balance_cents
+         FROM (
+             SELECT t.*, a.balance_cents + SUM(t.amount_cents) OVER (
+                 PARTITION BY t.account_id ORDER BY t.date, t.id
+                 ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+             ) AS running_balance_cents
+             FROM transactions t
+             JOIN accounts a ON a.id = t.account_id
+         )
+         WHERE 1=1{} ORDER BY date DESC, id DESC LIMIT ? OFFSET ?",
+        filter_builder.build_where_clause()
+    );
+
+    let query_builder = sqlx::query_as::<_, TransactionWithBalance>(&query).persistent(filter_builder.persistent());
+    let query_builder = filter_builder.bind_parameters(query_builder);
+    let query_builder = query_builder.bind(limit).bind(offset);
+
+    query_builder
+        .fetch_all(db)
+        .await
+        .map_err(|e| TransactionError::Database(e.to_string()))
+}
+
 pub async fn count_transactions_impl(
     db: &SqlitePool,
     filter: Option<TransactionFilter>,
@@ -153,10 +364,18 @@ pub async fn count_transactions_impl(
         start_date: None,
         end_date: None,
         search: None,
+        min_amount_cents: None,
+        max_amount_cents: None,
+        only_uncategorized: None,
+        exclude_category_ids: None,
+        pending: None,
+        member_id: None,
         limit: None,
         offset: None,
     });
 
+    validate_filter(&filter)?;
+
     // Build WHERE clause using helper to avoid duplication
     let filter_builder = TransactionFilterBuilder::new(&filter);
 
@@ -165,7 +384,7 @@ pub async fn count_transactions_impl(
         filter_builder.build_where_clause()
     );
 
-    let query_builder = sqlx::query_as::<_, (i64,)>(&query);
+    let query_builder = sqlx::query_as::<_, (i64,)>(&query).persistent(filter_builder.persistent());
     let query_builder = filter_builder.bind_parameters(query_builder);
 
     query_builder
@@ -175,11 +394,69 @@ pub async fn count_transactions_impl(
         .map_err(|e| TransactionError::Database(e.to_string()))
 }
 
+/// Absolute amounts of every transaction matching `filter`, in cents and smallest first
+/// Pagination is ignored; `expenses` keeps only outflows (true) or inflows (false)
+pub(crate) async fn matching_amounts(
+    db: &SqlitePool,
+    filter: Option<TransactionFilter>,
+    expenses: Option<bool>,
+) -> Result<Vec<money::Cents>, TransactionError> {
+    let filter = filter.unwrap_or(TransactionFilter {
+        account_id: None,
+        category_id: None,
+        start_date: None,
+        end_date: None,
+        search: None,
+        min_amount_cents: None,
+        max_amount_cents: None,
+        only_uncategorized: None,
+        exclude_category_ids: None,
+        pending: None,
+        member_id: None,
+        limit: None,
+        offset: None,
+    });
+
+    validate_filter(&filter)?;
+    let filter_builder = TransactionFilterBuilder::new(&filter);
+
+    let direction = match expenses {
+        Some(true) => " AND amount_cents < 0",
+        Some(false) => " AND amount_cents > 0",
+        None => "",
+    };
+    let query = format!(
+        "SELECT ABS(amount_cents) as cents FROM transactions WHERE 1=1{}{} ORDER BY cents",
+        filter_builder.build_where_clause(),
+        direction
+    );
+
+    let query_builder = sqlx::query_as::<_, (money::Cents,)>(&query).persistent(filter_builder.persistent());
+    let query_builder = filter_builder.bind_parameters(query_builder);
+
+    query_builder
+        .fetch_all(db)
+        .await
+        .map(|rows| rows.into_iter().map(|(cents,)| cents).collect())
+        .map_err(|e| TransactionError::Database(e.to_string()))
+}
+
+/// `override_lock` allows recategorizing a transaction dated in a locked month
 pub async fn update_transaction_category_impl(
     db: &SqlitePool,
     transaction_id: i64,
     category_id: i64,
+    override_lock: bool,
 ) -> Result<(), TransactionError> {
+    let previous: Option<(i64, String)> = sqlx::query_as("SELECT category_id, date FROM transactions WHERE id = ?")
+        .bind(transaction_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| TransactionError::Database(e.to_string()))?;
+    if let Some((_, date)) = &previous {
+        ensure_unlocked(db, &[date.as_str()], override_lock).await?;
+    }
+
     sqlx::query("UPDATE transactions SET category_id = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
         .bind(category_id)
         .bind(transaction_id)
@@ -187,6 +464,14 @@ pub async fn update_transaction_category_impl(
         .await
         .map_err(|e| TransactionError::Database(e.to_string()))?;
 
+    if let Some((previous_category_id, _)) = previous {
+        AuditRecord::updated(AuditEntity::Transaction, transaction_id)
+            .old_values(&json!({ "category_id": previous_category_id }))
+            .new_values(&json!({ "category_id": category_id }))
+            .record(db)
+            .await;
+    }
+
     Ok(())
 }
 
@@ -196,25 +481,30 @@ pub struct CategorizeResult {
     pub matched_rule_id: Option<i64>,
 }
 
+/// `override_lock` allows recategorizing a transaction dated in a locked month
 pub async fn categorize_transaction_impl(
     db: &SqlitePool,
     transaction_id: i64,
+    override_lock: bool,
 ) -> Result<CategorizeResult, TransactionError> {
     // Get the transaction
     let transaction = sqlx::query_as::<_, Transaction>(
-        "SELECT id, account_id, category_id, date, amount, description, merchant, hash, created_at
+        "SELECT id, account_id, category_id, date, amount_cents, description, merchant, hash, created_at, notes, status, member_id, tax_deductible
          FROM transactions WHERE id = ?"
     )
     .bind(transaction_id)
     .fetch_one(db)
     .await
     .map_err(|e| TransactionError::Database(e.to_string()))?;
+    ensure_unlocked(db, &[&transaction.date], override_lock).await?;
 
     // Use categorizer to find best category
     let category_id = Categorizer::categorize(
         db,
         transaction.merchant.as_deref(),
         &transaction.description,
+        transaction.amount_cents,
+        Some(transaction.account_id),
     )
     .await
     .map_err(|_| TransactionError::CategorizationError)?
@@ -228,12 +518,50 @@ pub async fn categorize_transaction_impl(
         .await
         .map_err(|e| TransactionError::Database(e.to_string()))?;
 
+    AuditRecord::updated(AuditEntity::Transaction, transaction_id)
+        .old_values(&json!({ "category_id": transaction.category_id }))
+        .new_values(&json!({ "category_id": category_id }))
+        .record(db)
+        .await;
+
     Ok(CategorizeResult {
         category_id,
         matched_rule_id: None, // TODO: Return actual matched rule ID
     })
 }
 
+/// Ranked category suggestions for a transaction based on how similar
+/// merchants/descriptions were categorized in the past (does not modify the transaction)
+pub async fn suggest_category_for_transaction_impl(
+    db: &SqlitePool,
+    transaction_id: i64,
+    limit: Option<i64>,
+) -> Result<Vec<CategorySuggestion>, TransactionError> {
+    let limit = limit
+        .unwrap_or(DEFAULT_CATEGORY_SUGGESTIONS)
+        .clamp(1, DEFAULT_TOP_ITEMS_LIMIT) as usize;
+
+    let transaction = sqlx::query_as::<_, Transaction>(
+        "SELECT id, account_id, category_id, date, amount_cents, description, merchant, hash, created_at, notes, status, member_id, tax_deductible
+         FROM transactions WHERE id = ?"
+    )
+    .bind(transaction_id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| TransactionError::Database(e.to_string()))?
+    .ok_or(TransactionError::NotFound(transaction_id))?;
+
+    CategorySuggester::suggest(
+        db,
+        transaction.merchant.as_deref(),
+        &transaction.description,
+        Some(transaction.id),
+        limit,
+    )
+    .await
+    .map_err(TransactionError::Database)
+}
+
 #[derive(Debug, Serialize)]
 pub struct ExportResult {
     pub success: bool,
@@ -241,17 +569,189 @@ pub struct ExportResult {
     pub record_count: usize,
 }
 
+/// Group transactions into QIF account sections with their category paths
+/// The merchant becomes the payee and the bank description is kept as the memo
+async fn build_qif_export(db: &SqlitePool, transactions: &[Transaction]) -> Result<String, TransactionError> {
+    let accounts: Vec<(i64, String, String)> = sqlx::query_as("SELECT id, name, type FROM accounts ORDER BY name")
+        .fetch_all(db)
+        .await
+        .map_err(|e| TransactionError::Database(e.to_string()))?;
+
+    let categories: std::collections::HashMap<i64, String> = sqlx::query_as::<_, (i64, String, Option<String>)>(
+        "SELECT c.id, c.name, p.name FROM categories c LEFT JOIN categories p ON p.id = c.parent_id"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| TransactionError::Database(e.to_string()))?
+    .into_iter()
+    .map(|(id, name, parent)| (id, QifWriter::category_path(&name, parent.as_deref())))
+    .collect();
+
+    let qif_accounts: Vec<QifAccount> = accounts
+        .into_iter()
+        .filter_map(|(account_id, name, account_type)| {
+            let mut account_transactions: Vec<QifTransaction> = transactions
+                .iter()
+                .filter(|t| t.account_id == account_id)
+                .map(|t| QifTransaction {
+                    date: t.date.clone(),
+                    amount_cents: t.amount_cents,
+                    payee: t.merchant.clone().unwrap_or_else(|| t.description.clone()),
+                    memo: t.merchant.as_ref().map(|_| t.description.clone()),
+                    category: (t.category_id != DEFAULT_CATEGORY_ID)
+                        .then(|| categories.get(&t.category_id).cloned())
+                        .flatten(),
+                })
+                .collect();
+            if account_transactions.is_empty() {
+                return None;
+            }
+            // Register order reads oldest first
+            account_transactions.sort_by(|a, b| a.date.cmp(&b.date));
+            Some(QifAccount {
+                name,
+                account_type,
+                transactions: account_transactions,
+            })
+        })
+        .collect();
+
+    Ok(QifWriter::write(&qif_accounts))
+}
+
+type ExportTemplateRow = (i64, String, String, Option<String>, String, String, bool, String);
+
+fn export_template_from_row(row: ExportTemplateRow) -> Result<ExportTemplate, TransactionError> {
+    let (id, name, columns, date_format, decimal_separator, delimiter, include_header, created_at) = row;
+    Ok(ExportTemplate {
+        id,
+        name,
+        columns: serde_json::from_str(&columns)
+            .map_err(|e| TransactionError::Database(format!("Invalid columns for export template {}: {}", id, e)))?,
+        date_format,
+        decimal_separator,
+        delimiter,
+        include_header,
+        created_at,
+    })
+}
+
+/// Save a CSV export layout; names are unique
+pub async fn save_export_template_impl(
+    db: &SqlitePool,
+    template: NewExportTemplate,
+) -> Result<i64, TransactionError> {
+    TemplateCsvWriter::validate(&template).map_err(TransactionError::ValidationError)?;
+    let name = template.name.trim();
+    let columns = serde_json::to_string(&template.columns)
+        .map_err(|e| TransactionError::Database(e.to_string()))?;
+
+    let result = sqlx::query(
+        "INSERT INTO export_templates (name, columns, date_format, decimal_separator, delimiter, include_header)
+         VALUES (?, ?, ?, ?, ?, ?)"
+    )
+    .bind(name)
+    .bind(columns)
+    .bind(&template.date_format)
+    .bind(&template.decimal_separator)
+    .bind(&template.delimiter)
+    .bind(template.include_header)
+    .execute(db)
+    .await
+    .map_err(|e| {
+        if e.to_string().contains("UNIQUE constraint failed") {
+            TransactionError::ValidationError(format!("Export template '{}' already exists", name))
+        } else {
+            TransactionError::Database(e.to_string())
+        }
+    })?;
+
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn list_export_templates_impl(db: &SqlitePool) -> Result<Vec<ExportTemplate>, TransactionError> {
+    sqlx::query_as::<_, ExportTemplateRow>(
+        "SELECT id, name, columns, date_format, decimal_separator, delimiter, include_header, created_at
+         FROM export_templates ORDER BY name"
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| TransactionError::Database(e.to_string()))?
+    .into_iter()
+    .map(export_template_from_row)
+    .collect()
+}
+
+/// Write `transactions` in a saved template's layout
+async fn build_template_export(
+    db: &SqlitePool,
+    template_id: i64,
+    transactions: &[Transaction],
+) -> Result<String, TransactionError> {
+    let template = sqlx::query_as::<_, ExportTemplateRow>(
+        "SELECT id, name, columns, date_format, decimal_separator, delimiter, include_header, created_at
+         FROM export_templates WHERE id = ?"
+    )
+    .bind(template_id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| TransactionError::Database(e.to_string()))?
+    .ok_or_else(|| TransactionError::ValidationError(format!("Export template {} not found", template_id)))
+    .and_then(export_template_from_row)?;
+
+    let categories: std::collections::HashMap<i64, String> = sqlx::query_as("SELECT id, name FROM categories")
+        .fetch_all(db)
+        .await
+        .map_err(|e| TransactionError::Database(e.to_string()))?
+        .into_iter()
+        .collect();
+    let accounts: std::collections::HashMap<i64, String> = sqlx::query_as("SELECT id, name FROM accounts")
+        .fetch_all(db)
+        .await
+        .map_err(|e| TransactionError::Database(e.to_string()))?
+        .into_iter()
+        .collect();
+
+    let rows: Vec<ExportRow> = transactions
+        .iter()
+        .map(|t| ExportRow {
+            date: t.date.clone(),
+            amount_cents: t.amount_cents,
+            description: t.description.clone(),
+            merchant: t.merchant.clone(),
+            category: categories.get(&t.category_id).cloned().unwrap_or_else(|| "Unknown".to_string()),
+            account: accounts.get(&t.account_id).cloned().unwrap_or_default(),
+            notes: t.notes.clone(),
+            status: t.status.clone(),
+        })
+        .collect();
+
+    TemplateCsvWriter::write(&template, &rows).map_err(TransactionError::ValidationError)
+}
+
+/// `template_id` lays a CSV export out with a saved template instead of the default columns
 pub async fn export_transactions_impl(
     db: &SqlitePool,
     format: String,
     output_path: String,
     filter: Option<TransactionFilter>,
+    template_id: Option<i64>,
 ) -> Result<ExportResult, TransactionError> {
+    if template_id.is_some() && format != "csv" {
+        return Err(TransactionError::ValidationError("Export templates only apply to CSV exports".to_string()));
+    }
+
     // Get transactions using the filter
     let transactions = list_transactions_impl(db, filter).await?;
 
-    match format.as_str() {
-        "csv" => {
+    match (format.as_str(), template_id) {
+        ("csv", Some(template_id)) => {
+            let csv_content = build_template_export(db, template_id, &transactions).await?;
+
+            std::fs::write(&output_path, csv_content)
+                .map_err(|e| TransactionError::Database(format!("Failed to write file: {}", e)))?;
+        }
+        ("csv", None) => {
             // Create CSV content
             let mut csv_content = String::from("Date,Amount,Description,Merchant,Category\n");
 
@@ -261,7 +761,7 @@ pub async fn export_transactions_impl(
                 std::fs::write(&output_path, csv_content)
                     .map_err(|e| TransactionError::Database(format!("Failed to write file: {}", e)))?;
             } else {
-                let placeholders = transaction_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+                let placeholders = placeholders(transaction_ids.len());
                 let query_str = format!(
                     "SELECT t.id, c.name FROM transactions t
                      JOIN categories c ON t.category_id = c.id
@@ -269,7 +769,8 @@ pub async fn export_transactions_impl(
                     placeholders
                 );
 
-                let mut query = sqlx::query_as::<_, (i64, String)>(&query_str);
+                let mut query = sqlx::query_as::<_, (i64, String)>(&query_str)
+                    .persistent(cache_in_list(transaction_ids.len()));
                 for id in &transaction_ids {
                     query = query.bind(id);
                 }
@@ -290,7 +791,7 @@ pub async fn export_transactions_impl(
                     csv_content.push_str(&format!(
                         "{},{},{},{},{}\n",
                         transaction.date,
-                        transaction.amount,
+                        money::from_cents(transaction.amount_cents),
                         transaction.description,
                         transaction.merchant.as_ref().unwrap_or(&String::from("")),
                         category_name
@@ -302,13 +803,19 @@ pub async fn export_transactions_impl(
                     .map_err(|e| TransactionError::Database(format!("Failed to write file: {}", e)))?;
             }
         }
-        "json" => {
+        ("json", _) => {
             let json_content = serde_json::to_string_pretty(&transactions)
                 .map_err(|e| TransactionError::Database(format!("Failed to serialize JSON: {}", e)))?;
 
             std::fs::write(&output_path, json_content)
                 .map_err(|e| TransactionError::Database(format!("Failed to write file: {}", e)))?;
         }
+        ("qif", _) => {
+            let qif_content = build_qif_export(db, &transactions).await?;
+
+            std::fs::write(&output_path, qif_content)
+                .map_err(|e| TransactionError::Database(format!("Failed to write file: {}", e)))?;
+        }
         _ => return Err(TransactionError::Database(format!("Unsupported format: {}", format))),
     }
 
@@ -326,28 +833,57 @@ pub async fn list_transactions(
     db_pool: tauri::State<'_, DbPool>,
     filter: Option<TransactionFilter>,
 ) -> Result<Vec<Transaction>, String> {
-    list_transactions_impl(&db_pool.0, filter)
+    list_transactions_impl(&db_pool.pool(), filter)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn list_transactions_with_balance(
+    db_pool: tauri::State<'_, DbPool>,
+    filter: Option<TransactionFilter>,
+) -> Result<Vec<TransactionWithBalance>, String> {
+    list_transactions_with_balance_impl(&db_pool.pool(), filter)
         .await
         .map_err(|e| e.to_user_message())
 }
 
 #[tauri::command]
 pub async fn update_transaction_category(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     transaction_id: i64,
     category_id: i64,
+    override_lock: Option<bool>,
 ) -> Result<(), String> {
-    update_transaction_category_impl(&db_pool.0, transaction_id, category_id)
+    update_transaction_category_impl(&db_pool.pool(), transaction_id, category_id, override_lock.unwrap_or(false))
         .await
-        .map_err(|e| e.to_user_message())
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Updated, [transaction_id]);
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn categorize_transaction(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     transaction_id: i64,
+    override_lock: Option<bool>,
 ) -> Result<CategorizeResult, String> {
-    categorize_transaction_impl(&db_pool.0, transaction_id)
+    let result = categorize_transaction_impl(&db_pool.pool(), transaction_id, override_lock.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Updated, [transaction_id]);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn suggest_category_for_transaction(
+    db_pool: tauri::State<'_, DbPool>,
+    transaction_id: i64,
+    limit: Option<i64>,
+) -> Result<Vec<CategorySuggestion>, String> {
+    suggest_category_for_transaction_impl(&db_pool.pool(), transaction_id, limit)
         .await
         .map_err(|e| e.to_user_message())
 }
@@ -358,8 +894,26 @@ pub async fn export_transactions(
     format: String,
     output_path: String,
     filter: Option<TransactionFilter>,
+    template_id: Option<i64>,
 ) -> Result<ExportResult, String> {
-    export_transactions_impl(&db_pool.0, format, output_path, filter)
+    export_transactions_impl(&db_pool.pool(), format, output_path, filter, template_id)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn save_export_template(
+    db_pool: tauri::State<'_, DbPool>,
+    template: NewExportTemplate,
+) -> Result<i64, String> {
+    save_export_template_impl(&db_pool.pool(), template)
+        .await
+        .map_err(|e| e.to_user_message())
+}
+
+#[tauri::command]
+pub async fn list_export_templates(db_pool: tauri::State<'_, DbPool>) -> Result<Vec<ExportTemplate>, String> {
+    list_export_templates_impl(&db_pool.pool())
         .await
         .map_err(|e| e.to_user_message())
 }
@@ -369,7 +923,7 @@ pub async fn count_transactions(
     db_pool: tauri::State<'_, DbPool>,
     filter: Option<TransactionFilter>,
 ) -> Result<i64, String> {
-    count_transactions_impl(&db_pool.0, filter)
+    count_transactions_impl(&db_pool.pool(), filter)
         .await
         .map_err(|e| e.to_user_message())
 }
@@ -394,6 +948,12 @@ pub async fn search_transactions_impl(
         start_date: None,
         end_date: None,
         search: None,
+        min_amount_cents: None,
+        max_amount_cents: None,
+        only_uncategorized: None,
+        exclude_category_ids: None,
+        pending: None,
+        member_id: None,
         limit: Some(DEFAULT_PAGE_SIZE),
         offset: Some(DEFAULT_OFFSET),
     });
@@ -408,16 +968,21 @@ pub async fn search_transactions(
     query: String,
     filter: Option<TransactionFilter>,
 ) -> Result<Vec<Transaction>, String> {
-    search_transactions_impl(&db_pool.0, query, filter)
+    search_transactions_impl(&db_pool.pool(), query, filter)
         .await
         .map_err(|e| e.to_user_message())
 }
 
 // Delete transaction implementation
+/// `override_lock` allows deleting a transaction dated in a locked month
 pub async fn delete_transaction_impl(
     db: &SqlitePool,
     transaction_id: i64,
+    override_lock: bool,
 ) -> Result<(), TransactionError> {
+    let current = get_transaction(db, transaction_id).await?;
+    ensure_unlocked(db, &[&current.date], override_lock).await?;
+
     let result = sqlx::query("DELETE FROM transactions WHERE id = ?")
         .bind(transaction_id)
         .execute(db)
@@ -428,17 +993,362 @@ pub async fn delete_transaction_impl(
         return Err(TransactionError::NotFound(transaction_id));
     }
 
+    AuditRecord::deleted(AuditEntity::Transaction, transaction_id)
+        .old_values(&current)
+        .record(db)
+        .await;
+
     Ok(())
 }
 
 #[tauri::command]
 pub async fn delete_transaction(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     transaction_id: i64,
+    override_lock: Option<bool>,
 ) -> Result<(), String> {
-    delete_transaction_impl(&db_pool.0, transaction_id)
+    delete_transaction_impl(&db_pool.pool(), transaction_id, override_lock.unwrap_or(false))
         .await
-        .map_err(|e| e.to_user_message())
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Deleted, [transaction_id]);
+    Ok(())
+}
+
+/// Validate hand-entered fields, returning the date normalized to YYYY-MM-DD
+pub(crate) fn validate_transaction_fields(
+    date: &str,
+    amount_cents: money::Cents,
+    description: &str,
+    merchant: Option<&str>,
+    notes: Option<&str>,
+) -> Result<String, TransactionError> {
+    let date = dates::parse_date(date)
+        .map(dates::format_date)
+        .map_err(|_| TransactionError::InvalidDate(date.to_string()))?;
+
+    if amount_cents == 0 {
+        return Err(TransactionError::InvalidAmount("Amount must be a non-zero number".to_string()));
+    }
+    if amount_cents.abs() > MAX_TRANSACTION_CENTS {
+        return Err(TransactionError::InvalidAmount(format!(
+            "Amount exceeds maximum of {}",
+            money::from_cents(MAX_TRANSACTION_CENTS)
+        )));
+    }
+
+    if description.trim().is_empty() {
+        return Err(TransactionError::ValidationError("Description cannot be empty".to_string()));
+    }
+    if description.len() > MAX_DESCRIPTION_LENGTH {
+        return Err(TransactionError::ValidationError(format!(
+            "Description too long (max {} characters)",
+            MAX_DESCRIPTION_LENGTH
+        )));
+    }
+    if merchant.is_some_and(|m| m.len() > MAX_MERCHANT_LENGTH) {
+        return Err(TransactionError::ValidationError(format!(
+            "Merchant too long (max {} characters)",
+            MAX_MERCHANT_LENGTH
+        )));
+    }
+    if notes.is_some_and(|n| n.len() > MAX_NOTES_LENGTH) {
+        return Err(TransactionError::ValidationError(format!(
+            "Notes too long (max {} characters)",
+            MAX_NOTES_LENGTH
+        )));
+    }
+
+    Ok(date)
+}
+
+/// Trim an optional text field, treating blank as absent
+pub(crate) fn non_empty(value: Option<&str>) -> Option<String> {
+    value.map(str::trim).filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+pub(crate) async fn ensure_account_exists(db: &SqlitePool, account_id: i64) -> Result<(), TransactionError> {
+    let found: Option<(i64,)> = sqlx::query_as("SELECT id FROM accounts WHERE id = ?")
+        .bind(account_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| TransactionError::Database(e.to_string()))?;
+    found.map(|_| ()).ok_or(TransactionError::AccountNotFound(account_id))
+}
+
+pub(crate) async fn ensure_category_exists(db: &SqlitePool, category_id: i64) -> Result<(), TransactionError> {
+    let found: Option<(i64,)> = sqlx::query_as("SELECT id FROM categories WHERE id = ?")
+        .bind(category_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| TransactionError::Database(e.to_string()))?;
+    found.map(|_| ()).ok_or(TransactionError::CategoryNotFound(category_id))
+}
+
+/// Fail when any of `dates` falls in a locked month, unless the change overrides the lock
+pub(crate) async fn ensure_unlocked(db: &SqlitePool, dates: &[&str], override_lock: bool) -> Result<(), TransactionError> {
+    if override_lock {
+        return Ok(());
+    }
+    let locks = PeriodLocks::load(db)
+        .await
+        .map_err(|e| TransactionError::Database(e.to_string()))?;
+    match locks.first_locked(dates.iter().copied()) {
+        Some(month) => Err(TransactionError::PeriodLocked(month)),
+        None => Ok(()),
+    }
+}
+
+/// Locked months for a bulk change, loaded once and checked per chunk; none when overriding
+async fn locks_for(db: &SqlitePool, override_lock: bool) -> Result<PeriodLocks, TransactionError> {
+    if override_lock {
+        return Ok(PeriodLocks::default());
+    }
+    PeriodLocks::load(db)
+        .await
+        .map_err(|e| TransactionError::Database(e.to_string()))
+}
+
+/// Fail if another transaction already has the row's hash under any version, since imports use
+/// it to detect duplicates; returns the current version's hash
+async fn ensure_hash_unused(
+    db: &SqlitePool,
+    account_id: i64,
+    date: &str,
+    amount_cents: money::Cents,
+    description: &str,
+    transaction_id: Option<i64>,
+) -> Result<String, TransactionError> {
+    let hashes: Vec<String> = (1..=NewTransaction::HASH_VERSION)
+        .map(|version| NewTransaction::hash_for_version(version, account_id, date, amount_cents, description))
+        .collect();
+    let hashes_json = serde_json::to_string(&hashes).map_err(|e| TransactionError::Database(e.to_string()))?;
+    let existing: Option<(i64,)> = sqlx::query_as(
+        "SELECT id FROM transactions WHERE hash IN (SELECT value FROM json_each(?)) AND id IS NOT ? LIMIT 1"
+    )
+    .bind(hashes_json)
+    .bind(transaction_id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| TransactionError::Database(e.to_string()))?;
+    match existing {
+        Some((id,)) => Err(TransactionError::Duplicate(id)),
+        None => Ok(hashes.last().cloned().unwrap_or_default()),
+    }
+}
+
+async fn get_transaction(db: &SqlitePool, transaction_id: i64) -> Result<Transaction, TransactionError> {
+    sqlx::query_as::<_, Transaction>(
+        "SELECT id, account_id, category_id, date, amount_cents, description, merchant, hash, created_at, notes, status, member_id, tax_deductible
+         FROM transactions WHERE id = ?"
+    )
+    .bind(transaction_id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| TransactionError::Database(e.to_string()))?
+    .ok_or(TransactionError::NotFound(transaction_id))
+}
+
+// Create transaction implementation
+/// `override_lock` allows adding a transaction dated in a locked month
+pub async fn create_transaction_impl(
+    db: &SqlitePool,
+    transaction: CreateTransaction,
+    override_lock: bool,
+) -> Result<Transaction, TransactionError> {
+    let merchant = non_empty(transaction.merchant.as_deref());
+    let notes = non_empty(transaction.notes.as_deref());
+    let description = transaction.description.trim().to_string();
+    let date = validate_transaction_fields(
+        &transaction.date,
+        transaction.amount_cents,
+        &description,
+        merchant.as_deref(),
+        notes.as_deref(),
+    )?;
+    ensure_unlocked(db, &[&date], override_lock).await?;
+
+    ensure_account_exists(db, transaction.account_id).await?;
+    let category_id = match transaction.category_id {
+        Some(category_id) => {
+            ensure_category_exists(db, category_id).await?;
+            category_id
+        }
+        None => Categorizer::categorize(
+            db,
+            merchant.as_deref(),
+            &description,
+            transaction.amount_cents,
+            Some(transaction.account_id),
+        )
+        .await
+        .map_err(|_| TransactionError::CategorizationError)?
+        .unwrap_or(DEFAULT_CATEGORY_ID),
+    };
+
+    // Same hash as an import of this row would get, so the row isn't imported again later
+    let hash = ensure_hash_unused(db, transaction.account_id, &date, transaction.amount_cents, &description, None).await?;
+
+    let merchant_id = match merchant.as_deref() {
+        Some(raw) => MerchantNormalizer::resolve(db, raw)
+            .await
+            .map_err(TransactionError::Database)?,
+        None => None,
+    };
+
+    let result = sqlx::query(
+        "INSERT INTO transactions (account_id, category_id, date, amount_cents, description, merchant, merchant_id, hash,
+            hash_version, notes)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(transaction.account_id)
+    .bind(category_id)
+    .bind(&date)
+    .bind(transaction.amount_cents)
+    .bind(&description)
+    .bind(&merchant)
+    .bind(merchant_id)
+    .bind(&hash)
+    .bind(NewTransaction::HASH_VERSION)
+    .bind(&notes)
+    .execute(db)
+    .await
+    .map_err(|e| TransactionError::Database(e.to_string()))?;
+    let transaction_id = result.last_insert_rowid();
+
+    if let Err(e) = flag_freeze_violations_impl(db, &[transaction_id]).await {
+        tracing::warn!(error = %e, "Failed to flag spending freeze violation");
+    }
+
+    let created = get_transaction(db, transaction_id).await?;
+    AuditRecord::created(AuditEntity::Transaction, transaction_id)
+        .new_values(&created)
+        .record(db)
+        .await;
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn create_transaction(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    transaction: CreateTransaction,
+    override_lock: Option<bool>,
+) -> Result<Transaction, String> {
+    let result = create_transaction_impl(&db_pool.pool(), transaction, override_lock.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Created, [result.id]);
+    Ok(result)
+}
+
+// Update transaction implementation
+/// `override_lock` allows changing a transaction dated in, or moved into, a locked month
+pub async fn update_transaction_impl(
+    db: &SqlitePool,
+    update: UpdateTransaction,
+    override_lock: bool,
+) -> Result<Transaction, TransactionError> {
+    let current = get_transaction(db, update.id).await?;
+
+    let account_id = update.account_id.unwrap_or(current.account_id);
+    let category_id = update.category_id.unwrap_or(current.category_id);
+    let amount_cents = update.amount_cents.unwrap_or(current.amount_cents);
+    let description = update
+        .description
+        .as_deref()
+        .map(str::trim)
+        .unwrap_or(&current.description)
+        .to_string();
+    let merchant = match update.merchant.as_deref() {
+        Some(merchant) => non_empty(Some(merchant)),
+        None => current.merchant.clone(),
+    };
+    let notes = match update.notes.as_deref() {
+        Some(notes) => non_empty(Some(notes)),
+        None => current.notes.clone(),
+    };
+    let date = validate_transaction_fields(
+        update.date.as_deref().unwrap_or(&current.date),
+        amount_cents,
+        &description,
+        merchant.as_deref(),
+        notes.as_deref(),
+    )?;
+    ensure_unlocked(db, &[&current.date, &date], override_lock).await?;
+
+    if update.account_id.is_some() {
+        ensure_account_exists(db, account_id).await?;
+    }
+    if update.category_id.is_some() {
+        ensure_category_exists(db, category_id).await?;
+    }
+
+    // Regenerate the duplicate-detection hash only when a hashed field changed,
+    // so hashes of untouched rows (e.g. salted quick entries) are kept along with their version
+    let rehashed = account_id != current.account_id
+        || date != current.date
+        || amount_cents != current.amount_cents
+        || description != current.description;
+    let (hash, hash_version) = if rehashed {
+        let hash = ensure_hash_unused(db, account_id, &date, amount_cents, &description, Some(update.id)).await?;
+        (hash, Some(NewTransaction::HASH_VERSION))
+    } else {
+        (current.hash.clone(), None)
+    };
+
+    let merchant_id = match merchant.as_deref() {
+        Some(raw) => MerchantNormalizer::resolve(db, raw)
+            .await
+            .map_err(TransactionError::Database)?,
+        None => None,
+    };
+
+    sqlx::query(
+        "UPDATE transactions
+         SET account_id = ?, category_id = ?, date = ?, amount_cents = ?, description = ?, merchant = ?,
+             merchant_id = ?, hash = ?, hash_version = COALESCE(?, hash_version), notes = ?,
+             updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?"
+    )
+    .bind(account_id)
+    .bind(category_id)
+    .bind(&date)
+    .bind(amount_cents)
+    .bind(&description)
+    .bind(&merchant)
+    .bind(merchant_id)
+    .bind(&hash)
+    .bind(hash_version)
+    .bind(&notes)
+    .bind(update.id)
+    .execute(db)
+    .await
+    .map_err(|e| TransactionError::Database(e.to_string()))?;
+
+    let updated = get_transaction(db, update.id).await?;
+    AuditRecord::updated(AuditEntity::Transaction, update.id)
+        .old_values(&current)
+        .new_values(&updated)
+        .record(db)
+        .await;
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn update_transaction(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    update: UpdateTransaction,
+    override_lock: Option<bool>,
+) -> Result<Transaction, String> {
+    let result = update_transaction_impl(&db_pool.pool(), update, override_lock.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Updated, [result.id]);
+    Ok(result)
 }
 
 // Bulk delete transactions implementation
@@ -449,9 +1359,33 @@ pub struct BulkDeleteResult {
     pub failed_ids: Vec<i64>,
 }
 
+/// The transactions among `ids`, read inside the bulk operation's database transaction
+async fn existing_transactions(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    ids: &[i64],
+) -> Result<Vec<Transaction>, TransactionError> {
+    let query_str = format!(
+        "SELECT id, account_id, category_id, date, amount_cents, description, merchant, hash, created_at, notes, status, member_id, tax_deductible
+         FROM transactions WHERE id IN ({})",
+        placeholders(ids.len())
+    );
+    let mut query = sqlx::query_as::<_, Transaction>(&query_str).persistent(cache_in_list(ids.len()));
+    for id in ids {
+        query = query.bind(id);
+    }
+
+    query
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| TransactionError::Database(e.to_string()))
+}
+
+/// Nothing is deleted when any of the transactions is dated in a locked month, unless
+/// `override_lock` is set
 pub async fn bulk_delete_transactions_impl(
     db: &SqlitePool,
     transaction_ids: Vec<i64>,
+    override_lock: bool,
 ) -> Result<BulkDeleteResult, TransactionError> {
     // Validate input
     if transaction_ids.is_empty() {
@@ -463,39 +1397,49 @@ pub async fn bulk_delete_transactions_impl(
         ));
     }
 
-    // First, check which IDs exist before deletion (to identify non-existent IDs later)
-    let check_placeholders = transaction_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
-    let check_query_str = format!("SELECT id FROM transactions WHERE id IN ({})", check_placeholders);
-
-    let mut check_query = sqlx::query_as::<_, (i64,)>(&check_query_str);
-    for id in &transaction_ids {
-        check_query = check_query.bind(id);
-    }
-
-    let existing_ids_before: std::collections::HashSet<i64> = check_query
-        .fetch_all(db)
-        .await
-        .map_err(|e| TransactionError::Database(e.to_string()))?
-        .into_iter()
-        .map(|(id,)| id)
-        .collect();
+    let locks = if override_lock {
+        PeriodLocks::default()
+    } else {
+        PeriodLocks::load(db)
+            .await
+            .map_err(|e| TransactionError::Database(e.to_string()))?
+    };
+
+    // One DELETE per chunk, all in one database transaction so a failure
+    // part-way through leaves every row in place
+    let mut tx = db.begin().await.map_err(|e| TransactionError::Database(e.to_string()))?;
+    let mut existing_ids_before = std::collections::HashSet::new();
+    let mut deleted_count = 0;
+
+    for chunk in transaction_ids.chunks(BULK_OPERATION_CHUNK_SIZE) {
+        // Check which IDs exist before deletion (to identify non-existent IDs later)
+        let existing = existing_transactions(&mut tx, chunk).await?;
+        if let Some(month) = locks.first_locked(existing.iter().map(|t| t.date.as_str())) {
+            return Err(TransactionError::PeriodLocked(month));
+        }
+        existing_ids_before.extend(existing.iter().map(|t| t.id));
 
-    // Build batched DELETE query with IN clause for performance
-    // This executes 1 query instead of N queries (potentially 1000x faster)
-    let placeholders = transaction_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
-    let query_str = format!("DELETE FROM transactions WHERE id IN ({})", placeholders);
+        let query_str = format!("DELETE FROM transactions WHERE id IN ({})", placeholders(chunk.len()));
+        let mut query = sqlx::query(&query_str).persistent(cache_in_list(chunk.len()));
+        for id in chunk {
+            query = query.bind(id);
+        }
 
-    let mut query = sqlx::query(&query_str);
-    for id in &transaction_ids {
-        query = query.bind(id);
+        let result = query
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| TransactionError::Database(e.to_string()))?;
+        deleted_count += result.rows_affected() as i64;
+
+        for transaction in &existing {
+            AuditRecord::deleted(AuditEntity::Transaction, transaction.id)
+                .old_values(transaction)
+                .record(&mut *tx)
+                .await;
+        }
     }
 
-    let result = query
-        .execute(db)
-        .await
-        .map_err(|e| TransactionError::Database(e.to_string()))?;
-
-    let deleted_count = result.rows_affected() as i64;
+    tx.commit().await.map_err(|e| TransactionError::Database(e.to_string()))?;
 
     // Determine which IDs failed
     // Failed IDs are those that either didn't exist or couldn't be deleted
@@ -514,12 +1458,17 @@ pub async fn bulk_delete_transactions_impl(
 
 #[tauri::command]
 pub async fn bulk_delete_transactions(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     transaction_ids: Vec<i64>,
+    override_lock: Option<bool>,
 ) -> Result<BulkDeleteResult, String> {
-    bulk_delete_transactions_impl(&db_pool.0, transaction_ids)
+    let ids = transaction_ids.clone();
+    let result = bulk_delete_transactions_impl(&db_pool.pool(), transaction_ids, override_lock.unwrap_or(false))
         .await
-        .map_err(|e| e.to_user_message())
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Deleted, ids);
+    Ok(result)
 }
 
 // Bulk update category implementation
@@ -530,10 +1479,13 @@ pub struct BulkUpdateResult {
     pub failed_ids: Vec<i64>,
 }
 
+/// Nothing is recategorized when any of the transactions is dated in a locked month, unless
+/// `override_lock` is set
 pub async fn bulk_update_category_impl(
     db: &SqlitePool,
     transaction_ids: Vec<i64>,
     category_id: i64,
+    override_lock: bool,
 ) -> Result<BulkUpdateResult, TransactionError> {
     // Validate input
     if transaction_ids.is_empty() {
@@ -556,46 +1508,321 @@ pub async fn bulk_update_category_impl(
         return Err(TransactionError::CategoryNotFound(category_id));
     }
 
-    // First, check which IDs exist before update (to identify non-existent IDs)
-    let check_placeholders = transaction_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
-    let check_query_str = format!("SELECT id FROM transactions WHERE id IN ({})", check_placeholders);
+    let locks = locks_for(db, override_lock).await?;
+
+    // One UPDATE per chunk, all in one database transaction so the rows are
+    // recategorized together or not at all
+    let mut tx = db.begin().await.map_err(|e| TransactionError::Database(e.to_string()))?;
+    let mut existing_ids_before = std::collections::HashSet::new();
+    let mut updated_count = 0;
+
+    for chunk in transaction_ids.chunks(BULK_OPERATION_CHUNK_SIZE) {
+        // Check which IDs exist before update (to identify non-existent IDs)
+        let existing = existing_transactions(&mut tx, chunk).await?;
+        if let Some(month) = locks.first_locked(existing.iter().map(|t| t.date.as_str())) {
+            return Err(TransactionError::PeriodLocked(month));
+        }
+        existing_ids_before.extend(existing.iter().map(|t| t.id));
+
+        let query_str = format!(
+            "UPDATE transactions SET category_id = ?, updated_at = CURRENT_TIMESTAMP WHERE id IN ({})",
+            placeholders(chunk.len())
+        );
+        let mut query = sqlx::query(&query_str).persistent(cache_in_list(chunk.len()));
+        query = query.bind(category_id);
+        for id in chunk {
+            query = query.bind(id);
+        }
 
-    let mut check_query = sqlx::query_as::<_, (i64,)>(&check_query_str);
-    for id in &transaction_ids {
-        check_query = check_query.bind(id);
+        let result = query
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| TransactionError::Database(e.to_string()))?;
+        updated_count += result.rows_affected() as i64;
+
+        for transaction in &existing {
+            AuditRecord::updated(AuditEntity::Transaction, transaction.id)
+                .old_values(&json!({ "category_id": transaction.category_id }))
+                .new_values(&json!({ "category_id": category_id }))
+                .record(&mut *tx)
+                .await;
+        }
     }
 
-    let existing_ids_before: std::collections::HashSet<i64> = check_query
-        .fetch_all(db)
+    tx.commit().await.map_err(|e| TransactionError::Database(e.to_string()))?;
+
+    // Determine which IDs failed
+    // Failed IDs are those that didn't exist in the database
+    let failed_ids: Vec<i64> = transaction_ids
+        .iter()
+        .filter(|id| !existing_ids_before.contains(id))
+        .copied()
+        .collect();
+
+    Ok(BulkUpdateResult {
+        success: true,
+        updated_count,
+        failed_ids,
+    })
+}
+
+#[tauri::command]
+pub async fn bulk_update_category(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    transaction_ids: Vec<i64>,
+    category_id: i64,
+    override_lock: Option<bool>,
+) -> Result<BulkUpdateResult, String> {
+    let ids = transaction_ids.clone();
+    let result = bulk_update_category_impl(&db_pool.pool(), transaction_ids, category_id, override_lock.unwrap_or(false))
         .await
-        .map_err(|e| TransactionError::Database(e.to_string()))?
-        .into_iter()
-        .map(|(id,)| id)
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Updated, ids);
+    Ok(result)
+}
+
+/// Attribute transactions to a household member, or clear their member when `member_id` is None
+/// Nothing changes when any of them is in a locked month, unless `override_lock` is set
+pub async fn bulk_assign_member_impl(
+    db: &SqlitePool,
+    transaction_ids: Vec<i64>,
+    member_id: Option<i64>,
+    override_lock: bool,
+) -> Result<BulkUpdateResult, TransactionError> {
+    if transaction_ids.is_empty() {
+        return Err(TransactionError::ValidationError("Transaction IDs cannot be empty".to_string()));
+    }
+    if transaction_ids.len() > MAX_BULK_OPERATION_IDS {
+        return Err(TransactionError::ValidationError(
+            format!("Cannot update more than {} transactions at once", MAX_BULK_OPERATION_IDS)
+        ));
+    }
+
+    if let Some(member_id) = member_id {
+        let member_exists = sqlx::query("SELECT id FROM members WHERE id = ?")
+            .bind(member_id)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| TransactionError::Database(e.to_string()))?;
+        if member_exists.is_none() {
+            return Err(TransactionError::MemberNotFound(member_id));
+        }
+    }
+
+    let locks = locks_for(db, override_lock).await?;
+    let mut tx = db.begin().await.map_err(|e| TransactionError::Database(e.to_string()))?;
+    let mut existing_ids_before = std::collections::HashSet::new();
+    let mut updated_count = 0;
+
+    for chunk in transaction_ids.chunks(BULK_OPERATION_CHUNK_SIZE) {
+        let existing = existing_transactions(&mut tx, chunk).await?;
+        if let Some(month) = locks.first_locked(existing.iter().map(|t| t.date.as_str())) {
+            return Err(TransactionError::PeriodLocked(month));
+        }
+        existing_ids_before.extend(existing.iter().map(|t| t.id));
+
+        let query_str = format!(
+            "UPDATE transactions SET member_id = ?, updated_at = CURRENT_TIMESTAMP WHERE id IN ({})",
+            placeholders(chunk.len())
+        );
+        let mut query = sqlx::query(&query_str).persistent(cache_in_list(chunk.len()));
+        query = query.bind(member_id);
+        for id in chunk {
+            query = query.bind(id);
+        }
+
+        let result = query
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| TransactionError::Database(e.to_string()))?;
+        updated_count += result.rows_affected() as i64;
+
+        for transaction in existing.iter().filter(|t| t.member_id != member_id) {
+            AuditRecord::updated(AuditEntity::Transaction, transaction.id)
+                .old_values(&json!({ "member_id": transaction.member_id }))
+                .new_values(&json!({ "member_id": member_id }))
+                .record(&mut *tx)
+                .await;
+        }
+    }
+
+    tx.commit().await.map_err(|e| TransactionError::Database(e.to_string()))?;
+
+    let failed_ids: Vec<i64> = transaction_ids
+        .iter()
+        .filter(|id| !existing_ids_before.contains(id))
+        .copied()
         .collect();
 
-    // Build batched UPDATE query with IN clause for performance
-    // This executes 1 query instead of N queries (potentially 1000x faster)
-    let placeholders = transaction_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
-    let query_str = format!(
-        "UPDATE transactions SET category_id = ?, updated_at = CURRENT_TIMESTAMP WHERE id IN ({})",
-        placeholders
-    );
+    Ok(BulkUpdateResult {
+        success: true,
+        updated_count,
+        failed_ids,
+    })
+}
 
-    let mut query = sqlx::query(&query_str);
-    query = query.bind(category_id);
-    for id in &transaction_ids {
-        query = query.bind(id);
+#[tauri::command]
+pub async fn bulk_assign_member(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    transaction_ids: Vec<i64>,
+    member_id: Option<i64>,
+    override_lock: Option<bool>,
+) -> Result<BulkUpdateResult, String> {
+    let ids = transaction_ids.clone();
+    let result = bulk_assign_member_impl(&db_pool.pool(), transaction_ids, member_id, override_lock.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Updated, ids);
+    Ok(result)
+}
+
+/// Override whether transactions count as tax-deductible; None goes back to following
+/// their category's flag. Nothing changes when any of them is in a locked month, unless
+/// `override_lock` is set
+pub async fn bulk_set_tax_deductible_impl(
+    db: &SqlitePool,
+    transaction_ids: Vec<i64>,
+    tax_deductible: Option<bool>,
+    override_lock: bool,
+) -> Result<BulkUpdateResult, TransactionError> {
+    if transaction_ids.is_empty() {
+        return Err(TransactionError::ValidationError("Transaction IDs cannot be empty".to_string()));
+    }
+    if transaction_ids.len() > MAX_BULK_OPERATION_IDS {
+        return Err(TransactionError::ValidationError(
+            format!("Cannot update more than {} transactions at once", MAX_BULK_OPERATION_IDS)
+        ));
     }
 
-    let result = query
-        .execute(db)
+    let locks = locks_for(db, override_lock).await?;
+    let mut tx = db.begin().await.map_err(|e| TransactionError::Database(e.to_string()))?;
+    let mut existing_ids_before = std::collections::HashSet::new();
+    let mut updated_count = 0;
+
+    for chunk in transaction_ids.chunks(BULK_OPERATION_CHUNK_SIZE) {
+        let existing = existing_transactions(&mut tx, chunk).await?;
+        if let Some(month) = locks.first_locked(existing.iter().map(|t| t.date.as_str())) {
+            return Err(TransactionError::PeriodLocked(month));
+        }
+        existing_ids_before.extend(existing.iter().map(|t| t.id));
+
+        let query_str = format!(
+            "UPDATE transactions SET tax_deductible = ?, updated_at = CURRENT_TIMESTAMP WHERE id IN ({})",
+            placeholders(chunk.len())
+        );
+        let mut query = sqlx::query(&query_str).persistent(cache_in_list(chunk.len()));
+        query = query.bind(tax_deductible);
+        for id in chunk {
+            query = query.bind(id);
+        }
+
+        let result = query
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| TransactionError::Database(e.to_string()))?;
+        updated_count += result.rows_affected() as i64;
+
+        for transaction in existing.iter().filter(|t| t.tax_deductible != tax_deductible) {
+            AuditRecord::updated(AuditEntity::Transaction, transaction.id)
+                .old_values(&json!({ "tax_deductible": transaction.tax_deductible }))
+                .new_values(&json!({ "tax_deductible": tax_deductible }))
+                .record(&mut *tx)
+                .await;
+        }
+    }
+
+    tx.commit().await.map_err(|e| TransactionError::Database(e.to_string()))?;
+
+    let failed_ids: Vec<i64> = transaction_ids
+        .iter()
+        .filter(|id| !existing_ids_before.contains(id))
+        .copied()
+        .collect();
+
+    Ok(BulkUpdateResult {
+        success: true,
+        updated_count,
+        failed_ids,
+    })
+}
+
+#[tauri::command]
+pub async fn bulk_set_tax_deductible(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+    transaction_ids: Vec<i64>,
+    tax_deductible: Option<bool>,
+    override_lock: Option<bool>,
+) -> Result<BulkUpdateResult, String> {
+    let ids = transaction_ids.clone();
+    let result =
+        bulk_set_tax_deductible_impl(&db_pool.pool(), transaction_ids, tax_deductible, override_lock.unwrap_or(false))
         .await
-        .map_err(|e| TransactionError::Database(e.to_string()))?;
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Updated, ids);
+    Ok(result)
+}
+
+/// Mark pending transactions as posted, e.g. when the bank cleared one that the next import
+/// didn't match. Transactions already posted are left alone, and nothing changes when a
+/// pending one is in a locked month, unless `override_lock` is set
+pub async fn mark_posted_impl(
+    db: &SqlitePool,
+    transaction_ids: Vec<i64>,
+    override_lock: bool,
+) -> Result<BulkUpdateResult, TransactionError> {
+    if transaction_ids.is_empty() {
+        return Err(TransactionError::ValidationError("Transaction IDs cannot be empty".to_string()));
+    }
+    if transaction_ids.len() > MAX_BULK_OPERATION_IDS {
+        return Err(TransactionError::ValidationError(
+            format!("Cannot update more than {} transactions at once", MAX_BULK_OPERATION_IDS)
+        ));
+    }
 
-    let updated_count = result.rows_affected() as i64;
+    let posted = TransactionStatus::Posted.to_string();
+    let locks = locks_for(db, override_lock).await?;
+    let mut tx = db.begin().await.map_err(|e| TransactionError::Database(e.to_string()))?;
+    let mut existing_ids_before = std::collections::HashSet::new();
+    let mut updated_count = 0;
+
+    for chunk in transaction_ids.chunks(BULK_OPERATION_CHUNK_SIZE) {
+        let existing = existing_transactions(&mut tx, chunk).await?;
+        let pending = existing.iter().filter(|t| t.status != posted);
+        if let Some(month) = locks.first_locked(pending.map(|t| t.date.as_str())) {
+            return Err(TransactionError::PeriodLocked(month));
+        }
+        existing_ids_before.extend(existing.iter().map(|t| t.id));
+
+        let query_str = format!(
+            "UPDATE transactions SET status = ?, updated_at = CURRENT_TIMESTAMP WHERE status != ? AND id IN ({})",
+            placeholders(chunk.len())
+        );
+        let mut query = sqlx::query(&query_str).persistent(cache_in_list(chunk.len()));
+        query = query.bind(&posted).bind(&posted);
+        for id in chunk {
+            query = query.bind(id);
+        }
+
+        let result = query
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| TransactionError::Database(e.to_string()))?;
+        updated_count += result.rows_affected() as i64;
+
+        for transaction in existing.iter().filter(|t| t.status != posted) {
+            AuditRecord::updated(AuditEntity::Transaction, transaction.id)
+                .old_values(&json!({ "status": transaction.status }))
+                .new_values(&json!({ "status": posted }))
+                .record(&mut *tx)
+                .await;
+        }
+    }
+
+    tx.commit().await.map_err(|e| TransactionError::Database(e.to_string()))?;
 
-    // Determine which IDs failed
-    // Failed IDs are those that didn't exist in the database
     let failed_ids: Vec<i64> = transaction_ids
         .iter()
         .filter(|id| !existing_ids_before.contains(id))
@@ -610,12 +1837,16 @@ pub async fn bulk_update_category_impl(
 }
 
 #[tauri::command]
-pub async fn bulk_update_category(
+pub async fn mark_posted(
+    app: tauri::AppHandle,
     db_pool: tauri::State<'_, DbPool>,
     transaction_ids: Vec<i64>,
-    category_id: i64,
+    override_lock: Option<bool>,
 ) -> Result<BulkUpdateResult, String> {
-    bulk_update_category_impl(&db_pool.0, transaction_ids, category_id)
+    let ids = transaction_ids.clone();
+    let result = mark_posted_impl(&db_pool.pool(), transaction_ids, override_lock.unwrap_or(false))
         .await
-        .map_err(|e| e.to_user_message())
+        .map_err(|e| e.to_user_message())?;
+    notify_changed(&app, DataKind::Transactions, ChangeAction::Updated, ids);
+    Ok(result)
 }
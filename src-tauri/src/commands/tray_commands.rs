@@ -0,0 +1,24 @@
+use crate::services::tray_summary::{TraySummary, TraySummaryBuilder};
+use crate::utils::dates;
+use crate::DbPool;
+use sqlx::SqlitePool;
+
+// Business logic functions (used by both commands and tests)
+
+/// Safe-to-spend and next-bill summary for the tray/menu bar, as of `today`
+pub async fn get_tray_summary_impl(db: &SqlitePool, today: Option<&str>) -> Result<TraySummary, String> {
+    let today = match today {
+        Some(date) => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date: {}", date))?,
+        None => dates::today(db).await,
+    };
+
+    TraySummaryBuilder::build(db, today).await
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+#[tauri::command]
+pub async fn get_tray_summary(db_pool: tauri::State<'_, DbPool>) -> Result<TraySummary, String> {
+    get_tray_summary_impl(&db_pool.pool(), None).await
+}
@@ -0,0 +1,281 @@
+// Automatic imports from the watch folder
+//
+// A CSV dropped into the folder is recognized by its headers, imported with the saved mapping
+// and account it was last imported with, then moved into the archive subfolder. Files that
+// can't be imported stay where they are and raise a notification saying why
+
+use crate::commands::csv_commands::{
+    detect_mapping_for_csv_impl, import_checked, notify_import_changes, read_csv_file_impl, ImportResult,
+    IMPORT_PROGRESS_EVENT,
+};
+use crate::commands::notification_commands::create_notification_impl;
+use crate::commands::settings_commands::get_setting_impl;
+use crate::models::notification::{NewNotification, NotificationSeverity};
+use crate::models::setting::SettingKey;
+use crate::services::folder_watcher::{FolderWatcher, WatchedFormat};
+use crate::services::transaction_importer::ImportProgress;
+use crate::utils::access_guard::AccessGuard;
+use crate::utils::change_events::{notify_changed, ChangeAction, DataKind};
+use crate::DbPool;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use tauri::{Emitter, Manager};
+
+/// Event emitted with a WatchedImport for each file the watch folder picks up
+pub const WATCH_FOLDER_EVENT: &str = "watch-folder-import";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchedImportStatus {
+    Imported,
+    Skipped, // Not recognized; the file is left in the folder
+    Failed,
+}
+
+/// What happened to one file from the watch folder
+#[derive(Debug, Serialize)]
+pub struct WatchedImport {
+    pub file: String,
+    pub format: Option<WatchedFormat>,
+    pub status: WatchedImportStatus,
+    pub mapping_name: Option<String>,
+    pub account_id: Option<i64>,
+    pub result: Option<ImportResult>,
+    pub archived_to: Option<String>, // None when the file wasn't imported or couldn't be moved
+    pub message: String,
+}
+
+impl WatchedImport {
+    fn not_imported(path: &Path, format: Option<WatchedFormat>, status: WatchedImportStatus, message: String) -> Self {
+        WatchedImport {
+            file: path.display().to_string(),
+            format,
+            status,
+            mapping_name: None,
+            account_id: None,
+            result: None,
+            archived_to: None,
+            message,
+        }
+    }
+}
+
+// Business logic functions (used by both commands and tests)
+
+/// Import one file from the watch folder `dir` and record a notification with the outcome
+/// Never fails: a file that can't be imported is reported in the returned WatchedImport
+pub async fn import_watched_file_impl<F>(db: &SqlitePool, dir: &Path, path: &Path, on_progress: F) -> WatchedImport
+where
+    F: FnMut(&ImportProgress) + Send,
+{
+    let watched = import_watched_file(db, dir, path, on_progress).await;
+
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_else(|| watched.file.clone());
+    let (severity, title) = match watched.status {
+        WatchedImportStatus::Imported => (NotificationSeverity::Info, format!("Imported {}", name)),
+        WatchedImportStatus::Skipped | WatchedImportStatus::Failed => {
+            (NotificationSeverity::Warning, format!("Couldn't import {}", name))
+        }
+    };
+    let notification = NewNotification {
+        notification_type: "watch_folder_import".to_string(),
+        severity,
+        title,
+        message: watched.message.clone(),
+        entity_type: watched.account_id.map(|_| "account".to_string()),
+        entity_id: watched.account_id,
+    };
+    // The import itself is done, so a failed notification is only logged
+    if let Err(e) = create_notification_impl(db, notification).await {
+        tracing::warn!(error = %e, "Failed to record watch folder notification");
+    }
+    watched
+}
+
+async fn import_watched_file<F>(db: &SqlitePool, dir: &Path, path: &Path, mut on_progress: F) -> WatchedImport
+where
+    F: FnMut(&ImportProgress) + Send,
+{
+    let format = WatchedFormat::of(path);
+    match format {
+        Some(WatchedFormat::Csv) => {}
+        Some(WatchedFormat::Ofx) => {
+            return WatchedImport::not_imported(
+                path,
+                format,
+                WatchedImportStatus::Skipped,
+                "OFX files can't be imported yet. Download the statement as CSV instead".to_string(),
+            );
+        }
+        None => {
+            return WatchedImport::not_imported(
+                path,
+                format,
+                WatchedImportStatus::Skipped,
+                "Only CSV files are imported from the watch folder".to_string(),
+            );
+        }
+    }
+
+    let failed = |message: String| WatchedImport::not_imported(path, format, WatchedImportStatus::Failed, message);
+    let csv_content = match read_csv_file_impl(&path.display().to_string(), None) {
+        Ok(content) => content,
+        Err(e) => return failed(e.to_user_message()),
+    };
+    let detected = match detect_mapping_for_csv_impl(db, &csv_content).await {
+        Ok(Some(detected)) => detected,
+        Ok(None) => {
+            return WatchedImport::not_imported(
+                path,
+                format,
+                WatchedImportStatus::Skipped,
+                "No saved mapping matches this file's headers. Import it once by hand and save the mapping".to_string(),
+            );
+        }
+        Err(e) => return failed(e.to_user_message()),
+    };
+    let Some(account_id) = detected.account_id else {
+        return WatchedImport {
+            mapping_name: Some(detected.mapping_name.clone()),
+            ..WatchedImport::not_imported(
+                path,
+                format,
+                WatchedImportStatus::Skipped,
+                format!("The saved mapping \"{}\" isn't linked to an account", detected.mapping_name),
+            )
+        };
+    };
+
+    let result = import_checked(db, account_id, csv_content, detected.mapping, false, |progress| {
+        on_progress(progress);
+        ControlFlow::Continue(())
+    })
+    .await;
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            return WatchedImport {
+                mapping_name: Some(detected.mapping_name),
+                account_id: Some(account_id),
+                ..failed(e.to_user_message())
+            };
+        }
+    };
+
+    // Duplicate detection keeps a file that stays behind from being imported twice
+    let archived_to = FolderWatcher::archive(dir, path)
+        .map(|target| target.display().to_string())
+        .inspect_err(|e| tracing::warn!(error = %e, "Failed to archive watched file"))
+        .ok();
+    let message = match (&detected.account_name, &archived_to) {
+        (Some(account), Some(_)) => format!("{} into {}", result.message, account),
+        (Some(account), None) => format!("{} into {}. The file couldn't be moved to the archive folder", result.message, account),
+        (None, _) => result.message.clone(),
+    };
+    WatchedImport {
+        file: path.display().to_string(),
+        format,
+        status: WatchedImportStatus::Imported,
+        mapping_name: Some(detected.mapping_name),
+        account_id: Some(account_id),
+        result: Some(result),
+        archived_to,
+        message,
+    }
+}
+
+/// Import every statement file sitting in the watch folder, in name order
+/// Returns an empty list when no watch folder is set
+pub async fn scan_watch_folder_impl<F>(db: &SqlitePool, mut on_import: F) -> Result<Vec<WatchedImport>, String>
+where
+    F: FnMut(&WatchedImport) + Send,
+{
+    let Some(dir) = get_setting_impl(db, SettingKey::WatchDir).await? else {
+        return Ok(Vec::new());
+    };
+    let dir = PathBuf::from(dir);
+
+    let mut imports = Vec::new();
+    for path in FolderWatcher::pending_files(&dir)? {
+        let watched = import_watched_file_impl(db, &dir, &path, |_| {}).await;
+        on_import(&watched);
+        imports.push(watched);
+    }
+    Ok(imports)
+}
+
+/// Tell the frontend about a watched file: the import event, the data it added, and its notification
+fn emit_watched_import(app: &tauri::AppHandle, watched: &WatchedImport) {
+    if let Err(e) = app.emit(WATCH_FOLDER_EVENT, watched) {
+        tracing::warn!(error = %e, "Failed to emit watch folder import");
+    }
+    if let Some(result) = &watched.result {
+        notify_import_changes(app, result.import_batch_id);
+    }
+    notify_changed(app, DataKind::Notifications, ChangeAction::Created, vec![]);
+}
+
+/// Held while importing watched files, so the startup scan and the watcher don't import the same file
+static WATCH_IMPORT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Import files the watcher handed over, unless the app is read-only or locked
+fn import_watched_files(app: &tauri::AppHandle, dir: &Path, files: Vec<PathBuf>) {
+    if let Err(e) = app.state::<AccessGuard>().check("scan_watch_folder") {
+        tracing::info!(reason = %e.to_user_message(), count = files.len(), "Leaving watched files until imports are allowed");
+        return;
+    }
+    let _importing = WATCH_IMPORT_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let db = app.state::<DbPool>().pool();
+    tauri::async_runtime::block_on(async {
+        // Skip files archived by an import that finished while this one waited for the lock
+        for path in files.into_iter().filter(|path| path.is_file()) {
+            let watched = import_watched_file_impl(&db, dir, &path, |progress| {
+                if let Err(e) = app.emit(IMPORT_PROGRESS_EVENT, progress.clone()) {
+                    tracing::warn!(error = %e, "Failed to emit import progress");
+                }
+            })
+            .await;
+            emit_watched_import(app, &watched);
+        }
+    });
+}
+
+/// Watch the folder in the watch_dir setting, or stop watching when it's cleared
+/// Called at startup, when the setting changes, and when another profile is opened
+pub async fn restart_folder_watcher(app: &tauri::AppHandle) -> Result<(), String> {
+    let watcher = app.state::<FolderWatcher>();
+    let db = app.state::<DbPool>().pool();
+    let Some(dir) = get_setting_impl(&db, SettingKey::WatchDir).await? else {
+        watcher.stop();
+        return Ok(());
+    };
+    let dir = PathBuf::from(dir);
+
+    let handle = app.clone();
+    let watched_dir = dir.clone();
+    watcher.start(&dir, move |files| import_watched_files(&handle, &watched_dir, files))?;
+    tracing::info!(dir = %dir.display(), "Watching folder for statements");
+
+    // Pick up anything that arrived while nothing was watching
+    let handle = app.clone();
+    std::thread::spawn(move || match FolderWatcher::pending_files(&dir) {
+        Ok(files) if !files.is_empty() => import_watched_files(&handle, &dir, files),
+        Ok(_) => {}
+        Err(e) => tracing::warn!(error = %e, "Failed to scan watch folder"),
+    });
+    Ok(())
+}
+
+// Tauri command handlers (extract pool from managed state)
+
+/// Import whatever is in the watch folder now, without waiting for the watcher
+#[tauri::command]
+pub async fn scan_watch_folder(
+    app: tauri::AppHandle,
+    db_pool: tauri::State<'_, DbPool>,
+) -> Result<Vec<WatchedImport>, String> {
+    scan_watch_folder_impl(&db_pool.pool(), |watched| emit_watched_import(&app, watched)).await
+}
@@ -17,6 +17,37 @@ pub const MAX_CSV_FILE_SIZE: usize = 10 * BYTES_PER_MB;
 /// Maximum number of rows allowed in a CSV import
 pub const MAX_CSV_ROWS: usize = 10_000;
 
+/// Maximum number of files in one batch import
+pub const MAX_BATCH_IMPORT_FILES: usize = 20;
+
+/// Rows inserted per database transaction during CSV import
+pub const IMPORT_BATCH_SIZE: usize = 500;
+
+/// Days a pending transaction can take to post: an imported posted row takes the place of a
+/// pending one with the same amount dated up to this many days before it
+pub const PENDING_MATCH_DAYS: i64 = 10;
+
+/// Transactions given a new hash per database transaction when migrating to the current hash version
+pub const REHASH_BATCH_SIZE: i64 = 500;
+
+/// Parsed rows shown by an import preview when no limit is given
+pub const DEFAULT_IMPORT_PREVIEW_ROWS: usize = 20;
+
+/// Maximum parsed rows an import preview returns
+pub const MAX_IMPORT_PREVIEW_ROWS: usize = 200;
+
+/// Rows inspected when suggesting a column mapping
+pub const COLUMN_DETECTION_SAMPLE_ROWS: usize = 50;
+
+/// Maximum length of a computed column mapping expression
+pub const MAX_MAPPING_EXPRESSION_LENGTH: usize = 500;
+
+/// Maximum nesting of function calls in a mapping expression
+pub const MAX_MAPPING_EXPRESSION_DEPTH: usize = 16;
+
+/// Longest window a per-account duplicate setting can compare dates across
+pub const MAX_DUPLICATE_LOOKBACK_DAYS: i64 = 366;
+
 /// Minimum interval between CSV imports in milliseconds (2 seconds)
 pub const MIN_CSV_IMPORT_INTERVAL_MS: u64 = 2000;
 
@@ -28,8 +59,8 @@ pub const MIN_INTEREST_RATE: f64 = 0.0;
 /// Maximum valid interest rate percentage
 pub const MAX_INTEREST_RATE: f64 = 100.0;
 
-/// Maximum reasonable transaction amount (1 billion)
-pub const MAX_TRANSACTION_AMOUNT: f64 = 1_000_000_000.0;
+/// Maximum reasonable transaction amount in cents (1 billion dollars)
+pub const MAX_TRANSACTION_CENTS: i64 = 100_000_000_000;
 
 /// Maximum description length
 pub const MAX_DESCRIPTION_LENGTH: usize = 500;
@@ -37,12 +68,45 @@ pub const MAX_DESCRIPTION_LENGTH: usize = 500;
 /// Maximum merchant name length
 pub const MAX_MERCHANT_LENGTH: usize = 200;
 
+/// Maximum transaction notes length
+pub const MAX_NOTES_LENGTH: usize = 1000;
+
+/// Maximum spending freeze name length (kept short enough for notification titles)
+pub const MAX_FREEZE_NAME_LENGTH: usize = 100;
+
+/// Maximum category name length
+pub const MAX_CATEGORY_NAME_LENGTH: usize = 100;
+
+/// Maximum category icon length in characters (room for multi-codepoint emoji)
+pub const MAX_CATEGORY_ICON_LENGTH: usize = 16;
+
 /// Maximum search query length
 pub const MAX_SEARCH_QUERY_LENGTH: usize = 100;
 
+/// Maximum number of account or category ids in one transaction filter
+pub const MAX_FILTER_IDS: usize = 100;
+
 /// Maximum number of IDs allowed in bulk operations
 pub const MAX_BULK_OPERATION_IDS: usize = 1000;
 
+/// IDs per statement in bulk operations (keeps `IN` lists well under SQLite's variable limit)
+pub const BULK_OPERATION_CHUNK_SIZE: usize = 500;
+
+/// Maximum length for category rule patterns
+pub const MAX_RULE_PATTERN_LENGTH: usize = 200;
+
+/// Maximum compiled size (bytes) for regex category rules
+pub const MAX_RULE_REGEX_SIZE: usize = BYTES_PER_MB;
+
+/// Days without a match after which a category rule is reported as stale
+pub const DEFAULT_STALE_RULE_DAYS: i64 = 180;
+
+/// Example transactions returned per conflicting pair of category rules
+pub const DEFAULT_RULE_CONFLICT_EXAMPLES: i64 = 5;
+
+/// Maximum size for imported category template documents (1MB)
+pub const MAX_CATEGORY_TEMPLATE_SIZE: usize = BYTES_PER_MB;
+
 // ===== Pagination Defaults =====
 
 /// Default number of items per page
@@ -54,6 +118,12 @@ pub const MAX_PAGE_SIZE: i64 = 100;
 /// Default offset for pagination
 pub const DEFAULT_OFFSET: i64 = 0;
 
+/// Default number of items returned by top-N analytics queries
+pub const DEFAULT_TOP_ITEMS_LIMIT: i64 = 10;
+
+/// Default number of ranked category suggestions returned for a transaction
+pub const DEFAULT_CATEGORY_SUGGESTIONS: i64 = 3;
+
 // ===== Database Connection Pool =====
 
 /// Maximum number of concurrent database connections
@@ -64,12 +134,27 @@ pub const MAX_DB_CONNECTIONS: u32 = 5;
 /// Number of months in a year (for interest rate calculations)
 pub const MONTHS_PER_YEAR: f64 = 12.0;
 
+/// Days in a year for daily interest accrual between payments
+pub const DAYS_PER_YEAR: f64 = 365.0;
+
 /// Divisor to convert percentage to decimal (e.g., 18% -> 0.18)
 pub const PERCENT_TO_DECIMAL_DIVISOR: f64 = 100.0;
 
 /// Maximum years allowed for debt payoff calculations
 pub const MAX_PAYOFF_YEARS: i32 = 100;
 
+/// Most monthly amounts one payoff simulation can compare
+pub const MAX_PAYOFF_SCENARIOS: usize = 20;
+
+/// Extra monthly amounts in cents the marginal payment impact compares when none are given
+pub const DEFAULT_PAYMENT_INCREMENTS: [i64; 3] = [2500, 5000, 10_000];
+
+/// Days a cash-flow projection covers when no horizon is given
+pub const DEFAULT_PROJECTION_DAYS: i64 = 90;
+
+/// Longest cash-flow projection horizon in days
+pub const MAX_PROJECTION_DAYS: i64 = 730;
+
 // ===== Spending Tracker Thresholds =====
 
 /// Percentage threshold for "under budget" status
@@ -77,3 +162,256 @@ pub const SPENDING_UNDER_THRESHOLD_PERCENT: f64 = 80.0;
 
 /// Percentage threshold for "on track" status (at or below target)
 pub const SPENDING_ON_TRACK_THRESHOLD_PERCENT: f64 = 100.0;
+
+// ===== Notifications =====
+
+/// Days a read notification is kept before being pruned
+pub const NOTIFICATION_RETENTION_DAYS: i64 = 30;
+
+/// Maximum notification title length
+pub const MAX_NOTIFICATION_TITLE_LENGTH: usize = 200;
+
+// ===== Audit Log =====
+
+/// Days an audit log entry is kept before being pruned
+pub const AUDIT_LOG_RETENTION_DAYS: i64 = 365;
+
+// ===== Alerts =====
+
+/// Share of a monthly spending target used before an alert is raised
+pub const ALERT_TARGET_PERCENT: f64 = 90.0;
+
+/// Days before a debt's due date that an unpaid minimum payment raises an alert
+pub const ALERT_PAYMENT_DUE_DAYS: i64 = 5;
+
+/// Days of recent expenses checked for unusually large transactions
+pub const ALERT_LARGE_TRANSACTION_DAYS: i64 = 7;
+
+/// Days of category history a recent expense is compared against
+pub const ALERT_HISTORY_DAYS: i64 = 90;
+
+/// Expenses in a category's history needed before any of its expenses look unusual
+pub const ALERT_MIN_HISTORY_TRANSACTIONS: i64 = 5;
+
+/// An expense this many times its category's average is unusual
+pub const ALERT_LARGE_TRANSACTION_MULTIPLIER: f64 = 3.0;
+
+// ===== System Tray =====
+
+/// How often the tray summary (safe-to-spend, next bill) is refreshed
+pub const TRAY_REFRESH_INTERVAL_SECS: u64 = 300;
+
+// ===== Streaks =====
+
+/// Completed months checked for under-budget and on-time payment streaks
+pub const STREAK_LOOKBACK_MONTHS: u32 = 24;
+
+/// Days checked for the no-spend streak, ending today
+pub const NO_SPEND_LOOKBACK_DAYS: i64 = 365;
+
+// ===== Currencies =====
+
+/// Length of an ISO 4217 currency code
+pub const CURRENCY_CODE_LENGTH: usize = 3;
+
+// ===== Statement Periods =====
+
+/// Latest allowed statement closing day (later days in short months close on the last day)
+pub const MAX_STATEMENT_CLOSING_DAY: u32 = 31;
+
+// ===== Debt Due Dates =====
+
+/// Day of the month a debt is due unless set otherwise
+pub const DEFAULT_DEBT_DUE_DAY: u32 = 15;
+
+/// Latest allowed due day (later days in short months fall on the last day)
+pub const MAX_DEBT_DUE_DAY: u32 = 31;
+
+/// Longest loan term, in months (40 years)
+pub const MAX_LOAN_TERM_MONTHS: u32 = 480;
+
+/// Longest look-ahead for upcoming payments, in months
+pub const MAX_UPCOMING_PAYMENT_MONTHS: u32 = 24;
+
+/// Months of payments, bills, and target periods in a calendar export unless asked otherwise
+pub const DEFAULT_CALENDAR_EXPORT_MONTHS: u32 = 6;
+
+// ===== Account Validation =====
+
+/// Rows needed before an import's amount signs are judged
+pub const MIN_ROWS_FOR_SIGN_CHECK: usize = 5;
+
+/// Share of positive amounts above which a checking import looks sign-inverted
+pub const CHECKING_MAX_POSITIVE_SHARE: f64 = 0.9;
+
+/// Share of positive amounts above which a credit card import looks sign-inverted
+pub const CREDIT_CARD_MAX_POSITIVE_SHARE: f64 = 0.5;
+
+/// Transactions in one month above which a savings import looks like another account type
+pub const SAVINGS_MAX_MONTHLY_TRANSACTIONS: usize = 30;
+
+// ===== App Access =====
+
+/// Shortest app password accepted (a 4-digit PIN is enough to keep a viewer out)
+pub const MIN_APP_PASSWORD_LENGTH: usize = 4;
+
+/// Longest app password accepted
+pub const MAX_APP_PASSWORD_LENGTH: usize = 128;
+
+/// How long to wait after a wrong app password before another one is checked (2 seconds)
+pub const APP_PASSWORD_RETRY_INTERVAL_MS: u64 = 2000;
+
+// ===== Insights =====
+
+/// Categories reported in each direction (and new merchants) by spending insights
+pub const INSIGHT_TOP_MOVERS: usize = 3;
+
+/// Smallest change against the previous period worth reporting for a category, in cents
+pub const INSIGHT_MIN_CATEGORY_CHANGE_CENTS: i64 = 1000;
+
+/// Largest expenses reported by spending insights
+pub const INSIGHT_LARGEST_TRANSACTIONS: i64 = 3;
+
+/// Weeks of category spending checked for rising streaks, ending on the period's last day
+pub const INSIGHT_STREAK_LOOKBACK_WEEKS: usize = 8;
+
+/// Week-over-week increases in a row before a rising streak is reported
+pub const INSIGHT_MIN_STREAK_WEEKS: usize = 3;
+
+// ===== Category Statistics =====
+
+/// Completed months of spending summarized for a category unless asked otherwise
+pub const DEFAULT_CATEGORY_STATS_MONTHS: u32 = 24;
+
+/// Longest window summarized for a category, in months
+pub const MAX_CATEGORY_STATS_MONTHS: u32 = 120;
+
+// ===== Financial Health =====
+
+/// Completed months of income and spending behind the financial health metrics unless asked otherwise
+pub const DEFAULT_FINANCIAL_HEALTH_MONTHS: u32 = 12;
+
+/// Longest window the financial health metrics cover, in months
+pub const MAX_FINANCIAL_HEALTH_MONTHS: u32 = 120;
+
+// ===== Amount Distribution =====
+
+/// Upper bounds of the histogram buckets transaction amounts are counted into, in cents;
+/// a last, open-ended bucket takes everything above
+pub const AMOUNT_DISTRIBUTION_BUCKETS_CENTS: [i64; 8] = [1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 500_000];
+
+// ===== Query Console =====
+
+/// Rows returned by the read-only query console unless asked otherwise
+pub const DEFAULT_QUERY_ROW_LIMIT: usize = 200;
+
+/// Most rows the query console returns, whatever LIMIT the statement has
+pub const MAX_QUERY_ROW_LIMIT: usize = 1000;
+
+/// Longest statement accepted by the query console, in characters
+pub const MAX_QUERY_LENGTH: usize = 10_000;
+
+// ===== Bank Sync =====
+
+/// Days of history fetched the first time a bank connection syncs
+pub const BANK_SYNC_INITIAL_DAYS: i64 = 90;
+
+/// Days before the last sync fetched again, so transactions that posted late aren't missed
+/// Anything fetched twice is caught by duplicate detection
+pub const BANK_SYNC_OVERLAP_DAYS: i64 = 7;
+
+/// Seconds to wait on the bank sync provider before giving up
+pub const BANK_SYNC_TIMEOUT_SECS: u64 = 30;
+
+// ===== Data Archive =====
+
+/// Largest full data archive accepted for import
+pub const MAX_DATA_ARCHIVE_SIZE: usize = 200 * BYTES_PER_MB;
+
+// ===== Analytics Cache =====
+
+/// Most analytics results kept at once; the cache is emptied when full
+pub const MAX_ANALYTICS_CACHE_ENTRIES: usize = 256;
+
+// ===== Backups =====
+
+/// Backup files are named this, then a UTC timestamp; only files named so are ever pruned
+pub const BACKUP_FILE_PREFIX: &str = "budget-balancer-backup-";
+
+/// Backups kept when no retention count is set
+pub const DEFAULT_BACKUP_RETENTION: usize = 10;
+
+/// Largest retention count accepted for the `backup_retention` setting
+pub const MAX_BACKUP_RETENTION: usize = 365;
+
+// ===== Profiles =====
+
+/// Profile created on first run; it keeps the database file from before profiles existed
+pub const DEFAULT_PROFILE_NAME: &str = "Personal";
+
+/// Database file of the default profile, in the app data folder
+pub const DEFAULT_DATABASE_FILE: &str = "budget_balancer.db";
+
+/// Registry of profiles and which one is open, in the app data folder
+pub const PROFILE_REGISTRY_FILE: &str = "profiles.json";
+
+/// Folder, inside the app data folder, holding the databases of every other profile
+pub const PROFILE_DATABASE_DIR: &str = "profiles";
+
+/// Longest profile name accepted
+pub const MAX_PROFILE_NAME_LENGTH: usize = 50;
+
+// ===== Household Members =====
+
+/// Longest household member name accepted
+pub const MAX_MEMBER_NAME_LENGTH: usize = 50;
+
+// ===== Dashboard =====
+
+/// Name of the dashboard layout the app shows
+pub const DEFAULT_DASHBOARD_LAYOUT: &str = "default";
+
+/// Top categories shown by the spending widget when it doesn't set a limit
+pub const DEFAULT_DASHBOARD_TOP_CATEGORIES: i64 = 5;
+
+/// Bills shown by the upcoming bills widget when it doesn't set a limit
+pub const DEFAULT_DASHBOARD_UPCOMING_BILLS: i64 = 10;
+
+/// How far ahead the upcoming bills widget looks when it doesn't say, in days
+pub const DEFAULT_UPCOMING_BILL_DAYS: i64 = 14;
+
+/// Largest item limit a dashboard widget can ask for
+pub const MAX_DASHBOARD_WIDGET_LIMIT: i64 = 50;
+
+/// Furthest the upcoming bills widget can look ahead, in days
+pub const MAX_UPCOMING_BILL_DAYS: i64 = 90;
+
+// ===== Receipts =====
+
+/// Largest receipt image read for OCR (10 MB)
+pub const MAX_RECEIPT_IMAGE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Image file extensions accepted as receipts, lowercase
+pub const RECEIPT_IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "tif", "tiff", "bmp"];
+
+/// Tesseract OCR executable, looked up on the PATH
+pub const TESSERACT_COMMAND: &str = "tesseract";
+
+// ===== Watch Folder =====
+
+/// Subfolder of the watch folder that imported files are moved into
+pub const WATCH_ARCHIVE_DIR: &str = "imported";
+
+/// How long a new file has to stop changing before it's imported, so half-written downloads are skipped
+pub const WATCH_SETTLE_MS: u64 = 1500;
+
+// ===== Investments =====
+
+/// Longest ticker symbol accepted for a holding or price
+pub const MAX_SECURITY_SYMBOL_LENGTH: usize = 12;
+
+/// Month-end points in the net worth history unless asked otherwise
+pub const DEFAULT_NET_WORTH_HISTORY_MONTHS: u32 = 12;
+
+/// Most month-end points the net worth history covers
+pub const MAX_NET_WORTH_HISTORY_MONTHS: u32 = 120;
@@ -43,6 +43,9 @@ pub enum DebtError {
     #[error("Minimum payment must be non-negative, got {0}")]
     InvalidMinPayment(f64),
 
+    #[error("Minimum payment percentage must be above 0 and at most 100, got {0}")]
+    InvalidMinPaymentPercent(f64),
+
     #[error("Interest rate must be between {min} and {max}, got {actual}")]
     InvalidInterestRate { min: f64, max: f64, actual: f64 },
 
@@ -70,9 +73,42 @@ pub enum DebtError {
     #[error("Debt plan not found with ID {0}")]
     PlanNotFound(i64),
 
+    #[error("Debt payment not found with ID {0}")]
+    PaymentNotFound(i64),
+
     #[error("Payment amount must be positive, got {0}")]
     InvalidPaymentAmount(f64),
 
+    #[error("Invalid payment date '{0}': expected YYYY-MM-DD")]
+    InvalidPaymentDate(String),
+
+    #[error("Account not found with ID {0}")]
+    AccountNotFound(i64),
+
+    #[error("Due day must be between 1 and {max}, got {actual}")]
+    InvalidDueDay { max: u32, actual: u32 },
+
+    #[error("Account {0} is not a credit card")]
+    NotCreditCard(i64),
+
+    #[error("Debt {0} is not linked to a credit card account")]
+    NotLinked(i64),
+
+    #[error("Loan term must be 1 to {max} months, got {actual}")]
+    InvalidLoanTerm { max: u32, actual: u32 },
+
+    #[error("Invalid loan start date '{0}': expected YYYY-MM-DD")]
+    InvalidLoanStartDate(String),
+
+    #[error("Debt {0} is not a loan")]
+    NotLoan(i64),
+
+    #[error("Upcoming payments must cover 1 to {max} months, got {actual}")]
+    InvalidMonthsAhead { max: u32, actual: u32 },
+
+    #[error("Provide 1 to {max} payoff scenarios, got {actual}")]
+    InvalidScenarioCount { max: usize, actual: usize },
+
     #[error("Database error: {0}")]
     Database(String),
 }
@@ -84,6 +120,7 @@ impl DebtError {
             // These errors are already safe to show to users
             DebtError::InvalidBalance(_) => self.to_string(),
             DebtError::InvalidMinPayment(_) => self.to_string(),
+            DebtError::InvalidMinPaymentPercent(_) => self.to_string(),
             DebtError::InvalidInterestRate { .. } => self.to_string(),
             DebtError::NotFound(_) => self.to_string(),
             DebtError::InsufficientFunds { .. } => self.to_string(),
@@ -92,7 +129,18 @@ impl DebtError {
             DebtError::InvalidStrategy(_) => self.to_string(),
             DebtError::PaymentExceedsBalance { .. } => self.to_string(),
             DebtError::PlanNotFound(_) => self.to_string(),
+            DebtError::PaymentNotFound(_) => self.to_string(),
             DebtError::InvalidPaymentAmount(_) => self.to_string(),
+            DebtError::InvalidPaymentDate(_) => self.to_string(),
+            DebtError::AccountNotFound(_) => self.to_string(),
+            DebtError::InvalidDueDay { .. } => self.to_string(),
+            DebtError::NotCreditCard(_) => self.to_string(),
+            DebtError::NotLinked(_) => self.to_string(),
+            DebtError::InvalidLoanTerm { .. } => self.to_string(),
+            DebtError::InvalidLoanStartDate(_) => self.to_string(),
+            DebtError::NotLoan(_) => self.to_string(),
+            DebtError::InvalidMonthsAhead { .. } => self.to_string(),
+            DebtError::InvalidScenarioCount { .. } => self.to_string(),
 
             // Database errors should be sanitized
             DebtError::Database(e) => {
@@ -121,9 +169,24 @@ pub enum TransactionError {
     #[error("Account not found with ID {0}")]
     AccountNotFound(i64),
 
+    #[error("Household member not found with ID {0}")]
+    MemberNotFound(i64),
+
     #[error("Failed to categorize transaction")]
     CategorizationError,
 
+    #[error("Transaction {0} already has the same date, amount and description")]
+    Duplicate(i64),
+
+    #[error("Scheduled transaction not found with ID {0}")]
+    ScheduleNotFound(i64),
+
+    #[error("Scheduled transaction {0} has no occurrences left")]
+    ScheduleFinished(i64),
+
+    #[error("{0} is locked. Unlock the month or override the lock to change its transactions")]
+    PeriodLocked(String),
+
     #[error("Validation error: {0}")]
     ValidationError(String),
 
@@ -141,7 +204,12 @@ impl TransactionError {
             TransactionError::InvalidDate(_) => self.to_string(),
             TransactionError::CategoryNotFound(_) => self.to_string(),
             TransactionError::AccountNotFound(_) => self.to_string(),
+            TransactionError::MemberNotFound(_) => self.to_string(),
             TransactionError::CategorizationError => self.to_string(),
+            TransactionError::Duplicate(_) => self.to_string(),
+            TransactionError::ScheduleNotFound(_) => self.to_string(),
+            TransactionError::ScheduleFinished(_) => self.to_string(),
+            TransactionError::PeriodLocked(_) => self.to_string(),
             TransactionError::ValidationError(_) => self.to_string(),
 
             // Database errors should be sanitized
@@ -168,9 +236,18 @@ pub enum CsvImportError {
     #[error("Invalid CSV format: {0}")]
     InvalidFormat(String),
 
+    #[error("Failed to read CSV file: {0}")]
+    FileRead(String),
+
+    #[error("{0}")]
+    InvalidEncoding(String),
+
     #[error("Missing required column: {0}")]
     MissingColumn(String),
 
+    #[error("Invalid column mapping: {0}")]
+    InvalidMapping(String),
+
     #[error("Column mapping '{0}' already exists")]
     DuplicateMapping(String),
 
@@ -183,10 +260,42 @@ pub enum CsvImportError {
     #[error("Duplicate detection error: {0}")]
     DuplicateDetectionError(String),
 
+    #[error("Import batch {0} not found")]
+    ImportBatchNotFound(i64),
+
+    #[error("Import batch {id} cannot be undone ({status})")]
+    ImportNotUndoable { id: i64, status: String },
+
+    #[error("Import cancelled; rows imported before cancelling can be removed with undo")]
+    Cancelled,
+
+    #[error("Invalid batch import: {0}")]
+    InvalidBatch(String),
+
+    #[error("Account {0} not found")]
+    AccountNotFound(i64),
+
+    #[error("Invalid duplicate settings: {0}")]
+    InvalidDuplicateSettings(String),
+
+    #[error("{0} is locked. Unlock the month or override the lock to change its transactions")]
+    PeriodLocked(String),
+
     #[error("Database error: {0}")]
     Database(String),
 }
 
+impl From<crate::services::csv_parser::CsvError> for CsvImportError {
+    fn from(error: crate::services::csv_parser::CsvError) -> Self {
+        use crate::services::csv_parser::CsvError;
+        match error {
+            CsvError::MissingColumn(column) => CsvImportError::MissingColumn(column),
+            CsvError::InvalidMapping(e) => CsvImportError::InvalidMapping(e),
+            e => CsvImportError::InvalidFormat(e.to_string()),
+        }
+    }
+}
+
 impl CsvImportError {
     /// Convert to user-friendly error message (sanitized)
     pub fn to_user_message(&self) -> String {
@@ -202,11 +311,27 @@ impl CsvImportError {
                 format!("Rate limit exceeded. Please wait {:.1} seconds before trying again.", secs)
             }
             CsvImportError::InvalidFormat(_) => "Failed to parse CSV file. Please check the file format.".to_string(),
+            CsvImportError::InvalidEncoding(e) => e.clone(),
             CsvImportError::MissingColumn(col) => format!("Missing required column: {}", col),
+            CsvImportError::InvalidMapping(e) => e.clone(),
             CsvImportError::DuplicateMapping(name) => format!("A mapping with the name '{}' already exists", name),
             CsvImportError::ParseError(_) => "Failed to parse CSV file. Please check the file format.".to_string(),
+            CsvImportError::ImportBatchNotFound(id) => format!("Import {} not found", id),
+            CsvImportError::ImportNotUndoable { id, status } => match status.as_str() {
+                "undone" => format!("Import {} has already been undone", id),
+                _ => format!("Import {} is still running and cannot be undone yet", id),
+            },
+            CsvImportError::Cancelled => self.to_string(),
+            CsvImportError::InvalidBatch(e) => e.clone(),
+            CsvImportError::AccountNotFound(_) => self.to_string(),
+            CsvImportError::InvalidDuplicateSettings(e) => e.clone(),
+            CsvImportError::PeriodLocked(_) => self.to_string(),
 
             // Internal errors should be sanitized
+            CsvImportError::FileRead(e) => {
+                tracing::error!(error = %e, "Failed to read CSV file");
+                "Failed to read the CSV file".to_string()
+            }
             CsvImportError::CategorizationError(e) => {
                 tracing::error!(error = %e, "Categorization error during CSV import");
                 "Failed to categorize transactions".to_string()
@@ -223,6 +348,496 @@ impl CsvImportError {
     }
 }
 
+/// Errors related to category and category rule operations
+#[derive(Debug, Error)]
+pub enum CategoryError {
+    #[error("Category not found with ID {0}")]
+    NotFound(i64),
+
+    #[error("Category rule not found with ID {0}")]
+    RuleNotFound(i64),
+
+    #[error("Account not found with ID {0}")]
+    AccountNotFound(i64),
+
+    #[error("Invalid rule pattern: {0}")]
+    InvalidPattern(String),
+
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl CategoryError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            // These errors are safe to show
+            CategoryError::NotFound(_) => self.to_string(),
+            CategoryError::RuleNotFound(_) => self.to_string(),
+            CategoryError::AccountNotFound(_) => self.to_string(),
+            CategoryError::InvalidPattern(_) => self.to_string(),
+            CategoryError::ValidationError(_) => self.to_string(),
+
+            // Database errors should be sanitized
+            CategoryError::Database(e) => {
+                tracing::error!(error = %e, "Database error in category operation");
+                "Failed to complete category operation".to_string()
+            }
+        }
+    }
+}
+
+/// Errors related to merchant operations
+#[derive(Debug, Error)]
+pub enum MerchantError {
+    #[error("Merchant not found with ID {0}")]
+    NotFound(i64),
+
+    #[error("A merchant named '{0}' already exists")]
+    DuplicateName(String),
+
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl MerchantError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            // These errors are safe to show
+            MerchantError::NotFound(_) => self.to_string(),
+            MerchantError::DuplicateName(_) => self.to_string(),
+            MerchantError::ValidationError(_) => self.to_string(),
+
+            // Database errors should be sanitized
+            MerchantError::Database(e) => {
+                tracing::error!(error = %e, "Database error in merchant operation");
+                "Failed to complete merchant operation".to_string()
+            }
+        }
+    }
+}
+
+/// Errors related to notification operations
+#[derive(Debug, Error)]
+pub enum NotificationError {
+    #[error("Notification not found with ID {0}")]
+    NotFound(i64),
+
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl NotificationError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            // These errors are safe to show
+            NotificationError::NotFound(_) => self.to_string(),
+            NotificationError::ValidationError(_) => self.to_string(),
+
+            // Database errors should be sanitized
+            NotificationError::Database(e) => {
+                tracing::error!(error = %e, "Database error in notification operation");
+                "Failed to complete notification operation".to_string()
+            }
+        }
+    }
+}
+
+/// Errors related to alert operations
+#[derive(Debug, Error)]
+pub enum AlertError {
+    #[error("Alert not found with ID {0}")]
+    NotFound(i64),
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl AlertError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            AlertError::NotFound(_) => self.to_string(),
+
+            // Database errors should be sanitized
+            AlertError::Database(e) => {
+                tracing::error!(error = %e, "Database error in alert operation");
+                "Failed to complete alert operation".to_string()
+            }
+        }
+    }
+}
+
+/// Errors related to the audit log
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl AuditError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            AuditError::ValidationError(_) => self.to_string(),
+
+            // Database errors should be sanitized
+            AuditError::Database(e) => {
+                tracing::error!(error = %e, "Database error in audit log operation");
+                "Failed to load audit log".to_string()
+            }
+        }
+    }
+}
+
+/// Errors related to background jobs
+#[derive(Debug, Error)]
+pub enum JobError {
+    #[error("Job not found with ID {0}")]
+    NotFound(i64),
+
+    #[error("Job {id} has already finished ({status})")]
+    AlreadyFinished { id: i64, status: String },
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl JobError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            JobError::NotFound(_) => self.to_string(),
+            JobError::AlreadyFinished { .. } => self.to_string(),
+
+            // Database errors should be sanitized
+            JobError::Database(e) => {
+                tracing::error!(error = %e, "Database error in job operation");
+                "Failed to complete job operation".to_string()
+            }
+        }
+    }
+}
+
+/// Errors from the read-only query console
+#[derive(Debug, Error)]
+pub enum QueryError {
+    #[error("Query is empty")]
+    Empty,
+
+    #[error("Query too long (max {max} characters)")]
+    TooLong { max: usize },
+
+    #[error("Only one statement can be run at a time")]
+    MultipleStatements,
+
+    #[error("Only SELECT queries can be run")]
+    NotSelect,
+
+    #[error("{0} is not allowed in a read-only query")]
+    ForbiddenKeyword(String),
+
+    #[error("Query failed: {0}")]
+    Invalid(String), // SQLite's message for the user's own statement, e.g. a syntax error
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl QueryError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            QueryError::Database(e) => {
+                tracing::error!(error = %e, "Database error in query console");
+                "Failed to run query".to_string()
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
+/// Errors from bank sync connections
+#[derive(Debug, Error)]
+pub enum BankSyncError {
+    #[error("Bank sync isn't included in this build of Budget Balancer")]
+    Unavailable,
+
+    #[error("Unknown bank sync provider: {0}")]
+    UnknownProvider(String),
+
+    #[error("Connection name cannot be empty")]
+    EmptyName,
+
+    #[error("Invalid setup token: {0}")]
+    InvalidSetupToken(String),
+
+    #[error("Bank connection not found with ID {0}")]
+    ConnectionNotFound(i64),
+
+    #[error("Bank account link not found with ID {0}")]
+    LinkNotFound(i64),
+
+    #[error("Account not found with ID {0}")]
+    AccountNotFound(i64),
+
+    #[error("The credentials for bank connection {0} are missing. Remove the connection and link it again")]
+    MissingCredential(i64),
+
+    #[error("{0}")]
+    Provider(String), // Refused by the provider with a message meant for the user, e.g. an expired grant
+
+    #[error("Network error: {0}")]
+    Network(String),
+
+    #[error("Credential storage error: {0}")]
+    Storage(String),
+
+    #[error("Import error: {0}")]
+    Import(String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl BankSyncError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            BankSyncError::Network(e) => {
+                tracing::error!(error = %e, "Network error in bank sync");
+                "Couldn't reach the bank sync service. Check your connection and try again".to_string()
+            }
+            BankSyncError::Storage(e) => {
+                tracing::error!(error = %e, "Credential storage error in bank sync");
+                "Failed to access saved bank credentials".to_string()
+            }
+            BankSyncError::Import(e) => {
+                tracing::error!(error = %e, "Import error in bank sync");
+                "Failed to import synced transactions".to_string()
+            }
+            BankSyncError::Database(e) => {
+                tracing::error!(error = %e, "Database error in bank sync");
+                "Failed to complete bank sync operation".to_string()
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
+/// Errors from exporting or importing the full data archive
+#[derive(Debug, Error)]
+pub enum DataArchiveError {
+    #[error("Archive too large (max {max_mb} MB)")]
+    TooLarge { max_mb: usize },
+
+    #[error("Invalid archive: {0}")]
+    Invalid(String),
+
+    #[error("Unsupported archive version {found} (max {max})")]
+    UnsupportedVersion { found: u32, max: u32 },
+
+    #[error("{kind} '{name}' is referenced but isn't in the archive or the database")]
+    MissingReference { kind: &'static str, name: String },
+
+    #[error("{0} is locked. Unlock the month or override the lock to change its transactions")]
+    PeriodLocked(String),
+
+    #[error("File error: {0}")]
+    Io(String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl DataArchiveError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            DataArchiveError::Io(e) => {
+                tracing::error!(error = %e, "File error in data archive");
+                "Failed to read or write the archive file".to_string()
+            }
+            DataArchiveError::Database(e) => {
+                tracing::error!(error = %e, "Database error in data archive");
+                "Failed to complete data archive operation".to_string()
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
+/// Errors from creating and switching profiles
+#[derive(Debug, Error)]
+pub enum ProfileError {
+    #[error("Profile name cannot be empty")]
+    EmptyName,
+
+    #[error("Profile name too long (max {max} characters)")]
+    NameTooLong { max: usize },
+
+    #[error("A profile named '{0}' already exists")]
+    AlreadyExists(String),
+
+    #[error("No profile named '{0}'")]
+    NotFound(String),
+
+    #[error("Wait for background jobs to finish before switching profiles")]
+    JobsRunning,
+
+    #[error("Editing is locked. Enter the app password to switch profiles")]
+    Locked,
+
+    #[error(transparent)]
+    Access(AccessError),
+
+    #[error("File error: {0}")]
+    Io(String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl ProfileError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            ProfileError::Io(e) => {
+                tracing::error!(error = %e, "File error in profile operation");
+                "Failed to read or write the profile list".to_string()
+            }
+            ProfileError::Database(e) => {
+                tracing::error!(error = %e, "Database error in profile operation");
+                "Failed to open the profile's database".to_string()
+            }
+            ProfileError::Access(e) => e.to_user_message(),
+            _ => self.to_string(),
+        }
+    }
+}
+
+/// Errors related to global search
+#[derive(Debug, Error)]
+pub enum SearchError {
+    #[error("Search query too long (max {max} characters)")]
+    QueryTooLong { max: usize },
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl SearchError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            SearchError::QueryTooLong { .. } => self.to_string(),
+
+            // Database errors should be sanitized
+            SearchError::Database(e) => {
+                tracing::error!(error = %e, "Database error in search");
+                "Failed to search".to_string()
+            }
+        }
+    }
+}
+
+/// Errors from reading a receipt image
+#[derive(Debug, Error)]
+pub enum ReceiptError {
+    #[error("Receipt image not found: {0}")]
+    NotFound(String),
+
+    #[error("Unsupported receipt image type '{0}'. Use PNG, JPEG, TIFF, or BMP")]
+    UnsupportedFormat(String),
+
+    #[error("Receipt image too large (max {max_mb} MB)")]
+    TooLarge { max_mb: u64 },
+
+    #[error("Text recognition needs Tesseract OCR. Install it and make sure it is on your PATH")]
+    OcrUnavailable,
+
+    #[error("OCR failed: {0}")]
+    OcrFailed(String),
+}
+
+impl ReceiptError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            ReceiptError::OcrFailed(e) => {
+                tracing::error!(error = %e, "Receipt OCR failed");
+                "Failed to read text from the receipt image".to_string()
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
+/// Errors returned when a command is blocked by the app's access mode
+#[derive(Debug, Error)]
+pub enum AccessError {
+    #[error("Budget Balancer is in read-only mode. Turn off read-only mode in Settings to make changes")]
+    ReadOnly,
+
+    #[error("Budget Balancer was started with --read-only. Restart without it to make changes")]
+    ReadOnlyFlag,
+
+    #[error("Budget Balancer is in viewer mode. Enter the app password to make changes")]
+    ViewerLocked,
+
+    #[error("Incorrect app password")]
+    WrongPassword,
+
+    #[error("Too many incorrect passwords. Please wait {0:.1} seconds before trying again")]
+    TooManyAttempts(f64),
+
+    #[error("Set an app password before switching to viewer mode")]
+    NoPassword,
+
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+
+    #[error("Password hashing error: {0}")]
+    Hashing(String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl AccessError {
+    /// Convert to user-friendly error message (sanitized)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            // Database errors should be sanitized
+            AccessError::Database(e) => {
+                tracing::error!(error = %e, "Database error in access operation");
+                "Failed to update app access".to_string()
+            }
+            AccessError::Hashing(e) => {
+                tracing::error!(error = %e, "Password hashing error");
+                "Failed to update app access".to_string()
+            }
+
+            // Everything else is safe to show
+            _ => self.to_string(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
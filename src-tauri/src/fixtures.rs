@@ -0,0 +1,267 @@
+//! Canonical scenario datasets built through the normal commands.
+//!
+//! Used by integration tests that need realistic, multi-account history and by
+//! features that load sample data. Everything is deterministic: the same
+//! options always produce the same accounts, debts, and transactions.
+//!
+//! # Example
+//! ```no_run
+//! use budget_balancer_lib::fixtures::{build_family_scenario, ScenarioOptions};
+//!
+//! # async fn example(db: &sqlx::SqlitePool) -> Result<(), String> {
+//! let scenario = build_family_scenario(db, &ScenarioOptions::default()).await?;
+//! println!("{} transactions", scenario.transaction_count);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::commands::account_commands::create_account_impl;
+use crate::commands::debt_commands::{create_debt_impl, record_debt_payment_impl};
+use crate::commands::transaction_commands::create_transaction_impl;
+use crate::constants::DEFAULT_CATEGORY_ID;
+use crate::errors::sanitize_db_error;
+use crate::models::account::{AccountType, NewAccount};
+use crate::models::debt::NewDebt;
+use crate::models::transaction::CreateTransaction;
+use crate::utils::dates;
+use crate::utils::money::Cents;
+use chrono::{Datelike, Months, NaiveDate};
+use sqlx::SqlitePool;
+
+/// Longest history a scenario can cover
+const MAX_SCENARIO_MONTHS: u32 = 120;
+
+/// What to build and where it sits in time
+#[derive(Debug, Clone)]
+pub struct ScenarioOptions {
+    /// Any day in the last month of data; earlier months count back from it
+    /// None ends the scenario in the current month, in the user's timezone
+    pub end_month: Option<NaiveDate>,
+    pub months: u32,
+    /// Appended to account names, debt names, and descriptions so a scenario can
+    /// be built more than once in the same database (e.g. by parallel tests)
+    pub label: Option<String>,
+}
+
+impl Default for ScenarioOptions {
+    /// Twelve months ending this month, with no label
+    fn default() -> Self {
+        Self {
+            end_month: None,
+            months: 12,
+            label: None,
+        }
+    }
+}
+
+/// Ids of everything a scenario created
+#[derive(Debug, Clone)]
+pub struct FamilyScenario {
+    pub checking_account_id: i64,
+    pub savings_account_id: i64,
+    pub credit_card_account_id: i64,
+    pub debt_ids: Vec<i64>,
+    pub transaction_count: usize,
+    pub start_date: String,
+    pub end_date: String,
+}
+
+/// One generated transaction before insertion
+struct Entry {
+    account_id: i64,
+    day: u32,
+    amount_cents: Cents,
+    description: &'static str,
+    merchant: Option<&'static str>,
+    category: &'static str,
+}
+
+impl Entry {
+    fn new(account_id: i64, day: u32, amount_cents: Cents, description: &'static str, category: &'static str) -> Self {
+        Self { account_id, day, amount_cents, description, merchant: None, category }
+    }
+
+    fn at(mut self, merchant: &'static str) -> Self {
+        self.merchant = Some(merchant);
+        self
+    }
+}
+
+/// Amounts that vary from month to month by whole dollars, without randomness
+fn vary(base: Cents, spread: u32, month: u32, salt: u32) -> Cents {
+    let offset = (month * 37 + salt * 11) % (spread + 1);
+    base + Cents::from(offset) * 100
+}
+
+/// A household with checking, savings, and a credit card, a car loan and a
+/// student loan, and `months` of income, bills, spending, and payments
+///
+/// Checking gets two paychecks, rent, utilities, groceries, loan payments, a
+/// savings transfer, and the card payment each month. Card charges are
+/// negative and the monthly payment brings the card back to zero.
+pub async fn build_family_scenario(db: &SqlitePool, options: &ScenarioOptions) -> Result<FamilyScenario, String> {
+    if options.months == 0 || options.months > MAX_SCENARIO_MONTHS {
+        return Err(format!("Scenario must cover 1 to {} months", MAX_SCENARIO_MONTHS));
+    }
+
+    let label = |name: &str| match &options.label {
+        Some(label) => format!("{} ({})", name, label),
+        None => name.to_string(),
+    };
+
+    let end_month = match options.end_month {
+        Some(date) => date,
+        None => dates::today(db).await,
+    };
+    let end_month = end_month.with_day(1).ok_or("Invalid end month")?;
+    let start_month = end_month
+        .checked_sub_months(Months::new(options.months - 1))
+        .ok_or("Invalid start month")?;
+
+    let account = |name: &str, account_type: AccountType, initial_balance_cents: Cents| NewAccount {
+        name: label(name),
+        account_type,
+        initial_balance_cents,
+    };
+    let checking = create_account_impl(db, account("Family Checking", AccountType::Checking, 250_000)).await?;
+    let savings = create_account_impl(db, account("Family Savings", AccountType::Savings, 800_000)).await?;
+    let card = create_account_impl(db, account("Family Credit Card", AccountType::CreditCard, 0)).await?;
+
+    let car_loan = NewDebt {
+        name: label("Car Loan"),
+        balance_cents: 1_450_000,
+        interest_rate: 4.9,
+        min_payment_cents: 32_000,
+    };
+    let student_loan = NewDebt {
+        name: label("Student Loan"),
+        balance_cents: 2_200_000,
+        interest_rate: 5.5,
+        min_payment_cents: 25_000,
+    };
+    let debts = [
+        (car_loan.min_payment_cents, "Car loan payment"),
+        (student_loan.min_payment_cents, "Student loan payment"),
+    ];
+    let debt_ids = vec![
+        create_debt_impl(db, car_loan).await.map_err(|e| e.to_user_message())?,
+        create_debt_impl(db, student_loan).await.map_err(|e| e.to_user_message())?,
+    ];
+
+    // Backdate the debts so interest accrues from the start of the scenario
+    for debt_id in &debt_ids {
+        sqlx::query("UPDATE debts SET created_at = ? WHERE id = ?")
+            .bind(format!("{} 00:00:00", start_month.format("%Y-%m-%d")))
+            .bind(debt_id)
+            .execute(db)
+            .await
+            .map_err(|e| sanitize_db_error(e, "backdate scenario debts"))?;
+    }
+
+    let mut categories = std::collections::HashMap::new();
+    for name in ["Groceries", "Dining", "Transportation", "Entertainment", "Utilities", "Shopping", "Income"] {
+        let id: Option<(i64,)> = sqlx::query_as("SELECT id FROM categories WHERE name = ? AND type = 'predefined'")
+            .bind(name)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| sanitize_db_error(e, "load categories"))?;
+        categories.insert(name, id.map(|(id,)| id).unwrap_or(DEFAULT_CATEGORY_ID));
+    }
+
+    let mut transaction_count = 0;
+    let mut payments = Vec::new();
+
+    for month in 0..options.months {
+        let first = start_month + Months::new(month);
+
+        let mut card_entries = vec![
+            Entry::new(card, 6, -vary(1800, 40, month, 1), "Chipotle", "Dining").at("Chipotle"),
+            Entry::new(card, 9, -1549, "Netflix subscription", "Entertainment").at("Netflix"),
+            Entry::new(card, 14, -vary(2400, 40, month, 2), "Pizza night", "Dining"),
+            Entry::new(card, 16, -vary(4000, 25, month, 3), "Shell gas station", "Transportation").at("Shell"),
+            Entry::new(card, 24, -vary(6000, 120, month, 4), "Target", "Shopping").at("Target"),
+            Entry::new(card, 27, -vary(3000, 35, month, 5), "Starbucks", "Dining").at("Starbucks"),
+        ];
+        let card_payment: Cents = -card_entries.iter().map(|e| e.amount_cents).sum::<Cents>();
+
+        let mut entries = vec![
+            Entry::new(checking, 1, 260_000, "Payroll deposit", "Income"),
+            Entry::new(checking, 3, -185_000, "Rent", "Uncategorized"),
+            Entry::new(checking, 4, -vary(9500, 60, month, 6), "Whole Foods Market", "Groceries").at("Whole Foods"),
+            Entry::new(checking, 8, -vary(11_000, 45, month, 7), "City electric bill", "Utilities"),
+            Entry::new(checking, 11, -vary(9500, 60, month, 8), "Trader Joe's", "Groceries").at("Trader Joe's"),
+            Entry::new(checking, 12, -7000, "Internet service", "Utilities"),
+            Entry::new(checking, 15, 260_000, "Payroll deposit", "Income"),
+            Entry::new(checking, 18, -vary(9500, 60, month, 9), "Whole Foods Market", "Groceries").at("Whole Foods"),
+            Entry::new(checking, 20, -debts[0].0, debts[0].1, "Uncategorized"),
+            Entry::new(checking, 21, -debts[1].0, debts[1].1, "Uncategorized"),
+            Entry::new(checking, 22, -30_000, "Transfer to savings", "Uncategorized"),
+            Entry::new(checking, 25, -vary(9500, 60, month, 10), "Trader Joe's", "Groceries").at("Trader Joe's"),
+            Entry::new(checking, 28, -card_payment, "Credit card payment", "Uncategorized"),
+            Entry::new(savings, 22, 30_000, "Transfer from checking", "Uncategorized"),
+            Entry::new(savings, 28, vary(200, 3, month, 11), "Interest earned", "Income"),
+            Entry::new(card, 28, card_payment, "Payment received", "Uncategorized"),
+        ];
+        entries.append(&mut card_entries);
+
+        for entry in entries {
+            let transaction = CreateTransaction {
+                account_id: entry.account_id,
+                category_id: Some(categories.get(entry.category).copied().unwrap_or(DEFAULT_CATEGORY_ID)),
+                date: dates::format_date(first.with_day(entry.day).ok_or("Invalid scenario day")?),
+                amount_cents: entry.amount_cents,
+                description: label(entry.description),
+                merchant: entry.merchant.map(str::to_string),
+                notes: None,
+            };
+            create_transaction_impl(db, transaction, false)
+                .await
+                .map_err(|e| e.to_user_message())?;
+            transaction_count += 1;
+        }
+
+        for (debt_id, (payment, _)) in debt_ids.iter().zip(debts.iter()) {
+            let date = dates::format_date(first.with_day(20).ok_or("Invalid scenario day")?);
+            payments.push((*debt_id, *payment, date));
+        }
+    }
+
+    for (debt_id, payment, date) in payments {
+        record_debt_payment_impl(db, debt_id, payment, date, None)
+            .await
+            .map_err(|e| e.to_user_message())?;
+    }
+
+    let last_day = end_month
+        .checked_add_months(Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .ok_or("Invalid end month")?;
+
+    tracing::info!(transaction_count = transaction_count, months = options.months, "Built family scenario");
+
+    Ok(FamilyScenario {
+        checking_account_id: checking,
+        savings_account_id: savings,
+        credit_card_account_id: card,
+        debt_ids,
+        transaction_count,
+        start_date: start_month.format("%Y-%m-%d").to_string(),
+        end_date: last_day.format("%Y-%m-%d").to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vary_is_deterministic_and_bounded() {
+        for month in 0..24 {
+            let amount = vary(9500, 60, month, 6);
+            assert_eq!(amount, vary(9500, 60, month, 6));
+            assert!((9500..=15_500).contains(&amount));
+            assert_eq!(amount % 100, 0);
+        }
+        assert_ne!(vary(9500, 60, 0, 6), vary(9500, 60, 1, 6));
+    }
+}
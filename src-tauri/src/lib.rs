@@ -8,13 +8,43 @@ pub mod models;
 pub mod services;
 pub mod commands;
 pub mod utils;
+pub mod fixtures;
+#[cfg(desktop)]
+mod tray;
 
+use services::analytics_cache::AnalyticsCache;
+use services::folder_watcher::FolderWatcher;
+use services::profile_registry::ProfileRegistry;
 use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::sync::{PoisonError, RwLock};
 use tauri::Manager;
+use tauri_plugin_deep_link::DeepLinkExt;
 use tracing_subscriber::prelude::*;
+use utils::access_guard::AccessGuard;
+use utils::change_events::{notify_changed, ChangeAction, DataKind};
 
-// Managed state for database pool
-pub struct DbPool(pub SqlitePool);
+/// Managed state for the database pool of the open profile
+///
+/// Switching profiles swaps the pool while the app runs, so commands take a
+/// handle to the current pool for each call rather than holding on to one.
+pub struct DbPool(RwLock<SqlitePool>);
+
+impl DbPool {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self(RwLock::new(pool))
+    }
+
+    /// The open profile's pool
+    pub fn pool(&self) -> SqlitePool {
+        self.0.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Swap in `pool`, returning the previous one for the caller to close
+    pub fn replace(&self, pool: SqlitePool) -> SqlitePool {
+        std::mem::replace(&mut *self.0.write().unwrap_or_else(PoisonError::into_inner), pool)
+    }
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -29,79 +59,342 @@ pub fn run() {
 
     tracing::info!("Starting Budget Balancer application");
 
+    let access_guard = AccessGuard::from_env();
+    if access_guard.is_forced() {
+        tracing::info!("Started in read-only mode");
+    }
+
+    let handler: fn(tauri::ipc::Invoke) -> bool = tauri::generate_handler![
+        commands::csv_commands::get_csv_headers,
+        commands::csv_commands::read_csv_file,
+        commands::csv_commands::preview_csv_import,
+        commands::csv_commands::import_csv,
+        commands::csv_commands::save_column_mapping,
+        commands::csv_commands::suggest_column_mapping,
+        commands::csv_commands::detect_mapping_for_csv,
+        commands::csv_commands::list_import_batches,
+        commands::csv_commands::undo_import,
+        commands::csv_commands::get_duplicate_settings,
+        commands::csv_commands::update_duplicate_settings,
+        commands::transaction_commands::list_transactions,
+        commands::transaction_commands::list_transactions_with_balance,
+        commands::transaction_commands::count_transactions,
+        commands::transaction_commands::update_transaction_category,
+        commands::transaction_commands::categorize_transaction,
+        commands::transaction_commands::suggest_category_for_transaction,
+        commands::transaction_commands::export_transactions,
+        commands::transaction_commands::save_export_template,
+        commands::transaction_commands::list_export_templates,
+        commands::transaction_commands::search_transactions,
+        commands::transaction_commands::delete_transaction,
+        commands::transaction_commands::create_transaction,
+        commands::receipt_commands::extract_receipt_data,
+        commands::transaction_commands::update_transaction,
+        commands::transaction_commands::bulk_delete_transactions,
+        commands::transaction_commands::bulk_update_category,
+        commands::transaction_commands::bulk_assign_member,
+        commands::transaction_commands::bulk_set_tax_deductible,
+        commands::transaction_commands::mark_posted,
+        commands::category_commands::list_categories,
+        commands::category_commands::archive_category,
+        commands::category_commands::unarchive_category,
+        commands::category_commands::set_category_tax_deductible,
+        commands::category_commands::create_category,
+        commands::category_commands::update_category,
+        commands::category_commands::list_category_rules,
+        commands::category_commands::get_rule_statistics,
+        commands::category_commands::create_category_rule,
+        commands::category_commands::delete_category_rule,
+        commands::category_commands::preview_categorization,
+        commands::category_commands::detect_rule_conflicts,
+        commands::category_commands::export_category_template,
+        commands::category_commands::import_category_template,
+        commands::category_commands::list_budget_templates,
+        commands::category_commands::apply_budget_template,
+        commands::category_commands::list_category_seed_sets,
+        commands::category_commands::reseed_categories,
+        commands::account_commands::list_accounts,
+        commands::account_commands::create_account,
+        commands::account_commands::update_account,
+        commands::account_commands::set_statement_closing_day,
+        commands::account_commands::set_statement_cycle,
+        commands::account_commands::get_statement_summary,
+        commands::account_commands::delete_account,
+        commands::account_commands::archive_account,
+        commands::account_commands::unarchive_account,
+        commands::debt_commands::create_debt,
+        commands::debt_commands::list_debts,
+        commands::debt_commands::update_debt,
+        commands::debt_commands::calculate_payoff_plan,
+        commands::debt_commands::get_payoff_plan,
+        commands::debt_commands::record_debt_payment,
+        commands::debt_commands::update_debt_payment,
+        commands::debt_commands::delete_debt_payment,
+        commands::debt_commands::get_debt_progress,
+        commands::debt_commands::compare_strategies,
+        commands::debt_commands::simulate_payoff_scenarios,
+        commands::debt_commands::get_marginal_payment_impact,
+        commands::debt_commands::set_debt_autopay,
+        commands::debt_commands::remove_debt_autopay,
+        commands::debt_commands::list_debt_autopays,
+        commands::debt_commands::create_loan,
+        commands::debt_commands::get_amortization_schedule,
+        commands::debt_commands::set_debt_due_day,
+        commands::debt_commands::set_debt_min_payment_percent,
+        commands::debt_commands::link_debt_account,
+        commands::debt_commands::sync_debt_to_statement,
+        commands::debt_commands::get_upcoming_payments,
+        commands::analytics_commands::get_spending_by_category,
+        commands::analytics_commands::get_spending_by_merchant,
+        commands::analytics_commands::get_spending_by_member,
+        commands::analytics_commands::get_spending_trends,
+        commands::analytics_commands::get_spending_heatmap,
+        commands::analytics_commands::get_spending_targets_progress,
+        commands::analytics_commands::get_budget_variance_report,
+        commands::analytics_commands::create_spending_target,
+        commands::analytics_commands::update_spending_target,
+        commands::analytics_commands::export_budgets_csv,
+        commands::analytics_commands::import_budgets_csv,
+        commands::analytics_commands::get_dashboard_summary,
+        commands::dashboard_commands::get_dashboard_config,
+        commands::dashboard_commands::save_dashboard_config,
+        commands::analytics_commands::invalidate_analytics_cache,
+        commands::analytics_commands::get_spending_insights,
+        commands::analytics_commands::get_category_statistics,
+        commands::analytics_commands::get_financial_health,
+        commands::analytics_commands::get_net_worth_history,
+        commands::job_commands::get_job_status,
+        commands::job_commands::list_jobs,
+        commands::job_commands::cancel_job,
+        commands::job_commands::start_import_job,
+        commands::job_commands::import_csv_batch,
+        commands::job_commands::start_report_export_job,
+        commands::job_commands::rehash_transactions,
+        commands::bank_sync_commands::list_bank_connections,
+        commands::bank_sync_commands::link_bank_connection,
+        commands::bank_sync_commands::map_bank_account,
+        commands::bank_sync_commands::sync_accounts,
+        commands::bank_sync_commands::remove_bank_connection,
+        commands::data_commands::export_all_data,
+        commands::data_commands::import_all_data,
+        commands::data_commands::import_from_app,
+        commands::backup_commands::run_backup_now,
+        commands::backup_commands::list_backup_history,
+        commands::watch_folder_commands::scan_watch_folder,
+        commands::profile_commands::list_profiles,
+        commands::profile_commands::create_profile,
+        commands::profile_commands::switch_profile,
+        commands::member_commands::list_members,
+        commands::member_commands::create_member,
+        commands::member_commands::delete_member,
+        commands::investment_commands::record_holding,
+        commands::investment_commands::list_holdings,
+        commands::investment_commands::list_holding_history,
+        commands::investment_commands::delete_holding,
+        commands::investment_commands::set_security_price,
+        commands::investment_commands::list_security_prices,
+        commands::investment_commands::import_security_prices_csv,
+        commands::period_lock_commands::list_period_locks,
+        commands::period_lock_commands::lock_period,
+        commands::period_lock_commands::unlock_period,
+        commands::analytics_commands::get_largest_transactions,
+        commands::analytics_commands::get_amount_distribution,
+        commands::analytics_commands::get_account_summary,
+        commands::analytics_commands::copy_report_to_clipboard,
+        commands::analytics_commands::export_analytics_report,
+        commands::analytics_commands::generate_tax_report,
+        commands::onboarding_commands::get_onboarding_status,
+        commands::onboarding_commands::complete_onboarding_step,
+        commands::schema_commands::describe_data_model,
+        commands::schema_commands::execute_readonly_query,
+        commands::schema_commands::get_query_plan,
+        commands::merchant_commands::list_merchants,
+        commands::merchant_commands::rename_merchant,
+        commands::merchant_commands::merge_merchants,
+        commands::merchant_commands::normalize_merchants,
+        commands::notification_commands::list_notifications,
+        commands::notification_commands::mark_notifications_read,
+        commands::notification_commands::mark_all_notifications_read,
+        commands::notification_commands::dismiss_notification,
+        commands::settings_commands::list_settings,
+        commands::settings_commands::update_setting,
+        commands::settings_commands::get_read_only_mode,
+        commands::settings_commands::set_read_only_mode,
+        commands::digest_commands::generate_weekly_digest,
+        commands::digest_commands::generate_digest,
+        commands::deep_link_commands::handle_deep_link,
+        commands::tray_commands::get_tray_summary,
+        commands::freeze_commands::create_spending_freeze,
+        commands::freeze_commands::lift_spending_freeze,
+        commands::freeze_commands::delete_spending_freeze,
+        commands::freeze_commands::get_freeze_status,
+        commands::streak_commands::get_streaks,
+        commands::fx_commands::set_exchange_rate,
+        commands::fx_commands::list_exchange_rates,
+        commands::fx_commands::set_transaction_currency,
+        commands::fx_commands::get_foreign_spending_report,
+        commands::cash_flow_commands::get_cash_flow_projection,
+        commands::access_commands::get_access_status,
+        commands::access_commands::set_app_password,
+        commands::access_commands::set_app_role,
+        commands::access_commands::unlock_editing,
+        commands::access_commands::lock_editing,
+        commands::alert_commands::evaluate_alerts,
+        commands::alert_commands::list_alerts,
+        commands::alert_commands::dismiss_alert,
+        commands::search_commands::global_search,
+        commands::audit_commands::list_audit_log,
+        commands::scheduled_transaction_commands::create_scheduled_transaction,
+        commands::scheduled_transaction_commands::list_scheduled_transactions,
+        commands::scheduled_transaction_commands::delete_scheduled_transaction,
+        commands::scheduled_transaction_commands::post_due_scheduled_transactions,
+        commands::scheduled_transaction_commands::confirm_scheduled_transaction,
+        commands::calendar_commands::export_payment_calendar,
+    ];
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .manage(access_guard)
+        .manage(AnalyticsCache::new())
+        .manage(FolderWatcher::new())
         .setup(|app| {
             // Initialize database with migrations at app startup
+            let registry = ProfileRegistry::load(&app_data_dir()?).map_err(|e| e.to_user_message())?;
+            tracing::info!(profile = %registry.active(), "Opening profile");
             tauri::async_runtime::block_on(async {
-                match initialize_database().await {
+                let guard = app.state::<AccessGuard>();
+                match initialize_database(&registry, &guard).await {
                     Ok(pool) => {
                         tracing::info!("Database initialized successfully");
+                        start_profile(&pool, &guard).await;
                         // Store pool in managed state
-                        app.manage(DbPool(pool));
+                        app.manage(DbPool::new(pool));
+                        app.manage(registry);
                         Ok(())
                     }
                     Err(e) => {
                         tracing::error!(error = %e, "Failed to initialize database");
-                        Err(e.into())
+                        Err(e)
+                    }
+                }
+            })?;
+
+            // Post scheduled transactions and raise alerts for anything that came up while the app was closed
+            if !app.state::<AccessGuard>().is_read_only() {
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let db_pool = handle.state::<DbPool>();
+                    match commands::scheduled_transaction_commands::post_due_scheduled_transactions_impl(&db_pool.pool(), None).await {
+                        Ok(postings) if !postings.is_empty() => {
+                            let ids: Vec<i64> = postings.iter().map(|posting| posting.transaction_id).collect();
+                            notify_changed(&handle, DataKind::Transactions, ChangeAction::Created, ids);
+                        }
+                        Ok(_) => {}
+                        Err(e) => tracing::warn!(error = %e, "Failed to post scheduled transactions at startup"),
+                    }
+                    if let Err(e) = commands::alert_commands::evaluate_and_emit(&handle, &db_pool.pool()).await {
+                        tracing::warn!(error = %e, "Failed to evaluate alerts at startup");
+                    }
+                });
+            }
+
+            // Take the automatic backup if one came due while the app was closed
+            // Not in read-only mode: each backup is recorded in the history and prunes old backup files
+            if !app.state::<AccessGuard>().is_read_only() {
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let db_pool = handle.state::<DbPool>();
+                    match commands::backup_commands::run_scheduled_backup_impl(&db_pool.pool()).await {
+                        Ok(Some(backup)) => tracing::info!(pruned = backup.pruned_count, "Wrote scheduled backup"),
+                        Ok(None) => {}
+                        Err(e) => tracing::warn!(error = %e, "Scheduled backup failed"),
                     }
+                });
+            }
+
+            // Import statements dropped into the watch folder, including any that arrived while the app was closed
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = commands::watch_folder_commands::restart_folder_watcher(&handle).await {
+                    tracing::warn!(error = %e, "Failed to start folder watcher");
                 }
-            })
+            });
+
+            // budgetbalancer:// links (registered at install time on macOS; at runtime elsewhere)
+            #[cfg(any(windows, target_os = "linux"))]
+            app.deep_link().register_all()?;
+
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                commands::deep_link_commands::dispatch_deep_links(&handle, event.urls());
+            });
+            if let Some(urls) = app.deep_link().get_current()? {
+                commands::deep_link_commands::dispatch_deep_links(app.handle(), urls);
+            }
+
+            #[cfg(desktop)]
+            tray::setup(app)?;
+
+            Ok(())
+        })
+        .invoke_handler(move |invoke| {
+            // Mutating commands are rejected here in read-only or locked viewer mode, before they run
+            let access = {
+                let webview = invoke.message.webview();
+                let guard = webview.state::<AccessGuard>();
+                guard.check(invoke.message.command())
+            };
+            if let Err(e) = access {
+                tracing::warn!(command = invoke.message.command(), error = %e, "Blocked command");
+                invoke.resolver.reject(e.to_user_message());
+                return true;
+            }
+            handler(invoke)
         })
-        .invoke_handler(tauri::generate_handler![
-            commands::csv_commands::get_csv_headers,
-            commands::csv_commands::import_csv,
-            commands::csv_commands::save_column_mapping,
-            commands::transaction_commands::list_transactions,
-            commands::transaction_commands::count_transactions,
-            commands::transaction_commands::update_transaction_category,
-            commands::transaction_commands::categorize_transaction,
-            commands::transaction_commands::export_transactions,
-            commands::transaction_commands::search_transactions,
-            commands::transaction_commands::delete_transaction,
-            commands::transaction_commands::bulk_delete_transactions,
-            commands::transaction_commands::bulk_update_category,
-            commands::category_commands::list_categories,
-            commands::category_commands::create_category,
-            commands::account_commands::list_accounts,
-            commands::account_commands::create_account,
-            commands::account_commands::update_account,
-            commands::account_commands::delete_account,
-            commands::debt_commands::create_debt,
-            commands::debt_commands::list_debts,
-            commands::debt_commands::update_debt,
-            commands::debt_commands::calculate_payoff_plan,
-            commands::debt_commands::get_payoff_plan,
-            commands::debt_commands::record_debt_payment,
-            commands::debt_commands::get_debt_progress,
-            commands::debt_commands::compare_strategies,
-            commands::analytics_commands::get_spending_by_category,
-            commands::analytics_commands::get_spending_trends,
-            commands::analytics_commands::get_spending_targets_progress,
-            commands::analytics_commands::create_spending_target,
-            commands::analytics_commands::update_spending_target,
-            commands::analytics_commands::get_dashboard_summary,
-            commands::analytics_commands::export_analytics_report,
-        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
-async fn initialize_database() -> Result<SqlitePool, String> {
-    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
-    use std::str::FromStr;
-
-    // Get database path in app data directory
-    let mut db_path = dirs::data_dir()
+/// Folder holding the registry and every profile's database
+fn app_data_dir() -> Result<PathBuf, String> {
+    let mut dir = dirs::data_dir()
         .ok_or_else(|| "Could not find data directory".to_string())?;
+    dir.push("budget-balancer");
+    Ok(dir)
+}
 
-    db_path.push("budget-balancer");
-    std::fs::create_dir_all(&db_path)
-        .map_err(|e| format!("Failed to create data directory: {}", e))?;
+async fn initialize_database(registry: &ProfileRegistry, guard: &AccessGuard) -> Result<SqlitePool, String> {
+    let db_path = registry.active_database_path().map_err(|e| e.to_user_message())?;
+    open_database(&db_path, guard).await
+}
 
-    db_path.push("budget_balancer.db");
+/// Turn `PRAGMA query_only` on or off to match read-only mode
+async fn set_query_only(conn: &mut sqlx::SqliteConnection, read_only: bool) -> Result<(), sqlx::Error> {
+    let pragma = if read_only { "PRAGMA query_only = ON" } else { "PRAGMA query_only = OFF" };
+    sqlx::query(pragma).execute(conn).await.map(|_| ())
+}
+
+/// Open (creating if needed) the database at `db_path` and bring its schema up to date;
+/// used at startup and when switching profiles
+///
+/// Every connection the pool hands out follows `guard`'s read-only mode through
+/// `PRAGMA query_only`, so in read-only mode the database itself refuses writes.
+/// Migrations run first on a connection of their own, since the app can't read an
+/// out-of-date schema even when it won't change the data.
+pub async fn open_database(db_path: &Path, guard: &AccessGuard) -> Result<SqlitePool, String> {
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+    use std::str::FromStr;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    if let Some(dir) = db_path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
 
     // Log filename at info level, full path only at debug level (per SECURITY.md)
     tracing::info!("Initializing database");
@@ -112,19 +405,78 @@ async fn initialize_database() -> Result<SqlitePool, String> {
         .map_err(|e| format!("Failed to parse database URL: {}", e))?
         .create_if_missing(true);
 
-    // Create connection pool
-    let pool = SqlitePoolOptions::new()
-        .max_connections(5)
-        .connect_with(options)
+    // Run migrations
+    let setup = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options.clone())
         .await
         .map_err(|e| format!("Failed to connect to database: {}", e))?;
-
-    // Run migrations
     sqlx::migrate!("./migrations")
-        .run(&pool)
+        .run(&setup)
         .await
         .map_err(|e| format!("Failed to run migrations: {}", e))?;
+    setup.close().await;
+
+    // Create connection pool; read-only mode can change while a connection sits idle,
+    // so it's applied again each time one is handed out
+    let read_only = guard.read_only_flag();
+    let on_connect = Arc::clone(&read_only);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(5)
+        .after_connect(move |conn, _| {
+            let read_only = on_connect.load(Ordering::SeqCst);
+            Box::pin(set_query_only(conn, read_only))
+        })
+        .before_acquire(move |conn, _| {
+            let read_only = read_only.load(Ordering::SeqCst);
+            Box::pin(async move { set_query_only(conn, read_only).await.map(|_| true) })
+        })
+        .connect_with(options)
+        .await
+        .map_err(|e| format!("Failed to connect to database: {}", e))?;
 
     tracing::info!("Database initialized successfully");
     Ok(pool)
 }
+
+/// Apply the open profile's read-only and role settings, then clear out old records and
+/// fail jobs the last shutdown interrupted, unless the profile is read-only
+///
+/// Used at startup and when switching profiles. Failures are logged rather than
+/// returned, since the profile is already open; the settings fail closed.
+pub async fn start_profile(pool: &SqlitePool, guard: &AccessGuard) {
+    if let Err(e) = commands::settings_commands::load_read_only_mode(pool, guard).await {
+        tracing::warn!(error = %e, "Failed to load read-only setting");
+        let _ = guard.set_read_only(true);
+    }
+    if let Err(e) = commands::access_commands::load_app_role(pool, guard).await {
+        // A viewer must not get edit access because the role couldn't be read
+        tracing::warn!(error = %e, "Failed to load app role");
+        guard.set_locked(true);
+    }
+    if guard.is_read_only() {
+        return;
+    }
+
+    // Prune old read notifications; a failure here shouldn't block startup
+    match commands::notification_commands::prune_notifications_impl(
+        pool,
+        constants::NOTIFICATION_RETENTION_DAYS,
+    )
+    .await
+    {
+        Ok(pruned) => tracing::debug!(pruned, "Pruned old notifications"),
+        Err(e) => tracing::warn!(error = %e, "Failed to prune notifications"),
+    }
+
+    match commands::audit_commands::prune_audit_log_impl(pool, constants::AUDIT_LOG_RETENTION_DAYS).await {
+        Ok(pruned) => tracing::debug!(pruned, "Pruned old audit log entries"),
+        Err(e) => tracing::warn!(error = %e, "Failed to prune audit log"),
+    }
+
+    match services::job_queue::JobQueue::fail_interrupted(pool).await {
+        Ok(0) => {}
+        Ok(count) => tracing::warn!(count = count, "Marked jobs interrupted by the last shutdown as failed"),
+        Err(e) => tracing::warn!(error = %e, "Failed to recover interrupted jobs"),
+    }
+}
@@ -1,3 +1,4 @@
+use crate::utils::money::{self, Cents};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,7 +26,13 @@ pub struct Account {
     #[serde(rename = "type")]
     #[sqlx(rename = "type")]
     pub account_type: String,
-    pub balance: f64,
+    #[serde(rename = "balance", with = "money::dollars")]
+    pub balance_cents: Cents,
+    pub statement_closing_day: Option<i64>, // None means statements follow calendar months
+    #[sqlx(default)]
+    pub statement_due_day: Option<i64>, // Credit cards: payment is due on this day after each statement closes
+    #[sqlx(default)]
+    pub archived: bool, // Hidden from account lists; transactions stay in analytics
     pub created_at: String,
     pub updated_at: String,
 }
@@ -34,7 +41,8 @@ pub struct Account {
 pub struct NewAccount {
     pub name: String,
     pub account_type: AccountType,
-    pub initial_balance: f64,
+    #[serde(rename = "initial_balance", with = "money::dollars")]
+    pub initial_balance_cents: Cents,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,5 +50,28 @@ pub struct UpdateAccount {
     pub id: i64,
     pub name: Option<String>,
     pub account_type: Option<AccountType>,
-    pub balance: Option<f64>,
+    #[serde(rename = "balance", default, with = "money::dollars::option")]
+    pub balance_cents: Option<Cents>,
+}
+
+/// Charges, payments and balances for one statement period of an account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatementSummary {
+    pub account_id: i64,
+    pub period_start: String,
+    pub period_end: String, // The closing date
+    pub due_date: Option<String>, // None when the account has no due day
+    #[serde(rename = "opening_balance", with = "money::dollars")]
+    pub opening_balance_cents: Cents,
+    #[serde(rename = "charges", with = "money::dollars")]
+    pub charges_cents: Cents, // Purchases and fees, as a positive amount
+    #[serde(rename = "payments", with = "money::dollars")]
+    pub payments_cents: Cents, // Payments and refunds
+    #[serde(rename = "closing_balance", with = "money::dollars")]
+    pub closing_balance_cents: Cents, // The statement balance; negative on a card means money owed
+    #[serde(rename = "amount_due", with = "money::dollars")]
+    pub amount_due_cents: Cents, // Owed at closing; zero when the account is in credit
+    pub transaction_count: i64,
+    pub closed: bool, // The period ended before today
+    pub linked_debt_id: Option<i64>, // A debt tracking this card, see sync_debt_to_statement
 }
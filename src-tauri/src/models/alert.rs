@@ -0,0 +1,46 @@
+use crate::models::notification::NotificationSeverity;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertType {
+    TargetThreshold,  // A spending target is nearly or fully used for the month
+    LargeTransaction, // An expense well above the usual amount for its category
+    PaymentDue,       // A debt's minimum payment is due soon and hasn't been paid
+}
+
+impl std::fmt::Display for AlertType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlertType::TargetThreshold => write!(f, "target_threshold"),
+            AlertType::LargeTransaction => write!(f, "large_transaction"),
+            AlertType::PaymentDue => write!(f, "payment_due"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Alert {
+    pub id: i64,
+    pub alert_type: String,
+    pub alert_key: String, // e.g. "payment_due:3:2025-03-15"; one alert per event
+    pub severity: String,
+    pub title: String,
+    pub message: String,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<i64>,
+    pub dismissed_at: Option<String>,
+    pub created_at: String,
+}
+
+/// An alert found by the evaluator, before it is stored
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewAlert {
+    pub alert_type: AlertType,
+    pub alert_key: String,
+    pub severity: NotificationSeverity,
+    pub title: String,
+    pub message: String,
+    pub entity_type: Option<&'static str>,
+    pub entity_id: Option<i64>,
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+}
+
+impl std::fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditAction::Create => write!(f, "create"),
+            AuditAction::Update => write!(f, "update"),
+            AuditAction::Delete => write!(f, "delete"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEntity {
+    Transaction,
+    Category,
+    Debt,
+    DebtPayment,
+    Import, // An import batch; its transactions aren't logged one by one
+    ScheduledTransaction,
+}
+
+impl std::fmt::Display for AuditEntity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditEntity::Transaction => write!(f, "transaction"),
+            AuditEntity::Category => write!(f, "category"),
+            AuditEntity::Debt => write!(f, "debt"),
+            AuditEntity::DebtPayment => write!(f, "debt_payment"),
+            AuditEntity::Import => write!(f, "import"),
+            AuditEntity::ScheduledTransaction => write!(f, "scheduled_transaction"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: Option<i64>,
+    pub old_values: Option<Json<serde_json::Value>>, // None for creates
+    pub new_values: Option<Json<serde_json::Value>>, // None for deletes
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditLogFilter {
+    pub entity_type: Option<AuditEntity>,
+    pub entity_id: Option<i64>,
+    pub action: Option<AuditAction>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// One backup attempt
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BackupRecord {
+    pub id: i64,
+    pub started_at: String, // UTC
+    pub kind: String,       // "scheduled" or "manual"
+    pub status: String,     // "succeeded" or "failed"
+    pub file_path: Option<String>,
+    pub size_bytes: Option<i64>,
+    pub pruned_count: i64,
+    pub error: Option<String>,
+}
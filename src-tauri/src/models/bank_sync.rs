@@ -0,0 +1,55 @@
+use crate::utils::money::{self, Cents};
+use serde::{Deserialize, Serialize};
+
+/// A linked login at a bank data aggregator
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BankConnection {
+    pub id: i64,
+    pub provider: String,
+    pub name: String,
+    pub last_synced_at: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub accounts: Vec<BankAccountLink>,
+}
+
+/// A remote account seen on a connection, and the local account its transactions go to
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BankAccountLink {
+    pub id: i64,
+    pub connection_id: i64,
+    pub remote_account_id: String,
+    pub remote_name: String,
+    pub institution: Option<String>,
+    #[serde(rename = "remote_balance", default, with = "money::dollars::option")]
+    pub remote_balance_cents: Option<Cents>,
+    pub account_id: Option<i64>, // None until mapped; unmapped accounts are skipped when syncing
+    pub last_synced_at: Option<String>,
+}
+
+/// What one remote account contributed to a sync
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountSyncResult {
+    pub link_id: i64,
+    pub remote_name: String,
+    pub account_id: Option<i64>,
+    pub fetched: usize, // Posted transactions the provider returned; pending ones are left for a later sync
+    pub imported: usize,
+    pub duplicates: usize,
+    pub locked: usize, // Dated in a locked month, so left out
+    pub errors: usize,
+    pub import_batch_id: Option<i64>, // Undo with undo_import; None when the account isn't mapped
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncSummary {
+    pub connection_id: i64,
+    pub start_date: String, // Earliest posting date requested from the provider
+    pub accounts: Vec<AccountSyncResult>,
+    pub imported: usize,
+    pub duplicates: usize,
+    pub provider_messages: Vec<String>, // Non-fatal problems the provider reported, e.g. a login needing attention
+}
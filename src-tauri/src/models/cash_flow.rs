@@ -0,0 +1,71 @@
+use crate::utils::money::{self, Cents};
+use serde::{Deserialize, Serialize};
+
+/// A scheduled autopay debit in a cash-flow projection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectedPayment {
+    pub date: String,
+    pub account_id: i64,
+    pub debt_id: i64,
+    pub debt_name: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    #[serde(rename = "balance_after", with = "money::dollars")]
+    pub balance_after_cents: Cents, // Funding account balance after the payment
+}
+
+/// An occurrence of a scheduled transaction in a cash-flow projection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectedScheduledTransaction {
+    pub date: String,
+    pub account_id: i64,
+    pub scheduled_transaction_id: i64,
+    pub description: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,        // Negative for bills, positive for income
+    #[serde(rename = "balance_after", with = "money::dollars")]
+    pub balance_after_cents: Cents, // Account balance after it posts
+}
+
+/// An autopay the funding account can't fully cover
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortfallWarning {
+    pub date: String,
+    pub account_id: i64,
+    pub account_name: String,
+    pub debt_id: i64,
+    pub debt_name: String,
+    #[serde(rename = "payment", with = "money::dollars")]
+    pub payment_cents: Cents,
+    #[serde(rename = "balance_before", with = "money::dollars")]
+    pub balance_before_cents: Cents,
+    #[serde(rename = "shortfall", with = "money::dollars")]
+    pub shortfall_cents: Cents,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountProjection {
+    pub account_id: i64,
+    pub account_name: String,
+    #[serde(rename = "starting_balance", with = "money::dollars")]
+    pub starting_balance_cents: Cents,
+    #[serde(rename = "ending_balance", with = "money::dollars")]
+    pub ending_balance_cents: Cents,
+    #[serde(rename = "lowest_balance", with = "money::dollars")]
+    pub lowest_balance_cents: Cents,
+    pub lowest_balance_date: String,
+    #[serde(rename = "autopay_total", with = "money::dollars")]
+    pub autopay_total_cents: Cents,
+    #[serde(rename = "scheduled_total", with = "money::dollars")]
+    pub scheduled_total_cents: Cents, // Net of scheduled bills and income
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CashFlowProjection {
+    pub start_date: String,
+    pub end_date: String,
+    pub accounts: Vec<AccountProjection>,
+    pub payments: Vec<ProjectedPayment>,
+    pub scheduled: Vec<ProjectedScheduledTransaction>,
+    pub warnings: Vec<ShortfallWarning>,
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CategoryType {
     Predefined,
@@ -25,6 +25,11 @@ pub struct Category {
     pub category_type: String,
     pub parent_id: Option<i64>,
     pub icon: Option<String>,
+    pub color: Option<String>,
+    pub archived: bool, // Hidden from category lists; transactions keep it
+    #[sqlx(default)]
+    #[serde(default)]
+    pub tax_deductible: bool, // Its transactions go in the year-end tax report
     pub created_at: String,
 }
 
@@ -32,4 +37,21 @@ pub struct Category {
 pub struct NewCategory {
     pub name: String,
     pub icon: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Changes to a category; fields left out are kept
+/// Predefined categories can be renamed and restyled too, but no category changes type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCategory {
+    pub id: i64,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>, // Empty removes the icon
+    #[serde(default)]
+    pub color: Option<String>, // Empty removes the color
+    #[serde(default, rename = "type")]
+    pub category_type: Option<CategoryType>, // Only accepted when it matches the current type
 }
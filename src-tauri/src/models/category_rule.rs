@@ -1,11 +1,53 @@
+use crate::utils::money::{self, Cents};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleMatchType {
+    #[default]
+    Contains,
+    StartsWith,
+    Regex,
+    Exact,
+}
+
+impl std::fmt::Display for RuleMatchType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleMatchType::Contains => write!(f, "contains"),
+            RuleMatchType::StartsWith => write!(f, "starts_with"),
+            RuleMatchType::Regex => write!(f, "regex"),
+            RuleMatchType::Exact => write!(f, "exact"),
+        }
+    }
+}
+
+impl std::str::FromStr for RuleMatchType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "contains" => Ok(RuleMatchType::Contains),
+            "starts_with" => Ok(RuleMatchType::StartsWith),
+            "regex" => Ok(RuleMatchType::Regex),
+            "exact" => Ok(RuleMatchType::Exact),
+            _ => Err(format!("Invalid match type: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct CategoryRule {
     pub id: i64,
     pub pattern: String,
     pub category_id: i64,
     pub priority: i32,
+    pub match_type: String,
+    #[serde(rename = "min_amount", default, with = "money::dollars::option")]
+    pub min_amount_cents: Option<Cents>,   // Inclusive, compared against ABS(amount)
+    #[serde(rename = "max_amount", default, with = "money::dollars::option")]
+    pub max_amount_cents: Option<Cents>,   // Inclusive, compared against ABS(amount)
+    pub account_id: Option<i64>,   // None = applies to all accounts
     pub created_at: String,
 }
 
@@ -14,4 +56,31 @@ pub struct NewCategoryRule {
     pub pattern: String,
     pub category_id: i64,
     pub priority: i32,
+    #[serde(default)]
+    pub match_type: RuleMatchType,
+    #[serde(rename = "min_amount", default, with = "money::dollars::option")]
+    pub min_amount_cents: Option<Cents>,
+    #[serde(rename = "max_amount", default, with = "money::dollars::option")]
+    pub max_amount_cents: Option<Cents>,
+    #[serde(default)]
+    pub account_id: Option<i64>,
+}
+
+/// How often a rule has categorized a transaction, for finding rules that are dead weight
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RuleStatistics {
+    pub rule_id: i64,
+    pub pattern: String,
+    pub match_type: String,
+    pub category_id: i64,
+    pub category_name: String,
+    pub priority: i32,
+    pub account_id: Option<i64>,
+    pub match_count: i64,
+    pub last_matched_at: Option<String>, // None when the rule has never matched
+    pub created_at: String,
+    #[sqlx(skip)]
+    pub never_matched: bool,
+    #[sqlx(skip)]
+    pub stale: bool, // Never matched, or not within the stale rule window
 }
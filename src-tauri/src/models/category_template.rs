@@ -0,0 +1,115 @@
+use crate::models::category_rule::RuleMatchType;
+use crate::utils::money::{self, Cents};
+use serde::{Deserialize, Serialize};
+
+/// Current version of the category template document format
+pub const CATEGORY_TEMPLATE_VERSION: u32 = 1;
+
+/// Shareable category tree with budgets and rules (e.g. a "50/30/20 starter template")
+/// Categories are referenced by name so templates can be imported into any database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryTemplate {
+    pub version: u32,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub exported_at: Option<String>,
+    pub categories: Vec<TemplateCategory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateCategory {
+    pub name: String,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub budgets: Vec<TemplateBudget>,
+    #[serde(default)]
+    pub rules: Vec<TemplateRule>,
+    #[serde(default)]
+    pub children: Vec<TemplateCategory>,
+}
+
+/// Spending target without dates; the start date is chosen at import time
+/// Either a fixed `amount`, or `percent_of_income` resolved against the monthly income
+/// supplied when the template is applied (scaled by period length)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateBudget {
+    #[serde(rename = "amount", default, with = "money::dollars")]
+    pub amount_cents: Cents,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percent_of_income: Option<f64>,
+    pub period: String, // 'monthly', 'quarterly', 'yearly'
+}
+
+/// Category rule without account scope (accounts are specific to one database)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateRule {
+    pub pattern: String,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub match_type: RuleMatchType,
+    #[serde(rename = "min_amount", default, with = "money::dollars::option")]
+    pub min_amount_cents: Option<Cents>,
+    #[serde(rename = "max_amount", default, with = "money::dollars::option")]
+    pub max_amount_cents: Option<Cents>,
+}
+
+/// How to handle template categories whose name already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    /// Leave the existing category alone and skip the template's budgets and rules for it
+    Skip,
+    /// Create the template category under a new name, e.g. "Groceries (2)"
+    Rename,
+    /// Reuse the existing category and add the template's budgets and rules to it
+    #[default]
+    Merge,
+}
+
+/// Where `apply_budget_template` loads its template from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum BudgetTemplateSource {
+    Bundled { id: String },
+    Imported { content: String },
+}
+
+/// Gallery entry for a template bundled with the app
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetTemplateInfo {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub category_count: usize,
+    pub requires_income: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CategoryTemplateImportResult {
+    pub categories_created: usize,
+    pub categories_existing: usize,
+    pub categories_renamed: usize,
+    pub categories_skipped: usize,
+    pub budgets_created: usize,
+    pub budgets_skipped: usize,   // Category already has an active budget for the period
+    pub rules_created: usize,
+    pub rules_skipped: usize,     // Identical rule already exists
+}
+
+/// Outcome of reseeding the predefined categories from a category set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CategoryReseedResult {
+    pub set: String,
+    #[serde(flatten)]
+    pub import: CategoryTemplateImportResult, // Categories and rules added; same-named categories are reused
+    pub categories_restored: usize, // Archived categories in the set, unarchived
+    pub categories_moved: usize,    // Predefined categories moved under the set's parent
+    pub categories_archived: usize, // Predefined categories not in the set, when replacing
+}
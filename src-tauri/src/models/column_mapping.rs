@@ -8,14 +8,42 @@ pub struct ColumnMapping {
     pub amount_col: String,
     pub description_col: String,
     pub merchant_col: Option<String>,
+    pub debit_col: Option<String>,
+    pub credit_col: Option<String>,
+    pub status_col: Option<String>,
+    pub member_col: Option<String>,
+    pub invert_amounts: bool,
+    pub date_format: Option<String>,
+    pub decimal_separator: Option<String>,
+    pub header_fingerprint: Option<String>,
+    pub account_id: Option<i64>,
     pub created_at: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NewColumnMapping {
     pub source_name: String,
     pub date_col: String,
+    #[serde(default)]
     pub amount_col: String,
     pub description_col: String,
     pub merchant_col: Option<String>,
+    #[serde(default)]
+    pub debit_col: Option<String>,
+    #[serde(default)]
+    pub credit_col: Option<String>,
+    #[serde(default)]
+    pub status_col: Option<String>, // Rows whose status reads as pending are imported as pending
+    #[serde(default)]
+    pub member_col: Option<String>, // Matched by name to a household member
+    #[serde(default)]
+    pub invert_amounts: bool,
+    #[serde(default)]
+    pub date_format: Option<String>, // Detected from each file when None
+    #[serde(default)]
+    pub decimal_separator: Option<char>, // '.' or ','; worked out from each amount when None
+    #[serde(default)]
+    pub headers: Option<Vec<String>>, // The file's headers, so files like it can be auto-mapped
+    #[serde(default)]
+    pub account_id: Option<i64>, // Account files with these headers are suggested to import into
 }
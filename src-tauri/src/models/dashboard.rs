@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// A widget the dashboard can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WidgetKind {
+    Spending, // Top categories and the comparison with the previous period
+    Debts,
+    Targets,
+    NetWorth,
+    UpcomingBills, // Debt payments and scheduled bills coming due
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DashboardWidget {
+    pub kind: WidgetKind,
+    #[serde(default)]
+    pub limit: Option<i64>, // Spending: top categories; upcoming bills: bills. None uses the default
+    #[serde(default)]
+    pub days_ahead: Option<i64>, // Upcoming bills only
+}
+
+impl DashboardWidget {
+    pub fn new(kind: WidgetKind) -> Self {
+        Self {
+            kind,
+            limit: None,
+            days_ahead: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardConfig {
+    pub widgets: Vec<DashboardWidget>, // In display order; each kind at most once
+    #[serde(default)]
+    pub updated_at: Option<String>, // None until a layout is saved
+}
+
+impl Default for DashboardConfig {
+    /// The dashboard as it was before layouts could be configured
+    fn default() -> Self {
+        Self {
+            widgets: vec![
+                DashboardWidget::new(WidgetKind::Spending),
+                DashboardWidget::new(WidgetKind::Debts),
+                DashboardWidget::new(WidgetKind::Targets),
+            ],
+            updated_at: None,
+        }
+    }
+}
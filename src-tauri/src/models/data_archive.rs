@@ -0,0 +1,279 @@
+use crate::models::category_rule::RuleMatchType;
+use crate::models::transaction::TransactionStatus;
+use crate::utils::money::{self, Cents};
+use serde::{Deserialize, Serialize};
+
+/// Current version of the full data archive format
+pub const DATA_ARCHIVE_VERSION: u32 = 1;
+
+/// Everything needed to rebuild the user's data in another database, as one JSON document
+/// Records refer to each other by name rather than ID, so an archive can be merged into a
+/// database that already has data, or written by hand when moving from another app
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataArchive {
+    pub version: u32,
+    #[serde(default)]
+    pub exported_at: Option<String>,
+    #[serde(default)]
+    pub accounts: Vec<ArchivedAccount>,
+    #[serde(default)]
+    pub categories: Vec<ArchivedCategory>,
+    #[serde(default)]
+    pub category_rules: Vec<ArchivedRule>,
+    #[serde(default)]
+    pub transactions: Vec<ArchivedTransaction>,
+    #[serde(default)]
+    pub debts: Vec<ArchivedDebt>,
+    #[serde(default)]
+    pub debt_payments: Vec<ArchivedDebtPayment>,
+    #[serde(default)]
+    pub spending_targets: Vec<ArchivedTarget>,
+    #[serde(default)]
+    pub column_mappings: Vec<ArchivedColumnMapping>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ArchivedAccount {
+    pub name: String,
+    #[serde(rename = "type")]
+    #[sqlx(rename = "type")]
+    pub account_type: String, // 'checking', 'savings' or 'credit_card'
+    #[serde(rename = "balance", default, with = "money::dollars")]
+    pub balance_cents: Cents,
+    #[serde(default)]
+    pub statement_closing_day: Option<i64>,
+    #[serde(default)]
+    pub statement_due_day: Option<i64>,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+impl ArchivedAccount {
+    /// An account another app's export only names, typed from its name (those exports don't say)
+    pub fn named(name: &str, balance_cents: Cents) -> Self {
+        let lower = name.to_lowercase();
+        let account_type = if lower.contains("credit") || lower.contains("card") {
+            "credit_card"
+        } else if lower.contains("saving") {
+            "savings"
+        } else {
+            "checking"
+        };
+        Self {
+            name: name.to_string(),
+            account_type: account_type.to_string(),
+            balance_cents,
+            statement_closing_day: None,
+            statement_due_day: None,
+            archived: false,
+        }
+    }
+}
+
+/// Categories are created as custom categories; predefined ones are matched by name
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ArchivedCategory {
+    pub name: String,
+    #[serde(default)]
+    pub parent: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub tax_deductible: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedRule {
+    pub pattern: String,
+    pub category: String,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub match_type: RuleMatchType,
+    #[serde(rename = "min_amount", default, with = "money::dollars::option")]
+    pub min_amount_cents: Option<Cents>,
+    #[serde(rename = "max_amount", default, with = "money::dollars::option")]
+    pub max_amount_cents: Option<Cents>,
+    #[serde(default)]
+    pub account: Option<String>, // None applies to every account
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ArchivedTransaction {
+    pub account: String,
+    #[serde(default)]
+    pub category: Option<String>, // None is Uncategorized
+    pub date: String, // YYYY-MM-DD
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    pub description: String,
+    #[serde(default)]
+    pub merchant: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub original_currency: Option<String>,
+    #[serde(rename = "original_amount", default, with = "money::dollars::option")]
+    pub original_amount_cents: Option<Cents>,
+    #[serde(default = "default_status")]
+    pub status: String, // "pending" or "posted"
+    #[serde(default)]
+    pub member: Option<String>, // Household member's name; created on import if it's new
+    #[serde(default)]
+    pub tax_deductible: Option<bool>, // Overrides the category's tax flag; None follows it
+}
+
+fn default_status() -> String {
+    TransactionStatus::Posted.to_string()
+}
+
+/// Debts are matched by name; when several share one, payments go to the first
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ArchivedDebt {
+    pub name: String,
+    #[serde(rename = "balance", with = "money::dollars")]
+    pub balance_cents: Cents,
+    #[serde(rename = "original_balance", default, with = "money::dollars::option")]
+    pub original_balance_cents: Option<Cents>, // Defaults to the balance
+    pub interest_rate: f64,
+    #[serde(rename = "min_payment", with = "money::dollars")]
+    pub min_payment_cents: Cents,
+    #[serde(default)]
+    pub min_payment_percent: Option<f64>,
+    #[serde(default = "default_debt_kind")]
+    pub kind: String,
+    #[serde(default)]
+    pub term_months: Option<i64>,
+    #[serde(default)]
+    pub loan_start_date: Option<String>,
+    #[serde(default = "default_due_day")]
+    pub due_day: i64,
+    #[serde(default)]
+    pub account: Option<String>, // Credit card whose statements the debt follows
+}
+
+fn default_due_day() -> i64 {
+    15
+}
+
+fn default_debt_kind() -> String {
+    "revolving".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ArchivedDebtPayment {
+    pub debt: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    pub date: String,
+    #[serde(rename = "interest", default, with = "money::dollars")]
+    pub interest_cents: Cents,
+    #[serde(rename = "principal", default, with = "money::dollars::option")]
+    pub principal_cents: Option<Cents>, // Defaults to the amount less interest
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ArchivedTarget {
+    #[serde(default = "default_scope")]
+    pub scope: String, // 'category', 'account' or 'overall'
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub account: Option<String>,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    pub period: String,
+    pub start_date: String,
+    #[serde(default)]
+    pub end_date: Option<String>,
+}
+
+fn default_scope() -> String {
+    "category".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ArchivedColumnMapping {
+    pub source_name: String,
+    pub date_col: String,
+    pub amount_col: String,
+    pub description_col: String,
+    #[serde(default)]
+    pub merchant_col: Option<String>,
+    #[serde(default)]
+    pub debit_col: Option<String>,
+    #[serde(default)]
+    pub credit_col: Option<String>,
+    #[serde(default)]
+    pub status_col: Option<String>,
+    #[serde(default)]
+    pub member_col: Option<String>,
+    #[serde(default)]
+    pub invert_amounts: bool,
+    #[serde(default)]
+    pub date_format: Option<String>,
+    #[serde(default)]
+    pub decimal_separator: Option<String>,
+    #[serde(default)]
+    pub header_fingerprint: Option<String>,
+    #[serde(default)]
+    pub account: Option<String>, // Account files with this mapping are imported into
+}
+
+/// Another budgeting app whose transaction export can be imported directly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppExportFormat {
+    Mint, // Transactions CSV from Mint
+    Ynab, // Register CSV from YNAB (current or YNAB 4)
+}
+
+/// What to do with an archived record that matches one already in the database
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveConflict {
+    /// Keep the existing record as it is
+    #[default]
+    Skip,
+    /// Replace the existing record's details with the archived ones
+    Overwrite,
+    /// Keep the existing record, filling in only what it's missing (e.g. notes, icons,
+    /// statement days, or a category for an uncategorized transaction)
+    Merge,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ImportCounts {
+    pub created: usize,
+    pub updated: usize, // Overwritten or merged
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DataImportResult {
+    pub accounts: ImportCounts,
+    pub categories: ImportCounts,
+    pub category_rules: ImportCounts,
+    pub transactions: ImportCounts,
+    pub debts: ImportCounts,
+    pub debt_payments: ImportCounts,
+    pub spending_targets: ImportCounts,
+    pub column_mappings: ImportCounts,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DataExportResult {
+    pub path: String,
+    pub accounts: usize,
+    pub categories: usize,
+    pub category_rules: usize,
+    pub transactions: usize,
+    pub debts: usize,
+    pub debt_payments: usize,
+    pub spending_targets: usize,
+    pub column_mappings: usize,
+}
@@ -1,35 +1,155 @@
+use crate::constants::DEFAULT_DEBT_DUE_DAY;
+use crate::utils::money::{self, Cents};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Debt {
     pub id: i64,
     pub name: String,
-    pub balance: f64,
-    pub original_balance: f64,
+    #[serde(rename = "balance", with = "money::dollars")]
+    pub balance_cents: Cents,
+    #[serde(rename = "original_balance", with = "money::dollars")]
+    pub original_balance_cents: Cents,
     pub interest_rate: f64,  // Annual percentage
-    pub min_payment: f64,
+    #[serde(rename = "min_payment", with = "money::dollars")]
+    pub min_payment_cents: Cents, // The floor when min_payment_percent is set
+    #[sqlx(default)]
+    #[serde(default)]
+    pub min_payment_percent: Option<f64>, // Minimum as a percentage of the balance, like a credit card's
+    #[sqlx(default)]
+    #[serde(default = "default_kind")]
+    pub kind: String, // "revolving" or "loan"
+    #[sqlx(default)]
+    #[serde(default)]
+    pub term_months: Option<i64>, // Loans only: number of monthly payments
+    #[sqlx(default)]
+    #[serde(default)]
+    pub loan_start_date: Option<String>, // Loans only: the first payment is due a month later
+    #[sqlx(default)]
+    #[serde(default = "default_due_day")]
+    pub due_day: u32, // Day of the month the payment is due
+    #[sqlx(default)]
+    #[serde(default)]
+    pub account_id: Option<i64>, // The credit card account this debt tracks, if any
     pub created_at: String,
     pub updated_at: String,
 }
 
+impl Debt {
+    /// The minimum payment due on the current balance
+    pub fn current_min_payment(&self) -> Cents {
+        minimum_payment(self.min_payment_cents, self.min_payment_percent, self.balance_cents)
+    }
+}
+
+/// The minimum payment due on `balance`: `percent` of it when that's more than the fixed
+/// `floor`, and never more than the balance itself
+pub fn minimum_payment(floor: Cents, percent: Option<f64>, balance: Cents) -> Cents {
+    let scaled = percent.map_or(0, |p| money::percent_of(balance, p));
+    floor.max(scaled).min(balance.max(0))
+}
+
+fn default_due_day() -> u32 {
+    DEFAULT_DEBT_DUE_DAY
+}
+
+fn default_kind() -> String {
+    DebtKind::Revolving.to_string()
+}
+
+/// Revolving debts (credit cards, lines of credit) have a balance that can go up and down;
+/// loans are paid off in a fixed number of equal payments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DebtKind {
+    Revolving,
+    Loan,
+}
+
+impl std::fmt::Display for DebtKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DebtKind::Revolving => write!(f, "revolving"),
+            DebtKind::Loan => write!(f, "loan"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewDebt {
     pub name: String,
-    pub balance: f64,
+    #[serde(rename = "balance", with = "money::dollars")]
+    pub balance_cents: Cents,
     pub interest_rate: f64,
-    pub min_payment: f64,
+    #[serde(rename = "min_payment", with = "money::dollars")]
+    pub min_payment_cents: Cents,
+}
+
+/// A fixed-term loan; its monthly payment comes from the amortization schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewLoan {
+    pub name: String,
+    #[serde(rename = "principal", with = "money::dollars")]
+    pub principal_cents: Cents,
+    pub interest_rate: f64, // APR
+    pub term_months: u32,
+    pub start_date: String,
+    #[serde(rename = "balance", default, with = "money::dollars::option")]
+    pub balance_cents: Option<Cents>, // Defaults to what the schedule leaves owed today
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct DebtPayment {
     pub id: i64,
     pub debt_id: i64,
-    pub amount: f64,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    #[serde(rename = "interest", with = "money::dollars")]
+    pub interest_cents: Cents,  // Interest accrued since the previous payment, paid first
+    #[serde(rename = "principal", with = "money::dollars")]
+    pub principal_cents: Cents, // The part that reduced the balance
     pub date: String,
     pub plan_id: Option<i64>,
     pub created_at: String,
 }
 
+/// A debt paid automatically from an account on each due date
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DebtAutopay {
+    pub debt_id: i64,
+    pub debt_name: String,
+    pub account_id: i64,
+    pub account_name: String,
+    #[serde(rename = "amount", default, with = "money::dollars::option")]
+    pub amount_cents: Option<Cents>, // None pays the minimum payment
+    pub created_at: String,
+}
+
+/// Why a debt's balance changed, as kept in its balance history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BalanceChange {
+    Opened,     // The debt was added
+    Payment,    // A payment was recorded
+    Correction, // A recorded payment was edited or deleted
+    Adjustment, // The balance was edited directly
+    Statement,  // Synced to a credit card statement
+    Import,     // Restored from a data archive
+}
+
+impl std::fmt::Display for BalanceChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BalanceChange::Opened => write!(f, "opened"),
+            BalanceChange::Payment => write!(f, "payment"),
+            BalanceChange::Correction => write!(f, "correction"),
+            BalanceChange::Adjustment => write!(f, "adjustment"),
+            BalanceChange::Statement => write!(f, "statement"),
+            BalanceChange::Import => write!(f, "import"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum PayoffStrategy {
@@ -50,7 +170,8 @@ impl std::fmt::Display for PayoffStrategy {
 pub struct DebtPlan {
     pub id: i64,
     pub strategy: String,
-    pub monthly_amount: f64,
+    #[serde(rename = "monthly_amount", with = "money::dollars")]
+    pub monthly_amount_cents: Cents,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+/// A transaction field that import duplicate detection compares
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateField {
+    Date,
+    Amount,
+    Description,
+    Merchant,
+}
+
+impl std::fmt::Display for DuplicateField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DuplicateField::Date => write!(f, "date"),
+            DuplicateField::Amount => write!(f, "amount"),
+            DuplicateField::Description => write!(f, "description"),
+            DuplicateField::Merchant => write!(f, "merchant"),
+        }
+    }
+}
+
+impl std::str::FromStr for DuplicateField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "date" => Ok(DuplicateField::Date),
+            "amount" => Ok(DuplicateField::Amount),
+            "description" => Ok(DuplicateField::Description),
+            "merchant" => Ok(DuplicateField::Merchant),
+            _ => Err(format!("Invalid duplicate field: {}", s)),
+        }
+    }
+}
+
+/// How matching rows are treated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateStrictness {
+    /// Any match, stored or earlier in the file, is a duplicate
+    #[default]
+    Strict,
+    /// Each stored transaction absorbs one matching row, so identical rows within a file
+    /// (the same coffee twice a day) are all kept the first time and skipped on re-import
+    Counted,
+    /// Every row is imported
+    Off,
+}
+
+impl std::fmt::Display for DuplicateStrictness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DuplicateStrictness::Strict => write!(f, "strict"),
+            DuplicateStrictness::Counted => write!(f, "counted"),
+            DuplicateStrictness::Off => write!(f, "off"),
+        }
+    }
+}
+
+impl std::str::FromStr for DuplicateStrictness {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(DuplicateStrictness::Strict),
+            "counted" => Ok(DuplicateStrictness::Counted),
+            "off" => Ok(DuplicateStrictness::Off),
+            _ => Err(format!("Invalid duplicate strictness: {}", s)),
+        }
+    }
+}
+
+/// How imports into an account decide a row is already there
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateSettings {
+    pub account_id: i64,
+    pub fields: Vec<DuplicateField>,
+    pub lookback_days: Option<i64>, // How many days apart matching dates can be; None is any
+    pub strictness: DuplicateStrictness,
+    /// True when the account has no settings of its own and rows are compared by hash (date,
+    /// amount and description); hashes not yet migrated from version 1 match across accounts
+    pub is_default: bool,
+    pub updated_at: Option<String>,
+}
+
+impl DuplicateSettings {
+    pub fn default_for(account_id: i64) -> Self {
+        Self {
+            account_id,
+            fields: vec![DuplicateField::Date, DuplicateField::Amount, DuplicateField::Description],
+            lookback_days: None,
+            strictness: DuplicateStrictness::Strict,
+            is_default: true,
+            updated_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DuplicateSettingsUpdate {
+    pub fields: Vec<DuplicateField>,
+    #[serde(default)]
+    pub lookback_days: Option<i64>,
+    #[serde(default)]
+    pub strictness: DuplicateStrictness,
+}
+
+/// Progress of migrating stored hashes to the current version, reported after each batch
+#[derive(Debug, Clone, Serialize)]
+pub struct RehashProgress {
+    pub processed: usize,
+    pub total: usize,
+}
+
+/// Outcome of migrating stored hashes; a cancelled run keeps the batches already done
+#[derive(Debug, Clone, Serialize)]
+pub struct RehashResult {
+    pub hash_version: i64,
+    pub rehashed: usize,
+    pub remaining: i64, // Transactions still on an older version
+}
@@ -0,0 +1,42 @@
+use crate::utils::money::{self, Cents};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ExchangeRate {
+    pub id: i64,
+    pub currency: String, // ISO 4217 code, e.g. "EUR"
+    pub date: String,
+    pub rate: f64, // Home-currency units per one unit of `currency`
+    pub created_at: String,
+}
+
+/// Spending in one foreign currency, converted at transaction-date and current rates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrencySpending {
+    pub currency: String,
+    pub transaction_count: i64,
+    #[serde(rename = "original_total", with = "money::dollars")]
+    pub original_total_cents: Cents,   // In `currency`
+    #[serde(rename = "historical_total", with = "money::dollars")]
+    pub historical_total_cents: Cents, // Home currency at each transaction date's rate
+    #[serde(rename = "current_total", with = "money::dollars")]
+    pub current_total_cents: Cents,    // Home currency at the latest rate
+    #[serde(rename = "fx_impact", with = "money::dollars")]
+    pub fx_impact_cents: Cents,        // current_total - historical_total; positive means it would cost more today
+    pub current_rate: Option<f64>,
+    pub missing_rate_count: i64, // Transactions with no rate on file, valued at their posted amount
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForeignSpendingReport {
+    pub start_date: String,
+    pub end_date: String,
+    pub as_of: String,
+    pub currencies: Vec<CurrencySpending>,
+    #[serde(rename = "historical_total", with = "money::dollars")]
+    pub historical_total_cents: Cents,
+    #[serde(rename = "current_total", with = "money::dollars")]
+    pub current_total_cents: Cents,
+    #[serde(rename = "fx_impact", with = "money::dollars")]
+    pub fx_impact_cents: Cents,
+}
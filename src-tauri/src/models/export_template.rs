@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/// A transaction field an export template can write
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportField {
+    Date,
+    Amount, // Signed: negative for money out
+    Debit,  // Money out as a positive number, blank for money in
+    Credit, // Money in, blank for money out
+    Description,
+    Merchant,
+    Category,
+    Account,
+    Notes,
+    Status,
+}
+
+impl ExportField {
+    /// Header written when the template doesn't name the column
+    pub fn default_header(&self) -> &'static str {
+        match self {
+            ExportField::Date => "Date",
+            ExportField::Amount => "Amount",
+            ExportField::Debit => "Debit",
+            ExportField::Credit => "Credit",
+            ExportField::Description => "Description",
+            ExportField::Merchant => "Merchant",
+            ExportField::Category => "Category",
+            ExportField::Account => "Account",
+            ExportField::Notes => "Notes",
+            ExportField::Status => "Status",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportColumn {
+    pub field: ExportField,
+    #[serde(default)]
+    pub header: Option<String>, // None uses the field's default header
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportTemplate {
+    pub id: i64,
+    pub name: String,
+    pub columns: Vec<ExportColumn>, // In file order; a field may appear more than once
+    pub date_format: Option<String>, // e.g. "DD/MM/YYYY" or "%d.%m.%Y"; None writes YYYY-MM-DD
+    pub decimal_separator: String,   // "." or ","
+    pub delimiter: String,           // ",", ";", "|" or a tab
+    pub include_header: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewExportTemplate {
+    pub name: String,
+    pub columns: Vec<ExportColumn>,
+    #[serde(default)]
+    pub date_format: Option<String>,
+    #[serde(default = "default_decimal_separator")]
+    pub decimal_separator: String,
+    #[serde(default = "default_delimiter")]
+    pub delimiter: String,
+    #[serde(default = "default_include_header")]
+    pub include_header: bool,
+}
+
+fn default_decimal_separator() -> String {
+    ".".to_string()
+}
+
+fn default_delimiter() -> String {
+    ",".to_string()
+}
+
+fn default_include_header() -> bool {
+    true
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ImportBatch {
+    pub id: i64,
+    pub account_id: i64,
+    pub account_name: String,
+    pub status: String, // 'in_progress', 'completed', 'failed', 'undone'
+    pub total_rows: i64,
+    pub imported_count: i64,
+    pub duplicate_count: i64,
+    pub error_count: i64,
+    pub remaining_count: i64, // Imported transactions not since deleted
+    pub created_at: String,
+    pub undone_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoImportResult {
+    pub import_batch_id: i64,
+    pub deleted: u64,
+}
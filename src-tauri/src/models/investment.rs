@@ -0,0 +1,68 @@
+use crate::utils::money::{self, Cents};
+use serde::{Deserialize, Serialize};
+
+/// A recorded position: `quantity` shares of `symbol` in an account from `date` on
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Holding {
+    pub id: i64,
+    pub account_id: i64,
+    pub symbol: String,
+    pub quantity: f64, // 0 once the position is sold
+    #[serde(rename = "cost_basis", default, with = "money::dollars::option")]
+    pub cost_basis_cents: Option<Cents>, // Total paid for the position
+    pub date: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewHolding {
+    pub account_id: i64,
+    pub symbol: String,
+    pub quantity: f64,
+    #[serde(rename = "cost_basis", default, with = "money::dollars::option")]
+    pub cost_basis_cents: Option<Cents>,
+    pub date: Option<String>, // Defaults to today; a record on the same date is replaced
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SecurityPrice {
+    pub id: i64,
+    pub symbol: String,
+    pub date: String,
+    pub price: f64, // Per share, effective until the next price on file
+    pub source: String, // 'manual' or 'csv'
+    pub created_at: String,
+}
+
+/// An account's position in one security on a date, at the latest price on or before it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldingValue {
+    pub holding_id: i64,
+    pub account_id: i64,
+    pub account_name: String,
+    pub symbol: String,
+    pub quantity: f64,
+    #[serde(rename = "cost_basis", default, with = "money::dollars::option")]
+    pub cost_basis_cents: Option<Cents>,
+    pub held_since: String, // Date of the holding record in effect
+    pub price: Option<f64>, // None when no price is on file yet
+    pub price_date: Option<String>,
+    #[serde(rename = "value", default, with = "money::dollars::option")]
+    pub value_cents: Option<Cents>,
+    #[serde(rename = "gain", default, with = "money::dollars::option")]
+    pub gain_cents: Option<Cents>, // Value less cost basis, when both are known
+}
+
+/// A line of a price file that couldn't be imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceImportError {
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceImportResult {
+    pub imported: usize, // New prices and prices replaced on the same date
+    pub symbols: Vec<String>,
+    pub errors: Vec<PriceImportError>,
+}
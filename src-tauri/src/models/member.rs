@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// A person in the household that transactions can be attributed to
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Member {
+    pub id: i64,
+    pub name: String,
+    pub color: Option<String>, // Hex color for charts
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewMember {
+    pub name: String,
+    #[serde(default)]
+    pub color: Option<String>,
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Merchant {
+    pub id: i64,
+    pub name: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerchantSummary {
+    pub id: i64,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub transaction_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerchantMergeResult {
+    pub merchant_id: i64,
+    pub merged_count: usize,
+    pub transactions_updated: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerchantLinkResult {
+    pub transactions_linked: usize,
+    pub merchants_created: usize,
+}
@@ -2,6 +2,31 @@ pub mod transaction;
 pub mod account;
 pub mod category;
 pub mod category_rule;
+pub mod category_template;
 pub mod debt;
 pub mod spending_target;
 pub mod column_mapping;
+pub mod merchant;
+pub mod onboarding;
+pub mod notification;
+pub mod setting;
+pub mod report;
+pub mod spending_freeze;
+pub mod import_batch;
+pub mod exchange_rate;
+pub mod cash_flow;
+pub mod alert;
+pub mod search;
+pub mod audit_log;
+pub mod scheduled_transaction;
+pub mod job;
+pub mod bank_sync;
+pub mod data_archive;
+pub mod duplicate_settings;
+pub mod export_template;
+pub mod backup;
+pub mod profile;
+pub mod member;
+pub mod dashboard;
+pub mod period_lock;
+pub mod investment;
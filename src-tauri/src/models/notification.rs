@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+/// Record types a notification can link to
+pub const NOTIFICATION_ENTITY_TYPES: &[&str] = &["account", "transaction", "category", "debt", "spending_target"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSeverity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+impl std::fmt::Display for NotificationSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotificationSeverity::Info => write!(f, "info"),
+            NotificationSeverity::Warning => write!(f, "warning"),
+            NotificationSeverity::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+impl std::str::FromStr for NotificationSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(NotificationSeverity::Info),
+            "warning" => Ok(NotificationSeverity::Warning),
+            "critical" => Ok(NotificationSeverity::Critical),
+            _ => Err(format!("Invalid notification severity: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Notification {
+    pub id: i64,
+    pub notification_type: String, // e.g. "budget_exceeded", "import_completed"
+    pub severity: String,
+    pub title: String,
+    pub message: String,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<i64>,
+    pub read_at: Option<String>,
+    pub dismissed_at: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewNotification {
+    pub notification_type: String,
+    #[serde(default)]
+    pub severity: NotificationSeverity,
+    pub title: String,
+    pub message: String,
+    #[serde(default)]
+    pub entity_type: Option<String>,
+    #[serde(default)]
+    pub entity_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationFilter {
+    pub unread_only: bool,
+    pub include_dismissed: bool,
+    pub severity: Option<NotificationSeverity>,
+    pub notification_type: Option<String>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<i64>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationList {
+    pub notifications: Vec<Notification>,
+    pub unread_count: i64, // Unread and not dismissed, regardless of filter
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    AccountCreated,
+    FirstImport,
+    FirstBudget,
+    FirstDebt,
+}
+
+impl OnboardingStep {
+    /// All steps in checklist order
+    pub const ALL: [OnboardingStep; 4] = [
+        OnboardingStep::AccountCreated,
+        OnboardingStep::FirstImport,
+        OnboardingStep::FirstBudget,
+        OnboardingStep::FirstDebt,
+    ];
+}
+
+impl std::fmt::Display for OnboardingStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OnboardingStep::AccountCreated => write!(f, "account_created"),
+            OnboardingStep::FirstImport => write!(f, "first_import"),
+            OnboardingStep::FirstBudget => write!(f, "first_budget"),
+            OnboardingStep::FirstDebt => write!(f, "first_debt"),
+        }
+    }
+}
+
+impl std::str::FromStr for OnboardingStep {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "account_created" => Ok(OnboardingStep::AccountCreated),
+            "first_import" => Ok(OnboardingStep::FirstImport),
+            "first_budget" => Ok(OnboardingStep::FirstBudget),
+            "first_debt" => Ok(OnboardingStep::FirstDebt),
+            _ => Err(format!("Invalid onboarding step: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnboardingStepStatus {
+    pub step: OnboardingStep,
+    pub completed: bool,
+    pub completed_at: Option<String>, // Only set when the step was explicitly completed
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnboardingStatus {
+    pub steps: Vec<OnboardingStepStatus>,
+    pub completed_count: usize,
+    pub total_steps: usize,
+    pub is_complete: bool,
+}
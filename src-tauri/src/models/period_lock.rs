@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+/// A month closed to changes after it has been reconciled
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PeriodLock {
+    pub month: String, // YYYY-MM
+    pub locked_at: String,
+}
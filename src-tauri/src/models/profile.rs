@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// A separate set of budget data with a database file of its own
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub file_name: String, // Relative to the app data folder
+    pub created_at: String,
+}
+
+/// Every profile and which one is open; also the layout of the registry file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileList {
+    pub active: String, // Name of the open profile
+    pub profiles: Vec<Profile>,
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// Which report to render and its parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "report", rename_all = "snake_case")]
+pub enum ReportSpec {
+    SpendingByCategory {
+        start_date: String,
+        end_date: String,
+        #[serde(default)]
+        account_id: Option<i64>,
+    },
+    SpendingByMerchant {
+        start_date: String,
+        end_date: String,
+        #[serde(default)]
+        account_id: Option<i64>,
+        #[serde(default)]
+        limit: Option<i64>,
+    },
+    TargetsProgress {
+        start_date: String,
+        end_date: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportTextFormat {
+    #[default]
+    Markdown,
+    Tsv,
+}
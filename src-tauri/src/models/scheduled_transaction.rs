@@ -0,0 +1,82 @@
+use crate::utils::money::{self, Cents};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Recurrence {
+    Once,
+    Weekly,
+    Biweekly,
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl std::fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Recurrence::Once => write!(f, "once"),
+            Recurrence::Weekly => write!(f, "weekly"),
+            Recurrence::Biweekly => write!(f, "biweekly"),
+            Recurrence::Monthly => write!(f, "monthly"),
+            Recurrence::Quarterly => write!(f, "quarterly"),
+            Recurrence::Yearly => write!(f, "yearly"),
+        }
+    }
+}
+
+impl std::str::FromStr for Recurrence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "once" => Ok(Recurrence::Once),
+            "weekly" => Ok(Recurrence::Weekly),
+            "biweekly" => Ok(Recurrence::Biweekly),
+            "monthly" => Ok(Recurrence::Monthly),
+            "quarterly" => Ok(Recurrence::Quarterly),
+            "yearly" => Ok(Recurrence::Yearly),
+            _ => Err(format!("Invalid recurrence: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ScheduledTransaction {
+    pub id: i64,
+    pub account_id: i64,
+    pub category_id: i64,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    pub description: String,
+    pub merchant: Option<String>,
+    pub recurrence: String,
+    pub start_date: String,            // First occurrence
+    pub end_date: Option<String>,      // No occurrences after this date
+    pub next_due_date: Option<String>, // None once every occurrence has been posted
+    pub auto_post: bool,               // Posted when due; otherwise waits for confirmation
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewScheduledTransaction {
+    pub account_id: i64,
+    pub category_id: Option<i64>, // Uncategorized when None
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    pub description: String,
+    pub merchant: Option<String>,
+    pub recurrence: Recurrence,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub auto_post: Option<bool>, // Defaults to true
+}
+
+/// One occurrence of a scheduled transaction turned into a real transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledPosting {
+    pub scheduled_transaction_id: i64,
+    pub date: String,
+    pub transaction_id: i64,
+    pub already_existed: bool, // An identical transaction (e.g. imported) was there already
+}
@@ -0,0 +1,33 @@
+use crate::utils::money::{self, Cents};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchEntityType {
+    Transaction, // Matched on description, merchant, or notes
+    Category,
+    Debt,
+}
+
+impl std::fmt::Display for SearchEntityType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchEntityType::Transaction => write!(f, "transaction"),
+            SearchEntityType::Category => write!(f, "category"),
+            SearchEntityType::Debt => write!(f, "debt"),
+        }
+    }
+}
+
+/// One match from global search, best matches first
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SearchResult {
+    pub entity_type: String,
+    pub entity_id: i64,
+    pub title: String,        // Transaction description, category name, or debt name
+    pub snippet: String,      // Best-matching excerpt of the title, merchant, or notes
+    pub score: f64,           // Relevance; higher is better
+    pub date: Option<String>, // Transactions only
+    #[serde(rename = "amount", default, with = "money::dollars::option")]
+    pub amount_cents: Option<Cents>,  // Transactions only
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingKey {
+    DigestOutputDir, // Folder weekly digests are written to (absolute path)
+    ReadOnly,        // "true" blocks every command that changes data
+    Timezone,        // "system", "UTC", a zone like "America/New_York" or an offset like "-05:00"; decides which day "today" is
+    ExcludePending,  // "true" leaves pending transactions out of analytics
+    BackupDir,       // Folder automatic backups are written to (absolute path)
+    BackupFrequency, // "daily", "weekly" or "monthly"; no automatic backups when unset
+    BackupRetention, // How many backups to keep in the backup folder
+    WatchDir,        // Folder new CSV statements are imported from automatically (absolute path)
+}
+
+impl SettingKey {
+    pub const ALL: [SettingKey; 8] = [
+        SettingKey::DigestOutputDir,
+        SettingKey::ReadOnly,
+        SettingKey::Timezone,
+        SettingKey::ExcludePending,
+        SettingKey::BackupDir,
+        SettingKey::BackupFrequency,
+        SettingKey::BackupRetention,
+        SettingKey::WatchDir,
+    ];
+}
+
+impl std::fmt::Display for SettingKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingKey::DigestOutputDir => write!(f, "digest_output_dir"),
+            SettingKey::ReadOnly => write!(f, "read_only"),
+            SettingKey::Timezone => write!(f, "timezone"),
+            SettingKey::ExcludePending => write!(f, "exclude_pending"),
+            SettingKey::BackupDir => write!(f, "backup_dir"),
+            SettingKey::BackupFrequency => write!(f, "backup_frequency"),
+            SettingKey::BackupRetention => write!(f, "backup_retention"),
+            SettingKey::WatchDir => write!(f, "watch_dir"),
+        }
+    }
+}
+
+impl std::str::FromStr for SettingKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "digest_output_dir" => Ok(SettingKey::DigestOutputDir),
+            "read_only" => Ok(SettingKey::ReadOnly),
+            "timezone" => Ok(SettingKey::Timezone),
+            "exclude_pending" => Ok(SettingKey::ExcludePending),
+            "backup_dir" => Ok(SettingKey::BackupDir),
+            "backup_frequency" => Ok(SettingKey::BackupFrequency),
+            "backup_retention" => Ok(SettingKey::BackupRetention),
+            "watch_dir" => Ok(SettingKey::WatchDir),
+            _ => Err(format!("Unknown setting: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSetting {
+    pub key: SettingKey,
+    pub value: Option<String>, // None when the setting has not been configured
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ReadOnlyStatus {
+    pub enabled: bool,
+    pub forced: bool, // Started with --read-only; can't be turned off until restart
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppRole {
+    Owner,  // Full access
+    Viewer, // Mutating commands need the app password
+}
+
+impl std::fmt::Display for AppRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppRole::Owner => write!(f, "owner"),
+            AppRole::Viewer => write!(f, "viewer"),
+        }
+    }
+}
+
+impl std::str::FromStr for AppRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "owner" => Ok(AppRole::Owner),
+            "viewer" => Ok(AppRole::Viewer),
+            _ => Err(format!("Unknown role: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AccessStatus {
+    pub role: AppRole,
+    pub locked: bool, // Viewer role and the password hasn't been entered this session
+    pub password_set: bool,
+}
@@ -0,0 +1,45 @@
+use crate::utils::money::{self, Cents};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SpendingFreeze {
+    pub id: i64,
+    pub name: String,
+    pub category_id: Option<i64>, // None = global freeze on all spending
+    pub start_date: String,
+    pub end_date: Option<String>, // None = until lifted
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewSpendingFreeze {
+    pub name: String,
+    pub category_id: Option<i64>,
+    pub start_date: String,
+    pub end_date: Option<String>,
+}
+
+/// An expense that landed in a frozen category during the freeze
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FreezeViolation {
+    pub transaction_id: i64,
+    pub date: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    pub description: String,
+    pub category_id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreezeStatus {
+    pub freeze: SpendingFreeze,
+    pub category_name: Option<String>,
+    pub active: bool,
+    pub violation_count: usize,
+    #[serde(rename = "violation_total", with = "money::dollars")]
+    pub violation_total_cents: Cents, // Sum of violating expenses (positive)
+    pub last_violation_date: Option<String>,
+    pub current_streak_days: i64, // Days since the last violation (or since the freeze started)
+    pub longest_streak_days: i64,
+    pub violations: Vec<FreezeViolation>,
+}
@@ -1,10 +1,45 @@
+use crate::utils::money::{self, Cents};
 use serde::{Deserialize, Serialize};
 
+/// What a target's spending is measured over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetScope {
+    Category, // One category
+    Account,  // Everything spent from one account
+    Overall,  // All spending
+}
+
+impl TargetScope {
+    /// The scope implied by which of a target's category and account is set
+    pub fn from_ids(category_id: Option<i64>, account_id: Option<i64>) -> Result<Self, String> {
+        match (category_id, account_id) {
+            (Some(_), Some(_)) => Err("A target can apply to a category or an account, not both".to_string()),
+            (Some(_), None) => Ok(TargetScope::Category),
+            (None, Some(_)) => Ok(TargetScope::Account),
+            (None, None) => Ok(TargetScope::Overall),
+        }
+    }
+}
+
+impl std::fmt::Display for TargetScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TargetScope::Category => write!(f, "category"),
+            TargetScope::Account => write!(f, "account"),
+            TargetScope::Overall => write!(f, "overall"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpendingTarget {
     pub id: i64,
-    pub category_id: i64,
-    pub amount: f64,
+    pub scope: String,            // 'category', 'account', 'overall'
+    pub category_id: Option<i64>, // Set for category targets
+    pub account_id: Option<i64>,  // Set for account targets
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
     pub period: String,  // 'monthly', 'quarterly', 'yearly'
     pub start_date: String,
     pub end_date: Option<String>,
@@ -13,8 +48,10 @@ pub struct SpendingTarget {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewSpendingTarget {
-    pub category_id: i64,
-    pub amount: f64,
+    pub category_id: Option<i64>, // Neither category nor account makes an overall target
+    pub account_id: Option<i64>,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
     pub period: String,
     pub start_date: String,
     pub end_date: Option<String>,
@@ -24,10 +61,14 @@ pub struct NewSpendingTarget {
 pub struct TargetProgress {
     pub category_id: i64,
     pub category_name: String,
-    pub target_amount: f64,
-    pub actual_amount: f64,
-    pub remaining: f64,
+    #[serde(rename = "target_amount", with = "money::dollars")]
+    pub target_amount_cents: Cents,
+    #[serde(rename = "actual_amount", with = "money::dollars")]
+    pub actual_amount_cents: Cents,
+    #[serde(rename = "remaining", with = "money::dollars")]
+    pub remaining_cents: Cents,
     pub percentage_used: f64,
     pub status: String,  // 'under', 'on_track', 'over'
-    pub variance: f64,
+    #[serde(rename = "variance", with = "money::dollars")]
+    pub variance_cents: Cents,
 }
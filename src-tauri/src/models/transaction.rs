@@ -1,3 +1,4 @@
+use crate::utils::money::{self, Cents};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -6,11 +7,55 @@ pub struct Transaction {
     pub account_id: i64,
     pub category_id: i64,
     pub date: String,           // ISO 8601 format
-    pub amount: f64,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
     pub description: String,
     pub merchant: Option<String>,
     pub hash: String,
     pub created_at: String,
+    #[sqlx(default)]
+    pub notes: Option<String>,
+    #[sqlx(default)]
+    #[serde(default = "default_status")]
+    pub status: String, // "pending" or "posted"
+    #[sqlx(default)]
+    #[serde(default)]
+    pub member_id: Option<i64>, // Household member who made the transaction
+    #[sqlx(default)]
+    #[serde(default)]
+    pub tax_deductible: Option<bool>, // Overrides the category's tax flag; None follows it
+}
+
+fn default_status() -> String {
+    TransactionStatus::Posted.to_string()
+}
+
+/// Pending transactions are authorized but not yet posted; the bank may still change their
+/// date, description or amount before they post
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionStatus {
+    Pending,
+    Posted,
+}
+
+impl std::fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransactionStatus::Pending => write!(f, "pending"),
+            TransactionStatus::Posted => write!(f, "posted"),
+        }
+    }
+}
+
+/// A transaction with the account's balance after it, in date order
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TransactionWithBalance {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub transaction: Transaction,
+    #[serde(rename = "running_balance", with = "money::dollars")]
+    pub running_balance_cents: Cents, // Initial account balance plus this and all earlier transactions
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,17 +63,63 @@ pub struct NewTransaction {
     pub account_id: i64,
     pub category_id: i64,
     pub date: String,
-    pub amount: f64,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
     pub description: String,
     pub merchant: Option<String>,
     pub hash: String,
 }
 
+/// A transaction entered by hand, e.g. a cash purchase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTransaction {
+    pub account_id: i64,
+    pub category_id: Option<i64>, // Categorized by rules when None
+    pub date: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    pub description: String,
+    pub merchant: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Changes to a transaction; None leaves a field alone and an empty merchant or notes clears it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateTransaction {
+    pub id: i64,
+    pub account_id: Option<i64>,
+    pub category_id: Option<i64>,
+    pub date: Option<String>,
+    #[serde(rename = "amount", default, with = "money::dollars::option")]
+    pub amount_cents: Option<Cents>,
+    pub description: Option<String>,
+    pub merchant: Option<String>,
+    pub notes: Option<String>,
+}
+
 impl NewTransaction {
-    pub fn calculate_hash(date: &str, amount: f64, description: &str) -> String {
+    /// Version of the duplicate-detection hash given to new and updated transactions
+    /// 1: date, amount and description as entered, shared by every account
+    /// 2: scoped to the account, with the amount in cents and the description's case and spacing ignored
+    pub const HASH_VERSION: i64 = 2;
+
+    /// The current version's hash of a transaction
+    pub fn calculate_hash(account_id: i64, date: &str, amount_cents: Cents, description: &str) -> String {
+        Self::hash_for_version(Self::HASH_VERSION, account_id, date, amount_cents, description)
+    }
+
+    /// A transaction's hash under a given version, so rows stored under an older one can still be
+    /// matched and migrated
+    pub fn hash_for_version(version: i64, account_id: i64, date: &str, amount_cents: Cents, description: &str) -> String {
         use sha2::{Digest, Sha256};
         let mut hasher = Sha256::new();
-        hasher.update(format!("{}{}{}", date, amount, description));
+        if version <= 1 {
+            // Version 1 hashed the amount as dollars
+            hasher.update(format!("{}{}{}", date, money::from_cents(amount_cents), description));
+        } else {
+            let description = description.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+            hasher.update(format!("v{}|{}|{}|{}|{}", version, account_id, date, amount_cents, description));
+        }
         format!("{:x}", hasher.finalize())
     }
 }
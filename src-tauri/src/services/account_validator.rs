@@ -0,0 +1,182 @@
+use crate::constants::{
+    CHECKING_MAX_POSITIVE_SHARE, CREDIT_CARD_MAX_POSITIVE_SHARE, MIN_ROWS_FOR_SIGN_CHECK,
+    SAVINGS_MAX_MONTHLY_TRANSACTIONS,
+};
+use crate::utils::money::{self, Cents};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportWarningKind {
+    MostlyPositive,  // Amounts look sign-inverted for the account type
+    HighVolume,      // More transactions in a month than the account type usually has
+    PositiveBalance, // A credit card with money owed to the holder
+}
+
+/// Something about an import that suggests the file or account was mixed up; never blocks the import
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportWarning {
+    pub kind: ImportWarningKind,
+    pub message: String,
+}
+
+/// What's normal for an account type
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidationProfile {
+    pub max_positive_share: Option<f64>,
+    pub max_monthly_transactions: Option<usize>,
+    pub non_positive_balance: bool,
+}
+
+pub struct AccountValidator;
+
+impl AccountValidator {
+    pub fn profile(account_type: &str) -> ValidationProfile {
+        match account_type {
+            "checking" => ValidationProfile {
+                max_positive_share: Some(CHECKING_MAX_POSITIVE_SHARE),
+                ..Default::default()
+            },
+            "savings" => ValidationProfile {
+                max_monthly_transactions: Some(SAVINGS_MAX_MONTHLY_TRANSACTIONS),
+                ..Default::default()
+            },
+            "credit_card" => ValidationProfile {
+                max_positive_share: Some(CREDIT_CARD_MAX_POSITIVE_SHARE),
+                non_positive_balance: true,
+                ..Default::default()
+            },
+            _ => ValidationProfile::default(),
+        }
+    }
+
+    /// Check (date, cents) rows and the account's balance against its type's profile
+    pub fn check(account_type: &str, balance_cents: Cents, rows: &[(String, Cents)]) -> Vec<ImportWarning> {
+        let profile = Self::profile(account_type);
+        let account = account_type.replace('_', " ");
+        let mut warnings = Vec::new();
+
+        if let Some(max_share) = profile.max_positive_share {
+            let positive = rows.iter().filter(|(_, amount_cents)| *amount_cents > 0).count();
+            let share = positive as f64 / rows.len().max(1) as f64;
+            if rows.len() >= MIN_ROWS_FOR_SIGN_CHECK && share > max_share {
+                let hint = if account_type == "credit_card" {
+                    "Card exports often list charges as positive; try flipping amount signs in the mapping."
+                } else {
+                    "Check that withdrawals aren't listed as positive amounts."
+                };
+                warnings.push(ImportWarning {
+                    kind: ImportWarningKind::MostlyPositive,
+                    message: format!(
+                        "{:.0}% of amounts are positive, which is unusual for a {} account. {}",
+                        share * 100.0,
+                        account,
+                        hint
+                    ),
+                });
+            }
+        }
+
+        if let Some(max_monthly) = profile.max_monthly_transactions {
+            let mut by_month: BTreeMap<&str, usize> = BTreeMap::new();
+            for (date, _) in rows {
+                *by_month.entry(date.get(..7).unwrap_or(date)).or_default() += 1;
+            }
+            // Report the busiest month only
+            if let Some((month, count)) = by_month.into_iter().filter(|(_, c)| *c > max_monthly).max_by_key(|(_, c)| *c) {
+                warnings.push(ImportWarning {
+                    kind: ImportWarningKind::HighVolume,
+                    message: format!(
+                        "{} transactions in {}, which is a lot for a {} account. Is this file from another account?",
+                        count, month, account
+                    ),
+                });
+            }
+        }
+
+        if profile.non_positive_balance && balance_cents > 0 {
+            warnings.push(ImportWarning {
+                kind: ImportWarningKind::PositiveBalance,
+                message: format!(
+                    "This {} account's balance is positive (${:.2}). Card balances are usually zero or negative.",
+                    account,
+                    money::from_cents(balance_cents)
+                ),
+            });
+        }
+
+        warnings
+    }
+
+    /// Warnings for importing (date, cents) rows into an account
+    pub async fn import_warnings(
+        db: &SqlitePool,
+        account_id: i64,
+        rows: &[(String, Cents)],
+    ) -> Result<Vec<ImportWarning>, String> {
+        let account = sqlx::query_as::<_, (String, Cents)>("SELECT type, balance_cents FROM accounts WHERE id = ?")
+            .bind(account_id)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        // Unknown accounts fail elsewhere; there's nothing to compare against here
+        Ok(account.map_or_else(Vec::new, |(account_type, balance)| Self::check(&account_type, balance, rows)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(amounts: &[Cents]) -> Vec<(String, Cents)> {
+        amounts.iter().map(|a| ("2025-01-15".to_string(), *a)).collect()
+    }
+
+    fn kinds(warnings: &[ImportWarning]) -> Vec<ImportWarningKind> {
+        warnings.iter().map(|w| w.kind).collect()
+    }
+
+    #[test]
+    fn test_credit_card_positive_amounts() {
+        let warnings = AccountValidator::check("credit_card", -25_000, &rows(&[1200, 4000, 850, 9900, -30_000]));
+        assert_eq!(kinds(&warnings), vec![ImportWarningKind::MostlyPositive]);
+        assert!(warnings[0].message.contains("80%"));
+
+        // Mostly charges is normal
+        assert!(AccountValidator::check("credit_card", -25_000, &rows(&[-1200, -4000, -850, -9900, 30_000])).is_empty());
+    }
+
+    #[test]
+    fn test_sign_check_needs_enough_rows() {
+        assert!(AccountValidator::check("credit_card", 0, &rows(&[1200, 4000])).is_empty());
+        assert!(AccountValidator::check("checking", 0, &rows(&[100; 4])).is_empty());
+        assert_eq!(
+            kinds(&AccountValidator::check("checking", 0, &rows(&[100; 5]))),
+            vec![ImportWarningKind::MostlyPositive]
+        );
+    }
+
+    #[test]
+    fn test_credit_card_positive_balance() {
+        assert_eq!(
+            kinds(&AccountValidator::check("credit_card", 12_500, &[])),
+            vec![ImportWarningKind::PositiveBalance]
+        );
+        // Checking balances are expected to be positive
+        assert!(AccountValidator::check("checking", 12_500, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_savings_high_volume() {
+        let mut busy: Vec<(String, Cents)> = (0..31).map(|_| ("2025-03-02".to_string(), -500)).collect();
+        busy.push(("2025-04-01".to_string(), 1000));
+        let warnings = AccountValidator::check("savings", 100_000, &busy);
+        assert_eq!(kinds(&warnings), vec![ImportWarningKind::HighVolume]);
+        assert!(warnings[0].message.contains("31 transactions in 2025-03"));
+
+        assert!(AccountValidator::check("savings", 100_000, &busy[..30]).is_empty());
+    }
+}
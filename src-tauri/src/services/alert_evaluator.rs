@@ -0,0 +1,318 @@
+use crate::constants::{
+    ALERT_HISTORY_DAYS, ALERT_LARGE_TRANSACTION_DAYS, ALERT_LARGE_TRANSACTION_MULTIPLIER,
+    ALERT_MIN_HISTORY_TRANSACTIONS, ALERT_PAYMENT_DUE_DAYS, ALERT_TARGET_PERCENT, SPENDING_ON_TRACK_THRESHOLD_PERCENT,
+};
+use crate::models::alert::{AlertType, NewAlert};
+use crate::models::notification::NotificationSeverity;
+use crate::services::payment_scheduler::PaymentScheduler;
+use crate::services::target_tracker::{TargetProgress, TargetTracker};
+use crate::utils::money::{self, Cents};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use sqlx::SqlitePool;
+
+/// A recent expense and its category's history before it
+#[derive(Debug, Clone)]
+pub struct RecentExpense {
+    pub transaction_id: i64,
+    pub date: String,
+    pub amount_cents: Cents, // Negative, like all expenses
+    pub description: String,
+    pub category_name: String,
+    pub history_average: Option<f64>, // Average expense in the category over the history window
+    pub history_count: i64,
+}
+
+/// An open debt's next due date and what has been paid since the previous one
+#[derive(Debug, Clone)]
+pub struct DueDebt {
+    pub debt_id: i64,
+    pub name: String,
+    pub min_payment_cents: Cents,
+    pub due_date: NaiveDate,
+    pub paid_this_cycle_cents: Cents,
+}
+
+pub struct AlertEvaluator;
+
+impl AlertEvaluator {
+    fn format_date(date: NaiveDate) -> String {
+        date.format("%Y-%m-%d").to_string()
+    }
+
+    /// Everything that deserves an alert as of `today`
+    ///
+    /// Alerts that were already raised come back too; storing them by `alert_key`
+    /// is what keeps each event to a single alert.
+    pub async fn evaluate(db: &SqlitePool, today: NaiveDate) -> Result<Vec<NewAlert>, String> {
+        let month_start = today.with_day(1).unwrap_or(today);
+        let month = month_start.format("%Y-%m").to_string();
+        let progress =
+            TargetTracker::get_targets_progress(db, &Self::format_date(month_start), &Self::format_date(today)).await?;
+
+        let mut alerts: Vec<NewAlert> = progress
+            .targets
+            .iter()
+            .filter_map(|target| Self::target_alert(target, &month))
+            .collect();
+        alerts.extend(Self::recent_expenses(db, today).await?.iter().filter_map(Self::large_transaction_alert));
+        alerts.extend(Self::due_debts(db, today).await?.iter().filter_map(|debt| Self::payment_due_alert(debt, today)));
+
+        Ok(alerts)
+    }
+
+    /// A target at or past the alert threshold this month; going over raises a second, critical alert
+    pub fn target_alert(target: &TargetProgress, month: &str) -> Option<NewAlert> {
+        if target.target_amount_cents <= 0 || target.percentage_used < ALERT_TARGET_PERCENT {
+            return None;
+        }
+
+        let over = target.percentage_used > SPENDING_ON_TRACK_THRESHOLD_PERCENT;
+        let (level, severity, title) = if over {
+            ("over", NotificationSeverity::Critical, format!("Over target: {}", target.category_name))
+        } else {
+            (
+                "near",
+                NotificationSeverity::Warning,
+                format!("{} target {:.0}% used", target.category_name, target.percentage_used),
+            )
+        };
+
+        // Category targets keep keying alerts by category id, as they did before other scopes existed
+        let (subject, entity_type, entity_id) = match (target.category_id, target.account_id) {
+            (Some(category_id), _) => (category_id.to_string(), Some("category"), Some(category_id)),
+            (None, Some(account_id)) => (format!("account-{}", account_id), Some("account"), Some(account_id)),
+            (None, None) => ("overall".to_string(), None, None),
+        };
+
+        Some(NewAlert {
+            alert_type: AlertType::TargetThreshold,
+            alert_key: format!("{}:{}:{}:{}", AlertType::TargetThreshold, subject, month, level),
+            severity,
+            title,
+            message: format!(
+                "${:.2} of ${:.2} spent in {}",
+                money::from_cents(target.actual_amount_cents),
+                money::from_cents(target.target_amount_cents),
+                month
+            ),
+            entity_type,
+            entity_id,
+        })
+    }
+
+    /// An expense at least `ALERT_LARGE_TRANSACTION_MULTIPLIER` times its category's average
+    /// Categories without enough history never look unusual
+    pub fn large_transaction_alert(expense: &RecentExpense) -> Option<NewAlert> {
+        let average = expense.history_average.filter(|avg| *avg > 0.0)?;
+        let spent = money::from_cents(-expense.amount_cents);
+        if expense.history_count < ALERT_MIN_HISTORY_TRANSACTIONS || spent < average * ALERT_LARGE_TRANSACTION_MULTIPLIER {
+            return None;
+        }
+
+        Some(NewAlert {
+            alert_type: AlertType::LargeTransaction,
+            alert_key: format!("{}:{}", AlertType::LargeTransaction, expense.transaction_id),
+            severity: NotificationSeverity::Warning,
+            title: format!("Unusually large {} expense", expense.category_name),
+            message: format!(
+                "{}: ${:.2} on {}, {:.1}x the usual ${:.2}",
+                expense.description,
+                spent,
+                expense.date,
+                spent / average,
+                average
+            ),
+            entity_type: Some("transaction"),
+            entity_id: Some(expense.transaction_id),
+        })
+    }
+
+    /// A minimum payment due within `ALERT_PAYMENT_DUE_DAYS` that hasn't been paid this cycle
+    pub fn payment_due_alert(debt: &DueDebt, today: NaiveDate) -> Option<NewAlert> {
+        let days_left = (debt.due_date - today).num_days();
+        let remaining = debt.min_payment_cents - debt.paid_this_cycle_cents;
+        if !(0..=ALERT_PAYMENT_DUE_DAYS).contains(&days_left) || remaining <= 0 {
+            return None;
+        }
+
+        let when = match days_left {
+            0 => "today".to_string(),
+            1 => "tomorrow".to_string(),
+            days => format!("in {} days", days),
+        };
+
+        Some(NewAlert {
+            alert_type: AlertType::PaymentDue,
+            alert_key: format!("{}:{}:{}", AlertType::PaymentDue, debt.debt_id, Self::format_date(debt.due_date)),
+            severity: NotificationSeverity::Warning,
+            title: format!("{} payment due {}", debt.name, when),
+            message: format!("${:.2} minimum payment due {}", money::from_cents(remaining), Self::format_date(debt.due_date)),
+            entity_type: Some("debt"),
+            entity_id: Some(debt.debt_id),
+        })
+    }
+
+    /// Expenses from the last `ALERT_LARGE_TRANSACTION_DAYS` days with their category history
+    async fn recent_expenses(db: &SqlitePool, today: NaiveDate) -> Result<Vec<RecentExpense>, String> {
+        let since = today - Duration::days(ALERT_LARGE_TRANSACTION_DAYS - 1);
+        let history = format!("-{} days", ALERT_HISTORY_DAYS);
+
+        let rows = sqlx::query_as::<_, (i64, String, Cents, String, Option<String>, Option<f64>, i64)>(
+            "SELECT t.id, t.date, t.amount_cents, t.description, c.name,
+                    (SELECT AVG(-h.amount_cents) FROM transactions h
+                     WHERE h.category_id = t.category_id AND h.amount_cents < 0
+                       AND h.date >= date(t.date, ?) AND h.date < t.date),
+                    (SELECT COUNT(*) FROM transactions h
+                     WHERE h.category_id = t.category_id AND h.amount_cents < 0
+                       AND h.date >= date(t.date, ?) AND h.date < t.date)
+             FROM transactions t
+             LEFT JOIN categories c ON c.id = t.category_id
+             WHERE t.amount_cents < 0 AND t.date >= ? AND t.date <= ?"
+        )
+        .bind(&history)
+        .bind(&history)
+        .bind(Self::format_date(since))
+        .bind(Self::format_date(today))
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(transaction_id, date, amount_cents, description, category_name, history_average, history_count)| RecentExpense {
+                    transaction_id,
+                    date,
+                    amount_cents,
+                    description,
+                    category_name: category_name.unwrap_or_else(|| "Uncategorized".to_string()),
+                    history_average: history_average.map(|cents| cents / money::CENTS_PER_DOLLAR),
+                    history_count,
+                },
+            )
+            .collect())
+    }
+
+    /// Open debts that existed by `today`, with their next due date and payments since the previous one
+    async fn due_debts(db: &SqlitePool, today: NaiveDate) -> Result<Vec<DueDebt>, String> {
+        let debts = sqlx::query_as::<_, (i64, String, Cents, u32)>(
+            "SELECT id, name, min_payment_cents, due_day FROM debts
+             WHERE balance_cents > 0 AND min_payment_cents > 0 AND date(created_at) <= ?
+             ORDER BY name"
+        )
+        .bind(Self::format_date(today))
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let mut due_debts = Vec::with_capacity(debts.len());
+        for (debt_id, name, min_payment_cents, due_day) in debts {
+            let due_date = PaymentScheduler::next_due_date_on(today, due_day);
+            let previous_due = due_date
+                .with_day(1)
+                .and_then(|first| first.checked_sub_months(Months::new(1)))
+                .map(|month| PaymentScheduler::next_due_date_on(month, due_day))
+                .unwrap_or(due_date);
+
+            let (paid_this_cycle_cents,): (Cents,) = sqlx::query_as(
+                "SELECT COALESCE(SUM(amount_cents), 0) FROM debt_payments
+                 WHERE debt_id = ? AND date > ? AND date <= ?"
+            )
+            .bind(debt_id)
+            .bind(Self::format_date(previous_due))
+            .bind(Self::format_date(today))
+            .fetch_one(db)
+            .await
+            .map_err(|e| e.to_string())?;
+
+            due_debts.push(DueDebt {
+                debt_id,
+                name,
+                min_payment_cents,
+                due_date,
+                paid_this_cycle_cents,
+            });
+        }
+        Ok(due_debts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn target(actual_amount_cents: Cents, target_amount_cents: Cents) -> TargetProgress {
+        TargetProgress {
+            target_id: 1,
+            scope: "category".to_string(),
+            category_id: Some(4),
+            account_id: None,
+            category_name: "Dining".to_string(),
+            target_amount_cents,
+            actual_amount_cents,
+            remaining_cents: target_amount_cents - actual_amount_cents,
+            percentage_used: actual_amount_cents as f64 / target_amount_cents as f64 * 100.0,
+            status: String::new(),
+            variance_cents: actual_amount_cents - target_amount_cents,
+        }
+    }
+
+    #[test]
+    fn test_target_alert_levels() {
+        assert!(AlertEvaluator::target_alert(&target(8000, 10_000), "2025-03").is_none());
+
+        let near = AlertEvaluator::target_alert(&target(9200, 10_000), "2025-03").unwrap();
+        assert_eq!(near.alert_key, "target_threshold:4:2025-03:near");
+        assert_eq!(near.severity, NotificationSeverity::Warning);
+        assert_eq!(near.title, "Dining target 92% used");
+
+        let over = AlertEvaluator::target_alert(&target(13_000, 10_000), "2025-03").unwrap();
+        assert_eq!(over.alert_key, "target_threshold:4:2025-03:over");
+        assert_eq!(over.severity, NotificationSeverity::Critical);
+    }
+
+    #[test]
+    fn test_large_transaction_alert() {
+        let expense = |amount_cents: Cents, history_count: i64| RecentExpense {
+            transaction_id: 7,
+            date: "2025-03-10".to_string(),
+            amount_cents,
+            description: "Steakhouse".to_string(),
+            category_name: "Dining".to_string(),
+            history_average: Some(40.0),
+            history_count,
+        };
+
+        let alert = AlertEvaluator::large_transaction_alert(&expense(-15_000, 10)).unwrap();
+        assert_eq!(alert.alert_key, "large_transaction:7");
+        assert_eq!(alert.message, "Steakhouse: $150.00 on 2025-03-10, 3.8x the usual $40.00");
+        assert!(AlertEvaluator::large_transaction_alert(&expense(-10_000, 10)).is_none());
+        // Not enough history to judge
+        assert!(AlertEvaluator::large_transaction_alert(&expense(-15_000, 2)).is_none());
+    }
+
+    #[test]
+    fn test_payment_due_alert() {
+        let debt = |due: &str, paid_this_cycle_cents: Cents| DueDebt {
+            debt_id: 3,
+            name: "Visa".to_string(),
+            min_payment_cents: 5000,
+            due_date: date(due),
+            paid_this_cycle_cents,
+        };
+
+        let alert = AlertEvaluator::payment_due_alert(&debt("2025-03-15", 2000), date("2025-03-12")).unwrap();
+        assert_eq!(alert.alert_key, "payment_due:3:2025-03-15");
+        assert_eq!(alert.title, "Visa payment due in 3 days");
+        assert_eq!(alert.message, "$30.00 minimum payment due 2025-03-15");
+
+        let today = AlertEvaluator::payment_due_alert(&debt("2025-03-15", 0), date("2025-03-15")).unwrap();
+        assert_eq!(today.title, "Visa payment due today");
+        assert!(AlertEvaluator::payment_due_alert(&debt("2025-03-15", 0), date("2025-03-01")).is_none());
+        assert!(AlertEvaluator::payment_due_alert(&debt("2025-03-15", 5000), date("2025-03-12")).is_none());
+    }
+}
@@ -0,0 +1,140 @@
+// Amortization schedules for fixed-term loans
+//
+// A loan is paid off in `term_months` equal payments, the first one month after it
+// starts. Each payment covers the month's interest on the remaining principal and the
+// rest pays principal down; the last payment is adjusted to clear whatever rounding left.
+
+use crate::constants::{MONTHS_PER_YEAR, PERCENT_TO_DECIMAL_DIVISOR};
+use crate::utils::dates;
+use crate::utils::money::{self, Cents};
+use chrono::{Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmortizationRow {
+    pub number: u32,
+    pub date: String,
+    #[serde(rename = "payment", with = "money::dollars")]
+    pub payment_cents: Cents,
+    #[serde(rename = "interest", with = "money::dollars")]
+    pub interest_cents: Cents,
+    #[serde(rename = "principal", with = "money::dollars")]
+    pub principal_cents: Cents,
+    #[serde(rename = "balance", with = "money::dollars")]
+    pub balance_cents: Cents, // Principal left after this payment
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmortizationSchedule {
+    pub debt_id: i64,
+    #[serde(rename = "principal", with = "money::dollars")]
+    pub principal_cents: Cents,
+    pub interest_rate: f64,
+    pub term_months: u32,
+    pub start_date: String,
+    #[serde(rename = "monthly_payment", with = "money::dollars")]
+    pub monthly_payment_cents: Cents,
+    #[serde(rename = "total_interest", with = "money::dollars")]
+    pub total_interest_cents: Cents,
+    #[serde(rename = "total_paid", with = "money::dollars")]
+    pub total_paid_cents: Cents,
+    pub rows: Vec<AmortizationRow>,
+}
+
+/// The fixed monthly payment that pays `principal` off in `term_months` at an annual
+/// percentage `rate`, rounded to a cent
+pub fn monthly_payment(principal: Cents, annual_rate: f64, term_months: u32) -> Cents {
+    if term_months == 0 || principal <= 0 {
+        return principal.max(0);
+    }
+    let n = term_months as f64;
+    let rate = annual_rate / PERCENT_TO_DECIMAL_DIVISOR / MONTHS_PER_YEAR;
+    if rate <= 0.0 {
+        return (principal as f64 / n).ceil() as Cents;
+    }
+    (principal as f64 * rate / (1.0 - (1.0 + rate).powf(-n))).round() as Cents
+}
+
+/// Every payment of a loan, from the first (a month after `start_date`) to the last
+pub fn schedule(principal: Cents, annual_rate: f64, term_months: u32, start_date: NaiveDate) -> Vec<AmortizationRow> {
+    let payment = monthly_payment(principal, annual_rate, term_months);
+    let mut balance = principal;
+    let mut rows = Vec::with_capacity(term_months as usize);
+
+    for number in 1..=term_months {
+        if balance <= 0 {
+            break;
+        }
+        let Some(date) = start_date.checked_add_months(Months::new(number)) else {
+            break;
+        };
+        let interest = money::monthly_interest(balance, annual_rate);
+        // The last payment clears the balance, whatever rounding left over
+        let principal_paid = if number == term_months {
+            balance
+        } else {
+            (payment - interest).clamp(0, balance)
+        };
+        balance -= principal_paid;
+
+        rows.push(AmortizationRow {
+            number,
+            date: dates::format_date(date),
+            payment_cents: interest + principal_paid,
+            interest_cents: interest,
+            principal_cents: principal_paid,
+            balance_cents: balance,
+        });
+    }
+    rows
+}
+
+/// Principal left on `date` when every payment due by then was made on schedule
+pub fn balance_on(principal: Cents, annual_rate: f64, term_months: u32, start_date: NaiveDate, date: NaiveDate) -> Cents {
+    schedule(principal, annual_rate, term_months, start_date)
+        .iter()
+        .take_while(|row| dates::parse_date(&row.date).is_ok_and(|due| due <= date))
+        .last()
+        .map_or(principal, |row| row.balance_cents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        dates::parse_date(s).unwrap()
+    }
+
+    #[test]
+    fn test_monthly_payment_matches_the_standard_formula() {
+        // $20,000 over 60 months at 6% APR is $386.66 a month
+        assert_eq!(monthly_payment(2_000_000, 6.0, 60), 38_666);
+        // Interest-free loans split the principal evenly, rounded up
+        assert_eq!(monthly_payment(100_000, 0.0, 3), 33_334);
+    }
+
+    #[test]
+    fn test_schedule_pays_the_loan_off_in_its_term() {
+        let rows = schedule(2_000_000, 6.0, 60, date("2024-01-31"));
+
+        assert_eq!(rows.len(), 60);
+        assert_eq!(rows[0].date, "2024-02-29");
+        assert_eq!(rows[0].interest_cents, 10_000);
+        assert_eq!(rows[0].principal_cents, 28_666);
+        assert_eq!(rows[59].date, "2029-01-31");
+        assert_eq!(rows[59].balance_cents, 0);
+        let principal: Cents = rows.iter().map(|r| r.principal_cents).sum();
+        assert_eq!(principal, 2_000_000);
+        // Only the last payment differs, by the rounding it absorbs
+        assert!((rows[59].payment_cents - 38_666).abs() < 100);
+    }
+
+    #[test]
+    fn test_balance_on_counts_payments_due_by_then() {
+        let start = date("2024-01-15");
+        assert_eq!(balance_on(100_000, 0.0, 4, start, date("2024-02-14")), 100_000);
+        assert_eq!(balance_on(100_000, 0.0, 4, start, date("2024-02-15")), 75_000);
+        assert_eq!(balance_on(100_000, 0.0, 4, start, date("2030-01-01")), 0);
+    }
+}
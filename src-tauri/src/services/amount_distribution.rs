@@ -0,0 +1,118 @@
+// How transaction amounts are spread: a histogram and the usual percentiles
+//
+// Amounts are absolute, so "most purchases are under $40" reads the same for charges
+// and refunds. Percentiles use the nearest-rank method: p90 is an amount some
+// transaction actually had, at or above 90% of them.
+
+use crate::constants::PERCENT_TO_DECIMAL_DIVISOR;
+use crate::utils::money::{self, Cents};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmountBucket {
+    #[serde(rename = "min", with = "money::dollars")]
+    pub min_cents: Cents, // Amounts above this (or from zero, for the first bucket)
+    #[serde(rename = "max", default, with = "money::dollars::option")]
+    pub max_cents: Option<Cents>, // Up to and including this; None for the last, open-ended bucket
+    pub count: i64,
+    #[serde(rename = "total", with = "money::dollars")]
+    pub total_cents: Cents,
+    pub percentage: f64, // Share of the transactions counted
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmountDistribution {
+    pub count: i64,
+    #[serde(rename = "total", with = "money::dollars")]
+    pub total_cents: Cents,
+    pub average: f64,
+    #[serde(rename = "p50", with = "money::dollars")]
+    pub p50_cents: Cents,
+    #[serde(rename = "p90", with = "money::dollars")]
+    pub p90_cents: Cents,
+    #[serde(rename = "p99", with = "money::dollars")]
+    pub p99_cents: Cents, // Percentiles are zero when no transactions match
+    pub buckets: Vec<AmountBucket>,
+}
+
+/// The nearest-rank `percent`th percentile of amounts sorted smallest first; zero when there are none
+pub fn percentile(sorted: &[Cents], percent: f64) -> Cents {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (percent * sorted.len() as f64 / PERCENT_TO_DECIMAL_DIVISOR).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Summarize absolute amounts in cents, sorted smallest first, into `bounds` (ascending upper bounds)
+pub fn distribution(sorted: &[Cents], bounds: &[Cents]) -> AmountDistribution {
+    let count = sorted.len() as i64;
+    let total: Cents = sorted.iter().sum();
+
+    let mut buckets = Vec::with_capacity(bounds.len() + 1);
+    let mut lower = 0;
+    let mut rest = sorted;
+    for max in bounds.iter().copied().map(Some).chain([None]) {
+        let taken = match max {
+            Some(max) => rest.partition_point(|&cents| cents <= max),
+            None => rest.len(),
+        };
+        let (inside, remaining) = rest.split_at(taken);
+        rest = remaining;
+        buckets.push(AmountBucket {
+            min_cents: lower,
+            max_cents: max,
+            count: inside.len() as i64,
+            total_cents: inside.iter().sum(),
+            percentage: if count > 0 { inside.len() as f64 / count as f64 * PERCENT_TO_DECIMAL_DIVISOR } else { 0.0 },
+        });
+        lower = max.unwrap_or(lower);
+    }
+
+    AmountDistribution {
+        count,
+        total_cents: total,
+        average: if count > 0 { money::round_money(money::from_cents(total) / count as f64) } else { 0.0 },
+        p50_cents: percentile(sorted, 50.0),
+        p90_cents: percentile(sorted, 90.0),
+        p99_cents: percentile(sorted, 99.0),
+        buckets,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_rank_percentiles() {
+        let sorted: Vec<Cents> = (1..=10).map(|n| n * 100).collect();
+        assert_eq!(percentile(&sorted, 50.0), 500);
+        assert_eq!(percentile(&sorted, 90.0), 900);
+        assert_eq!(percentile(&sorted, 99.0), 1000);
+        assert_eq!(percentile(&sorted, 0.0), 100);
+        assert_eq!(percentile(&[], 50.0), 0);
+    }
+
+    #[test]
+    fn test_buckets_include_their_upper_bound() {
+        let result = distribution(&[500, 1000, 1001, 2500, 90_000], &[1000, 2500]);
+
+        let buckets: Vec<(Cents, Option<Cents>, i64, Cents)> =
+            result.buckets.iter().map(|b| (b.min_cents, b.max_cents, b.count, b.total_cents)).collect();
+        assert_eq!(buckets, vec![(0, Some(1000), 2, 1500), (1000, Some(2500), 2, 3501), (2500, None, 1, 90_000)]);
+        assert_eq!(result.buckets[0].percentage, 40.0);
+        assert_eq!(result.count, 5);
+        assert_eq!(result.total_cents, 95_001);
+        assert_eq!(result.p50_cents, 1001);
+    }
+
+    #[test]
+    fn test_empty_distribution_keeps_its_buckets() {
+        let result = distribution(&[], &[1000]);
+        assert_eq!(result.count, 0);
+        assert_eq!(result.p90_cents, 0);
+        assert_eq!(result.buckets.len(), 2);
+        assert!(result.buckets.iter().all(|b| b.count == 0 && b.percentage == 0.0));
+    }
+}
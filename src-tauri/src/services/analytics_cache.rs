@@ -0,0 +1,155 @@
+// In-memory cache for expensive analytics queries
+// Results are kept per query and parameters until the data they were computed from changes
+
+use crate::constants::MAX_ANALYTICS_CACHE_ENTRIES;
+use serde::Serialize;
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+struct CacheEntry {
+    data_version: u64, // Version of the data the value was computed from
+    value: Arc<dyn Any + Send + Sync>,
+}
+
+/// Analytics results keyed on query name and parameters, held in managed state
+/// Every change to the data bumps a version counter, which makes all earlier results stale
+#[derive(Default)]
+pub struct AnalyticsCache {
+    data_version: AtomicU64,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl AnalyticsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache key for `query` run with `params`
+    pub fn key(query: &str, params: &impl Serialize) -> String {
+        format!("{}:{}", query, serde_json::to_string(params).unwrap_or_default())
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, CacheEntry>> {
+        match self.entries.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                tracing::warn!("Analytics cache mutex was poisoned, recovering");
+                poisoned.into_inner()
+            }
+        }
+    }
+
+    /// Current version of the data; changes whenever the cache is invalidated
+    pub fn data_version(&self) -> u64 {
+        self.data_version.load(Ordering::SeqCst)
+    }
+
+    /// Mark every cached result stale, e.g. after transactions change; returns the new data version
+    pub fn invalidate(&self) -> u64 {
+        let version = self.data_version.fetch_add(1, Ordering::SeqCst) + 1;
+        self.entries().clear();
+        version
+    }
+
+    /// Number of results currently cached
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The cached result for `key`, if it was computed from the current data
+    pub fn get<T: Clone + 'static>(&self, key: &str) -> Option<T> {
+        let version = self.data_version();
+        self.entries()
+            .get(key)
+            .filter(|entry| entry.data_version == version)
+            .and_then(|entry| entry.value.downcast_ref::<T>())
+            .cloned()
+    }
+
+    /// The cached result for `key`, or `compute`'s, which is cached unless the data changed
+    /// while it ran. Errors aren't cached
+    pub async fn get_or_compute<T, F, Fut>(&self, key: String, compute: F) -> Result<T, String>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, String>>,
+    {
+        if let Some(value) = self.get::<T>(&key) {
+            return Ok(value);
+        }
+
+        let version = self.data_version();
+        let value = compute().await?;
+
+        let mut entries = self.entries();
+        if self.data_version() == version {
+            if entries.len() >= MAX_ANALYTICS_CACHE_ENTRIES && !entries.contains_key(&key) {
+                entries.clear();
+            }
+            entries.insert(key, CacheEntry { data_version: version, value: Arc::new(value.clone()) });
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    async fn counted(cache: &AnalyticsCache, key: &str, calls: &AtomicUsize) -> Result<i64, String> {
+        cache
+            .get_or_compute(key.to_string(), || async {
+                Ok(calls.fetch_add(1, Ordering::SeqCst) as i64)
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_results_are_reused_until_invalidated() {
+        let cache = AnalyticsCache::new();
+        let calls = AtomicUsize::new(0);
+        let key = AnalyticsCache::key("spending", &("2024-01-01", "2024-01-31", None::<i64>));
+
+        assert_eq!(counted(&cache, &key, &calls).await, Ok(0));
+        assert_eq!(counted(&cache, &key, &calls).await, Ok(0), "Second call is served from the cache");
+        assert_eq!(counted(&cache, "other", &calls).await, Ok(1), "Other parameters are cached apart");
+
+        assert_eq!(cache.invalidate(), 1);
+        assert!(cache.is_empty());
+        assert_eq!(counted(&cache, &key, &calls).await, Ok(2));
+    }
+
+    #[tokio::test]
+    async fn test_result_computed_across_a_change_is_not_kept() {
+        let cache = AnalyticsCache::new();
+        let value: Result<i64, String> = cache
+            .get_or_compute("dashboard".to_string(), || async {
+                cache.invalidate(); // Data changes while the query runs
+                Ok(7)
+            })
+            .await;
+        assert_eq!(value, Ok(7));
+        assert!(cache.get::<i64>("dashboard").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_errors_and_mismatched_types_are_not_served() {
+        let cache = AnalyticsCache::new();
+        let failed: Result<i64, String> =
+            cache.get_or_compute("trends".to_string(), || async { Err("boom".to_string()) }).await;
+        assert!(failed.is_err());
+        assert!(cache.is_empty());
+
+        let _ = cache.get_or_compute("trends".to_string(), || async { Ok(3_i64) }).await;
+        assert_eq!(cache.get::<i64>("trends"), Some(3));
+        assert_eq!(cache.get::<String>("trends"), None);
+    }
+}
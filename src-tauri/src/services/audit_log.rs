@@ -0,0 +1,130 @@
+//! Audit trail of data changes made through mutating commands.
+//!
+//! Commands build an [`AuditRecord`] after a change succeeds and record it on the
+//! same pool or database transaction as the change. Recording is best effort: a
+//! failure is logged and never fails the command that made the change.
+//!
+//! # Example
+//! ```no_run
+//! use budget_balancer_lib::models::audit_log::AuditEntity;
+//! use budget_balancer_lib::services::audit_log::AuditRecord;
+//!
+//! # async fn example(db: &sqlx::SqlitePool) {
+//! AuditRecord::updated(AuditEntity::Transaction, 42)
+//!     .old_values(&serde_json::json!({ "category_id": 10 }))
+//!     .new_values(&serde_json::json!({ "category_id": 3 }))
+//!     .record(db)
+//!     .await;
+//! # }
+//! ```
+
+use crate::models::audit_log::{AuditAction, AuditEntity};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::SqliteExecutor;
+
+/// One change, built up before it is written to the audit log
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    pub action: AuditAction,
+    pub entity_type: AuditEntity,
+    pub entity_id: Option<i64>,
+    pub old_values: Option<Value>,
+    pub new_values: Option<Value>,
+}
+
+impl AuditRecord {
+    fn new(action: AuditAction, entity_type: AuditEntity, entity_id: i64) -> Self {
+        Self {
+            action,
+            entity_type,
+            entity_id: Some(entity_id),
+            old_values: None,
+            new_values: None,
+        }
+    }
+
+    pub fn created(entity_type: AuditEntity, entity_id: i64) -> Self {
+        Self::new(AuditAction::Create, entity_type, entity_id)
+    }
+
+    pub fn updated(entity_type: AuditEntity, entity_id: i64) -> Self {
+        Self::new(AuditAction::Update, entity_type, entity_id)
+    }
+
+    pub fn deleted(entity_type: AuditEntity, entity_id: i64) -> Self {
+        Self::new(AuditAction::Delete, entity_type, entity_id)
+    }
+
+    /// The entity's values before the change
+    pub fn old_values<T: Serialize + ?Sized>(mut self, values: &T) -> Self {
+        self.old_values = to_json(values);
+        self
+    }
+
+    /// The entity's values after the change
+    pub fn new_values<T: Serialize + ?Sized>(mut self, values: &T) -> Self {
+        self.new_values = to_json(values);
+        self
+    }
+
+    /// Write the record; failures are logged rather than returned
+    pub async fn record<'e, E: SqliteExecutor<'e>>(self, executor: E) {
+        let result = sqlx::query(
+            "INSERT INTO audit_log (action, entity_type, entity_id, old_values, new_values)
+             VALUES (?, ?, ?, ?, ?)"
+        )
+        .bind(self.action.to_string())
+        .bind(self.entity_type.to_string())
+        .bind(self.entity_id)
+        .bind(self.old_values.map(|v| v.to_string()))
+        .bind(self.new_values.map(|v| v.to_string()))
+        .execute(executor)
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!(
+                error = %e,
+                action = %self.action,
+                entity_type = %self.entity_type,
+                "Failed to write audit log entry"
+            );
+        }
+    }
+}
+
+fn to_json<T: Serialize + ?Sized>(values: &T) -> Option<Value> {
+    match serde_json::to_value(values) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to serialize audit values");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_serializes_values() {
+        let record = AuditRecord::updated(AuditEntity::Debt, 7)
+            .old_values(&serde_json::json!({ "balance": 100.0 }))
+            .new_values(&serde_json::json!({ "balance": 80.0 }));
+        assert_eq!(record.action, AuditAction::Update);
+        assert_eq!(record.entity_id, Some(7));
+        assert_eq!(record.old_values.unwrap()["balance"], 100.0);
+        assert_eq!(record.new_values.unwrap()["balance"], 80.0);
+    }
+
+    #[test]
+    fn test_entity_names_match_stored_values() {
+        assert_eq!(AuditEntity::DebtPayment.to_string(), "debt_payment");
+        assert_eq!(
+            serde_json::to_value(AuditEntity::DebtPayment).unwrap(),
+            serde_json::json!("debt_payment")
+        );
+        assert_eq!(AuditAction::Delete.to_string(), "delete");
+    }
+}
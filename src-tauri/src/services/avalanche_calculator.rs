@@ -1,13 +1,16 @@
-use crate::constants::{MAX_PAYOFF_YEARS, MONTHS_PER_YEAR, PERCENT_TO_DECIMAL_DIVISOR};
+use crate::constants::{MAX_PAYOFF_YEARS, MONTHS_PER_YEAR};
 use crate::errors::DebtError;
-use crate::models::debt::Debt;
+use crate::models::debt::{self, Debt};
+use crate::utils::money::{self, Cents};
+use chrono::{Months, NaiveDate};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PayoffPlan {
     pub strategy: String,
     pub payoff_date: String,
-    pub total_interest: f64,
+    #[serde(rename = "total_interest", with = "money::dollars")]
+    pub total_interest_cents: Cents,
     pub monthly_breakdown: Vec<MonthlyPayment>,
     pub debt_summaries: Vec<DebtSummary>,
 }
@@ -17,15 +20,18 @@ pub struct MonthlyPayment {
     pub month: i32,
     pub date: String,
     pub payments: Vec<DebtPaymentDetail>,
-    pub total_paid: f64,
-    pub remaining_balance: f64,
+    #[serde(rename = "total_paid", with = "money::dollars")]
+    pub total_paid_cents: Cents,
+    #[serde(rename = "remaining_balance", with = "money::dollars")]
+    pub remaining_balance_cents: Cents,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DebtPaymentDetail {
     pub debt_id: i64,
     pub debt_name: String,
-    pub amount: f64,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,34 +39,48 @@ pub struct DebtSummary {
     pub debt_id: i64,
     pub debt_name: String,
     pub payoff_month: i32,
-    pub total_interest_paid: f64,
+    #[serde(rename = "total_interest_paid", with = "money::dollars")]
+    pub total_interest_paid_cents: Cents,
 }
 
 #[derive(Debug, Clone)]
 struct DebtState {
     id: i64,
     name: String,
-    balance: f64,
+    balance: Cents,
     interest_rate: f64,
-    min_payment: f64,
-    total_interest_paid: f64,
+    min_payment: Cents,
+    min_payment_percent: Option<f64>,
+    total_interest_paid: Cents,
     payoff_month: Option<i32>,
 }
 
 pub struct AvalancheCalculator;
 
 impl AvalancheCalculator {
-    pub fn calculate_payoff_plan(debts: Vec<Debt>, monthly_amount: f64) -> Result<PayoffPlan, DebtError> {
+    /// Month 1 is paid on `start_date` and each later month on the same day of the following
+    /// calendar months (the last day in shorter months)
+    pub fn calculate_payoff_plan(
+        debts: Vec<Debt>,
+        monthly_amount: Cents,
+        start_date: NaiveDate,
+    ) -> Result<PayoffPlan, DebtError> {
         if debts.is_empty() {
             return Err(DebtError::NoDebts);
         }
 
-        // Validate monthly amount covers minimum payments
-        let total_min_payments: f64 = debts.iter().map(|d| d.min_payment).sum();
+        // Validate monthly amount covers the first month's minimum payments
+        let total_min_payments: Cents = debts
+            .iter()
+            .map(|d| {
+                let statement_balance = d.balance_cents + money::monthly_interest(d.balance_cents, d.interest_rate);
+                debt::minimum_payment(d.min_payment_cents, d.min_payment_percent, statement_balance)
+            })
+            .sum();
         if monthly_amount < total_min_payments {
             return Err(DebtError::InsufficientFunds {
-                monthly: monthly_amount,
-                min_payments: total_min_payments,
+                monthly: money::from_cents(monthly_amount),
+                min_payments: money::from_cents(total_min_payments),
             });
         }
 
@@ -70,10 +90,11 @@ impl AvalancheCalculator {
             .map(|d| DebtState {
                 id: d.id,
                 name: d.name.clone(),
-                balance: d.balance,
+                balance: d.balance_cents,
                 interest_rate: d.interest_rate,
-                min_payment: d.min_payment,
-                total_interest_paid: 0.0,
+                min_payment: d.min_payment_cents,
+                min_payment_percent: d.min_payment_percent,
+                total_interest_paid: 0,
                 payoff_month: None,
             })
             .collect();
@@ -82,16 +103,18 @@ impl AvalancheCalculator {
 
         let mut monthly_breakdown = Vec::new();
         let mut month: i32 = 1;
-        let start_date = chrono::Local::now().date_naive();
 
         // Simulate month-by-month payments until all debts paid off
-        while debt_states.iter().any(|d| d.balance > 0.01) {
-            let current_date = start_date + chrono::Duration::days(((month - 1) * 30) as i64);
+        while debt_states.iter().any(|d| d.balance > 0) {
+            // Always counted from the start date, so a short month doesn't pull later dates back
+            let current_date = start_date
+                .checked_add_months(Months::new((month - 1) as u32))
+                .ok_or(DebtError::PayoffExceeded(MAX_PAYOFF_YEARS))?;
 
             // Apply interest to all debts
             for debt in &mut debt_states {
-                if debt.balance > 0.01 {
-                    let monthly_interest = debt.balance * (debt.interest_rate / PERCENT_TO_DECIMAL_DIVISOR / MONTHS_PER_YEAR);
+                if debt.balance > 0 {
+                    let monthly_interest = money::monthly_interest(debt.balance, debt.interest_rate);
                     debt.balance += monthly_interest;
                     debt.total_interest_paid += monthly_interest;
                 }
@@ -100,56 +123,56 @@ impl AvalancheCalculator {
             let mut remaining_amount = monthly_amount;
             let mut payments = Vec::new();
 
-            // Pay minimums on all debts first
+            // Pay minimums on all debts first, recomputed on this month's balance
             for debt in &mut debt_states {
-                if debt.balance > 0.01 {
-                    let payment = debt.min_payment.min(debt.balance);
+                if debt.balance > 0 {
+                    let payment = debt::minimum_payment(debt.min_payment, debt.min_payment_percent, debt.balance);
                     debt.balance -= payment;
                     remaining_amount -= payment;
                     payments.push(DebtPaymentDetail {
                         debt_id: debt.id,
                         debt_name: debt.name.clone(),
-                        amount: payment,
+                        amount_cents: payment,
                     });
 
-                    if debt.balance < 0.01 && debt.payoff_month.is_none() {
+                    if debt.balance == 0 && debt.payoff_month.is_none() {
                         debt.payoff_month = Some(month);
                     }
                 }
             }
 
             // Allocate extra payment to highest interest rate debt with balance remaining
-            if remaining_amount > 0.01 {
-                if let Some(target_debt) = debt_states.iter_mut().find(|d| d.balance > 0.01) {
+            if remaining_amount > 0 {
+                if let Some(target_debt) = debt_states.iter_mut().find(|d| d.balance > 0) {
                     let extra_payment = remaining_amount.min(target_debt.balance);
                     target_debt.balance -= extra_payment;
 
                     // Add to existing payment or create new one
                     if let Some(payment_detail) = payments.iter_mut().find(|p| p.debt_id == target_debt.id) {
-                        payment_detail.amount += extra_payment;
+                        payment_detail.amount_cents += extra_payment;
                     } else {
                         payments.push(DebtPaymentDetail {
                             debt_id: target_debt.id,
                             debt_name: target_debt.name.clone(),
-                            amount: extra_payment,
+                            amount_cents: extra_payment,
                         });
                     }
 
-                    if target_debt.balance < 0.01 && target_debt.payoff_month.is_none() {
+                    if target_debt.balance == 0 && target_debt.payoff_month.is_none() {
                         target_debt.payoff_month = Some(month);
                     }
                 }
             }
 
-            let total_paid: f64 = payments.iter().map(|p| p.amount).sum();
-            let remaining_balance: f64 = debt_states.iter().map(|d| d.balance).sum();
+            let total_paid_cents = payments.iter().map(|p| p.amount_cents).sum();
+            let remaining_balance_cents = debt_states.iter().map(|d| d.balance).sum();
 
             monthly_breakdown.push(MonthlyPayment {
                 month,
                 date: current_date.format("%Y-%m-%d").to_string(),
                 payments,
-                total_paid,
-                remaining_balance,
+                total_paid_cents,
+                remaining_balance_cents,
             });
 
             month += 1;
@@ -160,7 +183,7 @@ impl AvalancheCalculator {
             }
         }
 
-        let total_interest: f64 = debt_states.iter().map(|d| d.total_interest_paid).sum();
+        let total_interest_cents = debt_states.iter().map(|d| d.total_interest_paid).sum();
         let payoff_date = monthly_breakdown.last().map(|m| m.date.clone()).unwrap_or_default();
 
         let debt_summaries: Vec<DebtSummary> = debt_states
@@ -169,14 +192,14 @@ impl AvalancheCalculator {
                 debt_id: d.id,
                 debt_name: d.name.clone(),
                 payoff_month: d.payoff_month.unwrap_or(0),
-                total_interest_paid: d.total_interest_paid,
+                total_interest_paid_cents: d.total_interest_paid,
             })
             .collect();
 
         Ok(PayoffPlan {
             strategy: "avalanche".to_string(),
             payoff_date,
-            total_interest,
+            total_interest_cents,
             monthly_breakdown,
             debt_summaries,
         })
@@ -187,35 +210,51 @@ impl AvalancheCalculator {
 mod tests {
     use super::*;
 
+    fn start() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+    }
+
     #[test]
     fn test_avalanche_calculation_prioritizes_high_interest() {
         let debts = vec![
             Debt {
                 id: 1,
                 name: "Low Interest Card".to_string(),
-                balance: 1000.0,
-                original_balance: 1000.0,
+                balance_cents: 100_000,
+                original_balance_cents: 100_000,
                 interest_rate: 10.0,
-                min_payment: 25.0,
+                min_payment_cents: 2500,
+                min_payment_percent: None,
+                kind: "revolving".to_string(),
+                term_months: None,
+                loan_start_date: None,
+                due_day: 15,
+                account_id: None,
                 created_at: "2025-01-01".to_string(),
                 updated_at: "2025-01-01".to_string(),
             },
             Debt {
                 id: 2,
                 name: "High Interest Card".to_string(),
-                balance: 1000.0,
-                original_balance: 1000.0,
+                balance_cents: 100_000,
+                original_balance_cents: 100_000,
                 interest_rate: 20.0,
-                min_payment: 25.0,
+                min_payment_cents: 2500,
+                min_payment_percent: None,
+                kind: "revolving".to_string(),
+                term_months: None,
+                loan_start_date: None,
+                due_day: 15,
+                account_id: None,
                 created_at: "2025-01-01".to_string(),
                 updated_at: "2025-01-01".to_string(),
             },
         ];
 
-        let plan = AvalancheCalculator::calculate_payoff_plan(debts, 200.0).unwrap();
+        let plan = AvalancheCalculator::calculate_payoff_plan(debts, 20_000, start()).unwrap();
 
         assert_eq!(plan.strategy, "avalanche");
-        assert!(plan.total_interest > 0.0);
+        assert!(plan.total_interest_cents > 0);
         assert!(!plan.monthly_breakdown.is_empty());
 
         // First month should have extra payment going to high interest debt (id: 2)
@@ -224,14 +263,43 @@ mod tests {
         let low_interest_payment = first_month.payments.iter().find(|p| p.debt_id == 1).unwrap();
 
         // High interest debt should get more than minimum
-        assert!(high_interest_payment.amount > 25.0);
+        assert!(high_interest_payment.amount_cents > 2500);
         // Low interest debt should get only minimum
-        assert_eq!(low_interest_payment.amount, 25.0);
+        assert_eq!(low_interest_payment.amount_cents, 2500);
 
         // Verify total_paid and remaining_balance are calculated correctly
-        assert_eq!(first_month.total_paid, 200.0);
-        assert!(first_month.remaining_balance > 0.0);
-        assert!(first_month.remaining_balance < 2000.0);
+        assert_eq!(first_month.total_paid_cents, 20_000);
+        assert!(first_month.remaining_balance_cents > 0);
+        assert!(first_month.remaining_balance_cents < 200_000);
+    }
+
+    #[test]
+    fn test_months_follow_the_calendar() {
+        let debts = vec![Debt {
+            id: 1,
+            name: "Interest-Free Loan".to_string(),
+            balance_cents: 600_000,
+            original_balance_cents: 600_000,
+            interest_rate: 0.0,
+            min_payment_cents: 10_000,
+            min_payment_percent: None,
+            kind: "revolving".to_string(),
+            term_months: None,
+            loan_start_date: None,
+            due_day: 31,
+            account_id: None,
+            created_at: "2024-01-01".to_string(),
+            updated_at: "2024-01-01".to_string(),
+        }];
+
+        let start = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        let plan = AvalancheCalculator::calculate_payoff_plan(debts, 10_000, start).unwrap();
+
+        let dates: Vec<&str> = plan.monthly_breakdown.iter().take(4).map(|m| m.date.as_str()).collect();
+        assert_eq!(dates, ["2024-01-31", "2024-02-29", "2024-03-31", "2024-04-30"]);
+        // Five years of payments end in the 60th calendar month, with no drift
+        assert_eq!(plan.monthly_breakdown.len(), 60);
+        assert_eq!(plan.payoff_date, "2028-12-31");
     }
 
     #[test]
@@ -239,18 +307,62 @@ mod tests {
         let debts = vec![Debt {
             id: 1,
             name: "Card".to_string(),
-            balance: 1000.0,
-            original_balance: 1000.0,
+            balance_cents: 100_000,
+            original_balance_cents: 100_000,
             interest_rate: 15.0,
-            min_payment: 50.0,
+            min_payment_cents: 5000,
+            min_payment_percent: None,
+            kind: "revolving".to_string(),
+            term_months: None,
+            loan_start_date: None,
+            due_day: 15,
+            account_id: None,
             created_at: "2025-01-01".to_string(),
             updated_at: "2025-01-01".to_string(),
         }];
 
-        let result = AvalancheCalculator::calculate_payoff_plan(debts, 25.0);
+        let result = AvalancheCalculator::calculate_payoff_plan(debts, 2500, start());
         assert!(result.is_err());
         let error = result.unwrap_err();
         let error_msg = error.to_string();
         assert!(error_msg.contains("Insufficient funds"));
     }
+
+    #[test]
+    fn test_percentage_minimum_declines_with_the_balance() {
+        let debt = |id: i64, name: &str, balance_cents: Cents, interest_rate: f64, min_payment_cents: Cents, percent: Option<f64>| Debt {
+            id,
+            name: name.to_string(),
+            balance_cents,
+            original_balance_cents: balance_cents,
+            interest_rate,
+            min_payment_cents,
+            min_payment_percent: percent,
+            kind: "revolving".to_string(),
+            term_months: None,
+            loan_start_date: None,
+            due_day: 15,
+            account_id: None,
+            created_at: "2025-01-01".to_string(),
+            updated_at: "2025-01-01".to_string(),
+        };
+        // The extra goes to the loan, so the card only ever gets its minimum
+        let debts = vec![
+            debt(1, "Card", 500_000, 0.0, 2500, Some(2.0)),
+            debt(2, "Loan", 1_000_000, 5.0, 5000, None),
+        ];
+
+        let plan = AvalancheCalculator::calculate_payoff_plan(debts.clone(), 30_000, start()).unwrap();
+        let card_payments: Vec<Cents> = plan
+            .monthly_breakdown
+            .iter()
+            .take(3)
+            .map(|m| m.payments.iter().find(|p| p.debt_id == 1).unwrap().amount_cents)
+            .collect();
+        assert_eq!(card_payments, vec![10_000, 9800, 9604]);
+
+        // The first month's minimums are 2% of the card plus the loan's fixed payment
+        let result = AvalancheCalculator::calculate_payoff_plan(debts, 14_999, start());
+        assert!(result.unwrap_err().to_string().contains("Insufficient funds"));
+    }
 }
@@ -0,0 +1,134 @@
+// When automatic backups are due, what they're called, and which old ones to delete
+//
+// A backup is a full data archive in the backup folder, named with the UTC time it was
+// taken so the names sort oldest first. Pruning only ever touches files with that name
+// pattern, so anything else the user keeps in the folder is left alone
+
+use crate::constants::BACKUP_FILE_PREFIX;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const BACKUP_EXTENSION: &str = ".json";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S%3f";
+
+/// How often automatic backups are taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl BackupFrequency {
+    fn interval(self) -> Duration {
+        match self {
+            BackupFrequency::Daily => Duration::days(1),
+            BackupFrequency::Weekly => Duration::days(7),
+            BackupFrequency::Monthly => Duration::days(30),
+        }
+    }
+
+    /// Whether a backup is due at `now` when the last successful one was at `last`
+    pub fn is_due(self, last: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+        last.is_none_or(|last| now - last >= self.interval())
+    }
+}
+
+impl std::fmt::Display for BackupFrequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackupFrequency::Daily => write!(f, "daily"),
+            BackupFrequency::Weekly => write!(f, "weekly"),
+            BackupFrequency::Monthly => write!(f, "monthly"),
+        }
+    }
+}
+
+impl std::str::FromStr for BackupFrequency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "daily" => Ok(BackupFrequency::Daily),
+            "weekly" => Ok(BackupFrequency::Weekly),
+            "monthly" => Ok(BackupFrequency::Monthly),
+            _ => Err(format!("Invalid backup frequency: {}. Use daily, weekly or monthly", s)),
+        }
+    }
+}
+
+pub struct BackupScheduler;
+
+impl BackupScheduler {
+    /// File name for a backup taken at `at`
+    pub fn file_name(at: DateTime<Utc>) -> String {
+        format!("{}{}{}", BACKUP_FILE_PREFIX, at.format(TIMESTAMP_FORMAT), BACKUP_EXTENSION)
+    }
+
+    fn is_backup_file(name: &str) -> bool {
+        name.strip_prefix(BACKUP_FILE_PREFIX)
+            .and_then(|rest| rest.strip_suffix(BACKUP_EXTENSION))
+            .is_some_and(|stamp| !stamp.is_empty() && stamp.chars().all(|c| c.is_ascii_digit() || c == '-'))
+    }
+
+    /// Delete all but the newest `keep` backups in `dir`, returning the deleted files
+    pub fn prune(dir: &Path, keep: usize) -> Result<Vec<PathBuf>, String> {
+        let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read backup folder: {}", e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file() && path.file_name().and_then(|n| n.to_str()).is_some_and(Self::is_backup_file))
+            .collect();
+        // Timestamps sort the same as the times they stand for
+        backups.sort();
+
+        let excess = backups.len().saturating_sub(keep);
+        let mut pruned = Vec::with_capacity(excess);
+        for path in backups.into_iter().take(excess) {
+            std::fs::remove_file(&path).map_err(|e| format!("Failed to delete old backup: {}", e))?;
+            pruned.push(path);
+        }
+        Ok(pruned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_is_due() {
+        let last = at("2025-03-01T08:00:00Z");
+        assert!(BackupFrequency::Daily.is_due(None, last));
+        assert!(!BackupFrequency::Daily.is_due(Some(last), at("2025-03-02T07:59:59Z")));
+        assert!(BackupFrequency::Daily.is_due(Some(last), at("2025-03-02T08:00:00Z")));
+        assert!(!BackupFrequency::Weekly.is_due(Some(last), at("2025-03-07T08:00:00Z")));
+        assert!(BackupFrequency::Monthly.is_due(Some(last), at("2025-03-31T08:00:00Z")));
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_backups_only() {
+        let dir = std::env::temp_dir().join(format!("backup-prune-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let names: Vec<String> = ["2025-01-01T00:00:00Z", "2025-02-01T00:00:00Z", "2025-03-01T00:00:00Z"]
+            .iter()
+            .map(|s| BackupScheduler::file_name(at(s)))
+            .collect();
+        for name in names.iter().chain([&"notes.json".to_string()]) {
+            std::fs::write(dir.join(name), "{}").unwrap();
+        }
+
+        let pruned = BackupScheduler::prune(&dir, 2).unwrap();
+        assert_eq!(pruned, vec![dir.join(&names[0])]);
+        assert!(dir.join(&names[2]).exists());
+        assert!(dir.join("notes.json").exists(), "Files that aren't backups are left alone");
+        assert!(BackupScheduler::prune(&dir, 2).unwrap().is_empty());
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,30 @@
+//! Point-in-time balances of each debt.
+//!
+//! Every change to a debt's stored balance appends the new balance here, on the
+//! same pool or database transaction as the change, so progress charts can show
+//! what actually happened. Unlike the audit log this is data the app reads back,
+//! so a failure to record fails the change.
+
+use crate::models::debt::BalanceChange;
+use crate::utils::dates;
+use crate::utils::money::Cents;
+use chrono::NaiveDate;
+use sqlx::SqliteExecutor;
+
+/// Append `balance_cents` as the debt's balance from `date` on
+pub async fn record_balance<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    debt_id: i64,
+    balance_cents: Cents,
+    date: NaiveDate,
+    change: BalanceChange,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO debt_balance_history (debt_id, balance_cents, date, change) VALUES (?, ?, ?, ?)")
+        .bind(debt_id)
+        .bind(balance_cents)
+        .bind(dates::format_date(date))
+        .bind(change.to_string())
+        .execute(executor)
+        .await?;
+    Ok(())
+}
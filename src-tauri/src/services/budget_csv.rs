@@ -0,0 +1,115 @@
+use crate::utils::money::{self, Cents};
+use super::csv_parser::CsvParser;
+use csv::{ReaderBuilder, Trim, WriterBuilder};
+use serde::Serialize;
+
+/// Header names accepted for each column, compared ignoring case
+const CATEGORY_HEADERS: &[&str] = &["category", "category name", "name"];
+const PERIOD_HEADERS: &[&str] = &["period", "frequency"];
+const AMOUNT_HEADERS: &[&str] = &["amount", "budget", "target"];
+
+/// One budget as exported: the category's name, period and amount
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BudgetCsvRow {
+    pub category: String,
+    pub period: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+}
+
+/// One data line of an imported budget file, as written; values are checked by the importer
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetCsvLine {
+    pub line: usize, // 1-based line in the file, counting the header
+    pub category: String,
+    pub period: String, // Blank when the file has no period column or leaves it empty
+    pub amount: String,
+}
+
+pub struct BudgetCsv;
+
+impl BudgetCsv {
+    /// A header row, then one row per budget
+    pub fn to_csv(rows: &[BudgetCsvRow]) -> Result<String, String> {
+        let mut writer = WriterBuilder::new().from_writer(Vec::new());
+        writer.write_record(["Category", "Period", "Amount"]).map_err(|e| e.to_string())?;
+        for row in rows {
+            writer
+                .write_record([&row.category, &row.period, &format!("{:.2}", money::from_cents(row.amount_cents))])
+                .map_err(|e| e.to_string())?;
+        }
+
+        let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+        String::from_utf8(bytes).map_err(|e| e.to_string())
+    }
+
+    /// Read the category, period and amount of each line; blank lines are skipped
+    /// The columns are found by header name, so they can come in any order and the period
+    /// column can be left out
+    pub fn parse(content: &str) -> Result<Vec<BudgetCsvLine>, String> {
+        let content = content.trim_start_matches('\u{feff}');
+        let mut reader = ReaderBuilder::new().trim(Trim::All).flexible(true).from_reader(content.as_bytes());
+
+        let headers = reader.headers().map_err(|e| format!("Invalid CSV: {}", e))?.clone();
+        let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.to_lowercase().as_str()));
+        let category = column(CATEGORY_HEADERS).ok_or("The file needs a Category column")?;
+        let amount = column(AMOUNT_HEADERS).ok_or("The file needs an Amount column")?;
+        let period = column(PERIOD_HEADERS);
+
+        let mut lines = Vec::new();
+        for (index, record) in reader.records().enumerate() {
+            let record = record.map_err(|e| format!("Invalid CSV: {}", e))?;
+            let line = CsvParser::record_line(content, &record).unwrap_or(index + 2);
+            if record.iter().all(str::is_empty) {
+                continue;
+            }
+            let field = |i: usize| record.get(i).unwrap_or("").to_string();
+            lines.push(BudgetCsvLine {
+                line,
+                category: field(category),
+                period: period.map(field).unwrap_or_default(),
+                amount: field(amount),
+            });
+        }
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let rows = vec![
+            BudgetCsvRow { category: "Groceries".to_string(), period: "monthly".to_string(), amount_cents: 60000 },
+            BudgetCsvRow { category: "Gifts, Holidays".to_string(), period: "yearly".to_string(), amount_cents: 120050 },
+        ];
+        let csv = BudgetCsv::to_csv(&rows).unwrap();
+        assert!(csv.starts_with("Category,Period,Amount\n"));
+        assert!(csv.contains("\"Gifts, Holidays\",yearly,1200.50"));
+
+        let lines = BudgetCsv::parse(&csv).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].category, "Gifts, Holidays");
+        assert_eq!(lines[1].period, "yearly");
+        assert_eq!(lines[1].amount, "1200.50");
+        assert_eq!(lines[1].line, 3);
+    }
+
+    #[test]
+    fn test_parse_finds_columns_by_name() {
+        let lines = BudgetCsv::parse("Budget, Name\n$250.00 , Dining\n\n 40,Coffee").unwrap();
+        assert_eq!(lines.len(), 2, "Blank lines are skipped");
+        assert_eq!(lines[0].category, "Dining");
+        assert_eq!(lines[0].amount, "$250.00");
+        assert_eq!(lines[0].period, "", "No period column leaves it blank");
+        assert_eq!(lines[1].line, 4);
+    }
+
+    #[test]
+    fn test_parse_needs_category_and_amount() {
+        assert!(BudgetCsv::parse("Category,Period\nDining,monthly").is_err());
+        assert!(BudgetCsv::parse("Period,Amount\nmonthly,10").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::models::category_template::{BudgetTemplateInfo, CategoryTemplate, TemplateCategory};
+
+/// Templates shipped with the app: (id, JSON document)
+const BUNDLED_TEMPLATES: &[(&str, &str)] = &[
+    ("fifty_thirty_twenty", include_str!("../../templates/fifty_thirty_twenty.json")),
+    ("essentials", include_str!("../../templates/essentials.json")),
+];
+
+/// Category sets the predefined categories can be reseeded from: (id, JSON document)
+/// Every set keeps "Income" and "Uncategorized" so imports and the default category still work
+const SEED_SETS: &[(&str, &str)] = &[
+    ("standard", include_str!("../../templates/seeds/standard.json")),
+    ("minimal", include_str!("../../templates/seeds/minimal.json")),
+    ("detailed", include_str!("../../templates/seeds/detailed.json")),
+    ("business", include_str!("../../templates/seeds/business.json")),
+];
+
+fn find(table: &[(&str, &str)], kind: &str, id: &str) -> Result<CategoryTemplate, String> {
+    let (_, content) = table
+        .iter()
+        .find(|(template_id, _)| *template_id == id)
+        .ok_or_else(|| format!("Unknown {}: {}", kind, id))?;
+
+    serde_json::from_str(content).map_err(|e| format!("Invalid bundled {} '{}': {}", kind, id, e))
+}
+
+fn list(table: &[(&str, &str)], kind: &str) -> Result<Vec<BudgetTemplateInfo>, String> {
+    fn count(categories: &[TemplateCategory]) -> usize {
+        categories.iter().map(|c| 1 + count(&c.children)).sum()
+    }
+    fn uses_income(categories: &[TemplateCategory]) -> bool {
+        categories.iter().any(|c| {
+            c.budgets.iter().any(|b| b.percent_of_income.is_some()) || uses_income(&c.children)
+        })
+    }
+
+    table
+        .iter()
+        .map(|(id, _)| {
+            let template = find(table, kind, id)?;
+            Ok(BudgetTemplateInfo {
+                id: id.to_string(),
+                name: template.name.clone().unwrap_or_else(|| id.to_string()),
+                description: template.description.clone(),
+                category_count: count(&template.categories),
+                requires_income: uses_income(&template.categories),
+            })
+        })
+        .collect()
+}
+
+pub struct BudgetTemplates;
+
+impl BudgetTemplates {
+    /// Parse a bundled template by id
+    pub fn get(id: &str) -> Result<CategoryTemplate, String> {
+        find(BUNDLED_TEMPLATES, "budget template", id)
+    }
+
+    /// Gallery listing of all bundled templates
+    pub fn list() -> Result<Vec<BudgetTemplateInfo>, String> {
+        list(BUNDLED_TEMPLATES, "budget template")
+    }
+
+    /// Parse a category seed set by id
+    pub fn seed_set(id: &str) -> Result<CategoryTemplate, String> {
+        find(SEED_SETS, "category set", id)
+    }
+
+    /// Listing of the category seed sets, in the order they're offered
+    pub fn list_seed_sets() -> Result<Vec<BudgetTemplateInfo>, String> {
+        list(SEED_SETS, "category set")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_templates_parse() {
+        let templates = BudgetTemplates::list().expect("Bundled templates should parse");
+        assert_eq!(templates.len(), BUNDLED_TEMPLATES.len());
+        assert!(templates.iter().all(|t| t.category_count > 0));
+
+        let fifty = templates.iter().find(|t| t.id == "fifty_thirty_twenty").unwrap();
+        assert!(fifty.requires_income);
+        let essentials = templates.iter().find(|t| t.id == "essentials").unwrap();
+        assert!(!essentials.requires_income);
+    }
+
+    #[test]
+    fn test_unknown_template() {
+        assert!(BudgetTemplates::get("does_not_exist").is_err());
+    }
+
+    #[test]
+    fn test_seed_sets_keep_required_categories() {
+        fn names(categories: &[TemplateCategory], out: &mut Vec<String>) {
+            for category in categories {
+                out.push(category.name.clone());
+                names(&category.children, out);
+            }
+        }
+
+        let sets = BudgetTemplates::list_seed_sets().expect("Seed sets should parse");
+        assert_eq!(sets.len(), SEED_SETS.len());
+        for set in sets {
+            let mut all = Vec::new();
+            names(&BudgetTemplates::seed_set(&set.id).unwrap().categories, &mut all);
+            assert!(all.iter().any(|n| n == "Income"), "{} has no Income category", set.id);
+            assert!(all.iter().any(|n| n == "Uncategorized"), "{} has no Uncategorized category", set.id);
+            assert!(!set.requires_income, "Seed sets carry no budgets");
+        }
+    }
+}
@@ -0,0 +1,409 @@
+use super::payment_scheduler::PaymentScheduler;
+use super::transaction_scheduler::Schedule;
+use crate::models::cash_flow::{
+    AccountProjection, CashFlowProjection, ProjectedPayment, ProjectedScheduledTransaction, ShortfallWarning,
+};
+use crate::models::scheduled_transaction::ScheduledTransaction;
+use crate::utils::dates;
+use crate::utils::money::Cents;
+use chrono::{Datelike, Months, NaiveDate};
+use sqlx::SqlitePool;
+
+/// An account's current balance, the starting point of its projection
+#[derive(Debug, Clone)]
+pub struct ProjectionAccount {
+    pub id: i64,
+    pub name: String,
+    pub balance_cents: Cents,
+}
+
+/// An autopay debt: `payment` comes out of `account_id` each due date until `debt_balance` is paid off
+#[derive(Debug, Clone)]
+pub struct ProjectionAutopay {
+    pub debt_id: i64,
+    pub debt_name: String,
+    pub account_id: i64,
+    pub payment_cents: Cents,
+    pub debt_balance_cents: Cents,
+    pub due_day: u32,
+}
+
+/// A scheduled transaction still to come: `amount` hits `account_id` on each occurrence from `next_due`
+#[derive(Debug, Clone)]
+pub struct ProjectionScheduled {
+    pub scheduled_transaction_id: i64,
+    pub description: String,
+    pub account_id: i64,
+    pub amount_cents: Cents,
+    pub next_due: NaiveDate,
+    pub schedule: Schedule,
+}
+
+/// What happens on a projected day; scheduled transactions land before autopay debits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Event {
+    Scheduled(usize),
+    Autopay(usize),
+}
+
+pub struct CashFlowProjector;
+
+impl CashFlowProjector {
+    fn format_date(date: NaiveDate) -> String {
+        date.format("%Y-%m-%d").to_string()
+    }
+
+    /// Due dates from `start` through `end` inclusive for a debt due on `due_day`
+    pub fn due_dates(start: NaiveDate, end: NaiveDate, due_day: u32) -> Vec<NaiveDate> {
+        let mut dates = Vec::new();
+        let mut due = PaymentScheduler::next_due_date_on(start, due_day);
+        while due <= end {
+            dates.push(due);
+            let Some(next_month) = due.with_day(1).and_then(|d| d.checked_add_months(Months::new(1))) else {
+                break;
+            };
+            due = PaymentScheduler::next_due_date_on(next_month, due_day);
+        }
+        dates
+    }
+
+    fn track_lowest(account: &mut AccountProjection, date: NaiveDate) {
+        if account.ending_balance_cents < account.lowest_balance_cents {
+            account.lowest_balance_cents = account.ending_balance_cents;
+            account.lowest_balance_date = Self::format_date(date);
+        }
+    }
+
+    /// Run scheduled transactions and autopay debits against account balances from `start` through `end`
+    /// Payments stop once a debt is paid off; interest isn't modeled
+    pub fn project(
+        accounts: &[ProjectionAccount],
+        autopays: &[ProjectionAutopay],
+        scheduled: &[ProjectionScheduled],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> CashFlowProjection {
+        let mut projections: Vec<AccountProjection> = accounts
+            .iter()
+            .map(|account| AccountProjection {
+                account_id: account.id,
+                account_name: account.name.clone(),
+                starting_balance_cents: account.balance_cents,
+                ending_balance_cents: account.balance_cents,
+                lowest_balance_cents: account.balance_cents,
+                lowest_balance_date: Self::format_date(start),
+                autopay_total_cents: 0,
+                scheduled_total_cents: 0,
+            })
+            .collect();
+
+        let mut remaining: Vec<Cents> = autopays.iter().map(|a| a.debt_balance_cents).collect();
+        let mut payments = Vec::new();
+        let mut scheduled_postings = Vec::new();
+        let mut warnings = Vec::new();
+
+        // Every event in date order; same-day events keep the input order
+        let mut events: Vec<(NaiveDate, Event)> = autopays
+            .iter()
+            .enumerate()
+            .flat_map(|(index, autopay)| {
+                Self::due_dates(start, end, autopay.due_day)
+                    .into_iter()
+                    .map(move |due| (due, Event::Autopay(index)))
+            })
+            .collect();
+        events.extend(scheduled.iter().enumerate().flat_map(|(index, item)| {
+            item.schedule
+                .occurrences_between(item.next_due.max(start), end)
+                .into_iter()
+                .map(move |due| (due, Event::Scheduled(index)))
+        }));
+        events.sort();
+
+        for (due, event) in events {
+            let index = match event {
+                Event::Autopay(index) => index,
+                Event::Scheduled(index) => {
+                    let item = &scheduled[index];
+                    let Some(account) = projections.iter_mut().find(|p| p.account_id == item.account_id) else {
+                        continue;
+                    };
+                    account.ending_balance_cents += item.amount_cents;
+                    account.scheduled_total_cents += item.amount_cents;
+                    Self::track_lowest(account, due);
+                    scheduled_postings.push(ProjectedScheduledTransaction {
+                        date: Self::format_date(due),
+                        account_id: account.account_id,
+                        scheduled_transaction_id: item.scheduled_transaction_id,
+                        description: item.description.clone(),
+                        amount_cents: item.amount_cents,
+                        balance_after_cents: account.ending_balance_cents,
+                    });
+                    continue;
+                }
+            };
+            let autopay = &autopays[index];
+            let debt_balance = &mut remaining[index];
+            let amount = autopay.payment_cents.min(*debt_balance);
+            let Some(account) = projections.iter_mut().find(|p| p.account_id == autopay.account_id) else {
+                continue;
+            };
+            if amount <= 0 {
+                continue;
+            }
+
+            let balance_before = account.ending_balance_cents;
+            if balance_before < amount {
+                warnings.push(ShortfallWarning {
+                    date: Self::format_date(due),
+                    account_id: account.account_id,
+                    account_name: account.account_name.clone(),
+                    debt_id: autopay.debt_id,
+                    debt_name: autopay.debt_name.clone(),
+                    payment_cents: amount,
+                    balance_before_cents: balance_before,
+                    shortfall_cents: amount - balance_before.max(0),
+                });
+            }
+
+            *debt_balance -= amount;
+            account.ending_balance_cents -= amount;
+            account.autopay_total_cents += amount;
+            Self::track_lowest(account, due);
+            payments.push(ProjectedPayment {
+                date: Self::format_date(due),
+                account_id: account.account_id,
+                debt_id: autopay.debt_id,
+                debt_name: autopay.debt_name.clone(),
+                amount_cents: amount,
+                balance_after_cents: account.ending_balance_cents,
+            });
+        }
+
+        CashFlowProjection {
+            start_date: Self::format_date(start),
+            end_date: Self::format_date(end),
+            accounts: projections,
+            payments,
+            scheduled: scheduled_postings,
+            warnings,
+        }
+    }
+
+    /// Project every open account from its current balance, applying scheduled transactions and autopay debts
+    pub async fn build(db: &SqlitePool, start: NaiveDate, end: NaiveDate) -> Result<CashFlowProjection, String> {
+        let accounts: Vec<ProjectionAccount> = sqlx::query_as::<_, (i64, String, Cents)>(
+            "SELECT id, name, balance_cents FROM accounts WHERE archived = 0 ORDER BY name"
+        )
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|(id, name, balance_cents)| ProjectionAccount { id, name, balance_cents })
+        .collect();
+
+        let autopays: Vec<ProjectionAutopay> = sqlx::query_as::<_, (i64, String, i64, Cents, Cents, u32)>(
+            "SELECT d.id, d.name, ap.account_id, COALESCE(ap.amount_cents, d.min_payment_cents), d.balance_cents, d.due_day
+             FROM debt_autopay ap
+             JOIN debts d ON d.id = ap.debt_id
+             WHERE d.balance_cents > 0
+             ORDER BY d.name, d.id"
+        )
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|(debt_id, debt_name, account_id, payment_cents, debt_balance_cents, due_day)| ProjectionAutopay {
+            debt_id,
+            debt_name,
+            account_id,
+            payment_cents,
+            debt_balance_cents,
+            due_day,
+        })
+        .collect();
+
+        let scheduled: Vec<ProjectionScheduled> = sqlx::query_as::<_, ScheduledTransaction>(
+            "SELECT id, account_id, category_id, amount_cents, description, merchant, recurrence, start_date, end_date,
+                    next_due_date, auto_post, created_at
+             FROM scheduled_transactions
+             WHERE next_due_date IS NOT NULL AND next_due_date <= ?
+             ORDER BY next_due_date, id"
+        )
+        .bind(Self::format_date(end))
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter_map(|item| {
+            let schedule = Schedule::of(&item)?;
+            let next_due = dates::parse_date(item.next_due_date.as_deref()?).ok()?;
+            Some(ProjectionScheduled {
+                scheduled_transaction_id: item.id,
+                description: item.description,
+                account_id: item.account_id,
+                amount_cents: item.amount_cents,
+                next_due,
+                schedule,
+            })
+        })
+        .collect();
+
+        Ok(Self::project(&accounts, &autopays, &scheduled, start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::scheduled_transaction::Recurrence;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn account(id: i64, balance_cents: Cents) -> ProjectionAccount {
+        ProjectionAccount {
+            id,
+            name: format!("Account {}", id),
+            balance_cents,
+        }
+    }
+
+    fn autopay(debt_id: i64, account_id: i64, payment_cents: Cents, debt_balance_cents: Cents) -> ProjectionAutopay {
+        ProjectionAutopay {
+            debt_id,
+            debt_name: format!("Debt {}", debt_id),
+            account_id,
+            payment_cents,
+            debt_balance_cents,
+            due_day: 15,
+        }
+    }
+
+    #[test]
+    fn test_due_dates() {
+        assert_eq!(
+            CashFlowProjector::due_dates(date("2025-01-15"), date("2025-03-14"), 15),
+            vec![date("2025-01-15"), date("2025-02-15")]
+        );
+        assert!(CashFlowProjector::due_dates(date("2025-01-16"), date("2025-02-14"), 15).is_empty());
+        assert_eq!(
+            CashFlowProjector::due_dates(date("2025-01-01"), date("2025-03-31"), 31),
+            vec![date("2025-01-31"), date("2025-02-28"), date("2025-03-31")]
+        );
+    }
+
+    #[test]
+    fn test_project_debits_funding_account() {
+        let projection = CashFlowProjector::project(
+            &[account(1, 100_000), account(2, 5000)],
+            &[autopay(10, 1, 20_000, 500_000)],
+            &[],
+            date("2025-01-01"),
+            date("2025-03-31"),
+        );
+
+        assert_eq!(projection.payments.len(), 3);
+        let checking = &projection.accounts[0];
+        assert_eq!(checking.ending_balance_cents, 40_000);
+        assert_eq!(checking.autopay_total_cents, 60_000);
+        assert_eq!(checking.lowest_balance_date, "2025-03-15");
+        // Other accounts are untouched
+        assert_eq!(projection.accounts[1].ending_balance_cents, 5000);
+        assert!(projection.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_project_warns_on_shortfall() {
+        let projection = CashFlowProjector::project(
+            &[account(1, 25_000)],
+            &[autopay(10, 1, 20_000, 500_000)],
+            &[],
+            date("2025-01-01"),
+            date("2025-02-28"),
+        );
+
+        assert_eq!(projection.warnings.len(), 1);
+        let warning = &projection.warnings[0];
+        assert_eq!(warning.date, "2025-02-15");
+        assert_eq!(warning.balance_before_cents, 5000);
+        assert_eq!(warning.shortfall_cents, 15_000);
+        assert_eq!(projection.accounts[0].ending_balance_cents, -15_000);
+    }
+
+    #[test]
+    fn test_project_stops_when_debt_paid_off() {
+        let projection = CashFlowProjector::project(
+            &[account(1, 100_000)],
+            &[autopay(10, 1, 20_000, 25_000)],
+            &[],
+            date("2025-01-01"),
+            date("2025-04-30"),
+        );
+
+        let amounts: Vec<Cents> = projection.payments.iter().map(|p| p.amount_cents).collect();
+        assert_eq!(amounts, vec![20_000, 5000]);
+    }
+
+    #[test]
+    fn test_project_uses_each_debts_due_day() {
+        let late = ProjectionAutopay { due_day: 20, ..autopay(10, 1, 10_000, 500_000) };
+        let early = ProjectionAutopay { due_day: 5, ..autopay(11, 1, 5000, 500_000) };
+        let projection = CashFlowProjector::project(
+            &[account(1, 100_000)],
+            &[late, early],
+            &[],
+            date("2025-01-10"),
+            date("2025-02-28"),
+        );
+
+        let dates: Vec<(&str, i64)> = projection.payments.iter().map(|p| (p.date.as_str(), p.debt_id)).collect();
+        assert_eq!(dates, vec![("2025-01-20", 10), ("2025-02-05", 11), ("2025-02-20", 10)]);
+    }
+
+    #[test]
+    fn test_project_applies_scheduled_transactions() {
+        let rent = ProjectionScheduled {
+            scheduled_transaction_id: 1,
+            description: "Rent".to_string(),
+            account_id: 1,
+            amount_cents: -90_000,
+            next_due: date("2025-02-01"),
+            schedule: Schedule {
+                recurrence: Recurrence::Monthly,
+                start: date("2025-01-01"),
+                end: None,
+            },
+        };
+        let paycheck = ProjectionScheduled {
+            scheduled_transaction_id: 2,
+            description: "Paycheck".to_string(),
+            amount_cents: 100_000,
+            next_due: date("2025-01-15"),
+            schedule: Schedule {
+                recurrence: Recurrence::Monthly,
+                start: date("2025-01-15"),
+                end: None,
+            },
+            ..rent.clone()
+        };
+        let projection = CashFlowProjector::project(
+            &[account(1, 50_000)],
+            &[autopay(10, 1, 20_000, 500_000)],
+            &[rent, paycheck],
+            date("2025-01-10"),
+            date("2025-02-28"),
+        );
+
+        // The paycheck lands before the same-day autopay; January's rent was already posted
+        let events: Vec<(&str, Cents)> = projection.scheduled.iter().map(|s| (s.date.as_str(), s.amount_cents)).collect();
+        assert_eq!(events, vec![("2025-01-15", 100_000), ("2025-02-01", -90_000), ("2025-02-15", 100_000)]);
+        assert!(projection.warnings.is_empty());
+        let checking = &projection.accounts[0];
+        assert_eq!(checking.scheduled_total_cents, 110_000);
+        assert_eq!(checking.ending_balance_cents, 120_000);
+        assert_eq!(checking.lowest_balance_cents, 40_000);
+        assert_eq!(checking.lowest_balance_date, "2025-02-01");
+    }
+}
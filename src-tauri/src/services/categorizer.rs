@@ -1,45 +1,194 @@
+use crate::constants::MAX_RULE_REGEX_SIZE;
+use crate::models::category_rule::{CategoryRule, RuleMatchType};
+use crate::utils::money::Cents;
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+
 #[derive(Debug)]
 pub enum CategorizerError {
     DatabaseError(String),
+    InvalidPattern(String),
 }
 
 impl std::fmt::Display for CategorizerError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CategorizerError::DatabaseError(e) => write!(f, "Database Error: {}", e),
+            CategorizerError::InvalidPattern(e) => write!(f, "Invalid Pattern: {}", e),
         }
     }
 }
 
 impl std::error::Error for CategorizerError {}
 
+/// A category rule prepared for evaluation (regex compiled once, patterns lowercased)
+#[derive(Debug, Clone)]
+pub struct CompiledRule {
+    pub rule: CategoryRule,
+    match_type: RuleMatchType,
+    pattern: String,
+    regex: Option<Regex>,
+}
+
+impl CompiledRule {
+    pub fn new(rule: CategoryRule) -> Result<Self, CategorizerError> {
+        let match_type: RuleMatchType = rule
+            .match_type
+            .parse()
+            .map_err(CategorizerError::InvalidPattern)?;
+
+        let regex = if match_type == RuleMatchType::Regex {
+            Some(Categorizer::compile_regex(&rule.pattern)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            pattern: rule.pattern.to_lowercase(),
+            match_type,
+            regex,
+            rule,
+        })
+    }
+
+    /// Check whether this rule applies to a transaction
+    /// `text` is the merchant (or description when no merchant is present)
+    pub fn matches(&self, text: &str, amount_cents: Cents, account_id: Option<i64>) -> bool {
+        if let Some(rule_account) = self.rule.account_id {
+            if account_id != Some(rule_account) {
+                return false;
+            }
+        }
+
+        let magnitude = amount_cents.abs();
+        if self.rule.min_amount_cents.is_some_and(|min| magnitude < min) {
+            return false;
+        }
+        if self.rule.max_amount_cents.is_some_and(|max| magnitude > max) {
+            return false;
+        }
+
+        self.matches_text(text)
+    }
+
+    /// Check the rule's pattern alone, ignoring its amount range and account
+    pub fn matches_text(&self, text: &str) -> bool {
+        let text = text.trim().to_lowercase();
+        match self.match_type {
+            RuleMatchType::Contains => text.contains(&self.pattern),
+            RuleMatchType::StartsWith => text.starts_with(&self.pattern),
+            RuleMatchType::Exact => text == self.pattern,
+            RuleMatchType::Regex => self.regex.as_ref().is_some_and(|re| re.is_match(&text)),
+        }
+    }
+
+    /// Whether some transaction could match both rules
+    /// Patterns overlap when either rule matches the other's literal pattern; two
+    /// regexes are only caught through transactions they both match.
+    pub fn overlaps(&self, other: &CompiledRule) -> bool {
+        let accounts_overlap = match (self.rule.account_id, other.rule.account_id) {
+            (Some(a), Some(b)) => a == b,
+            _ => true,
+        };
+        let lower = self.rule.min_amount_cents.unwrap_or(0).max(other.rule.min_amount_cents.unwrap_or(0));
+        let upper = self.rule.max_amount_cents.unwrap_or(Cents::MAX).min(other.rule.max_amount_cents.unwrap_or(Cents::MAX));
+        if !accounts_overlap || lower > upper {
+            return false;
+        }
+
+        let literal_matches = |rule: &CompiledRule, literal: &CompiledRule| {
+            literal.match_type != RuleMatchType::Regex && rule.matches_text(&literal.pattern)
+        };
+        literal_matches(self, other) || literal_matches(other, self)
+    }
+}
+
 pub struct Categorizer;
 
 impl Categorizer {
-    /// Finds the best matching category for a transaction based on merchant/description
-    /// Returns the category_id, or None if no match found
-    pub async fn categorize(
-        db: &sqlx::Pool<sqlx::Sqlite>,
-        merchant: Option<&str>,
-        description: &str,
-    ) -> Result<Option<i64>, CategorizerError> {
+    /// Compile a user-supplied rule regex (case-insensitive, size-limited)
+    pub fn compile_regex(pattern: &str) -> Result<Regex, CategorizerError> {
+        RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .size_limit(MAX_RULE_REGEX_SIZE)
+            .build()
+            .map_err(|e| CategorizerError::InvalidPattern(e.to_string()))
+    }
 
-        // Get all category rules ordered by priority (highest first)
-        let rules: Vec<(i64, String, i64)> = sqlx::query_as(
-            "SELECT id, pattern, category_id FROM category_rules ORDER BY priority DESC"
+    /// Load all category rules ordered by priority (highest first), ready for matching
+    /// Rules that fail to compile are skipped and logged rather than aborting categorization
+    pub async fn load_rules(
+        db: &sqlx::Pool<sqlx::Sqlite>,
+    ) -> Result<Vec<CompiledRule>, CategorizerError> {
+        let rules = sqlx::query_as::<_, CategoryRule>(
+            "SELECT id, pattern, category_id, priority, match_type, min_amount_cents, max_amount_cents, account_id, created_at
+             FROM category_rules ORDER BY priority DESC, created_at ASC, id ASC"
         )
         .fetch_all(db)
         .await
         .map_err(|e| CategorizerError::DatabaseError(e.to_string()))?;
 
+        Ok(rules
+            .into_iter()
+            .filter_map(|rule| {
+                let rule_id = rule.id;
+                CompiledRule::new(rule)
+                    .map_err(|e| tracing::warn!(rule_id, error = %e, "Skipping invalid category rule"))
+                    .ok()
+            })
+            .collect())
+    }
+
+    /// Find the first rule (in priority order) matching a transaction
+    pub fn find_match<'a>(
+        rules: &'a [CompiledRule],
+        merchant: Option<&str>,
+        description: &str,
+        amount_cents: Cents,
+        account_id: Option<i64>,
+    ) -> Option<&'a CompiledRule> {
         // Try to match against merchant first, then description
-        let text_to_match = merchant.unwrap_or(description).to_lowercase();
+        let text_to_match = merchant.unwrap_or(description);
+        rules
+            .iter()
+            .find(|rule| rule.matches(text_to_match, amount_cents, account_id))
+    }
 
-        for (_rule_id, pattern, category_id) in rules {
-            if text_to_match.contains(&pattern.to_lowercase()) {
-                return Ok(Some(category_id));
+    /// Count `hits` (matches per rule ID) toward each rule's statistics
+    /// The statistics are informational, so a failure is logged rather than failing categorization
+    pub async fn record_hits(db: &sqlx::Pool<sqlx::Sqlite>, hits: &HashMap<i64, i64>) {
+        for (rule_id, count) in hits {
+            let result = sqlx::query(
+                "UPDATE category_rules
+                 SET match_count = match_count + ?, last_matched_at = CURRENT_TIMESTAMP
+                 WHERE id = ?"
+            )
+            .bind(count)
+            .bind(rule_id)
+            .execute(db)
+            .await;
+            if let Err(e) = result {
+                tracing::warn!(rule_id, error = %e, "Failed to record category rule match");
             }
         }
+    }
+
+    /// Finds the best matching category for a transaction based on merchant/description,
+    /// amount and account
+    /// Returns the category_id, or None if no match found
+    pub async fn categorize(
+        db: &sqlx::Pool<sqlx::Sqlite>,
+        merchant: Option<&str>,
+        description: &str,
+        amount_cents: Cents,
+        account_id: Option<i64>,
+    ) -> Result<Option<i64>, CategorizerError> {
+        let rules = Self::load_rules(db).await?;
+
+        if let Some(rule) = Self::find_match(&rules, merchant, description, amount_cents, account_id) {
+            Self::record_hits(db, &HashMap::from([(rule.rule.id, 1)])).await;
+            return Ok(Some(rule.rule.category_id));
+        }
 
         // No match found - return uncategorized category by querying for it
         let uncategorized_id: Option<(i64,)> = sqlx::query_as(
@@ -52,3 +201,117 @@ impl Categorizer {
         Ok(uncategorized_id.map(|r| r.0))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, match_type: RuleMatchType) -> CategoryRule {
+        CategoryRule {
+            id: 1,
+            pattern: pattern.to_string(),
+            category_id: 3,
+            priority: 10,
+            match_type: match_type.to_string(),
+            min_amount_cents: None,
+            max_amount_cents: None,
+            account_id: None,
+            created_at: "2025-01-01".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_contains_is_case_insensitive() {
+        let compiled = CompiledRule::new(rule("Starbucks", RuleMatchType::Contains)).unwrap();
+        assert!(compiled.matches("STARBUCKS #1234", -500, None));
+        assert!(!compiled.matches("Peet's Coffee", -500, None));
+    }
+
+    #[test]
+    fn test_regex_word_boundary_avoids_substring_misfire() {
+        let compiled = CompiledRule::new(rule(r"\bshell\b", RuleMatchType::Regex)).unwrap();
+        assert!(compiled.matches("SHELL OIL 5744", -4000, None));
+        assert!(!compiled.matches("Michelle's Cafe", -1200, None));
+    }
+
+    #[test]
+    fn test_starts_with_and_exact() {
+        let starts = CompiledRule::new(rule("amzn", RuleMatchType::StartsWith)).unwrap();
+        assert!(starts.matches("AMZN Mktp US", -2000, None));
+        assert!(!starts.matches("Pay AMZN", -2000, None));
+
+        let exact = CompiledRule::new(rule("netflix", RuleMatchType::Exact)).unwrap();
+        assert!(exact.matches(" Netflix ", -1500, None));
+        assert!(!exact.matches("Netflix.com", -1500, None));
+    }
+
+    #[test]
+    fn test_amount_range_and_account_scope() {
+        let mut r = rule("venmo", RuleMatchType::Contains);
+        r.min_amount_cents = Some(1000);
+        r.max_amount_cents = Some(5000);
+        r.account_id = Some(7);
+        let compiled = CompiledRule::new(r).unwrap();
+
+        assert!(compiled.matches("VENMO PAYMENT", -2500, Some(7)));
+        assert!(!compiled.matches("VENMO PAYMENT", -7500, Some(7)));
+        assert!(!compiled.matches("VENMO PAYMENT", -500, Some(7)));
+        assert!(!compiled.matches("VENMO PAYMENT", -2500, Some(8)));
+        assert!(!compiled.matches("VENMO PAYMENT", -2500, None));
+    }
+
+    #[test]
+    fn test_invalid_regex_rejected() {
+        assert!(CompiledRule::new(rule("(unclosed", RuleMatchType::Regex)).is_err());
+        assert!(Categorizer::compile_regex("[a-").is_err());
+    }
+
+    #[test]
+    fn test_overlapping_patterns() {
+        let general = CompiledRule::new(rule("amazon", RuleMatchType::Contains)).unwrap();
+        let prime = CompiledRule::new(rule("amazon prime", RuleMatchType::StartsWith)).unwrap();
+        let regex = CompiledRule::new(rule(r"^amazon\b", RuleMatchType::Regex)).unwrap();
+        let other = CompiledRule::new(rule("netflix", RuleMatchType::Exact)).unwrap();
+
+        assert!(general.overlaps(&prime));
+        assert!(prime.overlaps(&general));
+        assert!(regex.overlaps(&general), "A regex matching the other rule's pattern overlaps");
+        assert!(!general.overlaps(&other));
+    }
+
+    #[test]
+    fn test_overlap_needs_shared_accounts_and_amounts() {
+        let mut small = rule("venmo", RuleMatchType::Contains);
+        small.max_amount_cents = Some(5000);
+        let mut large = rule("venmo", RuleMatchType::Contains);
+        large.min_amount_cents = Some(10_000);
+        let small = CompiledRule::new(small).unwrap();
+        assert!(!small.overlaps(&CompiledRule::new(large).unwrap()));
+
+        let mut scoped = rule("venmo", RuleMatchType::Contains);
+        scoped.account_id = Some(1);
+        let mut other_account = rule("venmo", RuleMatchType::Contains);
+        other_account.account_id = Some(2);
+        let scoped = CompiledRule::new(scoped).unwrap();
+        assert!(!scoped.overlaps(&CompiledRule::new(other_account).unwrap()));
+        assert!(scoped.overlaps(&small), "An unscoped rule applies to every account");
+    }
+
+    #[test]
+    fn test_find_match_respects_rule_order() {
+        let mut specific = rule(r"^uber\s+eats", RuleMatchType::Regex);
+        specific.id = 2;
+        specific.category_id = 2;
+        let general = rule("uber", RuleMatchType::Contains);
+
+        let rules = vec![
+            CompiledRule::new(specific).unwrap(),
+            CompiledRule::new(general).unwrap(),
+        ];
+
+        let eats = Categorizer::find_match(&rules, Some("Uber Eats"), "food", -3000, None).unwrap();
+        assert_eq!(eats.rule.category_id, 2);
+        let ride = Categorizer::find_match(&rules, None, "UBER TRIP", -1800, None).unwrap();
+        assert_eq!(ride.rule.category_id, 3);
+    }
+}
@@ -0,0 +1,213 @@
+use crate::utils::money::{self, Cents};
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+/// Average spending in one calendar month across the years covered
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeasonalMonth {
+    pub month: u32, // 1 = January
+    pub average: f64,
+    pub months_counted: usize,
+    pub index: Option<f64>, // average / overall monthly average; None when there is no overall spending
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonthlyAmount {
+    pub month: String, // YYYY-MM
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryStatistics {
+    pub category_id: i64,
+    pub category_name: String,
+    pub start_month: Option<String>, // None when the category has no spending in the window
+    pub end_month: String,
+    pub months_counted: usize, // Months without spending count as zero
+    #[serde(rename = "total", with = "money::dollars")]
+    pub total_cents: Cents,
+    pub average: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub std_dev: f64, // Population standard deviation of the monthly amounts
+    pub monthly: Vec<MonthlyAmount>,
+    pub seasonal_profile: Vec<SeasonalMonth>, // Always 12 entries, January first
+}
+
+pub struct CategoryStatisticsCalculator;
+
+impl CategoryStatisticsCalculator {
+    fn month_key(date: NaiveDate) -> String {
+        date.format("%Y-%m").to_string()
+    }
+
+    /// (average, median, min, max, population standard deviation) of `amounts`; all zero when empty
+    pub fn summarize(amounts: &[f64]) -> (f64, f64, f64, f64, f64) {
+        if amounts.is_empty() {
+            return (0.0, 0.0, 0.0, 0.0, 0.0);
+        }
+
+        let count = amounts.len() as f64;
+        let average = amounts.iter().sum::<f64>() / count;
+        let variance = amounts.iter().map(|a| (a - average).powi(2)).sum::<f64>() / count;
+
+        let mut sorted = amounts.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2.0
+        } else {
+            sorted[middle]
+        };
+
+        (average, median, sorted[0], sorted[sorted.len() - 1], variance.sqrt())
+    }
+
+    /// Average per calendar month of `(month_start, amount)` pairs, January first
+    pub fn seasonal_profile(monthly: &[(NaiveDate, f64)]) -> Vec<SeasonalMonth> {
+        let overall = if monthly.is_empty() {
+            0.0
+        } else {
+            monthly.iter().map(|(_, amount)| amount).sum::<f64>() / monthly.len() as f64
+        };
+
+        (1..=12)
+            .map(|month| {
+                let amounts: Vec<f64> = monthly
+                    .iter()
+                    .filter(|(date, _)| date.month() == month)
+                    .map(|(_, amount)| *amount)
+                    .collect();
+                let average = if amounts.is_empty() {
+                    0.0
+                } else {
+                    amounts.iter().sum::<f64>() / amounts.len() as f64
+                };
+                SeasonalMonth {
+                    month,
+                    average,
+                    months_counted: amounts.len(),
+                    index: (overall > 0.0 && !amounts.is_empty()).then(|| average / overall),
+                }
+            })
+            .collect()
+    }
+
+    /// Statistics over the `months` completed months before `today`'s month
+    /// The window starts at the category's first expense if that is later, so a new category
+    /// isn't averaged against months before it existed
+    pub async fn calculate(
+        db: &SqlitePool,
+        category_id: i64,
+        months: u32,
+        today: NaiveDate,
+    ) -> Result<CategoryStatistics, String> {
+        let category_name: String = sqlx::query_as::<_, (String,)>("SELECT name FROM categories WHERE id = ?")
+            .bind(category_id)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Category {} not found", category_id))?
+            .0;
+
+        let this_month = today.with_day(1).unwrap_or(today);
+        let window_end = this_month - chrono::Duration::days(1);
+        let window_start = this_month - Months::new(months);
+
+        let rows = sqlx::query_as::<_, (String, Cents)>(
+            "SELECT strftime('%Y-%m', date) as month, SUM(-amount_cents)
+             FROM transactions
+             WHERE category_id = ? AND amount_cents < 0 AND date >= ? AND date <= ?
+             GROUP BY month
+             ORDER BY month"
+        )
+        .bind(category_id)
+        .bind(window_start.format("%Y-%m-%d").to_string())
+        .bind(window_end.format("%Y-%m-%d").to_string())
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+        let amounts: HashMap<String, Cents> = rows.into_iter().collect();
+
+        // Every month from the first with spending, zero-filled
+        let first_month = (0..months)
+            .map(|k| window_start + Months::new(k))
+            .find(|month| amounts.contains_key(&Self::month_key(*month)));
+        let monthly: Vec<(NaiveDate, Cents)> = match first_month {
+            Some(first) => std::iter::successors(Some(first), |month| Some(*month + Months::new(1)))
+                .take_while(|month| *month < this_month)
+                .map(|month| (month, amounts.get(&Self::month_key(month)).copied().unwrap_or(0)))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        // The statistics are dollars; only the monthly amounts and total are stored money
+        let in_dollars: Vec<(NaiveDate, f64)> =
+            monthly.iter().map(|(month, amount)| (*month, money::from_cents(*amount))).collect();
+        let values: Vec<f64> = in_dollars.iter().map(|(_, amount)| *amount).collect();
+        let (average, median, min, max, std_dev) = Self::summarize(&values);
+
+        Ok(CategoryStatistics {
+            category_id,
+            category_name,
+            start_month: first_month.map(Self::month_key),
+            end_month: Self::month_key(window_end),
+            months_counted: monthly.len(),
+            total_cents: monthly.iter().map(|(_, amount)| amount).sum(),
+            average,
+            median,
+            min,
+            max,
+            std_dev,
+            seasonal_profile: Self::seasonal_profile(&in_dollars),
+            monthly: monthly
+                .into_iter()
+                .map(|(month, amount_cents)| MonthlyAmount { month: Self::month_key(month), amount_cents })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn month(y: i32, m: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, 1).unwrap()
+    }
+
+    #[test]
+    fn test_summarize() {
+        let (average, median, min, max, std_dev) =
+            CategoryStatisticsCalculator::summarize(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert_eq!(average, 5.0);
+        assert_eq!(median, 4.5);
+        assert_eq!(min, 2.0);
+        assert_eq!(max, 9.0);
+        assert_eq!(std_dev, 2.0);
+
+        assert_eq!(CategoryStatisticsCalculator::summarize(&[3.0, 1.0, 2.0]).1, 2.0, "Odd count takes the middle value");
+        assert_eq!(CategoryStatisticsCalculator::summarize(&[]), (0.0, 0.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_seasonal_profile() {
+        let monthly = [
+            (month(2022, 12), 300.0),
+            (month(2023, 1), 100.0),
+            (month(2023, 12), 500.0),
+            (month(2024, 1), 100.0),
+        ];
+        let profile = CategoryStatisticsCalculator::seasonal_profile(&monthly);
+
+        assert_eq!(profile.len(), 12);
+        assert_eq!(profile[0], SeasonalMonth { month: 1, average: 100.0, months_counted: 2, index: Some(0.4) });
+        assert_eq!(profile[11], SeasonalMonth { month: 12, average: 400.0, months_counted: 2, index: Some(1.6) });
+        assert_eq!(profile[5].months_counted, 0);
+        assert_eq!(profile[5].index, None, "Months without data have no index");
+    }
+}
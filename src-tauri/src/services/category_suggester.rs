@@ -0,0 +1,223 @@
+use crate::constants::DEFAULT_CATEGORY_ID;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+
+/// Tokens that appear across unrelated merchants and carry no category signal
+const NOISE_TOKENS: &[&str] = &[
+    "the", "and", "pos", "purchase", "debit", "credit", "card", "payment", "online",
+    "www", "com", "inc", "llc", "ltd", "store", "recurring",
+];
+
+/// Minimum token length considered meaningful
+const MIN_TOKEN_LENGTH: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategorySuggestion {
+    pub category_id: i64,
+    pub category_name: String,
+    pub category_icon: Option<String>,
+    pub confidence: f64,             // 0.0 - 1.0
+    pub matching_transactions: i64,  // Historical transactions sharing a token with this category
+}
+
+pub struct CategorySuggester;
+
+impl CategorySuggester {
+    /// Normalize merchant/description text into distinct tokens
+    /// Lowercases, splits on non-alphanumerics, and drops short, noisy or mostly-numeric tokens
+    /// (store numbers, reference codes)
+    pub fn tokenize(text: &str) -> Vec<String> {
+        let mut seen = HashSet::new();
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| token.chars().count() >= MIN_TOKEN_LENGTH)
+            .filter(|token| {
+                let digits = token.chars().filter(|c| c.is_ascii_digit()).count();
+                digits * 2 <= token.chars().count()
+            })
+            .filter(|token| !NOISE_TOKENS.contains(token))
+            .filter(|token| seen.insert(token.to_string()))
+            .map(|token| token.to_string())
+            .collect()
+    }
+
+    /// Rank categories for `tokens` given historical (category_id, tokens) pairs
+    /// Each target token votes for categories in proportion to how often it was seen with them;
+    /// confidence is the average vote across all target tokens, so unseen tokens lower it
+    /// Returns (category_id, confidence, matching_transactions) ordered by confidence
+    pub fn rank(tokens: &[String], history: &[(i64, Vec<String>)]) -> Vec<(i64, f64, i64)> {
+        if tokens.is_empty() {
+            return Vec::new();
+        }
+
+        let targets: HashSet<&str> = tokens.iter().map(|t| t.as_str()).collect();
+        let mut token_category_counts: HashMap<&str, HashMap<i64, i64>> = HashMap::new();
+        let mut matching: HashMap<i64, i64> = HashMap::new();
+
+        for (category_id, row_tokens) in history {
+            let mut shared = false;
+            for token in row_tokens {
+                if let Some(target) = targets.get(token.as_str()) {
+                    *token_category_counts
+                        .entry(target)
+                        .or_default()
+                        .entry(*category_id)
+                        .or_insert(0) += 1;
+                    shared = true;
+                }
+            }
+            if shared {
+                *matching.entry(*category_id).or_insert(0) += 1;
+            }
+        }
+
+        let mut scores: HashMap<i64, f64> = HashMap::new();
+        for counts in token_category_counts.values() {
+            let total: i64 = counts.values().sum();
+            for (category_id, count) in counts {
+                *scores.entry(*category_id).or_insert(0.0) += *count as f64 / total as f64;
+            }
+        }
+
+        let mut ranked: Vec<(i64, f64, i64)> = scores
+            .into_iter()
+            .map(|(category_id, score)| {
+                let confidence = score / tokens.len() as f64;
+                (category_id, confidence, matching.get(&category_id).copied().unwrap_or(0))
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.2.cmp(&a.2))
+                .then(a.0.cmp(&b.0))
+        });
+        ranked
+    }
+
+    /// Suggest categories for a merchant/description based on previously categorized transactions
+    /// Uncategorized history is ignored; `exclude_transaction_id` keeps a transaction from voting for itself
+    pub async fn suggest(
+        db: &SqlitePool,
+        merchant: Option<&str>,
+        description: &str,
+        exclude_transaction_id: Option<i64>,
+        limit: usize,
+    ) -> Result<Vec<CategorySuggestion>, String> {
+        let text = match merchant {
+            Some(merchant) => format!("{} {}", merchant, description),
+            None => description.to_string(),
+        };
+        let tokens = Self::tokenize(&text);
+        if tokens.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows: Vec<(i64, Option<String>, String)> = sqlx::query_as(
+            "SELECT category_id, merchant, description FROM transactions
+             WHERE category_id != ? AND (? IS NULL OR id != ?)"
+        )
+        .bind(DEFAULT_CATEGORY_ID)
+        .bind(exclude_transaction_id)
+        .bind(exclude_transaction_id)
+        .fetch_all(db)
+        .await
+        .map_err(|e| format!("Failed to load categorization history: {}", e))?;
+
+        let history: Vec<(i64, Vec<String>)> = rows
+            .into_iter()
+            .map(|(category_id, merchant, description)| {
+                let text = match merchant {
+                    Some(merchant) => format!("{} {}", merchant, description),
+                    None => description,
+                };
+                (category_id, Self::tokenize(&text))
+            })
+            .collect();
+
+        let ranked = Self::rank(&tokens, &history);
+
+        let categories: Vec<(i64, String, Option<String>)> =
+            sqlx::query_as("SELECT id, name, icon FROM categories WHERE archived = 0")
+                .fetch_all(db)
+                .await
+                .map_err(|e| format!("Failed to load categories: {}", e))?;
+
+        Ok(ranked
+            .into_iter()
+            .filter_map(|(category_id, confidence, matching_transactions)| {
+                categories
+                    .iter()
+                    .find(|(id, _, _)| *id == category_id)
+                    .map(|(_, name, icon)| CategorySuggestion {
+                        category_id,
+                        category_name: name.clone(),
+                        category_icon: icon.clone(),
+                        confidence,
+                        matching_transactions,
+                    })
+            })
+            .take(limit)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(rows: &[(i64, &str)]) -> Vec<(i64, Vec<String>)> {
+        rows.iter()
+            .map(|(category_id, text)| (*category_id, CategorySuggester::tokenize(text)))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_drops_noise_and_numbers() {
+        let tokens = CategorySuggester::tokenize("POS PURCHASE Blue Bottle Coffee #1234 98765A");
+        assert_eq!(tokens, vec!["blue", "bottle", "coffee"]);
+    }
+
+    #[test]
+    fn test_tokenize_deduplicates() {
+        let tokens = CategorySuggester::tokenize("Costco costco COSTCO Gas");
+        assert_eq!(tokens, vec!["costco", "gas"]);
+    }
+
+    #[test]
+    fn test_rank_prefers_most_frequent_category() {
+        let history = history(&[
+            (1, "Costco Wholesale"),
+            (1, "COSTCO WHOLESALE #42"),
+            (3, "Costco Gas"),
+        ]);
+        let tokens = CategorySuggester::tokenize("Costco Wholesale 0099");
+
+        let ranked = CategorySuggester::rank(&tokens, &history);
+        assert_eq!(ranked[0].0, 1);
+        assert_eq!(ranked[1].0, 3);
+        assert!(ranked[0].1 > ranked[1].1);
+        assert!(ranked[0].1 <= 1.0);
+        assert_eq!(ranked[0].2, 2);
+    }
+
+    #[test]
+    fn test_rank_unseen_tokens_lower_confidence() {
+        let history = history(&[(2, "Blue Bottle")]);
+
+        let known = CategorySuggester::rank(&CategorySuggester::tokenize("Blue Bottle"), &history);
+        let partly_new = CategorySuggester::rank(&CategorySuggester::tokenize("Blue Lagoon Spa"), &history);
+
+        assert!((known[0].1 - 1.0).abs() < f64::EPSILON);
+        assert!(partly_new[0].1 < known[0].1);
+    }
+
+    #[test]
+    fn test_rank_no_history_returns_empty() {
+        let tokens = CategorySuggester::tokenize("Brand New Merchant");
+        assert!(CategorySuggester::rank(&tokens, &[]).is_empty());
+        assert!(CategorySuggester::rank(&[], &history(&[(1, "Costco")])).is_empty());
+    }
+}
@@ -0,0 +1,328 @@
+use super::csv_parser::{ColumnMapping, CsvParser};
+use serde::Serialize;
+use std::collections::HashSet;
+
+// Header names that suggest each field; an exact match counts more than a partial one
+const DATE_HINTS: &[&str] = &["date", "transaction date", "posted date", "posting date", "posted", "trans date"];
+const AMOUNT_HINTS: &[&str] = &["amount", "transaction amount", "amt", "value"];
+const DEBIT_HINTS: &[&str] = &["debit", "debits", "withdrawal", "withdrawals", "debit amount", "money out"];
+const CREDIT_HINTS: &[&str] = &["credit", "credits", "deposit", "deposits", "credit amount", "money in"];
+const DESCRIPTION_HINTS: &[&str] = &["description", "memo", "details", "narrative", "transaction description", "name"];
+const MERCHANT_HINTS: &[&str] = &["merchant", "payee", "merchant name", "vendor"];
+const STATUS_HINTS: &[&str] = &["status", "transaction status", "pending"];
+const MEMBER_HINTS: &[&str] = &["member", "card member", "cardholder", "card holder", "spender"];
+
+/// Weight of the sample values vs. the header name in a field score
+const VALUE_WEIGHT: f64 = 0.7;
+const HINT_WEIGHT: f64 = 0.3;
+
+/// How much each column's header and sample values look like each field (0.0-1.0)
+#[derive(Debug, Clone, Serialize)]
+pub struct ColumnScore {
+    pub column: String,
+    pub date: f64,
+    pub amount: f64,
+    pub description: f64,
+    pub merchant: f64,
+    pub debit: f64,
+    pub credit: f64,
+}
+
+/// Best-guess mapping for a file, either detected or taken from a saved mapping
+#[derive(Debug, Clone, Serialize)]
+pub struct MappingSuggestion {
+    pub mapping: Option<ColumnMapping>, // None when no column fits a required field
+    pub confidence: f64,                // Mean score of the chosen date, amount and description columns
+    pub saved_mapping_id: Option<i64>,
+    pub saved_mapping_name: Option<String>,
+    pub header_fingerprint: String,
+    pub columns: Vec<ColumnScore>,
+}
+
+pub struct ColumnDetector;
+
+impl ColumnDetector {
+    fn normalize_header(header: &str) -> String {
+        header
+            .trim()
+            .trim_start_matches('\u{feff}')
+            .to_lowercase()
+            .replace(['_', '-'], " ")
+    }
+
+    /// Identifies a header row regardless of case, spacing and BOM, but not column order
+    pub fn header_fingerprint(headers: &[String]) -> String {
+        use sha2::{Digest, Sha256};
+        let normalized: Vec<String> = headers.iter().map(|h| Self::normalize_header(h)).collect();
+        let mut hasher = Sha256::new();
+        hasher.update(normalized.join("\u{1f}"));
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn hint_score(header: &str, hints: &[&str]) -> f64 {
+        let header = Self::normalize_header(header);
+        if hints.contains(&header.as_str()) {
+            1.0
+        } else if hints.iter().any(|hint| header.split(' ').any(|word| word == *hint)) {
+            0.5
+        } else {
+            0.0
+        }
+    }
+
+    fn parse_amount(value: &str) -> Option<f64> {
+        CsvParser::parse_amount(value, None).ok()
+    }
+
+    /// Share of non-empty values that are dates, amounts and free text, plus whether any values exist
+    /// Whole numbers only count half as amounts, since they're often check numbers or IDs
+    fn value_scores(values: &[&str]) -> Option<(f64, f64, f64)> {
+        let values: Vec<&str> = values.iter().map(|v| v.trim()).filter(|v| !v.is_empty()).collect();
+        if values.is_empty() {
+            return None;
+        }
+        let count = values.len() as f64;
+
+        let mut dates = 0.0;
+        let mut amounts = 0.0;
+        let mut text = 0.0;
+        for value in &values {
+            if CsvParser::normalize_date(value).is_ok() {
+                dates += 1.0;
+            } else if Self::parse_amount(value).is_some() {
+                amounts += if value.contains(['.', ',']) || value.starts_with(['-', '+', '(']) { 1.0 } else { 0.5 };
+            } else if value.chars().any(|c| c.is_alphabetic()) {
+                text += 1.0;
+            }
+        }
+
+        // Free text varies from row to row; a column of a few repeated words is more like a type or status
+        let distinct = values.iter().collect::<HashSet<_>>().len() as f64;
+        let variety = if values.len() > 1 { distinct / count } else { 1.0 };
+
+        Some((dates / count, amounts / count, (text / count) * variety.max(0.5)))
+    }
+
+    fn combine(value: Option<f64>, hint: f64) -> f64 {
+        match value {
+            Some(value) => VALUE_WEIGHT * value + HINT_WEIGHT * hint,
+            None => HINT_WEIGHT * hint, // No samples, so only the header name tells us anything
+        }
+    }
+
+    /// Score every column for every field from its header and sample values
+    pub fn score_columns(headers: &[String], samples: &[Vec<String>]) -> Vec<ColumnScore> {
+        headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                let values: Vec<&str> = samples.iter().filter_map(|row| row.get(i).map(String::as_str)).collect();
+                let scores = Self::value_scores(&values);
+                ColumnScore {
+                    column: header.clone(),
+                    date: Self::combine(scores.map(|s| s.0), Self::hint_score(header, DATE_HINTS)),
+                    amount: Self::combine(scores.map(|s| s.1), Self::hint_score(header, AMOUNT_HINTS)),
+                    description: Self::combine(scores.map(|s| s.2), Self::hint_score(header, DESCRIPTION_HINTS)),
+                    merchant: Self::combine(scores.map(|s| s.2), Self::hint_score(header, MERCHANT_HINTS)),
+                    debit: Self::combine(scores.map(|s| s.1), Self::hint_score(header, DEBIT_HINTS)),
+                    credit: Self::combine(scores.map(|s| s.1), Self::hint_score(header, CREDIT_HINTS)),
+                }
+            })
+            .collect()
+    }
+
+    /// Pick a column per field, best-scoring first among columns not already used
+    pub fn suggest(headers: &[String], samples: &[Vec<String>]) -> MappingSuggestion {
+        let columns = Self::score_columns(headers, samples);
+        let mut used: HashSet<usize> = HashSet::new();
+
+        let mut pick = |score: fn(&ColumnScore) -> f64, min: f64| -> Option<(usize, f64)> {
+            let best = columns
+                .iter()
+                .enumerate()
+                .filter(|(i, column)| !used.contains(i) && score(column) > min)
+                .max_by(|(_, a), (_, b)| score(a).total_cmp(&score(b)))
+                .map(|(i, column)| (i, score(column)))?;
+            used.insert(best.0);
+            Some(best)
+        };
+
+        let date = pick(|c| c.date, 0.0);
+        // Separate debit/credit columns are only used when no column is named like a signed amount
+        let has_header = |hints: &[&str], exact: bool| {
+            headers.iter().any(|h| {
+                let score = Self::hint_score(h, hints);
+                if exact { score == 1.0 } else { score > 0.0 }
+            })
+        };
+        let debit_credit = if !has_header(AMOUNT_HINTS, true)
+            && has_header(DEBIT_HINTS, false)
+            && has_header(CREDIT_HINTS, false)
+        {
+            let debit = pick(|c| if Self::hint_score(&c.column, DEBIT_HINTS) > 0.0 { c.debit } else { 0.0 }, 0.0);
+            let credit = pick(|c| if Self::hint_score(&c.column, CREDIT_HINTS) > 0.0 { c.credit } else { 0.0 }, 0.0);
+            debit.zip(credit)
+        } else {
+            None
+        };
+        let amount = match debit_credit {
+            Some(((_, debit), (_, credit))) => Some((usize::MAX, (debit + credit) / 2.0)),
+            None => pick(|c| c.amount, 0.0),
+        };
+        // Prefer a merchant column only when its header says so, leaving the description to free text
+        let merchant = pick(|c| if c.merchant > c.description { c.merchant } else { 0.0 }, 0.5);
+        let description = pick(|c| c.description, 0.0);
+        // Statuses are a handful of repeated words, so only a column named for them is taken as one
+        let status = headers
+            .iter()
+            .enumerate()
+            .find(|(i, h)| !used.contains(i) && Self::hint_score(h, STATUS_HINTS) == 1.0)
+            .map(|(_, h)| h.clone());
+        // Names of people look like any other text, so the same goes for the member column
+        let member = headers
+            .iter()
+            .enumerate()
+            .find(|(i, h)| !used.contains(i) && Some(*h) != status.as_ref() && Self::hint_score(h, MEMBER_HINTS) == 1.0)
+            .map(|(_, h)| h.clone());
+
+        let (mapping, confidence) = match (date, amount, description) {
+            (Some(date), Some(amount), Some(description)) => (
+                Some(ColumnMapping {
+                    date: headers[date.0].clone(),
+                    amount: headers.get(amount.0).cloned().unwrap_or_default(),
+                    description: headers[description.0].clone(),
+                    merchant: merchant.map(|(i, _)| headers[i].clone()),
+                    debit: debit_credit.map(|((i, _), _)| headers[i].clone()),
+                    credit: debit_credit.map(|(_, (i, _))| headers[i].clone()),
+                    invert_amounts: false,
+                    date_format: None, // Detected from the whole file at import
+                    status,
+                    member,
+                    delimiter: None, // Detected from the file at import
+                    decimal_separator: None,
+                }),
+                (date.1 + amount.1 + description.1) / 3.0,
+            ),
+            _ => (None, 0.0),
+        };
+
+        MappingSuggestion {
+            mapping,
+            confidence,
+            saved_mapping_id: None,
+            saved_mapping_name: None,
+            header_fingerprint: Self::header_fingerprint(headers),
+            columns,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_suggest_from_headers_and_values() {
+        let headers = strings(&["Posted Date", "Reference", "Payee", "Memo", "Amount"]);
+        let samples = vec![
+            strings(&["06/15/2025", "1001", "STARBUCKS", "Card purchase 1234", "-4.50"]),
+            strings(&["06/16/2025", "1002", "SAFEWAY", "Card purchase 5678", "-82.10"]),
+            strings(&["06/17/2025", "1003", "ACME PAYROLL", "Direct deposit", "2500.00"]),
+        ];
+
+        let suggestion = ColumnDetector::suggest(&headers, &samples);
+        let mapping = suggestion.mapping.expect("Mapping should be detected");
+        assert_eq!(mapping.date, "Posted Date");
+        assert_eq!(mapping.amount, "Amount");
+        assert_eq!(mapping.description, "Memo");
+        assert_eq!(mapping.merchant.as_deref(), Some("Payee"));
+        assert!(suggestion.confidence > 0.8, "confidence was {}", suggestion.confidence);
+    }
+
+    #[test]
+    fn test_suggest_from_values_alone() {
+        let headers = strings(&["col1", "col2", "col3"]);
+        let samples = vec![
+            strings(&["Grocery run", "2025-01-03", "(12.50)"]),
+            strings(&["Gas station", "2025-01-04", "40.00"]),
+        ];
+
+        let mapping = ColumnDetector::suggest(&headers, &samples).mapping.unwrap();
+        assert_eq!(mapping.date, "col2");
+        assert_eq!(mapping.amount, "col3");
+        assert_eq!(mapping.description, "col1");
+        assert_eq!(mapping.merchant, None);
+    }
+
+    #[test]
+    fn test_suggest_debit_credit_columns() {
+        let headers = strings(&["Date", "Description", "Withdrawals", "Deposits", "Balance"]);
+        let samples = vec![
+            strings(&["2025-01-03", "Coffee", "4.50", "", "995.50"]),
+            strings(&["2025-01-04", "Payroll", "", "2000.00", "2995.50"]),
+            strings(&["2025-01-05", "Rent", "1200.00", "", "1795.50"]),
+        ];
+
+        let mapping = ColumnDetector::suggest(&headers, &samples).mapping.unwrap();
+        assert_eq!(mapping.debit.as_deref(), Some("Withdrawals"));
+        assert_eq!(mapping.credit.as_deref(), Some("Deposits"));
+        assert_eq!(mapping.amount, "");
+        assert_eq!(mapping.description, "Description");
+        assert_eq!(mapping.status, None);
+    }
+
+    #[test]
+    fn test_suggest_status_column_by_name() {
+        let headers = strings(&["Date", "Status", "Description", "Amount"]);
+        let samples = vec![
+            strings(&["2025-01-03", "Pending", "Coffee shop", "-4.50"]),
+            strings(&["2025-01-04", "Posted", "Grocery store", "-62.00"]),
+        ];
+
+        let mapping = ColumnDetector::suggest(&headers, &samples).mapping.unwrap();
+        assert_eq!(mapping.status.as_deref(), Some("Status"));
+        assert_eq!(mapping.description, "Description");
+    }
+
+    #[test]
+    fn test_suggest_member_column_by_name() {
+        let headers = strings(&["Date", "Description", "Card Member", "Amount"]);
+        let samples = vec![
+            strings(&["2025-01-03", "Coffee shop", "ALEX MORGAN", "4.50"]),
+            strings(&["2025-01-04", "Grocery store", "SAM MORGAN", "62.00"]),
+        ];
+
+        let mapping = ColumnDetector::suggest(&headers, &samples).mapping.unwrap();
+        assert_eq!(mapping.member.as_deref(), Some("Card Member"));
+        assert_eq!(mapping.description, "Description");
+    }
+
+    #[test]
+    fn test_suggest_needs_three_columns() {
+        let headers = strings(&["Date", "Amount"]);
+        let samples = vec![strings(&["2025-01-03", "-5.00"])];
+        let suggestion = ColumnDetector::suggest(&headers, &samples);
+        assert!(suggestion.mapping.is_none());
+        assert_eq!(suggestion.confidence, 0.0);
+    }
+
+    #[test]
+    fn test_header_fingerprint_ignores_case_and_spacing() {
+        let a = ColumnDetector::header_fingerprint(&strings(&["\u{feff}Date", "Amount ", "Description"]));
+        let b = ColumnDetector::header_fingerprint(&strings(&["date", "AMOUNT", "description"]));
+        let c = ColumnDetector::header_fingerprint(&strings(&["Amount", "Date", "Description"]));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(ColumnDetector::parse_amount("$1,234.50"), Some(1234.5));
+        assert_eq!(ColumnDetector::parse_amount("(12.50)"), Some(-12.5));
+        assert_eq!(ColumnDetector::parse_amount("abc"), None);
+    }
+}
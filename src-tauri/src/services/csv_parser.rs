@@ -1,21 +1,66 @@
+use super::mapping_expression::MappingExpression;
+use crate::utils::money::{self, Cents};
 use csv::ReaderBuilder;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ColumnMapping {
     pub date: String,
-    pub amount: String,
-    pub description: String,
-    pub merchant: Option<String>,
+    #[serde(default)]
+    pub amount: String, // Signed amount column; ignored when debit/credit columns are given
+    pub description: String,      // Column name, or an expression like `=[Payee] + " " + [Memo]`
+    pub merchant: Option<String>, // Column name or expression
+    #[serde(default)]
+    pub debit: Option<String>, // Money out, as a positive number
+    #[serde(default)]
+    pub credit: Option<String>, // Money in, as a positive number
+    #[serde(default)]
+    pub invert_amounts: bool, // Flip signs, e.g. for card exports where charges are positive
+    #[serde(default)]
+    pub date_format: Option<String>, // e.g. "DD/MM/YYYY" or "%d/%m/%Y"; detected from the file when None
+    #[serde(default)]
+    pub status: Option<String>, // Column saying whether a row is pending; every row is posted without one
+    #[serde(default)]
+    pub member: Option<String>, // Column naming the household member, e.g. the cardholder
+    #[serde(default)]
+    pub delimiter: Option<char>, // e.g. ';' or '\t'; detected from the header line when None
+    #[serde(default)]
+    pub decimal_separator: Option<char>, // '.' or ','; worked out from each amount when None
+}
+
+impl ColumnMapping {
+    /// Whether amounts come from separate debit/credit columns
+    pub fn uses_debit_credit(&self) -> bool {
+        self.debit.is_some() || self.credit.is_some()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedTransaction {
     pub date: String,
-    pub amount: f64,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
     pub description: String,
     pub merchant: Option<String>,
+    #[serde(default)]
+    pub pending: bool,
+    #[serde(default)]
+    pub member: Option<String>, // As written in the file; matched to a member by name on import
+}
+
+/// Text encodings a CSV file can be read in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CsvEncoding {
+    #[serde(rename = "utf-8")]
+    Utf8,
+    #[serde(rename = "utf-16le")]
+    Utf16Le,
+    #[serde(rename = "utf-16be")]
+    Utf16Be,
+    /// A superset of Latin-1 (ISO-8859-1), so Latin-1 files read the same way
+    #[serde(rename = "windows-1252", alias = "latin-1", alias = "iso-8859-1")]
+    Windows1252,
 }
 
 #[derive(Debug)]
@@ -23,6 +68,7 @@ pub enum CsvError {
     IoError(String),
     ParseError(String),
     MissingColumn(String),
+    InvalidMapping(String),
 }
 
 impl std::fmt::Display for CsvError {
@@ -31,6 +77,7 @@ impl std::fmt::Display for CsvError {
             CsvError::IoError(e) => write!(f, "IO Error: {}", e),
             CsvError::ParseError(e) => write!(f, "Parse Error: {}", e),
             CsvError::MissingColumn(col) => write!(f, "Missing column: {}", col),
+            CsvError::InvalidMapping(e) => write!(f, "Invalid mapping: {}", e),
         }
     }
 }
@@ -39,38 +86,293 @@ impl std::error::Error for CsvError {}
 
 pub struct CsvParser;
 
+/// Formats tried when a mapping doesn't name one, in order of preference
+/// Month-first comes before day-first, so a file is only read as day-first when its dates require it
+const DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",  // 2025-06-15
+    "%m/%d/%Y",  // 06/15/2025
+    "%m/%d/%y",  // 06/15/25
+    "%Y/%m/%d",  // 2025/06/15
+    "%d/%m/%Y",  // 15/06/2025
+    "%d/%m/%y",  // 15/06/25
+    "%m-%d-%Y",  // 06-15-2025
+    "%m-%d-%y",  // 06-15-25
+    "%d-%m-%Y",  // 15-06-2025
+    "%d.%m.%Y",  // 15.06.2025
+    "%b %d, %Y", // Jun 15, 2025
+    "%B %d, %Y", // June 15, 2025
+    "%d %b %Y",  // 15 Jun 2025
+];
+
+/// Status column values that mark a row as pending, compared ignoring case; anything else is posted
+const PENDING_STATUSES: &[&str] = &["pending", "pend", "p", "authorized", "authorization", "hold", "processing"];
+
+/// Currency symbols allowed around an amount, e.g. "$12.00" or "1.234,56 €"
+const CURRENCY_SYMBOLS: &[char] = &['$', '€', '£', '¥', '₹', '₩', '₽', '¢', '₺', '₪', '₱', '฿', '₫', '₴', '₦', '₣'];
+
+/// Currency codes allowed before or after an amount, e.g. "EUR 12,00"
+const CURRENCY_CODES: &[&str] = &[
+    "USD", "EUR", "GBP", "CAD", "AUD", "NZD", "CHF", "JPY", "CNY", "HKD", "SGD", "INR", "SEK", "NOK", "DKK",
+    "PLN", "CZK", "HUF", "MXN", "BRL", "ZAR",
+];
+
+/// Delimiters detected from a file's header line; the earlier one wins a tie
+const DELIMITERS: &[u8] = b",;\t";
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// Windows-1252 characters for bytes 0x80-0x9F; every other byte is the Latin-1 code point
+/// Bytes the encoding leaves undefined keep their C1 control code point
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
 impl CsvParser {
-    /// Normalize date to YYYY-MM-DD format
-    fn normalize_date(date_str: &str) -> Result<String, CsvError> {
-        use chrono::NaiveDate;
-
-        // Try common date formats
-        let formats = [
-            "%Y-%m-%d",    // 2025-06-15
-            "%m/%d/%Y",    // 06/15/2025
-            "%m/%d/%y",    // 06/15/25
-            "%Y/%m/%d",    // 2025/06/15
-            "%d/%m/%Y",    // 15/06/2025
-            "%d-%m-%Y",    // 15-06-2025
-            "%b %d, %Y",   // Jun 15, 2025
-            "%B %d, %Y",   // June 15, 2025
-        ];
-
-        for format in &formats {
-            if let Ok(date) = NaiveDate::parse_from_str(date_str.trim(), format) {
-                return Ok(date.format("%Y-%m-%d").to_string());
+    pub(crate) fn parse_date(date_str: &str, format: &str) -> Option<String> {
+        use chrono::Datelike;
+        chrono::NaiveDate::parse_from_str(date_str.trim(), format)
+            .ok()
+            // %Y also accepts two-digit years; leave those to the %y formats
+            .filter(|date| !format.contains("%Y") || date.year() >= 1000)
+            .map(|date| date.format("%Y-%m-%d").to_string())
+    }
+
+    /// Parse an amount written the way banks and spreadsheets write them: "$1,234.56",
+    /// "1.234,56 €", "EUR -12,00", "(45.00)" or "45.00-"
+    /// Without a `decimal_separator`, it's worked out from the value (see `infer_decimal_separator`)
+    pub(crate) fn parse_amount(amount_str: &str, decimal_separator: Option<char>) -> Result<f64, CsvError> {
+        let invalid = || CsvError::ParseError(format!("Invalid amount: {}", amount_str));
+
+        let mut text = amount_str.trim();
+        for code in CURRENCY_CODES {
+            text = text.strip_prefix(code).or_else(|| text.strip_suffix(code)).unwrap_or(text).trim();
+        }
+
+        // Signs and parentheses only count outside the digits, so "2024-01-05" isn't an amount
+        let first_digit = text.find(|c: char| c.is_ascii_digit()).ok_or_else(invalid)?;
+        let last_digit = text.rfind(|c: char| c.is_ascii_digit()).unwrap_or(first_digit);
+        let mut number = String::new();
+        let (mut minus, mut open, mut close) = (false, 0, 0);
+        for (i, c) in text.char_indices() {
+            let outside = i < first_digit || i > last_digit;
+            match c {
+                '0'..='9' | '.' | ',' => number.push(c),
+                '-' | '\u{2212}' if outside => minus = true,
+                '(' if outside => open += 1,
+                ')' if outside => close += 1,
+                '+' if outside => {}
+                // Spaces and apostrophes group thousands: "1 234,56", "1'234.56"
+                c if c.is_whitespace() || matches!(c, '\'' | '\u{2019}') => {}
+                c if outside && CURRENCY_SYMBOLS.contains(&c) => {}
+                _ => return Err(invalid()),
             }
         }
+        // Accounting negatives: (45.00)
+        let negative = match (open, close) {
+            (0, 0) => minus,
+            (1, 1) => true,
+            _ => return Err(invalid()),
+        };
 
-        Err(CsvError::ParseError(format!(
-            "Unable to parse date: {}. Supported formats: YYYY-MM-DD, MM/DD/YYYY, etc.",
-            date_str
-        )))
+        let decimal = decimal_separator.or_else(|| Self::infer_decimal_separator(&number));
+        let number: String = number
+            .chars()
+            .filter_map(|c| match c {
+                '0'..='9' => Some(c),
+                c if Some(c) == decimal => Some('.'),
+                _ => None, // Thousands separators
+            })
+            .collect();
+
+        let amount = number.parse::<f64>().map_err(|_| invalid())?;
+        Ok(money::round_money(if negative { -amount } else { amount }))
+    }
+
+    /// `parse_amount` for a money column, in cents
+    pub(crate) fn parse_cents(amount_str: &str, decimal_separator: Option<char>) -> Result<Cents, CsvError> {
+        let amount = Self::parse_amount(amount_str, decimal_separator)?;
+        money::parse_dollars(amount).map_err(CsvError::ParseError)
+    }
+
+    /// Which of '.' and ',' is the decimal point in `number` (digits and separators only)
+    /// With both, the last one is. A lone comma is one unless three digits follow it ("12,50" but
+    /// "1,234"), and a lone dot always is; a separator that repeats groups thousands.
+    fn infer_decimal_separator(number: &str) -> Option<char> {
+        match (number.rfind('.'), number.rfind(',')) {
+            (Some(dot), Some(comma)) => Some(if dot > comma { '.' } else { ',' }),
+            (Some(_), None) => (number.matches('.').count() == 1).then_some('.'),
+            (None, Some(comma)) => {
+                (number.matches(',').count() == 1 && number.len() - comma - 1 != 3).then_some(',')
+            }
+            (None, None) => None,
+        }
+    }
+
+    /// Normalize date to YYYY-MM-DD format, trying each supported format
+    pub(crate) fn normalize_date(date_str: &str) -> Result<String, CsvError> {
+        DATE_FORMATS
+            .iter()
+            .find_map(|format| Self::parse_date(date_str, format))
+            .ok_or_else(|| {
+                CsvError::ParseError(format!(
+                    "Unable to parse date: {}. Supported formats: YYYY-MM-DD, MM/DD/YYYY, etc.",
+                    date_str
+                ))
+            })
+    }
+
+    /// Convert a date format like "DD/MM/YYYY" or "MMM D, YYYY" to a chrono format string
+    /// Formats already containing `%` are taken as chrono formats
+    pub fn date_format_pattern(format: &str) -> Result<String, CsvError> {
+        let pattern = if format.contains('%') {
+            format.to_string()
+        } else {
+            let tokens = [
+                ("YYYY", "%Y"),
+                ("YY", "%y"),
+                ("MMMM", "%B"),
+                ("MMM", "%b"),
+                ("MM", "%m"),
+                ("M", "%m"),
+                ("DD", "%d"),
+                ("D", "%d"),
+            ];
+            let mut pattern = String::new();
+            let mut rest = format.trim();
+            'outer: while !rest.is_empty() {
+                for (token, replacement) in tokens {
+                    if let Some(after) = rest.strip_prefix(token) {
+                        pattern.push_str(replacement);
+                        rest = after;
+                        continue 'outer;
+                    }
+                }
+                let mut chars = rest.chars();
+                pattern.extend(chars.next());
+                rest = chars.as_str();
+            }
+            pattern
+        };
+
+        let has_year = pattern.contains("%Y") || pattern.contains("%y");
+        let has_month = ["%m", "%b", "%B"].iter().any(|m| pattern.contains(m));
+        if !has_year || !has_month || !pattern.contains("%d") {
+            return Err(CsvError::InvalidMapping(format!(
+                "Invalid date format: {}. It needs a day, month and year, e.g. DD/MM/YYYY",
+                format
+            )));
+        }
+        Ok(pattern)
+    }
+
+    /// The supported format that reads the most of `values`, earliest in the list on ties
+    /// None when no format reads any of them
+    pub fn detect_date_format<'a>(values: impl IntoIterator<Item = &'a str>) -> Option<&'static str> {
+        let values: Vec<&str> = values.into_iter().map(str::trim).filter(|v| !v.is_empty()).collect();
+        let mut best: Option<(&'static str, usize)> = None;
+        for format in DATE_FORMATS {
+            let parsed = values.iter().filter(|v| Self::parse_date(v, format).is_some()).count();
+            if parsed > best.map_or(0, |(_, count)| count) {
+                best = Some((format, parsed));
+            }
+        }
+        best.map(|(format, _)| format)
+    }
+
+    /// Guess the encoding of a file's bytes
+    /// A byte order mark picks UTF-8 or UTF-16; otherwise valid UTF-8 is UTF-8 and anything else
+    /// is taken as Windows-1252, which every byte sequence is valid in
+    pub fn detect_encoding(bytes: &[u8]) -> CsvEncoding {
+        if bytes.starts_with(UTF16_LE_BOM) {
+            CsvEncoding::Utf16Le
+        } else if bytes.starts_with(UTF16_BE_BOM) {
+            CsvEncoding::Utf16Be
+        } else if std::str::from_utf8(bytes).is_ok() {
+            CsvEncoding::Utf8
+        } else {
+            CsvEncoding::Windows1252
+        }
+    }
+
+    /// Decode a file's bytes to text, detecting the encoding when none is given
+    pub fn decode(bytes: &[u8], encoding: Option<CsvEncoding>) -> Result<String, CsvError> {
+        let invalid = |name: &str| CsvError::ParseError(format!("File is not valid {}; choose its encoding", name));
+
+        match encoding.unwrap_or_else(|| Self::detect_encoding(bytes)) {
+            CsvEncoding::Utf8 => {
+                let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+                String::from_utf8(bytes.to_vec()).map_err(|_| invalid("UTF-8"))
+            }
+            encoding @ (CsvEncoding::Utf16Le | CsvEncoding::Utf16Be) => {
+                let little_endian = encoding == CsvEncoding::Utf16Le;
+                let bom = if little_endian { UTF16_LE_BOM } else { UTF16_BE_BOM };
+                let bytes = bytes.strip_prefix(bom).unwrap_or(bytes);
+                if bytes.len() % 2 != 0 {
+                    return Err(invalid("UTF-16"));
+                }
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| {
+                        let pair = [pair[0], pair[1]];
+                        if little_endian { u16::from_le_bytes(pair) } else { u16::from_be_bytes(pair) }
+                    })
+                    .collect();
+                String::from_utf16(&units).map_err(|_| invalid("UTF-16"))
+            }
+            CsvEncoding::Windows1252 => Ok(bytes
+                .iter()
+                .map(|&byte| match byte {
+                    0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(byte - 0x80)],
+                    _ => char::from(byte),
+                })
+                .collect()),
+        }
+    }
+
+    /// The delimiter used most in the header line outside quotes, or a comma when none appears
+    pub fn detect_delimiter(csv_content: &str) -> u8 {
+        let header = csv_content.lines().next().unwrap_or_default();
+        let count = |delimiter: u8| {
+            let mut in_quotes = false;
+            header
+                .bytes()
+                .filter(|&byte| {
+                    if byte == b'"' {
+                        in_quotes = !in_quotes;
+                    }
+                    !in_quotes && byte == delimiter
+                })
+                .count()
+        };
+
+        let mut best = (b',', 0);
+        for &delimiter in DELIMITERS {
+            let found = count(delimiter);
+            if found > best.1 {
+                best = (delimiter, found);
+            }
+        }
+        best.0
+    }
+
+    /// The mapping's delimiter, or the file's detected one
+    fn delimiter(delimiter: Option<char>, csv_content: &str) -> Result<u8, CsvError> {
+        match delimiter {
+            None => Ok(Self::detect_delimiter(csv_content)),
+            Some(c) if c.is_ascii() && !matches!(c, '"' | '\n' | '\r') => Ok(c as u8),
+            Some(c) => Err(CsvError::InvalidMapping(format!("'{}' can't be used as a delimiter", c.escape_default()))),
+        }
     }
 
     pub fn get_headers(csv_content: &str) -> Result<Vec<String>, CsvError> {
         let mut reader = ReaderBuilder::new()
             .has_headers(true)
+            .delimiter(Self::detect_delimiter(csv_content))
             .from_reader(csv_content.as_bytes());
 
         match reader.headers() {
@@ -79,12 +381,39 @@ impl CsvParser {
         }
     }
 
-    pub fn parse(
-        csv_content: &str,
-        mapping: &ColumnMapping,
-    ) -> Result<Vec<ParsedTransaction>, CsvError> {
+    /// Headers and up to `limit` raw rows, for inspecting a file before a mapping is chosen
+    pub fn sample(csv_content: &str, limit: usize) -> Result<(Vec<String>, Vec<Vec<String>>), CsvError> {
+        let mut reader = ReaderBuilder::new()
+            .has_headers(true)
+            .flexible(true)
+            .delimiter(Self::detect_delimiter(csv_content))
+            .from_reader(csv_content.as_bytes());
+
+        let headers = reader
+            .headers()
+            .map_err(|e| CsvError::ParseError(e.to_string()))?
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+        let rows = reader
+            .records()
+            .take(limit)
+            .map(|record| {
+                record
+                    .map(|r| r.iter().map(|v| v.to_string()).collect())
+                    .map_err(|e| CsvError::ParseError(e.to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok((headers, rows))
+    }
+
+    /// Lazily parse rows so large files are processed without materializing every transaction
+    pub fn rows<'a>(csv_content: &'a str, mapping: &ColumnMapping) -> Result<ParsedRows<'a>, CsvError> {
+        let delimiter = Self::delimiter(mapping.delimiter, csv_content)?;
         let mut reader = ReaderBuilder::new()
             .has_headers(true)
+            .delimiter(delimiter)
             .from_reader(csv_content.as_bytes());
 
         let headers = match reader.headers() {
@@ -99,57 +428,385 @@ impl CsvParser {
             .map(|(i, h)| (h.to_string(), i))
             .collect();
 
-        if !header_map.contains_key(&mapping.date) {
-            return Err(CsvError::MissingColumn(mapping.date.clone()));
+        let column = |name: &str| {
+            header_map
+                .get(name)
+                .copied()
+                .ok_or_else(|| CsvError::MissingColumn(name.to_string()))
+        };
+
+        let amount = if mapping.uses_debit_credit() {
+            AmountColumns::DebitCredit {
+                debit_idx: mapping.debit.as_deref().map(column).transpose()?,
+                credit_idx: mapping.credit.as_deref().map(column).transpose()?,
+            }
+        } else {
+            AmountColumns::Signed(column(&mapping.amount)?)
+        };
+
+        let date_idx = column(&mapping.date)?;
+        // Without a format, read the whole date column first so one file never mixes day-first
+        // and month-first readings of ambiguous dates like 03/06/2025
+        let date_format = match &mapping.date_format {
+            Some(format) => DateFormat::Given(Self::date_format_pattern(format)?),
+            None => {
+                let mut reader = ReaderBuilder::new()
+                    .has_headers(true)
+                    .flexible(true)
+                    .delimiter(delimiter)
+                    .from_reader(csv_content.as_bytes());
+                let dates: Vec<String> = reader
+                    .records()
+                    .filter_map(|record| record.ok()?.get(date_idx).map(str::to_string))
+                    .collect();
+                match Self::detect_date_format(dates.iter().map(String::as_str)) {
+                    Some(format) => DateFormat::Detected(format),
+                    None => DateFormat::Any,
+                }
+            }
+        };
+
+        let description = if MappingExpression::is_expression(&mapping.description) {
+            FieldSource::Expression(MappingExpression::parse(&mapping.description, column)?)
+        } else {
+            FieldSource::Column(column(&mapping.description)?)
+        };
+        // A merchant column that isn't in the file is ignored, but a bad expression is an error
+        let merchant = match &mapping.merchant {
+            Some(m) if MappingExpression::is_expression(m) => {
+                Some(FieldSource::Expression(MappingExpression::parse(m, column)?))
+            }
+            Some(m) => header_map.get(m).copied().map(FieldSource::Column),
+            None => None,
+        };
+        let status_idx = mapping.status.as_deref().map(column).transpose()?;
+        let member_idx = mapping.member.as_deref().map(column).transpose()?;
+        if let Some(separator) = mapping.decimal_separator {
+            Self::validate_decimal_separator(separator)?;
         }
-        if !header_map.contains_key(&mapping.amount) {
-            return Err(CsvError::MissingColumn(mapping.amount.clone()));
+
+        Ok(ParsedRows {
+            date_idx,
+            date_format,
+            amount,
+            description,
+            merchant,
+            status_idx,
+            member_idx,
+            invert_amounts: mapping.invert_amounts,
+            decimal_separator: mapping.decimal_separator,
+            records: reader.into_records(),
+        })
+    }
+
+    pub fn validate_decimal_separator(separator: char) -> Result<(), CsvError> {
+        match separator {
+            '.' | ',' => Ok(()),
+            c => Err(CsvError::InvalidMapping(format!("Decimal separator must be '.' or ',', not '{}'", c))),
         }
-        if !header_map.contains_key(&mapping.description) {
-            return Err(CsvError::MissingColumn(mapping.description.clone()));
+    }
+
+    /// Whether a status column value marks the row as pending
+    pub fn is_pending_status(value: &str) -> bool {
+        let value = value.trim();
+        PENDING_STATUSES.iter().any(|status| value.eq_ignore_ascii_case(status))
+    }
+
+    /// The 1-based line a record starts on; the reader skips blank lines without counting them
+    /// and positions the record at the first one
+    pub fn record_line(csv_content: &str, record: &csv::StringRecord) -> Option<usize> {
+        let start = record.position()?.byte() as usize;
+        let rest = csv_content.get(start..)?;
+        let blank = rest.len() - rest.trim_start_matches(['\r', '\n']).len();
+        Some(csv_content[..start + blank].matches('\n').count() + 1)
+    }
+
+    pub fn parse(
+        csv_content: &str,
+        mapping: &ColumnMapping,
+    ) -> Result<Vec<ParsedTransaction>, CsvError> {
+        Self::rows(csv_content, mapping)?.collect()
+    }
+}
+
+/// Where a text field's value comes from
+enum FieldSource {
+    Column(usize),
+    Expression(MappingExpression),
+}
+
+impl FieldSource {
+    fn value(&self, record: &csv::StringRecord) -> Option<String> {
+        match self {
+            FieldSource::Column(idx) => record.get(*idx).map(str::to_string),
+            FieldSource::Expression(expr) => Some(expr.eval(record)),
         }
+    }
+}
+
+/// How a row's date is read
+enum DateFormat {
+    Given(String),          // From the mapping; rows that don't match are errors
+    Detected(&'static str), // Read most of the file's dates; other rows fall back to any supported format
+    Any,
+}
+
+/// Where a row's amount comes from
+enum AmountColumns {
+    Signed(usize),
+    DebitCredit {
+        debit_idx: Option<usize>,
+        credit_idx: Option<usize>,
+    },
+}
+
+/// Iterator over the parsed rows of a CSV file
+pub struct ParsedRows<'a> {
+    records: csv::StringRecordsIntoIter<&'a [u8]>,
+    date_idx: usize,
+    date_format: DateFormat,
+    amount: AmountColumns,
+    description: FieldSource,
+    merchant: Option<FieldSource>,
+    status_idx: Option<usize>,
+    member_idx: Option<usize>,
+    invert_amounts: bool,
+    decimal_separator: Option<char>,
+}
+
+impl ParsedRows<'_> {
+    /// Signed amount from either a single column or debit/credit columns (credit minus debit)
+    fn amount(&self, record: &csv::StringRecord) -> Result<Cents, CsvError> {
+        let amount = match self.amount {
+            AmountColumns::Signed(idx) => {
+                let amount_str = record
+                    .get(idx)
+                    .ok_or_else(|| CsvError::ParseError("Missing amount value".to_string()))?;
+                CsvParser::parse_cents(amount_str, self.decimal_separator)?
+            }
+            AmountColumns::DebitCredit { debit_idx, credit_idx } => {
+                // Banks leave the unused side blank (or sometimes zero); values are taken as magnitudes
+                let value = |idx: Option<usize>| {
+                    idx.and_then(|i| record.get(i))
+                        .map(str::trim)
+                        .filter(|v| !v.is_empty())
+                        .map(|v| CsvParser::parse_cents(v, self.decimal_separator).map(Cents::abs))
+                        .transpose()
+                };
+                match (value(debit_idx)?, value(credit_idx)?) {
+                    (None, None) => return Err(CsvError::ParseError("Missing debit and credit values".to_string())),
+                    (debit, credit) => credit.unwrap_or(0) - debit.unwrap_or(0),
+                }
+            }
+        };
+
+        Ok(if self.invert_amounts { -amount } else { amount })
+    }
+
+    fn parse_record(&self, record: &csv::StringRecord) -> Result<ParsedTransaction, CsvError> {
+        let date_raw = record
+            .get(self.date_idx)
+            .ok_or_else(|| CsvError::ParseError("Missing date value".to_string()))?;
+
+        let date = match &self.date_format {
+            DateFormat::Given(format) => CsvParser::parse_date(date_raw, format).ok_or_else(|| {
+                CsvError::ParseError(format!("Date {} doesn't match the mapping's date format", date_raw))
+            })?,
+            DateFormat::Detected(format) => match CsvParser::parse_date(date_raw, format) {
+                Some(date) => date,
+                None => CsvParser::normalize_date(date_raw)?,
+            },
+            DateFormat::Any => CsvParser::normalize_date(date_raw)?,
+        };
+
+        let amount_cents = self.amount(record)?;
 
-        let mut transactions = Vec::new();
-
-        for result in reader.records() {
-            let record = match result {
-                Ok(r) => r,
-                Err(e) => return Err(CsvError::ParseError(e.to_string())),
-            };
-
-            let date_raw = record
-                .get(header_map[&mapping.date])
-                .ok_or_else(|| CsvError::ParseError("Missing date value".to_string()))?;
-
-            let date = Self::normalize_date(date_raw)?;
-
-            let amount_str = record
-                .get(header_map[&mapping.amount])
-                .ok_or_else(|| CsvError::ParseError("Missing amount value".to_string()))?;
-
-            // Clean amount string (remove $ and commas)
-            let cleaned_amount = amount_str.replace("$", "").replace(",", "");
-            let amount: f64 = cleaned_amount
-                .parse()
-                .map_err(|_| CsvError::ParseError(format!("Invalid amount: {}", amount_str)))?;
-
-            let description = record
-                .get(header_map[&mapping.description])
-                .ok_or_else(|| CsvError::ParseError("Missing description value".to_string()))?
-                .to_string();
-
-            let merchant = mapping.merchant.as_ref().and_then(|m| {
-                header_map.get(m).and_then(|&i| record.get(i).map(|s| s.to_string()))
-            });
-
-            transactions.push(ParsedTransaction {
-                date,
-                amount,
-                description,
-                merchant,
-            });
+        let description = self
+            .description
+            .value(record)
+            .ok_or_else(|| CsvError::ParseError("Missing description value".to_string()))?;
+
+        let merchant = self.merchant.as_ref().and_then(|m| m.value(record));
+        let pending = self
+            .status_idx
+            .and_then(|idx| record.get(idx))
+            .is_some_and(CsvParser::is_pending_status);
+        let member = self
+            .member_idx
+            .and_then(|idx| record.get(idx))
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+
+        Ok(ParsedTransaction {
+            date,
+            amount_cents,
+            description,
+            merchant,
+            pending,
+            member,
+        })
+    }
+}
+
+impl Iterator for ParsedRows<'_> {
+    type Item = Result<ParsedTransaction, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = self.records.next()?;
+        Some(
+            record
+                .map_err(|e| CsvError::ParseError(e.to_string()))
+                .and_then(|record| self.parse_record(&record)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_formatted_amounts() {
+        let amount = |value| CsvParser::parse_amount(value, None).unwrap();
+        assert_eq!(amount("$1,234.56"), 1234.56);
+        assert_eq!(amount("1.234,56 €"), 1234.56);
+        assert_eq!(amount("-12,50"), -12.50);
+        assert_eq!(amount("(45.00)"), -45.00);
+        assert_eq!(amount("45.00-"), -45.00);
+        assert_eq!(amount("EUR 1 234,56"), 1234.56);
+        assert_eq!(amount("1'234.50 CHF"), 1234.50);
+        assert_eq!(amount("-£3.10"), -3.10);
+        assert_eq!(amount("1,234"), 1234.0);
+        assert_eq!(amount("1.234.567"), 1234567.0);
+        assert_eq!(amount("+7"), 7.0);
+    }
+
+    #[test]
+    fn test_parse_amount_rejects_non_amounts() {
+        for value in ["", "abc", "Coffee 12", "2024-01-05", "(45.00", "1.2.3,4,5", "12 apples"] {
+            assert!(CsvParser::parse_amount(value, None).is_err(), "{} isn't an amount", value);
         }
+    }
+
+    #[test]
+    fn test_given_decimal_separator() {
+        assert_eq!(CsvParser::parse_amount("1.234", Some(',')).unwrap(), 1234.0);
+        assert_eq!(CsvParser::parse_amount("12,5", Some(',')).unwrap(), 12.5);
+        assert_eq!(CsvParser::parse_amount("1,234", Some('.')).unwrap(), 1234.0);
+    }
+
+    #[test]
+    fn test_detect_delimiter() {
+        assert_eq!(CsvParser::detect_delimiter("Date;Amount;Description\n2025-06-01;-1,00;a"), b';');
+        assert_eq!(CsvParser::detect_delimiter("Date\tAmount\tDescription"), b'\t');
+        assert_eq!(CsvParser::detect_delimiter("\"Date; posted\",Amount,Description"), b',', "Quoted delimiters don't count");
+        assert_eq!(CsvParser::detect_delimiter("Date"), b',');
+    }
+
+    #[test]
+    fn test_rows_with_semicolons() {
+        let mapping = ColumnMapping {
+            date: "Date".to_string(),
+            amount: "Amount".to_string(),
+            description: "Description".to_string(),
+            ..Default::default()
+        };
+        let csv = "Date;Amount;Description\n2025-06-01;-4.50;Caf\u{e9}";
+        let rows = CsvParser::parse(csv, &mapping).unwrap();
+        assert_eq!(rows[0].amount_cents, -450);
+        assert_eq!(rows[0].description, "Café");
+
+        let tabs = ColumnMapping { delimiter: Some('\t'), ..mapping.clone() };
+        assert!(CsvParser::parse(csv, &tabs).is_err(), "A given delimiter overrides detection");
+        let quote = ColumnMapping { delimiter: Some('"'), ..mapping };
+        assert!(matches!(CsvParser::parse(csv, &quote), Err(CsvError::InvalidMapping(_))));
+    }
+
+    #[test]
+    fn test_decode_detects_encodings() {
+        // "Café €5" in Windows-1252
+        let latin = [0x43, 0x61, 0x66, 0xE9, 0x20, 0x80, 0x35];
+        assert_eq!(CsvParser::detect_encoding(&latin), CsvEncoding::Windows1252);
+        assert_eq!(CsvParser::decode(&latin, None).unwrap(), "Café €5");
+
+        let utf16: Vec<u8> = [0xFF, 0xFE].into_iter().chain("Café".encode_utf16().flat_map(u16::to_le_bytes)).collect();
+        assert_eq!(CsvParser::decode(&utf16, None).unwrap(), "Café");
+
+        let utf8 = "\u{feff}Café".as_bytes();
+        assert_eq!(CsvParser::decode(utf8, None).unwrap(), "Café", "The BOM is dropped");
+        assert!(CsvParser::decode(&latin, Some(CsvEncoding::Utf8)).is_err());
+        assert_eq!(CsvParser::decode("Café".as_bytes(), Some(CsvEncoding::Windows1252)).unwrap(), "CafÃ©");
+    }
+
+    #[test]
+    fn test_date_format_pattern() {
+        assert_eq!(CsvParser::date_format_pattern("DD/MM/YYYY").unwrap(), "%d/%m/%Y");
+        assert_eq!(CsvParser::date_format_pattern("MM-DD-YY").unwrap(), "%m-%d-%y");
+        assert_eq!(CsvParser::date_format_pattern("MMM D, YYYY").unwrap(), "%b %d, %Y");
+        assert_eq!(CsvParser::date_format_pattern("%d.%m.%Y").unwrap(), "%d.%m.%Y");
+        assert!(CsvParser::date_format_pattern("MM/YYYY").is_err());
+    }
+
+    #[test]
+    fn test_detect_date_format_prefers_month_first_unless_required() {
+        assert_eq!(CsvParser::detect_date_format(["03/06/2025", "04/07/2025"]), Some("%m/%d/%Y"));
+        assert_eq!(CsvParser::detect_date_format(["03/06/2025", "15/06/2025", ""]), Some("%d/%m/%Y"));
+        assert_eq!(CsvParser::detect_date_format(["Jan 3, 2025"]), Some("%b %d, %Y"));
+        assert_eq!(CsvParser::detect_date_format(["not a date"]), None);
+    }
+
+    #[test]
+    fn test_rows_read_ambiguous_dates_consistently() {
+        let mapping = ColumnMapping {
+            date: "Date".to_string(),
+            amount: "Amount".to_string(),
+            description: "Description".to_string(),
+            ..Default::default()
+        };
+        // Day-first because of 15/06; the last row falls back to another supported format
+        let csv = "Date,Amount,Description\n03/06/2025,-1.00,a\n15/06/2025,-2.00,b\n\"Jun 20, 2025\",-3.00,c";
+        let dates: Vec<String> = CsvParser::parse(csv, &mapping).unwrap().into_iter().map(|t| t.date).collect();
+        assert_eq!(dates, vec!["2025-06-03", "2025-06-15", "2025-06-20"]);
+
+        let given = ColumnMapping {
+            date_format: Some("MM/DD/YYYY".to_string()),
+            ..mapping
+        };
+        assert!(CsvParser::parse(csv, &given).is_err(), "Rows must match a given format");
+    }
+
+    #[test]
+    fn test_status_column_marks_pending_rows() {
+        let mapping = ColumnMapping {
+            date: "Date".to_string(),
+            amount: "Amount".to_string(),
+            description: "Description".to_string(),
+            status: Some("Status".to_string()),
+            ..Default::default()
+        };
+        let csv = "Date,Amount,Description,Status\n2025-06-01,-1.00,a,Pending\n2025-06-02,-2.00,b,Posted\n2025-06-03,-3.00,c,";
+        let pending: Vec<bool> = CsvParser::parse(csv, &mapping).unwrap().into_iter().map(|t| t.pending).collect();
+        assert_eq!(pending, vec![true, false, false]);
 
-        Ok(transactions)
+        let missing = ColumnMapping {
+            status: Some("State".to_string()),
+            ..mapping
+        };
+        assert!(CsvParser::parse(csv, &missing).is_err(), "A mapped status column must be in the file");
+    }
+
+    #[test]
+    fn test_member_column_names_the_member() {
+        let mapping = ColumnMapping {
+            date: "Date".to_string(),
+            amount: "Amount".to_string(),
+            description: "Description".to_string(),
+            member: Some("Cardholder".to_string()),
+            ..Default::default()
+        };
+        let csv = "Date,Amount,Description,Cardholder\n2025-06-01,-1.00,a, Alex \n2025-06-02,-2.00,b,";
+        let members: Vec<Option<String>> = CsvParser::parse(csv, &mapping).unwrap().into_iter().map(|t| t.member).collect();
+        assert_eq!(members, vec![Some("Alex".to_string()), None]);
     }
 }
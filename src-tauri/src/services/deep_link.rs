@@ -0,0 +1,131 @@
+use crate::utils::money::{self, Cents};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// URI scheme registered with the OS (budgetbalancer://...)
+pub const DEEP_LINK_SCHEME: &str = "budgetbalancer";
+
+/// Frontend views a deep link may open
+pub const DEEP_LINK_VIEWS: &[&str] = &["transactions", "dashboard", "debts", "spending", "trends", "settings"];
+
+/// An action requested through a deep link
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DeepLinkAction {
+    /// budgetbalancer://add-expense?amount=12.50&category=Coffee&description=Latte&account=Checking&date=2025-01-31
+    AddExpense {
+        #[serde(rename = "amount", with = "money::dollars")]
+        amount_cents: Cents, // Positive; stored as a negative transaction
+        category: Option<String>,
+        description: Option<String>,
+        merchant: Option<String>,
+        account: Option<String>,
+        date: Option<String>,
+    },
+    /// budgetbalancer://open?view=debts
+    Open { view: String },
+}
+
+pub struct DeepLink;
+
+impl DeepLink {
+    /// Parse a deep link URL into an action
+    /// Accepts both budgetbalancer://add-expense?... and budgetbalancer:add-expense?...
+    pub fn parse(raw: &str) -> Result<DeepLinkAction, String> {
+        let url = Url::parse(raw.trim()).map_err(|_| "Invalid deep link URL".to_string())?;
+        if url.scheme() != DEEP_LINK_SCHEME {
+            return Err(format!("Unsupported deep link scheme: {}", url.scheme()));
+        }
+
+        let action = url
+            .host_str()
+            .filter(|host| !host.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| url.path().trim_matches('/').to_string());
+
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        match action.to_lowercase().as_str() {
+            "add-expense" => {
+                let amount_str = param("amount").ok_or_else(|| "Missing amount".to_string())?;
+                let amount_cents = amount_str
+                    .trim_start_matches('$')
+                    .parse()
+                    .map_err(|_| format!("Invalid amount: {}", amount_str))
+                    .and_then(money::parse_dollars)?;
+                if amount_cents <= 0 {
+                    return Err("Amount must be greater than zero".to_string());
+                }
+
+                Ok(DeepLinkAction::AddExpense {
+                    amount_cents,
+                    category: param("category"),
+                    description: param("description"),
+                    merchant: param("merchant"),
+                    account: param("account"),
+                    date: param("date"),
+                })
+            }
+            "open" => {
+                let view = param("view").ok_or_else(|| "Missing view".to_string())?.to_lowercase();
+                if !DEEP_LINK_VIEWS.contains(&view.as_str()) {
+                    return Err(format!("Unknown view: {}", view));
+                }
+                Ok(DeepLinkAction::Open { view })
+            }
+            "" => Err("Missing deep link action".to_string()),
+            other => Err(format!("Unknown deep link action: {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_add_expense() {
+        let action = DeepLink::parse("budgetbalancer://add-expense?amount=12.50&category=Coffee&description=Flat%20white").unwrap();
+        assert_eq!(
+            action,
+            DeepLinkAction::AddExpense {
+                amount_cents: 1250,
+                category: Some("Coffee".to_string()),
+                description: Some("Flat white".to_string()),
+                merchant: None,
+                account: None,
+                date: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_without_slashes() {
+        let action = DeepLink::parse("budgetbalancer:add-expense?amount=$4").unwrap();
+        assert!(matches!(action, DeepLinkAction::AddExpense { amount_cents: 400, .. }));
+    }
+
+    #[test]
+    fn test_parse_open_view() {
+        assert_eq!(
+            DeepLink::parse("budgetbalancer://open?view=Debts").unwrap(),
+            DeepLinkAction::Open { view: "debts".to_string() }
+        );
+        assert!(DeepLink::parse("budgetbalancer://open?view=admin").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_links() {
+        assert!(DeepLink::parse("https://add-expense?amount=5").is_err());
+        assert!(DeepLink::parse("budgetbalancer://add-expense").is_err());
+        assert!(DeepLink::parse("budgetbalancer://add-expense?amount=-5").is_err());
+        assert!(DeepLink::parse("budgetbalancer://add-expense?amount=abc").is_err());
+        assert!(DeepLink::parse("budgetbalancer://delete-everything").is_err());
+        assert!(DeepLink::parse("not a url").is_err());
+    }
+}
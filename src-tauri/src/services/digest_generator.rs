@@ -0,0 +1,544 @@
+use crate::constants::PERCENT_TO_DECIMAL_DIVISOR;
+use crate::services::spending_aggregator::{CategorySpending, DatePeriod, SpendingAggregator};
+use crate::services::target_tracker::{TargetProgress, TargetTracker};
+use crate::utils::money::{self, Cents};
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// Days covered by a weekly digest
+const DIGEST_WEEK_DAYS: i64 = 7;
+
+/// Categories listed in a digest's top spending
+const DIGEST_TOP_CATEGORIES: i64 = 5;
+
+/// Whether a digest covers a week or a calendar month
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestFrequency {
+    Weekly,
+    Monthly,
+}
+
+impl DigestFrequency {
+    /// "week" or "month", as used in the digest's wording
+    pub fn unit(self) -> &'static str {
+        match self {
+            DigestFrequency::Weekly => "week",
+            DigestFrequency::Monthly => "month",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            DigestFrequency::Weekly => "Weekly",
+            DigestFrequency::Monthly => "Monthly",
+        }
+    }
+}
+
+impl std::str::FromStr for DigestFrequency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "weekly" => Ok(DigestFrequency::Weekly),
+            "monthly" => Ok(DigestFrequency::Monthly),
+            _ => Err(format!("Invalid digest frequency: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestBill {
+    pub debt_id: i64,
+    pub name: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents, // Minimum payment; due dates are not tracked yet
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestDebtProgress {
+    #[serde(rename = "total_balance", with = "money::dollars")]
+    pub total_balance_cents: Cents,
+    #[serde(rename = "total_original_balance", with = "money::dollars")]
+    pub total_original_balance_cents: Cents,
+    pub percent_paid: f64,
+    #[serde(rename = "paid_in_period", with = "money::dollars")]
+    pub paid_in_period_cents: Cents,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Digest {
+    pub frequency: DigestFrequency,
+    pub period: DatePeriod,
+    pub previous_period: DatePeriod,
+    #[serde(rename = "total_spending", with = "money::dollars")]
+    pub total_spending_cents: Cents,
+    #[serde(rename = "previous_spending", with = "money::dollars")]
+    pub previous_spending_cents: Cents,
+    pub change_percent: Option<f64>, // None when nothing was spent the previous week or month
+    #[serde(rename = "total_income", with = "money::dollars")]
+    pub total_income_cents: Cents,
+    pub top_categories: Vec<CategorySpending>,
+    pub budget_period: DatePeriod,   // The month (to date, for weekly digests), since targets are monthly
+    pub budgets: Vec<TargetProgress>,
+    pub budgets_over: usize,
+    pub upcoming_bills: Vec<DigestBill>,
+    pub debt_progress: DigestDebtProgress,
+}
+
+pub struct DigestGenerator;
+
+impl DigestGenerator {
+    fn format_date(date: NaiveDate) -> String {
+        date.format("%Y-%m-%d").to_string()
+    }
+
+    /// (start, end) of the digest period for `date` and of the period before it
+    /// A weekly digest covers the 7 days ending on `date`; a monthly one the calendar month containing it
+    pub fn periods(frequency: DigestFrequency, date: NaiveDate) -> ((NaiveDate, NaiveDate), (NaiveDate, NaiveDate)) {
+        match frequency {
+            DigestFrequency::Weekly => {
+                let start = date - chrono::Duration::days(DIGEST_WEEK_DAYS - 1);
+                let previous_end = start - chrono::Duration::days(1);
+                let previous_start = previous_end - chrono::Duration::days(DIGEST_WEEK_DAYS - 1);
+                ((start, date), (previous_start, previous_end))
+            }
+            DigestFrequency::Monthly => {
+                let start = date.with_day(1).unwrap_or(date);
+                let end = start + Months::new(1) - chrono::Duration::days(1);
+                let previous_end = start - chrono::Duration::days(1);
+                let previous_start = previous_end.with_day(1).unwrap_or(previous_end);
+                ((start, end), (previous_start, previous_end))
+            }
+        }
+    }
+
+    /// Build the digest for the 7 days ending on `week_ending` (inclusive)
+    pub async fn build_weekly(db: &SqlitePool, week_ending: NaiveDate) -> Result<Digest, String> {
+        Self::build(db, DigestFrequency::Weekly, week_ending).await
+    }
+
+    /// Build the digest for the week ending on `date` or the month containing it
+    pub async fn build(db: &SqlitePool, frequency: DigestFrequency, date: NaiveDate) -> Result<Digest, String> {
+        let ((start, end), (previous_start, previous_end)) = Self::periods(frequency, date);
+
+        let period = DatePeriod {
+            start_date: Self::format_date(start),
+            end_date: Self::format_date(end),
+        };
+        let previous_period = DatePeriod {
+            start_date: Self::format_date(previous_start),
+            end_date: Self::format_date(previous_end),
+        };
+
+        let total_spending = SpendingAggregator::get_total_spending(db, &period.start_date, &period.end_date).await?;
+        let previous_spending =
+            SpendingAggregator::get_total_spending(db, &previous_period.start_date, &previous_period.end_date).await?;
+        let total_income = SpendingAggregator::get_total_income(db, &period.start_date, &period.end_date).await?;
+        let change_percent = if previous_spending > 0 {
+            Some((total_spending - previous_spending) as f64 / previous_spending as f64 * PERCENT_TO_DECIMAL_DIVISOR)
+        } else {
+            None
+        };
+        let top_categories = SpendingAggregator::get_top_categories(
+            db,
+            &period.start_date,
+            &period.end_date,
+            DIGEST_TOP_CATEGORIES,
+            None,
+        )
+        .await?;
+
+        let budget_period = match frequency {
+            DigestFrequency::Weekly => DatePeriod {
+                start_date: format!("{}-{:02}-01", end.year(), end.month()),
+                end_date: Self::format_date(end),
+            },
+            DigestFrequency::Monthly => period.clone(),
+        };
+        let budgets = TargetTracker::get_targets_progress(db, &budget_period.start_date, &budget_period.end_date)
+            .await?
+            .targets;
+        let budgets_over = budgets.iter().filter(|b| b.status == "over").count();
+
+        let upcoming_bills = sqlx::query_as::<_, (i64, String, Cents)>(
+            "SELECT id, name, min_payment_cents FROM debts WHERE balance_cents > 0 AND min_payment_cents > 0 ORDER BY name"
+        )
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|(debt_id, name, amount_cents)| DigestBill { debt_id, name, amount_cents })
+        .collect();
+
+        let (total_balance, total_original_balance) = sqlx::query_as::<_, (Cents, Cents)>(
+            "SELECT COALESCE(SUM(balance_cents), 0), COALESCE(SUM(original_balance_cents), 0)
+             FROM debts"
+        )
+        .fetch_one(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let (paid_in_period,) = sqlx::query_as::<_, (Cents,)>(
+            "SELECT COALESCE(SUM(amount_cents), 0) FROM debt_payments WHERE date >= ? AND date <= ?"
+        )
+        .bind(&period.start_date)
+        .bind(&period.end_date)
+        .fetch_one(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let percent_paid = if total_original_balance > 0 {
+            ((total_original_balance - total_balance) as f64 / total_original_balance as f64 * PERCENT_TO_DECIMAL_DIVISOR)
+                .max(0.0)
+        } else {
+            0.0
+        };
+
+        Ok(Digest {
+            frequency,
+            period,
+            previous_period,
+            total_spending_cents: total_spending,
+            previous_spending_cents: previous_spending,
+            change_percent,
+            total_income_cents: total_income,
+            top_categories,
+            budget_period,
+            budgets,
+            budgets_over,
+            upcoming_bills,
+            debt_progress: DigestDebtProgress {
+                total_balance_cents: total_balance,
+                total_original_balance_cents: total_original_balance,
+                percent_paid,
+                paid_in_period_cents: paid_in_period,
+            },
+        })
+    }
+
+    /// One-line summary used as the notification message
+    pub fn summary(digest: &Digest) -> String {
+        let unit = digest.frequency.unit();
+        let mut summary = format!("Spent ${:.2}", money::from_cents(digest.total_spending_cents));
+        if let Some(change) = digest.change_percent {
+            summary.push_str(&format!(" ({:+.0}% vs last {})", change, unit));
+        }
+        summary.push('.');
+        if !digest.budgets.is_empty() {
+            summary.push_str(&format!(
+                " {} of {} budgets over target.",
+                digest.budgets_over,
+                digest.budgets.len()
+            ));
+        }
+        if digest.debt_progress.paid_in_period_cents > 0 {
+            summary.push_str(&format!(
+                " Paid ${:.2} toward debts.",
+                money::from_cents(digest.debt_progress.paid_in_period_cents)
+            ));
+        }
+        summary
+    }
+
+    fn title(digest: &Digest) -> String {
+        format!(
+            "{} Summary: {} to {}",
+            digest.frequency.title(),
+            digest.period.start_date,
+            digest.period.end_date
+        )
+    }
+
+    /// Targets that were exceeded, with the amount over
+    fn breaches(digest: &Digest) -> impl Iterator<Item = (&TargetProgress, Cents)> {
+        digest
+            .budgets
+            .iter()
+            .filter(|b| b.status == "over")
+            .map(|b| (b, b.actual_amount_cents - b.target_amount_cents))
+    }
+
+    pub fn render_markdown(digest: &Digest) -> String {
+        let unit = digest.frequency.unit();
+        let mut out = format!(
+            "# {}\n\n{}\n\n## Spending\n\n- This {unit}: ${:.2}\n- Last {unit}: ${:.2}\n- Income: ${:.2}\n",
+            Self::title(digest),
+            Self::summary(digest),
+            money::from_cents(digest.total_spending_cents),
+            money::from_cents(digest.previous_spending_cents),
+            money::from_cents(digest.total_income_cents)
+        );
+
+        out.push_str("\n## Top Categories\n\n");
+        if digest.top_categories.is_empty() {
+            out.push_str("No spending recorded.\n");
+        } else {
+            for (rank, category) in digest.top_categories.iter().enumerate() {
+                out.push_str(&format!(
+                    "{}. {}: ${:.2} ({:.0}%)\n",
+                    rank + 1,
+                    category.category_name,
+                    money::from_cents(category.amount_cents),
+                    category.percentage
+                ));
+            }
+        }
+
+        out.push_str(&format!(
+            "\n## Budgets ({} to {})\n\n",
+            digest.budget_period.start_date, digest.budget_period.end_date
+        ));
+        if digest.budgets.is_empty() {
+            out.push_str("No spending targets set.\n");
+        } else {
+            out.push_str("| Category | Spent | Target | Used | Status |\n|---|---|---|---|---|\n");
+            for budget in &digest.budgets {
+                out.push_str(&format!(
+                    "| {} | ${:.2} | ${:.2} | {:.0}% | {} |\n",
+                    budget.category_name.replace('|', "\\|"),
+                    money::from_cents(budget.actual_amount_cents),
+                    money::from_cents(budget.target_amount_cents),
+                    budget.percentage_used,
+                    budget.status
+                ));
+            }
+            let breaches: Vec<String> = Self::breaches(digest)
+                .map(|(budget, over)| {
+                    format!(
+                        "- {} is ${:.2} over its ${:.2} target\n",
+                        budget.category_name,
+                        money::from_cents(over),
+                        money::from_cents(budget.target_amount_cents)
+                    )
+                })
+                .collect();
+            if !breaches.is_empty() {
+                out.push_str("\n### Over Target\n\n");
+                out.push_str(&breaches.concat());
+            }
+        }
+
+        out.push_str("\n## Upcoming Bills\n\n");
+        if digest.upcoming_bills.is_empty() {
+            out.push_str("No upcoming debt payments.\n");
+        } else {
+            for bill in &digest.upcoming_bills {
+                out.push_str(&format!("- {}: ${:.2} minimum\n", bill.name, money::from_cents(bill.amount_cents)));
+            }
+        }
+
+        let debt = &digest.debt_progress;
+        out.push_str(&format!(
+            "\n## Debt Progress\n\n- Remaining balance: ${:.2} of ${:.2}\n- Paid off: {:.1}%\n- Paid this {unit}: ${:.2}\n",
+            money::from_cents(debt.total_balance_cents),
+            money::from_cents(debt.total_original_balance_cents),
+            debt.percent_paid,
+            money::from_cents(debt.paid_in_period_cents)
+        ));
+
+        out
+    }
+
+    fn escape_html(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    pub fn render_html(digest: &Digest) -> String {
+        let unit = digest.frequency.unit();
+        let title = Self::title(digest);
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{title}</title></head>\n<body>\n\
+             <h1>{title}</h1>\n<p>{}</p>\n<h2>Spending</h2>\n<ul>\n\
+             <li>This {unit}: ${:.2}</li>\n<li>Last {unit}: ${:.2}</li>\n<li>Income: ${:.2}</li>\n</ul>\n",
+            Self::escape_html(&Self::summary(digest)),
+            money::from_cents(digest.total_spending_cents),
+            money::from_cents(digest.previous_spending_cents),
+            money::from_cents(digest.total_income_cents)
+        );
+
+        out.push_str("<h2>Top Categories</h2>\n");
+        if digest.top_categories.is_empty() {
+            out.push_str("<p>No spending recorded.</p>\n");
+        } else {
+            out.push_str("<ol>\n");
+            for category in &digest.top_categories {
+                out.push_str(&format!(
+                    "<li>{}: ${:.2} ({:.0}%)</li>\n",
+                    Self::escape_html(&category.category_name),
+                    money::from_cents(category.amount_cents),
+                    category.percentage
+                ));
+            }
+            out.push_str("</ol>\n");
+        }
+
+        out.push_str(&format!(
+            "<h2>Budgets ({} to {})</h2>\n",
+            digest.budget_period.start_date, digest.budget_period.end_date
+        ));
+        if digest.budgets.is_empty() {
+            out.push_str("<p>No spending targets set.</p>\n");
+        } else {
+            out.push_str("<table>\n<tr><th>Category</th><th>Spent</th><th>Target</th><th>Used</th><th>Status</th></tr>\n");
+            for budget in &digest.budgets {
+                out.push_str(&format!(
+                    "<tr><td>{}</td><td>${:.2}</td><td>${:.2}</td><td>{:.0}%</td><td>{}</td></tr>\n",
+                    Self::escape_html(&budget.category_name),
+                    money::from_cents(budget.actual_amount_cents),
+                    money::from_cents(budget.target_amount_cents),
+                    budget.percentage_used,
+                    budget.status
+                ));
+            }
+            out.push_str("</table>\n");
+            let breaches: Vec<String> = Self::breaches(digest)
+                .map(|(budget, over)| {
+                    format!(
+                        "<li>{} is ${:.2} over its ${:.2} target</li>\n",
+                        Self::escape_html(&budget.category_name),
+                        money::from_cents(over),
+                        money::from_cents(budget.target_amount_cents)
+                    )
+                })
+                .collect();
+            if !breaches.is_empty() {
+                out.push_str("<h3>Over Target</h3>\n<ul>\n");
+                out.push_str(&breaches.concat());
+                out.push_str("</ul>\n");
+            }
+        }
+
+        out.push_str("<h2>Upcoming Bills</h2>\n");
+        if digest.upcoming_bills.is_empty() {
+            out.push_str("<p>No upcoming debt payments.</p>\n");
+        } else {
+            out.push_str("<ul>\n");
+            for bill in &digest.upcoming_bills {
+                out.push_str(&format!(
+                    "<li>{}: ${:.2} minimum</li>\n",
+                    Self::escape_html(&bill.name),
+                    money::from_cents(bill.amount_cents)
+                ));
+            }
+            out.push_str("</ul>\n");
+        }
+
+        let debt = &digest.debt_progress;
+        out.push_str(&format!(
+            "<h2>Debt Progress</h2>\n<ul>\n<li>Remaining balance: ${:.2} of ${:.2}</li>\n\
+             <li>Paid off: {:.1}%</li>\n<li>Paid this {unit}: ${:.2}</li>\n</ul>\n</body>\n</html>\n",
+            money::from_cents(debt.total_balance_cents),
+            money::from_cents(debt.total_original_balance_cents),
+            debt.percent_paid,
+            money::from_cents(debt.paid_in_period_cents)
+        ));
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest() -> Digest {
+        Digest {
+            frequency: DigestFrequency::Weekly,
+            period: DatePeriod { start_date: "2025-03-03".to_string(), end_date: "2025-03-09".to_string() },
+            previous_period: DatePeriod { start_date: "2025-02-24".to_string(), end_date: "2025-03-02".to_string() },
+            total_spending_cents: 15_000,
+            previous_spending_cents: 10_000,
+            change_percent: Some(50.0),
+            total_income_cents: 0,
+            top_categories: vec![CategorySpending {
+                category_id: 2,
+                category_name: "Dining <Out>".to_string(),
+                category_icon: None,
+                amount_cents: 12_000,
+                percentage: 80.0,
+                transaction_count: 3,
+            }],
+            budget_period: DatePeriod { start_date: "2025-03-01".to_string(), end_date: "2025-03-09".to_string() },
+            budgets: vec![TargetProgress {
+                target_id: 1,
+                scope: "category".to_string(),
+                category_id: Some(2),
+                account_id: None,
+                category_name: "Dining <Out>".to_string(),
+                target_amount_cents: 10_000,
+                actual_amount_cents: 12_000,
+                remaining_cents: -2000,
+                percentage_used: 120.0,
+                status: "over".to_string(),
+                variance_cents: 2000,
+            }],
+            budgets_over: 1,
+            upcoming_bills: vec![DigestBill { debt_id: 1, name: "Visa".to_string(), amount_cents: 3500 }],
+            debt_progress: DigestDebtProgress {
+                total_balance_cents: 75_000,
+                total_original_balance_cents: 100_000,
+                percent_paid: 25.0,
+                paid_in_period_cents: 5000,
+            },
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(
+            DigestGenerator::summary(&digest()),
+            "Spent $150.00 (+50% vs last week). 1 of 1 budgets over target. Paid $50.00 toward debts."
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_sections() {
+        let markdown = DigestGenerator::render_markdown(&digest());
+        assert!(markdown.starts_with("# Weekly Summary: 2025-03-03 to 2025-03-09"));
+        assert!(markdown.contains("| Dining <Out> | $120.00 | $100.00 | 120% | over |"));
+        assert!(markdown.contains("- Visa: $35.00 minimum"));
+        assert!(markdown.contains("- Paid off: 25.0%"));
+    }
+
+    #[test]
+    fn test_periods() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+        let day = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+
+        assert_eq!(
+            DigestGenerator::periods(DigestFrequency::Weekly, date),
+            ((day(3, 8), day(3, 14)), (day(3, 1), day(3, 7)))
+        );
+        assert_eq!(
+            DigestGenerator::periods(DigestFrequency::Monthly, date),
+            ((day(3, 1), day(3, 31)), (day(2, 1), day(2, 29)))
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_monthly_breaches() {
+        let mut digest = digest();
+        digest.frequency = DigestFrequency::Monthly;
+        let markdown = DigestGenerator::render_markdown(&digest);
+
+        assert!(markdown.starts_with("# Monthly Summary: 2025-03-03 to 2025-03-09"));
+        assert!(markdown.contains("(+50% vs last month)"));
+        assert!(markdown.contains("- Last month: $100.00"));
+        assert!(markdown.contains("1. Dining <Out>: $120.00 (80%)"));
+        assert!(markdown.contains("### Over Target\n\n- Dining <Out> is $20.00 over its $100.00 target"));
+        assert!(markdown.contains("- Paid this month: $50.00"));
+    }
+
+    #[test]
+    fn test_render_html_escapes_names() {
+        let html = DigestGenerator::render_html(&digest());
+        assert!(html.contains("<td>Dining &lt;Out&gt;</td>"));
+        assert!(!html.contains("<Out>"));
+    }
+}
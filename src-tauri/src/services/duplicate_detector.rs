@@ -1,4 +1,10 @@
+use super::csv_parser::ParsedTransaction;
+use crate::models::duplicate_settings::{DuplicateField, DuplicateSettings, DuplicateStrictness};
 use crate::models::transaction::NewTransaction;
+use crate::utils::dates;
+use crate::utils::money::Cents;
+use chrono::NaiveDate;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug)]
 pub enum DuplicateError {
@@ -17,34 +23,326 @@ impl std::error::Error for DuplicateError {}
 
 pub struct DuplicateDetector;
 
+/// A transaction reduced to the fields an account's settings compare
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MatchKey {
+    date: Option<String>,
+    cents: Option<i64>,
+    description: Option<String>,
+    merchant: Option<String>,
+}
+
+/// A stored transaction that later rows can match
+struct Candidate {
+    date: Option<NaiveDate>,
+    claimed: bool, // Already matched by a row, under counted strictness
+}
+
+/// Duplicate detection under an account's own settings, against that account's transactions
+pub struct DuplicateMatcher {
+    settings: DuplicateSettings,
+    existing: HashMap<MatchKey, Vec<Candidate>>,
+    seen: HashMap<MatchKey, Vec<Option<NaiveDate>>>, // Earlier rows of this import
+}
+
+impl DuplicateMatcher {
+    /// `existing` holds the account's stored (date, amount in cents, description, merchant)
+    pub fn new(settings: DuplicateSettings, existing: Vec<(String, Cents, String, Option<String>)>) -> Self {
+        let mut matcher = Self { settings, existing: HashMap::new(), seen: HashMap::new() };
+        for (date, amount_cents, description, merchant) in existing {
+            let key = matcher.key(&date, amount_cents, &description, merchant.as_deref());
+            matcher.existing.entry(key).or_default().push(Candidate { date: dates::parse_date(&date).ok(), claimed: false });
+        }
+        matcher
+    }
+
+    fn key(&self, date: &str, amount_cents: Cents, description: &str, merchant: Option<&str>) -> MatchKey {
+        let uses = |field| self.settings.fields.contains(&field);
+        MatchKey {
+            date: uses(DuplicateField::Date).then(|| date.to_string()),
+            cents: uses(DuplicateField::Amount).then_some(amount_cents),
+            description: uses(DuplicateField::Description).then(|| description.trim().to_string()),
+            merchant: uses(DuplicateField::Merchant).then(|| merchant.unwrap_or_default().trim().to_lowercase()),
+        }
+    }
+
+    /// Whether two dates are close enough to match; an unreadable date matches any
+    fn within(lookback_days: Option<i64>, a: Option<NaiveDate>, b: Option<NaiveDate>) -> bool {
+        match (lookback_days, a, b) {
+            (Some(days), Some(a), Some(b)) => (a - b).num_days().abs() <= days,
+            _ => true,
+        }
+    }
+
+    /// Check a row, recording it so later rows are checked against it too
+    pub fn is_duplicate(&mut self, transaction: &ParsedTransaction) -> bool {
+        let key = self.key(
+            &transaction.date,
+            transaction.amount_cents,
+            &transaction.description,
+            transaction.merchant.as_deref(),
+        );
+        let date = dates::parse_date(&transaction.date).ok();
+        let days = self.settings.lookback_days;
+
+        match self.settings.strictness {
+            DuplicateStrictness::Off => false,
+            DuplicateStrictness::Strict => {
+                let stored = self
+                    .existing
+                    .get(&key)
+                    .is_some_and(|candidates| candidates.iter().any(|c| Self::within(days, c.date, date)));
+                let repeated = self
+                    .seen
+                    .get(&key)
+                    .is_some_and(|dates| dates.iter().any(|d| Self::within(days, *d, date)));
+                if !stored && !repeated {
+                    self.seen.entry(key).or_default().push(date);
+                }
+                stored || repeated
+            }
+            DuplicateStrictness::Counted => {
+                let candidate = self.existing.get_mut(&key).and_then(|candidates| {
+                    candidates.iter_mut().find(|c| !c.claimed && Self::within(days, c.date, date))
+                });
+                match candidate {
+                    Some(candidate) => {
+                        candidate.claimed = true;
+                        true
+                    }
+                    None => false,
+                }
+            }
+        }
+    }
+}
+
+/// How an import decides which rows are already there
+pub enum DuplicateCheck {
+    /// The default: by hash, and against earlier rows of the file
+    /// `legacy_versions` are older hash versions still stored, whose hashes are checked as well
+    Hashes { account_id: i64, legacy_versions: Vec<i64>, seen: HashSet<String> },
+    /// The account's own settings; `reserved` holds hashes given to rows not yet written
+    Settings { matcher: Box<DuplicateMatcher>, reserved: HashSet<String> },
+}
+
+impl DuplicateCheck {
+    pub async fn for_account(db: &sqlx::Pool<sqlx::Sqlite>, account_id: i64) -> Result<Self, DuplicateError> {
+        let settings = DuplicateDetector::settings(db, account_id).await?;
+        if settings.is_default {
+            let legacy_versions = DuplicateDetector::legacy_versions(db).await?;
+            return Ok(DuplicateCheck::Hashes { account_id, legacy_versions, seen: HashSet::new() });
+        }
+
+        let existing = if settings.strictness == DuplicateStrictness::Off {
+            Vec::new()
+        } else {
+            sqlx::query_as("SELECT date, amount_cents, description, merchant FROM transactions WHERE account_id = ?")
+                .bind(account_id)
+                .fetch_all(db)
+                .await
+                .map_err(|e| DuplicateError::DatabaseError(e.to_string()))?
+        };
+        Ok(DuplicateCheck::Settings { matcher: Box::new(DuplicateMatcher::new(settings, existing)), reserved: HashSet::new() })
+    }
+
+    /// Which rows of a batch are duplicates; `hashes` are the rows' transaction hashes
+    pub async fn check_batch(
+        &mut self,
+        db: &sqlx::Pool<sqlx::Sqlite>,
+        batch: &[&ParsedTransaction],
+        hashes: &[String],
+    ) -> Result<Vec<bool>, DuplicateError> {
+        match self {
+            DuplicateCheck::Hashes { account_id, legacy_versions, seen } => {
+                let existing = DuplicateDetector::existing_hashes(db, hashes).await?;
+                let mut flags: Vec<bool> = hashes.iter().map(|hash| existing.contains(hash)).collect();
+
+                // Rows not yet rehashed only match under the version they were stored with
+                for &version in legacy_versions.iter() {
+                    let legacy: Vec<String> = batch
+                        .iter()
+                        .map(|t| NewTransaction::hash_for_version(version, *account_id, &t.date, t.amount_cents, &t.description))
+                        .collect();
+                    let existing = DuplicateDetector::existing_hashes(db, &legacy).await?;
+                    for (flag, hash) in flags.iter_mut().zip(&legacy) {
+                        *flag |= existing.contains(hash);
+                    }
+                }
+
+                Ok(flags.into_iter().zip(hashes).map(|(flag, hash)| flag || !seen.insert(hash.clone())).collect())
+            }
+            DuplicateCheck::Settings { matcher, .. } => Ok(batch.iter().map(|t| matcher.is_duplicate(t)).collect()),
+        }
+    }
+
+    /// The hash to store a row that isn't a duplicate under; settings can keep rows that are
+    /// identical to stored ones, which then need a hash of their own
+    pub async fn storable_hash(&mut self, db: &sqlx::Pool<sqlx::Sqlite>, hash: String) -> Result<String, DuplicateError> {
+        match self {
+            DuplicateCheck::Hashes { .. } => Ok(hash),
+            DuplicateCheck::Settings { reserved, .. } => {
+                let hash = DuplicateDetector::free_hash(db, &hash, reserved).await?;
+                reserved.insert(hash.clone());
+                Ok(hash)
+            }
+        }
+    }
+}
+
 impl DuplicateDetector {
+    /// The account's duplicate settings, or the default when it has none
+    pub async fn settings(db: &sqlx::Pool<sqlx::Sqlite>, account_id: i64) -> Result<DuplicateSettings, DuplicateError> {
+        let row: Option<(String, Option<i64>, String, String)> = sqlx::query_as(
+            "SELECT match_fields, lookback_days, strictness, updated_at FROM duplicate_settings WHERE account_id = ?"
+        )
+        .bind(account_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| DuplicateError::DatabaseError(e.to_string()))?;
+
+        let Some((fields, lookback_days, strictness, updated_at)) = row else {
+            return Ok(DuplicateSettings::default_for(account_id));
+        };
+        Ok(DuplicateSettings {
+            account_id,
+            fields: fields.split(',').filter_map(|f| f.trim().parse().ok()).collect(),
+            lookback_days,
+            strictness: strictness.parse().unwrap_or_default(),
+            is_default: false,
+            updated_at: Some(updated_at),
+        })
+    }
+
+    /// Hash versions older than the current one that some stored transactions still have
+    pub async fn legacy_versions(db: &sqlx::Pool<sqlx::Sqlite>) -> Result<Vec<i64>, DuplicateError> {
+        sqlx::query_scalar("SELECT DISTINCT hash_version FROM transactions WHERE hash_version < ? ORDER BY hash_version")
+            .bind(NewTransaction::HASH_VERSION)
+            .fetch_all(db)
+            .await
+            .map_err(|e| DuplicateError::DatabaseError(e.to_string()))
+    }
+
     pub async fn is_duplicate(
         db: &sqlx::Pool<sqlx::Sqlite>,
+        account_id: i64,
         date: &str,
-        amount: f64,
+        amount_cents: Cents,
         description: &str,
     ) -> Result<bool, DuplicateError> {
-        let hash = NewTransaction::calculate_hash(date, amount, description);
+        // A match under any version counts, since older rows may not be rehashed yet
+        let hashes: Vec<String> = (1..=NewTransaction::HASH_VERSION)
+            .map(|version| NewTransaction::hash_for_version(version, account_id, date, amount_cents, description))
+            .collect();
+        Ok(!Self::existing_hashes(db, &hashes).await?.is_empty())
+    }
+
+    /// A hash to store a transaction under when identical ones are being kept: its own hash when
+    /// that's free, otherwise the first free `hash:n` (hashes are unique)
+    /// `reserved` holds hashes already chosen but not yet written
+    pub async fn free_hash<'e, E: sqlx::SqliteExecutor<'e>>(
+        executor: E,
+        hash: &str,
+        reserved: &HashSet<String>,
+    ) -> Result<String, DuplicateError> {
+        let taken: HashSet<String> = sqlx::query_scalar(
+            "SELECT hash FROM transactions WHERE hash = ? OR (hash > ? AND hash < ?)"
+        )
+        .bind(hash)
+        .bind(format!("{}:", hash))
+        .bind(format!("{};", hash))
+        .fetch_all(executor)
+        .await
+        .map_err(|e| DuplicateError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .collect();
+
+        let is_free = |candidate: &String| !taken.contains(candidate) && !reserved.contains(candidate);
+        let free = std::iter::once(hash.to_string())
+            .chain((1..).map(|n| format!("{}:{}", hash, n)))
+            .find(is_free)
+            .unwrap_or_default(); // The chain is endless, so a free hash is always found
+        Ok(free)
+    }
+
+    /// How many transactions are stored under an older hash version
+    pub async fn outdated_count(db: &sqlx::Pool<sqlx::Sqlite>) -> Result<i64, DuplicateError> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM transactions WHERE hash_version < ?")
+            .bind(NewTransaction::HASH_VERSION)
+            .fetch_one(db)
+            .await
+            .map_err(|e| DuplicateError::DatabaseError(e.to_string()))
+    }
+
+    /// Give up to `limit` transactions stored under an older version their current hash, written in
+    /// one database transaction; returns how many were migrated
+    /// Rows that now hash alike (e.g. the same purchase in two spellings) keep a suffixed hash each
+    pub async fn rehash_batch(db: &sqlx::Pool<sqlx::Sqlite>, limit: i64) -> Result<usize, DuplicateError> {
+        let db_error = |e: sqlx::Error| DuplicateError::DatabaseError(e.to_string());
+        let rows: Vec<(i64, i64, String, Cents, String)> = sqlx::query_as(
+            "SELECT id, account_id, date, amount_cents, description FROM transactions
+             WHERE hash_version < ? ORDER BY id LIMIT ?"
+        )
+        .bind(NewTransaction::HASH_VERSION)
+        .bind(limit)
+        .fetch_all(db)
+        .await
+        .map_err(db_error)?;
+
+        // Resolved before the write transaction opens, so it never has to upgrade a read lock
+        let mut reserved = HashSet::new();
+        let mut updates = Vec::with_capacity(rows.len());
+        for (id, account_id, date, amount_cents, description) in rows {
+            let hash = NewTransaction::calculate_hash(account_id, &date, amount_cents, &description);
+            let hash = Self::free_hash(db, &hash, &reserved).await?;
+            reserved.insert(hash.clone());
+            updates.push((id, hash));
+        }
+
+        let mut tx = db.begin().await.map_err(db_error)?;
+        for (id, hash) in &updates {
+            sqlx::query("UPDATE transactions SET hash = ?, hash_version = ? WHERE id = ?")
+                .bind(hash)
+                .bind(NewTransaction::HASH_VERSION)
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(db_error)?;
+        }
+        tx.commit().await.map_err(db_error)?;
+        Ok(updates.len())
+    }
+
+    /// The subset of `hashes` already stored, checked in one query
+    pub async fn existing_hashes(
+        db: &sqlx::Pool<sqlx::Sqlite>,
+        hashes: &[String],
+    ) -> Result<HashSet<String>, DuplicateError> {
+        if hashes.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let hashes = serde_json::to_string(hashes).map_err(|e| DuplicateError::DatabaseError(e.to_string()))?;
 
-        let result: Option<i64> = sqlx::query_scalar(
-            "SELECT COUNT(*) FROM transactions WHERE hash = ?"
+        let existing: Vec<String> = sqlx::query_scalar(
+            "SELECT hash FROM transactions WHERE hash IN (SELECT value FROM json_each(?))"
         )
-        .bind(&hash)
-        .fetch_one(db)
+        .bind(hashes)
+        .fetch_all(db)
         .await
         .map_err(|e| DuplicateError::DatabaseError(e.to_string()))?;
 
-        Ok(result.unwrap_or(0) > 0)
+        Ok(existing.into_iter().collect())
     }
 
     pub async fn filter_duplicates(
         db: &sqlx::Pool<sqlx::Sqlite>,
-        transactions: Vec<(String, f64, String)>, // (date, amount, description)
+        account_id: i64,
+        transactions: Vec<(String, Cents, String)>, // (date, amount in cents, description)
     ) -> Result<Vec<bool>, DuplicateError> {
         let mut results = Vec::new();
 
-        for (date, amount, description) in transactions {
-            let is_dup = Self::is_duplicate(db, &date, amount, &description).await?;
+        for (date, amount_cents, description) in transactions {
+            let is_dup = Self::is_duplicate(db, account_id, &date, amount_cents, &description).await?;
             results.push(is_dup);
         }
 
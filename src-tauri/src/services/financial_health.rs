@@ -0,0 +1,207 @@
+use crate::constants::PERCENT_TO_DECIMAL_DIVISOR;
+use crate::models::debt::minimum_payment;
+use crate::services::spending_aggregator::SpendingAggregator;
+use crate::utils::money::{self, Cents};
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+/// Income, spending, and debt payments for one calendar month
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthMonth {
+    pub month: String, // YYYY-MM
+    #[serde(rename = "income", with = "money::dollars")]
+    pub income_cents: Cents,
+    #[serde(rename = "spending", with = "money::dollars")]
+    pub spending_cents: Cents,
+    #[serde(rename = "debt_payments", with = "money::dollars")]
+    pub debt_payments_cents: Cents,
+    pub savings_rate: Option<f64>,   // Percent; None in a month without income
+    pub debt_to_income: Option<f64>, // Percent; None in a month without income
+}
+
+/// Headline numbers for the completed months before today's month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinancialHealth {
+    pub start_month: String,
+    pub end_month: String,
+    #[serde(rename = "total_income", with = "money::dollars")]
+    pub total_income_cents: Cents,
+    #[serde(rename = "total_spending", with = "money::dollars")]
+    pub total_spending_cents: Cents,
+    /// (income - spending) / income over the whole window, in percent; None without income
+    pub savings_rate: Option<f64>,
+    /// Current minimum payments on every debt, per month
+    #[serde(rename = "monthly_debt_payments", with = "money::dollars")]
+    pub monthly_debt_payments_cents: Cents,
+    /// Minimum payments over average monthly income, in percent; None without income
+    pub debt_to_income: Option<f64>,
+    /// Checking and savings balances now, leaving archived accounts out
+    #[serde(rename = "liquid_balance", with = "money::dollars")]
+    pub liquid_balance_cents: Cents,
+    /// How many months of average spending the liquid balance covers; None without spending
+    pub months_of_expenses: Option<f64>,
+    pub monthly: Vec<HealthMonth>, // Oldest first, zero-filled
+    /// Slope of the monthly savings rates, in percentage points per month; None with fewer than two
+    pub savings_rate_trend: Option<f64>,
+    /// Slope of the monthly debt-to-income ratios, in percentage points per month
+    pub debt_to_income_trend: Option<f64>,
+}
+
+pub struct FinancialHealthCalculator;
+
+impl FinancialHealthCalculator {
+    fn month_key(date: NaiveDate) -> String {
+        date.format("%Y-%m").to_string()
+    }
+
+    /// `part` as a percentage of `whole`, both in the same unit; None when `whole` isn't positive
+    fn percent_of(part: f64, whole: f64) -> Option<f64> {
+        (whole > 0.0).then(|| part / whole * PERCENT_TO_DECIMAL_DIVISOR)
+    }
+
+    /// Least-squares slope of evenly spaced `values`, skipping the missing ones
+    /// None when fewer than two values are present
+    pub fn trend(values: &[Option<f64>]) -> Option<f64> {
+        let points: Vec<(f64, f64)> = values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, value)| value.map(|v| (i as f64, v)))
+            .collect();
+        if points.len() < 2 {
+            return None;
+        }
+
+        let count = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+        let covariance: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        Some(covariance / variance)
+    }
+
+    /// Health metrics over the `months` completed months before `today`'s month
+    pub async fn calculate(db: &SqlitePool, months: u32, today: NaiveDate) -> Result<FinancialHealth, String> {
+        let this_month = today.with_day(1).unwrap_or(today);
+        let window_end = this_month - chrono::Duration::days(1);
+        let window_start = this_month - Months::new(months);
+        let start_date = window_start.format("%Y-%m-%d").to_string();
+        let end_date = window_end.format("%Y-%m-%d").to_string();
+
+        let totals = sqlx::query_as::<_, (String, Cents, Cents)>(
+            "SELECT strftime('%Y-%m', date) as month,
+                COALESCE(SUM(CASE WHEN amount_cents > 0 THEN amount_cents ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN amount_cents < 0 THEN -amount_cents ELSE 0 END), 0)
+             FROM transactions
+             WHERE date >= ? AND date <= ? AND (? = 0 OR status = 'posted')
+             GROUP BY month"
+        )
+        .bind(&start_date)
+        .bind(&end_date)
+        .bind(SpendingAggregator::excludes_pending(db).await)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+        let totals: HashMap<String, (Cents, Cents)> =
+            totals.into_iter().map(|(month, income, spending)| (month, (income, spending))).collect();
+
+        let payments = sqlx::query_as::<_, (String, Cents)>(
+            "SELECT strftime('%Y-%m', date) as month, SUM(amount_cents)
+             FROM debt_payments
+             WHERE date >= ? AND date <= ?
+             GROUP BY month"
+        )
+        .bind(&start_date)
+        .bind(&end_date)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+        let payments: HashMap<String, Cents> = payments.into_iter().collect();
+
+        let monthly: Vec<HealthMonth> = (0..months)
+            .map(|k| {
+                let month = Self::month_key(window_start + Months::new(k));
+                let (income, spending) = totals.get(&month).copied().unwrap_or((0, 0));
+                let debt_payments = payments.get(&month).copied().unwrap_or(0);
+                HealthMonth {
+                    savings_rate: Self::percent_of((income - spending) as f64, income as f64),
+                    debt_to_income: Self::percent_of(debt_payments as f64, income as f64),
+                    month,
+                    income_cents: income,
+                    spending_cents: spending,
+                    debt_payments_cents: debt_payments,
+                }
+            })
+            .collect();
+
+        let debts = sqlx::query_as::<_, (Cents, Cents, Option<f64>)>(
+            "SELECT balance_cents, min_payment_cents, min_payment_percent FROM debts WHERE balance_cents > 0"
+        )
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+        let monthly_debt_payments: Cents = debts
+            .into_iter()
+            .map(|(balance, floor, percent)| minimum_payment(floor, percent, balance))
+            .sum();
+
+        let (liquid_balance,) = sqlx::query_as::<_, (Cents,)>(
+            "SELECT COALESCE(SUM(balance_cents), 0)
+             FROM accounts
+             WHERE type IN ('checking', 'savings') AND archived = 0"
+        )
+        .fetch_one(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let total_income: Cents = monthly.iter().map(|m| m.income_cents).sum();
+        let total_spending: Cents = monthly.iter().map(|m| m.spending_cents).sum();
+        let month_count = f64::from(months.max(1));
+        let average_spending = total_spending as f64 / month_count;
+
+        Ok(FinancialHealth {
+            start_month: Self::month_key(window_start),
+            end_month: Self::month_key(window_end),
+            total_income_cents: total_income,
+            total_spending_cents: total_spending,
+            savings_rate: Self::percent_of((total_income - total_spending) as f64, total_income as f64),
+            monthly_debt_payments_cents: monthly_debt_payments,
+            debt_to_income: Self::percent_of(monthly_debt_payments as f64, total_income as f64 / month_count),
+            liquid_balance_cents: liquid_balance,
+            months_of_expenses: (average_spending > 0.0).then(|| liquid_balance as f64 / average_spending),
+            savings_rate_trend: Self::trend(&monthly.iter().map(|m| m.savings_rate).collect::<Vec<_>>()),
+            debt_to_income_trend: Self::trend(&monthly.iter().map(|m| m.debt_to_income).collect::<Vec<_>>()),
+            monthly,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trend_of_a_straight_line() {
+        let slope = FinancialHealthCalculator::trend(&[Some(10.0), Some(12.0), Some(14.0), Some(16.0)]).unwrap();
+        assert!((slope - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trend_skips_missing_months() {
+        let slope = FinancialHealthCalculator::trend(&[Some(30.0), None, Some(20.0)]).unwrap();
+        assert!((slope + 5.0).abs() < 1e-9, "Gaps keep their place on the x axis");
+    }
+
+    #[test]
+    fn test_trend_needs_two_points() {
+        assert_eq!(FinancialHealthCalculator::trend(&[]), None);
+        assert_eq!(FinancialHealthCalculator::trend(&[None, Some(5.0), None]), None);
+    }
+
+    #[test]
+    fn test_percent_of_without_income() {
+        assert_eq!(FinancialHealthCalculator::percent_of(100.0, 0.0), None);
+        assert_eq!(FinancialHealthCalculator::percent_of(25.0, 200.0), Some(12.5));
+    }
+}
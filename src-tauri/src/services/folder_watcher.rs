@@ -0,0 +1,175 @@
+// Watches the watch folder for new statement files and hands them over once they stop changing
+//
+// Only the folder itself is watched, not its subfolders, so moving an imported file into
+// the archive subfolder doesn't come back around as a new file
+
+use crate::constants::{WATCH_ARCHIVE_DIR, WATCH_SETTLE_MS};
+use chrono::Utc;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// Kinds of statement file picked up from the watch folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchedFormat {
+    Csv,
+    Ofx, // Recognized so it can be reported; there is no OFX importer yet
+}
+
+impl WatchedFormat {
+    /// The format of `path` from its extension; None for anything else, including hidden files
+    /// and the partial files browsers write while downloading
+    pub fn of(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        if name.starts_with('.') {
+            return None;
+        }
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "csv" => Some(WatchedFormat::Csv),
+            "ofx" | "qfx" => Some(WatchedFormat::Ofx),
+            _ => None,
+        }
+    }
+}
+
+/// The running watcher, kept in app state so changing the watch folder can replace it
+#[derive(Default)]
+pub struct FolderWatcher {
+    watcher: Mutex<Option<RecommendedWatcher>>,
+}
+
+impl FolderWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_watching(&self) -> bool {
+        self.watcher.lock().unwrap_or_else(PoisonError::into_inner).is_some()
+    }
+
+    /// Watch `dir`, calling `on_files` on a background thread with each group of new statement
+    /// files once the folder has been quiet for a moment. Replaces any folder already watched
+    pub fn start<F>(&self, dir: &Path, mut on_files: F) -> Result<(), String>
+    where
+        F: FnMut(Vec<PathBuf>) + Send + 'static,
+    {
+        self.stop();
+
+        let (sender, receiver) = mpsc::channel::<PathBuf>();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| match event {
+            Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                for path in event.paths.into_iter().filter(|path| WatchedFormat::of(path).is_some()) {
+                    // Only fails once the watcher is stopped
+                    let _ = sender.send(path);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error = %e, "Watch folder error"),
+        })
+        .map_err(|e| format!("Failed to watch folder: {}", e))?;
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to watch folder: {}", e))?;
+
+        // The channel disconnects when the watcher is dropped, which ends the thread
+        std::thread::Builder::new()
+            .name("watch-folder".to_string())
+            .spawn(move || {
+                let settle = Duration::from_millis(WATCH_SETTLE_MS);
+                while let Ok(first) = receiver.recv() {
+                    // A download raises events until it's written, so wait for the folder to go quiet
+                    let mut paths = BTreeSet::from([first]);
+                    loop {
+                        match receiver.recv_timeout(settle) {
+                            Ok(path) => {
+                                paths.insert(path);
+                            }
+                            Err(RecvTimeoutError::Timeout) => break,
+                            Err(RecvTimeoutError::Disconnected) => return,
+                        }
+                    }
+                    let files: Vec<PathBuf> = paths.into_iter().filter(|path| path.is_file()).collect();
+                    if !files.is_empty() {
+                        on_files(files);
+                    }
+                }
+            })
+            .map_err(|e| format!("Failed to start folder watcher: {}", e))?;
+
+        *self.watcher.lock().unwrap_or_else(PoisonError::into_inner) = Some(watcher);
+        Ok(())
+    }
+
+    /// Stop watching; files already handed over still finish importing
+    pub fn stop(&self) {
+        self.watcher.lock().unwrap_or_else(PoisonError::into_inner).take();
+    }
+
+    /// Statement files sitting in `dir`, by name, for picking up what arrived while the app was closed
+    pub fn pending_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read watch folder: {}", e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file() && WatchedFormat::of(path).is_some())
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+
+    /// Move an imported `file` into the archive subfolder of `dir`, returning where it went
+    /// A file already archived under the same name is kept; the new one gets a timestamp prefix
+    pub fn archive(dir: &Path, file: &Path) -> Result<PathBuf, String> {
+        let archive_dir = dir.join(WATCH_ARCHIVE_DIR);
+        std::fs::create_dir_all(&archive_dir).map_err(|e| format!("Failed to create archive folder: {}", e))?;
+
+        let name = file
+            .file_name()
+            .ok_or_else(|| format!("Not a file: {}", file.display()))?
+            .to_string_lossy()
+            .into_owned();
+        let mut target = archive_dir.join(&name);
+        if target.exists() {
+            target = archive_dir.join(format!("{}-{}", Utc::now().format("%Y%m%d-%H%M%S%3f"), name));
+        }
+        std::fs::rename(file, &target).map_err(|e| format!("Failed to archive {}: {}", name, e))?;
+        Ok(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(WatchedFormat::of(Path::new("/in/Statement.CSV")), Some(WatchedFormat::Csv));
+        assert_eq!(WatchedFormat::of(Path::new("/in/checking.qfx")), Some(WatchedFormat::Ofx));
+        assert_eq!(WatchedFormat::of(Path::new("/in/statement.csv.crdownload")), None);
+        assert_eq!(WatchedFormat::of(Path::new("/in/.~lock.statement.csv")), None);
+        assert_eq!(WatchedFormat::of(Path::new("/in/notes.txt")), None);
+    }
+
+    #[test]
+    fn test_archive_keeps_earlier_files() {
+        let dir = std::env::temp_dir().join(format!("folder-watcher-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        std::fs::write(dir.join("march.csv"), "first").unwrap();
+        let first = FolderWatcher::archive(&dir, &dir.join("march.csv")).unwrap();
+        assert_eq!(first, dir.join(WATCH_ARCHIVE_DIR).join("march.csv"));
+
+        std::fs::write(dir.join("march.csv"), "second").unwrap();
+        let second = FolderWatcher::archive(&dir, &dir.join("march.csv")).unwrap();
+        assert_ne!(second, first);
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "first");
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "second");
+        assert!(FolderWatcher::pending_files(&dir).unwrap().is_empty(), "Archived files aren't pending");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,169 @@
+use crate::models::spending_freeze::{FreezeStatus, FreezeViolation, SpendingFreeze};
+use crate::utils::dates;
+use crate::utils::money::Cents;
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+
+/// Violations of one freeze among a set of newly added transactions
+#[derive(Debug, Clone)]
+pub struct FreezeBreach {
+    pub freeze_id: i64,
+    pub freeze_name: String,
+    pub violations: Vec<FreezeViolation>,
+}
+
+pub struct FreezeTracker;
+
+impl FreezeTracker {
+    /// Expenses in the freeze's category (any category for a global freeze) from its start through `through`
+    pub async fn violations(
+        db: &SqlitePool,
+        freeze: &SpendingFreeze,
+        through: &str,
+    ) -> Result<Vec<FreezeViolation>, String> {
+        let end = match freeze.end_date.as_deref() {
+            Some(end) if end < through => end,
+            _ => through,
+        };
+
+        sqlx::query_as::<_, FreezeViolation>(
+            "SELECT id AS transaction_id, date, amount_cents, description, category_id
+             FROM transactions
+             WHERE amount_cents < 0 AND date >= ? AND date <= ?
+               AND (? IS NULL OR category_id = ?)
+             ORDER BY date, id"
+        )
+        .bind(&freeze.start_date)
+        .bind(end)
+        .bind(freeze.category_id)
+        .bind(freeze.category_id)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Violations among the given transactions, grouped by the freeze they break
+    pub async fn breaches(db: &SqlitePool, transaction_ids: &[i64]) -> Result<Vec<FreezeBreach>, String> {
+        if transaction_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids = serde_json::to_string(transaction_ids).map_err(|e| e.to_string())?;
+
+        let rows = sqlx::query_as::<_, (i64, String, i64, String, Cents, String, i64)>(
+            "SELECT f.id, f.name, t.id, t.date, t.amount_cents, t.description, t.category_id
+             FROM transactions t
+             JOIN spending_freezes f
+               ON t.date >= f.start_date
+              AND (f.end_date IS NULL OR t.date <= f.end_date)
+              AND (f.category_id IS NULL OR f.category_id = t.category_id)
+             WHERE t.amount_cents < 0 AND t.id IN (SELECT value FROM json_each(?))
+             ORDER BY f.id, t.date, t.id"
+        )
+        .bind(ids)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let mut breaches: Vec<FreezeBreach> = Vec::new();
+        for (freeze_id, freeze_name, transaction_id, date, amount_cents, description, category_id) in rows {
+            let violation = FreezeViolation {
+                transaction_id,
+                date,
+                amount_cents,
+                description,
+                category_id,
+            };
+            match breaches.last_mut() {
+                Some(breach) if breach.freeze_id == freeze_id => breach.violations.push(violation),
+                _ => breaches.push(FreezeBreach {
+                    freeze_id,
+                    freeze_name,
+                    violations: vec![violation],
+                }),
+            }
+        }
+        Ok(breaches)
+    }
+
+    /// Streaks of violation-free days as (current, longest), counted from `start` through `end` inclusive
+    /// `violation_dates` must be sorted
+    pub fn streaks(start: NaiveDate, end: NaiveDate, violation_dates: &[NaiveDate]) -> (i64, i64) {
+        if end < start {
+            return (0, 0);
+        }
+
+        let mut longest = 0;
+        let mut streak_start = start;
+        for date in violation_dates {
+            longest = longest.max((*date - streak_start).num_days());
+            streak_start = streak_start.max(*date + chrono::Duration::days(1));
+        }
+        let current = (end - streak_start).num_days() + 1;
+
+        (current.max(0), longest.max(current))
+    }
+
+    /// Violation tally and streaks for a freeze as of `as_of`
+    pub async fn status(
+        db: &SqlitePool,
+        freeze: SpendingFreeze,
+        category_name: Option<String>,
+        as_of: NaiveDate,
+    ) -> Result<FreezeStatus, String> {
+        let start = dates::parse_date(&freeze.start_date)?;
+        let end_date = freeze.end_date.as_deref().map(dates::parse_date).transpose()?;
+        let active = start <= as_of && end_date.is_none_or(|end| as_of <= end);
+        let end = end_date.map_or(as_of, |end| end.min(as_of));
+
+        let violations = Self::violations(db, &freeze, &as_of.format("%Y-%m-%d").to_string()).await?;
+        let violation_dates = violations
+            .iter()
+            .map(|v| dates::parse_date(&v.date))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (current_streak_days, longest_streak_days) = Self::streaks(start, end, &violation_dates);
+
+        Ok(FreezeStatus {
+            category_name,
+            active,
+            violation_count: violations.len(),
+            violation_total_cents: violations.iter().map(|v| -v.amount_cents).sum(),
+            last_violation_date: violations.last().map(|v| v.date.clone()),
+            current_streak_days,
+            longest_streak_days,
+            violations,
+            freeze,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        dates::parse_date(s).unwrap()
+    }
+
+    #[test]
+    fn test_streaks_without_violations() {
+        assert_eq!(FreezeTracker::streaks(date("2025-11-01"), date("2025-11-10"), &[]), (10, 10));
+    }
+
+    #[test]
+    fn test_streaks_reset_on_violation() {
+        let violations = [date("2025-11-05"), date("2025-11-05"), date("2025-11-08")];
+        // Clean Nov 1-4 (4 days), Nov 6-7 (2 days), Nov 9-10 (2 days)
+        assert_eq!(FreezeTracker::streaks(date("2025-11-01"), date("2025-11-10"), &violations), (2, 4));
+    }
+
+    #[test]
+    fn test_streaks_violation_today() {
+        let violations = [date("2025-11-10")];
+        assert_eq!(FreezeTracker::streaks(date("2025-11-01"), date("2025-11-10"), &violations), (0, 9));
+    }
+
+    #[test]
+    fn test_streaks_before_start() {
+        assert_eq!(FreezeTracker::streaks(date("2025-11-01"), date("2025-10-31"), &[]), (0, 0));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::models::exchange_rate::{CurrencySpending, ForeignSpendingReport};
+use crate::utils::money::Cents;
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, HashMap};
+
+/// A foreign-currency expense, with both amounts as positive spending
+#[derive(Debug, Clone)]
+pub struct ForeignExpense {
+    pub currency: String,
+    pub date: String,
+    pub posted_amount_cents: Cents,   // Home currency, as the bank converted it
+    pub original_amount_cents: Cents, // In `currency`
+}
+
+/// Rates per currency as (date, rate), sorted by date
+pub type RateHistory = HashMap<String, Vec<(String, f64)>>;
+
+pub struct FxConverter;
+
+impl FxConverter {
+    /// Rate in effect on `date`: the latest one on or before it
+    pub fn rate_on(rates: &[(String, f64)], date: &str) -> Option<f64> {
+        let idx = rates.partition_point(|(rate_date, _)| rate_date.as_str() <= date);
+        idx.checked_sub(1).map(|i| rates[i].1)
+    }
+
+    /// `amount` converted at `rate`, rounded to a cent
+    fn convert(amount: Cents, rate: f64) -> Cents {
+        (amount as f64 * rate).round() as Cents
+    }
+
+    /// Group expenses by currency and convert them at transaction-date and `as_of` rates
+    /// Without a rate on file, an expense is valued at its posted amount
+    pub fn summarize(expenses: &[ForeignExpense], rates: &RateHistory, as_of: &str) -> Vec<CurrencySpending> {
+        let mut by_currency: BTreeMap<&str, Vec<&ForeignExpense>> = BTreeMap::new();
+        for expense in expenses {
+            by_currency.entry(expense.currency.as_str()).or_default().push(expense);
+        }
+
+        let mut summaries: Vec<CurrencySpending> = by_currency
+            .into_iter()
+            .map(|(currency, expenses)| {
+                let history = rates.get(currency).map(Vec::as_slice).unwrap_or(&[]);
+                let current_rate = Self::rate_on(history, as_of);
+
+                let mut summary = CurrencySpending {
+                    currency: currency.to_string(),
+                    transaction_count: expenses.len() as i64,
+                    original_total_cents: 0,
+                    historical_total_cents: 0,
+                    current_total_cents: 0,
+                    fx_impact_cents: 0,
+                    current_rate,
+                    missing_rate_count: 0,
+                };
+                for expense in expenses {
+                    let historical = match Self::rate_on(history, &expense.date) {
+                        Some(rate) => Self::convert(expense.original_amount_cents, rate),
+                        None => {
+                            summary.missing_rate_count += 1;
+                            expense.posted_amount_cents
+                        }
+                    };
+                    summary.original_total_cents += expense.original_amount_cents;
+                    summary.historical_total_cents += historical;
+                    summary.current_total_cents +=
+                        current_rate.map_or(historical, |rate| Self::convert(expense.original_amount_cents, rate));
+                }
+                summary.fx_impact_cents = summary.current_total_cents - summary.historical_total_cents;
+                summary
+            })
+            .collect();
+
+        summaries.sort_by_key(|s| std::cmp::Reverse(s.historical_total_cents));
+        summaries
+    }
+
+    /// Foreign-currency spending between `start_date` and `end_date`, valued at `as_of` for current rates
+    pub async fn report(
+        db: &SqlitePool,
+        start_date: &str,
+        end_date: &str,
+        as_of: &str,
+    ) -> Result<ForeignSpendingReport, String> {
+        let expenses: Vec<ForeignExpense> = sqlx::query_as::<_, (String, String, Cents, Cents)>(
+            "SELECT original_currency, date, -amount_cents, -original_amount_cents
+             FROM transactions
+             WHERE original_currency IS NOT NULL AND original_amount_cents IS NOT NULL
+               AND amount_cents < 0 AND date >= ? AND date <= ?"
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|(currency, date, posted_amount_cents, original_amount_cents)| ForeignExpense {
+            currency,
+            date,
+            posted_amount_cents,
+            original_amount_cents,
+        })
+        .collect();
+
+        let mut rates = RateHistory::new();
+        let rows = sqlx::query_as::<_, (String, String, f64)>(
+            "SELECT currency, date, rate FROM exchange_rates WHERE date <= ? ORDER BY currency, date"
+        )
+        .bind(end_date.max(as_of))
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+        for (currency, date, rate) in rows {
+            rates.entry(currency).or_default().push((date, rate));
+        }
+
+        let currencies = Self::summarize(&expenses, &rates, as_of);
+        let historical_total: Cents = currencies.iter().map(|c| c.historical_total_cents).sum();
+        let current_total: Cents = currencies.iter().map(|c| c.current_total_cents).sum();
+
+        Ok(ForeignSpendingReport {
+            start_date: start_date.to_string(),
+            end_date: end_date.to_string(),
+            as_of: as_of.to_string(),
+            currencies,
+            historical_total_cents: historical_total,
+            current_total_cents: current_total,
+            fx_impact_cents: current_total - historical_total,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expense(currency: &str, date: &str, posted_amount_cents: Cents, original_amount_cents: Cents) -> ForeignExpense {
+        ForeignExpense {
+            currency: currency.to_string(),
+            date: date.to_string(),
+            posted_amount_cents,
+            original_amount_cents,
+        }
+    }
+
+    fn history(rates: &[(&str, f64)]) -> Vec<(String, f64)> {
+        rates.iter().map(|(date, rate)| (date.to_string(), *rate)).collect()
+    }
+
+    #[test]
+    fn test_rate_on_uses_latest_rate_not_after_date() {
+        let rates = history(&[("2025-01-01", 1.10), ("2025-02-01", 1.20)]);
+        assert_eq!(FxConverter::rate_on(&rates, "2024-12-31"), None);
+        assert_eq!(FxConverter::rate_on(&rates, "2025-01-01"), Some(1.10));
+        assert_eq!(FxConverter::rate_on(&rates, "2025-01-31"), Some(1.10));
+        assert_eq!(FxConverter::rate_on(&rates, "2025-03-15"), Some(1.20));
+    }
+
+    #[test]
+    fn test_summarize_fx_impact() {
+        let mut rates = RateHistory::new();
+        rates.insert("EUR".to_string(), history(&[("2025-01-01", 1.10), ("2025-06-01", 1.20)]));
+
+        let expenses = vec![
+            expense("EUR", "2025-01-10", 11_100, 10_000),
+            expense("EUR", "2025-02-10", 5600, 5000),
+        ];
+        let summaries = FxConverter::summarize(&expenses, &rates, "2025-06-15");
+
+        assert_eq!(summaries.len(), 1);
+        let eur = &summaries[0];
+        assert_eq!(eur.transaction_count, 2);
+        assert_eq!(eur.original_total_cents, 15_000);
+        assert_eq!(eur.historical_total_cents, 16_500);
+        assert_eq!(eur.current_total_cents, 18_000);
+        assert_eq!(eur.fx_impact_cents, 1500);
+        assert_eq!(eur.current_rate, Some(1.20));
+        assert_eq!(eur.missing_rate_count, 0);
+    }
+
+    #[test]
+    fn test_summarize_without_rates_uses_posted_amount() {
+        let expenses = vec![expense("JPY", "2025-03-01", 6750, 1_000_000)];
+        let summaries = FxConverter::summarize(&expenses, &RateHistory::new(), "2025-06-15");
+
+        let jpy = &summaries[0];
+        assert_eq!(jpy.missing_rate_count, 1);
+        assert_eq!(jpy.current_rate, None);
+        assert_eq!(jpy.historical_total_cents, 6750);
+        assert_eq!(jpy.current_total_cents, 6750);
+        assert_eq!(jpy.fx_impact_cents, 0);
+    }
+
+    #[test]
+    fn test_summarize_sorts_by_spending() {
+        let expenses = vec![
+            expense("GBP", "2025-03-01", 2000, 1600),
+            expense("EUR", "2025-03-01", 9000, 8000),
+        ];
+        let summaries = FxConverter::summarize(&expenses, &RateHistory::new(), "2025-06-15");
+        let order: Vec<&str> = summaries.iter().map(|s| s.currency.as_str()).collect();
+        assert_eq!(order, vec!["EUR", "GBP"]);
+    }
+}
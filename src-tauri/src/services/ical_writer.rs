@@ -0,0 +1,133 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// An all-day calendar event
+#[derive(Debug, Clone)]
+pub struct IcalEvent {
+    pub uid: String, // Stable across exports so calendar apps update events instead of duplicating them
+    pub date: NaiveDate,
+    pub summary: String,
+    pub description: Option<String>,
+    pub category: String,
+}
+
+pub struct IcalWriter;
+
+impl IcalWriter {
+    /// Longest content line in octets before it's folded (RFC 5545 section 3.1)
+    const MAX_LINE_OCTETS: usize = 75;
+
+    /// Escape TEXT values: backslash, semicolon, and comma are escaped and newlines become \n
+    fn text(value: &str) -> String {
+        let mut out = String::with_capacity(value.len());
+        for c in value.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                ';' => out.push_str("\\;"),
+                ',' => out.push_str("\\,"),
+                '\n' => out.push_str("\\n"),
+                '\r' => {}
+                _ => out.push(c),
+            }
+        }
+        out
+    }
+
+    /// Append one content line, folded onto continuation lines (which start with a space)
+    /// without splitting a multi-byte character
+    fn push_line(out: &mut String, line: &str) {
+        let mut octets = 0;
+        for c in line.chars() {
+            let width = c.len_utf8();
+            if octets + width > Self::MAX_LINE_OCTETS {
+                out.push_str("\r\n ");
+                octets = 1;
+            }
+            out.push(c);
+            octets += width;
+        }
+        out.push_str("\r\n");
+    }
+
+    fn date(date: NaiveDate) -> String {
+        date.format("%Y%m%d").to_string()
+    }
+
+    /// Render `events` as an iCalendar (.ics) document
+    pub fn write(calendar_name: &str, events: &[IcalEvent], generated_at: DateTime<Utc>) -> String {
+        let stamp = generated_at.format("%Y%m%dT%H%M%SZ").to_string();
+        let mut out = String::new();
+        for line in [
+            "BEGIN:VCALENDAR",
+            "VERSION:2.0",
+            "PRODID:-//Budget Balancer//Payment Calendar//EN",
+            "CALSCALE:GREGORIAN",
+            "METHOD:PUBLISH",
+        ] {
+            Self::push_line(&mut out, line);
+        }
+        Self::push_line(&mut out, &format!("X-WR-CALNAME:{}", Self::text(calendar_name)));
+
+        for event in events {
+            let end = event.date.succ_opt().unwrap_or(event.date);
+            Self::push_line(&mut out, "BEGIN:VEVENT");
+            Self::push_line(&mut out, &format!("UID:{}", Self::text(&event.uid)));
+            Self::push_line(&mut out, &format!("DTSTAMP:{}", stamp));
+            Self::push_line(&mut out, &format!("DTSTART;VALUE=DATE:{}", Self::date(event.date)));
+            Self::push_line(&mut out, &format!("DTEND;VALUE=DATE:{}", Self::date(end)));
+            Self::push_line(&mut out, &format!("SUMMARY:{}", Self::text(&event.summary)));
+            if let Some(description) = &event.description {
+                Self::push_line(&mut out, &format!("DESCRIPTION:{}", Self::text(description)));
+            }
+            Self::push_line(&mut out, &format!("CATEGORIES:{}", Self::text(&event.category)));
+            Self::push_line(&mut out, "TRANSP:TRANSPARENT");
+            Self::push_line(&mut out, "END:VEVENT");
+        }
+
+        Self::push_line(&mut out, "END:VCALENDAR");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(summary: &str) -> IcalEvent {
+        IcalEvent {
+            uid: "debt-1-2025-01-15@budget-balancer".to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            summary: summary.to_string(),
+            description: Some("Minimum payment, card ending 1234".to_string()),
+            category: "Debt payment".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_write_all_day_event() {
+        let generated_at = DateTime::parse_from_rfc3339("2025-01-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        let ics = IcalWriter::write("Budget Balancer", &[event("Visa: $50.00")], generated_at);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert!(ics.contains("\r\nDTSTAMP:20250101T120000Z\r\n"));
+        assert!(ics.contains("\r\nDTSTART;VALUE=DATE:20250115\r\nDTEND;VALUE=DATE:20250116\r\n"));
+        assert!(ics.contains("\r\nDESCRIPTION:Minimum payment\\, card ending 1234\r\n"));
+    }
+
+    #[test]
+    fn test_text_escaping() {
+        assert_eq!(IcalWriter::text("a;b,c\\d\r\ne"), "a\\;b\\,c\\\\d\\ne");
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let summary = "é".repeat(60);
+        let ics = IcalWriter::write("Budget Balancer", &[event(&summary)], Utc::now());
+
+        for line in ics.split("\r\n") {
+            assert!(line.len() <= 75, "line too long: {}", line);
+        }
+        // Unfolding restores the original value
+        assert!(ics.replace("\r\n ", "").contains(&format!("SUMMARY:{}\r\n", summary)));
+    }
+}
@@ -0,0 +1,422 @@
+use crate::constants::{
+    INSIGHT_LARGEST_TRANSACTIONS, INSIGHT_MIN_CATEGORY_CHANGE_CENTS, INSIGHT_MIN_STREAK_WEEKS, INSIGHT_STREAK_LOOKBACK_WEEKS,
+    INSIGHT_TOP_MOVERS, PERCENT_TO_DECIMAL_DIVISOR,
+};
+use crate::services::spending_aggregator::{DatePeriod, SpendingAggregator};
+use crate::utils::money::{self, Cents};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+/// What an insight found, with the numbers behind it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InsightDetail {
+    CategoryIncrease {
+        category_id: i64,
+        category_name: String,
+        #[serde(rename = "amount", with = "money::dollars")]
+        amount_cents: Cents,
+        #[serde(rename = "previous_amount", with = "money::dollars")]
+        previous_amount_cents: Cents,
+        #[serde(rename = "change", with = "money::dollars")]
+        change_cents: Cents,
+        change_percent: Option<f64>, // None when nothing was spent in the previous period
+    },
+    CategoryDecrease {
+        category_id: i64,
+        category_name: String,
+        #[serde(rename = "amount", with = "money::dollars")]
+        amount_cents: Cents,
+        #[serde(rename = "previous_amount", with = "money::dollars")]
+        previous_amount_cents: Cents,
+        #[serde(rename = "change", with = "money::dollars")]
+        change_cents: Cents, // Negative
+        change_percent: Option<f64>,
+    },
+    NewMerchant {
+        merchant_name: String,
+        #[serde(rename = "amount", with = "money::dollars")]
+        amount_cents: Cents,
+        transaction_count: i64,
+        first_date: String,
+    },
+    LargeTransaction {
+        transaction_id: i64,
+        date: String,
+        #[serde(rename = "amount", with = "money::dollars")]
+        amount_cents: Cents, // Negative, as stored
+        description: String,
+        category_name: String,
+    },
+    RisingStreak {
+        category_id: i64,
+        category_name: String,
+        weeks: usize, // Consecutive weeks each higher than the one before
+        #[serde(rename = "latest_amount", with = "money::dollars")]
+        latest_amount_cents: Cents,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Insight {
+    #[serde(flatten)]
+    pub detail: InsightDetail,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpendingInsights {
+    pub period: DatePeriod,
+    pub previous_period: DatePeriod,
+    pub insights: Vec<Insight>,
+}
+
+pub struct InsightGenerator;
+
+impl InsightGenerator {
+    fn format_date(date: NaiveDate) -> String {
+        date.format("%Y-%m-%d").to_string()
+    }
+
+    /// Week-over-week increases at the end of chronologically ordered weekly amounts
+    pub fn rising_streak(weekly_amounts: &[Cents]) -> usize {
+        weekly_amounts
+            .windows(2)
+            .rev()
+            .take_while(|pair| pair[1] > pair[0])
+            .count()
+    }
+
+    /// Categories whose spending moved most against the previous period, largest increases then largest decreases
+    /// `current` and `previous` map category id to (name, amount)
+    pub fn category_movers(
+        current: &HashMap<i64, (String, Cents)>,
+        previous: &HashMap<i64, (String, Cents)>,
+    ) -> Vec<InsightDetail> {
+        let mut changes: Vec<(i64, String, Cents, Cents)> = current
+            .iter()
+            .map(|(&id, (name, amount))| (id, name.clone(), *amount, previous.get(&id).map_or(0, |(_, a)| *a)))
+            .chain(
+                previous
+                    .iter()
+                    .filter(|(id, _)| !current.contains_key(id))
+                    .map(|(&id, (name, amount))| (id, name.clone(), 0, *amount)),
+            )
+            .filter(|(_, _, amount, previous_amount)| (amount - previous_amount).abs() >= INSIGHT_MIN_CATEGORY_CHANGE_CENTS)
+            .collect();
+        // Largest change first; ties by name so the order is stable
+        changes.sort_by(|a, b| (b.2 - b.3).cmp(&(a.2 - a.3)).then_with(|| a.1.cmp(&b.1)));
+
+        let change_percent = |amount: Cents, previous_amount: Cents| {
+            (previous_amount > 0)
+                .then(|| (amount - previous_amount) as f64 / previous_amount as f64 * PERCENT_TO_DECIMAL_DIVISOR)
+        };
+
+        let increases = changes
+            .iter()
+            .filter(|(_, _, amount, previous_amount)| amount > previous_amount)
+            .take(INSIGHT_TOP_MOVERS)
+            .map(|(id, name, amount, previous_amount)| InsightDetail::CategoryIncrease {
+                category_id: *id,
+                category_name: name.clone(),
+                amount_cents: *amount,
+                previous_amount_cents: *previous_amount,
+                change_cents: amount - previous_amount,
+                change_percent: change_percent(*amount, *previous_amount),
+            });
+        let decreases = changes
+            .iter()
+            .rev()
+            .filter(|(_, _, amount, previous_amount)| amount < previous_amount)
+            .take(INSIGHT_TOP_MOVERS)
+            .map(|(id, name, amount, previous_amount)| InsightDetail::CategoryDecrease {
+                category_id: *id,
+                category_name: name.clone(),
+                amount_cents: *amount,
+                previous_amount_cents: *previous_amount,
+                change_cents: amount - previous_amount,
+                change_percent: change_percent(*amount, *previous_amount),
+            });
+
+        increases.chain(decreases).collect()
+    }
+
+    /// One-line description of an insight
+    pub fn describe(detail: &InsightDetail) -> String {
+        match detail {
+            InsightDetail::CategoryIncrease { category_name, change_cents, change_percent, .. } => {
+                let change = money::from_cents(*change_cents);
+                match change_percent {
+                    Some(percent) => format!("{} spending is up ${:.2} ({:+.1}%)", category_name, change, percent),
+                    None => format!("{} spending is up ${:.2} from nothing last period", category_name, change),
+                }
+            }
+            InsightDetail::CategoryDecrease { category_name, change_cents, change_percent, .. } => {
+                let change = money::from_cents(change_cents.abs());
+                match change_percent {
+                    Some(percent) => format!("{} spending is down ${:.2} ({:+.1}%)", category_name, change, percent),
+                    None => format!("{} spending is down ${:.2}", category_name, change),
+                }
+            }
+            InsightDetail::NewMerchant { merchant_name, amount_cents, transaction_count, .. } => format!(
+                "New merchant: {} (${:.2} across {} transaction{})",
+                merchant_name,
+                money::from_cents(*amount_cents),
+                transaction_count,
+                if *transaction_count == 1 { "" } else { "s" }
+            ),
+            InsightDetail::LargeTransaction { date, amount_cents, description, .. } => {
+                format!("Large purchase: {} for ${:.2} on {}", description, money::from_cents(amount_cents.abs()), date)
+            }
+            InsightDetail::RisingStreak { category_name, weeks, latest_amount_cents, .. } => format!(
+                "{} weeks of rising {} spending, ${:.2} in the latest week",
+                weeks,
+                category_name,
+                money::from_cents(*latest_amount_cents)
+            ),
+        }
+    }
+
+    async fn category_amounts(
+        db: &SqlitePool,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<HashMap<i64, (String, Cents)>, String> {
+        Ok(SpendingAggregator::get_spending_by_category(db, start_date, end_date, None, None)
+            .await?
+            .categories
+            .into_iter()
+            .map(|c| (c.category_id, (c.category_name, c.amount_cents)))
+            .collect())
+    }
+
+    /// Merchants with expenses in the period and none before it
+    async fn new_merchants(db: &SqlitePool, start_date: &str, end_date: &str) -> Result<Vec<InsightDetail>, String> {
+        let rows = sqlx::query_as::<_, (String, Cents, i64, String)>(
+            "WITH period AS (
+                SELECT
+                    COALESCE(m.name, t.merchant) as merchant_name,
+                    SUM(-t.amount_cents) as amount,
+                    COUNT(t.id) as count,
+                    MIN(t.date) as first_date
+                FROM transactions t
+                LEFT JOIN merchants m ON m.id = t.merchant_id
+                WHERE t.date >= ? AND t.date <= ? AND t.amount_cents < 0
+                    AND COALESCE(m.name, t.merchant) IS NOT NULL
+                GROUP BY COALESCE(m.name, t.merchant)
+            )
+            SELECT merchant_name, amount, count, first_date
+            FROM period
+            WHERE NOT EXISTS (
+                SELECT 1
+                FROM transactions p
+                LEFT JOIN merchants pm ON pm.id = p.merchant_id
+                WHERE p.date < ? AND COALESCE(pm.name, p.merchant) = period.merchant_name
+            )
+            ORDER BY amount DESC, merchant_name ASC
+            LIMIT ?"
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(start_date)
+        .bind(INSIGHT_TOP_MOVERS as i64)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(merchant_name, amount, transaction_count, first_date)| InsightDetail::NewMerchant {
+                merchant_name,
+                amount_cents: amount,
+                transaction_count,
+                first_date,
+            })
+            .collect())
+    }
+
+    /// Categories whose weekly spending has risen for several weeks running up to `end`
+    async fn rising_streaks(db: &SqlitePool, end: NaiveDate) -> Result<Vec<InsightDetail>, String> {
+        let weeks = INSIGHT_STREAK_LOOKBACK_WEEKS;
+        let window_start = end - chrono::Duration::days(weeks as i64 * 7 - 1);
+
+        // Weeks are counted back from `end`, so week 0 is the seven days ending on it
+        let rows = sqlx::query_as::<_, (i64, String, i64, Cents)>(
+            "SELECT
+                c.id,
+                c.name,
+                CAST((julianday(?) - julianday(t.date)) / 7 AS INTEGER) as weeks_ago,
+                SUM(-t.amount_cents) as amount
+            FROM transactions t
+            JOIN categories c ON c.id = t.category_id
+            WHERE t.date >= ? AND t.date <= ? AND t.amount_cents < 0
+            GROUP BY c.id, c.name, weeks_ago"
+        )
+        .bind(Self::format_date(end))
+        .bind(Self::format_date(window_start))
+        .bind(Self::format_date(end))
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let mut by_category: HashMap<i64, (String, Vec<Cents>)> = HashMap::new();
+        for (id, name, weeks_ago, amount) in rows {
+            let (_, amounts) = by_category.entry(id).or_insert_with(|| (name, vec![0; weeks]));
+            if let Some(slot) = usize::try_from(weeks_ago).ok().filter(|w| *w < weeks) {
+                amounts[weeks - 1 - slot] += amount;
+            }
+        }
+
+        let mut streaks: Vec<InsightDetail> = by_category
+            .into_iter()
+            .filter_map(|(category_id, (category_name, amounts))| {
+                let weeks = Self::rising_streak(&amounts);
+                (weeks >= INSIGHT_MIN_STREAK_WEEKS).then(|| InsightDetail::RisingStreak {
+                    category_id,
+                    category_name,
+                    weeks,
+                    latest_amount_cents: amounts.last().copied().unwrap_or(0),
+                })
+            })
+            .collect();
+        streaks.sort_by(|a, b| match (a, b) {
+            (
+                InsightDetail::RisingStreak { weeks: wa, category_name: na, .. },
+                InsightDetail::RisingStreak { weeks: wb, category_name: nb, .. },
+            ) => wb.cmp(wa).then_with(|| na.cmp(nb)),
+            _ => std::cmp::Ordering::Equal,
+        });
+
+        Ok(streaks)
+    }
+
+    /// Notable findings for `start..=end`, compared against the equally long period just before it
+    /// Streaks look at the weeks ending on `end`, whatever the period's length
+    pub async fn build(db: &SqlitePool, start: NaiveDate, end: NaiveDate) -> Result<SpendingInsights, String> {
+        if end < start {
+            return Err("End date must not be before start date".to_string());
+        }
+
+        let previous_end = start - chrono::Duration::days(1);
+        let previous_start = previous_end - (end - start);
+        let period = DatePeriod {
+            start_date: Self::format_date(start),
+            end_date: Self::format_date(end),
+        };
+        let previous_period = DatePeriod {
+            start_date: Self::format_date(previous_start),
+            end_date: Self::format_date(previous_end),
+        };
+
+        let current = Self::category_amounts(db, &period.start_date, &period.end_date).await?;
+        let previous = Self::category_amounts(db, &previous_period.start_date, &previous_period.end_date).await?;
+
+        let mut details = Self::category_movers(&current, &previous);
+        details.extend(Self::new_merchants(db, &period.start_date, &period.end_date).await?);
+        details.extend(
+            SpendingAggregator::get_largest_transactions(
+                db,
+                &period.start_date,
+                &period.end_date,
+                INSIGHT_LARGEST_TRANSACTIONS,
+                true,
+                None,
+            )
+            .await?
+            .into_iter()
+            .map(|t| InsightDetail::LargeTransaction {
+                transaction_id: t.id,
+                date: t.date,
+                amount_cents: t.amount_cents,
+                description: t.description,
+                category_name: t.category_name,
+            }),
+        );
+        details.extend(Self::rising_streaks(db, end).await?);
+
+        let insights = details
+            .into_iter()
+            .map(|detail| Insight { message: Self::describe(&detail), detail })
+            .collect();
+
+        Ok(SpendingInsights { period, previous_period, insights })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amounts(entries: &[(i64, &str, Cents)]) -> HashMap<i64, (String, Cents)> {
+        entries.iter().map(|(id, name, amount)| (*id, (name.to_string(), *amount))).collect()
+    }
+
+    #[test]
+    fn test_rising_streak_counts_trailing_increases() {
+        assert_eq!(InsightGenerator::rising_streak(&[1000, 2000, 3000, 4000]), 3);
+        assert_eq!(InsightGenerator::rising_streak(&[5000, 2000, 3000, 4000]), 2);
+        assert_eq!(InsightGenerator::rising_streak(&[1000, 2000, 2000]), 0, "Flat weeks end a streak");
+        assert_eq!(InsightGenerator::rising_streak(&[4000, 3000]), 0);
+        assert_eq!(InsightGenerator::rising_streak(&[]), 0);
+    }
+
+    #[test]
+    fn test_category_movers_orders_increases_then_decreases() {
+        let current = amounts(&[(1, "Dining", 30_000), (2, "Groceries", 25_000), (3, "Fuel", 2000), (4, "Gifts", 8000)]);
+        let previous = amounts(&[(1, "Dining", 10_000), (2, "Groceries", 24_500), (3, "Fuel", 12_000), (5, "Travel", 50_000)]);
+
+        let movers = InsightGenerator::category_movers(&current, &previous);
+        let names: Vec<&str> = movers
+            .iter()
+            .map(|m| match m {
+                InsightDetail::CategoryIncrease { category_name, .. } => category_name.as_str(),
+                InsightDetail::CategoryDecrease { category_name, .. } => category_name.as_str(),
+                _ => unreachable!(),
+            })
+            .collect();
+
+        // Groceries moved by less than the minimum change
+        assert_eq!(names, vec!["Dining", "Gifts", "Travel", "Fuel"]);
+        assert!(matches!(
+            movers[1],
+            InsightDetail::CategoryIncrease { change_percent: None, .. }
+        ), "A category new this period has no percentage change");
+        assert!(matches!(
+            movers[2],
+            InsightDetail::CategoryDecrease { amount_cents: 0, change_cents: -50_000, .. }
+        ));
+    }
+
+    #[test]
+    fn test_describe_streak() {
+        let detail = InsightDetail::RisingStreak {
+            category_id: 1,
+            category_name: "dining".to_string(),
+            weeks: 5,
+            latest_amount_cents: 8250,
+        };
+        assert_eq!(
+            InsightGenerator::describe(&detail),
+            "5 weeks of rising dining spending, $82.50 in the latest week"
+        );
+    }
+
+    #[test]
+    fn test_insight_serializes_kind_alongside_fields() {
+        let insight = Insight {
+            detail: InsightDetail::NewMerchant {
+                merchant_name: "Corner Cafe".to_string(),
+                amount_cents: 1200,
+                transaction_count: 1,
+                first_date: "2025-03-02".to_string(),
+            },
+            message: "New merchant".to_string(),
+        };
+        let json = serde_json::to_value(&insight).unwrap();
+        assert_eq!(json["kind"], "new_merchant");
+        assert_eq!(json["merchant_name"], "Corner Cafe");
+        assert_eq!(json["message"], "New merchant");
+    }
+}
@@ -1,22 +1,39 @@
 /// Utility functions for interest calculations
-use crate::constants::{MONTHS_PER_YEAR, PERCENT_TO_DECIMAL_DIVISOR};
+use crate::constants::{DAYS_PER_YEAR, MONTHS_PER_YEAR, PERCENT_TO_DECIMAL_DIVISOR};
+use crate::utils::money::{self, Cents};
 
-/// Calculate monthly interest on a balance given an annual interest rate
-pub fn calculate_monthly_interest(balance: f64, annual_rate: f64) -> f64 {
-    if balance <= 0.0 || annual_rate < 0.0 {
-        return 0.0;
+/// Calculate monthly interest on a balance given an annual interest rate, rounded to a cent
+pub fn calculate_monthly_interest(balance: Cents, annual_rate: f64) -> Cents {
+    if balance <= 0 || annual_rate < 0.0 {
+        return 0;
     }
-    balance * (annual_rate / PERCENT_TO_DECIMAL_DIVISOR / MONTHS_PER_YEAR)
+    money::monthly_interest(balance, annual_rate)
+}
+
+/// Simple interest accrued on a balance over `days` days, rounded to a cent
+pub fn calculate_accrued_interest(balance: Cents, annual_rate: f64, days: i64) -> Cents {
+    if balance <= 0 || annual_rate < 0.0 || days <= 0 {
+        return 0;
+    }
+    money::percent_of(balance, annual_rate * (days as f64 / DAYS_PER_YEAR))
+}
+
+/// Split a payment into (interest, principal): accrued interest is paid first and the
+/// rest goes to principal
+pub fn split_payment(balance: Cents, annual_rate: f64, days: i64, payment: Cents) -> (Cents, Cents) {
+    let accrued = calculate_accrued_interest(balance, annual_rate, days);
+    let interest = accrued.min(payment.max(0));
+    (interest, payment - interest)
 }
 
 /// Calculate the total interest paid over a series of payments
 pub fn calculate_total_interest(
-    initial_balance: f64,
-    final_balance: f64,
-    total_payments: f64,
-) -> f64 {
-    if total_payments <= 0.0 {
-        return 0.0;
+    initial_balance: Cents,
+    final_balance: Cents,
+    total_payments: Cents,
+) -> Cents {
+    if total_payments <= 0 {
+        return 0;
     }
     // Total interest = (initial balance - final balance - total payments)
     // This accounts for the principal reduction
@@ -24,7 +41,7 @@ pub fn calculate_total_interest(
     if total_payments > principal_paid {
         total_payments - principal_paid
     } else {
-        0.0
+        0
     }
 }
 
@@ -35,13 +52,12 @@ pub fn calculate_effective_annual_rate(monthly_rate: f64) -> f64 {
 
 /// Calculate new balance after applying monthly interest and payment
 pub fn apply_payment_with_interest(
-    balance: f64,
+    balance: Cents,
     annual_rate: f64,
-    payment: f64,
-) -> f64 {
+    payment: Cents,
+) -> Cents {
     let interest = calculate_monthly_interest(balance, annual_rate);
-    let new_balance = balance + interest - payment;
-    new_balance.max(0.0)
+    (balance + interest - payment).max(0)
 }
 
 #[cfg(test)]
@@ -50,60 +66,60 @@ mod tests {
 
     #[test]
     fn test_monthly_interest_calculation() {
-        let balance = 1000.0;
+        let balance = 100_000;
         let annual_rate = 18.0; // 18% APR
         let monthly_interest = calculate_monthly_interest(balance, annual_rate);
 
         // 18% / 12 = 1.5% per month
-        // 1000 * 0.015 = 15.0
-        assert!((monthly_interest - 15.0).abs() < 0.01);
+        // 1000.00 * 0.015 = 15.00
+        assert_eq!(monthly_interest, 1500);
     }
 
     #[test]
     fn test_monthly_interest_zero_balance() {
-        assert_eq!(calculate_monthly_interest(0.0, 18.0), 0.0);
+        assert_eq!(calculate_monthly_interest(0, 18.0), 0);
     }
 
     #[test]
     fn test_monthly_interest_zero_rate() {
-        assert_eq!(calculate_monthly_interest(1000.0, 0.0), 0.0);
+        assert_eq!(calculate_monthly_interest(100_000, 0.0), 0);
     }
 
     #[test]
     fn test_apply_payment_with_interest() {
-        let balance = 1000.0;
+        let balance = 100_000;
         let annual_rate = 18.0;
-        let payment = 100.0;
+        let payment = 10_000;
 
         let new_balance = apply_payment_with_interest(balance, annual_rate, payment);
 
         // Balance after interest: 1000 + 15 = 1015
         // Balance after payment: 1015 - 100 = 915
-        assert!((new_balance - 915.0).abs() < 0.01);
+        assert_eq!(new_balance, 91_500);
     }
 
     #[test]
     fn test_apply_payment_exceeding_balance() {
-        let balance = 100.0;
+        let balance = 10_000;
         let annual_rate = 15.0;
-        let payment = 200.0;
+        let payment = 20_000;
 
         let new_balance = apply_payment_with_interest(balance, annual_rate, payment);
 
         // Should not go negative
-        assert_eq!(new_balance, 0.0);
+        assert_eq!(new_balance, 0);
     }
 
     #[test]
     fn test_total_interest_calculation() {
-        let initial = 1000.0;
-        let final_balance = 0.0;
-        let total_payments = 1150.0;
+        let initial = 100_000;
+        let final_balance = 0;
+        let total_payments = 115_000;
 
         let total_interest = calculate_total_interest(initial, final_balance, total_payments);
 
         // Paid 1150 to eliminate 1000 debt = 150 in interest
-        assert!((total_interest - 150.0).abs() < 0.01);
+        assert_eq!(total_interest, 15_000);
     }
 
     #[test]
@@ -115,4 +131,21 @@ mod tests {
         assert!(ear > 18.0);
         assert!(ear < 20.0);
     }
+
+    #[test]
+    fn test_accrued_interest() {
+        // 1000 at 18.25% for 20 days: 1000 * 0.1825 * 20 / 365 = 10.00
+        assert_eq!(calculate_accrued_interest(100_000, 18.25, 20), 1000);
+        assert_eq!(calculate_accrued_interest(100_000, 18.0, 0), 0);
+        assert_eq!(calculate_accrued_interest(100_000, 18.0, -5), 0);
+        assert_eq!(calculate_accrued_interest(0, 18.0, 30), 0);
+    }
+
+    #[test]
+    fn test_split_payment() {
+        assert_eq!(split_payment(100_000, 18.25, 20, 10_000), (1000, 9000));
+
+        // A payment smaller than the interest covers interest only
+        assert_eq!(split_payment(100_000, 18.25, 20, 600), (600, 0));
+    }
 }
@@ -0,0 +1,200 @@
+use crate::errors::JobError;
+use crate::models::job::{Job, JobKind, JobStatus};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Called with the job's latest state whenever it starts, reports progress, or finishes
+pub type JobNotifier = Arc<dyn Fn(&Job) + Send + Sync>;
+
+const JOB_COLUMNS: &str = "id, kind, status, processed, total, message, result, error, cancel_requested,
+    created_at, started_at, finished_at";
+
+/// Handed to a running job so it can report progress and notice cancellation
+#[derive(Clone)]
+pub struct JobContext {
+    pub db: SqlitePool,
+    pub job_id: i64,
+    cancelled: Arc<AtomicBool>,
+    notify: JobNotifier,
+}
+
+impl JobContext {
+    /// Record progress and notify listeners
+    /// Also picks up a pending cancel request, so work should stop soon after an `Err`.
+    /// Reports arriving out of order never move `processed` backwards
+    pub async fn progress(&self, processed: i64, total: Option<i64>, message: Option<&str>) -> Result<(), String> {
+        sqlx::query("UPDATE jobs SET processed = ?, total = ?, message = ? WHERE id = ? AND processed <= ?")
+            .bind(processed)
+            .bind(total)
+            .bind(message)
+            .bind(self.job_id)
+            .bind(processed)
+            .execute(&self.db)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let job = JobQueue::get(&self.db, self.job_id).await.map_err(|e| e.to_string())?;
+        (self.notify)(&job);
+
+        if job.cancel_requested {
+            self.cancelled.store(true, Ordering::SeqCst);
+            return Err("Job cancelled".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether a cancel request was seen at the last progress report
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+pub struct JobQueue;
+
+impl JobQueue {
+    /// Record a new job waiting to run
+    pub async fn enqueue(db: &SqlitePool, kind: JobKind) -> Result<i64, JobError> {
+        let result = sqlx::query("INSERT INTO jobs (kind) VALUES (?)")
+            .bind(kind.to_string())
+            .execute(db)
+            .await
+            .map_err(|e| JobError::Database(e.to_string()))?;
+        Ok(result.last_insert_rowid())
+    }
+
+    pub async fn get(db: &SqlitePool, job_id: i64) -> Result<Job, JobError> {
+        sqlx::query_as::<_, Job>(&format!("SELECT {} FROM jobs WHERE id = ?", JOB_COLUMNS))
+            .bind(job_id)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| JobError::Database(e.to_string()))?
+            .ok_or(JobError::NotFound(job_id))
+    }
+
+    /// Most recent jobs first
+    pub async fn list(db: &SqlitePool, status: Option<JobStatus>, limit: i64) -> Result<Vec<Job>, JobError> {
+        let status = status.map(|s| s.to_string());
+        sqlx::query_as::<_, Job>(&format!(
+            "SELECT {} FROM jobs WHERE (? IS NULL OR status = ?) ORDER BY created_at DESC, id DESC LIMIT ?",
+            JOB_COLUMNS
+        ))
+        .bind(&status)
+        .bind(&status)
+        .bind(limit)
+        .fetch_all(db)
+        .await
+        .map_err(|e| JobError::Database(e.to_string()))
+    }
+
+    /// Cancel a job: one still queued is cancelled outright, a running one stops at its next progress report
+    pub async fn request_cancel(db: &SqlitePool, job_id: i64) -> Result<Job, JobError> {
+        let job = Self::get(db, job_id).await?;
+        let status: JobStatus = job.status.parse().map_err(JobError::Database)?;
+        if status.is_finished() {
+            return Err(JobError::AlreadyFinished { id: job_id, status: job.status });
+        }
+
+        sqlx::query(
+            "UPDATE jobs
+             SET cancel_requested = 1,
+                 status = CASE WHEN status = 'queued' THEN 'cancelled' ELSE status END,
+                 finished_at = CASE WHEN status = 'queued' THEN CURRENT_TIMESTAMP ELSE finished_at END
+             WHERE id = ?"
+        )
+        .bind(job_id)
+        .execute(db)
+        .await
+        .map_err(|e| JobError::Database(e.to_string()))?;
+
+        Self::get(db, job_id).await
+    }
+
+    /// Number of jobs still queued or running
+    pub async fn count_unfinished(db: &SqlitePool) -> Result<i64, JobError> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE status IN ('queued', 'running')")
+            .fetch_one(db)
+            .await
+            .map_err(|e| JobError::Database(e.to_string()))
+    }
+
+    /// Mark jobs left queued or running by a previous session as failed. Runs at startup
+    pub async fn fail_interrupted(db: &SqlitePool) -> Result<u64, JobError> {
+        let result = sqlx::query(
+            "UPDATE jobs
+             SET status = 'failed', error = 'Interrupted when the app closed', finished_at = CURRENT_TIMESTAMP
+             WHERE status IN ('queued', 'running')"
+        )
+        .execute(db)
+        .await
+        .map_err(|e| JobError::Database(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn finish(
+        db: &SqlitePool,
+        job_id: i64,
+        status: JobStatus,
+        result: Option<String>,
+        error: Option<String>,
+    ) -> Result<Job, JobError> {
+        sqlx::query(
+            "UPDATE jobs SET status = ?, result = ?, error = ?, finished_at = CURRENT_TIMESTAMP WHERE id = ?"
+        )
+        .bind(status.to_string())
+        .bind(result)
+        .bind(error)
+        .bind(job_id)
+        .execute(db)
+        .await
+        .map_err(|e| JobError::Database(e.to_string()))?;
+        Self::get(db, job_id).await
+    }
+
+    /// Run `work` as job `job_id`, recording its status and result and notifying as it goes
+    /// Meant to be spawned; a job cancelled while still queued never starts
+    pub async fn run<T, F, Fut>(db: SqlitePool, job_id: i64, notify: JobNotifier, work: F) -> Result<Job, JobError>
+    where
+        T: Serialize,
+        F: FnOnce(JobContext) -> Fut,
+        Fut: Future<Output = Result<T, String>>,
+    {
+        let started = sqlx::query(
+            "UPDATE jobs SET status = 'running', started_at = CURRENT_TIMESTAMP WHERE id = ? AND status = 'queued'"
+        )
+        .bind(job_id)
+        .execute(&db)
+        .await
+        .map_err(|e| JobError::Database(e.to_string()))?;
+        let job = Self::get(&db, job_id).await?;
+        if started.rows_affected() == 0 {
+            return Ok(job);
+        }
+        notify(&job);
+
+        let context = JobContext {
+            db: db.clone(),
+            job_id,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            notify: notify.clone(),
+        };
+        let outcome = work(context.clone()).await;
+
+        let job = match outcome {
+            Ok(value) => {
+                let result = serde_json::to_string(&value).map_err(|e| JobError::Database(e.to_string()))?;
+                Self::finish(&db, job_id, JobStatus::Completed, Some(result), None).await?
+            }
+            Err(_) if context.is_cancelled() || Self::get(&db, job_id).await?.cancel_requested => {
+                Self::finish(&db, job_id, JobStatus::Cancelled, None, None).await?
+            }
+            Err(error) => Self::finish(&db, job_id, JobStatus::Failed, None, Some(error)).await?,
+        };
+        tracing::info!(job_id = job_id, kind = %job.kind, status = %job.status, "Job finished");
+        notify(&job);
+
+        Ok(job)
+    }
+}
@@ -0,0 +1,278 @@
+use super::csv_parser::CsvError;
+use crate::constants::{MAX_MAPPING_EXPRESSION_DEPTH, MAX_MAPPING_EXPRESSION_LENGTH};
+use csv::StringRecord;
+
+/// A computed mapping field, written like a spreadsheet formula:
+/// `=[Payee] + " - " + trim([Memo])`, `="Card purchase"`, `=substr([Memo], 0, 20)`
+///
+/// Supports quoted text, `[Column]` references, `+` to join text and the functions
+/// `trim`, `upper`, `lower`, `substr(text, start[, length])` (0-based, in characters)
+/// and `coalesce(a, b, ...)` (the first non-blank value). Nothing else can be evaluated.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MappingExpression {
+    Text(String),
+    Column(usize),
+    Concat(Vec<MappingExpression>),
+    Trim(Box<MappingExpression>),
+    Upper(Box<MappingExpression>),
+    Lower(Box<MappingExpression>),
+    Substr(Box<MappingExpression>, usize, Option<usize>),
+    Coalesce(Vec<MappingExpression>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Text(String),
+    Number(usize),
+    Column(String),
+    Ident(String),
+    LParen,
+    RParen,
+    Comma,
+    Plus,
+}
+
+fn invalid(message: impl Into<String>) -> CsvError {
+    CsvError::InvalidMapping(format!("Invalid mapping expression: {}", message.into()))
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, CsvError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' => tokens.push(Token::LParen),
+            ')' => tokens.push(Token::RParen),
+            ',' => tokens.push(Token::Comma),
+            '+' => tokens.push(Token::Plus),
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => text.extend(chars.next()),
+                        Some('"') => break,
+                        Some(c) => text.push(c),
+                        None => return Err(invalid("unterminated text")),
+                    }
+                }
+                tokens.push(Token::Text(text));
+            }
+            '[' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(invalid("unterminated column reference")),
+                    }
+                }
+                tokens.push(Token::Column(name));
+            }
+            c if c.is_ascii_digit() => {
+                let mut digits = c.to_string();
+                while let Some(d) = chars.next_if(char::is_ascii_digit) {
+                    digits.push(d);
+                }
+                tokens.push(Token::Number(digits.parse().map_err(|_| invalid("number too large"))?));
+            }
+            c if c.is_ascii_alphabetic() => {
+                let mut ident = c.to_string();
+                while let Some(d) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    ident.push(d);
+                }
+                tokens.push(Token::Ident(ident.to_ascii_lowercase()));
+            }
+            c => return Err(invalid(format!("unexpected '{}'", c))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a, F> {
+    tokens: std::iter::Peekable<std::vec::IntoIter<Token>>,
+    column: &'a F,
+}
+
+impl<F: Fn(&str) -> Result<usize, CsvError>> Parser<'_, F> {
+    fn expect(&mut self, token: Token) -> Result<(), CsvError> {
+        match self.tokens.next() {
+            Some(t) if t == token => Ok(()),
+            _ => Err(invalid(format!("expected {:?}", token))),
+        }
+    }
+
+    fn number(&mut self) -> Result<usize, CsvError> {
+        match self.tokens.next() {
+            Some(Token::Number(n)) => Ok(n),
+            _ => Err(invalid("expected a number")),
+        }
+    }
+
+    /// term ('+' term)*
+    fn concat(&mut self, depth: usize) -> Result<MappingExpression, CsvError> {
+        let mut parts = vec![self.term(depth)?];
+        while self.tokens.next_if_eq(&Token::Plus).is_some() {
+            parts.push(self.term(depth)?);
+        }
+        Ok(if parts.len() == 1 { parts.remove(0) } else { MappingExpression::Concat(parts) })
+    }
+
+    fn term(&mut self, depth: usize) -> Result<MappingExpression, CsvError> {
+        if depth > MAX_MAPPING_EXPRESSION_DEPTH {
+            return Err(invalid("too deeply nested"));
+        }
+
+        match self.tokens.next() {
+            Some(Token::Text(text)) => Ok(MappingExpression::Text(text)),
+            Some(Token::Number(n)) => Ok(MappingExpression::Text(n.to_string())),
+            Some(Token::Column(name)) => Ok(MappingExpression::Column((self.column)(&name)?)),
+            Some(Token::LParen) => {
+                let inner = self.concat(depth + 1)?;
+                self.expect(Token::RParen)?;
+                Ok(inner)
+            }
+            Some(Token::Ident(name)) => {
+                self.expect(Token::LParen)?;
+                let expr = match name.as_str() {
+                    "trim" => MappingExpression::Trim(Box::new(self.concat(depth + 1)?)),
+                    "upper" => MappingExpression::Upper(Box::new(self.concat(depth + 1)?)),
+                    "lower" => MappingExpression::Lower(Box::new(self.concat(depth + 1)?)),
+                    "substr" => {
+                        let text = self.concat(depth + 1)?;
+                        self.expect(Token::Comma)?;
+                        let start = self.number()?;
+                        let length = match self.tokens.next_if_eq(&Token::Comma) {
+                            Some(_) => Some(self.number()?),
+                            None => None,
+                        };
+                        MappingExpression::Substr(Box::new(text), start, length)
+                    }
+                    "coalesce" => {
+                        let mut values = vec![self.concat(depth + 1)?];
+                        while self.tokens.next_if_eq(&Token::Comma).is_some() {
+                            values.push(self.concat(depth + 1)?);
+                        }
+                        MappingExpression::Coalesce(values)
+                    }
+                    _ => return Err(invalid(format!("unknown function {}", name))),
+                };
+                self.expect(Token::RParen)?;
+                Ok(expr)
+            }
+            _ => Err(invalid("expected text, a [Column] or a function")),
+        }
+    }
+}
+
+impl MappingExpression {
+    /// Whether a mapping field is an expression rather than a column name
+    pub fn is_expression(field: &str) -> bool {
+        field.trim_start().starts_with('=')
+    }
+
+    /// Parse an expression (with or without its leading `=`), resolving column names with `column`
+    pub fn parse(source: &str, column: impl Fn(&str) -> Result<usize, CsvError>) -> Result<Self, CsvError> {
+        if source.len() > MAX_MAPPING_EXPRESSION_LENGTH {
+            return Err(invalid(format!("longer than {} characters", MAX_MAPPING_EXPRESSION_LENGTH)));
+        }
+        let source = source.trim_start();
+        let source = source.strip_prefix('=').unwrap_or(source);
+
+        let mut parser = Parser {
+            tokens: tokenize(source)?.into_iter().peekable(),
+            column: &column,
+        };
+        let expr = parser.concat(0)?;
+        if parser.tokens.next().is_some() {
+            return Err(invalid("unexpected input after the expression"));
+        }
+        Ok(expr)
+    }
+
+    /// Check an expression's syntax without a file to resolve its columns against
+    pub fn validate(source: &str) -> Result<(), CsvError> {
+        Self::parse(source, |_| Ok(0)).map(|_| ())
+    }
+
+    /// Evaluate against a row; cells missing from a short row count as blank
+    pub fn eval(&self, record: &StringRecord) -> String {
+        match self {
+            MappingExpression::Text(text) => text.clone(),
+            MappingExpression::Column(idx) => record.get(*idx).unwrap_or_default().to_string(),
+            MappingExpression::Concat(parts) => parts.iter().map(|p| p.eval(record)).collect(),
+            MappingExpression::Trim(inner) => inner.eval(record).trim().to_string(),
+            MappingExpression::Upper(inner) => inner.eval(record).to_uppercase(),
+            MappingExpression::Lower(inner) => inner.eval(record).to_lowercase(),
+            MappingExpression::Substr(inner, start, length) => {
+                let text = inner.eval(record);
+                let chars = text.chars().skip(*start);
+                match length {
+                    Some(length) => chars.take(*length).collect(),
+                    None => chars.collect(),
+                }
+            }
+            MappingExpression::Coalesce(values) => values
+                .iter()
+                .map(|v| v.eval(record))
+                .find(|v| !v.trim().is_empty())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(name: &str) -> Result<usize, CsvError> {
+        ["Payee", "Memo", "Ref"]
+            .iter()
+            .position(|c| *c == name)
+            .ok_or_else(|| CsvError::MissingColumn(name.to_string()))
+    }
+
+    fn eval(source: &str, row: &[&str]) -> String {
+        MappingExpression::parse(source, columns).unwrap().eval(&StringRecord::from(row.to_vec()))
+    }
+
+    #[test]
+    fn test_concat_and_constants() {
+        let row = ["ACME", "  Invoice 42 ", "7"];
+        assert_eq!(eval("=[Payee] + \" - \" + trim([Memo])", &row), "ACME - Invoice 42");
+        assert_eq!(eval("=\"Card purchase\"", &row), "Card purchase");
+        assert_eq!(eval("=\"Ref #\" + [Ref]", &row), "Ref #7");
+        assert_eq!(eval("=\"say \\\"hi\\\"\"", &row), "say \"hi\"");
+    }
+
+    #[test]
+    fn test_functions() {
+        let row = ["", "POS 1234 COFFEE ROASTERS", ""];
+        assert_eq!(eval("=substr([Memo], 9)", &row), "COFFEE ROASTERS");
+        assert_eq!(eval("=lower(substr([Memo], 0, 3))", &row), "pos");
+        assert_eq!(eval("=COALESCE([Payee], [Memo])", &row), "POS 1234 COFFEE ROASTERS");
+        assert_eq!(eval("=coalesce([Payee], [Ref])", &row), "");
+        assert_eq!(eval("=upper(\"a\" + (\"b\"))", &row), "AB");
+    }
+
+    #[test]
+    fn test_short_rows_are_blank() {
+        assert_eq!(eval("=[Payee] + [Ref]", &["ACME"]), "ACME");
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert!(matches!(
+            MappingExpression::parse("=[Amount]", columns),
+            Err(CsvError::MissingColumn(c)) if c == "Amount"
+        ));
+        for source in ["=", "=\"open", "=[Payee", "=eval([Payee])", "=[Payee] [Memo]", "=substr([Memo])", "=1 - 2"] {
+            assert!(MappingExpression::validate(source).is_err(), "{} should be rejected", source);
+        }
+        let nested = format!("={}\"x\"{}", "trim(".repeat(40), ")".repeat(40));
+        assert!(MappingExpression::validate(&nested).is_err());
+        assert!(MappingExpression::validate(&format!("=\"{}\"", "x".repeat(600))).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use sqlx::SqlitePool;
+
+/// Payment processor prefixes that precede the real merchant name
+const PROCESSOR_PREFIXES: &[&str] = &[
+    "SQ *", "SQ*", "TST*", "TST *", "SP *", "SP*", "PAYPAL *", "PP*", "POS ", "DEBIT ",
+    "CHECKCARD ", "PURCHASE ",
+];
+
+/// US state/territory codes commonly appended to card descriptors
+const STATE_CODES: &[&str] = &[
+    "AL", "AK", "AZ", "AR", "CA", "CO", "CT", "DE", "DC", "FL", "GA", "HI", "ID", "IL", "IN",
+    "IA", "KS", "KY", "LA", "ME", "MD", "MA", "MI", "MN", "MS", "MO", "MT", "NE", "NV", "NH",
+    "NJ", "NM", "NY", "NC", "ND", "OH", "OK", "OR", "PA", "RI", "SC", "SD", "TN", "TX", "UT",
+    "VT", "VA", "WA", "WV", "WI", "WY", "PR",
+];
+
+pub struct MerchantNormalizer;
+
+impl MerchantNormalizer {
+    /// Looks like a store/terminal number ("#1234", "0042", "T-1234")
+    fn is_store_number(token: &str) -> bool {
+        let alnum: Vec<char> = token.chars().filter(|c| c.is_alphanumeric()).collect();
+        let digits = alnum.iter().filter(|c| c.is_ascii_digit()).count();
+        token.starts_with('#') || (digits > 0 && digits * 2 >= alnum.len())
+    }
+
+    fn title_case(word: &str) -> String {
+        let lower = word.to_lowercase();
+        match lower.char_indices().find(|(_, c)| c.is_alphabetic()) {
+            Some((i, c)) => {
+                let mut result = lower[..i].to_string();
+                result.extend(c.to_uppercase());
+                result.push_str(&lower[i + c.len_utf8()..]);
+                result
+            }
+            None => lower,
+        }
+    }
+
+    /// Clean a raw merchant descriptor into a display name
+    /// Strips processor prefixes, store numbers and anything after them (usually the city),
+    /// and a trailing state code: "STARBUCKS #1234 SEATTLE" -> "Starbucks"
+    /// Returns None when nothing meaningful is left
+    pub fn normalize(raw: &str) -> Option<String> {
+        let mut text = raw.trim().to_uppercase();
+        while let Some(prefix) = PROCESSOR_PREFIXES.iter().find(|p| text.starts_with(*p)) {
+            text = text[prefix.len()..].trim_start().to_string();
+        }
+
+        let mut tokens: Vec<&str> = Vec::new();
+        for token in text.split(|c: char| c.is_whitespace() || c == '*') {
+            let token = token.trim_matches(|c: char| matches!(c, ',' | '.' | '-' | ':' | ';'));
+            if token.is_empty() {
+                continue;
+            }
+            // Everything from the first store number on is location noise
+            // (a leading number is kept: "76 GAS", "7-ELEVEN")
+            if !tokens.is_empty() && Self::is_store_number(token) {
+                break;
+            }
+            tokens.push(token);
+        }
+
+        if tokens.len() > 1 && tokens.last().is_some_and(|t| STATE_CODES.contains(t)) {
+            tokens.pop();
+        }
+
+        if tokens.is_empty() {
+            return None;
+        }
+
+        Some(
+            tokens
+                .iter()
+                .map(|t| Self::title_case(t))
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+
+    /// Lookup key used for merchant aliases (normalized, lowercase)
+    pub fn alias_key(raw: &str) -> Option<String> {
+        Self::normalize(raw).map(|name| name.to_lowercase())
+    }
+
+    /// Find the merchant for a raw descriptor, creating the merchant and alias on first sight
+    /// Returns None when the descriptor normalizes to nothing
+    pub async fn resolve(db: &SqlitePool, raw: &str) -> Result<Option<i64>, String> {
+        let Some(name) = Self::normalize(raw) else {
+            return Ok(None);
+        };
+        let key = name.to_lowercase();
+
+        let alias: Option<(i64,)> = sqlx::query_as("SELECT merchant_id FROM merchant_aliases WHERE alias = ?")
+            .bind(&key)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| format!("Failed to look up merchant alias: {}", e))?;
+        if let Some((merchant_id,)) = alias {
+            return Ok(Some(merchant_id));
+        }
+
+        // INSERT OR IGNORE keeps concurrent imports from racing on the unique name
+        sqlx::query("INSERT OR IGNORE INTO merchants (name) VALUES (?)")
+            .bind(&name)
+            .execute(db)
+            .await
+            .map_err(|e| format!("Failed to create merchant: {}", e))?;
+
+        let (merchant_id,): (i64,) = sqlx::query_as("SELECT id FROM merchants WHERE name = ?")
+            .bind(&name)
+            .fetch_one(db)
+            .await
+            .map_err(|e| format!("Failed to load merchant: {}", e))?;
+
+        sqlx::query("INSERT OR IGNORE INTO merchant_aliases (merchant_id, alias) VALUES (?, ?)")
+            .bind(merchant_id)
+            .bind(&key)
+            .execute(db)
+            .await
+            .map_err(|e| format!("Failed to create merchant alias: {}", e))?;
+
+        Ok(Some(merchant_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_strips_store_number_and_city() {
+        assert_eq!(MerchantNormalizer::normalize("STARBUCKS #1234 SEATTLE").as_deref(), Some("Starbucks"));
+        assert_eq!(MerchantNormalizer::normalize("Starbucks 0042").as_deref(), Some("Starbucks"));
+        assert_eq!(MerchantNormalizer::normalize("TARGET T-1234 MINNEAPOLIS MN").as_deref(), Some("Target"));
+    }
+
+    #[test]
+    fn test_normalize_strips_processor_prefix_and_state() {
+        assert_eq!(MerchantNormalizer::normalize("SQ *BLUE BOTTLE COFFEE").as_deref(), Some("Blue Bottle Coffee"));
+        assert_eq!(MerchantNormalizer::normalize("POS WHOLE FOODS AUSTIN TX").as_deref(), Some("Whole Foods Austin"));
+        assert_eq!(MerchantNormalizer::normalize("TST* JOE'S PIZZA").as_deref(), Some("Joe's Pizza"));
+    }
+
+    #[test]
+    fn test_normalize_keeps_leading_numbers() {
+        assert_eq!(MerchantNormalizer::normalize("7-ELEVEN 38211").as_deref(), Some("7-Eleven"));
+        assert_eq!(MerchantNormalizer::normalize("76 GAS STATION").as_deref(), Some("76 Gas Station"));
+    }
+
+    #[test]
+    fn test_normalize_empty() {
+        assert_eq!(MerchantNormalizer::normalize("   "), None);
+        assert_eq!(MerchantNormalizer::normalize(" * "), None);
+    }
+
+    #[test]
+    fn test_alias_key_is_case_insensitive() {
+        assert_eq!(
+            MerchantNormalizer::alias_key("STARBUCKS #1234 SEATTLE"),
+            MerchantNormalizer::alias_key("starbucks 0042 portland")
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use super::csv_parser::{CsvError, CsvParser};
+use crate::models::data_archive::{ArchivedAccount, ArchivedCategory, ArchivedTransaction, DataArchive, DATA_ARCHIVE_VERSION};
+use crate::models::transaction::TransactionStatus;
+use crate::utils::money::Cents;
+use csv::ReaderBuilder;
+use std::collections::HashMap;
+
+/// Mint's category for transactions it couldn't categorize
+const UNCATEGORIZED: &str = "Uncategorized";
+
+pub struct MintImporter;
+
+impl MintImporter {
+    /// Read Mint's transactions export (Date, Description, Original Description, Amount,
+    /// Transaction Type, Category, Account Name, Labels, Notes) into an archive of its
+    /// accounts, categories and transactions
+    pub fn parse(csv_content: &str) -> Result<DataArchive, CsvError> {
+        let mut reader = ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(csv_content.trim_start_matches('\u{feff}').as_bytes());
+        let headers = reader.headers().map_err(|e| CsvError::ParseError(e.to_string()))?.clone();
+        let position = |name: &str| headers.iter().position(|h| h.trim().eq_ignore_ascii_case(name));
+        let column = |name: &str| position(name).ok_or_else(|| CsvError::MissingColumn(name.to_string()));
+
+        let date_idx = column("Date")?;
+        let description_idx = column("Description")?;
+        let original_idx = position("Original Description");
+        let amount_idx = column("Amount")?;
+        let type_idx = column("Transaction Type")?;
+        let category_idx = column("Category")?;
+        let account_idx = column("Account Name")?;
+        let labels_idx = position("Labels");
+        let notes_idx = position("Notes");
+
+        let mut archive = DataArchive { version: DATA_ARCHIVE_VERSION, ..Default::default() };
+        let mut balances: HashMap<String, Cents> = HashMap::new();
+        let mut accounts: Vec<String> = Vec::new();
+
+        for (row, record) in reader.records().enumerate() {
+            let record = record.map_err(|e| CsvError::ParseError(e.to_string()))?;
+            let line = row + 2; // After the header, counting from 1
+            let field = |idx: usize| record.get(idx).map(str::trim).unwrap_or_default();
+            let optional = |idx: Option<usize>| idx.map(field).filter(|v| !v.is_empty()).map(str::to_string);
+
+            let date = CsvParser::normalize_date(field(date_idx))?;
+            // Amounts are unsigned; the transaction type gives the direction
+            let amount = CsvParser::parse_cents(field(amount_idx), None)?.abs();
+            let amount = match field(type_idx).to_lowercase().as_str() {
+                "debit" => -amount,
+                "credit" => amount,
+                other => {
+                    return Err(CsvError::ParseError(format!("Line {}: unknown transaction type '{}'", line, other)))
+                }
+            };
+
+            let account = field(account_idx);
+            if account.is_empty() {
+                return Err(CsvError::ParseError(format!("Line {}: missing account name", line)));
+            }
+            if !balances.contains_key(account) {
+                accounts.push(account.to_string());
+            }
+            *balances.entry(account.to_string()).or_default() += amount;
+
+            let category = optional(Some(category_idx)).filter(|c| !c.eq_ignore_ascii_case(UNCATEGORIZED));
+            if let Some(name) = category.as_ref().filter(|c| !archive.categories.iter().any(|a| &a.name == *c)) {
+                archive.categories.push(ArchivedCategory {
+                    name: name.clone(),
+                    parent: None,
+                    icon: None,
+                    color: None,
+                    archived: false,
+                    tax_deductible: false,
+                });
+            }
+
+            // The original description is the bank's text, so it lines up with CSV imports of the
+            // same account; Mint's cleaned-up description is the merchant
+            let merchant = optional(Some(description_idx));
+            let description = optional(original_idx).or_else(|| merchant.clone()).unwrap_or_else(|| "Mint transaction".to_string());
+            let labels = optional(labels_idx).map(|labels| format!("Labels: {}", labels));
+            let notes = match (optional(notes_idx), labels) {
+                (Some(notes), Some(labels)) => Some(format!("{}\n{}", notes, labels)),
+                (notes, labels) => notes.or(labels),
+            };
+
+            archive.transactions.push(ArchivedTransaction {
+                account: account.to_string(),
+                category,
+                date,
+                amount_cents: amount,
+                description,
+                merchant,
+                notes,
+                original_currency: None,
+                original_amount_cents: None,
+                status: TransactionStatus::Posted.to_string(),
+                member: None,
+                tax_deductible: None,
+            });
+        }
+
+        archive.accounts = accounts
+            .iter()
+            .map(|name| ArchivedAccount::named(name, balances[name]))
+            .collect();
+        Ok(archive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = "\"Date\",\"Description\",\"Original Description\",\"Amount\",\"Transaction Type\",\"Category\",\"Account Name\",\"Labels\",\"Notes\"
+\"1/15/2024\",\"Starbucks\",\"STARBUCKS STORE 1234\",\"4.50\",\"debit\",\"Coffee Shops\",\"Chase Sapphire Card\",\"work\",\"\"
+\"1/16/2024\",\"Paycheck\",\"ACME PAYROLL\",\"2,000.00\",\"credit\",\"Paycheck\",\"Everyday Checking\",\"\",\"January\"
+\"1/17/2024\",\"Mystery\",\"POS 5555\",\"10.00\",\"debit\",\"Uncategorized\",\"Everyday Checking\",\"\",\"\"
+";
+
+    #[test]
+    fn test_parse_mint_export() {
+        let archive = MintImporter::parse(EXPORT).unwrap();
+
+        assert_eq!(archive.accounts.len(), 2);
+        assert_eq!(archive.accounts[0].name, "Chase Sapphire Card");
+        assert_eq!(archive.accounts[0].account_type, "credit_card");
+        assert_eq!(archive.accounts[1].account_type, "checking");
+        assert_eq!(archive.accounts[1].balance_cents, 199_000);
+
+        let names: Vec<&str> = archive.categories.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Coffee Shops", "Paycheck"]);
+
+        let coffee = &archive.transactions[0];
+        assert_eq!(coffee.date, "2024-01-15");
+        assert_eq!(coffee.amount_cents, -450);
+        assert_eq!(coffee.description, "STARBUCKS STORE 1234");
+        assert_eq!(coffee.merchant.as_deref(), Some("Starbucks"));
+        assert_eq!(coffee.notes.as_deref(), Some("Labels: work"));
+        assert_eq!(archive.transactions[1].amount_cents, 200_000);
+        assert_eq!(archive.transactions[2].category, None);
+    }
+
+    #[test]
+    fn test_parse_rejects_other_exports() {
+        assert!(matches!(
+            MintImporter::parse("Date,Amount,Description\n2024-01-01,1.00,Test\n"),
+            Err(CsvError::MissingColumn(_))
+        ));
+    }
+}
@@ -1,11 +1,55 @@
 pub mod csv_parser;
 pub mod duplicate_detector;
 pub mod categorizer;
+pub mod category_suggester;
+pub mod budget_templates;
+pub mod merchant_normalizer;
 pub mod transaction_importer;
 pub mod avalanche_calculator;
 pub mod snowball_calculator;
 pub mod payment_scheduler;
+pub mod amortization;
+pub mod balance_history;
 pub mod interest_calculator;
 pub mod spending_aggregator;
 pub mod trends_calculator;
 pub mod target_tracker;
+pub mod digest_generator;
+pub mod report_renderer;
+pub mod pdf_report;
+pub mod deep_link;
+pub mod qif_writer;
+pub mod template_csv_writer;
+pub mod tray_summary;
+pub mod freeze_tracker;
+pub mod streak_tracker;
+pub mod fx_converter;
+pub mod column_detector;
+pub mod statement_period;
+pub mod cash_flow_projector;
+pub mod mapping_expression;
+pub mod account_validator;
+pub mod alert_evaluator;
+pub mod audit_log;
+pub mod transaction_scheduler;
+pub mod ical_writer;
+pub mod variance_reporter;
+pub mod insight_generator;
+pub mod category_statistics;
+pub mod amount_distribution;
+pub mod job_queue;
+pub mod query_console;
+pub mod bank_sync;
+pub mod mint_importer;
+pub mod ynab_importer;
+pub mod analytics_cache;
+pub mod backup_scheduler;
+pub mod profile_registry;
+pub mod tax_report;
+pub mod receipt_extractor;
+pub mod financial_health;
+pub mod period_locks;
+pub mod folder_watcher;
+pub mod budget_csv;
+pub mod price_csv;
+pub mod portfolio_valuer;
@@ -1,12 +1,15 @@
-use crate::models::debt::Debt;
-use chrono::{Datelike, NaiveDate};
+use crate::constants::DEFAULT_DEBT_DUE_DAY;
+use crate::models::debt::{minimum_payment, Debt};
+use crate::utils::money::{self, Cents};
+use chrono::{Datelike, Months, NaiveDate};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScheduledPayment {
     pub debt_id: i64,
     pub debt_name: String,
-    pub amount: f64,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
     pub due_date: String,
     pub is_minimum: bool,
 }
@@ -14,85 +17,65 @@ pub struct ScheduledPayment {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaymentSchedule {
     pub month: String,         // YYYY-MM format
-    pub total_amount: f64,
+    #[serde(rename = "total_amount", with = "money::dollars")]
+    pub total_amount_cents: Cents,
     pub payments: Vec<ScheduledPayment>,
 }
 
 pub struct PaymentScheduler;
 
 impl PaymentScheduler {
-    /// Generate a payment schedule for the current month based on debts
-    pub fn generate_monthly_schedule(debts: Vec<Debt>) -> Vec<ScheduledPayment> {
-        let today = chrono::Local::now().date_naive();
-        let year = today.year();
-        let month = today.month();
-
-        // Calculate due date (e.g., 15th of the month)
-        let due_day = 15u32;
-        let due_date = NaiveDate::from_ymd_opt(year, month, due_day.min(28))
-            .unwrap_or(today)
-            .format("%Y-%m-%d")
-            .to_string();
+    fn format_date(date: NaiveDate) -> String {
+        date.format("%Y-%m-%d").to_string()
+    }
 
+    /// Generate a payment schedule for the current month based on debts
+    /// Pass `dates::today` so the month follows the user's timezone
+    pub fn generate_monthly_schedule(debts: Vec<Debt>, today: NaiveDate) -> Vec<ScheduledPayment> {
         debts
             .into_iter()
-            .filter(|d| d.balance > 0.0)
+            .filter(|d| d.balance_cents > 0)
             .map(|d| ScheduledPayment {
+                due_date: Self::format_date(Self::due_date_in(today.year(), today.month(), d.due_day).unwrap_or(today)),
                 debt_id: d.id,
+                amount_cents: d.current_min_payment(),
                 debt_name: d.name,
-                amount: d.min_payment,
-                due_date: due_date.clone(),
                 is_minimum: true,
             })
             .collect()
     }
 
-    /// Generate payment schedules for the next N months
-    pub fn generate_future_schedules(debts: Vec<Debt>, months_ahead: u32) -> Vec<PaymentSchedule> {
-        let today = chrono::Local::now().date_naive();
+    /// Generate payment schedules for the next N months, starting with the month `today` is in
+    pub fn generate_future_schedules(debts: Vec<Debt>, today: NaiveDate, months_ahead: u32) -> Vec<PaymentSchedule> {
+        let this_month = today.with_day(1).unwrap_or(today);
         let mut schedules = Vec::new();
 
         for month_offset in 0..months_ahead {
-            let target_date = if month_offset == 0 {
-                today
-            } else {
-                // Add months
-                let new_month = today.month() + month_offset;
-                let year_offset = (new_month - 1) / 12;
-                let month = ((new_month - 1) % 12) + 1;
-                let year = today.year() + year_offset as i32;
-
-                NaiveDate::from_ymd_opt(year, month, 1)
-                    .unwrap_or(today)
-            };
+            let target_date = this_month
+                .checked_add_months(Months::new(month_offset))
+                .unwrap_or(today);
 
             let year = target_date.year();
             let month = target_date.month();
             let month_str = format!("{:04}-{:02}", year, month);
 
-            let due_day = 15u32;
-            let due_date = NaiveDate::from_ymd_opt(year, month, due_day.min(28))
-                .unwrap_or(target_date)
-                .format("%Y-%m-%d")
-                .to_string();
-
             let payments: Vec<ScheduledPayment> = debts
                 .iter()
-                .filter(|d| d.balance > 0.0)
+                .filter(|d| d.balance_cents > 0)
                 .map(|d| ScheduledPayment {
                     debt_id: d.id,
                     debt_name: d.name.clone(),
-                    amount: d.min_payment,
-                    due_date: due_date.clone(),
+                    amount_cents: d.current_min_payment(),
+                    due_date: Self::format_date(Self::due_date_in(year, month, d.due_day).unwrap_or(target_date)),
                     is_minimum: true,
                 })
                 .collect();
 
-            let total_amount: f64 = payments.iter().map(|p| p.amount).sum();
+            let total_amount_cents = payments.iter().map(|p| p.amount_cents).sum();
 
             schedules.push(PaymentSchedule {
                 month: month_str,
-                total_amount,
+                total_amount_cents,
                 payments,
             });
         }
@@ -100,30 +83,94 @@ impl PaymentScheduler {
         schedules
     }
 
-    /// Calculate the next due date for a debt payment
-    pub fn get_next_due_date() -> String {
-        let today = chrono::Local::now().date_naive();
-        let year = today.year();
-        let month = today.month();
-        let day = today.day();
-
-        let due_day = 15u32;
-
-        // If we've passed the due date this month, return next month's due date
-        let (target_year, target_month) = if day > due_day {
-            if month == 12 {
-                (year + 1, 1)
-            } else {
-                (year, month + 1)
+    /// Minimum payments due from `today` (inclusive) through the next `months_ahead`
+    /// months, grouped by calendar month
+    ///
+    /// Each open debt gets one payment per due date, and the last one is cut down to
+    /// what's left so a debt stops appearing once it would be paid off. Percentage
+    /// minimums shrink with the balance. Interest isn't modeled. Debts without a minimum
+    /// payment are left out.
+    pub fn upcoming_payments(debts: &[Debt], today: NaiveDate, months_ahead: u32) -> Vec<PaymentSchedule> {
+        let end = today.checked_add_months(Months::new(months_ahead)).unwrap_or(today);
+
+        let mut payments: Vec<(NaiveDate, ScheduledPayment)> = Vec::new();
+        for debt in debts.iter().filter(|d| d.balance_cents > 0 && d.current_min_payment() > 0) {
+            let floor = debt.min_payment_cents;
+            let mut remaining = debt.balance_cents;
+            let mut due = Self::next_due_date_on(today, debt.due_day);
+            while due < end && remaining > 0 {
+                let amount = minimum_payment(floor, debt.min_payment_percent, remaining);
+                if amount == 0 {
+                    break;
+                }
+                remaining -= amount;
+                payments.push((
+                    due,
+                    ScheduledPayment {
+                        debt_id: debt.id,
+                        debt_name: debt.name.clone(),
+                        amount_cents: amount,
+                        due_date: Self::format_date(due),
+                        is_minimum: true,
+                    },
+                ));
+
+                let Some(next_month) = due.with_day(1).and_then(|d| d.checked_add_months(Months::new(1))) else {
+                    break;
+                };
+                due = Self::next_due_date_on(next_month, debt.due_day);
             }
-        } else {
-            (year, month)
-        };
+        }
+        payments.sort_by(|(a, pa), (b, pb)| a.cmp(b).then_with(|| pa.debt_name.cmp(&pb.debt_name)));
 
-        NaiveDate::from_ymd_opt(target_year, target_month, due_day.min(28))
+        let mut schedules: Vec<PaymentSchedule> = Vec::new();
+        for (due, payment) in payments {
+            let month = due.format("%Y-%m").to_string();
+            match schedules.last_mut() {
+                Some(schedule) if schedule.month == month => {
+                    schedule.total_amount_cents += payment.amount_cents;
+                    schedule.payments.push(payment);
+                }
+                _ => schedules.push(PaymentSchedule {
+                    month,
+                    total_amount_cents: payment.amount_cents,
+                    payments: vec![payment],
+                }),
+            }
+        }
+        schedules
+    }
+
+    /// Calculate the next due date for a debt payment
+    pub fn get_next_due_date(today: NaiveDate) -> String {
+        Self::format_date(Self::next_due_date_from(today))
+    }
+
+    /// Due date in a month for a debt due on `due_day`; days past the month's end fall on its last day
+    pub fn due_date_in(year: i32, month: u32, due_day: u32) -> Option<NaiveDate> {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let last = first.checked_add_months(Months::new(1))?.pred_opt()?;
+        first.with_day(due_day.clamp(1, last.day()))
+    }
+
+    /// Next due date on or after `today` for a debt due on `due_day`
+    pub fn next_due_date_on(today: NaiveDate, due_day: u32) -> NaiveDate {
+        let this_month = Self::due_date_in(today.year(), today.month(), due_day).unwrap_or(today);
+        if this_month >= today {
+            return this_month;
+        }
+
+        // Past this month's due date, so it's next month's
+        today
+            .with_day(1)
+            .and_then(|first| first.checked_add_months(Months::new(1)))
+            .and_then(|next| Self::due_date_in(next.year(), next.month(), due_day))
             .unwrap_or(today)
-            .format("%Y-%m-%d")
-            .to_string()
+    }
+
+    /// Next due date on or after `today` for a debt on the default due day
+    pub fn next_due_date_from(today: NaiveDate) -> NaiveDate {
+        Self::next_due_date_on(today, DEFAULT_DEBT_DUE_DAY)
     }
 }
 
@@ -137,33 +184,47 @@ mod tests {
             Debt {
                 id: 1,
                 name: "Credit Card A".to_string(),
-                balance: 1000.0,
-                original_balance: 1000.0,
+                balance_cents: 100_000,
+                original_balance_cents: 100_000,
                 interest_rate: 18.0,
-                min_payment: 50.0,
+                min_payment_cents: 5000,
+                min_payment_percent: None,
+                kind: "revolving".to_string(),
+                term_months: None,
+                loan_start_date: None,
+                due_day: 15,
+                account_id: None,
                 created_at: "2025-01-01".to_string(),
                 updated_at: "2025-01-01".to_string(),
             },
             Debt {
                 id: 2,
                 name: "Credit Card B".to_string(),
-                balance: 2000.0,
-                original_balance: 2000.0,
+                balance_cents: 200_000,
+                original_balance_cents: 200_000,
                 interest_rate: 15.0,
-                min_payment: 75.0,
+                min_payment_cents: 7500,
+                min_payment_percent: None,
+                kind: "revolving".to_string(),
+                term_months: None,
+                loan_start_date: None,
+                due_day: 15,
+                account_id: None,
                 created_at: "2025-01-01".to_string(),
                 updated_at: "2025-01-01".to_string(),
             },
         ];
 
-        let schedule = PaymentScheduler::generate_monthly_schedule(debts);
+        let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let schedule = PaymentScheduler::generate_monthly_schedule(debts, today);
 
         assert_eq!(schedule.len(), 2);
         assert_eq!(schedule[0].debt_id, 1);
-        assert_eq!(schedule[0].amount, 50.0);
+        assert_eq!(schedule[0].amount_cents, 5000);
         assert!(schedule[0].is_minimum);
         assert_eq!(schedule[1].debt_id, 2);
-        assert_eq!(schedule[1].amount, 75.0);
+        assert_eq!(schedule[1].amount_cents, 7500);
+        assert_eq!(schedule[0].due_date, "2025-03-15");
     }
 
     #[test]
@@ -171,29 +232,46 @@ mod tests {
         let debts = vec![Debt {
             id: 1,
             name: "Card".to_string(),
-            balance: 1000.0,
-            original_balance: 1000.0,
+            balance_cents: 100_000,
+            original_balance_cents: 100_000,
             interest_rate: 18.0,
-            min_payment: 50.0,
+            min_payment_cents: 5000,
+            min_payment_percent: None,
+            kind: "revolving".to_string(),
+            term_months: None,
+            loan_start_date: None,
+            due_day: 15,
+            account_id: None,
             created_at: "2025-01-01".to_string(),
             updated_at: "2025-01-01".to_string(),
         }];
 
-        let schedules = PaymentScheduler::generate_future_schedules(debts, 3);
+        let today = NaiveDate::from_ymd_opt(2025, 11, 20).unwrap();
+        let schedules = PaymentScheduler::generate_future_schedules(debts, today, 3);
 
         assert_eq!(schedules.len(), 3);
         assert_eq!(schedules[0].payments.len(), 1);
-        assert_eq!(schedules[0].total_amount, 50.0);
+        assert_eq!(schedules[0].total_amount_cents, 5000);
+        assert_eq!(schedules[2].month, "2026-01");
     }
 
     #[test]
     fn test_next_due_date_format() {
-        let due_date = PaymentScheduler::get_next_due_date();
+        let due_date = PaymentScheduler::get_next_due_date(NaiveDate::from_ymd_opt(2025, 3, 20).unwrap());
 
         // Should be in YYYY-MM-DD format
         assert_eq!(due_date.len(), 10);
         assert_eq!(&due_date[4..5], "-");
         assert_eq!(&due_date[7..8], "-");
+        assert_eq!(due_date, "2025-04-15");
+    }
+
+    #[test]
+    fn test_next_due_date_from() {
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert_eq!(PaymentScheduler::next_due_date_from(date("2025-03-10")), date("2025-03-15"));
+        assert_eq!(PaymentScheduler::next_due_date_from(date("2025-03-15")), date("2025-03-15"));
+        assert_eq!(PaymentScheduler::next_due_date_from(date("2025-12-20")), date("2026-01-15"));
     }
 
     #[test]
@@ -202,29 +280,120 @@ mod tests {
             Debt {
                 id: 1,
                 name: "Active Card".to_string(),
-                balance: 1000.0,
-                original_balance: 1000.0,
+                balance_cents: 100_000,
+                original_balance_cents: 100_000,
                 interest_rate: 18.0,
-                min_payment: 50.0,
+                min_payment_cents: 5000,
+                min_payment_percent: None,
+                kind: "revolving".to_string(),
+                term_months: None,
+                loan_start_date: None,
+                due_day: 15,
+                account_id: None,
                 created_at: "2025-01-01".to_string(),
                 updated_at: "2025-01-01".to_string(),
             },
             Debt {
                 id: 2,
                 name: "Paid Off Card".to_string(),
-                balance: 0.0,
-                original_balance: 1000.0,
+                balance_cents: 0,
+                original_balance_cents: 100_000,
                 interest_rate: 15.0,
-                min_payment: 0.0,
+                min_payment_cents: 0,
+                min_payment_percent: None,
+                kind: "revolving".to_string(),
+                term_months: None,
+                loan_start_date: None,
+                due_day: 15,
+                account_id: None,
                 created_at: "2025-01-01".to_string(),
                 updated_at: "2025-01-01".to_string(),
             },
         ];
 
-        let schedule = PaymentScheduler::generate_monthly_schedule(debts);
+        let schedule = PaymentScheduler::generate_monthly_schedule(debts, NaiveDate::from_ymd_opt(2025, 3, 10).unwrap());
 
         // Should only include the debt with positive balance
         assert_eq!(schedule.len(), 1);
         assert_eq!(schedule[0].debt_id, 1);
     }
+
+    #[test]
+    fn test_due_date_in_clamps_to_month_end() {
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert_eq!(PaymentScheduler::due_date_in(2025, 3, 31), Some(date("2025-03-31")));
+        assert_eq!(PaymentScheduler::due_date_in(2025, 2, 31), Some(date("2025-02-28")));
+        assert_eq!(PaymentScheduler::due_date_in(2024, 2, 30), Some(date("2024-02-29")));
+        assert_eq!(PaymentScheduler::due_date_in(2025, 4, 1), Some(date("2025-04-01")));
+    }
+
+    #[test]
+    fn test_next_due_date_on() {
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert_eq!(PaymentScheduler::next_due_date_on(date("2025-03-10"), 3), date("2025-04-03"));
+        assert_eq!(PaymentScheduler::next_due_date_on(date("2025-01-31"), 31), date("2025-01-31"));
+        assert_eq!(PaymentScheduler::next_due_date_on(date("2025-02-01"), 31), date("2025-02-28"));
+    }
+
+    #[test]
+    fn test_upcoming_payments_use_each_debts_due_day() {
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let debt = |id: i64, name: &str, balance_cents: Cents, due_day: u32| Debt {
+            id,
+            name: name.to_string(),
+            balance_cents,
+            original_balance_cents: balance_cents,
+            interest_rate: 10.0,
+            min_payment_cents: 10_000,
+            min_payment_percent: None,
+            kind: "revolving".to_string(),
+            term_months: None,
+            loan_start_date: None,
+            due_day,
+            account_id: None,
+            created_at: "2025-01-01".to_string(),
+            updated_at: "2025-01-01".to_string(),
+        };
+        let debts = vec![debt(1, "Loan", 500_000, 5), debt(2, "Card", 15_000, 20)];
+
+        let schedules = PaymentScheduler::upcoming_payments(&debts, date("2025-03-10"), 3);
+
+        // The loan's March 5th payment has passed; the card is paid off after two payments
+        let months: Vec<&str> = schedules.iter().map(|s| s.month.as_str()).collect();
+        assert_eq!(months, vec!["2025-03", "2025-04", "2025-05", "2025-06"]);
+        assert_eq!(schedules[0].payments.len(), 1);
+        assert_eq!(schedules[0].payments[0].due_date, "2025-03-20");
+        assert_eq!(schedules[1].payments[0].due_date, "2025-04-05");
+        assert_eq!(schedules[1].payments[1].amount_cents, 5000);
+        assert_eq!(schedules[1].total_amount_cents, 15_000);
+        assert_eq!(schedules[3].payments[0].due_date, "2025-06-05");
+        let loan_payments = schedules.iter().flat_map(|s| &s.payments).filter(|p| p.debt_id == 1).count();
+        assert_eq!(loan_payments, 3);
+    }
+
+    #[test]
+    fn test_upcoming_percentage_minimums_shrink_with_the_balance() {
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let debts = vec![Debt {
+            id: 1,
+            name: "Card".to_string(),
+            balance_cents: 500_000,
+            original_balance_cents: 500_000,
+            interest_rate: 20.0,
+            min_payment_cents: 2500,
+            min_payment_percent: Some(2.0),
+            kind: "revolving".to_string(),
+            term_months: None,
+            loan_start_date: None,
+            due_day: 15,
+            account_id: None,
+            created_at: "2025-01-01".to_string(),
+            updated_at: "2025-01-01".to_string(),
+        }];
+
+        let schedules = PaymentScheduler::upcoming_payments(&debts, date("2025-03-01"), 3);
+
+        let amounts: Vec<Cents> = schedules.iter().map(|s| s.payments[0].amount_cents).collect();
+        assert_eq!(amounts, vec![10_000, 9800, 9604]);
+    }
 }
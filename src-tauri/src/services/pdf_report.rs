@@ -0,0 +1,424 @@
+use crate::services::spending_aggregator::SpendingByCategory;
+use crate::services::tax_report::TaxReport;
+use crate::services::target_tracker::TargetsProgress;
+use crate::services::trends_calculator::SpendingTrends;
+use crate::utils::money::{self, Cents};
+use printpdf::{
+    BuiltinFont, Color, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference,
+    PdfLayerReference, Point, Rect, Rgb,
+};
+
+// A4 portrait, in millimetres
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const CONTENT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+
+const TITLE_SIZE: f32 = 18.0;
+const HEADING_SIZE: f32 = 13.0;
+const BODY_SIZE: f32 = 10.0;
+const ROW_HEIGHT: f32 = 6.0;
+
+// Bar charts: label column, then bars scaled to the largest value
+const CHART_LABEL_WIDTH: f32 = 45.0;
+const CHART_VALUE_WIDTH: f32 = 25.0;
+const CHART_BAR_HEIGHT: f32 = 4.0;
+
+/// Everything that goes into an exported analytics report
+pub struct AnalyticsReportData {
+    pub start_date: String,
+    pub end_date: String,
+    pub spending: SpendingByCategory,
+    pub trends: SpendingTrends,
+    pub targets: TargetsProgress,
+}
+
+/// Builtin PDF fonts only cover Latin-1; replace anything else so text never renders as garbage
+fn pdf_text(text: &str) -> String {
+    text.chars()
+        .map(|c| if (' '..='~').contains(&c) || ('\u{a0}'..='\u{ff}').contains(&c) { c } else { '?' })
+        .collect()
+}
+
+fn money(amount: Cents) -> String {
+    format!("${:.2}", money::from_cents(amount))
+}
+
+fn rgb(r: f32, g: f32, b: f32) -> Color {
+    Color::Rgb(Rgb::new(r, g, b, None))
+}
+
+/// Writes top-to-bottom onto A4 pages, starting a new page when the cursor runs out of room
+struct PdfWriter {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    font: IndirectFontRef,
+    bold: IndirectFontRef,
+    y: f32, // Cursor, measured from the bottom of the page
+}
+
+impl PdfWriter {
+    fn new(title: &str) -> Result<Self, String> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Content");
+        let font = doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(|e| format!("Failed to load PDF font: {}", e))?;
+        let bold = doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(|e| format!("Failed to load PDF font: {}", e))?;
+        let layer = doc.get_page(page).get_layer(layer);
+
+        Ok(Self {
+            doc,
+            layer,
+            font,
+            bold,
+            y: PAGE_HEIGHT - MARGIN,
+        })
+    }
+
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Content");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn text_at(&self, text: &str, size: f32, x: f32, bold: bool) {
+        let font = if bold { &self.bold } else { &self.font };
+        self.layer.use_text(pdf_text(text), size, Mm(x), Mm(self.y), font);
+    }
+
+    fn title(&mut self, text: &str) {
+        self.ensure_space(TITLE_SIZE);
+        self.y -= 7.0;
+        self.text_at(text, TITLE_SIZE, MARGIN, true);
+        self.y -= 6.0;
+    }
+
+    fn heading(&mut self, text: &str) {
+        // Keep a heading together with at least a couple of rows
+        self.ensure_space(HEADING_SIZE + 3.0 * ROW_HEIGHT);
+        self.y -= 8.0;
+        self.text_at(text, HEADING_SIZE, MARGIN, true);
+        self.y -= 3.0;
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        self.ensure_space(ROW_HEIGHT);
+        self.y -= ROW_HEIGHT;
+        self.text_at(text, BODY_SIZE, MARGIN, false);
+    }
+
+    fn rule(&self) {
+        self.layer.set_outline_color(rgb(0.75, 0.75, 0.75));
+        self.layer.set_outline_thickness(0.5);
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(MARGIN), Mm(self.y - 1.5)), false),
+                (Point::new(Mm(PAGE_WIDTH - MARGIN), Mm(self.y - 1.5)), false),
+            ],
+            is_closed: false,
+        });
+    }
+
+    /// `widths` are fractions of the content width; the header repeats after a page break
+    fn table(&mut self, headers: &[&str], widths: &[f32], rows: &[Vec<String>]) {
+        let columns: Vec<f32> = widths
+            .iter()
+            .scan(MARGIN, |x, width| {
+                let start = *x;
+                *x += width * CONTENT_WIDTH;
+                Some(start)
+            })
+            .collect();
+
+        let draw_header = |writer: &mut Self| {
+            writer.y -= ROW_HEIGHT;
+            for (header, x) in headers.iter().zip(&columns) {
+                writer.text_at(header, BODY_SIZE, *x, true);
+            }
+            writer.rule();
+        };
+
+        self.ensure_space(2.0 * ROW_HEIGHT);
+        draw_header(self);
+
+        for row in rows {
+            if self.y - ROW_HEIGHT < MARGIN {
+                self.ensure_space(2.0 * ROW_HEIGHT);
+                draw_header(self);
+            }
+            self.y -= ROW_HEIGHT;
+            for (cell, x) in row.iter().zip(&columns) {
+                self.text_at(cell, BODY_SIZE, *x, false);
+            }
+        }
+    }
+
+    fn bar_chart(&mut self, items: &[(String, Cents)]) {
+        let max = items.iter().map(|(_, value)| *value).max().unwrap_or(0);
+        if max <= 0 {
+            return;
+        }
+        let bar_space = CONTENT_WIDTH - CHART_LABEL_WIDTH - CHART_VALUE_WIDTH;
+
+        self.y -= 2.0;
+        for (label, value) in items {
+            self.ensure_space(ROW_HEIGHT);
+            self.y -= ROW_HEIGHT;
+            self.text_at(label, BODY_SIZE - 1.0, MARGIN, false);
+
+            let bar_start = MARGIN + CHART_LABEL_WIDTH;
+            let bar_width = ((*value as f64 / max as f64) as f32 * bar_space).max(0.5);
+            self.layer.set_fill_color(rgb(0.23, 0.51, 0.96));
+            self.layer.add_rect(Rect::new(
+                Mm(bar_start),
+                Mm(self.y - 0.5),
+                Mm(bar_start + bar_width),
+                Mm(self.y - 0.5 + CHART_BAR_HEIGHT),
+            ));
+            self.layer.set_fill_color(rgb(0.0, 0.0, 0.0));
+            self.text_at(&money(*value), BODY_SIZE - 1.0, bar_start + bar_width + 2.0, false);
+        }
+    }
+
+    fn finish(self) -> Result<Vec<u8>, String> {
+        self.doc
+            .save_to_bytes()
+            .map_err(|e| format!("Failed to generate PDF: {}", e))
+    }
+}
+
+pub struct PdfReport;
+
+impl PdfReport {
+    /// Render the analytics report: category spending, spending trend and budget targets,
+    /// with bar charts of the data when `include_charts` is set
+    pub fn render(data: &AnalyticsReportData, include_charts: bool) -> Result<Vec<u8>, String> {
+        let mut writer = PdfWriter::new("Budget Balancer Analytics Report")?;
+
+        writer.title("Budget Balancer Analytics Report");
+        writer.paragraph(&format!("Period: {} to {}", data.start_date, data.end_date));
+        writer.paragraph(&format!("Total spending: {}", money(data.spending.total_spending_cents)));
+
+        writer.heading("Spending by Category");
+        if data.spending.categories.is_empty() {
+            writer.paragraph("No spending in this period.");
+        } else {
+            if include_charts {
+                let bars: Vec<(String, Cents)> = data
+                    .spending
+                    .categories
+                    .iter()
+                    .map(|c| (c.category_name.clone(), c.amount_cents))
+                    .collect();
+                writer.bar_chart(&bars);
+                writer.y -= 2.0;
+            }
+            let rows: Vec<Vec<String>> = data
+                .spending
+                .categories
+                .iter()
+                .map(|c| {
+                    vec![
+                        c.category_name.clone(),
+                        money(c.amount_cents),
+                        format!("{:.1}%", c.percentage),
+                        c.transaction_count.to_string(),
+                    ]
+                })
+                .collect();
+            writer.table(&["Category", "Amount", "Share", "Transactions"], &[0.4, 0.2, 0.2, 0.2], &rows);
+        }
+
+        writer.heading("Spending Trend");
+        if data.trends.data_points.is_empty() {
+            writer.paragraph("No spending in this period.");
+        } else {
+            if include_charts {
+                let bars: Vec<(String, Cents)> = data
+                    .trends
+                    .data_points
+                    .iter()
+                    .map(|p| (p.date.clone(), p.amount_cents))
+                    .collect();
+                writer.bar_chart(&bars);
+                writer.y -= 2.0;
+            }
+            let rows: Vec<Vec<String>> = data
+                .trends
+                .data_points
+                .iter()
+                .map(|p| vec![p.date.clone(), money(p.amount_cents), p.transaction_count.to_string()])
+                .collect();
+            writer.table(&["Period", "Amount", "Transactions"], &[0.4, 0.3, 0.3], &rows);
+            writer.paragraph(&format!(
+                "Average per period: {}",
+                money(money::to_cents(data.trends.average_per_interval))
+            ));
+        }
+
+        writer.heading("Spending Targets");
+        if data.targets.targets.is_empty() {
+            writer.paragraph("No spending targets set.");
+        } else {
+            let rows: Vec<Vec<String>> = data
+                .targets
+                .targets
+                .iter()
+                .map(|t| {
+                    vec![
+                        t.category_name.clone(),
+                        money(t.actual_amount_cents),
+                        money(t.target_amount_cents),
+                        format!("{:.0}%", t.percentage_used),
+                        t.status.replace('_', " "),
+                    ]
+                })
+                .collect();
+            writer.table(
+                &["Category", "Spent", "Target", "Used", "Status"],
+                &[0.32, 0.17, 0.17, 0.14, 0.2],
+                &rows,
+            );
+            writer.paragraph(&format!("Overall: {}", data.targets.overall_status.replace('_', " ")));
+        }
+
+        writer.finish()
+    }
+
+    /// Render the year-end tax report: a summary by category, then each category's transactions
+    pub fn render_tax_report(report: &TaxReport) -> Result<Vec<u8>, String> {
+        let title = format!("Tax Report {}", report.year);
+        let mut writer = PdfWriter::new(&title)?;
+
+        writer.title(&title);
+        writer.paragraph(&format!(
+            "Tax-deductible transactions: {}, totalling {}",
+            report.transaction_count,
+            money(report.total_cents)
+        ));
+
+        if report.categories.is_empty() {
+            writer.paragraph("No tax-deductible transactions this year.");
+            return writer.finish();
+        }
+
+        writer.heading("Summary");
+        let rows: Vec<Vec<String>> = report
+            .categories
+            .iter()
+            .map(|c| vec![c.category_name.clone(), c.transactions.len().to_string(), money(c.total_cents)])
+            .collect();
+        writer.table(&["Category", "Transactions", "Total"], &[0.5, 0.25, 0.25], &rows);
+
+        for category in &report.categories {
+            writer.heading(&format!("{} ({})", category.category_name, money(category.total_cents)));
+            let rows: Vec<Vec<String>> = category
+                .transactions
+                .iter()
+                .map(|t| vec![t.date.clone(), t.description.clone(), t.account_name.clone(), money(t.amount_cents)])
+                .collect();
+            writer.table(&["Date", "Description", "Account", "Amount"], &[0.18, 0.47, 0.2, 0.15], &rows);
+        }
+
+        writer.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::spending_aggregator::{CategorySpending, DatePeriod};
+    use crate::services::target_tracker::{DatePeriod as TargetPeriod, TargetProgress};
+    use crate::services::trends_calculator::TrendPoint;
+
+    fn data(categories: usize) -> AnalyticsReportData {
+        AnalyticsReportData {
+            start_date: "2025-01-01".to_string(),
+            end_date: "2025-03-31".to_string(),
+            spending: SpendingByCategory {
+                period: DatePeriod { start_date: "2025-01-01".to_string(), end_date: "2025-03-31".to_string() },
+                categories: (0..categories)
+                    .map(|i| CategorySpending {
+                        category_id: i as i64,
+                        category_name: format!("Category {}", i),
+                        category_icon: None,
+                        amount_cents: 10_000 + i as Cents * 100,
+                        percentage: 1.0,
+                        transaction_count: 2,
+                    })
+                    .collect(),
+                total_spending_cents: 100_000,
+            },
+            trends: SpendingTrends {
+                data_points: vec![TrendPoint { date: "2025-01".to_string(), amount_cents: 40_000, transaction_count: 8 }],
+                total_spending_cents: 40_000,
+                average_per_interval: 400.0,
+            },
+            targets: TargetsProgress {
+                period: TargetPeriod { start_date: "2025-01-01".to_string(), end_date: "2025-03-31".to_string() },
+                targets: vec![TargetProgress {
+                    target_id: 1,
+                    scope: "category".to_string(),
+                    category_id: Some(2),
+                    account_id: None,
+                    category_name: "Dining".to_string(),
+                    target_amount_cents: 10_000,
+                    actual_amount_cents: 8000,
+                    remaining_cents: 2000,
+                    percentage_used: 80.0,
+                    status: "on_track".to_string(),
+                    variance_cents: -2000,
+                }],
+                overall_status: "on_track".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_render_produces_pdf() {
+        let bytes = PdfReport::render(&data(3), true).unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_long_tables_span_pages() {
+        let short = PdfReport::render(&data(3), false).unwrap();
+        let long = PdfReport::render(&data(120), false).unwrap();
+        let count_pages = |bytes: &[u8]| printpdf::lopdf::Document::load_mem(bytes).unwrap().get_pages().len();
+        assert_eq!(count_pages(&short), 1);
+        assert!(count_pages(&long) > 1);
+    }
+
+    #[test]
+    fn test_render_tax_report_produces_pdf() {
+        use crate::services::tax_report::{TaxReportCategory, TaxReportTransaction};
+        let report = TaxReport {
+            year: 2024,
+            categories: vec![TaxReportCategory {
+                category_id: 1,
+                category_name: "Charity".to_string(),
+                total_cents: 4000,
+                transactions: vec![TaxReportTransaction {
+                    id: 1,
+                    date: "2024-12-01".to_string(),
+                    description: "Food bank".to_string(),
+                    merchant: None,
+                    account_name: "Checking".to_string(),
+                    amount_cents: 4000,
+                }],
+            }],
+            total_cents: 4000,
+            transaction_count: 1,
+        };
+        assert!(PdfReport::render_tax_report(&report).unwrap().starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_pdf_text_replaces_unsupported_characters() {
+        assert_eq!(pdf_text("Café 🍕"), "Café ?");
+    }
+}
@@ -0,0 +1,47 @@
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+/// The locked months, loaded once so a batch of dates can be checked without a query each
+#[derive(Debug, Clone, Default)]
+pub struct PeriodLocks {
+    months: HashSet<String>, // YYYY-MM
+}
+
+impl PeriodLocks {
+    pub async fn load(db: &SqlitePool) -> Result<Self, sqlx::Error> {
+        let months: Vec<(String,)> = sqlx::query_as("SELECT month FROM period_locks").fetch_all(db).await?;
+        Ok(Self { months: months.into_iter().map(|(month,)| month).collect() })
+    }
+
+    /// The YYYY-MM month of a YYYY-MM-DD date
+    pub fn month_of(date: &str) -> &str {
+        date.get(..7).unwrap_or(date)
+    }
+
+    pub fn is_locked(&self, date: &str) -> bool {
+        self.months.contains(Self::month_of(date))
+    }
+
+    /// The month of the first date that falls in a locked month
+    pub fn first_locked<'a>(&self, dates: impl IntoIterator<Item = &'a str>) -> Option<String> {
+        dates
+            .into_iter()
+            .find(|date| self.is_locked(date))
+            .map(|date| Self::month_of(date).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_locked() {
+        let locks = PeriodLocks { months: ["2024-03".to_string()].into_iter().collect() };
+        assert!(locks.is_locked("2024-03-31"));
+        assert!(!locks.is_locked("2024-04-01"));
+        assert_eq!(locks.first_locked(["2024-02-28", "2024-03-01"]), Some("2024-03".to_string()));
+        assert_eq!(locks.first_locked(["2024-02-28"]), None);
+        assert_eq!(PeriodLocks::default().first_locked(["2024-03-01"]), None);
+    }
+}
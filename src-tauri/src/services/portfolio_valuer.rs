@@ -0,0 +1,69 @@
+// Values the securities held in each account from the holding records and prices on file
+//
+// A holding record and a price both stay in effect until the next one for the same symbol,
+// so a position can be valued on any date, not just the days it was recorded or priced
+
+use crate::models::investment::HoldingValue;
+use crate::utils::money::{self, Cents};
+use sqlx::SqlitePool;
+
+pub struct PortfolioValuer;
+
+impl PortfolioValuer {
+    /// Positions held on `date`, optionally in one account, valued at the latest price on or before it
+    /// Sold positions (quantity 0) are left out; a position with no price yet has no value
+    pub async fn holdings_on(
+        db: &SqlitePool,
+        date: &str,
+        account_id: Option<i64>,
+    ) -> Result<Vec<HoldingValue>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, (i64, i64, String, String, f64, Option<Cents>, String, Option<f64>, Option<String>)>(
+            "SELECT h.id, h.account_id, a.name, h.symbol, h.quantity, h.cost_basis_cents, h.date, p.price, p.date
+             FROM holdings h
+             JOIN accounts a ON a.id = h.account_id
+             LEFT JOIN security_prices p ON p.id = (
+                 SELECT id FROM security_prices
+                 WHERE symbol = h.symbol AND date <= ?1
+                 ORDER BY date DESC LIMIT 1
+             )
+             WHERE h.date = (
+                 SELECT MAX(date) FROM holdings
+                 WHERE account_id = h.account_id AND symbol = h.symbol AND date <= ?1
+             )
+               AND h.quantity > 0
+               AND (?2 IS NULL OR h.account_id = ?2)
+             ORDER BY a.name, h.symbol"
+        )
+        .bind(date)
+        .bind(account_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(holding_id, account_id, account_name, symbol, quantity, cost_basis_cents, held_since, price, price_date)| {
+                let value_cents = price.map(|price| money::to_cents(quantity * price));
+                let gain_cents = value_cents.zip(cost_basis_cents).map(|(value, cost)| value - cost);
+                HoldingValue {
+                    holding_id,
+                    account_id,
+                    account_name,
+                    symbol,
+                    quantity,
+                    cost_basis_cents,
+                    held_since,
+                    price,
+                    price_date,
+                    value_cents,
+                    gain_cents,
+                }
+            })
+            .collect())
+    }
+
+    /// Value of everything held on `date`, across all accounts
+    pub async fn total_value(db: &SqlitePool, date: &str) -> Result<Cents, sqlx::Error> {
+        let holdings = Self::holdings_on(db, date, None).await?;
+        Ok(holdings.iter().filter_map(|holding| holding.value_cents).sum())
+    }
+}
@@ -0,0 +1,70 @@
+use super::csv_parser::CsvParser;
+use csv::{ReaderBuilder, Trim};
+
+/// Header names accepted for each column, compared ignoring case
+const SYMBOL_HEADERS: &[&str] = &["symbol", "ticker"];
+const DATE_HEADERS: &[&str] = &["date", "as of"];
+const PRICE_HEADERS: &[&str] = &["price", "close", "close price", "last price"];
+
+/// One data line of a security price file, as written; values are checked by the importer
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceCsvLine {
+    pub line: usize, // 1-based line in the file, counting the header
+    pub symbol: String,
+    pub date: String,
+    pub price: String,
+}
+
+pub struct PriceCsv;
+
+impl PriceCsv {
+    /// Read the symbol, date and price of each line; blank lines are skipped
+    /// The columns are found by header name, so brokerage and quote downloads can be used as they are
+    pub fn parse(content: &str) -> Result<Vec<PriceCsvLine>, String> {
+        let content = content.trim_start_matches('\u{feff}');
+        let mut reader = ReaderBuilder::new().trim(Trim::All).flexible(true).from_reader(content.as_bytes());
+
+        let headers = reader.headers().map_err(|e| format!("Invalid CSV: {}", e))?.clone();
+        let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.to_lowercase().as_str()));
+        let symbol = column(SYMBOL_HEADERS).ok_or("The file needs a Symbol column")?;
+        let date = column(DATE_HEADERS).ok_or("The file needs a Date column")?;
+        let price = column(PRICE_HEADERS).ok_or("The file needs a Price column")?;
+
+        let mut lines = Vec::new();
+        for (index, record) in reader.records().enumerate() {
+            let record = record.map_err(|e| format!("Invalid CSV: {}", e))?;
+            let line = CsvParser::record_line(content, &record).unwrap_or(index + 2);
+            if record.iter().all(str::is_empty) {
+                continue;
+            }
+            let field = |i: usize| record.get(i).unwrap_or("").to_string();
+            lines.push(PriceCsvLine { line, symbol: field(symbol), date: field(date), price: field(price) });
+        }
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_finds_columns_by_name() {
+        let lines = PriceCsv::parse("Date,Open,Close,Ticker\n2024-03-01,10,$10.50,vti\n\n03/04/2024,11,11.25,VTI").unwrap();
+        assert_eq!(lines.len(), 2, "Blank lines are skipped");
+        assert_eq!(lines[0], PriceCsvLine {
+            line: 2,
+            symbol: "vti".to_string(),
+            date: "2024-03-01".to_string(),
+            price: "$10.50".to_string(),
+        });
+        assert_eq!(lines[1].line, 4);
+    }
+
+    #[test]
+    fn test_parse_needs_every_column() {
+        assert!(PriceCsv::parse("Symbol,Date\nVTI,2024-03-01").is_err());
+        assert!(PriceCsv::parse("Symbol,Price\nVTI,10").is_err());
+        assert!(PriceCsv::parse("Date,Price\n2024-03-01,10").is_err());
+    }
+}
@@ -0,0 +1,267 @@
+// Which profiles exist, where their databases are, and which one is open
+//
+// The registry is a small JSON file in the app data folder. The default profile keeps the
+// database file from before profiles existed, so upgrading doesn't move anyone's data;
+// every other profile gets a file named after it in the profiles folder
+
+use crate::constants::{
+    DEFAULT_DATABASE_FILE, DEFAULT_PROFILE_NAME, MAX_PROFILE_NAME_LENGTH, PROFILE_DATABASE_DIR,
+    PROFILE_REGISTRY_FILE,
+};
+use crate::errors::ProfileError;
+use crate::models::profile::{Profile, ProfileList};
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// The profile registry, held in managed state
+pub struct ProfileRegistry {
+    dir: PathBuf,
+    list: Mutex<ProfileList>,
+}
+
+fn io_error(e: impl std::fmt::Display) -> ProfileError {
+    ProfileError::Io(e.to_string())
+}
+
+fn now() -> String {
+    Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+impl ProfileRegistry {
+    /// Load the registry in `dir`, creating it with the default profile on first run
+    pub fn load(dir: &Path) -> Result<Self, ProfileError> {
+        let list = match std::fs::read_to_string(dir.join(PROFILE_REGISTRY_FILE)) {
+            Ok(content) => {
+                let list: ProfileList = serde_json::from_str(&content)
+                    .map_err(|e| io_error(format!("Invalid profile list: {}", e)))?;
+                if Self::find(&list, &list.active).is_none() {
+                    return Err(io_error(format!("Active profile '{}' isn't in the profile list", list.active)));
+                }
+                list
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let list = ProfileList {
+                    active: DEFAULT_PROFILE_NAME.to_string(),
+                    profiles: vec![Profile {
+                        name: DEFAULT_PROFILE_NAME.to_string(),
+                        file_name: DEFAULT_DATABASE_FILE.to_string(),
+                        created_at: now(),
+                    }],
+                };
+                Self::save(dir, &list)?;
+                list
+            }
+            Err(e) => return Err(io_error(e)),
+        };
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            list: Mutex::new(list),
+        })
+    }
+
+    // Written to a temporary file first so a crash can't leave a half-written registry
+    fn save(dir: &Path, list: &ProfileList) -> Result<(), ProfileError> {
+        std::fs::create_dir_all(dir).map_err(io_error)?;
+        let content = serde_json::to_string_pretty(list).map_err(io_error)?;
+        let temp = dir.join(format!("{}.tmp", PROFILE_REGISTRY_FILE));
+        std::fs::write(&temp, content).map_err(io_error)?;
+        std::fs::rename(&temp, dir.join(PROFILE_REGISTRY_FILE)).map_err(io_error)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ProfileList> {
+        match self.list.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                tracing::warn!("Profile registry mutex was poisoned, recovering");
+                poisoned.into_inner()
+            }
+        }
+    }
+
+    // Profile names are matched ignoring case, so "personal" opens "Personal"
+    fn find<'a>(list: &'a ProfileList, name: &str) -> Option<&'a Profile> {
+        let name = name.trim().to_lowercase();
+        list.profiles.iter().find(|p| p.name.to_lowercase() == name)
+    }
+
+    fn validate_name(list: &ProfileList, name: &str) -> Result<String, ProfileError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ProfileError::EmptyName);
+        }
+        if name.chars().count() > MAX_PROFILE_NAME_LENGTH {
+            return Err(ProfileError::NameTooLong { max: MAX_PROFILE_NAME_LENGTH });
+        }
+        if let Some(existing) = Self::find(list, name) {
+            return Err(ProfileError::AlreadyExists(existing.name.clone()));
+        }
+        Ok(name.to_string())
+    }
+
+    /// File-name-safe form of a profile name, e.g. "Small Business" -> "small-business"
+    fn slug(name: &str) -> String {
+        let mut slug = String::new();
+        for c in name.chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        let slug = slug.trim_end_matches('-');
+        if slug.is_empty() { "profile".to_string() } else { slug.to_string() }
+    }
+
+    /// Every profile and which one is open
+    pub fn list(&self) -> ProfileList {
+        self.lock().clone()
+    }
+
+    /// Name of the open profile
+    pub fn active(&self) -> String {
+        self.lock().active.clone()
+    }
+
+    /// Database file of `profile`
+    pub fn file_path(&self, profile: &Profile) -> PathBuf {
+        self.dir.join(&profile.file_name)
+    }
+
+    /// Database file of the named profile
+    pub fn database_path(&self, name: &str) -> Result<PathBuf, ProfileError> {
+        Self::find(&self.lock(), name)
+            .map(|profile| self.file_path(profile))
+            .ok_or_else(|| ProfileError::NotFound(name.trim().to_string()))
+    }
+
+    /// Database file of the open profile
+    pub fn active_database_path(&self) -> Result<PathBuf, ProfileError> {
+        self.database_path(&self.active())
+    }
+
+    /// A new profile named `name` with a database file no other profile uses
+    /// Nothing is saved until it's passed to `add`, so its database can be set up first
+    pub fn new_profile(&self, name: &str) -> Result<Profile, ProfileError> {
+        let list = self.lock();
+        let name = Self::validate_name(&list, name)?;
+        let slug = Self::slug(&name);
+        let mut file_name = format!("{}/{}.db", PROFILE_DATABASE_DIR, slug);
+        let mut suffix = 2;
+        // A leftover file from a profile removed by hand is never reused
+        while list.profiles.iter().any(|p| p.file_name == file_name) || self.dir.join(&file_name).exists() {
+            file_name = format!("{}/{}-{}.db", PROFILE_DATABASE_DIR, slug, suffix);
+            suffix += 1;
+        }
+        Ok(Profile {
+            name,
+            file_name,
+            created_at: now(),
+        })
+    }
+
+    /// Save a profile made by `new_profile`
+    pub fn add(&self, profile: Profile) -> Result<(), ProfileError> {
+        let mut list = self.lock();
+        Self::validate_name(&list, &profile.name)?;
+        list.profiles.push(profile);
+        if let Err(e) = Self::save(&self.dir, &list) {
+            list.profiles.pop();
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Make the named profile the one opened from now on, including at the next start
+    pub fn set_active(&self, name: &str) -> Result<(), ProfileError> {
+        let mut list = self.lock();
+        let name = Self::find(&list, name)
+            .map(|profile| profile.name.clone())
+            .ok_or_else(|| ProfileError::NotFound(name.trim().to_string()))?;
+        let previous = std::mem::replace(&mut list.active, name);
+        if let Err(e) = Self::save(&self.dir, &list) {
+            list.active = previous;
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("profile-registry-{}-{}", name, std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        dir
+    }
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(ProfileRegistry::slug("Small Business"), "small-business");
+        assert_eq!(ProfileRegistry::slug("  Jo's -- Rentals! "), "jo-s-rentals");
+        assert_eq!(ProfileRegistry::slug("Ménage"), "m-nage");
+        assert_eq!(ProfileRegistry::slug("€€€"), "profile");
+    }
+
+    #[test]
+    fn test_first_run_opens_the_existing_database() {
+        let dir = temp_dir("first-run");
+        let registry = ProfileRegistry::load(&dir).unwrap();
+        assert_eq!(registry.active(), DEFAULT_PROFILE_NAME);
+        assert_eq!(registry.active_database_path().unwrap(), dir.join(DEFAULT_DATABASE_FILE));
+        assert!(dir.join(PROFILE_REGISTRY_FILE).exists());
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_add_and_switch_are_saved() {
+        let dir = temp_dir("add");
+        let registry = ProfileRegistry::load(&dir).unwrap();
+
+        let profile = registry.new_profile("  Small Business ").unwrap();
+        assert_eq!(profile.name, "Small Business");
+        assert_eq!(profile.file_name, "profiles/small-business.db");
+        registry.add(profile).unwrap();
+        registry.set_active("small business").unwrap();
+
+        let reloaded = ProfileRegistry::load(&dir).unwrap();
+        assert_eq!(reloaded.active(), "Small Business");
+        assert_eq!(reloaded.list().profiles.len(), 2);
+        assert_eq!(
+            reloaded.database_path("Small Business").unwrap(),
+            dir.join("profiles/small-business.db")
+        );
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_rejects_bad_names() {
+        let dir = temp_dir("names");
+        let registry = ProfileRegistry::load(&dir).unwrap();
+
+        assert!(matches!(registry.new_profile(" "), Err(ProfileError::EmptyName)));
+        assert!(matches!(registry.new_profile("PERSONAL"), Err(ProfileError::AlreadyExists(_))));
+        assert!(matches!(
+            registry.new_profile(&"x".repeat(MAX_PROFILE_NAME_LENGTH + 1)),
+            Err(ProfileError::NameTooLong { .. })
+        ));
+        assert!(matches!(registry.set_active("Nope"), Err(ProfileError::NotFound(_))));
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_file_names_are_never_shared() {
+        let dir = temp_dir("files");
+        let registry = ProfileRegistry::load(&dir).unwrap();
+        registry.add(registry.new_profile("Rentals").unwrap()).unwrap();
+
+        // Same slug as "Rentals", and a leftover file in the way of the next one
+        std::fs::create_dir_all(dir.join(PROFILE_DATABASE_DIR)).unwrap();
+        std::fs::write(dir.join("profiles/rentals-2.db"), "").unwrap();
+        let profile = registry.new_profile("Rentals!").unwrap();
+        assert_eq!(profile.file_name, "profiles/rentals-3.db");
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,130 @@
+use crate::utils::money::{self, Cents};
+
+/// One transaction in QIF terms
+#[derive(Debug, Clone)]
+pub struct QifTransaction {
+    pub date: String, // YYYY-MM-DD
+    pub amount_cents: Cents,
+    pub payee: String,
+    pub memo: Option<String>,
+    pub category: Option<String>, // "Parent:Child" for subcategories
+}
+
+/// An account section of a QIF file
+#[derive(Debug, Clone)]
+pub struct QifAccount {
+    pub name: String,
+    pub account_type: String, // checking, savings, credit_card
+    pub transactions: Vec<QifTransaction>,
+}
+
+pub struct QifWriter;
+
+impl QifWriter {
+    /// QIF account type header for an app account type
+    fn qif_type(account_type: &str) -> &'static str {
+        match account_type {
+            "credit_card" => "CCard",
+            _ => "Bank",
+        }
+    }
+
+    /// QIF fields are line-based and ':' / '/' are category/class separators,
+    /// so field values are flattened to one line and separators replaced
+    fn field(value: &str) -> String {
+        value.replace(['\r', '\n'], " ").trim().to_string()
+    }
+
+    fn category_part(value: &str) -> String {
+        Self::field(value).replace([':', '/'], "-")
+    }
+
+    /// QIF dates are MM/DD/YYYY (Quicken US and GnuCash both accept the 4-digit year)
+    fn date(date: &str) -> String {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(|d| d.format("%m/%d/%Y").to_string())
+            .unwrap_or_else(|_| date.to_string())
+    }
+
+    /// Build a category path from a category and its optional parent
+    pub fn category_path(name: &str, parent: Option<&str>) -> String {
+        match parent {
+            Some(parent) => format!("{}:{}", Self::category_part(parent), Self::category_part(name)),
+            None => Self::category_part(name),
+        }
+    }
+
+    /// Render accounts and their transactions as a multi-account QIF document
+    pub fn write(accounts: &[QifAccount]) -> String {
+        let mut out = String::new();
+        for account in accounts {
+            let qif_type = Self::qif_type(&account.account_type);
+            out.push_str("!Account\n");
+            out.push_str(&format!("N{}\n", Self::field(&account.name)));
+            out.push_str(&format!("T{}\n", qif_type));
+            out.push_str("^\n");
+            out.push_str(&format!("!Type:{}\n", qif_type));
+
+            for transaction in &account.transactions {
+                out.push_str(&format!("D{}\n", Self::date(&transaction.date)));
+                out.push_str(&format!("T{:.2}\n", money::from_cents(transaction.amount_cents)));
+                out.push_str(&format!("P{}\n", Self::field(&transaction.payee)));
+                if let Some(memo) = transaction.memo.as_deref().map(Self::field).filter(|m| !m.is_empty()) {
+                    out.push_str(&format!("M{}\n", memo));
+                }
+                if let Some(category) = &transaction.category {
+                    out.push_str(&format!("L{}\n", category));
+                }
+                out.push_str("^\n");
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_account_sections() {
+        let accounts = vec![
+            QifAccount {
+                name: "Everyday Checking".to_string(),
+                account_type: "checking".to_string(),
+                transactions: vec![QifTransaction {
+                    date: "2025-01-31".to_string(),
+                    amount_cents: -1250,
+                    payee: "Starbucks".to_string(),
+                    memo: Some("Latte\nwith oat milk".to_string()),
+                    category: Some(QifWriter::category_path("Coffee", Some("Dining"))),
+                }],
+            },
+            QifAccount {
+                name: "Visa".to_string(),
+                account_type: "credit_card".to_string(),
+                transactions: vec![QifTransaction {
+                    date: "2025-02-01".to_string(),
+                    amount_cents: 10000,
+                    payee: "Payment".to_string(),
+                    memo: None,
+                    category: None,
+                }],
+            },
+        ];
+
+        assert_eq!(
+            QifWriter::write(&accounts),
+            "!Account\nNEveryday Checking\nTBank\n^\n!Type:Bank\n\
+             D01/31/2025\nT-12.50\nPStarbucks\nMLatte with oat milk\nLDining:Coffee\n^\n\
+             !Account\nNVisa\nTCCard\n^\n!Type:CCard\n\
+             D02/01/2025\nT100.00\nPPayment\n^\n"
+        );
+    }
+
+    #[test]
+    fn test_category_path_escapes_separators() {
+        assert_eq!(QifWriter::category_path("Gas/Fuel", None), "Gas-Fuel");
+        assert_eq!(QifWriter::category_path("Kids: School", Some("Family")), "Family:Kids- School");
+    }
+}
@@ -0,0 +1,325 @@
+use crate::constants::{DEFAULT_QUERY_ROW_LIMIT, MAX_QUERY_LENGTH, MAX_QUERY_ROW_LIMIT};
+use crate::errors::QueryError;
+use serde::Serialize;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Column, ConnectOptions, Connection, Executor, Row, SqlitePool, Statement, TypeInfo, ValueRef};
+
+/// Statements that change data or the connection; rejected wherever they appear
+const FORBIDDEN_KEYWORDS: &[&str] = &[
+    "INSERT", "UPDATE", "DELETE", "DROP", "ALTER", "CREATE", "ATTACH", "DETACH", "PRAGMA", "VACUUM",
+    "REINDEX", "ANALYZE", "BEGIN", "COMMIT", "ROLLBACK", "SAVEPOINT", "RELEASE", "LOAD_EXTENSION",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>, // One value per column; BLOBs as hex strings
+    pub row_count: usize,
+    pub limit: usize,
+    pub truncated: bool, // The query had more rows than the limit
+}
+
+/// One line of SQLite's EXPLAIN QUERY PLAN output
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryPlanStep {
+    pub id: i64,
+    pub parent: i64, // 0 for top-level steps
+    pub detail: String, // e.g. "SEARCH transactions USING INDEX idx_transactions_account_date (account_id=?)"
+}
+
+/// How SQLite would run a query, with the indexes it uses and the tables it reads in full
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryPlan {
+    pub steps: Vec<QueryPlanStep>,
+    pub indexes: Vec<String>,
+    pub full_scans: Vec<String>,
+}
+
+impl QueryPlan {
+    fn from_steps(steps: Vec<QueryPlanStep>) -> Self {
+        let mut indexes = Vec::new();
+        let mut full_scans = Vec::new();
+        for step in &steps {
+            let words: Vec<&str> = step.detail.split_whitespace().collect();
+            if let Some(index) = words.iter().position(|w| *w == "INDEX").and_then(|i| words.get(i + 1)) {
+                if !indexes.iter().any(|name| name == index) {
+                    indexes.push(index.to_string());
+                }
+            } else if words.first() == Some(&"SCAN") && !words.contains(&"USING") {
+                // Subqueries and constant rows are scans of nothing stored
+                if let Some(table) = words.get(1).filter(|t| !t.starts_with('(') && **t != "CONSTANT") {
+                    full_scans.push(table.to_string());
+                }
+            }
+        }
+        Self { steps, indexes, full_scans }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String), // Uppercased keyword or bare identifier
+    Semicolon(usize), // Byte offset in the statement
+}
+
+pub struct QueryConsole;
+
+impl QueryConsole {
+    /// Keywords, identifiers and semicolons outside string literals, quoted identifiers and comments
+    fn tokenize(sql: &str) -> Vec<Token> {
+        let chars: Vec<(usize, char)> = sql.char_indices().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let (offset, c) = chars[i];
+            let next = chars.get(i + 1).map(|(_, c)| *c);
+            match c {
+                '\'' | '"' | '`' => {
+                    // A doubled quote is an escaped quote, so skipping both keeps us inside the literal
+                    i += 1;
+                    while i < chars.len() {
+                        if chars[i].1 == c {
+                            if chars.get(i + 1).map(|(_, n)| *n) == Some(c) {
+                                i += 1;
+                            } else {
+                                break;
+                            }
+                        }
+                        i += 1;
+                    }
+                }
+                '[' => {
+                    while i < chars.len() && chars[i].1 != ']' {
+                        i += 1;
+                    }
+                }
+                '-' if next == Some('-') => {
+                    while i < chars.len() && chars[i].1 != '\n' {
+                        i += 1;
+                    }
+                }
+                '/' if next == Some('*') => {
+                    i += 2;
+                    while i < chars.len() && !(chars[i].1 == '*' && chars.get(i + 1).map(|(_, n)| *n) == Some('/')) {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                ';' => tokens.push(Token::Semicolon(offset)),
+                c if c.is_alphabetic() || c == '_' => {
+                    let start = i;
+                    while i + 1 < chars.len() && (chars[i + 1].1.is_alphanumeric() || matches!(chars[i + 1].1, '_' | '$')) {
+                        i += 1;
+                    }
+                    let word: String = chars[start..=i].iter().map(|(_, c)| *c).collect();
+                    tokens.push(Token::Word(word.to_uppercase()));
+                }
+                c if c.is_ascii_digit() => {
+                    // Numbers like 1e5 shouldn't produce a word
+                    while i + 1 < chars.len() && (chars[i + 1].1.is_alphanumeric() || chars[i + 1].1 == '.') {
+                        i += 1;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+
+        tokens
+    }
+
+    /// Check that `sql` is a single SELECT (or WITH ... SELECT) statement
+    /// Returns the statement without its trailing semicolon
+    pub fn validate(sql: &str) -> Result<&str, QueryError> {
+        let sql = sql.trim();
+        if sql.chars().count() > MAX_QUERY_LENGTH {
+            return Err(QueryError::TooLong { max: MAX_QUERY_LENGTH });
+        }
+
+        let tokens = Self::tokenize(sql);
+        let end = tokens.iter().position(|t| matches!(t, Token::Semicolon(_)));
+        let statement = match end {
+            Some(index) => {
+                if tokens[index..].iter().any(|t| matches!(t, Token::Word(_))) {
+                    return Err(QueryError::MultipleStatements);
+                }
+                match tokens[index] {
+                    Token::Semicolon(offset) => &sql[..offset],
+                    Token::Word(_) => sql,
+                }
+            }
+            None => sql,
+        };
+
+        let words: Vec<&str> = tokens
+            .iter()
+            .filter_map(|t| match t {
+                Token::Word(word) => Some(word.as_str()),
+                Token::Semicolon(_) => None,
+            })
+            .collect();
+        match words.first() {
+            None => return Err(QueryError::Empty),
+            Some(&"SELECT") | Some(&"WITH") => {}
+            Some(_) => return Err(QueryError::NotSelect),
+        }
+
+        if let Some(word) = words.iter().find(|w| FORBIDDEN_KEYWORDS.contains(w)) {
+            return Err(QueryError::ForbiddenKeyword(word.to_string()));
+        }
+        // replace() is a string function; only REPLACE INTO is a write
+        if words.windows(2).any(|pair| pair == ["REPLACE", "INTO"]) {
+            return Err(QueryError::ForbiddenKeyword("REPLACE".to_string()));
+        }
+
+        Ok(statement.trim_end())
+    }
+
+    /// A connection to the same database that SQLite itself refuses to write through
+    pub async fn connect_read_only(db: &SqlitePool) -> Result<SqliteConnection, QueryError> {
+        let options = (*db.connect_options()).clone().read_only(true).create_if_missing(false);
+        let mut conn = options
+            .connect()
+            .await
+            .map_err(|e| QueryError::Database(e.to_string()))?;
+        sqlx::query("PRAGMA query_only = ON")
+            .execute(&mut conn)
+            .await
+            .map_err(|e| QueryError::Database(e.to_string()))?;
+        Ok(conn)
+    }
+
+    fn to_json(row: &sqlx::sqlite::SqliteRow, index: usize) -> serde_json::Value {
+        let Ok(raw) = row.try_get_raw(index) else {
+            return serde_json::Value::Null;
+        };
+        if raw.is_null() {
+            return serde_json::Value::Null;
+        }
+        let type_name = raw.type_info().name().to_string();
+        match type_name.as_str() {
+            "INTEGER" => row.try_get::<i64, _>(index).map(Into::into).unwrap_or_default(),
+            "REAL" => row.try_get::<f64, _>(index).map(Into::into).unwrap_or_default(),
+            "BLOB" => row
+                .try_get::<Vec<u8>, _>(index)
+                .map(|bytes| bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>().into())
+                .unwrap_or_default(),
+            _ => row.try_get::<String, _>(index).map(Into::into).unwrap_or_default(),
+        }
+    }
+
+    /// Run a validated SELECT on a read-only connection, returning at most `limit` rows
+    pub async fn execute(db: &SqlitePool, sql: &str, limit: Option<usize>) -> Result<QueryResult, QueryError> {
+        let statement = Self::validate(sql)?;
+        let limit = limit.unwrap_or(DEFAULT_QUERY_ROW_LIMIT).clamp(1, MAX_QUERY_ROW_LIMIT);
+        // Newlines keep a trailing line comment from swallowing the closing parenthesis;
+        // one extra row tells us whether the result was cut off
+        let wrapped = format!("SELECT * FROM (\n{}\n) LIMIT {}", statement, limit + 1);
+
+        let mut conn = Self::connect_read_only(db).await?;
+        let query_error = |e: sqlx::Error| match e {
+            sqlx::Error::Database(e) => QueryError::Invalid(e.message().to_string()),
+            other => QueryError::Database(other.to_string()),
+        };
+        let prepared = conn.prepare(&wrapped).await.map_err(query_error)?;
+        let columns: Vec<String> = prepared.columns().iter().map(|c| c.name().to_string()).collect();
+        let rows = prepared.query().fetch_all(&mut conn).await.map_err(query_error)?;
+        if let Err(e) = conn.close().await {
+            tracing::warn!(error = %e, "Failed to close query console connection");
+        }
+
+        let truncated = rows.len() > limit;
+        let rows: Vec<Vec<serde_json::Value>> = rows
+            .iter()
+            .take(limit)
+            .map(|row| (0..columns.len()).map(|i| Self::to_json(row, i)).collect())
+            .collect();
+
+        Ok(QueryResult {
+            columns,
+            row_count: rows.len(),
+            rows,
+            limit,
+            truncated,
+        })
+    }
+
+    /// SQLite's plan for a validated SELECT, without running it; placeholders are planned as NULLs
+    pub async fn plan(db: &SqlitePool, sql: &str) -> Result<QueryPlan, QueryError> {
+        let statement = Self::validate(sql)?;
+
+        let mut conn = Self::connect_read_only(db).await?;
+        let rows: Result<Vec<(i64, i64, i64, String)>, sqlx::Error> =
+            sqlx::query_as(&format!("EXPLAIN QUERY PLAN\n{}", statement)).fetch_all(&mut conn).await;
+        if let Err(e) = conn.close().await {
+            tracing::warn!(error = %e, "Failed to close query console connection");
+        }
+        let rows = rows.map_err(|e| match e {
+            sqlx::Error::Database(e) => QueryError::Invalid(e.message().to_string()),
+            other => QueryError::Database(other.to_string()),
+        })?;
+
+        let steps = rows
+            .into_iter()
+            .map(|(id, parent, _, detail)| QueryPlanStep { id, parent, detail })
+            .collect();
+        Ok(QueryPlan::from_steps(steps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_accepts_selects() {
+        assert_eq!(QueryConsole::validate("  SELECT 1;  ").unwrap(), "SELECT 1");
+        assert_eq!(QueryConsole::validate("select * from transactions -- all of them").unwrap(), "select * from transactions -- all of them");
+        assert!(QueryConsole::validate("WITH t AS (SELECT 1 AS x) SELECT x FROM t").is_ok());
+        assert!(QueryConsole::validate("SELECT replace(description, 'a', 'b') FROM transactions").is_ok());
+        // Keywords inside literals, quoted identifiers and comments don't count
+        assert!(QueryConsole::validate("SELECT 'DELETE FROM x; DROP TABLE y' AS \"update\" /* insert */").is_ok());
+        assert!(QueryConsole::validate("SELECT 'it''s; delete'").is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_writes() {
+        assert!(matches!(QueryConsole::validate(""), Err(QueryError::Empty)));
+        assert!(matches!(QueryConsole::validate("-- nothing"), Err(QueryError::Empty)));
+        assert!(matches!(QueryConsole::validate("DELETE FROM transactions"), Err(QueryError::NotSelect)));
+        assert!(matches!(QueryConsole::validate("PRAGMA table_info(accounts)"), Err(QueryError::NotSelect)));
+        assert!(matches!(
+            QueryConsole::validate("SELECT 1; DELETE FROM transactions"),
+            Err(QueryError::MultipleStatements)
+        ));
+        assert!(matches!(
+            QueryConsole::validate("WITH t AS (SELECT 1) DELETE FROM transactions"),
+            Err(QueryError::ForbiddenKeyword(k)) if k == "DELETE"
+        ));
+        assert!(matches!(
+            QueryConsole::validate("WITH t AS (SELECT 1) REPLACE INTO accounts SELECT * FROM t"),
+            Err(QueryError::ForbiddenKeyword(k)) if k == "REPLACE"
+        ));
+        assert!(matches!(
+            QueryConsole::validate(&format!("SELECT '{}'", "x".repeat(MAX_QUERY_LENGTH))),
+            Err(QueryError::TooLong { .. })
+        ));
+    }
+
+    #[test]
+    fn test_plan_lists_indexes_and_full_scans() {
+        let step = |id, detail: &str| QueryPlanStep { id, parent: 0, detail: detail.to_string() };
+        let plan = QueryPlan::from_steps(vec![
+            step(2, "SEARCH t USING INDEX idx_transactions_account_date (account_id=? AND date>?)"),
+            step(3, "SCAN c USING COVERING INDEX idx_categories_type"),
+            step(4, "SCAN merchants"),
+            step(5, "SCAN (subquery-1)"),
+            step(6, "SEARCH a USING INTEGER PRIMARY KEY (rowid=?)"),
+            step(7, "USE TEMP B-TREE FOR ORDER BY"),
+        ]);
+        assert_eq!(plan.indexes, vec!["idx_transactions_account_date", "idx_categories_type"]);
+        assert_eq!(plan.full_scans, vec!["merchants"]);
+        assert_eq!(plan.steps.len(), 6);
+    }
+}
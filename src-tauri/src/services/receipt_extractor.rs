@@ -0,0 +1,268 @@
+use crate::constants::{MAX_RECEIPT_IMAGE_BYTES, RECEIPT_IMAGE_EXTENSIONS, TESSERACT_COMMAND};
+use crate::errors::ReceiptError;
+use crate::services::merchant_normalizer::MerchantNormalizer;
+use crate::utils::money;
+use serde::Serialize;
+use std::path::Path;
+use std::process::Command;
+
+/// Date formats tried on each receipt word (and the three words from it), output as YYYY-MM-DD
+const RECEIPT_DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",  // 2025-06-15
+    "%m/%d/%Y",  // 06/15/2025
+    "%m/%d/%y",  // 06/15/25
+    "%d/%m/%Y",  // 15/06/2025 (only when the day can't be a month)
+    "%d/%m/%y",  // 15/06/25
+    "%m-%d-%Y",  // 06-15-2025
+    "%d.%m.%Y",  // 15.06.2025
+    "%b %d %Y",  // Jun 15 2025
+    "%B %d %Y",  // June 15 2025
+    "%d %b %Y",  // 15 Jun 2025
+    "%d %B %Y",  // 15 June 2025
+];
+
+/// Line labels for the amount actually charged, checked after ruling out subtotals
+const TOTAL_LABELS: &[&str] = &["total", "amount due", "balance due", "amount paid"];
+
+/// Line labels that look like a total but aren't the amount charged
+const NOT_TOTAL_LABELS: &[&str] = &["subtotal", "sub total", "sub-total", "total savings", "total items", "tax"];
+
+/// Values read from a receipt, for the user to confirm before a transaction is created
+///
+/// Every field is a guess; any of them can be missing when the text doesn't show it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReceiptData {
+    /// Total charged, as a negative (expense) amount
+    pub amount: Option<f64>,
+    pub date: Option<String>,
+    pub merchant: Option<String>,
+    /// Everything OCR read, so the user can check the guesses
+    pub text: String,
+}
+
+pub struct ReceiptExtractor;
+
+impl ReceiptExtractor {
+    /// Check that the receipt is an image of a supported type and size
+    pub fn validate_image(path: &Path) -> Result<(), ReceiptError> {
+        let metadata = std::fs::metadata(path).map_err(|_| ReceiptError::NotFound(path.display().to_string()))?;
+        if !metadata.is_file() {
+            return Err(ReceiptError::NotFound(path.display().to_string()));
+        }
+
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .unwrap_or_default();
+        if !RECEIPT_IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+            return Err(ReceiptError::UnsupportedFormat(extension));
+        }
+
+        if metadata.len() > MAX_RECEIPT_IMAGE_BYTES {
+            return Err(ReceiptError::TooLarge { max_mb: MAX_RECEIPT_IMAGE_BYTES / (1024 * 1024) });
+        }
+        Ok(())
+    }
+
+    /// Read the text of a receipt image with the Tesseract command-line tool
+    ///
+    /// Blocks until Tesseract finishes; call it from the blocking thread pool.
+    pub fn ocr(path: &Path) -> Result<String, ReceiptError> {
+        let output = Command::new(TESSERACT_COMMAND)
+            .arg(path)
+            .arg("stdout")
+            .output()
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => ReceiptError::OcrUnavailable,
+                _ => ReceiptError::OcrFailed(e.to_string()),
+            })?;
+
+        if !output.status.success() {
+            return Err(ReceiptError::OcrFailed(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Validate, OCR, and parse a receipt image
+    pub fn extract(path: &Path) -> Result<ReceiptData, ReceiptError> {
+        Self::validate_image(path)?;
+        let text = Self::ocr(path)?;
+        Ok(Self::parse(&text))
+    }
+
+    /// Guess the amount, date, and merchant from a receipt's text
+    pub fn parse(text: &str) -> ReceiptData {
+        let lines: Vec<&str> = text.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+
+        ReceiptData {
+            amount: Self::find_total(&lines).map(|amount| -amount),
+            date: lines.iter().find_map(|line| Self::find_date(line)),
+            merchant: Self::find_merchant(&lines),
+            text: text.trim().to_string(),
+        }
+    }
+
+    /// Money amounts on a line, in order ("$1,234.56", "12.99", "-3.00")
+    ///
+    /// Only numbers with two decimal places count, which skips quantities, phone
+    /// numbers, and store numbers. Discounts keep their sign, whether it's printed
+    /// before the number ("-3.00") or after it ("3.00-").
+    fn amounts(line: &str) -> Vec<f64> {
+        line.split(|c: char| c.is_whitespace() || c == '$')
+            .map(|word| word.trim_matches(|c: char| !c.is_ascii_digit() && c != '-'))
+            .map(|word| {
+                let negative = word.starts_with('-') || word.ends_with('-');
+                (negative, word.trim_matches('-').replace(',', ""))
+            })
+            .filter(|(_, number)| {
+                number
+                    .split_once('.')
+                    .is_some_and(|(whole, cents)| !whole.is_empty() && cents.len() == 2)
+            })
+            .filter_map(|(negative, number)| {
+                number.parse::<f64>().ok().map(|amount| if negative { -amount } else { amount })
+            })
+            .map(money::round_money)
+            .collect()
+    }
+
+    /// The total from the last "total" line, or the largest amount when no line is labelled
+    fn find_total(lines: &[&str]) -> Option<f64> {
+        let labelled = lines.iter().rev().find_map(|line| {
+            let lower = line.to_lowercase();
+            let is_total = TOTAL_LABELS.iter().any(|label| lower.contains(label))
+                && !NOT_TOTAL_LABELS.iter().any(|label| lower.starts_with(label) || lower.contains(&format!(" {}", label)));
+            if is_total {
+                Self::amounts(line).last().copied()
+            } else {
+                None
+            }
+        });
+
+        labelled.or_else(|| lines.iter().flat_map(|line| Self::amounts(line)).reduce(f64::max))
+    }
+
+    /// The first date on a line, as YYYY-MM-DD
+    fn find_date(line: &str) -> Option<String> {
+        let words: Vec<String> = line
+            .split_whitespace()
+            .map(|w| w.trim_matches(|c: char| matches!(c, ',' | ':' | ';' | '(' | ')')).to_string())
+            .collect();
+
+        // Month-name dates span three words; numeric ones fit in one
+        (0..words.len()).find_map(|i| {
+            let candidates = [
+                words[i..(i + 3).min(words.len())].join(" "),
+                words[i].clone(),
+            ];
+            candidates.iter().find_map(|candidate| {
+                RECEIPT_DATE_FORMATS.iter().find_map(|format| Self::parse_date(candidate, format))
+            })
+        })
+    }
+
+    fn parse_date(candidate: &str, format: &str) -> Option<String> {
+        use chrono::Datelike;
+        chrono::NaiveDate::parse_from_str(candidate, format)
+            .ok()
+            // %Y also accepts two-digit years; leave those to the %y formats
+            .filter(|date| !format.contains("%Y") || date.year() >= 1000)
+            .map(|date| date.format("%Y-%m-%d").to_string())
+    }
+
+    /// The store name, usually the first line with words in it
+    fn find_merchant(lines: &[&str]) -> Option<String> {
+        lines
+            .iter()
+            .take(5)
+            .filter(|line| line.chars().filter(|c| c.is_alphabetic()).count() >= 3)
+            .filter(|line| Self::find_date(line).is_none() && Self::amounts(line).is_empty())
+            .find_map(|line| MerchantNormalizer::normalize(line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GROCERY_RECEIPT: &str = "
+        WHOLE FOODS MARKET #10234
+        123 Main St
+        Seattle WA 98101
+        06/15/2025 14:32
+
+        ORGANIC BANANAS        2.49
+        ALMOND MILK            4.99
+        SUBTOTAL              $7.48
+        TAX                    0.62
+        TOTAL                 $8.10
+        VISA TEND             $8.10
+    ";
+
+    #[test]
+    fn test_parse_grocery_receipt() {
+        let data = ReceiptExtractor::parse(GROCERY_RECEIPT);
+        assert_eq!(data.amount, Some(-8.10));
+        assert_eq!(data.date.as_deref(), Some("2025-06-15"));
+        assert_eq!(data.merchant.as_deref(), Some("Whole Foods Market"));
+    }
+
+    #[test]
+    fn test_total_skips_subtotal_and_tax() {
+        let text = "Cafe\nTotal Savings 1.00\nSub Total 10.00\nTax 0.80\nAmount Due 10.80";
+        assert_eq!(ReceiptExtractor::parse(text).amount, Some(-10.80));
+    }
+
+    #[test]
+    fn test_total_falls_back_to_largest_amount() {
+        let text = "Corner Store\nMILK 3.49\nBREAD 2.99\nCASH 10.00\nCHANGE 3.52";
+        assert_eq!(ReceiptExtractor::parse(text).amount, Some(-10.00));
+    }
+
+    #[test]
+    fn test_amounts_ignore_numbers_without_cents() {
+        assert_eq!(ReceiptExtractor::amounts("Store 0042 Tel 555-1234 Qty 2"), Vec::<f64>::new());
+        assert_eq!(ReceiptExtractor::amounts("Total: $1,234.56"), vec![1234.56]);
+    }
+
+    #[test]
+    fn test_amounts_keep_discount_sign() {
+        assert_eq!(ReceiptExtractor::amounts("Coupon -3.00"), vec![-3.00]);
+        assert_eq!(ReceiptExtractor::amounts("Member Savings 1.25-"), vec![-1.25]);
+        assert_eq!(ReceiptExtractor::amounts("Item-Code 12.99"), vec![12.99]);
+
+        // A discount is never mistaken for the largest amount
+        let text = "Bakery\nBREAD 5.00\nCOUPON -8.00";
+        assert_eq!(ReceiptExtractor::parse(text).amount, Some(-5.00));
+    }
+
+    #[test]
+    fn test_dates_in_other_formats() {
+        assert_eq!(ReceiptExtractor::find_date("Date: 2025-03-04").as_deref(), Some("2025-03-04"));
+        assert_eq!(ReceiptExtractor::find_date("25/12/2024 09:10").as_deref(), Some("2024-12-25"));
+        assert_eq!(ReceiptExtractor::find_date("Sold Jun 5, 2025 at 10am").as_deref(), Some("2025-06-05"));
+        assert_eq!(ReceiptExtractor::find_date("No date here"), None);
+    }
+
+    #[test]
+    fn test_empty_text_finds_nothing() {
+        let data = ReceiptExtractor::parse("  \n ");
+        assert_eq!(data, ReceiptData { amount: None, date: None, merchant: None, text: String::new() });
+    }
+
+    #[test]
+    fn test_validate_rejects_unsupported_files() {
+        let dir = std::env::temp_dir().join(format!("receipt-extractor-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pdf = dir.join("receipt.pdf");
+        std::fs::write(&pdf, b"%PDF").unwrap();
+
+        assert!(matches!(ReceiptExtractor::validate_image(&pdf), Err(ReceiptError::UnsupportedFormat(_))));
+        assert!(matches!(
+            ReceiptExtractor::validate_image(&dir.join("missing.png")),
+            Err(ReceiptError::NotFound(_))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
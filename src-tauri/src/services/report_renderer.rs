@@ -0,0 +1,188 @@
+use crate::constants::{DEFAULT_TOP_ITEMS_LIMIT, MAX_PAGE_SIZE};
+use crate::models::report::{ReportSpec, ReportTextFormat};
+use crate::services::spending_aggregator::SpendingAggregator;
+use crate::services::target_tracker::TargetTracker;
+use crate::utils::money::{self, Cents};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// A report flattened into display-ready rows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportTable {
+    pub title: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+pub struct ReportRenderer;
+
+impl ReportRenderer {
+    fn money(amount: Cents) -> String {
+        format!("${:.2}", money::from_cents(amount))
+    }
+
+    fn percent(value: f64) -> String {
+        format!("{:.1}%", value)
+    }
+
+    /// Run the report described by `spec` and flatten it into a table
+    pub async fn build(db: &SqlitePool, spec: &ReportSpec) -> Result<ReportTable, String> {
+        match spec {
+            ReportSpec::SpendingByCategory { start_date, end_date, account_id } => {
+                let data = SpendingAggregator::get_spending_by_category(db, start_date, end_date, *account_id, None).await?;
+                let mut rows: Vec<Vec<String>> = data
+                    .categories
+                    .iter()
+                    .map(|c| {
+                        vec![
+                            c.category_name.clone(),
+                            Self::money(c.amount_cents),
+                            Self::percent(c.percentage),
+                            c.transaction_count.to_string(),
+                        ]
+                    })
+                    .collect();
+                rows.push(vec![
+                    "Total".to_string(),
+                    Self::money(data.total_spending_cents),
+                    String::new(),
+                    data.categories.iter().map(|c| c.transaction_count).sum::<i64>().to_string(),
+                ]);
+                Ok(ReportTable {
+                    title: format!("Spending by category, {} to {}", start_date, end_date),
+                    headers: vec!["Category".into(), "Amount".into(), "Share".into(), "Transactions".into()],
+                    rows,
+                })
+            }
+            ReportSpec::SpendingByMerchant { start_date, end_date, account_id, limit } => {
+                let limit = limit.unwrap_or(DEFAULT_TOP_ITEMS_LIMIT).clamp(1, MAX_PAGE_SIZE);
+                let data = SpendingAggregator::get_spending_by_merchant(
+                    db,
+                    start_date,
+                    end_date,
+                    *account_id,
+                    limit as usize,
+                )
+                .await?;
+                Ok(ReportTable {
+                    title: format!("Top merchants, {} to {}", start_date, end_date),
+                    headers: vec![
+                        "Merchant".into(),
+                        "Amount".into(),
+                        "Transactions".into(),
+                        "Average".into(),
+                        "vs Previous".into(),
+                    ],
+                    rows: data
+                        .merchants
+                        .iter()
+                        .map(|m| {
+                            vec![
+                                m.merchant_name.clone(),
+                                Self::money(m.amount_cents),
+                                m.transaction_count.to_string(),
+                                Self::money(money::to_cents(m.average_ticket)),
+                                m.change_percent.map(|c| format!("{:+.1}%", c)).unwrap_or_else(|| "new".to_string()),
+                            ]
+                        })
+                        .collect(),
+                })
+            }
+            ReportSpec::TargetsProgress { start_date, end_date } => {
+                let data = TargetTracker::get_targets_progress(db, start_date, end_date).await?;
+                Ok(ReportTable {
+                    title: format!("Budget progress, {} to {}", start_date, end_date),
+                    headers: vec![
+                        "Category".into(),
+                        "Spent".into(),
+                        "Target".into(),
+                        "Remaining".into(),
+                        "Status".into(),
+                    ],
+                    rows: data
+                        .targets
+                        .iter()
+                        .map(|t| {
+                            vec![
+                                t.category_name.clone(),
+                                Self::money(t.actual_amount_cents),
+                                Self::money(t.target_amount_cents),
+                                Self::money(t.remaining_cents),
+                                t.status.clone(),
+                            ]
+                        })
+                        .collect(),
+                })
+            }
+        }
+    }
+
+    fn markdown_cell(cell: &str) -> String {
+        cell.replace('|', "\\|").replace(['\n', '\r'], " ")
+    }
+
+    fn tsv_cell(cell: &str) -> String {
+        cell.replace(['\t', '\n', '\r'], " ")
+    }
+
+    pub fn to_markdown(table: &ReportTable) -> String {
+        let mut out = format!("**{}**\n\n", table.title);
+        let header: Vec<String> = table.headers.iter().map(|h| Self::markdown_cell(h)).collect();
+        out.push_str(&format!("| {} |\n", header.join(" | ")));
+        out.push_str(&format!("|{}\n", "---|".repeat(table.headers.len())));
+        for row in &table.rows {
+            let cells: Vec<String> = row.iter().map(|c| Self::markdown_cell(c)).collect();
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        out
+    }
+
+    /// Tab-separated rows (pastes into spreadsheets as cells)
+    pub fn to_tsv(table: &ReportTable) -> String {
+        let mut out = String::new();
+        for row in std::iter::once(&table.headers).chain(table.rows.iter()) {
+            let cells: Vec<String> = row.iter().map(|c| Self::tsv_cell(c)).collect();
+            out.push_str(&cells.join("\t"));
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn render(table: &ReportTable, format: ReportTextFormat) -> String {
+        match format {
+            ReportTextFormat::Markdown => Self::to_markdown(table),
+            ReportTextFormat::Tsv => Self::to_tsv(table),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> ReportTable {
+        ReportTable {
+            title: "Spending by category".to_string(),
+            headers: vec!["Category".to_string(), "Amount".to_string()],
+            rows: vec![
+                vec!["Dining | Bars".to_string(), "$12.50".to_string()],
+                vec!["Tabs\tand\nlines".to_string(), "$3.00".to_string()],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_to_markdown_escapes_pipes() {
+        let markdown = ReportRenderer::to_markdown(&table());
+        assert_eq!(
+            markdown,
+            "**Spending by category**\n\n| Category | Amount |\n|---|---|\n| Dining \\| Bars | $12.50 |\n| Tabs\tand lines | $3.00 |\n"
+        );
+    }
+
+    #[test]
+    fn test_to_tsv_flattens_tabs_and_newlines() {
+        let tsv = ReportRenderer::to_tsv(&table());
+        assert_eq!(tsv, "Category\tAmount\nDining | Bars\t$12.50\nTabs and lines\t$3.00\n");
+    }
+}
@@ -1,33 +1,48 @@
-use crate::constants::{MAX_PAYOFF_YEARS, MONTHS_PER_YEAR, PERCENT_TO_DECIMAL_DIVISOR};
+use crate::constants::{MAX_PAYOFF_YEARS, MONTHS_PER_YEAR};
 use crate::errors::DebtError;
-use crate::models::debt::Debt;
+use crate::models::debt::{self, Debt};
+use crate::utils::money::{self, Cents};
 use crate::services::avalanche_calculator::{DebtPaymentDetail, DebtSummary, MonthlyPayment, PayoffPlan};
+use chrono::{Months, NaiveDate};
 
 #[derive(Debug, Clone)]
 struct DebtState {
     id: i64,
     name: String,
-    balance: f64,
+    balance: Cents,
     interest_rate: f64,
-    min_payment: f64,
-    total_interest_paid: f64,
+    min_payment: Cents,
+    min_payment_percent: Option<f64>,
+    total_interest_paid: Cents,
     payoff_month: Option<i32>,
 }
 
 pub struct SnowballCalculator;
 
 impl SnowballCalculator {
-    pub fn calculate_payoff_plan(debts: Vec<Debt>, monthly_amount: f64) -> Result<PayoffPlan, DebtError> {
+    /// Month 1 is paid on `start_date` and each later month on the same day of the following
+    /// calendar months (the last day in shorter months)
+    pub fn calculate_payoff_plan(
+        debts: Vec<Debt>,
+        monthly_amount: Cents,
+        start_date: NaiveDate,
+    ) -> Result<PayoffPlan, DebtError> {
         if debts.is_empty() {
             return Err(DebtError::NoDebts);
         }
 
-        // Validate monthly amount covers minimum payments
-        let total_min_payments: f64 = debts.iter().map(|d| d.min_payment).sum();
+        // Validate monthly amount covers the first month's minimum payments
+        let total_min_payments: Cents = debts
+            .iter()
+            .map(|d| {
+                let statement_balance = d.balance_cents + money::monthly_interest(d.balance_cents, d.interest_rate);
+                debt::minimum_payment(d.min_payment_cents, d.min_payment_percent, statement_balance)
+            })
+            .sum();
         if monthly_amount < total_min_payments {
             return Err(DebtError::InsufficientFunds {
-                monthly: monthly_amount,
-                min_payments: total_min_payments,
+                monthly: money::from_cents(monthly_amount),
+                min_payments: money::from_cents(total_min_payments),
             });
         }
 
@@ -37,39 +52,42 @@ impl SnowballCalculator {
             .map(|d| DebtState {
                 id: d.id,
                 name: d.name.clone(),
-                balance: d.balance,
+                balance: d.balance_cents,
                 interest_rate: d.interest_rate,
-                min_payment: d.min_payment,
-                total_interest_paid: 0.0,
+                min_payment: d.min_payment_cents,
+                min_payment_percent: d.min_payment_percent,
+                total_interest_paid: 0,
                 payoff_month: None,
             })
             .collect();
 
         let mut monthly_breakdown = Vec::new();
         let mut month: i32 = 1;
-        let start_date = chrono::Local::now().date_naive();
 
         // Simulate month-by-month payments until all debts paid off
-        while debt_states.iter().any(|d| d.balance > 0.01) {
-            let current_date = start_date + chrono::Duration::days(((month - 1) * 30) as i64);
+        while debt_states.iter().any(|d| d.balance > 0) {
+            // Always counted from the start date, so a short month doesn't pull later dates back
+            let current_date = start_date
+                .checked_add_months(Months::new((month - 1) as u32))
+                .ok_or(DebtError::PayoffExceeded(MAX_PAYOFF_YEARS))?;
 
             // Sort by balance (lowest first) at the beginning of each month
             debt_states.sort_by(|a, b| {
-                if a.balance < 0.01 && b.balance < 0.01 {
+                if a.balance == 0 && b.balance == 0 {
                     std::cmp::Ordering::Equal
-                } else if a.balance < 0.01 {
+                } else if a.balance == 0 {
                     std::cmp::Ordering::Greater
-                } else if b.balance < 0.01 {
+                } else if b.balance == 0 {
                     std::cmp::Ordering::Less
                 } else {
-                    a.balance.partial_cmp(&b.balance).unwrap_or(std::cmp::Ordering::Equal)
+                    a.balance.cmp(&b.balance)
                 }
             });
 
             // Apply interest to all debts
             for debt in &mut debt_states {
-                if debt.balance > 0.01 {
-                    let monthly_interest = debt.balance * (debt.interest_rate / PERCENT_TO_DECIMAL_DIVISOR / MONTHS_PER_YEAR);
+                if debt.balance > 0 {
+                    let monthly_interest = money::monthly_interest(debt.balance, debt.interest_rate);
                     debt.balance += monthly_interest;
                     debt.total_interest_paid += monthly_interest;
                 }
@@ -78,56 +96,56 @@ impl SnowballCalculator {
             let mut remaining_amount = monthly_amount;
             let mut payments = Vec::new();
 
-            // Pay minimums on all debts first
+            // Pay minimums on all debts first, recomputed on this month's balance
             for debt in &mut debt_states {
-                if debt.balance > 0.01 {
-                    let payment = debt.min_payment.min(debt.balance);
+                if debt.balance > 0 {
+                    let payment = debt::minimum_payment(debt.min_payment, debt.min_payment_percent, debt.balance);
                     debt.balance -= payment;
                     remaining_amount -= payment;
                     payments.push(DebtPaymentDetail {
                         debt_id: debt.id,
                         debt_name: debt.name.clone(),
-                        amount: payment,
+                        amount_cents: payment,
                     });
 
-                    if debt.balance < 0.01 && debt.payoff_month.is_none() {
+                    if debt.balance == 0 && debt.payoff_month.is_none() {
                         debt.payoff_month = Some(month);
                     }
                 }
             }
 
             // Allocate extra payment to lowest balance debt with balance remaining
-            if remaining_amount > 0.01 {
-                if let Some(target_debt) = debt_states.iter_mut().find(|d| d.balance > 0.01) {
+            if remaining_amount > 0 {
+                if let Some(target_debt) = debt_states.iter_mut().find(|d| d.balance > 0) {
                     let extra_payment = remaining_amount.min(target_debt.balance);
                     target_debt.balance -= extra_payment;
 
                     // Add to existing payment or create new one
                     if let Some(payment_detail) = payments.iter_mut().find(|p| p.debt_id == target_debt.id) {
-                        payment_detail.amount += extra_payment;
+                        payment_detail.amount_cents += extra_payment;
                     } else {
                         payments.push(DebtPaymentDetail {
                             debt_id: target_debt.id,
                             debt_name: target_debt.name.clone(),
-                            amount: extra_payment,
+                            amount_cents: extra_payment,
                         });
                     }
 
-                    if target_debt.balance < 0.01 && target_debt.payoff_month.is_none() {
+                    if target_debt.balance == 0 && target_debt.payoff_month.is_none() {
                         target_debt.payoff_month = Some(month);
                     }
                 }
             }
 
-            let total_paid: f64 = payments.iter().map(|p| p.amount).sum();
-            let remaining_balance: f64 = debt_states.iter().map(|d| d.balance).sum();
+            let total_paid_cents = payments.iter().map(|p| p.amount_cents).sum();
+            let remaining_balance_cents = debt_states.iter().map(|d| d.balance).sum();
 
             monthly_breakdown.push(MonthlyPayment {
                 month,
                 date: current_date.format("%Y-%m-%d").to_string(),
                 payments,
-                total_paid,
-                remaining_balance,
+                total_paid_cents,
+                remaining_balance_cents,
             });
 
             month += 1;
@@ -138,7 +156,7 @@ impl SnowballCalculator {
             }
         }
 
-        let total_interest: f64 = debt_states.iter().map(|d| d.total_interest_paid).sum();
+        let total_interest_cents = debt_states.iter().map(|d| d.total_interest_paid).sum();
         let payoff_date = monthly_breakdown.last().map(|m| m.date.clone()).unwrap_or_default();
 
         let debt_summaries: Vec<DebtSummary> = debt_states
@@ -147,14 +165,14 @@ impl SnowballCalculator {
                 debt_id: d.id,
                 debt_name: d.name.clone(),
                 payoff_month: d.payoff_month.unwrap_or(0),
-                total_interest_paid: d.total_interest_paid,
+                total_interest_paid_cents: d.total_interest_paid,
             })
             .collect();
 
         Ok(PayoffPlan {
             strategy: "snowball".to_string(),
             payoff_date,
-            total_interest,
+            total_interest_cents,
             monthly_breakdown,
             debt_summaries,
         })
@@ -165,35 +183,51 @@ impl SnowballCalculator {
 mod tests {
     use super::*;
 
+    fn start() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+    }
+
     #[test]
     fn test_snowball_calculation_prioritizes_low_balance() {
         let debts = vec![
             Debt {
                 id: 1,
                 name: "Small Balance Card".to_string(),
-                balance: 500.0,
-                original_balance: 500.0,
+                balance_cents: 50_000,
+                original_balance_cents: 50_000,
                 interest_rate: 20.0,
-                min_payment: 25.0,
+                min_payment_cents: 2500,
+                min_payment_percent: None,
+                kind: "revolving".to_string(),
+                term_months: None,
+                loan_start_date: None,
+                due_day: 15,
+                account_id: None,
                 created_at: "2025-01-01".to_string(),
                 updated_at: "2025-01-01".to_string(),
             },
             Debt {
                 id: 2,
                 name: "Large Balance Card".to_string(),
-                balance: 2000.0,
-                original_balance: 2000.0,
+                balance_cents: 200_000,
+                original_balance_cents: 200_000,
                 interest_rate: 10.0,
-                min_payment: 25.0,
+                min_payment_cents: 2500,
+                min_payment_percent: None,
+                kind: "revolving".to_string(),
+                term_months: None,
+                loan_start_date: None,
+                due_day: 15,
+                account_id: None,
                 created_at: "2025-01-01".to_string(),
                 updated_at: "2025-01-01".to_string(),
             },
         ];
 
-        let plan = SnowballCalculator::calculate_payoff_plan(debts, 200.0).unwrap();
+        let plan = SnowballCalculator::calculate_payoff_plan(debts, 20_000, start()).unwrap();
 
         assert_eq!(plan.strategy, "snowball");
-        assert!(plan.total_interest > 0.0);
+        assert!(plan.total_interest_cents > 0);
         assert!(!plan.monthly_breakdown.is_empty());
 
         // First month should have extra payment going to smallest balance debt (id: 1)
@@ -202,14 +236,14 @@ mod tests {
         let large_balance_payment = first_month.payments.iter().find(|p| p.debt_id == 2).unwrap();
 
         // Small balance debt should get more than minimum
-        assert!(small_balance_payment.amount > 25.0);
+        assert!(small_balance_payment.amount_cents > 2500);
         // Large balance debt should get only minimum
-        assert_eq!(large_balance_payment.amount, 25.0);
+        assert_eq!(large_balance_payment.amount_cents, 2500);
 
         // Verify total_paid and remaining_balance are calculated correctly
-        assert_eq!(first_month.total_paid, 200.0);
-        assert!(first_month.remaining_balance > 0.0);
-        assert!(first_month.remaining_balance < 2500.0);
+        assert_eq!(first_month.total_paid_cents, 20_000);
+        assert!(first_month.remaining_balance_cents > 0);
+        assert!(first_month.remaining_balance_cents < 250_000);
     }
 
     #[test]
@@ -217,15 +251,21 @@ mod tests {
         let debts = vec![Debt {
             id: 1,
             name: "Card".to_string(),
-            balance: 1000.0,
-            original_balance: 1000.0,
+            balance_cents: 100_000,
+            original_balance_cents: 100_000,
             interest_rate: 15.0,
-            min_payment: 50.0,
+            min_payment_cents: 5000,
+            min_payment_percent: None,
+            kind: "revolving".to_string(),
+            term_months: None,
+            loan_start_date: None,
+            due_day: 15,
+            account_id: None,
             created_at: "2025-01-01".to_string(),
             updated_at: "2025-01-01".to_string(),
         }];
 
-        let result = SnowballCalculator::calculate_payoff_plan(debts, 25.0);
+        let result = SnowballCalculator::calculate_payoff_plan(debts, 2500, start());
         assert!(result.is_err());
         let error = result.unwrap_err();
         let error_msg = error.to_string();
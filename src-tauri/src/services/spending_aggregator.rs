@@ -1,13 +1,18 @@
 use crate::constants::PERCENT_TO_DECIMAL_DIVISOR;
+use crate::models::setting::SettingKey;
+use crate::utils::money::{self, Cents};
+use crate::utils::query_builder::{cache_in_list, placeholders};
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CategorySpending {
     pub category_id: i64,
     pub category_name: String,
     pub category_icon: Option<String>,
-    pub amount: f64,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
     pub percentage: f64,
     pub transaction_count: i64,
 }
@@ -16,7 +21,64 @@ pub struct CategorySpending {
 pub struct SpendingByCategory {
     pub period: DatePeriod,
     pub categories: Vec<CategorySpending>,
-    pub total_spending: f64,
+    #[serde(rename = "total_spending", with = "money::dollars")]
+    pub total_spending_cents: Cents,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerchantSpending {
+    pub merchant_id: Option<i64>,    // None when the transactions were never linked to a merchant
+    pub merchant_name: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    pub percentage: f64,
+    pub transaction_count: i64,
+    pub average_ticket: f64,
+    #[serde(rename = "previous_amount", with = "money::dollars")]
+    pub previous_amount_cents: Cents,
+    pub change_percent: Option<f64>, // None when there was no spending in the previous period
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpendingByMerchant {
+    pub period: DatePeriod,
+    pub previous_period: DatePeriod,
+    pub merchants: Vec<MerchantSpending>,
+    #[serde(rename = "total_spending", with = "money::dollars")]
+    pub total_spending_cents: Cents,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberSpending {
+    pub member_id: Option<i64>, // None for spending not attributed to anyone
+    pub member_name: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    pub percentage: f64,
+    pub transaction_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpendingByMember {
+    pub period: DatePeriod,
+    pub members: Vec<MemberSpending>,
+    #[serde(rename = "total_spending", with = "money::dollars")]
+    pub total_spending_cents: Cents,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct LargestTransaction {
+    pub id: i64,
+    pub date: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    pub description: String,
+    pub merchant: Option<String>,
+    pub category_id: i64,
+    pub category_name: String,
+    pub category_icon: Option<String>,
+    pub account_id: i64,
+    pub account_name: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,71 +90,113 @@ pub struct DatePeriod {
 pub struct SpendingAggregator;
 
 impl SpendingAggregator {
+    /// Whether the `exclude_pending` setting leaves pending transactions out of analytics
+    /// They're counted by default, since they are money already spent
+    pub(crate) async fn excludes_pending(db: &SqlitePool) -> bool {
+        let stored: Option<(String,)> = sqlx::query_as("SELECT value FROM app_settings WHERE key = ?")
+            .bind(SettingKey::ExcludePending.to_string())
+            .fetch_optional(db)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Failed to load exclude_pending setting");
+                None
+            });
+        stored.is_some_and(|(value,)| value == "true")
+    }
+
     /// Calculate total spending per category for a time period
+    /// `member_id` limits the spending to what one household member spent
     pub async fn get_spending_by_category(
         db: &SqlitePool,
         start_date: &str,
         end_date: &str,
         account_id: Option<i64>,
+        member_id: Option<i64>,
     ) -> Result<SpendingByCategory, String> {
+        let exclude_pending = Self::excludes_pending(db).await;
         // Build query based on whether account filter is provided
         let query = if let Some(account_id) = account_id {
-            sqlx::query_as::<_, (i64, String, Option<String>, f64, i64)>(
+            sqlx::query_as::<_, (i64, String, Option<String>, Cents, i64)>(
                 "SELECT
                     c.id,
                     c.name,
                     c.icon,
-                    CAST(COALESCE(SUM(ABS(t.amount)), 0) AS REAL) as total_amount,
+                    COALESCE(SUM(-t.amount_cents), 0) as total_cents,
                     COUNT(t.id) as transaction_count
                 FROM categories c
                 LEFT JOIN transactions t ON t.category_id = c.id
                     AND t.date >= ?
                     AND t.date <= ?
-                    AND t.amount < 0
+                    AND t.amount_cents < 0
                     AND t.account_id = ?
+                    AND (? = 0 OR t.status = 'posted')
+                    AND (? IS NULL OR t.member_id = ?)
                 GROUP BY c.id, c.name, c.icon
-                HAVING total_amount > 0
-                ORDER BY total_amount DESC"
+                HAVING total_cents > 0
+                ORDER BY total_cents DESC"
             )
             .bind(start_date)
             .bind(end_date)
             .bind(account_id)
+            .bind(exclude_pending)
+            .bind(member_id)
+            .bind(member_id)
             .fetch_all(db)
             .await
         } else {
-            sqlx::query_as::<_, (i64, String, Option<String>, f64, i64)>(
+            sqlx::query_as::<_, (i64, String, Option<String>, Cents, i64)>(
                 "SELECT
                     c.id,
                     c.name,
                     c.icon,
-                    CAST(COALESCE(SUM(ABS(t.amount)), 0) AS REAL) as total_amount,
+                    COALESCE(SUM(-t.amount_cents), 0) as total_cents,
                     COUNT(t.id) as transaction_count
                 FROM categories c
                 LEFT JOIN transactions t ON t.category_id = c.id
                     AND t.date >= ?
                     AND t.date <= ?
-                    AND t.amount < 0
+                    AND t.amount_cents < 0
+                    AND (? = 0 OR t.status = 'posted')
+                    AND (? IS NULL OR t.member_id = ?)
                 GROUP BY c.id, c.name, c.icon
-                HAVING total_amount > 0
-                ORDER BY total_amount DESC"
+                HAVING total_cents > 0
+                ORDER BY total_cents DESC"
             )
             .bind(start_date)
             .bind(end_date)
+            .bind(exclude_pending)
+            .bind(member_id)
+            .bind(member_id)
             .fetch_all(db)
             .await
         };
 
         let rows = query.map_err(|e| e.to_string())?;
+        let (categories, total_spending_cents) = Self::category_spending_from_rows(rows);
+
+        Ok(SpendingByCategory {
+            period: DatePeriod {
+                start_date: start_date.to_string(),
+                end_date: end_date.to_string(),
+            },
+            categories,
+            total_spending_cents,
+        })
+    }
 
-        // Calculate total spending
-        let total_spending: f64 = rows.iter().map(|(_, _, _, amount, _)| amount).sum();
+    /// Turn (id, name, icon, cents, count) rows into category spending with percentages of their total
+    fn category_spending_from_rows(
+        rows: Vec<(i64, String, Option<String>, Cents, i64)>,
+    ) -> (Vec<CategorySpending>, Cents) {
+        // Calculate total spending in cents so it matches the category amounts exactly
+        let total_cents: Cents = rows.iter().map(|(_, _, _, cents, _)| cents).sum();
 
         // Build category spending list with percentages
         let categories = rows
             .into_iter()
-            .map(|(id, name, icon, amount, count)| {
-                let percentage = if total_spending > 0.0 {
-                    (amount / total_spending) * PERCENT_TO_DECIMAL_DIVISOR
+            .map(|(id, name, icon, cents, count)| {
+                let percentage = if total_cents > 0 {
+                    (cents as f64 / total_cents as f64) * PERCENT_TO_DECIMAL_DIVISOR
                 } else {
                     0.0
                 };
@@ -101,33 +205,95 @@ impl SpendingAggregator {
                     category_id: id,
                     category_name: name,
                     category_icon: icon,
-                    amount,
+                    amount_cents: cents,
                     percentage,
                     transaction_count: count,
                 }
             })
             .collect();
 
-        Ok(SpendingByCategory {
-            period: DatePeriod {
-                start_date: start_date.to_string(),
-                end_date: end_date.to_string(),
-            },
-            categories,
-            total_spending,
-        })
+        (categories, total_cents)
     }
 
     /// Get top N categories by spending amount
+    /// `account_ids` limits the spending to those accounts; `None` covers every account
     pub async fn get_top_categories(
         db: &SqlitePool,
         start_date: &str,
         end_date: &str,
         limit: i64,
+        account_ids: Option<&[i64]>,
     ) -> Result<Vec<CategorySpending>, String> {
-        let result = Self::get_spending_by_category(db, start_date, end_date, None).await?;
+        let Some(account_ids) = account_ids else {
+            let result = Self::get_spending_by_category(db, start_date, end_date, None, None).await?;
+            return Ok(result.categories.into_iter().take(limit as usize).collect());
+        };
+
+        let query_str = format!(
+            "SELECT
+                c.id,
+                c.name,
+                c.icon,
+                COALESCE(SUM(-t.amount_cents), 0) as total_cents,
+                COUNT(t.id) as transaction_count
+            FROM categories c
+            LEFT JOIN transactions t ON t.category_id = c.id
+                AND t.date >= ?
+                AND t.date <= ?
+                AND t.amount_cents < 0
+                AND (? = 0 OR t.status = 'posted')
+                AND t.account_id IN ({})
+            GROUP BY c.id, c.name, c.icon
+            HAVING total_cents > 0
+            ORDER BY total_cents DESC",
+            placeholders(account_ids.len())
+        );
+        let mut query = sqlx::query_as::<_, (i64, String, Option<String>, Cents, i64)>(&query_str)
+            .persistent(cache_in_list(account_ids.len()))
+            .bind(start_date)
+            .bind(end_date)
+            .bind(Self::excludes_pending(db).await);
+        for id in account_ids {
+            query = query.bind(id);
+        }
+        let rows = query.fetch_all(db).await.map_err(|e| e.to_string())?;
+
+        let (categories, _) = Self::category_spending_from_rows(rows);
+        Ok(categories.into_iter().take(limit as usize).collect())
+    }
+
+    /// Calculate (total spending, total income) for a period
+    /// `account_ids` limits the totals to those accounts; `None` covers every account
+    pub async fn get_totals(
+        db: &SqlitePool,
+        start_date: &str,
+        end_date: &str,
+        account_ids: Option<&[i64]>,
+    ) -> Result<(Cents, Cents), String> {
+        let Some(account_ids) = account_ids else {
+            let spending = Self::get_total_spending(db, start_date, end_date).await?;
+            let income = Self::get_total_income(db, start_date, end_date).await?;
+            return Ok((spending, income));
+        };
 
-        Ok(result.categories.into_iter().take(limit as usize).collect())
+        let query_str = format!(
+            "SELECT
+                COALESCE(SUM(CASE WHEN amount_cents < 0 THEN -amount_cents ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN amount_cents > 0 THEN amount_cents ELSE 0 END), 0)
+             FROM transactions
+             WHERE date >= ? AND date <= ? AND (? = 0 OR status = 'posted') AND account_id IN ({})",
+            placeholders(account_ids.len())
+        );
+        let mut query = sqlx::query_as::<_, (Cents, Cents)>(&query_str)
+            .persistent(cache_in_list(account_ids.len()))
+            .bind(start_date)
+            .bind(end_date)
+            .bind(Self::excludes_pending(db).await);
+        for id in account_ids {
+            query = query.bind(id);
+        }
+
+        query.fetch_one(db).await.map_err(|e| e.to_string())
     }
 
     /// Calculate total income for a period
@@ -135,14 +301,15 @@ impl SpendingAggregator {
         db: &SqlitePool,
         start_date: &str,
         end_date: &str,
-    ) -> Result<f64, String> {
-        let result = sqlx::query_as::<_, (f64,)>(
-            "SELECT CAST(COALESCE(SUM(amount), 0) AS REAL)
+    ) -> Result<Cents, String> {
+        let result = sqlx::query_as::<_, (Cents,)>(
+            "SELECT COALESCE(SUM(amount_cents), 0)
              FROM transactions
-             WHERE date >= ? AND date <= ? AND amount > 0"
+             WHERE date >= ? AND date <= ? AND amount_cents > 0 AND (? = 0 OR status = 'posted')"
         )
         .bind(start_date)
         .bind(end_date)
+        .bind(Self::excludes_pending(db).await)
         .fetch_one(db)
         .await
         .map_err(|e| e.to_string())?;
@@ -155,18 +322,243 @@ impl SpendingAggregator {
         db: &SqlitePool,
         start_date: &str,
         end_date: &str,
-    ) -> Result<f64, String> {
-        let result = sqlx::query_as::<_, (f64,)>(
-            "SELECT CAST(COALESCE(SUM(ABS(amount)), 0) AS REAL)
+    ) -> Result<Cents, String> {
+        let result = sqlx::query_as::<_, (Cents,)>(
+            "SELECT COALESCE(SUM(-amount_cents), 0)
              FROM transactions
-             WHERE date >= ? AND date <= ? AND amount < 0"
+             WHERE date >= ? AND date <= ? AND amount_cents < 0 AND (? = 0 OR status = 'posted')"
         )
         .bind(start_date)
         .bind(end_date)
+        .bind(Self::excludes_pending(db).await)
         .fetch_one(db)
         .await
         .map_err(|e| e.to_string())?;
 
         Ok(result.0)
     }
+
+    /// Get the largest individual transactions for a period
+    /// Expenses are ranked by absolute amount (most negative first), income by amount
+    /// `account_id` limits them to one account; `None` covers every account
+    pub async fn get_largest_transactions(
+        db: &SqlitePool,
+        start_date: &str,
+        end_date: &str,
+        limit: i64,
+        expenses: bool,
+        account_id: Option<i64>,
+    ) -> Result<Vec<LargestTransaction>, String> {
+        let query = if expenses {
+            "SELECT
+                t.id, t.date, t.amount_cents, t.description, t.merchant,
+                t.category_id, c.name as category_name, c.icon as category_icon,
+                t.account_id, a.name as account_name
+            FROM transactions t
+            JOIN categories c ON c.id = t.category_id
+            JOIN accounts a ON a.id = t.account_id
+            WHERE t.date >= ? AND t.date <= ? AND t.amount_cents < 0
+                AND (? IS NULL OR t.account_id = ?)
+                AND (? = 0 OR t.status = 'posted')
+            ORDER BY t.amount_cents ASC, t.date DESC
+            LIMIT ?"
+        } else {
+            "SELECT
+                t.id, t.date, t.amount_cents, t.description, t.merchant,
+                t.category_id, c.name as category_name, c.icon as category_icon,
+                t.account_id, a.name as account_name
+            FROM transactions t
+            JOIN categories c ON c.id = t.category_id
+            JOIN accounts a ON a.id = t.account_id
+            WHERE t.date >= ? AND t.date <= ? AND t.amount_cents > 0
+                AND (? IS NULL OR t.account_id = ?)
+                AND (? = 0 OR t.status = 'posted')
+            ORDER BY t.amount_cents DESC, t.date DESC
+            LIMIT ?"
+        };
+
+        sqlx::query_as::<_, LargestTransaction>(query)
+            .bind(start_date)
+            .bind(end_date)
+            .bind(account_id)
+            .bind(account_id)
+            .bind(Self::excludes_pending(db).await)
+            .bind(limit)
+            .fetch_all(db)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Sum expenses per merchant for a period
+    /// Linked merchants are grouped under their canonical name, unlinked ones under the raw merchant text;
+    /// transactions without any merchant are left out
+    /// Returns (merchant_id, merchant_name, amount, count) ordered by amount
+    async fn merchant_totals(
+        db: &SqlitePool,
+        start_date: &str,
+        end_date: &str,
+        account_id: Option<i64>,
+    ) -> Result<Vec<(Option<i64>, String, Cents, i64)>, String> {
+        sqlx::query_as::<_, (Option<i64>, String, Cents, i64)>(
+            "SELECT
+                MAX(t.merchant_id),
+                COALESCE(m.name, t.merchant) as merchant_name,
+                SUM(-t.amount_cents) as amount,
+                COUNT(t.id) as count
+            FROM transactions t
+            LEFT JOIN merchants m ON m.id = t.merchant_id
+            WHERE t.date >= ? AND t.date <= ? AND t.amount_cents < 0
+                AND COALESCE(m.name, t.merchant) IS NOT NULL
+                AND (? IS NULL OR t.account_id = ?)
+                AND (? = 0 OR t.status = 'posted')
+            GROUP BY COALESCE(m.name, t.merchant)
+            ORDER BY amount DESC, merchant_name ASC"
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(account_id)
+        .bind(account_id)
+        .bind(Self::excludes_pending(db).await)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Total spending per household member for a period, largest first, with spending nobody
+    /// is attributed to as its own row
+    pub async fn get_spending_by_member(
+        db: &SqlitePool,
+        start_date: &str,
+        end_date: &str,
+        account_id: Option<i64>,
+    ) -> Result<SpendingByMember, String> {
+        let exclude_pending = Self::excludes_pending(db).await;
+        let rows = sqlx::query_as::<_, (Option<i64>, Option<String>, Cents, i64)>(
+            "SELECT
+                m.id,
+                m.name,
+                SUM(-t.amount_cents) as total_cents,
+                COUNT(t.id) as transaction_count
+            FROM transactions t
+            LEFT JOIN members m ON m.id = t.member_id
+            WHERE t.date >= ?
+                AND t.date <= ?
+                AND t.amount_cents < 0
+                AND (? IS NULL OR t.account_id = ?)
+                AND (? = 0 OR t.status = 'posted')
+            GROUP BY m.id, m.name
+            ORDER BY total_cents DESC"
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(account_id)
+        .bind(account_id)
+        .bind(exclude_pending)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let total_cents: Cents = rows.iter().map(|(_, _, cents, _)| cents).sum();
+        let members = rows
+            .into_iter()
+            .map(|(member_id, name, cents, count)| MemberSpending {
+                member_id,
+                member_name: name.unwrap_or_else(|| "Unassigned".to_string()),
+                amount_cents: cents,
+                percentage: if total_cents > 0 {
+                    (cents as f64 / total_cents as f64) * PERCENT_TO_DECIMAL_DIVISOR
+                } else {
+                    0.0
+                },
+                transaction_count: count,
+            })
+            .collect();
+
+        Ok(SpendingByMember {
+            period: DatePeriod {
+                start_date: start_date.to_string(),
+                end_date: end_date.to_string(),
+            },
+            members,
+            total_spending_cents: total_cents,
+        })
+    }
+
+    /// Get the top merchants by spending for a period, compared against the
+    /// equal-length period immediately before it
+    pub async fn get_spending_by_merchant(
+        db: &SqlitePool,
+        start_date: &str,
+        end_date: &str,
+        account_id: Option<i64>,
+        limit: usize,
+    ) -> Result<SpendingByMerchant, String> {
+        let start = chrono::NaiveDate::parse_from_str(start_date, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid start date: {}", start_date))?;
+        let end = chrono::NaiveDate::parse_from_str(end_date, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid end date: {}", end_date))?;
+        if end < start {
+            return Err("End date must not be before start date".to_string());
+        }
+
+        let previous_end = start - chrono::Duration::days(1);
+        let previous_start = previous_end - (end - start);
+        let previous_period = DatePeriod {
+            start_date: previous_start.format("%Y-%m-%d").to_string(),
+            end_date: previous_end.format("%Y-%m-%d").to_string(),
+        };
+
+        let current = Self::merchant_totals(db, start_date, end_date, account_id).await?;
+        let previous: HashMap<String, Cents> = Self::merchant_totals(
+            db,
+            &previous_period.start_date,
+            &previous_period.end_date,
+            account_id,
+        )
+        .await?
+        .into_iter()
+        .map(|(_, name, amount, _)| (name, amount))
+        .collect();
+
+        let total_spending_cents: Cents = current.iter().map(|(_, _, amount, _)| amount).sum();
+
+        let merchants = current
+            .into_iter()
+            .take(limit)
+            .map(|(merchant_id, merchant_name, amount, count)| {
+                let percentage = if total_spending_cents > 0 {
+                    (amount as f64 / total_spending_cents as f64) * PERCENT_TO_DECIMAL_DIVISOR
+                } else {
+                    0.0
+                };
+                let previous_amount = previous.get(&merchant_name).copied().unwrap_or(0);
+                let change_percent = if previous_amount > 0 {
+                    Some((amount - previous_amount) as f64 / previous_amount as f64 * PERCENT_TO_DECIMAL_DIVISOR)
+                } else {
+                    None
+                };
+
+                MerchantSpending {
+                    merchant_id,
+                    merchant_name,
+                    amount_cents: amount,
+                    percentage,
+                    transaction_count: count,
+                    average_ticket: if count > 0 { money::from_cents(amount) / count as f64 } else { 0.0 },
+                    previous_amount_cents: previous_amount,
+                    change_percent,
+                }
+            })
+            .collect();
+
+        Ok(SpendingByMerchant {
+            period: DatePeriod {
+                start_date: start_date.to_string(),
+                end_date: end_date.to_string(),
+            },
+            previous_period,
+            merchants,
+            total_spending_cents,
+        })
+    }
 }
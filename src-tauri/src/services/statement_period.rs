@@ -0,0 +1,212 @@
+use crate::models::account::StatementSummary;
+use chrono::{Datelike, Months, NaiveDate};
+use sqlx::SqlitePool;
+
+pub struct StatementPeriod;
+
+impl StatementPeriod {
+    /// Statement closing date in a month; a closing day past the month's end (or None) is its last day
+    pub fn closing_date(year: i32, month: u32, closing_day: Option<u32>) -> Option<NaiveDate> {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let last = first.checked_add_months(Months::new(1))?.pred_opt()?;
+        match closing_day {
+            Some(day) if day < last.day() => first.with_day(day.max(1)),
+            _ => Some(last),
+        }
+    }
+
+    /// The statement period (start, end) containing `date`
+    pub fn containing(date: NaiveDate, closing_day: Option<u32>) -> Option<(NaiveDate, NaiveDate)> {
+        let closing = |d: NaiveDate| Self::closing_date(d.year(), d.month(), closing_day);
+        let this_close = closing(date)?;
+        let (previous_close, end) = if date <= this_close {
+            (closing(date.with_day(1)?.checked_sub_months(Months::new(1))?)?, this_close)
+        } else {
+            (this_close, closing(date.with_day(1)?.checked_add_months(Months::new(1))?)?)
+        };
+        Some((previous_close.succ_opt()?, end))
+    }
+
+    /// Payment due date for a statement closing on `closing`: the first `due_day` after it
+    /// A due day past the end of a short month falls on its last day
+    pub fn due_date(closing: NaiveDate, due_day: u32) -> Option<NaiveDate> {
+        let this_month = Self::closing_date(closing.year(), closing.month(), Some(due_day))?;
+        if this_month > closing {
+            return Some(this_month);
+        }
+        let next = closing.with_day(1)?.checked_add_months(Months::new(1))?;
+        Self::closing_date(next.year(), next.month(), Some(due_day))
+    }
+
+    /// Statement periods overlapping `start` through `end`, in order
+    pub fn periods_between(
+        start: NaiveDate,
+        end: NaiveDate,
+        closing_day: Option<u32>,
+    ) -> Vec<(NaiveDate, NaiveDate)> {
+        let mut periods = Vec::new();
+        let mut current = Self::containing(start, closing_day);
+        while let Some((period_start, period_end)) = current {
+            if period_start > end {
+                break;
+            }
+            periods.push((period_start, period_end));
+            current = period_end.succ_opt().and_then(|next| Self::containing(next, closing_day));
+        }
+        periods
+    }
+
+    /// Charges, payments and balances for the statement period of `account_id` containing `date`
+    pub async fn summarize(
+        db: &SqlitePool,
+        account_id: i64,
+        date: NaiveDate,
+        today: NaiveDate,
+    ) -> Result<StatementSummary, String> {
+        let (balance, closing_day, due_day) = sqlx::query_as::<_, (i64, Option<i64>, Option<i64>)>(
+            "SELECT balance_cents, statement_closing_day, statement_due_day FROM accounts WHERE id = ?"
+        )
+        .bind(account_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Account with id {} not found", account_id))?;
+        let closing_day = closing_day.and_then(|day| u32::try_from(day).ok());
+        let due_day = due_day.and_then(|day| u32::try_from(day).ok());
+
+        let (start, end) = Self::containing(date, closing_day).ok_or("Date calculation error")?;
+        let start_str = start.format("%Y-%m-%d").to_string();
+        let end_str = end.format("%Y-%m-%d").to_string();
+
+        // The account balance is its opening balance; transactions before the period carry it forward
+        let (earlier, charges, payments, transaction_count) = sqlx::query_as::<_, (i64, i64, i64, i64)>(
+            "SELECT COALESCE(SUM(CASE WHEN date < ?1 THEN amount_cents END), 0),
+                    COALESCE(SUM(CASE WHEN date >= ?1 AND amount_cents < 0 THEN -amount_cents END), 0),
+                    COALESCE(SUM(CASE WHEN date >= ?1 AND amount_cents > 0 THEN amount_cents END), 0),
+                    COUNT(CASE WHEN date >= ?1 THEN 1 END)
+             FROM transactions
+             WHERE account_id = ?2 AND date <= ?3"
+        )
+        .bind(&start_str)
+        .bind(account_id)
+        .bind(&end_str)
+        .fetch_one(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let linked_debt_id = sqlx::query_as::<_, (i64,)>("SELECT id FROM debts WHERE account_id = ? ORDER BY id LIMIT 1")
+            .bind(account_id)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| e.to_string())?
+            .map(|row| row.0);
+
+        let opening_balance = balance + earlier;
+        let closing_balance = opening_balance - charges + payments;
+        Ok(StatementSummary {
+            account_id,
+            period_start: start_str,
+            period_end: end_str,
+            due_date: due_day
+                .and_then(|day| Self::due_date(end, day))
+                .map(|d| d.format("%Y-%m-%d").to_string()),
+            opening_balance_cents: opening_balance,
+            charges_cents: charges,
+            payments_cents: payments,
+            closing_balance_cents: closing_balance,
+            amount_due_cents: (-closing_balance).max(0),
+            transaction_count,
+            closed: end < today,
+            linked_debt_id,
+        })
+    }
+
+    /// An account's closing day, or None when it uses calendar months
+    pub async fn closing_day(db: &SqlitePool, account_id: i64) -> Result<Option<u32>, String> {
+        let row = sqlx::query_as::<_, (Option<i64>,)>("SELECT statement_closing_day FROM accounts WHERE id = ?")
+            .bind(account_id)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Account with id {} not found", account_id))?;
+        Ok(row.0.and_then(|day| u32::try_from(day).ok()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_closing_date_clamps_to_month_end() {
+        assert_eq!(StatementPeriod::closing_date(2025, 2, Some(30)), Some(date("2025-02-28")));
+        assert_eq!(StatementPeriod::closing_date(2024, 2, Some(30)), Some(date("2024-02-29")));
+        assert_eq!(StatementPeriod::closing_date(2025, 3, Some(12)), Some(date("2025-03-12")));
+        assert_eq!(StatementPeriod::closing_date(2025, 4, None), Some(date("2025-04-30")));
+    }
+
+    #[test]
+    fn test_containing() {
+        // Closes on the 20th: Feb 21 - Mar 20
+        assert_eq!(
+            StatementPeriod::containing(date("2025-03-05"), Some(20)),
+            Some((date("2025-02-21"), date("2025-03-20")))
+        );
+        assert_eq!(
+            StatementPeriod::containing(date("2025-03-20"), Some(20)),
+            Some((date("2025-02-21"), date("2025-03-20")))
+        );
+        assert_eq!(
+            StatementPeriod::containing(date("2025-03-21"), Some(20)),
+            Some((date("2025-03-21"), date("2025-04-20")))
+        );
+        // Across a year boundary
+        assert_eq!(
+            StatementPeriod::containing(date("2025-01-02"), Some(5)),
+            Some((date("2024-12-06"), date("2025-01-05")))
+        );
+        // Calendar months
+        assert_eq!(
+            StatementPeriod::containing(date("2025-02-14"), None),
+            Some((date("2025-02-01"), date("2025-02-28")))
+        );
+    }
+
+    #[test]
+    fn test_containing_after_short_month() {
+        // Closes on the 30th: Feb closes on the 28th, so March's period starts Mar 1
+        assert_eq!(
+            StatementPeriod::containing(date("2025-03-10"), Some(30)),
+            Some((date("2025-03-01"), date("2025-03-30")))
+        );
+    }
+
+    #[test]
+    fn test_due_date() {
+        // Due later in the closing month, or in the following one
+        assert_eq!(StatementPeriod::due_date(date("2025-03-05"), 28), Some(date("2025-03-28")));
+        assert_eq!(StatementPeriod::due_date(date("2025-03-20"), 15), Some(date("2025-04-15")));
+        assert_eq!(StatementPeriod::due_date(date("2025-03-20"), 20), Some(date("2025-04-20")));
+        assert_eq!(StatementPeriod::due_date(date("2025-01-31"), 31), Some(date("2025-02-28")));
+        assert_eq!(StatementPeriod::due_date(date("2024-12-10"), 5), Some(date("2025-01-05")));
+    }
+
+    #[test]
+    fn test_periods_between() {
+        let periods = StatementPeriod::periods_between(date("2025-01-10"), date("2025-03-25"), Some(15));
+        assert_eq!(
+            periods,
+            vec![
+                (date("2024-12-16"), date("2025-01-15")),
+                (date("2025-01-16"), date("2025-02-15")),
+                (date("2025-02-16"), date("2025-03-15")),
+                (date("2025-03-16"), date("2025-04-15")),
+            ]
+        );
+        assert!(StatementPeriod::periods_between(date("2025-03-01"), date("2025-01-01"), Some(15)).is_empty());
+    }
+}
@@ -0,0 +1,252 @@
+use crate::constants::{NO_SPEND_LOOKBACK_DAYS, STREAK_LOOKBACK_MONTHS};
+use crate::services::payment_scheduler::PaymentScheduler;
+use crate::services::target_tracker::TargetTracker;
+use crate::utils::money::Cents;
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreakMetric {
+    pub current: i64,
+    pub longest: i64, // Within the lookback window
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Streaks {
+    pub as_of: String,
+    pub under_budget_months: StreakMetric, // Completed months with targets and none over
+    pub on_time_payment_months: StreakMetric, // Completed months where every open debt got its minimum by the due date
+    pub no_spend_days: StreakMetric, // Days without any expense, ending today
+}
+
+/// A debt payment used to check on-time streaks
+#[derive(Debug, Clone)]
+pub struct PaymentRecord {
+    pub debt_id: i64,
+    pub date: NaiveDate,
+    pub amount_cents: Cents,
+}
+
+/// An open debt with the date it was added
+#[derive(Debug, Clone)]
+pub struct OpenDebt {
+    pub id: i64,
+    pub min_payment_cents: Cents,
+    pub due_day: u32,
+    pub created: NaiveDate,
+}
+
+pub struct StreakTracker;
+
+impl StreakTracker {
+    fn format_date(date: NaiveDate) -> String {
+        date.format("%Y-%m-%d").to_string()
+    }
+
+    /// Current (trailing) and longest runs of `true` in chronologically ordered flags
+    pub fn run_lengths(flags: &[bool]) -> StreakMetric {
+        let mut metric = StreakMetric::default();
+        for &flag in flags {
+            metric.current = if flag { metric.current + 1 } else { 0 };
+            metric.longest = metric.longest.max(metric.current);
+        }
+        metric
+    }
+
+    /// First day of each completed month in the lookback window, oldest first
+    fn completed_months(as_of: NaiveDate) -> Vec<NaiveDate> {
+        let this_month = as_of.with_day(1).unwrap_or(as_of);
+        (1..=STREAK_LOOKBACK_MONTHS)
+            .rev()
+            .filter_map(|back| this_month.checked_sub_months(Months::new(back)))
+            .collect()
+    }
+
+    /// Whether every debt open by a month's due date received its minimum payment
+    /// between the previous due date (exclusive) and this one (inclusive)
+    /// Months with no debts to pay don't count toward the streak
+    pub fn paid_on_time(month: NaiveDate, debts: &[OpenDebt], payments: &[PaymentRecord]) -> bool {
+        let Some(previous_month) = month.checked_sub_months(Months::new(1)) else {
+            return false;
+        };
+
+        // (debt, previous due date, due date) for each debt open by its due date
+        let owed: Vec<(&OpenDebt, NaiveDate, NaiveDate)> = debts
+            .iter()
+            .filter(|d| d.min_payment_cents > 0)
+            .map(|debt| {
+                let due = PaymentScheduler::next_due_date_on(month, debt.due_day);
+                let previous_due = PaymentScheduler::next_due_date_on(previous_month, debt.due_day);
+                (debt, previous_due, due)
+            })
+            .filter(|(debt, _, due)| debt.created <= *due)
+            .collect();
+
+        !owed.is_empty()
+            && owed.iter().all(|&(debt, previous_due, due)| {
+                let paid: Cents = payments
+                    .iter()
+                    .filter(|p| p.debt_id == debt.id && p.date > previous_due && p.date <= due)
+                    .map(|p| p.amount_cents)
+                    .sum();
+                paid >= debt.min_payment_cents
+            })
+    }
+
+    /// Compute all streaks as of `as_of`
+    pub async fn get_streaks(db: &SqlitePool, as_of: NaiveDate) -> Result<Streaks, String> {
+        let months = Self::completed_months(as_of);
+
+        let mut under_budget = Vec::with_capacity(months.len());
+        for month in &months {
+            let month_end = month
+                .checked_add_months(Months::new(1))
+                .and_then(|next| next.pred_opt())
+                .unwrap_or(*month);
+            let progress = TargetTracker::get_targets_progress(
+                db,
+                &Self::format_date(*month),
+                &Self::format_date(month_end),
+            )
+            .await?;
+            under_budget.push(!progress.targets.is_empty() && progress.targets.iter().all(|t| t.status != "over"));
+        }
+
+        let debts: Vec<OpenDebt> = sqlx::query_as::<_, (i64, Cents, u32, String)>(
+            "SELECT id, min_payment_cents, due_day, created_at FROM debts WHERE balance_cents > 0"
+        )
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|(id, min_payment_cents, due_day, created_at)| OpenDebt {
+            id,
+            min_payment_cents,
+            due_day,
+            // created_at is "YYYY-MM-DD HH:MM:SS"
+            created: NaiveDate::parse_from_str(created_at.get(..10).unwrap_or(&created_at), "%Y-%m-%d")
+                .unwrap_or(NaiveDate::MIN),
+        })
+        .collect();
+
+        let window_start = months.first().copied().unwrap_or(as_of);
+        let payments: Vec<PaymentRecord> = sqlx::query_as::<_, (i64, String, Cents)>(
+            "SELECT p.debt_id, p.date, p.amount_cents
+             FROM debt_payments p
+             JOIN debts d ON d.id = p.debt_id
+             WHERE d.balance_cents > 0 AND p.date >= ? AND p.date <= ?"
+        )
+        .bind(Self::format_date(window_start.checked_sub_months(Months::new(1)).unwrap_or(window_start)))
+        .bind(Self::format_date(as_of))
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter_map(|(debt_id, date, amount_cents)| {
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .ok()
+                .map(|date| PaymentRecord { debt_id, date, amount_cents })
+        })
+        .collect();
+
+        let on_time: Vec<bool> = months
+            .iter()
+            .map(|month| Self::paid_on_time(*month, &debts, &payments))
+            .collect();
+
+        let first_day = as_of - chrono::Duration::days(NO_SPEND_LOOKBACK_DAYS - 1);
+        let spend_days: HashSet<String> = sqlx::query_as::<_, (String,)>(
+            "SELECT DISTINCT date FROM transactions WHERE amount_cents < 0 AND date >= ? AND date <= ?"
+        )
+        .bind(Self::format_date(first_day))
+        .bind(Self::format_date(as_of))
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|(date,)| date)
+        .collect();
+        let no_spend: Vec<bool> = first_day
+            .iter_days()
+            .take_while(|day| *day <= as_of)
+            .map(|day| !spend_days.contains(&Self::format_date(day)))
+            .collect();
+
+        Ok(Streaks {
+            as_of: Self::format_date(as_of),
+            under_budget_months: Self::run_lengths(&under_budget),
+            on_time_payment_months: Self::run_lengths(&on_time),
+            no_spend_days: Self::run_lengths(&no_spend),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn payment(debt_id: i64, day: &str, amount_cents: Cents) -> PaymentRecord {
+        PaymentRecord {
+            debt_id,
+            date: date(day),
+            amount_cents,
+        }
+    }
+
+    #[test]
+    fn test_run_lengths() {
+        assert_eq!(StreakTracker::run_lengths(&[]), StreakMetric { current: 0, longest: 0 });
+        assert_eq!(
+            StreakTracker::run_lengths(&[true, true, true, false, true, true]),
+            StreakMetric { current: 2, longest: 3 }
+        );
+        assert_eq!(StreakTracker::run_lengths(&[true, false]), StreakMetric { current: 0, longest: 1 });
+    }
+
+    #[test]
+    fn test_completed_months_excludes_current_month() {
+        let months = StreakTracker::completed_months(date("2025-03-20"));
+        assert_eq!(months.len(), STREAK_LOOKBACK_MONTHS as usize);
+        assert_eq!(*months.last().unwrap(), date("2025-02-01"));
+        assert!(months.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_paid_on_time() {
+        let debts = vec![
+            OpenDebt { id: 1, min_payment_cents: 5000, due_day: 15, created: date("2024-01-01") },
+            OpenDebt { id: 2, min_payment_cents: 10000, due_day: 15, created: date("2025-02-20") },
+        ];
+        let payments = vec![
+            payment(1, "2025-01-10", 5000),
+            payment(1, "2025-02-16", 2000), // Early for the March due date
+            payment(1, "2025-03-15", 3000),
+            payment(2, "2025-03-01", 10000),
+        ];
+
+        assert!(StreakTracker::paid_on_time(date("2025-01-01"), &debts, &payments));
+        // Nothing paid between Jan 16 and Feb 15
+        assert!(!StreakTracker::paid_on_time(date("2025-02-01"), &debts, &payments));
+        assert!(StreakTracker::paid_on_time(date("2025-03-01"), &debts, &payments));
+        // Debt 2 existed by the April due date but wasn't paid
+        assert!(!StreakTracker::paid_on_time(date("2025-04-01"), &debts, &payments));
+        // No debts means no streak credit
+        assert!(!StreakTracker::paid_on_time(date("2025-01-01"), &[], &payments));
+    }
+
+    #[test]
+    fn test_paid_on_time_uses_each_debts_due_day() {
+        let debts = vec![OpenDebt { id: 1, min_payment_cents: 5000, due_day: 28, created: date("2024-01-01") }];
+        // Paid after the 15th but before the 28th
+        let payments = vec![payment(1, "2025-03-20", 5000)];
+
+        assert!(StreakTracker::paid_on_time(date("2025-03-01"), &debts, &payments));
+        assert!(!StreakTracker::paid_on_time(date("2025-04-01"), &debts, &payments));
+    }
+}
@@ -1,17 +1,27 @@
 use crate::constants::{PERCENT_TO_DECIMAL_DIVISOR, SPENDING_ON_TRACK_THRESHOLD_PERCENT, SPENDING_UNDER_THRESHOLD_PERCENT};
+use crate::models::spending_target::{NewSpendingTarget, TargetScope};
+use crate::utils::money::{self, Cents};
+use crate::utils::query_builder::UpdateBuilder;
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TargetProgress {
-    pub category_id: i64,
-    pub category_name: String,
-    pub target_amount: f64,
-    pub actual_amount: f64,
-    pub remaining: f64,
+    pub target_id: i64,
+    pub scope: String,            // "category", "account", "overall"
+    pub category_id: Option<i64>, // Set for category targets
+    pub account_id: Option<i64>,  // Set for account targets
+    pub category_name: String,    // The category or account name, or "All spending"
+    #[serde(rename = "target_amount", with = "money::dollars")]
+    pub target_amount_cents: Cents,
+    #[serde(rename = "actual_amount", with = "money::dollars")]
+    pub actual_amount_cents: Cents,
+    #[serde(rename = "remaining", with = "money::dollars")]
+    pub remaining_cents: Cents,
     pub percentage_used: f64,
     pub status: String, // "under", "on_track", "over"
-    pub variance: f64,
+    #[serde(rename = "variance", with = "money::dollars")]
+    pub variance_cents: Cents,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,18 +39,61 @@ pub struct DatePeriod {
 
 pub struct TargetTracker;
 
+/// Display name of an overall target
+const OVERALL_TARGET_NAME: &str = "All spending";
+
 impl TargetTracker {
+    /// Id of another target over the same scope and period as `target` whose dates overlap it
+    async fn find_overlapping_target(
+        db: &SqlitePool,
+        target: &NewSpendingTarget,
+        exclude_id: Option<i64>,
+    ) -> Result<Option<i64>, String> {
+        let scope = TargetScope::from_ids(target.category_id, target.account_id)?;
+        sqlx::query_scalar(
+            "SELECT id FROM spending_targets
+             WHERE scope = ? AND category_id IS ? AND account_id IS ? AND period = ?
+               AND (end_date IS NULL OR end_date >= ?)
+               AND (? IS NULL OR start_date <= ?)
+               AND id IS NOT ?
+             LIMIT 1"
+        )
+        .bind(scope.to_string())
+        .bind(target.category_id)
+        .bind(target.account_id)
+        .bind(&target.period)
+        .bind(&target.start_date)
+        .bind(&target.end_date)
+        .bind(&target.end_date)
+        .bind(exclude_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    fn overlap_error(scope: TargetScope, period: &str, existing_id: i64) -> String {
+        let subject = match scope {
+            TargetScope::Category => "this category",
+            TargetScope::Account => "this account",
+            TargetScope::Overall => "overall spending",
+        };
+        format!("A {} target for {} already covers these dates (target {})", period, subject, existing_id)
+    }
+
     /// Get progress against spending targets
     pub async fn get_targets_progress(
         db: &SqlitePool,
         start_date: &str,
         end_date: &str,
     ) -> Result<TargetsProgress, String> {
-        // Get all active targets for the period
-        let targets = sqlx::query_as::<_, (i64, i64, String, f64)>(
-            "SELECT id, category_id, (SELECT name FROM categories WHERE id = category_id) as category_name, amount
-             FROM spending_targets
-             WHERE (start_date <= ? AND (end_date IS NULL OR end_date >= ?))"
+        // Get all active targets for the period; overall targets first, then accounts, then categories
+        let targets = sqlx::query_as::<_, (i64, String, Option<i64>, Option<i64>, Option<String>, Cents)>(
+            "SELECT st.id, st.scope, st.category_id, st.account_id, COALESCE(c.name, a.name), st.amount_cents
+             FROM spending_targets st
+             LEFT JOIN categories c ON c.id = st.category_id
+             LEFT JOIN accounts a ON a.id = st.account_id
+             WHERE (st.start_date <= ? AND (st.end_date IS NULL OR st.end_date >= ?))
+             ORDER BY CASE st.scope WHEN 'overall' THEN 0 WHEN 'account' THEN 1 ELSE 2 END, st.id"
         )
         .bind(end_date)
         .bind(start_date)
@@ -53,17 +106,21 @@ impl TargetTracker {
         let mut on_track_count = 0;
         let mut over_count = 0;
 
-        for (_, category_id, category_name, target_amount) in targets {
-            // Get actual spending for this category in the period
-            let actual_amount = sqlx::query_as::<_, (f64,)>(
-                "SELECT CAST(COALESCE(SUM(ABS(amount)), 0) AS REAL)
+        for (target_id, scope, category_id, account_id, name, target_amount_cents) in targets {
+            // Get actual spending in the period; unset ids don't narrow it
+            let actual_cents = sqlx::query_as::<_, (Cents,)>(
+                "SELECT COALESCE(SUM(-amount_cents), 0)
                  FROM transactions
-                 WHERE category_id = ?
+                 WHERE (? IS NULL OR category_id = ?)
+                   AND (? IS NULL OR account_id = ?)
                    AND date >= ?
                    AND date <= ?
-                   AND amount < 0"
+                   AND amount_cents < 0"
             )
             .bind(category_id)
+            .bind(category_id)
+            .bind(account_id)
+            .bind(account_id)
             .bind(start_date)
             .bind(end_date)
             .fetch_one(db)
@@ -71,13 +128,11 @@ impl TargetTracker {
             .map_err(|e| e.to_string())?
             .0;
 
-            let remaining = target_amount - actual_amount;
-            let percentage_used = if target_amount > 0.0 {
-                (actual_amount / target_amount) * PERCENT_TO_DECIMAL_DIVISOR
+            let percentage_used = if target_amount_cents > 0 {
+                (actual_cents as f64 / target_amount_cents as f64) * PERCENT_TO_DECIMAL_DIVISOR
             } else {
                 0.0
             };
-            let variance = actual_amount - target_amount;
 
             // Determine status
             // under: < 80%, on_track: 80-100%, over: > 100%
@@ -93,14 +148,17 @@ impl TargetTracker {
             };
 
             target_progress_list.push(TargetProgress {
+                target_id,
+                scope,
                 category_id,
-                category_name,
-                target_amount,
-                actual_amount,
-                remaining,
+                account_id,
+                category_name: name.unwrap_or_else(|| OVERALL_TARGET_NAME.to_string()),
+                target_amount_cents,
+                actual_amount_cents: actual_cents,
+                remaining_cents: target_amount_cents - actual_cents,
                 percentage_used,
                 status,
-                variance,
+                variance_cents: actual_cents - target_amount_cents,
             });
         }
 
@@ -127,24 +185,25 @@ impl TargetTracker {
         })
     }
 
-    /// Create a spending target
-    pub async fn create_target(
-        db: &SqlitePool,
-        category_id: i64,
-        amount: f64,
-        period: &str,
-        start_date: &str,
-        end_date: Option<&str>,
-    ) -> Result<i64, String> {
+    /// Create a spending target for a category, an account, or (with neither) all spending
+    /// Fails if a target over the same scope and period already covers any of its dates
+    pub async fn create_target(db: &SqlitePool, target: &NewSpendingTarget) -> Result<i64, String> {
+        let scope = TargetScope::from_ids(target.category_id, target.account_id)?;
+        if let Some(existing_id) = Self::find_overlapping_target(db, target, None).await? {
+            return Err(Self::overlap_error(scope, &target.period, existing_id));
+        }
+
         let result = sqlx::query(
-            "INSERT INTO spending_targets (category_id, amount, period, start_date, end_date)
-             VALUES (?, ?, ?, ?, ?)"
+            "INSERT INTO spending_targets (scope, category_id, account_id, amount_cents, period, start_date, end_date)
+             VALUES (?, ?, ?, ?, ?, ?, ?)"
         )
-        .bind(category_id)
-        .bind(amount)
-        .bind(period)
-        .bind(start_date)
-        .bind(end_date)
+        .bind(scope.to_string())
+        .bind(target.category_id)
+        .bind(target.account_id)
+        .bind(target.amount_cents)
+        .bind(&target.period)
+        .bind(&target.start_date)
+        .bind(&target.end_date)
         .execute(db)
         .await
         .map_err(|e| e.to_string())?;
@@ -156,39 +215,45 @@ impl TargetTracker {
     pub async fn update_target(
         db: &SqlitePool,
         target_id: i64,
-        amount: Option<f64>,
+        amount_cents: Option<Cents>,
         end_date: Option<&str>,
     ) -> Result<bool, String> {
         // Check if target exists
-        let exists = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM spending_targets WHERE id = ?")
-            .bind(target_id)
-            .fetch_one(db)
-            .await
-            .map_err(|e| e.to_string())?
-            .0 > 0;
+        let existing = sqlx::query_as::<_, (Option<i64>, Option<i64>, Cents, String, String)>(
+            "SELECT category_id, account_id, amount_cents, period, start_date FROM spending_targets WHERE id = ?"
+        )
+        .bind(target_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| e.to_string())?;
 
-        if !exists {
+        let Some((category_id, account_id, current_amount, period, start_date)) = existing else {
             return Err("Target not found".to_string());
-        }
+        };
 
-        // Build update query based on what's being updated
-        if let Some(amt) = amount {
-            sqlx::query("UPDATE spending_targets SET amount = ? WHERE id = ?")
-                .bind(amt)
-                .bind(target_id)
-                .execute(db)
-                .await
-                .map_err(|e| e.to_string())?;
+        // A new end date can run into a later target
+        if let Some(end_date) = end_date {
+            let updated = NewSpendingTarget {
+                category_id,
+                account_id,
+                amount_cents: amount_cents.unwrap_or(current_amount),
+                period,
+                start_date,
+                end_date: Some(end_date.to_string()),
+            };
+            if let Some(existing_id) = Self::find_overlapping_target(db, &updated, Some(target_id)).await? {
+                let scope = TargetScope::from_ids(category_id, account_id)?;
+                return Err(Self::overlap_error(scope, &updated.period, existing_id));
+            }
         }
 
-        if let Some(date) = end_date {
-            sqlx::query("UPDATE spending_targets SET end_date = ? WHERE id = ?")
-                .bind(date)
-                .bind(target_id)
-                .execute(db)
-                .await
-                .map_err(|e| e.to_string())?;
-        }
+        // Both fields change in one statement
+        UpdateBuilder::new("spending_targets")
+            .set_if("amount_cents", amount_cents)
+            .set_if("end_date", end_date)
+            .execute(db, target_id)
+            .await
+            .map_err(|e| e.to_string())?;
 
         Ok(true)
     }
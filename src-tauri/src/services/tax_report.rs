@@ -0,0 +1,158 @@
+// Year-end tax report: every tax-deductible transaction in a year, grouped by category
+//
+// A transaction counts when its own tax flag says so, or when it has none and its category
+// is flagged. Pending charges are left out since their amounts can still change
+
+use crate::utils::money::{self, Cents};
+use csv::WriterBuilder;
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaxReportTransaction {
+    pub id: i64,
+    pub date: String,
+    pub description: String,
+    pub merchant: Option<String>,
+    pub account_name: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents, // Money out is positive; refunds are negative
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaxReportCategory {
+    pub category_id: i64,
+    pub category_name: String,
+    #[serde(rename = "total", with = "money::dollars")]
+    pub total_cents: Cents,
+    pub transactions: Vec<TaxReportTransaction>, // In date order
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaxReport {
+    pub year: i32,
+    pub categories: Vec<TaxReportCategory>, // By name
+    #[serde(rename = "total", with = "money::dollars")]
+    pub total_cents: Cents,
+    pub transaction_count: i64,
+}
+
+type TaxRow = (i64, String, String, Option<String>, String, i64, String, Cents);
+
+pub struct TaxReportBuilder;
+
+impl TaxReportBuilder {
+    /// Collect the year's tax-deductible transactions
+    pub async fn build(db: &SqlitePool, year: i32) -> Result<TaxReport, String> {
+        let rows = sqlx::query_as::<_, TaxRow>(
+            "SELECT t.id, t.date, t.description, t.merchant, a.name, c.id, c.name,
+                    -t.amount_cents
+             FROM transactions t
+             JOIN categories c ON c.id = t.category_id
+             JOIN accounts a ON a.id = t.account_id
+             WHERE t.date >= ? AND t.date <= ?
+                AND t.status = 'posted'
+                AND COALESCE(t.tax_deductible, c.tax_deductible) = 1
+             ORDER BY c.name, c.id, t.date, t.id"
+        )
+        .bind(format!("{:04}-01-01", year))
+        .bind(format!("{:04}-12-31", year))
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let mut categories: Vec<TaxReportCategory> = Vec::new();
+        let mut total_cents: Cents = 0;
+        let transaction_count = rows.len() as i64;
+        for (id, date, description, merchant, account_name, category_id, category_name, cents) in rows {
+            if categories.last().is_none_or(|c| c.category_id != category_id) {
+                categories.push(TaxReportCategory {
+                    category_id,
+                    category_name,
+                    total_cents: 0,
+                    transactions: Vec::new(),
+                });
+            }
+            let Some(category) = categories.last_mut() else {
+                continue;
+            };
+            category.total_cents += cents;
+            total_cents += cents;
+            category.transactions.push(TaxReportTransaction {
+                id,
+                date,
+                description,
+                merchant,
+                account_name,
+                amount_cents: cents,
+            });
+        }
+
+        Ok(TaxReport {
+            year,
+            categories,
+            total_cents,
+            transaction_count,
+        })
+    }
+
+    /// One row per transaction, a total row after each category and a grand total at the end
+    pub fn to_csv(report: &TaxReport) -> Result<String, String> {
+        let mut writer = WriterBuilder::new().from_writer(Vec::new());
+        let mut write = |record: [&str; 6]| writer.write_record(record).map_err(|e| e.to_string());
+
+        write(["Category", "Date", "Description", "Merchant", "Account", "Amount"])?;
+        for category in &report.categories {
+            for t in &category.transactions {
+                write([
+                    &category.category_name,
+                    &t.date,
+                    &t.description,
+                    t.merchant.as_deref().unwrap_or(""),
+                    &t.account_name,
+                    &format!("{:.2}", money::from_cents(t.amount_cents)),
+                ])?;
+            }
+            write([&format!("{} total", category.category_name), "", "", "", "", &format!("{:.2}", money::from_cents(category.total_cents))])?;
+        }
+        write([&format!("Total {}", report.year), "", "", "", "", &format!("{:.2}", money::from_cents(report.total_cents))])?;
+
+        let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+        String::from_utf8(bytes).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_csv_adds_category_and_grand_totals() {
+        let transaction = |id: i64, amount_cents: Cents| TaxReportTransaction {
+            id,
+            date: "2024-03-01".to_string(),
+            description: "Food bank, monthly".to_string(),
+            merchant: None,
+            account_name: "Checking".to_string(),
+            amount_cents,
+        };
+        let report = TaxReport {
+            year: 2024,
+            categories: vec![TaxReportCategory {
+                category_id: 7,
+                category_name: "Charity".to_string(),
+                total_cents: 7500,
+                transactions: vec![transaction(1, 5000), transaction(2, 2500)],
+            }],
+            total_cents: 7500,
+            transaction_count: 2,
+        };
+
+        let csv = TaxReportBuilder::to_csv(&report).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "Category,Date,Description,Merchant,Account,Amount");
+        assert_eq!(lines[1], "Charity,2024-03-01,\"Food bank, monthly\",,Checking,50.00");
+        assert_eq!(lines[3], "Charity total,,,,,75.00");
+        assert_eq!(lines[4], "Total 2024,,,,,75.00");
+    }
+}
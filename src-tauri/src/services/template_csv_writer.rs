@@ -0,0 +1,200 @@
+// Transaction CSV exports laid out by a saved template
+//
+// The default export writes a fixed set of columns; a template picks the columns, their
+// order and headers, and how dates and amounts are written, so the file drops straight
+// into whatever the accountant's spreadsheet or software expects
+
+use super::csv_parser::CsvParser;
+use crate::models::export_template::{ExportField, ExportTemplate, NewExportTemplate};
+use crate::utils::dates;
+use crate::utils::money::{self, Cents};
+use csv::WriterBuilder;
+
+/// One transaction, with the names its ids refer to
+#[derive(Debug, Clone)]
+pub struct ExportRow {
+    pub date: String, // YYYY-MM-DD
+    pub amount_cents: Cents,
+    pub description: String,
+    pub merchant: Option<String>,
+    pub category: String,
+    pub account: String,
+    pub notes: Option<String>,
+    pub status: String,
+}
+
+pub struct TemplateCsvWriter;
+
+impl TemplateCsvWriter {
+    /// Check a template before it's saved, so exports with it can't fail on its settings
+    pub fn validate(template: &NewExportTemplate) -> Result<(), String> {
+        if template.name.trim().is_empty() {
+            return Err("Template name cannot be empty".to_string());
+        }
+        if template.columns.is_empty() {
+            return Err("A template needs at least one column".to_string());
+        }
+        if let Some(format) = &template.date_format {
+            CsvParser::date_format_pattern(format).map_err(|e| e.to_string())?;
+        }
+        if !matches!(template.decimal_separator.as_str(), "." | ",") {
+            return Err(format!("Decimal separator must be '.' or ',', not '{}'", template.decimal_separator));
+        }
+        Self::delimiter(&template.delimiter)?;
+        Ok(())
+    }
+
+    fn delimiter(value: &str) -> Result<u8, String> {
+        match value {
+            "," | ";" | "|" | "\t" => Ok(value.as_bytes()[0]),
+            other => Err(format!("Delimiter must be a comma, semicolon, pipe or tab, not '{}'", other)),
+        }
+    }
+
+    fn amount(amount: Cents, decimal_separator: &str) -> String {
+        let formatted = format!("{:.2}", money::from_cents(amount));
+        if decimal_separator == "," { formatted.replace('.', ",") } else { formatted }
+    }
+
+    /// Render `rows` as a CSV document in the template's layout
+    pub fn write(template: &ExportTemplate, rows: &[ExportRow]) -> Result<String, String> {
+        let date_pattern = template
+            .date_format
+            .as_deref()
+            .map(CsvParser::date_format_pattern)
+            .transpose()
+            .map_err(|e| e.to_string())?;
+        let mut writer = WriterBuilder::new()
+            .delimiter(Self::delimiter(&template.delimiter)?)
+            .from_writer(Vec::new());
+
+        if template.include_header {
+            let headers = template
+                .columns
+                .iter()
+                .map(|c| c.header.clone().unwrap_or_else(|| c.field.default_header().to_string()));
+            writer.write_record(headers).map_err(|e| e.to_string())?;
+        }
+
+        for row in rows {
+            let date = match &date_pattern {
+                Some(pattern) => dates::parse_date(&row.date)
+                    .map(|d| d.format(pattern).to_string())
+                    .unwrap_or_else(|_| row.date.clone()),
+                None => row.date.clone(),
+            };
+            let record = template.columns.iter().map(|column| match column.field {
+                ExportField::Date => date.clone(),
+                ExportField::Amount => Self::amount(row.amount_cents, &template.decimal_separator),
+                ExportField::Debit if row.amount_cents < 0 => Self::amount(-row.amount_cents, &template.decimal_separator),
+                ExportField::Credit if row.amount_cents > 0 => Self::amount(row.amount_cents, &template.decimal_separator),
+                ExportField::Debit | ExportField::Credit => String::new(),
+                ExportField::Description => row.description.clone(),
+                ExportField::Merchant => row.merchant.clone().unwrap_or_default(),
+                ExportField::Category => row.category.clone(),
+                ExportField::Account => row.account.clone(),
+                ExportField::Notes => row.notes.clone().unwrap_or_default(),
+                ExportField::Status => row.status.clone(),
+            });
+            writer.write_record(record).map_err(|e| e.to_string())?;
+        }
+
+        let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+        String::from_utf8(bytes).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::export_template::ExportColumn;
+
+    fn column(field: ExportField, header: Option<&str>) -> ExportColumn {
+        ExportColumn {
+            field,
+            header: header.map(str::to_string),
+        }
+    }
+
+    fn template(columns: Vec<ExportColumn>) -> ExportTemplate {
+        ExportTemplate {
+            id: 1,
+            name: "Accountant".to_string(),
+            columns,
+            date_format: Some("DD/MM/YYYY".to_string()),
+            decimal_separator: ",".to_string(),
+            delimiter: ";".to_string(),
+            include_header: true,
+            created_at: String::new(),
+        }
+    }
+
+    fn row(date: &str, amount_cents: Cents, description: &str) -> ExportRow {
+        ExportRow {
+            date: date.to_string(),
+            amount_cents,
+            description: description.to_string(),
+            merchant: None,
+            category: "Groceries".to_string(),
+            account: "Checking".to_string(),
+            notes: None,
+            status: "posted".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_write_in_template_layout() {
+        let template = template(vec![
+            column(ExportField::Date, Some("Booking date")),
+            column(ExportField::Description, None),
+            column(ExportField::Debit, Some("Out")),
+            column(ExportField::Credit, Some("In")),
+            column(ExportField::Category, None),
+        ]);
+        let rows = vec![row("2025-03-07", -123450, "Market; weekly"), row("2025-03-08", 2000, "Refund")];
+
+        let csv = TemplateCsvWriter::write(&template, &rows).unwrap();
+        assert_eq!(
+            csv,
+            "Booking date;Description;Out;In;Category\n\
+             07/03/2025;\"Market; weekly\";1234,50;;Groceries\n\
+             08/03/2025;Refund;;20,00;Groceries\n"
+        );
+    }
+
+    #[test]
+    fn test_write_without_header_in_default_formats() {
+        let mut template = template(vec![column(ExportField::Date, None), column(ExportField::Amount, None)]);
+        template.date_format = None;
+        template.decimal_separator = ".".to_string();
+        template.delimiter = ",".to_string();
+        template.include_header = false;
+
+        let csv = TemplateCsvWriter::write(&template, &[row("2025-03-07", -450, "Coffee")]).unwrap();
+        assert_eq!(csv, "2025-03-07,-4.50\n");
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_settings() {
+        let valid = NewExportTemplate {
+            name: "Accountant".to_string(),
+            columns: vec![column(ExportField::Date, None)],
+            date_format: None,
+            decimal_separator: ".".to_string(),
+            delimiter: ",".to_string(),
+            include_header: true,
+        };
+        assert!(TemplateCsvWriter::validate(&valid).is_ok());
+
+        let invalid = [
+            NewExportTemplate { columns: vec![], ..valid.clone() },
+            NewExportTemplate { name: " ".to_string(), ..valid.clone() },
+            NewExportTemplate { date_format: Some("MM/YYYY".to_string()), ..valid.clone() },
+            NewExportTemplate { decimal_separator: "'".to_string(), ..valid.clone() },
+            NewExportTemplate { delimiter: "::".to_string(), ..valid.clone() },
+        ];
+        for template in &invalid {
+            assert!(TemplateCsvWriter::validate(template).is_err(), "{:?} should be rejected", template);
+        }
+    }
+}
@@ -1,8 +1,17 @@
-use super::csv_parser::{CsvParser, ColumnMapping};
-use super::duplicate_detector::DuplicateDetector;
-use super::categorizer::Categorizer;
-use crate::constants::{DEFAULT_CATEGORY_ID, MAX_TRANSACTION_AMOUNT};
-use crate::models::transaction::NewTransaction;
+use super::account_validator::{AccountValidator, ImportWarning};
+use super::csv_parser::{CsvError, CsvParser, ColumnMapping, ParsedRows, ParsedTransaction};
+use super::duplicate_detector::DuplicateCheck;
+use super::categorizer::{Categorizer, CompiledRule};
+use super::merchant_normalizer::MerchantNormalizer;
+use super::period_locks::PeriodLocks;
+use crate::constants::{DEFAULT_CATEGORY_ID, IMPORT_BATCH_SIZE, MAX_TRANSACTION_CENTS, PENDING_MATCH_DAYS};
+use crate::models::transaction::{NewTransaction, TransactionStatus};
+use crate::utils::dates;
+use crate::utils::money::{self, Cents};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::ControlFlow;
 
 #[derive(Debug)]
 pub enum ImportError {
@@ -11,6 +20,7 @@ pub enum ImportError {
     CategorizerError(String),
     ValidationError(String),
     DatabaseError(String),
+    Cancelled, // Stopped between batches; earlier batches stay imported
 }
 
 impl std::fmt::Display for ImportError {
@@ -21,6 +31,7 @@ impl std::fmt::Display for ImportError {
             ImportError::CategorizerError(e) => write!(f, "Categorization Error: {}", e),
             ImportError::ValidationError(e) => write!(f, "Validation Error: {}", e),
             ImportError::DatabaseError(e) => write!(f, "Database Error: {}", e),
+            ImportError::Cancelled => write!(f, "Import cancelled"),
         }
     }
 }
@@ -32,95 +43,560 @@ pub struct ImportStats {
     pub imported: usize,
     pub duplicates: usize,
     pub errors: usize,
+    pub pending_posted: usize,     // Pending transactions that arrived posted and were updated in place
+    pub locked: usize,             // Rows dated in a locked month, left out
+    pub transaction_ids: Vec<i64>, // Rows inserted by this import
+    pub import_batch_id: i64,
+}
+
+/// Progress reported after each batch
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportProgress {
+    pub processed: usize,
+    pub estimated_total: usize, // Data lines in the file; quoted multi-line fields make this an upper bound
+    pub imported: usize,
+    pub duplicates: usize,
+    pub errors: usize,
+    pub done: bool,
+}
+
+/// A parsed row as it would be imported
+#[derive(Debug, Clone, Serialize)]
+pub struct PreviewRow {
+    pub row_number: usize, // 1-based, not counting the header
+    pub date: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    pub description: String,
+    pub merchant: Option<String>,
+    pub normalized_merchant: Option<String>,
+    pub category_id: i64,
+    pub category_name: Option<String>,
+    pub duplicate: bool, // Already imported, or repeated earlier in the file, under the account's duplicate settings
+}
+
+/// A row that would be counted as an error on import
+#[derive(Debug, Clone, Serialize)]
+pub struct PreviewRowError {
+    pub row_number: usize,
+    pub message: String,
+}
+
+/// What an import would do, without writing anything
+/// The lists are capped at the preview limit; the counts cover the whole file
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportPreview {
+    pub total_rows: usize,
+    pub valid_count: usize,
+    pub duplicate_count: usize,
+    pub error_count: usize,
+    pub rows: Vec<PreviewRow>,
+    pub duplicates: Vec<PreviewRow>,
+    pub errors: Vec<PreviewRowError>,
+    pub warnings: Vec<ImportWarning>, // The file looks wrong for the account's type
+}
+
+/// A parsed row that passed validation and duplicate checks, ready to insert
+struct PreparedRow {
+    transaction: ParsedTransaction,
+    category_id: i64,
+    merchant_id: Option<i64>,
+    member_id: Option<i64>,
+    hash: String,
+    pending_id: Option<i64>, // The pending transaction this posted row replaces
 }
 
 pub struct TransactionImporter;
 
 impl TransactionImporter {
-    pub async fn import(
+    /// Import a CSV in batches of IMPORT_BATCH_SIZE rows, each inserted in a single DB transaction
+    /// The import is recorded in import_batches (even if it fails partway) so it can be undone
+    /// `on_progress` is called after every batch and once more when the import finishes;
+    /// returning `ControlFlow::Break` after a batch stops the import there
+    /// Rows dated in a locked month are left out unless `override_lock` is set
+    pub async fn import<F>(
         db: &sqlx::Pool<sqlx::Sqlite>,
         account_id: i64,
         csv_content: &str,
         mapping: &ColumnMapping,
-    ) -> Result<ImportStats, ImportError> {
-        // Parse CSV
-        let transactions = CsvParser::parse(csv_content, mapping)
+        override_lock: bool,
+        on_progress: F,
+    ) -> Result<ImportStats, ImportError>
+    where
+        F: FnMut(&ImportProgress) -> ControlFlow<()> + Send,
+    {
+        let rows = CsvParser::rows(csv_content, mapping)
             .map_err(|e| ImportError::CsvError(e.to_string()))?;
+        let estimated_total = csv_content.lines().count().saturating_sub(1);
+        Self::import_recorded(db, account_id, rows, estimated_total, override_lock, on_progress).await
+    }
 
-        let total = transactions.len();
-        let mut imported = 0;
-        let mut duplicates = 0;
-        let mut errors = 0;
+    /// Import transactions that didn't come from a CSV (e.g. fetched by bank sync) through the
+    /// same amount checks, duplicate detection, categorization and import batch as a CSV import
+    pub async fn import_transactions<F>(
+        db: &sqlx::Pool<sqlx::Sqlite>,
+        account_id: i64,
+        transactions: Vec<ParsedTransaction>,
+        override_lock: bool,
+        on_progress: F,
+    ) -> Result<ImportStats, ImportError>
+    where
+        F: FnMut(&ImportProgress) -> ControlFlow<()> + Send,
+    {
+        let estimated_total = transactions.len();
+        Self::import_recorded(db, account_id, transactions.into_iter().map(Ok), estimated_total, override_lock, on_progress)
+            .await
+    }
 
-        for transaction in transactions {
-            // Validate transaction amount
-            if transaction.amount.abs() > MAX_TRANSACTION_AMOUNT {
-                return Err(ImportError::ValidationError(
-                    format!("Transaction amount ${:.2} exceeds maximum allowed amount of ${:.2}",
-                        transaction.amount.abs(), MAX_TRANSACTION_AMOUNT)
-                ));
+    /// Run an import recorded in import_batches, whatever the rows came from
+    async fn import_recorded<I, F>(
+        db: &sqlx::Pool<sqlx::Sqlite>,
+        account_id: i64,
+        rows: I,
+        estimated_total: usize,
+        override_lock: bool,
+        mut on_progress: F,
+    ) -> Result<ImportStats, ImportError>
+    where
+        I: Iterator<Item = Result<ParsedTransaction, CsvError>> + Send,
+        F: FnMut(&ImportProgress) -> ControlFlow<()> + Send,
+    {
+        let import_batch_id = sqlx::query("INSERT INTO import_batches (account_id) VALUES (?)")
+            .bind(account_id)
+            .execute(db)
+            .await
+            .map_err(|e| ImportError::DatabaseError(e.to_string()))?
+            .last_insert_rowid();
+
+        let mut stats = ImportStats {
+            total: 0,
+            imported: 0,
+            duplicates: 0,
+            errors: 0,
+            pending_posted: 0,
+            locked: 0,
+            transaction_ids: Vec::new(),
+            import_batch_id,
+        };
+        let result =
+            Self::import_rows(db, account_id, rows, estimated_total, override_lock, &mut stats, &mut on_progress).await;
+
+        let status = if result.is_ok() { "completed" } else { "failed" };
+        sqlx::query(
+            "UPDATE import_batches
+             SET status = ?, total_rows = ?, imported_count = ?, duplicate_count = ?, error_count = ?
+             WHERE id = ?"
+        )
+        .bind(status)
+        .bind(stats.total as i64)
+        .bind(stats.imported as i64)
+        .bind(stats.duplicates as i64)
+        .bind(stats.errors as i64)
+        .bind(import_batch_id)
+        .execute(db)
+        .await
+        .map_err(|e| ImportError::DatabaseError(e.to_string()))?;
+
+        result.map(|_| stats)
+    }
+
+    async fn import_rows<I, F>(
+        db: &sqlx::Pool<sqlx::Sqlite>,
+        account_id: i64,
+        mut rows: I,
+        estimated_total: usize,
+        override_lock: bool,
+        stats: &mut ImportStats,
+        on_progress: &mut F,
+    ) -> Result<(), ImportError>
+    where
+        I: Iterator<Item = Result<ParsedTransaction, CsvError>> + Send,
+        F: FnMut(&ImportProgress) -> ControlFlow<()> + Send,
+    {
+        // Rules and merchants are loaded once per import rather than once per row
+        let rules = Categorizer::load_rules(db)
+            .await
+            .map_err(|e| ImportError::CategorizerError(e.to_string()))?;
+        let mut merchant_ids: HashMap<String, Option<i64>> = HashMap::new();
+        let members = Self::members(db).await?;
+        let locks = if override_lock {
+            PeriodLocks::default()
+        } else {
+            PeriodLocks::load(db)
+                .await
+                .map_err(|e| ImportError::DatabaseError(e.to_string()))?
+        };
+        // Rule matches per rule ID, recorded once each batch is in
+        let mut rule_hits: HashMap<i64, i64> = HashMap::new();
+
+        let mut progress = ImportProgress {
+            processed: 0,
+            estimated_total,
+            imported: 0,
+            duplicates: 0,
+            errors: 0,
+            done: false,
+        };
+        let mut duplicates = DuplicateCheck::for_account(db, account_id)
+            .await
+            .map_err(|e| ImportError::DuplicateError(e.to_string()))?;
+        let mut pending = Self::pending_transactions(db, account_id).await?;
+
+        loop {
+            let batch = rows
+                .by_ref()
+                .take(IMPORT_BATCH_SIZE)
+                .collect::<Result<Vec<ParsedTransaction>, _>>()
+                .map_err(|e| ImportError::CsvError(e.to_string()))?;
+            if batch.is_empty() {
+                break;
             }
+            stats.total += batch.len();
 
-            // Check for duplicates
-            let is_duplicate = DuplicateDetector::is_duplicate(
-                db,
-                &transaction.date,
-                transaction.amount,
-                &transaction.description,
-            )
+            for transaction in &batch {
+                // Validate transaction amount
+                if transaction.amount_cents.abs() > MAX_TRANSACTION_CENTS {
+                    return Err(ImportError::ValidationError(
+                        format!("Transaction amount ${:.2} exceeds maximum allowed amount of ${:.2}",
+                            money::from_cents(transaction.amount_cents.abs()), money::from_cents(MAX_TRANSACTION_CENTS))
+                    ));
+                }
+                // Every source normalizes to YYYY-MM-DD; anything else would fall outside period queries
+                dates::parse_date(&transaction.date).map_err(ImportError::ValidationError)?;
+            }
+
+            let hashes: Vec<String> = batch
+                .iter()
+                .map(|t| NewTransaction::calculate_hash(account_id, &t.date, t.amount_cents, &t.description))
+                .collect();
+            let flags = duplicates
+                .check_batch(db, &batch.iter().collect::<Vec<_>>(), &hashes)
+                .await
+                .map_err(|e| ImportError::DuplicateError(e.to_string()))?;
+
+            let mut prepared = Vec::with_capacity(batch.len());
+            for ((transaction, hash), duplicate) in batch.into_iter().zip(hashes).zip(flags) {
+                if duplicate {
+                    stats.duplicates += 1;
+                    continue;
+                }
+                // A closed month isn't changed by an import, even one that overlaps it
+                if locks.is_locked(&transaction.date) {
+                    stats.locked += 1;
+                    continue;
+                }
+                let hash = duplicates
+                    .storable_hash(db, hash)
+                    .await
+                    .map_err(|e| ImportError::DuplicateError(e.to_string()))?;
+
+                let (category_id, rule_id) = Self::category_for(&rules, &transaction, account_id);
+                if let Some(rule_id) = rule_id {
+                    *rule_hits.entry(rule_id).or_default() += 1;
+                }
+
+                // Link to a canonical merchant (created on first sight)
+                // Resolved before the batch transaction opens so it never waits on our own write lock
+                let merchant_id = match transaction.merchant.as_deref() {
+                    Some(merchant) => match merchant_ids.get(merchant) {
+                        Some(id) => *id,
+                        None => {
+                            let id = MerchantNormalizer::resolve(db, merchant)
+                                .await
+                                .map_err(ImportError::DatabaseError)?;
+                            merchant_ids.insert(merchant.to_string(), id);
+                            id
+                        }
+                    },
+                    None => None,
+                };
+
+                let member_id = transaction.member.as_deref().and_then(|name| Self::member_for(&members, name));
+
+                let pending_id = if transaction.pending {
+                    None
+                } else {
+                    Self::take_pending_match(&mut pending, &transaction)
+                };
+
+                prepared.push(PreparedRow {
+                    transaction,
+                    category_id,
+                    merchant_id,
+                    member_id,
+                    hash,
+                    pending_id,
+                });
+            }
+
+            Self::insert_batch(db, account_id, &prepared, stats).await?;
+            Categorizer::record_hits(db, &std::mem::take(&mut rule_hits)).await;
+
+            progress.processed = stats.total;
+            progress.imported = stats.imported;
+            progress.duplicates = stats.duplicates;
+            progress.errors = stats.errors;
+            if on_progress(&progress).is_break() {
+                return Err(ImportError::Cancelled);
+            }
+        }
+
+        progress.estimated_total = stats.total;
+        progress.done = true;
+        // Everything is imported by now, so there is nothing left to stop
+        let _ = on_progress(&progress);
+
+        Ok(())
+    }
+
+    /// Household members' IDs keyed on their lowercased names
+    async fn members(db: &sqlx::Pool<sqlx::Sqlite>) -> Result<HashMap<String, i64>, ImportError> {
+        let rows: Vec<(i64, String)> = sqlx::query_as("SELECT id, name FROM members")
+            .fetch_all(db)
+            .await
+            .map_err(|e| ImportError::DatabaseError(e.to_string()))?;
+        Ok(rows.into_iter().map(|(id, name)| (name.to_lowercase(), id)).collect())
+    }
+
+    /// The member a file's member column names: by full name, or by first name since card
+    /// exports give the cardholder's full name ("ALEX MORGAN" is Alex). Unknown names are
+    /// left unattributed rather than creating members
+    fn member_for(members: &HashMap<String, i64>, name: &str) -> Option<i64> {
+        let name = name.trim().to_lowercase();
+        members
+            .get(&name)
+            .or_else(|| name.split_whitespace().next().and_then(|first| members.get(first)))
+            .copied()
+    }
+
+    /// The account's pending transactions as (id, date, cents), to match posted rows against
+    async fn pending_transactions(
+        db: &sqlx::Pool<sqlx::Sqlite>,
+        account_id: i64,
+    ) -> Result<Vec<(i64, NaiveDate, Cents)>, ImportError> {
+        let rows: Vec<(i64, String, Cents)> =
+            sqlx::query_as("SELECT id, date, amount_cents FROM transactions WHERE account_id = ? AND status = ?")
+                .bind(account_id)
+                .bind(TransactionStatus::Pending.to_string())
+                .fetch_all(db)
+                .await
+                .map_err(|e| ImportError::DatabaseError(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, date, amount_cents)| Some((id, dates::parse_date(&date).ok()?, amount_cents)))
+            .collect())
+    }
+
+    /// Remove and return the pending transaction a posted row is the settled version of: the same
+    /// amount, dated on or up to PENDING_MATCH_DAYS before it, latest first. Descriptions often
+    /// change when a transaction posts, so they aren't compared
+    fn take_pending_match(pending: &mut Vec<(i64, NaiveDate, Cents)>, transaction: &ParsedTransaction) -> Option<i64> {
+        let posted = dates::parse_date(&transaction.date).ok()?;
+        let cents = transaction.amount_cents;
+        let earliest = posted - chrono::Duration::days(PENDING_MATCH_DAYS);
+
+        let (index, _) = pending
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, date, amount))| *amount == cents && (earliest..=posted).contains(date))
+            .max_by_key(|(_, (id, date, _))| (*date, std::cmp::Reverse(*id)))?;
+        Some(pending.swap_remove(index).0)
+    }
+
+    /// The matching rule's category and ID, or uncategorized and None when no rule matches
+    fn category_for(rules: &[CompiledRule], transaction: &ParsedTransaction, account_id: i64) -> (i64, Option<i64>) {
+        Categorizer::find_match(
+            rules,
+            transaction.merchant.as_deref(),
+            &transaction.description,
+            transaction.amount_cents,
+            Some(account_id),
+        )
+        .map(|rule| (rule.rule.category_id, Some(rule.rule.id)))
+        .unwrap_or((DEFAULT_CATEGORY_ID, None)) // Default to uncategorized
+    }
+
+    /// Parse every row and report what an import into `account_id` would do, keeping up to
+    /// `limit` rows, duplicates and errors each. Nothing is written (merchants are only normalized)
+    /// Unlike an import, rows over the amount limit are reported as errors instead of aborting
+    pub async fn preview(
+        db: &sqlx::Pool<sqlx::Sqlite>,
+        account_id: i64,
+        rows: ParsedRows<'_>,
+        limit: usize,
+    ) -> Result<ImportPreview, ImportError> {
+        let rules = Categorizer::load_rules(db)
+            .await
+            .map_err(|e| ImportError::CategorizerError(e.to_string()))?;
+        let category_names: HashMap<i64, String> = sqlx::query_as::<_, (i64, String)>("SELECT id, name FROM categories")
+            .fetch_all(db)
+            .await
+            .map_err(|e| ImportError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .collect();
+
+        let mut preview = ImportPreview {
+            total_rows: 0,
+            valid_count: 0,
+            duplicate_count: 0,
+            error_count: 0,
+            rows: Vec::new(),
+            duplicates: Vec::new(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        };
+        let mut parsed: Vec<(String, Cents)> = Vec::new();
+        let mut duplicates = DuplicateCheck::for_account(db, account_id)
             .await
             .map_err(|e| ImportError::DuplicateError(e.to_string()))?;
+        let mut rows = rows.enumerate().peekable();
 
-            if is_duplicate {
-                duplicates += 1;
-                continue;
+        while rows.peek().is_some() {
+            let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+            for (index, row) in rows.by_ref().take(IMPORT_BATCH_SIZE) {
+                let row_number = index + 1;
+                preview.total_rows += 1;
+
+                let error = match row {
+                    Ok(transaction) if transaction.amount_cents.abs() > MAX_TRANSACTION_CENTS => format!(
+                        "Amount ${:.2} exceeds maximum allowed amount of ${:.2}",
+                        money::from_cents(transaction.amount_cents.abs()),
+                        money::from_cents(MAX_TRANSACTION_CENTS)
+                    ),
+                    Ok(transaction) => {
+                        parsed.push((transaction.date.clone(), transaction.amount_cents));
+                        batch.push((row_number, transaction));
+                        continue;
+                    }
+                    Err(e) => e.to_string(),
+                };
+                preview.error_count += 1;
+                if preview.errors.len() < limit {
+                    preview.errors.push(PreviewRowError { row_number, message: error });
+                }
             }
 
-            // Categorize
-            let category_id = Categorizer::categorize(
-                db,
-                transaction.merchant.as_deref(),
-                &transaction.description,
-            )
+            let hashes: Vec<String> = batch
+                .iter()
+                .map(|(_, t)| NewTransaction::calculate_hash(account_id, &t.date, t.amount_cents, &t.description))
+                .collect();
+            let flags = duplicates
+                .check_batch(db, &batch.iter().map(|(_, t)| t).collect::<Vec<_>>(), &hashes)
+                .await
+                .map_err(|e| ImportError::DuplicateError(e.to_string()))?;
+
+            for ((row_number, transaction), duplicate) in batch.into_iter().zip(flags) {
+                if duplicate {
+                    preview.duplicate_count += 1;
+                } else {
+                    preview.valid_count += 1;
+                }
+
+                let list = if duplicate { &mut preview.duplicates } else { &mut preview.rows };
+                if list.len() >= limit {
+                    continue;
+                }
+                let (category_id, _) = Self::category_for(&rules, &transaction, account_id);
+                list.push(PreviewRow {
+                    row_number,
+                    normalized_merchant: transaction.merchant.as_deref().and_then(MerchantNormalizer::normalize),
+                    category_name: category_names.get(&category_id).cloned(),
+                    category_id,
+                    duplicate,
+                    date: transaction.date,
+                    amount_cents: transaction.amount_cents,
+                    description: transaction.description,
+                    merchant: transaction.merchant,
+                });
+            }
+        }
+
+        preview.warnings = AccountValidator::import_warnings(db, account_id, &parsed)
             .await
-            .map_err(|e| ImportError::CategorizerError(e.to_string()))?
-            .unwrap_or(DEFAULT_CATEGORY_ID); // Default to uncategorized
+            .map_err(ImportError::DatabaseError)?;
 
-            // Calculate hash
-            let hash = NewTransaction::calculate_hash(
-                &transaction.date,
-                transaction.amount,
-                &transaction.description,
-            );
+        Ok(preview)
+    }
 
-            // Insert transaction
+    async fn insert_batch(
+        db: &sqlx::Pool<sqlx::Sqlite>,
+        account_id: i64,
+        rows: &[PreparedRow],
+        stats: &mut ImportStats,
+    ) -> Result<(), ImportError> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = db.begin().await.map_err(|e| ImportError::DatabaseError(e.to_string()))?;
+        let mut inserted = Vec::with_capacity(rows.len());
+        let mut posted = 0;
+        let mut errors = 0;
+
+        for row in rows {
+            // A pending transaction that posted takes the bank's final date and description but keeps
+            // its category and notes, and stays in the import that added it
+            if let Some(pending_id) = row.pending_id {
+                let result = sqlx::query(
+                    "UPDATE transactions SET status = ?, date = ?, description = ?, merchant = ?, merchant_id = ?,
+                        member_id = COALESCE(member_id, ?), hash = ?, hash_version = ?, updated_at = CURRENT_TIMESTAMP
+                     WHERE id = ?"
+                )
+                .bind(TransactionStatus::Posted.to_string())
+                .bind(&row.transaction.date)
+                .bind(&row.transaction.description)
+                .bind(&row.transaction.merchant)
+                .bind(row.merchant_id)
+                .bind(row.member_id)
+                .bind(&row.hash)
+                .bind(NewTransaction::HASH_VERSION)
+                .bind(pending_id)
+                .execute(&mut *tx)
+                .await;
+                match result {
+                    Ok(_) => posted += 1,
+                    Err(_) => errors += 1,
+                }
+                continue;
+            }
+
+            let status = if row.transaction.pending { TransactionStatus::Pending } else { TransactionStatus::Posted };
             let result = sqlx::query(
                 r#"
-                INSERT INTO transactions (account_id, category_id, date, amount, description, merchant, hash)
-                VALUES (?, ?, ?, ?, ?, ?, ?)
+                INSERT INTO transactions (account_id, category_id, date, amount_cents, description, merchant, merchant_id, hash,
+                    hash_version, import_batch_id, status, member_id)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 "#
             )
             .bind(account_id)
-            .bind(category_id)
-            .bind(&transaction.date)
-            .bind(transaction.amount)
-            .bind(&transaction.description)
-            .bind(&transaction.merchant)
-            .bind(&hash)
-            .execute(db)
+            .bind(row.category_id)
+            .bind(&row.transaction.date)
+            .bind(row.transaction.amount_cents)
+            .bind(&row.transaction.description)
+            .bind(&row.transaction.merchant)
+            .bind(row.merchant_id)
+            .bind(&row.hash)
+            .bind(NewTransaction::HASH_VERSION)
+            .bind(stats.import_batch_id)
+            .bind(status.to_string())
+            .bind(row.member_id)
+            .execute(&mut *tx)
             .await;
 
+            // A failed statement (e.g. a constraint violation) doesn't abort the SQLite transaction
             match result {
-                Ok(_) => imported += 1,
+                Ok(result) => inserted.push(result.last_insert_rowid()),
                 Err(_) => errors += 1,
             }
         }
 
-        Ok(ImportStats {
-            total,
-            imported,
-            duplicates,
-            errors,
-        })
+        tx.commit().await.map_err(|e| ImportError::DatabaseError(e.to_string()))?;
+
+        stats.imported += inserted.len();
+        stats.pending_posted += posted;
+        stats.errors += errors;
+        stats.transaction_ids.extend(inserted);
+        Ok(())
     }
 }
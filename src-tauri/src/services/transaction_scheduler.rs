@@ -0,0 +1,119 @@
+use crate::models::scheduled_transaction::{Recurrence, ScheduledTransaction};
+use crate::utils::dates;
+use chrono::{Duration, Months, NaiveDate};
+
+/// Upper bound on occurrences walked for one schedule, so a bad start date can't loop for ages
+const MAX_OCCURRENCES: u32 = 10_000;
+
+/// When a scheduled transaction comes due
+///
+/// The k-th occurrence is counted from `start` rather than from the previous
+/// occurrence, so a monthly item that starts on Jan 31 falls on Feb 28 and then
+/// Mar 31 instead of drifting to the 28th.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Schedule {
+    pub recurrence: Recurrence,
+    pub start: NaiveDate,
+    pub end: Option<NaiveDate>,
+}
+
+impl Schedule {
+    /// The schedule of a stored item; None if its recurrence or dates don't parse
+    pub fn of(item: &ScheduledTransaction) -> Option<Self> {
+        let parse = |date: &str| dates::parse_date(date).ok();
+        let end = match item.end_date.as_deref() {
+            Some(date) => Some(parse(date)?),
+            None => None,
+        };
+        Some(Self {
+            recurrence: item.recurrence.parse().ok()?,
+            start: parse(&item.start_date)?,
+            end,
+        })
+    }
+
+    /// The `index`-th occurrence (0 is `start`); None past the end of the schedule
+    pub fn occurrence(&self, index: u32) -> Option<NaiveDate> {
+        let date = match self.recurrence {
+            Recurrence::Once if index == 0 => Some(self.start),
+            Recurrence::Once => None,
+            Recurrence::Weekly => self.start.checked_add_signed(Duration::weeks(index as i64)),
+            Recurrence::Biweekly => self.start.checked_add_signed(Duration::weeks(2 * index as i64)),
+            Recurrence::Monthly => self.start.checked_add_months(Months::new(index)),
+            Recurrence::Quarterly => self.start.checked_add_months(Months::new(3 * index)),
+            Recurrence::Yearly => self.start.checked_add_months(Months::new(12 * index)),
+        }?;
+        match self.end {
+            Some(end) if date > end => None,
+            _ => Some(date),
+        }
+    }
+
+    /// Occurrences from `from` through `through` inclusive
+    pub fn occurrences_between(&self, from: NaiveDate, through: NaiveDate) -> Vec<NaiveDate> {
+        (0..MAX_OCCURRENCES)
+            .map_while(|index| self.occurrence(index))
+            .take_while(|date| *date <= through)
+            .filter(|date| *date >= from)
+            .collect()
+    }
+
+    /// The first occurrence after `date`; None once the schedule has run out
+    pub fn next_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        (0..MAX_OCCURRENCES)
+            .map_while(|index| self.occurrence(index))
+            .find(|occurrence| *occurrence > date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn schedule(recurrence: Recurrence, start: &str, end: Option<&str>) -> Schedule {
+        Schedule {
+            recurrence,
+            start: date(start),
+            end: end.map(date),
+        }
+    }
+
+    #[test]
+    fn test_monthly_keeps_month_end() {
+        let rent = schedule(Recurrence::Monthly, "2025-01-31", None);
+        assert_eq!(
+            rent.occurrences_between(date("2025-01-01"), date("2025-04-30")),
+            vec![date("2025-01-31"), date("2025-02-28"), date("2025-03-31"), date("2025-04-30")]
+        );
+        assert_eq!(rent.next_after(date("2025-02-28")), Some(date("2025-03-31")));
+    }
+
+    #[test]
+    fn test_weekly_and_biweekly() {
+        let weekly = schedule(Recurrence::Weekly, "2025-01-03", None);
+        assert_eq!(weekly.next_after(date("2025-01-03")), Some(date("2025-01-10")));
+        let paycheck = schedule(Recurrence::Biweekly, "2025-01-03", None);
+        assert_eq!(
+            paycheck.occurrences_between(date("2025-01-04"), date("2025-02-14")),
+            vec![date("2025-01-17"), date("2025-01-31"), date("2025-02-14")]
+        );
+    }
+
+    #[test]
+    fn test_schedule_ends() {
+        let once = schedule(Recurrence::Once, "2025-03-01", None);
+        assert_eq!(once.next_after(date("2025-02-01")), Some(date("2025-03-01")));
+        assert_eq!(once.next_after(date("2025-03-01")), None);
+
+        let lease = schedule(Recurrence::Quarterly, "2025-01-15", Some("2025-09-30"));
+        assert_eq!(
+            lease.occurrences_between(date("2025-01-01"), date("2026-12-31")),
+            vec![date("2025-01-15"), date("2025-04-15"), date("2025-07-15")]
+        );
+        assert_eq!(lease.next_after(date("2025-07-15")), None);
+    }
+}
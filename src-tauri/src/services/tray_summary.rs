@@ -0,0 +1,230 @@
+use crate::services::deep_link::DEEP_LINK_SCHEME;
+use crate::services::payment_scheduler::PaymentScheduler;
+use crate::services::spending_aggregator::SpendingAggregator;
+use crate::utils::money::{self, Cents};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrayBill {
+    pub debt_id: i64,
+    pub name: String,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents, // Minimum payment still owed for the cycle
+    pub due_date: String,
+    pub overdue: bool,
+}
+
+/// A tray menu entry; clicking it opens the deep link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrayAction {
+    pub id: String,
+    pub label: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraySummary {
+    pub as_of: String,
+    #[serde(rename = "month_income", with = "money::dollars")]
+    pub month_income_cents: Cents,
+    #[serde(rename = "month_spending", with = "money::dollars")]
+    pub month_spending_cents: Cents,
+    #[serde(rename = "bills_remaining", with = "money::dollars")]
+    pub bills_remaining_cents: Cents,
+    #[serde(rename = "safe_to_spend", with = "money::dollars")]
+    pub safe_to_spend_cents: Cents, // Month-to-date income - spending - minimum payments still due this month
+    pub next_bill: Option<TrayBill>,
+    pub tooltip: String,
+    pub actions: Vec<TrayAction>,
+}
+
+/// An open debt's minimum payment and what has been paid toward it this month
+#[derive(Debug, Clone)]
+pub struct BillStatus {
+    pub debt_id: i64,
+    pub name: String,
+    pub min_payment_cents: Cents,
+    pub paid_this_month_cents: Cents,
+    pub due_day: u32,
+}
+
+impl BillStatus {
+    fn remaining(&self) -> Cents {
+        (self.min_payment_cents - self.paid_this_month_cents).max(0)
+    }
+}
+
+pub struct TraySummaryBuilder;
+
+impl TraySummaryBuilder {
+    /// Build the tray summary for the month containing `today`
+    pub async fn build(db: &SqlitePool, today: NaiveDate) -> Result<TraySummary, String> {
+        let month_start = today.with_day(1).unwrap_or(today).format("%Y-%m-%d").to_string();
+        let as_of = today.format("%Y-%m-%d").to_string();
+
+        let month_income = SpendingAggregator::get_total_income(db, &month_start, &as_of).await?;
+        let month_spending = SpendingAggregator::get_total_spending(db, &month_start, &as_of).await?;
+
+        let bills: Vec<BillStatus> = sqlx::query_as::<_, (i64, String, Cents, Cents, u32)>(
+            "SELECT d.id, d.name, d.min_payment_cents,
+                    COALESCE((SELECT SUM(p.amount_cents) FROM debt_payments p
+                              WHERE p.debt_id = d.id AND p.date >= ? AND p.date <= ?), 0),
+                    d.due_day
+             FROM debts d
+             WHERE d.balance_cents > 0 AND d.min_payment_cents > 0
+             ORDER BY d.name"
+        )
+        .bind(&month_start)
+        .bind(&as_of)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|(debt_id, name, min_payment_cents, paid_this_month_cents, due_day)| BillStatus {
+            debt_id,
+            name,
+            min_payment_cents,
+            paid_this_month_cents,
+            due_day,
+        })
+        .collect();
+
+        Ok(Self::summarize(today, month_income, month_spending, &bills))
+    }
+
+    /// Combine month-to-date totals and bill status into the tray payload
+    pub fn summarize(today: NaiveDate, month_income: Cents, month_spending: Cents, bills: &[BillStatus]) -> TraySummary {
+        let bills_remaining: Cents = bills.iter().map(BillStatus::remaining).sum();
+        let safe_to_spend = month_income - month_spending - bills_remaining;
+        let next_bill = Self::next_bill(today, bills);
+
+        let safe_label = format!("Safe to spend: ${:.2}", money::from_cents(safe_to_spend));
+        let bill_label = match &next_bill {
+            Some(bill) if bill.overdue => {
+                format!("Overdue: {} ${:.2} (due {})", bill.name, money::from_cents(bill.amount_cents), bill.due_date)
+            }
+            Some(bill) => format!("Next bill: {} ${:.2} on {}", bill.name, money::from_cents(bill.amount_cents), bill.due_date),
+            None => "No upcoming bills".to_string(),
+        };
+
+        let open = |view: &str| format!("{}://open?view={}", DEEP_LINK_SCHEME, view);
+        let actions = vec![
+            TrayAction {
+                id: "safe_to_spend".to_string(),
+                label: safe_label.clone(),
+                url: open("dashboard"),
+            },
+            TrayAction {
+                id: "next_bill".to_string(),
+                label: bill_label.clone(),
+                url: open("debts"),
+            },
+        ];
+
+        TraySummary {
+            as_of: today.format("%Y-%m-%d").to_string(),
+            month_income_cents: month_income,
+            month_spending_cents: month_spending,
+            bills_remaining_cents: bills_remaining,
+            safe_to_spend_cents: safe_to_spend,
+            next_bill,
+            tooltip: format!("{}\n{}", safe_label, bill_label),
+            actions,
+        }
+    }
+
+    /// Largest minimum payment still owed this month; once everything is paid, the largest one due next cycle
+    fn next_bill(today: NaiveDate, bills: &[BillStatus]) -> Option<TrayBill> {
+        let largest = |amount: fn(&BillStatus) -> Cents| {
+            bills
+                .iter()
+                .filter(|bill| amount(bill) > 0)
+                .max_by(|a, b| amount(a).cmp(&amount(b)).then_with(|| b.name.cmp(&a.name)))
+        };
+
+        let this_month_due =
+            |bill: &BillStatus| PaymentScheduler::next_due_date_on(today.with_day(1).unwrap_or(today), bill.due_day);
+        let (bill, amount, due_date) = match largest(BillStatus::remaining) {
+            Some(bill) => (bill, bill.remaining(), this_month_due(bill)),
+            None => {
+                let bill = largest(|bill| bill.min_payment_cents)?;
+                let next_cycle = PaymentScheduler::next_due_date_on(this_month_due(bill).succ_opt()?, bill.due_day);
+                (bill, bill.min_payment_cents, next_cycle)
+            }
+        };
+
+        Some(TrayBill {
+            debt_id: bill.debt_id,
+            name: bill.name.clone(),
+            amount_cents: amount,
+            due_date: due_date.format("%Y-%m-%d").to_string(),
+            overdue: today > due_date,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn bill(debt_id: i64, name: &str, min_payment_cents: Cents, paid_this_month_cents: Cents) -> BillStatus {
+        BillStatus {
+            debt_id,
+            name: name.to_string(),
+            min_payment_cents,
+            paid_this_month_cents,
+            due_day: 15,
+        }
+    }
+
+    #[test]
+    fn test_safe_to_spend_subtracts_unpaid_bills() {
+        let bills = vec![bill(1, "Visa", 5000, 2000), bill(2, "Car Loan", 30_000, 0)];
+        let summary = TraySummaryBuilder::summarize(date("2025-03-10"), 300_000, 120_000, &bills);
+
+        assert_eq!(summary.bills_remaining_cents, 33_000);
+        assert_eq!(summary.safe_to_spend_cents, 147_000);
+        let next = summary.next_bill.unwrap();
+        assert_eq!(next.name, "Car Loan");
+        assert_eq!(next.due_date, "2025-03-15");
+        assert!(!next.overdue);
+        assert_eq!(summary.actions.len(), 2);
+        assert_eq!(summary.actions[1].url, "budgetbalancer://open?view=debts");
+        assert!(summary.tooltip.starts_with("Safe to spend: $1470.00"));
+    }
+
+    #[test]
+    fn test_unpaid_bill_after_due_date_is_overdue() {
+        let bills = vec![bill(1, "Visa", 5000, 0)];
+        let next = TraySummaryBuilder::summarize(date("2025-03-20"), 0, 0, &bills).next_bill.unwrap();
+        assert_eq!(next.due_date, "2025-03-15");
+        assert!(next.overdue);
+    }
+
+    #[test]
+    fn test_paid_bills_roll_to_next_cycle() {
+        let bills = vec![bill(1, "Visa", 5000, 5000)];
+        let summary = TraySummaryBuilder::summarize(date("2025-12-05"), 10_000, 0, &bills);
+        assert_eq!(summary.safe_to_spend_cents, 10_000);
+        let next = summary.next_bill.unwrap();
+        assert_eq!(next.amount_cents, 5000);
+        assert_eq!(next.due_date, "2026-01-15");
+        assert!(!next.overdue);
+
+        assert!(TraySummaryBuilder::summarize(date("2025-12-05"), 0, 0, &[]).next_bill.is_none());
+    }
+
+    #[test]
+    fn test_next_bill_uses_its_due_day() {
+        let bills = vec![BillStatus { due_day: 31, ..bill(1, "Visa", 5000, 0) }];
+        let next = TraySummaryBuilder::summarize(date("2025-02-20"), 0, 0, &bills).next_bill.unwrap();
+        assert_eq!(next.due_date, "2025-02-28");
+        assert!(!next.overdue);
+    }
+}
@@ -1,3 +1,6 @@
+use super::spending_aggregator::SpendingAggregator;
+use super::statement_period::StatementPeriod;
+use crate::utils::money::{self, Cents};
 use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
@@ -5,47 +8,190 @@ use sqlx::SqlitePool;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrendPoint {
     pub date: String,
-    pub amount: f64,
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
     pub transaction_count: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpendingTrends {
     pub data_points: Vec<TrendPoint>,
-    pub total_spending: f64,
+    #[serde(rename = "total_spending", with = "money::dollars")]
+    pub total_spending_cents: Cents,
     pub average_per_interval: f64,
 }
 
+/// Spending that fell on one weekday or one day of the month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeatmapCell {
+    pub bucket: u32, // 0 (Monday) to 6 (Sunday) by weekday; 1 to 31 by day of month
+    #[serde(rename = "amount", with = "money::dollars")]
+    pub amount_cents: Cents,
+    pub transaction_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpendingHeatmap {
+    pub by_weekday: Vec<HeatmapCell>,      // All 7 weekdays, Monday first
+    pub by_day_of_month: Vec<HeatmapCell>, // All 31 days, including those without spending
+    #[serde(rename = "total_spending", with = "money::dollars")]
+    pub total_spending_cents: Cents,
+}
+
 pub struct TrendsCalculator;
 
 impl TrendsCalculator {
     /// Get spending trends over time with specified interval
+    /// `account_id` limits the spending to one account; `None` covers every account
     pub async fn get_spending_trends(
         db: &SqlitePool,
         start_date: &str,
         end_date: &str,
         interval: &str,
         category_id: Option<i64>,
+        account_id: Option<i64>,
     ) -> Result<SpendingTrends, String> {
         let data_points = match interval {
-            "daily" => Self::get_daily_trends(db, start_date, end_date, category_id).await?,
-            "weekly" => Self::get_weekly_trends(db, start_date, end_date, category_id).await?,
-            "monthly" => Self::get_monthly_trends(db, start_date, end_date, category_id).await?,
+            "daily" => Self::get_daily_trends(db, start_date, end_date, category_id, account_id).await?,
+            "weekly" => Self::get_weekly_trends(db, start_date, end_date, category_id, account_id).await?,
+            "monthly" => Self::get_monthly_trends(db, start_date, end_date, category_id, account_id).await?,
             _ => return Err(format!("Invalid interval: {}", interval)),
         };
 
-        let total_spending: f64 = data_points.iter().map(|p| p.amount).sum();
+        Ok(Self::summarize(data_points))
+    }
+
+    /// Spending per statement period (dated by the period's first day) for an account's closing day
+    /// The closing day only sets the bucket boundaries; periods cover every account's spending
+    /// unless `account_id` limits them to one
+    pub async fn get_statement_trends(
+        db: &SqlitePool,
+        start_date: &str,
+        end_date: &str,
+        closing_day: Option<u32>,
+        category_id: Option<i64>,
+        account_id: Option<i64>,
+    ) -> Result<SpendingTrends, String> {
+        let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid start_date: {}", e))?;
+        let end = NaiveDate::parse_from_str(end_date, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid end_date: {}", e))?;
+
+        let exclude_pending = SpendingAggregator::excludes_pending(db).await;
+        let mut data_points = Vec::new();
+        for (period_start, period_end) in StatementPeriod::periods_between(start, end, closing_day) {
+            let period_start = period_start.format("%Y-%m-%d").to_string();
+            let (cents, count) = sqlx::query_as::<_, (Cents, i64)>(
+                "SELECT
+                    COALESCE(SUM(-amount_cents), 0) as total,
+                    COUNT(*) as count
+                FROM transactions
+                WHERE date >= ? AND date <= ?
+                    AND amount_cents < 0
+                    AND (? IS NULL OR category_id = ?)
+                    AND (? IS NULL OR account_id = ?)
+                    AND (? = 0 OR status = 'posted')"
+            )
+            .bind(&period_start)
+            .bind(period_end.format("%Y-%m-%d").to_string())
+            .bind(category_id)
+            .bind(category_id)
+            .bind(account_id)
+            .bind(account_id)
+            .bind(exclude_pending)
+            .fetch_one(db)
+            .await
+            .map_err(|e| e.to_string())?;
+
+            data_points.push(TrendPoint {
+                date: period_start,
+                amount_cents: cents,
+                transaction_count: count,
+            });
+        }
+
+        Ok(Self::summarize(data_points))
+    }
+
+    /// Spending totalled by weekday and by day of the month, for calendar heatmaps
+    pub async fn get_spending_heatmap(
+        db: &SqlitePool,
+        start_date: &str,
+        end_date: &str,
+        category_id: Option<i64>,
+    ) -> Result<SpendingHeatmap, String> {
+        // SQLite numbers weekdays from Sunday (0); cells start the week on Monday
+        let weekdays = Self::spending_by_date_part(db, start_date, end_date, category_id, "%w").await?;
+        let by_weekday = (0..7)
+            .map(|bucket| Self::heatmap_cell(&weekdays, bucket, (bucket + 1) % 7))
+            .collect();
+        let days = Self::spending_by_date_part(db, start_date, end_date, category_id, "%d").await?;
+        let by_day_of_month = (1..=31).map(|day| Self::heatmap_cell(&days, day, day)).collect();
+
+        Ok(SpendingHeatmap {
+            by_weekday,
+            by_day_of_month,
+            total_spending_cents: days.values().map(|(cents, _)| cents).sum(),
+        })
+    }
+
+    /// (cents spent, transaction count) keyed by `strftime(part, date)`
+    async fn spending_by_date_part(
+        db: &SqlitePool,
+        start_date: &str,
+        end_date: &str,
+        category_id: Option<i64>,
+        part: &str,
+    ) -> Result<std::collections::HashMap<u32, (Cents, i64)>, String> {
+        let rows = sqlx::query_as::<_, (i64, Cents, i64)>(
+            "SELECT
+                CAST(strftime(?, date) AS INTEGER) as part,
+                COALESCE(SUM(-amount_cents), 0) as total,
+                COUNT(*) as count
+            FROM transactions
+            WHERE date >= ? AND date <= ? AND amount_cents < 0
+                AND (? IS NULL OR category_id = ?)
+                AND (? = 0 OR status = 'posted')
+            GROUP BY part"
+        )
+        .bind(part)
+        .bind(start_date)
+        .bind(end_date)
+        .bind(category_id)
+        .bind(category_id)
+        .bind(SpendingAggregator::excludes_pending(db).await)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(part, cents, count)| (part as u32, (cents, count)))
+            .collect())
+    }
+
+    fn heatmap_cell(totals: &std::collections::HashMap<u32, (Cents, i64)>, bucket: u32, key: u32) -> HeatmapCell {
+        let (cents, count) = totals.get(&key).copied().unwrap_or((0, 0));
+        HeatmapCell {
+            bucket,
+            amount_cents: cents,
+            transaction_count: count,
+        }
+    }
+
+    fn summarize(data_points: Vec<TrendPoint>) -> SpendingTrends {
+        let total_spending_cents: Cents = data_points.iter().map(|p| p.amount_cents).sum();
         let average_per_interval = if !data_points.is_empty() {
-            total_spending / data_points.len() as f64
+            money::round_money(money::from_cents(total_spending_cents) / data_points.len() as f64)
         } else {
             0.0
         };
 
-        Ok(SpendingTrends {
+        SpendingTrends {
             data_points,
-            total_spending,
+            total_spending_cents,
             average_per_interval,
-        })
+        }
     }
 
     async fn get_daily_trends(
@@ -53,47 +199,37 @@ impl TrendsCalculator {
         start_date: &str,
         end_date: &str,
         category_id: Option<i64>,
+        account_id: Option<i64>,
     ) -> Result<Vec<TrendPoint>, String> {
-        let query = if let Some(cat_id) = category_id {
-            sqlx::query_as::<_, (String, f64, i64)>(
-                "SELECT
-                    date,
-                    CAST(COALESCE(SUM(ABS(amount)), 0) AS REAL) as total,
-                    COUNT(*) as count
-                FROM transactions
-                WHERE date >= ? AND date <= ? AND amount < 0 AND category_id = ?
-                GROUP BY date
-                ORDER BY date"
-            )
-            .bind(start_date)
-            .bind(end_date)
-            .bind(cat_id)
-            .fetch_all(db)
-            .await
-        } else {
-            sqlx::query_as::<_, (String, f64, i64)>(
-                "SELECT
-                    date,
-                    CAST(COALESCE(SUM(ABS(amount)), 0) AS REAL) as total,
-                    COUNT(*) as count
-                FROM transactions
-                WHERE date >= ? AND date <= ? AND amount < 0
-                GROUP BY date
-                ORDER BY date"
-            )
-            .bind(start_date)
-            .bind(end_date)
-            .fetch_all(db)
-            .await
-        };
-
-        let rows = query.map_err(|e| e.to_string())?;
+        let rows = sqlx::query_as::<_, (String, Cents, i64)>(
+            "SELECT
+                date,
+                COALESCE(SUM(-amount_cents), 0) as total,
+                COUNT(*) as count
+            FROM transactions
+            WHERE date >= ? AND date <= ? AND amount_cents < 0
+                AND (? IS NULL OR category_id = ?)
+                AND (? IS NULL OR account_id = ?)
+                AND (? = 0 OR status = 'posted')
+            GROUP BY date
+            ORDER BY date"
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(category_id)
+        .bind(category_id)
+        .bind(account_id)
+        .bind(account_id)
+        .bind(SpendingAggregator::excludes_pending(db).await)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
 
         Ok(rows
             .into_iter()
-            .map(|(date, amount, count)| TrendPoint {
+            .map(|(date, cents, count)| TrendPoint {
                 date,
-                amount,
+                amount_cents: cents,
                 transaction_count: count,
             })
             .collect())
@@ -104,11 +240,12 @@ impl TrendsCalculator {
         start_date: &str,
         end_date: &str,
         category_id: Option<i64>,
+        account_id: Option<i64>,
     ) -> Result<Vec<TrendPoint>, String> {
         // Get daily data and aggregate by week
-        let daily_trends = Self::get_daily_trends(db, start_date, end_date, category_id).await?;
+        let daily_trends = Self::get_daily_trends(db, start_date, end_date, category_id, account_id).await?;
 
-        let mut weekly_data: std::collections::HashMap<String, (f64, i64)> =
+        let mut weekly_data: std::collections::HashMap<String, (Cents, i64)> =
             std::collections::HashMap::new();
 
         for point in daily_trends {
@@ -117,17 +254,17 @@ impl TrendsCalculator {
                 let week_start = date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64);
                 let week_key = week_start.format("%Y-%m-%d").to_string();
 
-                let entry = weekly_data.entry(week_key).or_insert((0.0, 0));
-                entry.0 += point.amount;
+                let entry = weekly_data.entry(week_key).or_insert((0, 0));
+                entry.0 += point.amount_cents;
                 entry.1 += point.transaction_count;
             }
         }
 
         let mut result: Vec<TrendPoint> = weekly_data
             .into_iter()
-            .map(|(date, (amount, count))| TrendPoint {
+            .map(|(date, (cents, count))| TrendPoint {
                 date,
-                amount,
+                amount_cents: cents,
                 transaction_count: count,
             })
             .collect();
@@ -142,6 +279,7 @@ impl TrendsCalculator {
         start_date: &str,
         end_date: &str,
         category_id: Option<i64>,
+        account_id: Option<i64>,
     ) -> Result<Vec<TrendPoint>, String> {
         // Parse start and end dates
         let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d")
@@ -167,45 +305,66 @@ impl TrendsCalculator {
             };
         }
 
-        // Get spending data for each month
-        let mut result = Vec::new();
-        for month_start in months {
-            let query = if let Some(cat_id) = category_id {
-                sqlx::query_as::<_, (f64, i64)>(
-                    "SELECT
-                        CAST(COALESCE(SUM(ABS(amount)), 0) AS REAL) as total,
-                        COUNT(*) as count
-                    FROM transactions
-                    WHERE strftime('%Y-%m', date) = strftime('%Y-%m', ?)
-                        AND amount < 0
-                        AND category_id = ?"
-                )
-                .bind(&month_start)
-                .bind(cat_id)
-                .fetch_one(db)
-                .await
-            } else {
-                sqlx::query_as::<_, (f64, i64)>(
-                    "SELECT
-                        CAST(COALESCE(SUM(ABS(amount)), 0) AS REAL) as total,
-                        COUNT(*) as count
-                    FROM transactions
-                    WHERE strftime('%Y-%m', date) = strftime('%Y-%m', ?)
-                        AND amount < 0"
-                )
-                .bind(&month_start)
-                .fetch_one(db)
-                .await
-            };
-
-            let (amount, count) = query.map_err(|e| e.to_string())?;
-
-            result.push(TrendPoint {
-                date: month_start,
-                amount,
-                transaction_count: count,
-            });
+        // Totals are kept per month and category, so the whole range is one read;
+        // they aren't kept per account or status, so one account's months, or posted spending
+        // alone, come from the transactions
+        let exclude_pending = SpendingAggregator::excludes_pending(db).await;
+        let rows = if account_id.is_some() || exclude_pending {
+            sqlx::query_as::<_, (String, Cents, i64)>(
+                "SELECT
+                    strftime('%Y-%m', date) as month,
+                    COALESCE(SUM(-amount_cents), 0) as total,
+                    COUNT(*) as count
+                FROM transactions
+                WHERE date >= ? AND date <= ? AND amount_cents < 0
+                    AND (? IS NULL OR category_id = ?)
+                    AND (? IS NULL OR account_id = ?)
+                    AND (? = 0 OR status = 'posted')
+                GROUP BY month"
+            )
+            .bind(format!("{}-01", start.format("%Y-%m")))
+            .bind(format!("{}-31", end.format("%Y-%m")))
+            .bind(category_id)
+            .bind(category_id)
+            .bind(account_id)
+            .bind(account_id)
+            .bind(exclude_pending)
+            .fetch_all(db)
+            .await
+        } else {
+            sqlx::query_as::<_, (String, Cents, i64)>(
+                "SELECT
+                    month,
+                    CAST(COALESCE(SUM(spending_cents), 0) AS INTEGER) as total,
+                    CAST(COALESCE(SUM(transaction_count), 0) AS INTEGER) as count
+                FROM monthly_category_totals
+                WHERE month >= ? AND month <= ?
+                    AND (? IS NULL OR category_id = ?)
+                GROUP BY month"
+            )
+            .bind(start.format("%Y-%m").to_string())
+            .bind(end.format("%Y-%m").to_string())
+            .bind(category_id)
+            .bind(category_id)
+            .fetch_all(db)
+            .await
         }
+        .map_err(|e| e.to_string())?;
+        let totals: std::collections::HashMap<String, (Cents, i64)> =
+            rows.into_iter().map(|(month, cents, count)| (month, (cents, count))).collect();
+
+        // Months without spending still get a point
+        let result = months
+            .into_iter()
+            .map(|month_start| {
+                let (amount_cents, count) = totals.get(&month_start[..7]).copied().unwrap_or((0, 0));
+                TrendPoint {
+                    date: month_start,
+                    amount_cents,
+                    transaction_count: count,
+                }
+            })
+            .collect();
 
         Ok(result)
     }
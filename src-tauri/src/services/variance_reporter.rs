@@ -0,0 +1,217 @@
+use crate::constants::PERCENT_TO_DECIMAL_DIVISOR;
+use crate::utils::money::{self, Cents};
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// One budgeted category's budget against what was actually spent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryVariance {
+    pub category_id: i64,
+    pub category_name: String,
+    #[serde(rename = "budget", with = "money::dollars")]
+    pub budget_cents: Cents, // Target amount scaled to the report period
+    #[serde(rename = "actual", with = "money::dollars")]
+    pub actual_cents: Cents,
+    #[serde(rename = "variance", with = "money::dollars")]
+    pub variance_cents: Cents, // actual - budget; positive is over budget
+    pub percent_consumed: f64,
+    #[serde(rename = "projected", with = "money::dollars")]
+    pub projected_cents: Cents, // End-of-period spending at the current run rate
+    #[serde(rename = "projected_variance", with = "money::dollars")]
+    pub projected_variance_cents: Cents,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetVarianceReport {
+    pub period: String, // "monthly" or "quarterly"
+    pub start_date: String,
+    pub end_date: String,
+    pub days_elapsed: i64, // Days of the period up to and including today (all of them once it's over)
+    pub total_days: i64,
+    pub categories: Vec<CategoryVariance>,
+    #[serde(rename = "total_budget", with = "money::dollars")]
+    pub total_budget_cents: Cents,
+    #[serde(rename = "total_actual", with = "money::dollars")]
+    pub total_actual_cents: Cents,
+    #[serde(rename = "total_projected", with = "money::dollars")]
+    pub total_projected_cents: Cents,
+    #[serde(rename = "unbudgeted_spending", with = "money::dollars")]
+    pub unbudgeted_spending_cents: Cents, // Spending in categories without a target
+}
+
+pub struct VarianceReporter;
+
+impl VarianceReporter {
+    fn format_date(date: NaiveDate) -> String {
+        date.format("%Y-%m-%d").to_string()
+    }
+
+    fn months_in(period: &str) -> Option<u32> {
+        match period {
+            "monthly" => Some(1),
+            "quarterly" => Some(3),
+            "yearly" => Some(12),
+            _ => None,
+        }
+    }
+
+    /// First and last day of the month or quarter containing `date`
+    pub fn period_bounds(period: &str, date: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        let start_month = match period {
+            "monthly" => date.month(),
+            "quarterly" => (date.month() - 1) / 3 * 3 + 1,
+            _ => return None,
+        };
+        let start = NaiveDate::from_ymd_opt(date.year(), start_month, 1)?;
+        let end = start.checked_add_months(Months::new(Self::months_in(period)?))?.pred_opt()?;
+        Some((start, end))
+    }
+
+    /// A target's amount over a report period of a different length, e.g. a yearly
+    /// budget's share of one quarter, rounded to a cent
+    pub fn scale_budget(amount: Cents, target_period: &str, report_period: &str) -> Option<Cents> {
+        let scaled = amount as f64 * Self::months_in(report_period)? as f64 / Self::months_in(target_period)? as f64;
+        Some(scaled.round() as Cents)
+    }
+
+    /// Spending at the end of the period if it continues at the rate so far, rounded to a cent
+    pub fn project(actual: Cents, days_elapsed: i64, total_days: i64) -> Cents {
+        if days_elapsed <= 0 {
+            return 0;
+        }
+        if days_elapsed >= total_days {
+            return actual;
+        }
+        (actual as f64 / days_elapsed as f64 * total_days as f64).round() as Cents
+    }
+
+    /// Budget against actual for every category with a target in the month or quarter
+    /// containing `date`, projected from spending through `today`
+    ///
+    /// A category with several targets uses the one matching the report period, and
+    /// otherwise the shortest one, scaled to the period.
+    pub async fn build(
+        db: &SqlitePool,
+        period: &str,
+        date: NaiveDate,
+        today: NaiveDate,
+    ) -> Result<BudgetVarianceReport, String> {
+        let (start, end) = Self::period_bounds(period, date)
+            .ok_or_else(|| format!("Invalid period: {} (expected monthly or quarterly)", period))?;
+        let (start_date, end_date) = (Self::format_date(start), Self::format_date(end));
+        let total_days = (end - start).num_days() + 1;
+        let days_elapsed = ((today.min(end) - start).num_days() + 1).clamp(0, total_days);
+
+        let targets = sqlx::query_as::<_, (i64, String, Cents, String)>(
+            "SELECT st.category_id, c.name, st.amount_cents, st.period
+             FROM spending_targets st
+             JOIN categories c ON c.id = st.category_id
+             WHERE st.scope = 'category'
+               AND st.start_date <= ? AND (st.end_date IS NULL OR st.end_date >= ?)
+             ORDER BY c.name,
+                      st.period != ?,
+                      CASE st.period WHEN 'monthly' THEN 0 WHEN 'quarterly' THEN 1 ELSE 2 END,
+                      st.id"
+        )
+        .bind(&end_date)
+        .bind(&start_date)
+        .bind(period)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let spending: std::collections::HashMap<i64, Cents> = sqlx::query_as::<_, (i64, Cents)>(
+            "SELECT category_id, SUM(-amount_cents)
+             FROM transactions
+             WHERE date >= ? AND date <= ? AND amount_cents < 0
+             GROUP BY category_id"
+        )
+        .bind(&start_date)
+        .bind(&end_date)
+        .fetch_all(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .collect();
+
+        let mut categories: Vec<CategoryVariance> = Vec::new();
+        for (category_id, category_name, amount, target_period) in targets {
+            // Rows come preferred target first
+            if categories.last().is_some_and(|c| c.category_id == category_id) {
+                continue;
+            }
+            let Some(budget) = Self::scale_budget(amount, &target_period, period) else {
+                continue;
+            };
+            let actual = spending.get(&category_id).copied().unwrap_or(0);
+            let projected = Self::project(actual, days_elapsed, total_days);
+            categories.push(CategoryVariance {
+                category_id,
+                category_name,
+                budget_cents: budget,
+                actual_cents: actual,
+                variance_cents: actual - budget,
+                percent_consumed: if budget > 0 { actual as f64 / budget as f64 * PERCENT_TO_DECIMAL_DIVISOR } else { 0.0 },
+                projected_cents: projected,
+                projected_variance_cents: projected - budget,
+            });
+        }
+
+        let total_spending: Cents = spending.values().sum();
+        let total_actual_cents: Cents = categories.iter().map(|c| c.actual_cents).sum();
+
+        Ok(BudgetVarianceReport {
+            period: period.to_string(),
+            start_date,
+            end_date,
+            days_elapsed,
+            total_days,
+            total_budget_cents: categories.iter().map(|c| c.budget_cents).sum(),
+            total_projected_cents: categories.iter().map(|c| c.projected_cents).sum(),
+            unbudgeted_spending_cents: total_spending - total_actual_cents,
+            total_actual_cents,
+            categories,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_period_bounds() {
+        assert_eq!(
+            VarianceReporter::period_bounds("monthly", date("2024-02-10")),
+            Some((date("2024-02-01"), date("2024-02-29")))
+        );
+        assert_eq!(
+            VarianceReporter::period_bounds("quarterly", date("2025-08-31")),
+            Some((date("2025-07-01"), date("2025-09-30")))
+        );
+        assert_eq!(VarianceReporter::period_bounds("weekly", date("2025-08-31")), None);
+    }
+
+    #[test]
+    fn test_scale_budget() {
+        assert_eq!(VarianceReporter::scale_budget(120_000, "yearly", "monthly"), Some(10_000));
+        assert_eq!(VarianceReporter::scale_budget(10_000, "monthly", "quarterly"), Some(30_000));
+        assert_eq!(VarianceReporter::scale_budget(10_000, "yearly", "monthly"), Some(833));
+        assert_eq!(VarianceReporter::scale_budget(10_000, "weekly", "monthly"), None);
+    }
+
+    #[test]
+    fn test_project_run_rate() {
+        // $100 in the first 10 days of a 30-day month heads for $300
+        assert_eq!(VarianceReporter::project(10_000, 10, 30), 30_000);
+        // A finished period projects to what was actually spent
+        assert_eq!(VarianceReporter::project(25_000, 30, 30), 25_000);
+        // A period that hasn't started has nothing to project from
+        assert_eq!(VarianceReporter::project(0, 0, 30), 0);
+    }
+}
@@ -0,0 +1,211 @@
+use super::csv_parser::{CsvError, CsvParser};
+use crate::models::data_archive::{ArchivedAccount, ArchivedCategory, ArchivedTransaction, DataArchive, DATA_ARCHIVE_VERSION};
+use crate::models::transaction::TransactionStatus;
+use crate::utils::money::Cents;
+use csv::ReaderBuilder;
+use std::collections::{HashMap, HashSet};
+
+/// Category groups YNAB uses for income waiting to be budgeted rather than real categories
+const INFLOW_GROUPS: &[&str] = &["Inflow", "Internal Master Category"];
+
+/// Payee prefix YNAB gives both sides of a transfer between accounts
+const TRANSFER_PREFIX: &str = "Transfer : ";
+
+pub struct YnabImporter;
+
+impl YnabImporter {
+    /// The first of `names` present in the headers
+    fn position(headers: &csv::StringRecord, names: &[&str]) -> Option<usize> {
+        names.iter().find_map(|name| headers.iter().position(|h| h.trim().eq_ignore_ascii_case(name)))
+    }
+
+    /// Strip currency symbols and spaces, e.g. "$1,234.56" or "€12.00"
+    fn parse_money(value: &str) -> Result<Cents, CsvError> {
+        let cleaned: String = value.chars().filter(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | '-')).collect();
+        if cleaned.is_empty() {
+            return Ok(0);
+        }
+        CsvParser::parse_cents(&cleaned, None)
+    }
+
+    /// Read a YNAB register export (Account, Date, Payee, Category Group, Category, Memo, Outflow,
+    /// Inflow; YNAB 4 names the categories Master Category and Sub Category) into an archive of its
+    /// accounts, categories and transactions
+    /// Category groups become parent categories
+    pub fn parse(csv_content: &str) -> Result<DataArchive, CsvError> {
+        let mut reader = ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(csv_content.trim_start_matches('\u{feff}').as_bytes());
+        let headers = reader.headers().map_err(|e| CsvError::ParseError(e.to_string()))?.clone();
+        let column = |names: &[&str]| Self::position(&headers, names).ok_or_else(|| CsvError::MissingColumn(names[0].to_string()));
+
+        let account_idx = column(&["Account"])?;
+        let date_idx = column(&["Date"])?;
+        let payee_idx = column(&["Payee"])?;
+        let group_idx = column(&["Category Group", "Master Category"])?;
+        let category_idx = column(&["Category", "Sub Category"])?;
+        let memo_idx = Self::position(&headers, &["Memo"]);
+        let outflow_idx = column(&["Outflow"])?;
+        let inflow_idx = column(&["Inflow"])?;
+
+        let records: Vec<csv::StringRecord> = reader
+            .records()
+            .collect::<Result<_, _>>()
+            .map_err(|e| CsvError::ParseError(e.to_string()))?;
+        // YNAB writes dates in the budget's own format, so read the whole column to tell which
+        let date_format = CsvParser::detect_date_format(records.iter().filter_map(|r| r.get(date_idx)));
+
+        // Groups and categories share one namespace here, so a category that clashes with a group
+        // or with a same-named category in another group is qualified with its group
+        let groups: HashSet<&str> = records
+            .iter()
+            .filter_map(|r| r.get(group_idx).map(str::trim))
+            .filter(|g| !g.is_empty() && !INFLOW_GROUPS.contains(g))
+            .collect();
+        let mut category_groups: HashMap<String, String> = HashMap::new();
+        let mut category_names: HashMap<(String, String), String> = HashMap::new();
+
+        let mut archive = DataArchive { version: DATA_ARCHIVE_VERSION, ..Default::default() };
+        let mut balances: HashMap<String, Cents> = HashMap::new();
+        let mut accounts: Vec<String> = Vec::new();
+
+        for (row, record) in records.iter().enumerate() {
+            let line = row + 2; // After the header, counting from 1
+            let field = |idx: usize| record.get(idx).map(str::trim).unwrap_or_default();
+
+            let date_raw = field(date_idx);
+            let date = match date_format.and_then(|format| CsvParser::parse_date(date_raw, format)) {
+                Some(date) => date,
+                None => CsvParser::normalize_date(date_raw)?,
+            };
+            let amount = Self::parse_money(field(inflow_idx))? - Self::parse_money(field(outflow_idx))?;
+
+            let account = field(account_idx);
+            if account.is_empty() {
+                return Err(CsvError::ParseError(format!("Line {}: missing account", line)));
+            }
+            if !balances.contains_key(account) {
+                accounts.push(account.to_string());
+            }
+            *balances.entry(account.to_string()).or_default() += amount;
+
+            let group = field(group_idx);
+            let name = field(category_idx);
+            let category = if name.is_empty() || INFLOW_GROUPS.contains(&group) {
+                None
+            } else if let Some(existing) = category_names.get(&(group.to_string(), name.to_string())) {
+                Some(existing.clone())
+            } else {
+                let taken = category_groups.get(name).is_some_and(|g| g != group);
+                let qualified = if taken || groups.contains(name) {
+                    format!("{} ({})", name, group)
+                } else {
+                    name.to_string()
+                };
+                if !group.is_empty() && !archive.categories.iter().any(|c| c.name == group) {
+                    archive.categories.push(ArchivedCategory {
+                        name: group.to_string(),
+                        parent: None,
+                        icon: None,
+                        color: None,
+                        archived: false,
+                        tax_deductible: false,
+                    });
+                }
+                archive.categories.push(ArchivedCategory {
+                    name: qualified.clone(),
+                    parent: Some(group.to_string()).filter(|g| !g.is_empty()),
+                    icon: None,
+                    color: None,
+                    archived: false,
+                    tax_deductible: false,
+                });
+                category_groups.entry(name.to_string()).or_insert_with(|| group.to_string());
+                category_names.insert((group.to_string(), name.to_string()), qualified.clone());
+                Some(qualified)
+            };
+
+            // Transfers name the other account as the payee, which isn't a merchant
+            let payee = field(payee_idx);
+            let memo = memo_idx.map(field).filter(|m| !m.is_empty()).map(str::to_string);
+            let merchant = Some(payee.to_string()).filter(|p| !p.is_empty() && !p.starts_with(TRANSFER_PREFIX));
+            let description = [Some(payee), memo.as_deref(), category.as_deref()]
+                .into_iter()
+                .flatten()
+                .find(|d| !d.is_empty())
+                .unwrap_or("YNAB transaction")
+                .to_string();
+
+            archive.transactions.push(ArchivedTransaction {
+                account: account.to_string(),
+                category,
+                date,
+                amount_cents: amount,
+                description,
+                merchant,
+                notes: memo,
+                original_currency: None,
+                original_amount_cents: None,
+                status: TransactionStatus::Posted.to_string(),
+                member: None,
+                tax_deductible: None,
+            });
+        }
+
+        archive.accounts = accounts
+            .iter()
+            .map(|name| ArchivedAccount::named(name, balances[name]))
+            .collect();
+        Ok(archive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = "\u{feff}\"Account\",\"Flag\",\"Date\",\"Payee\",\"Category Group/Category\",\"Category Group\",\"Category\",\"Memo\",\"Outflow\",\"Inflow\",\"Cleared\"
+\"Checking\",\"\",\"15/01/2024\",\"Whole Foods\",\"Food: Groceries\",\"Food\",\"Groceries\",\"weekly shop\",\"$82.10\",\"$0.00\",\"Cleared\"
+\"Checking\",\"\",\"16/01/2024\",\"Employer\",\"Inflow: Ready to Assign\",\"Inflow\",\"Ready to Assign\",\"\",\"$0.00\",\"$1,500.00\",\"Cleared\"
+\"Checking\",\"\",\"17/01/2024\",\"Transfer : Savings\",\"\",\"\",\"\",\"\",\"$200.00\",\"$0.00\",\"Cleared\"
+\"Savings\",\"\",\"17/01/2024\",\"Transfer : Checking\",\"\",\"\",\"\",\"\",\"$0.00\",\"$200.00\",\"Cleared\"
+\"Checking\",\"\",\"18/01/2024\",\"Corner Shop\",\"Fun: Groceries\",\"Fun\",\"Groceries\",\"\",\"$5.00\",\"$0.00\",\"Cleared\"
+\"Checking\",\"\",\"19/01/2024\",\"Bank\",\"Savings: Savings\",\"Savings\",\"Savings\",\"\",\"$10.00\",\"$0.00\",\"Cleared\"
+";
+
+    #[test]
+    fn test_parse_ynab_export() {
+        let archive = YnabImporter::parse(EXPORT).unwrap();
+
+        let accounts: Vec<(&str, &str, Cents)> =
+            archive.accounts.iter().map(|a| (a.name.as_str(), a.account_type.as_str(), a.balance_cents)).collect();
+        assert_eq!(accounts, vec![("Checking", "checking", 120_290), ("Savings", "savings", 20_000)]);
+
+        let groceries = &archive.transactions[0];
+        assert_eq!(groceries.date, "2024-01-15", "Day-first dates are detected from the file");
+        assert_eq!(groceries.amount_cents, -8210);
+        assert_eq!(groceries.category.as_deref(), Some("Groceries"));
+        assert_eq!(groceries.merchant.as_deref(), Some("Whole Foods"));
+        assert_eq!(groceries.notes.as_deref(), Some("weekly shop"));
+
+        assert_eq!(archive.transactions[1].category, None, "Ready to Assign isn't a category");
+        assert_eq!(archive.transactions[1].amount_cents, 150_000);
+        assert_eq!(archive.transactions[2].merchant, None, "Transfers have no merchant");
+        assert_eq!(archive.transactions[4].category.as_deref(), Some("Groceries (Fun)"));
+        assert_eq!(archive.transactions[5].category.as_deref(), Some("Savings (Savings)"));
+
+        let food = archive.categories.iter().find(|c| c.name == "Groceries").unwrap();
+        assert_eq!(food.parent.as_deref(), Some("Food"));
+        assert!(archive.categories.iter().any(|c| c.name == "Food" && c.parent.is_none()));
+    }
+
+    #[test]
+    fn test_parse_ynab4_headers() {
+        let export = "Account,Date,Payee,Master Category,Sub Category,Memo,Outflow,Inflow
+Checking,01/02/2024,Cafe,Everyday,Coffee,,$3.00,$0.00
+";
+        let archive = YnabImporter::parse(export).unwrap();
+        assert_eq!(archive.transactions[0].category.as_deref(), Some("Coffee"));
+        assert_eq!(archive.transactions[0].amount_cents, -300);
+    }
+}
@@ -0,0 +1,111 @@
+// System tray / menu bar widget showing safe-to-spend and the next bill
+use crate::commands::deep_link_commands::dispatch_deep_links;
+use crate::constants::TRAY_REFRESH_INTERVAL_SECS;
+use crate::services::tray_summary::{TraySummary, TraySummaryBuilder};
+use crate::utils::dates;
+use crate::DbPool;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager};
+
+const TRAY_ID: &str = "budget-balancer";
+const MENU_OPEN: &str = "tray:open";
+const MENU_REFRESH: &str = "tray:refresh";
+const MENU_QUIT: &str = "tray:quit";
+
+/// Create the tray icon and start the periodic summary refresh
+pub fn setup(app: &tauri::App) -> tauri::Result<()> {
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Budget Balancer")
+        .menu(&build_menu(app.handle(), None)?)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| handle_menu_event(app, event.id().as_ref()))
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    let handle = app.handle().clone();
+    std::thread::spawn(move || loop {
+        tauri::async_runtime::block_on(refresh(&handle));
+        std::thread::sleep(std::time::Duration::from_secs(TRAY_REFRESH_INTERVAL_SECS));
+    });
+
+    Ok(())
+}
+
+/// Summary entries (ids are their deep link URLs) followed by the fixed app entries
+fn build_menu(app: &AppHandle, summary: Option<&TraySummary>) -> tauri::Result<Menu<tauri::Wry>> {
+    let menu = Menu::new(app)?;
+    if let Some(summary) = summary {
+        for action in &summary.actions {
+            menu.append(&MenuItem::with_id(app, action.url.as_str(), &action.label, true, None::<&str>)?)?;
+        }
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+    menu.append(&MenuItem::with_id(app, MENU_OPEN, "Open Budget Balancer", true, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(app, MENU_REFRESH, "Refresh", true, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(app, MENU_QUIT, "Quit", true, None::<&str>)?)?;
+    Ok(menu)
+}
+
+/// Reload the summary and update the tray tooltip and menu
+async fn refresh(app: &AppHandle) {
+    let Some(db_pool) = app.try_state::<DbPool>() else {
+        return;
+    };
+    let pool = db_pool.pool();
+    let summary = match TraySummaryBuilder::build(&pool, dates::today(&pool).await).await {
+        Ok(summary) => summary,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to refresh tray summary");
+            return;
+        }
+    };
+
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let result = build_menu(app, Some(&summary))
+        .and_then(|menu| tray.set_menu(Some(menu)))
+        .and_then(|_| tray.set_tooltip(Some(&summary.tooltip)));
+    if let Err(e) = result {
+        tracing::warn!(error = %e, "Failed to update tray menu");
+    }
+}
+
+fn handle_menu_event(app: &AppHandle, id: &str) {
+    match id {
+        MENU_OPEN => show_main_window(app),
+        MENU_REFRESH => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move { refresh(&app).await });
+        }
+        MENU_QUIT => app.exit(0),
+        link => match url::Url::parse(link) {
+            Ok(url) => {
+                show_main_window(app);
+                dispatch_deep_links(app, vec![url]);
+            }
+            Err(e) => tracing::warn!(error = %e, id = link, "Unknown tray menu item"),
+        },
+    }
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
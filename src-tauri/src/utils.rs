@@ -1,3 +1,8 @@
 // Utility modules for common functionality
 
 pub mod rate_limiter;
+pub mod access_guard;
+pub mod query_builder;
+pub mod change_events;
+pub mod money;
+pub mod dates;
@@ -0,0 +1,359 @@
+// App-wide guard that blocks mutating commands in read-only and viewer mode
+
+use crate::constants::APP_PASSWORD_RETRY_INTERVAL_MS;
+use crate::errors::AccessError;
+use crate::utils::rate_limiter::RateLimiter;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Command-line flag that opens the app in read-only mode for the whole session
+pub const READ_ONLY_FLAG: &str = "--read-only";
+
+/// Environment variable that does the same as `--read-only` (any value but "0" or "false")
+pub const READ_ONLY_ENV: &str = "BUDGET_BALANCER_READ_ONLY";
+
+/// Commands that change data, checked by the invoke handler before they run
+///
+/// A new command that writes to the database must be added here, or it will
+/// still run in read-only and viewer mode.
+pub const MUTATING_COMMANDS: &[&str] = &[
+    // CSV import
+    "import_csv",
+    "save_column_mapping",
+    "undo_import",
+    "update_duplicate_settings",
+    // Transactions
+    "update_transaction_category",
+    "categorize_transaction",
+    "delete_transaction",
+    "create_transaction",
+    "update_transaction",
+    "bulk_delete_transactions",
+    "bulk_update_category",
+    "bulk_assign_member",
+    "bulk_set_tax_deductible",
+    "mark_posted",
+    "save_export_template",
+    // Categories
+    "create_category",
+    "update_category",
+    "archive_category",
+    "unarchive_category",
+    "set_category_tax_deductible",
+    "create_category_rule",
+    "delete_category_rule",
+    "import_category_template",
+    "apply_budget_template",
+    "reseed_categories",
+    // Accounts
+    "create_account",
+    "update_account",
+    "set_statement_closing_day",
+    "set_statement_cycle",
+    "delete_account",
+    "archive_account",
+    "unarchive_account",
+    // Debts
+    "create_debt",
+    "update_debt",
+    "calculate_payoff_plan",
+    "record_debt_payment",
+    "update_debt_payment",
+    "delete_debt_payment",
+    "create_loan",
+    "set_debt_min_payment_percent",
+    "set_debt_autopay",
+    "remove_debt_autopay",
+    "set_debt_due_day",
+    "link_debt_account",
+    "sync_debt_to_statement",
+    // Spending targets
+    "create_spending_target",
+    "update_spending_target",
+    "import_budgets_csv",
+    // Onboarding
+    "complete_onboarding_step",
+    // Merchants
+    "rename_merchant",
+    "merge_merchants",
+    "normalize_merchants",
+    // Notifications
+    "mark_notifications_read",
+    "mark_all_notifications_read",
+    "dismiss_notification",
+    // Digests (each one is stored as a notification)
+    "generate_weekly_digest",
+    // Alerts
+    "evaluate_alerts",
+    "dismiss_alert",
+    // Settings
+    "update_setting",
+    // Deep links (add-expense links create transactions)
+    "handle_deep_link",
+    // Spending freezes
+    "create_spending_freeze",
+    "lift_spending_freeze",
+    "delete_spending_freeze",
+    // Currencies
+    "set_exchange_rate",
+    "set_transaction_currency",
+    // Scheduled transactions
+    "create_scheduled_transaction",
+    "delete_scheduled_transaction",
+    "post_due_scheduled_transactions",
+    "confirm_scheduled_transaction",
+    // Background jobs
+    "start_import_job",
+    "import_csv_batch",
+    "start_report_export_job",
+    "rehash_transactions",
+    "cancel_job",
+    // Bank sync
+    "link_bank_connection",
+    "map_bank_account",
+    "sync_accounts",
+    "remove_bank_connection",
+    // Backups (each one is recorded in the history and prunes old backup files)
+    "run_backup_now",
+    // Data archive
+    "import_all_data",
+    "import_from_app",
+    // Watch folder
+    "scan_watch_folder",
+    // Profiles (switching isn't listed: each profile applies its own access settings)
+    "create_profile",
+    // Household members
+    "create_member",
+    "delete_member",
+    // Period locks
+    "lock_period",
+    "unlock_period",
+    // Investments
+    "record_holding",
+    "delete_holding",
+    "set_security_price",
+    "import_security_prices_csv",
+    // Dashboard
+    "save_dashboard_config",
+];
+
+/// Whether the named command changes data
+pub fn is_mutating(command: &str) -> bool {
+    MUTATING_COMMANDS.contains(&command)
+}
+
+/// Whether the process was asked to start in read-only mode
+pub fn read_only_requested<I, S>(args: I, env_value: Option<&str>) -> bool
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let from_env = env_value
+        .map(|value| !matches!(value.trim().to_lowercase().as_str(), "" | "0" | "false"))
+        .unwrap_or(false);
+
+    from_env || args.into_iter().any(|arg| arg.as_ref() == READ_ONLY_FLAG)
+}
+
+/// Access state shared by the invoke handler and the settings commands
+///
+/// Read-only mode is on when the app was started with `--read-only` (fixed for
+/// the session) or when the `read_only` setting is turned on. Separately, the
+/// viewer role locks mutating commands until the app password is entered.
+/// Read-only mode is also enforced by the database pool (see `read_only_flag`), so a
+/// write that isn't in `MUTATING_COMMANDS` fails rather than going through.
+#[derive(Debug)]
+pub struct AccessGuard {
+    forced: bool,
+    read_only: Arc<AtomicBool>,
+    locked: AtomicBool,
+    password_attempts: RateLimiter, // Stamped by each wrong app password
+}
+
+impl Default for AccessGuard {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl AccessGuard {
+    /// Create a guard; `forced` comes from the command line and can't be turned off
+    pub fn new(forced: bool) -> Self {
+        Self {
+            forced,
+            read_only: Arc::new(AtomicBool::new(forced)),
+            locked: AtomicBool::new(false),
+            password_attempts: RateLimiter::new(APP_PASSWORD_RETRY_INTERVAL_MS),
+        }
+    }
+
+    /// Create a guard from the process arguments and environment
+    pub fn from_env() -> Self {
+        let env_value = std::env::var(READ_ONLY_ENV).ok();
+        Self::new(read_only_requested(std::env::args(), env_value.as_deref()))
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::SeqCst)
+    }
+
+    /// The read-only flag itself, for the database pool to follow
+    pub fn read_only_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.read_only)
+    }
+
+    /// Whether read-only mode was forced from the command line
+    pub fn is_forced(&self) -> bool {
+        self.forced
+    }
+
+    /// Turn read-only mode on or off for the rest of the session
+    ///
+    /// # Errors
+    /// `AccessError::ReadOnlyFlag` when turning it off after starting with `--read-only`
+    pub fn set_read_only(&self, read_only: bool) -> Result<(), AccessError> {
+        if self.forced && !read_only {
+            return Err(AccessError::ReadOnlyFlag);
+        }
+        self.read_only.store(read_only, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Whether mutating commands are locked behind the app password (viewer role)
+    pub fn is_locked(&self) -> bool {
+        self.locked.load(Ordering::SeqCst)
+    }
+
+    pub fn set_locked(&self, locked: bool) {
+        self.locked.store(locked, Ordering::SeqCst);
+    }
+
+    /// Check that the last wrong app password was long enough ago to try another
+    ///
+    /// # Errors
+    /// `AccessError::TooManyAttempts` with the seconds left to wait
+    pub fn check_password_attempt(&self) -> Result<(), AccessError> {
+        self.password_attempts
+            .check()
+            .map_err(|e| AccessError::TooManyAttempts(e.seconds()))
+    }
+
+    /// Note a wrong app password, so the next attempt has to wait
+    pub fn record_wrong_password(&self) {
+        let _ = self.password_attempts.check_and_update();
+    }
+
+    /// Allow the next password attempt right away
+    ///
+    /// Public so integration tests can retry without waiting out the interval.
+    pub fn reset_password_attempts(&self) {
+        self.password_attempts.reset();
+    }
+
+    /// Check that read-only mode is off, for commands that write but aren't in
+    /// `MUTATING_COMMANDS` because the viewer lock mustn't block them
+    ///
+    /// # Errors
+    /// `AccessError::ReadOnly`, or `ReadOnlyFlag` after starting with `--read-only`
+    pub fn ensure_writable(&self) -> Result<(), AccessError> {
+        if !self.is_read_only() {
+            return Ok(());
+        }
+        Err(if self.forced {
+            AccessError::ReadOnlyFlag
+        } else {
+            AccessError::ReadOnly
+        })
+    }
+
+    /// Check whether the named command may run
+    ///
+    /// # Errors
+    /// - `AccessError::ReadOnly` (or `ReadOnlyFlag`) for mutating commands in read-only mode
+    /// - `AccessError::ViewerLocked` for mutating commands while the viewer role is locked
+    pub fn check(&self, command: &str) -> Result<(), AccessError> {
+        if !is_mutating(command) {
+            return Ok(());
+        }
+        self.ensure_writable()?;
+        if self.is_locked() {
+            return Err(AccessError::ViewerLocked);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_mutating() {
+        assert!(is_mutating("import_csv"));
+        assert!(is_mutating("update_setting"));
+        assert!(is_mutating("generate_weekly_digest"), "Digests are stored as notifications");
+        assert!(is_mutating("run_backup_now"), "Backups are recorded in the backup history");
+        assert!(!is_mutating("list_transactions"));
+        assert!(!is_mutating("export_transactions"));
+        assert!(!is_mutating("set_read_only_mode"));
+    }
+
+    #[test]
+    fn test_read_only_requested() {
+        assert!(read_only_requested(["budget-balancer", "--read-only"], None));
+        assert!(!read_only_requested(["budget-balancer"], None));
+        assert!(read_only_requested(["budget-balancer"], Some("1")));
+        assert!(read_only_requested(["budget-balancer"], Some("true")));
+        assert!(!read_only_requested(["budget-balancer"], Some("0")));
+        assert!(!read_only_requested(["budget-balancer"], Some("FALSE")));
+    }
+
+    #[test]
+    fn test_check_blocks_only_mutating_commands() {
+        let guard = AccessGuard::new(false);
+        assert!(guard.check("create_account").is_ok());
+
+        guard.set_read_only(true).unwrap();
+        assert!(matches!(guard.check("create_account"), Err(AccessError::ReadOnly)));
+        assert!(guard.check("list_accounts").is_ok());
+
+        guard.set_read_only(false).unwrap();
+        assert!(guard.check("create_account").is_ok());
+    }
+
+    #[test]
+    fn test_locked_viewer_blocks_mutating_commands() {
+        let guard = AccessGuard::new(false);
+        guard.set_locked(true);
+        assert!(matches!(guard.check("create_transaction"), Err(AccessError::ViewerLocked)));
+        assert!(guard.check("list_transactions").is_ok());
+
+        // Read-only mode is reported first when both apply
+        guard.set_read_only(true).unwrap();
+        assert!(matches!(guard.check("create_transaction"), Err(AccessError::ReadOnly)));
+
+        guard.set_read_only(false).unwrap();
+        guard.set_locked(false);
+        assert!(guard.check("create_transaction").is_ok());
+    }
+
+    #[test]
+    fn test_wrong_password_delays_the_next_attempt() {
+        let guard = AccessGuard::new(false);
+        assert!(guard.check_password_attempt().is_ok());
+        guard.record_wrong_password();
+        assert!(matches!(guard.check_password_attempt(), Err(AccessError::TooManyAttempts(_))));
+        guard.reset_password_attempts();
+        assert!(guard.check_password_attempt().is_ok());
+    }
+
+    #[test]
+    fn test_forced_read_only_cannot_be_turned_off() {
+        let guard = AccessGuard::new(true);
+        assert!(guard.is_read_only());
+        assert!(matches!(guard.check("delete_account"), Err(AccessError::ReadOnlyFlag)));
+        assert!(matches!(guard.set_read_only(false), Err(AccessError::ReadOnlyFlag)));
+        assert!(guard.set_read_only(true).is_ok());
+        assert!(guard.is_read_only());
+    }
+}
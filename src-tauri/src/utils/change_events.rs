@@ -0,0 +1,178 @@
+// Data-change events emitted after mutating commands, so frontend stores can refresh
+// just the data that changed instead of refetching everything after every action
+
+use crate::services::analytics_cache::AnalyticsCache;
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+/// The kind of data a change touched; each has its own `<kind>:changed` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataKind {
+    Transactions,
+    ImportBatches,
+    ColumnMappings,
+    Accounts,
+    Categories,
+    CategoryRules,
+    Debts,
+    PayoffPlans,
+    SpendingTargets,
+    SpendingFreezes,
+    Merchants,
+    Notifications,
+    Alerts,
+    Settings,
+    Onboarding,
+    ExchangeRates,
+    ScheduledTransactions,
+    BankConnections,
+    Members,
+    PeriodLocks,
+    Investments,
+}
+
+impl DataKind {
+    pub const ALL: [DataKind; 21] = [
+        DataKind::Transactions,
+        DataKind::ImportBatches,
+        DataKind::ColumnMappings,
+        DataKind::Accounts,
+        DataKind::Categories,
+        DataKind::CategoryRules,
+        DataKind::Debts,
+        DataKind::PayoffPlans,
+        DataKind::SpendingTargets,
+        DataKind::SpendingFreezes,
+        DataKind::Merchants,
+        DataKind::Notifications,
+        DataKind::Alerts,
+        DataKind::Settings,
+        DataKind::Onboarding,
+        DataKind::ExchangeRates,
+        DataKind::ScheduledTransactions,
+        DataKind::BankConnections,
+        DataKind::Members,
+        DataKind::PeriodLocks,
+        DataKind::Investments,
+    ];
+
+    /// Whether cached analytics (spending, trends, the dashboard) can be computed from this kind
+    pub fn affects_analytics(self) -> bool {
+        !matches!(
+            self,
+            DataKind::ColumnMappings
+                | DataKind::CategoryRules
+                | DataKind::PayoffPlans
+                | DataKind::Notifications
+                | DataKind::Alerts
+                | DataKind::Onboarding
+                | DataKind::BankConnections
+                | DataKind::PeriodLocks
+        )
+    }
+
+    /// Name of the Tauri event emitted when this kind of data changes
+    pub fn event_name(self) -> &'static str {
+        match self {
+            DataKind::Transactions => "transactions:changed",
+            DataKind::ImportBatches => "import-batches:changed",
+            DataKind::ColumnMappings => "column-mappings:changed",
+            DataKind::Accounts => "accounts:changed",
+            DataKind::Categories => "categories:changed",
+            DataKind::CategoryRules => "category-rules:changed",
+            DataKind::Debts => "debts:changed",
+            DataKind::PayoffPlans => "payoff-plans:changed",
+            DataKind::SpendingTargets => "spending-targets:changed",
+            DataKind::SpendingFreezes => "spending-freezes:changed",
+            DataKind::Merchants => "merchants:changed",
+            DataKind::Notifications => "notifications:changed",
+            DataKind::Alerts => "alerts:changed",
+            DataKind::Settings => "settings:changed",
+            DataKind::Onboarding => "onboarding:changed",
+            DataKind::ExchangeRates => "exchange-rates:changed",
+            DataKind::ScheduledTransactions => "scheduled-transactions:changed",
+            DataKind::BankConnections => "bank-connections:changed",
+            DataKind::Members => "members:changed",
+            DataKind::PeriodLocks => "period-locks:changed",
+            DataKind::Investments => "investments:changed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// Payload of a `<kind>:changed` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangeEvent {
+    pub action: ChangeAction,
+    pub ids: Vec<i64>, // Empty when the change can't be narrowed down; refetch everything of the kind
+}
+
+/// Where change events go; the app handle in production
+pub trait ChangeSink {
+    fn send_change(&self, kind: DataKind, event: &ChangeEvent);
+}
+
+impl ChangeSink for tauri::AppHandle {
+    fn send_change(&self, kind: DataKind, event: &ChangeEvent) {
+        // Every mutating command reports here, so this is where cached analytics go stale
+        if kind.affects_analytics() {
+            if let Some(cache) = self.try_state::<AnalyticsCache>() {
+                cache.invalidate();
+            }
+        }
+        if let Err(e) = self.emit(kind.event_name(), event.clone()) {
+            tracing::warn!(error = %e, event = kind.event_name(), "Failed to emit change event");
+        }
+    }
+}
+
+/// Tell listeners that records of `kind` were created, updated, or deleted
+pub fn notify_changed(sink: &impl ChangeSink, kind: DataKind, action: ChangeAction, ids: impl Into<Vec<i64>>) {
+    sink.send_change(kind, &ChangeEvent { action, ids: ids.into() });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+
+    #[derive(Default)]
+    struct RecordingSink(RefCell<Vec<(&'static str, ChangeEvent)>>);
+
+    impl ChangeSink for RecordingSink {
+        fn send_change(&self, kind: DataKind, event: &ChangeEvent) {
+            self.0.borrow_mut().push((kind.event_name(), event.clone()));
+        }
+    }
+
+    #[test]
+    fn test_event_names_are_unique() {
+        let names: HashSet<&str> = DataKind::ALL.iter().map(|kind| kind.event_name()).collect();
+        assert_eq!(names.len(), DataKind::ALL.len());
+        assert!(names.iter().all(|name| name.ends_with(":changed")));
+    }
+
+    #[test]
+    fn test_notify_changed() {
+        let sink = RecordingSink::default();
+        notify_changed(&sink, DataKind::Debts, ChangeAction::Updated, [7]);
+        notify_changed(&sink, DataKind::Transactions, ChangeAction::Deleted, vec![]);
+
+        let sent = sink.0.borrow();
+        assert_eq!(sent[0], ("debts:changed", ChangeEvent { action: ChangeAction::Updated, ids: vec![7] }));
+        assert_eq!(sent[1].0, "transactions:changed");
+        assert!(sent[1].1.ids.is_empty());
+        assert_eq!(
+            serde_json::to_value(&sent[0].1).unwrap(),
+            serde_json::json!({ "action": "updated", "ids": [7] })
+        );
+    }
+}
@@ -0,0 +1,192 @@
+// Calendar dates and the user's timezone
+//
+// Transactions are dated by calendar day (`NaiveDate`, stored as YYYY-MM-DD), and period
+// boundaries are compared as dates. A date only depends on a timezone when it comes from
+// an instant: "today", or a bank feed's posting timestamp. Those conversions go through
+// `UserTimezone`, set with the `timezone` setting, so a purchase at 11pm lands on the day
+// (and month) the user made it rather than the day it already was in UTC. A named zone
+// ("America/New_York") follows daylight saving; a fixed offset does not
+
+use crate::models::setting::SettingKey;
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, Utc};
+use chrono_tz::Tz;
+use sqlx::SqlitePool;
+
+/// How dates are written everywhere: in the database, in commands, and in exports
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// The timezone dates are reckoned in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UserTimezone {
+    #[default]
+    System, // Whatever the operating system is set to
+    Zone(Tz), // An IANA zone such as "Europe/Berlin"
+    Offset(FixedOffset),
+}
+
+impl UserTimezone {
+    /// The configured timezone; unset or unreadable settings fall back to the system's
+    pub async fn load(db: &SqlitePool) -> Self {
+        let stored: Option<(String,)> = sqlx::query_as("SELECT value FROM app_settings WHERE key = ?")
+            .bind(SettingKey::Timezone.to_string())
+            .fetch_optional(db)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Failed to load timezone setting");
+                None
+            });
+        stored.and_then(|(value,)| value.parse().ok()).unwrap_or_default()
+    }
+
+    /// The calendar date at `instant`
+    pub fn date_of(&self, instant: DateTime<Utc>) -> NaiveDate {
+        match self {
+            UserTimezone::System => instant.with_timezone(&Local).date_naive(),
+            UserTimezone::Zone(zone) => instant.with_timezone(zone).date_naive(),
+            UserTimezone::Offset(offset) => instant.with_timezone(offset).date_naive(),
+        }
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.date_of(Utc::now())
+    }
+}
+
+impl std::str::FromStr for UserTimezone {
+    type Err = String;
+
+    /// "system", "UTC", an IANA zone such as "America/New_York", or an offset from UTC such
+    /// as "-05:00", "+0530" or "+9"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || {
+            format!(
+                "Invalid timezone: {}. Use \"system\", \"UTC\", a zone like America/New_York, or an offset like -05:00",
+                s
+            )
+        };
+        if s.eq_ignore_ascii_case("system") {
+            return Ok(UserTimezone::System);
+        }
+        if s.eq_ignore_ascii_case("utc") || s.eq_ignore_ascii_case("z") {
+            return Ok(UserTimezone::Offset(FixedOffset::east_opt(0).ok_or_else(invalid)?));
+        }
+        if let Ok(zone) = s.parse::<Tz>() {
+            return Ok(UserTimezone::Zone(zone));
+        }
+
+        let (sign, rest) = match s.as_bytes().first() {
+            Some(b'+') => (1, &s[1..]),
+            Some(b'-') => (-1, &s[1..]),
+            _ => return Err(invalid()),
+        };
+        let (hours, minutes) = match rest.split_once(':') {
+            Some((hours, minutes)) => (hours, minutes),
+            None if rest.len() == 4 => rest.split_at(2),
+            None => (rest, "0"),
+        };
+        let hours: i32 = hours.parse().map_err(|_| invalid())?;
+        let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+        if !(0..60).contains(&minutes) {
+            return Err(invalid());
+        }
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+            .filter(|_| hours <= 14)
+            .map(UserTimezone::Offset)
+            .ok_or_else(invalid)
+    }
+}
+
+impl std::fmt::Display for UserTimezone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UserTimezone::System => write!(f, "system"),
+            UserTimezone::Zone(zone) => write!(f, "{}", zone.name()),
+            UserTimezone::Offset(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+
+/// Today in the configured timezone
+pub async fn today(db: &SqlitePool) -> NaiveDate {
+    UserTimezone::load(db).await.today()
+}
+
+/// A stored or user-entered YYYY-MM-DD date
+pub fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), DATE_FORMAT).map_err(|_| format!("Invalid date: {}", value))
+}
+
+pub fn format_date(date: NaiveDate) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+pub fn quarter_start(date: NaiveDate) -> NaiveDate {
+    NaiveDate::from_ymd_opt(date.year(), (date.month() - 1) / 3 * 3 + 1, 1).unwrap_or(date)
+}
+
+pub fn year_start(date: NaiveDate) -> NaiveDate {
+    NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap_or(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        parse_date(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!("system".parse::<UserTimezone>().unwrap(), UserTimezone::System);
+        assert_eq!("UTC".parse::<UserTimezone>().unwrap().to_string(), "+00:00");
+        assert_eq!("-05:00".parse::<UserTimezone>().unwrap().to_string(), "-05:00");
+        assert_eq!("+0530".parse::<UserTimezone>().unwrap().to_string(), "+05:30");
+        assert_eq!("+9".parse::<UserTimezone>().unwrap().to_string(), "+09:00");
+        assert_eq!(
+            " America/New_York ".parse::<UserTimezone>().unwrap(),
+            UserTimezone::Zone(chrono_tz::America::New_York)
+        );
+        assert_eq!("Asia/Kolkata".parse::<UserTimezone>().unwrap().to_string(), "Asia/Kolkata");
+        for bad in ["", "Mars/Olympus", "05:00", "+15:00", "+05:75", "-aa"] {
+            assert!(bad.parse::<UserTimezone>().is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn test_late_night_lands_on_the_local_day() {
+        // 11:30pm on Jan 31 in New York is already Feb 1 in UTC
+        let instant = DateTime::parse_from_rfc3339("2025-02-01T04:30:00Z").unwrap().with_timezone(&Utc);
+        let new_york: UserTimezone = "-05:00".parse().unwrap();
+        assert_eq!(new_york.date_of(instant), date("2025-01-31"));
+        let utc: UserTimezone = "UTC".parse().unwrap();
+        assert_eq!(utc.date_of(instant), date("2025-02-01"));
+    }
+
+    #[test]
+    fn test_named_zone_follows_daylight_saving() {
+        let new_york: UserTimezone = "America/New_York".parse().unwrap();
+        let winter = DateTime::parse_from_rfc3339("2025-02-01T04:30:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(new_york.date_of(winter), date("2025-01-31"));
+
+        // 12:30am on Jul 1 under EDT; a fixed -05:00 would still say Jun 30
+        let summer = DateTime::parse_from_rfc3339("2025-07-01T04:30:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(new_york.date_of(summer), date("2025-07-01"));
+        let fixed: UserTimezone = "-05:00".parse().unwrap();
+        assert_eq!(fixed.date_of(summer), date("2025-06-30"));
+    }
+
+    #[test]
+    fn test_period_starts() {
+        assert_eq!(month_start(date("2024-02-29")), date("2024-02-01"));
+        assert_eq!(quarter_start(date("2024-08-15")), date("2024-07-01"));
+        assert_eq!(year_start(date("2024-08-15")), date("2024-01-01"));
+        assert!(parse_date("2024-02-30").is_err());
+        assert!(parse_date("02/03/2024").is_err());
+    }
+}
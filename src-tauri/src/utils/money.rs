@@ -0,0 +1,148 @@
+// Money in whole cents
+//
+// Amounts are stored as INTEGER cents (the `*_cents` columns) and held as `Cents` in the
+// models, so sums and comparisons are exact: thirty 0.10 payments add up to exactly 3.00.
+// The frontend still reads and writes dollars; `dollars` converts at the command boundary,
+// so a field like `amount_cents` travels as `"amount": 12.34`. Every rounding decision in the
+// app goes through this module, so the policy lives in one place:
+//
+// - Dollars from the user, files and bank feeds are rounded to the nearest cent, halves
+//   away from zero (12.345 -> 12.35, -12.345 -> -12.35), as banks round statements
+// - Interest and percentages are computed on the cent amount and rounded to a cent, as
+//   they are charged
+// - Averages, shares and rates computed for reports stay `f64`; they're never stored
+
+use crate::constants::{MONTHS_PER_YEAR, PERCENT_TO_DECIMAL_DIVISOR};
+
+/// An amount of money in whole cents
+pub type Cents = i64;
+
+pub const CENTS_PER_DOLLAR: f64 = 100.0;
+
+/// Convert dollars to cents, rounding to the nearest cent
+pub fn to_cents(amount: f64) -> Cents {
+    (amount * CENTS_PER_DOLLAR).round() as Cents
+}
+
+/// Convert cents back to dollars
+pub fn from_cents(cents: Cents) -> f64 {
+    cents as f64 / CENTS_PER_DOLLAR
+}
+
+/// Round a dollar amount to whole cents
+pub fn round_money(amount: f64) -> f64 {
+    from_cents(to_cents(amount))
+}
+
+/// Parse a dollar amount from outside the app, rejecting values that aren't a whole number
+/// of cents an `f64` can hold exactly
+pub fn parse_dollars(amount: f64) -> Result<Cents, String> {
+    if amount.is_finite() && (amount * CENTS_PER_DOLLAR).abs() <= MAX_EXACT_CENTS {
+        Ok(to_cents(amount))
+    } else {
+        Err(format!("Invalid amount: {}", amount))
+    }
+}
+
+/// The largest cent amount that converts to and from dollars without losing a cent
+const MAX_EXACT_CENTS: f64 = 9_007_199_254_740_991.0;
+
+/// `percent` of `amount`, rounded to a cent
+pub fn percent_of(amount: Cents, percent: f64) -> Cents {
+    (amount as f64 * percent / PERCENT_TO_DECIMAL_DIVISOR).round() as Cents
+}
+
+/// One month's interest on `balance` at an annual percentage `rate`, rounded to a cent
+pub fn monthly_interest(balance: Cents, annual_rate: f64) -> Cents {
+    (balance as f64 * annual_rate / PERCENT_TO_DECIMAL_DIVISOR / MONTHS_PER_YEAR).round() as Cents
+}
+
+/// Serde for `Cents` fields the frontend reads and writes as dollars
+/// `#[serde(rename = "amount", with = "money::dollars")] pub amount_cents: Cents`
+pub mod dollars {
+    use super::{from_cents, parse_dollars, Cents};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(cents: &Cents, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(from_cents(*cents))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Cents, D::Error> {
+        parse_dollars(f64::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+
+    /// For `Option<Cents>`; pair with `#[serde(default)]` so a missing field is None
+    pub mod option {
+        use super::super::{from_cents, parse_dollars, Cents};
+        use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(cents: &Option<Cents>, serializer: S) -> Result<S::Ok, S::Error> {
+            match cents {
+                Some(cents) => serializer.serialize_some(&from_cents(*cents)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Cents>, D::Error> {
+            Option::<f64>::deserialize(deserializer)?
+                .map(parse_dollars)
+                .transpose()
+                .map_err(D::Error::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounds_halves_away_from_zero() {
+        assert_eq!(to_cents(12.345), 1235);
+        assert_eq!(to_cents(-12.345), -1235);
+        assert_eq!(to_cents(0.004), 0);
+        assert_eq!(round_money(19.999), 20.0);
+    }
+
+    #[test]
+    fn test_cents_do_not_drift() {
+        let float_sum: f64 = std::iter::repeat(0.1).take(30).sum();
+        assert_ne!(float_sum, 3.0);
+        let cents: Cents = std::iter::repeat(to_cents(0.1)).take(30).sum();
+        assert_eq!(from_cents(cents), 3.0);
+        assert_eq!(from_cents(to_cents(0.1) + to_cents(0.2)), 0.3);
+    }
+
+    #[test]
+    fn test_dollars_cross_the_boundary_as_cents() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Payment {
+            #[serde(rename = "amount", with = "dollars")]
+            amount_cents: Cents,
+            #[serde(rename = "fee", default, with = "dollars::option")]
+            fee_cents: Option<Cents>,
+        }
+
+        let payment: Payment = serde_json::from_str(r#"{"amount": 19.999}"#).unwrap();
+        assert_eq!(payment, Payment { amount_cents: 2000, fee_cents: None });
+        assert_eq!(serde_json::to_string(&payment).unwrap(), r#"{"amount":20.0,"fee":null}"#);
+
+        let payment: Payment = serde_json::from_str(r#"{"amount": -0.1, "fee": 2.5}"#).unwrap();
+        assert_eq!(payment, Payment { amount_cents: -10, fee_cents: Some(250) });
+        assert!(serde_json::from_str::<Payment>(r#"{"amount": 1e300}"#).is_err());
+    }
+
+    #[test]
+    fn test_monthly_interest_rounds_to_cents() {
+        // 1000.00 at 18% APR is exactly 15.00 a month
+        assert_eq!(monthly_interest(100_000, 18.0), 1500);
+        // 1234.56 at 19.99% APR is 20.5655... a month
+        assert_eq!(monthly_interest(123_456, 19.99), 2057);
+    }
+
+    #[test]
+    fn test_percent_of_rounds_to_cents() {
+        assert_eq!(percent_of(123_456, 2.0), 2469);
+        assert_eq!(percent_of(50, 1.0), 1);
+    }
+}
@@ -0,0 +1,212 @@
+// Small builders for the dynamic SQL used by update and bulk commands
+//
+// sqlx caches a prepared statement per connection for every distinct SQL
+// string, so builders here produce a stable string for the same set of
+// columns (cache hits) and mark one-off strings such as long `IN (...)` lists
+// as non-persistent so they don't evict the statements that get reused.
+
+use sqlx::sqlite::{SqliteArguments, SqliteQueryResult};
+use sqlx::{Sqlite, SqliteExecutor};
+
+type SqliteQuery<'q> = sqlx::query::Query<'q, Sqlite, SqliteArguments<'q>>;
+
+/// `IN` lists up to this many ids are cached like any other statement
+const MAX_CACHED_IN_LIST: usize = 8;
+
+/// A value bound to a builder column
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue {
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Null,
+}
+
+impl From<i64> for SqlValue {
+    fn from(value: i64) -> Self {
+        SqlValue::Integer(value)
+    }
+}
+
+impl From<f64> for SqlValue {
+    fn from(value: f64) -> Self {
+        SqlValue::Real(value)
+    }
+}
+
+impl From<bool> for SqlValue {
+    fn from(value: bool) -> Self {
+        SqlValue::Integer(value as i64)
+    }
+}
+
+impl From<String> for SqlValue {
+    fn from(value: String) -> Self {
+        SqlValue::Text(value)
+    }
+}
+
+impl From<&str> for SqlValue {
+    fn from(value: &str) -> Self {
+        SqlValue::Text(value.to_string())
+    }
+}
+
+impl<T: Into<SqlValue>> From<Option<T>> for SqlValue {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(SqlValue::Null)
+    }
+}
+
+fn bind_value<'q>(query: SqliteQuery<'q>, value: &SqlValue) -> SqliteQuery<'q> {
+    match value {
+        SqlValue::Integer(v) => query.bind(*v),
+        SqlValue::Real(v) => query.bind(*v),
+        SqlValue::Text(v) => query.bind(v.clone()),
+        SqlValue::Null => query.bind(None::<String>),
+    }
+}
+
+/// `?,?,?` for an `IN (...)` list of `count` values
+pub fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(",")
+}
+
+/// Whether a statement with an `IN` list of `count` values should be cached
+///
+/// Short lists repeat often (a single id, a handful of selected rows); long
+/// ones are usually unique and would only churn the statement cache.
+pub fn cache_in_list(count: usize) -> bool {
+    count <= MAX_CACHED_IN_LIST
+}
+
+/// Builds `UPDATE <table> SET a = ?, b = ? WHERE id = ?` from the fields that were provided
+///
+/// Column names are `&'static str` so only names written in the code can end up
+/// in the SQL; values are always bound. Columns appear in the order `set` was
+/// called, so the same combination of fields always produces the same statement.
+///
+/// # Examples
+/// ```no_run
+/// use budget_balancer_lib::utils::query_builder::UpdateBuilder;
+///
+/// # async fn example(db: &sqlx::SqlitePool) -> Result<(), sqlx::Error> {
+/// let update = UpdateBuilder::new("debts")
+///     .set_if("balance_cents", Some(120_000))
+///     .set_if("interest_rate", None::<f64>)
+///     .touch_updated_at();
+/// assert_eq!(update.sql(), "UPDATE debts SET balance_cents = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?");
+/// update.execute(db, 1).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct UpdateBuilder {
+    table: &'static str,
+    assignments: Vec<(&'static str, SqlValue)>,
+    touch_updated_at: bool,
+}
+
+impl UpdateBuilder {
+    pub fn new(table: &'static str) -> Self {
+        Self {
+            table,
+            assignments: Vec::new(),
+            touch_updated_at: false,
+        }
+    }
+
+    /// Set a column, including to NULL
+    pub fn set(mut self, column: &'static str, value: impl Into<SqlValue>) -> Self {
+        self.assignments.push((column, value.into()));
+        self
+    }
+
+    /// Set a column only when a value was provided; None leaves it alone
+    pub fn set_if<T: Into<SqlValue>>(self, column: &'static str, value: Option<T>) -> Self {
+        match value {
+            Some(value) => self.set(column, value),
+            None => self,
+        }
+    }
+
+    /// Also set `updated_at` to the current time
+    pub fn touch_updated_at(mut self) -> Self {
+        self.touch_updated_at = true;
+        self
+    }
+
+    /// Whether no columns were set (`updated_at` alone doesn't count)
+    pub fn is_empty(&self) -> bool {
+        self.assignments.is_empty()
+    }
+
+    pub fn sql(&self) -> String {
+        let mut columns: Vec<String> = self
+            .assignments
+            .iter()
+            .map(|(column, _)| format!("{} = ?", column))
+            .collect();
+        if self.touch_updated_at {
+            columns.push("updated_at = CURRENT_TIMESTAMP".to_string());
+        }
+        format!("UPDATE {} SET {} WHERE id = ?", self.table, columns.join(", "))
+    }
+
+    /// Run the update against the row with `id`
+    ///
+    /// With no columns set this only touches `updated_at` (when asked to), and
+    /// otherwise does nothing and reports no rows affected.
+    pub async fn execute<'e, E: SqliteExecutor<'e>>(&self, executor: E, id: i64) -> Result<SqliteQueryResult, sqlx::Error> {
+        if self.is_empty() && !self.touch_updated_at {
+            return Ok(SqliteQueryResult::default());
+        }
+
+        let sql = self.sql();
+        let mut query = sqlx::query(&sql).persistent(true);
+        for (_, value) in &self.assignments {
+            query = bind_value(query, value);
+        }
+        query.bind(id).execute(executor).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_sql_follows_set_order() {
+        let update = UpdateBuilder::new("accounts")
+            .set_if("name", Some("Checking"))
+            .set_if("type", None::<String>)
+            .set_if("balance_cents", Some(1000))
+            .touch_updated_at();
+        assert_eq!(
+            update.sql(),
+            "UPDATE accounts SET name = ?, balance_cents = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+        );
+        assert!(!update.is_empty());
+    }
+
+    #[test]
+    fn test_update_without_fields_is_empty() {
+        let update = UpdateBuilder::new("debts").set_if("balance_cents", None::<i64>).touch_updated_at();
+        assert!(update.is_empty());
+    }
+
+    #[test]
+    fn test_set_can_clear_a_column() {
+        let update = UpdateBuilder::new("transactions").set("merchant", None::<String>);
+        assert_eq!(update.assignments[0].1, SqlValue::Null);
+        assert_eq!(update.sql(), "UPDATE transactions SET merchant = ? WHERE id = ?");
+    }
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(placeholders(1), "?");
+        assert_eq!(placeholders(3), "?,?,?");
+        assert!(cache_in_list(1));
+        assert!(!cache_in_list(500));
+    }
+}
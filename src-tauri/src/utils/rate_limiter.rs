@@ -26,6 +26,7 @@ impl RateLimitError {
     }
 }
 
+#[derive(Debug)]
 pub struct RateLimiter {
     last_request: Mutex<Instant>,
     min_interval: Duration,
@@ -34,6 +34,7 @@
 use budget_balancer_lib::commands::account_commands::create_account_impl;
 use budget_balancer_lib::constants::DEFAULT_CATEGORY_ID;
 use budget_balancer_lib::models::account::{AccountType, NewAccount};
+use budget_balancer_lib::utils::money::{self, Cents};
 use fake::{Fake, Faker};
 use sha2::{Digest, Sha256};
 use sqlx::{Row, SqlitePool};
@@ -44,7 +45,7 @@ pub async fn create_test_account(db: &SqlitePool, name: &str) -> i64 {
     let account = NewAccount {
         name: account_name.clone(),
         account_type: AccountType::Checking,
-        initial_balance: 0.0,
+        initial_balance_cents: 0,
     };
     create_account_impl(db, account)
         .await
@@ -62,16 +63,16 @@ pub async fn insert_test_transactions(
 
     for (idx, tx) in transactions.iter().enumerate() {
         // Calculate hash (same logic as CSV import)
-        let hash = calculate_transaction_hash(&tx.date, tx.amount, &tx.description);
+        let hash = calculate_transaction_hash(&tx.date, tx.amount_cents, &tx.description);
 
         let result = sqlx::query(
-            "INSERT INTO transactions (account_id, date, amount, description, merchant, category_id, hash)
+            "INSERT INTO transactions (account_id, date, amount_cents, description, merchant, category_id, hash)
              VALUES (?, ?, ?, ?, ?, ?, ?)
              RETURNING id"
         )
         .bind(account_id)
         .bind(&tx.date)
-        .bind(tx.amount)
+        .bind(tx.amount_cents)
         .bind(&tx.description)
         .bind(&tx.merchant)
         .bind(tx.category_id.unwrap_or(DEFAULT_CATEGORY_ID)) // Default to Uncategorized
@@ -80,8 +81,8 @@ pub async fn insert_test_transactions(
         .await
         .unwrap_or_else(|e| {
             panic!(
-                "Failed to insert test transaction #{} (desc='{}', date={}, amount={}): {}",
-                idx + 1, tx.description, tx.date, tx.amount, e
+                "Failed to insert test transaction #{} (desc='{}', date={}, amount_cents={}): {}",
+                idx + 1, tx.description, tx.date, tx.amount_cents, e
             )
         });
 
@@ -93,9 +94,9 @@ pub async fn insert_test_transactions(
 }
 
 /// Calculate transaction hash (same logic as NewTransaction::calculate_hash)
-fn calculate_transaction_hash(date: &str, amount: f64, description: &str) -> String {
+fn calculate_transaction_hash(date: &str, amount_cents: Cents, description: &str) -> String {
     let mut hasher = Sha256::new();
-    hasher.update(format!("{}{}{}", date, amount, description));
+    hasher.update(format!("{}{}{}", date, money::from_cents(amount_cents), description));
     format!("{:x}", hasher.finalize())
 }
 
@@ -103,14 +104,14 @@ fn calculate_transaction_hash(date: &str, amount: f64, description: &str) -> Str
 #[derive(Clone)]
 pub struct TestTransaction {
     pub date: String,
-    pub amount: f64,
+    pub amount_cents: Cents,
     pub description: String,
     pub merchant: Option<String>,
     pub category_id: Option<i64>,
 }
 
 impl TestTransaction {
-    pub fn new(date: &str, amount: f64, description: &str) -> Self {
+    pub fn new(date: &str, amount_cents: Cents, description: &str) -> Self {
         // Add unique suffix to description to ensure uniqueness across parallel tests
         // Combines fake data (name) with random number for guaranteed uniqueness
         // Range: 100000..999999 = 900K possibilities (<<1% collision with 113 tests)
@@ -123,7 +124,7 @@ impl TestTransaction {
 
         Self {
             date: date.to_string(),
-            amount,
+            amount_cents,
             description: format!("{} ({})", description, unique_suffix),
             merchant: None,
             category_id: None,
@@ -146,10 +147,10 @@ pub async fn insert_single_transaction(
     db: &SqlitePool,
     account_id: i64,
     date: &str,
-    amount: f64,
+    amount_cents: Cents,
     description: &str,
 ) -> i64 {
-    let tx = TestTransaction::new(date, amount, description);
+    let tx = TestTransaction::new(date, amount_cents, description);
     insert_test_transactions(db, account_id, vec![tx])
         .await
         .into_iter()
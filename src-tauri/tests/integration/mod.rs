@@ -2,22 +2,72 @@
 // These tests verify the contract/interface of each command
 
 pub mod fixtures;
+mod test_access_roles;
 mod test_account_commands;
+mod test_account_summary;
+mod test_alerts;
+mod test_amount_distribution;
+mod test_app_import;
+mod test_audit_log;
+mod test_backups;
+mod test_bank_sync;
+mod test_budget_csv;
+mod test_budget_variance;
+mod test_budget_templates;
+mod test_cash_flow_projection;
 mod test_categorize;
 mod test_category_commands;
+mod test_category_rules;
+mod test_category_statistics;
+mod test_category_suggestions;
+mod test_category_template;
 mod test_column_mapping;
 mod test_create_target;
 mod test_dashboard;
+mod test_data_archive;
 mod test_debt_commands;
+mod test_deep_links;
+mod test_describe_data_model;
+mod test_duplicate_settings;
 mod test_export_report;
 mod test_export_transactions;
+mod test_financial_health;
+mod test_foreign_spending;
+mod test_global_search;
+mod test_hash_versions;
 mod test_import_csv;
+mod test_investments;
+mod test_jobs;
+mod test_largest_transactions;
+mod test_members;
+mod test_merchants;
+mod test_notifications;
+mod test_onboarding;
+mod test_payment_calendar;
+mod test_period_locks;
+mod test_pending_transactions;
+mod test_profiles;
+mod test_read_only_mode;
+mod test_readonly_query;
+mod test_report_clipboard;
+mod test_scenario_fixtures;
+mod test_scheduled_transactions;
 mod test_security;
 mod test_spending_by_category;
+mod test_spending_by_merchant;
+mod test_spending_freeze;
+mod test_spending_insights;
 mod test_spending_trends;
+mod test_statement_summary;
+mod test_streaks;
 mod test_targets_progress;
+mod test_tax_report;
+mod test_timezone;
 mod test_transaction_commands;
+mod test_tray_summary;
 mod test_update_target;
+mod test_watch_folder;
+mod test_weekly_digest;
 
 use sqlx::SqlitePool;
 use std::sync::OnceLock;
@@ -78,6 +128,19 @@ pub fn unique_name(prefix: &str) -> String {
     format!("{} {}", prefix, timestamp)
 }
 
+/// Helper function to generate a unique all-letter word (for merchant names,
+/// which drop digit-heavy tokens during normalization)
+pub fn unique_word(prefix: &str) -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    let letters: String = nanos
+        .to_string()
+        .chars()
+        .map(|c| (b'a' + c.to_digit(10).unwrap() as u8) as char)
+        .collect();
+    format!("{}{}", prefix, letters)
+}
+
 /// Helper function to get a date N days ago from today in YYYY-MM-DD format
 /// This ensures test dates are always relative to the current date, preventing
 /// failures when the calendar month changes.
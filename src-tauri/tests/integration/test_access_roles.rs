@@ -0,0 +1,123 @@
+use budget_balancer_lib::commands::access_commands::{
+    get_access_status_impl, load_app_role, lock_editing_impl, set_app_password_impl,
+    set_app_role_impl, unlock_editing_impl,
+};
+use budget_balancer_lib::errors::AccessError;
+use budget_balancer_lib::models::setting::AppRole;
+use budget_balancer_lib::utils::access_guard::AccessGuard;
+use serial_test::serial;
+
+// The role and password are app-wide, so these tests run serially and clear them when done
+
+#[tokio::test]
+#[serial]
+async fn test_viewer_role_requires_password() {
+    let db = super::get_test_db_pool().await;
+    let guard = AccessGuard::new(false);
+
+    // No viewer role without a password to get back out of it
+    let result = set_app_role_impl(db, &guard, "viewer", None).await;
+    assert!(matches!(result, Err(AccessError::NoPassword)));
+
+    let status = set_app_password_impl(db, &guard, None, Some("1234")).await.unwrap();
+    assert!(status.password_set);
+    assert_eq!(status.role, AppRole::Owner);
+
+    let status = set_app_role_impl(db, &guard, "viewer", None).await.unwrap();
+    assert_eq!(status.role, AppRole::Viewer);
+    assert!(status.locked);
+    assert!(matches!(guard.check("create_transaction"), Err(AccessError::ViewerLocked)));
+    assert!(guard.check("list_transactions").is_ok());
+
+    // The role survives a restart
+    let next_launch = AccessGuard::new(false);
+    load_app_role(db, &next_launch).await.unwrap();
+    assert!(next_launch.is_locked());
+
+    // Unlocking is for this session only; the role stays viewer
+    let result = unlock_editing_impl(db, &guard, "0000").await;
+    assert!(matches!(result, Err(AccessError::WrongPassword)));
+    // Right after a wrong password, even the right one has to wait
+    let result = unlock_editing_impl(db, &guard, "1234").await;
+    assert!(matches!(result, Err(AccessError::TooManyAttempts(_))));
+    guard.reset_password_attempts();
+    let status = unlock_editing_impl(db, &guard, "1234").await.unwrap();
+    assert!(!status.locked);
+    assert_eq!(status.role, AppRole::Viewer);
+    assert!(guard.check("create_transaction").is_ok());
+
+    let status = lock_editing_impl(db, &guard).await.unwrap();
+    assert!(status.locked);
+
+    // Switching back to owner needs the password
+    let result = set_app_role_impl(db, &guard, "owner", Some("wrong")).await;
+    assert!(matches!(result, Err(AccessError::WrongPassword)));
+    let result = set_app_role_impl(db, &guard, "owner", Some("1234")).await;
+    assert!(matches!(result, Err(AccessError::TooManyAttempts(_))));
+    guard.reset_password_attempts();
+    let status = set_app_role_impl(db, &guard, "owner", Some("1234")).await.unwrap();
+    assert_eq!(status.role, AppRole::Owner);
+    assert!(!status.locked);
+
+    set_app_password_impl(db, &guard, Some("1234"), None).await.unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_set_app_password() {
+    let db = super::get_test_db_pool().await;
+    let guard = AccessGuard::new(false);
+
+    let result = set_app_password_impl(db, &guard, None, Some("12")).await;
+    assert!(matches!(result, Err(AccessError::ValidationError(_))));
+
+    set_app_password_impl(db, &guard, None, Some("first-pass")).await.unwrap();
+
+    // Changing it needs the current password
+    let result = set_app_password_impl(db, &guard, None, Some("second-pass")).await;
+    assert!(matches!(result, Err(AccessError::WrongPassword)));
+    guard.reset_password_attempts();
+    set_app_password_impl(db, &guard, Some("first-pass"), Some("second-pass")).await.unwrap();
+
+    // Clearing the password while a viewer also drops back to owner
+    set_app_role_impl(db, &guard, "viewer", None).await.unwrap();
+    let status = set_app_password_impl(db, &guard, Some("second-pass"), None).await.unwrap();
+    assert!(!status.password_set);
+    assert_eq!(status.role, AppRole::Owner);
+    assert!(!guard.is_locked());
+
+    let status = get_access_status_impl(db, &guard).await.unwrap();
+    assert!(!status.password_set);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_old_password_hash_is_upgraded() {
+    use sha2::{Digest, Sha256};
+
+    let db = super::get_test_db_pool().await;
+    let guard = AccessGuard::new(false);
+
+    set_app_password_impl(db, &guard, None, Some("first-pass")).await.unwrap();
+    let stored = || async {
+        sqlx::query_scalar::<_, String>("SELECT value FROM app_settings WHERE key = 'app_password_hash'")
+            .fetch_one(db)
+            .await
+            .unwrap()
+    };
+    assert!(stored().await.starts_with("$argon2id$"));
+
+    // A password saved by an earlier version: one round of salted SHA-256
+    let legacy = format!("abc123${:x}", Sha256::digest("abc123:old-pass"));
+    sqlx::query("UPDATE app_settings SET value = ? WHERE key = 'app_password_hash'")
+        .bind(&legacy)
+        .execute(db)
+        .await
+        .unwrap();
+
+    unlock_editing_impl(db, &guard, "old-pass").await.unwrap();
+    assert!(stored().await.starts_with("$argon2id$"), "The old hash is replaced once the password is entered");
+    unlock_editing_impl(db, &guard, "old-pass").await.unwrap();
+
+    set_app_password_impl(db, &guard, Some("old-pass"), None).await.unwrap();
+}
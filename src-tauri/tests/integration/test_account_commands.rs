@@ -1,6 +1,8 @@
 use budget_balancer_lib::commands::account_commands::{
-    create_account_impl, delete_account_impl, list_accounts_impl, update_account_impl,
+    archive_account_impl, create_account_impl, delete_account_impl, list_accounts_impl,
+    unarchive_account_impl, update_account_impl,
 };
+use budget_balancer_lib::commands::analytics_commands::get_spending_by_category_impl;
 use budget_balancer_lib::models::account::{NewAccount, UpdateAccount};
 use sqlx::Row;
 
@@ -13,7 +15,7 @@ async fn test_create_account_checking() {
     let account = NewAccount {
         name: format!("Test Checking {}", timestamp),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 1000.0,
+        initial_balance_cents: 100_000,
     };
 
     let result = create_account_impl(db, account).await;
@@ -32,7 +34,7 @@ async fn test_create_account_savings() {
     let account = NewAccount {
         name: format!("Test Savings {}", timestamp),
         account_type: budget_balancer_lib::models::account::AccountType::Savings,
-        initial_balance: 5000.0,
+        initial_balance_cents: 500_000,
     };
 
     let result = create_account_impl(db, account).await;
@@ -48,7 +50,7 @@ async fn test_create_account_credit_card() {
     let account = NewAccount {
         name: format!("Test Credit Card {}", timestamp),
         account_type: budget_balancer_lib::models::account::AccountType::CreditCard,
-        initial_balance: -500.0,
+        initial_balance_cents: -50_000,
     };
 
     let result = create_account_impl(db, account).await;
@@ -65,12 +67,12 @@ async fn test_list_accounts() {
     let account = NewAccount {
         name: format!("List Test Account {}", timestamp),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 100.0,
+        initial_balance_cents: 10_000,
     };
 
     let _ = create_account_impl(db, account).await.expect("Failed to create account");
 
-    let result = list_accounts_impl(db).await;
+    let result = list_accounts_impl(db, false).await;
     assert!(result.is_ok(), "Failed to list accounts: {:?}", result);
 
     let accounts = result.unwrap();
@@ -92,18 +94,18 @@ async fn test_list_accounts_ordered_by_name() {
     let account_b = NewAccount {
         name: format!("B Account {}", timestamp),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 100.0,
+        initial_balance_cents: 10_000,
     };
     let account_a = NewAccount {
         name: format!("A Account {}", timestamp),
         account_type: budget_balancer_lib::models::account::AccountType::Savings,
-        initial_balance: 200.0,
+        initial_balance_cents: 20_000,
     };
 
     create_account_impl(db, account_b).await.expect("Failed to create account B");
     create_account_impl(db, account_a).await.expect("Failed to create account A");
 
-    let accounts = list_accounts_impl(db).await.expect("Failed to list accounts");
+    let accounts = list_accounts_impl(db, false).await.expect("Failed to list accounts");
 
     // Verify accounts are ordered by name
     for i in 0..accounts.len().saturating_sub(1) {
@@ -125,7 +127,7 @@ async fn test_update_account_name() {
     let account = NewAccount {
         name: format!("Old Name {}", timestamp),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 100.0,
+        initial_balance_cents: 10_000,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -134,14 +136,14 @@ async fn test_update_account_name() {
         id: account_id,
         name: Some(format!("New Name {}", timestamp)),
         account_type: None,
-        balance: None,
+        balance_cents: None,
     };
 
     let result = update_account_impl(db, update).await;
     assert!(result.is_ok(), "Failed to update account: {:?}", result);
 
     // Verify the update
-    let accounts = list_accounts_impl(db).await.expect("Failed to list accounts");
+    let accounts = list_accounts_impl(db, false).await.expect("Failed to list accounts");
     let updated = accounts.iter().find(|a| a.id == account_id).expect("Account not found");
     assert_eq!(updated.name, format!("New Name {}", timestamp));
     assert_eq!(updated.account_type, "checking"); // Unchanged
@@ -157,7 +159,7 @@ async fn test_update_account_balance() {
     let account = NewAccount {
         name: format!("Balance Test {}", timestamp),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 100.0,
+        initial_balance_cents: 10_000,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -166,16 +168,16 @@ async fn test_update_account_balance() {
         id: account_id,
         name: None,
         account_type: None,
-        balance: Some(500.0),
+        balance_cents: Some(50_000),
     };
 
     let result = update_account_impl(db, update).await;
     assert!(result.is_ok(), "Failed to update balance: {:?}", result);
 
     // Verify the update
-    let accounts = list_accounts_impl(db).await.expect("Failed to list accounts");
+    let accounts = list_accounts_impl(db, false).await.expect("Failed to list accounts");
     let updated = accounts.iter().find(|a| a.id == account_id).expect("Account not found");
-    assert_eq!(updated.balance, 500.0);
+    assert_eq!(updated.balance_cents, 50_000);
 }
 
 #[tokio::test]
@@ -188,7 +190,7 @@ async fn test_update_account_type() {
     let account = NewAccount {
         name: format!("Type Test {}", timestamp),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 0.0,
+        initial_balance_cents: 0,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -197,14 +199,14 @@ async fn test_update_account_type() {
         id: account_id,
         name: None,
         account_type: Some(budget_balancer_lib::models::account::AccountType::Savings),
-        balance: None,
+        balance_cents: None,
     };
 
     let result = update_account_impl(db, update).await;
     assert!(result.is_ok(), "Failed to update account type: {:?}", result);
 
     // Verify the update
-    let accounts = list_accounts_impl(db).await.expect("Failed to list accounts");
+    let accounts = list_accounts_impl(db, false).await.expect("Failed to list accounts");
     let updated = accounts.iter().find(|a| a.id == account_id).expect("Account not found");
     assert_eq!(updated.account_type, "savings");
 }
@@ -217,7 +219,7 @@ async fn test_update_account_nonexistent() {
         id: 999999, // Non-existent ID
         name: Some("New Name".to_string()),
         account_type: None,
-        balance: None,
+        balance_cents: None,
     };
 
     let result = update_account_impl(db, update).await;
@@ -237,19 +239,19 @@ async fn test_delete_account_with_no_transactions() {
     let account = NewAccount {
         name: format!("To Delete {}", timestamp),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 0.0,
+        initial_balance_cents: 0,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
     // Delete the account
-    let result = delete_account_impl(db, account_id).await;
+    let result = delete_account_impl(db, account_id, false).await;
     assert!(result.is_ok(), "Failed to delete account: {:?}", result);
 
     let deleted_count = result.unwrap();
     assert_eq!(deleted_count, 0, "Should have deleted 0 transactions");
 
     // Verify account no longer exists
-    let accounts = list_accounts_impl(db).await.expect("Failed to list accounts");
+    let accounts = list_accounts_impl(db, false).await.expect("Failed to list accounts");
     assert!(!accounts.iter().any(|a| a.id == account_id), "Account should be deleted");
 }
 
@@ -263,7 +265,7 @@ async fn test_delete_account_cascade_transactions() {
     let account = NewAccount {
         name: format!("Cascade Delete {}", timestamp),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 1000.0,
+        initial_balance_cents: 100_000,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -271,9 +273,9 @@ async fn test_delete_account_cascade_transactions() {
     // Note: This test requires transaction_commands to be implemented
     // For now, we'll insert transactions directly
     let tx_count = sqlx::query(
-        "INSERT INTO transactions (account_id, category_id, date, amount, description, hash)
-         VALUES (?, 1, '2025-01-01', -50.0, 'Test Transaction 1', ?),
-                (?, 1, '2025-01-02', -75.0, 'Test Transaction 2', ?)"
+        "INSERT INTO transactions (account_id, category_id, date, amount_cents, description, hash)
+         VALUES (?, 1, '2025-01-01', -5000, 'Test Transaction 1', ?),
+                (?, 1, '2025-01-02', -7500, 'Test Transaction 2', ?)"
     )
     .bind(account_id)
     .bind(format!("hash1_{}", timestamp))
@@ -286,14 +288,14 @@ async fn test_delete_account_cascade_transactions() {
     assert_eq!(tx_count.rows_affected(), 2, "Should have inserted 2 transactions");
 
     // Delete the account
-    let result = delete_account_impl(db, account_id).await;
+    let result = delete_account_impl(db, account_id, false).await;
     assert!(result.is_ok(), "Failed to delete account: {:?}", result);
 
     let deleted_count = result.unwrap();
     assert_eq!(deleted_count, 2, "Should have cascaded 2 transactions");
 
     // Verify account no longer exists
-    let accounts = list_accounts_impl(db).await.expect("Failed to list accounts");
+    let accounts = list_accounts_impl(db, false).await.expect("Failed to list accounts");
     assert!(!accounts.iter().any(|a| a.id == account_id), "Account should be deleted");
 
     // Verify transactions are also deleted
@@ -311,8 +313,48 @@ async fn test_delete_account_cascade_transactions() {
 async fn test_delete_account_nonexistent() {
     let db = super::get_test_db_pool().await;
 
-    let result = delete_account_impl(db, 999999).await;
+    let result = delete_account_impl(db, 999999, false).await;
     assert!(result.is_err(), "Should fail for non-existent account");
     let error_msg = result.unwrap_err();
     assert!(error_msg.contains("not found") || error_msg.contains("Account"));
 }
+
+#[tokio::test]
+async fn test_archive_account() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Archive Test").await;
+    super::fixtures::insert_test_transactions(
+        db,
+        account_id,
+        vec![super::fixtures::TestTransaction::new("2015-09-10", -7500, "Closed account purchase")],
+    )
+    .await;
+
+    let account = archive_account_impl(db, account_id).await.expect("Failed to archive account");
+    assert!(account.archived);
+
+    // Hidden from the default list, shown when asked for
+    let accounts = list_accounts_impl(db, false).await.unwrap();
+    assert!(!accounts.iter().any(|a| a.id == account_id), "Archived account should be hidden");
+    let accounts = list_accounts_impl(db, true).await.unwrap();
+    assert!(accounts.iter().any(|a| a.id == account_id && a.archived));
+
+    // History stays in analytics
+    let spending = get_spending_by_category_impl(db, "2015-09-01", "2015-09-30", Some(account_id), None)
+        .await
+        .unwrap();
+    assert_eq!(spending.total_spending_cents, 7500);
+
+    let account = unarchive_account_impl(db, account_id).await.expect("Failed to unarchive account");
+    assert!(!account.archived);
+    let accounts = list_accounts_impl(db, false).await.unwrap();
+    assert!(accounts.iter().any(|a| a.id == account_id));
+}
+
+#[tokio::test]
+async fn test_archive_account_nonexistent() {
+    let db = super::get_test_db_pool().await;
+
+    let result = archive_account_impl(db, 999999).await;
+    assert!(result.unwrap_err().contains("not found"));
+}
@@ -0,0 +1,61 @@
+use budget_balancer_lib::commands::analytics_commands::get_account_summary_impl;
+use budget_balancer_lib::commands::category_commands::create_category_impl;
+use budget_balancer_lib::models::category::NewCategory;
+
+#[tokio::test]
+async fn test_get_account_summary() {
+    let db = super::get_test_db_pool().await;
+    let checking = super::fixtures::create_test_account(db, &super::unique_name("Summary Checking")).await;
+    let savings = super::fixtures::create_test_account(db, &super::unique_name("Summary Savings")).await;
+    let mut categories = Vec::new();
+    for name in ["Summary Rent", "Summary Food"] {
+        let category = NewCategory {
+            name: super::unique_name(name),
+            icon: None,
+            color: None,
+        };
+        categories.push(create_category_impl(db, category).await.unwrap());
+    }
+    let (rent, food) = (categories[0], categories[1]);
+
+    super::fixtures::insert_test_transactions(
+        db,
+        checking,
+        vec![
+            super::fixtures::TestTransaction::new("1971-08-01", 250_000, "Paycheck"),
+            super::fixtures::TestTransaction::new("1971-08-03", -120_000, "Rent").with_category(rent),
+            super::fixtures::TestTransaction::new("1971-08-10", -4510, "Groceries").with_category(food),
+            super::fixtures::TestTransaction::new("1971-08-24", -3020, "Groceries").with_category(food),
+            super::fixtures::TestTransaction::new("1971-09-01", -99_900, "Next month").with_category(food),
+        ],
+    )
+    .await;
+    // Another account's money stays out of the summary
+    super::fixtures::insert_test_transactions(
+        db,
+        savings,
+        vec![super::fixtures::TestTransaction::new("1971-08-05", -50_000, "Transfer out").with_category(rent)],
+    )
+    .await;
+
+    let summary = get_account_summary_impl(db, checking, "1971-08-01", "1971-08-31")
+        .await
+        .expect("Account summary should succeed");
+    assert_eq!(summary.account_id, checking);
+    assert!(summary.account_name.starts_with("Summary Checking"));
+    assert_eq!(summary.period.start_date, "1971-08-01");
+    assert_eq!(summary.period.end_date, "1971-08-31");
+    assert_eq!(summary.inflows_cents, 250_000);
+    assert_eq!(summary.outflows_cents, 127_530);
+    assert_eq!(summary.net_change_cents, 122_470);
+
+    let categories: Vec<(i64, i64)> = summary.top_categories.iter().map(|c| (c.category_id, c.amount_cents)).collect();
+    assert_eq!(categories, vec![(rent, 120_000), (food, 7530)]);
+
+    let largest: Vec<i64> = summary.largest_transactions.iter().map(|t| t.amount_cents).collect();
+    assert_eq!(largest, vec![-120_000, -4510, -3020]);
+    assert!(summary.largest_transactions.iter().all(|t| t.account_id == checking));
+
+    assert!(get_account_summary_impl(db, checking, "1971-08-31", "1971-08-01").await.is_err());
+    assert!(get_account_summary_impl(db, i64::MAX, "1971-08-01", "1971-08-31").await.is_err());
+}
@@ -0,0 +1,98 @@
+use budget_balancer_lib::commands::alert_commands::{dismiss_alert_impl, evaluate_alerts_impl, list_alerts_impl};
+use budget_balancer_lib::commands::analytics_commands::create_spending_target_impl;
+use budget_balancer_lib::commands::category_commands::create_category_impl;
+use budget_balancer_lib::commands::debt_commands::{create_debt_impl, set_debt_due_day_impl};
+use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::models::debt::NewDebt;
+use budget_balancer_lib::models::spending_target::NewSpendingTarget;
+use chrono::NaiveDate;
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn test_evaluate_alerts_raises_each_event_once() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Alerts Test").await;
+    // A fresh category keeps other tests' transactions out of the history
+    let category_id = create_category_impl(db, NewCategory {
+        name: super::unique_name("Alert Dining"),
+        icon: None,
+        color: None,
+    })
+    .await
+    .unwrap();
+
+    let mut transactions: Vec<_> = ["1998-04-03", "1998-04-17", "1998-05-01", "1998-05-15", "1998-05-29"]
+        .iter()
+        .map(|date| super::fixtures::TestTransaction::new(date, -2000, "Lunch").with_category(category_id))
+        .collect();
+    transactions.push(super::fixtures::TestTransaction::new("1998-06-13", -20_000, "Anniversary dinner").with_category(category_id));
+    let ids = super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+    let large_id = *ids.last().unwrap();
+
+    create_spending_target_impl(db, NewSpendingTarget {
+        category_id: Some(category_id),
+        account_id: None,
+        amount_cents: 21_000,
+        period: "monthly".to_string(),
+        start_date: "1998-06-01".to_string(),
+        end_date: Some("1998-06-30".to_string()),
+    })
+        .await
+        .unwrap();
+
+    let debt_id = create_debt_impl(db, NewDebt {
+        name: super::unique_name("Alert Card"),
+        balance_cents: 90_000,
+        interest_rate: 20.0,
+        min_payment_cents: 3500,
+    })
+    .await
+    .unwrap();
+    set_debt_due_day_impl(db, debt_id, 18).await.unwrap();
+    sqlx::query("UPDATE debts SET created_at = '1998-01-01 00:00:00' WHERE id = ?")
+        .bind(debt_id)
+        .execute(db)
+        .await
+        .unwrap();
+
+    let today = NaiveDate::from_ymd_opt(1998, 6, 15).unwrap();
+    let raised = evaluate_alerts_impl(db, today).await.unwrap();
+
+    let target = raised
+        .iter()
+        .find(|a| a.alert_type == "target_threshold" && a.entity_id == Some(category_id))
+        .expect("Target alert should be raised");
+    assert!(target.title.ends_with("target 95% used"), "Got {}", target.title);
+
+    let large = raised
+        .iter()
+        .find(|a| a.alert_type == "large_transaction" && a.entity_id == Some(large_id))
+        .expect("Large transaction alert should be raised");
+    assert!(large.message.contains(": $200.00 on 1998-06-13, 10.0x the usual $20.00"), "Got {}", large.message);
+
+    let due = raised
+        .iter()
+        .find(|a| a.alert_type == "payment_due" && a.entity_id == Some(debt_id))
+        .expect("Payment due alert should be raised");
+    assert!(due.title.ends_with("payment due in 3 days"));
+    assert_eq!(due.message, "$35.00 minimum payment due 1998-06-18");
+
+    // The same events aren't raised again
+    let again = evaluate_alerts_impl(db, today).await.unwrap();
+    assert!(!again.iter().any(|a| [target.id, large.id, due.id].contains(&a.id)));
+    assert!(!again.iter().any(|a| a.entity_id == Some(debt_id) || a.entity_id == Some(large_id)));
+
+    dismiss_alert_impl(db, due.id).await.unwrap();
+    let active = list_alerts_impl(db, false).await.unwrap();
+    assert!(active.iter().any(|a| a.id == large.id));
+    assert!(!active.iter().any(|a| a.id == due.id));
+    let all = list_alerts_impl(db, true).await.unwrap();
+    assert!(all.iter().any(|a| a.id == due.id && a.dismissed_at.is_some()));
+
+    // Dismissed alerts stay dismissed through re-evaluation
+    evaluate_alerts_impl(db, today).await.unwrap();
+    assert!(!list_alerts_impl(db, false).await.unwrap().iter().any(|a| a.entity_id == Some(debt_id)));
+
+    assert!(dismiss_alert_impl(db, 999_999_999).await.is_err());
+}
@@ -0,0 +1,57 @@
+use budget_balancer_lib::commands::analytics_commands::get_amount_distribution_impl;
+use budget_balancer_lib::commands::transaction_commands::TransactionFilter;
+
+fn account_filter(account_id: i64) -> TransactionFilter {
+    TransactionFilter {
+        account_id: Some(vec![account_id]),
+        category_id: None,
+        start_date: None,
+        end_date: None,
+        search: None,
+        min_amount_cents: None,
+        max_amount_cents: None,
+        only_uncategorized: None,
+        exclude_category_ids: None,
+        pending: None,
+        member_id: None,
+        limit: Some(1), // Paging doesn't apply to the distribution
+        offset: None,
+    }
+}
+
+#[tokio::test]
+async fn test_get_amount_distribution() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, &super::unique_name("Distribution")).await;
+    let mut transactions: Vec<_> = (1..=9)
+        .map(|n| super::fixtures::TestTransaction::new("1972-04-10", -(n as i64) * 500, &format!("Coffee {}", n)))
+        .collect();
+    transactions.push(super::fixtures::TestTransaction::new("1972-04-11", -70_000, "Flight"));
+    transactions.push(super::fixtures::TestTransaction::new("1972-04-12", 150_000, "Salary"));
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let expenses = get_amount_distribution_impl(db, Some(account_filter(account_id)), None)
+        .await
+        .expect("Distribution should succeed");
+    assert_eq!(expenses.count, 10, "Income is left out of the default expense distribution");
+    assert_eq!(expenses.total_cents, 92_500);
+    assert_eq!(expenses.average, 92.5);
+    assert_eq!(expenses.p50_cents, 2500);
+    assert_eq!(expenses.p90_cents, 4500);
+    assert_eq!(expenses.p99_cents, 70_000);
+
+    let counts: Vec<(Option<i64>, i64)> = expenses.buckets.iter().map(|b| (b.max_cents, b.count)).collect();
+    assert_eq!(counts[0], (Some(1000), 2));
+    assert_eq!(counts[1], (Some(2500), 3));
+    assert_eq!(counts[2], (Some(5000), 4));
+    assert_eq!(counts.iter().map(|(_, count)| count).sum::<i64>(), 10);
+    assert_eq!(counts.last(), Some(&(None, 0)));
+
+    let both = get_amount_distribution_impl(db, Some(account_filter(account_id)), Some("both")).await.unwrap();
+    assert_eq!(both.count, 11);
+    assert_eq!(both.p99_cents, 150_000);
+    let income = get_amount_distribution_impl(db, Some(account_filter(account_id)), Some("income")).await.unwrap();
+    assert_eq!((income.count, income.p50_cents), (1, 150_000));
+
+    assert!(get_amount_distribution_impl(db, Some(account_filter(account_id)), Some("sideways")).await.is_err());
+}
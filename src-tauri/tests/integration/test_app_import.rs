@@ -0,0 +1,89 @@
+use budget_balancer_lib::commands::data_commands::import_from_app_impl;
+use budget_balancer_lib::errors::DataArchiveError;
+use budget_balancer_lib::models::data_archive::{AppExportFormat, ArchiveConflict};
+
+#[tokio::test]
+async fn test_import_mint_export() {
+    let db = super::get_test_db_pool().await;
+    let tag = super::unique_word("mint");
+    let csv = format!(
+        "\"Date\",\"Description\",\"Original Description\",\"Amount\",\"Transaction Type\",\"Category\",\"Account Name\",\"Labels\",\"Notes\"
+\"3/04/1982\",\"Cafe {tag}\",\"CAFE {tag} 0042\",\"3.75\",\"debit\",\"Coffee {tag}\",\"Mint Card {tag}\",\"\",\"\"
+\"3/05/1982\",\"Cafe {tag}\",\"CAFE {tag} 0042\",\"3.75\",\"debit\",\"Coffee {tag}\",\"Mint Card {tag}\",\"\",\"\"
+\"3/06/1982\",\"Refund {tag}\",\"REFUND {tag}\",\"20.00\",\"credit\",\"Uncategorized\",\"Mint Card {tag}\",\"\",\"\"
+"
+    );
+
+    let result = import_from_app_impl(db, AppExportFormat::Mint, &csv, ArchiveConflict::Skip, false)
+        .await
+        .expect("Mint import should succeed");
+    assert_eq!(result.accounts.created, 1);
+    assert_eq!(result.categories.created, 1);
+    assert_eq!(result.transactions.created, 3);
+
+    let (account_type, balance_cents): (String, i64) = sqlx::query_as("SELECT type, balance_cents FROM accounts WHERE name = ?")
+        .bind(format!("Mint Card {}", tag))
+        .fetch_one(db)
+        .await
+        .unwrap();
+    assert_eq!(account_type, "credit_card");
+    assert_eq!(balance_cents, 1250);
+
+    let (categorized,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM transactions t JOIN categories c ON c.id = t.category_id
+         WHERE c.name = ? AND t.description = ?"
+    )
+    .bind(format!("Coffee {}", tag))
+    .bind(format!("CAFE {} 0042", tag))
+    .fetch_one(db)
+    .await
+    .unwrap();
+    assert_eq!(categorized, 2, "Mint's category assignments are kept");
+
+    // Importing the same export again adds nothing
+    let again = import_from_app_impl(db, AppExportFormat::Mint, &csv, ArchiveConflict::Skip, false)
+        .await
+        .expect("Second import should succeed");
+    assert_eq!(again.transactions.created, 0);
+    assert_eq!(again.transactions.skipped, 3);
+}
+
+#[tokio::test]
+async fn test_import_ynab_export() {
+    let db = super::get_test_db_pool().await;
+    let tag = super::unique_word("ynab");
+    let csv = format!(
+        "\"Account\",\"Flag\",\"Date\",\"Payee\",\"Category Group/Category\",\"Category Group\",\"Category\",\"Memo\",\"Outflow\",\"Inflow\",\"Cleared\"
+\"Budget {tag}\",\"\",\"03/04/1982\",\"Grocer {tag}\",\"Needs {tag}: Food {tag}\",\"Needs {tag}\",\"Food {tag}\",\"\",\"$40.00\",\"$0.00\",\"Cleared\"
+"
+    );
+
+    let result = import_from_app_impl(db, AppExportFormat::Ynab, &csv, ArchiveConflict::Skip, false)
+        .await
+        .expect("YNAB import should succeed");
+    assert_eq!(result.categories.created, 2);
+    assert_eq!(result.transactions.created, 1);
+
+    let (parent,): (String,) = sqlx::query_as(
+        "SELECT p.name FROM categories c JOIN categories p ON p.id = c.parent_id WHERE c.name = ?"
+    )
+    .bind(format!("Food {}", tag))
+    .fetch_one(db)
+    .await
+    .unwrap();
+    assert_eq!(parent, format!("Needs {}", tag), "Category groups become parent categories");
+}
+
+#[tokio::test]
+async fn test_import_rejects_wrong_format() {
+    let db = super::get_test_db_pool().await;
+    let result = import_from_app_impl(
+        db,
+        AppExportFormat::Ynab,
+        "Date,Description,Amount\n1982-03-04,Test,1.00\n",
+        ArchiveConflict::Skip,
+        false,
+    )
+    .await;
+    assert!(matches!(result, Err(DataArchiveError::Invalid(_))));
+}
@@ -0,0 +1,144 @@
+use budget_balancer_lib::commands::audit_commands::{list_audit_log_impl, prune_audit_log_impl};
+use budget_balancer_lib::commands::debt_commands::{create_debt_impl, record_debt_payment_impl};
+use budget_balancer_lib::commands::transaction_commands::{
+    bulk_update_category_impl, create_transaction_impl, delete_transaction_impl, update_transaction_impl,
+};
+use budget_balancer_lib::models::audit_log::{AuditAction, AuditEntity, AuditLogEntry, AuditLogFilter};
+use budget_balancer_lib::models::debt::NewDebt;
+use budget_balancer_lib::models::transaction::{CreateTransaction, UpdateTransaction};
+use serial_test::serial;
+use sqlx::SqlitePool;
+
+async fn history(db: &SqlitePool, entity_type: AuditEntity, entity_id: i64) -> Vec<AuditLogEntry> {
+    list_audit_log_impl(db, Some(AuditLogFilter {
+        entity_type: Some(entity_type),
+        entity_id: Some(entity_id),
+        ..Default::default()
+    }))
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_transaction_changes_are_audited() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Audit Test").await;
+
+    let created = create_transaction_impl(db, CreateTransaction {
+        account_id,
+        category_id: Some(1),
+        date: "2004-02-10".to_string(),
+        amount_cents: -4200,
+        description: super::unique_name("Audited purchase"),
+        merchant: None,
+        notes: None,
+    }, false)
+    .await
+    .unwrap();
+
+    update_transaction_impl(db, UpdateTransaction {
+        id: created.id,
+        account_id: None,
+        category_id: None,
+        date: None,
+        amount_cents: Some(-4000),
+        description: None,
+        merchant: None,
+        notes: None,
+    }, false)
+    .await
+    .unwrap();
+    bulk_update_category_impl(db, vec![created.id], 2, false).await.unwrap();
+    delete_transaction_impl(db, created.id, false).await.unwrap();
+
+    // Newest first
+    let entries = history(db, AuditEntity::Transaction, created.id).await;
+    let actions: Vec<&str> = entries.iter().map(|e| e.action.as_str()).collect();
+    assert_eq!(actions, vec!["delete", "update", "update", "create"]);
+
+    let create = &entries[3];
+    assert!(create.old_values.is_none());
+    assert_eq!(create.new_values.as_ref().unwrap().0["amount"], -42.0);
+
+    let edit = &entries[2];
+    assert_eq!(edit.old_values.as_ref().unwrap().0["amount"], -42.0);
+    assert_eq!(edit.new_values.as_ref().unwrap().0["amount"], -40.0);
+
+    let recategorize = &entries[1];
+    assert_eq!(recategorize.old_values.as_ref().unwrap().0["category_id"], 1);
+    assert_eq!(recategorize.new_values.as_ref().unwrap().0["category_id"], 2);
+
+    let delete = &entries[0];
+    assert_eq!(delete.old_values.as_ref().unwrap().0["amount"], -40.0);
+    assert!(delete.new_values.is_none());
+
+    let deletes = list_audit_log_impl(db, Some(AuditLogFilter {
+        entity_type: Some(AuditEntity::Transaction),
+        entity_id: Some(created.id),
+        action: Some(AuditAction::Delete),
+        ..Default::default()
+    }))
+    .await
+    .unwrap();
+    assert_eq!(deletes.len(), 1);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_debt_payment_is_audited() {
+    let db = super::get_test_db_pool().await;
+    let debt_id = create_debt_impl(db, NewDebt {
+        name: super::unique_name("Audit Loan"),
+        balance_cents: 100_000,
+        interest_rate: 0.0,
+        min_payment_cents: 5000,
+    })
+    .await
+    .unwrap();
+
+    let payment = record_debt_payment_impl(db, debt_id, 10_000, "2099-01-15".to_string(), None).await.unwrap();
+
+    let debt_history = history(db, AuditEntity::Debt, debt_id).await;
+    assert_eq!(debt_history.len(), 2);
+    assert_eq!(debt_history[0].action, "update");
+    assert_eq!(debt_history[0].old_values.as_ref().unwrap().0["balance"], 1000.0);
+    assert_eq!(debt_history[0].new_values.as_ref().unwrap().0["balance"], 900.0);
+    assert_eq!(debt_history[1].action, "create");
+
+    let payment_history = history(db, AuditEntity::DebtPayment, payment.payment_id).await;
+    assert_eq!(payment_history.len(), 1);
+    assert_eq!(payment_history[0].new_values.as_ref().unwrap().0["debt_id"], debt_id);
+}
+
+#[tokio::test]
+async fn test_prune_audit_log_removes_only_old_entries() {
+    let db = super::get_test_db_pool().await;
+    let entity_id = i64::MAX - 7;
+    // The test database persists between runs, so clear rows left by earlier runs
+    sqlx::query("DELETE FROM audit_log WHERE entity_id = ?")
+        .bind(entity_id)
+        .execute(db)
+        .await
+        .unwrap();
+
+    for created_at in ["2000-01-01 00:00:00", "2999-01-01 00:00:00"] {
+        sqlx::query(
+            "INSERT INTO audit_log (action, entity_type, entity_id, new_values, created_at)
+             VALUES ('create', 'category', ?, '{}', ?)"
+        )
+        .bind(entity_id)
+        .bind(created_at)
+        .execute(db)
+        .await
+        .unwrap();
+    }
+
+    let pruned = prune_audit_log_impl(db, 365).await.unwrap();
+    assert!(pruned >= 1);
+
+    let remaining = history(db, AuditEntity::Category, entity_id).await;
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].created_at, "2999-01-01 00:00:00");
+
+    assert!(prune_audit_log_impl(db, -1).await.is_err());
+}
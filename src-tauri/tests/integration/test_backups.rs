@@ -0,0 +1,71 @@
+use budget_balancer_lib::commands::backup_commands::{
+    list_backup_history_impl, run_backup_impl, run_scheduled_backup_impl,
+};
+use budget_balancer_lib::commands::settings_commands::update_setting_impl;
+use budget_balancer_lib::models::setting::SettingKey;
+use serial_test::serial;
+use sqlx::SqlitePool;
+
+async fn set(db: &SqlitePool, key: SettingKey, value: Option<&str>) {
+    update_setting_impl(db, &key.to_string(), value).await.expect("Setting should save");
+}
+
+fn backup_files(dir: &std::path::Path) -> usize {
+    std::fs::read_dir(dir).unwrap().filter(|entry| entry.is_ok()).count()
+}
+
+#[tokio::test]
+#[serial]
+async fn test_run_backup_writes_and_prunes() {
+    let db = super::get_test_db_pool().await;
+    let dir = std::env::temp_dir().join(super::unique_name("backups").replace(' ', "_"));
+    std::fs::create_dir_all(&dir).unwrap();
+    let dir_setting = dir.display().to_string();
+
+    set(db, SettingKey::BackupDir, None).await;
+    assert!(run_backup_impl(db, "manual").await.is_err(), "A backup folder is needed");
+
+    set(db, SettingKey::BackupDir, Some(&dir_setting)).await;
+    set(db, SettingKey::BackupRetention, Some("2")).await;
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let backup = run_backup_impl(db, "manual").await.expect("Backup should succeed");
+        assert_eq!(backup.status, "succeeded");
+        assert!(backup.size_bytes.unwrap_or(0) > 0);
+        ids.push(backup.id);
+        // Backup names are timestamped to the millisecond
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+    assert_eq!(backup_files(&dir), 2, "Only the newest backups are kept");
+
+    let history = list_backup_history_impl(db, Some(100)).await.unwrap();
+    let last = history.iter().find(|b| b.id == ids[2]).expect("Backup should be recorded");
+    assert_eq!(last.kind, "manual");
+    assert_eq!(last.pruned_count, 1);
+    let content = std::fs::read_to_string(last.file_path.as_deref().unwrap()).unwrap();
+    assert!(content.contains("\"transactions\""));
+
+    // Not due again until a day has passed
+    set(db, SettingKey::BackupFrequency, Some("daily")).await;
+    assert!(run_scheduled_backup_impl(db).await.unwrap().is_none());
+    assert_eq!(backup_files(&dir), 2);
+
+    set(db, SettingKey::BackupFrequency, None).await;
+    set(db, SettingKey::BackupRetention, None).await;
+    set(db, SettingKey::BackupDir, None).await;
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_backup_settings_are_validated() {
+    let db = super::get_test_db_pool().await;
+    for (key, value) in [
+        (SettingKey::BackupFrequency, "hourly"),
+        (SettingKey::BackupRetention, "0"),
+        (SettingKey::BackupRetention, "ten"),
+        (SettingKey::BackupDir, "relative/backups"),
+    ] {
+        assert!(update_setting_impl(db, &key.to_string(), Some(value)).await.is_err(), "{} = {}", key, value);
+    }
+}
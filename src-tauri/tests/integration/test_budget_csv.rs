@@ -0,0 +1,96 @@
+use budget_balancer_lib::commands::analytics_commands::{
+    create_spending_target_impl, export_budgets_csv_impl, import_budgets_csv_impl, BudgetImportAction,
+};
+use budget_balancer_lib::commands::category_commands::create_category_impl;
+use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::models::spending_target::NewSpendingTarget;
+use budget_balancer_lib::utils::money::Cents;
+use sqlx::SqlitePool;
+
+async fn category(db: &SqlitePool, name: &str) -> (i64, String) {
+    let name = super::unique_name(name);
+    let id = create_category_impl(db, NewCategory { name: name.clone(), icon: None, color: None })
+        .await
+        .expect("Failed to create category");
+    (id, name)
+}
+
+async fn target(db: &SqlitePool, category_id: i64, amount_cents: Cents, start_date: &str) -> i64 {
+    create_spending_target_impl(db, NewSpendingTarget {
+        category_id: Some(category_id),
+        account_id: None,
+        amount_cents,
+        period: "monthly".to_string(),
+        start_date: start_date.to_string(),
+        end_date: None,
+    })
+    .await
+    .expect("Failed to create target")
+}
+
+async fn targets(db: &SqlitePool, category_id: i64) -> Vec<(Cents, String, String, Option<String>)> {
+    sqlx::query_as(
+        "SELECT amount_cents, period, start_date, end_date FROM spending_targets WHERE category_id = ? ORDER BY start_date",
+    )
+    .bind(category_id)
+    .fetch_all(db)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_import_budgets_csv() {
+    let db = super::get_test_db_pool().await;
+    let (raised_id, raised) = category(db, "Budget Csv Groceries").await;
+    let (new_id, new) = category(db, "Budget Csv Travel").await;
+    let (same_id, same) = category(db, "Budget Csv Coffee").await;
+    let earlier = target(db, raised_id, 10_000, "1955-01-01").await;
+    let kept = target(db, same_id, 5000, "1956-01-01").await;
+
+    let rows = format!("Category,Period,Amount\n{raised},monthly,$150.00\n\"{new}\",Quarterly,80\n{same},,50.00\n");
+    let csv = format!("{rows}Budget Csv Nowhere,monthly,10\n{new},quarterly,90\n{same},weekly,5\n");
+    let report = import_budgets_csv_impl(db, &csv, Some("1956-01-01"), false).await.unwrap();
+    assert!(!report.applied, "Nothing is written while any row has an error");
+    assert_eq!(report.errors, 3);
+    let errors: Vec<usize> = report
+        .rows
+        .iter()
+        .filter(|row| row.action == BudgetImportAction::Error)
+        .map(|row| row.line)
+        .collect();
+    assert_eq!(errors, vec![5, 6, 7], "Unknown categories, repeats and bad periods are reported by line");
+    assert_eq!(targets(db, new_id).await.len(), 0);
+
+    let preview = import_budgets_csv_impl(db, &rows, Some("1956-01-01"), true).await.unwrap();
+    assert!(!preview.applied);
+    assert_eq!((preview.created, preview.updated, preview.unchanged, preview.errors), (1, 1, 1, 0));
+    assert_eq!(preview.rows[0].target_id, Some(earlier));
+    assert_eq!(preview.rows[2].target_id, Some(kept));
+    assert_eq!(preview.rows[2].period, "monthly", "A blank period means monthly");
+    assert_eq!(targets(db, new_id).await.len(), 0, "A dry run writes nothing");
+
+    let report = import_budgets_csv_impl(db, &rows, Some("1956-01-01"), false).await.unwrap();
+    assert!(report.applied);
+    assert_eq!(
+        targets(db, raised_id).await,
+        vec![
+            (10_000, "monthly".to_string(), "1955-01-01".to_string(), Some("1955-12-31".to_string())),
+            (15_000, "monthly".to_string(), "1956-01-01".to_string(), None),
+        ],
+        "The earlier target keeps its past periods"
+    );
+    assert_eq!(targets(db, new_id).await, vec![(8000, "quarterly".to_string(), "1956-01-01".to_string(), None)]);
+    assert_eq!(targets(db, same_id).await.len(), 1);
+
+    let again = import_budgets_csv_impl(db, &rows, Some("1956-01-01"), false).await.unwrap();
+    assert_eq!(again.unchanged, 3, "Importing the same file twice changes nothing");
+
+    let path = std::env::temp_dir().join(format!("{}.csv", super::unique_word("budgets")));
+    let export = export_budgets_csv_impl(db, &path.display().to_string()).await.unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(export.record_count >= 3);
+    assert!(content.starts_with("Category,Period,Amount\n"));
+    assert!(content.contains(&format!("{raised},monthly,150.00")));
+    assert!(content.contains(&format!("{new},quarterly,80.00")));
+}
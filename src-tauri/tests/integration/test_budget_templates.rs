@@ -0,0 +1,157 @@
+use budget_balancer_lib::commands::category_commands::{
+    apply_budget_template_impl, create_category_impl, list_budget_templates_impl,
+    list_categories_impl,
+};
+use budget_balancer_lib::errors::CategoryError;
+use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::models::category_template::{BudgetTemplateSource, ConflictResolution};
+use budget_balancer_lib::utils::money::Cents;
+
+async fn budgets_for(db: &sqlx::SqlitePool, category_id: i64) -> Vec<(Cents, String)> {
+    sqlx::query_as("SELECT amount_cents, period FROM spending_targets WHERE category_id = ? ORDER BY id")
+        .bind(category_id)
+        .fetch_all(db)
+        .await
+        .unwrap()
+}
+
+fn conflict_template(existing: &str, child: &str) -> BudgetTemplateSource {
+    BudgetTemplateSource::Imported {
+        content: serde_json::json!({
+            "version": 1,
+            "categories": [{
+                "name": existing,
+                "budgets": [{ "amount": 100.0, "period": "monthly" }],
+                "children": [{ "name": child }]
+            }]
+        })
+        .to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_list_budget_templates() {
+    let templates = list_budget_templates_impl().expect("Failed to list templates");
+
+    let fifty = templates.iter().find(|t| t.id == "fifty_thirty_twenty").expect("50/30/20 should be bundled");
+    assert!(fifty.requires_income);
+    assert!(fifty.description.is_some());
+    assert!(templates.iter().any(|t| t.id == "essentials"));
+}
+
+#[tokio::test]
+async fn test_apply_bundled_template() {
+    let db = super::get_test_db_pool().await;
+
+    let result = apply_budget_template_impl(
+        db,
+        BudgetTemplateSource::Bundled { id: "fifty_thirty_twenty".to_string() },
+        None,
+        None,
+        None,
+    )
+    .await;
+    assert!(matches!(result, Err(CategoryError::ValidationError(_))), "Percentage budgets need an income");
+
+    let result = apply_budget_template_impl(
+        db,
+        BudgetTemplateSource::Bundled { id: "essentials".to_string() },
+        Some(ConflictResolution::Merge),
+        None,
+        Some("2019-01-01"),
+    )
+    .await
+    .expect("Failed to apply bundled template");
+    assert_eq!(result.categories_created + result.categories_existing, 6);
+
+    let categories = list_categories_impl(db, false).await.unwrap();
+    assert!(categories.iter().any(|c| c.name == "Subscriptions"));
+
+    let result = apply_budget_template_impl(
+        db,
+        BudgetTemplateSource::Bundled { id: "missing".to_string() },
+        None,
+        None,
+        None,
+    )
+    .await;
+    assert!(matches!(result, Err(CategoryError::ValidationError(_))));
+}
+
+#[tokio::test]
+async fn test_apply_template_conflict_resolution() {
+    let db = super::get_test_db_pool().await;
+    let existing = super::unique_name("Template Conflict");
+    let existing_id = create_category_impl(db, NewCategory {
+        name: existing.clone(),
+        icon: None,
+        color: None,
+    })
+    .await
+    .unwrap();
+
+    // Skip: existing category untouched, children still attach to it
+    let skip_child = super::unique_name("Skip Child");
+    let result = apply_budget_template_impl(db, conflict_template(&existing, &skip_child), Some(ConflictResolution::Skip), None, Some("2019-01-01"))
+        .await
+        .unwrap();
+    assert_eq!(result.categories_skipped, 1);
+    assert_eq!(result.budgets_skipped, 1);
+    assert_eq!(result.categories_created, 1);
+    assert!(budgets_for(db, existing_id).await.is_empty());
+    let categories = list_categories_impl(db, false).await.unwrap();
+    assert_eq!(categories.iter().find(|c| c.name == skip_child).unwrap().parent_id, Some(existing_id));
+
+    // Rename: a copy is created next to the existing category
+    let rename_child = super::unique_name("Rename Child");
+    let result = apply_budget_template_impl(db, conflict_template(&existing, &rename_child), Some(ConflictResolution::Rename), None, Some("2019-01-01"))
+        .await
+        .unwrap();
+    assert_eq!(result.categories_renamed, 1);
+    let categories = list_categories_impl(db, false).await.unwrap();
+    let renamed = categories.iter().find(|c| c.name == format!("{} (2)", existing)).expect("Renamed copy should exist");
+    assert_eq!(budgets_for(db, renamed.id).await, vec![(10_000, "monthly".to_string())]);
+    assert_eq!(categories.iter().find(|c| c.name == rename_child).unwrap().parent_id, Some(renamed.id));
+
+    // Merge (default): budgets are added to the existing category
+    let merge_child = super::unique_name("Merge Child");
+    let result = apply_budget_template_impl(db, conflict_template(&existing, &merge_child), None, None, Some("2019-01-01"))
+        .await
+        .unwrap();
+    assert_eq!(result.categories_existing, 1);
+    assert_eq!(result.budgets_created, 1);
+    assert_eq!(budgets_for(db, existing_id).await, vec![(10_000, "monthly".to_string())]);
+}
+
+#[tokio::test]
+async fn test_apply_template_percentage_budgets() {
+    let db = super::get_test_db_pool().await;
+    let name = super::unique_name("Percent Budget");
+    let template = BudgetTemplateSource::Imported {
+        content: serde_json::json!({
+            "version": 1,
+            "categories": [{
+                "name": name,
+                "budgets": [
+                    { "percent_of_income": 10.0, "period": "monthly" },
+                    { "percent_of_income": 10.0, "period": "quarterly" }
+                ]
+            }]
+        })
+        .to_string(),
+    };
+
+    let result = apply_budget_template_impl(db, template.clone(), None, Some(0), None).await;
+    assert!(matches!(result, Err(CategoryError::ValidationError(_))), "Income must be positive");
+
+    apply_budget_template_impl(db, template, None, Some(500_000), Some("2019-01-01"))
+        .await
+        .expect("Failed to apply percentage template");
+
+    let categories = list_categories_impl(db, false).await.unwrap();
+    let category = categories.iter().find(|c| c.name == name).unwrap();
+    assert_eq!(
+        budgets_for(db, category.id).await,
+        vec![(50_000, "monthly".to_string()), (150_000, "quarterly".to_string())]
+    );
+}
@@ -0,0 +1,86 @@
+use budget_balancer_lib::commands::analytics_commands::{create_spending_target_impl, get_budget_variance_report_impl};
+use budget_balancer_lib::commands::category_commands::create_category_impl;
+use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::models::spending_target::NewSpendingTarget;
+use budget_balancer_lib::utils::money::Cents;
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+
+async fn category_with_target(db: &SqlitePool, name: &str, amount_cents: Cents, period: &str) -> i64 {
+    let category_id = create_category_impl(db, NewCategory {
+        name: super::unique_name(name),
+        icon: None,
+        color: None,
+    })
+    .await
+    .expect("Failed to create category");
+    create_spending_target_impl(db, NewSpendingTarget {
+        category_id: Some(category_id),
+        account_id: None,
+        amount_cents,
+        period: period.to_string(),
+        start_date: "1995-01-01".to_string(),
+        end_date: Some("1995-12-31".to_string()),
+    })
+    .await
+    .expect("Failed to create target");
+    category_id
+}
+
+#[tokio::test]
+async fn test_budget_variance_report() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Variance Checking").await;
+    let groceries = category_with_target(db, "Variance Groceries", 30_000, "monthly").await;
+    let gifts = category_with_target(db, "Variance Gifts", 120_000, "yearly").await;
+    let unbudgeted = create_category_impl(db, NewCategory {
+        name: super::unique_name("Variance Hobbies"),
+        icon: None,
+        color: None,
+    })
+    .await
+    .unwrap();
+
+    super::fixtures::insert_test_transactions(db, account_id, vec![
+        super::fixtures::TestTransaction::new("1995-04-05", -10_000, "Market").with_category(groceries),
+        super::fixtures::TestTransaction::new("1995-04-10", -5000, "Market").with_category(groceries),
+        super::fixtures::TestTransaction::new("1995-04-12", -4000, "Flowers").with_category(gifts),
+        super::fixtures::TestTransaction::new("1995-04-14", -2500, "Paint").with_category(unbudgeted),
+    ])
+    .await;
+
+    // Halfway through April
+    let today = NaiveDate::from_ymd_opt(1995, 4, 15).unwrap();
+    let report = get_budget_variance_report_impl(db, "monthly", None, today).await.unwrap();
+    assert_eq!(report.start_date, "1995-04-01");
+    assert_eq!(report.end_date, "1995-04-30");
+    assert_eq!((report.days_elapsed, report.total_days), (15, 30));
+    assert!(report.unbudgeted_spending_cents >= 2500 - 1);
+
+    let row = report.categories.iter().find(|c| c.category_id == groceries).unwrap();
+    assert_eq!(row.budget_cents, 30_000);
+    assert_eq!(row.actual_cents, 15_000);
+    assert_eq!(row.variance_cents, -15_000);
+    assert!((row.percent_consumed - 50.0).abs() < 0.01);
+    assert_eq!(row.projected_cents, 30_000);
+
+    // A yearly budget contributes a month's share
+    let row = report.categories.iter().find(|c| c.category_id == gifts).unwrap();
+    assert_eq!(row.budget_cents, 10_000);
+    assert_eq!(row.projected_cents, 8000);
+    assert!(!report.categories.iter().any(|c| c.category_id == unbudgeted));
+
+    // Once the quarter is over the projection is just what was spent
+    let later = NaiveDate::from_ymd_opt(1996, 1, 1).unwrap();
+    let report = get_budget_variance_report_impl(db, "quarterly", Some("1995-05-20"), later).await.unwrap();
+    assert_eq!(report.start_date, "1995-04-01");
+    assert_eq!(report.end_date, "1995-06-30");
+    assert_eq!(report.days_elapsed, report.total_days);
+    let row = report.categories.iter().find(|c| c.category_id == groceries).unwrap();
+    assert_eq!(row.budget_cents, 90_000);
+    assert_eq!(row.projected_cents, row.actual_cents);
+    assert_eq!(row.projected_variance_cents, -75_000);
+
+    assert!(get_budget_variance_report_impl(db, "weekly", None, today).await.is_err());
+    assert!(get_budget_variance_report_impl(db, "monthly", Some("April"), today).await.is_err());
+}
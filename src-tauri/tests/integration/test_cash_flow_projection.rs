@@ -0,0 +1,83 @@
+use budget_balancer_lib::commands::account_commands::create_account_impl;
+use budget_balancer_lib::commands::cash_flow_commands::get_cash_flow_projection_impl;
+use budget_balancer_lib::commands::debt_commands::{
+    create_debt_impl, list_debt_autopays_impl, remove_debt_autopay_impl, set_debt_autopay_impl,
+};
+use budget_balancer_lib::errors::DebtError;
+use budget_balancer_lib::models::account::{AccountType, NewAccount};
+use budget_balancer_lib::models::debt::NewDebt;
+
+#[tokio::test]
+async fn test_cash_flow_projection_with_autopay() {
+    let db = super::get_test_db_pool().await;
+    let account_id = create_account_impl(
+        db,
+        NewAccount {
+            name: super::unique_name("Autopay Checking"),
+            account_type: AccountType::Checking,
+            initial_balance_cents: 30_000,
+        },
+    )
+    .await
+    .unwrap();
+    let debt_id = create_debt_impl(
+        db,
+        NewDebt {
+            name: super::unique_name("Autopay Card"),
+            balance_cents: 100_000,
+            interest_rate: 19.99,
+            min_payment_cents: 20_000,
+        },
+    )
+    .await
+    .unwrap();
+
+    let autopay = set_debt_autopay_impl(db, debt_id, account_id, None).await.expect("Failed to set autopay");
+    assert_eq!(autopay.account_id, account_id);
+    assert_eq!(autopay.amount_cents, None);
+    assert!(list_debt_autopays_impl(db).await.unwrap().iter().any(|a| a.debt_id == debt_id));
+
+    // Due dates Jan 15 and Feb 15; the second payment overdraws the account
+    let projection = get_cash_flow_projection_impl(db, Some(60), Some("2030-01-01")).await.unwrap();
+    assert_eq!(projection.start_date, "2030-01-01");
+    assert_eq!(projection.end_date, "2030-03-01");
+
+    let account = projection.accounts.iter().find(|a| a.account_id == account_id).unwrap();
+    assert_eq!(account.starting_balance_cents, 30_000);
+    assert_eq!(account.ending_balance_cents, -10_000);
+    assert_eq!(account.lowest_balance_date, "2030-02-15");
+
+    let payments: Vec<&str> = projection
+        .payments
+        .iter()
+        .filter(|p| p.debt_id == debt_id)
+        .map(|p| p.date.as_str())
+        .collect();
+    assert_eq!(payments, vec!["2030-01-15", "2030-02-15"]);
+
+    let warnings: Vec<_> = projection.warnings.iter().filter(|w| w.account_id == account_id).collect();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].shortfall_cents, 10_000);
+
+    // A fixed amount replaces the minimum payment
+    set_debt_autopay_impl(db, debt_id, account_id, Some(5000)).await.unwrap();
+    let projection = get_cash_flow_projection_impl(db, Some(60), Some("2030-01-01")).await.unwrap();
+    assert!(!projection.warnings.iter().any(|w| w.account_id == account_id));
+
+    assert!(remove_debt_autopay_impl(db, debt_id).await.unwrap());
+    let projection = get_cash_flow_projection_impl(db, Some(60), Some("2030-01-01")).await.unwrap();
+    assert!(!projection.payments.iter().any(|p| p.debt_id == debt_id));
+}
+
+#[tokio::test]
+async fn test_debt_autopay_validation() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Autopay Validation").await;
+
+    assert!(matches!(
+        set_debt_autopay_impl(db, -1, account_id, None).await,
+        Err(DebtError::NotFound(-1))
+    ));
+    assert!(get_cash_flow_projection_impl(db, Some(0), None).await.is_err());
+    assert!(get_cash_flow_projection_impl(db, None, Some("2030-02-30")).await.is_err());
+}
@@ -15,7 +15,7 @@ async fn test_categorize_transaction_with_matching_rule() {
     let account = NewAccount {
         name: super::unique_name("Categorize Test"),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 0.0,
+        initial_balance_cents: 0,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -26,6 +26,7 @@ async fn test_categorize_transaction_with_matching_rule() {
         amount: "Amount".to_string(),
         description: "Description".to_string(),
         merchant: Some("Merchant".to_string()),
+        ..Default::default()
     };
 
     import_csv_impl(db, account_id, csv_content.to_string(), mapping)
@@ -54,7 +55,7 @@ async fn test_categorize_transaction_no_rule_match() {
     let account = NewAccount {
         name: super::unique_name("No Match Test"),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 0.0,
+        initial_balance_cents: 0,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -65,6 +66,7 @@ async fn test_categorize_transaction_no_rule_match() {
         amount: "Amount".to_string(),
         description: "Description".to_string(),
         merchant: Some("Merchant".to_string()),
+        ..Default::default()
     };
 
     import_csv_impl(db, account_id, csv_content.to_string(), mapping)
@@ -88,7 +90,7 @@ async fn test_categorize_transaction_custom_category() {
     let account = NewAccount {
         name: super::unique_name("Custom Cat Test"),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 0.0,
+        initial_balance_cents: 0,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -96,6 +98,7 @@ async fn test_categorize_transaction_custom_category() {
     let category = NewCategory {
         name: super::unique_name("Test Category"),
         icon: Some("🎯".to_string()),
+        color: None,
     };
     let _category_id = create_category_impl(db, category)
         .await
@@ -108,6 +111,7 @@ async fn test_categorize_transaction_custom_category() {
         amount: "Amount".to_string(),
         description: "Description".to_string(),
         merchant: Some("Merchant".to_string()),
+        ..Default::default()
     };
 
     import_csv_impl(db, account_id, csv_content.to_string(), mapping)
@@ -1,5 +1,16 @@
-use budget_balancer_lib::commands::category_commands::{create_category_impl, list_categories_impl};
-use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::commands::analytics_commands::get_spending_by_category_impl;
+use budget_balancer_lib::commands::category_commands::{
+    archive_category_impl, create_category_impl, list_categories_impl, unarchive_category_impl,
+    update_category_impl,
+};
+use budget_balancer_lib::errors::CategoryError;
+use budget_balancer_lib::models::category::{CategoryType, NewCategory, UpdateCategory};
+use budget_balancer_lib::open_database;
+use budget_balancer_lib::utils::access_guard::AccessGuard;
+
+fn rename(id: i64, name: &str) -> UpdateCategory {
+    UpdateCategory { id, name: Some(name.to_string()), icon: None, color: None, category_type: None }
+}
 
 #[tokio::test]
 async fn test_create_category() {
@@ -7,6 +18,7 @@ async fn test_create_category() {
     let category = NewCategory {
         name: super::unique_name("Test Category"),
         icon: Some("💰".to_string()),
+        color: None,
     };
 
     let result = create_category_impl(db, category).await;
@@ -22,6 +34,7 @@ async fn test_create_category_without_icon() {
     let category = NewCategory {
         name: super::unique_name("No Icon Category"),
         icon: None,
+        color: None,
     };
 
     let result = create_category_impl(db, category).await;
@@ -35,11 +48,12 @@ async fn test_list_categories() {
     let category = NewCategory {
         name: super::unique_name("List Test Category"),
         icon: Some("📊".to_string()),
+        color: None,
     };
 
     let _ = create_category_impl(db, category).await.expect("Failed to create category");
 
-    let result = list_categories_impl(db).await;
+    let result = list_categories_impl(db, false).await;
     assert!(result.is_ok(), "Failed to list categories: {:?}", result);
 
     let categories = result.unwrap();
@@ -54,7 +68,7 @@ async fn test_list_categories() {
 #[tokio::test]
 async fn test_list_categories_includes_seeded_categories() {
     let db = super::get_test_db_pool().await;
-    let categories = list_categories_impl(db).await.expect("Failed to list categories");
+    let categories = list_categories_impl(db, false).await.expect("Failed to list categories");
 
     // Should have seeded categories like Food, Transportation, etc.
     let category_names: Vec<String> = categories.iter().map(|c| c.name.clone()).collect();
@@ -68,7 +82,7 @@ async fn test_list_categories_includes_seeded_categories() {
 #[tokio::test]
 async fn test_list_categories_ordered_by_name() {
     let db = super::get_test_db_pool().await;
-    let categories = list_categories_impl(db).await.expect("Failed to list categories");
+    let categories = list_categories_impl(db, false).await.expect("Failed to list categories");
 
     // Verify categories are ordered by name
     for i in 0..categories.len().saturating_sub(1) {
@@ -78,3 +92,114 @@ async fn test_list_categories_ordered_by_name() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_archive_category() {
+    let db = super::get_test_db_pool().await;
+    let category_id = create_category_impl(db, NewCategory {
+        name: super::unique_name("Wedding"),
+        icon: None,
+        color: None,
+    })
+    .await
+    .expect("Failed to create category");
+    let account_id = super::fixtures::create_test_account(db, "Archive Category Test").await;
+    super::fixtures::insert_test_transactions(
+        db,
+        account_id,
+        vec![super::fixtures::TestTransaction::new("2014-06-21", -90_000, "Venue deposit").with_category(category_id)],
+    )
+    .await;
+
+    let category = archive_category_impl(db, category_id).await.expect("Failed to archive category");
+    assert!(category.archived);
+
+    // Hidden from the default list, shown when asked for
+    let categories = list_categories_impl(db, false).await.unwrap();
+    assert!(!categories.iter().any(|c| c.id == category_id), "Archived category should be hidden");
+    let categories = list_categories_impl(db, true).await.unwrap();
+    assert!(categories.iter().any(|c| c.id == category_id && c.archived));
+
+    // Transactions keep the category and history stays in analytics
+    let (kept,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM transactions WHERE account_id = ? AND category_id = ?")
+        .bind(account_id)
+        .bind(category_id)
+        .fetch_one(db)
+        .await
+        .unwrap();
+    assert_eq!(kept, 1);
+    let spending = get_spending_by_category_impl(db, "2014-06-01", "2014-06-30", Some(account_id), None)
+        .await
+        .unwrap();
+    assert!(spending.categories.iter().any(|c| c.category_id == category_id && c.amount_cents == 90_000));
+
+    let category = unarchive_category_impl(db, category_id).await.expect("Failed to unarchive category");
+    assert!(!category.archived);
+    let categories = list_categories_impl(db, false).await.unwrap();
+    assert!(categories.iter().any(|c| c.id == category_id));
+}
+
+#[tokio::test]
+async fn test_archive_category_nonexistent() {
+    let db = super::get_test_db_pool().await;
+
+    let result = archive_category_impl(db, 999999).await;
+    assert!(result.unwrap_err().to_user_message().contains("not found"));
+}
+
+#[tokio::test]
+async fn test_update_predefined_category() {
+    // Renaming a predefined category would confuse tests that look them up by name, so use a database of its own
+    let dir = std::env::temp_dir().join(super::unique_name("update-category").replace(' ', "_"));
+    let db = &open_database(&dir.join("budget.db"), &AccessGuard::default()).await.unwrap();
+    let groceries = list_categories_impl(db, false)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|c| c.name == "Groceries" && c.category_type == "predefined")
+        .expect("Groceries is seeded");
+
+    let food = update_category_impl(
+        db,
+        UpdateCategory { icon: Some("🥕".to_string()), ..rename(groceries.id, "  Food ") },
+    )
+    .await
+    .expect("Predefined categories can be renamed");
+    assert_eq!(food.name, "Food");
+    assert_eq!(food.icon.as_deref(), Some("🥕"));
+    assert_eq!(food.category_type, "predefined", "Renaming keeps the type");
+
+    let cleared = update_category_impl(
+        db,
+        UpdateCategory { id: food.id, name: None, icon: Some(String::new()), color: Some("#4CAF50".to_string()), category_type: None },
+    )
+    .await
+    .unwrap();
+    assert_eq!(cleared.icon, None, "An empty icon removes it");
+    assert_eq!(cleared.color.as_deref(), Some("#4CAF50"));
+    assert_eq!(cleared.name, "Food");
+
+    // Same type is accepted; a different one isn't
+    let same_type = UpdateCategory { category_type: Some(CategoryType::Predefined), ..rename(food.id, "Food & Groceries") };
+    assert!(update_category_impl(db, same_type).await.is_ok());
+    let custom = UpdateCategory { category_type: Some(CategoryType::Custom), ..rename(food.id, "Food") };
+    assert!(matches!(update_category_impl(db, custom).await, Err(CategoryError::ValidationError(_))));
+
+    for bad in [rename(food.id, "dining"), rename(food.id, "   "), rename(food.id, &"x".repeat(101))] {
+        assert!(
+            matches!(update_category_impl(db, bad).await, Err(CategoryError::ValidationError(_))),
+            "Duplicate (ignoring case), blank and overlong names are rejected"
+        );
+    }
+    let bad_color = UpdateCategory { id: food.id, name: None, icon: None, color: Some("green".to_string()), category_type: None };
+    assert!(matches!(update_category_impl(db, bad_color).await, Err(CategoryError::ValidationError(_))));
+    let nothing = UpdateCategory { id: food.id, name: None, icon: None, color: None, category_type: None };
+    assert!(matches!(update_category_impl(db, nothing).await, Err(CategoryError::ValidationError(_))));
+    assert!(matches!(update_category_impl(db, rename(999999, "Ghost")).await, Err(CategoryError::NotFound(999999))));
+
+    let renamed = list_categories_impl(db, false).await.unwrap();
+    assert!(renamed.iter().any(|c| c.id == groceries.id && c.name == "Food & Groceries"));
+    assert!(!renamed.iter().any(|c| c.name == "Groceries"));
+    db.close().await;
+    let _ = std::fs::remove_dir_all(&dir);
+}
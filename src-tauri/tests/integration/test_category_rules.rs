@@ -0,0 +1,317 @@
+use budget_balancer_lib::commands::category_commands::{
+    create_category_rule_impl, delete_category_rule_impl, detect_rule_conflicts_impl, get_rule_statistics_impl, list_category_rules_impl,
+    preview_categorization_impl,
+};
+use budget_balancer_lib::commands::transaction_commands::categorize_transaction_impl;
+use budget_balancer_lib::errors::CategoryError;
+use budget_balancer_lib::models::category_rule::{NewCategoryRule, RuleMatchType};
+
+fn scoped_rule(pattern: &str, match_type: RuleMatchType, category_id: i64, account_id: i64) -> NewCategoryRule {
+    NewCategoryRule {
+        pattern: pattern.to_string(),
+        category_id,
+        priority: 50,
+        match_type,
+        min_amount_cents: None,
+        max_amount_cents: None,
+        account_id: Some(account_id),
+    }
+}
+
+#[tokio::test]
+async fn test_create_and_list_category_rule() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Rule List Test").await;
+
+    let mut rule = scoped_rule(r"^costco\s+gas", RuleMatchType::Regex, 3, account_id);
+    rule.min_amount_cents = Some(1000);
+    rule.max_amount_cents = Some(20_000);
+
+    let rule_id = create_category_rule_impl(db, rule).await.expect("Failed to create rule");
+    assert!(rule_id > 0);
+
+    let rules = list_category_rules_impl(db, Some(3)).await.expect("Failed to list rules");
+    let created = rules.iter().find(|r| r.id == rule_id).expect("Rule should be listed");
+    assert_eq!(created.match_type, "regex");
+    assert_eq!(created.min_amount_cents, Some(1000));
+    assert_eq!(created.max_amount_cents, Some(20_000));
+    assert_eq!(created.account_id, Some(account_id));
+    assert!(rules.iter().all(|r| r.category_id == 3));
+
+    delete_category_rule_impl(db, rule_id).await.expect("Failed to delete rule");
+    let result = delete_category_rule_impl(db, rule_id).await;
+    assert!(matches!(result, Err(CategoryError::RuleNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_create_category_rule_validation() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Rule Validation Test").await;
+
+    let result = create_category_rule_impl(db, scoped_rule("(unclosed", RuleMatchType::Regex, 3, account_id)).await;
+    assert!(matches!(result, Err(CategoryError::InvalidPattern(_))), "Invalid regex should be rejected");
+
+    let result = create_category_rule_impl(db, scoped_rule("   ", RuleMatchType::Contains, 3, account_id)).await;
+    assert!(matches!(result, Err(CategoryError::ValidationError(_))), "Empty pattern should be rejected");
+
+    let mut rule = scoped_rule("venmo", RuleMatchType::Contains, 3, account_id);
+    rule.min_amount_cents = Some(10_000);
+    rule.max_amount_cents = Some(1000);
+    let result = create_category_rule_impl(db, rule).await;
+    assert!(matches!(result, Err(CategoryError::ValidationError(_))), "min > max should be rejected");
+
+    let result = create_category_rule_impl(db, scoped_rule("venmo", RuleMatchType::Contains, 999999, account_id)).await;
+    assert!(matches!(result, Err(CategoryError::NotFound(999999))));
+
+    let result = create_category_rule_impl(db, scoped_rule("venmo", RuleMatchType::Contains, 3, 999999)).await;
+    assert!(matches!(result, Err(CategoryError::AccountNotFound(999999))));
+}
+
+#[tokio::test]
+async fn test_categorize_with_regex_amount_and_account_rules() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Rule Match Test").await;
+    let other_account_id = super::fixtures::create_test_account(db, "Rule Other Account").await;
+
+    // Word-boundary regex: matches "SHELL OIL" but not "Michelle's"
+    let shell_rule = create_category_rule_impl(db, scoped_rule(r"\bshell\b", RuleMatchType::Regex, 8, account_id))
+        .await
+        .expect("Failed to create regex rule");
+
+    // Only small Venmo payments match; larger ones fall through to other rules
+    let mut venmo = scoped_rule("venmo", RuleMatchType::StartsWith, 4, account_id);
+    venmo.max_amount_cents = Some(5000);
+    let venmo_rule = create_category_rule_impl(db, venmo).await.expect("Failed to create amount rule");
+
+    let ids = super::fixtures::insert_test_transactions(
+        db,
+        account_id,
+        vec![
+            super::fixtures::TestTransaction::new("2019-06-01", -4120, "Fuel").with_merchant("SHELL OIL 5744"),
+            super::fixtures::TestTransaction::new("2019-06-02", -1800, "Lunch").with_merchant("Michelle's Cafe"),
+            super::fixtures::TestTransaction::new("2019-06-03", -2500, "VENMO *ALEX"),
+            super::fixtures::TestTransaction::new("2019-06-04", -90_000, "VENMO *LANDLORD"),
+        ],
+    )
+    .await;
+    let other_ids = super::fixtures::insert_test_transactions(
+        db,
+        other_account_id,
+        vec![super::fixtures::TestTransaction::new("2019-06-01", -4120, "Fuel").with_merchant("SHELL OIL 5744")],
+    )
+    .await;
+
+    // Rules above outrank the seeded rules (priority 10), so their categories win when they match
+    let shell = categorize_transaction_impl(db, ids[0], false).await.unwrap();
+    assert_eq!(shell.category_id, 8);
+
+    let cafe = categorize_transaction_impl(db, ids[1], false).await.unwrap();
+    assert_ne!(cafe.category_id, 8, "Regex word boundary should not match Michelle's");
+
+    let small_venmo = categorize_transaction_impl(db, ids[2], false).await.unwrap();
+    assert_eq!(small_venmo.category_id, 4);
+
+    let rent_venmo = categorize_transaction_impl(db, ids[3], false).await.unwrap();
+    assert_ne!(rent_venmo.category_id, 4, "Amount above max should not match");
+
+    let other_shell = categorize_transaction_impl(db, other_ids[0], false).await.unwrap();
+    assert_ne!(other_shell.category_id, 8, "Account-scoped rule should not apply to other accounts");
+
+    delete_category_rule_impl(db, shell_rule).await.unwrap();
+    delete_category_rule_impl(db, venmo_rule).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_preview_categorization_unsaved_rule() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Preview Unsaved Test").await;
+
+    let ids = super::fixtures::insert_test_transactions(
+        db,
+        account_id,
+        vec![
+            super::fixtures::TestTransaction::new("2019-07-01", -6000, "Hardware").with_merchant("ACME TOOLS").with_category(10),
+            super::fixtures::TestTransaction::new("2019-07-02", -1500, "Hardware").with_merchant("Acme Tools #2").with_category(7),
+            super::fixtures::TestTransaction::new("2019-07-03", -3000, "Groceries").with_merchant("Kroger").with_category(1),
+        ],
+    )
+    .await;
+
+    let rule = scoped_rule("acme tools", RuleMatchType::StartsWith, 7, account_id);
+    let preview = preview_categorization_impl(db, None, Some(rule), None)
+        .await
+        .expect("Failed to preview rule");
+
+    assert_eq!(preview.category_id, 7);
+    assert_eq!(preview.matched_count, 2);
+    assert_eq!(preview.changed_count, 1, "Only the uncategorized transaction should change");
+    let changed: Vec<i64> = preview.matches.iter().filter(|m| m.would_change).map(|m| m.transaction_id).collect();
+    assert_eq!(changed, vec![ids[0]]);
+
+    // Nothing is written
+    let rules = list_category_rules_impl(db, Some(7)).await.unwrap();
+    assert!(rules.iter().all(|r| r.account_id != Some(account_id)), "Preview should not save the rule");
+    let category: (i64,) = sqlx::query_as("SELECT category_id FROM transactions WHERE id = ?")
+        .bind(ids[0])
+        .fetch_one(db)
+        .await
+        .unwrap();
+    assert_eq!(category.0, 10, "Preview should not recategorize transactions");
+}
+
+#[tokio::test]
+async fn test_preview_categorization_respects_higher_priority_rules() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Preview Priority Test").await;
+
+    let mut specific = scoped_rule("acme fuel", RuleMatchType::Contains, 3, account_id);
+    specific.priority = 90;
+    let specific_id = create_category_rule_impl(db, specific).await.unwrap();
+    let general_id = create_category_rule_impl(db, scoped_rule("acme", RuleMatchType::Contains, 7, account_id))
+        .await
+        .unwrap();
+
+    super::fixtures::insert_test_transactions(
+        db,
+        account_id,
+        vec![
+            super::fixtures::TestTransaction::new("2019-08-01", -4500, "ACME FUEL 12").with_category(10),
+            super::fixtures::TestTransaction::new("2019-08-02", -2000, "ACME HARDWARE").with_category(10),
+        ],
+    )
+    .await;
+
+    let preview = preview_categorization_impl(db, Some(general_id), None, Some(1))
+        .await
+        .expect("Failed to preview saved rule");
+    assert_eq!(preview.matched_count, 2);
+    assert_eq!(preview.changed_count, 2);
+    assert_eq!(preview.matches.len(), 1, "Match list should respect limit");
+
+    let full = preview_categorization_impl(db, Some(general_id), None, None).await.unwrap();
+    let fuel = full.matches.iter().find(|m| m.description.starts_with("ACME FUEL 12")).unwrap();
+    assert_eq!(fuel.new_category_id, 3, "Higher-priority rule should win");
+
+    delete_category_rule_impl(db, specific_id).await.unwrap();
+    delete_category_rule_impl(db, general_id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_preview_categorization_invalid_input() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Preview Invalid Test").await;
+
+    let result = preview_categorization_impl(db, None, None, None).await;
+    assert!(matches!(result, Err(CategoryError::ValidationError(_))));
+
+    let rule = scoped_rule("acme", RuleMatchType::Contains, 7, account_id);
+    let result = preview_categorization_impl(db, Some(1), Some(rule), None).await;
+    assert!(matches!(result, Err(CategoryError::ValidationError(_))));
+
+    let result = preview_categorization_impl(db, Some(999999), None, None).await;
+    assert!(matches!(result, Err(CategoryError::RuleNotFound(999999))));
+
+    let rule = scoped_rule("[bad", RuleMatchType::Regex, 7, account_id);
+    let result = preview_categorization_impl(db, None, Some(rule), None).await;
+    assert!(matches!(result, Err(CategoryError::InvalidPattern(_))));
+}
+
+#[tokio::test]
+async fn test_rule_statistics_count_matches() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Rule Stats Test").await;
+
+    let used_rule = create_category_rule_impl(db, scoped_rule("zanzibar grill", RuleMatchType::Contains, 4, account_id))
+        .await
+        .expect("Failed to create rule");
+    let unused_rule = create_category_rule_impl(db, scoped_rule("never seen merchant", RuleMatchType::Contains, 4, account_id))
+        .await
+        .expect("Failed to create rule");
+
+    let ids = super::fixtures::insert_test_transactions(
+        db,
+        account_id,
+        vec![
+            super::fixtures::TestTransaction::new("2019-07-01", -2200, "ZANZIBAR GRILL #12"),
+            super::fixtures::TestTransaction::new("2019-07-08", -1950, "Zanzibar Grill"),
+        ],
+    )
+    .await;
+    for id in ids {
+        categorize_transaction_impl(db, id, false).await.unwrap();
+    }
+
+    let statistics = get_rule_statistics_impl(db, None).await.expect("Failed to get rule statistics");
+    let used = statistics.iter().find(|r| r.rule_id == used_rule).expect("Used rule should be listed");
+    assert_eq!(used.match_count, 2);
+    assert!(used.last_matched_at.is_some());
+    assert!(!used.never_matched);
+    assert!(!used.stale, "A rule that just matched isn't stale");
+
+    let unused = statistics.iter().find(|r| r.rule_id == unused_rule).expect("Unused rule should be listed");
+    assert_eq!(unused.match_count, 0);
+    assert_eq!(unused.last_matched_at, None);
+    assert!(unused.never_matched);
+    assert!(unused.stale);
+
+    let used_position = statistics.iter().position(|r| r.rule_id == used_rule).unwrap();
+    let unused_position = statistics.iter().position(|r| r.rule_id == unused_rule).unwrap();
+    assert!(used_position < unused_position, "Most used rules come first");
+
+    let result = get_rule_statistics_impl(db, Some(0)).await;
+    assert!(matches!(result, Err(CategoryError::ValidationError(_))));
+
+    delete_category_rule_impl(db, used_rule).await.unwrap();
+    delete_category_rule_impl(db, unused_rule).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_detect_rule_conflicts() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Rule Conflict Test").await;
+
+    let mut general = scoped_rule("quokka", RuleMatchType::Contains, 4, account_id);
+    general.priority = 77;
+    let general = create_category_rule_impl(db, general).await.expect("Failed to create rule");
+    let mut cafe = scoped_rule("quokka cafe", RuleMatchType::StartsWith, 8, account_id);
+    cafe.priority = 77;
+    let cafe = create_category_rule_impl(db, cafe).await.expect("Failed to create rule");
+    // Lower priority always loses, so it doesn't conflict
+    let mut lower = scoped_rule("quokka", RuleMatchType::Contains, 2, account_id);
+    lower.priority = 76;
+    let lower = create_category_rule_impl(db, lower).await.expect("Failed to create rule");
+    // Same priority but a pattern that never overlaps
+    let mut unrelated = scoped_rule("wombat", RuleMatchType::Contains, 2, account_id);
+    unrelated.priority = 77;
+    let unrelated = create_category_rule_impl(db, unrelated).await.expect("Failed to create rule");
+
+    super::fixtures::insert_test_transactions(
+        db,
+        account_id,
+        vec![
+            super::fixtures::TestTransaction::new("2019-08-01", -650, "QUOKKA CAFE PERTH"),
+            super::fixtures::TestTransaction::new("2019-08-09", -725, "Quokka Cafe"),
+            super::fixtures::TestTransaction::new("2019-08-10", -3000, "Quokka Tours"),
+        ],
+    )
+    .await;
+
+    let ours = [general, cafe, lower, unrelated];
+    let conflicts: Vec<_> = detect_rule_conflicts_impl(db, Some(1))
+        .await
+        .expect("Failed to detect conflicts")
+        .into_iter()
+        .filter(|c| ours.contains(&c.first_rule.id) || ours.contains(&c.second_rule.id))
+        .collect();
+
+    assert_eq!(conflicts.len(), 1, "Only the equal-priority overlap should be reported");
+    let conflict = &conflicts[0];
+    assert_eq!((conflict.first_rule.id, conflict.second_rule.id), (general, cafe), "The older rule wins today");
+    assert_eq!(conflict.matched_count, 2);
+    assert_eq!(conflict.examples.len(), 1);
+    assert!(conflict.examples[0].description.starts_with("Quokka Cafe ("), "Newest example first");
+
+    for rule_id in ours {
+        delete_category_rule_impl(db, rule_id).await.unwrap();
+    }
+}
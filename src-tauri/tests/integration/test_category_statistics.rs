@@ -0,0 +1,64 @@
+use budget_balancer_lib::commands::analytics_commands::get_category_statistics_impl;
+use budget_balancer_lib::commands::category_commands::create_category_impl;
+use budget_balancer_lib::models::category::NewCategory;
+use chrono::NaiveDate;
+
+#[tokio::test]
+async fn test_get_category_statistics() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Category Stats").await;
+    let category_id = create_category_impl(db, NewCategory {
+        name: super::unique_name("Holiday Gifts"),
+        icon: None,
+        color: None,
+    })
+    .await
+    .expect("Failed to create category");
+
+    use super::fixtures::TestTransaction;
+    super::fixtures::insert_test_transactions(db, account_id, vec![
+        TestTransaction::new("2011-12-20", -99_900, "Before the window").with_category(category_id),
+        TestTransaction::new("2012-03-10", -10_000, "Birthday").with_category(category_id),
+        TestTransaction::new("2012-05-02", -5000, "Mother's day").with_category(category_id),
+        TestTransaction::new("2012-05-20", -5000, "Graduation").with_category(category_id),
+        TestTransaction::new("2012-06-01", 4000, "Return").with_category(category_id),
+        TestTransaction::new("2012-12-15", -30_000, "Holidays").with_category(category_id),
+        TestTransaction::new("2013-01-05", -99_900, "Current month").with_category(category_id),
+    ])
+    .await;
+
+    let today = NaiveDate::from_ymd_opt(2013, 1, 15).unwrap();
+    let stats = get_category_statistics_impl(db, category_id, Some(12), today)
+        .await
+        .expect("Failed to calculate statistics");
+
+    // March (first spending) through December, empty months counted as zero
+    assert_eq!(stats.start_month.as_deref(), Some("2012-03"));
+    assert_eq!(stats.end_month, "2012-12");
+    assert_eq!(stats.months_counted, 10);
+    assert_eq!(stats.monthly.len(), 10);
+    assert_eq!(stats.total_cents, 50_000);
+    assert_eq!(stats.average, 50.0);
+    assert_eq!(stats.median, 0.0);
+    assert_eq!(stats.min, 0.0);
+    assert_eq!(stats.max, 300.0);
+    assert!((stats.std_dev - 92.195).abs() < 0.01, "std_dev was {}", stats.std_dev);
+
+    assert_eq!(stats.seasonal_profile.len(), 12);
+    let december = &stats.seasonal_profile[11];
+    assert_eq!(december.average, 300.0);
+    assert_eq!(december.index, Some(6.0));
+    assert_eq!(stats.seasonal_profile[0].months_counted, 0, "No January in the window");
+}
+
+#[tokio::test]
+async fn test_get_category_statistics_invalid_input() {
+    let db = super::get_test_db_pool().await;
+    let today = NaiveDate::from_ymd_opt(2013, 1, 15).unwrap();
+
+    let result = get_category_statistics_impl(db, 999999, None, today).await;
+    assert!(result.unwrap_err().contains("not found"));
+
+    let result = get_category_statistics_impl(db, 1, Some(0), today).await;
+    assert!(result.is_err(), "An empty window should be rejected");
+}
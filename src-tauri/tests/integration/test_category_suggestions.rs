@@ -0,0 +1,45 @@
+use budget_balancer_lib::commands::transaction_commands::suggest_category_for_transaction_impl;
+use budget_balancer_lib::errors::TransactionError;
+
+#[tokio::test]
+async fn test_suggest_category_ranks_by_history() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Suggestion Test").await;
+    let merchant = super::unique_word("qz");
+
+    let ids = super::fixtures::insert_test_transactions(
+        db,
+        account_id,
+        vec![
+            super::fixtures::TestTransaction::new("2019-09-01", -2000, "x").with_merchant(&format!("{} Market", merchant)).with_category(1),
+            super::fixtures::TestTransaction::new("2019-09-08", -2500, "x").with_merchant(&format!("{} MARKET #12", merchant)).with_category(1),
+            super::fixtures::TestTransaction::new("2019-09-10", -4000, "x").with_merchant(&format!("{} Market", merchant)).with_category(7),
+            super::fixtures::TestTransaction::new("2019-09-15", -2200, "x").with_merchant(&format!("{} Market", merchant)).with_category(10),
+        ],
+    )
+    .await;
+
+    let suggestions = suggest_category_for_transaction_impl(db, ids[3], None)
+        .await
+        .expect("Failed to suggest category");
+
+    assert!(!suggestions.is_empty(), "Should suggest categories from history");
+    assert!(suggestions.len() <= 3, "Should default to three suggestions");
+    assert_eq!(suggestions[0].category_id, 1, "Most frequent category should rank first");
+    assert_eq!(suggestions[0].category_name, "Groceries");
+    let shopping = suggestions.iter().find(|s| s.category_id == 7).expect("Shopping should be suggested");
+    assert!(suggestions[0].confidence > shopping.confidence);
+    assert!(suggestions.iter().all(|s| s.confidence > 0.0 && s.confidence <= 1.0));
+    assert!(suggestions.iter().all(|s| s.category_id != 10), "Uncategorized should never be suggested");
+
+    let top_only = suggest_category_for_transaction_impl(db, ids[3], Some(1)).await.unwrap();
+    assert_eq!(top_only.len(), 1);
+}
+
+#[tokio::test]
+async fn test_suggest_category_transaction_not_found() {
+    let db = super::get_test_db_pool().await;
+
+    let result = suggest_category_for_transaction_impl(db, 999999999, None).await;
+    assert!(matches!(result, Err(TransactionError::NotFound(999999999))));
+}
@@ -0,0 +1,187 @@
+use budget_balancer_lib::commands::category_commands::{
+    create_category_impl, delete_category_rule_impl, export_category_template_impl,
+    import_category_template_impl, list_categories_impl, list_category_rules_impl, reseed_categories_impl,
+};
+use budget_balancer_lib::constants::DEFAULT_CATEGORY_ID;
+use budget_balancer_lib::errors::CategoryError;
+use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::models::category_template::{CategoryTemplate, TemplateCategory};
+use budget_balancer_lib::open_database;
+use budget_balancer_lib::utils::access_guard::AccessGuard;
+
+fn find<'a>(categories: &'a [TemplateCategory], name: &str) -> Option<&'a TemplateCategory> {
+    categories.iter().find_map(|c| {
+        if c.name == name {
+            Some(c)
+        } else {
+            find(&c.children, name)
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_export_category_template() {
+    let db = super::get_test_db_pool().await;
+    let name = super::unique_name("Export Template Category");
+    create_category_impl(db, NewCategory {
+        name: name.clone(),
+        icon: Some("🎨".to_string()),
+        color: Some("#4CAF50".to_string()),
+    })
+    .await
+    .expect("Failed to create category");
+
+    let template = export_category_template_impl(db, Some("My Budget".to_string()))
+        .await
+        .expect("Failed to export template");
+
+    assert_eq!(template.version, 1);
+    assert_eq!(template.name.as_deref(), Some("My Budget"));
+
+    let exported = find(&template.categories, &name).expect("Created category should be exported");
+    assert_eq!(exported.color.as_deref(), Some("#4CAF50"));
+    assert_eq!(exported.icon.as_deref(), Some("🎨"));
+
+    let groceries = find(&template.categories, "Groceries").expect("Seeded categories should be exported");
+    assert!(groceries.rules.iter().any(|r| r.pattern == "walmart"), "Seeded rules should be exported");
+}
+
+#[tokio::test]
+async fn test_import_category_template_round_trip() {
+    let db = super::get_test_db_pool().await;
+    let parent = super::unique_name("Needs Template");
+    let child = super::unique_name("Needs Rent");
+    let pattern = super::unique_name("landlordco").to_lowercase();
+
+    let content = serde_json::json!({
+        "version": 1,
+        "name": "50/30/20 starter",
+        "categories": [{
+            "name": parent,
+            "color": "#2196F3",
+            "budgets": [{ "amount": 2500.0, "period": "monthly" }],
+            "children": [{
+                "name": child,
+                "icon": "🏠",
+                "budgets": [{ "amount": 1500.0, "period": "monthly" }],
+                "rules": [{ "pattern": pattern, "priority": 20, "match_type": "starts_with" }]
+            }]
+        }, {
+            "name": "Groceries",
+            "color": "#000000"
+        }]
+    })
+    .to_string();
+
+    let result = import_category_template_impl(db, &content, Some("2019-01-01"))
+        .await
+        .expect("Failed to import template");
+    assert_eq!(result.categories_created, 2);
+    assert_eq!(result.categories_existing, 1, "Existing categories are reused by name");
+    assert_eq!(result.budgets_created, 2);
+    assert_eq!(result.rules_created, 1);
+
+    let categories = list_categories_impl(db, false).await.unwrap();
+    let parent_row = categories.iter().find(|c| c.name == parent).unwrap();
+    let child_row = categories.iter().find(|c| c.name == child).unwrap();
+    assert_eq!(child_row.parent_id, Some(parent_row.id));
+    assert_eq!(parent_row.color.as_deref(), Some("#2196F3"));
+
+    // Importing again creates nothing new
+    let again = import_category_template_impl(db, &content, Some("2019-01-01")).await.unwrap();
+    assert_eq!(again.categories_created, 0);
+    assert_eq!(again.budgets_created, 0);
+    assert_eq!(again.budgets_skipped, 2);
+    assert_eq!(again.rules_skipped, 1);
+
+    // Export includes the imported tree
+    let exported: CategoryTemplate = export_category_template_impl(db, None).await.unwrap();
+    let exported_parent = find(&exported.categories, &parent).unwrap();
+    let exported_child = exported_parent.children.iter().find(|c| c.name == child).unwrap();
+    assert_eq!(exported_child.budgets[0].amount_cents, 150_000);
+    assert_eq!(exported_child.rules[0].pattern, pattern);
+
+    for rule in list_category_rules_impl(db, Some(child_row.id)).await.unwrap() {
+        delete_category_rule_impl(db, rule.id).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_import_category_template_validation_is_atomic() {
+    let db = super::get_test_db_pool().await;
+    let name = super::unique_name("Invalid Template Category");
+
+    let result = import_category_template_impl(db, "not json", None).await;
+    assert!(matches!(result, Err(CategoryError::ValidationError(_))));
+
+    let content = serde_json::json!({
+        "version": 1,
+        "categories": [
+            { "name": name, "budgets": [{ "amount": 100.0, "period": "monthly" }] },
+            { "name": super::unique_name("Bad Rule"), "rules": [{ "pattern": "(unclosed", "match_type": "regex" }] }
+        ]
+    })
+    .to_string();
+    let result = import_category_template_impl(db, &content, None).await;
+    assert!(matches!(result, Err(CategoryError::InvalidPattern(_))));
+
+    let categories = list_categories_impl(db, false).await.unwrap();
+    assert!(!categories.iter().any(|c| c.name == name), "Nothing should be written when validation fails");
+
+    let content = serde_json::json!({
+        "version": 1,
+        "categories": [{ "name": name, "color": "green" }]
+    })
+    .to_string();
+    let result = import_category_template_impl(db, &content, None).await;
+    assert!(matches!(result, Err(CategoryError::ValidationError(_))), "Invalid colors should be rejected");
+
+    let content = serde_json::json!({ "version": 99, "categories": [] }).to_string();
+    let result = import_category_template_impl(db, &content, None).await;
+    assert!(matches!(result, Err(CategoryError::ValidationError(_))), "Newer versions should be rejected");
+}
+
+#[tokio::test]
+async fn test_reseed_categories() {
+    // Reseeding rearranges the predefined categories, so it gets a database of its own
+    let dir = std::env::temp_dir().join(super::unique_name("reseed").replace(' ', "_"));
+    let db = &open_database(&dir.join("budget.db"), &AccessGuard::default()).await.unwrap();
+    create_category_impl(db, NewCategory { name: "Coffee".to_string(), icon: None, color: None }).await.unwrap();
+
+    let detailed = reseed_categories_impl(db, "detailed", false).await.unwrap();
+    assert_eq!(detailed.set, "detailed");
+    assert!(detailed.import.categories_created > 0);
+    assert!(detailed.import.categories_existing >= 3, "Groceries, Dining and Coffee are reused");
+    assert_eq!(detailed.categories_archived, 0, "Nothing is archived without replace");
+
+    let categories = list_categories_impl(db, true).await.unwrap();
+    let by_name = |name: &str| categories.iter().find(|c| c.name == name).unwrap().clone();
+    assert_eq!(categories.iter().filter(|c| c.name == "Groceries").count(), 1, "Reseeding doesn't duplicate");
+    assert_eq!(by_name("Groceries").parent_id, Some(by_name("Food").id), "Predefined categories move under the set's parent");
+    assert_eq!(by_name("Coffee").category_type, "custom", "Custom categories stay custom");
+    assert_eq!(by_name("Coffee").parent_id, None, "Custom categories keep their place");
+    assert_eq!(by_name("Salary").category_type, "predefined");
+
+    let again = reseed_categories_impl(db, "detailed", false).await.unwrap();
+    assert_eq!(again.import.categories_created, 0);
+    assert_eq!(again.import.rules_created, 0, "Rules aren't seeded twice");
+
+    let minimal = reseed_categories_impl(db, "minimal", true).await.unwrap();
+    assert!(minimal.categories_archived > 0);
+    let categories = list_categories_impl(db, true).await.unwrap();
+    let archived = |name: &str| categories.iter().find(|c| c.name == name).unwrap().archived;
+    assert!(archived("Groceries") && archived("Salary"));
+    assert!(!archived("Essentials") && !archived("Income") && !archived("Coffee"));
+    assert!(categories.iter().any(|c| c.id == DEFAULT_CATEGORY_ID && !c.archived), "Uncategorized is never archived");
+
+    let standard = reseed_categories_impl(db, "standard", true).await.unwrap();
+    assert!(standard.categories_restored >= 8, "Switching back restores the archived categories");
+    assert_eq!(standard.categories_archived, minimal.import.categories_created);
+
+    assert!(matches!(
+        reseed_categories_impl(db, "mystery", false).await,
+        Err(CategoryError::ValidationError(_))
+    ));
+    db.close().await;
+    let _ = std::fs::remove_dir_all(&dir);
+}
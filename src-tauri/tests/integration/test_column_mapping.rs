@@ -1,4 +1,8 @@
-use budget_balancer_lib::commands::csv_commands::save_column_mapping_impl;
+use budget_balancer_lib::commands::account_commands::archive_account_impl;
+use budget_balancer_lib::commands::csv_commands::{
+    detect_mapping_for_csv_impl, save_column_mapping_impl, suggest_column_mapping_impl,
+};
+use budget_balancer_lib::errors::CsvImportError;
 use budget_balancer_lib::models::column_mapping::NewColumnMapping;
 
 #[tokio::test]
@@ -10,6 +14,8 @@ async fn test_save_column_mapping() {
         amount_col: "Amount".to_string(),
         description_col: "Description".to_string(),
         merchant_col: Some("Merchant".to_string()),
+        headers: None,
+        ..Default::default()
     };
 
     let result = save_column_mapping_impl(db, mapping).await;
@@ -30,6 +36,8 @@ async fn test_save_column_mapping_duplicate_name() {
         amount_col: "Amount".to_string(),
         description_col: "Description".to_string(),
         merchant_col: None,
+        headers: None,
+        ..Default::default()
     };
 
     // First save should succeed
@@ -43,6 +51,8 @@ async fn test_save_column_mapping_duplicate_name() {
         amount_col: "Amount2".to_string(),
         description_col: "Description2".to_string(),
         merchant_col: None,
+        headers: None,
+        ..Default::default()
     };
 
     let result2 = save_column_mapping_impl(db, mapping2).await;
@@ -58,8 +68,208 @@ async fn test_save_column_mapping_without_merchant() {
         amount_col: "Amount".to_string(),
         description_col: "Description".to_string(),
         merchant_col: None,
+        headers: None,
+        ..Default::default()
     };
 
     let result = save_column_mapping_impl(db, mapping).await;
     assert!(result.is_ok(), "Should save mapping without merchant column");
 }
+
+#[tokio::test]
+async fn test_suggest_column_mapping() {
+    let db = super::get_test_db_pool().await;
+
+    // A header unique to this run, so no saved mapping from an earlier run matches
+    let notes = super::unique_word("Notes");
+    let csv_content = format!(
+        "Transaction Date,Details,{notes},Amount\n\
+         01/15/2016,Coffee shop,note a,-4.50\n\
+         01/16/2016,Paycheck,note b,1500.00\n\
+         01/17/2016,Hardware store,note c,-23.99"
+    );
+
+    let detected = suggest_column_mapping_impl(db, &csv_content).await.expect("Suggestion should succeed");
+    let mapping = detected.mapping.expect("Mapping should be detected");
+    assert_eq!(mapping.date, "Transaction Date");
+    assert_eq!(mapping.amount, "Amount");
+    assert_eq!(mapping.description, "Details");
+    assert_eq!(detected.saved_mapping_id, None);
+    assert_eq!(detected.columns.len(), 4);
+
+    // Once a mapping is saved for these headers, it wins over detection
+    let mapping_id = save_column_mapping_impl(
+        db,
+        NewColumnMapping {
+            source_name: super::unique_name("Fingerprint Bank"),
+            date_col: "Transaction Date".to_string(),
+            amount_col: "Amount".to_string(),
+            description_col: notes.clone(),
+            merchant_col: Some("Details".to_string()),
+            headers: Some(vec![
+                "transaction date".to_string(),
+                "DETAILS".to_string(),
+                notes.clone(),
+                "Amount".to_string(),
+            ]),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let saved = suggest_column_mapping_impl(db, &csv_content).await.unwrap();
+    assert_eq!(saved.saved_mapping_id, Some(mapping_id));
+    assert_eq!(saved.confidence, 1.0);
+    let mapping = saved.mapping.unwrap();
+    assert_eq!(mapping.description, notes);
+    assert_eq!(mapping.merchant.as_deref(), Some("Details"));
+}
+
+#[tokio::test]
+async fn test_saved_debit_credit_mapping() {
+    let db = super::get_test_db_pool().await;
+
+    let reference = super::unique_word("Reference");
+    let csv_content = format!(
+        "Posted Date,Description,{reference},Debit,Credit\n\
+         02/01/2016,Grocery store,r1,45.10,\n\
+         02/02/2016,Transfer in,r2,,300.00"
+    );
+
+    let detected = suggest_column_mapping_impl(db, &csv_content).await.unwrap();
+    let mapping = detected.mapping.expect("Mapping should be detected");
+    assert_eq!(mapping.debit.as_deref(), Some("Debit"));
+    assert_eq!(mapping.credit.as_deref(), Some("Credit"));
+    assert!(!mapping.invert_amounts);
+
+    let mapping_id = save_column_mapping_impl(
+        db,
+        NewColumnMapping {
+            source_name: super::unique_name("Debit Credit Bank"),
+            date_col: "Posted Date".to_string(),
+            description_col: "Description".to_string(),
+            debit_col: Some("Debit".to_string()),
+            credit_col: Some("Credit".to_string()),
+            invert_amounts: true,
+            date_format: Some("MM/DD/YYYY".to_string()),
+            headers: Some(vec![
+                "Posted Date".to_string(),
+                "Description".to_string(),
+                reference.clone(),
+                "Debit".to_string(),
+                "Credit".to_string(),
+            ]),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let saved = suggest_column_mapping_impl(db, &csv_content).await.unwrap();
+    assert_eq!(saved.saved_mapping_id, Some(mapping_id));
+    let mapping = saved.mapping.unwrap();
+    assert_eq!(mapping.amount, "");
+    assert_eq!(mapping.debit.as_deref(), Some("Debit"));
+    assert_eq!(mapping.credit.as_deref(), Some("Credit"));
+    assert!(mapping.invert_amounts);
+    assert_eq!(mapping.date_format.as_deref(), Some("MM/DD/YYYY"));
+}
+
+#[tokio::test]
+async fn test_save_column_mapping_rejects_invalid_date_format() {
+    let db = super::get_test_db_pool().await;
+    let mapping = NewColumnMapping {
+        source_name: super::unique_name("Bad Date Format Bank"),
+        date_col: "Date".to_string(),
+        amount_col: "Amount".to_string(),
+        description_col: "Description".to_string(),
+        date_format: Some("DD/MM".to_string()),
+        ..Default::default()
+    };
+
+    let result = save_column_mapping_impl(db, mapping).await;
+    assert!(matches!(result, Err(CsvImportError::InvalidMapping(_))));
+}
+
+#[tokio::test]
+async fn test_save_column_mapping_with_expression() {
+    let db = super::get_test_db_pool().await;
+    let mapping = NewColumnMapping {
+        source_name: super::unique_name("Expression Bank"),
+        date_col: "Date".to_string(),
+        amount_col: "Amount".to_string(),
+        description_col: "=[Payee] + \" \" + [Memo]".to_string(),
+        ..Default::default()
+    };
+    assert!(save_column_mapping_impl(db, mapping.clone()).await.is_ok());
+
+    let invalid = NewColumnMapping {
+        source_name: super::unique_name("Bad Expression Bank"),
+        description_col: "=system(\"ls\")".to_string(),
+        ..mapping
+    };
+    let result = save_column_mapping_impl(db, invalid).await;
+    assert!(matches!(result, Err(CsvImportError::InvalidMapping(_))));
+}
+
+#[tokio::test]
+async fn test_detect_mapping_for_csv_suggests_account() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Detect Mapping Account").await;
+
+    let memo = super::unique_word("Memo");
+    let csv_content = format!(
+        "Date,Payee,{memo},Amount\n\
+         03/01/2016,Bakery,m1,-8.25"
+    );
+    assert!(
+        detect_mapping_for_csv_impl(db, &csv_content).await.unwrap().is_none(),
+        "Nothing is detected before a mapping is saved for these headers"
+    );
+
+    let result = save_column_mapping_impl(
+        db,
+        NewColumnMapping {
+            source_name: super::unique_name("Linked Bank"),
+            date_col: "Date".to_string(),
+            amount_col: "Amount".to_string(),
+            description_col: "Payee".to_string(),
+            headers: Some(vec!["Date".to_string(), "Payee".to_string(), memo.clone(), "Amount".to_string()]),
+            account_id: Some(999999),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(CsvImportError::AccountNotFound(999999))));
+
+    let mapping_id = save_column_mapping_impl(
+        db,
+        NewColumnMapping {
+            source_name: super::unique_name("Linked Bank"),
+            date_col: "Date".to_string(),
+            amount_col: "Amount".to_string(),
+            description_col: "Payee".to_string(),
+            headers: Some(vec!["date".to_string(), "PAYEE".to_string(), memo.clone(), "Amount".to_string()]),
+            account_id: Some(account_id),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let detected = detect_mapping_for_csv_impl(db, &csv_content)
+        .await
+        .unwrap()
+        .expect("Saved mapping should be recognized");
+    assert_eq!(detected.mapping_id, mapping_id);
+    assert_eq!(detected.mapping.description, "Payee");
+    assert_eq!(detected.account_id, Some(account_id));
+    assert!(detected.account_name.is_some());
+
+    // Archived accounts aren't suggested, but the mapping still is
+    archive_account_impl(db, account_id).await.unwrap();
+    let detected = detect_mapping_for_csv_impl(db, &csv_content).await.unwrap().unwrap();
+    assert_eq!(detected.mapping_id, mapping_id);
+    assert_eq!(detected.account_id, None);
+}
@@ -1,34 +1,36 @@
 use budget_balancer_lib::commands::analytics_commands::get_dashboard_summary_impl;
+use budget_balancer_lib::commands::dashboard_commands::{get_dashboard_config_impl, save_dashboard_config_impl};
+use budget_balancer_lib::models::dashboard::{DashboardConfig, DashboardWidget, WidgetKind};
 use serial_test::serial;
 
 #[tokio::test]
 #[serial]
 async fn test_get_dashboard_summary_current_month() {
     let db = super::get_test_db_pool().await;
-    let result = get_dashboard_summary_impl(db, "current_month").await;
+    let result = get_dashboard_summary_impl(db, Some("current_month"), None, None, None, None).await;
 
     assert!(result.is_ok(), "Failed to get dashboard summary: {:?}", result);
 
     let response = result.unwrap();
-    assert!(response.total_spending >= 0.0, "Total spending should be >= 0");
-    assert!(response.total_income >= 0.0, "Total income should be >= 0");
-    assert!(response.top_categories.len() <= 5, "Should have at most 5 top categories");
-    assert!(response.debt_summary.total_debt >= 0.0, "Total debt should be >= 0");
+    assert!(response.total_spending_cents >= 0, "Total spending should be >= 0");
+    assert!(response.total_income_cents >= 0, "Total income should be >= 0");
+    assert!(response.top_categories.unwrap().len() <= 5, "Should have at most 5 top categories");
+    assert!(response.debt_summary.unwrap().total_debt_cents >= 0, "Total debt should be >= 0");
 }
 
 #[tokio::test]
 #[serial]
 async fn test_get_dashboard_summary_last_30_days() {
     let db = super::get_test_db_pool().await;
-    let result = get_dashboard_summary_impl(db, "last_30_days").await;
+    let result = get_dashboard_summary_impl(db, Some("last_30_days"), None, None, None, None).await;
 
     assert!(result.is_ok(), "Should get dashboard for last 30 days");
 
     let response = result.unwrap();
     // Net = income - spending
     assert_eq!(
-        response.net,
-        response.total_income - response.total_spending,
+        response.net_cents,
+        response.total_income_cents - response.total_spending_cents,
         "Net should equal income - spending"
     );
 }
@@ -37,7 +39,7 @@ async fn test_get_dashboard_summary_last_30_days() {
 #[serial]
 async fn test_get_dashboard_summary_current_year() {
     let db = super::get_test_db_pool().await;
-    let result = get_dashboard_summary_impl(db, "current_year").await;
+    let result = get_dashboard_summary_impl(db, Some("current_year"), None, None, None, None).await;
 
     assert!(result.is_ok(), "Should get dashboard for current year");
 }
@@ -51,17 +53,129 @@ async fn test_dashboard_with_data() {
     // Create test transactions with relative dates (2 and 4 days ago)
     // This ensures tests work regardless of current date or month
     let transactions = vec![
-        super::fixtures::TestTransaction::new(&super::days_ago(4), -100.00, "Groceries").with_merchant("Whole Foods"),
-        super::fixtures::TestTransaction::new(&super::days_ago(2), 500.00, "Paycheck").with_merchant("Employer"),
+        super::fixtures::TestTransaction::new(&super::days_ago(4), -10_000, "Groceries").with_merchant("Whole Foods"),
+        super::fixtures::TestTransaction::new(&super::days_ago(2), 50_000, "Paycheck").with_merchant("Employer"),
     ];
     super::fixtures::insert_test_transactions(db, account_id, transactions).await;
 
     // Get dashboard
-    let result = get_dashboard_summary_impl(db, "current_month").await;
+    let result = get_dashboard_summary_impl(db, Some("current_month"), None, None, None, None).await;
 
     assert!(result.is_ok(), "Dashboard should work with data");
 
     let response = result.unwrap();
-    println!("Dashboard response: total_spending={}, total_income={}", response.total_spending, response.total_income);
-    assert!(response.total_spending > 0.0 || response.total_income > 0.0, "Should have some financial activity. Got spending={}, income={}", response.total_spending, response.total_income);
+    println!("Dashboard response: total_spending={}, total_income={}", response.total_spending_cents, response.total_income_cents);
+    assert!(response.total_spending_cents > 0 || response.total_income_cents > 0, "Should have some financial activity. Got spending={}, income={}", response.total_spending_cents, response.total_income_cents);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_dashboard_custom_range_for_selected_accounts() {
+    let db = super::get_test_db_pool().await;
+    let joint = super::fixtures::create_test_account(db, "Dashboard Joint").await;
+    let personal = super::fixtures::create_test_account(db, "Dashboard Personal").await;
+
+    // A ten-day "trip" with the ten days before it as the comparison period
+    super::fixtures::insert_test_transactions(db, joint, vec![
+        super::fixtures::TestTransaction::new("1991-06-12", -4000, "Trip dinner"),
+        super::fixtures::TestTransaction::new("1991-06-15", -6000, "Trip hotel"),
+        super::fixtures::TestTransaction::new("1991-06-05", -5000, "Before trip"),
+        super::fixtures::TestTransaction::new("1991-06-14", 20_000, "Refund"),
+    ])
+    .await;
+    super::fixtures::insert_test_transactions(db, personal, vec![
+        super::fixtures::TestTransaction::new("1991-06-13", -50_000, "Personal purchase"),
+    ])
+    .await;
+
+    let summary = get_dashboard_summary_impl(db, None, Some("1991-06-11"), Some("1991-06-20"), Some(&[joint]), None)
+        .await
+        .expect("Dashboard should accept a custom range");
+
+    assert_eq!(summary.period.start_date, "1991-06-11");
+    assert_eq!(summary.period.end_date, "1991-06-20");
+    assert_eq!(summary.account_ids, vec![joint]);
+    assert_eq!(summary.total_spending_cents, 10_000, "Only the joint account's trip spending counts");
+    assert_eq!(summary.total_income_cents, 20_000);
+    assert!(summary.top_categories.as_ref().unwrap().iter().all(|c| c.amount_cents <= 10_000));
+
+    let comparison = summary.comparison.as_ref().unwrap();
+    assert_eq!(comparison.previous_period.start_date, "1991-06-01");
+    assert_eq!(comparison.previous_period.end_date, "1991-06-10");
+    assert_eq!(comparison.previous_spending_cents, 5000);
+    assert_eq!(comparison.spending_change_cents, 5000);
+    assert_eq!(comparison.spending_change_percent, Some(100.0));
+    assert_eq!(comparison.income_change_percent, None, "Nothing was earned the period before");
+    assert_eq!(comparison.net_change_cents, summary.net_cents - comparison.previous_net_cents);
+
+    let both = get_dashboard_summary_impl(db, None, Some("1991-06-11"), Some("1991-06-20"), Some(&[joint, personal]), None)
+        .await
+        .unwrap();
+    assert_eq!(both.total_spending_cents, 60_000);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_dashboard_rejects_invalid_ranges() {
+    let db = super::get_test_db_pool().await;
+
+    let reversed = get_dashboard_summary_impl(db, None, Some("1991-06-20"), Some("1991-06-11"), None, None).await;
+    assert!(reversed.is_err(), "Start after end should be rejected");
+
+    let half_open = get_dashboard_summary_impl(db, None, Some("1991-06-11"), None, None, None).await;
+    assert!(half_open.is_err(), "A custom range needs both dates");
+
+    let missing_account = get_dashboard_summary_impl(db, Some("current_month"), None, None, Some(&[i64::MAX]), None).await;
+    assert!(missing_account.unwrap_err().contains("Account not found"));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_dashboard_builds_only_configured_widgets() {
+    let db = super::get_test_db_pool().await;
+    let layout = vec![
+        DashboardWidget { limit: Some(2), ..DashboardWidget::new(WidgetKind::Spending) },
+        DashboardWidget::new(WidgetKind::NetWorth),
+        DashboardWidget { days_ahead: Some(30), ..DashboardWidget::new(WidgetKind::UpcomingBills) },
+    ];
+    let saved = save_dashboard_config_impl(db, layout.clone()).await.expect("Layout should save");
+    assert_eq!(saved.widgets, layout);
+    assert!(saved.updated_at.is_some());
+    assert_eq!(get_dashboard_config_impl(db).await.unwrap().widgets, layout);
+
+    let summary = get_dashboard_summary_impl(db, Some("current_year"), None, None, None, None).await.unwrap();
+    assert_eq!(summary.widgets, layout);
+    assert!(summary.top_categories.unwrap().len() <= 2);
+    assert!(summary.comparison.is_some());
+    let net_worth = summary.net_worth.unwrap();
+    let expected = net_worth.accounts_total_cents + net_worth.investments_total_cents - net_worth.debts_total_cents;
+    assert_eq!(net_worth.net_worth_cents, expected);
+    assert!(summary.upcoming_bills.is_some());
+    assert!(summary.debt_summary.is_none(), "Widgets not in the layout aren't computed");
+    assert!(summary.target_summary.is_none());
+
+    // A layout passed in is used instead of the saved one
+    let debts_only = [DashboardWidget::new(WidgetKind::Debts)];
+    let summary = get_dashboard_summary_impl(db, Some("current_year"), None, None, None, Some(&debts_only))
+        .await
+        .unwrap();
+    assert!(summary.debt_summary.is_some());
+    assert!(summary.top_categories.is_none() && summary.net_worth.is_none());
+
+    save_dashboard_config_impl(db, DashboardConfig::default().widgets).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_dashboard_config_is_validated() {
+    let db = super::get_test_db_pool().await;
+    let invalid = [
+        vec![DashboardWidget::new(WidgetKind::Debts), DashboardWidget::new(WidgetKind::Debts)],
+        vec![DashboardWidget { limit: Some(0), ..DashboardWidget::new(WidgetKind::Spending) }],
+        vec![DashboardWidget { limit: Some(3), ..DashboardWidget::new(WidgetKind::Targets) }],
+        vec![DashboardWidget { days_ahead: Some(7), ..DashboardWidget::new(WidgetKind::Spending) }],
+        vec![DashboardWidget { days_ahead: Some(1000), ..DashboardWidget::new(WidgetKind::UpcomingBills) }],
+    ];
+    for widgets in invalid {
+        assert!(save_dashboard_config_impl(db, widgets.clone()).await.is_err(), "{:?}", widgets);
+    }
 }
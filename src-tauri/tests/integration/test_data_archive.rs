@@ -0,0 +1,232 @@
+use budget_balancer_lib::commands::data_commands::{
+    export_all_data_impl, import_all_data_impl, parse_data_archive, restore_data_archive,
+};
+use budget_balancer_lib::errors::DataArchiveError;
+use budget_balancer_lib::models::data_archive::{ArchiveConflict, DataArchive};
+use serde_json::json;
+use serial_test::serial;
+use sqlx::SqlitePool;
+
+/// A hand-written archive covering every section, with names unique to this test
+fn sample_archive(tag: &str) -> DataArchive {
+    serde_json::from_value(json!({
+        "version": 1,
+        "accounts": [{ "name": format!("Archive Checking {}", tag), "type": "checking", "balance": 250.0 }],
+        "categories": [
+            { "name": format!("Archive Child {}", tag), "parent": format!("Archive Parent {}", tag) },
+            { "name": format!("Archive Parent {}", tag), "color": "#123456" }
+        ],
+        "category_rules": [{ "pattern": format!("archiveshop{}", tag), "category": format!("Archive Child {}", tag) }],
+        "transactions": [{
+            "account": format!("Archive Checking {}", tag),
+            "date": "1983-04-02",
+            "amount": -42.5,
+            "description": format!("Archive purchase {}", tag)
+        }],
+        "debts": [{ "name": format!("Archive Loan {}", tag), "balance": 900.0, "interest_rate": 5.0, "min_payment": 50.0 }],
+        "debt_payments": [{ "debt": format!("Archive Loan {}", tag), "amount": 50.0, "date": "1983-04-15", "interest": 4.0 }],
+        "spending_targets": [{
+            "category": format!("Archive Child {}", tag),
+            "amount": 100.0,
+            "period": "monthly",
+            "start_date": "1983-04-01"
+        }],
+        "column_mappings": [{
+            "source_name": format!("Archive Bank {}", tag),
+            "date_col": "Date",
+            "amount_col": "Amount",
+            "description_col": "Memo"
+        }]
+    }))
+    .expect("Sample archive should deserialize")
+}
+
+async fn delete_debt(db: &SqlitePool, name: &str) {
+    sqlx::query("DELETE FROM debts WHERE name = ?").bind(name).execute(db).await.unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_restore_creates_then_skips() {
+    let db = super::get_test_db_pool().await;
+    let tag = super::unique_word("skip");
+    let archive = sample_archive(&tag);
+
+    let created = restore_data_archive(db, &archive, ArchiveConflict::Skip, false).await.expect("Restore should succeed");
+    assert_eq!(created.accounts.created, 1);
+    assert_eq!(created.categories.created, 2, "Parent is created before its child regardless of order");
+    assert_eq!(created.category_rules.created, 1);
+    assert_eq!(created.transactions.created, 1);
+    assert_eq!(created.debts.created, 1);
+    assert_eq!(created.debt_payments.created, 1);
+    assert_eq!(created.spending_targets.created, 1);
+    assert_eq!(created.column_mappings.created, 1);
+
+    let (parent,): (String,) = sqlx::query_as(
+        "SELECT p.name FROM categories c JOIN categories p ON p.id = c.parent_id WHERE c.name = ?"
+    )
+    .bind(format!("Archive Child {}", tag))
+    .fetch_one(db)
+    .await
+    .unwrap();
+    assert_eq!(parent, format!("Archive Parent {}", tag));
+
+    let (principal_cents,): (i64,) = sqlx::query_as(
+        "SELECT p.principal_cents FROM debt_payments p JOIN debts d ON d.id = p.debt_id WHERE d.name = ?"
+    )
+    .bind(format!("Archive Loan {}", tag))
+    .fetch_one(db)
+    .await
+    .unwrap();
+    assert_eq!(principal_cents, 4600, "Principal defaults to the amount less interest");
+
+    // Restoring the same archive again matches every record
+    let again = restore_data_archive(db, &archive, ArchiveConflict::Skip, false).await.expect("Restore should succeed");
+    assert_eq!(again.accounts.skipped, 1);
+    assert_eq!(again.categories.skipped, 2);
+    assert_eq!(again.category_rules.skipped, 1);
+    assert_eq!(again.transactions.skipped, 1);
+    assert_eq!(again.debts.skipped, 1);
+    assert_eq!(again.debt_payments.skipped, 1);
+    assert_eq!(again.spending_targets.skipped, 1);
+    assert_eq!(again.column_mappings.skipped, 1);
+    assert_eq!(again.transactions.created + again.accounts.created, 0);
+
+    delete_debt(db, &format!("Archive Loan {}", tag)).await;
+}
+
+#[tokio::test]
+#[serial]
+async fn test_restore_overwrite_and_merge() {
+    let db = super::get_test_db_pool().await;
+    let tag = super::unique_word("conflict");
+    let mut archive = sample_archive(&tag);
+    restore_data_archive(db, &archive, ArchiveConflict::Skip, false).await.expect("Restore should succeed");
+
+    archive.accounts[0].balance_cents = 99_900;
+    archive.accounts[0].account_type = "savings".to_string();
+    archive.transactions[0].notes = Some("From the archive".to_string());
+    archive.debts[0].balance_cents = 70_000;
+
+    // Merge fills in what's missing but keeps existing values
+    let merged = restore_data_archive(db, &archive, ArchiveConflict::Merge, false).await.expect("Merge should succeed");
+    assert_eq!(merged.accounts.updated, 1);
+    let (account_type, balance_cents): (String, i64) = sqlx::query_as("SELECT type, balance_cents FROM accounts WHERE name = ?")
+        .bind(format!("Archive Checking {}", tag))
+        .fetch_one(db)
+        .await
+        .unwrap();
+    assert_eq!(account_type, "checking");
+    assert_eq!(balance_cents, 25_000);
+    let (notes,): (Option<String>,) = sqlx::query_as("SELECT notes FROM transactions WHERE description = ?")
+        .bind(format!("Archive purchase {}", tag))
+        .fetch_one(db)
+        .await
+        .unwrap();
+    assert_eq!(notes.as_deref(), Some("From the archive"));
+
+    // Overwrite replaces the details
+    let overwritten = restore_data_archive(db, &archive, ArchiveConflict::Overwrite, false).await.expect("Overwrite should succeed");
+    assert_eq!(overwritten.accounts.updated, 1);
+    assert_eq!(overwritten.debts.updated, 1);
+    let (account_type, balance_cents): (String, i64) = sqlx::query_as("SELECT type, balance_cents FROM accounts WHERE name = ?")
+        .bind(format!("Archive Checking {}", tag))
+        .fetch_one(db)
+        .await
+        .unwrap();
+    assert_eq!(account_type, "savings");
+    assert_eq!(balance_cents, 99_900);
+    let (debt_balance_cents,): (i64,) = sqlx::query_as("SELECT balance_cents FROM debts WHERE name = ?")
+        .bind(format!("Archive Loan {}", tag))
+        .fetch_one(db)
+        .await
+        .unwrap();
+    assert_eq!(debt_balance_cents, 70_000);
+
+    delete_debt(db, &format!("Archive Loan {}", tag)).await;
+}
+
+#[tokio::test]
+#[serial]
+async fn test_export_round_trip() {
+    let db = super::get_test_db_pool().await;
+    let tag = super::unique_word("export");
+    restore_data_archive(db, &sample_archive(&tag), ArchiveConflict::Skip, false).await.expect("Restore should succeed");
+
+    let output_path = std::env::temp_dir().join(format!("{}.json", super::unique_word("archive")));
+    let output_path = output_path.to_str().unwrap();
+    let exported = export_all_data_impl(db, output_path).await.expect("Export should succeed");
+    assert!(exported.transactions >= 1);
+
+    let archive = parse_data_archive(&std::fs::read_to_string(output_path).unwrap()).expect("Export should parse");
+    let account = archive.accounts.iter().find(|a| a.name == format!("Archive Checking {}", tag)).expect("Account exported");
+    assert_eq!(account.account_type, "checking");
+    let child = archive.categories.iter().find(|c| c.name == format!("Archive Child {}", tag)).expect("Category exported");
+    assert_eq!(child.parent, Some(format!("Archive Parent {}", tag)));
+    let transaction = archive
+        .transactions
+        .iter()
+        .find(|t| t.description == format!("Archive purchase {}", tag))
+        .expect("Transaction exported");
+    assert_eq!(transaction.account, format!("Archive Checking {}", tag));
+    assert!(archive.debt_payments.iter().any(|p| p.debt == format!("Archive Loan {}", tag)));
+    assert!(archive.column_mappings.iter().any(|m| m.source_name == format!("Archive Bank {}", tag)));
+
+    // Importing the file back changes nothing that was already there
+    let reimported = import_all_data_impl(db, output_path, ArchiveConflict::Skip, false).await;
+    let _ = std::fs::remove_file(output_path);
+    delete_debt(db, &format!("Archive Loan {}", tag)).await;
+    let result = reimported.expect("Exported archive should import");
+    assert_eq!(result.accounts.created, 0);
+    assert_eq!(result.categories.created, 0);
+    assert_eq!(result.transactions.created, 0);
+    assert_eq!(result.debts.created, 0);
+}
+
+#[tokio::test]
+async fn test_missing_reference_rolls_back() {
+    let db = super::get_test_db_pool().await;
+    let tag = super::unique_word("missing");
+    let mut archive = sample_archive(&tag);
+    archive.debts.clear();
+    archive.debt_payments.clear();
+    archive.transactions[0].account = format!("Nowhere {}", tag);
+
+    let result = restore_data_archive(db, &archive, ArchiveConflict::Skip, false).await;
+    assert!(matches!(result, Err(DataArchiveError::MissingReference { kind: "Account", .. })));
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM accounts WHERE name = ?")
+        .bind(format!("Archive Checking {}", tag))
+        .fetch_one(db)
+        .await
+        .unwrap();
+    assert_eq!(count, 0, "Nothing is written when a reference fails");
+}
+
+#[tokio::test]
+async fn test_parse_rejects_newer_version_and_invalid_records() {
+    let result = parse_data_archive(r#"{ "version": 99 }"#);
+    assert!(matches!(result, Err(DataArchiveError::UnsupportedVersion { found: 99, .. })));
+
+    let db = super::get_test_db_pool().await;
+    let mut archive = sample_archive(&super::unique_word("invalid"));
+    archive.transactions[0].date = "04/02/1983".to_string();
+    let result = restore_data_archive(db, &archive, ArchiveConflict::Skip, false).await;
+    assert!(matches!(result, Err(DataArchiveError::Invalid(_))));
+}
+
+#[tokio::test]
+async fn test_restore_keeps_identical_transactions() {
+    let db = super::get_test_db_pool().await;
+    let tag = super::unique_word("repeat");
+    let mut archive = sample_archive(&tag);
+    archive.debts.clear();
+    archive.debt_payments.clear();
+    archive.transactions.push(archive.transactions[0].clone());
+
+    let result = restore_data_archive(db, &archive, ArchiveConflict::Skip, false).await.expect("Restore should succeed");
+    assert_eq!(result.transactions.created, 2, "Two identical purchases are both kept");
+
+    let again = restore_data_archive(db, &archive, ArchiveConflict::Skip, false).await.expect("Restore should succeed");
+    assert_eq!(again.transactions.skipped, 2);
+}
@@ -1,8 +1,12 @@
-use budget_balancer_lib::models::debt::NewDebt;
+use budget_balancer_lib::models::debt::{NewDebt, NewLoan};
 use budget_balancer_lib::commands::debt_commands::{
-    calculate_payoff_plan_impl, compare_strategies_impl, create_debt_impl, get_debt_progress_impl, get_payoff_plan_impl,
-    list_debts_impl, record_debt_payment_impl, update_debt_impl,
+    calculate_payoff_plan_impl, compare_strategies_impl, create_debt_impl, create_loan_impl, delete_debt_payment_impl,
+    get_amortization_schedule_impl, get_debt_progress_impl, get_marginal_payment_impact_impl, get_payoff_plan_impl,
+    get_upcoming_payments_impl, list_debts_impl, record_debt_payment_impl, set_debt_due_day_impl,
+    set_debt_min_payment_percent_impl, simulate_payoff_scenarios_impl, update_debt_impl, update_debt_payment_impl,
 };
+use budget_balancer_lib::utils::dates;
+use chrono::NaiveDate;
 use serial_test::serial;
 use sqlx::SqlitePool;
 
@@ -27,6 +31,7 @@ async fn get_test_db() -> SqlitePool {
 }
 
 // Helper function to clean up debts by name pattern
+#[allow(dead_code)]
 async fn cleanup_test_debts(name_pattern: &str) {
     let db = get_test_db().await;
     let pattern = format!("%{}%", name_pattern);
@@ -51,9 +56,9 @@ async fn test_create_debt_success() {
     let db = super::get_test_db_pool().await;
     let debt = NewDebt {
         name: unique_name("Credit Card A"),
-        balance: 5000.0,
+        balance_cents: 500_000,
         interest_rate: 18.5,
-        min_payment: 150.0,
+        min_payment_cents: 15_000,
     };
 
     let result = create_debt_impl(db, debt).await;
@@ -72,9 +77,9 @@ async fn test_create_debt_invalid_interest_rate() {
     let db = super::get_test_db_pool().await;
     let debt = NewDebt {
         name: unique_name("Test Debt"),
-        balance: 1000.0,
+        balance_cents: 100_000,
         interest_rate: 150.0, // Invalid: > 100
-        min_payment: 50.0,
+        min_payment_cents: 5000,
     };
 
     let result = create_debt_impl(db, debt).await;
@@ -93,9 +98,9 @@ async fn test_create_debt_negative_balance() {
     let db = super::get_test_db_pool().await;
     let debt = NewDebt {
         name: unique_name("Test Debt"),
-        balance: -1000.0, // Invalid
+        balance_cents: -100_000, // Invalid
         interest_rate: 15.0,
-        min_payment: 50.0,
+        min_payment_cents: 5000,
     };
 
     let result = create_debt_impl(db, debt).await;
@@ -116,9 +121,9 @@ async fn test_list_debts() {
     // Create a test debt first
     let debt = NewDebt {
         name: unique_name("Test List Debt"),
-        balance: 2000.0,
+        balance_cents: 200_000,
         interest_rate: 15.0,
-        min_payment: 75.0,
+        min_payment_cents: 7500,
     };
     create_debt_impl(db, debt).await.unwrap();
 
@@ -129,7 +134,7 @@ async fn test_list_debts() {
     assert!(!debts.is_empty(), "Should have at least one debt");
     assert!(debts[0].id > 0);
     assert!(!debts[0].name.is_empty());
-    assert!(debts[0].balance >= 0.0);
+    assert!(debts[0].balance_cents >= 0);
 }
 
 // T032: Contract test for update_debt command
@@ -139,14 +144,14 @@ async fn test_update_debt_balance() {
     // Create a test debt
     let debt = NewDebt {
         name: unique_name("Test Update Debt"),
-        balance: 3000.0,
+        balance_cents: 300_000,
         interest_rate: 18.0,
-        min_payment: 100.0,
+        min_payment_cents: 10_000,
     };
     let debt_id = create_debt_impl(db, debt).await.unwrap();
 
     // Update the balance
-    let result = update_debt_impl(db, debt_id, Some(2500.0), None, None).await;
+    let result = update_debt_impl(db, debt_id, Some(250_000), None, None).await;
     assert!(
         result.is_ok(),
         "Failed to update debt: {:?}",
@@ -157,13 +162,13 @@ async fn test_update_debt_balance() {
     let debts = list_debts_impl(db).await.unwrap();
     let updated_debt = debts.iter().find(|d| d.id == debt_id);
     assert!(updated_debt.is_some(), "Updated debt should exist");
-    assert_eq!(updated_debt.unwrap().balance, 2500.0);
+    assert_eq!(updated_debt.unwrap().balance_cents, 250_000);
 }
 
 #[tokio::test]
 async fn test_update_debt_not_found() {
     let db = super::get_test_db_pool().await;
-    let result = update_debt_impl(db, 99999, Some(1000.0), None, None).await;
+    let result = update_debt_impl(db, 99999, Some(100_000), None, None).await;
     assert!(result.is_err(), "Should fail for non-existent debt");
     let error = result.unwrap_err();
     let error_msg = error.to_string();
@@ -185,20 +190,20 @@ async fn test_calculate_avalanche_payoff_plan() {
     // Create two test debts with different interest rates
     let debt1 = NewDebt {
         name: unique_name("Low Interest"),
-        balance: 1000.0,
+        balance_cents: 100_000,
         interest_rate: 10.0,
-        min_payment: 25.0,
+        min_payment_cents: 2500,
     };
     let debt2 = NewDebt {
         name: unique_name("High Interest"),
-        balance: 1000.0,
+        balance_cents: 100_000,
         interest_rate: 20.0,
-        min_payment: 25.0,
+        min_payment_cents: 2500,
     };
     create_debt_impl(db, debt1).await.unwrap();
     create_debt_impl(db, debt2).await.unwrap();
 
-    let result = calculate_payoff_plan_impl(db, "avalanche".to_string(), 200.0).await;
+    let result = calculate_payoff_plan_impl(db, "avalanche".to_string(), 20_000).await;
     assert!(
         result.is_ok(),
         "Failed to calculate avalanche plan: {:?}",
@@ -208,7 +213,7 @@ async fn test_calculate_avalanche_payoff_plan() {
     let plan = result.unwrap();
     assert_eq!(plan.strategy, "avalanche");
     assert!(!plan.payoff_date.is_empty());
-    assert!(plan.total_interest > 0.0);
+    assert!(plan.total_interest_cents > 0);
     assert!(!plan.monthly_breakdown.is_empty());
 
     // Verify first month prioritizes high interest debt
@@ -226,20 +231,20 @@ async fn test_calculate_snowball_payoff_plan() {
     // Create two test debts with different balances
     let debt1 = NewDebt {
         name: unique_name("Small Balance"),
-        balance: 500.0,
+        balance_cents: 50_000,
         interest_rate: 20.0,
-        min_payment: 25.0,
+        min_payment_cents: 2500,
     };
     let debt2 = NewDebt {
         name: unique_name("Large Balance"),
-        balance: 2000.0,
+        balance_cents: 200_000,
         interest_rate: 10.0,
-        min_payment: 25.0,
+        min_payment_cents: 2500,
     };
     create_debt_impl(db, debt1).await.unwrap();
     create_debt_impl(db, debt2).await.unwrap();
 
-    let result = calculate_payoff_plan_impl(db, "snowball".to_string(), 200.0).await;
+    let result = calculate_payoff_plan_impl(db, "snowball".to_string(), 20_000).await;
     assert!(
         result.is_ok(),
         "Failed to calculate snowball plan: {:?}",
@@ -257,13 +262,13 @@ async fn test_calculate_payoff_plan_insufficient_funds() {
     let db = super::get_test_db_pool().await;
     let debt = NewDebt {
         name: unique_name("Test Debt"),
-        balance: 1000.0,
+        balance_cents: 100_000,
         interest_rate: 15.0,
-        min_payment: 100.0,
+        min_payment_cents: 10_000,
     };
     create_debt_impl(db, debt).await.unwrap();
 
-    let result = calculate_payoff_plan_impl(db, "avalanche".to_string(), 50.0).await;
+    let result = calculate_payoff_plan_impl(db, "avalanche".to_string(), 5000).await;
     assert!(
         result.is_err(),
         "Should reject insufficient monthly amount"
@@ -286,13 +291,13 @@ async fn test_calculate_payoff_plan_invalid_strategy() {
 
     let debt = NewDebt {
         name: unique_name("Test Debt"),
-        balance: 1000.0,
+        balance_cents: 100_000,
         interest_rate: 15.0,
-        min_payment: 50.0,
+        min_payment_cents: 5000,
     };
     create_debt_impl(db, debt).await.unwrap();
 
-    let result = calculate_payoff_plan_impl(db, "invalid_strategy".to_string(), 150.0).await;
+    let result = calculate_payoff_plan_impl(db, "invalid_strategy".to_string(), 15_000).await;
     assert!(
         result.is_err(),
         "Should reject invalid strategy"
@@ -317,13 +322,13 @@ async fn test_get_payoff_plan() {
     // Create a debt and plan
     let debt = NewDebt {
         name: unique_name("Plan Test Debt"),
-        balance: 1000.0,
+        balance_cents: 100_000,
         interest_rate: 15.0,
-        min_payment: 50.0,
+        min_payment_cents: 5000,
     };
     create_debt_impl(db, debt).await.unwrap();
 
-    let plan = calculate_payoff_plan_impl(db, "avalanche".to_string(), 150.0)
+    let plan = calculate_payoff_plan_impl(db, "avalanche".to_string(), 15_000)
         .await
         .unwrap();
 
@@ -361,13 +366,13 @@ async fn test_record_debt_payment() {
     let db = super::get_test_db_pool().await;
     let debt = NewDebt {
         name: unique_name("Payment Test Debt"),
-        balance: 1000.0,
+        balance_cents: 100_000,
         interest_rate: 15.0,
-        min_payment: 50.0,
+        min_payment_cents: 5000,
     };
     let debt_id = create_debt_impl(db, debt).await.unwrap();
 
-    let result = record_debt_payment_impl(db, debt_id, 200.0, "2025-10-15".to_string(), None).await;
+    let result = record_debt_payment_impl(db, debt_id, 20_000, "2025-10-15".to_string(), None).await;
     assert!(
         result.is_ok(),
         "Failed to record payment: {:?}",
@@ -376,7 +381,7 @@ async fn test_record_debt_payment() {
 
     let payment_response = result.unwrap();
     assert!(payment_response.payment_id > 0);
-    assert!(payment_response.updated_balance < 1000.0);
+    assert!(payment_response.updated_balance_cents < 100_000);
 }
 
 #[tokio::test]
@@ -384,13 +389,13 @@ async fn test_record_debt_payment_exceeds_balance() {
     let db = super::get_test_db_pool().await;
     let debt = NewDebt {
         name: unique_name("Payment Exceed Test"),
-        balance: 500.0,
+        balance_cents: 50_000,
         interest_rate: 15.0,
-        min_payment: 50.0,
+        min_payment_cents: 5000,
     };
     let debt_id = create_debt_impl(db, debt).await.unwrap();
 
-    let result = record_debt_payment_impl(db, debt_id, 999999.0, "2025-10-15".to_string(), None).await;
+    let result = record_debt_payment_impl(db, debt_id, 99_999_900, "2025-10-15".to_string(), None).await;
     assert!(
         result.is_err(),
         "Should reject payment exceeding balance"
@@ -409,14 +414,14 @@ async fn test_record_debt_payment_invalid_amount() {
     let db = super::get_test_db_pool().await;
     let debt = NewDebt {
         name: unique_name("Invalid Payment Test"),
-        balance: 1000.0,
+        balance_cents: 100_000,
         interest_rate: 15.0,
-        min_payment: 50.0,
+        min_payment_cents: 5000,
     };
     let debt_id = create_debt_impl(db, debt).await.unwrap();
 
     // Test zero payment
-    let result = record_debt_payment_impl(db, debt_id, 0.0, "2025-10-15".to_string(), None).await;
+    let result = record_debt_payment_impl(db, debt_id, 0, "2025-10-15".to_string(), None).await;
     assert!(
         result.is_err(),
         "Should reject zero payment amount"
@@ -430,7 +435,7 @@ async fn test_record_debt_payment_invalid_amount() {
     );
 
     // Test negative payment
-    let result = record_debt_payment_impl(db, debt_id, -100.0, "2025-10-15".to_string(), None).await;
+    let result = record_debt_payment_impl(db, debt_id, -10_000, "2025-10-15".to_string(), None).await;
     assert!(
         result.is_err(),
         "Should reject negative payment amount"
@@ -451,14 +456,14 @@ async fn test_get_debt_progress() {
     let db = super::get_test_db_pool().await;
     let debt = NewDebt {
         name: unique_name("Progress Test Debt"),
-        balance: 1000.0,
+        balance_cents: 100_000,
         interest_rate: 15.0,
-        min_payment: 50.0,
+        min_payment_cents: 5000,
     };
     let debt_id = create_debt_impl(db, debt).await.unwrap();
 
     // Record a payment
-    record_debt_payment_impl(db, debt_id, 100.0, "2025-10-15".to_string(), None)
+    record_debt_payment_impl(db, debt_id, 10_000, "2025-10-15".to_string(), None)
         .await
         .unwrap();
 
@@ -472,10 +477,41 @@ async fn test_get_debt_progress() {
     let progress = result.unwrap();
     assert_eq!(progress.debt.id, debt_id);
     assert!(!progress.payments.is_empty());
-    assert!(progress.total_paid > 0.0);
+    assert!(progress.total_paid_cents > 0);
     assert!(!progress.balance_history.is_empty());
 }
 
+#[tokio::test]
+#[serial]
+async fn test_balance_history_records_every_change() {
+    let db = super::get_test_db_pool().await;
+    let today = dates::today(db).await;
+    let debt_id = create_debt_impl(db, NewDebt {
+        name: unique_name("History Debt"),
+        balance_cents: 100_000,
+        interest_rate: 0.0,
+        min_payment_cents: 5000,
+    })
+    .await
+    .unwrap();
+
+    let payment_date = dates::format_date(today);
+    let payment = record_debt_payment_impl(db, debt_id, 20_000, payment_date.clone(), None).await.unwrap();
+    update_debt_payment_impl(db, payment.payment_id, Some(25_000), None).await.unwrap();
+    // A new charge raises the balance, which a history rebuilt from payments couldn't show
+    update_debt_impl(db, debt_id, Some(120_000), None, None).await.unwrap();
+    // Unchanged balances aren't recorded
+    update_debt_impl(db, debt_id, None, Some(5.0), None).await.unwrap();
+
+    let progress = get_debt_progress_impl(db, debt_id, None, None).await.unwrap();
+    let history: Vec<(&str, i64)> = progress.balance_history.iter().map(|p| (p.change.as_str(), p.balance_cents)).collect();
+    assert_eq!(
+        history,
+        vec![("opened", 100_000), ("payment", 80_000), ("correction", 75_000), ("adjustment", 120_000)]
+    );
+    assert!(progress.balance_history.iter().all(|p| p.date == payment_date));
+}
+
 // T037: Contract test for compare_strategies command
 #[tokio::test]
 #[serial]
@@ -487,20 +523,20 @@ async fn test_compare_strategies() {
     // Create test debts
     let debt1 = NewDebt {
         name: unique_name("Compare Debt 1"),
-        balance: 1000.0,
+        balance_cents: 100_000,
         interest_rate: 18.0,
-        min_payment: 50.0,
+        min_payment_cents: 5000,
     };
     let debt2 = NewDebt {
         name: unique_name("Compare Debt 2"),
-        balance: 2000.0,
+        balance_cents: 200_000,
         interest_rate: 12.0,
-        min_payment: 75.0,
+        min_payment_cents: 7500,
     };
     create_debt_impl(db, debt1).await.unwrap();
     create_debt_impl(db, debt2).await.unwrap();
 
-    let result = compare_strategies_impl(db, 300.0).await;
+    let result = compare_strategies_impl(db, 30_000).await;
     assert!(
         result.is_ok(),
         "Failed to compare strategies: {:?}",
@@ -510,12 +546,339 @@ async fn test_compare_strategies() {
     let comparison = result.unwrap();
     assert_eq!(comparison.avalanche.strategy, "avalanche");
     assert_eq!(comparison.snowball.strategy, "snowball");
-    assert!(comparison.savings.interest_saved >= 0.0);
+    assert!(comparison.savings.interest_saved_cents >= 0);
     assert!(comparison.savings.months_saved >= 0);
 
     // Avalanche should typically save on interest
     assert!(
-        comparison.avalanche.total_interest <= comparison.snowball.total_interest,
+        comparison.avalanche.total_interest_cents <= comparison.snowball.total_interest_cents,
         "Avalanche should save interest compared to snowball"
     );
 }
+
+#[tokio::test]
+#[serial]
+async fn test_set_debt_due_day() {
+    let db = super::get_test_db_pool().await;
+    let debt_id = create_debt_impl(db, NewDebt {
+        name: unique_name("Due Day Debt"),
+        balance_cents: 100_000,
+        interest_rate: 12.0,
+        min_payment_cents: 5000,
+    })
+    .await
+    .unwrap();
+
+    let listed = list_debts_impl(db).await.unwrap();
+    assert_eq!(listed.iter().find(|d| d.id == debt_id).unwrap().due_day, 15, "New debts are due on the 15th");
+
+    let debt = set_debt_due_day_impl(db, debt_id, 31).await.unwrap();
+    assert_eq!(debt.due_day, 31);
+
+    let err = set_debt_due_day_impl(db, debt_id, 0).await.unwrap_err();
+    assert!(err.to_user_message().contains("between 1 and 31"));
+    assert!(set_debt_due_day_impl(db, debt_id, 32).await.is_err());
+    assert!(set_debt_due_day_impl(db, 999_999_999, 10).await.is_err());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_set_debt_min_payment_percent() {
+    let db = super::get_test_db_pool().await;
+    let debt_id = create_debt_impl(db, NewDebt {
+        name: unique_name("Percent Minimum Debt"),
+        balance_cents: 500_000,
+        interest_rate: 22.0,
+        min_payment_cents: 2500,
+    })
+    .await
+    .unwrap();
+
+    let debt = set_debt_min_payment_percent_impl(db, debt_id, Some(2.0)).await.unwrap();
+    assert_eq!(debt.min_payment_percent, Some(2.0));
+    assert_eq!(debt.current_min_payment(), 10_000, "2% of the balance is above the $25 floor");
+
+    let err = set_debt_min_payment_percent_impl(db, debt_id, Some(0.0)).await.unwrap_err();
+    assert!(err.to_user_message().contains("Minimum payment percentage"));
+    assert!(set_debt_min_payment_percent_impl(db, debt_id, Some(101.0)).await.is_err());
+    assert!(set_debt_min_payment_percent_impl(db, 999_999_999, Some(2.0)).await.is_err());
+
+    let debt = set_debt_min_payment_percent_impl(db, debt_id, None).await.unwrap();
+    assert_eq!(debt.min_payment_percent, None);
+    assert_eq!(debt.current_min_payment(), 2500);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_get_upcoming_payments() {
+    let db = super::get_test_db_pool().await;
+    let debt_id = create_debt_impl(db, NewDebt {
+        name: unique_name("Upcoming Debt"),
+        balance_cents: 12_000,
+        interest_rate: 10.0,
+        min_payment_cents: 5000,
+    })
+    .await
+    .unwrap();
+    set_debt_due_day_impl(db, debt_id, 31).await.unwrap();
+
+    let today = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+    let schedules = get_upcoming_payments_impl(db, 6, today).await.unwrap();
+    let payments: Vec<(String, i64)> = schedules
+        .iter()
+        .flat_map(|s| &s.payments)
+        .filter(|p| p.debt_id == debt_id)
+        .map(|p| (p.due_date.clone(), p.amount_cents))
+        .collect();
+
+    // Due on the last day of short months, and the final payment covers what's left
+    assert_eq!(
+        payments,
+        vec![
+            ("2025-01-31".to_string(), 5000),
+            ("2025-02-28".to_string(), 5000),
+            ("2025-03-31".to_string(), 2000),
+        ]
+    );
+    for schedule in &schedules {
+        let total: i64 = schedule.payments.iter().map(|p| p.amount_cents).sum();
+        assert_eq!(schedule.total_amount_cents, total);
+    }
+
+    assert!(get_upcoming_payments_impl(db, 0, today).await.is_err());
+    assert!(get_upcoming_payments_impl(db, 25, today).await.is_err());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_simulate_payoff_scenarios() {
+    let db = super::get_test_db_pool().await;
+    cleanup_all_debts().await;
+
+    create_debt_impl(db, NewDebt {
+        name: unique_name("Scenario Card"),
+        balance_cents: 300_000,
+        interest_rate: 22.0,
+        min_payment_cents: 9000,
+    })
+    .await
+    .unwrap();
+    create_debt_impl(db, NewDebt {
+        name: unique_name("Scenario Loan"),
+        balance_cents: 150_000,
+        interest_rate: 6.0,
+        min_payment_cents: 6000,
+    })
+    .await
+    .unwrap();
+
+    let results = simulate_payoff_scenarios_impl(db, vec![50_000, 30_000, 10_000]).await.unwrap();
+
+    // Same order as requested; more money pays off sooner with less interest
+    let amounts: Vec<i64> = results.iter().map(|r| r.monthly_amount_cents).collect();
+    assert_eq!(amounts, vec![50_000, 30_000, 10_000]);
+    let fast = results[0].avalanche.as_ref().unwrap();
+    let slow = results[1].avalanche.as_ref().unwrap();
+    assert!(fast.payoff_months < slow.payoff_months);
+    assert!(fast.total_interest_cents < slow.total_interest_cents);
+    assert!(results[1].snowball.as_ref().unwrap().total_interest_cents >= slow.total_interest_cents);
+
+    // Below the $150 of minimum payments
+    assert!(results[2].avalanche.is_none());
+    assert!(results[2].error.as_ref().unwrap().contains("Insufficient funds"));
+
+    let (plans,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM debt_plans").fetch_one(db).await.unwrap();
+    assert_eq!(plans, 0, "Simulations must not save plans");
+
+    assert!(simulate_payoff_scenarios_impl(db, vec![]).await.is_err());
+    assert!(simulate_payoff_scenarios_impl(db, vec![0]).await.is_err());
+    assert!(simulate_payoff_scenarios_impl(db, vec![30_000; 21]).await.is_err());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_get_marginal_payment_impact() {
+    let db = super::get_test_db_pool().await;
+    cleanup_all_debts().await;
+
+    create_debt_impl(db, NewDebt {
+        name: unique_name("Marginal Card"),
+        balance_cents: 400_000,
+        interest_rate: 24.0,
+        min_payment_cents: 10_000,
+    })
+    .await
+    .unwrap();
+
+    let result = get_marginal_payment_impact_impl(db, "avalanche".to_string(), 15_000, None).await.unwrap();
+
+    assert_eq!(result.monthly_amount_cents, 15_000);
+    let increments: Vec<i64> = result.impacts.iter().map(|i| i.increment_cents).collect();
+    assert_eq!(increments, vec![2500, 5000, 10_000]);
+    assert_eq!(result.impacts[1].monthly_amount_cents, 20_000);
+
+    // Every extra dollar helps, and more helps more
+    for pair in result.impacts.windows(2) {
+        assert!(pair[0].months_saved > 0 && pair[0].interest_saved_cents > 0);
+        assert!(pair[1].months_saved >= pair[0].months_saved);
+        assert!(pair[1].interest_saved_cents > pair[0].interest_saved_cents);
+    }
+    let last = result.impacts.last().unwrap();
+    assert_eq!(last.payoff_months, result.current.payoff_months - last.months_saved);
+
+    let (plans,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM debt_plans").fetch_one(db).await.unwrap();
+    assert_eq!(plans, 0, "The comparison must not save plans");
+
+    let custom = get_marginal_payment_impact_impl(db, "snowball".to_string(), 15_000, Some(vec![1000])).await.unwrap();
+    assert_eq!(custom.impacts.len(), 1);
+    assert_eq!(custom.current.strategy, "snowball");
+
+    assert!(get_marginal_payment_impact_impl(db, "avalanche".to_string(), 5000, None).await.is_err());
+    assert!(get_marginal_payment_impact_impl(db, "fastest".to_string(), 15_000, None).await.is_err());
+    assert!(get_marginal_payment_impact_impl(db, "avalanche".to_string(), 15_000, Some(vec![-2500])).await.is_err());
+    assert!(get_marginal_payment_impact_impl(db, "avalanche".to_string(), 15_000, Some(vec![])).await.is_err());
+}
+
+#[tokio::test]
+async fn test_record_payment_splits_interest_and_principal() {
+    let db = super::get_test_db_pool().await;
+    let debt_id = create_debt_impl(db, NewDebt {
+        name: unique_name("Accrual Debt"),
+        balance_cents: 100_000,
+        interest_rate: 18.25,
+        min_payment_cents: 5000,
+    })
+    .await
+    .unwrap();
+    sqlx::query("UPDATE debts SET created_at = '2020-01-01 00:00:00' WHERE id = ?")
+        .bind(debt_id)
+        .execute(db)
+        .await
+        .unwrap();
+
+    // 20 days at 18.25% on 1000 accrues 10.00
+    let first = record_debt_payment_impl(db, debt_id, 10_000, "2020-01-21".to_string(), None).await.unwrap();
+    assert_eq!(first.interest_cents, 1000);
+    assert_eq!(first.principal_cents, 9000);
+    assert_eq!(first.updated_balance_cents, 91_000);
+
+    // Accrues from the previous payment: 20 days on 910 is 9.10
+    let second = record_debt_payment_impl(db, debt_id, 10_000, "2020-02-10".to_string(), None).await.unwrap();
+    assert_eq!(second.interest_cents, 910);
+    assert_eq!(second.updated_balance_cents, 81_910);
+
+    let progress = get_debt_progress_impl(db, debt_id, None, None).await.unwrap();
+    assert_eq!(progress.total_paid_cents, 20_000);
+    assert_eq!(progress.total_interest_paid_cents, 1910);
+    assert_eq!(progress.total_principal_paid_cents, 18_090);
+    assert!(progress.payments.iter().all(|p| p.interest_cents + p.principal_cents == p.amount_cents));
+
+    // Paying off takes the balance plus interest accrued since the last payment
+    assert!(record_debt_payment_impl(db, debt_id, 83_000, "2020-03-01".to_string(), None).await.is_err());
+    let payoff = record_debt_payment_impl(db, debt_id, 82_729, "2020-03-01".to_string(), None).await.unwrap();
+    assert_eq!(payoff.interest_cents, 819);
+    assert_eq!(payoff.updated_balance_cents, 0);
+
+    assert!(record_debt_payment_impl(db, debt_id, 1000, "03/01/2020".to_string(), None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_edit_and_delete_debt_payments() {
+    let db = super::get_test_db_pool().await;
+    let debt_id = create_debt_impl(db, NewDebt {
+        name: unique_name("Fat Finger Debt"),
+        balance_cents: 100_000,
+        interest_rate: 0.0,
+        min_payment_cents: 5000,
+    })
+    .await
+    .unwrap();
+
+    let typo = record_debt_payment_impl(db, debt_id, 50_000, "2025-01-15".to_string(), None).await.unwrap();
+    let second = record_debt_payment_impl(db, debt_id, 10_000, "2025-02-15".to_string(), None).await.unwrap();
+    assert_eq!(second.updated_balance_cents, 40_000);
+
+    // Meant to pay 50, not 500
+    let debt = update_debt_payment_impl(db, typo.payment_id, Some(5000), None).await.unwrap();
+    assert_eq!(debt.balance_cents, 85_000);
+    let progress = get_debt_progress_impl(db, debt_id, None, None).await.unwrap();
+    let fixed = progress.payments.iter().find(|p| p.id == typo.payment_id).unwrap();
+    assert_eq!((fixed.amount_cents, fixed.principal_cents, fixed.date.as_str()), (5000, 5000, "2025-01-15"));
+
+    let debt = update_debt_payment_impl(db, typo.payment_id, None, Some("2025-01-20".to_string())).await.unwrap();
+    assert_eq!(debt.balance_cents, 85_000);
+
+    let debt = delete_debt_payment_impl(db, second.payment_id).await.unwrap();
+    assert_eq!(debt.balance_cents, 95_000);
+    let progress = get_debt_progress_impl(db, debt_id, None, None).await.unwrap();
+    assert_eq!(progress.payments.len(), 1);
+
+    // A manual balance edit survives later corrections, which only shift it
+    update_debt_impl(db, debt_id, Some(90_000), None, None).await.unwrap();
+    let debt = update_debt_payment_impl(db, typo.payment_id, Some(8000), None).await.unwrap();
+    assert_eq!(debt.balance_cents, 87_000);
+    let progress = get_debt_progress_impl(db, debt_id, None, None).await.unwrap();
+    let last = progress.balance_history.last().unwrap();
+    assert_eq!((last.change.as_str(), last.balance_cents), ("correction", 87_000));
+
+    assert!(update_debt_payment_impl(db, typo.payment_id, Some(0), None).await.is_err());
+    assert!(update_debt_payment_impl(db, typo.payment_id, None, Some("Jan 20".to_string())).await.is_err());
+    let err = delete_debt_payment_impl(db, second.payment_id).await.unwrap_err();
+    assert!(err.to_user_message().contains("payment not found"));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_loan_amortization_and_payoff() {
+    let db = super::get_test_db_pool().await;
+    cleanup_all_debts().await;
+    let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+    let car_loan = NewLoan {
+        name: unique_name("Car Loan"),
+        principal_cents: 2_000_000,
+        interest_rate: 6.0,
+        term_months: 60,
+        start_date: "2024-01-15".to_string(),
+        balance_cents: None,
+    };
+
+    // Two payments (Feb 15th and Mar 15th) have been made by April
+    let loan_id = create_loan_impl(db, car_loan.clone(), date("2024-04-01")).await.unwrap();
+    let loan = list_debts_impl(db).await.unwrap().into_iter().find(|d| d.id == loan_id).unwrap();
+    assert_eq!(loan.kind, "loan");
+    assert_eq!(loan.min_payment_cents, 38_666);
+    assert_eq!(loan.original_balance_cents, 2_000_000);
+    assert_eq!(loan.balance_cents, 1_942_525);
+    assert_eq!(loan.due_day, 15);
+
+    let schedule = get_amortization_schedule_impl(db, loan_id).await.unwrap();
+    assert_eq!(schedule.rows.len(), 60);
+    assert_eq!(schedule.rows[1].balance_cents, 1_942_525);
+    assert_eq!(schedule.rows[59].balance_cents, 0);
+    assert_eq!(schedule.total_paid_cents - schedule.total_interest_cents, 2_000_000);
+
+    // Payoff plans take the loan's payment as its minimum: 58 payments remain
+    let plan = calculate_payoff_plan_impl(db, "avalanche".to_string(), 38_666).await.unwrap();
+    assert_eq!(plan.monthly_breakdown.len(), 58);
+
+    let card_id = create_debt_impl(db, NewDebt {
+        name: unique_name("Not A Loan"),
+        balance_cents: 50_000,
+        interest_rate: 20.0,
+        min_payment_cents: 2500,
+    })
+    .await
+    .unwrap();
+    assert!(get_amortization_schedule_impl(db, card_id).await.unwrap_err().to_user_message().contains("not a loan"));
+
+    let invalid = |f: fn(&mut NewLoan)| {
+        let mut loan = car_loan.clone();
+        f(&mut loan);
+        loan
+    };
+    let today = date("2024-04-01");
+    assert!(create_loan_impl(db, invalid(|l| l.term_months = 0), today).await.is_err());
+    assert!(create_loan_impl(db, invalid(|l| l.term_months = 481), today).await.is_err());
+    assert!(create_loan_impl(db, invalid(|l| l.start_date = "01/15/2024".to_string()), today).await.is_err());
+    assert!(create_loan_impl(db, invalid(|l| l.principal_cents = -100), today).await.is_err());
+}
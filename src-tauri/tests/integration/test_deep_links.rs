@@ -0,0 +1,76 @@
+use budget_balancer_lib::commands::deep_link_commands::{handle_deep_link_impl, DeepLinkResult};
+
+async fn account_name(db: &sqlx::SqlitePool, account_id: i64) -> String {
+    let (name,): (String,) = sqlx::query_as("SELECT name FROM accounts WHERE id = ?")
+        .bind(account_id)
+        .fetch_one(db)
+        .await
+        .unwrap();
+    name
+}
+
+#[tokio::test]
+async fn test_deep_link_add_expense() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Deep Link Test").await;
+    let account = account_name(db, account_id).await;
+
+    let url = format!(
+        "budgetbalancer://add-expense?amount=12.50&category=dining&description=Flat%20white&account={}&date=2019-12-02",
+        account.replace(' ', "%20")
+    );
+    let result = handle_deep_link_impl(db, &url).await;
+    assert!(result.is_ok(), "Failed to handle deep link: {:?}", result);
+
+    let DeepLinkResult::ExpenseAdded { transaction_id, account_id: added_to, category_id, amount_cents } = result.unwrap() else {
+        panic!("Expected an added expense");
+    };
+    assert_eq!(added_to, account_id);
+    assert_eq!(category_id, 2, "Category names match case-insensitively");
+    assert_eq!(amount_cents, -1250);
+
+    let row: (String, i64, String) = sqlx::query_as("SELECT date, amount_cents, description FROM transactions WHERE id = ?")
+        .bind(transaction_id)
+        .fetch_one(db)
+        .await
+        .unwrap();
+    assert_eq!(row, ("2019-12-02".to_string(), -1250, "Flat white".to_string()));
+
+    // The same quick entry twice is two real purchases, not a duplicate
+    let again = handle_deep_link_impl(db, &url).await;
+    assert!(again.is_ok(), "Repeated quick entries should both be recorded: {:?}", again);
+}
+
+#[tokio::test]
+async fn test_deep_link_add_expense_errors() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Deep Link Error Test").await;
+    let account = account_name(db, account_id).await.replace(' ', "%20");
+
+    let result = handle_deep_link_impl(
+        db,
+        &format!("budgetbalancer://add-expense?amount=5&category=NoSuchCategory&account={}", account),
+    )
+    .await;
+    assert!(result.unwrap_err().contains("Unknown category"));
+
+    let result = handle_deep_link_impl(db, "budgetbalancer://add-expense?amount=5&account=No%20Such%20Account").await;
+    assert!(result.unwrap_err().contains("Unknown account"));
+
+    let result = handle_deep_link_impl(
+        db,
+        &format!("budgetbalancer://add-expense?amount=5&account={}&date=12/02/2019", account),
+    )
+    .await;
+    assert!(result.unwrap_err().contains("Invalid date"));
+}
+
+#[tokio::test]
+async fn test_deep_link_navigate() {
+    let db = super::get_test_db_pool().await;
+
+    let result = handle_deep_link_impl(db, "budgetbalancer://open?view=debts").await.unwrap();
+    assert!(matches!(result, DeepLinkResult::Navigate { view } if view == "debts"));
+
+    assert!(handle_deep_link_impl(db, "budgetbalancer://format-disk").await.is_err());
+}
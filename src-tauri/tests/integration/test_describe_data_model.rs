@@ -0,0 +1,62 @@
+use budget_balancer_lib::commands::schema_commands::describe_data_model_impl;
+
+#[tokio::test]
+async fn test_describe_data_model_lists_core_entities() {
+    let db = super::get_test_db_pool().await;
+
+    let model = describe_data_model_impl(db).await.expect("Failed to describe data model");
+    let names: Vec<&str> = model.entities.iter().map(|e| e.name.as_str()).collect();
+
+    for expected in ["accounts", "categories", "category_rules", "transactions", "debts", "spending_targets"] {
+        assert!(names.contains(&expected), "Missing entity {}", expected);
+    }
+    assert!(!names.iter().any(|n| n.starts_with("_sqlx") || n.starts_with("sqlite_")), "Internal tables should be hidden");
+    for hidden in ["audit_log", "jobs", "monthly_category_totals", "search_index", "app_settings"] {
+        assert!(!names.contains(&hidden), "{} is bookkeeping, not part of the data model", hidden);
+    }
+}
+
+#[tokio::test]
+async fn test_describe_data_model_fields_and_relationships() {
+    let db = super::get_test_db_pool().await;
+
+    let model = describe_data_model_impl(db).await.unwrap();
+    let transactions = model.entities.iter().find(|e| e.name == "transactions").unwrap();
+
+    let id = transactions.fields.iter().find(|f| f.name == "id").unwrap();
+    assert!(id.primary_key);
+    assert!(!id.nullable);
+
+    let merchant = transactions.fields.iter().find(|f| f.name == "merchant").unwrap();
+    assert_eq!(merchant.data_type, "TEXT");
+    assert!(merchant.nullable);
+
+    let account_fk = transactions
+        .relationships
+        .iter()
+        .find(|r| r.field == "account_id")
+        .expect("transactions should reference accounts");
+    assert_eq!(account_fk.references_entity, "accounts");
+    assert_eq!(account_fk.references_field, "id");
+}
+
+#[tokio::test]
+async fn test_describe_data_model_explains_each_field() {
+    let db = super::get_test_db_pool().await;
+
+    let model = describe_data_model_impl(db).await.unwrap();
+    for entity in &model.entities {
+        assert!(!entity.description.is_empty(), "{} needs a description", entity.name);
+        assert!(entity.fields.iter().any(|f| f.name == "id"), "{} is missing fields", entity.name);
+        for field in &entity.fields {
+            assert!(!field.description.is_empty(), "{}.{} needs a description", entity.name, field.name);
+        }
+    }
+
+    let transactions = model.entities.iter().find(|e| e.name == "transactions").unwrap();
+    assert!(!transactions.fields.iter().any(|f| f.name == "hash"), "Duplicate hashes are internal");
+    assert!(
+        !transactions.relationships.iter().any(|r| r.field == "import_batch_id"),
+        "Links to hidden tables are left out"
+    );
+}
@@ -0,0 +1,111 @@
+use budget_balancer_lib::commands::csv_commands::{
+    get_duplicate_settings_impl, import_csv_impl, reset_rate_limiter, update_duplicate_settings_impl,
+};
+use budget_balancer_lib::errors::CsvImportError;
+use budget_balancer_lib::models::duplicate_settings::{DuplicateField, DuplicateSettingsUpdate, DuplicateStrictness};
+use budget_balancer_lib::services::csv_parser::ColumnMapping;
+use serial_test::serial;
+
+fn mapping() -> ColumnMapping {
+    ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_default_and_reset() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, &super::unique_name("Dup Default")).await;
+
+    let settings = get_duplicate_settings_impl(db, account_id).await.expect("Failed to get settings");
+    assert!(settings.is_default);
+    assert_eq!(settings.fields, vec![DuplicateField::Date, DuplicateField::Amount, DuplicateField::Description]);
+
+    let update = DuplicateSettingsUpdate {
+        fields: vec![DuplicateField::Amount, DuplicateField::Description, DuplicateField::Amount],
+        lookback_days: Some(3),
+        strictness: DuplicateStrictness::Counted,
+    };
+    let settings = update_duplicate_settings_impl(db, account_id, Some(update)).await.expect("Failed to update");
+    assert!(!settings.is_default);
+    assert_eq!(settings.fields, vec![DuplicateField::Amount, DuplicateField::Description]);
+    assert_eq!(settings.lookback_days, Some(3));
+    assert_eq!(settings.strictness, DuplicateStrictness::Counted);
+
+    let settings = update_duplicate_settings_impl(db, account_id, None).await.expect("Failed to reset");
+    assert!(settings.is_default);
+}
+
+#[tokio::test]
+async fn test_invalid_settings() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, &super::unique_name("Dup Invalid")).await;
+
+    let date_only = DuplicateSettingsUpdate {
+        fields: vec![DuplicateField::Date],
+        lookback_days: None,
+        strictness: DuplicateStrictness::Strict,
+    };
+    let result = update_duplicate_settings_impl(db, account_id, Some(date_only)).await;
+    assert!(matches!(result, Err(CsvImportError::InvalidDuplicateSettings(_))));
+
+    let result = get_duplicate_settings_impl(db, 999_999_999).await;
+    assert!(matches!(result, Err(CsvImportError::AccountNotFound(999_999_999))));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_counted_keeps_repeated_rows() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, &super::unique_name("Dup Counted")).await;
+    let coffee = super::unique_name("Coffee");
+    let csv = format!("Date,Amount,Description\n1981-05-04,-3.50,{coffee}\n1981-05-04,-3.50,{coffee}\n");
+
+    let update = DuplicateSettingsUpdate {
+        fields: vec![DuplicateField::Date, DuplicateField::Amount, DuplicateField::Description],
+        lookback_days: None,
+        strictness: DuplicateStrictness::Counted,
+    };
+    update_duplicate_settings_impl(db, account_id, Some(update)).await.expect("Failed to update");
+
+    reset_rate_limiter();
+    let first = import_csv_impl(db, account_id, csv.clone(), mapping()).await.expect("Import failed");
+    assert_eq!(first.imported, 2, "Both coffees are kept");
+    assert_eq!(first.duplicates, 0);
+
+    // A later export overlapping the first, with a third coffee the same day
+    reset_rate_limiter();
+    let overlap = format!("{csv}1981-05-04,-3.50,{coffee}\n");
+    let second = import_csv_impl(db, account_id, overlap, mapping()).await.expect("Import failed");
+    assert_eq!(second.imported, 1);
+    assert_eq!(second.duplicates, 2);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_lookback_window() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, &super::unique_name("Dup Window")).await;
+    let rent = super::unique_name("Rent");
+
+    let update = DuplicateSettingsUpdate {
+        fields: vec![DuplicateField::Amount, DuplicateField::Description],
+        lookback_days: Some(3),
+        strictness: DuplicateStrictness::Strict,
+    };
+    update_duplicate_settings_impl(db, account_id, Some(update)).await.expect("Failed to update");
+
+    reset_rate_limiter();
+    let csv = format!("Date,Amount,Description\n1981-06-01,-900.00,{rent}\n");
+    import_csv_impl(db, account_id, csv, mapping()).await.expect("Import failed");
+
+    // Posted two days later is the same payment; a month later is the next one
+    reset_rate_limiter();
+    let csv = format!("Date,Amount,Description\n1981-06-03,-900.00,{rent}\n1981-07-01,-900.00,{rent}\n");
+    let result = import_csv_impl(db, account_id, csv, mapping()).await.expect("Import failed");
+    assert_eq!(result.duplicates, 1);
+    assert_eq!(result.imported, 1);
+}
@@ -32,6 +32,8 @@ async fn test_export_analytics_report_pdf() {
 
     // Verify file exists
     assert!(PathBuf::from(&output_path).exists(), "Export file should exist");
+    let content = fs::read(&output_path).unwrap();
+    assert!(content.starts_with(b"%PDF"), "Export should be a real PDF document");
 
     // Clean up
     fs::remove_file(output_path).ok();
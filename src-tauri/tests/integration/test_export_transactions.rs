@@ -1,4 +1,7 @@
-use budget_balancer_lib::commands::transaction_commands::export_transactions_impl;
+use budget_balancer_lib::commands::transaction_commands::{
+    export_transactions_impl, list_export_templates_impl, save_export_template_impl,
+};
+use budget_balancer_lib::models::export_template::{ExportColumn, ExportField, NewExportTemplate};
 use std::fs;
 use std::path::PathBuf;
 
@@ -9,8 +12,8 @@ async fn test_export_transactions_to_csv() {
 
     // Create test transactions directly
     let transactions = vec![
-        super::fixtures::TestTransaction::new("2024-01-01", -50.00, "Coffee").with_merchant("Starbucks"),
-        super::fixtures::TestTransaction::new("2024-01-02", -100.00, "Groceries").with_merchant("Whole Foods"),
+        super::fixtures::TestTransaction::new("2024-01-01", -5000, "Coffee").with_merchant("Starbucks"),
+        super::fixtures::TestTransaction::new("2024-01-02", -10_000, "Groceries").with_merchant("Whole Foods"),
     ];
     super::fixtures::insert_test_transactions(db, account_id, transactions).await;
 
@@ -22,6 +25,7 @@ async fn test_export_transactions_to_csv() {
         "csv".to_string(),
         output_path.clone(),
         None, // No filters
+        None,
     )
     .await;
 
@@ -46,7 +50,7 @@ async fn test_export_transactions_to_json() {
 
     // Create test transaction directly
     let transactions = vec![
-        super::fixtures::TestTransaction::new("2024-01-01", -50.00, "Coffee").with_merchant("Starbucks"),
+        super::fixtures::TestTransaction::new("2024-01-01", -5000, "Coffee").with_merchant("Starbucks"),
     ];
     super::fixtures::insert_test_transactions(db, account_id, transactions).await;
 
@@ -58,6 +62,7 @@ async fn test_export_transactions_to_json() {
         "json".to_string(),
         output_path.clone(),
         None,
+        None,
     )
     .await;
 
@@ -81,8 +86,8 @@ async fn test_export_transactions_with_date_filter() {
 
     // Create transactions with different dates
     let transactions = vec![
-        super::fixtures::TestTransaction::new("2024-01-01", -50.00, "Coffee").with_merchant("Starbucks"),
-        super::fixtures::TestTransaction::new("2024-02-01", -100.00, "Groceries").with_merchant("Whole Foods"),
+        super::fixtures::TestTransaction::new("2024-01-01", -5000, "Coffee").with_merchant("Starbucks"),
+        super::fixtures::TestTransaction::new("2024-02-01", -10_000, "Groceries").with_merchant("Whole Foods"),
     ];
     super::fixtures::insert_test_transactions(db, account_id, transactions).await;
 
@@ -96,6 +101,7 @@ async fn test_export_transactions_with_date_filter() {
         "csv".to_string(),
         output_path.clone(),
         None, // TODO: Add filters when implemented
+        None,
     )
     .await;
 
@@ -104,3 +110,127 @@ async fn test_export_transactions_with_date_filter() {
     // Clean up
     fs::remove_file(output_path).ok();
 }
+
+#[tokio::test]
+async fn test_export_transactions_to_qif() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Export QIF Test").await;
+
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("2024-03-02", -10_000, "WHOLEFDS #123").with_merchant("Whole Foods").with_category(1),
+        super::fixtures::TestTransaction::new("2024-03-01", -450, "Coffee"),
+    ];
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let output_path = format!("/tmp/export_test_{}.qif", std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos());
+    let filter = budget_balancer_lib::commands::transaction_commands::TransactionFilter {
+        account_id: Some(vec![account_id]),
+        category_id: None,
+        start_date: None,
+        end_date: None,
+        search: None,
+        min_amount_cents: None,
+        max_amount_cents: None,
+        only_uncategorized: None,
+        exclude_category_ids: None,
+        pending: None,
+        member_id: None,
+        limit: None,
+        offset: None,
+    };
+
+    let result = export_transactions_impl(db, "qif".to_string(), output_path.clone(), Some(filter), None)
+        .await
+        .expect("QIF export should succeed");
+    assert_eq!(result.record_count, 2);
+
+    let content = fs::read_to_string(&output_path).expect("QIF file should exist");
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines[0], "!Account");
+    assert!(lines[1].starts_with("NExport QIF Test"));
+    assert_eq!(lines[2], "TBank");
+    assert!(lines.contains(&"!Type:Bank"));
+
+    // Oldest transaction first; uncategorized transactions carry no category line
+    let first = lines.iter().position(|l| *l == "D03/01/2024").expect("first transaction date");
+    assert_eq!(lines[first + 1], "T-4.50");
+    assert!(lines[first + 2].starts_with("PCoffee"));
+    assert_eq!(lines[first + 3], "^");
+
+    let second = lines.iter().position(|l| *l == "D03/02/2024").expect("second transaction date");
+    assert!(second > first);
+    assert_eq!(lines[second + 1], "T-100.00");
+    assert_eq!(lines[second + 2], "PWhole Foods");
+    assert!(lines[second + 3].starts_with("MWHOLEFDS #123"));
+    assert_eq!(lines[second + 4], "LGroceries");
+
+    fs::remove_file(output_path).ok();
+}
+
+#[tokio::test]
+async fn test_export_transactions_with_template() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Export Template Test").await;
+
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("2024-04-02", -125_075, "Rent").with_category(1),
+        super::fixtures::TestTransaction::new("2024-04-01", 30_000, "Refund"),
+    ];
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let name = super::unique_name("Accountant");
+    let column = |field, header: Option<&str>| ExportColumn { field, header: header.map(str::to_string) };
+    let template = NewExportTemplate {
+        name: name.clone(),
+        columns: vec![
+            column(ExportField::Date, Some("Datum")),
+            column(ExportField::Description, Some("Omschrijving")),
+            column(ExportField::Debit, Some("Af")),
+            column(ExportField::Credit, Some("Bij")),
+            column(ExportField::Category, None),
+        ],
+        date_format: Some("DD-MM-YYYY".to_string()),
+        decimal_separator: ",".to_string(),
+        delimiter: ";".to_string(),
+        include_header: true,
+    };
+    let template_id = save_export_template_impl(db, template.clone()).await.expect("Template should save");
+    assert!(save_export_template_impl(db, template).await.is_err(), "Template names are unique");
+    let saved = list_export_templates_impl(db).await.unwrap();
+    let saved = saved.iter().find(|t| t.id == template_id).expect("Saved template should be listed");
+    assert_eq!(saved.name, name);
+    assert_eq!(saved.columns.len(), 5);
+
+    let output_path = format!("/tmp/export_template_{}.csv", std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos());
+    let filter = budget_balancer_lib::commands::transaction_commands::TransactionFilter {
+        account_id: Some(vec![account_id]),
+        category_id: None,
+        start_date: None,
+        end_date: None,
+        search: None,
+        min_amount_cents: None,
+        max_amount_cents: None,
+        only_uncategorized: None,
+        exclude_category_ids: None,
+        pending: None,
+        member_id: None,
+        limit: None,
+        offset: None,
+    };
+    let result = export_transactions_impl(db, "csv".to_string(), output_path.clone(), Some(filter), Some(template_id))
+        .await
+        .expect("Template export should succeed");
+    assert_eq!(result.record_count, 2);
+
+    let content = fs::read_to_string(&output_path).expect("CSV file should exist");
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines[0], "Datum;Omschrijving;Af;Bij;Category");
+    // Descriptions carry the fixture's unique suffix
+    assert!(lines.iter().any(|l| l.starts_with("02-04-2024;Rent (") && l.ends_with(";1250,75;;Groceries")));
+    assert!(lines.iter().any(|l| l.starts_with("01-04-2024;Refund (") && l.ends_with(";;300,00;Uncategorized")));
+    fs::remove_file(output_path).ok();
+
+    // Templates lay out CSV files only
+    let qif = export_transactions_impl(db, "qif".to_string(), "/tmp/unused.qif".to_string(), None, Some(template_id)).await;
+    assert!(qif.is_err());
+}
@@ -0,0 +1,66 @@
+use budget_balancer_lib::commands::analytics_commands::get_financial_health_impl;
+use chrono::NaiveDate;
+
+#[tokio::test]
+async fn test_get_financial_health() {
+    let db = super::get_test_db_pool().await;
+
+    // Totals cover every account, so start from an empty window
+    sqlx::query("DELETE FROM transactions WHERE date >= '1962-12-01' AND date <= '1963-04-30'")
+        .execute(db)
+        .await
+        .unwrap();
+
+    let account_id = super::fixtures::create_test_account(db, "Financial Health").await;
+
+    use super::fixtures::TestTransaction;
+    super::fixtures::insert_test_transactions(db, account_id, vec![
+        TestTransaction::new("1962-12-31", 999_900, "Before the window"),
+        TestTransaction::new("1963-01-01", 400_000, "Paycheck"),
+        TestTransaction::new("1963-01-15", -300_000, "Rent and groceries"),
+        // No income in February
+        TestTransaction::new("1963-02-10", -50_000, "Groceries"),
+        TestTransaction::new("1963-03-01", 400_000, "Paycheck"),
+        TestTransaction::new("1963-03-20", -200_000, "Rent"),
+        TestTransaction::new("1963-04-02", -999_900, "Current month"),
+    ])
+    .await;
+
+    let today = NaiveDate::from_ymd_opt(1963, 4, 15).unwrap();
+    let health = get_financial_health_impl(db, Some(3), today)
+        .await
+        .expect("Failed to calculate financial health");
+
+    assert_eq!(health.start_month, "1963-01");
+    assert_eq!(health.end_month, "1963-03");
+    assert_eq!(health.total_income_cents, 800_000);
+    assert_eq!(health.total_spending_cents, 550_000);
+    assert!((health.savings_rate.unwrap() - 31.25).abs() < 1e-9);
+
+    let months: Vec<&str> = health.monthly.iter().map(|m| m.month.as_str()).collect();
+    assert_eq!(months, vec!["1963-01", "1963-02", "1963-03"]);
+    assert_eq!(health.monthly[0].savings_rate, Some(25.0));
+    assert_eq!(health.monthly[1].savings_rate, None, "No savings rate without income");
+    assert_eq!(health.monthly[2].savings_rate, Some(50.0));
+    assert!((health.savings_rate_trend.unwrap() - 12.5).abs() < 1e-9);
+
+    // Balances and debts are shared with other tests, so only their relationship is checked
+    let average_spending = 550_000.0 / 3.0;
+    match health.months_of_expenses {
+        Some(months) => assert!((months - health.liquid_balance_cents as f64 / average_spending).abs() < 1e-9),
+        None => panic!("Spending in the window should give months of expenses"),
+    }
+    if health.monthly_debt_payments_cents > 0 {
+        let expected = health.monthly_debt_payments_cents as f64 / (800_000.0 / 3.0) * 100.0;
+        assert!((health.debt_to_income.unwrap() - expected).abs() < 1e-9);
+    }
+}
+
+#[tokio::test]
+async fn test_get_financial_health_invalid_months() {
+    let db = super::get_test_db_pool().await;
+    let today = NaiveDate::from_ymd_opt(1963, 4, 15).unwrap();
+
+    assert!(get_financial_health_impl(db, Some(0), today).await.is_err());
+    assert!(get_financial_health_impl(db, Some(121), today).await.is_err());
+}
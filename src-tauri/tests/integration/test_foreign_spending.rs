@@ -0,0 +1,79 @@
+use budget_balancer_lib::commands::fx_commands::{
+    get_foreign_spending_report_impl, list_exchange_rates_impl, set_exchange_rate_impl,
+    set_transaction_currency_impl,
+};
+
+#[tokio::test]
+async fn test_foreign_spending_report() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Foreign Spending Test").await;
+
+    set_exchange_rate_impl(db, "eur", "2013-01-01", 1.10).await.unwrap();
+    set_exchange_rate_impl(db, "EUR", "2013-06-01", 1.30).await.unwrap();
+
+    // The test database persists between runs, so compare against a report taken before inserting
+    let before = get_foreign_spending_report_impl(db, "2013-01-01", "2013-03-31", Some("2013-07-01"))
+        .await
+        .unwrap();
+
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("2013-02-10", -11_050, "Paris hotel"),
+        super::fixtures::TestTransaction::new("2013-02-11", -2500, "Local groceries"),
+    ];
+    let ids = super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+    set_transaction_currency_impl(db, ids[0], Some("EUR"), Some(-10_000), false).await.unwrap();
+
+    let report = get_foreign_spending_report_impl(db, "2013-01-01", "2013-03-31", Some("2013-07-01"))
+        .await
+        .unwrap();
+    let eur = report.currencies.iter().find(|c| c.currency == "EUR").expect("EUR spending missing");
+    let eur_before = before.currencies.iter().find(|c| c.currency == "EUR");
+    let (count_before, original_before, historical_before, current_before) = eur_before
+        .map(|c| (c.transaction_count, c.original_total_cents, c.historical_total_cents, c.current_total_cents))
+        .unwrap_or_default();
+
+    assert_eq!(eur.transaction_count - count_before, 1);
+    assert_eq!(eur.original_total_cents - original_before, 10_000);
+    assert_eq!(eur.historical_total_cents - historical_before, 11_000);
+    assert_eq!(eur.current_total_cents - current_before, 13_000);
+    assert_eq!(eur.current_rate, Some(1.30));
+    assert_eq!(report.fx_impact_cents, report.current_total_cents - report.historical_total_cents);
+
+    // Clearing the currency drops it from the report
+    set_transaction_currency_impl(db, ids[0], None, None, false).await.unwrap();
+    let cleared = get_foreign_spending_report_impl(db, "2013-01-01", "2013-03-31", Some("2013-07-01"))
+        .await
+        .unwrap();
+    let count_after = cleared
+        .currencies
+        .iter()
+        .find(|c| c.currency == "EUR")
+        .map_or(0, |c| c.transaction_count);
+    assert_eq!(count_after, count_before);
+
+    let rates = list_exchange_rates_impl(db, Some("EUR")).await.unwrap();
+    assert!(rates.iter().any(|r| r.date == "2013-06-01" && (r.rate - 1.30).abs() < 1e-9));
+}
+
+#[tokio::test]
+async fn test_foreign_spending_validation() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Foreign Spending Validation").await;
+    let ids = super::fixtures::insert_test_transactions(
+        db,
+        account_id,
+        vec![super::fixtures::TestTransaction::new("2013-04-01", -4000, "Souvenirs")],
+    )
+    .await;
+
+    assert!(set_exchange_rate_impl(db, "EURO", "2013-01-01", 1.1).await.is_err());
+    assert!(set_exchange_rate_impl(db, "GBP", "2013-01-01", 0.0).await.is_err());
+    assert!(set_exchange_rate_impl(db, "GBP", "2013-02-30", 1.2).await.is_err());
+
+    // Sign must match the posted amount, and an amount is required with a currency
+    assert!(set_transaction_currency_impl(db, ids[0], Some("GBP"), Some(3000), false).await.is_err());
+    assert!(set_transaction_currency_impl(db, ids[0], Some("GBP"), None, false).await.is_err());
+    assert!(set_transaction_currency_impl(db, -1, Some("GBP"), Some(-3000), false).await.is_err());
+
+    assert!(get_foreign_spending_report_impl(db, "2013-03-01", "2013-01-01", None).await.is_err());
+}
@@ -0,0 +1,119 @@
+use budget_balancer_lib::commands::category_commands::create_category_impl;
+use budget_balancer_lib::commands::debt_commands::create_debt_impl;
+use budget_balancer_lib::commands::search_commands::global_search_impl;
+use budget_balancer_lib::commands::transaction_commands::{delete_transaction_impl, update_transaction_impl};
+use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::models::debt::NewDebt;
+use budget_balancer_lib::models::search::SearchEntityType;
+use budget_balancer_lib::models::transaction::UpdateTransaction;
+use serial_test::serial;
+
+use super::fixtures::{insert_test_transactions, TestTransaction};
+
+/// A word no other test uses, so searches only see this test's rows
+fn unique_word(prefix: &str) -> String {
+    super::unique_name(prefix).replace(' ', "")
+}
+
+#[tokio::test]
+#[serial]
+async fn test_global_search_finds_transactions_categories_and_debts() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Search Test").await;
+    let word = unique_word("quokka");
+
+    let ids = insert_test_transactions(db, account_id, vec![
+        TestTransaction::new("2001-02-03", -1250, &format!("{} cafe", word)),
+        TestTransaction::new("2001-02-04", -800, "Corner store").with_merchant(&format!("{} Market", word)),
+    ])
+    .await;
+    let category_id = create_category_impl(db, NewCategory {
+        name: format!("{} Treats", word),
+        icon: None,
+        color: None,
+    })
+    .await
+    .unwrap();
+    let debt_id = create_debt_impl(db, NewDebt {
+        name: format!("{} Card", word),
+        balance_cents: 50_000,
+        interest_rate: 10.0,
+        min_payment_cents: 2500,
+    })
+    .await
+    .unwrap();
+
+    // A prefix of the word matches, in any case
+    let prefix = word[..word.len() - 2].to_uppercase();
+    let results = global_search_impl(db, prefix, None, None).await.unwrap();
+    assert_eq!(results.len(), 4);
+
+    let found = |entity_type: &str, id: i64| results.iter().find(|r| r.entity_type == entity_type && r.entity_id == id);
+    let cafe = found("transaction", ids[0]).expect("description match");
+    assert_eq!(cafe.date.as_deref(), Some("2001-02-03"));
+    assert_eq!(cafe.amount_cents, Some(-1250));
+    assert!(found("transaction", ids[1]).is_some(), "merchant match");
+    assert!(found("category", category_id).is_some());
+    assert!(found("debt", debt_id).unwrap().date.is_none());
+
+    // Title matches rank above the merchant-only match
+    assert_eq!(results.last().unwrap().entity_id, ids[1]);
+    assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+
+    let debts = global_search_impl(db, word.clone(), Some(SearchEntityType::Debt), None).await.unwrap();
+    assert_eq!(debts.len(), 1);
+    assert_eq!(debts[0].entity_id, debt_id);
+
+    let limited = global_search_impl(db, word.clone(), None, Some(2)).await.unwrap();
+    assert_eq!(limited.len(), 2);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_global_search_index_follows_edits_and_deletes() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Search Edit Test").await;
+    let old_word = unique_word("wombat");
+    let new_word = unique_word("numbat");
+
+    let ids = insert_test_transactions(db, account_id, vec![
+        TestTransaction::new("2001-03-01", -2000, &format!("{} supplies", old_word)),
+    ])
+    .await;
+
+    update_transaction_impl(db, UpdateTransaction {
+        id: ids[0],
+        account_id: None,
+        category_id: None,
+        date: None,
+        amount_cents: None,
+        description: Some("Hardware supplies".to_string()),
+        merchant: None,
+        notes: Some(format!("ask {} about the refund", new_word)),
+    }, false)
+    .await
+    .unwrap();
+
+    assert!(global_search_impl(db, old_word, None, None).await.unwrap().is_empty());
+    let results = global_search_impl(db, new_word.clone(), None, None).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].title, "Hardware supplies");
+    assert!(results[0].snippet.contains(&new_word), "snippet comes from the notes: {}", results[0].snippet);
+
+    delete_transaction_impl(db, ids[0], false).await.unwrap();
+    assert!(global_search_impl(db, new_word, None, None).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_global_search_treats_query_syntax_as_text() {
+    let db = super::get_test_db_pool().await;
+
+    // FTS5 operators and stray quotes must not cause a query error
+    for query in ["\"", "NOT", "a OR (b", "rent* -", "NEAR(x y)", ""] {
+        assert!(global_search_impl(db, query.to_string(), None, None).await.is_ok(), "query {:?}", query);
+    }
+
+    let too_long = "a".repeat(101);
+    let err = global_search_impl(db, too_long, None, None).await.unwrap_err();
+    assert!(err.to_user_message().contains("too long"));
+}
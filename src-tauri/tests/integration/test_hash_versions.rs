@@ -0,0 +1,122 @@
+use budget_balancer_lib::commands::csv_commands::{import_csv_impl, reset_rate_limiter, rehash_transactions_impl};
+use budget_balancer_lib::commands::job_commands::run_rehash_job;
+use budget_balancer_lib::models::job::JobKind;
+use budget_balancer_lib::models::transaction::NewTransaction;
+use budget_balancer_lib::services::csv_parser::ColumnMapping;
+use budget_balancer_lib::services::job_queue::{JobNotifier, JobQueue};
+use budget_balancer_lib::utils::money::Cents;
+use serial_test::serial;
+use sqlx::SqlitePool;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+fn mapping() -> ColumnMapping {
+    ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        ..Default::default()
+    }
+}
+
+/// Store a transaction the way it was stored before hashes were versioned
+async fn insert_version_one(db: &SqlitePool, account_id: i64, date: &str, amount_cents: Cents, description: &str) -> i64 {
+    sqlx::query(
+        "INSERT INTO transactions (account_id, category_id, date, amount_cents, description, hash, hash_version)
+         VALUES (?, 1, ?, ?, ?, ?, 1)"
+    )
+    .bind(account_id)
+    .bind(date)
+    .bind(amount_cents)
+    .bind(description)
+    .bind(NewTransaction::hash_for_version(1, account_id, date, amount_cents, description))
+    .execute(db)
+    .await
+    .expect("Failed to insert transaction")
+    .last_insert_rowid()
+}
+
+async fn stored_hash(db: &SqlitePool, transaction_id: i64) -> (String, i64) {
+    sqlx::query_as("SELECT hash, hash_version FROM transactions WHERE id = ?")
+        .bind(transaction_id)
+        .fetch_one(db)
+        .await
+        .unwrap()
+}
+
+#[test]
+fn test_current_hash_ignores_case_and_spacing() {
+    let hash = NewTransaction::calculate_hash(1, "2020-01-02", -450, "Corner  Cafe");
+    assert_eq!(hash, NewTransaction::calculate_hash(1, "2020-01-02", -450, " corner cafe "));
+    assert_ne!(hash, NewTransaction::calculate_hash(2, "2020-01-02", -450, "Corner Cafe"), "Hashes are per account");
+    assert_ne!(hash, NewTransaction::hash_for_version(1, 1, "2020-01-02", -450, "Corner  Cafe"));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_import_matches_unmigrated_hashes() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, &super::unique_name("Legacy Hash")).await;
+    let description = super::unique_name("Legacy purchase");
+    insert_version_one(db, account_id, "1979-02-03", -1200, &description).await;
+
+    reset_rate_limiter();
+    let csv = format!("Date,Amount,Description\n1979-02-03,-12.00,{}\n", description);
+    let result = import_csv_impl(db, account_id, csv, mapping()).await.expect("Import should succeed");
+    assert_eq!(result.duplicates, 1, "A row stored under version 1 is still found");
+    assert_eq!(result.imported, 0);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_rehash_job_migrates_hashes() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, &super::unique_name("Rehash")).await;
+    let other_account = super::fixtures::create_test_account(db, &super::unique_name("Rehash Other")).await;
+    let description = super::unique_name("Rehash purchase");
+    let first = insert_version_one(db, account_id, "1979-03-04", -825, &description).await;
+    // Differs only in case, so it hashes alike once migrated
+    let second = insert_version_one(db, account_id, "1979-03-04", -825, &description.to_uppercase()).await;
+
+    let job_id = JobQueue::enqueue(db, JobKind::Rehash).await.expect("Failed to enqueue job");
+    let notify: JobNotifier = Arc::new(|_| {});
+    let job = run_rehash_job(db.clone(), job_id, notify).await.expect("Job should run");
+    assert_eq!(job.kind, "rehash");
+    assert_eq!(job.status, "completed", "{:?}", job.error);
+    let result = job.result.expect("Finished job has a result").0;
+    assert_eq!(result["remaining"], 0);
+    assert_eq!(result["hash_version"], NewTransaction::HASH_VERSION);
+    assert!(result["rehashed"].as_u64().unwrap() >= 2);
+
+    let current = NewTransaction::calculate_hash(account_id, "1979-03-04", -825, &description);
+    assert_eq!(stored_hash(db, first).await, (current.clone(), NewTransaction::HASH_VERSION));
+    assert_eq!(stored_hash(db, second).await, (format!("{}:1", current), NewTransaction::HASH_VERSION));
+
+    // Migrated rows are still duplicates in their own account, but not in another
+    let csv = format!("Date,Amount,Description\n1979-03-04,-8.25,{}\n", description);
+    reset_rate_limiter();
+    let same = import_csv_impl(db, account_id, csv.clone(), mapping()).await.expect("Import should succeed");
+    assert_eq!(same.duplicates, 1);
+    reset_rate_limiter();
+    let other = import_csv_impl(db, other_account, csv, mapping()).await.expect("Import should succeed");
+    assert_eq!(other.imported, 1);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_rehash_stops_between_batches() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, &super::unique_name("Rehash Stop")).await;
+    let description = super::unique_name("Rehash stop");
+    insert_version_one(db, account_id, "1979-04-05", -100, &description).await;
+
+    let mut reports = 0;
+    let result = rehash_transactions_impl(db, |_| {
+        reports += 1;
+        ControlFlow::Break(())
+    })
+    .await
+    .expect("Rehash should succeed");
+    assert_eq!(reports, 1, "Stopping after the first batch skips the rest");
+    assert!(result.rehashed >= 1);
+}
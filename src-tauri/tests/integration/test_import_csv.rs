@@ -1,7 +1,13 @@
 use budget_balancer_lib::commands::account_commands::create_account_impl;
-use budget_balancer_lib::commands::csv_commands::{get_csv_headers, import_csv_impl, reset_rate_limiter};
+use budget_balancer_lib::commands::csv_commands::{
+    get_csv_headers, import_csv_impl, import_csv_with_progress_impl, list_import_batches_impl,
+    preview_csv_import_impl, read_csv_file_impl, reset_rate_limiter, undo_import_impl,
+};
+use budget_balancer_lib::commands::category_commands::create_category_rule_impl;
+use budget_balancer_lib::models::category_rule::NewCategoryRule;
+use budget_balancer_lib::errors::CsvImportError;
 use budget_balancer_lib::models::account::NewAccount;
-use budget_balancer_lib::services::csv_parser::ColumnMapping;
+use budget_balancer_lib::services::csv_parser::{ColumnMapping, CsvEncoding};
 use serial_test::serial;
 
 #[tokio::test]
@@ -39,6 +45,72 @@ async fn test_get_csv_headers_empty_file() {
     assert!(result.is_err(), "Should fail on empty CSV");
 }
 
+#[tokio::test]
+async fn test_read_latin1_semicolon_file() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Latin-1 Import Test").await;
+
+    // "Café Müller" in Windows-1252, semicolon-delimited
+    let mut bytes = b"Date;Amount;Description\n2024-03-01;-12.50;Caf".to_vec();
+    bytes.extend([0xE9, b' ', b'M', 0xFC]);
+    bytes.extend(b"ller\n");
+    let path = std::env::temp_dir().join(format!("latin1-import-{}.csv", std::process::id()));
+    std::fs::write(&path, &bytes).unwrap();
+    let path = path.to_str().unwrap().to_string();
+
+    let content = read_csv_file_impl(&path, None).expect("Failed to read file");
+    assert_eq!(get_csv_headers(content.clone()).await.unwrap(), vec!["Date", "Amount", "Description"]);
+
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        ..Default::default()
+    };
+    let preview = preview_csv_import_impl(db, account_id, &content, &mapping, None).await.unwrap();
+    assert_eq!(preview.rows[0].description, "Café Müller");
+    assert_eq!(preview.rows[0].amount_cents, -1250);
+
+    let result = read_csv_file_impl(&path, Some(CsvEncoding::Utf8));
+    assert!(matches!(result, Err(CsvImportError::InvalidEncoding(_))), "Latin-1 bytes aren't valid UTF-8");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_preview_formatted_amounts() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Formatted Amounts Test").await;
+
+    let csv_content = "Datum;Betrag;Beschreibung\n\
+                       2024-04-01;-1.234,56 €;Miete\n\
+                       2024-04-02;(45,00);Tanken\n\
+                       2024-04-03;EUR 12,50;Erstattung\n\
+                       2024-04-04;1.234;Bonus";
+    let mapping = ColumnMapping {
+        date: "Datum".to_string(),
+        amount: "Betrag".to_string(),
+        description: "Beschreibung".to_string(),
+        ..Default::default()
+    };
+    let preview = preview_csv_import_impl(db, account_id, csv_content, &mapping, None).await.unwrap();
+    let amounts: Vec<i64> = preview.rows.iter().map(|r| r.amount_cents).collect();
+    assert_eq!(amounts, vec![-123_456, -4500, 1250, 123], "A lone dot is read as a decimal point");
+
+    let comma_decimal = ColumnMapping {
+        decimal_separator: Some(','),
+        ..mapping.clone()
+    };
+    let preview = preview_csv_import_impl(db, account_id, csv_content, &comma_decimal, None).await.unwrap();
+    assert_eq!(preview.rows[3].amount_cents, 123_400, "With a decimal comma, dots group thousands");
+
+    let invalid = ColumnMapping {
+        decimal_separator: Some(';'),
+        ..mapping
+    };
+    let result = preview_csv_import_impl(db, account_id, csv_content, &invalid, None).await;
+    assert!(matches!(result, Err(CsvImportError::InvalidMapping(_))));
+}
+
 #[tokio::test]
 #[serial]
 async fn test_import_csv_basic() {
@@ -48,7 +120,7 @@ async fn test_import_csv_basic() {
     let account = NewAccount {
         name: super::unique_name("CSV Import Test"),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 0.0,
+        initial_balance_cents: 0,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -59,6 +131,7 @@ async fn test_import_csv_basic() {
         amount: "Amount".to_string(),
         description: "Description".to_string(),
         merchant: Some("Merchant".to_string()),
+        ..Default::default()
     };
 
     let result = import_csv_impl(db, account_id, csv_content.to_string(), mapping).await;
@@ -79,7 +152,7 @@ async fn test_import_csv_duplicate_detection() {
     let account = NewAccount {
         name: super::unique_name("Duplicate Test"),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 0.0,
+        initial_balance_cents: 0,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -90,6 +163,7 @@ async fn test_import_csv_duplicate_detection() {
         amount: "Amount".to_string(),
         description: "Description".to_string(),
         merchant: None,
+        ..Default::default()
     };
 
     // Import first time
@@ -115,7 +189,7 @@ async fn test_import_csv_invalid_date_format() {
     let account = NewAccount {
         name: super::unique_name("Invalid Date Test"),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 0.0,
+        initial_balance_cents: 0,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -126,6 +200,7 @@ async fn test_import_csv_invalid_date_format() {
         amount: "Amount".to_string(),
         description: "Description".to_string(),
         merchant: None,
+        ..Default::default()
     };
 
     let result = import_csv_impl(db, account_id, csv_content.to_string(), mapping).await;
@@ -141,7 +216,7 @@ async fn test_import_csv_missing_required_column() {
     let account = NewAccount {
         name: super::unique_name("Missing Column Test"),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 0.0,
+        initial_balance_cents: 0,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -152,6 +227,7 @@ async fn test_import_csv_missing_required_column() {
         amount: "Amount".to_string(),
         description: "NonExistent".to_string(), // Column doesn't exist
         merchant: None,
+        ..Default::default()
     };
 
     let result = import_csv_impl(db, account_id, csv_content.to_string(), mapping).await;
@@ -166,7 +242,7 @@ async fn test_import_csv_with_categorization() {
     let account = NewAccount {
         name: super::unique_name("Categorization Test"),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 0.0,
+        initial_balance_cents: 0,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -178,6 +254,7 @@ async fn test_import_csv_with_categorization() {
         amount: "Amount".to_string(),
         description: "Description".to_string(),
         merchant: Some("Merchant".to_string()),
+        ..Default::default()
     };
 
     let result = import_csv_impl(db, account_id, csv_content.to_string(), mapping).await;
@@ -201,7 +278,7 @@ async fn test_import_csv_transaction_amount_exceeds_max() {
     let account = NewAccount {
         name: super::unique_name("Max Amount Test"),
         account_type: budget_balancer_lib::models::account::AccountType::Checking,
-        initial_balance: 0.0,
+        initial_balance_cents: 0,
     };
     let account_id = create_account_impl(db, account).await.expect("Failed to create account");
 
@@ -213,6 +290,7 @@ async fn test_import_csv_transaction_amount_exceeds_max() {
         amount: "Amount".to_string(),
         description: "Description".to_string(),
         merchant: None,
+        ..Default::default()
     };
 
     let result = import_csv_impl(db, account_id, csv_content.to_string(), mapping).await;
@@ -225,3 +303,410 @@ async fn test_import_csv_transaction_amount_exceeds_max() {
         error_msg
     );
 }
+
+#[tokio::test]
+#[serial]
+async fn test_import_csv_in_batches_reports_progress() {
+    reset_rate_limiter();
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "CSV Batch Import Test").await;
+
+    // 1,100 unique rows span three batches; the repeated last row is a duplicate within the file
+    let run = super::unique_word("batch");
+    let mut csv_content = String::from("Date,Amount,Description\n");
+    for i in 0..1100 {
+        csv_content.push_str(&format!("2015-03-{:02},-{}.25,{} row {}\n", i % 28 + 1, i + 1, run, i));
+    }
+    csv_content.push_str(&format!("2015-03-01,-1.25,{} row 0\n", run));
+
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        merchant: None,
+        ..Default::default()
+    };
+
+    let mut updates = Vec::new();
+    let result = import_csv_with_progress_impl(db, account_id, csv_content, mapping, false, |progress| {
+        updates.push(progress.clone())
+    })
+    .await
+    .expect("Batch import should succeed");
+
+    assert_eq!(result.total, 1101);
+    assert_eq!(result.imported, 1100);
+    assert_eq!(result.duplicates, 1);
+    assert_eq!(result.errors, 0);
+
+    // One update per batch plus the final one
+    assert_eq!(updates.len(), 4);
+    assert_eq!(updates[0].processed, 500);
+    assert_eq!(updates[0].estimated_total, 1101);
+    assert!(!updates[0].done);
+    assert_eq!(updates[2].processed, 1101);
+    let last = updates.last().unwrap();
+    assert!(last.done);
+    assert_eq!(last.imported, 1100);
+    assert_eq!(last.duplicates, 1);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_undo_import_batch() {
+    reset_rate_limiter();
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "CSV Undo Test").await;
+
+    let run = super::unique_word("undo");
+    let csv_content = format!(
+        "Date,Amount,Description\n2015-04-01,-10.00,{run} one\n2015-04-02,-20.00,{run} two\n2015-04-03,-30.00,{run} three"
+    );
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        merchant: None,
+        ..Default::default()
+    };
+
+    let result = import_csv_impl(db, account_id, csv_content.clone(), mapping.clone())
+        .await
+        .expect("Import should succeed");
+    assert_eq!(result.imported, 3);
+
+    let batches = list_import_batches_impl(db, Some(account_id), None).await.unwrap();
+    assert_eq!(batches.len(), 1);
+    let batch = &batches[0];
+    assert_eq!(batch.id, result.import_batch_id);
+    assert_eq!(batch.status, "completed");
+    assert_eq!(batch.total_rows, 3);
+    assert_eq!(batch.imported_count, 3);
+    assert_eq!(batch.remaining_count, 3);
+
+    let undo = undo_import_impl(db, result.import_batch_id, false).await.expect("Undo should succeed");
+    assert_eq!(undo.deleted, 3);
+
+    let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM transactions WHERE account_id = ?")
+        .bind(account_id)
+        .fetch_one(db)
+        .await
+        .unwrap();
+    assert_eq!(count.0, 0, "All imported transactions should be removed");
+
+    let batch = &list_import_batches_impl(db, Some(account_id), None).await.unwrap()[0];
+    assert_eq!(batch.status, "undone");
+    assert!(batch.undone_at.is_some());
+    assert_eq!(batch.remaining_count, 0);
+
+    assert!(matches!(
+        undo_import_impl(db, result.import_batch_id, false).await,
+        Err(CsvImportError::ImportNotUndoable { .. })
+    ));
+    assert!(matches!(
+        undo_import_impl(db, i64::MAX, false).await,
+        Err(CsvImportError::ImportBatchNotFound(_))
+    ));
+
+    // The rows are no longer duplicates, so the corrected file can be imported again
+    reset_rate_limiter();
+    let reimport = import_csv_impl(db, account_id, csv_content, mapping).await.unwrap();
+    assert_eq!(reimport.imported, 3);
+}
+
+#[tokio::test]
+async fn test_preview_csv_import() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "CSV Preview Test").await;
+
+    let run = super::unique_word("preview");
+    let ids = super::fixtures::insert_test_transactions(
+        db,
+        account_id,
+        vec![super::fixtures::TestTransaction::new("2015-05-01", -1000, &format!("{run} existing"))],
+    )
+    .await;
+    let (existing,): (String,) = sqlx::query_as("SELECT description FROM transactions WHERE id = ?")
+        .bind(ids[0])
+        .fetch_one(db)
+        .await
+        .unwrap();
+
+    create_category_rule_impl(
+        db,
+        NewCategoryRule {
+            pattern: "BLUE BOTTLE".to_string(), // Rules match the merchant when there is one
+            category_id: 2,
+            priority: 100,
+            match_type: Default::default(),
+            min_amount_cents: None,
+            max_amount_cents: None,
+            account_id: Some(account_id),
+        },
+    )
+    .await
+    .expect("Failed to create rule");
+
+    let csv_content = format!(
+        "Date,Amount,Description,Merchant\n\
+         2015-05-01,-10.00,{existing},\n\
+         2015-05-02,-4.50,{run} coffee,SQ *BLUE BOTTLE #123\n\
+         not a date,-5.00,{run} bad date,\n\
+         2015-05-03,abc,{run} bad amount,\n\
+         2015-05-02,-4.50,{run} coffee,SQ *BLUE BOTTLE #123\n\
+         2015-05-04,-30.00,{run} books,"
+    );
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        merchant: Some("Merchant".to_string()),
+        ..Default::default()
+    };
+
+    let before: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM transactions WHERE account_id = ?")
+        .bind(account_id)
+        .fetch_one(db)
+        .await
+        .unwrap();
+
+    let preview = preview_csv_import_impl(db, account_id, &csv_content, &mapping, None)
+        .await
+        .expect("Preview should succeed");
+    assert_eq!(preview.total_rows, 6);
+    assert_eq!(preview.valid_count, 2);
+    assert_eq!(preview.duplicate_count, 2, "Existing and repeated rows are duplicates");
+    assert_eq!(preview.error_count, 2);
+
+    let coffee = &preview.rows[0];
+    assert_eq!(coffee.row_number, 2);
+    assert_eq!(coffee.category_id, 2, "Category should be inferred from rules");
+    assert_eq!(coffee.category_name.as_deref(), Some("Dining"));
+    assert!(coffee.normalized_merchant.is_some());
+    assert_eq!(preview.rows[1].category_id, 10, "Unmatched rows fall back to uncategorized");
+
+    let duplicate_rows: Vec<usize> = preview.duplicates.iter().map(|r| r.row_number).collect();
+    assert_eq!(duplicate_rows, vec![1, 5]);
+    let error_rows: Vec<usize> = preview.errors.iter().map(|e| e.row_number).collect();
+    assert_eq!(error_rows, vec![3, 4]);
+    assert!(preview.errors[1].message.contains("Invalid amount"));
+
+    // The limit caps the lists but not the counts
+    let limited = preview_csv_import_impl(db, account_id, &csv_content, &mapping, Some(1))
+        .await
+        .unwrap();
+    assert_eq!(limited.rows.len(), 1);
+    assert_eq!(limited.valid_count, 2);
+
+    let after: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM transactions WHERE account_id = ?")
+        .bind(account_id)
+        .fetch_one(db)
+        .await
+        .unwrap();
+    assert_eq!(after.0, before.0, "Preview must not write transactions");
+
+    let bad_mapping = ColumnMapping {
+        date: "Posted".to_string(),
+        ..mapping
+    };
+    let result = preview_csv_import_impl(db, account_id, &csv_content, &bad_mapping, None).await;
+    assert!(matches!(result, Err(CsvImportError::MissingColumn(col)) if col == "Posted"));
+}
+
+#[tokio::test]
+async fn test_preview_debit_credit_columns() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Debit Credit Test").await;
+
+    let run = super::unique_word("dualcol");
+    let csv_content = format!(
+        "Date,Description,Debit,Credit\n\
+         2015-06-01,{run} coffee,$4.50,\n\
+         2015-06-02,{run} payroll,,\"2,000.00\"\n\
+         2015-06-03,{run} refund,-12.00,\n\
+         2015-06-04,{run} blank,,"
+    );
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        description: "Description".to_string(),
+        debit: Some("Debit".to_string()),
+        credit: Some("Credit".to_string()),
+        ..Default::default()
+    };
+
+    let preview = preview_csv_import_impl(db, account_id, &csv_content, &mapping, None)
+        .await
+        .expect("Preview should succeed without an amount column");
+    let amounts: Vec<i64> = preview.rows.iter().map(|r| r.amount_cents).collect();
+    assert_eq!(amounts, vec![-450, 200_000, -1200], "Debits are negative whatever their sign in the file");
+    assert_eq!(preview.error_count, 1);
+    assert!(preview.errors[0].message.contains("Missing debit and credit"));
+
+    let missing = ColumnMapping {
+        credit: Some("Deposits".to_string()),
+        ..mapping
+    };
+    let result = preview_csv_import_impl(db, account_id, &csv_content, &missing, None).await;
+    assert!(matches!(result, Err(CsvImportError::MissingColumn(col)) if col == "Deposits"));
+}
+
+#[tokio::test]
+async fn test_preview_inverted_amounts() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Inverted Card Test").await;
+
+    // Credit card export where charges are positive and payments negative
+    let run = super::unique_word("inverted");
+    let csv_content = format!(
+        "Date,Amount,Description\n\
+         2015-07-01,25.00,{run} charge\n\
+         2015-07-02,-100.00,{run} payment"
+    );
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        invert_amounts: true,
+        ..Default::default()
+    };
+
+    let preview = preview_csv_import_impl(db, account_id, &csv_content, &mapping, None)
+        .await
+        .unwrap();
+    let amounts: Vec<i64> = preview.rows.iter().map(|r| r.amount_cents).collect();
+    assert_eq!(amounts, vec![-2500, 10_000]);
+}
+
+#[tokio::test]
+async fn test_preview_date_format() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Date Format Test").await;
+
+    let run = super::unique_word("datefmt");
+    let csv_content = format!(
+        "Date,Amount,Description\n\
+         03/04/15,-8.00,{run} bakery\n\
+         11/04/15,-9.00,{run} market"
+    );
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        ..Default::default()
+    };
+
+    // Nothing in the file rules out month-first, so that's how it's read by default
+    let detected = preview_csv_import_impl(db, account_id, &csv_content, &mapping, None)
+        .await
+        .unwrap();
+    let dates: Vec<&str> = detected.rows.iter().map(|r| r.date.as_str()).collect();
+    assert_eq!(dates, vec!["2015-03-04", "2015-11-04"]);
+
+    let european = ColumnMapping {
+        date_format: Some("DD/MM/YY".to_string()),
+        ..mapping.clone()
+    };
+    let preview = preview_csv_import_impl(db, account_id, &csv_content, &european, None)
+        .await
+        .unwrap();
+    let dates: Vec<&str> = preview.rows.iter().map(|r| r.date.as_str()).collect();
+    assert_eq!(dates, vec!["2015-04-03", "2015-04-11"]);
+
+    let invalid = ColumnMapping {
+        date_format: Some("YYYY".to_string()),
+        ..mapping
+    };
+    let result = preview_csv_import_impl(db, account_id, &csv_content, &invalid, None).await;
+    assert!(matches!(result, Err(CsvImportError::InvalidMapping(_))));
+}
+
+#[tokio::test]
+async fn test_preview_computed_fields() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Computed Fields Test").await;
+
+    // No description column at all, just a payee and a reference memo
+    let run = super::unique_word("computed");
+    let csv_content = format!(
+        "Date,Amount,Payee,Memo\n\
+         2015-08-01,-15.00,{run} hardware,REF 0042 paint\n\
+         2015-08-02,-6.25,,REF 0043 parking"
+    );
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "=coalesce([Payee], \"Unknown\") + \" - \" + substr([Memo], 9)".to_string(),
+        merchant: Some("=upper([Payee])".to_string()),
+        ..Default::default()
+    };
+
+    let preview = preview_csv_import_impl(db, account_id, &csv_content, &mapping, None)
+        .await
+        .expect("Preview should succeed");
+    assert_eq!(preview.rows[0].description, format!("{run} hardware - paint"));
+    assert_eq!(preview.rows[0].merchant.as_deref(), Some(format!("{run} hardware").to_uppercase().as_str()));
+    assert_eq!(preview.rows[1].description, "Unknown - parking");
+
+    let missing = ColumnMapping {
+        description: "=[Notes]".to_string(),
+        ..mapping.clone()
+    };
+    let result = preview_csv_import_impl(db, account_id, &csv_content, &missing, None).await;
+    assert!(matches!(result, Err(CsvImportError::MissingColumn(col)) if col == "Notes"));
+
+    let invalid = ColumnMapping {
+        description: "=[Payee] +".to_string(),
+        ..mapping
+    };
+    let result = preview_csv_import_impl(db, account_id, &csv_content, &invalid, None).await;
+    assert!(matches!(result, Err(CsvImportError::InvalidMapping(_))));
+}
+
+#[tokio::test]
+async fn test_preview_warns_for_account_type() {
+    use budget_balancer_lib::services::account_validator::ImportWarningKind;
+
+    let db = super::get_test_db_pool().await;
+    let account_id = create_account_impl(
+        db,
+        NewAccount {
+            name: super::unique_name("Validation Card"),
+            account_type: budget_balancer_lib::models::account::AccountType::CreditCard,
+            initial_balance_cents: -48_000,
+        },
+    )
+    .await
+    .expect("Failed to create account");
+
+    // A card export with charges as positive amounts
+    let run = super::unique_word("cardsign");
+    let csv_content = format!(
+        "Date,Amount,Description\n\
+         2015-09-01,12.00,{run} a\n\
+         2015-09-02,40.00,{run} b\n\
+         2015-09-03,8.50,{run} c\n\
+         2015-09-04,99.00,{run} d\n\
+         2015-09-05,-300.00,{run} payment"
+    );
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        ..Default::default()
+    };
+
+    let preview = preview_csv_import_impl(db, account_id, &csv_content, &mapping, None)
+        .await
+        .unwrap();
+    let kinds: Vec<ImportWarningKind> = preview.warnings.iter().map(|w| w.kind).collect();
+    assert_eq!(kinds, vec![ImportWarningKind::MostlyPositive]);
+
+    let inverted = ColumnMapping {
+        invert_amounts: true,
+        ..mapping
+    };
+    let preview = preview_csv_import_impl(db, account_id, &csv_content, &inverted, None)
+        .await
+        .unwrap();
+    assert!(preview.warnings.is_empty(), "Flipped signs look like a normal card export");
+}
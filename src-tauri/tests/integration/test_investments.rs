@@ -0,0 +1,107 @@
+use budget_balancer_lib::commands::account_commands::create_account_impl;
+use budget_balancer_lib::commands::analytics_commands::get_net_worth_history_impl;
+use budget_balancer_lib::commands::investment_commands::{
+    delete_holding_impl, import_security_prices_csv_impl, list_holding_history_impl, list_holdings_impl,
+    list_security_prices_impl, record_holding_impl, set_security_price_impl,
+};
+use budget_balancer_lib::models::account::{AccountType, NewAccount};
+use budget_balancer_lib::models::investment::NewHolding;
+use budget_balancer_lib::open_database;
+use budget_balancer_lib::utils::access_guard::AccessGuard;
+use budget_balancer_lib::utils::money::Cents;
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+
+// Symbols are shared across accounts, so each test values its holdings in a database of its own
+async fn fresh_db(name: &str) -> SqlitePool {
+    let dir = std::env::temp_dir().join(super::unique_name(name).replace(' ', "_"));
+    open_database(&dir.join("budget.db"), &AccessGuard::default()).await.unwrap()
+}
+
+async fn account(db: &SqlitePool, name: &str, account_type: AccountType, initial_balance_cents: Cents) -> i64 {
+    create_account_impl(db, NewAccount { name: name.to_string(), account_type, initial_balance_cents })
+        .await
+        .expect("Failed to create account")
+}
+
+fn holding(account_id: i64, symbol: &str, quantity: f64, cost_basis_cents: Option<Cents>, date: &str) -> NewHolding {
+    NewHolding {
+        account_id,
+        symbol: symbol.to_string(),
+        quantity,
+        cost_basis_cents,
+        date: Some(date.to_string()),
+    }
+}
+
+#[tokio::test]
+async fn test_holdings_and_prices() {
+    let db = &fresh_db("holdings").await;
+    let brokerage = account(db, "Brokerage", AccountType::Savings, 0).await;
+
+    let bought = record_holding_impl(db, holding(brokerage, " vti", 10.0, Some(200_000), "2024-01-15")).await.unwrap();
+    assert_eq!(bought.symbol, "VTI");
+    record_holding_impl(db, holding(brokerage, "VTI", 15.0, Some(310_000), "2024-03-01")).await.unwrap();
+    set_security_price_impl(db, "vti", 210.0, Some("2024-01-31")).await.unwrap();
+
+    let csv = "Date,Symbol,Close\n2024-02-29,vti,$220.00\n03/29/2024,VTI,230\n2024-04-01,,5\n2024-04-02,VTI,abc\n";
+    let imported = import_security_prices_csv_impl(db, csv).await.unwrap();
+    assert_eq!(imported.imported, 2);
+    assert_eq!(imported.symbols, vec!["VTI".to_string()]);
+    let skipped: Vec<usize> = imported.errors.iter().map(|error| error.line).collect();
+    assert_eq!(skipped, vec![4, 5], "Lines without a symbol or price are skipped");
+
+    let prices = list_security_prices_impl(db, Some("VTI")).await.unwrap();
+    assert_eq!(prices.len(), 3);
+    assert_eq!((prices[0].date.as_str(), prices[0].source.as_str()), ("2024-03-29", "csv"));
+    assert_eq!(prices[2].source, "manual");
+
+    assert!(list_holdings_impl(db, None, Some("2024-01-10")).await.unwrap().is_empty(), "Nothing held yet");
+    let february = list_holdings_impl(db, Some(brokerage), Some("2024-02-15")).await.unwrap();
+    assert_eq!(february.len(), 1);
+    assert_eq!(february[0].quantity, 10.0);
+    assert_eq!(february[0].price, Some(210.0));
+    assert_eq!((february[0].value_cents, february[0].gain_cents), (Some(210_000), Some(10_000)));
+    let march = list_holdings_impl(db, Some(brokerage), Some("2024-03-31")).await.unwrap();
+    assert_eq!(march[0].held_since, "2024-03-01");
+    assert_eq!((march[0].value_cents, march[0].gain_cents), (Some(345_000), Some(35_000)));
+
+    let sold = record_holding_impl(db, holding(brokerage, "VTI", 0.0, None, "2024-04-15")).await.unwrap();
+    assert!(list_holdings_impl(db, None, Some("2024-04-30")).await.unwrap().is_empty(), "Sold positions aren't held");
+    assert_eq!(list_holding_history_impl(db, brokerage).await.unwrap().len(), 3);
+    delete_holding_impl(db, sold.id).await.unwrap();
+    let restored = list_holdings_impl(db, None, Some("2024-04-30")).await.unwrap();
+    assert_eq!(restored[0].quantity, 15.0, "Deleting a record puts the one before it back in effect");
+
+    assert!(record_holding_impl(db, holding(brokerage, "VTI", -1.0, None, "2024-05-01")).await.is_err());
+    assert!(record_holding_impl(db, holding(brokerage + 1000, "VTI", 1.0, None, "2024-05-01")).await.is_err());
+    assert!(set_security_price_impl(db, "NOT A TICKER", 1.0, None).await.is_err());
+    assert!(delete_holding_impl(db, sold.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_net_worth_history_includes_investments() {
+    let db = &fresh_db("net-worth").await;
+    let checking = account(db, "Checking", AccountType::Checking, 100_000).await;
+    let brokerage = account(db, "Brokerage", AccountType::Savings, 0).await;
+    super::fixtures::insert_test_transactions(db, checking, vec![
+        super::fixtures::TestTransaction::new("2024-03-10", -20_000, "Rent"),
+    ])
+    .await;
+    record_holding_impl(db, holding(brokerage, "VTI", 10.0, None, "2024-01-15")).await.unwrap();
+    set_security_price_impl(db, "VTI", 100.0, Some("2024-01-01")).await.unwrap();
+    set_security_price_impl(db, "VTI", 120.0, Some("2024-03-01")).await.unwrap();
+
+    let today = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+    let history = get_net_worth_history_impl(db, Some(3), today).await.unwrap();
+    let dates: Vec<&str> = history.iter().map(|point| point.date.as_str()).collect();
+    assert_eq!(dates, vec!["2024-01-31", "2024-02-29", "2024-03-20"], "Month ends, then today");
+
+    let accounts: Vec<i64> = history.iter().map(|point| point.accounts_total_cents).collect();
+    assert_eq!(accounts, vec![120_000, 120_000, 100_000], "Balances before later transactions");
+    let investments: Vec<i64> = history.iter().map(|point| point.investments_total_cents).collect();
+    assert_eq!(investments, vec![100_000, 100_000, 120_000]);
+    assert!(history.iter().all(|point| point.net_worth_cents == point.accounts_total_cents + point.investments_total_cents));
+
+    assert!(get_net_worth_history_impl(db, Some(0), today).await.is_err());
+}
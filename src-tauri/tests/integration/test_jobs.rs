@@ -0,0 +1,241 @@
+use budget_balancer_lib::commands::csv_commands::{reset_rate_limiter, BatchImportFile};
+use budget_balancer_lib::commands::job_commands::{
+    cancel_job_impl, get_job_status_impl, list_jobs_impl, run_batch_import_job, run_import_job,
+};
+use budget_balancer_lib::errors::JobError;
+use budget_balancer_lib::models::job::{Job, JobKind};
+use budget_balancer_lib::services::csv_parser::ColumnMapping;
+use budget_balancer_lib::services::job_queue::{JobNotifier, JobQueue};
+use serial_test::serial;
+use std::sync::{Arc, Mutex};
+
+/// A notifier that records the status of every job update it sees
+fn collecting_notifier() -> (JobNotifier, Arc<Mutex<Vec<String>>>) {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    let notify: JobNotifier = Arc::new(move |job: &Job| sink.lock().unwrap().push(job.status.clone()));
+    (notify, seen)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_job_runs_to_completion() {
+    let db = super::get_test_db_pool().await;
+    let job_id = JobQueue::enqueue(db, JobKind::ReportExport).await.expect("Failed to enqueue job");
+    assert_eq!(get_job_status_impl(db, job_id).await.unwrap().status, "queued");
+
+    let (notify, seen) = collecting_notifier();
+    let job = JobQueue::run(db.clone(), job_id, notify, |ctx| async move {
+        ctx.progress(1, Some(2), Some("Halfway")).await?;
+        Ok(serde_json::json!({ "rows": 2 }))
+    })
+    .await
+    .expect("Job should run");
+
+    assert_eq!(job.status, "completed");
+    assert_eq!(job.processed, 1);
+    assert_eq!(job.total, Some(2));
+    assert_eq!(job.message.as_deref(), Some("Halfway"));
+    assert_eq!(job.result.as_ref().map(|r| r.0.clone()), Some(serde_json::json!({ "rows": 2 })));
+    assert!(job.started_at.is_some() && job.finished_at.is_some());
+    assert_eq!(*seen.lock().unwrap(), vec!["running", "running", "completed"]);
+
+    // Queryable after the fact
+    let fetched = get_job_status_impl(db, job_id).await.unwrap();
+    assert_eq!(fetched.status, "completed");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_failed_job_records_error() {
+    let db = super::get_test_db_pool().await;
+    let job_id = JobQueue::enqueue(db, JobKind::ReportExport).await.unwrap();
+
+    let (notify, _) = collecting_notifier();
+    let job = JobQueue::run(db.clone(), job_id, notify, |_| async { Err::<(), _>("Disk full".to_string()) })
+        .await
+        .unwrap();
+
+    assert_eq!(job.status, "failed");
+    assert_eq!(job.error.as_deref(), Some("Disk full"));
+    assert!(job.result.is_none());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cancel_queued_job() {
+    let db = super::get_test_db_pool().await;
+    let job_id = JobQueue::enqueue(db, JobKind::CsvImport).await.unwrap();
+
+    let cancelled = cancel_job_impl(db, job_id).await.expect("Failed to cancel job");
+    assert_eq!(cancelled.status, "cancelled");
+    assert!(cancelled.cancel_requested);
+
+    // A job cancelled before it started never runs
+    let (notify, seen) = collecting_notifier();
+    let job = JobQueue::run(db.clone(), job_id, notify, |_| async { panic!("Cancelled job should not run") as Result<(), String> })
+        .await
+        .unwrap();
+    assert_eq!(job.status, "cancelled");
+    assert!(seen.lock().unwrap().is_empty());
+
+    let result = cancel_job_impl(db, job_id).await;
+    assert!(matches!(result, Err(JobError::AlreadyFinished { .. })), "Got {:?}", result);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cancel_running_job() {
+    let db = super::get_test_db_pool().await;
+    let job_id = JobQueue::enqueue(db, JobKind::CsvImport).await.unwrap();
+
+    let (notify, _) = collecting_notifier();
+    let job = JobQueue::run(db.clone(), job_id, notify, |ctx| async move {
+        // Cancelled from elsewhere while running; noticed at the next progress report
+        let running = cancel_job_impl(&ctx.db, ctx.job_id).await.map_err(|e| e.to_string())?;
+        assert_eq!(running.status, "running");
+        ctx.progress(5, Some(10), None).await?;
+        Ok("unreachable")
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(job.status, "cancelled");
+    assert_eq!(job.processed, 5);
+    assert!(job.error.is_none());
+    assert!(job.result.is_none());
+}
+
+#[tokio::test]
+async fn test_job_not_found() {
+    let db = super::get_test_db_pool().await;
+    assert!(matches!(get_job_status_impl(db, -1).await, Err(JobError::NotFound(-1))));
+    assert!(matches!(cancel_job_impl(db, -1).await, Err(JobError::NotFound(-1))));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_list_jobs_by_status() {
+    let db = super::get_test_db_pool().await;
+    let job_id = JobQueue::enqueue(db, JobKind::ReportExport).await.unwrap();
+    cancel_job_impl(db, job_id).await.unwrap();
+
+    let cancelled = list_jobs_impl(db, Some("cancelled"), Some(500)).await.expect("Failed to list jobs");
+    assert!(cancelled.iter().any(|job| job.id == job_id));
+    assert!(cancelled.iter().all(|job| job.status == "cancelled"));
+
+    let completed = list_jobs_impl(db, Some("completed"), None).await.unwrap();
+    assert!(completed.iter().all(|job| job.id != job_id));
+
+    assert!(list_jobs_impl(db, Some("paused"), None).await.is_err());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_interrupted_jobs_fail_at_startup() {
+    let db = super::get_test_db_pool().await;
+    let queued = JobQueue::enqueue(db, JobKind::CsvImport).await.unwrap();
+    let running = JobQueue::enqueue(db, JobKind::CsvImport).await.unwrap();
+    sqlx::query("UPDATE jobs SET status = 'running' WHERE id = ?")
+        .bind(running)
+        .execute(db)
+        .await
+        .unwrap();
+
+    let recovered = JobQueue::fail_interrupted(db).await.expect("Failed to recover jobs");
+    assert!(recovered >= 2);
+
+    for job_id in [queued, running] {
+        let job = get_job_status_impl(db, job_id).await.unwrap();
+        assert_eq!(job.status, "failed");
+        assert_eq!(job.error.as_deref(), Some("Interrupted when the app closed"));
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_import_job() {
+    reset_rate_limiter();
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Import Job").await;
+    let job_id = JobQueue::enqueue(db, JobKind::CsvImport).await.unwrap();
+
+    // Unique descriptions so earlier runs against the shared database aren't duplicates
+    let csv_content = format!(
+        "Date,Amount,Description\n1986-03-01,-12.50,{}\n1986-03-02,-40.00,{}\n",
+        super::unique_name("Job Import Coffee"),
+        super::unique_name("Job Import Fuel")
+    );
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        ..Default::default()
+    };
+
+    let (notify, seen) = collecting_notifier();
+    let job = run_import_job(db.clone(), job_id, notify, account_id, csv_content, mapping, false)
+        .await
+        .expect("Import job should run");
+
+    assert_eq!(job.status, "completed", "Job error: {:?}", job.error);
+    let result = job.result.expect("Completed import should have a result").0;
+    assert_eq!(result["imported"], 2);
+    assert_eq!(result["success"], true);
+    assert_eq!(seen.lock().unwrap().last().map(String::as_str), Some("completed"));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_batch_import_job() {
+    reset_rate_limiter();
+    let db = super::get_test_db_pool().await;
+    let checking_id = super::fixtures::create_test_account(db, "Batch Import Checking").await;
+    let card_id = super::fixtures::create_test_account(db, "Batch Import Card").await;
+    let job_id = JobQueue::enqueue(db, JobKind::CsvBatchImport).await.unwrap();
+
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        ..Default::default()
+    };
+    let file = |account_id, csv_content: String| BatchImportFile {
+        path: None,
+        csv_content: Some(csv_content),
+        encoding: None,
+        account_id,
+        mapping: mapping.clone(),
+    };
+    let files = vec![
+        file(
+            checking_id,
+            format!("Date,Amount,Description\n1958-02-01,-9.00,{}\n", super::unique_name("Batch Bakery")),
+        ),
+        // Missing the Amount column, so this file fails without stopping the others
+        file(card_id, "Date,Total,Description\n1958-02-02,-5.00,Parking\n".to_string()),
+        file(
+            card_id,
+            format!(
+                "Date;Amount;Description\n1958-02-03;-20,00;{}\n1958-02-04;-7,50;{}\n",
+                super::unique_name("Batch Fuel"),
+                super::unique_name("Batch Cinema")
+            ),
+        ),
+    ];
+
+    let (notify, _) = collecting_notifier();
+    let job = run_batch_import_job(db.clone(), job_id, notify, files, false)
+        .await
+        .expect("Batch import job should run");
+
+    assert_eq!(job.status, "completed", "Job error: {:?}", job.error);
+    assert_eq!(job.kind, "csv_batch_import");
+    let result = job.result.expect("Completed batch should have a result").0;
+    assert_eq!(result["imported"], 3);
+    assert_eq!(result["failed_files"], 1);
+    assert_eq!(result["files"][0]["result"]["imported"], 1);
+    assert!(result["files"][1]["error"].is_string());
+    assert_eq!(result["files"][2]["account_id"], card_id);
+    assert_eq!(result["files"][2]["result"]["imported"], 2);
+}
@@ -0,0 +1,58 @@
+use budget_balancer_lib::commands::analytics_commands::get_largest_transactions_impl;
+
+#[tokio::test]
+async fn test_get_largest_transactions() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Largest Tx Test").await;
+
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("2019-03-10", -900_000, "New Roof").with_category(7),
+        super::fixtures::TestTransaction::new("2019-03-11", -1250, "Coffee").with_category(2),
+        super::fixtures::TestTransaction::new("2019-03-15", 800_000, "Bonus").with_category(9),
+    ];
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let result = get_largest_transactions_impl(db, "2019", Some(5), None).await;
+    assert!(result.is_ok(), "Failed to get largest transactions: {:?}", result);
+
+    let response = result.unwrap();
+    assert_eq!(response.period.start_date, "2019-01-01");
+    assert_eq!(response.period.end_date, "2019-12-31");
+    assert!(!response.expenses.is_empty(), "Should have expenses");
+    assert!(!response.income.is_empty(), "Should have income");
+    assert!(response.expenses.len() <= 5, "Should respect limit");
+
+    // Biggest expense comes first and carries category/account info
+    let top_expense = &response.expenses[0];
+    assert!(top_expense.amount_cents <= -900_000, "Largest expense should be first");
+    assert!(!top_expense.category_name.is_empty());
+    assert!(!top_expense.account_name.is_empty());
+    for pair in response.expenses.windows(2) {
+        assert!(pair[0].amount_cents <= pair[1].amount_cents, "Expenses should be ordered by size");
+    }
+
+    assert!(response.income[0].amount_cents >= 800_000, "Largest income should be first");
+    assert!(response.income.iter().all(|t| t.amount_cents > 0));
+}
+
+#[tokio::test]
+async fn test_get_largest_transactions_expense_only() {
+    let db = super::get_test_db_pool().await;
+    let result = get_largest_transactions_impl(db, "current_month", None, Some("expense")).await;
+    assert!(result.is_ok(), "Should succeed: {:?}", result);
+
+    let response = result.unwrap();
+    assert!(response.income.is_empty(), "Income should be omitted");
+    assert!(response.expenses.iter().all(|t| t.amount_cents < 0));
+}
+
+#[tokio::test]
+async fn test_get_largest_transactions_invalid_input() {
+    let db = super::get_test_db_pool().await;
+
+    let result = get_largest_transactions_impl(db, "next_decade", None, None).await;
+    assert!(result.is_err(), "Invalid period should fail");
+
+    let result = get_largest_transactions_impl(db, "current_month", None, Some("sideways")).await;
+    assert!(result.is_err(), "Invalid direction should fail");
+}
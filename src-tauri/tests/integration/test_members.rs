@@ -0,0 +1,126 @@
+use budget_balancer_lib::commands::analytics_commands::{
+    get_spending_by_category_impl, get_spending_by_member_impl,
+};
+use budget_balancer_lib::commands::csv_commands::{import_csv_impl, reset_rate_limiter};
+use budget_balancer_lib::commands::member_commands::{
+    create_member_impl, delete_member_impl, list_members_impl,
+};
+use budget_balancer_lib::commands::transaction_commands::{
+    bulk_assign_member_impl, list_transactions_impl, TransactionFilter,
+};
+use budget_balancer_lib::errors::TransactionError;
+use budget_balancer_lib::models::member::NewMember;
+use budget_balancer_lib::services::csv_parser::ColumnMapping;
+use serial_test::serial;
+
+fn new_member(name: &str) -> NewMember {
+    NewMember {
+        name: name.to_string(),
+        color: None,
+    }
+}
+
+fn filter(account_id: i64, member_id: Option<i64>) -> TransactionFilter {
+    TransactionFilter {
+        account_id: Some(vec![account_id]),
+        category_id: None,
+        start_date: None,
+        end_date: None,
+        search: None,
+        min_amount_cents: None,
+        max_amount_cents: None,
+        only_uncategorized: None,
+        exclude_category_ids: None,
+        pending: None,
+        member_id: member_id.map(|id| vec![id]),
+        limit: None,
+        offset: None,
+    }
+}
+
+#[tokio::test]
+async fn test_create_and_delete_members() {
+    let db = super::get_test_db_pool().await;
+    let name = super::unique_name("Jordan");
+
+    let member = create_member_impl(db, new_member(&format!("  {} ", name))).await.unwrap();
+    assert_eq!(member.name, name);
+    assert!(create_member_impl(db, new_member(&name.to_uppercase())).await.is_err(), "Names are unique ignoring case");
+    assert!(create_member_impl(db, new_member(" ")).await.is_err());
+    let bad_color = NewMember {
+        name: super::unique_name("Casey"),
+        color: Some("red".to_string()),
+    };
+    assert!(create_member_impl(db, bad_color).await.is_err());
+    assert!(list_members_impl(db).await.unwrap().iter().any(|m| m.id == member.id));
+
+    delete_member_impl(db, member.id).await.unwrap();
+    assert!(!list_members_impl(db).await.unwrap().iter().any(|m| m.id == member.id));
+    assert!(delete_member_impl(db, member.id).await.is_err());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_import_assign_and_spending_by_member() {
+    reset_rate_limiter();
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Shared Card").await;
+    // One word, so the cardholder's full name in the file matches it by first name
+    let alex = create_member_impl(db, new_member(&super::unique_name("Alex").replace(' ', ""))).await.unwrap();
+    let sam = create_member_impl(db, new_member(&super::unique_name("Sam"))).await.unwrap();
+
+    let csv = format!(
+        "Date,Amount,Description,Card Member\n\
+         2013-02-03,-40.00,Groceries,{} MORGAN\n\
+         2013-02-04,-10.00,Coffee,{}\n\
+         2013-02-05,-50.00,Hardware,Someone Else",
+        alex.name.to_uppercase(),
+        sam.name
+    );
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        member: Some("Card Member".to_string()),
+        ..Default::default()
+    };
+    let result = import_csv_impl(db, account_id, csv, mapping).await.unwrap();
+    assert_eq!(result.imported, 3);
+
+    let all = list_transactions_impl(db, Some(filter(account_id, None))).await.unwrap();
+    let by_description = |d: &str| all.iter().find(|t| t.description == d).unwrap();
+    assert_eq!(by_description("Groceries").member_id, Some(alex.id));
+    assert_eq!(by_description("Coffee").member_id, Some(sam.id));
+    assert_eq!(by_description("Hardware").member_id, None, "Unknown names are left unassigned");
+
+    let hardware = by_description("Hardware").id;
+    let result = bulk_assign_member_impl(db, vec![hardware, i64::MAX], Some(sam.id), false).await.unwrap();
+    assert_eq!(result.updated_count, 1);
+    assert_eq!(result.failed_ids, vec![i64::MAX]);
+    assert!(matches!(
+        bulk_assign_member_impl(db, vec![hardware], Some(i64::MAX), false).await,
+        Err(TransactionError::MemberNotFound(_))
+    ));
+
+    let sams = list_transactions_impl(db, Some(filter(account_id, Some(sam.id)))).await.unwrap();
+    assert_eq!(sams.len(), 2);
+
+    let spending = get_spending_by_member_impl(db, "2013-02-01", "2013-02-28", Some(account_id)).await.unwrap();
+    assert_eq!(spending.total_spending_cents, 10_000);
+    assert_eq!(spending.members[0].member_id, Some(sam.id));
+    assert_eq!(spending.members[0].amount_cents, 6000);
+    assert!((spending.members[0].percentage - 60.0).abs() < 0.01);
+    assert_eq!(spending.members[1].member_id, Some(alex.id));
+
+    let categories = get_spending_by_category_impl(db, "2013-02-01", "2013-02-28", Some(account_id), Some(alex.id))
+        .await
+        .unwrap();
+    assert_eq!(categories.total_spending_cents, 4000);
+
+    // Deleting a member keeps their transactions, unassigned
+    delete_member_impl(db, alex.id).await.unwrap();
+    let spending = get_spending_by_member_impl(db, "2013-02-01", "2013-02-28", Some(account_id)).await.unwrap();
+    let unassigned = spending.members.iter().find(|m| m.member_id.is_none()).unwrap();
+    assert_eq!(unassigned.member_name, "Unassigned");
+    assert_eq!(unassigned.amount_cents, 4000);
+}
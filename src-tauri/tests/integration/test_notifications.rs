@@ -0,0 +1,156 @@
+use budget_balancer_lib::commands::notification_commands::{
+    create_notification_impl, dismiss_notification_impl, list_notifications_impl,
+    mark_notifications_read_impl, prune_notifications_impl,
+};
+use budget_balancer_lib::errors::NotificationError;
+use budget_balancer_lib::models::notification::{NewNotification, NotificationFilter, NotificationSeverity};
+
+fn notification(notification_type: &str, severity: NotificationSeverity) -> NewNotification {
+    NewNotification {
+        notification_type: notification_type.to_string(),
+        severity,
+        title: "Budget exceeded".to_string(),
+        message: "Dining is over its monthly target".to_string(),
+        entity_type: None,
+        entity_id: None,
+    }
+}
+
+fn filter_type(notification_type: &str) -> NotificationFilter {
+    NotificationFilter {
+        notification_type: Some(notification_type.to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_create_and_filter_notifications() {
+    let db = super::get_test_db_pool().await;
+    let notification_type = super::unique_name("budget_alert");
+
+    let mut linked = notification(&notification_type, NotificationSeverity::Warning);
+    linked.entity_type = Some("category".to_string());
+    linked.entity_id = Some(2);
+    let warning = create_notification_impl(db, linked).await.expect("Failed to create notification");
+    assert_eq!(warning.severity, "warning");
+    assert!(warning.read_at.is_none());
+
+    let critical = create_notification_impl(db, notification(&notification_type, NotificationSeverity::Critical))
+        .await
+        .unwrap();
+
+    let all = list_notifications_impl(db, Some(filter_type(&notification_type))).await.unwrap();
+    assert_eq!(all.notifications.len(), 2);
+    assert!(all.unread_count >= 2);
+
+    let mut by_severity = filter_type(&notification_type);
+    by_severity.severity = Some(NotificationSeverity::Critical);
+    let result = list_notifications_impl(db, Some(by_severity)).await.unwrap();
+    assert_eq!(result.notifications.len(), 1);
+    assert_eq!(result.notifications[0].id, critical.id);
+
+    let mut by_entity = filter_type(&notification_type);
+    by_entity.entity_type = Some("category".to_string());
+    by_entity.entity_id = Some(2);
+    let result = list_notifications_impl(db, Some(by_entity)).await.unwrap();
+    assert_eq!(result.notifications.len(), 1);
+    assert_eq!(result.notifications[0].id, warning.id);
+}
+
+#[tokio::test]
+async fn test_mark_read_and_dismiss() {
+    let db = super::get_test_db_pool().await;
+    let notification_type = super::unique_name("import_done");
+
+    let first = create_notification_impl(db, notification(&notification_type, NotificationSeverity::Info)).await.unwrap();
+    let second = create_notification_impl(db, notification(&notification_type, NotificationSeverity::Info)).await.unwrap();
+
+    let marked = mark_notifications_read_impl(db, vec![first.id]).await.unwrap();
+    assert_eq!(marked, 1);
+    let again = mark_notifications_read_impl(db, vec![first.id]).await.unwrap();
+    assert_eq!(again, 0, "Already-read notifications are left alone");
+
+    let mut unread = filter_type(&notification_type);
+    unread.unread_only = true;
+    let result = list_notifications_impl(db, Some(unread)).await.unwrap();
+    let ids: Vec<i64> = result.notifications.iter().map(|n| n.id).collect();
+    assert_eq!(ids, vec![second.id]);
+
+    dismiss_notification_impl(db, second.id).await.expect("Failed to dismiss");
+    let visible = list_notifications_impl(db, Some(filter_type(&notification_type))).await.unwrap();
+    assert!(visible.notifications.iter().all(|n| n.id != second.id), "Dismissed are hidden by default");
+
+    let mut with_dismissed = filter_type(&notification_type);
+    with_dismissed.include_dismissed = true;
+    let result = list_notifications_impl(db, Some(with_dismissed)).await.unwrap();
+    let dismissed = result.notifications.iter().find(|n| n.id == second.id).unwrap();
+    assert!(dismissed.dismissed_at.is_some());
+    assert!(dismissed.read_at.is_some(), "Dismissing also marks read");
+
+    let result = mark_notifications_read_impl(db, vec![first.id, 999999]).await;
+    assert!(matches!(result, Err(NotificationError::NotFound(999999))));
+    let result = dismiss_notification_impl(db, 999999).await;
+    assert!(matches!(result, Err(NotificationError::NotFound(999999))));
+}
+
+#[tokio::test]
+async fn test_prune_removes_only_old_read_notifications() {
+    let db = super::get_test_db_pool().await;
+    let notification_type = super::unique_name("prune_check");
+
+    let old_read = create_notification_impl(db, notification(&notification_type, NotificationSeverity::Info)).await.unwrap();
+    let old_unread = create_notification_impl(db, notification(&notification_type, NotificationSeverity::Info)).await.unwrap();
+    let recent_read = create_notification_impl(db, notification(&notification_type, NotificationSeverity::Info)).await.unwrap();
+
+    sqlx::query("UPDATE notifications SET read_at = datetime('now', '-60 days'), created_at = datetime('now', '-61 days') WHERE id = ?")
+        .bind(old_read.id)
+        .execute(db)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE notifications SET created_at = datetime('now', '-61 days') WHERE id = ?")
+        .bind(old_unread.id)
+        .execute(db)
+        .await
+        .unwrap();
+    mark_notifications_read_impl(db, vec![recent_read.id]).await.unwrap();
+
+    let pruned = prune_notifications_impl(db, 30).await.expect("Failed to prune");
+    assert!(pruned >= 1);
+
+    let mut filter = filter_type(&notification_type);
+    filter.include_dismissed = true;
+    let remaining: Vec<i64> = list_notifications_impl(db, Some(filter))
+        .await
+        .unwrap()
+        .notifications
+        .iter()
+        .map(|n| n.id)
+        .collect();
+    assert!(!remaining.contains(&old_read.id));
+    assert!(remaining.contains(&old_unread.id), "Unread notifications are never pruned");
+    assert!(remaining.contains(&recent_read.id));
+}
+
+#[tokio::test]
+async fn test_create_notification_validation() {
+    let db = super::get_test_db_pool().await;
+
+    let mut missing_id = notification("validation", NotificationSeverity::Info);
+    missing_id.entity_type = Some("debt".to_string());
+    let result = create_notification_impl(db, missing_id).await;
+    assert!(matches!(result, Err(NotificationError::ValidationError(_))));
+
+    let mut bad_entity = notification("validation", NotificationSeverity::Info);
+    bad_entity.entity_type = Some("widget".to_string());
+    bad_entity.entity_id = Some(1);
+    let result = create_notification_impl(db, bad_entity).await;
+    assert!(matches!(result, Err(NotificationError::ValidationError(_))));
+
+    let mut empty_title = notification("validation", NotificationSeverity::Info);
+    empty_title.title = "  ".to_string();
+    let result = create_notification_impl(db, empty_title).await;
+    assert!(matches!(result, Err(NotificationError::ValidationError(_))));
+
+    let result = mark_notifications_read_impl(db, vec![]).await;
+    assert!(matches!(result, Err(NotificationError::ValidationError(_))));
+}
@@ -0,0 +1,64 @@
+use budget_balancer_lib::commands::onboarding_commands::{
+    complete_onboarding_step_impl, get_onboarding_status_impl,
+};
+use budget_balancer_lib::models::onboarding::OnboardingStep;
+
+#[tokio::test]
+async fn test_get_onboarding_status_lists_all_steps() {
+    let db = super::get_test_db_pool().await;
+
+    let status = get_onboarding_status_impl(db).await.expect("Failed to get onboarding status");
+    assert_eq!(status.total_steps, 4);
+    assert_eq!(status.steps.len(), 4);
+    assert_eq!(status.steps[0].step, OnboardingStep::AccountCreated);
+    assert_eq!(status.completed_count, status.steps.iter().filter(|s| s.completed).count());
+    assert_eq!(status.is_complete, status.completed_count == status.total_steps);
+}
+
+#[tokio::test]
+async fn test_account_step_detected_from_existing_data() {
+    let db = super::get_test_db_pool().await;
+    super::fixtures::create_test_account(db, "Onboarding Account Test").await;
+
+    let status = get_onboarding_status_impl(db).await.unwrap();
+    let account_step = status
+        .steps
+        .iter()
+        .find(|s| s.step == OnboardingStep::AccountCreated)
+        .unwrap();
+    assert!(account_step.completed, "Existing accounts should satisfy the account step");
+}
+
+#[tokio::test]
+async fn test_complete_onboarding_step_is_persisted_and_idempotent() {
+    let db = super::get_test_db_pool().await;
+
+    let status = complete_onboarding_step_impl(db, "first_debt")
+        .await
+        .expect("Failed to complete onboarding step");
+    let first = status
+        .steps
+        .iter()
+        .find(|s| s.step == OnboardingStep::FirstDebt)
+        .unwrap()
+        .clone();
+    assert!(first.completed);
+    assert!(first.completed_at.is_some());
+
+    // Completing again keeps the original timestamp
+    let status = complete_onboarding_step_impl(db, "first_debt").await.unwrap();
+    let again = status
+        .steps
+        .iter()
+        .find(|s| s.step == OnboardingStep::FirstDebt)
+        .unwrap();
+    assert_eq!(again.completed_at, first.completed_at);
+}
+
+#[tokio::test]
+async fn test_complete_onboarding_step_rejects_unknown_step() {
+    let db = super::get_test_db_pool().await;
+
+    let result = complete_onboarding_step_impl(db, "first_rocket_launch").await;
+    assert!(result.is_err(), "Unknown steps should be rejected");
+}
@@ -0,0 +1,86 @@
+use budget_balancer_lib::commands::analytics_commands::create_spending_target_impl;
+use budget_balancer_lib::commands::calendar_commands::export_payment_calendar_impl;
+use budget_balancer_lib::commands::category_commands::create_category_impl;
+use budget_balancer_lib::commands::debt_commands::create_debt_impl;
+use budget_balancer_lib::commands::scheduled_transaction_commands::create_scheduled_transaction_impl;
+use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::models::debt::NewDebt;
+use budget_balancer_lib::models::scheduled_transaction::{NewScheduledTransaction, Recurrence};
+use budget_balancer_lib::models::spending_target::NewSpendingTarget;
+use chrono::NaiveDate;
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn test_export_payment_calendar() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Calendar Checking").await;
+
+    let debt_id = create_debt_impl(db, NewDebt {
+        name: super::unique_name("Calendar Card"),
+        balance_cents: 25_000,
+        interest_rate: 19.99,
+        min_payment_cents: 10_000,
+    })
+    .await
+    .unwrap();
+
+    let paycheck = create_scheduled_transaction_impl(db, NewScheduledTransaction {
+        account_id,
+        category_id: None,
+        amount_cents: 180_000,
+        description: super::unique_name("Paycheck"),
+        merchant: Some("Acme, Inc.".to_string()),
+        recurrence: Recurrence::Biweekly,
+        start_date: "2003-01-03".to_string(),
+        end_date: Some("2003-01-31".to_string()),
+        auto_post: Some(false),
+    })
+    .await
+    .unwrap();
+
+    let category_id = create_category_impl(db, NewCategory {
+        name: super::unique_name("Calendar Groceries"),
+        icon: None,
+        color: None,
+    })
+    .await
+    .unwrap();
+    let target_id = create_spending_target_impl(db, NewSpendingTarget {
+        category_id: Some(category_id),
+        account_id: None,
+        amount_cents: 20_000,
+        period: "monthly".to_string(),
+        start_date: "2002-12-01".to_string(),
+        end_date: None,
+    })
+        .await
+        .unwrap();
+
+    let output_path = std::env::temp_dir().join(format!("{}.ics", super::unique_word("calendar")));
+    let today = NaiveDate::from_ymd_opt(2003, 1, 1).unwrap();
+    let result = export_payment_calendar_impl(db, output_path.to_str().unwrap(), Some(3), today)
+        .await
+        .expect("Failed to export calendar");
+    assert!(result.success);
+
+    let ics = std::fs::read_to_string(&output_path).unwrap().replace("\r\n ", "");
+    std::fs::remove_file(&output_path).ok();
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), result.event_count);
+
+    // The debt is paid off by its third due date
+    let debt_uid = format!("UID:debt-{}-", debt_id);
+    assert_eq!(ics.matches(&debt_uid).count(), 3);
+    assert!(ics.contains(&format!("{}2003-03-15@budget-balancer", debt_uid)));
+
+    let paycheck_uid = format!("UID:scheduled-{}-", paycheck.id);
+    assert_eq!(ics.matches(&paycheck_uid).count(), 3);
+    assert!(ics.contains(&format!("SUMMARY:Income: {} $1800.00", paycheck.description)));
+    assert!(ics.contains("DESCRIPTION:Acme\\, Inc."));
+
+    let target_uid = format!("UID:target-{}-", target_id);
+    assert_eq!(ics.matches(&target_uid).count(), 3);
+    assert!(ics.contains(&format!("{}2003-03-01@budget-balancer", target_uid)));
+    assert!(!ics.contains(&format!("{}2003-04-01@budget-balancer", target_uid)));
+}
@@ -0,0 +1,122 @@
+use budget_balancer_lib::commands::analytics_commands::get_spending_by_category_impl;
+use budget_balancer_lib::commands::csv_commands::{import_csv_impl, reset_rate_limiter};
+use budget_balancer_lib::commands::settings_commands::update_setting_impl;
+use budget_balancer_lib::commands::transaction_commands::{
+    list_transactions_impl, mark_posted_impl, TransactionFilter,
+};
+use budget_balancer_lib::models::setting::SettingKey;
+use budget_balancer_lib::services::csv_parser::ColumnMapping;
+use serial_test::serial;
+
+fn status_mapping() -> ColumnMapping {
+    ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        status: Some("Status".to_string()),
+        ..Default::default()
+    }
+}
+
+fn account_filter(account_id: i64, pending: Option<bool>) -> TransactionFilter {
+    TransactionFilter {
+        account_id: Some(vec![account_id]),
+        category_id: None,
+        start_date: None,
+        end_date: None,
+        search: None,
+        min_amount_cents: None,
+        max_amount_cents: None,
+        only_uncategorized: None,
+        exclude_category_ids: None,
+        pending,
+        member_id: None,
+        limit: None,
+        offset: None,
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_import_status_column_and_reconcile_when_posted() {
+    reset_rate_limiter();
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Pending Card").await;
+
+    let first = "Date,Amount,Description,Status\n\
+                 2016-03-04,-42.17,PENDING COFFEE ROASTERS,Pending\n\
+                 2016-03-03,-15.00,Bookshop,Posted";
+    let result = import_csv_impl(db, account_id, first.to_string(), status_mapping()).await.unwrap();
+    assert_eq!(result.imported, 2);
+    assert_eq!(result.pending_posted, 0);
+
+    let pending = list_transactions_impl(db, Some(account_filter(account_id, Some(true)))).await.unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].description, "PENDING COFFEE ROASTERS");
+    assert_eq!(pending[0].status, "pending");
+
+    // Two days later the charge posts with its final date and description
+    tokio::time::sleep(std::time::Duration::from_millis(super::RATE_LIMITER_DELAY_MS)).await;
+    let second = "Date,Amount,Description,Status\n\
+                  2016-03-06,-42.17,Coffee Roasters Ltd,Posted\n\
+                  2016-03-03,-15.00,Bookshop,Posted";
+    let result = import_csv_impl(db, account_id, second.to_string(), status_mapping()).await.unwrap();
+    assert_eq!(result.imported, 0);
+    assert_eq!(result.duplicates, 1);
+    assert_eq!(result.pending_posted, 1);
+
+    let all = list_transactions_impl(db, Some(account_filter(account_id, None))).await.unwrap();
+    assert_eq!(all.len(), 2, "The posted charge replaces the pending one rather than adding to it");
+    let coffee = all.iter().find(|t| t.amount_cents == -4217).unwrap();
+    assert_eq!(coffee.id, pending[0].id);
+    assert_eq!(coffee.status, "posted");
+    assert_eq!(coffee.date, "2016-03-06");
+    assert_eq!(coffee.description, "Coffee Roasters Ltd");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_mark_posted() {
+    reset_rate_limiter();
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Mark Posted").await;
+
+    let csv = "Date,Amount,Description,Status\n\
+               2016-04-01,-9.99,Streaming hold,pending\n\
+               2016-04-02,-20.00,Groceries,posted";
+    import_csv_impl(db, account_id, csv.to_string(), status_mapping()).await.unwrap();
+    let all = list_transactions_impl(db, Some(account_filter(account_id, None))).await.unwrap();
+    let ids: Vec<i64> = all.iter().map(|t| t.id).collect();
+
+    let result = mark_posted_impl(db, ids.iter().copied().chain([i64::MAX]).collect(), false).await.unwrap();
+    assert_eq!(result.updated_count, 1, "Only the pending transaction changes");
+    assert_eq!(result.failed_ids, vec![i64::MAX]);
+
+    let pending = list_transactions_impl(db, Some(account_filter(account_id, Some(true)))).await.unwrap();
+    assert!(pending.is_empty());
+    assert!(mark_posted_impl(db, vec![], false).await.is_err());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_exclude_pending_setting_leaves_pending_out_of_analytics() {
+    reset_rate_limiter();
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Exclude Pending").await;
+
+    let csv = "Date,Amount,Description,Status\n\
+               2016-05-10,-30.00,Hotel hold,Pending\n\
+               2016-05-11,-12.50,Lunch,Posted";
+    import_csv_impl(db, account_id, csv.to_string(), status_mapping()).await.unwrap();
+
+    let spending = get_spending_by_category_impl(db, "2016-05-01", "2016-05-31", Some(account_id), None).await.unwrap();
+    assert_eq!(spending.total_spending_cents, 4250, "Pending spending counts by default");
+
+    let key = SettingKey::ExcludePending.to_string();
+    update_setting_impl(db, &key, Some("true")).await.unwrap();
+    let spending = get_spending_by_category_impl(db, "2016-05-01", "2016-05-31", Some(account_id), None).await;
+    update_setting_impl(db, &key, None).await.unwrap();
+    assert_eq!(spending.unwrap().total_spending_cents, 1250);
+
+    assert!(update_setting_impl(db, &key, Some("yes")).await.is_err());
+}
@@ -0,0 +1,218 @@
+use budget_balancer_lib::commands::account_commands::delete_account_impl;
+use budget_balancer_lib::commands::csv_commands::{import_csv_impl, reset_rate_limiter, undo_import_impl};
+use budget_balancer_lib::commands::data_commands::restore_data_archive;
+use budget_balancer_lib::commands::fx_commands::set_transaction_currency_impl;
+use budget_balancer_lib::commands::period_lock_commands::{
+    list_period_locks_impl, lock_period_impl, unlock_period_impl,
+};
+use budget_balancer_lib::commands::transaction_commands::{
+    bulk_assign_member_impl, bulk_delete_transactions_impl, bulk_set_tax_deductible_impl, bulk_update_category_impl,
+    create_transaction_impl, delete_transaction_impl, mark_posted_impl, update_transaction_category_impl,
+    update_transaction_impl,
+};
+use budget_balancer_lib::errors::{CsvImportError, DataArchiveError, TransactionError};
+use budget_balancer_lib::models::data_archive::{ArchiveConflict, DataArchive};
+use budget_balancer_lib::models::transaction::{CreateTransaction, UpdateTransaction};
+use budget_balancer_lib::services::csv_parser::ColumnMapping;
+use serial_test::serial;
+
+fn expense(account_id: i64, date: &str, description: &str) -> CreateTransaction {
+    CreateTransaction {
+        account_id,
+        category_id: None,
+        date: date.to_string(),
+        amount_cents: -2500,
+        description: super::unique_name(description),
+        merchant: None,
+        notes: None,
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_locked_month_rejects_changes() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Closed Books").await;
+    let open = create_transaction_impl(db, expense(account_id, "1961-06-02", "June groceries"), false)
+        .await
+        .unwrap();
+    let closed = create_transaction_impl(db, expense(account_id, "1961-05-20", "May groceries"), false)
+        .await
+        .unwrap();
+
+    let lock = lock_period_impl(db, "1961-05").await.unwrap();
+    assert_eq!(lock.month, "1961-05");
+    assert!(list_period_locks_impl(db).await.unwrap().iter().any(|l| l.month == "1961-05"));
+
+    assert!(matches!(
+        create_transaction_impl(db, expense(account_id, "1961-05-21", "Late receipt"), false).await,
+        Err(TransactionError::PeriodLocked(month)) if month == "1961-05"
+    ));
+    let edit = UpdateTransaction { id: closed.id, amount_cents: Some(-3000), ..Default::default() };
+    assert!(matches!(
+        update_transaction_impl(db, edit.clone(), false).await,
+        Err(TransactionError::PeriodLocked(_))
+    ));
+    let move_in = UpdateTransaction { id: open.id, date: Some("1961-05-31".to_string()), ..Default::default() };
+    assert!(matches!(
+        update_transaction_impl(db, move_in, false).await,
+        Err(TransactionError::PeriodLocked(_)),
+    ), "Moving a transaction into a locked month is a change to it");
+    assert!(matches!(delete_transaction_impl(db, closed.id, false).await, Err(TransactionError::PeriodLocked(_))));
+    assert!(matches!(
+        update_transaction_category_impl(db, closed.id, 1, false).await,
+        Err(TransactionError::PeriodLocked(_))
+    ));
+    assert!(matches!(
+        bulk_update_category_impl(db, vec![open.id, closed.id], 1, false).await,
+        Err(TransactionError::PeriodLocked(_))
+    ));
+    assert!(matches!(
+        bulk_delete_transactions_impl(db, vec![open.id, closed.id], false).await,
+        Err(TransactionError::PeriodLocked(_))
+    ));
+
+    // Overriding the lock allows the change
+    let edited = update_transaction_impl(db, edit, true).await.unwrap();
+    assert_eq!(edited.amount_cents, -3000);
+    create_transaction_impl(db, expense(account_id, "1961-05-21", "Late receipt"), true).await.unwrap();
+    delete_transaction_impl(db, closed.id, true).await.unwrap();
+
+    // The rejected bulk delete left the open month's transaction in place
+    delete_transaction_impl(db, open.id, false).await.unwrap();
+
+    unlock_period_impl(db, "1961-05").await.unwrap();
+    create_transaction_impl(db, expense(account_id, "1961-05-22", "After reopening"), false)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_import_skips_locked_months() {
+    reset_rate_limiter();
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Closed Import").await;
+    lock_period_impl(db, "1961-08").await.unwrap();
+
+    let csv = "Date,Amount,Description\n\
+               1961-08-30,-12.00,Stray August row\n\
+               1961-09-02,-8.00,September row"
+        .to_string();
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        ..Default::default()
+    };
+    let result = import_csv_impl(db, account_id, csv, mapping).await.unwrap();
+    assert_eq!(result.imported, 1);
+    assert_eq!(result.locked, 1);
+
+    unlock_period_impl(db, "1961-08").await.unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_undo_and_restore_respect_locked_months() {
+    reset_rate_limiter();
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Closed Restore").await;
+    let (account_name,): (String,) =
+        sqlx::query_as("SELECT name FROM accounts WHERE id = ?").bind(account_id).fetch_one(db).await.unwrap();
+
+    let csv = "Date,Amount,Description\n1961-10-04,-15.00,October row".to_string();
+    let mapping = ColumnMapping {
+        date: "Date".to_string(),
+        amount: "Amount".to_string(),
+        description: "Description".to_string(),
+        ..Default::default()
+    };
+    let imported = import_csv_impl(db, account_id, csv, mapping).await.unwrap();
+    lock_period_impl(db, "1961-10").await.unwrap();
+
+    assert!(matches!(
+        undo_import_impl(db, imported.import_batch_id, false).await,
+        Err(CsvImportError::PeriodLocked(month)) if month == "1961-10"
+    ));
+
+    let archive: DataArchive = serde_json::from_value(serde_json::json!({
+        "version": 1,
+        "transactions": [{
+            "account": account_name,
+            "date": "1961-10-05",
+            "amount": -9.0,
+            "description": super::unique_name("Restored October row")
+        }]
+    }))
+    .unwrap();
+    assert!(matches!(
+        restore_data_archive(db, &archive, ArchiveConflict::Skip, false).await,
+        Err(DataArchiveError::PeriodLocked(month)) if month == "1961-10"
+    ));
+
+    // Overriding the lock allows both
+    let restored = restore_data_archive(db, &archive, ArchiveConflict::Skip, true).await.unwrap();
+    assert_eq!(restored.transactions.created, 1);
+    undo_import_impl(db, imported.import_batch_id, true).await.unwrap();
+
+    unlock_period_impl(db, "1961-10").await.unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_bulk_edits_respect_locked_months() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Closed Bulk").await;
+    let closed = create_transaction_impl(db, expense(account_id, "1961-11-03", "November fuel"), false)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE transactions SET status = 'pending' WHERE id = ?")
+        .bind(closed.id)
+        .execute(db)
+        .await
+        .unwrap();
+    lock_period_impl(db, "1961-11").await.unwrap();
+
+    assert!(matches!(
+        bulk_assign_member_impl(db, vec![closed.id], None, false).await,
+        Err(TransactionError::PeriodLocked(month)) if month == "1961-11"
+    ));
+    assert!(matches!(
+        bulk_set_tax_deductible_impl(db, vec![closed.id], Some(true), false).await,
+        Err(TransactionError::PeriodLocked(_))
+    ));
+    assert!(matches!(mark_posted_impl(db, vec![closed.id], false).await, Err(TransactionError::PeriodLocked(_))));
+    assert!(set_transaction_currency_impl(db, closed.id, Some("EUR"), Some(-2000), false)
+        .await
+        .unwrap_err()
+        .contains("locked"));
+    let err = delete_account_impl(db, account_id, false).await.unwrap_err();
+    assert!(err.contains("locked"), "Deleting the account would cascade into the locked month");
+
+    // Overriding the lock allows each change
+    bulk_assign_member_impl(db, vec![closed.id], None, true).await.unwrap();
+    bulk_set_tax_deductible_impl(db, vec![closed.id], Some(true), true).await.unwrap();
+    assert_eq!(mark_posted_impl(db, vec![closed.id], true).await.unwrap().updated_count, 1);
+    // Posted transactions are left alone, so there's nothing left to block
+    mark_posted_impl(db, vec![closed.id], false).await.unwrap();
+    set_transaction_currency_impl(db, closed.id, Some("EUR"), Some(-2000), true).await.unwrap();
+    assert_eq!(delete_account_impl(db, account_id, true).await.unwrap(), 1);
+
+    unlock_period_impl(db, "1961-11").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_period_lock_validation() {
+    let db = super::get_test_db_pool().await;
+
+    assert!(lock_period_impl(db, "1961-13").await.is_err());
+    assert!(lock_period_impl(db, "May 1961").await.is_err());
+    assert!(unlock_period_impl(db, "1960-01").await.unwrap_err().contains("not locked"));
+
+    // Locking twice keeps the first lock
+    let first = lock_period_impl(db, "1960-02").await.unwrap();
+    let second = lock_period_impl(db, "1960-02").await.unwrap();
+    assert_eq!(first.locked_at, second.locked_at);
+    unlock_period_impl(db, "1960-02").await.unwrap();
+}
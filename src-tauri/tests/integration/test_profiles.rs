@@ -0,0 +1,97 @@
+use budget_balancer_lib::commands::access_commands::{set_app_password_impl, set_app_role_impl};
+use budget_balancer_lib::commands::account_commands::list_accounts_impl;
+use budget_balancer_lib::commands::profile_commands::{
+    create_profile_impl, list_profiles_impl, switch_profile_impl,
+};
+use budget_balancer_lib::commands::settings_commands::update_setting_impl;
+use budget_balancer_lib::errors::ProfileError;
+use budget_balancer_lib::models::setting::SettingKey;
+use budget_balancer_lib::services::profile_registry::ProfileRegistry;
+use budget_balancer_lib::utils::access_guard::AccessGuard;
+use budget_balancer_lib::{open_database, DbPool};
+
+async fn account_ids(db_pool: &DbPool) -> Vec<i64> {
+    list_accounts_impl(&db_pool.pool(), true)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|account| account.id)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_profiles_keep_separate_data() {
+    let dir = std::env::temp_dir().join(super::unique_name("profiles").replace(' ', "_"));
+    let registry = ProfileRegistry::load(&dir).unwrap();
+    let guard = AccessGuard::new(false);
+    let db_pool = DbPool::new(open_database(&registry.active_database_path().unwrap(), &guard).await.unwrap());
+
+    let profile = create_profile_impl(&registry, "Small Business").await.unwrap();
+    assert!(dir.join(&profile.file_name).exists(), "The database is set up right away");
+    assert!(matches!(
+        create_profile_impl(&registry, "small business").await,
+        Err(ProfileError::AlreadyExists(_))
+    ));
+    let list = list_profiles_impl(&registry);
+    assert_eq!(list.active, "Personal");
+    assert_eq!(list.profiles.len(), 2);
+
+    let personal_account = super::fixtures::create_test_account(&db_pool.pool(), "Personal Checking").await;
+
+    let list = switch_profile_impl(&db_pool, &registry, &guard, "Small Business", None).await.unwrap();
+    assert_eq!(list.active, "Small Business");
+    assert!(!account_ids(&db_pool).await.contains(&personal_account));
+
+    // Read-only mode belongs to the profile it was turned on in
+    update_setting_impl(&db_pool.pool(), &SettingKey::ReadOnly.to_string(), Some("true")).await.unwrap();
+    switch_profile_impl(&db_pool, &registry, &guard, "Personal", None).await.unwrap();
+    assert!(!guard.is_read_only());
+    assert!(account_ids(&db_pool).await.contains(&personal_account));
+    switch_profile_impl(&db_pool, &registry, &guard, "Small Business", None).await.unwrap();
+    assert!(guard.is_read_only());
+
+    // The open profile is remembered for the next start
+    assert_eq!(ProfileRegistry::load(&dir).unwrap().active(), "Small Business");
+
+    assert!(matches!(
+        switch_profile_impl(&db_pool, &registry, &guard, "Nope", None).await,
+        Err(ProfileError::NotFound(_))
+    ));
+    assert_eq!(registry.active(), "Small Business", "A failed switch keeps the open profile");
+
+    db_pool.pool().close().await;
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[tokio::test]
+async fn test_locked_viewer_needs_the_password_to_switch() {
+    let dir = std::env::temp_dir().join(super::unique_name("profiles").replace(' ', "_"));
+    let registry = ProfileRegistry::load(&dir).unwrap();
+    let guard = AccessGuard::new(false);
+    let db_pool = DbPool::new(open_database(&registry.active_database_path().unwrap(), &guard).await.unwrap());
+    create_profile_impl(&registry, "Small Business").await.unwrap();
+
+    set_app_password_impl(&db_pool.pool(), &guard, None, Some("correct horse")).await.unwrap();
+    set_app_role_impl(&db_pool.pool(), &guard, "viewer", None).await.unwrap();
+    assert!(guard.is_locked());
+
+    // The other profile has no viewer role, so switching would otherwise unlock editing
+    for password in [None, Some("wrong password")] {
+        assert!(matches!(
+            switch_profile_impl(&db_pool, &registry, &guard, "Small Business", password).await,
+            Err(ProfileError::Locked)
+        ));
+        guard.reset_password_attempts();
+    }
+    assert_eq!(registry.active(), "Personal");
+    assert!(guard.is_locked());
+
+    let list = switch_profile_impl(&db_pool, &registry, &guard, "Small Business", Some("correct horse"))
+        .await
+        .unwrap();
+    assert_eq!(list.active, "Small Business");
+    assert!(!guard.is_locked());
+
+    db_pool.pool().close().await;
+    std::fs::remove_dir_all(dir).ok();
+}
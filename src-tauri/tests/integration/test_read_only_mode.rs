@@ -0,0 +1,95 @@
+use budget_balancer_lib::commands::access_commands::set_app_password_impl;
+use budget_balancer_lib::commands::account_commands::{create_account_impl, list_accounts_impl};
+use budget_balancer_lib::commands::job_commands::get_job_status_impl;
+use budget_balancer_lib::commands::settings_commands::{
+    get_setting_impl, load_read_only_mode, set_read_only_mode_impl, update_setting_impl,
+};
+use budget_balancer_lib::errors::AccessError;
+use budget_balancer_lib::models::account::{AccountType, NewAccount};
+use budget_balancer_lib::models::job::JobKind;
+use budget_balancer_lib::models::setting::SettingKey;
+use budget_balancer_lib::services::job_queue::JobQueue;
+use budget_balancer_lib::utils::access_guard::AccessGuard;
+use budget_balancer_lib::{open_database, start_profile};
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn test_set_read_only_mode_persists() {
+    let db = super::get_test_db_pool().await;
+    let guard = AccessGuard::new(false);
+
+    let status = set_read_only_mode_impl(db, &guard, true).await.unwrap();
+    assert!(status.enabled);
+    assert!(!status.forced);
+    assert!(guard.check("create_account").is_err());
+    assert_eq!(get_setting_impl(db, SettingKey::ReadOnly).await.unwrap().as_deref(), Some("true"));
+
+    // The saved setting is applied on the next launch
+    let next_launch = AccessGuard::new(false);
+    load_read_only_mode(db, &next_launch).await.unwrap();
+    assert!(next_launch.is_read_only());
+
+    let status = set_read_only_mode_impl(db, &guard, false).await.unwrap();
+    assert!(!status.enabled);
+    assert!(guard.check("create_account").is_ok());
+
+    let next_launch = AccessGuard::new(false);
+    load_read_only_mode(db, &next_launch).await.unwrap();
+    assert!(!next_launch.is_read_only());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_forced_read_only_mode_cannot_be_turned_off() {
+    let db = super::get_test_db_pool().await;
+    let guard = AccessGuard::new(true);
+
+    let result = set_read_only_mode_impl(db, &guard, false).await;
+    assert!(result.unwrap_err().contains("--read-only"));
+    assert!(guard.is_read_only());
+}
+
+#[tokio::test]
+async fn test_read_only_setting_validation() {
+    let db = super::get_test_db_pool().await;
+
+    let result = update_setting_impl(db, "read_only", Some("yes")).await;
+    assert!(result.is_err(), "Only true or false should be accepted");
+}
+
+#[tokio::test]
+async fn test_read_only_database_refuses_writes() {
+    let dir = std::env::temp_dir().join(super::unique_name("read_only").replace(' ', "_"));
+    let guard = AccessGuard::new(false);
+    let db = &open_database(&dir.join("budget.db"), &guard).await.unwrap();
+    let job_id = JobQueue::enqueue(db, JobKind::CsvImport).await.unwrap();
+    let account = || NewAccount {
+        name: "Checking".to_string(),
+        account_type: AccountType::Checking,
+        initial_balance_cents: 0,
+    };
+
+    set_read_only_mode_impl(db, &guard, true).await.unwrap();
+
+    // Opening the profile again leaves interrupted jobs (and old records) alone
+    start_profile(db, &guard).await;
+    assert!(guard.is_read_only());
+    assert_eq!(get_job_status_impl(db, job_id).await.unwrap().status, "queued");
+
+    // A write that gets past the command check still fails in the database
+    assert!(create_account_impl(db, account()).await.is_err());
+    assert!(list_accounts_impl(db, true).await.is_ok());
+    assert!(matches!(
+        set_app_password_impl(db, &guard, None, Some("1234")).await,
+        Err(AccessError::ReadOnly)
+    ));
+
+    set_read_only_mode_impl(db, &guard, false).await.unwrap();
+    assert!(create_account_impl(db, account()).await.is_ok());
+    start_profile(db, &guard).await;
+    assert_eq!(get_job_status_impl(db, job_id).await.unwrap().status, "failed");
+
+    db.close().await;
+    std::fs::remove_dir_all(dir).ok();
+}
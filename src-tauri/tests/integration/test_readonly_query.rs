@@ -0,0 +1,113 @@
+use budget_balancer_lib::commands::schema_commands::{execute_readonly_query_impl, get_query_plan_impl};
+use budget_balancer_lib::errors::QueryError;
+use budget_balancer_lib::services::query_console::QueryConsole;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_execute_readonly_query_returns_rows() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Query Console").await;
+
+    use super::fixtures::TestTransaction;
+    super::fixtures::insert_test_transactions(db, account_id, vec![
+        TestTransaction::new("1985-02-01", -2000, "Console Coffee"),
+        TestTransaction::new("1985-02-03", -3550, "Console Books"),
+        TestTransaction::new("1985-02-05", -1225, "Console Lunch"),
+    ])
+    .await;
+
+    let sql = format!(
+        "SELECT description, amount_cents, NULL AS note FROM transactions WHERE account_id = {} ORDER BY date;",
+        account_id
+    );
+    let result = execute_readonly_query_impl(db, &sql, None).await.expect("Query should run");
+
+    assert_eq!(result.columns, vec!["description", "amount_cents", "note"]);
+    assert_eq!(result.row_count, 3);
+    assert!(!result.truncated);
+    assert!(result.rows[0][0].as_str().unwrap().starts_with("Console Coffee"));
+    assert_eq!(result.rows[0][1..], [json!(-2000), json!(null)]);
+
+    // The limit caps the rows even when the statement has none
+    let limited = execute_readonly_query_impl(db, &sql, Some(2)).await.unwrap();
+    assert_eq!(limited.row_count, 2);
+    assert_eq!(limited.limit, 2);
+    assert!(limited.truncated);
+}
+
+#[tokio::test]
+async fn test_execute_readonly_query_columns_without_rows() {
+    let db = super::get_test_db_pool().await;
+
+    let result = execute_readonly_query_impl(db, "SELECT id, name FROM accounts WHERE id = -1", None)
+        .await
+        .unwrap();
+    assert_eq!(result.columns, vec!["id", "name"]);
+    assert!(result.rows.is_empty());
+}
+
+#[tokio::test]
+async fn test_execute_readonly_query_rejects_writes() {
+    let db = super::get_test_db_pool().await;
+
+    let result = execute_readonly_query_impl(db, "DELETE FROM transactions", None).await;
+    assert!(matches!(result, Err(QueryError::NotSelect)), "Got {:?}", result);
+
+    let result = execute_readonly_query_impl(db, "SELECT 1; DROP TABLE transactions", None).await;
+    assert!(matches!(result, Err(QueryError::MultipleStatements)), "Got {:?}", result);
+
+    let result = execute_readonly_query_impl(db, "SELECT * FROM no_such_table", None).await;
+    match result {
+        Err(QueryError::Invalid(message)) => assert!(message.contains("no_such_table"), "Got {}", message),
+        other => panic!("Expected a query error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_read_only_connection_refuses_writes() {
+    let db = super::get_test_db_pool().await;
+
+    // Even without validation, the console's connection can't write
+    let mut conn = QueryConsole::connect_read_only(db).await.expect("Failed to connect");
+    let result = sqlx::query("INSERT INTO categories (name, type) VALUES ('Console Write', 'custom')")
+        .execute(&mut conn)
+        .await;
+    assert!(result.is_err(), "Write through the read-only connection should fail");
+}
+
+#[tokio::test]
+async fn test_query_plans_use_query_indexes() {
+    let db = super::get_test_db_pool().await;
+
+    let listing = get_query_plan_impl(
+        db,
+        "SELECT * FROM transactions WHERE account_id = ? AND date >= ? ORDER BY date DESC, id DESC LIMIT 50",
+    )
+    .await
+    .expect("Plan should succeed");
+    assert!(listing.indexes.contains(&"idx_transactions_account_date".to_string()), "Got {:?}", listing);
+    assert!(listing.full_scans.is_empty());
+
+    let payments = get_query_plan_impl(db, "SELECT * FROM debt_payments WHERE debt_id = ? ORDER BY date").await.unwrap();
+    assert_eq!(payments.indexes, vec!["idx_debt_payments_debt_date"]);
+
+    let rules = get_query_plan_impl(db, "SELECT * FROM category_rules ORDER BY priority DESC, created_at ASC, id ASC")
+        .await
+        .unwrap();
+    assert!(rules.steps.iter().all(|s| !s.detail.contains("TEMP B-TREE")), "Rules shouldn't need a sort: {:?}", rules);
+
+    let scan = get_query_plan_impl(db, "SELECT * FROM transactions WHERE notes = 'x'").await.unwrap();
+    assert_eq!(scan.full_scans, vec!["transactions"]);
+
+    let (redundant,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index'
+         AND name IN ('idx_transactions_account_id', 'idx_transactions_category_id', 'idx_transactions_hash')"
+    )
+    .fetch_one(db)
+    .await
+    .unwrap();
+    assert_eq!(redundant, 0);
+
+    let result = get_query_plan_impl(db, "DELETE FROM transactions").await;
+    assert!(matches!(result, Err(QueryError::NotSelect)));
+}
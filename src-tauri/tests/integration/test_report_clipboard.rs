@@ -0,0 +1,69 @@
+use budget_balancer_lib::commands::analytics_commands::render_report_text_impl;
+use budget_balancer_lib::models::report::{ReportSpec, ReportTextFormat};
+
+#[tokio::test]
+async fn test_render_category_report_markdown() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Clipboard Report Test").await;
+
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("2019-10-02", -7500, "Groceries").with_category(1),
+        super::fixtures::TestTransaction::new("2019-10-03", -2500, "Lunch").with_category(2),
+    ];
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let spec = ReportSpec::SpendingByCategory {
+        start_date: "2019-10-01".to_string(),
+        end_date: "2019-10-31".to_string(),
+        account_id: Some(account_id),
+    };
+    let result = render_report_text_impl(db, &spec, None).await;
+    assert!(result.is_ok(), "Failed to render report: {:?}", result);
+
+    let response = result.unwrap();
+    assert_eq!(response.row_count, 3, "Two categories plus a total row");
+    let lines: Vec<&str> = response.text.lines().collect();
+    assert_eq!(lines[0], "**Spending by category, 2019-10-01 to 2019-10-31**");
+    assert_eq!(lines[2], "| Category | Amount | Share | Transactions |");
+    assert_eq!(lines[4], "| Groceries | $75.00 | 75.0% | 1 |");
+    assert_eq!(lines[6], "| Total | $100.00 |  | 2 |");
+}
+
+#[tokio::test]
+async fn test_render_merchant_report_tsv() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Clipboard TSV Test").await;
+    let merchant = super::unique_word("Florist");
+
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("2019-11-04", -3000, "Flowers").with_merchant(&merchant),
+        super::fixtures::TestTransaction::new("2019-11-18", -5000, "Flowers").with_merchant(&merchant),
+    ];
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let spec: ReportSpec = serde_json::from_value(serde_json::json!({
+        "report": "spending_by_merchant",
+        "start_date": "2019-11-01",
+        "end_date": "2019-11-30",
+        "account_id": account_id,
+    }))
+    .expect("Spec should deserialize from the frontend shape");
+
+    let response = render_report_text_impl(db, &spec, Some(ReportTextFormat::Tsv)).await.unwrap();
+    let lines: Vec<&str> = response.text.lines().collect();
+    assert_eq!(lines[0], "Merchant\tAmount\tTransactions\tAverage\tvs Previous");
+    assert_eq!(lines[1], format!("{}\t$80.00\t2\t$40.00\tnew", merchant));
+}
+
+#[tokio::test]
+async fn test_render_report_invalid_dates() {
+    let db = super::get_test_db_pool().await;
+
+    let spec = ReportSpec::SpendingByMerchant {
+        start_date: "2019-11-30".to_string(),
+        end_date: "2019-11-01".to_string(),
+        account_id: None,
+        limit: None,
+    };
+    assert!(render_report_text_impl(db, &spec, None).await.is_err());
+}
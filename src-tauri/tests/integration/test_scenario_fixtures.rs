@@ -0,0 +1,75 @@
+use budget_balancer_lib::commands::debt_commands::{get_debt_progress_impl, list_debts_impl};
+use budget_balancer_lib::commands::transaction_commands::{
+    count_transactions_impl, list_transactions_with_balance_impl, TransactionFilter,
+};
+use budget_balancer_lib::fixtures::{build_family_scenario, ScenarioOptions};
+use chrono::NaiveDate;
+
+fn account_filter(account_id: i64) -> Option<TransactionFilter> {
+    Some(TransactionFilter {
+        account_id: Some(vec![account_id]),
+        category_id: None,
+        start_date: None,
+        end_date: None,
+        search: None,
+        min_amount_cents: None,
+        max_amount_cents: None,
+        only_uncategorized: None,
+        exclude_category_ids: None,
+        pending: None,
+        member_id: None,
+        limit: Some(1),
+        offset: None,
+    })
+}
+
+#[tokio::test]
+async fn test_build_family_scenario() {
+    let db = super::get_test_db_pool().await;
+    let options = ScenarioOptions {
+        end_month: NaiveDate::from_ymd_opt(2013, 12, 15),
+        months: 12,
+        label: Some(super::unique_word("Scenario")),
+    };
+
+    let scenario = build_family_scenario(db, &options).await.expect("Failed to build scenario");
+    assert_eq!(scenario.start_date, "2013-01-01");
+    assert_eq!(scenario.end_date, "2013-12-31");
+    assert_eq!(scenario.debt_ids.len(), 2);
+
+    let mut total = 0;
+    for account_id in [scenario.checking_account_id, scenario.savings_account_id, scenario.credit_card_account_id] {
+        let mut filter = account_filter(account_id);
+        filter.as_mut().unwrap().limit = None;
+        total += count_transactions_impl(db, filter).await.unwrap();
+    }
+    assert_eq!(total as usize, scenario.transaction_count);
+    assert_eq!(scenario.transaction_count, 12 * 22);
+
+    // The card is paid off every month, and savings grows by the transfers
+    let card = list_transactions_with_balance_impl(db, account_filter(scenario.credit_card_account_id)).await.unwrap();
+    assert!(card[0].running_balance_cents == 0, "Card should end at zero, got {}", card[0].running_balance_cents);
+    let savings = list_transactions_with_balance_impl(db, account_filter(scenario.savings_account_id)).await.unwrap();
+    assert!(savings[0].running_balance_cents > 800_000 + 12 * 30_000);
+
+    // Twelve loan payments came off each debt, part of each going to interest
+    let debts = list_debts_impl(db).await.unwrap();
+    let car_loan = debts.iter().find(|d| d.id == scenario.debt_ids[0]).unwrap();
+    let progress = get_debt_progress_impl(db, car_loan.id, None, None).await.unwrap();
+    assert_eq!(progress.payments.len(), 12);
+    assert_eq!(progress.total_paid_cents, 12 * 32_000);
+    assert!(progress.total_interest_paid_cents > 0);
+    assert_eq!(car_loan.balance_cents, 1_450_000 - progress.total_principal_paid_cents);
+}
+
+#[tokio::test]
+async fn test_build_family_scenario_rejects_empty_range() {
+    let db = super::get_test_db_pool().await;
+    let options = ScenarioOptions {
+        months: 0,
+        label: Some(super::unique_word("Empty")),
+        ..Default::default()
+    };
+
+    assert!(build_family_scenario(db, &options).await.is_err());
+}
@@ -0,0 +1,185 @@
+use budget_balancer_lib::commands::account_commands::create_account_impl;
+use budget_balancer_lib::commands::cash_flow_commands::get_cash_flow_projection_impl;
+use budget_balancer_lib::commands::scheduled_transaction_commands::{
+    confirm_scheduled_transaction_impl, create_scheduled_transaction_impl, delete_scheduled_transaction_impl,
+    list_scheduled_transactions_impl, post_due_scheduled_transactions_impl,
+};
+use budget_balancer_lib::commands::transaction_commands::create_transaction_impl;
+use budget_balancer_lib::errors::TransactionError;
+use budget_balancer_lib::models::account::{AccountType, NewAccount};
+use budget_balancer_lib::models::scheduled_transaction::{NewScheduledTransaction, Recurrence};
+use budget_balancer_lib::models::transaction::CreateTransaction;
+use sqlx::SqlitePool;
+
+fn scheduled(account_id: i64, recurrence: Recurrence, start_date: &str, end_date: Option<&str>) -> NewScheduledTransaction {
+    NewScheduledTransaction {
+        account_id,
+        category_id: Some(1),
+        amount_cents: -120_000,
+        description: super::unique_name("Rent"),
+        merchant: None,
+        recurrence,
+        start_date: start_date.to_string(),
+        end_date: end_date.map(str::to_string),
+        auto_post: Some(false),
+    }
+}
+
+async fn transaction_dates(db: &SqlitePool, description: &str) -> Vec<String> {
+    sqlx::query_scalar("SELECT date FROM transactions WHERE description = ? ORDER BY date")
+        .bind(description)
+        .fetch_all(db)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_post_due_scheduled_transactions_catches_up() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Scheduled Checking").await;
+    let rent = create_scheduled_transaction_impl(db, NewScheduledTransaction {
+        auto_post: None,
+        ..scheduled(account_id, Recurrence::Monthly, "2003-01-31", Some("2003-04-30"))
+    })
+    .await
+    .expect("Failed to schedule rent");
+    assert!(rent.auto_post);
+    assert_eq!(rent.next_due_date.as_deref(), Some("2003-01-31"));
+
+    let postings = post_due_scheduled_transactions_impl(db, Some("2003-03-15")).await.unwrap();
+    let posted: Vec<&str> = postings
+        .iter()
+        .filter(|p| p.scheduled_transaction_id == rent.id)
+        .map(|p| p.date.as_str())
+        .collect();
+    assert_eq!(posted, vec!["2003-01-31", "2003-02-28"]);
+    assert_eq!(transaction_dates(db, &rent.description).await, vec!["2003-01-31", "2003-02-28"]);
+
+    let listed = list_scheduled_transactions_impl(db).await.unwrap();
+    let listed = listed.iter().find(|s| s.id == rent.id).unwrap();
+    assert_eq!(listed.next_due_date.as_deref(), Some("2003-03-31"));
+
+    // Posting again is a no-op until the next occurrence comes due, and the schedule ends on Apr 30
+    post_due_scheduled_transactions_impl(db, Some("2003-03-15")).await.unwrap();
+    post_due_scheduled_transactions_impl(db, Some("2003-12-31")).await.unwrap();
+    assert_eq!(
+        transaction_dates(db, &rent.description).await,
+        vec!["2003-01-31", "2003-02-28", "2003-03-31", "2003-04-30"]
+    );
+    let listed = list_scheduled_transactions_impl(db).await.unwrap();
+    assert_eq!(listed.iter().find(|s| s.id == rent.id).unwrap().next_due_date, None);
+}
+
+#[tokio::test]
+async fn test_confirm_scheduled_transaction() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Scheduled Confirm").await;
+    let bill = create_scheduled_transaction_impl(db, scheduled(account_id, Recurrence::Once, "2002-05-01", None))
+        .await
+        .unwrap();
+
+    let posting = confirm_scheduled_transaction_impl(db, bill.id).await.expect("Failed to confirm");
+    assert_eq!(posting.date, "2002-05-01");
+    assert!(!posting.already_existed);
+    assert_eq!(transaction_dates(db, &bill.description).await, vec!["2002-05-01"]);
+
+    let result = confirm_scheduled_transaction_impl(db, bill.id).await;
+    assert!(matches!(result, Err(TransactionError::ScheduleFinished(id)) if id == bill.id));
+}
+
+#[tokio::test]
+async fn test_scheduled_transaction_matches_existing_transaction() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Scheduled Duplicate").await;
+    let bill = create_scheduled_transaction_impl(db, scheduled(account_id, Recurrence::Weekly, "2002-06-07", None))
+        .await
+        .unwrap();
+
+    // The bill already came in (say, from a bank import) before it was confirmed
+    let existing = create_transaction_impl(db, CreateTransaction {
+        account_id,
+        category_id: Some(1),
+        date: "2002-06-07".to_string(),
+        amount_cents: -120_000,
+        description: bill.description.clone(),
+        merchant: None,
+        notes: None,
+    }, false)
+    .await
+    .unwrap();
+
+    let posting = confirm_scheduled_transaction_impl(db, bill.id).await.unwrap();
+    assert!(posting.already_existed);
+    assert_eq!(posting.transaction_id, existing.id);
+    assert_eq!(transaction_dates(db, &bill.description).await, vec!["2002-06-07"]);
+
+    let listed = list_scheduled_transactions_impl(db).await.unwrap();
+    assert_eq!(listed.iter().find(|s| s.id == bill.id).unwrap().next_due_date.as_deref(), Some("2002-06-14"));
+}
+
+#[tokio::test]
+async fn test_scheduled_transaction_validation_and_delete() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Scheduled Validation").await;
+
+    let backwards = scheduled(account_id, Recurrence::Monthly, "2002-03-01", Some("2002-02-01"));
+    assert!(matches!(
+        create_scheduled_transaction_impl(db, backwards).await,
+        Err(TransactionError::ValidationError(_))
+    ));
+    let zero = NewScheduledTransaction { amount_cents: 0, ..scheduled(account_id, Recurrence::Monthly, "2002-03-01", None) };
+    assert!(matches!(
+        create_scheduled_transaction_impl(db, zero).await,
+        Err(TransactionError::InvalidAmount(_))
+    ));
+    let no_account = scheduled(i64::MAX, Recurrence::Monthly, "2002-03-01", None);
+    assert!(matches!(
+        create_scheduled_transaction_impl(db, no_account).await,
+        Err(TransactionError::AccountNotFound(_))
+    ));
+
+    let item = create_scheduled_transaction_impl(db, scheduled(account_id, Recurrence::Yearly, "2002-03-01", None))
+        .await
+        .unwrap();
+    delete_scheduled_transaction_impl(db, item.id).await.expect("Failed to delete");
+    assert!(!list_scheduled_transactions_impl(db).await.unwrap().iter().any(|s| s.id == item.id));
+    assert!(matches!(
+        delete_scheduled_transaction_impl(db, item.id).await,
+        Err(TransactionError::ScheduleNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_cash_flow_projection_includes_scheduled_transactions() {
+    let db = super::get_test_db_pool().await;
+    let account_id = create_account_impl(
+        db,
+        NewAccount {
+            name: super::unique_name("Scheduled Projection"),
+            account_type: AccountType::Checking,
+            initial_balance_cents: 100_000,
+        },
+    )
+    .await
+    .unwrap();
+    let rent = create_scheduled_transaction_impl(db, NewScheduledTransaction {
+        amount_cents: -40_000,
+        ..scheduled(account_id, Recurrence::Monthly, "2003-06-10", Some("2003-07-31"))
+    })
+    .await
+    .unwrap();
+
+    let projection = get_cash_flow_projection_impl(db, Some(61), Some("2003-06-01")).await.unwrap();
+    let dates: Vec<&str> = projection
+        .scheduled
+        .iter()
+        .filter(|s| s.scheduled_transaction_id == rent.id)
+        .map(|s| s.date.as_str())
+        .collect();
+    assert_eq!(dates, vec!["2003-06-10", "2003-07-10"]);
+
+    let account = projection.accounts.iter().find(|a| a.account_id == account_id).unwrap();
+    assert_eq!(account.scheduled_total_cents, -80_000);
+    assert_eq!(account.ending_balance_cents, 20_000);
+    assert_eq!(account.lowest_balance_date, "2003-07-10");
+}
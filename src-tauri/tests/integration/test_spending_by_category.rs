@@ -7,8 +7,8 @@ async fn test_get_spending_by_category() {
 
     // Create test transactions directly
     let transactions = vec![
-        super::fixtures::TestTransaction::new("2025-01-15", -100.00, "Groceries").with_merchant("Whole Foods"),
-        super::fixtures::TestTransaction::new("2025-01-20", -50.00, "Coffee").with_merchant("Starbucks"),
+        super::fixtures::TestTransaction::new("2025-01-15", -10_000, "Groceries").with_merchant("Whole Foods"),
+        super::fixtures::TestTransaction::new("2025-01-20", -5000, "Coffee").with_merchant("Starbucks"),
     ];
     super::fixtures::insert_test_transactions(db, account_id, transactions).await;
 
@@ -18,6 +18,7 @@ async fn test_get_spending_by_category() {
         "2025-01-01",
         "2025-01-31",
         Some(account_id),
+        None,
     )
     .await;
 
@@ -25,7 +26,7 @@ async fn test_get_spending_by_category() {
 
     let response = result.unwrap();
     assert!(response.categories.len() > 0, "Should have at least one category");
-    assert!(response.total_spending > 0.0, "Total spending should be greater than 0");
+    assert!(response.total_spending_cents > 0, "Total spending should be greater than 0");
 
     // Verify percentages sum to ~100
     let total_percentage: f64 = response.categories.iter().map(|c| c.percentage).sum();
@@ -40,6 +41,7 @@ async fn test_get_spending_by_category_empty_range() {
         "2020-01-01",
         "2020-01-31",
         None,
+        None,
     )
     .await;
 
@@ -47,7 +49,7 @@ async fn test_get_spending_by_category_empty_range() {
 
     let response = result.unwrap();
     assert_eq!(response.categories.len(), 0, "Should have no categories");
-    assert_eq!(response.total_spending, 0.0, "Total spending should be 0");
+    assert_eq!(response.total_spending_cents, 0, "Total spending should be 0");
 }
 
 #[tokio::test]
@@ -57,7 +59,7 @@ async fn test_get_spending_by_category_with_account_filter() {
 
     // Create test transaction directly
     let transactions = vec![
-        super::fixtures::TestTransaction::new("2025-01-15", -75.00, "Gas").with_merchant("Shell"),
+        super::fixtures::TestTransaction::new("2025-01-15", -7500, "Gas").with_merchant("Shell"),
     ];
     super::fixtures::insert_test_transactions(db, account_id, transactions).await;
 
@@ -67,8 +69,29 @@ async fn test_get_spending_by_category_with_account_filter() {
         "2025-01-01",
         "2025-01-31",
         Some(account_id),
+        None,
     )
     .await;
 
     assert!(result.is_ok(), "Should succeed with account filter");
 }
+
+#[tokio::test]
+async fn test_get_spending_by_category_sums_to_the_cent() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Cents Test").await;
+
+    // Thirty dimes add up to 3.0000000000000013 as floats
+    let transactions = (0..30)
+        .map(|_| super::fixtures::TestTransaction::new("2025-01-10", -10, "Gumball"))
+        .collect();
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let response = get_spending_by_category_impl(db, "2025-01-01", "2025-01-31", Some(account_id), None)
+        .await
+        .unwrap();
+
+    assert_eq!(response.total_spending_cents, 300);
+    assert_eq!(response.categories.len(), 1);
+    assert_eq!(response.categories[0].amount_cents, 300);
+}
@@ -0,0 +1,100 @@
+use budget_balancer_lib::commands::analytics_commands::get_spending_by_merchant_impl;
+
+#[tokio::test]
+async fn test_get_spending_by_merchant() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Merchant Spending Test").await;
+    let bakery = super::unique_word("Bakery");
+    let bookshop = super::unique_word("Bookshop");
+
+    let transactions = vec![
+        // Previous period (2019-04-01..2019-04-30)
+        super::fixtures::TestTransaction::new("2019-04-12", -4000, "Bread").with_merchant(&bakery),
+        // Current period (2019-05-01..2019-05-30)
+        super::fixtures::TestTransaction::new("2019-05-02", -2000, "Bread").with_merchant(&bakery),
+        super::fixtures::TestTransaction::new("2019-05-09", -4000, "Cake").with_merchant(&bakery),
+        super::fixtures::TestTransaction::new("2019-05-20", -3000, "Novel").with_merchant(&bookshop),
+        super::fixtures::TestTransaction::new("2019-05-21", 50_000, "Refund").with_merchant(&bookshop),
+        super::fixtures::TestTransaction::new("2019-05-22", -9900, "Cash withdrawal"),
+    ];
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let result = get_spending_by_merchant_impl(db, "2019-05-01", "2019-05-30", Some(account_id), None).await;
+    assert!(result.is_ok(), "Failed to get spending by merchant: {:?}", result);
+
+    let response = result.unwrap();
+    assert_eq!(response.previous_period.start_date, "2019-04-01");
+    assert_eq!(response.previous_period.end_date, "2019-04-30");
+    assert_eq!(response.merchants.len(), 2, "Income and merchant-less transactions are excluded");
+    assert_eq!(response.total_spending_cents, 9000);
+
+    let top = &response.merchants[0];
+    assert_eq!(top.merchant_name, bakery);
+    assert_eq!(top.merchant_id, None);
+    assert_eq!(top.transaction_count, 2);
+    assert_eq!(top.amount_cents, 6000);
+    assert!((top.average_ticket - 30.0).abs() < 0.01);
+    assert_eq!(top.previous_amount_cents, 4000);
+    assert!((top.change_percent.unwrap() - 50.0).abs() < 0.01);
+
+    let books = &response.merchants[1];
+    assert_eq!(books.merchant_name, bookshop);
+    assert_eq!(books.amount_cents, 3000);
+    assert_eq!(books.change_percent, None, "No previous spending means no trend");
+
+    let limited = get_spending_by_merchant_impl(db, "2019-05-01", "2019-05-30", Some(account_id), Some(1))
+        .await
+        .unwrap();
+    assert_eq!(limited.merchants.len(), 1, "Should respect limit");
+    assert_eq!(limited.total_spending_cents, 9000, "Total covers all merchants");
+}
+
+#[tokio::test]
+async fn test_get_spending_by_merchant_groups_linked_merchants() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Merchant Linked Test").await;
+    let name = super::unique_word("Grocer");
+
+    let ids = super::fixtures::insert_test_transactions(
+        db,
+        account_id,
+        vec![
+            super::fixtures::TestTransaction::new("2019-09-03", -2500, "Food").with_merchant(&format!("{} #12", name)),
+            super::fixtures::TestTransaction::new("2019-09-04", -3500, "Food").with_merchant(&format!("{} #98", name)),
+        ],
+    )
+    .await;
+
+    let (merchant_id,): (i64,) = sqlx::query_as("INSERT INTO merchants (name) VALUES (?) RETURNING id")
+        .bind(&name)
+        .fetch_one(db)
+        .await
+        .unwrap();
+    for id in &ids {
+        sqlx::query("UPDATE transactions SET merchant_id = ? WHERE id = ?")
+            .bind(merchant_id)
+            .bind(id)
+            .execute(db)
+            .await
+            .unwrap();
+    }
+
+    let response = get_spending_by_merchant_impl(db, "2019-09-01", "2019-09-30", Some(account_id), None)
+        .await
+        .unwrap();
+    assert_eq!(response.merchants.len(), 1, "Raw descriptors should roll up to the linked merchant");
+    assert_eq!(response.merchants[0].merchant_id, Some(merchant_id));
+    assert_eq!(response.merchants[0].merchant_name, name);
+    assert_eq!(response.merchants[0].transaction_count, 2);
+}
+
+#[tokio::test]
+async fn test_get_spending_by_merchant_invalid_range() {
+    let db = super::get_test_db_pool().await;
+
+    let result = get_spending_by_merchant_impl(db, "2019-05-30", "2019-05-01", None, None).await;
+    assert!(result.is_err(), "End before start should be rejected");
+
+    let result = get_spending_by_merchant_impl(db, "not-a-date", "2019-05-01", None, None).await;
+    assert!(result.is_err(), "Invalid date should be rejected");
+}
@@ -0,0 +1,116 @@
+use budget_balancer_lib::commands::category_commands::create_category_impl;
+use budget_balancer_lib::commands::freeze_commands::{
+    create_spending_freeze_impl, delete_spending_freeze_impl, flag_freeze_violations_impl,
+    get_freeze_status_impl, lift_spending_freeze_impl,
+};
+use budget_balancer_lib::commands::notification_commands::list_notifications_impl;
+use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::models::notification::NotificationFilter;
+use budget_balancer_lib::models::spending_freeze::NewSpendingFreeze;
+
+async fn create_unique_category(db: &sqlx::SqlitePool, prefix: &str) -> i64 {
+    create_category_impl(db, NewCategory {
+        name: super::unique_name(prefix),
+        icon: None,
+        color: None,
+    })
+    .await
+    .expect("Failed to create category")
+}
+
+#[tokio::test]
+async fn test_category_freeze_tracks_violations_and_streaks() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Freeze Test").await;
+    // A fresh category keeps other tests' transactions out of this freeze
+    let category_id = create_unique_category(db, "Freeze Takeout").await;
+
+    let freeze = create_spending_freeze_impl(db, NewSpendingFreeze {
+        name: "No takeout November".to_string(),
+        category_id: Some(category_id),
+        start_date: "2016-11-01".to_string(),
+        end_date: Some("2016-11-30".to_string()),
+    })
+    .await
+    .expect("Failed to create freeze");
+
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("2016-10-31", -2000, "Pizza").with_category(category_id),
+        super::fixtures::TestTransaction::new("2016-11-05", -1500, "Burritos").with_category(category_id),
+        super::fixtures::TestTransaction::new("2016-11-08", -1250, "Noodles").with_category(category_id),
+        super::fixtures::TestTransaction::new("2016-11-09", 500, "Refund").with_category(category_id),
+    ];
+    let ids = super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    assert_eq!(flag_freeze_violations_impl(db, &ids).await.unwrap(), 2);
+    let notifications = list_notifications_impl(db, Some(NotificationFilter {
+        notification_type: Some("freeze_violation".to_string()),
+        ..Default::default()
+    }))
+    .await
+    .unwrap();
+    assert!(notifications
+        .notifications
+        .iter()
+        .any(|n| n.title == "Spending freeze broken: No takeout November" && n.message.starts_with("2 transactions totaling $27.50")));
+
+    let statuses = get_freeze_status_impl(db, Some("2016-11-10")).await.unwrap();
+    let status = statuses.iter().find(|s| s.freeze.id == freeze.id).expect("Freeze should be listed");
+    assert!(status.active);
+    assert_eq!(status.violation_count, 2);
+    assert_eq!(status.violation_total_cents, 2750);
+    assert_eq!(status.last_violation_date.as_deref(), Some("2016-11-08"));
+    assert_eq!(status.current_streak_days, 2);
+    assert_eq!(status.longest_streak_days, 4);
+    assert_eq!(status.category_name.as_deref().map(|n| n.starts_with("Freeze Takeout")), Some(true));
+
+    // After the freeze ends the streaks stop at its end date
+    let statuses = get_freeze_status_impl(db, Some("2016-12-15")).await.unwrap();
+    let status = statuses.iter().find(|s| s.freeze.id == freeze.id).unwrap();
+    assert!(!status.active);
+    assert_eq!(status.current_streak_days, 22);
+    assert_eq!(status.longest_streak_days, 22);
+
+    delete_spending_freeze_impl(db, freeze.id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_lift_freeze() {
+    let db = super::get_test_db_pool().await;
+    let category_id = create_unique_category(db, "Freeze Lift").await;
+
+    let freeze = create_spending_freeze_impl(db, NewSpendingFreeze {
+        name: "Open-ended freeze".to_string(),
+        category_id: Some(category_id),
+        start_date: "2016-06-01".to_string(),
+        end_date: None,
+    })
+    .await
+    .unwrap();
+    assert!(freeze.end_date.is_none());
+
+    assert!(lift_spending_freeze_impl(db, freeze.id, Some("2016-05-01")).await.is_err());
+    let lifted = lift_spending_freeze_impl(db, freeze.id, Some("2016-06-10")).await.unwrap();
+    assert_eq!(lifted.end_date.as_deref(), Some("2016-06-10"));
+
+    delete_spending_freeze_impl(db, freeze.id).await.unwrap();
+    assert!(delete_spending_freeze_impl(db, freeze.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_create_freeze_validation() {
+    let db = super::get_test_db_pool().await;
+
+    let freeze = |name: &str, category_id: Option<i64>, start: &str, end: Option<&str>| NewSpendingFreeze {
+        name: name.to_string(),
+        category_id,
+        start_date: start.to_string(),
+        end_date: end.map(str::to_string),
+    };
+
+    assert!(create_spending_freeze_impl(db, freeze("  ", None, "2016-01-01", None)).await.is_err());
+    assert!(create_spending_freeze_impl(db, freeze("Bad date", None, "2016-13-01", None)).await.is_err());
+    assert!(create_spending_freeze_impl(db, freeze("Backwards", None, "2016-02-01", Some("2016-01-01"))).await.is_err());
+    assert!(create_spending_freeze_impl(db, freeze("Missing category", Some(999_999), "2016-01-01", None)).await.is_err());
+    assert!(get_freeze_status_impl(db, Some("not-a-date")).await.is_err());
+}
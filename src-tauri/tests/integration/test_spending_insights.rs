@@ -0,0 +1,88 @@
+use budget_balancer_lib::commands::analytics_commands::get_spending_insights_impl;
+use budget_balancer_lib::commands::category_commands::create_category_impl;
+use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::services::insight_generator::InsightDetail;
+use sqlx::SqlitePool;
+
+async fn create_category(db: &SqlitePool, name: &str) -> i64 {
+    create_category_impl(db, NewCategory {
+        name: super::unique_name(name),
+        icon: None,
+        color: None,
+    })
+    .await
+    .expect("Failed to create category")
+}
+
+#[tokio::test]
+async fn test_spending_insights() {
+    let db = super::get_test_db_pool().await;
+
+    // Movers compare every category, so start from an empty 1987
+    sqlx::query("DELETE FROM transactions WHERE date >= '1987-01-01' AND date <= '1987-12-31'")
+        .execute(db)
+        .await
+        .unwrap();
+
+    let account_id = super::fixtures::create_test_account(db, "Insights").await;
+    let dining = create_category(db, "Insight Dining").await;
+    let travel = create_category(db, "Insight Travel").await;
+    let cafe = super::unique_name("Corner Cafe");
+    let regular = super::unique_name("Regular Diner");
+
+    use super::fixtures::TestTransaction;
+    super::fixtures::insert_test_transactions(db, account_id, vec![
+        // Previous period: 1987-02-01..1987-02-28
+        TestTransaction::new("1987-02-10", -5000, "Diner").with_category(dining).with_merchant(&regular),
+        TestTransaction::new("1987-02-20", -40_000, "Flights").with_category(travel),
+        // Current period, dining rising every week
+        TestTransaction::new("1987-03-07", -2000, "Diner").with_category(dining).with_merchant(&regular),
+        TestTransaction::new("1987-03-14", -4000, "Cafe").with_category(dining).with_merchant(&cafe),
+        TestTransaction::new("1987-03-21", -6000, "Diner").with_category(dining).with_merchant(&regular),
+        TestTransaction::new("1987-03-28", -8000, "Tasting menu").with_category(dining),
+    ])
+    .await;
+
+    let result = get_spending_insights_impl(db, None, Some("1987-03-01"), Some("1987-03-28"))
+        .await
+        .expect("Failed to build insights");
+
+    assert_eq!(result.previous_period.start_date, "1987-02-01");
+    assert_eq!(result.previous_period.end_date, "1987-02-28");
+    let details: Vec<&InsightDetail> = result.insights.iter().map(|i| &i.detail).collect();
+
+    assert!(details.iter().any(|d| matches!(d,
+        InsightDetail::CategoryIncrease { category_id, change_cents, .. } if *category_id == dining && *change_cents == 15_000
+    )), "Dining should be the top increase: {:?}", details);
+    assert!(details.iter().any(|d| matches!(d,
+        InsightDetail::CategoryDecrease { category_id, amount_cents, .. } if *category_id == travel && *amount_cents == 0
+    )), "Travel should show as a decrease: {:?}", details);
+
+    let new_merchants: Vec<&str> = details
+        .iter()
+        .filter_map(|d| match d {
+            InsightDetail::NewMerchant { merchant_name, .. } => Some(merchant_name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(new_merchants, vec![cafe.as_str()], "Only the cafe is new this period");
+
+    assert!(matches!(
+        details.iter().find(|d| matches!(d, InsightDetail::LargeTransaction { .. })),
+        Some(InsightDetail::LargeTransaction { amount_cents, .. }) if *amount_cents == -8000
+    ));
+
+    assert!(details.iter().any(|d| matches!(d,
+        InsightDetail::RisingStreak { category_id, weeks, latest_amount_cents, .. }
+            if *category_id == dining && *weeks == 4 && *latest_amount_cents == 8000
+    )), "Dining has risen four weeks running: {:?}", details);
+
+    assert!(result.insights.iter().all(|i| !i.message.is_empty()));
+}
+
+#[tokio::test]
+async fn test_spending_insights_rejects_half_open_range() {
+    let db = super::get_test_db_pool().await;
+    let result = get_spending_insights_impl(db, None, Some("1987-03-01"), None).await;
+    assert!(result.is_err());
+}
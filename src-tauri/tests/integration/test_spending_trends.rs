@@ -1,4 +1,9 @@
-use budget_balancer_lib::commands::analytics_commands::get_spending_trends_impl;
+use budget_balancer_lib::commands::account_commands::set_statement_closing_day_impl;
+use budget_balancer_lib::commands::analytics_commands::{get_spending_heatmap_impl, get_spending_trends_impl};
+use budget_balancer_lib::commands::category_commands::create_category_impl;
+use budget_balancer_lib::commands::transaction_commands::{delete_transaction_impl, update_transaction_impl};
+use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::models::transaction::UpdateTransaction;
 
 #[tokio::test]
 async fn test_get_spending_trends_monthly() {
@@ -7,9 +12,9 @@ async fn test_get_spending_trends_monthly() {
 
     // Create transactions across multiple months
     let transactions = vec![
-        super::fixtures::TestTransaction::new("2025-01-15", -100.00, "Groceries").with_merchant("Whole Foods"),
-        super::fixtures::TestTransaction::new("2025-02-20", -150.00, "Groceries").with_merchant("Whole Foods"),
-        super::fixtures::TestTransaction::new("2025-03-10", -120.00, "Groceries").with_merchant("Whole Foods"),
+        super::fixtures::TestTransaction::new("2025-01-15", -10_000, "Groceries").with_merchant("Whole Foods"),
+        super::fixtures::TestTransaction::new("2025-02-20", -15_000, "Groceries").with_merchant("Whole Foods"),
+        super::fixtures::TestTransaction::new("2025-03-10", -12_000, "Groceries").with_merchant("Whole Foods"),
     ];
     super::fixtures::insert_test_transactions(db, account_id, transactions).await;
 
@@ -20,6 +25,8 @@ async fn test_get_spending_trends_monthly() {
         "2025-12-31",
         "monthly",
         None,
+        None,
+        None,
     )
     .await;
 
@@ -37,8 +44,8 @@ async fn test_get_spending_trends_for_category() {
 
     // Create transactions directly
     let transactions = vec![
-        super::fixtures::TestTransaction::new("2025-01-15", -50.00, "Coffee").with_merchant("Starbucks"),
-        super::fixtures::TestTransaction::new("2025-02-20", -60.00, "Coffee").with_merchant("Starbucks"),
+        super::fixtures::TestTransaction::new("2025-01-15", -5000, "Coffee").with_merchant("Starbucks"),
+        super::fixtures::TestTransaction::new("2025-02-20", -6000, "Coffee").with_merchant("Starbucks"),
     ];
     super::fixtures::insert_test_transactions(db, account_id, transactions).await;
 
@@ -49,6 +56,8 @@ async fn test_get_spending_trends_for_category() {
         "2025-12-31",
         "monthly",
         Some(1),
+        None,
+        None,
     )
     .await;
 
@@ -64,6 +73,8 @@ async fn test_get_spending_trends_weekly() {
         "2025-01-31",
         "weekly",
         None,
+        None,
+        None,
     )
     .await;
 
@@ -72,3 +83,208 @@ async fn test_get_spending_trends_weekly() {
     let response = result.unwrap();
     assert!(response.data_points.len() >= 4, "Should have at least 4 weekly data points for January");
 }
+
+#[tokio::test]
+async fn test_get_spending_trends_by_statement_period() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Statement Trends Test").await;
+    let account = set_statement_closing_day_impl(db, account_id, Some(20)).await.unwrap();
+    assert_eq!(account.statement_closing_day, Some(20));
+
+    // Far in the past and in a unique category so other tests don't affect the totals
+    let category_id = create_category_impl(
+        db,
+        NewCategory {
+            name: super::unique_name("Statement Trends"),
+            icon: None,
+            color: None,
+        },
+    )
+    .await
+    .unwrap();
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("2012-03-20", -1000, "Closing day").with_category(category_id),
+        super::fixtures::TestTransaction::new("2012-03-21", -2500, "Next cycle").with_category(category_id),
+        super::fixtures::TestTransaction::new("2012-04-05", -500, "Same cycle").with_category(category_id),
+    ];
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let result = get_spending_trends_impl(db, "2012-03-01", "2012-04-30", "statement", Some(category_id), None, Some(account_id))
+        .await
+        .expect("Statement trends should succeed");
+    let points: Vec<(&str, i64)> = result.data_points.iter().map(|p| (p.date.as_str(), p.amount_cents)).collect();
+    assert_eq!(points, vec![("2012-02-21", 1000), ("2012-03-21", 3000), ("2012-04-21", 0)]);
+
+    // Without a closing day, statement periods are calendar months
+    set_statement_closing_day_impl(db, account_id, None).await.unwrap();
+    let calendar = get_spending_trends_impl(db, "2012-03-01", "2012-04-30", "statement", Some(category_id), None, Some(account_id))
+        .await
+        .unwrap();
+    assert_eq!(calendar.data_points.len(), 2);
+    assert_eq!(calendar.data_points[0].date, "2012-03-01");
+
+    assert!(set_statement_closing_day_impl(db, account_id, Some(32)).await.is_err());
+    assert!(get_spending_trends_impl(db, "2012-03-01", "2012-04-30", "statement", None, None, None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_monthly_trends_follow_transaction_changes() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, &super::unique_name("Monthly Totals")).await;
+    let category_id = create_category_impl(
+        db,
+        NewCategory {
+            name: super::unique_name("Monthly Totals"),
+            icon: None,
+            color: None,
+        },
+    )
+    .await
+    .unwrap();
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("1974-01-05", -1010, "Lunch").with_category(category_id),
+        super::fixtures::TestTransaction::new("1974-01-20", -2020, "Dinner").with_category(category_id),
+        super::fixtures::TestTransaction::new("1974-02-03", 50_000, "Refund").with_category(category_id),
+        super::fixtures::TestTransaction::new("1974-03-15", -700, "Snack").with_category(category_id),
+    ];
+    let ids = super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let monthly = |db| get_spending_trends_impl(db, "1974-01-10", "1974-03-01", "monthly", Some(category_id), None, None);
+    let points = |trends: budget_balancer_lib::services::trends_calculator::SpendingTrends| {
+        trends.data_points.into_iter().map(|p| (p.date, p.amount_cents, p.transaction_count)).collect::<Vec<_>>()
+    };
+
+    let trends = monthly(db).await.expect("Monthly trends should succeed");
+    assert_eq!(
+        points(trends),
+        vec![
+            ("1974-01-01".to_string(), 3030, 2),
+            ("1974-02-01".to_string(), 0, 0),
+            ("1974-03-01".to_string(), 700, 1),
+        ],
+        "Whole months are counted and income is left out"
+    );
+
+    // Moving a purchase to another month and deleting one are reflected right away
+    update_transaction_impl(
+        db,
+        UpdateTransaction { id: ids[1], date: Some("1974-02-10".to_string()), amount_cents: Some(-500), ..Default::default() },
+        false,
+    )
+    .await
+    .unwrap();
+    delete_transaction_impl(db, ids[3], false).await.unwrap();
+
+    let trends = monthly(db).await.unwrap();
+    assert_eq!(
+        points(trends),
+        vec![
+            ("1974-01-01".to_string(), 1010, 1),
+            ("1974-02-01".to_string(), 500, 1),
+            ("1974-03-01".to_string(), 0, 0),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_spending_trends_for_one_account() {
+    let db = super::get_test_db_pool().await;
+    let checking = super::fixtures::create_test_account(db, &super::unique_name("Trends Checking")).await;
+    let card = super::fixtures::create_test_account(db, &super::unique_name("Trends Card")).await;
+    let category_id = create_category_impl(
+        db,
+        NewCategory {
+            name: super::unique_name("Account Trends"),
+            icon: None,
+            color: None,
+        },
+    )
+    .await
+    .unwrap();
+    super::fixtures::insert_test_transactions(
+        db,
+        checking,
+        vec![
+            super::fixtures::TestTransaction::new("1973-05-02", -4000, "Rent share").with_category(category_id),
+            super::fixtures::TestTransaction::new("1973-06-09", -1500, "Utilities").with_category(category_id),
+        ],
+    )
+    .await;
+    super::fixtures::insert_test_transactions(
+        db,
+        card,
+        vec![super::fixtures::TestTransaction::new("1973-05-02", -9900, "Gadget").with_category(category_id)],
+    )
+    .await;
+
+    let amounts = |trends: budget_balancer_lib::services::trends_calculator::SpendingTrends| {
+        trends.data_points.into_iter().map(|p| (p.date, p.amount_cents)).collect::<Vec<_>>()
+    };
+
+    let monthly = get_spending_trends_impl(db, "1973-05-01", "1973-06-30", "monthly", Some(category_id), Some(checking), None)
+        .await
+        .expect("Monthly trends for an account should succeed");
+    assert_eq!(monthly.total_spending_cents, 5500);
+    assert_eq!(amounts(monthly), vec![("1973-05-01".to_string(), 4000), ("1973-06-01".to_string(), 1500)]);
+
+    let daily = get_spending_trends_impl(db, "1973-05-01", "1973-06-30", "daily", Some(category_id), Some(card), None)
+        .await
+        .unwrap();
+    assert_eq!(amounts(daily), vec![("1973-05-02".to_string(), 9900)]);
+
+    // Both accounts together, and the account's own statement periods when no other is named
+    let all = get_spending_trends_impl(db, "1973-05-01", "1973-06-30", "monthly", Some(category_id), None, None)
+        .await
+        .unwrap();
+    assert_eq!(all.total_spending_cents, 15_400);
+    let statement = get_spending_trends_impl(db, "1973-05-01", "1973-06-30", "statement", Some(category_id), Some(checking), None)
+        .await
+        .unwrap();
+    assert_eq!(statement.total_spending_cents, 5500);
+}
+
+#[tokio::test]
+async fn test_spending_heatmap() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, &super::unique_name("Heatmap")).await;
+    let category_id = create_category_impl(
+        db,
+        NewCategory {
+            name: super::unique_name("Heatmap"),
+            icon: None,
+            color: None,
+        },
+    )
+    .await
+    .unwrap();
+    // 1975-09-01 was a Monday, 1975-09-07 a Sunday
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("1975-09-01", -1000, "Monday").with_category(category_id),
+        super::fixtures::TestTransaction::new("1975-09-08", -550, "Next Monday").with_category(category_id),
+        super::fixtures::TestTransaction::new("1975-09-07", -2000, "Sunday").with_category(category_id),
+        super::fixtures::TestTransaction::new("1975-10-01", -300, "First again").with_category(category_id),
+        super::fixtures::TestTransaction::new("1975-09-02", 10_000, "Refund").with_category(category_id),
+    ];
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let heatmap = get_spending_heatmap_impl(db, "1975-09-01", "1975-10-31", Some(category_id))
+        .await
+        .expect("Heatmap should succeed");
+    assert_eq!(heatmap.total_spending_cents, 3850);
+
+    assert_eq!(heatmap.by_weekday.len(), 7);
+    let weekdays: Vec<(u32, i64, i64)> =
+        heatmap.by_weekday.iter().map(|c| (c.bucket, c.amount_cents, c.transaction_count)).collect();
+    assert_eq!(weekdays[0], (0, 1550, 2), "Mondays");
+    assert_eq!(weekdays[2], (2, 300, 1), "1975-10-01 was a Wednesday");
+    assert_eq!(weekdays[6], (6, 2000, 1), "Sundays come last");
+    assert_eq!(weekdays[1], (1, 0, 0), "Income is left out");
+
+    assert_eq!(heatmap.by_day_of_month.len(), 31);
+    let day = |n: usize| &heatmap.by_day_of_month[n - 1];
+    assert_eq!((day(1).bucket, day(1).amount_cents, day(1).transaction_count), (1, 1300, 2));
+    assert_eq!((day(7).amount_cents, day(8).amount_cents), (2000, 550));
+    assert_eq!((day(31).bucket, day(31).amount_cents), (31, 0));
+
+    assert!(get_spending_heatmap_impl(db, "1975-10-31", "1975-09-01", None).await.is_err());
+}
@@ -0,0 +1,132 @@
+use budget_balancer_lib::commands::account_commands::{
+    create_account_impl, get_statement_summary_impl, set_statement_cycle_impl,
+};
+use budget_balancer_lib::commands::debt_commands::{
+    create_debt_impl, link_debt_account_impl, sync_debt_to_statement_impl,
+};
+use budget_balancer_lib::errors::DebtError;
+use budget_balancer_lib::models::account::{AccountType, NewAccount};
+use budget_balancer_lib::models::debt::NewDebt;
+use chrono::NaiveDate;
+use serial_test::serial;
+use sqlx::SqlitePool;
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+/// A card closing on the 20th, due on the 15th, with one statement's worth of activity
+async fn create_card(db: &SqlitePool) -> i64 {
+    let account_id = create_account_impl(db, NewAccount {
+        name: super::unique_name("Statement Card"),
+        account_type: AccountType::CreditCard,
+        initial_balance_cents: 0,
+    })
+    .await
+    .expect("Failed to create card");
+
+    use super::fixtures::TestTransaction;
+    super::fixtures::insert_test_transactions(db, account_id, vec![
+        TestTransaction::new("1984-02-10", -10_000, "Previous statement"),
+        TestTransaction::new("1984-02-25", -5000, "Groceries"),
+        TestTransaction::new("1984-03-05", -3000, "Fuel"),
+        TestTransaction::new("1984-03-10", 10_000, "Card payment"),
+        TestTransaction::new("1984-03-25", -99_900, "Next statement"),
+    ])
+    .await;
+
+    let account = set_statement_cycle_impl(db, account_id, Some(20), Some(15))
+        .await
+        .expect("Failed to set statement cycle");
+    assert_eq!(account.statement_closing_day, Some(20));
+    assert_eq!(account.statement_due_day, Some(15));
+    account_id
+}
+
+#[tokio::test]
+async fn test_get_statement_summary() {
+    let db = super::get_test_db_pool().await;
+    let account_id = create_card(db).await;
+
+    let summary = get_statement_summary_impl(db, account_id, Some("1984-03-01"), date("1984-06-01"))
+        .await
+        .expect("Failed to summarize statement");
+
+    assert_eq!(summary.period_start, "1984-02-21");
+    assert_eq!(summary.period_end, "1984-03-20");
+    assert_eq!(summary.due_date.as_deref(), Some("1984-04-15"));
+    assert_eq!(summary.opening_balance_cents, -10_000);
+    assert_eq!(summary.charges_cents, 8000);
+    assert_eq!(summary.payments_cents, 10_000);
+    assert_eq!(summary.closing_balance_cents, -8000);
+    assert_eq!(summary.amount_due_cents, 8000);
+    assert_eq!(summary.transaction_count, 3);
+    assert!(summary.closed);
+    assert_eq!(summary.linked_debt_id, None);
+
+    // Without a date: the latest statement that has closed
+    let latest = get_statement_summary_impl(db, account_id, None, date("1984-03-25")).await.unwrap();
+    assert_eq!(latest.period_end, "1984-03-20");
+
+    let open = get_statement_summary_impl(db, account_id, Some("1984-03-25"), date("1984-03-25")).await.unwrap();
+    assert!(!open.closed);
+    assert_eq!(open.opening_balance_cents, -8000);
+}
+
+#[tokio::test]
+async fn test_set_statement_cycle_validation() {
+    let db = super::get_test_db_pool().await;
+    let checking_id = super::fixtures::create_test_account(db, "Statement Checking").await;
+    let result = set_statement_cycle_impl(db, checking_id, Some(20), Some(15)).await;
+    assert!(result.unwrap_err().contains("credit card"));
+
+    let card_id = create_card(db).await;
+    assert!(set_statement_cycle_impl(db, card_id, Some(20), Some(32)).await.is_err());
+    assert!(set_statement_cycle_impl(db, card_id, Some(0), None).await.is_err());
+    assert!(set_statement_cycle_impl(db, -1, Some(20), None).await.is_err());
+
+    assert!(get_statement_summary_impl(db, card_id, Some("03/01/1984"), date("1984-06-01")).await.is_err());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_debt_synced_to_statement() {
+    let db = super::get_test_db_pool().await;
+    let account_id = create_card(db).await;
+    let debt_name = super::unique_name("Statement Debt");
+    let debt_id = create_debt_impl(db, NewDebt {
+        name: debt_name.clone(),
+        balance_cents: 50_000,
+        interest_rate: 19.99,
+        min_payment_cents: 2500,
+    })
+    .await
+    .expect("Failed to create debt");
+
+    let unlinked = sync_debt_to_statement_impl(db, debt_id, None, date("1984-03-25")).await;
+    assert!(matches!(unlinked, Err(DebtError::NotLinked(_))), "Got {:?}", unlinked);
+
+    let debt = link_debt_account_impl(db, debt_id, Some(account_id)).await.expect("Failed to link debt");
+    assert_eq!(debt.account_id, Some(account_id));
+    assert_eq!(debt.due_day, 15, "Linked debt takes the card's due day");
+
+    let summary = get_statement_summary_impl(db, account_id, None, date("1984-03-25")).await.unwrap();
+    assert_eq!(summary.linked_debt_id, Some(debt_id));
+
+    let synced = sync_debt_to_statement_impl(db, debt_id, None, date("1984-03-25"))
+        .await
+        .expect("Failed to sync debt");
+    assert_eq!(synced.balance_cents, 8000);
+
+    // Changing the card's due day moves the linked debt's too
+    set_statement_cycle_impl(db, account_id, Some(20), Some(5)).await.unwrap();
+    let unlinked = link_debt_account_impl(db, debt_id, None).await.unwrap();
+    assert_eq!(unlinked.due_day, 5);
+    assert_eq!(unlinked.account_id, None);
+
+    let checking_id = super::fixtures::create_test_account(db, "Statement Debt Checking").await;
+    let result = link_debt_account_impl(db, debt_id, Some(checking_id)).await;
+    assert!(matches!(result, Err(DebtError::NotCreditCard(_))), "Got {:?}", result);
+
+    sqlx::query("DELETE FROM debts WHERE id = ?").bind(debt_id).execute(db).await.unwrap();
+}
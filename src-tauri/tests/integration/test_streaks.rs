@@ -0,0 +1,32 @@
+use budget_balancer_lib::commands::streak_commands::get_streaks_impl;
+
+#[tokio::test]
+async fn test_get_streaks() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Streaks Test").await;
+
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("2014-03-05", 150_000, "Paycheck").with_category(9),
+        super::fixtures::TestTransaction::new("2014-03-10", -1250, "Coffee").with_category(2),
+    ];
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let streaks = get_streaks_impl(db, Some("2014-03-10")).await;
+    assert!(streaks.is_ok(), "Failed to get streaks: {:?}", streaks);
+
+    let streaks = streaks.unwrap();
+    assert_eq!(streaks.as_of, "2014-03-10");
+    // Spending on the as-of date breaks the no-spend streak; income doesn't count as spending
+    assert_eq!(streaks.no_spend_days.current, 0);
+    for metric in [streaks.under_budget_months, streaks.on_time_payment_months, streaks.no_spend_days] {
+        assert!(metric.current <= metric.longest, "Current streak can't exceed the longest: {:?}", metric);
+    }
+}
+
+#[tokio::test]
+async fn test_get_streaks_invalid_date() {
+    let db = super::get_test_db_pool().await;
+
+    let result = get_streaks_impl(db, Some("2014-13-01")).await;
+    assert!(result.is_err(), "Invalid date should be rejected");
+}
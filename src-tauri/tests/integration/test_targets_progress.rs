@@ -1,4 +1,8 @@
 use budget_balancer_lib::commands::analytics_commands::{create_spending_target_impl, get_spending_targets_progress_impl};
+use budget_balancer_lib::commands::category_commands::create_category_impl;
+use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::models::spending_target::NewSpendingTarget;
+use budget_balancer_lib::utils::money::Cents;
 
 #[tokio::test]
 async fn test_get_spending_targets_progress() {
@@ -8,6 +12,7 @@ async fn test_get_spending_targets_progress() {
         Some("monthly".to_string()),
         None,
         None,
+        None,
     )
     .await;
 
@@ -33,6 +38,7 @@ async fn test_get_spending_targets_progress_with_custom_range() {
         None,
         Some("2025-01-01".to_string()),
         Some("2025-01-31".to_string()),
+        None,
     )
     .await;
 
@@ -46,15 +52,24 @@ async fn test_get_spending_targets_progress_with_custom_range() {
 #[tokio::test]
 async fn test_target_status_calculation() {
     let db = super::get_test_db_pool().await;
+    // A fresh category each run, since overlapping targets for one category are rejected
+    let category_id = create_category_impl(db, NewCategory {
+        name: super::unique_name("Status Target Category"),
+        icon: None,
+        color: None,
+    })
+    .await
+    .expect("Failed to create category");
+
     // Create a target
-    let target_result = create_spending_target_impl(
-        db,
-        1, // category_id
-        500.0,
-        "monthly",
-        "2025-01-01",
-        None,
-    )
+    let target_result = create_spending_target_impl(db, NewSpendingTarget {
+        category_id: Some(category_id),
+        account_id: None,
+        amount_cents: 50_000,
+        period: "monthly".to_string(),
+        start_date: "2025-01-01".to_string(),
+        end_date: None,
+    })
     .await;
 
     assert!(target_result.is_ok(), "Failed to create spending target");
@@ -65,6 +80,7 @@ async fn test_target_status_calculation() {
         Some("monthly".to_string()),
         None,
         None,
+        None,
     )
     .await;
 
@@ -81,3 +97,69 @@ async fn test_target_status_calculation() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_overall_and_account_targets() {
+    let db = super::get_test_db_pool().await;
+    // Overall targets are shared by the whole database, so clear this test's from earlier runs
+    sqlx::query("DELETE FROM spending_targets WHERE scope = 'overall' AND start_date = '1996-03-01'")
+        .execute(db)
+        .await
+        .unwrap();
+
+    let account_id = super::fixtures::create_test_account(db, "Target Checking").await;
+    super::fixtures::insert_test_transactions(db, account_id, vec![
+        super::fixtures::TestTransaction::new("1996-03-05", -10_000, "Groceries").with_category(1),
+        super::fixtures::TestTransaction::new("1996-03-20", -5000, "Dinner").with_category(2),
+        super::fixtures::TestTransaction::new("1996-03-25", 50_000, "Paycheck"),
+    ])
+    .await;
+
+    let month = |category_id: Option<i64>, account_id: Option<i64>, amount_cents: Cents| NewSpendingTarget {
+        category_id,
+        account_id,
+        amount_cents,
+        period: "monthly".to_string(),
+        start_date: "1996-03-01".to_string(),
+        end_date: Some("1996-03-31".to_string()),
+    };
+    let overall_id = create_spending_target_impl(db, month(None, None, 300_000))
+        .await
+        .expect("Failed to create overall target");
+    let account_target_id = create_spending_target_impl(db, month(None, Some(account_id), 12_000))
+        .await
+        .expect("Failed to create account target");
+
+    // Overlapping duplicates are rejected, as is a target for both a category and an account
+    let overlapping = NewSpendingTarget {
+        start_date: "1996-03-15".to_string(),
+        end_date: None,
+        ..month(None, None, 250_000)
+    };
+    let error = create_spending_target_impl(db, overlapping).await.unwrap_err();
+    assert!(error.contains("already covers"), "Unexpected error: {}", error);
+    assert!(create_spending_target_impl(db, month(Some(1), Some(account_id), 10_000)).await.is_err());
+
+    let response = get_spending_targets_progress_impl(
+        db,
+        None,
+        Some("1996-03-01".to_string()),
+        Some("1996-03-31".to_string()),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let account_target = response.targets.iter().find(|t| t.target_id == account_target_id).unwrap();
+    assert_eq!(account_target.scope, "account");
+    assert_eq!(account_target.account_id, Some(account_id));
+    assert!(account_target.category_name.starts_with("Target Checking"));
+    assert_eq!(account_target.actual_amount_cents, 15_000);
+    assert_eq!(account_target.status, "over");
+
+    let overall = response.targets.iter().find(|t| t.target_id == overall_id).unwrap();
+    assert_eq!(overall.scope, "overall");
+    assert_eq!(overall.category_id, None);
+    assert_eq!(overall.category_name, "All spending");
+    assert!(overall.actual_amount_cents >= 15_000 - 1);
+}
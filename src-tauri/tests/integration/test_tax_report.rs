@@ -0,0 +1,81 @@
+use budget_balancer_lib::commands::analytics_commands::generate_tax_report_impl;
+use budget_balancer_lib::commands::category_commands::{create_category_impl, set_category_tax_deductible_impl};
+use budget_balancer_lib::commands::transaction_commands::bulk_set_tax_deductible_impl;
+use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::services::tax_report::TaxReportBuilder;
+
+#[tokio::test]
+async fn test_tax_report_follows_category_flags_and_overrides() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Tax Checking").await;
+    let charity = create_category_impl(db, NewCategory {
+        name: super::unique_name("Charity"),
+        icon: None,
+        color: None,
+    })
+    .await
+    .unwrap();
+    let groceries = create_category_impl(db, NewCategory {
+        name: super::unique_name("Tax Groceries"),
+        icon: None,
+        color: None,
+    })
+    .await
+    .unwrap();
+    let category = set_category_tax_deductible_impl(db, charity, true).await.unwrap();
+    assert!(category.tax_deductible);
+
+    let ids = super::fixtures::insert_test_transactions(db, account_id, vec![
+        super::fixtures::TestTransaction::new("1979-03-01", -5000, "Food bank").with_category(charity),
+        super::fixtures::TestTransaction::new("1979-06-01", -2500, "Animal shelter").with_category(charity),
+        super::fixtures::TestTransaction::new("1979-07-01", -3000, "Office supplies").with_category(groceries),
+        super::fixtures::TestTransaction::new("1980-01-02", -9900, "Next year").with_category(charity),
+    ])
+    .await;
+
+    // The shelter gift wasn't deductible after all; the office supplies were
+    bulk_set_tax_deductible_impl(db, vec![ids[1]], Some(false), false).await.unwrap();
+    let result = bulk_set_tax_deductible_impl(db, vec![ids[2], i64::MAX], Some(true), false).await.unwrap();
+    assert_eq!(result.failed_ids, vec![i64::MAX]);
+
+    let report = TaxReportBuilder::build(db, 1979).await.unwrap();
+    let ours: Vec<_> = report
+        .categories
+        .iter()
+        .filter(|c| c.category_id == charity || c.category_id == groceries)
+        .collect();
+    assert_eq!(ours.len(), 2);
+    let charity_group = ours.iter().find(|c| c.category_id == charity).unwrap();
+    assert_eq!(charity_group.total_cents, 5000);
+    assert_eq!(charity_group.transactions.len(), 1);
+    assert_eq!(charity_group.transactions[0].id, ids[0]);
+    assert_eq!(ours.iter().find(|c| c.category_id == groceries).unwrap().total_cents, 3000);
+
+    // Clearing the override goes back to the category's flag
+    bulk_set_tax_deductible_impl(db, vec![ids[1]], None, false).await.unwrap();
+    let report = TaxReportBuilder::build(db, 1979).await.unwrap();
+    let charity_group = report.categories.iter().find(|c| c.category_id == charity).unwrap();
+    assert_eq!(charity_group.total_cents, 7500);
+}
+
+#[tokio::test]
+async fn test_generate_tax_report_writes_files() {
+    let db = super::get_test_db_pool().await;
+    let dir = std::env::temp_dir();
+    let csv_path = dir.join(super::unique_name("tax-report").replace(' ', "_") + ".csv");
+    let pdf_path = dir.join(super::unique_name("tax-report").replace(' ', "_") + ".pdf");
+
+    let csv = generate_tax_report_impl(db, 1979, "csv", csv_path.to_str().unwrap()).await.unwrap();
+    assert!(csv.file_size > 0);
+    let content = std::fs::read_to_string(&csv_path).unwrap();
+    assert!(content.starts_with("Category,Date,Description,Merchant,Account,Amount"));
+    assert!(content.trim_end().lines().last().unwrap().starts_with("Total 1979,"));
+
+    generate_tax_report_impl(db, 1979, "pdf", pdf_path.to_str().unwrap()).await.unwrap();
+    assert!(std::fs::read(&pdf_path).unwrap().starts_with(b"%PDF"));
+
+    assert!(generate_tax_report_impl(db, 1979, "xlsx", csv_path.to_str().unwrap()).await.is_err());
+    assert!(generate_tax_report_impl(db, 79, "csv", csv_path.to_str().unwrap()).await.is_err());
+    std::fs::remove_file(csv_path).ok();
+    std::fs::remove_file(pdf_path).ok();
+}
@@ -0,0 +1,25 @@
+use budget_balancer_lib::commands::settings_commands::update_setting_impl;
+use budget_balancer_lib::utils::dates::{self, UserTimezone};
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn test_timezone_setting_decides_today() {
+    let db = super::get_test_db_pool().await;
+
+    assert!(update_setting_impl(db, "timezone", Some("Mars/Olympus")).await.is_err());
+    assert!(update_setting_impl(db, "timezone", Some("+15:00")).await.is_err());
+
+    let setting = update_setting_impl(db, "timezone", Some("UTC")).await.unwrap();
+    assert_eq!(setting.value.as_deref(), Some("UTC"));
+    assert_eq!(UserTimezone::load(db).await, "+00:00".parse().unwrap());
+    assert_eq!(dates::today(db).await, chrono::Utc::now().date_naive());
+
+    update_setting_impl(db, "timezone", Some("Pacific/Auckland")).await.unwrap();
+    assert_eq!(UserTimezone::load(db).await, UserTimezone::Zone(chrono_tz::Pacific::Auckland));
+    assert_eq!(dates::today(db).await, chrono::Utc::now().with_timezone(&chrono_tz::Pacific::Auckland).date_naive());
+
+    // Clearing the setting goes back to the system timezone
+    update_setting_impl(db, "timezone", None).await.unwrap();
+    assert_eq!(UserTimezone::load(db).await, UserTimezone::System);
+}
@@ -0,0 +1,42 @@
+use budget_balancer_lib::commands::tray_commands::get_tray_summary_impl;
+
+#[tokio::test]
+async fn test_get_tray_summary() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Tray Summary Test").await;
+
+    // The test database persists between runs, so compare against a summary taken before inserting
+    let before = get_tray_summary_impl(db, Some("2017-06-20")).await.unwrap();
+
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("2017-06-01", 200_000, "Paycheck").with_category(9),
+        super::fixtures::TestTransaction::new("2017-06-12", -15_000, "Groceries").with_category(1),
+        // Later in the month than the summary date, so not counted yet
+        super::fixtures::TestTransaction::new("2017-06-25", -50_000, "Rent"),
+    ];
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let summary = get_tray_summary_impl(db, Some("2017-06-20")).await;
+    assert!(summary.is_ok(), "Failed to get tray summary: {:?}", summary);
+
+    let summary = summary.unwrap();
+    assert_eq!(summary.as_of, "2017-06-20");
+    assert_eq!(summary.month_income_cents - before.month_income_cents, 200_000);
+    assert_eq!(summary.month_spending_cents - before.month_spending_cents, 15_000);
+    assert_eq!(
+        summary.safe_to_spend_cents,
+        summary.month_income_cents - summary.month_spending_cents - summary.bills_remaining_cents,
+        "Safe to spend should be income minus spending minus unpaid bills"
+    );
+    assert_eq!(summary.actions.len(), 2);
+    assert!(summary.actions.iter().all(|a| a.url.starts_with("budgetbalancer://open?view=")));
+    assert!(summary.tooltip.starts_with("Safe to spend: $"));
+}
+
+#[tokio::test]
+async fn test_get_tray_summary_invalid_date() {
+    let db = super::get_test_db_pool().await;
+
+    let result = get_tray_summary_impl(db, Some("2017-02-30")).await;
+    assert!(result.is_err(), "Invalid date should be rejected");
+}
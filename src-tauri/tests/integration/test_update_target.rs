@@ -1,6 +1,7 @@
 use budget_balancer_lib::commands::analytics_commands::{create_spending_target_impl, update_spending_target_impl};
 use budget_balancer_lib::commands::category_commands::create_category_impl;
 use budget_balancer_lib::models::category::NewCategory;
+use budget_balancer_lib::models::spending_target::NewSpendingTarget;
 
 #[tokio::test]
 async fn test_update_spending_target_amount() {
@@ -9,20 +10,21 @@ async fn test_update_spending_target_amount() {
     let category = NewCategory {
         name: super::unique_name("Update Target Category"),
         icon: Some("🎯".to_string()),
+        color: None,
     };
     let category_id = create_category_impl(db, category)
         .await
         .expect("Failed to create category");
 
     // Create spending target
-    let target_id = create_spending_target_impl(
-        db,
-        category_id,
-        500.0,
-        "monthly",
-        "2025-01-01",
-        None,
-    )
+    let target_id = create_spending_target_impl(db, NewSpendingTarget {
+        category_id: Some(category_id),
+        account_id: None,
+        amount_cents: 50_000,
+        period: "monthly".to_string(),
+        start_date: "2025-01-01".to_string(),
+        end_date: None,
+    })
     .await
     .expect("Failed to create target");
 
@@ -30,7 +32,7 @@ async fn test_update_spending_target_amount() {
     let result = update_spending_target_impl(
         db,
         target_id,
-        Some(600.0),
+        Some(60_000),
         None,
     )
     .await;
@@ -48,20 +50,21 @@ async fn test_update_spending_target_end_date() {
     let category = NewCategory {
         name: super::unique_name("Update End Date Category"),
         icon: Some("📆".to_string()),
+        color: None,
     };
     let category_id = create_category_impl(db, category)
         .await
         .expect("Failed to create category");
 
     // Create spending target
-    let target_id = create_spending_target_impl(
-        db,
-        category_id,
-        500.0,
-        "monthly",
-        "2025-01-01",
-        None,
-    )
+    let target_id = create_spending_target_impl(db, NewSpendingTarget {
+        category_id: Some(category_id),
+        account_id: None,
+        amount_cents: 50_000,
+        period: "monthly".to_string(),
+        start_date: "2025-01-01".to_string(),
+        end_date: None,
+    })
     .await
     .expect("Failed to create target");
 
@@ -77,13 +80,42 @@ async fn test_update_spending_target_end_date() {
     assert!(result.is_ok(), "Should update end date");
 }
 
+#[tokio::test]
+async fn test_update_end_date_cannot_overlap_later_target() {
+    let db = super::get_test_db_pool().await;
+    let category_id = create_category_impl(db, NewCategory {
+        name: super::unique_name("Overlap Target Category"),
+        icon: None,
+        color: None,
+    })
+    .await
+    .expect("Failed to create category");
+
+    let quarter = |start_date: &str, end_date: &str| NewSpendingTarget {
+        category_id: Some(category_id),
+        account_id: None,
+        amount_cents: 50_000,
+        period: "monthly".to_string(),
+        start_date: start_date.to_string(),
+        end_date: Some(end_date.to_string()),
+    };
+    let first_id = create_spending_target_impl(db, quarter("2025-01-01", "2025-03-31")).await.unwrap();
+    create_spending_target_impl(db, quarter("2025-04-01", "2025-06-30"))
+        .await
+        .expect("Back-to-back targets don't overlap");
+
+    let result = update_spending_target_impl(db, first_id, None, Some("2025-04-15")).await;
+    assert!(result.is_err(), "Extending into the next target should be rejected");
+    assert!(update_spending_target_impl(db, first_id, None, Some("2025-03-15")).await.is_ok());
+}
+
 #[tokio::test]
 async fn test_update_nonexistent_target() {
     let db = super::get_test_db_pool().await;
     let result = update_spending_target_impl(
         db,
         99999, // Non-existent ID
-        Some(700.0),
+        Some(70_000),
         None,
     )
     .await;
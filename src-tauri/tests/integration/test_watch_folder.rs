@@ -0,0 +1,76 @@
+use budget_balancer_lib::commands::csv_commands::save_column_mapping_impl;
+use budget_balancer_lib::commands::settings_commands::update_setting_impl;
+use budget_balancer_lib::commands::watch_folder_commands::{scan_watch_folder_impl, WatchedImportStatus};
+use budget_balancer_lib::constants::WATCH_ARCHIVE_DIR;
+use budget_balancer_lib::models::column_mapping::NewColumnMapping;
+use budget_balancer_lib::models::setting::SettingKey;
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn test_scan_imports_recognized_files() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Watched Checking").await;
+    let dir = std::env::temp_dir().join(super::unique_name("watch").replace(' ', "_"));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let reference = super::unique_word("Reference");
+    save_column_mapping_impl(
+        db,
+        NewColumnMapping {
+            source_name: super::unique_name("Watched Bank"),
+            date_col: "Date".to_string(),
+            amount_col: "Amount".to_string(),
+            description_col: "Payee".to_string(),
+            headers: Some(vec!["Date".to_string(), "Payee".to_string(), reference.clone(), "Amount".to_string()]),
+            account_id: Some(account_id),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    std::fs::write(
+        dir.join("a-statement.csv"),
+        format!("Date,Payee,{reference},Amount\n01/05/1957,Watched Grocer,r1,-42.10\n01/06/1957,Watched Cafe,r2,-3.75"),
+    )
+    .unwrap();
+    std::fs::write(dir.join("b-unknown.csv"), "When,What,How Much\n01/07/1957,Mystery,-1.00").unwrap();
+    std::fs::write(dir.join("c-checking.ofx"), "OFXHEADER:100").unwrap();
+    std::fs::write(dir.join("notes.txt"), "not a statement").unwrap();
+
+    update_setting_impl(db, &SettingKey::WatchDir.to_string(), Some(&dir.display().to_string()))
+        .await
+        .unwrap();
+    let mut emitted = 0;
+    let imports = scan_watch_folder_impl(db, |_| emitted += 1).await.unwrap();
+    update_setting_impl(db, &SettingKey::WatchDir.to_string(), None).await.unwrap();
+
+    assert_eq!(imports.len(), 3, "Only CSV and OFX files are picked up");
+    assert_eq!(emitted, 3);
+
+    let statement = &imports[0];
+    assert_eq!(statement.status, WatchedImportStatus::Imported, "{}", statement.message);
+    assert_eq!(statement.account_id, Some(account_id));
+    assert_eq!(statement.result.as_ref().unwrap().imported, 2);
+    assert!(!dir.join("a-statement.csv").exists());
+    assert!(dir.join(WATCH_ARCHIVE_DIR).join("a-statement.csv").exists(), "Imported files are archived");
+
+    assert_eq!(imports[1].status, WatchedImportStatus::Skipped, "Files without a saved mapping are skipped");
+    assert!(dir.join("b-unknown.csv").exists(), "Skipped files stay in the folder");
+    assert_eq!(imports[2].status, WatchedImportStatus::Skipped, "OFX files aren't imported");
+
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM notifications WHERE notification_type = 'watch_folder_import' AND message LIKE ?",
+    )
+    .bind(format!("%{}%", "Watched Checking"))
+    .fetch_one(db)
+    .await
+    .unwrap();
+    assert!(count >= 1, "Each import leaves a notification");
+
+    let again = scan_watch_folder_impl(db, |_| {}).await.unwrap();
+    assert!(again.is_empty(), "Nothing is scanned once the watch folder is cleared");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,143 @@
+use budget_balancer_lib::commands::digest_commands::{generate_digest_impl, generate_weekly_digest_impl};
+use budget_balancer_lib::commands::notification_commands::list_notifications_impl;
+use budget_balancer_lib::commands::settings_commands::update_setting_impl;
+use budget_balancer_lib::models::notification::NotificationFilter;
+use budget_balancer_lib::utils::money;
+use std::fs;
+
+#[tokio::test]
+async fn test_generate_weekly_digest() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Weekly Digest Test").await;
+
+    // The test database persists between runs, so compare against a digest taken before inserting
+    let before = generate_weekly_digest_impl(db, Some("2018-02-11"), None).await.unwrap().digest;
+
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("2018-02-01", -8000, "Groceries").with_category(1),
+        super::fixtures::TestTransaction::new("2018-02-06", -6000, "Groceries").with_category(1),
+        super::fixtures::TestTransaction::new("2018-02-10", -4000, "Dinner").with_category(2),
+        super::fixtures::TestTransaction::new("2018-02-09", 120_000, "Paycheck").with_category(9),
+    ];
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let output_dir = std::env::temp_dir().join(super::unique_word("digest"));
+    fs::create_dir_all(&output_dir).unwrap();
+    update_setting_impl(db, "digest_output_dir", Some(output_dir.to_str().unwrap()))
+        .await
+        .expect("Failed to configure digest folder");
+
+    let result = generate_weekly_digest_impl(db, Some("2018-02-11"), Some("html")).await;
+    update_setting_impl(db, "digest_output_dir", None).await.unwrap();
+    assert!(result.is_ok(), "Failed to generate digest: {:?}", result);
+
+    let result = result.unwrap();
+    let digest = &result.digest;
+    assert_eq!(digest.period.start_date, "2018-02-05");
+    assert_eq!(digest.previous_period.end_date, "2018-02-04");
+    assert_eq!(digest.budget_period.start_date, "2018-02-01");
+    assert_eq!(digest.total_spending_cents - before.total_spending_cents, 10_000);
+    assert_eq!(digest.previous_spending_cents - before.previous_spending_cents, 8000);
+    assert!(digest.change_percent.is_some());
+    assert_eq!(digest.total_income_cents - before.total_income_cents, 120_000);
+
+    let file_path = result.file_path.expect("Digest should be written to the configured folder");
+    assert!(file_path.ends_with("weekly-digest-2018-02-11.html"));
+    let content = fs::read_to_string(&file_path).unwrap();
+    assert!(content.contains("Weekly Summary: 2018-02-05 to 2018-02-11"));
+    fs::remove_dir_all(&output_dir).ok();
+
+    let notifications = list_notifications_impl(
+        db,
+        Some(NotificationFilter {
+            notification_type: Some("weekly_digest".to_string()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .unwrap();
+    let notification = notifications
+        .notifications
+        .iter()
+        .find(|n| n.id == result.notification_id)
+        .expect("Digest should be stored as a notification");
+    assert!(notification.message.starts_with(&format!("Spent ${:.2}", money::from_cents(digest.total_spending_cents))));
+}
+
+#[tokio::test]
+async fn test_generate_weekly_digest_invalid_input() {
+    let db = super::get_test_db_pool().await;
+
+    let result = generate_weekly_digest_impl(db, Some("2018-13-01"), None).await;
+    assert!(result.is_err(), "Invalid date should be rejected");
+
+    let result = generate_weekly_digest_impl(db, Some("2018-02-11"), Some("pdf")).await;
+    assert!(result.is_err(), "Unsupported format should be rejected");
+}
+
+#[tokio::test]
+async fn test_generate_monthly_digest() {
+    let db = super::get_test_db_pool().await;
+    let account_id = super::fixtures::create_test_account(db, "Monthly Digest Test").await;
+
+    let before = generate_digest_impl(db, "monthly", Some("2017-05-20"), None, None).await.unwrap().digest;
+
+    let transactions = vec![
+        super::fixtures::TestTransaction::new("2017-04-30", -2500, "Groceries").with_category(1),
+        super::fixtures::TestTransaction::new("2017-05-01", -7000, "Groceries").with_category(1),
+        super::fixtures::TestTransaction::new("2017-05-31", -3000, "Dinner").with_category(2),
+    ];
+    super::fixtures::insert_test_transactions(db, account_id, transactions).await;
+
+    let output_dir = std::env::temp_dir().join(super::unique_word("digest"));
+    fs::create_dir_all(&output_dir).unwrap();
+    let output_path = output_dir.join("may.html");
+
+    let result = generate_digest_impl(db, "monthly", Some("2017-05-20"), Some("html"), output_path.to_str())
+        .await
+        .expect("Failed to generate monthly digest");
+
+    let digest = &result.digest;
+    assert_eq!(digest.period.start_date, "2017-05-01");
+    assert_eq!(digest.period.end_date, "2017-05-31");
+    assert_eq!(digest.previous_period.start_date, "2017-04-01");
+    assert_eq!(digest.budget_period.end_date, "2017-05-31");
+    assert_eq!(digest.total_spending_cents - before.total_spending_cents, 10_000);
+    assert_eq!(digest.previous_spending_cents - before.previous_spending_cents, 2500);
+    assert!(!digest.top_categories.is_empty());
+
+    assert_eq!(result.format, "html");
+    assert!(result.content.contains("<h1>Monthly Summary: 2017-05-01 to 2017-05-31</h1>"));
+    assert!(result.content.contains("<h2>Top Categories</h2>"));
+    assert_eq!(fs::read_to_string(&output_path).unwrap(), result.content);
+    fs::remove_dir_all(&output_dir).ok();
+
+    let markdown = generate_digest_impl(db, "weekly", Some("2017-05-20"), None, None).await.unwrap();
+    assert!(markdown.content.starts_with("# Weekly Summary: 2017-05-14 to 2017-05-20"));
+    assert!(markdown.file_path.is_none());
+}
+
+#[tokio::test]
+async fn test_generate_digest_invalid_input() {
+    let db = super::get_test_db_pool().await;
+
+    let result = generate_digest_impl(db, "daily", None, None, None).await;
+    assert!(result.is_err(), "Unknown frequency should be rejected");
+
+    let result = generate_digest_impl(db, "monthly", Some("2017-02-30"), None, None).await;
+    assert!(result.is_err(), "Invalid date should be rejected");
+}
+
+#[tokio::test]
+async fn test_update_setting_validation() {
+    let db = super::get_test_db_pool().await;
+
+    let result = update_setting_impl(db, "unknown_key", Some("value")).await;
+    assert!(result.is_err(), "Unknown keys should be rejected");
+
+    let result = update_setting_impl(db, "digest_output_dir", Some("relative/folder")).await;
+    assert!(result.is_err(), "Relative paths should be rejected");
+
+    let result = update_setting_impl(db, "digest_output_dir", Some("/nonexistent/budget-balancer-digests")).await;
+    assert!(result.is_err(), "Missing folders should be rejected");
+}
@@ -1,3 +1,5 @@
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
 import { AppLayout } from "./components/layout/AppLayout";
 import { TransactionsPage } from "./pages/TransactionsPage";
 import { DashboardPage } from "./pages/DashboardPage";
@@ -7,8 +9,30 @@ import { TrendsPage } from "./pages/TrendsPage";
 import { useUIStore } from "./stores/uiStore";
 import "./App.css";
 
+type DeepLinkEvent =
+  | { ok: true; result: { action: "navigate"; view: string } | { action: "expense_added"; amount: number } }
+  | { ok: false; error: string };
+
 function App() {
   const currentPage = useUIStore((state) => state.currentPage);
+  const setCurrentPage = useUIStore((state) => state.setCurrentPage);
+  const showToast = useUIStore((state) => state.showToast);
+
+  // Outcomes of budgetbalancer:// links opened by the OS
+  useEffect(() => {
+    const unlisten = listen<DeepLinkEvent>("deep-link", ({ payload }) => {
+      if (!payload.ok) {
+        showToast(payload.error, "error");
+      } else if (payload.result.action === "navigate") {
+        setCurrentPage(payload.result.view);
+      } else {
+        showToast(`Added expense of $${Math.abs(payload.result.amount).toFixed(2)}`, "success");
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [setCurrentPage, showToast]);
 
   const renderPage = () => {
     switch (currentPage) {
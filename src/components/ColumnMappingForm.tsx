@@ -1,7 +1,10 @@
-import React, { useState } from 'react';
+import React, { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
 import { Button } from './ui/Button';
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from './ui/Select';
-import { importCsv, ColumnMapping } from '../lib/tauri';
+import { importCsv, suggestColumnMapping, ColumnMapping, ImportProgress } from '../lib/tauri';
+
+const DATE_FORMATS = ['YYYY-MM-DD', 'MM/DD/YYYY', 'DD/MM/YYYY', 'MM-DD-YY', 'DD.MM.YYYY', 'MMM D, YYYY'];
 
 interface ColumnMappingFormProps {
   accountId: number;
@@ -25,21 +28,47 @@ export const ColumnMappingForm: React.FC<ColumnMappingFormProps> = ({
     merchant: undefined,
   });
   const [importing, setImporting] = useState(false);
+  const [progress, setProgress] = useState<ImportProgress | null>(null);
   const [result, setResult] = useState<string | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [suggestedFrom, setSuggestedFrom] = useState<string | null>(null);
+  const debitCredit = Boolean(mapping.debit || mapping.credit);
+  // Descriptions starting with '=' are expressions, e.g. for files without a description column
+  const computedDescription = mapping.description.startsWith('=');
+
+  // Start from a detected (or previously saved) mapping; the positional default stays if detection fails
+  useEffect(() => {
+    let cancelled = false;
+    suggestColumnMapping(csvContent)
+      .then((suggestion) => {
+        if (cancelled || !suggestion.mapping) return;
+        setMapping(suggestion.mapping);
+        setSuggestedFrom(suggestion.saved_mapping_name ?? 'detected');
+      })
+      .catch(() => undefined);
+    return () => {
+      cancelled = true;
+    };
+  }, [csvContent]);
 
   const handleImport = async () => {
     setImporting(true);
     setError(null);
+    setProgress(null);
+    // Progress is best-effort; the import still runs if the listener can't be attached
+    const unlisten = await listen<ImportProgress>('csv-import-progress', ({ payload }) =>
+      setProgress(payload)
+    ).catch(() => null);
     try {
       const importResult = await importCsv(accountId, csvContent, mapping);
-      setResult(importResult.message);
+      setResult([importResult.message, ...importResult.warnings.map((w) => w.message)].join(' '));
       setTimeout(() => {
         onComplete();
       }, 2000);
     } catch (err) {
       setError(String(err));
     } finally {
+      unlisten?.();
       setImporting(false);
     }
   };
@@ -48,6 +77,10 @@ export const ColumnMappingForm: React.FC<ColumnMappingFormProps> = ({
     <div className="space-y-4">
       <div className="text-sm text-gray-600 mb-4">
         Map your CSV columns to transaction fields. We found {headers.length} columns.
+        {suggestedFrom &&
+          (suggestedFrom === 'detected'
+            ? ' Columns were matched automatically; check them before importing.'
+            : ` Using your saved mapping "${suggestedFrom}".`)}
       </div>
       <div className="space-y-3">
         <div>
@@ -70,29 +103,119 @@ export const ColumnMappingForm: React.FC<ColumnMappingFormProps> = ({
         </div>
 
         <div>
-          <label className="block text-sm font-medium mb-1">Amount Column</label>
+          <label className="block text-sm font-medium mb-1">Date Format</label>
           <Select
-            value={mapping.amount}
-            onValueChange={(value) => setMapping({ ...mapping, amount: value })}
+            value={mapping.date_format || 'auto'}
+            onValueChange={(value) => setMapping({ ...mapping, date_format: value === 'auto' ? undefined : value })}
           >
             <SelectTrigger>
-              <SelectValue placeholder="Select amount column" />
+              <SelectValue placeholder="Detect automatically" />
             </SelectTrigger>
             <SelectContent>
-              {headers.map((header) => (
-                <SelectItem key={header} value={header}>
-                  {header}
+              <SelectItem value="auto">Detect automatically</SelectItem>
+              {DATE_FORMATS.map((format) => (
+                <SelectItem key={format} value={format}>
+                  {format}
                 </SelectItem>
               ))}
             </SelectContent>
           </Select>
         </div>
 
+        <label className="flex items-center gap-2 text-sm">
+          <input
+            type="checkbox"
+            checked={debitCredit}
+            onChange={(e) =>
+              setMapping(
+                e.target.checked
+                  ? { ...mapping, amount: '', debit: headers[1] || '', credit: headers[2] || '' }
+                  : { ...mapping, amount: headers[1] || '', debit: undefined, credit: undefined }
+              )
+            }
+          />
+          Amounts are in separate Debit and Credit columns
+        </label>
+
+        {debitCredit ? (
+          <>
+            <div>
+              <label className="block text-sm font-medium mb-1">Debit Column</label>
+              <Select
+                value={mapping.debit || 'none'}
+                onValueChange={(value) => setMapping({ ...mapping, debit: value === 'none' ? undefined : value })}
+              >
+                <SelectTrigger>
+                  <SelectValue placeholder="Select debit column" />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="none">None</SelectItem>
+                  {headers.map((header) => (
+                    <SelectItem key={header} value={header}>
+                      {header}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+            <div>
+              <label className="block text-sm font-medium mb-1">Credit Column</label>
+              <Select
+                value={mapping.credit || 'none'}
+                onValueChange={(value) => setMapping({ ...mapping, credit: value === 'none' ? undefined : value })}
+              >
+                <SelectTrigger>
+                  <SelectValue placeholder="Select credit column" />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="none">None</SelectItem>
+                  {headers.map((header) => (
+                    <SelectItem key={header} value={header}>
+                      {header}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+          </>
+        ) : (
+          <div>
+            <label className="block text-sm font-medium mb-1">Amount Column</label>
+            <Select
+              value={mapping.amount}
+              onValueChange={(value) => setMapping({ ...mapping, amount: value })}
+            >
+              <SelectTrigger>
+                <SelectValue placeholder="Select amount column" />
+              </SelectTrigger>
+              <SelectContent>
+                {headers.map((header) => (
+                  <SelectItem key={header} value={header}>
+                    {header}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+          </div>
+  
+        )}
+
+        <label className="flex items-center gap-2 text-sm">
+          <input
+            type="checkbox"
+            checked={mapping.invert_amounts ?? false}
+            onChange={(e) => setMapping({ ...mapping, invert_amounts: e.target.checked })}
+          />
+          Flip amount signs (for card exports where charges are positive)
+        </label>
+
         <div>
           <label className="block text-sm font-medium mb-1">Description Column</label>
           <Select
-            value={mapping.description}
-            onValueChange={(value) => setMapping({ ...mapping, description: value })}
+            value={computedDescription ? 'computed' : mapping.description}
+            onValueChange={(value) =>
+              setMapping({ ...mapping, description: value === 'computed' ? '=' : value })
+            }
           >
             <SelectTrigger>
               <SelectValue placeholder="Select description column" />
@@ -103,8 +226,18 @@ export const ColumnMappingForm: React.FC<ColumnMappingFormProps> = ({
                   {header}
                 </SelectItem>
               ))}
+              <SelectItem value="computed">Computed...</SelectItem>
             </SelectContent>
           </Select>
+          {computedDescription && (
+            <input
+              type="text"
+              value={mapping.description}
+              onChange={(e) => setMapping({ ...mapping, description: e.target.value })}
+              placeholder={'=[Payee] + " - " + trim([Memo])'}
+              className="w-full mt-2 px-3 py-2 border border-input rounded-md bg-background font-mono text-sm"
+            />
+          )}
         </div>
 
         <div>
@@ -128,6 +261,13 @@ export const ColumnMappingForm: React.FC<ColumnMappingFormProps> = ({
         </div>
       </div>
 
+      {importing && progress && (
+        <div className="text-sm text-gray-600">
+          Processed {progress.processed.toLocaleString()} of ~{progress.estimated_total.toLocaleString()} rows
+          ({progress.imported.toLocaleString()} imported, {progress.duplicates.toLocaleString()} duplicates)
+        </div>
+      )}
+
       {result && (
         <div className="p-3 bg-green-50 dark:bg-green-900/20 border border-green-200 dark:border-green-800 rounded text-sm text-green-800 dark:text-green-200">
           {result}
@@ -1,10 +1,9 @@
 import React, { useState } from 'react';
 import { open as openDialog } from '@tauri-apps/plugin-dialog';
-import { readTextFile } from '@tauri-apps/plugin-fs';
 import { Dialog, DialogContent, DialogHeader, DialogTitle, DialogTrigger } from './ui/Dialog';
 import { Button } from './ui/Button';
 import { ColumnMappingForm } from './ColumnMappingForm';
-import { getCsvHeaders } from '../lib/tauri';
+import { getCsvHeaders, readCsvFile } from '../lib/tauri';
 
 interface CsvUploadDialogProps {
   accountId: number;
@@ -31,7 +30,8 @@ export const CsvUploadDialog: React.FC<CsvUploadDialogProps> = ({
       });
 
       if (selected && typeof selected === 'string') {
-        const content = await readTextFile(selected);
+        // Decoded by the backend, so Latin-1 and UTF-16 exports read correctly
+        const content = await readCsvFile(selected);
         setCsvContent(content);
 
         // Get headers from CSV
@@ -1,21 +1,125 @@
 import { invoke } from '@tauri-apps/api/core';
+import type { Debt } from '../stores/debtStore';
+import type { CategorySpending, DashboardWidget } from '../stores/analyticsStore';
 
 export interface ColumnMapping {
   date: string;
   amount: string;
-  description: string;
-  merchant?: string;
+  description: string; // Column name, or an expression like '=[Payee] + " " + [Memo]'
+  merchant?: string; // Column name or expression
+  debit?: string; // Separate debit/credit columns replace the amount column
+  credit?: string;
+  invert_amounts?: boolean; // For exports where charges are positive
+  date_format?: string; // e.g. 'DD/MM/YYYY'; detected from the file when omitted
+  status?: string; // Column saying whether a row is pending; every row is posted without one
+  member?: string; // Column naming the household member; matched to members by full or first name
+  delimiter?: string; // A single character such as ';' or '\t'; detected from the header line when omitted
+  decimal_separator?: '.' | ','; // Worked out from each amount when omitted, e.g. "1.234,56" or "1,234.56"
 }
 
+// Detected when omitted: a byte order mark means UTF-8 or UTF-16, otherwise UTF-8 if valid, else Windows-1252
+export type CsvEncoding = 'utf-8' | 'utf-16le' | 'utf-16be' | 'windows-1252' | 'latin-1';
+
 export interface ImportResult {
   success: boolean;
   total: number;
   imported: number;
   duplicates: number;
   errors: number;
+  pending_posted: number; // Pending transactions already imported that this file shows posted
+  locked: number; // Rows dated in a locked month, left out
+  freeze_violations: number;
+  import_batch_id: number;
+  message: string;
+  warnings: ImportWarning[];
+}
+
+// The file looks wrong for the account's type; imports still go ahead
+export interface ImportWarning {
+  kind: 'mostly_positive' | 'high_volume' | 'positive_balance';
   message: string;
 }
 
+export interface ImportBatch {
+  id: number;
+  account_id: number;
+  account_name: string;
+  status: 'in_progress' | 'completed' | 'failed' | 'undone';
+  total_rows: number;
+  imported_count: number;
+  duplicate_count: number;
+  error_count: number;
+  remaining_count: number;
+  created_at: string;
+  undone_at?: string;
+}
+
+export interface UndoImportResult {
+  import_batch_id: number;
+  deleted: number;
+}
+
+// Payload of the "csv-import-progress" event emitted after each imported batch
+export interface ImportProgress {
+  processed: number;
+  estimated_total: number;
+  imported: number;
+  duplicates: number;
+  errors: number;
+  done: boolean;
+}
+
+export interface MappingSuggestion {
+  mapping?: ColumnMapping;
+  confidence: number;
+  saved_mapping_id?: number;
+  saved_mapping_name?: string;
+  header_fingerprint: string;
+  columns: {
+    column: string;
+    date: number;
+    amount: number;
+    description: number;
+    merchant: number;
+    debit: number;
+    credit: number;
+  }[];
+}
+
+// A saved mapping recognized from a file's headers, with the account its files go into
+export interface DetectedMapping {
+  mapping_id: number;
+  mapping_name: string;
+  mapping: ColumnMapping;
+  account_id?: number; // Unset when the mapping has no account or it's archived
+  account_name?: string;
+  header_fingerprint: string;
+}
+
+export interface PreviewRow {
+  row_number: number;
+  date: string;
+  amount: number;
+  description: string;
+  merchant?: string;
+  normalized_merchant?: string;
+  category_id: number;
+  category_name?: string;
+  duplicate: boolean;
+}
+
+// Lists are capped at the preview limit; counts cover the whole file
+export interface ImportPreview {
+  total_rows: number;
+  valid_count: number;
+  duplicate_count: number;
+  error_count: number;
+  rows: PreviewRow[];
+  duplicates: PreviewRow[];
+  errors: { row_number: number; message: string }[];
+  warnings: ImportWarning[];
+}
+
 export interface Transaction {
   id: number;
   account_id: number;
@@ -26,13 +130,49 @@ export interface Transaction {
   merchant?: string;
   hash: string;
   created_at: string;
+  notes?: string;
+  status: 'pending' | 'posted';
+  member_id?: number; // Household member who made it
+  tax_deductible?: boolean | null; // Overrides the category's tax flag; null follows it
 }
 
-export interface TransactionFilter {
+export interface TransactionWithBalance extends Transaction {
+  running_balance: number; // Account balance after this transaction, in date order
+}
+
+export interface CreateTransaction {
+  account_id: number;
+  category_id?: number; // Categorized by rules when omitted
+  date: string;
+  amount: number;
+  description: string;
+  merchant?: string;
+  notes?: string;
+}
+
+// Omitted fields are left alone; an empty merchant or notes clears it
+export interface UpdateTransaction {
+  id: number;
   account_id?: number;
   category_id?: number;
+  date?: string;
+  amount?: number;
+  description?: string;
+  merchant?: string;
+  notes?: string;
+}
+
+export interface TransactionFilter {
+  account_id?: number | number[]; // A list matches any of its ids
+  category_id?: number | number[];
   start_date?: string;
   end_date?: string;
+  min_amount?: number; // Compared against the absolute amount
+  max_amount?: number;
+  only_uncategorized?: boolean;
+  exclude_category_ids?: number[];
+  pending?: boolean; // true for only pending transactions, false for only posted ones
+  member_id?: number | number[];
   limit?: number;
   offset?: number;
 }
@@ -43,6 +183,8 @@ export interface Category {
   type: string;
   parent_id?: number;
   icon?: string;
+  archived: boolean; // Hidden from listCategories unless includeArchived
+  tax_deductible: boolean; // Its transactions go in the year-end tax report
   created_at: string;
 }
 
@@ -56,6 +198,9 @@ export interface Account {
   name: string;
   type: string;
   balance: number;
+  statement_closing_day?: number; // Unset means statements follow calendar months
+  statement_due_day?: number; // Credit cards only; payment due the next time this day comes round
+  archived: boolean; // Hidden from listAccounts unless includeArchived
   created_at: string;
   updated_at: string;
 }
@@ -67,15 +212,74 @@ export interface NewAccount {
 }
 
 // CSV Commands
+export const readCsvFile = (path: string, encoding?: CsvEncoding): Promise<string> =>
+  invoke('read_csv_file', { path, encoding });
+
 export const getCsvHeaders = (csvContent: string): Promise<string[]> =>
   invoke('get_csv_headers', { csvContent });
 
+export const suggestColumnMapping = (csvContent: string): Promise<MappingSuggestion> =>
+  invoke('suggest_column_mapping', { csvContent });
+
+export const detectMappingForCsv = (csvContent: string): Promise<DetectedMapping | null> =>
+  invoke('detect_mapping_for_csv', { csvContent });
+
+export const previewCsvImport = (
+  accountId: number,
+  csvContent: string,
+  mapping: ColumnMapping,
+  limit?: number
+): Promise<ImportPreview> =>
+  invoke('preview_csv_import', { accountId, csvContent, mapping, limit });
+
+// Rows dated in a locked month are left out unless overrideLock is set
 export const importCsv = (
   accountId: number,
   csvContent: string,
-  mapping: ColumnMapping
+  mapping: ColumnMapping,
+  overrideLock?: boolean
 ): Promise<ImportResult> =>
-  invoke('import_csv', { accountId, csvContent, mapping });
+  invoke('import_csv', { accountId, csvContent, mapping, overrideLock });
+
+export const listImportBatches = (
+  accountId?: number,
+  limit?: number
+): Promise<ImportBatch[]> =>
+  invoke('list_import_batches', { accountId, limit });
+
+// Rejected when any of the import's rows is dated in a locked month, unless overrideLock is set
+export const undoImport = (importBatchId: number, overrideLock?: boolean): Promise<UndoImportResult> =>
+  invoke('undo_import', { importBatchId, overrideLock });
+
+export type DuplicateField = 'date' | 'amount' | 'description' | 'merchant';
+
+// strict: any match is a duplicate; counted: each stored transaction absorbs one matching row,
+// so identical rows in one file are all kept; off: import every row
+export type DuplicateStrictness = 'strict' | 'counted' | 'off';
+
+export interface DuplicateSettings {
+  account_id: number;
+  fields: DuplicateField[];
+  lookback_days: number | null; // How many days apart matching dates can be; null is any
+  strictness: DuplicateStrictness;
+  is_default: boolean; // Default settings compare against every account; custom ones only this account
+  updated_at: string | null;
+}
+
+export interface DuplicateSettingsUpdate {
+  fields: DuplicateField[];
+  lookback_days?: number | null;
+  strictness?: DuplicateStrictness;
+}
+
+export const getDuplicateSettings = (accountId: number): Promise<DuplicateSettings> =>
+  invoke('get_duplicate_settings', { accountId });
+
+// Pass no settings to return the account to the default
+export const updateDuplicateSettings = (
+  accountId: number,
+  settings?: DuplicateSettingsUpdate
+): Promise<DuplicateSettings> => invoke('update_duplicate_settings', { accountId, settings });
 
 // Transaction Commands
 export const listTransactions = (
@@ -83,6 +287,11 @@ export const listTransactions = (
 ): Promise<Transaction[]> =>
   invoke('list_transactions', { filter });
 
+export const listTransactionsWithBalance = (
+  filter?: TransactionFilter
+): Promise<TransactionWithBalance[]> =>
+  invoke('list_transactions_with_balance', { filter });
+
 export const countTransactions = (
   filter?: TransactionFilter
 ): Promise<number> =>
@@ -90,20 +299,1346 @@ export const countTransactions = (
 
 export const updateTransactionCategory = (
   transactionId: number,
-  categoryId: number
+  categoryId: number,
+  overrideLock?: boolean
 ): Promise<void> =>
-  invoke('update_transaction_category', { transactionId, categoryId });
+  invoke('update_transaction_category', { transactionId, categoryId, overrideLock });
+
+// Transactions dated in a locked month are rejected unless overrideLock is set
+export const createTransaction = (
+  transaction: CreateTransaction,
+  overrideLock?: boolean
+): Promise<Transaction> =>
+  invoke('create_transaction', { transaction, overrideLock });
+
+// Suggestions only; nothing is saved until the user creates the transaction
+export interface ReceiptData {
+  amount: number | null;
+  date: string | null;
+  merchant: string | null;
+  text: string;
+}
+
+export const extractReceiptData = (imagePath: string): Promise<ReceiptData> =>
+  invoke('extract_receipt_data', { imagePath });
+
+export const updateTransaction = (
+  update: UpdateTransaction,
+  overrideLock?: boolean
+): Promise<Transaction> =>
+  invoke('update_transaction', { update, overrideLock });
+
+// Period Lock Commands
+export interface PeriodLock {
+  month: string; // YYYY-MM
+  locked_at: string;
+}
+
+export const listPeriodLocks = (): Promise<PeriodLock[]> =>
+  invoke('list_period_locks');
+
+// Closes a month: its transactions can't be changed or imported without overriding the lock
+export const lockPeriod = (month: string): Promise<PeriodLock> =>
+  invoke('lock_period', { month });
+
+export const unlockPeriod = (month: string): Promise<void> =>
+  invoke('unlock_period', { month });
+
+// Investment Commands
+// Holdings and prices are dated; each stays in effect until the next one for the same symbol
+export interface Holding {
+  id: number;
+  account_id: number;
+  symbol: string;
+  quantity: number; // 0 once the position is sold
+  cost_basis: number | null; // Total paid for the position
+  date: string;
+  created_at: string;
+}
+
+export interface NewHolding {
+  account_id: number;
+  symbol: string;
+  quantity: number;
+  cost_basis?: number | null;
+  date?: string | null; // Defaults to today; a record on the same date is replaced
+}
+
+export interface HoldingValue {
+  holding_id: number;
+  account_id: number;
+  account_name: string;
+  symbol: string;
+  quantity: number;
+  cost_basis: number | null;
+  held_since: string;
+  price: number | null; // Null when no price is on file yet
+  price_date: string | null;
+  value: number | null;
+  gain: number | null; // Value less cost basis, when both are known
+}
+
+export interface SecurityPrice {
+  id: number;
+  symbol: string;
+  date: string;
+  price: number;
+  source: 'manual' | 'csv';
+  created_at: string;
+}
+
+export interface PriceImportResult {
+  imported: number;
+  symbols: string[];
+  errors: { line: number; message: string }[]; // Lines skipped
+}
+
+export const recordHolding = (holding: NewHolding): Promise<Holding> =>
+  invoke('record_holding', { holding });
+
+export const listHoldings = (accountId?: number, asOf?: string): Promise<HoldingValue[]> =>
+  invoke('list_holdings', { accountId, asOf });
+
+export const listHoldingHistory = (accountId: number): Promise<Holding[]> =>
+  invoke('list_holding_history', { accountId });
+
+export const deleteHolding = (holdingId: number): Promise<void> =>
+  invoke('delete_holding', { holdingId });
+
+export const setSecurityPrice = (symbol: string, price: number, date?: string): Promise<SecurityPrice> =>
+  invoke('set_security_price', { symbol, price, date });
+
+export const listSecurityPrices = (symbol?: string): Promise<SecurityPrice[]> =>
+  invoke('list_security_prices', { symbol });
+
+// CSV with Symbol, Date and Price columns, such as a quote history download
+export const importSecurityPricesCsv = (csvContent: string): Promise<PriceImportResult> =>
+  invoke('import_security_prices_csv', { csvContent });
+
+// Transactions already posted are left alone; pending ones in a locked month need overrideLock
+export const markPosted = (
+  transactionIds: number[],
+  overrideLock?: boolean
+): Promise<{ success: boolean; updated_count: number; failed_ids: number[] }> =>
+  invoke('mark_posted', { transactionIds, overrideLock });
+
+// A null taxDeductible goes back to following each transaction's category
+export const bulkSetTaxDeductible = (
+  transactionIds: number[],
+  taxDeductible: boolean | null,
+  overrideLock?: boolean
+): Promise<{ success: boolean; updated_count: number; failed_ids: number[] }> =>
+  invoke('bulk_set_tax_deductible', { transactionIds, taxDeductible, overrideLock });
+
+// A null memberId leaves the transactions unassigned
+export const bulkAssignMember = (
+  transactionIds: number[],
+  memberId: number | null,
+  overrideLock?: boolean
+): Promise<{ success: boolean; updated_count: number; failed_ids: number[] }> =>
+  invoke('bulk_assign_member', { transactionIds, memberId, overrideLock });
+
+export type ExportField =
+  | 'date'
+  | 'amount'
+  | 'debit'
+  | 'credit'
+  | 'description'
+  | 'merchant'
+  | 'category'
+  | 'account'
+  | 'notes'
+  | 'status';
+
+export interface ExportColumn {
+  field: ExportField;
+  header?: string; // The field's name, e.g. 'Date', when omitted
+}
+
+export interface NewExportTemplate {
+  name: string;
+  columns: ExportColumn[]; // In file order
+  date_format?: string; // e.g. 'DD/MM/YYYY'; YYYY-MM-DD when omitted
+  decimal_separator?: '.' | ',';
+  delimiter?: ',' | ';' | '|' | '\t';
+  include_header?: boolean;
+}
+
+export interface ExportTemplate extends Required<Omit<NewExportTemplate, 'date_format'>> {
+  id: number;
+  date_format?: string;
+  created_at: string;
+}
+
+export const saveExportTemplate = (template: NewExportTemplate): Promise<number> =>
+  invoke('save_export_template', { template });
+
+export const listExportTemplates = (): Promise<ExportTemplate[]> =>
+  invoke('list_export_templates');
+
+// templateId lays a CSV export out with a saved template
+export const exportTransactions = (
+  format: 'csv' | 'json' | 'qif',
+  outputPath: string,
+  filter?: TransactionFilter,
+  templateId?: number
+): Promise<{ success: boolean; file_path: string; record_count: number }> =>
+  invoke('export_transactions', { format, outputPath, filter, templateId });
 
 // Category Commands
-export const listCategories = (): Promise<Category[]> =>
-  invoke('list_categories');
+export const listCategories = (includeArchived?: boolean): Promise<Category[]> =>
+  invoke('list_categories', { includeArchived });
+
+export const archiveCategory = (categoryId: number): Promise<Category> =>
+  invoke('archive_category', { categoryId });
+
+export const unarchiveCategory = (categoryId: number): Promise<Category> =>
+  invoke('unarchive_category', { categoryId });
+
+export const setCategoryTaxDeductible = (
+  categoryId: number,
+  taxDeductible: boolean
+): Promise<Category> =>
+  invoke('set_category_tax_deductible', { categoryId, taxDeductible });
 
 export const createCategory = (category: NewCategory): Promise<number> =>
   invoke('create_category', { category });
 
+// Predefined categories can be renamed and restyled too; an empty icon or color removes it
+export interface UpdateCategory {
+  id: number;
+  name?: string;
+  icon?: string;
+  color?: string;
+}
+
+export const updateCategory = (update: UpdateCategory): Promise<Category> =>
+  invoke('update_category', { update });
+
+export interface RuleStatistics {
+  rule_id: number;
+  pattern: string;
+  match_type: string;
+  category_id: number;
+  category_name: string;
+  priority: number;
+  account_id?: number;
+  match_count: number;
+  last_matched_at?: string;
+  created_at: string;
+  never_matched: boolean;
+  stale: boolean; // No match within the stale window (180 days by default)
+}
+
+export const getRuleStatistics = (staleAfterDays?: number): Promise<RuleStatistics[]> =>
+  invoke('get_rule_statistics', { staleAfterDays });
+
+export interface CategoryRule {
+  id: number;
+  pattern: string;
+  category_id: number;
+  priority: number;
+  match_type: string;
+  min_amount?: number;
+  max_amount?: number;
+  account_id?: number; // Unset means the rule applies to every account
+  created_at: string;
+}
+
+export interface RuleConflictExample {
+  transaction_id: number;
+  account_id: number;
+  date: string;
+  amount: number;
+  description: string;
+  merchant?: string;
+  category_id: number;
+}
+
+export interface RuleConflict {
+  first_rule: CategoryRule; // Evaluated first, so it wins today
+  second_rule: CategoryRule;
+  matched_count: number; // 0 when only the patterns overlap
+  examples: RuleConflictExample[];
+}
+
+export const detectRuleConflicts = (exampleLimit?: number): Promise<RuleConflict[]> =>
+  invoke('detect_rule_conflicts', { exampleLimit });
+
+export type CategorySeedSet = 'standard' | 'minimal' | 'detailed' | 'business';
+
+export interface CategorySeedSetInfo {
+  id: CategorySeedSet;
+  name: string;
+  description?: string;
+  category_count: number;
+}
+
+export interface CategoryReseedResult {
+  set: CategorySeedSet;
+  categories_created: number;
+  categories_existing: number; // Same-named categories reused instead of duplicated
+  rules_created: number;
+  rules_skipped: number;
+  categories_restored: number; // Archived categories in the set, unarchived
+  categories_moved: number; // Predefined categories moved under the set's parents
+  categories_archived: number; // Predefined categories outside the set, when replacing
+}
+
+export const listCategorySeedSets = (): Promise<CategorySeedSetInfo[]> =>
+  invoke('list_category_seed_sets');
+
+// With replace, predefined categories outside the set are archived (never Uncategorized)
+export const reseedCategories = (set: CategorySeedSet, replace?: boolean): Promise<CategoryReseedResult> =>
+  invoke('reseed_categories', { set, replace });
+
 // Account Commands
-export const listAccounts = (): Promise<Account[]> =>
-  invoke('list_accounts');
+export const listAccounts = (includeArchived?: boolean): Promise<Account[]> =>
+  invoke('list_accounts', { includeArchived });
 
 export const createAccount = (account: NewAccount): Promise<number> =>
   invoke('create_account', { account });
+
+export const setStatementClosingDay = (
+  accountId: number,
+  closingDay?: number
+): Promise<Account> =>
+  invoke('set_statement_closing_day', { accountId, closingDay });
+
+export interface StatementSummary {
+  account_id: number;
+  period_start: string;
+  period_end: string;
+  due_date: string | null; // Null until the card has a due day
+  opening_balance: number;
+  charges: number;
+  payments: number;
+  closing_balance: number;
+  amount_due: number; // What the statement asks to be paid; zero when the card is in credit
+  transaction_count: number;
+  closed: boolean;
+  linked_debt_id: number | null;
+}
+
+// Credit cards only; also moves linked debts' due day
+export const setStatementCycle = (
+  accountId: number,
+  closingDay?: number,
+  dueDay?: number
+): Promise<Account> =>
+  invoke('set_statement_cycle', { accountId, closingDay, dueDay });
+
+// Without a date, summarizes the most recently closed statement
+export const getStatementSummary = (accountId: number, date?: string): Promise<StatementSummary> =>
+  invoke('get_statement_summary', { accountId, date });
+
+export interface LargestTransaction {
+  id: number;
+  date: string;
+  amount: number;
+  description: string;
+  merchant: string | null;
+  category_id: number;
+  category_name: string;
+  category_icon: string | null;
+  account_id: number;
+  account_name: string;
+}
+
+export interface AccountSummary {
+  account_id: number;
+  account_name: string;
+  period: {
+    start_date: string;
+    end_date: string;
+  };
+  inflows: number;
+  outflows: number;
+  net_change: number; // inflows - outflows
+  top_categories: CategorySpending[];
+  largest_transactions: LargestTransaction[]; // Biggest outflows first
+}
+
+export const getAccountSummary = (
+  accountId: number,
+  startDate: string,
+  endDate: string
+): Promise<AccountSummary> =>
+  invoke('get_account_summary', { accountId, startDate, endDate });
+
+export const archiveAccount = (accountId: number): Promise<Account> =>
+  invoke('archive_account', { accountId });
+
+export const unarchiveAccount = (accountId: number): Promise<Account> =>
+  invoke('unarchive_account', { accountId });
+
+// Debt Commands
+export interface ScheduledPayment {
+  debt_id: number;
+  debt_name: string;
+  amount: number;
+  due_date: string;
+  is_minimum: boolean;
+}
+
+export interface PaymentSchedule {
+  month: string; // YYYY-MM
+  total_amount: number;
+  payments: ScheduledPayment[];
+}
+
+export const getUpcomingPayments = (monthsAhead: number): Promise<PaymentSchedule[]> =>
+  invoke('get_upcoming_payments', { monthsAhead });
+
+export interface NewLoan {
+  name: string;
+  principal: number;
+  interest_rate: number; // APR
+  term_months: number;
+  start_date: string;
+  balance?: number; // Defaults to what the schedule leaves owed today
+}
+
+export interface AmortizationRow {
+  number: number;
+  date: string;
+  payment: number;
+  interest: number;
+  principal: number;
+  balance: number; // Principal left after this payment
+}
+
+export interface AmortizationSchedule {
+  debt_id: number;
+  principal: number;
+  interest_rate: number;
+  term_months: number;
+  start_date: string;
+  monthly_payment: number;
+  total_interest: number;
+  total_paid: number;
+  rows: AmortizationRow[];
+}
+
+export const createLoan = (loan: NewLoan): Promise<number> =>
+  invoke('create_loan', { loan });
+
+export const getAmortizationSchedule = (debtId: number): Promise<AmortizationSchedule> =>
+  invoke('get_amortization_schedule', { debtId });
+
+// Both recompute the debt's balance from its payments
+export const updateDebtPayment = (paymentId: number, amount?: number, date?: string): Promise<Debt> =>
+  invoke('update_debt_payment', { paymentId, amount, date });
+
+export const deleteDebtPayment = (paymentId: number): Promise<Debt> =>
+  invoke('delete_debt_payment', { paymentId });
+
+export const setDebtDueDay = (debtId: number, dueDay: number): Promise<Debt> =>
+  invoke('set_debt_due_day', { debtId, dueDay });
+
+// Pass no percentage to go back to a fixed minimum payment
+export const setDebtMinPaymentPercent = (debtId: number, percent?: number): Promise<Debt> =>
+  invoke('set_debt_min_payment_percent', { debtId, percent });
+
+// Pass no account to unlink
+export const linkDebtAccount = (debtId: number, accountId?: number): Promise<Debt> =>
+  invoke('link_debt_account', { debtId, accountId });
+
+// Sets the debt's balance to what its linked card's statement asks to be paid
+export const syncDebtToStatement = (debtId: number, date?: string): Promise<Debt> =>
+  invoke('sync_debt_to_statement', { debtId, date });
+
+export interface StrategyComparison {
+  strategy: 'avalanche' | 'snowball';
+  payoff_date: string;
+  total_interest: number;
+  payoff_months: number;
+}
+
+export interface PayoffScenarioResult {
+  monthly_amount: number;
+  avalanche?: StrategyComparison;
+  snowball?: StrategyComparison;
+  error?: string; // Set instead of results when the amount doesn't cover minimum payments
+}
+
+// Nothing is saved, unlike calculatePayoffPlan
+export const simulatePayoffScenarios = (monthlyAmounts: number[]): Promise<PayoffScenarioResult[]> =>
+  invoke('simulate_payoff_scenarios', { monthlyAmounts });
+
+export interface MarginalPaymentImpact {
+  increment: number;
+  monthly_amount: number; // The current amount plus the increment
+  payoff_date: string;
+  payoff_months: number;
+  months_saved: number;
+  interest_saved: number;
+}
+
+export interface MarginalPaymentImpactResponse {
+  current: StrategyComparison;
+  monthly_amount: number;
+  impacts: MarginalPaymentImpact[];
+}
+
+// Increments default to +$25, +$50 and +$100
+export const getMarginalPaymentImpact = (
+  strategy: 'avalanche' | 'snowball',
+  monthlyAmount: number,
+  increments?: number[]
+): Promise<MarginalPaymentImpactResponse> =>
+  invoke('get_marginal_payment_impact', { strategy, monthlyAmount, increments });
+
+// Alert Commands
+export type AlertType = 'target_threshold' | 'large_transaction' | 'payment_due';
+
+export interface Alert {
+  id: number;
+  alert_type: AlertType;
+  alert_key: string;
+  severity: 'info' | 'warning' | 'critical';
+  title: string;
+  message: string;
+  entity_type?: 'transaction' | 'category' | 'debt';
+  entity_id?: number;
+  dismissed_at?: string;
+  created_at: string;
+}
+
+// Emitted with the new alerts whenever an evaluation raises any (including at startup)
+export const ALERTS_EVENT = 'alerts-raised';
+
+export const evaluateAlerts = (): Promise<Alert[]> =>
+  invoke('evaluate_alerts');
+
+export const listAlerts = (includeDismissed?: boolean): Promise<Alert[]> =>
+  invoke('list_alerts', { includeDismissed });
+
+export const dismissAlert = (id: number): Promise<void> =>
+  invoke('dismiss_alert', { id });
+
+// Search Commands
+export type SearchEntityType = 'transaction' | 'category' | 'debt';
+
+export interface SearchResult {
+  entity_type: SearchEntityType;
+  entity_id: number;
+  title: string;
+  snippet: string;
+  score: number; // Higher is more relevant; results come best first
+  date?: string; // Transactions only
+  amount?: number; // Transactions only
+}
+
+export const globalSearch = (
+  query: string,
+  entityType?: SearchEntityType,
+  limit?: number
+): Promise<SearchResult[]> =>
+  invoke('global_search', { query, entityType, limit });
+
+// Audit Log Commands
+export type AuditAction = 'create' | 'update' | 'delete';
+export type AuditEntity =
+  | 'transaction'
+  | 'category'
+  | 'debt'
+  | 'debt_payment'
+  | 'import'
+  | 'scheduled_transaction';
+
+export interface AuditLogEntry {
+  id: number;
+  action: AuditAction;
+  entity_type: AuditEntity;
+  entity_id?: number;
+  old_values?: Record<string, unknown>; // Absent for creates
+  new_values?: Record<string, unknown>; // Absent for deletes
+  created_at: string;
+}
+
+export interface AuditLogFilter {
+  entity_type?: AuditEntity;
+  entity_id?: number;
+  action?: AuditAction;
+  limit?: number;
+  offset?: number;
+}
+
+export const listAuditLog = (filter?: AuditLogFilter): Promise<AuditLogEntry[]> =>
+  invoke('list_audit_log', { filter });
+
+// Scheduled Transaction Commands
+export type Recurrence = 'once' | 'weekly' | 'biweekly' | 'monthly' | 'quarterly' | 'yearly';
+
+export interface ScheduledTransaction {
+  id: number;
+  account_id: number;
+  category_id: number;
+  amount: number;
+  description: string;
+  merchant?: string;
+  recurrence: Recurrence;
+  start_date: string;
+  end_date?: string;
+  next_due_date?: string; // Absent once every occurrence has been posted
+  auto_post: boolean; // Posted when due; otherwise waits for confirmation
+  created_at: string;
+}
+
+export interface NewScheduledTransaction {
+  account_id: number;
+  category_id?: number; // Uncategorized when omitted
+  amount: number;
+  description: string;
+  merchant?: string;
+  recurrence: Recurrence;
+  start_date: string; // First occurrence
+  end_date?: string;
+  auto_post?: boolean; // Defaults to true
+}
+
+export interface ScheduledPosting {
+  scheduled_transaction_id: number;
+  date: string;
+  transaction_id: number;
+  already_existed: boolean; // Matched a transaction that was already there
+}
+
+export const createScheduledTransaction = (
+  scheduled: NewScheduledTransaction
+): Promise<ScheduledTransaction> =>
+  invoke('create_scheduled_transaction', { scheduled });
+
+export const listScheduledTransactions = (): Promise<ScheduledTransaction[]> =>
+  invoke('list_scheduled_transactions');
+
+export const deleteScheduledTransaction = (id: number): Promise<void> =>
+  invoke('delete_scheduled_transaction', { id });
+
+export const postDueScheduledTransactions = (): Promise<ScheduledPosting[]> =>
+  invoke('post_due_scheduled_transactions');
+
+export const confirmScheduledTransaction = (id: number): Promise<ScheduledPosting> =>
+  invoke('confirm_scheduled_transaction', { id });
+
+// Budget Variance Commands
+export interface CategoryVariance {
+  category_id: number;
+  category_name: string;
+  budget: number; // Target amount scaled to the report period
+  actual: number;
+  variance: number; // actual - budget; positive is over budget
+  percent_consumed: number;
+  projected: number; // End-of-period spending at the current run rate
+  projected_variance: number;
+}
+
+export interface BudgetVarianceReport {
+  period: 'monthly' | 'quarterly';
+  start_date: string;
+  end_date: string;
+  days_elapsed: number;
+  total_days: number;
+  categories: CategoryVariance[];
+  total_budget: number;
+  total_actual: number;
+  total_projected: number;
+  unbudgeted_spending: number; // Spending in categories without a target
+}
+
+// Tax Report Commands
+// Posted transactions in tax-deductible categories, or flagged one by one, grouped by category
+export interface TaxReportResponse {
+  success: boolean;
+  file_path: string;
+  file_size: number;
+  transaction_count: number;
+  total: number; // Money out, less refunds
+}
+
+export const generateTaxReport = (
+  year: number,
+  format: 'csv' | 'pdf',
+  outputPath: string
+): Promise<TaxReportResponse> =>
+  invoke('generate_tax_report', { year, format, outputPath });
+
+export const getBudgetVarianceReport = (
+  period: 'monthly' | 'quarterly',
+  date?: string // Any day in the month or quarter; defaults to today
+): Promise<BudgetVarianceReport> =>
+  invoke('get_budget_variance_report', { period, date });
+
+// Budget CSV Commands
+// Category targets as Category, Period, Amount rows
+export interface BudgetExportResponse {
+  success: boolean;
+  file_path: string;
+  record_count: number;
+}
+
+export interface BudgetImportRow {
+  line: number;
+  category: string;
+  period: string;
+  amount: number | null; // Null when the amount couldn't be read
+  action: 'create' | 'update' | 'unchanged' | 'error';
+  category_id: number | null;
+  target_id: number | null; // The existing target the row updates or leaves unchanged
+  message: string | null;
+}
+
+export interface BudgetImportReport {
+  start_date: string; // When the imported budgets take effect
+  rows: BudgetImportRow[];
+  created: number;
+  updated: number;
+  unchanged: number;
+  errors: number;
+  applied: boolean; // False for a dry run, or when any row has an error
+}
+
+export const exportBudgetsCsv = (outputPath: string): Promise<BudgetExportResponse> =>
+  invoke('export_budgets_csv', { outputPath });
+
+export const importBudgetsCsv = (
+  csvContent: string,
+  startDate?: string, // Defaults to the start of this month
+  dryRun?: boolean
+): Promise<BudgetImportReport> =>
+  invoke('import_budgets_csv', { csvContent, startDate, dryRun });
+
+// Calendar Commands
+export interface CalendarExportResult {
+  success: boolean;
+  file_path: string;
+  event_count: number;
+}
+
+export const exportPaymentCalendar = (
+  outputPath: string,
+  monthsAhead?: number // Defaults to 6
+): Promise<CalendarExportResult> =>
+  invoke('export_payment_calendar', { outputPath, monthsAhead });
+
+// Spending Insight Commands
+export type InsightDetail =
+  | {
+      kind: 'category_increase' | 'category_decrease';
+      category_id: number;
+      category_name: string;
+      amount: number;
+      previous_amount: number;
+      change: number; // Negative for decreases
+      change_percent: number | null; // null when nothing was spent in the previous period
+    }
+  | {
+      kind: 'new_merchant';
+      merchant_name: string;
+      amount: number;
+      transaction_count: number;
+      first_date: string;
+    }
+  | {
+      kind: 'large_transaction';
+      transaction_id: number;
+      date: string;
+      amount: number;
+      description: string;
+      category_name: string;
+    }
+  | {
+      kind: 'rising_streak';
+      category_id: number;
+      category_name: string;
+      weeks: number;
+      latest_amount: number;
+    };
+
+export type Insight = InsightDetail & { message: string };
+
+export interface SpendingInsights {
+  period: { start_date: string; end_date: string };
+  previous_period: { start_date: string; end_date: string };
+  insights: Insight[];
+}
+
+export const getSpendingInsights = (
+  period?: string, // Same named periods as the dashboard; defaults to the current month
+  customStart?: string,
+  customEnd?: string
+): Promise<SpendingInsights> =>
+  invoke('get_spending_insights', { period, customStart, customEnd });
+
+// Digest Commands
+export interface GeneratedDigest {
+  digest: {
+    frequency: 'weekly' | 'monthly';
+    period: { start_date: string; end_date: string };
+    previous_period: { start_date: string; end_date: string };
+    total_spending: number;
+    previous_spending: number;
+    change_percent: number | null;
+    total_income: number;
+    budgets_over: number;
+  };
+  format: 'markdown' | 'html';
+  content: string;
+  file_path: string | null; // Only set when an output path was given
+}
+
+export const generateDigest = (
+  frequency: 'weekly' | 'monthly',
+  date?: string, // Week ending on, or any day in the month; defaults to today
+  format?: 'markdown' | 'html',
+  outputPath?: string
+): Promise<GeneratedDigest> =>
+  invoke('generate_digest', { frequency, date, format, outputPath });
+
+// Category Statistics Commands
+export interface SeasonalMonth {
+  month: number; // 1 = January
+  average: number;
+  months_counted: number;
+  index: number | null; // Month average relative to the overall monthly average
+}
+
+export interface CategoryStatistics {
+  category_id: number;
+  category_name: string;
+  start_month: string | null; // null when there is no spending in the window
+  end_month: string;
+  months_counted: number; // Months without spending count as zero
+  total: number;
+  average: number;
+  median: number;
+  min: number;
+  max: number;
+  std_dev: number;
+  monthly: { month: string; amount: number }[];
+  seasonal_profile: SeasonalMonth[]; // 12 entries, January first
+}
+
+export const getCategoryStatistics = (
+  categoryId: number,
+  months?: number // Completed months to summarize; defaults to 24
+): Promise<CategoryStatistics> =>
+  invoke('get_category_statistics', { categoryId, months });
+
+// Financial Health Commands
+export interface HealthMonth {
+  month: string; // YYYY-MM
+  income: number;
+  spending: number;
+  debt_payments: number;
+  savings_rate: number | null; // Percent; null in a month without income
+  debt_to_income: number | null; // Percent; null in a month without income
+}
+
+export interface FinancialHealth {
+  start_month: string;
+  end_month: string;
+  total_income: number;
+  total_spending: number;
+  savings_rate: number | null; // Percent of income kept over the window
+  monthly_debt_payments: number; // Current minimum payments on every debt
+  debt_to_income: number | null; // Minimum payments over average monthly income, percent
+  liquid_balance: number; // Checking and savings balances now
+  months_of_expenses: number | null; // Months of average spending the liquid balance covers
+  monthly: HealthMonth[]; // Oldest first
+  savings_rate_trend: number | null; // Percentage points per month
+  debt_to_income_trend: number | null; // Percentage points per month
+}
+
+export const getFinancialHealth = (
+  months?: number // Completed months to cover; defaults to 12
+): Promise<FinancialHealth> =>
+  invoke('get_financial_health', { months });
+
+// Net Worth History Commands
+export interface NetWorthPoint {
+  date: string;
+  accounts_total: number; // Cash balances, walked back by later transactions
+  investments_total: number; // Holdings at the prices in effect on the date
+  debts_total: number;
+  net_worth: number;
+}
+
+export const getNetWorthHistory = (
+  months?: number // Month-end points to return; defaults to 12, the last being today
+): Promise<NetWorthPoint[]> =>
+  invoke('get_net_worth_history', { months });
+
+// Amount Distribution Commands
+export interface AmountBucket {
+  min: number; // Amounts above this (or from zero, for the first bucket)
+  max: number | null; // Up to and including this; null for the last, open-ended bucket
+  count: number;
+  total: number;
+  percentage: number;
+}
+
+export interface AmountDistribution {
+  count: number;
+  total: number;
+  average: number;
+  p50: number;
+  p90: number;
+  p99: number; // Percentiles are zero when no transactions match
+  buckets: AmountBucket[];
+}
+
+// Amounts are absolute; the filter's limit and offset are ignored
+export const getAmountDistribution = (
+  filter?: TransactionFilter,
+  direction?: 'expense' | 'income' | 'both' // Defaults to 'expense'
+): Promise<AmountDistribution> =>
+  invoke('get_amount_distribution', { filter, direction });
+
+// Spending Heatmap Commands
+export interface HeatmapCell {
+  bucket: number; // 0 (Monday) to 6 (Sunday) by weekday; 1 to 31 by day of month
+  amount: number;
+  transaction_count: number;
+}
+
+export interface SpendingHeatmap {
+  by_weekday: HeatmapCell[]; // All 7 weekdays, Monday first
+  by_day_of_month: HeatmapCell[]; // All 31 days, including those without spending
+  total_spending: number;
+}
+
+export const getSpendingHeatmap = (
+  startDate: string,
+  endDate: string,
+  categoryId?: number
+): Promise<SpendingHeatmap> =>
+  invoke('get_spending_heatmap', { startDate, endDate, categoryId });
+
+// Analytics Cache Commands
+// Dashboard Layout Commands
+// get_dashboard_summary only builds the sections for the saved layout's widgets
+export interface DashboardConfig {
+  widgets: DashboardWidget[]; // In display order; each kind at most once
+  updated_at: string | null; // null until a layout is saved
+}
+
+export const getDashboardConfig = (): Promise<DashboardConfig> =>
+  invoke('get_dashboard_config');
+
+export const saveDashboardConfig = (widgets: DashboardWidget[]): Promise<DashboardConfig> =>
+  invoke('save_dashboard_config', { widgets });
+
+// Spending, trends and dashboard results are cached until data changes; this drops them all
+// and returns the new data version
+export const invalidateAnalyticsCache = (): Promise<number> =>
+  invoke('invalidate_analytics_cache');
+
+// Background Job Commands
+export type JobStatus = 'queued' | 'running' | 'completed' | 'failed' | 'cancelled';
+
+export interface Job {
+  id: number;
+  kind: 'csv_import' | 'csv_batch_import' | 'report_export' | 'rehash';
+  status: JobStatus;
+  processed: number;
+  total: number | null; // null while the amount of work isn't known
+  message: string | null;
+  result: unknown | null; // ImportResult for imports; the export response for report exports; RehashResult for rehashes
+  error: string | null;
+  cancel_requested: boolean;
+  created_at: string;
+  started_at: string | null;
+  finished_at: string | null;
+}
+
+// Emitted with a Job whenever a background job starts, reports progress, or finishes
+export const JOB_PROGRESS_EVENT = 'job-progress';
+
+export const getJobStatus = (jobId: number): Promise<Job> =>
+  invoke('get_job_status', { jobId });
+
+export const listJobs = (status?: JobStatus, limit?: number): Promise<Job[]> =>
+  invoke('list_jobs', { status, limit });
+
+export const cancelJob = (jobId: number): Promise<Job> =>
+  invoke('cancel_job', { jobId });
+
+export const startImportJob = (
+  accountId: number,
+  csvContent: string,
+  mapping: ColumnMapping,
+  overrideLock?: boolean
+): Promise<number> =>
+  invoke('start_import_job', { accountId, csvContent, mapping, overrideLock });
+
+// One file of a batch import; give either its path (read and decoded by the backend) or its content
+export interface BatchImportFile {
+  path?: string;
+  csv_content?: string;
+  encoding?: CsvEncoding;
+  account_id: number;
+  mapping: ColumnMapping;
+}
+
+export interface BatchFileResult {
+  file: string; // The path, or "File N" for content passed directly
+  account_id: number;
+  result?: ImportResult;
+  error?: string; // Set when the file couldn't be imported; the other files still are
+}
+
+// The finished batch import job's result
+export interface BatchImportResult {
+  files: BatchFileResult[];
+  imported: number;
+  duplicates: number;
+  errors: number;
+  failed_files: number;
+  message: string;
+}
+
+// Imports the files one after another as a single job; returns the job ID
+export const importCsvBatch = (files: BatchImportFile[], overrideLock?: boolean): Promise<number> =>
+  invoke('import_csv_batch', { files, overrideLock });
+
+export const startReportExportJob = (
+  format: string,
+  startDate: string,
+  endDate: string,
+  includeCharts: boolean,
+  outputPath: string
+): Promise<number> =>
+  invoke('start_report_export_job', { format, startDate, endDate, includeCharts, outputPath });
+
+export interface RehashResult {
+  hash_version: number;
+  rehashed: number;
+  remaining: number; // Transactions still on an older hash version
+}
+
+// Starts migrating stored duplicate-detection hashes to the current version; returns the job ID
+export const rehashTransactions = (): Promise<number> =>
+  invoke('rehash_transactions');
+
+// Data Change Events
+// Emitted as `<kind>:changed` after a command creates, updates, or deletes data
+export type DataKind =
+  | 'transactions'
+  | 'import-batches'
+  | 'column-mappings'
+  | 'accounts'
+  | 'categories'
+  | 'category-rules'
+  | 'debts'
+  | 'payoff-plans'
+  | 'spending-targets'
+  | 'spending-freezes'
+  | 'merchants'
+  | 'notifications'
+  | 'alerts'
+  | 'settings'
+  | 'onboarding'
+  | 'exchange-rates'
+  | 'scheduled-transactions'
+  | 'bank-connections'
+  | 'members'
+  | 'period-locks'
+  | 'investments';
+
+export interface ChangeEvent {
+  action: 'created' | 'updated' | 'deleted';
+  ids: number[]; // Empty when the change can't be narrowed down; refetch everything of the kind
+}
+
+export const changeEventName = (kind: DataKind): string => `${kind}:changed`;
+
+// Query Console Commands
+export interface QueryResult {
+  columns: string[];
+  rows: unknown[][]; // One value per column; BLOBs as hex strings
+  row_count: number;
+  limit: number;
+  truncated: boolean; // The query had more rows than the limit
+}
+
+// Runs a single SELECT on a read-only connection; at most 1000 rows are returned
+export const executeReadonlyQuery = (sql: string, limit?: number): Promise<QueryResult> =>
+  invoke('execute_readonly_query', { sql, limit });
+
+export interface QueryPlanStep {
+  id: number;
+  parent: number; // 0 for top-level steps
+  detail: string;
+}
+
+export interface QueryPlan {
+  steps: QueryPlanStep[];
+  indexes: string[]; // Indexes the query would use
+  full_scans: string[]; // Tables it would read in full
+}
+
+// Plans a SELECT without running it, to check which indexes it uses
+export const getQueryPlan = (sql: string): Promise<QueryPlan> =>
+  invoke('get_query_plan', { sql });
+
+// Bank Sync Commands
+// Only available in builds with the bank-sync feature; otherwise linking and syncing return an error
+export interface BankAccountLink {
+  id: number;
+  connection_id: number;
+  remote_account_id: string;
+  remote_name: string;
+  institution: string | null;
+  remote_balance: number | null; // As last reported by the provider
+  account_id: number | null; // Null until mapped; unmapped accounts are skipped when syncing
+  last_synced_at: string | null;
+}
+
+export interface BankConnection {
+  id: number;
+  provider: string;
+  name: string;
+  last_synced_at: string | null;
+  last_error: string | null; // Cleared by the next successful sync
+  created_at: string;
+  updated_at: string;
+  accounts: BankAccountLink[];
+}
+
+export interface AccountSyncResult {
+  link_id: number;
+  remote_name: string;
+  account_id: number | null;
+  fetched: number; // Posted transactions only; pending ones are left for a later sync
+  imported: number;
+  duplicates: number;
+  locked: number; // Dated in a locked month, so left out
+  errors: number;
+  import_batch_id: number | null; // Undo with undoImport
+}
+
+export interface SyncSummary {
+  connection_id: number;
+  start_date: string;
+  accounts: AccountSyncResult[];
+  imported: number;
+  duplicates: number;
+  provider_messages: string[];
+}
+
+export const listBankConnections = (): Promise<BankConnection[]> =>
+  invoke('list_bank_connections');
+
+// setupToken is the one-time token from SimpleFIN Bridge
+export const linkBankConnection = (name: string, setupToken: string): Promise<BankConnection> =>
+  invoke('link_bank_connection', { name, setupToken });
+
+// Pass no account to stop importing a remote account
+export const mapBankAccount = (linkId: number, accountId?: number): Promise<BankAccountLink> =>
+  invoke('map_bank_account', { linkId, accountId });
+
+export const syncAccounts = (connectionId: number): Promise<SyncSummary> =>
+  invoke('sync_accounts', { connectionId });
+
+export const removeBankConnection = (connectionId: number): Promise<void> =>
+  invoke('remove_bank_connection', { connectionId });
+
+// Data Archive Commands
+// What to do with an archived record that matches an existing one; merge only fills in missing details
+export type ArchiveConflict = 'skip' | 'overwrite' | 'merge';
+
+export interface ImportCounts {
+  created: number;
+  updated: number; // Overwritten or merged
+  skipped: number;
+}
+
+export interface DataImportResult {
+  accounts: ImportCounts;
+  categories: ImportCounts;
+  category_rules: ImportCounts;
+  transactions: ImportCounts;
+  debts: ImportCounts;
+  debt_payments: ImportCounts;
+  spending_targets: ImportCounts;
+  column_mappings: ImportCounts;
+}
+
+export interface DataExportResult {
+  path: string;
+  accounts: number;
+  categories: number;
+  category_rules: number;
+  transactions: number;
+  debts: number;
+  debt_payments: number;
+  spending_targets: number;
+  column_mappings: number;
+}
+
+export const exportAllData = (outputPath: string): Promise<DataExportResult> =>
+  invoke('export_all_data', { outputPath });
+
+// Adding or changing transactions in a locked month fails unless overrideLock is set
+export const importAllData = (
+  inputPath: string,
+  conflict?: ArchiveConflict,
+  overrideLock?: boolean
+): Promise<DataImportResult> => invoke('import_all_data', { inputPath, conflict, overrideLock });
+
+// Transactions exported from another budgeting app; YNAB category groups become parent categories
+export type AppExportFormat = 'mint' | 'ynab';
+
+export const importFromApp = (
+  format: AppExportFormat,
+  csvContent: string,
+  conflict?: ArchiveConflict,
+  overrideLock?: boolean
+): Promise<DataImportResult> => invoke('import_from_app', { format, csvContent, conflict, overrideLock });
+
+// Backup Commands
+// Backups go to the backup_dir setting; backup_frequency and backup_retention schedule and prune them
+export interface BackupRecord {
+  id: number;
+  started_at: string; // UTC
+  kind: 'scheduled' | 'manual';
+  status: 'succeeded' | 'failed';
+  file_path?: string;
+  size_bytes?: number;
+  pruned_count: number; // Older backups deleted to keep the retention count
+  error?: string;
+}
+
+export const runBackupNow = (): Promise<BackupRecord> =>
+  invoke('run_backup_now');
+
+export const listBackupHistory = (limit?: number): Promise<BackupRecord[]> =>
+  invoke('list_backup_history', { limit });
+
+// Watch Folder Commands
+// CSVs dropped into the watch_dir setting's folder are imported with the saved mapping and account
+// their headers match, then moved into its "imported" subfolder
+export const WATCH_FOLDER_EVENT = 'watch-folder-import';
+
+export interface WatchedImport {
+  file: string;
+  format?: 'csv' | 'ofx'; // OFX files are reported but not imported yet
+  status: 'imported' | 'skipped' | 'failed'; // Skipped files stay in the folder
+  mapping_name?: string;
+  account_id?: number;
+  result?: ImportResult;
+  archived_to?: string;
+  message: string;
+}
+
+export const scanWatchFolder = (): Promise<WatchedImport[]> =>
+  invoke('scan_watch_folder');
+
+// Profile Commands
+// Each profile has its own database; switching emits a change event for every kind of data
+export interface Profile {
+  name: string;
+  file_name: string; // Relative to the app data folder
+  created_at: string;
+}
+
+export interface ProfileList {
+  active: string; // Name of the open profile
+  profiles: Profile[];
+}
+
+export const listProfiles = (): Promise<ProfileList> =>
+  invoke('list_profiles');
+
+export const createProfile = (name: string): Promise<Profile> =>
+  invoke('create_profile', { name });
+
+export const switchProfile = (name: string, password?: string): Promise<ProfileList> =>
+  invoke('switch_profile', { name, password });
+
+// Household Member Commands
+// Members say who made a transaction on a shared account
+export interface Member {
+  id: number;
+  name: string;
+  color?: string;
+  created_at: string;
+}
+
+export interface NewMember {
+  name: string;
+  color?: string; // Hex color, e.g. '#4CAF50'
+}
+
+export interface MemberSpending {
+  member_id: number | null; // null for spending not attributed to anyone
+  member_name: string;
+  amount: number;
+  percentage: number;
+  transaction_count: number;
+}
+
+export interface SpendingByMember {
+  period: { start_date: string; end_date: string };
+  members: MemberSpending[];
+  total_spending: number;
+}
+
+export const listMembers = (): Promise<Member[]> =>
+  invoke('list_members');
+
+export const createMember = (member: NewMember): Promise<Member> =>
+  invoke('create_member', { member });
+
+// Their transactions are kept, no longer attributed to anyone
+export const deleteMember = (memberId: number): Promise<void> =>
+  invoke('delete_member', { memberId });
+
+export const getSpendingByMember = (
+  startDate: string,
+  endDate: string,
+  accountId?: number
+): Promise<SpendingByMember> =>
+  invoke('get_spending_by_member', { startDate, endDate, accountId });
+
+// Settings Commands
+export interface ReadOnlyStatus {
+  enabled: boolean;
+  forced: boolean; // Started with --read-only; can't be turned off until restart
+}
+
+export const getReadOnlyMode = (): Promise<ReadOnlyStatus> =>
+  invoke('get_read_only_mode');
+
+export const setReadOnlyMode = (enabled: boolean): Promise<ReadOnlyStatus> =>
+  invoke('set_read_only_mode', { enabled });
+
+// Access Commands
+export type AppRole = 'owner' | 'viewer';
+
+export interface AccessStatus {
+  role: AppRole;
+  locked: boolean; // Viewer role and the password hasn't been entered this session
+  password_set: boolean;
+}
+
+export const getAccessStatus = (): Promise<AccessStatus> =>
+  invoke('get_access_status');
+
+// Pass newPassword undefined to clear the password (also switches back to owner)
+export const setAppPassword = (
+  currentPassword?: string,
+  newPassword?: string
+): Promise<AccessStatus> =>
+  invoke('set_app_password', { currentPassword, newPassword });
+
+export const setAppRole = (role: AppRole, password?: string): Promise<AccessStatus> =>
+  invoke('set_app_role', { role, password });
+
+export const unlockEditing = (password: string): Promise<AccessStatus> =>
+  invoke('unlock_editing', { password });
+
+export const lockEditing = (): Promise<AccessStatus> =>
+  invoke('lock_editing');
@@ -32,8 +32,11 @@ export interface SpendingTrends {
 }
 
 export interface TargetProgress {
-  category_id: number;
-  category_name: string;
+  target_id: number;
+  scope: "category" | "account" | "overall";
+  category_id?: number; // Category targets only
+  account_id?: number; // Account targets only
+  category_name: string; // The category or account name, or "All spending"
   target_amount: number;
   actual_amount: number;
   remaining: number;
@@ -42,6 +45,22 @@ export interface TargetProgress {
   variance: number;
 }
 
+export type WidgetKind = "spending" | "debts" | "targets" | "net_worth" | "upcoming_bills";
+
+export interface DashboardWidget {
+  kind: WidgetKind;
+  limit?: number; // Spending: top categories; upcoming bills: bills
+  days_ahead?: number; // Upcoming bills only
+}
+
+export interface UpcomingBill {
+  name: string;
+  amount: number;
+  due_date: string;
+  source: "debt" | "scheduled";
+}
+
+// Sections for widgets that aren't in the layout are null
 export interface DashboardSummary {
   period: {
     start_date: string;
@@ -50,17 +69,40 @@ export interface DashboardSummary {
   total_spending: number;
   total_income: number;
   net: number;
-  top_categories: CategorySpending[];
+  account_ids: number[]; // Empty when every account is included
+  widgets: DashboardWidget[];
+  top_categories: CategorySpending[] | null;
+  comparison: {
+    previous_period: {
+      start_date: string;
+      end_date: string;
+    };
+    previous_spending: number;
+    previous_income: number;
+    previous_net: number;
+    spending_change: number;
+    income_change: number;
+    net_change: number;
+    spending_change_percent: number | null;
+    income_change_percent: number | null;
+  } | null;
   debt_summary: {
     total_debt: number;
     total_monthly_payment: number;
     next_payoff_date: string | null;
-  };
+  } | null;
   target_summary: {
     on_track_count: number;
     over_count: number;
     total_variance: number;
-  };
+  } | null;
+  net_worth: {
+    accounts_total: number;
+    investments_total: number; // Holdings at their latest prices
+    debts_total: number;
+    net_worth: number;
+  } | null;
+  upcoming_bills: UpcomingBill[] | null; // Soonest first
 }
 
 interface AnalyticsState {
@@ -75,25 +117,35 @@ interface AnalyticsState {
   fetchSpendingByCategory: (
     startDate: string,
     endDate: string,
-    accountId?: number
+    accountId?: number,
+    memberId?: number // Only what this household member spent
   ) => Promise<void>;
   fetchTrends: (
     startDate: string,
     endDate: string,
     interval: "daily" | "weekly" | "monthly",
-    categoryId?: number
+    categoryId?: number,
+    accountId?: number
   ) => Promise<void>;
   fetchTargetsProgress: (
     period?: string,
     customStart?: string,
     customEnd?: string
   ) => Promise<void>;
-  fetchDashboard: (period: string) => Promise<void>;
+  // A custom start/end range takes precedence over the named period
+  fetchDashboard: (
+    period?: string,
+    customStart?: string,
+    customEnd?: string,
+    accountIds?: number[]
+  ) => Promise<void>;
+  // Pass neither a category nor an account for an overall spending target
   createTarget: (
-    categoryId: number,
+    categoryId: number | null,
     amount: number,
     period: string,
-    startDate: string
+    startDate: string,
+    accountId?: number
   ) => Promise<void>;
 }
 
@@ -105,13 +157,14 @@ export const useAnalyticsStore = create<AnalyticsState>((set) => ({
   loading: false,
   error: null,
 
-  fetchSpendingByCategory: async (startDate, endDate, accountId) => {
+  fetchSpendingByCategory: async (startDate, endDate, accountId, memberId) => {
     try {
       set({ loading: true, error: null });
       const data = await invoke<SpendingByCategory>("get_spending_by_category", {
         startDate,
         endDate,
         accountId,
+        memberId,
       });
       set({ spendingByCategory: data, loading: false });
     } catch (error) {
@@ -119,7 +172,7 @@ export const useAnalyticsStore = create<AnalyticsState>((set) => ({
     }
   },
 
-  fetchTrends: async (startDate, endDate, interval, categoryId) => {
+  fetchTrends: async (startDate, endDate, interval, categoryId, accountId) => {
     try {
       set({ loading: true, error: null });
       const data = await invoke<SpendingTrends>("get_spending_trends", {
@@ -127,6 +180,7 @@ export const useAnalyticsStore = create<AnalyticsState>((set) => ({
         endDate,
         interval,
         categoryId,
+        accountId,
       });
       set({ trends: data, loading: false });
     } catch (error) {
@@ -151,11 +205,14 @@ export const useAnalyticsStore = create<AnalyticsState>((set) => ({
     }
   },
 
-  fetchDashboard: async (period) => {
+  fetchDashboard: async (period, customStart, customEnd, accountIds) => {
     try {
       set({ loading: true, error: null });
       const data = await invoke<DashboardSummary>("get_dashboard_summary", {
         period,
+        customStart,
+        customEnd,
+        accountIds,
       });
       set({ dashboard: data, loading: false });
     } catch (error) {
@@ -163,14 +220,18 @@ export const useAnalyticsStore = create<AnalyticsState>((set) => ({
     }
   },
 
-  createTarget: async (categoryId, amount, period, startDate) => {
+  createTarget: async (categoryId, amount, period, startDate, accountId) => {
     try {
       set({ loading: true, error: null });
       await invoke("create_spending_target", {
-        categoryId,
-        amount,
-        period,
-        startDate,
+        target: {
+          category_id: categoryId,
+          account_id: accountId ?? null,
+          amount,
+          period,
+          start_date: startDate,
+          end_date: null,
+        },
       });
       set({ loading: false });
     } catch (error) {
@@ -8,6 +8,12 @@ export interface Debt {
   original_balance: number;
   interest_rate: number;
   min_payment: number;
+  min_payment_percent: number | null; // Minimum as a percentage of the balance; min_payment is then the floor
+  kind: "revolving" | "loan";
+  term_months: number | null; // Loans only
+  loan_start_date: string | null; // Loans only; the first payment is due a month later
+  due_day: number; // Day of the month the payment is due; 29-31 fall on the last day of short months
+  account_id: number | null; // Credit card whose statements this debt follows
   created_at: string;
   updated_at: string;
 }
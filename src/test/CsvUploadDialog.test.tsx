@@ -17,7 +17,7 @@ import { CsvUploadDialog } from '../components/CsvUploadDialog';
 
 // Create mock functions
 const mockOpenDialog = vi.fn();
-const mockReadTextFile = vi.fn();
+const mockReadCsvFile = vi.fn();
 const mockGetCsvHeaders = vi.fn();
 
 // Mock Tauri modules
@@ -25,12 +25,9 @@ vi.mock('@tauri-apps/plugin-dialog', () => ({
   open: mockOpenDialog,
 }));
 
-vi.mock('@tauri-apps/plugin-fs', () => ({
-  readTextFile: mockReadTextFile,
-}));
-
 vi.mock('../lib/tauri', () => ({
   getCsvHeaders: mockGetCsvHeaders,
+  readCsvFile: mockReadCsvFile,
   importCsv: vi.fn(),
 }));
 
@@ -71,7 +68,7 @@ describe('CsvUploadDialog', () => {
 
   it('should call dialog.open with correct permissions when selecting file', async () => {
     mockOpenDialog.mockResolvedValue('/path/to/file.csv');
-    mockReadTextFile.mockResolvedValue('Date,Amount,Description\n2024-01-01,50.00,Test');
+    mockReadCsvFile.mockResolvedValue('Date,Amount,Description\n2024-01-01,50.00,Test');
     mockGetCsvHeaders.mockResolvedValue(['Date', 'Amount', 'Description']);
 
     render(
@@ -132,7 +129,7 @@ describe('CsvUploadDialog', () => {
   it('should read and parse CSV file after selection', async () => {
     mockOpenDialog.mockResolvedValue('/path/to/file.csv');
     const csvContent = 'Date,Amount,Description\n2024-01-01,50.00,Coffee';
-    mockReadTextFile.mockResolvedValue(csvContent);
+    mockReadCsvFile.mockResolvedValue(csvContent);
     mockGetCsvHeaders.mockResolvedValue(['Date', 'Amount', 'Description']);
 
     render(
@@ -149,7 +146,7 @@ describe('CsvUploadDialog', () => {
 
     // Verify file was read
     await waitFor(() => {
-      expect(mockReadTextFile).toHaveBeenCalledWith('/path/to/file.csv');
+      expect(mockReadCsvFile).toHaveBeenCalledWith('/path/to/file.csv');
     });
 
     // Verify headers were parsed